@@ -0,0 +1,168 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use primordium_core::snapshot::WorldSnapshot;
+use primordium_core::spatial_hash::SpatialHash;
+use primordium_io::snapshot_delta::SnapshotDelta;
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::environment::Environment;
+use primordium_lib::model::world::World;
+use primordium_net::{NetMessage, PeerInfo};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const POPULATIONS: [usize; 3] = [100, 500, 2_000];
+/// Ticks simulated before measuring, so snapshots carry evolved state.
+const WARMUP_TICKS: u64 = 20;
+
+/// A seeded world of `population` entities after the warm-up, with
+/// snapshots taken one tick apart.
+fn world(population: usize) -> (World, Arc<WorldSnapshot>, Arc<WorldSnapshot>) {
+    let mut config = AppConfig::default();
+    config.world.seed = Some(42);
+    config.world.width = 200;
+    config.world.height = 100;
+    config.world.initial_population = population;
+    let mut world = World::new_ephemeral(population, config).expect("world");
+    let mut env = Environment::default();
+    for _ in 0..WARMUP_TICKS {
+        world.update(&mut env).expect("update");
+    }
+    let base = world.create_snapshot(None);
+    world.update(&mut env).expect("update");
+    let next = world.create_snapshot(None);
+    (world, base, next)
+}
+
+fn rkyv_bytes(snapshot: &WorldSnapshot) -> usize {
+    rkyv::to_bytes::<_, 4096>(snapshot).expect("rkyv").len()
+}
+
+fn bench_snapshot_create(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snapshot_create");
+    for population in POPULATIONS {
+        let (world, ..) = world(population);
+        group.throughput(Throughput::Elements(population as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(population), &world, |b, w| {
+            b.iter(|| black_box(w.create_snapshot(None)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_snapshot_serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snapshot_serialize");
+    for population in POPULATIONS {
+        let (_, base, next) = world(population);
+        group.throughput(Throughput::Elements(population as u64));
+        group.bench_with_input(BenchmarkId::new("serde_json", population), &next, |b, s| {
+            b.iter(|| black_box(serde_json::to_vec(&**s).expect("json").len()))
+        });
+        group.bench_with_input(BenchmarkId::new("rkyv", population), &next, |b, s| {
+            b.iter(|| black_box(rkyv_bytes(s)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("delta", population),
+            &(base, next),
+            |b, (base, next)| {
+                b.iter(|| {
+                    let delta = SnapshotDelta::between(base, next);
+                    black_box(rkyv::to_bytes::<_, 4096>(&delta).expect("rkyv").len())
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_net_message_encode(c: &mut Criterion) {
+    let (world, ..) = world(POPULATIONS[0]);
+    let dna = world
+        .ecs
+        .query::<&primordium_data::Intel>()
+        .iter()
+        .next()
+        .map(|(_, intel)| intel.genotype.to_hex())
+        .expect("a living entity");
+    let migrate = NetMessage::migrate_entity(
+        Uuid::from_u128(1),
+        dna,
+        120.0,
+        7,
+        "Benchmark".to_string(),
+        "fingerprint".to_string(),
+    );
+    let mut group = c.benchmark_group("net_message");
+    group.bench_function("migrate_entity_encode", |b| {
+        b.iter(|| black_box(serde_json::to_string(&migrate).expect("json")))
+    });
+    let json = serde_json::to_string(&migrate).expect("json");
+    group.bench_function("migrate_entity_decode", |b| {
+        b.iter(|| black_box(serde_json::from_str::<NetMessage>(&json).expect("json")))
+    });
+    for peers in [10, 100] {
+        let list = NetMessage::PeerList {
+            peers: (0..peers)
+                .map(|i| PeerInfo {
+                    peer_id: Uuid::from_u128(i as u128),
+                    entity_count: i * 10,
+                    migrations_sent: i,
+                    migrations_received: i,
+                })
+                .collect(),
+        };
+        group.bench_with_input(
+            BenchmarkId::new("peer_list_encode", peers),
+            &list,
+            |b, m| b.iter(|| black_box(serde_json::to_string(m).expect("json"))),
+        );
+    }
+    group.finish();
+}
+
+fn bench_spatial_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spatial_hash_population");
+    for population in POPULATIONS {
+        let (world, _, snapshot) = world(population);
+        let positions: Vec<(f64, f64)> = snapshot.entities.iter().map(|e| (e.x, e.y)).collect();
+        let (width, height) = (world.width, world.height);
+        group.throughput(Throughput::Elements(positions.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("build", population),
+            &positions,
+            |b, positions| {
+                b.iter(|| {
+                    let mut spatial = SpatialHash::new(5.0, width, height);
+                    spatial.build_parallel(positions, width, height);
+                    black_box(spatial)
+                })
+            },
+        );
+        let mut spatial = SpatialHash::new(5.0, width, height);
+        spatial.build_parallel(&positions, width, height);
+        group.bench_with_input(
+            BenchmarkId::new("query_all_r10", population),
+            &positions,
+            |b, positions| {
+                let mut results = Vec::new();
+                b.iter(|| {
+                    let mut found = 0;
+                    for &(x, y) in positions {
+                        results.clear();
+                        spatial.query_into(x, y, 10.0, &mut results);
+                        found += results.len();
+                    }
+                    black_box(found)
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_snapshot_create,
+    bench_snapshot_serialize,
+    bench_net_message_encode,
+    bench_spatial_hash
+);
+criterion_main!(benches);
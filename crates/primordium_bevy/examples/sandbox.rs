@@ -0,0 +1,27 @@
+//! A window onto a live world with the default controls.
+//!
+//! ```sh
+//! cargo run --release --example sandbox -- [initial_population]
+//! ```
+
+use bevy::prelude::*;
+use primordium_bevy::PrimordiumPlugin;
+
+fn main() {
+    let mut plugin = PrimordiumPlugin::default();
+    if let Some(population) = std::env::args().nth(1).and_then(|n| n.parse().ok()) {
+        plugin.initial_population = population;
+    }
+
+    App::new()
+        .insert_resource(ClearColor(Color::srgb_u8(10, 12, 10)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Primordium".into(),
+                ..default()
+            }),
+            ..default()
+        }))
+        .add_plugins(plugin)
+        .run();
+}
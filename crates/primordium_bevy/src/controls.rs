@@ -0,0 +1,176 @@
+//! Camera controls and god tools.
+//!
+//! | Input | Action |
+//! | ----- | ------ |
+//! | `WASD` / arrows | Pan |
+//! | Mouse wheel | Zoom |
+//! | `Space` | Pause / Resume |
+//! | `+` / `-` | Increase / Decrease time scale |
+//! | `Left Click` | Select organism |
+//! | `Right Click` | Inject food |
+//! | `C` | Clone the selected organism at the cursor |
+//! | `O` | Release an outbreak at the cursor |
+//! | `R` | Resource boom |
+//! | `Shift+K` | Toggle heat wave |
+//!
+//! Every tool except food injection goes through a [`ScenarioEvent`], so the
+//! app behaves as if the event had been dropped into the inbox.
+
+use crate::render::{scene_to_world, world_to_scene};
+use crate::{SimClock, Simulation};
+use bevy::input::mouse::AccumulatedMouseScroll;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use primordium_lib::model::environment::ClimateState;
+use primordium_lib::model::world::scenario::ScenarioEvent;
+
+/// Pan speed in screen pixels per second.
+const PAN_SPEED: f32 = 600.0;
+const MIN_ZOOM: f32 = 0.1;
+const MAX_ZOOM: f32 = 10.0;
+/// Farthest an organism can be from the cursor, in cells, to be selected.
+const PICK_RADIUS: f64 = 3.0;
+const CLONE_COUNT: usize = 5;
+const BOOM_FOOD: usize = 100;
+const MAX_TICKS_PER_SECOND: f32 = 640.0;
+
+pub(crate) struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_camera)
+            .add_systems(Update, (move_camera, time_controls, god_tools));
+    }
+}
+
+fn spawn_camera(mut commands: Commands, sim: Res<Simulation>) {
+    let snapshot = sim.snapshot();
+    let center = world_to_scene(
+        f64::from(snapshot.width) / 2.0,
+        f64::from(snapshot.height) / 2.0,
+    );
+    commands.spawn((Camera2d, Transform::from_translation(center.extend(100.0))));
+}
+
+fn move_camera(
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    scroll: Res<AccumulatedMouseScroll>,
+    mut camera: Single<&mut Transform, With<Camera2d>>,
+) {
+    let mut direction = Vec2::ZERO;
+    if keys.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
+        direction.y += 1.0;
+    }
+    if keys.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
+        direction.y -= 1.0;
+    }
+    if keys.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]) {
+        direction.x -= 1.0;
+    }
+    if keys.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]) {
+        direction.x += 1.0;
+    }
+    let zoom = camera.scale.x;
+    let step = direction.normalize_or_zero() * PAN_SPEED * zoom * time.delta_secs();
+    camera.translation += step.extend(0.0);
+
+    if scroll.delta.y != 0.0 {
+        let zoom = (zoom * (-scroll.delta.y * 0.1).exp()).clamp(MIN_ZOOM, MAX_ZOOM);
+        camera.scale = Vec3::new(zoom, zoom, 1.0);
+    }
+}
+
+fn time_controls(keys: Res<ButtonInput<KeyCode>>, mut clock: ResMut<SimClock>) {
+    if keys.just_pressed(KeyCode::Space) {
+        clock.paused = !clock.paused;
+    }
+    if keys.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+        clock.ticks_per_second = (clock.ticks_per_second * 2.0).min(MAX_TICKS_PER_SECOND);
+    }
+    if keys.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        clock.ticks_per_second = (clock.ticks_per_second / 2.0).max(1.0);
+    }
+}
+
+fn god_tools(
+    keys: Res<ButtonInput<KeyCode>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    mut sim: ResMut<Simulation>,
+) {
+    let (camera, camera_transform) = *camera;
+    let cursor = window
+        .cursor_position()
+        .and_then(|p| camera.viewport_to_world_2d(camera_transform, p).ok())
+        .map(scene_to_world);
+
+    let mut events = Vec::new();
+    if let Some((x, y)) = cursor {
+        if buttons.just_pressed(MouseButton::Left) {
+            let nearest = sim
+                .snapshot()
+                .entities
+                .iter()
+                .map(|e| (e.id, (e.x - x).hypot(e.y - y)))
+                .filter(|&(_, d)| d <= PICK_RADIUS)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(id, _)| id);
+            sim.select(nearest);
+        }
+        if buttons.just_pressed(MouseButton::Right)
+            && x >= 0.0
+            && y >= 0.0
+            && x < f64::from(sim.world.width)
+            && y < f64::from(sim.world.height)
+        {
+            let n_type = rand::random::<f32>();
+            sim.world.spawn_food_at(x as u16, y as u16, n_type);
+            sim.refresh();
+        }
+        if keys.just_pressed(KeyCode::KeyC) {
+            let selected = sim.selected();
+            let dna = sim
+                .snapshot()
+                .entities
+                .iter()
+                .find(|e| Some(e.id) == selected)
+                .and_then(|e| e.genotype_hex.clone());
+            if let Some(dna) = dna {
+                events.push(ScenarioEvent::Spawn {
+                    dna,
+                    count: CLONE_COUNT,
+                    x: Some(x),
+                    y: Some(y),
+                });
+            }
+        }
+        if keys.just_pressed(KeyCode::KeyO) {
+            events.push(ScenarioEvent::Outbreak {
+                x: Some(x),
+                y: Some(y),
+                radius: 10.0,
+            });
+        }
+    }
+    if keys.just_pressed(KeyCode::KeyR) {
+        events.push(ScenarioEvent::FoodBoom { amount: BOOM_FOOD });
+    }
+    if keys.just_pressed(KeyCode::KeyK)
+        && keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+    {
+        let state = match sim.env.god_climate_override {
+            Some(ClimateState::Scorching) => None,
+            _ => Some(ClimateState::Scorching),
+        };
+        events.push(ScenarioEvent::Climate { state });
+    }
+
+    for event in events {
+        match sim.apply(&event) {
+            Ok(outcome) => info!("{outcome}"),
+            Err(e) => warn!("god tool failed: {e:#}"),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! # Primordium for Bevy
+//!
+//! Embeds the simulation in a [Bevy](https://bevyengine.org) app. Add
+//! [`PrimordiumPlugin`] next to `DefaultPlugins` and the world is stepped in
+//! real time and drawn as sprites: one tile per terrain cell, a dot per food
+//! item and a lineage-coloured square per organism.
+//!
+//! The running world is the [`Simulation`] resource; other systems can read
+//! its latest snapshot or drive it with [`ScenarioEvent`]s, the same events
+//! the inbox accepts. [`controls`] maps the keyboard and mouse to camera
+//! movement and god tools.
+
+pub mod controls;
+pub mod render;
+
+use bevy::prelude::*;
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::environment::Environment;
+use primordium_lib::model::snapshot::WorldSnapshot;
+use primordium_lib::model::world::scenario::ScenarioEvent;
+use primordium_lib::model::world::World;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Upper bound on ticks run in one frame, so a slow frame cannot snowball.
+const MAX_TICKS_PER_FRAME: u32 = 8;
+
+/// Adds the simulation, its renderer and the default controls to an app.
+pub struct PrimordiumPlugin {
+    pub config: AppConfig,
+    pub initial_population: usize,
+}
+
+impl Default for PrimordiumPlugin {
+    fn default() -> Self {
+        let config = AppConfig::default();
+        Self {
+            initial_population: config.world.initial_population,
+            config,
+        }
+    }
+}
+
+impl Plugin for PrimordiumPlugin {
+    fn build(&self, app: &mut App) {
+        let simulation = Simulation::new(self.initial_population, self.config.clone())
+            .expect("failed to create the simulation");
+        app.insert_resource(simulation)
+            .init_resource::<SimClock>()
+            .add_plugins((render::RenderPlugin, controls::ControlsPlugin))
+            .add_systems(Update, step_simulation);
+    }
+}
+
+/// A running simulation: a world, the environment it evolves in and the
+/// snapshot the renderer draws from.
+#[derive(Resource)]
+pub struct Simulation {
+    pub world: World,
+    pub env: Environment,
+    selected: Option<Uuid>,
+    snapshot: Arc<WorldSnapshot>,
+}
+
+impl Simulation {
+    /// Creates a world that never touches the filesystem.
+    pub fn new(initial_population: usize, config: AppConfig) -> anyhow::Result<Self> {
+        let world = World::new_ephemeral(initial_population, config)?;
+        let snapshot = world.create_snapshot(None);
+        Ok(Self {
+            world,
+            env: Environment::default(),
+            selected: None,
+            snapshot,
+        })
+    }
+
+    /// Advances the world by `ticks` ticks, then refreshes the snapshot.
+    pub fn step(&mut self, ticks: u32) -> anyhow::Result<()> {
+        for _ in 0..ticks {
+            self.world.update(&mut self.env)?;
+        }
+        self.refresh();
+        Ok(())
+    }
+
+    /// Applies a scripted intervention and returns what it did.
+    pub fn apply(&mut self, event: &ScenarioEvent) -> anyhow::Result<String> {
+        let outcome = self.world.apply_scenario_event(&mut self.env, event)?;
+        self.refresh();
+        Ok(outcome)
+    }
+
+    /// The state as of the last tick or intervention.
+    pub fn snapshot(&self) -> &Arc<WorldSnapshot> {
+        &self.snapshot
+    }
+
+    /// The organism picked out for inspection, if it is still alive.
+    pub fn selected(&self) -> Option<Uuid> {
+        self.selected
+    }
+
+    /// Picks out an organism; its snapshot entry carries its genotype.
+    pub fn select(&mut self, id: Option<Uuid>) {
+        self.selected = id;
+        self.refresh();
+    }
+
+    /// Rebuilds the snapshot after the world was changed directly.
+    pub fn refresh(&mut self) {
+        self.snapshot = self.world.create_snapshot(self.selected);
+    }
+}
+
+/// How fast the simulation runs relative to the frame clock.
+#[derive(Resource, Debug)]
+pub struct SimClock {
+    pub paused: bool,
+    pub ticks_per_second: f32,
+    accumulated: f32,
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            ticks_per_second: 20.0,
+            accumulated: 0.0,
+        }
+    }
+}
+
+fn step_simulation(time: Res<Time>, mut clock: ResMut<SimClock>, mut sim: ResMut<Simulation>) {
+    if clock.paused {
+        clock.accumulated = 0.0;
+        return;
+    }
+    clock.accumulated += time.delta_secs() * clock.ticks_per_second;
+    let ticks = (clock.accumulated as u32).min(MAX_TICKS_PER_FRAME);
+    clock.accumulated = clock.accumulated.fract();
+    if ticks > 0 {
+        if let Err(e) = sim.step(ticks) {
+            error!("simulation step failed: {e:#}");
+            clock.paused = true;
+        }
+    }
+}
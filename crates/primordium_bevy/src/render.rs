@@ -0,0 +1,234 @@
+//! Draws the latest [`Simulation`] snapshot as sprites.
+//!
+//! World cell `(x, y)` is centred on `(x, -y) * TILE_SIZE` so the map reads
+//! top-down as it does in the terminal. Terrain tiles are spawned once and
+//! recoloured only when the snapshot carries a new terrain grid; organism
+//! sprites are matched to entities by ID and food sprites are pooled.
+
+use crate::Simulation;
+use bevy::prelude::*;
+use primordium_lib::model::terrain::{TerrainGrid, TerrainType};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Side of one world cell in Bevy units.
+pub const TILE_SIZE: f32 = 8.0;
+
+const TERRAIN_Z: f32 = 0.0;
+const FOOD_Z: f32 = 1.0;
+const ORGANISM_Z: f32 = 2.0;
+
+pub(crate) struct RenderPlugin;
+
+impl Plugin for RenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpriteIndex>()
+            .add_systems(Startup, spawn_terrain)
+            .add_systems(
+                PostUpdate,
+                (sync_terrain, sync_food, sync_organisms).before(TransformSystems::Propagate),
+            );
+    }
+}
+
+/// A terrain tile and the world cell it shows.
+#[derive(Component)]
+pub struct TerrainTile {
+    pub x: u16,
+    pub y: u16,
+}
+
+/// A food sprite.
+#[derive(Component)]
+pub struct FoodSprite;
+
+/// An organism sprite and the ID of the entity it shows.
+#[derive(Component)]
+pub struct OrganismSprite(pub Uuid);
+
+#[derive(Resource, Default)]
+struct SpriteIndex {
+    terrain: Option<Arc<TerrainGrid>>,
+    organisms: HashMap<Uuid, Entity>,
+    food: Vec<Entity>,
+}
+
+/// Bevy position of the centre of world position `(x, y)`.
+pub fn world_to_scene(x: f64, y: f64) -> Vec2 {
+    Vec2::new(x as f32, -(y as f32)) * TILE_SIZE
+}
+
+/// World position under Bevy position `point`.
+pub fn scene_to_world(point: Vec2) -> (f64, f64) {
+    (
+        f64::from(point.x / TILE_SIZE),
+        f64::from(-point.y / TILE_SIZE),
+    )
+}
+
+/// Tile colour for a terrain type, after the terminal palette.
+pub fn terrain_color(terrain: TerrainType) -> Color {
+    match terrain {
+        TerrainType::Plains => Color::srgb_u8(28, 36, 24),
+        TerrainType::Mountain => Color::srgb_u8(100, 100, 100),
+        TerrainType::River => Color::srgb_u8(70, 130, 180),
+        TerrainType::Oasis => Color::srgb_u8(50, 205, 50),
+        TerrainType::Barren => Color::srgb_u8(139, 69, 19),
+        TerrainType::Wall => Color::srgb_u8(60, 60, 60),
+        TerrainType::Forest => Color::srgb_u8(34, 139, 34),
+        TerrainType::Desert => Color::srgb_u8(210, 180, 140),
+        TerrainType::Nest => Color::srgb_u8(255, 215, 0),
+        TerrainType::Outpost => Color::srgb_u8(255, 69, 0),
+    }
+}
+
+fn spawn_terrain(mut commands: Commands, sim: Res<Simulation>) {
+    let terrain = &sim.snapshot().terrain;
+    for y in 0..terrain.height {
+        for x in 0..terrain.width {
+            let cell = terrain.get_cell(x, y);
+            let pos = world_to_scene(f64::from(x) + 0.5, f64::from(y) + 0.5);
+            commands.spawn((
+                Sprite::from_color(terrain_color(cell.terrain_type), Vec2::splat(TILE_SIZE)),
+                Transform::from_translation(pos.extend(TERRAIN_Z)),
+                TerrainTile { x, y },
+            ));
+        }
+    }
+}
+
+fn sync_terrain(
+    sim: Res<Simulation>,
+    mut index: ResMut<SpriteIndex>,
+    mut tiles: Query<(&TerrainTile, &mut Sprite)>,
+) {
+    let terrain = &sim.snapshot().terrain;
+    if index
+        .terrain
+        .as_ref()
+        .is_some_and(|drawn| Arc::ptr_eq(drawn, terrain))
+    {
+        return;
+    }
+    for (tile, mut sprite) in &mut tiles {
+        if tile.x < terrain.width && tile.y < terrain.height {
+            let color = terrain_color(terrain.get_cell(tile.x, tile.y).terrain_type);
+            if sprite.color != color {
+                sprite.color = color;
+            }
+        }
+    }
+    index.terrain = Some(terrain.clone());
+}
+
+fn sync_food(
+    mut commands: Commands,
+    sim: Res<Simulation>,
+    mut index: ResMut<SpriteIndex>,
+    mut sprites: Query<&mut Transform, With<FoodSprite>>,
+) {
+    let food = &sim.snapshot().food;
+    while index.food.len() > food.len() {
+        if let Some(entity) = index.food.pop() {
+            commands.entity(entity).despawn();
+        }
+    }
+    for (i, f) in food.iter().enumerate() {
+        let pos = world_to_scene(f64::from(f.x) + 0.5, f64::from(f.y) + 0.5).extend(FOOD_Z);
+        match index.food.get(i).and_then(|&e| sprites.get_mut(e).ok()) {
+            Some(mut transform) => transform.translation = pos,
+            None => {
+                let entity = commands
+                    .spawn((
+                        Sprite::from_color(
+                            Color::srgb_u8(120, 220, 90),
+                            Vec2::splat(TILE_SIZE * 0.4),
+                        ),
+                        Transform::from_translation(pos),
+                        FoodSprite,
+                    ))
+                    .id();
+                index.food.push(entity);
+            }
+        }
+    }
+}
+
+fn sync_organisms(
+    mut commands: Commands,
+    sim: Res<Simulation>,
+    mut index: ResMut<SpriteIndex>,
+    mut sprites: Query<(&mut Transform, &mut Sprite), With<OrganismSprite>>,
+) {
+    let snapshot = sim.snapshot();
+    let mut alive = HashMap::with_capacity(snapshot.entities.len());
+    for e in &snapshot.entities {
+        let pos = world_to_scene(e.x, e.y).extend(ORGANISM_Z);
+        let color = Color::srgb_u8(e.r, e.g, e.b);
+        let size = if sim.selected() == Some(e.id) {
+            Vec2::splat(TILE_SIZE * 1.4)
+        } else {
+            Vec2::splat(TILE_SIZE * 0.8)
+        };
+        let existing = index.organisms.get(&e.id).copied();
+        let entity = match existing.filter(|&entity| sprites.contains(entity)) {
+            Some(entity) => {
+                if let Ok((mut transform, mut sprite)) = sprites.get_mut(entity) {
+                    transform.translation = pos;
+                    sprite.color = color;
+                    sprite.custom_size = Some(size);
+                }
+                entity
+            }
+            None => commands
+                .spawn((
+                    Sprite::from_color(color, size),
+                    Transform::from_translation(pos),
+                    OrganismSprite(e.id),
+                ))
+                .id(),
+        };
+        alive.insert(e.id, entity);
+    }
+    for (id, entity) in index.organisms.drain() {
+        if !alive.contains_key(&id) {
+            commands.entity(entity).despawn();
+        }
+    }
+    index.organisms = alive;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primordium_lib::model::config::AppConfig;
+
+    #[test]
+    fn test_scene_coordinates_round_trip() {
+        let (x, y) = scene_to_world(world_to_scene(12.5, 40.25));
+        assert!((x - 12.5).abs() < 1e-4 && (y - 40.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_sprites_follow_the_simulation() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(Simulation::new(20, AppConfig::default()).unwrap())
+            .add_plugins(RenderPlugin);
+        app.update();
+        app.update();
+
+        let world = app.world_mut();
+        let organisms = world.query::<&OrganismSprite>().iter(world).count();
+        let food = world.query::<&FoodSprite>().iter(world).count();
+        let tiles = world.query::<&TerrainTile>().iter(world).count();
+        let sim = world.resource::<Simulation>();
+        assert_eq!(organisms, sim.snapshot().entities.len());
+        assert_eq!(food, sim.snapshot().food.len());
+        assert_eq!(
+            tiles,
+            usize::from(sim.world.width) * usize::from(sim.world.height)
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use primordium_core::brain::batch::{forward_batch, BatchItem};
 use primordium_core::brain::{BrainLogic, GenotypeLogic};
 use primordium_core::brain::{BRAIN_INPUTS, BRAIN_MEMORY};
-use primordium_data::{Brain, Genotype};
+use primordium_data::{Activations, Brain, Genotype};
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 
@@ -35,6 +36,42 @@ fn bench_brain_forward_extreme(c: &mut Criterion) {
     });
 }
 
+/// Benchmark one batched forward pass over 10k brains cloned from 20
+/// founders, as in a crowded world of a few lineages. Compare runs with and
+/// without `--features batch`.
+fn bench_brain_forward_batch(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    let founders: Vec<Brain> = (0..20)
+        .map(|_| Brain::new_random_with_rng(&mut rng))
+        .collect();
+    let brains: Vec<Brain> = (0..10_000)
+        .map(|i| {
+            let mut brain = founders[i % founders.len()].clone();
+            for conn in &mut brain.connections {
+                conn.weight *= 1.0 + (i as f32).sin() * 0.01;
+            }
+            brain
+        })
+        .collect();
+    let mut activations = vec![Activations::default(); brains.len()];
+
+    c.bench_function("brain_forward_batch_10k", |b| {
+        b.iter(|| {
+            let mut items: Vec<BatchItem> = brains
+                .iter()
+                .zip(&mut activations)
+                .map(|(brain, activations)| BatchItem {
+                    brain,
+                    inputs: [0.5; BRAIN_INPUTS],
+                    last_hidden: [0.0; BRAIN_MEMORY],
+                    activations,
+                })
+                .collect();
+            black_box(forward_batch(&mut items))
+        })
+    });
+}
+
 /// Benchmark brain creation.
 fn bench_brain_creation(c: &mut Criterion) {
     let mut rng = ChaCha8Rng::seed_from_u64(42);
@@ -104,6 +141,7 @@ criterion_group!(
     benches,
     bench_brain_forward,
     bench_brain_forward_extreme,
+    bench_brain_forward_batch,
     bench_brain_creation,
     bench_genotype_creation,
     bench_brain_crossover,
@@ -0,0 +1,72 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use primordium_core::config::CrowdConfig;
+use primordium_core::snapshot::InternalEntitySnapshot;
+use primordium_core::spatial_hash::SpatialHash;
+use primordium_core::systems::crowd::crowd_response;
+use primordium_data::EntityStatus;
+use uuid::Uuid;
+
+const WIDTH: u16 = 200;
+const HEIGHT: u16 = 200;
+
+/// `count` entities packed into a square herd of `side` cells.
+fn herd(count: usize, side: f64) -> Vec<InternalEntitySnapshot> {
+    (0..count)
+        .map(|i| {
+            let x = 100.0 + ((i * 7919) % 1000) as f64 / 1000.0 * side;
+            let y = 100.0 + ((i * 104_729) % 1000) as f64 / 1000.0 * side;
+            InternalEntitySnapshot {
+                id: Uuid::from_u128(i as u128 + 1),
+                lineage_id: Uuid::nil(),
+                parent_id: None,
+                x,
+                y,
+                vx: 0.0,
+                vy: 0.0,
+                energy: 100.0,
+                birth_tick: 0,
+                offspring_count: 0,
+                generation: 0,
+                max_energy: 200.0,
+                r: 0,
+                g: 0,
+                b: 0,
+                rank: 0.0,
+                status: EntityStatus::Foraging,
+                trophic_potential: 0.5,
+                genotype: None,
+                memory_writes: 0,
+                memory_recalls: 0,
+                deceptions: 0,
+                infection_remaining: 0,
+                dominance: 0.0,
+            }
+        })
+        .collect()
+}
+
+fn bench_crowd_response(c: &mut Criterion) {
+    let config = CrowdConfig::default();
+    let mut group = c.benchmark_group("crowd_response_all");
+    // Sparse herds rarely exceed the comfort density; packed ones trample.
+    for (label, side) in [("sparse", 60.0), ("packed", 8.0)] {
+        let snapshots = herd(2_000, side);
+        let positions: Vec<(f64, f64)> = snapshots.iter().map(|s| (s.x, s.y)).collect();
+        let mut spatial = SpatialHash::new(5.0, WIDTH, HEIGHT);
+        spatial.build_parallel(&positions, WIDTH, HEIGHT);
+        group.bench_with_input(BenchmarkId::new(label, 2_000), &snapshots, |b, snaps| {
+            b.iter(|| {
+                let mut damage = 0.0;
+                for s in snaps {
+                    damage +=
+                        crowd_response(s.x, s.y, &s.id, snaps, &spatial, &config).trample_damage;
+                }
+                black_box(damage)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_crowd_response);
+criterion_main!(benches);
@@ -0,0 +1,123 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use primordium_core::food_field::FoodField;
+use primordium_data::{Food, MetabolicNiche, Position};
+
+const WIDTH: u16 = 400;
+const HEIGHT: u16 = 400;
+
+fn food_positions(count: usize) -> Vec<(u16, u16, f32)> {
+    (0..count)
+        .map(|i| {
+            let x = ((i * 7919) % WIDTH as usize) as u16;
+            let y = ((i * 104_729) % HEIGHT as usize) as u16;
+            (x, y, (i % 10) as f32 / 10.0)
+        })
+        .collect()
+}
+
+fn spawn_entities(positions: &[(u16, u16, f32)]) -> hecs::World {
+    let mut world = hecs::World::new();
+    world.spawn_batch(positions.iter().map(|&(x, y, n)| {
+        (
+            Food::new(x, y, n),
+            Position {
+                x: x as f64,
+                y: y as f64,
+            },
+            MetabolicNiche(n),
+        )
+    }));
+    world
+}
+
+fn fill_field(positions: &[(u16, u16, f32)]) -> FoodField {
+    let mut field = FoodField::new(WIDTH, HEIGHT);
+    for &(x, y, n) in positions {
+        field.deposit(x, y, n);
+    }
+    field
+}
+
+fn bench_food_spawn(c: &mut Criterion) {
+    let mut group = c.benchmark_group("food_spawn");
+    for count in [10_000, 50_000] {
+        let positions = food_positions(count);
+        group.bench_with_input(BenchmarkId::new("entities", count), &positions, |b, p| {
+            b.iter(|| black_box(spawn_entities(p)))
+        });
+        group.bench_with_input(BenchmarkId::new("field", count), &positions, |b, p| {
+            b.iter(|| black_box(fill_field(p)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_food_index(c: &mut Criterion) {
+    let mut group = c.benchmark_group("food_index");
+    for count in [10_000, 50_000] {
+        let positions = food_positions(count);
+        let world = spawn_entities(&positions);
+        group.bench_function(BenchmarkId::new("entities", count), |b| {
+            b.iter(|| {
+                let data: Vec<_> = world
+                    .query::<(&Position, &Food)>()
+                    .iter()
+                    .map(|(h, (pos, f))| (h, pos.x, pos.y, f.nutrient_type))
+                    .collect();
+                black_box(data)
+            })
+        });
+
+        let mut field = fill_field(&positions);
+        group.bench_function(BenchmarkId::new("field", count), |b| {
+            b.iter(|| black_box(field.index_sites()))
+        });
+    }
+    group.finish();
+}
+
+fn bench_food_consume(c: &mut Criterion) {
+    let mut group = c.benchmark_group("food_consume_1000");
+    let positions = food_positions(50_000);
+    group.bench_function("entities", |b| {
+        b.iter_batched(
+            || {
+                let world = spawn_entities(&positions);
+                let handles: Vec<_> = world.query::<&Food>().iter().map(|(h, _)| h).collect();
+                (world, handles)
+            },
+            |(mut world, handles)| {
+                for h in handles.into_iter().take(1000) {
+                    let _ = world.despawn(h);
+                }
+                black_box(world)
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("field", |b| {
+        b.iter_batched(
+            || {
+                let mut field = fill_field(&positions);
+                field.index_sites();
+                field
+            },
+            |mut field| {
+                for site in 0..1000 {
+                    field.consume_site(site);
+                }
+                black_box(field)
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_food_spawn,
+    bench_food_index,
+    bench_food_consume
+);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use primordium_core::config::SoundConfig;
+use primordium_core::sound::SoundGrid;
+use primordium_core::terrain::TerrainGrid;
+
+const WIDTH: u16 = 200;
+const HEIGHT: u16 = 200;
+
+fn bench_sound_update(c: &mut Criterion) {
+    let terrain = TerrainGrid::generate(WIDTH, HEIGHT, 42);
+    let mut group = c.benchmark_group("sound_update");
+    // Occlusion adds a terrain pass and a multiply per cell to each tick.
+    for occlusion in [false, true] {
+        let config = SoundConfig {
+            occlusion,
+            ..SoundConfig::default()
+        };
+        let mut grid = SoundGrid::new(WIDTH, HEIGHT);
+        let label = if occlusion { "occluded" } else { "open" };
+        group.bench_function(BenchmarkId::new(label, WIDTH), |b| {
+            b.iter(|| {
+                for i in 0..50u32 {
+                    let x = f64::from((i * 7919) % u32::from(WIDTH));
+                    let y = f64::from((i * 104_729) % u32::from(HEIGHT));
+                    grid.deposit_parallel(x, y, 1.0);
+                }
+                grid.occlude(&terrain, &config);
+                grid.update();
+                black_box(grid.get_cell(100, 100))
+            })
+        });
+    }
+    let config = SoundConfig {
+        occlusion: true,
+        ..SoundConfig::default()
+    };
+    let mut grid = SoundGrid::new(WIDTH, HEIGHT);
+    group.bench_function(BenchmarkId::new("occlude_only", WIDTH), |b| {
+        b.iter(|| {
+            grid.occlude(&terrain, &config);
+            black_box(&grid);
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_sound_update);
+criterion_main!(benches);
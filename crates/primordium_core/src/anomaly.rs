@@ -0,0 +1,262 @@
+//! Anomaly detection on population metrics.
+//!
+//! Every `anomaly.sample_interval` ticks the population, total biomass and
+//! death rate (deaths per living entity over the interval) are compared
+//! against an exponentially weighted moving average and variance of their
+//! past samples. A sample more than `anomaly.z_threshold` standard
+//! deviations from the average is an [`Anomaly`]. Nothing is flagged during
+//! the first `warmup_samples` samples, and a metric stays quiet for
+//! `cooldown_samples` samples after it was flagged so one shift is not
+//! reported over and over while the average catches up.
+//!
+//! Each anomaly carries [`NarrationRefs`] to the sampled figures and, for
+//! declines, the deaths logged during its interval.
+
+use crate::config::AnomalyConfig;
+use primordium_data::{EventRef, LiveEvent, NarrationRefs};
+use serde::{Deserialize, Serialize};
+
+/// Most deaths referenced by one anomaly.
+const MAX_DEATH_REFS: usize = 20;
+
+/// A watched population metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Metric {
+    Population,
+    Biomass,
+    DeathRate,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [Metric::Population, Metric::Biomass, Metric::DeathRate];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Metric::Population => "population",
+            Metric::Biomass => "biomass",
+            Metric::DeathRate => "death rate",
+        }
+    }
+
+    fn format(self, value: f64) -> String {
+        match self {
+            Metric::Population | Metric::Biomass => format!("{value:.0}"),
+            Metric::DeathRate => format!("{:.1}%", value * 100.0),
+        }
+    }
+}
+
+/// A sample that broke from its metric's trend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Anomaly {
+    pub metric: Metric,
+    pub tick: u64,
+    pub value: f64,
+    /// The moving average the sample was compared against.
+    pub expected: f64,
+    /// Signed distance from the average in standard deviations.
+    pub z_score: f64,
+    /// The sampling interval, its figures and, for declines, its deaths.
+    pub refs: NarrationRefs,
+}
+
+impl Anomaly {
+    /// Whether the shift is bad news: falling population or biomass, or
+    /// rising deaths.
+    pub fn is_decline(&self) -> bool {
+        match self.metric {
+            Metric::Population | Metric::Biomass => self.z_score < 0.0,
+            Metric::DeathRate => self.z_score > 0.0,
+        }
+    }
+
+    /// One line for the chronicle.
+    pub fn narration(&self) -> String {
+        let headline = match (self.metric, self.is_decline()) {
+            (Metric::Population, true) => "The Silent Collapse begins…",
+            (Metric::Population, false) => "A Great Bloom sweeps the world…",
+            (Metric::Biomass, true) => "The Great Hunger sets in…",
+            (Metric::Biomass, false) => "Life swells beyond all measure…",
+            (Metric::DeathRate, true) => "A wave of death rolls across the land…",
+            (Metric::DeathRate, false) => "Death grows strangely rare…",
+        };
+        format!(
+            "{} {} at {} against an expected {} ({:+.1}σ).",
+            headline,
+            self.metric.label(),
+            self.metric.format(self.value),
+            self.metric.format(self.expected),
+            self.z_score
+        )
+    }
+}
+
+/// Exponentially weighted moving average and variance of one metric.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Ewma {
+    mean: f64,
+    variance: f64,
+    samples: u32,
+    quiet_until: u32,
+}
+
+impl Ewma {
+    /// Folds in `value` and returns its z-score against the state before.
+    fn push(&mut self, value: f64, alpha: f64) -> f64 {
+        self.samples += 1;
+        if self.samples == 1 {
+            self.mean = value;
+            return 0.0;
+        }
+        let deviation = value - self.mean;
+        // A flat history has no spread; any change at all is then infinitely
+        // surprising, so floor the deviation at a small fraction of the mean.
+        let floor = (self.mean.abs() * 0.01).max(1e-6);
+        let z = deviation / self.variance.sqrt().max(floor);
+        self.mean += alpha * deviation;
+        self.variance = (1.0 - alpha) * (self.variance + alpha * deviation * deviation);
+        z
+    }
+}
+
+/// Moving averages of the watched metrics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnomalyDetector {
+    series: [Ewma; 3],
+    deaths: usize,
+    death_refs: Vec<EventRef>,
+}
+
+impl AnomalyDetector {
+    /// Counts this tick's `Death` events towards the current interval.
+    pub fn record_deaths<'a>(&mut self, events: impl IntoIterator<Item = &'a LiveEvent>) {
+        for event in events {
+            if let LiveEvent::Death { .. } = event {
+                self.deaths += 1;
+                if self.death_refs.len() < MAX_DEATH_REFS {
+                    self.death_refs.push(EventRef::from(event));
+                }
+            }
+        }
+    }
+
+    /// Whether `tick` ends a sampling interval.
+    pub fn is_due(tick: u64, config: &AnomalyConfig) -> bool {
+        config.enabled && tick.is_multiple_of(config.sample_interval)
+    }
+
+    /// Takes a sample if `tick` ends an interval and returns the metrics
+    /// that broke from their trend.
+    pub fn observe(
+        &mut self,
+        tick: u64,
+        population: usize,
+        biomass: f64,
+        config: &AnomalyConfig,
+    ) -> Vec<Anomaly> {
+        if !Self::is_due(tick, config) {
+            return Vec::new();
+        }
+        let deaths = std::mem::take(&mut self.deaths);
+        let death_refs = std::mem::take(&mut self.death_refs);
+        let death_rate = deaths as f64 / population.max(1) as f64;
+        let values = [population as f64, biomass, death_rate];
+        let interval = NarrationRefs {
+            from_tick: tick.saturating_sub(config.sample_interval - 1),
+            to_tick: tick,
+            data: Metric::ALL
+                .iter()
+                .zip(values)
+                .map(|(metric, value)| format!("{}: {}", metric.label(), metric.format(value)))
+                .chain(std::iter::once(format!("deaths: {}", deaths)))
+                .collect(),
+            ..NarrationRefs::default()
+        };
+
+        let mut anomalies = Vec::new();
+        for (metric, value) in Metric::ALL.into_iter().zip(values) {
+            let ewma = &mut self.series[metric as usize];
+            let expected = ewma.mean;
+            let z_score = ewma.push(value, config.alpha);
+            if ewma.samples > config.warmup_samples
+                && ewma.samples > ewma.quiet_until
+                && z_score.abs() >= config.z_threshold
+            {
+                ewma.quiet_until = ewma.samples + config.cooldown_samples;
+                let mut anomaly = Anomaly {
+                    metric,
+                    tick,
+                    value,
+                    expected,
+                    z_score,
+                    refs: interval.clone(),
+                };
+                if anomaly.is_decline() {
+                    anomaly.refs.events = death_refs.clone();
+                }
+                anomalies.push(anomaly);
+            }
+        }
+        anomalies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(detector: &mut AnomalyDetector, config: &AnomalyConfig, samples: &[usize]) {
+        for (i, &population) in samples.iter().enumerate() {
+            let tick = (i as u64 + 1) * config.sample_interval;
+            assert!(detector
+                .observe(tick, population, population as f64 * 10.0, config)
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn test_steady_noise_is_not_flagged_but_a_crash_is() {
+        let config = AnomalyConfig::default();
+        let mut detector = AnomalyDetector::default();
+        let steady: Vec<usize> = (0..40).map(|i| 200 + (i * 7) % 11).collect();
+        feed(&mut detector, &config, &steady);
+
+        let tick = 41 * config.sample_interval;
+        let deaths: Vec<LiveEvent> = (0..150)
+            .map(|_| LiveEvent::Death {
+                id: uuid::Uuid::new_v4(),
+                age: 10,
+                offspring: 0,
+                tick: tick - 1,
+                timestamp: String::new(),
+                cause: String::new(),
+                x: None,
+                y: None,
+            })
+            .collect();
+        detector.record_deaths(&deaths);
+        let anomalies = detector.observe(tick, 60, 600.0, &config);
+        let crash = anomalies
+            .iter()
+            .find(|a| a.metric == Metric::Population)
+            .unwrap();
+        assert!(crash.is_decline());
+        assert!(crash.narration().starts_with("The Silent Collapse begins"));
+        assert_eq!(crash.refs.to_tick, tick);
+        assert_eq!(crash.refs.events.len(), MAX_DEATH_REFS);
+        assert!(crash.refs.data.contains(&"deaths: 150".to_string()));
+        assert!(anomalies.iter().any(|a| a.metric == Metric::DeathRate));
+
+        // The same crash is not reported again while the metric cools down.
+        let again = detector.observe(tick + config.sample_interval, 20, 200.0, &config);
+        assert!(again.iter().all(|a| a.metric != Metric::Population));
+    }
+
+    #[test]
+    fn test_warmup_and_off_interval_ticks_are_quiet() {
+        let config = AnomalyConfig::default();
+        let mut detector = AnomalyDetector::default();
+        feed(&mut detector, &config, &[100, 100, 5]);
+        assert!(detector.observe(1, 1000, 0.0, &config).is_empty());
+    }
+}
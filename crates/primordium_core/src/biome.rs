@@ -0,0 +1,441 @@
+//! Biomes: terrain classified by type, fertility, moisture and elevation.
+//!
+//! [`Biome::classify`] names the biome of a single cell; organisms sense it
+//! through their `Biome` brain input. [`BiomeMap`] groups contiguous cells of
+//! one biome into regions and names them after their place on the map, e.g.
+//! "Northern Swamp". Regions smaller than `biomes.min_region_cells` stay
+//! unnamed.
+//!
+//! Biomes drift as forests spread, soil is depleted and rivers move. When
+//! the map is reclassified, each region takes over the id and name of the
+//! previous region of the same biome it overlaps most, so a region keeps its
+//! name while its boundary shifts. Regions that appear, disappear or lose
+//! most of their inhabitants are reported as [`BiomeChange`]s.
+
+use crate::config::BiomeConfig;
+use crate::terrain::{TerrainCell, TerrainGrid, TerrainType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Moisture from which plains turn to swamp.
+const WET_MOISTURE: f32 = 0.6;
+/// Fertility from which plains are meadow rather than steppe.
+const FERTILE: f32 = 0.5;
+/// Elevation from which plains count as alpine.
+const ALPINE_ELEVATION: f32 = 0.7;
+/// Fewest inhabitants a region must have had to be reported as emptied.
+const MIN_DEPOPULATION: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Biome {
+    Meadow,
+    Steppe,
+    Swamp,
+    Forest,
+    Alpine,
+    Desert,
+    Wasteland,
+    Riverland,
+}
+
+impl Biome {
+    pub const ALL: [Biome; 8] = [
+        Biome::Meadow,
+        Biome::Steppe,
+        Biome::Swamp,
+        Biome::Forest,
+        Biome::Alpine,
+        Biome::Desert,
+        Biome::Wasteland,
+        Biome::Riverland,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Biome::Meadow => "Meadow",
+            Biome::Steppe => "Steppe",
+            Biome::Swamp => "Swamp",
+            Biome::Forest => "Forest",
+            Biome::Alpine => "Alpine",
+            Biome::Desert => "Desert",
+            Biome::Wasteland => "Wasteland",
+            Biome::Riverland => "Riverland",
+        }
+    }
+
+    /// The biome of `cell`; walls belong to none. Structures take the biome
+    /// of the ground they were built on.
+    pub fn classify(cell: &TerrainCell) -> Option<Biome> {
+        let ground = match cell.terrain_type {
+            TerrainType::Nest | TerrainType::Outpost => cell.original_type,
+            other => other,
+        };
+        match ground {
+            TerrainType::Wall => None,
+            TerrainType::Mountain => Some(Biome::Alpine),
+            TerrainType::River => Some(Biome::Riverland),
+            TerrainType::Oasis => Some(Biome::Swamp),
+            TerrainType::Forest => Some(Biome::Forest),
+            TerrainType::Desert => Some(Biome::Desert),
+            TerrainType::Barren => Some(Biome::Wasteland),
+            TerrainType::Plains | TerrainType::Nest | TerrainType::Outpost => {
+                Some(if cell.elevation > ALPINE_ELEVATION {
+                    Biome::Alpine
+                } else if cell.local_moisture >= WET_MOISTURE {
+                    Biome::Swamp
+                } else if cell.fertility >= FERTILE {
+                    Biome::Meadow
+                } else {
+                    Biome::Steppe
+                })
+            }
+        }
+    }
+
+    /// The `Biome` brain input: one step in (0, 1] per biome, 0 for none.
+    pub fn sensor(biome: Option<Biome>) -> f32 {
+        biome.map_or(0.0, |b| (b as usize + 1) as f32 / Self::ALL.len() as f32)
+    }
+}
+
+/// A named stretch of contiguous cells of one biome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BiomeRegion {
+    pub id: u32,
+    pub biome: Biome,
+    pub name: String,
+    pub cells: usize,
+    /// Mean position of the region's cells.
+    pub centre: (f32, f32),
+    /// Organisms inside the region at the last classification.
+    pub population: usize,
+    /// Tick the region was first classified.
+    pub since: u64,
+}
+
+/// A region appearing, disappearing or emptying between classifications.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BiomeChange {
+    Emerged(BiomeRegion),
+    Vanished(BiomeRegion),
+    /// Lost more than half of its `before` inhabitants.
+    Depopulated {
+        region: BiomeRegion,
+        before: usize,
+    },
+}
+
+impl BiomeChange {
+    pub fn region(&self) -> &BiomeRegion {
+        match self {
+            BiomeChange::Emerged(region)
+            | BiomeChange::Vanished(region)
+            | BiomeChange::Depopulated { region, .. } => region,
+        }
+    }
+
+    /// One line for the chronicle.
+    pub fn narration(&self) -> String {
+        match self {
+            BiomeChange::Emerged(region) => format!(
+                "A new land takes shape: the {} spreads over {} cells…",
+                region.name, region.cells
+            ),
+            BiomeChange::Vanished(region) => format!(
+                "The {} is no more, swallowed by the lands around it…",
+                region.name
+            ),
+            BiomeChange::Depopulated { region, before } => format!(
+                "Life drains from the {}: {} inhabitants, down from {}…",
+                region.name, region.population, before
+            ),
+        }
+    }
+}
+
+/// Biome regions of the whole map, see the module docs.
+#[derive(Debug, Clone, Default)]
+pub struct BiomeMap {
+    pub width: u16,
+    pub height: u16,
+    /// Region id of every cell; 0 outside any region.
+    pub region_of: Vec<u32>,
+    /// Regions, largest first.
+    pub regions: Vec<BiomeRegion>,
+    next_id: u32,
+}
+
+impl BiomeMap {
+    /// Whether the map has been classified yet.
+    pub fn is_classified(&self) -> bool {
+        !self.region_of.is_empty()
+    }
+
+    pub fn region(&self, id: u32) -> Option<&BiomeRegion> {
+        self.regions.iter().find(|r| r.id == id)
+    }
+
+    /// The region containing the point (`x`, `y`), if any.
+    pub fn region_at(&self, x: f64, y: f64) -> Option<&BiomeRegion> {
+        if x < 0.0 || y < 0.0 || x >= self.width as f64 || y >= self.height as f64 {
+            return None;
+        }
+        let idx = y as usize * self.width as usize + x as usize;
+        self.region_of
+            .get(idx)
+            .filter(|&&id| id != 0)
+            .and_then(|&id| self.region(id))
+    }
+
+    /// Reclassifies `terrain`, counts the organisms at `positions` into the
+    /// regions and returns what changed. The first classification reports
+    /// nothing.
+    pub fn update(
+        &mut self,
+        terrain: &TerrainGrid,
+        positions: impl IntoIterator<Item = (f64, f64)>,
+        tick: u64,
+        config: &BiomeConfig,
+    ) -> Vec<BiomeChange> {
+        let (w, h) = (terrain.width as usize, terrain.height as usize);
+        let reclassified = self.is_classified() && self.region_of.len() == w * h;
+        let biomes: Vec<Option<Biome>> = terrain.cells().map(Biome::classify).collect();
+        let mut components = components(&biomes, w, h);
+        components.retain(|(_, cells)| cells.len() >= config.min_region_cells);
+        components.sort_by_key(|(_, cells)| std::cmp::Reverse(cells.len()));
+
+        let old_regions: HashMap<u32, &BiomeRegion> = if reclassified {
+            self.regions.iter().map(|r| (r.id, r)).collect()
+        } else {
+            HashMap::new()
+        };
+        let mut claimed = HashSet::new();
+        let mut names: HashSet<String> = HashSet::new();
+        let mut region_of = vec![0; w * h];
+        let mut regions = Vec::with_capacity(components.len());
+        let mut emerged = Vec::new();
+        for (biome, cells) in components {
+            let mut overlap: HashMap<u32, usize> = HashMap::new();
+            for &idx in &cells {
+                let old = self.region_of.get(idx).copied().unwrap_or(0);
+                if old_regions.get(&old).is_some_and(|r| r.biome == biome) {
+                    *overlap.entry(old).or_default() += 1;
+                }
+            }
+            let heir = overlap
+                .into_iter()
+                .filter(|(id, _)| !claimed.contains(id))
+                .max_by_key(|&(id, count)| (count, std::cmp::Reverse(id)))
+                .map(|(id, _)| old_regions[&id]);
+
+            let (sx, sy) = cells.iter().fold((0.0, 0.0), |(sx, sy), &idx| {
+                (sx + (idx % w) as f32, sy + (idx / w) as f32)
+            });
+            let centre = (sx / cells.len() as f32, sy / cells.len() as f32);
+            let region = match heir {
+                Some(old) => {
+                    claimed.insert(old.id);
+                    BiomeRegion {
+                        cells: cells.len(),
+                        centre,
+                        population: 0,
+                        ..old.clone()
+                    }
+                }
+                None => {
+                    self.next_id += 1;
+                    BiomeRegion {
+                        id: self.next_id,
+                        biome,
+                        name: String::new(),
+                        cells: cells.len(),
+                        centre,
+                        population: 0,
+                        since: tick,
+                    }
+                }
+            };
+            if heir.is_none() {
+                emerged.push(region.id);
+            } else {
+                names.insert(region.name.clone());
+            }
+            for idx in cells {
+                region_of[idx] = region.id;
+            }
+            regions.push(region);
+        }
+        for region in regions.iter_mut().filter(|r| r.name.is_empty()) {
+            region.name = unique_name(region, w, h, &mut names);
+        }
+
+        for (x, y) in positions {
+            if x >= 0.0 && y >= 0.0 && (x as usize) < w && (y as usize) < h {
+                let id = region_of[y as usize * w + x as usize];
+                if let Some(region) = regions.iter_mut().find(|r| r.id == id) {
+                    region.population += 1;
+                }
+            }
+        }
+
+        let mut changes = Vec::new();
+        if reclassified {
+            for region in &regions {
+                if emerged.contains(&region.id) {
+                    changes.push(BiomeChange::Emerged(region.clone()));
+                } else if let Some(old) = old_regions.get(&region.id) {
+                    if old.population >= MIN_DEPOPULATION && region.population * 2 < old.population
+                    {
+                        changes.push(BiomeChange::Depopulated {
+                            region: region.clone(),
+                            before: old.population,
+                        });
+                    }
+                }
+            }
+            for old in self.regions.iter().filter(|r| !claimed.contains(&r.id)) {
+                changes.push(BiomeChange::Vanished(old.clone()));
+            }
+        }
+
+        self.width = terrain.width;
+        self.height = terrain.height;
+        self.region_of = region_of;
+        self.regions = regions;
+        changes
+    }
+
+    /// Inhabitants per biome, over the named regions.
+    pub fn population_by_biome(&self) -> HashMap<Biome, usize> {
+        let mut population = HashMap::new();
+        for region in &self.regions {
+            *population.entry(region.biome).or_default() += region.population;
+        }
+        population
+    }
+}
+
+/// Contiguous (4-neighbour) runs of cells of one biome.
+fn components(biomes: &[Option<Biome>], w: usize, h: usize) -> Vec<(Biome, Vec<usize>)> {
+    let mut seen = vec![false; biomes.len()];
+    let mut components = Vec::new();
+    for start in 0..biomes.len() {
+        let Some(biome) = biomes[start] else {
+            continue;
+        };
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut cells = Vec::new();
+        let mut stack = vec![start];
+        while let Some(idx) = stack.pop() {
+            cells.push(idx);
+            let (x, y) = (idx % w, idx / w);
+            let neighbours = [
+                (x > 0).then(|| idx - 1),
+                (x + 1 < w).then(|| idx + 1),
+                (y > 0).then(|| idx - w),
+                (y + 1 < h).then(|| idx + w),
+            ];
+            for next in neighbours.into_iter().flatten() {
+                if !seen[next] && biomes[next] == Some(biome) {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        components.push((biome, cells));
+    }
+    components
+}
+
+/// "Northern Swamp", or "Northern Swamp II" if that name is taken.
+fn unique_name(region: &BiomeRegion, w: usize, h: usize, names: &mut HashSet<String>) -> String {
+    const COMPASS: [[&str; 3]; 3] = [
+        ["North-Western", "Northern", "North-Eastern"],
+        ["Western", "Central", "Eastern"],
+        ["South-Western", "Southern", "South-Eastern"],
+    ];
+    const NUMERALS: [&str; 9] = ["II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X"];
+    let column = ((region.centre.0 * 3.0 / w as f32) as usize).min(2);
+    let row = ((region.centre.1 * 3.0 / h as f32) as usize).min(2);
+    let base = format!("{} {}", COMPASS[row][column], region.biome.name());
+    let mut name = base.clone();
+    let mut n = 0;
+    while names.contains(&name) {
+        name = match NUMERALS.get(n) {
+            Some(numeral) => format!("{} {}", base, numeral),
+            None => format!("{} {}", base, n + 2),
+        };
+        n += 1;
+    }
+    names.insert(name.clone());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 12x12 meadow with a desert covering columns `desert` of every row.
+    fn terrain(desert: std::ops::Range<u16>) -> TerrainGrid {
+        let mut cells = vec![TerrainCell::default(); 144];
+        for (idx, cell) in cells.iter_mut().enumerate() {
+            if desert.contains(&((idx % 12) as u16)) {
+                cell.terrain_type = TerrainType::Desert;
+                cell.original_type = TerrainType::Desert;
+            }
+        }
+        TerrainGrid::from_cells(12, 12, cells)
+    }
+
+    #[test]
+    fn test_cells_classify_by_type_moisture_and_fertility() {
+        let mut cell = TerrainCell::default();
+        assert_eq!(Biome::classify(&cell), Some(Biome::Meadow));
+        cell.fertility = 0.2;
+        assert_eq!(Biome::classify(&cell), Some(Biome::Steppe));
+        cell.local_moisture = 0.9;
+        assert_eq!(Biome::classify(&cell), Some(Biome::Swamp));
+        cell.terrain_type = TerrainType::Mountain;
+        assert_eq!(Biome::classify(&cell), Some(Biome::Alpine));
+        cell.terrain_type = TerrainType::Wall;
+        assert_eq!(Biome::classify(&cell), None);
+        assert_eq!(Biome::sensor(None), 0.0);
+        assert_eq!(Biome::sensor(Some(Biome::Riverland)), 1.0);
+    }
+
+    #[test]
+    fn test_regions_keep_their_names_as_boundaries_shift() {
+        let config = BiomeConfig {
+            interval: 1,
+            min_region_cells: 20,
+        };
+        let mut map = BiomeMap::default();
+        assert!(map
+            .update(&terrain(8..12), [(10.0, 5.0), (1.0, 1.0)], 0, &config)
+            .is_empty());
+        let desert = map.region_at(10.0, 5.0).unwrap().clone();
+        assert_eq!(desert.name, "Eastern Desert");
+        assert_eq!((desert.cells, desert.population), (48, 1));
+        assert_eq!(map.population_by_biome()[&Biome::Meadow], 1);
+
+        // The desert advances two columns west but is still the same region.
+        assert!(map
+            .update(&terrain(6..12), [(10.0, 5.0)], 1, &config)
+            .is_empty());
+        let grown = map.region_at(6.0, 5.0).unwrap();
+        assert_eq!(
+            (grown.id, grown.name.as_str()),
+            (desert.id, "Eastern Desert")
+        );
+        assert_eq!(grown.cells, 72);
+
+        // A desert too small to name, then none at all.
+        let changes = map.update(&terrain(11..12), [], 2, &config);
+        assert!(matches!(&changes[..], [BiomeChange::Vanished(r)] if r.id == desert.id));
+        assert!(changes[0].narration().contains("Eastern Desert is no more"));
+        assert!(map.region_at(11.0, 5.0).is_none());
+    }
+}
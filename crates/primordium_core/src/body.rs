@@ -0,0 +1,73 @@
+//! Body plans: how an organism's size gene shapes it.
+//!
+//! The body size gene spans 1.0 (a single cell) to 3.0 cells across. Energy
+//! stores grow with size, but so does the metabolic bill, faster than
+//! linearly, and big bodies are slower. In a fight the larger body has the
+//! edge, and prey too large for the attacker cannot be taken at all. Bodies
+//! reach across their footprint: they feed, hunt and are hunted from its
+//! edge rather than its centre.
+
+use crate::config::BodyConfig;
+use primordium_data::MIN_BODY_SIZE;
+
+/// Distance from a body's centre to its edge, 0.0 for a single cell.
+pub fn radius(size: f32) -> f64 {
+    f64::from(size.max(MIN_BODY_SIZE) - MIN_BODY_SIZE) / 2.0
+}
+
+/// Side of the square of cells a body covers on the map.
+pub fn footprint(size: f32) -> u16 {
+    size.max(MIN_BODY_SIZE).round() as u16
+}
+
+/// Multiplier of a body's energy capacity.
+pub fn energy_capacity(size: f32) -> f64 {
+    f64::from(size.max(MIN_BODY_SIZE))
+}
+
+/// Multiplier of a body's metabolic costs.
+pub fn metabolic_factor(size: f32, config: &BodyConfig) -> f64 {
+    f64::from(size.max(MIN_BODY_SIZE)).powf(config.metabolic_exponent)
+}
+
+/// Multiplier of a body's top speed.
+pub fn speed_factor(size: f32, config: &BodyConfig) -> f64 {
+    f64::from(size.max(MIN_BODY_SIZE)).powf(-config.speed_exponent)
+}
+
+/// Combat strength of an attacker against a target: the ratio of their
+/// sizes.
+pub fn strength(attacker: f32, target: f32) -> f64 {
+    f64::from(attacker.max(MIN_BODY_SIZE) / target.max(MIN_BODY_SIZE))
+}
+
+/// Whether an attacker is large enough to take a target.
+pub fn can_overpower(attacker: f32, target: f32, config: &BodyConfig) -> bool {
+    strength(target, attacker) <= config.max_prey_ratio
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primordium_data::MAX_BODY_SIZE;
+
+    #[test]
+    fn test_larger_bodies_trade_speed_and_upkeep_for_strength() {
+        let config = BodyConfig::default();
+        assert_eq!(radius(MIN_BODY_SIZE), 0.0);
+        assert_eq!(footprint(MIN_BODY_SIZE), 1);
+        assert_eq!(footprint(MAX_BODY_SIZE), 3);
+        assert_eq!(radius(MAX_BODY_SIZE), 1.0);
+        assert_eq!(metabolic_factor(MIN_BODY_SIZE, &config), 1.0);
+        assert_eq!(speed_factor(MIN_BODY_SIZE, &config), 1.0);
+
+        let big = 2.0;
+        assert!(energy_capacity(big) > 1.0);
+        assert!(metabolic_factor(big, &config) > energy_capacity(big));
+        assert!(speed_factor(big, &config) < 1.0);
+        assert!(strength(big, MIN_BODY_SIZE) > 1.0);
+
+        assert!(can_overpower(big, MAX_BODY_SIZE, &config));
+        assert!(!can_overpower(MIN_BODY_SIZE, MAX_BODY_SIZE, &config));
+    }
+}
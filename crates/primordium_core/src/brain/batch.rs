@@ -0,0 +1,341 @@
+//! Batched brain inference.
+//!
+//! [`forward_batch`] runs the forward pass of a whole population at once.
+//! Without the `batch` feature it runs each brain's scalar pass
+//! ([`forward::forward_internal`]) in parallel. With it, brains that share
+//! a topology, the same nodes, activations and wiring with only the
+//! weights differing, are evaluated together as node-by-brain matrices:
+//! each step of the pass, one connection or one node, becomes a single
+//! vector operation across the group.
+//!
+//! Every element is computed with the same operations in the same order as
+//! the scalar pass, so outputs, hidden state and the activations kept for
+//! learning are bit-identical with the feature on or off.
+
+use super::forward;
+use super::{BRAIN_INPUTS, BRAIN_MEMORY, BRAIN_OUTPUTS};
+use primordium_data::{Activations, Brain};
+use rayon::prelude::*;
+
+/// Outputs and next hidden state of one forward pass.
+pub type BrainOutput = ([f32; BRAIN_OUTPUTS], [f32; BRAIN_MEMORY]);
+
+/// One brain's share of a batched forward pass.
+pub struct BatchItem<'a> {
+    pub brain: &'a Brain,
+    pub inputs: [f32; BRAIN_INPUTS],
+    pub last_hidden: [f32; BRAIN_MEMORY],
+    /// Updated exactly as [`forward::forward_internal`] updates them.
+    pub activations: &'a mut Activations,
+}
+
+impl BatchItem<'_> {
+    fn forward_scalar(&mut self) -> BrainOutput {
+        forward::forward_internal(self.brain, self.inputs, self.last_hidden, self.activations)
+    }
+}
+
+/// Runs the forward pass of every item, returning their outputs in order.
+pub fn forward_batch(items: &mut [BatchItem]) -> Vec<BrainOutput> {
+    #[cfg(feature = "batch")]
+    {
+        matrix::forward_grouped(items)
+    }
+    #[cfg(not(feature = "batch"))]
+    {
+        items
+            .par_iter_mut()
+            .map(BatchItem::forward_scalar)
+            .collect()
+    }
+}
+
+#[cfg(feature = "batch")]
+mod matrix {
+    use super::*;
+    use crate::brain::BRAIN_HIDDEN_START;
+    use ndarray::{s, Array2, Zip};
+    use std::collections::HashMap;
+
+    /// Groups smaller than this run the scalar pass.
+    const MIN_GROUP: usize = 8;
+    /// Brains evaluated together per matrix, so large groups still spread
+    /// across threads.
+    const CHUNK: usize = 256;
+
+    /// Whether `a` and `b` take the same steps in the forward pass. Equal
+    /// `wiring_hash`es make this all but certain; checking keeps a stale or
+    /// colliding hash from mixing brains up.
+    fn same_wiring(a: &Brain, b: &Brain) -> bool {
+        let endpoints = |brain: &Brain, k: usize| {
+            let conn = &brain.connections[brain.recurrent_connections[k]];
+            (conn.from, conn.to)
+        };
+        a.nodes.len() == b.nodes.len()
+            && a.nodes
+                .iter()
+                .zip(&b.nodes)
+                .all(|(x, y)| x.id == y.id && x.activation == y.activation)
+            && a.fast_forward_order == b.fast_forward_order
+            && a.incoming_offsets == b.incoming_offsets
+            && a.incoming_flat.len() == b.incoming_flat.len()
+            && a.incoming_flat
+                .iter()
+                .zip(&b.incoming_flat)
+                .all(|(x, y)| x.0 == y.0)
+            && a.recurrent_connections.len() == b.recurrent_connections.len()
+            && (0..a.recurrent_connections.len()).all(|k| endpoints(a, k) == endpoints(b, k))
+    }
+
+    pub(super) fn forward_grouped(items: &mut [BatchItem]) -> Vec<BrainOutput> {
+        let mut results = vec![([0.0; BRAIN_OUTPUTS], [0.0; BRAIN_MEMORY]); items.len()];
+        // The pass swaps the activation buffers first, so the previous
+        // tick's values, which decide the recurrent connections it
+        // resolves, are the ones in `.0` now.
+        let mut groups: HashMap<(u64, usize), Vec<usize>> = HashMap::new();
+        let mut scalar = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            let brain = item.brain;
+            if brain.node_idx_map.is_empty() || brain.incoming_offsets.is_empty() {
+                scalar.push(i);
+            } else {
+                let key = (brain.wiring_hash, item.activations.0.len());
+                groups.entry(key).or_default().push(i);
+            }
+        }
+
+        let mut chunks: Vec<Vec<usize>> = Vec::new();
+        for members in groups.into_values() {
+            let leader = items[members[0]].brain;
+            let (same, different): (Vec<usize>, Vec<usize>) = members
+                .into_par_iter()
+                .partition(|&i| same_wiring(leader, items[i].brain));
+            scalar.extend(different);
+            if same.len() < MIN_GROUP {
+                scalar.extend(same);
+            } else {
+                chunks.extend(same.chunks(CHUNK).map(<[usize]>::to_vec));
+            }
+        }
+
+        // Hand each chunk its items as disjoint mutable borrows.
+        let mut slots: Vec<Option<&mut BatchItem>> = items.iter_mut().map(Some).collect();
+        let mut take = |i: usize| (i, slots[i].take().expect("item taken twice"));
+        let scalar_items: Vec<(usize, &mut BatchItem)> =
+            scalar.into_iter().map(&mut take).collect();
+        let chunk_items: Vec<Vec<(usize, &mut BatchItem)>> = chunks
+            .into_iter()
+            .map(|members| members.into_iter().map(&mut take).collect())
+            .collect();
+
+        let scalar_results: Vec<(usize, BrainOutput)> = scalar_items
+            .into_par_iter()
+            .map(|(i, item)| (i, item.forward_scalar()))
+            .collect();
+        let chunk_results: Vec<Vec<(usize, BrainOutput)>> =
+            chunk_items.into_par_iter().map(forward_chunk).collect();
+        for (i, output) in scalar_results
+            .into_iter()
+            .chain(chunk_results.into_iter().flatten())
+        {
+            results[i] = output;
+        }
+        results
+    }
+
+    /// Runs the forward pass of brains sharing their wiring, one column per
+    /// brain, mirroring [`forward::forward_internal`] step for step.
+    fn forward_chunk(mut members: Vec<(usize, &mut BatchItem)>) -> Vec<(usize, BrainOutput)> {
+        let leader = members[0].1.brain;
+        let nodes = leader.nodes.len();
+        let brains = members.len();
+        let index_of = |id: usize| leader.node_idx_map.get(&id).copied();
+
+        // Recurrent connections the scalar pass resolves, as (position in
+        // `recurrent_connections`, from, to).
+        let prev_len = members[0].1.activations.0.len();
+        let recurrent: Vec<(usize, usize, usize)> = leader
+            .recurrent_connections
+            .iter()
+            .enumerate()
+            .filter_map(|(k, &conn_idx)| {
+                let conn = &leader.connections[conn_idx];
+                let (f_idx, t_idx) = (index_of(conn.from)?, index_of(conn.to)?);
+                (f_idx < prev_len).then_some((k, f_idx, t_idx))
+            })
+            .collect();
+        let offset = recurrent.len();
+
+        let mut values = Array2::<f32>::zeros((nodes, brains));
+        let mut prev = Array2::<f32>::zeros((offset, brains));
+        let mut weights = Array2::<f32>::zeros((offset + leader.incoming_flat.len(), brains));
+        let mut inputs = Array2::<f32>::zeros((BRAIN_INPUTS, brains));
+        for (col, (_, item)) in members.iter_mut().enumerate() {
+            let activations = &mut *item.activations;
+            std::mem::swap(&mut activations.0, &mut activations.1);
+            activations.prepare(nodes);
+            // `prepare` only zeroes buffers that keep their length, so the
+            // pass starts from whatever it left behind.
+            values
+                .column_mut(col)
+                .assign(&ndarray::aview1(&activations.0));
+            let brain = item.brain;
+            for (row, &(k, f_idx, _)) in recurrent.iter().enumerate() {
+                prev[[row, col]] = activations.1[f_idx];
+                let conn_idx = brain.recurrent_connections[k];
+                weights[[row, col]] = brain.connections[conn_idx].weight;
+            }
+            for (k, &(_, conn_idx)) in brain.incoming_flat.iter().enumerate() {
+                weights[[offset + k, col]] = brain.connections[conn_idx].weight;
+            }
+            inputs
+                .column_mut(col)
+                .assign(&ndarray::aview1(&item.inputs));
+        }
+
+        for (row, &(_, _, t_idx)) in recurrent.iter().enumerate() {
+            Zip::from(values.row_mut(t_idx))
+                .and(prev.row(row))
+                .and(weights.row(row))
+                .for_each(|v, &p, &w| *v += p * w);
+        }
+
+        let offsets = &leader.incoming_offsets;
+        for &node_idx in &leader.fast_forward_order {
+            let node = &leader.nodes[node_idx];
+            if node.id < BRAIN_INPUTS {
+                values.row_mut(node_idx).assign(&inputs.row(node.id));
+                continue;
+            }
+            if node_idx < offsets.len() - 1 {
+                for k in offsets[node_idx]..offsets[node_idx + 1] {
+                    let from_idx = leader.incoming_flat[k].0;
+                    let (from, mut to) =
+                        values.multi_slice_mut((s![from_idx, ..], s![node_idx, ..]));
+                    Zip::from(&mut to)
+                        .and(&from)
+                        .and(weights.row(offset + k))
+                        .for_each(|v, &f, &w| *v += f * w);
+                }
+            }
+            let activation = node.activation;
+            values
+                .row_mut(node_idx)
+                .mapv_inplace(|v| activation.apply(v));
+        }
+
+        let outputs_idx: [Option<usize>; BRAIN_OUTPUTS] =
+            std::array::from_fn(|i| index_of(i + BRAIN_INPUTS));
+        let hidden_idx: [Option<usize>; BRAIN_MEMORY] =
+            std::array::from_fn(|i| index_of(i + BRAIN_HIDDEN_START));
+        members
+            .into_iter()
+            .enumerate()
+            .map(|(col, (i, item))| {
+                let column = values.column(col);
+                for (slot, &v) in item.activations.0.iter_mut().zip(column.iter()) {
+                    *slot = v;
+                }
+                let mut outputs = [0.0; BRAIN_OUTPUTS];
+                for (output, idx) in outputs.iter_mut().zip(&outputs_idx) {
+                    if let Some(idx) = idx {
+                        *output = column[*idx];
+                    }
+                }
+                let mut hidden = [0.0; BRAIN_MEMORY];
+                for (value, idx) in hidden.iter_mut().zip(&hidden_idx) {
+                    if let Some(idx) = idx {
+                        *value = column[*idx];
+                    }
+                }
+                (i, (outputs, hidden))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brain::{BrainLogic, GenotypeLogic};
+    use primordium_data::{Activation, Genotype, NodeType};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_batched_pass_matches_scalar_bit_for_bit() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let founder = Genotype::new_random_with_rng(&mut rng);
+        // Clones of one founder with their own weights share a topology;
+        // a few that lost a connection form a group too small to batch.
+        let mut brains: Vec<Brain> = (0..40)
+            .map(|i| {
+                let mut brain = founder.brain.clone();
+                for conn in &mut brain.connections {
+                    conn.weight += (i as f32 * 0.37 + conn.innovation as f32).sin() * 0.1;
+                }
+                if i % 10 == 9 {
+                    brain.connections.pop();
+                }
+                brain.initialize_node_idx_map();
+                brain
+            })
+            .collect();
+        // A brain whose activation changed without its caches being rebuilt
+        // keeps a stale `wiring_hash` and must still not join the group.
+        let hidden = brains[3]
+            .nodes
+            .iter()
+            .position(|n| matches!(n.node_type, NodeType::Hidden))
+            .expect("random brains have hidden nodes");
+        brains[3].nodes[hidden].activation = Activation::Gaussian;
+        assert_eq!(brains[3].wiring_hash, brains[0].wiring_hash);
+        // An unwired brain takes the scalar pass's shortcut.
+        let mut unwired = founder.brain.clone();
+        unwired.node_idx_map.clear();
+        brains.push(unwired);
+
+        let inputs: Vec<[f32; BRAIN_INPUTS]> = (0..brains.len())
+            .map(|i| std::array::from_fn(|k| ((i * BRAIN_INPUTS + k) as f32 * 0.61).cos()))
+            .collect();
+        let mut scalar_acts = vec![Activations::default(); brains.len()];
+        let mut batch_acts = vec![Activations::default(); brains.len()];
+
+        // Several ticks, so recurrent connections read earlier activations.
+        for _ in 0..3 {
+            let expected: Vec<BrainOutput> = brains
+                .iter()
+                .zip(&inputs)
+                .zip(&mut scalar_acts)
+                .map(|((brain, inputs), acts)| {
+                    brain.forward_internal(*inputs, [0.0; BRAIN_MEMORY], acts)
+                })
+                .collect();
+            let mut items: Vec<BatchItem> = brains
+                .iter()
+                .zip(&inputs)
+                .zip(&mut batch_acts)
+                .map(|((brain, inputs), activations)| BatchItem {
+                    brain,
+                    inputs: *inputs,
+                    last_hidden: [0.0; BRAIN_MEMORY],
+                    activations,
+                })
+                .collect();
+            let actual = forward_batch(&mut items);
+
+            let bits = |outputs: &[BrainOutput]| -> Vec<u32> {
+                outputs
+                    .iter()
+                    .flat_map(|(o, h)| o.iter().chain(h).map(|v| v.to_bits()))
+                    .collect()
+            };
+            assert_eq!(bits(&actual), bits(&expected));
+            for (a, b) in batch_acts.iter().zip(&scalar_acts) {
+                let bits = |v: &[f32]| v.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+                assert_eq!(bits(&a.0), bits(&b.0));
+                assert_eq!(bits(&a.1), bits(&b.1));
+            }
+        }
+    }
+}
@@ -28,6 +28,14 @@ pub fn brain_crossover_with_rng<R: Rng>(brain: &Brain, other: &Brain, rng: &mut
 
     let other_node_map: HashMap<usize, &Node> = other.nodes.iter().map(|n| (n.id, n)).collect();
 
+    for node in &mut child_nodes {
+        if let Some(n2) = other_node_map.get(&node.id) {
+            if n2.activation != node.activation && rng.gen_bool(0.5) {
+                node.activation = n2.activation;
+            }
+        }
+    }
+
     for c in &child_connections {
         if !existing_node_ids.contains(&c.from) {
             if let Some(&n) = other_node_map.get(&c.from) {
@@ -61,6 +69,7 @@ pub fn brain_crossover_with_rng<R: Rng>(brain: &Brain, other: &Brain, rng: &mut
         fast_forward_order: Vec::new(),
         incoming_flat: Vec::new(),
         incoming_offsets: Vec::new(),
+        wiring_hash: 0,
     };
     child.initialize_node_idx_map();
     child
@@ -90,5 +99,11 @@ pub fn genotype_crossover_with_rng<R: Rng>(
         pairing_bias: parent.pairing_bias,
         specialization_bias: parent.specialization_bias,
         regulatory_rules: parent.regulatory_rules.clone(),
+        oviparity: parent.oviparity,
+        clutch_size: parent.clutch_size,
+        parental_care: parent.parental_care,
+        skepticism: parent.skepticism,
+        death_avoidance: parent.death_avoidance,
+        body_size: parent.body_size,
     }
 }
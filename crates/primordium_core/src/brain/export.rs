@@ -0,0 +1,216 @@
+//! Brain figures: GraphViz DOT and standalone SVG renderings of a brain.
+//!
+//! Nodes are labeled with the sensor/actuator names, edge thickness follows
+//! `|weight|` and, when activations from a forward pass are given, nodes are
+//! colored green (positive) or red (negative) by their current value.
+
+use super::*;
+use primordium_data::{Activation, Brain, Node, NodeType};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+const INPUT_FILL: &str = "#e1f5fe";
+const OUTPUT_FILL: &str = "#fff3e0";
+const HIDDEN_FILL: &str = "#eeeeee";
+
+/// Display label of a node: its sensor/actuator name, or `H<id>` for hidden
+/// nodes, followed by the activation function if it is not tanh.
+#[must_use]
+pub fn node_label(node: &Node) -> String {
+    let name = match node.node_type {
+        NodeType::Input => INPUT_LABELS.get(node.id).map(|l| l.to_string()),
+        NodeType::Output => OUTPUT_LABELS
+            .get(node.id.wrapping_sub(BRAIN_INPUTS))
+            .map(|l| l.to_string()),
+        NodeType::Hidden => None,
+    }
+    .or_else(|| node.label.clone())
+    .unwrap_or_else(|| format!("H{}", node.id));
+    if matches!(node.node_type, NodeType::Hidden) && node.activation != Activation::Tanh {
+        format!("{name} ({})", node.activation.label())
+    } else {
+        name
+    }
+}
+
+/// Fill color for an activation in `[-1, 1]`: white fading to green for
+/// positive values and to red for negative ones.
+#[must_use]
+pub fn activation_color(value: f32) -> String {
+    let t = value.abs().min(1.0);
+    let (r, g, b) = if value >= 0.0 {
+        (0x2e, 0x7d, 0x32)
+    } else {
+        (0xc6, 0x28, 0x28)
+    };
+    let mix = |c: u8| (255.0 - t * (255.0 - f32::from(c))).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", mix(r), mix(g), mix(b))
+}
+
+fn edge_width(weight: f32) -> f32 {
+    0.5 + weight.abs().min(5.0)
+}
+
+fn edge_color(weight: f32, enabled: bool) -> &'static str {
+    if !enabled {
+        "#bdbdbd"
+    } else if weight >= 0.0 {
+        "#1565c0"
+    } else {
+        "#c62828"
+    }
+}
+
+fn node_fill(node: &Node, idx: usize, activations: Option<&[f32]>) -> String {
+    match activations {
+        Some(values) => activation_color(values.get(idx).copied().unwrap_or(0.0)),
+        None => match node.node_type {
+            NodeType::Input => INPUT_FILL,
+            NodeType::Output => OUTPUT_FILL,
+            NodeType::Hidden => HIDDEN_FILL,
+        }
+        .to_string(),
+    }
+}
+
+/// Export a brain to Graphviz DOT format.
+///
+/// `activations` are node values indexed like `brain.nodes` (as left in
+/// [`primordium_data::Activations`] by a forward pass); when given, nodes are
+/// colored by them and show their value.
+#[must_use]
+pub fn to_dot(brain: &Brain, activations: Option<&[f32]>) -> String {
+    let mut dot = String::from("digraph Brain {\n");
+    dot.push_str("  rankdir=LR;\n");
+    dot.push_str("  node [shape=ellipse, style=filled, fontname=\"Arial\", fontsize=10];\n");
+
+    for (idx, node) in brain.nodes.iter().enumerate() {
+        let mut label = node_label(node);
+        if let Some(value) = activations.and_then(|values| values.get(idx)) {
+            let _ = write!(label, "\\n{value:.2}");
+        }
+        let _ = writeln!(
+            dot,
+            "  \"{}\" [label=\"{}\", fillcolor=\"{}\"];",
+            node.id,
+            label,
+            node_fill(node, idx, activations)
+        );
+    }
+
+    for (kind, rank) in [(NodeType::Input, "source"), (NodeType::Output, "sink")] {
+        let ids: Vec<String> = brain
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == kind)
+            .map(|n| format!("\"{}\"", n.id))
+            .collect();
+        if !ids.is_empty() {
+            let _ = writeln!(dot, "  {{ rank={rank}; {}; }}", ids.join("; "));
+        }
+    }
+
+    for conn in &brain.connections {
+        let style = if conn.enabled { "solid" } else { "dashed" };
+        let _ = writeln!(
+            dot,
+            "  \"{}\" -> \"{}\" [penwidth={:.2}, color=\"{}\", style={}, tooltip=\"{:.3}\"];",
+            conn.from,
+            conn.to,
+            edge_width(conn.weight),
+            edge_color(conn.weight, conn.enabled),
+            style,
+            conn.weight
+        );
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Render a brain as a standalone SVG figure, without needing Graphviz.
+///
+/// Inputs, hidden nodes and outputs are laid out in three columns; edges and
+/// node colors follow [`to_dot`].
+#[must_use]
+pub fn to_svg(brain: &Brain, activations: Option<&[f32]>) -> String {
+    const COLUMN_X: [f32; 3] = [110.0, 360.0, 610.0];
+    const ROW_HEIGHT: f32 = 22.0;
+    const TOP: f32 = 40.0;
+
+    let column = |node: &Node| match node.node_type {
+        NodeType::Input => 0,
+        NodeType::Hidden => 1,
+        NodeType::Output => 2,
+    };
+
+    let mut rows = [0usize; 3];
+    let mut positions: HashMap<usize, (f32, f32)> = HashMap::new();
+    for node in &brain.nodes {
+        let col = column(node);
+        let y = TOP + rows[col] as f32 * ROW_HEIGHT;
+        rows[col] += 1;
+        positions.insert(node.id, (COLUMN_X[col], y));
+    }
+
+    let height = TOP * 2.0 + rows.iter().max().copied().unwrap_or(0) as f32 * ROW_HEIGHT;
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"720\" height=\"{height:.0}\" \
+         font-family=\"Arial\" font-size=\"10\">"
+    );
+    svg.push_str("  <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    for (x, title) in COLUMN_X.iter().zip(["Inputs", "Hidden", "Outputs"]) {
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{x}\" y=\"20\" text-anchor=\"middle\" font-weight=\"bold\">{title}</text>"
+        );
+    }
+
+    for conn in &brain.connections {
+        if let (Some(&(x1, y1)), Some(&(x2, y2))) =
+            (positions.get(&conn.from), positions.get(&conn.to))
+        {
+            let dash = if conn.enabled {
+                ""
+            } else {
+                " stroke-dasharray=\"4 3\""
+            };
+            let _ = writeln!(
+                svg,
+                "  <line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"{}\" \
+                 stroke-width=\"{:.2}\" stroke-opacity=\"0.6\"{dash}/>",
+                edge_color(conn.weight, conn.enabled),
+                edge_width(conn.weight)
+            );
+        }
+    }
+
+    for (idx, node) in brain.nodes.iter().enumerate() {
+        let (x, y) = positions[&node.id];
+        let mut label = xml_escape(&node_label(node));
+        if let Some(value) = activations.and_then(|values| values.get(idx)) {
+            let _ = write!(label, " {value:.2}");
+        }
+        let _ = writeln!(
+            svg,
+            "  <ellipse cx=\"{x}\" cy=\"{y}\" rx=\"95\" ry=\"9\" fill=\"{}\" stroke=\"#616161\"/>",
+            node_fill(node, idx, activations)
+        );
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{x}\" y=\"{:.0}\" text-anchor=\"middle\">{label}</text>",
+            y + 3.5
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
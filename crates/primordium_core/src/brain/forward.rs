@@ -57,7 +57,9 @@ pub fn forward_internal(
             }
         }
 
-        node_values[node_idx] = node_values[node_idx].tanh();
+        node_values[node_idx] = brain.nodes[node_idx]
+            .activation
+            .apply(node_values[node_idx]);
     }
 
     let mut outputs = [0.0; BRAIN_OUTPUTS];
@@ -1,4 +1,6 @@
+pub mod batch;
 pub mod crossover;
+pub mod export;
 pub mod forward;
 pub mod mutation;
 pub mod topology;
@@ -21,7 +23,7 @@ pub use topology::{create_brain_random_with_rng, create_genotype_random_with_rng
 ///
 /// let mut rng = ChaCha8Rng::seed_from_u64(42);
 /// let brain = Brain::new_random_with_rng(&mut rng);
-/// let inputs = [0.0; 29];
+/// let inputs = [0.0; 36];
 /// let hidden = [0.0; 6];
 /// let (outputs, next_hidden) = brain.forward(inputs, hidden);
 /// ```
@@ -66,6 +68,13 @@ pub trait BrainLogic {
         rng: &mut R,
     );
 
+    /// Removes dead and near-zero connections and orphaned evolved nodes;
+    /// returns how many connections were removed.
+    fn prune(&mut self, threshold: f32) -> usize;
+
+    /// Per-tick energy cost of maintaining this brain's nodes and connections.
+    fn maintenance_cost(&self, config: &crate::config::BrainConfig) -> f64;
+
     fn genotype_distance(&self, other: &Brain) -> f32;
     fn distance(&self, other: &Brain) -> f32;
     fn crossover_with_rng<R: Rng>(&self, other: &Brain, rng: &mut R) -> Brain;
@@ -112,7 +121,7 @@ pub trait GenotypeLogic {
         Self: Sized;
 }
 
-pub const INPUT_LABELS: [&str; 29] = [
+pub const INPUT_LABELS: [&str; 36] = [
     "FoodDX",
     "FoodDY",
     "Energy",
@@ -142,9 +151,16 @@ pub const INPUT_LABELS: [&str; 29] = [
     "LineagePop",
     "LineageEnergy",
     "Overmind",
+    "RecallDX",
+    "RecallDY",
+    "RecallAge",
+    "RecallPartner",
+    "Biome",
+    "Light",
+    "Water",
 ];
 
-pub const OUTPUT_LABELS: [&str; 12] = [
+pub const OUTPUT_LABELS: [&str; 15] = [
     "MoveX",
     "MoveY",
     "Speed",
@@ -157,6 +173,9 @@ pub const OUTPUT_LABELS: [&str; 12] = [
     "Dig",
     "Build",
     "OvermindEmit",
+    "MemWrite",
+    "MemAddr",
+    "Deceive",
 ];
 
 pub const BRAIN_INPUTS: usize = INPUT_LABELS.len();
@@ -205,6 +224,17 @@ impl BrainLogic for Brain {
         mutation::mutate_with_config(self, config, specialization, rng)
     }
 
+    fn prune(&mut self, threshold: f32) -> usize {
+        let removed = mutation::prune(self, threshold);
+        self.initialize_node_idx_map();
+        removed
+    }
+
+    fn maintenance_cost(&self, config: &crate::config::BrainConfig) -> f64 {
+        self.nodes.len() as f64 * config.hidden_node_cost
+            + self.connections.len() as f64 * config.connection_cost
+    }
+
     fn genotype_distance(&self, other: &Brain) -> f32 {
         let mut weight_diff = 0.0;
         let mut matching = 0;
@@ -278,10 +308,10 @@ impl GenotypeLogic for Genotype {
     }
 
     fn to_hex(&self) -> String {
-        match serde_json::to_vec(self) {
+        match primordium_data::data::dna::encode(self) {
             Ok(bytes) => hex::encode(bytes),
             Err(e) => {
-                tracing::error!(error = %e, "Failed to serialize genotype to JSON");
+                tracing::error!(error = %e, "Failed to encode genotype DNA");
                 String::new()
             }
         }
@@ -295,8 +325,10 @@ impl GenotypeLogic for Genotype {
             return Err(anyhow::anyhow!("Empty hex string"));
         }
 
-        let genotype = serde_json::from_slice(&bytes)
+        let mut genotype = primordium_data::data::dna::decode(&bytes)
             .map_err(|e| anyhow::anyhow!("Failed to deserialize genotype: {}", e))?;
+        // DNA written by builds with fewer sensors gets the new ones unwired.
+        topology::pad_to_current(&mut genotype.brain);
         Ok(genotype)
     }
 }
@@ -1,5 +1,5 @@
 use super::*;
-use primordium_data::{Brain, Connection, Node, NodeType, Specialization};
+use primordium_data::{Activation, Brain, Connection, Node, NodeType, Specialization};
 use rand::Rng;
 
 pub fn mutate_with_config<R: Rng>(
@@ -15,9 +15,9 @@ pub fn mutate_with_config<R: Rng>(
     if let Some(spec) = specialization {
         use primordium_data::Specialization;
         let target_nodes = match spec {
-            Specialization::Soldier => vec![32],
-            Specialization::Engineer => vec![38, 39],
-            Specialization::Provider => vec![33],
+            Specialization::Soldier => vec![BRAIN_INPUTS + 3],
+            Specialization::Engineer => vec![BRAIN_INPUTS + 9, BRAIN_INPUTS + 10],
+            Specialization::Provider => vec![BRAIN_INPUTS + 4],
         };
         for &t in &target_nodes {
             protected_nodes.insert(t);
@@ -76,6 +76,7 @@ pub fn mutate_with_config<R: Rng>(
                     id: new_id,
                     node_type: NodeType::Hidden,
                     label: None,
+                    activation: Activation::Tanh,
                 });
             }
 
@@ -96,16 +97,67 @@ pub fn mutate_with_config<R: Rng>(
         }
     }
 
-    if rng.gen_bool(0.1) {
-        brain
-            .connections
-            .retain(|c| c.weight.abs() >= config.brain.pruning_threshold || !c.enabled);
+    if rng.gen::<f32>() < topo_rate {
+        mutate_activation(brain, rng);
+    }
+
+    if rng.gen_bool(f64::from(config.brain.pruning_rate)) {
+        prune(brain, config.brain.pruning_threshold);
     }
     brain.initialize_node_idx_map();
 }
 
+/// Switches a random hidden node to a different activation function.
+pub fn mutate_activation<R: Rng>(brain: &mut Brain, rng: &mut R) {
+    let hidden: Vec<usize> = brain
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| matches!(n.node_type, NodeType::Hidden))
+        .map(|(idx, _)| idx)
+        .collect();
+    if hidden.is_empty() {
+        return;
+    }
+    let node = &mut brain.nodes[hidden[rng.gen_range(0..hidden.len())]];
+    let others: Vec<Activation> = Activation::ALL
+        .into_iter()
+        .filter(|a| *a != node.activation)
+        .collect();
+    node.activation = others[rng.gen_range(0..others.len())];
+}
+
+/// Removes disabled connections, enabled ones weaker than `threshold` and
+/// the evolved hidden nodes left without connections. Returns how many
+/// connections were removed. The caller rebuilds the node index map.
+pub fn prune(brain: &mut Brain, threshold: f32) -> usize {
+    let before = brain.connections.len();
+    brain
+        .connections
+        .retain(|c| c.enabled && c.weight.abs() >= threshold);
+
+    let linked: std::collections::HashSet<usize> = brain
+        .connections
+        .iter()
+        .flat_map(|c| [c.from, c.to])
+        .collect();
+    brain.nodes.retain(|n| {
+        !matches!(n.node_type, NodeType::Hidden)
+            || n.id < BRAIN_HIDDEN_END
+            || linked.contains(&n.id)
+    });
+
+    let innovations: std::collections::HashSet<usize> =
+        brain.connections.iter().map(|c| c.innovation).collect();
+    brain
+        .weight_deltas
+        .retain(|innovation, _| innovations.contains(innovation));
+    before - brain.connections.len()
+}
+
 pub fn remodel_for_adult_with_rng<R: Rng>(brain: &mut Brain, rng: &mut R) {
-    let adult_outputs = [34, 35, 36, 37, 38, 39, 40];
+    // Color through OvermindEmit.
+    let adult_outputs = BRAIN_INPUTS + 5..BRAIN_INPUTS + 12;
     let hidden_nodes: Vec<usize> = brain
         .nodes
         .iter()
@@ -117,7 +169,7 @@ pub fn remodel_for_adult_with_rng<R: Rng>(brain: &mut Brain, rng: &mut R) {
         return;
     }
 
-    for &out_id in &adult_outputs {
+    for out_id in adult_outputs {
         let has_conn = brain
             .connections
             .iter()
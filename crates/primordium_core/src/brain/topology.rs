@@ -1,5 +1,5 @@
 use super::*;
-use primordium_data::{Brain, Connection, Genotype, Node, NodeType};
+use primordium_data::{Activation, Brain, Connection, Genotype, Node, NodeType, MIN_BODY_SIZE};
 use rand::Rng;
 use std::collections::HashMap;
 
@@ -11,6 +11,7 @@ pub fn create_brain_random_with_rng<R: Rng>(rng: &mut R) -> Brain {
             id: i,
             node_type: NodeType::Input,
             label: Some(label.to_string()),
+            activation: Activation::Tanh,
         });
     }
 
@@ -19,6 +20,7 @@ pub fn create_brain_random_with_rng<R: Rng>(rng: &mut R) -> Brain {
             id: i + BRAIN_INPUTS,
             node_type: NodeType::Output,
             label: Some(label.to_string()),
+            activation: Activation::Tanh,
         });
     }
     for i in BRAIN_HIDDEN_START..BRAIN_HIDDEN_END {
@@ -26,6 +28,7 @@ pub fn create_brain_random_with_rng<R: Rng>(rng: &mut R) -> Brain {
             id: i,
             node_type: NodeType::Hidden,
             label: None,
+            activation: Activation::Tanh,
         });
     }
 
@@ -67,11 +70,82 @@ pub fn create_brain_random_with_rng<R: Rng>(rng: &mut R) -> Brain {
         fast_forward_order: Vec::new(),
         incoming_flat: Vec::new(),
         incoming_offsets: Vec::new(),
+        wiring_hash: 0,
     };
     brain.initialize_node_idx_map();
     brain
 }
 
+/// Adapts a brain saved by a build with only `saved_inputs` sensors and
+/// `saved_outputs` outputs.
+///
+/// Outputs and the fixed hidden block move up by the number of missing
+/// nodes before them (split nodes keep their hashed ids), and the new
+/// sensors and outputs are added unconnected, so they stay silent until
+/// mutation wires them in. Brains with the full layout are left alone.
+pub fn pad_layout(brain: &mut Brain, saved_inputs: usize, saved_outputs: usize) {
+    if saved_inputs >= BRAIN_INPUTS && saved_outputs >= BRAIN_OUTPUTS {
+        return;
+    }
+    let input_shift = BRAIN_INPUTS.saturating_sub(saved_inputs);
+    let output_shift = input_shift + BRAIN_OUTPUTS.saturating_sub(saved_outputs);
+    let saved_hidden_start = saved_inputs + saved_outputs;
+    let saved_fixed_end = saved_hidden_start + (BRAIN_HIDDEN_END - BRAIN_HIDDEN_START);
+    let remap = |id: usize| {
+        if (saved_inputs..saved_hidden_start).contains(&id) {
+            id + input_shift
+        } else if (saved_hidden_start..saved_fixed_end).contains(&id) {
+            id + output_shift
+        } else {
+            id
+        }
+    };
+
+    for node in &mut brain.nodes {
+        node.id = remap(node.id);
+    }
+    for conn in &mut brain.connections {
+        conn.from = remap(conn.from);
+        conn.to = remap(conn.to);
+        conn.innovation = get_innovation_id(conn.from, conn.to);
+    }
+    let new_inputs = (saved_inputs..BRAIN_INPUTS).map(|id| Node {
+        id,
+        node_type: NodeType::Input,
+        label: Some(INPUT_LABELS[id].to_string()),
+        activation: Activation::Tanh,
+    });
+    let at = brain
+        .nodes
+        .iter()
+        .position(|n| n.node_type != NodeType::Input)
+        .unwrap_or(brain.nodes.len());
+    brain.nodes.splice(at..at, new_inputs);
+    let new_outputs = (saved_outputs..BRAIN_OUTPUTS).map(|i| Node {
+        id: BRAIN_INPUTS + i,
+        node_type: NodeType::Output,
+        label: Some(OUTPUT_LABELS[i].to_string()),
+        activation: Activation::Tanh,
+    });
+    let at = brain
+        .nodes
+        .iter()
+        .rposition(|n| matches!(n.node_type, NodeType::Input | NodeType::Output))
+        .map_or(0, |last| last + 1);
+    brain.nodes.splice(at..at, new_outputs);
+    brain.next_node_id = brain.next_node_id.max(BRAIN_HIDDEN_END);
+    brain.weight_deltas.clear();
+    brain.initialize_node_idx_map();
+}
+
+/// Pads a brain loaded from a genome of an older build to the current
+/// layout, see [`pad_layout`].
+pub fn pad_to_current(brain: &mut Brain) {
+    let count = |kind: NodeType| brain.nodes.iter().filter(|n| n.node_type == kind).count();
+    let (inputs, outputs) = (count(NodeType::Input), count(NodeType::Output));
+    pad_layout(brain, inputs, outputs);
+}
+
 pub fn get_innovation_id(from: usize, to: usize) -> usize {
     let h = (from as u64) << 32 | (to as u64);
     let mut hash = 0xcbf2_9ce4_8422_2325_u64;
@@ -109,6 +183,12 @@ pub fn create_genotype_random_with_rng<R: Rng>(rng: &mut R) -> Genotype {
         pairing_bias: 0.5,
         specialization_bias: [0.33, 0.33, 0.34],
         regulatory_rules: Vec::new(),
+        oviparity: 0.4,
+        clutch_size: 1,
+        parental_care: 0.2,
+        skepticism: 0.0,
+        death_avoidance: 0.0,
+        body_size: MIN_BODY_SIZE,
     }
 }
 
@@ -238,4 +318,33 @@ pub fn initialize_node_idx_map(brain: &mut Brain) {
     brain.fast_forward_order = fast_forward_order;
     brain.incoming_flat = incoming_flat;
     brain.incoming_offsets = incoming_offsets;
+    brain.wiring_hash = wiring_hash(brain);
+}
+
+/// FNV-1a fingerprint of everything the forward pass depends on except the
+/// weights: node ids and activations, evaluation order, forward wiring and
+/// the endpoints of recurrent connections. Batched inference groups brains
+/// by it.
+fn wiring_hash(brain: &Brain) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut add = |value: usize| hash = (hash ^ value as u64).wrapping_mul(0x0100_0000_01b3);
+    add(brain.nodes.len());
+    for node in &brain.nodes {
+        add(node.id);
+        add(node.activation as usize);
+    }
+    add(brain.fast_forward_order.len());
+    brain.fast_forward_order.iter().for_each(|&idx| add(idx));
+    brain
+        .incoming_offsets
+        .iter()
+        .for_each(|&offset| add(offset));
+    brain.incoming_flat.iter().for_each(|&(from, _)| add(from));
+    add(brain.recurrent_connections.len());
+    for &conn_idx in &brain.recurrent_connections {
+        let conn = &brain.connections[conn_idx];
+        add(conn.from);
+        add(conn.to);
+    }
+    hash
 }
@@ -28,7 +28,10 @@
 //! mutation_rate = 0.1
 //! ```
 
+use crate::environment::{Era, Season};
+use crate::pheromone::{PheromoneChannel, EMIT_OUTPUTS, MAX_CHANNELS, SENSED_INPUTS};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 
 /// World-level simulation configuration.
 ///
@@ -134,9 +137,31 @@ pub struct BrainConfig {
     pub learning_reinforcement: f32,
     pub coupling_spring_constant: f64,
     pub alpha_following_force: f64,
+    /// Connections weaker than this are removed by the pruning operator.
     pub pruning_threshold: f32,
+    /// Chance that a mutation also runs the pruning operator.
+    #[serde(default = "default_pruning_rate")]
+    pub pruning_rate: f32,
     pub max_nodes: usize,
     pub max_connections: usize,
+    /// Episodic memory slots per organism; 0 disables the memory.
+    #[serde(default = "default_memory_slots")]
+    pub memory_slots: usize,
+    /// Ticks after which a stored memory fades.
+    #[serde(default = "default_memory_horizon")]
+    pub memory_horizon: u64,
+}
+
+fn default_pruning_rate() -> f32 {
+    0.1
+}
+
+fn default_memory_slots() -> usize {
+    4
+}
+
+fn default_memory_horizon() -> u64 {
+    1000
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -159,49 +184,1374 @@ pub struct SocialConfig {
     pub energy_sharing_low_threshold: f32,
     pub defense_per_ally_reduction: f64,
     pub min_defense_multiplier: f64,
+    /// Energy spent on each deceptive food trail.
+    #[serde(default = "default_deception_cost")]
+    pub deception_cost: f64,
+    /// Strength of a deceptive food trail; eating lays a 0.3 trail.
+    #[serde(default = "default_deception_strength")]
+    pub deception_strength: f32,
+}
+
+fn default_deception_cost() -> f64 {
+    0.5
+}
+
+fn default_deception_strength() -> f32 {
+    0.3
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TerraformConfig {
+    pub dig_cost: f64,
+    pub build_cost: f64,
+    pub canal_cost: f64,
+    pub engineer_discount: f64,
+    pub nest_energy_req: f64,
+    pub dig_oxygen_cost: f64,
+    pub build_oxygen_cost: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VisualConfig {
+    /// Enable SDF/character density rendering for entities
+    pub sdf_rendering: bool,
+    /// Enable bloom/glow effects (simulated CRT)
+    pub glow_enabled: bool,
+    /// Bloom intensity (0.0-1.0)
+    pub glow_intensity: f32,
+    /// Enable terrain density variation
+    pub density_variation: bool,
+    /// Color saturation multiplier (0.0-2.0)
+    pub color_saturation: f32,
+    /// Glyph theme file; the built-in theme is used if it does not exist
+    #[serde(default = "default_glyph_theme")]
+    pub glyph_theme: String,
+    /// Tint the world by climate, era and time of day
+    #[serde(default = "default_environment_palette")]
+    pub environment_palette: bool,
+}
+
+fn default_glyph_theme() -> String {
+    "theme.toml".to_string()
+}
+
+fn default_environment_palette() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct EcosystemConfig {
+    pub carbon_emission_rate: f64,
+    pub sequestration_rate: f64,
+    pub oxygen_consumption_unit: f64,
+    pub soil_depletion_unit: f32,
+    pub corpse_fertility_mult: f32,
+    pub base_spawn_chance: f32,
+    pub nutrient_niche_multiplier: f32,
+    pub predation_energy_gain_fraction: f64,
+    pub predation_competition_scale: f64,
+    pub predation_min_efficiency: f64,
+    pub spawn_rate_limit_enabled: bool,
+    pub max_entities_per_tick: usize,
+    pub max_food_per_tick: usize,
+    pub solar_energy_rate: f64,
+}
+
+/// Circuit-breaker limits that halt a runaway simulation before it exhausts memory.
+///
+/// When any limit is exceeded the world stops advancing and emits a
+/// `LiveEvent::CircuitBreaker` naming the suspected configuration source.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct GuardrailConfig {
+    pub enabled: bool,
+    pub max_entities: usize,
+    pub max_food: usize,
+    pub max_births_per_tick: usize,
+}
+
+impl Default for GuardrailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entities: 50_000,
+            max_food: 100_000,
+            max_births_per_tick: 2_000,
+        }
+    }
+}
+
+/// Retention policy for dead lineage records.
+///
+/// Lineages referenced by a fossil or a legend are always kept. Other extinct
+/// lineages that produced fewer than `min_entities_produced` members are
+/// dropped at every fossil interval (after being folded into a sibling record
+/// when `merge_tiny_siblings` is set), and the remainder expire
+/// `world.lineage_extinction_age_threshold` ticks after their last death.
+/// Beyond `world.max_lineages` records, the least significant extinct
+/// lineages are evicted first.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LineageGcConfig {
+    pub min_entities_produced: usize,
+    pub merge_tiny_siblings: bool,
+}
+
+impl Default for LineageGcConfig {
+    fn default() -> Self {
+        Self {
+            min_entities_produced: 3,
+            merge_tiny_siblings: true,
+        }
+    }
+}
+
+/// Long-distance lineage migration.
+///
+/// A lineage whose collective threat memory reaches `threat_trigger` picks a
+/// more fertile area to move to. Every `replan_interval` ticks a route from
+/// the lineage centroid is requested from the path service (see
+/// [`PathfindingConfig`]), and members are pushed toward the next waypoint
+/// with `steering_force`. The migration ends once the centroid is
+/// within `arrival_radius` cells of the target.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct MigrationConfig {
+    pub enabled: bool,
+    pub threat_trigger: f32,
+    pub replan_interval: u64,
+    pub steering_force: f64,
+    pub arrival_radius: f64,
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threat_trigger: 2.0,
+            replan_interval: 20,
+            steering_force: 0.15,
+            arrival_radius: 4.0,
+        }
+    }
+}
+
+/// Soft collisions in dense crowds.
+///
+/// An entity with more than `comfort_density` neighbours within `radius`
+/// cells is pushed away from them by up to twice `separation_force`, growing
+/// with the excess. From `trample_density` neighbours on, it also loses
+/// `trample_damage` energy per tick for each neighbour over that limit.
+/// A zero force or damage disables that effect.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CrowdConfig {
+    pub radius: f64,
+    pub comfort_density: usize,
+    pub separation_force: f64,
+    pub trample_density: usize,
+    pub trample_damage: f64,
+}
+
+impl Default for CrowdConfig {
+    fn default() -> Self {
+        Self {
+            radius: 1.5,
+            comfort_density: 4,
+            separation_force: 0.15,
+            trample_density: 12,
+            trample_damage: 0.5,
+        }
+    }
+}
+
+/// Egg laying.
+///
+/// Parents whose oviparity gene is above 0.5 lay a clutch of eggs instead of
+/// giving live birth; the clutch shares the energy of one live-born
+/// offspring. An egg hatches after `incubation_ticks` ticks at the base rate.
+/// On a nest it incubates `nest_bonus` times as fast (half the extra next to
+/// one), and the climate speeds it up or slows it down. A stranger with a
+/// trophic potential of at least `predator_trophic_min` within
+/// `predation_radius` eats the egg and gains `energy_efficiency` of its energy.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct EggConfig {
+    pub enabled: bool,
+    pub incubation_ticks: u32,
+    pub nest_bonus: f32,
+    pub predator_trophic_min: f32,
+    pub predation_radius: f64,
+    pub energy_efficiency: f64,
+}
+
+impl Default for EggConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            incubation_ticks: 120,
+            nest_bonus: 1.5,
+            predator_trophic_min: 0.4,
+            predation_radius: 1.0,
+            energy_efficiency: 0.8,
+        }
+    }
+}
+
+/// Parental care.
+///
+/// A parent feeds each of its own offspring within `radius` for the first
+/// `parental_care` × `metabolism.maturity_age` ticks of the offspring's life,
+/// handing over up to `provision_rate` energy per juvenile per tick. Feeding
+/// stops while the parent is below `reserve_fraction` of its maximum energy.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CareConfig {
+    pub enabled: bool,
+    pub radius: f64,
+    pub provision_rate: f64,
+    pub reserve_fraction: f64,
+}
+
+impl Default for CareConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: 3.0,
+            provision_rate: 1.0,
+            reserve_fraction: 0.5,
+        }
+    }
+}
+
+/// Grooming of infected neighbours.
+///
+/// An entity whose `Share` output is above `intent_threshold` grooms the
+/// nearest infected neighbour within `radius`, preferring its own tribe. Each
+/// grooming costs the groomer `energy_cost` and takes `relief_ticks` off the
+/// neighbour's infection.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct GroomingConfig {
+    pub enabled: bool,
+    pub radius: f64,
+    pub intent_threshold: f32,
+    pub energy_cost: f64,
+    pub relief_ticks: u32,
+}
+
+impl Default for GroomingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: 2.0,
+            intent_threshold: 0.5,
+            energy_cost: 1.0,
+            relief_ticks: 10,
+        }
+    }
+}
+
+/// Dominance contests within a lineage.
+///
+/// An entity whose `Aggro` output is above `challenge_threshold` challenges
+/// the nearest lineage mate standing above it within `radius`. Both pay
+/// `contest_cost` and the higher rank score takes the higher standing.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct DominanceConfig {
+    pub enabled: bool,
+    pub radius: f64,
+    pub challenge_threshold: f32,
+    pub contest_cost: f64,
+}
+
+impl Default for DominanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: 2.0,
+            challenge_threshold: 0.8,
+            contest_cost: 0.5,
+        }
+    }
+}
+
+/// Diplomacy between lineages (see [`crate::diplomacy`]).
+///
+/// Each attack across a pair of lineages costs their standing
+/// `predation_penalty`; a bond across them earns `bond_bonus` and energy
+/// handed over earns `gift_bonus_per_energy` per unit, at most `bond_bonus`
+/// a gift. Standing drifts back toward zero by `decay` per tick. Pairs at or
+/// below `war_threshold` go to war and at or above `alliance_threshold` ally.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct DiplomacyConfig {
+    pub enabled: bool,
+    pub predation_penalty: f32,
+    pub bond_bonus: f32,
+    pub gift_bonus_per_energy: f32,
+    pub decay: f32,
+    pub war_threshold: f32,
+    pub alliance_threshold: f32,
+}
+
+impl Default for DiplomacyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            predation_penalty: 0.05,
+            bond_bonus: 0.05,
+            gift_bonus_per_energy: 0.002,
+            decay: 0.001,
+            war_threshold: -0.5,
+            alliance_threshold: 0.6,
+        }
+    }
+}
+
+/// Monuments raised by civilised lineages (see [`crate::monument`]).
+///
+/// An engineer of a lineage at `min_civilization_level` or above, standing on
+/// one of its lineage's outposts with more than twice `energy_cost`, spends
+/// `energy_cost` to raise a monument there. A lineage keeps at most
+/// `max_per_lineage` standing, none within `min_spacing` cells of another.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct MonumentConfig {
+    pub enabled: bool,
+    pub min_civilization_level: u32,
+    pub energy_cost: f64,
+    pub max_per_lineage: usize,
+    pub min_spacing: f64,
+}
+
+impl Default for MonumentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_civilization_level: 2,
+            energy_cost: 200.0,
+            max_per_lineage: 3,
+            min_spacing: 12.0,
+        }
+    }
+}
+
+/// Death scent left by infection victims.
+///
+/// An entity that dies infected leaves `deposit` of death pheromone where it
+/// fell. Living entities are pushed away from rising scent, sampled `radius`
+/// cells out, by `avoidance_force` times their `death_avoidance` gene.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct DeathScentConfig {
+    pub enabled: bool,
+    pub deposit: f32,
+    pub radius: f64,
+    pub avoidance_force: f64,
+}
+
+impl Default for DeathScentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            deposit: 1.0,
+            radius: 3.0,
+            avoidance_force: 0.5,
+        }
+    }
+}
+
+/// Pheromone channels (see [`crate::pheromone`]).
+///
+/// The grid keeps one layer per entry of `channels`, each with its own decay
+/// and diffusion. Food trails and death scent are laid on the channels named
+/// `food` and `death`. `emit` names the channel each of the brain's two Emit
+/// outputs deposits on, and `sensed` the channels read by its four pheromone
+/// inputs (Phero, Tribe, SA, SB); outputs and inputs past the end of these
+/// lists are unused.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PheromoneConfig {
+    pub channels: Vec<PheromoneChannel>,
+    pub emit: Vec<String>,
+    pub sensed: Vec<String>,
+}
+
+impl Default for PheromoneConfig {
+    fn default() -> Self {
+        Self {
+            channels: PheromoneChannel::defaults(),
+            emit: vec!["signal_a".to_string(), "signal_b".to_string()],
+            sensed: ["food", "danger", "signal_a", "signal_b"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+/// Sound propagation (see [`crate::sound`]).
+///
+/// With `occlusion` on, each cell passes on only a share of the sound
+/// reaching it: `wall_transmission` for walls, `mountain_transmission` for
+/// mountains and all of it elsewhere, so ridges and walls muffle calls from
+/// the far side. Sound made on a cell is not damped there.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SoundConfig {
+    pub occlusion: bool,
+    pub wall_transmission: f32,
+    pub mountain_transmission: f32,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        Self {
+            occlusion: false,
+            wall_transmission: 0.05,
+            mountain_transmission: 0.4,
+        }
+    }
+}
+
+/// Named biome regions (see [`crate::biome`]).
+///
+/// Every `interval` ticks the terrain is reclassified into biomes and
+/// contiguous stretches of one biome are labelled as regions. Stretches
+/// smaller than `min_region_cells` are left unnamed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct BiomeConfig {
+    pub interval: u64,
+    pub min_region_cells: usize,
+}
+
+impl Default for BiomeConfig {
+    fn default() -> Self {
+        Self {
+            interval: 200,
+            min_region_cells: 16,
+        }
+    }
+}
+
+/// Roads worn by repeated movement (see [`crate::road`]).
+///
+/// Each moving entity wears `wear_per_step` into its cell per tick, up to
+/// `max_wear`; wear fades by `decay` per tick. The owning lineage moves up to
+/// `speed_bonus` faster on a fully worn road, and roads worn to
+/// `link_threshold` link outposts into a power grid.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct RoadConfig {
+    pub enabled: bool,
+    pub wear_per_step: f32,
+    pub decay: f32,
+    pub max_wear: f32,
+    pub speed_bonus: f64,
+    pub link_threshold: f32,
+}
+
+impl Default for RoadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            wear_per_step: 0.02,
+            decay: 0.001,
+            max_wear: 1.0,
+            speed_bonus: 0.3,
+            link_threshold: 0.5,
+        }
+    }
+}
+
+/// Mutation heat map (see [`crate::mutation_heat`]).
+///
+/// Each birth deposits the newborn's genetic distance from its parent into
+/// its cell; deposits fade by half every `half_life` ticks.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct MutationHeatConfig {
+    pub half_life: f32,
+}
+
+impl Default for MutationHeatConfig {
+    fn default() -> Self {
+        Self { half_life: 300.0 }
+    }
+}
+
+/// Anomaly detection on population metrics (see [`crate::anomaly`]).
+///
+/// Every `sample_interval` ticks, population, biomass and death rate are
+/// compared against their moving averages (smoothing factor `alpha`); a
+/// sample `z_threshold` standard deviations off is narrated and bookmarked.
+/// The first `warmup_samples` samples only train the averages, and a flagged
+/// metric is quiet for the next `cooldown_samples` samples.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AnomalyConfig {
+    pub enabled: bool,
+    pub sample_interval: u64,
+    pub alpha: f64,
+    pub z_threshold: f64,
+    pub warmup_samples: u32,
+    pub cooldown_samples: u32,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sample_interval: 50,
+            alpha: 0.1,
+            z_threshold: 4.0,
+            warmup_samples: 10,
+            cooldown_samples: 10,
+        }
+    }
+}
+
+/// Population and food forecasts (see [`crate::forecast`]).
+///
+/// Every `sample_interval` ticks the population, food count and soil
+/// fertility are sampled; the last `window` samples are fitted and projected
+/// `horizon` ticks ahead. A famine is forecast when projected food falls
+/// below `famine_food_per_head` items per living entity.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ForecastConfig {
+    pub sample_interval: u64,
+    pub window: usize,
+    pub horizon: u64,
+    pub famine_food_per_head: f64,
+}
+
+impl Default for ForecastConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval: 20,
+            window: 60,
+            horizon: 500,
+            famine_food_per_head: 0.25,
+        }
+    }
+}
+
+/// Brain-selected flocking.
+///
+/// Each tick, flee, wander, separation, cohesion and alignment forces are
+/// weighted by the brain outputs (see
+/// [`crate::systems::action::steering::SteeringWeights`]) and their sum,
+/// scaled by `flocking_force`, is added to the velocity. Zero disables it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SteeringConfig {
+    pub flocking_force: f64,
+}
+
+impl Default for SteeringConfig {
+    fn default() -> Self {
+        Self {
+            flocking_force: 0.05,
+        }
+    }
+}
+
+/// Budget of the shared path service.
+///
+/// At most `queries_per_tick` queued path queries are planned per tick, and a
+/// query only starts while at least `max_expansions` of the
+/// `expansions_per_tick` cell budget is left; a single query never expands
+/// more than `max_expansions` cells. Up to `cache_capacity` finished paths
+/// are kept until the terrain they cross changes.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PathfindingConfig {
+    pub queries_per_tick: usize,
+    pub expansions_per_tick: usize,
+    pub max_expansions: usize,
+    pub cache_capacity: usize,
+}
+
+impl Default for PathfindingConfig {
+    fn default() -> Self {
+        Self {
+            queries_per_tick: 8,
+            expansions_per_tick: 16_000,
+            max_expansions: 4_000,
+            cache_capacity: 256,
+        }
+    }
+}
+
+/// How food is stored in the world.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FoodRepresentation {
+    /// One ECS entity per food item.
+    #[default]
+    Entities,
+    /// A per-cell density field; cheaper at very high food counts.
+    Field,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct FoodConfig {
+    pub representation: FoodRepresentation,
+}
+
+/// Evolving plants, see [`crate::flora`].
+///
+/// Every tick each plant seeds a fertile cell within `spread_radius` with
+/// probability `seed_chance` scaled by its regrowth gene, the fertility of
+/// the target cell and `1 - toxicity * toxicity_cost`. Seedlings pay
+/// `metabolism.food_energy_cost` like wild food, count against
+/// `world.max_food`, and inherit their parent's genome with each gene
+/// mutated by up to `mutation_amount` at rate `mutation_rate`. Eating a plant
+/// costs `toxin_damage` energy per unit of toxicity, scaled by how far the
+/// plant's nutrient type is from the eater's niche. Plants only evolve while
+/// food is stored as entities.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct FloraConfig {
+    pub enabled: bool,
+    pub seed_chance: f32,
+    pub spread_radius: u16,
+    pub min_fertility: f32,
+    pub mutation_rate: f32,
+    pub mutation_amount: f32,
+    pub toxicity_cost: f32,
+    pub toxin_damage: f64,
+}
+
+impl Default for FloraConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            seed_chance: 0.002,
+            spread_radius: 2,
+            min_fertility: 0.3,
+            mutation_rate: 0.1,
+            mutation_amount: 0.05,
+            toxicity_cost: 0.8,
+            toxin_damage: 60.0,
+        }
+    }
+}
+
+/// Body plans, see [`crate::body`].
+///
+/// An organism's body size multiplies its energy capacity, its metabolic
+/// costs by `size ^ metabolic_exponent` and its top speed by
+/// `size ^ -speed_exponent`. Predators win fights in proportion to their
+/// size over their prey's, and cannot take prey more than `max_prey_ratio`
+/// times their own size.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct BodyConfig {
+    pub metabolic_exponent: f64,
+    pub speed_exponent: f64,
+    pub max_prey_ratio: f64,
+}
+
+impl Default for BodyConfig {
+    fn default() -> Self {
+        Self {
+            metabolic_exponent: 1.5,
+            speed_exponent: 0.5,
+            max_prey_ratio: 1.5,
+        }
+    }
+}
+
+/// Optional behavior analytics.
+///
+/// With `record_interactions` set, the world tallies interaction commands
+/// (kills, shares, births, eats, builds, digs, bonds, grooms) every tick and
+/// publishes their mean over the last `interaction_window` ticks in the
+/// population stats. It also records who groomed whom and, every
+/// `graph_export_interval` ticks (0 disables it), writes that network to
+/// `interaction_graph.dot` and `interaction_graph.csv` in the log directory.
+/// The energy ledger of a tracked entity keeps its last `energy_ledger_ticks`
+/// ticks of entries, its route trail its last `trail_length` positions.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AnalyticsConfig {
+    pub record_interactions: bool,
+    pub interaction_window: usize,
+    pub energy_ledger_ticks: usize,
+    pub trail_length: usize,
+    pub graph_export_interval: u64,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            record_interactions: false,
+            interaction_window: 100,
+            energy_ledger_ticks: 50,
+            trail_length: 100,
+            graph_export_interval: 1000,
+        }
+    }
+}
+
+/// Periodic full-genome dumps for offline analysis.
+///
+/// Every `genome_interval` ticks (0 disables the archive) all living genotypes
+/// are written, gzip-compressed, to `dir` and listed in its `index.jsonl`. A
+/// dump can reseed a world with `--reseed-from-archive <TICK>`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ArchiveConfig {
+    pub genome_interval: u64,
+    pub dir: String,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            genome_interval: 0,
+            dir: "genomes".to_string(),
+        }
+    }
+}
+
+/// Fitness that ranks the survivors carried into a new world by
+/// `new --continue-from <SAVE> --survivors top:<N>`.
+///
+/// An organism scores `age_weight` per tick lived, `offspring_weight` per
+/// child, `energy_weight` per unit of its peak energy and `generation_weight`
+/// per generation; the defaults are the Hall of Fame's score.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ContinuityConfig {
+    pub age_weight: f64,
+    pub offspring_weight: f64,
+    pub energy_weight: f64,
+    pub generation_weight: f64,
+}
+
+impl Default for ContinuityConfig {
+    fn default() -> Self {
+        Self {
+            age_weight: 0.5,
+            offspring_weight: 10.0,
+            energy_weight: 0.2,
+            generation_weight: 0.0,
+        }
+    }
+}
+
+/// Rotation and disk quota for the logs directory.
+///
+/// `live.jsonl` is rotated once it reaches `max_file_mb` or, when
+/// `max_age_hours` is non-zero, once it is that old (0 disables either). The
+/// rotated file is zstd-compressed to `live-<timestamp>.jsonl.zst`. While the
+/// directory holds more than `quota_mb` (0 for no quota), rotated files are
+/// deleted oldest first. The TUI warns once usage reaches `warn_at` of the
+/// quota.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LogsConfig {
+    pub max_file_mb: u64,
+    pub max_age_hours: u64,
+    pub quota_mb: u64,
+    pub warn_at: f32,
+    pub zstd_level: i32,
+}
+
+impl Default for LogsConfig {
+    fn default() -> Self {
+        Self {
+            max_file_mb: 64,
+            max_age_hours: 0,
+            quota_mb: 2048,
+            warn_at: 0.9,
+            zstd_level: 3,
+        }
+    }
+}
+
+/// Trial runs of marketplace genomes before they are released.
+///
+/// `founders` copies of the genome are dropped into a `width` x `height`
+/// world of `natives` organisms and run for `ticks` ticks next to a control
+/// world without them.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SandboxConfig {
+    pub ticks: u64,
+    pub founders: usize,
+    pub natives: usize,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            ticks: 500,
+            founders: 5,
+            natives: 40,
+            width: 60,
+            height: 30,
+        }
+    }
+}
+
+/// Rule changes applied while an era is active. Each field scales the
+/// matching base setting; 1.0 leaves it alone.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct EraModifiers {
+    /// Scales `evolution.mutation_rate`.
+    pub mutation_scale: f32,
+    /// Scales `world.disaster_chance`.
+    pub disaster_frequency: f32,
+    /// Scales how often food spawns.
+    pub food_richness: f32,
+}
+
+impl Default for EraModifiers {
+    fn default() -> Self {
+        Self {
+            mutation_scale: 1.0,
+            disaster_frequency: 1.0,
+            food_richness: 1.0,
+        }
+    }
+}
+
+impl EraModifiers {
+    fn new(mutation_scale: f32, disaster_frequency: f32, food_richness: f32) -> Self {
+        Self {
+            mutation_scale,
+            disaster_frequency,
+            food_richness,
+        }
+    }
+}
+
+/// Modifier sets of the world's eras, see [`Era`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ErasConfig {
+    pub primordial: EraModifiers,
+    pub expansion: EraModifiers,
+    pub strife: EraModifiers,
+    pub enlightenment: EraModifiers,
+}
+
+impl Default for ErasConfig {
+    fn default() -> Self {
+        Self {
+            primordial: EraModifiers::default(),
+            expansion: EraModifiers::new(1.2, 1.0, 1.2),
+            strife: EraModifiers::new(1.0, 2.0, 0.7),
+            enlightenment: EraModifiers::new(0.6, 0.5, 1.0),
+        }
+    }
+}
+
+impl ErasConfig {
+    pub fn modifiers(&self, era: Era) -> EraModifiers {
+        match era {
+            Era::Primordial => self.primordial,
+            Era::Expansion => self.expansion,
+            Era::Strife => self.strife,
+            Era::Enlightenment => self.enlightenment,
+        }
+    }
+
+    fn all(&self) -> [EraModifiers; 4] {
+        [
+            self.primordial,
+            self.expansion,
+            self.strife,
+            self.enlightenment,
+        ]
+    }
+}
+
+/// How a season shapes the world. `food`, `metabolism`, `migration_pressure`
+/// and `fertility_regrowth` scale the matching base rates (1.0 leaves them
+/// alone); `temperature` shifts the climate, in units of 10% CPU load.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct SeasonModifiers {
+    /// Scales how often food spawns.
+    pub food: f64,
+    /// Scales metabolic costs.
+    pub metabolism: f64,
+    /// Warms (positive) or cools (negative) the climate.
+    pub temperature: f64,
+    /// Scales the threat that sets lineages migrating; above 1.0 they
+    /// leave sooner.
+    pub migration_pressure: f64,
+    /// Scales how fast depleted soil recovers its fertility.
+    pub fertility_regrowth: f64,
+}
+
+impl Default for SeasonModifiers {
+    fn default() -> Self {
+        Self {
+            food: 1.0,
+            metabolism: 1.0,
+            temperature: 0.0,
+            migration_pressure: 1.0,
+            fertility_regrowth: 1.0,
+        }
+    }
+}
+
+impl SeasonModifiers {
+    fn new(
+        food: f64,
+        metabolism: f64,
+        temperature: f64,
+        migration_pressure: f64,
+        fertility_regrowth: f64,
+    ) -> Self {
+        Self {
+            food,
+            metabolism,
+            temperature,
+            migration_pressure,
+            fertility_regrowth,
+        }
+    }
+
+    /// Blend from `self` towards `next`, `t` in `0.0..=1.0`.
+    #[must_use]
+    pub fn lerp(&self, next: &Self, t: f64) -> Self {
+        let mix = |a: f64, b: f64| a + (b - a) * t;
+        Self {
+            food: mix(self.food, next.food),
+            metabolism: mix(self.metabolism, next.metabolism),
+            temperature: mix(self.temperature, next.temperature),
+            migration_pressure: mix(self.migration_pressure, next.migration_pressure),
+            fertility_regrowth: mix(self.fertility_regrowth, next.fertility_regrowth),
+        }
+    }
+}
+
+/// The day/night cycle.
+///
+/// A day lasts `day_length` ticks, the first half of it lit. Organisms sense
+/// the light through their `Light` brain input, and at night their metabolism
+/// is scaled by `night_metabolism`. While disabled it is always noon.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CircadianConfig {
+    pub enabled: bool,
+    pub day_length: u64,
+    pub night_metabolism: f64,
+}
+
+impl Default for CircadianConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            day_length: 2000,
+            night_metabolism: 0.6,
+        }
+    }
+}
+
+/// Water on the map and in organisms.
+///
+/// Every organism loses `thirst` of its hydration per tick (scaled like
+/// metabolism) and drinks up to `drink` from the cell it stands on while the
+/// cell holds at least `min_drinkable`. Rivers and oases refill to full every
+/// tick, deserts lose `desert_drain` a tick and every other cell seeps back
+/// towards its moisture at `seepage`. An organism whose hydration runs out
+/// dies of dehydration.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct HydrationConfig {
+    pub enabled: bool,
+    pub thirst: f32,
+    pub drink: f32,
+    pub min_drinkable: f32,
+    pub seepage: f32,
+    pub desert_drain: f32,
+}
+
+impl Default for HydrationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            thirst: 0.0005,
+            drink: 0.05,
+            min_drinkable: 0.2,
+            seepage: 0.01,
+            desert_drain: 0.02,
+        }
+    }
+}
+
+/// The yearly cycle of seasons, see [`Season`].
+///
+/// Each season lasts `duration` ticks; over the last `transition` ticks of
+/// one season its modifiers blend smoothly into the next one's.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SeasonsConfig {
+    pub enabled: bool,
+    pub duration: u64,
+    pub transition: u64,
+    pub spring: SeasonModifiers,
+    pub summer: SeasonModifiers,
+    #[serde(alias = "autumn")]
+    pub fall: SeasonModifiers,
+    pub winter: SeasonModifiers,
+}
+
+impl Default for SeasonsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            duration: 10000,
+            transition: 1000,
+            spring: SeasonModifiers::new(1.5, 0.8, 0.0, 1.0, 1.25),
+            summer: SeasonModifiers::new(1.0, 1.2, 1.0, 1.0, 1.0),
+            fall: SeasonModifiers::new(1.2, 1.0, -0.5, 1.5, 0.75),
+            winter: SeasonModifiers::new(0.5, 1.5, -1.5, 1.25, 0.4),
+        }
+    }
+}
+
+impl SeasonsConfig {
+    pub fn modifiers(&self, season: Season) -> SeasonModifiers {
+        match season {
+            Season::Spring => self.spring,
+            Season::Summer => self.summer,
+            Season::Fall => self.fall,
+            Season::Winter => self.winter,
+        }
+    }
+
+    fn all(&self) -> [SeasonModifiers; 4] {
+        [self.spring, self.summer, self.fall, self.winter]
+    }
+}
+
+/// Drop-in directory for injecting genomes and scenario events into a
+/// running world.
+///
+/// While `enabled` and `dir` exists, files placed in it are applied on the
+/// next tick: `*.dna` holds a genotype hex string to spawn, `*.json` one
+/// scenario event or an array of them. Handled files move to `processed/` or
+/// `failed/` and each result is appended to `results.jsonl`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct InboxConfig {
+    pub enabled: bool,
+    pub dir: String,
+}
+
+impl Default for InboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: "inbox".to_string(),
+        }
+    }
+}
+
+/// HTTP inspector of headless runs (`--mode headless`).
+///
+/// While `enabled`, the run is served on `bind`: `GET /api/status` and
+/// `GET /api/stats` for dashboards, and `POST /api/command` applying one
+/// scenario event or an array of them, like an inbox `*.json` file. Every
+/// request carries one of `tokens` as a Bearer token; `read` tokens cannot
+/// send commands. Each address may make `requests_per_minute` requests, and a
+/// `control` token at most its `command_quota` commands per run (0 for no
+/// limit).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct InspectorConfig {
+    pub enabled: bool,
+    pub bind: String,
+    pub requests_per_minute: u32,
+    pub tokens: Vec<InspectorToken>,
+}
+
+impl Default for InspectorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: "127.0.0.1:7878".to_string(),
+            requests_per_minute: 120,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+/// A client of the inspector, named in logs and rate limits.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InspectorToken {
+    pub name: String,
+    pub token: String,
+    pub scope: InspectorScope,
+    #[serde(default)]
+    pub command_quota: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum InspectorScope {
+    /// Status and statistics only.
+    #[default]
+    Read,
+    /// Also scenario commands.
+    Control,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct TerraformConfig {
-    pub dig_cost: f64,
-    pub build_cost: f64,
-    pub canal_cost: f64,
-    pub engineer_discount: f64,
-    pub nest_energy_req: f64,
-    pub dig_oxygen_cost: f64,
-    pub build_oxygen_cost: f64,
+/// Autonomous screensaver mode (`--mode screensaver`).
+///
+/// Each run starts from the next seed in `seeds` and is restarted after
+/// extinction or, when `restart_after_ticks` is non-zero, after that many
+/// ticks. A `gallery_share` of runs instead replay a seed drawn from the
+/// seed gallery (see [`GalleryConfig`]), when it has any. The view cycles
+/// through `views` (view mode indices, see the `1`-`8` keys) every
+/// `view_rotation_secs` seconds; 0 keeps the first view. With
+/// `suppress_file_writes` set, no saves, logs or history are written.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ScreensaverConfig {
+    pub restart_after_ticks: u64,
+    pub view_rotation_secs: u64,
+    pub views: Vec<u8>,
+    pub seeds: Vec<u64>,
+    pub gallery_share: f64,
+    pub suppress_file_writes: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct VisualConfig {
-    /// Enable SDF/character density rendering for entities
-    pub sdf_rendering: bool,
-    /// Enable bloom/glow effects (simulated CRT)
-    pub glow_enabled: bool,
-    /// Bloom intensity (0.0-1.0)
-    pub glow_intensity: f32,
-    /// Enable terrain density variation
-    pub density_variation: bool,
-    /// Color saturation multiplier (0.0-2.0)
-    pub color_saturation: f32,
+impl Default for ScreensaverConfig {
+    fn default() -> Self {
+        Self {
+            restart_after_ticks: 20_000,
+            view_rotation_secs: 30,
+            views: vec![0, 1, 2, 4],
+            seeds: vec![42, 1337, 2718, 31415, 8675309],
+            gallery_share: 0.5,
+            suppress_file_writes: true,
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct EcosystemConfig {
-    pub carbon_emission_rate: f64,
-    pub sequestration_rate: f64,
-    pub oxygen_consumption_unit: f64,
-    pub soil_depletion_unit: f32,
-    pub corpse_fertility_mult: f32,
-    pub base_spawn_chance: f32,
-    pub nutrient_niche_multiplier: f32,
-    pub predation_energy_gain_fraction: f64,
-    pub predation_competition_scale: f64,
-    pub predation_min_efficiency: f64,
-    pub spawn_rate_limit_enabled: bool,
-    pub max_entities_per_tick: usize,
-    pub max_food_per_tick: usize,
-    pub solar_energy_rate: f64,
+/// Narrator behind the Silicon Scribe (`llm` feature).
+///
+/// `heuristic` narrates macro events from fixed templates. `llm` sends them
+/// to the OpenAI-compatible chat completions API at `base_url` as `model`,
+/// authenticating with `api_key` (or `PRIMORDIUM_LLM_API_KEY` when empty),
+/// and falls back to the templates when no reply arrives within
+/// `timeout_ms`. Replies are capped at `max_tokens`.
+///
+/// While `chronicle` is set, every narration is also appended to
+/// `chronicle.jsonl` in the run's log directory, so the archeology view can
+/// replay the whole story of a long run rather than the last 100 entries.
+///
+/// Narrations at least as severe as `event_log_severity` are also shown in
+/// the TUI event log. During busy stretches `digest` folds them into one
+/// summary per window.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct NarratorConfig {
+    pub backend: NarratorBackend,
+    pub base_url: String,
+    pub model: String,
+    pub api_key: String,
+    pub timeout_ms: u64,
+    pub max_tokens: u32,
+    pub chronicle: bool,
+    pub event_log_severity: f32,
+    pub digest: DigestConfig,
+}
+
+impl Default for NarratorConfig {
+    fn default() -> Self {
+        Self {
+            backend: NarratorBackend::Heuristic,
+            base_url: "https://api.openai.com/v1".to_string(),
+            model: "gpt-4o-mini".to_string(),
+            api_key: String::new(),
+            timeout_ms: 10_000,
+            max_tokens: 120,
+            chronicle: true,
+            event_log_severity: 0.5,
+            digest: DigestConfig::default(),
+        }
+    }
+}
+
+/// Digest mode of the narrator.
+///
+/// While `enabled`, once `enter_events` macro events are observed within
+/// `window` ticks the observer stops narrating them one by one and instead
+/// narrates a single summary of each `window` ticks of events. It returns to
+/// narrating events singly once fewer than `exit_events` happen in a window.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct DigestConfig {
+    pub enabled: bool,
+    pub window: u64,
+    pub enter_events: usize,
+    pub exit_events: usize,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: 500,
+            enter_events: 8,
+            exit_events: 3,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NarratorBackend {
+    #[default]
+    Heuristic,
+    Llm,
+}
+
+/// Gallery of interesting seeds (see `primordium_io::seed_gallery`).
+///
+/// While `enabled`, every run is watched for speciations, wars and
+/// civilization levels, sampled every `sample_interval` ticks. Runs of at
+/// least `min_ticks` ticks are scored when they end and the best `capacity`
+/// seeds are kept in the JSON file at `path`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct GalleryConfig {
+    pub enabled: bool,
+    pub path: String,
+    pub capacity: usize,
+    pub min_ticks: u64,
+    pub sample_interval: u64,
+}
+
+impl Default for GalleryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "seed_gallery.json".to_string(),
+            capacity: 20,
+            min_ticks: 2_000,
+            sample_interval: 25,
+        }
+    }
+}
+
+/// Panel shown in the TUI sidebar.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SidebarPanel {
+    /// Whatever panel is toggled on (brain, ancestry, archeology, ...).
+    #[default]
+    Auto,
+    Brain,
+    Lineage,
+    Network,
+}
+
+/// Sizes of the TUI panes around the map, in rows (the sidebar in columns).
+/// A size of 0 hides the pane; the map takes the remaining space.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct PaneLayout {
+    pub name: String,
+    pub stats: u16,
+    pub trends: u16,
+    pub chronicle: u16,
+    pub sidebar: u16,
+    /// Keeps this panel in the sidebar even when no panel is toggled on.
+    pub sidebar_panel: SidebarPanel,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            name: "Standard".to_string(),
+            stats: 6,
+            trends: 4,
+            chronicle: 7,
+            sidebar: 45,
+            sidebar_panel: SidebarPanel::Auto,
+        }
+    }
+}
+
+/// TUI pane layouts.
+///
+/// `Tab` cycles through `presets`. A layout edited with `U` is kept for the
+/// current view mode only and saved to `views`, keyed by view mode index.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LayoutConfig {
+    pub presets: Vec<PaneLayout>,
+    pub views: BTreeMap<String, PaneLayout>,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            presets: vec![
+                PaneLayout::default(),
+                PaneLayout {
+                    name: "Immersive".to_string(),
+                    stats: 1,
+                    trends: 0,
+                    chronicle: 0,
+                    ..PaneLayout::default()
+                },
+                PaneLayout {
+                    name: "Expert".to_string(),
+                    stats: 2,
+                    trends: 2,
+                    chronicle: 0,
+                    ..PaneLayout::default()
+                },
+            ],
+            views: BTreeMap::new(),
+        }
+    }
+}
+
+/// Worker thread pools.
+///
+/// The simulation runs on a dedicated pool of `sim_threads` workers (0 uses
+/// every logical core); background work (IO, networking, history queries)
+/// runs on the global pool of `io_threads` workers. With `pin_workers` set,
+/// simulation workers are pinned to consecutive cores starting at
+/// `first_core`. Pools are built once at startup.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ThreadingConfig {
+    pub sim_threads: usize,
+    pub io_threads: usize,
+    pub pin_workers: bool,
+    pub first_core: usize,
+}
+
+impl Default for ThreadingConfig {
+    fn default() -> Self {
+        Self {
+            sim_threads: 0,
+            io_threads: 2,
+            pin_workers: false,
+            first_core: 0,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -214,6 +1564,84 @@ pub struct AppConfig {
     pub terraform: TerraformConfig,
     pub ecosystem: EcosystemConfig,
     pub visual: VisualConfig,
+    #[serde(default)]
+    pub guardrail: GuardrailConfig,
+    #[serde(default)]
+    pub lineage_gc: LineageGcConfig,
+    #[serde(default)]
+    pub migration: MigrationConfig,
+    #[serde(default)]
+    pub pathfinding: PathfindingConfig,
+    #[serde(default)]
+    pub crowd: CrowdConfig,
+    #[serde(default)]
+    pub steering: SteeringConfig,
+    #[serde(default)]
+    pub eggs: EggConfig,
+    #[serde(default)]
+    pub care: CareConfig,
+    #[serde(default)]
+    pub grooming: GroomingConfig,
+    #[serde(default)]
+    pub dominance: DominanceConfig,
+    #[serde(default)]
+    pub diplomacy: DiplomacyConfig,
+    #[serde(default)]
+    pub monuments: MonumentConfig,
+    #[serde(default)]
+    pub death_scent: DeathScentConfig,
+    #[serde(default)]
+    pub pheromone: PheromoneConfig,
+    #[serde(default)]
+    pub sound: SoundConfig,
+    #[serde(default)]
+    pub roads: RoadConfig,
+    #[serde(default)]
+    pub mutation_heat: MutationHeatConfig,
+    #[serde(default)]
+    pub biomes: BiomeConfig,
+    #[serde(default)]
+    pub anomaly: AnomalyConfig,
+    #[serde(default)]
+    pub forecast: ForecastConfig,
+    #[serde(default)]
+    pub food: FoodConfig,
+    #[serde(default)]
+    pub analytics: AnalyticsConfig,
+    #[serde(default)]
+    pub archive: ArchiveConfig,
+    #[serde(default)]
+    pub continuity: ContinuityConfig,
+    #[serde(default)]
+    pub logs: LogsConfig,
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    #[serde(default)]
+    pub eras: ErasConfig,
+    #[serde(default)]
+    pub seasons: SeasonsConfig,
+    #[serde(default)]
+    pub circadian: CircadianConfig,
+    #[serde(default)]
+    pub hydration: HydrationConfig,
+    #[serde(default)]
+    pub flora: FloraConfig,
+    #[serde(default)]
+    pub body: BodyConfig,
+    #[serde(default)]
+    pub inbox: InboxConfig,
+    #[serde(default)]
+    pub inspector: InspectorConfig,
+    #[serde(default)]
+    pub threading: ThreadingConfig,
+    #[serde(default)]
+    pub screensaver: ScreensaverConfig,
+    #[serde(default)]
+    pub gallery: GalleryConfig,
+    #[serde(default)]
+    pub narrator: NarratorConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
     pub target_fps: u64,
     pub game_mode: GameMode,
 }
@@ -278,8 +1706,11 @@ impl Default for AppConfig {
                 coupling_spring_constant: 0.05,
                 alpha_following_force: 0.02,
                 pruning_threshold: 0.01,
+                pruning_rate: default_pruning_rate(),
                 max_nodes: 128,
                 max_connections: 512,
+                memory_slots: default_memory_slots(),
+                memory_horizon: default_memory_horizon(),
             },
             social: SocialConfig {
                 rank_weights: [0.3, 0.3, 0.1, 0.3],
@@ -300,6 +1731,8 @@ impl Default for AppConfig {
                 energy_sharing_low_threshold: 0.5,
                 defense_per_ally_reduction: 0.15,
                 min_defense_multiplier: 0.4,
+                deception_cost: default_deception_cost(),
+                deception_strength: default_deception_strength(),
             },
             terraform: TerraformConfig {
                 dig_cost: 10.0,
@@ -332,138 +1765,562 @@ impl Default for AppConfig {
                 glow_intensity: 0.5,
                 density_variation: false,
                 color_saturation: 1.0,
+                glyph_theme: default_glyph_theme(),
+                environment_palette: default_environment_palette(),
             },
+            guardrail: GuardrailConfig::default(),
+            lineage_gc: LineageGcConfig::default(),
+            migration: MigrationConfig::default(),
+            pathfinding: PathfindingConfig::default(),
+            crowd: CrowdConfig::default(),
+            steering: SteeringConfig::default(),
+            eggs: EggConfig::default(),
+            care: CareConfig::default(),
+            grooming: GroomingConfig::default(),
+            dominance: DominanceConfig::default(),
+            diplomacy: DiplomacyConfig::default(),
+            monuments: MonumentConfig::default(),
+            death_scent: DeathScentConfig::default(),
+            pheromone: PheromoneConfig::default(),
+            sound: SoundConfig::default(),
+            roads: RoadConfig::default(),
+            mutation_heat: MutationHeatConfig::default(),
+            biomes: BiomeConfig::default(),
+            anomaly: AnomalyConfig::default(),
+            forecast: ForecastConfig::default(),
+            food: FoodConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            archive: ArchiveConfig::default(),
+            continuity: ContinuityConfig::default(),
+            logs: LogsConfig::default(),
+            sandbox: SandboxConfig::default(),
+            eras: ErasConfig::default(),
+            seasons: SeasonsConfig::default(),
+            circadian: CircadianConfig::default(),
+            hydration: HydrationConfig::default(),
+            flora: FloraConfig::default(),
+            body: BodyConfig::default(),
+            inbox: InboxConfig::default(),
+            inspector: InspectorConfig::default(),
+            threading: ThreadingConfig::default(),
+            screensaver: ScreensaverConfig::default(),
+            gallery: GalleryConfig::default(),
+            narrator: NarratorConfig::default(),
+            layout: LayoutConfig::default(),
             target_fps: 60,
             game_mode: GameMode::Standard,
         }
-    }
-}
+    }
+}
+
+impl AppConfig {
+    /// Validates all configuration parameters.
+    ///
+    /// Returns `Ok(())` if all parameters are valid, or `Err` with a description
+    /// of the first validation failure.
+    ///
+    /// # Validation Rules
+    /// - World dimensions must be positive and reasonable (< 1000)
+    /// - Population and food counts must be within sensible bounds
+    /// - Rates and multipliers must be non-negative
+    /// - Percentages must be in valid range [0.0, 1.0] where applicable
+    pub fn validate(&self) -> anyhow::Result<()> {
+        // World validation
+        anyhow::ensure!(self.world.width > 0, "World width must be positive");
+        anyhow::ensure!(self.world.width <= 1000, "World width too large (max 1000)");
+        anyhow::ensure!(self.world.height > 0, "World height must be positive");
+        anyhow::ensure!(
+            self.world.height <= 1000,
+            "World height too large (max 1000)"
+        );
+        anyhow::ensure!(
+            self.world.initial_population <= 10000,
+            "Initial population too large (max 10000)"
+        );
+        anyhow::ensure!(
+            self.world.max_food <= 10000,
+            "Max food too large (max 10000)"
+        );
+        anyhow::ensure!(
+            self.world.repulsion_force >= 0.0,
+            "Repulsion force must be non-negative"
+        );
+
+        // Metabolism validation
+        anyhow::ensure!(
+            self.metabolism.base_move_cost >= 0.0,
+            "Base move cost must be non-negative"
+        );
+        anyhow::ensure!(
+            self.metabolism.base_idle_cost >= 0.0,
+            "Base idle cost must be non-negative"
+        );
+        anyhow::ensure!(
+            self.metabolism.crowding_cost >= 0.0,
+            "Crowding cost must be non-negative"
+        );
+        anyhow::ensure!(
+            self.metabolism.reproduction_threshold > 0.0,
+            "Reproduction threshold must be positive"
+        );
+        anyhow::ensure!(
+            self.metabolism.food_value > 0.0,
+            "Food value must be positive"
+        );
+        anyhow::ensure!(
+            self.metabolism.food_energy_cost >= 0.0,
+            "Food energy cost must be non-negative"
+        );
+
+        // Evolution validation
+        anyhow::ensure!(
+            self.evolution.mutation_rate >= 0.0 && self.evolution.mutation_rate <= 1.0,
+            "Mutation rate must be in [0.0, 1.0]"
+        );
+        anyhow::ensure!(
+            self.evolution.mutation_amount >= 0.0,
+            "Mutation amount must be non-negative"
+        );
+        anyhow::ensure!(
+            self.evolution.drift_rate >= 0.0 && self.evolution.drift_rate <= 1.0,
+            "Drift rate must be in [0.0, 1.0]"
+        );
+
+        // Brain validation
+        anyhow::ensure!(
+            self.brain.hidden_node_cost >= 0.0,
+            "Hidden node cost must be non-negative"
+        );
+        anyhow::ensure!(
+            self.brain.connection_cost >= 0.0,
+            "Connection cost must be non-negative"
+        );
+        anyhow::ensure!(
+            self.brain.pruning_threshold >= 0.0,
+            "Pruning threshold must be non-negative"
+        );
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.brain.pruning_rate),
+            "Pruning rate must be in [0.0, 1.0]"
+        );
+        anyhow::ensure!(
+            self.brain.learning_rate_max >= 0.0 && self.brain.learning_rate_max <= 1.0,
+            "Learning rate max must be in [0.0, 1.0]"
+        );
+        anyhow::ensure!(
+            self.brain.memory_slots <= 64,
+            "Memory slots too large (max 64)"
+        );
+        anyhow::ensure!(
+            self.brain.memory_horizon > 0,
+            "Memory horizon must be positive"
+        );
+        anyhow::ensure!(
+            self.social.deception_cost >= 0.0,
+            "Deception cost must be non-negative"
+        );
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.social.deception_strength),
+            "Deception strength must be in [0.0, 1.0]"
+        );
+
+        // Social validation
+        anyhow::ensure!(
+            self.social.sharing_threshold >= 0.0 && self.social.sharing_threshold <= 1.0,
+            "Sharing threshold must be in [0.0, 1.0]"
+        );
+        anyhow::ensure!(
+            self.social.sharing_fraction >= 0.0 && self.social.sharing_fraction <= 1.0,
+            "Sharing fraction must be in [0.0, 1.0]"
+        );
+        anyhow::ensure!(
+            self.social.aggression_threshold >= 0.0 && self.social.aggression_threshold <= 1.0,
+            "Aggression threshold must be in [0.0, 1.0]"
+        );
+
+        // Ecosystem validation
+        anyhow::ensure!(
+            self.ecosystem.carbon_emission_rate >= 0.0,
+            "Carbon emission rate must be non-negative"
+        );
+        anyhow::ensure!(
+            self.ecosystem.sequestration_rate >= 0.0,
+            "Sequestration rate must be non-negative"
+        );
+        anyhow::ensure!(
+            self.ecosystem.base_spawn_chance >= 0.0 && self.ecosystem.base_spawn_chance <= 1.0,
+            "Base spawn chance must be in [0.0, 1.0]"
+        );
+        anyhow::ensure!(
+            self.ecosystem.max_entities_per_tick > 0,
+            "Max entities per tick must be positive"
+        );
+        anyhow::ensure!(
+            self.ecosystem.max_food_per_tick > 0,
+            "Max food per tick must be positive"
+        );
+        anyhow::ensure!(
+            self.ecosystem.solar_energy_rate >= 0.0,
+            "Solar energy rate must be non-negative"
+        );
+
+        // Guardrail validation
+        anyhow::ensure!(
+            self.guardrail.max_entities > 0,
+            "Guardrail max entities must be positive"
+        );
+        anyhow::ensure!(
+            self.guardrail.max_food > 0,
+            "Guardrail max food must be positive"
+        );
+        anyhow::ensure!(
+            self.guardrail.max_births_per_tick > 0,
+            "Guardrail max births per tick must be positive"
+        );
+
+        anyhow::ensure!(
+            self.migration.replan_interval > 0,
+            "Migration replan interval must be positive"
+        );
+        anyhow::ensure!(
+            self.migration.steering_force >= 0.0,
+            "Migration steering force must be non-negative"
+        );
+        anyhow::ensure!(
+            self.migration.arrival_radius > 0.0,
+            "Migration arrival radius must be positive"
+        );
+
+        anyhow::ensure!(
+            self.pathfinding.queries_per_tick > 0 && self.pathfinding.max_expansions > 0,
+            "Pathfinding queries per tick and max expansions must be positive"
+        );
+        anyhow::ensure!(
+            self.pathfinding.expansions_per_tick >= self.pathfinding.max_expansions,
+            "Pathfinding expansions per tick must be at least max expansions"
+        );
+
+        anyhow::ensure!(self.crowd.radius > 0.0, "Crowd radius must be positive");
+        anyhow::ensure!(
+            self.crowd.separation_force >= 0.0 && self.crowd.trample_damage >= 0.0,
+            "Crowd separation force and trample damage must be non-negative"
+        );
+        anyhow::ensure!(
+            self.crowd.trample_density > self.crowd.comfort_density,
+            "Crowd trample density must exceed comfort density"
+        );
+        anyhow::ensure!(
+            self.steering.flocking_force >= 0.0,
+            "Steering flocking force must be non-negative"
+        );
+
+        anyhow::ensure!(
+            self.eggs.incubation_ticks > 0,
+            "Egg incubation ticks must be positive"
+        );
+        anyhow::ensure!(
+            self.eggs.nest_bonus >= 1.0,
+            "Egg nest bonus must be at least 1.0"
+        );
+        anyhow::ensure!(
+            self.eggs.predation_radius > 0.0,
+            "Egg predation radius must be positive"
+        );
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.eggs.energy_efficiency)
+                && (0.0..=1.0).contains(&self.eggs.predator_trophic_min),
+            "Egg energy efficiency and predator trophic minimum must be between 0.0 and 1.0"
+        );
+
+        anyhow::ensure!(
+            self.care.radius > 0.0 && self.care.provision_rate >= 0.0,
+            "Care radius must be positive and provision rate non-negative"
+        );
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.care.reserve_fraction),
+            "Care reserve fraction must be between 0.0 and 1.0"
+        );
+
+        anyhow::ensure!(
+            self.grooming.radius > 0.0 && self.grooming.energy_cost >= 0.0,
+            "Grooming radius must be positive and energy cost non-negative"
+        );
+
+        anyhow::ensure!(
+            self.dominance.radius > 0.0 && self.dominance.contest_cost >= 0.0,
+            "Dominance radius must be positive and contest cost non-negative"
+        );
+
+        anyhow::ensure!(
+            (-1.0..0.0).contains(&self.diplomacy.war_threshold)
+                && self.diplomacy.alliance_threshold > 0.0
+                && self.diplomacy.alliance_threshold <= 1.0,
+            "Diplomacy war threshold must be in [-1, 0) and alliance threshold in (0, 1]"
+        );
+        anyhow::ensure!(
+            self.diplomacy.predation_penalty >= 0.0
+                && self.diplomacy.bond_bonus >= 0.0
+                && self.diplomacy.gift_bonus_per_energy >= 0.0
+                && self.diplomacy.decay >= 0.0,
+            "Diplomacy penalties, bonuses and decay must be non-negative"
+        );
+        anyhow::ensure!(
+            self.monuments.energy_cost >= 0.0 && self.monuments.min_spacing >= 0.0,
+            "Monument energy cost and spacing must be non-negative"
+        );
+
+        anyhow::ensure!(
+            self.death_scent.radius > 0.0
+                && self.death_scent.deposit >= 0.0
+                && self.death_scent.avoidance_force >= 0.0,
+            "Death scent radius must be positive and deposit and force non-negative"
+        );
+
+        // Pheromone validation
+        anyhow::ensure!(
+            (1..=MAX_CHANNELS).contains(&self.pheromone.channels.len()),
+            "Pheromone needs between 1 and {} channels",
+            MAX_CHANNELS
+        );
+        let mut channel_names = HashSet::new();
+        for channel in &self.pheromone.channels {
+            anyhow::ensure!(
+                !channel.name.is_empty() && channel_names.insert(channel.name.as_str()),
+                "Pheromone channel names must be non-empty and unique"
+            );
+            anyhow::ensure!(
+                channel.decay > 0.0
+                    && channel.decay <= 1.0
+                    && (0.0..=1.0).contains(&channel.diffusion),
+                "Pheromone channel `{}` needs decay in (0, 1] and diffusion in [0, 1]",
+                channel.name
+            );
+        }
+        anyhow::ensure!(
+            self.pheromone.emit.len() <= EMIT_OUTPUTS
+                && self.pheromone.sensed.len() <= SENSED_INPUTS,
+            "Pheromone maps at most {} emit outputs and {} sensed inputs",
+            EMIT_OUTPUTS,
+            SENSED_INPUTS
+        );
+        for name in self.pheromone.emit.iter().chain(&self.pheromone.sensed) {
+            anyhow::ensure!(
+                channel_names.contains(name.as_str()),
+                "Pheromone mapping names unknown channel `{}`",
+                name
+            );
+        }
 
-impl AppConfig {
-    /// Validates all configuration parameters.
-    ///
-    /// Returns `Ok(())` if all parameters are valid, or `Err` with a description
-    /// of the first validation failure.
-    ///
-    /// # Validation Rules
-    /// - World dimensions must be positive and reasonable (< 1000)
-    /// - Population and food counts must be within sensible bounds
-    /// - Rates and multipliers must be non-negative
-    /// - Percentages must be in valid range [0.0, 1.0] where applicable
-    pub fn validate(&self) -> anyhow::Result<()> {
-        // World validation
-        anyhow::ensure!(self.world.width > 0, "World width must be positive");
-        anyhow::ensure!(self.world.width <= 1000, "World width too large (max 1000)");
-        anyhow::ensure!(self.world.height > 0, "World height must be positive");
+        // Sound validation
         anyhow::ensure!(
-            self.world.height <= 1000,
-            "World height too large (max 1000)"
+            (0.0..=1.0).contains(&self.sound.wall_transmission)
+                && (0.0..=1.0).contains(&self.sound.mountain_transmission),
+            "Sound transmissions must be between 0 and 1"
         );
+
         anyhow::ensure!(
-            self.world.initial_population <= 10000,
-            "Initial population too large (max 10000)"
+            self.roads.max_wear > 0.0
+                && self.roads.wear_per_step >= 0.0
+                && self.roads.decay >= 0.0
+                && self.roads.speed_bonus >= 0.0,
+            "Road max_wear must be positive and wear, decay and speed bonus non-negative"
         );
+
         anyhow::ensure!(
-            self.world.max_food <= 10000,
-            "Max food too large (max 10000)"
+            self.mutation_heat.half_life > 0.0,
+            "Mutation heat half_life must be positive"
         );
+
         anyhow::ensure!(
-            self.world.repulsion_force >= 0.0,
-            "Repulsion force must be non-negative"
+            self.biomes.interval > 0 && self.biomes.min_region_cells > 0,
+            "Biome interval and min_region_cells must be positive"
         );
 
-        // Metabolism validation
         anyhow::ensure!(
-            self.metabolism.base_move_cost >= 0.0,
-            "Base move cost must be non-negative"
+            self.anomaly.sample_interval > 0
+                && self.anomaly.alpha > 0.0
+                && self.anomaly.alpha <= 1.0
+                && self.anomaly.z_threshold > 0.0,
+            "Anomaly sample_interval and z_threshold must be positive and alpha in (0, 1]"
         );
+
         anyhow::ensure!(
-            self.metabolism.base_idle_cost >= 0.0,
-            "Base idle cost must be non-negative"
+            self.forecast.sample_interval > 0
+                && self.forecast.window >= crate::forecast::MIN_SAMPLES
+                && self.forecast.horizon > 0
+                && self.forecast.famine_food_per_head >= 0.0,
+            "Forecast sample_interval and horizon must be positive, window at least {} and famine_food_per_head non-negative",
+            crate::forecast::MIN_SAMPLES
         );
+
         anyhow::ensure!(
-            self.metabolism.crowding_cost >= 0.0,
-            "Crowding cost must be non-negative"
+            self.analytics.interaction_window > 0,
+            "Analytics interaction window must be positive"
         );
         anyhow::ensure!(
-            self.metabolism.reproduction_threshold > 0.0,
-            "Reproduction threshold must be positive"
+            self.analytics.energy_ledger_ticks > 0,
+            "Analytics energy ledger ticks must be positive"
         );
         anyhow::ensure!(
-            self.metabolism.food_value > 0.0,
-            "Food value must be positive"
+            self.analytics.trail_length > 0,
+            "Analytics trail length must be positive"
         );
+
         anyhow::ensure!(
-            self.metabolism.food_energy_cost >= 0.0,
-            "Food energy cost must be non-negative"
+            self.logs.warn_at > 0.0 && self.logs.warn_at <= 1.0,
+            "Logs warn_at must be in (0, 1]"
+        );
+        anyhow::ensure!(
+            (1..=22).contains(&self.logs.zstd_level),
+            "Logs zstd_level must be 1-22"
         );
 
-        // Evolution validation
         anyhow::ensure!(
-            self.evolution.mutation_rate >= 0.0 && self.evolution.mutation_rate <= 1.0,
-            "Mutation rate must be in [0.0, 1.0]"
+            self.sandbox.ticks > 0 && self.sandbox.founders > 0,
+            "Sandbox ticks and founders must be positive"
         );
         anyhow::ensure!(
-            self.evolution.mutation_amount >= 0.0,
-            "Mutation amount must be non-negative"
+            self.sandbox.width >= 10 && self.sandbox.height >= 10,
+            "Sandbox world must be at least 10x10"
         );
+
         anyhow::ensure!(
-            self.evolution.drift_rate >= 0.0 && self.evolution.drift_rate <= 1.0,
-            "Drift rate must be in [0.0, 1.0]"
+            self.eras.all().iter().all(|m| m.mutation_scale >= 0.0
+                && m.disaster_frequency >= 0.0
+                && m.food_richness >= 0.0),
+            "Era modifiers must not be negative"
         );
 
-        // Brain validation
         anyhow::ensure!(
-            self.brain.hidden_node_cost >= 0.0,
-            "Hidden node cost must be non-negative"
+            self.seasons.duration > 0 && self.seasons.transition <= self.seasons.duration,
+            "Seasons must last at least one tick and no shorter than their transition"
         );
         anyhow::ensure!(
-            self.brain.connection_cost >= 0.0,
-            "Connection cost must be non-negative"
+            self.seasons.all().iter().all(|m| m.food >= 0.0
+                && m.metabolism >= 0.0
+                && m.migration_pressure > 0.0
+                && m.fertility_regrowth >= 0.0),
+            "Season modifiers must not be negative and migration pressure must be positive"
         );
         anyhow::ensure!(
-            self.brain.learning_rate_max >= 0.0 && self.brain.learning_rate_max <= 1.0,
-            "Learning rate max must be in [0.0, 1.0]"
+            self.circadian.day_length >= 2 && self.circadian.night_metabolism >= 0.0,
+            "Circadian day_length must be at least 2 and night_metabolism not negative"
+        );
+        let h = &self.hydration;
+        anyhow::ensure!(
+            h.thirst >= 0.0
+                && h.drink >= 0.0
+                && h.seepage >= 0.0
+                && h.desert_drain >= 0.0
+                && (0.0..=1.0).contains(&h.min_drinkable),
+            "Hydration rates must not be negative and min_drinkable must be within 0.0-1.0"
+        );
+        let f = &self.flora;
+        anyhow::ensure!(
+            f.seed_chance >= 0.0
+                && f.spread_radius > 0
+                && (0.0..=1.0).contains(&f.min_fertility)
+                && (0.0..=1.0).contains(&f.mutation_rate)
+                && f.mutation_amount >= 0.0
+                && f.toxicity_cost >= 0.0
+                && f.toxin_damage >= 0.0,
+            "Flora rates must not be negative, spread_radius must be positive and min_fertility and mutation_rate must be within 0.0-1.0"
+        );
+        let b = &self.body;
+        anyhow::ensure!(
+            b.metabolic_exponent >= 0.0 && b.speed_exponent >= 0.0 && b.max_prey_ratio >= 1.0,
+            "Body exponents must not be negative and max_prey_ratio must be at least 1.0"
         );
 
-        // Social validation
         anyhow::ensure!(
-            self.social.sharing_threshold >= 0.0 && self.social.sharing_threshold <= 1.0,
-            "Sharing threshold must be in [0.0, 1.0]"
+            self.threading.io_threads > 0,
+            "Threading io threads must be positive"
+        );
+
+        anyhow::ensure!(
+            !self.screensaver.seeds.is_empty(),
+            "Screensaver needs at least one seed"
         );
         anyhow::ensure!(
-            self.social.sharing_fraction >= 0.0 && self.social.sharing_fraction <= 1.0,
-            "Sharing fraction must be in [0.0, 1.0]"
+            !self.screensaver.views.is_empty() && self.screensaver.views.iter().all(|&v| v < 8),
+            "Screensaver views must be a non-empty list of view modes 0-7"
         );
         anyhow::ensure!(
-            self.social.aggression_threshold >= 0.0 && self.social.aggression_threshold <= 1.0,
-            "Aggression threshold must be in [0.0, 1.0]"
+            (0.0..=1.0).contains(&self.screensaver.gallery_share),
+            "Screensaver gallery_share must be in [0, 1]"
         );
 
-        // Ecosystem validation
         anyhow::ensure!(
-            self.ecosystem.carbon_emission_rate >= 0.0,
-            "Carbon emission rate must be non-negative"
+            self.narrator.timeout_ms > 0 && self.narrator.max_tokens > 0,
+            "Narrator timeout_ms and max_tokens must be positive"
         );
         anyhow::ensure!(
-            self.ecosystem.sequestration_rate >= 0.0,
-            "Sequestration rate must be non-negative"
+            self.narrator.backend != NarratorBackend::Llm
+                || !(self.narrator.base_url.is_empty() || self.narrator.model.is_empty()),
+            "The llm narrator needs a base_url and a model"
         );
         anyhow::ensure!(
-            self.ecosystem.base_spawn_chance >= 0.0 && self.ecosystem.base_spawn_chance <= 1.0,
-            "Base spawn chance must be in [0.0, 1.0]"
+            (0.0..=1.0).contains(&self.narrator.event_log_severity),
+            "Narrator event_log_severity must be in [0, 1]"
         );
         anyhow::ensure!(
-            self.ecosystem.max_entities_per_tick > 0,
-            "Max entities per tick must be positive"
+            self.narrator.digest.window > 0
+                && self.narrator.digest.exit_events <= self.narrator.digest.enter_events,
+            "Narrator digest window must be positive and exit_events at most enter_events"
+        );
+
+        if self.inspector.enabled {
+            anyhow::ensure!(
+                self.inspector.bind.parse::<std::net::SocketAddr>().is_ok(),
+                "Inspector bind must be an address like 127.0.0.1:7878"
+            );
+            anyhow::ensure!(
+                self.inspector.requests_per_minute > 0,
+                "Inspector requests_per_minute must be positive"
+            );
+            anyhow::ensure!(
+                !self.inspector.tokens.is_empty(),
+                "The inspector needs at least one token"
+            );
+        }
+        let mut inspector_tokens = HashSet::new();
+        anyhow::ensure!(
+            self.inspector
+                .tokens
+                .iter()
+                .all(|t| !t.token.is_empty() && inspector_tokens.insert(t.token.as_str())),
+            "Inspector tokens must be non-empty and unique"
         );
+
         anyhow::ensure!(
-            self.ecosystem.max_food_per_tick > 0,
-            "Max food per tick must be positive"
+            self.gallery.capacity > 0 && self.gallery.sample_interval > 0,
+            "Gallery capacity and sample_interval must be positive"
         );
+
         anyhow::ensure!(
-            self.ecosystem.solar_energy_rate >= 0.0,
-            "Solar energy rate must be non-negative"
+            !self.layout.presets.is_empty(),
+            "Layout needs at least one preset"
+        );
+        anyhow::ensure!(
+            self.layout
+                .views
+                .keys()
+                .all(|k| k.parse::<u8>().is_ok_and(|v| v < 8)),
+            "Layout views must be keyed by view modes 0-7"
+        );
+
+        // Continuity validation
+        let continuity = &self.continuity;
+        anyhow::ensure!(
+            [
+                continuity.age_weight,
+                continuity.offspring_weight,
+                continuity.energy_weight,
+                continuity.generation_weight,
+            ]
+            .iter()
+            .all(|w| w.is_finite() && *w >= 0.0),
+            "Continuity fitness weights must be finite and non-negative"
         );
 
         // Target FPS validation
@@ -483,17 +2340,58 @@ impl AppConfig {
         Ok(config)
     }
 
+    /// Sets the value at a dotted `key` such as `world.max_food`, and
+    /// validates the result. `value` is a TOML literal (`500`, `true`,
+    /// `"field"`); anything that does not parse as one is taken as a string.
+    /// On error the configuration is left unchanged.
+    pub fn set_value(&mut self, key: &str, value: &str) -> anyhow::Result<()> {
+        let parsed = toml::from_str::<toml::Table>(&format!("v = {value}"))
+            .ok()
+            .and_then(|mut t| t.remove("v"))
+            .unwrap_or_else(|| toml::Value::String(value.to_string()));
+
+        let mut root = toml::Value::try_from(&*self)?;
+        let (path, field) = key.rsplit_once('.').unwrap_or(("", key));
+        let mut table = root
+            .as_table_mut()
+            .ok_or_else(|| anyhow::anyhow!("Config is not a table"))?;
+        for part in path.split('.').filter(|p| !p.is_empty()) {
+            table = table
+                .get_mut(part)
+                .and_then(toml::Value::as_table_mut)
+                .ok_or_else(|| anyhow::anyhow!("Unknown config section: {part}"))?;
+        }
+        if table.get(field).is_some_and(toml::Value::is_table) {
+            anyhow::bail!("{key} is a section, not a value");
+        }
+        table.insert(field.to_string(), parsed);
+
+        let config: Self = root.try_into()?;
+        // Unset optional fields are absent from the table, so a key is only
+        // known to exist once the new config serializes it back.
+        let written = toml::Value::try_from(&config)?;
+        if key
+            .split('.')
+            .try_fold(&written, |v, part| v.get(part))
+            .is_none()
+        {
+            anyhow::bail!("Unknown config key: {key}");
+        }
+        config.validate()?;
+        *self = config;
+        Ok(())
+    }
+
     #[must_use]
     pub fn fingerprint(&self) -> String {
-        use sha2::{Digest, Sha256};
-        let mut hasher = Sha256::new();
-        hasher.update(format!("{:?}", self.metabolism).as_bytes());
-        hasher.update(format!("{:?}", self.evolution).as_bytes());
-        hasher.update(format!("{:?}", self.brain).as_bytes());
-        hasher.update(format!("{:?}", self.social).as_bytes());
-        hasher.update(format!("{:?}", self.terraform).as_bytes());
-        hasher.update(format!("{:?}", self.ecosystem).as_bytes());
-        hex::encode(hasher.finalize())
+        primordium_net::fingerprint([
+            format!("{:?}", self.metabolism),
+            format!("{:?}", self.evolution),
+            format!("{:?}", self.brain),
+            format!("{:?}", self.social),
+            format!("{:?}", self.terraform),
+            format!("{:?}", self.ecosystem),
+        ])
     }
 }
 
@@ -564,6 +2462,182 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_invalid_guardrail_limit() {
+        let config = AppConfig {
+            guardrail: GuardrailConfig {
+                max_entities: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_interaction_window() {
+        let config = AppConfig {
+            analytics: AnalyticsConfig {
+                interaction_window: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_io_threads() {
+        let config = AppConfig {
+            threading: ThreadingConfig {
+                io_threads: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_llm_narrator_needs_a_model() {
+        let mut config = AppConfig::default();
+        config.narrator.backend = NarratorBackend::Llm;
+        assert!(config.validate().is_ok());
+        config.narrator.model.clear();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_screensaver_views() {
+        let config = AppConfig {
+            screensaver: ScreensaverConfig {
+                views: vec![0, 9],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_layout_views_parse_and_validate() {
+        let mut config: AppConfig = toml::from_str(&format!(
+            "{}\n[layout.views.6]\nname = \"Research\"\nchronicle = 0\nsidebar_panel = \"brain\"\n",
+            toml::to_string(&AppConfig::default()).unwrap()
+        ))
+        .unwrap();
+        let research = &config.layout.views["6"];
+        assert_eq!(research.sidebar_panel, SidebarPanel::Brain);
+        assert_eq!((research.stats, research.chronicle), (6, 0));
+        assert!(config.validate().is_ok());
+
+        config
+            .layout
+            .views
+            .insert("9".to_string(), PaneLayout::default());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_set_value_by_dotted_key() {
+        let mut config = AppConfig::default();
+        config.set_value("world.max_food", "123").unwrap();
+        assert_eq!(config.world.max_food, 123);
+        config.set_value("roads.enabled", "false").unwrap();
+        assert!(!config.roads.enabled);
+        config.set_value("world.seed", "7").unwrap();
+        assert_eq!(config.world.seed, Some(7));
+
+        assert!(config.set_value("world.no_such_key", "1").is_err());
+        assert!(config.set_value("world", "1").is_err());
+        assert!(config.set_value("brain.pruning_rate", "1.5").is_err());
+        assert_eq!(config.brain.pruning_rate, 0.1);
+    }
+
+    #[test]
+    fn test_missing_guardrail_section_uses_defaults() {
+        let mut value = toml::Value::try_from(AppConfig::default()).unwrap();
+        value.as_table_mut().unwrap().remove("guardrail");
+        let config: AppConfig = toml::from_str(&toml::to_string(&value).unwrap()).unwrap();
+        assert!(config.guardrail.enabled);
+        assert_eq!(config.guardrail.max_entities, 50_000);
+    }
+
+    #[test]
+    fn test_pruning_rate_defaults_and_validation() {
+        let mut value = toml::Value::try_from(AppConfig::default()).unwrap();
+        value["brain"]
+            .as_table_mut()
+            .unwrap()
+            .remove("pruning_rate");
+        let mut config: AppConfig = toml::from_str(&toml::to_string(&value).unwrap()).unwrap();
+        assert_eq!(config.brain.pruning_rate, 0.1);
+
+        config.brain.pruning_rate = 1.5;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_migration_validation() {
+        let mut config = AppConfig::default();
+        config.migration.replan_interval = 0;
+        assert!(config.validate().is_err());
+
+        let mut value = toml::Value::try_from(AppConfig::default()).unwrap();
+        value.as_table_mut().unwrap().insert(
+            "migration".into(),
+            toml::from_str("threat_trigger = 0.5").unwrap(),
+        );
+        let config: AppConfig = toml::from_str(&toml::to_string(&value).unwrap()).unwrap();
+        assert_eq!(config.migration.threat_trigger, 0.5);
+        assert_eq!(config.migration.replan_interval, 20);
+    }
+
+    #[test]
+    fn test_pathfinding_budget_must_fit_a_query() {
+        let mut config = AppConfig::default();
+        config.pathfinding.expansions_per_tick = config.pathfinding.max_expansions - 1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_crowd_trample_density_above_comfort() {
+        let mut config = AppConfig::default();
+        config.crowd.trample_density = config.crowd.comfort_density;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_negative_flocking_force_rejected() {
+        let mut config = AppConfig::default();
+        config.steering.flocking_force = -0.1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_egg_nest_bonus_cannot_slow_incubation() {
+        let mut config = AppConfig::default();
+        config.eggs.nest_bonus = 0.5;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_care_reserve_fraction_bounded() {
+        let mut config = AppConfig::default();
+        config.care.reserve_fraction = 1.5;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_food_representation_parses_lowercase() {
+        let config: FoodConfig = toml::from_str("representation = \"field\"").unwrap();
+        assert_eq!(config.representation, FoodRepresentation::Field);
+        assert_eq!(
+            FoodConfig::default().representation,
+            FoodRepresentation::Entities
+        );
+    }
+
     #[test]
     fn test_fingerprint_consistency() {
         let config1 = AppConfig::default();
@@ -0,0 +1,218 @@
+//! Lineage crests: small procedural emblems that tell lineages apart where
+//! colour alone does not.
+//!
+//! The lineage id fixes a crest's colours, how its shield is divided and a
+//! mirrored 5x5 pattern; the trophic potential of the lineage's
+//! representative genotype picks its charge (♣ for grazers, ♠ for hunters,
+//! ◆ for everything between). Lineages without a known genotype bear a
+//! plain ✧ until one is recorded. The same crest is drawn as a two-cell
+//! [`Crest::emblem`] in the terminal, as pixels on creature cards and as SVG
+//! by [`Crest::to_svg`].
+
+use primordium_data::Genotype;
+use uuid::Uuid;
+
+/// Potentials below this bear a grazer's charge.
+const GRAZER_MAX: f32 = 0.3;
+/// Potentials above this bear a hunter's charge.
+const HUNTER_MIN: f32 = 0.7;
+
+/// How the shield is split between the crest's two colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Division {
+    Plain,
+    /// Split left and right.
+    PerPale,
+    /// Split top and bottom.
+    PerFess,
+    /// Split diagonally from the top left.
+    PerBend,
+    Quarterly,
+}
+
+impl Division {
+    const ALL: [Division; 5] = [
+        Division::Plain,
+        Division::PerPale,
+        Division::PerFess,
+        Division::PerBend,
+        Division::Quarterly,
+    ];
+
+    /// Terminal cell drawn in the tincture on an accent background.
+    pub fn glyph(self) -> char {
+        match self {
+            Division::Plain => '█',
+            Division::PerPale => '▌',
+            Division::PerFess => '▀',
+            Division::PerBend => '◤',
+            Division::Quarterly => '▚',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charge {
+    Grazer,
+    Omnivore,
+    Hunter,
+    Unknown,
+}
+
+impl Charge {
+    fn of(trophic_potential: Option<f32>) -> Self {
+        match trophic_potential {
+            None => Charge::Unknown,
+            Some(tp) if tp < GRAZER_MAX => Charge::Grazer,
+            Some(tp) if tp > HUNTER_MIN => Charge::Hunter,
+            Some(_) => Charge::Omnivore,
+        }
+    }
+
+    pub fn glyph(self) -> char {
+        match self {
+            Charge::Grazer => '♣',
+            Charge::Omnivore => '◆',
+            Charge::Hunter => '♠',
+            Charge::Unknown => '✧',
+        }
+    }
+}
+
+/// A lineage's emblem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crest {
+    pub lineage_id: Uuid,
+    pub division: Division,
+    pub charge: Charge,
+    /// Main colour, bright enough to stand out on a dark background.
+    pub tincture: [u8; 3],
+    /// Second colour, dark enough to set off the tincture.
+    pub accent: [u8; 3],
+    /// Cells of the pattern, mirrored left to right.
+    pub pattern: [[bool; 5]; 5],
+}
+
+impl Crest {
+    /// The crest of `lineage_id`, charged from its representative genotype
+    /// if one is known.
+    pub fn new(lineage_id: Uuid, genotype: Option<&Genotype>) -> Self {
+        let b = lineage_id.as_bytes();
+        let bits = u16::from_le_bytes([b[0], b[1]]);
+        Self {
+            lineage_id,
+            division: Division::ALL[b[9] as usize % Division::ALL.len()],
+            charge: Charge::of(genotype.map(|g| g.trophic_potential)),
+            tincture: std::array::from_fn(|i| 96 + b[3 + i] % 160),
+            accent: std::array::from_fn(|i| 16 + b[6 + i] % 64),
+            pattern: std::array::from_fn(|row| {
+                std::array::from_fn(|col| bits >> (row * 3 + col.min(4 - col)) & 1 == 1)
+            }),
+        }
+    }
+
+    /// The crest of a genotype's own lineage.
+    pub fn of_genotype(genotype: &Genotype) -> Self {
+        Self::new(genotype.lineage_id, Some(genotype))
+    }
+
+    /// Two terminal cells: the divided field, then the charge. Draw the
+    /// field in the tincture on the accent and the charge in the tincture.
+    pub fn emblem(&self) -> [char; 2] {
+        [self.division.glyph(), self.charge.glyph()]
+    }
+
+    /// A shield of `size` pixels wide (and 1.2x as tall) as SVG.
+    pub fn to_svg(&self, size: u32) -> String {
+        const SHIELD: &str = "M5 5 H95 V60 Q95 100 50 117 Q5 100 5 60 Z";
+        let hex = |[r, g, b]: [u8; 3]| format!("#{r:02x}{g:02x}{b:02x}");
+        let (tincture, accent) = (hex(self.tincture), hex(self.accent));
+        let division = match self.division {
+            Division::Plain => "<rect width=\"100\" height=\"120\"/>".to_string(),
+            Division::PerPale => "<rect width=\"50\" height=\"120\"/>".to_string(),
+            Division::PerFess => "<rect width=\"100\" height=\"60\"/>".to_string(),
+            Division::PerBend => "<polygon points=\"0,0 100,0 100,120\"/>".to_string(),
+            Division::Quarterly => {
+                "<rect width=\"50\" height=\"60\"/><rect x=\"50\" y=\"60\" width=\"50\" height=\"60\"/>"
+                    .to_string()
+            }
+        };
+        let cells: String = self
+            .pattern
+            .iter()
+            .enumerate()
+            .flat_map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &on)| on)
+                    .map(move |(col, _)| {
+                        format!(
+                            "<rect x=\"{}\" y=\"{}\" width=\"6\" height=\"6\"/>",
+                            35 + col * 6,
+                            27 + row * 6
+                        )
+                    })
+            })
+            .collect();
+        format!(
+            concat!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 100 120\">\n",
+                "<title>Crest of lineage {id}</title>\n",
+                "<defs><clipPath id=\"shield\"><path d=\"{shield}\"/></clipPath></defs>\n",
+                "<g clip-path=\"url(#shield)\">\n",
+                "<rect width=\"100\" height=\"120\" fill=\"{accent}\"/>\n",
+                "<g fill=\"{tincture}\">{division}</g>\n",
+                "</g>\n",
+                "<circle cx=\"50\" cy=\"42\" r=\"21\" fill=\"{accent}\" stroke=\"{tincture}\" stroke-width=\"2\"/>\n",
+                "<g fill=\"{tincture}\">{cells}</g>\n",
+                "<text x=\"50\" y=\"92\" font-size=\"24\" text-anchor=\"middle\" fill=\"{tincture}\" stroke=\"{accent}\" stroke-width=\"1\">{charge}</text>\n",
+                "<path d=\"{shield}\" fill=\"none\" stroke=\"#202020\" stroke-width=\"3\"/>\n",
+                "</svg>\n"
+            ),
+            w = size,
+            h = size * 6 / 5,
+            id = self.lineage_id,
+            shield = SHIELD,
+            accent = accent,
+            tincture = tincture,
+            division = division,
+            cells = cells,
+            charge = self.charge.glyph(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crest_is_fixed_by_lineage_and_charged_by_diet() {
+        let id = Uuid::from_u128(0x1234_5678_9abc_def0_0fed_cba9_8765_4321);
+        let mut genotype = crate::brain::create_genotype_random_with_rng(
+            &mut <rand_chacha::ChaCha8Rng as rand::SeedableRng>::seed_from_u64(1),
+        );
+        genotype.lineage_id = id;
+        genotype.trophic_potential = 0.9;
+
+        let crest = Crest::of_genotype(&genotype);
+        assert_eq!(crest, Crest::of_genotype(&genotype));
+        assert_eq!(crest.emblem()[1], '♠');
+        assert_eq!(Crest::new(id, None).charge, Charge::Unknown);
+        assert_eq!(Crest::new(id, None).pattern, crest.pattern);
+        assert!(crest
+            .pattern
+            .iter()
+            .all(|row| row[0] == row[4] && row[1] == row[3]));
+        assert_ne!(
+            Crest::new(Uuid::from_u128(7), None).tincture,
+            crest.tincture
+        );
+
+        let svg = crest.to_svg(100);
+        assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(&id.to_string()));
+        assert!(svg.contains("♠"));
+    }
+}
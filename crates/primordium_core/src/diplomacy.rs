@@ -0,0 +1,302 @@
+//! Diplomacy between lineages.
+//!
+//! Every pair of lineages that has dealt with each other keeps a standing
+//! between -1 and 1. Predation across the pair lowers it; bonds and energy
+//! gifts raise it, and it drifts back toward zero by `diplomacy.decay` per
+//! tick. A pair whose standing falls to `diplomacy.war_threshold` goes to
+//! war and stays there until the standing recovers to half that threshold.
+//! Standing at or above `diplomacy.alliance_threshold` makes an alliance.
+//! Anything in between is a truce, which is also where strangers stand.
+//!
+//! At war, lineages attack each other whatever their tribe colours say;
+//! allies never do.
+
+use crate::config::DiplomacyConfig;
+use crate::interaction::InteractionCommand;
+use crate::snapshot::InternalEntitySnapshot;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stance {
+    War,
+    #[default]
+    Truce,
+    Alliance,
+}
+
+impl Stance {
+    /// Whether an attack on a member of the other lineage goes ahead, given
+    /// whether its colours mark it as another tribe.
+    pub fn permits_attack(self, foreign_colors: bool) -> bool {
+        match self {
+            Stance::War => true,
+            Stance::Truce => foreign_colors,
+            Stance::Alliance => false,
+        }
+    }
+}
+
+/// Where two lineages stand with each other; `a` sorts before `b`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Relation {
+    pub a: Uuid,
+    pub b: Uuid,
+    pub standing: f32,
+    pub stance: Stance,
+}
+
+/// A pair of lineages whose stance changed while settling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StanceChange {
+    pub a: Uuid,
+    pub b: Uuid,
+    pub from: Stance,
+    pub to: Stance,
+}
+
+/// Relations between every pair of lineages that has met.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<Relation>", into = "Vec<Relation>")]
+pub struct Diplomacy {
+    relations: HashMap<(Uuid, Uuid), Relation>,
+}
+
+impl From<Vec<Relation>> for Diplomacy {
+    fn from(relations: Vec<Relation>) -> Self {
+        Self {
+            relations: relations.into_iter().map(|r| ((r.a, r.b), r)).collect(),
+        }
+    }
+}
+
+impl From<Diplomacy> for Vec<Relation> {
+    fn from(diplomacy: Diplomacy) -> Self {
+        let mut relations: Vec<_> = diplomacy.relations.into_values().collect();
+        relations.sort_by_key(|r| (r.a, r.b));
+        relations
+    }
+}
+
+fn key(x: Uuid, y: Uuid) -> (Uuid, Uuid) {
+    if x < y {
+        (x, y)
+    } else {
+        (y, x)
+    }
+}
+
+impl Diplomacy {
+    /// Stance between two lineages. A lineage is allied with itself.
+    pub fn stance(&self, x: Uuid, y: Uuid) -> Stance {
+        if x == y {
+            return Stance::Alliance;
+        }
+        self.relations
+            .get(&key(x, y))
+            .map_or(Stance::Truce, |r| r.stance)
+    }
+
+    pub fn relation(&self, x: Uuid, y: Uuid) -> Option<&Relation> {
+        self.relations.get(&key(x, y))
+    }
+
+    /// Every relation, ordered by lineage pair.
+    pub fn relations(&self) -> Vec<&Relation> {
+        let mut relations: Vec<_> = self.relations.values().collect();
+        relations.sort_by_key(|r| (r.a, r.b));
+        relations
+    }
+
+    /// Number of pairs at `stance`.
+    pub fn count(&self, stance: Stance) -> usize {
+        self.relations
+            .values()
+            .filter(|r| r.stance == stance)
+            .count()
+    }
+
+    /// Shifts the standing between two lineages by `delta`. The stance
+    /// only changes when the pair is next [`settle`](Self::settle)d.
+    pub fn adjust(&mut self, x: Uuid, y: Uuid, delta: f32) {
+        if x == y || delta == 0.0 {
+            return;
+        }
+        let (a, b) = key(x, y);
+        let relation = self.relations.entry((a, b)).or_insert(Relation {
+            a,
+            b,
+            standing: 0.0,
+            stance: Stance::Truce,
+        });
+        relation.standing = (relation.standing + delta).clamp(-1.0, 1.0);
+    }
+
+    /// Takes the cross-lineage dealings out of one tick's interaction
+    /// commands: attacks, bonds, and energy handed over as a credit followed
+    /// by the matching debit.
+    pub fn observe(
+        &mut self,
+        commands: &[InteractionCommand],
+        snapshots: &[InternalEntitySnapshot],
+        config: &DiplomacyConfig,
+    ) {
+        let lineage = |idx: usize| snapshots.get(idx).map(|s| s.lineage_id);
+        for (i, cmd) in commands.iter().enumerate() {
+            match cmd {
+                InteractionCommand::Kill {
+                    target_idx,
+                    attacker_lineage,
+                    ..
+                } => {
+                    if let Some(victim) = lineage(*target_idx) {
+                        self.adjust(*attacker_lineage, victim, -config.predation_penalty);
+                    }
+                }
+                InteractionCommand::Bond {
+                    target_idx,
+                    partner_id,
+                } => {
+                    let partner = snapshots.iter().find(|s| s.id == *partner_id);
+                    if let (Some(own), Some(partner)) = (lineage(*target_idx), partner) {
+                        self.adjust(own, partner.lineage_id, config.bond_bonus);
+                    }
+                }
+                InteractionCommand::TransferEnergy {
+                    target_idx: receiver,
+                    amount,
+                } if *amount > 0.0 => {
+                    if let Some(InteractionCommand::TransferEnergy {
+                        target_idx: giver,
+                        amount: debit,
+                    }) = commands.get(i + 1)
+                    {
+                        if *debit == -*amount {
+                            if let (Some(to), Some(from)) = (lineage(*receiver), lineage(*giver)) {
+                                let gift = (*amount as f32 * config.gift_bonus_per_energy)
+                                    .min(config.bond_bonus);
+                                self.adjust(from, to, gift);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Lets every standing drift toward zero, forgets pairs with a lineage
+    /// for which `alive` is false, and moves pairs to their new stance.
+    pub fn settle(
+        &mut self,
+        config: &DiplomacyConfig,
+        alive: impl Fn(Uuid) -> bool,
+    ) -> Vec<StanceChange> {
+        self.relations.retain(|&(a, b), _| alive(a) && alive(b));
+        let mut changes = Vec::new();
+        for relation in self.relations.values_mut() {
+            let drift = relation.standing.abs().min(config.decay);
+            relation.standing -= drift * relation.standing.signum();
+
+            let to = if relation.standing <= config.war_threshold
+                || (relation.stance == Stance::War
+                    && relation.standing < config.war_threshold / 2.0)
+            {
+                Stance::War
+            } else if relation.standing >= config.alliance_threshold {
+                Stance::Alliance
+            } else {
+                Stance::Truce
+            };
+            if to != relation.stance {
+                changes.push(StanceChange {
+                    a: relation.a,
+                    b: relation.b,
+                    from: relation.stance,
+                    to,
+                });
+                relation.stance = to;
+            }
+        }
+        // Pairs at rest in a truce carry nothing worth keeping.
+        self.relations
+            .retain(|_, r| r.stance != Stance::Truce || r.standing != 0.0);
+        changes.sort_by_key(|c| (c.a, c.b));
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lineages() -> (Uuid, Uuid) {
+        (Uuid::from_u128(1), Uuid::from_u128(2))
+    }
+
+    #[test]
+    fn test_raids_lead_to_war_and_time_to_peace() {
+        let config = DiplomacyConfig::default();
+        let (x, y) = lineages();
+        let mut diplomacy = Diplomacy::default();
+
+        let mut declared = Vec::new();
+        for _ in 0..20 {
+            diplomacy.adjust(x, y, -config.predation_penalty);
+            declared.extend(diplomacy.settle(&config, |_| true));
+        }
+        assert_eq!(diplomacy.stance(y, x), Stance::War);
+        assert_eq!(declared.len(), 1);
+        assert_eq!(declared[0].to, Stance::War);
+
+        let mut peace = Vec::new();
+        while peace.is_empty() {
+            peace = diplomacy.settle(&config, |_| true);
+        }
+        assert_eq!(peace[0].from, Stance::War);
+        assert_eq!(peace[0].to, Stance::Truce);
+        assert!(diplomacy.relation(x, y).unwrap().standing > config.war_threshold);
+    }
+
+    #[test]
+    fn test_alliance_blocks_attacks_and_war_ignores_colors() {
+        let config = DiplomacyConfig::default();
+        let (x, y) = lineages();
+        let mut diplomacy = Diplomacy::default();
+        diplomacy.adjust(x, y, 1.0);
+        diplomacy.settle(&config, |_| true);
+
+        assert_eq!(diplomacy.stance(x, y), Stance::Alliance);
+        assert!(!diplomacy.stance(x, y).permits_attack(true));
+        assert!(Stance::War.permits_attack(false));
+        assert!(Stance::Truce.permits_attack(true));
+        assert!(!Stance::Truce.permits_attack(false));
+    }
+
+    #[test]
+    fn test_extinct_lineages_are_forgotten() {
+        let config = DiplomacyConfig::default();
+        let (x, y) = lineages();
+        let mut diplomacy = Diplomacy::default();
+        diplomacy.adjust(x, y, -1.0);
+        diplomacy.settle(&config, |_| true);
+        diplomacy.settle(&config, |id| id != y);
+
+        assert!(diplomacy.relation(x, y).is_none());
+        assert_eq!(diplomacy.stance(x, y), Stance::Truce);
+    }
+
+    #[test]
+    fn test_relations_round_trip_through_json() {
+        let config = DiplomacyConfig::default();
+        let (x, y) = lineages();
+        let mut diplomacy = Diplomacy::default();
+        diplomacy.adjust(x, y, -1.0);
+        diplomacy.settle(&config, |_| true);
+
+        let json = serde_json::to_string(&diplomacy).unwrap();
+        let back: Diplomacy = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.relations(), diplomacy.relations());
+    }
+}
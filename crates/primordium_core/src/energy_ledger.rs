@@ -0,0 +1,182 @@
+//! Per-entity energy ledger for debugging metabolism balance.
+//!
+//! While an entity is tracked the world records every energy change it
+//! undergoes, tagged with its source, and keeps the last `N` ticks of entries.
+//! Changes the world cannot attribute to a specific source (disease, brain
+//! upkeep, terraforming, civilization transfers, ...) are booked as
+//! [`EnergySource::Other`] so each tick's entries always sum to the observed
+//! energy difference.
+
+use crate::interaction::InteractionCommand;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EnergySource {
+    Movement,
+    Basal,
+    Food,
+    Sharing,
+    Predation,
+    Reproduction,
+    Trampling,
+    Other,
+}
+
+impl EnergySource {
+    pub const ALL: [EnergySource; 8] = [
+        EnergySource::Movement,
+        EnergySource::Basal,
+        EnergySource::Food,
+        EnergySource::Sharing,
+        EnergySource::Predation,
+        EnergySource::Reproduction,
+        EnergySource::Trampling,
+        EnergySource::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EnergySource::Movement => "Movement",
+            EnergySource::Basal => "Basal",
+            EnergySource::Food => "Food",
+            EnergySource::Sharing => "Sharing",
+            EnergySource::Predation => "Predation",
+            EnergySource::Reproduction => "Reproduction",
+            EnergySource::Trampling => "Trampling",
+            EnergySource::Other => "Other",
+        }
+    }
+
+    /// The source an interaction command's energy change is booked under.
+    pub fn of_command(cmd: &InteractionCommand) -> Self {
+        match cmd {
+            InteractionCommand::EatFood { .. } => EnergySource::Food,
+            InteractionCommand::TransferEnergy { .. } => EnergySource::Sharing,
+            InteractionCommand::Kill { .. } => EnergySource::Predation,
+            InteractionCommand::Birth { .. } => EnergySource::Reproduction,
+            _ => EnergySource::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub tick: u64,
+    pub source: EnergySource,
+    pub delta: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnergyLedger {
+    pub entity_id: Uuid,
+    pub ticks: usize,
+    entries: VecDeque<LedgerEntry>,
+    /// Energy at the start of the tick being recorded.
+    opening_energy: Option<f64>,
+    /// Sum of attributed deltas since `opening_energy` was taken.
+    attributed: f64,
+}
+
+impl EnergyLedger {
+    pub fn new(entity_id: Uuid, ticks: usize) -> Self {
+        Self {
+            entity_id,
+            ticks: ticks.max(1),
+            entries: VecDeque::new(),
+            opening_energy: None,
+            attributed: 0.0,
+        }
+    }
+
+    /// Marks the start of a tick with the entity's current energy.
+    pub fn open_tick(&mut self, energy: f64) {
+        self.opening_energy = Some(energy);
+        self.attributed = 0.0;
+    }
+
+    pub fn record(&mut self, tick: u64, source: EnergySource, delta: f64) {
+        if delta == 0.0 || !delta.is_finite() {
+            return;
+        }
+        self.attributed += delta;
+        self.entries.push_back(LedgerEntry {
+            tick,
+            source,
+            delta,
+        });
+    }
+
+    /// Books the unattributed remainder of the tick as [`EnergySource::Other`]
+    /// and drops entries older than the retention window.
+    pub fn close_tick(&mut self, tick: u64, energy: f64) {
+        if let Some(opening) = self.opening_energy.take() {
+            let residual = energy - opening - self.attributed;
+            if residual.abs() > 1e-9 {
+                self.record(tick, EnergySource::Other, residual);
+            }
+        }
+        self.attributed = 0.0;
+        let oldest = tick.saturating_sub(self.ticks as u64 - 1);
+        while self.entries.front().is_some_and(|e| e.tick < oldest) {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn entries(&self) -> &VecDeque<LedgerEntry> {
+        &self.entries
+    }
+
+    /// Net energy change per source across the retained window.
+    pub fn totals(&self) -> Vec<(EnergySource, f64)> {
+        EnergySource::ALL
+            .iter()
+            .map(|&source| {
+                let sum = self
+                    .entries
+                    .iter()
+                    .filter(|e| e.source == source)
+                    .map(|e| e.delta)
+                    .sum();
+                (source, sum)
+            })
+            .collect()
+    }
+
+    /// Net energy change across the retained window.
+    pub fn net(&self) -> f64 {
+        self.entries.iter().map(|e| e.delta).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_residual_balances_tick() {
+        let mut ledger = EnergyLedger::new(Uuid::nil(), 10);
+        ledger.open_tick(100.0);
+        ledger.record(1, EnergySource::Movement, -2.0);
+        ledger.record(1, EnergySource::Food, 10.0);
+        ledger.close_tick(1, 107.5);
+
+        let totals: std::collections::HashMap<_, _> = ledger.totals().into_iter().collect();
+        assert_eq!(totals[&EnergySource::Food], 10.0);
+        assert!((totals[&EnergySource::Other] - -0.5).abs() < 1e-9);
+        assert!((ledger.net() - 7.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_window_drops_old_ticks() {
+        let mut ledger = EnergyLedger::new(Uuid::nil(), 2);
+        for tick in 1..=5 {
+            ledger.open_tick(0.0);
+            ledger.record(tick, EnergySource::Basal, -1.0);
+            ledger.close_tick(tick, -1.0);
+        }
+        assert_eq!(ledger.entries().len(), 2);
+        assert_eq!(ledger.entries().front().map(|e| e.tick), Some(4));
+    }
+}
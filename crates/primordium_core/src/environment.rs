@@ -1,13 +1,50 @@
+use crate::config::{EraModifiers, SeasonModifiers, SeasonsConfig};
+use crate::schedule::Schedule;
 use serde::{Deserialize, Serialize};
 
+/// Age of the world, set from population statistics by
+/// [`update_era`](crate::systems::environment::update_era). Each era applies
+/// its [`EraModifiers`] from `config.eras` while it lasts.
 #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub enum Era {
     #[default]
     Primordial,
-    DawnOfLife,
-    Flourishing,
-    DominanceWar,
-    ApexEra,
+    #[serde(alias = "DawnOfLife", alias = "Flourishing")]
+    Expansion,
+    #[serde(alias = "DominanceWar")]
+    Strife,
+    #[serde(alias = "ApexEra")]
+    Enlightenment,
+}
+
+impl Era {
+    pub const ALL: [Era; 4] = [
+        Era::Primordial,
+        Era::Expansion,
+        Era::Strife,
+        Era::Enlightenment,
+    ];
+
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Era::Primordial => "Primordial",
+            Era::Expansion => "Expansion",
+            Era::Strife => "Strife",
+            Era::Enlightenment => "Enlightenment",
+        }
+    }
+
+    /// One line for the chronicle when the world enters this era.
+    #[must_use]
+    pub fn narration(&self) -> &'static str {
+        match self {
+            Era::Primordial => "The world sinks back into the primordial ooze…",
+            Era::Expansion => "Life spills out across the land: the Age of Expansion begins…",
+            Era::Strife => "The land grows crowded and choked: an Age of Strife dawns…",
+            Era::Enlightenment => "Minds sharpen beyond all before: the Age of Enlightenment…",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -19,6 +56,16 @@ pub enum ClimateState {
 }
 
 impl ClimateState {
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClimateState::Temperate => "Temperate",
+            ClimateState::Warm => "Warm",
+            ClimateState::Hot => "Hot",
+            ClimateState::Scorching => "Scorching",
+        }
+    }
+
     #[must_use]
     pub fn icon(&self) -> &'static str {
         match self {
@@ -50,6 +97,9 @@ impl ResourceState {
     }
 }
 
+/// Season of the yearly cycle advanced by
+/// [`update_season`](crate::systems::environment::update_season), each with
+/// the [`SeasonModifiers`] of `config.seasons`.
 #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub enum Season {
     #[default]
@@ -60,6 +110,8 @@ pub enum Season {
 }
 
 impl Season {
+    pub const ALL: [Season; 4] = [Season::Spring, Season::Summer, Season::Fall, Season::Winter];
+
     #[must_use]
     pub fn icon(&self) -> &'static str {
         match self {
@@ -71,22 +123,12 @@ impl Season {
     }
 
     #[must_use]
-    pub fn food_multiplier(&self) -> f64 {
-        match self {
-            Season::Spring => 1.5,
-            Season::Summer => 1.0,
-            Season::Fall => 1.2,
-            Season::Winter => 0.5,
-        }
-    }
-
-    #[must_use]
-    pub fn metabolism_multiplier(&self) -> f64 {
+    pub fn name(&self) -> &'static str {
         match self {
-            Season::Spring => 0.8,
-            Season::Summer => 1.2,
-            Season::Fall => 1.0,
-            Season::Winter => 1.5,
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Fall => "Fall",
+            Season::Winter => "Winter",
         }
     }
 
@@ -100,24 +142,11 @@ impl Season {
         }
     }
 
-    fn smooth_step(t: f64) -> f64 {
-        t * t * (3.0 - 2.0 * t)
-    }
-
-    #[must_use]
-    pub fn food_multiplier_smooth(&self, next: Season, progress: f64) -> f64 {
-        let t = Self::smooth_step(progress.clamp(0.0, 1.0));
-        let from = self.food_multiplier();
-        let to = next.food_multiplier();
-        from + (to - from) * t
-    }
-
+    /// Season of the `index`-th season since the world began, which
+    /// started in spring.
     #[must_use]
-    pub fn metabolism_multiplier_smooth(&self, next: Season, progress: f64) -> f64 {
-        let t = Self::smooth_step(progress.clamp(0.0, 1.0));
-        let from = self.metabolism_multiplier();
-        let to = next.metabolism_multiplier();
-        from + (to - from) * t
+    pub fn nth(index: u64) -> Season {
+        Season::ALL[(index % 4) as usize]
     }
 }
 
@@ -147,13 +176,23 @@ pub struct Environment {
     pub abundance_timer: u32,
     pub radiation_timer: u32,
     pub current_era: Era,
+    /// Modifiers of `current_era`, as configured when it began.
+    #[serde(default)]
+    pub era_modifiers: EraModifiers,
     pub current_season: Season,
     pub next_season: Season,
     pub season_tick: u64,
     pub season_duration: u64,
     pub transition_duration: u64,
+    /// Modifiers of the season, blended towards `next_season` during the
+    /// transition.
+    #[serde(default)]
+    pub season_modifiers: SeasonModifiers,
     pub world_time: u64,
     pub day_cycle_ticks: u64,
+    /// Scale of metabolic costs at night.
+    #[serde(default = "default_night_metabolism")]
+    pub night_metabolism: f64,
     pub god_climate_override: Option<ClimateState>,
     pub carbon_level: f64,
     pub oxygen_level: f64,
@@ -165,6 +204,13 @@ pub struct Environment {
     pub dda_solar_multiplier: f64,
     /// Phase 67 Task C: DDA base idle multiplier (adjusts base_idle_cost dynamically)
     pub dda_base_idle_multiplier: f64,
+    /// Upcoming and recent scheduled events.
+    #[serde(default)]
+    pub schedule: Schedule,
+}
+
+fn default_night_metabolism() -> f64 {
+    0.6
 }
 
 impl Default for Environment {
@@ -178,13 +224,16 @@ impl Default for Environment {
             abundance_timer: 0,
             radiation_timer: 0,
             current_era: Era::Primordial,
+            era_modifiers: EraModifiers::default(),
             current_season: Season::Spring,
             next_season: Season::Summer,
             season_tick: 0,
             season_duration: 10000,
             transition_duration: 1000,
+            season_modifiers: SeasonsConfig::default().spring,
             world_time: 0,
             day_cycle_ticks: 2000,
+            night_metabolism: default_night_metabolism(),
             god_climate_override: None,
             carbon_level: 300.0,
             oxygen_level: 21.0,
@@ -192,6 +241,7 @@ impl Default for Environment {
             available_energy: 10000.0,
             dda_solar_multiplier: 1.0,
             dda_base_idle_multiplier: 1.0,
+            schedule: Schedule::default(),
         }
     }
 }
@@ -210,8 +260,7 @@ impl Environment {
         self.radiation_timer = 0;
 
         self.current_era = Era::Primordial;
-        self.current_season = Season::Spring;
-        self.world_time = 500;
+        self.era_modifiers = EraModifiers::default();
 
         let t = tick as f32 * 0.01;
         self.cpu_usage = 50.0 + (t.sin() * 20.0);
@@ -261,28 +310,18 @@ impl Environment {
 
     #[must_use]
     pub fn current_food_multiplier(&self) -> f64 {
-        let transition_start = self.season_duration - self.transition_duration;
-        if self.season_tick >= transition_start {
-            let progress =
-                (self.season_tick - transition_start) as f64 / self.transition_duration as f64;
-            self.current_season
-                .food_multiplier_smooth(self.next_season, progress)
-        } else {
-            self.current_season.food_multiplier()
-        }
+        self.season_modifiers.food
     }
 
     #[must_use]
     pub fn current_metabolism_multiplier(&self) -> f64 {
-        let transition_start = self.season_duration - self.transition_duration;
-        if self.season_tick >= transition_start {
-            let progress =
-                (self.season_tick - transition_start) as f64 / self.transition_duration as f64;
-            self.current_season
-                .metabolism_multiplier_smooth(self.next_season, progress)
-        } else {
-            self.current_season.metabolism_multiplier()
-        }
+        self.season_modifiers.metabolism
+    }
+
+    /// Seasonal shift of the climate, in units of 10% CPU load.
+    #[must_use]
+    pub fn temperature(&self) -> f64 {
+        self.season_modifiers.temperature
     }
 
     #[must_use]
@@ -314,7 +353,8 @@ impl Environment {
         }
 
         let carbon_forcing = ((self.carbon_level - 300.0) / 100.0).max(0.0) as f32;
-        let effective_cpu = self.cpu_usage + carbon_forcing * 10.0;
+        let effective_cpu =
+            self.cpu_usage + carbon_forcing * 10.0 + self.temperature() as f32 * 10.0;
 
         if self.is_heat_wave() {
             ClimateState::Scorching
@@ -356,14 +396,13 @@ impl Environment {
 
         let era_mult = match self.current_era {
             Era::Primordial => 1.0,
-            Era::DawnOfLife => 0.9,
-            Era::Flourishing => 1.1,
-            Era::DominanceWar => 1.5,
-            Era::ApexEra => 1.2,
+            Era::Expansion => 0.9,
+            Era::Strife => 1.5,
+            Era::Enlightenment => 1.2,
         };
 
         let circadian = if matches!(self.time_of_day(), TimeOfDay::Night) {
-            0.6
+            self.night_metabolism
         } else {
             1.0
         };
@@ -390,7 +429,7 @@ impl Environment {
         if self.is_abundance() {
             base *= 2.0;
         }
-        base * self.current_food_multiplier()
+        base * self.current_food_multiplier() * self.era_modifiers.food_richness as f64
     }
 
     pub fn carbon_stress_factor(&self) -> f64 {
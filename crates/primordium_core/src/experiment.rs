@@ -0,0 +1,266 @@
+//! Cohort experiments.
+//!
+//! An [`Experiment`] splits part of a live world into named cohorts, e.g. a
+//! control and a treatment group, without forking the world. Members carry
+//! their cohort's system tag ([`Experiment::tag`]), which offspring inherit,
+//! and receive the cohort's [`CohortModifiers`] as status effects every
+//! tick. Outcomes are tracked per cohort and compared against the first
+//! cohort, the control, in an [`ExperimentReport`].
+
+use crate::systems::status;
+use primordium_data::{StatusEffect, StatusKind, Tags};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Prefix of the system tags marking cohort members.
+pub const TAG_PREFIX: &str = "exp:";
+
+/// Parameter changes applied to the members of a cohort.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CohortModifiers {
+    /// Multiplier on the mutation rate of members' offspring.
+    pub mutation_rate: f32,
+    /// Multiplier on members' metabolic costs.
+    pub metabolic_rate: f32,
+}
+
+impl Default for CohortModifiers {
+    fn default() -> Self {
+        Self {
+            mutation_rate: 1.0,
+            metabolic_rate: 1.0,
+        }
+    }
+}
+
+impl CohortModifiers {
+    /// Effects applying the modifiers for one tick; none for a control.
+    pub fn effects(&self) -> Vec<StatusEffect> {
+        [
+            (StatusKind::MutationRate, self.mutation_rate),
+            (StatusKind::MetabolicRate, self.metabolic_rate),
+        ]
+        .into_iter()
+        .filter(|(_, factor)| *factor != 1.0)
+        .map(|(kind, factor)| status::treatment(kind, factor))
+        .collect()
+    }
+}
+
+/// Outcome metrics of one cohort.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CohortOutcome {
+    /// Entities assigned by hand or by a split, not counting offspring.
+    pub assigned: usize,
+    pub births: u64,
+    pub deaths: u64,
+    /// Total age at death, for [`CohortOutcome::mean_lifespan`].
+    pub lifespan_sum: u64,
+    /// Living members at the end of the last tick.
+    pub population: usize,
+    pub peak_population: usize,
+    pub mean_energy: f64,
+    pub mean_generation: f64,
+}
+
+impl CohortOutcome {
+    /// Mean age at death, or `None` before the first death.
+    pub fn mean_lifespan(&self) -> Option<f64> {
+        (self.deaths > 0).then(|| self.lifespan_sum as f64 / self.deaths as f64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cohort {
+    pub name: String,
+    pub modifiers: CohortModifiers,
+    #[serde(default)]
+    pub outcome: CohortOutcome,
+}
+
+/// A running cohort experiment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Experiment {
+    pub name: String,
+    pub start_tick: u64,
+    /// Cohorts in order; the first is the control the others are compared to.
+    pub cohorts: Vec<Cohort>,
+}
+
+impl Experiment {
+    pub fn new(name: &str, start_tick: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            start_tick,
+            cohorts: Vec::new(),
+        }
+    }
+
+    pub fn with_cohort(mut self, name: &str, modifiers: CohortModifiers) -> Self {
+        self.cohorts.push(Cohort {
+            name: name.to_string(),
+            modifiers,
+            outcome: CohortOutcome::default(),
+        });
+        self
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !self.name.is_empty() && !self.name.contains(':'),
+            "experiment name must be non-empty and contain no ':'"
+        );
+        anyhow::ensure!(
+            self.cohorts.len() >= 2,
+            "an experiment needs at least two cohorts"
+        );
+        for (i, cohort) in self.cohorts.iter().enumerate() {
+            anyhow::ensure!(!cohort.name.is_empty(), "cohort names must be non-empty");
+            anyhow::ensure!(
+                self.cohorts[..i].iter().all(|c| c.name != cohort.name),
+                "duplicate cohort '{}'",
+                cohort.name
+            );
+            let m = &cohort.modifiers;
+            anyhow::ensure!(
+                m.mutation_rate >= 0.0 && m.metabolic_rate > 0.0,
+                "cohort '{}' has a negative or zero modifier",
+                cohort.name
+            );
+        }
+        Ok(())
+    }
+
+    /// System tag carried by the members of cohort `cohort`.
+    pub fn tag(&self, cohort: usize) -> String {
+        format!("{}{}:{}", TAG_PREFIX, self.name, self.cohorts[cohort].name)
+    }
+
+    /// Tags of all cohorts, in cohort order.
+    pub fn tags(&self) -> Vec<String> {
+        (0..self.cohorts.len()).map(|i| self.tag(i)).collect()
+    }
+
+    pub fn cohort_index(&self, name: &str) -> Option<usize> {
+        self.cohorts.iter().position(|c| c.name == name)
+    }
+
+    /// Cohort an entity with `tags` belongs to.
+    pub fn cohort_of(&self, tags: &Tags) -> Option<usize> {
+        self.tags().iter().position(|t| tags.system.contains(t))
+    }
+
+    /// Updates population and means from this tick's members, given as
+    /// `(cohort, energy, generation)`.
+    pub fn observe(&mut self, members: &[(usize, f64, u32)]) {
+        for (i, cohort) in self.cohorts.iter_mut().enumerate() {
+            let (mut count, mut energy, mut generation) = (0usize, 0.0, 0.0);
+            for &(_, e, g) in members.iter().filter(|(c, ..)| *c == i) {
+                count += 1;
+                energy += e;
+                generation += f64::from(g);
+            }
+            let outcome = &mut cohort.outcome;
+            outcome.population = count;
+            outcome.peak_population = outcome.peak_population.max(count);
+            if count > 0 {
+                outcome.mean_energy = energy / count as f64;
+                outcome.mean_generation = generation / count as f64;
+            }
+        }
+    }
+
+    pub fn record_birth(&mut self, cohort: usize) {
+        self.cohorts[cohort].outcome.births += 1;
+    }
+
+    pub fn record_death(&mut self, cohort: usize, age: u64) {
+        let outcome = &mut self.cohorts[cohort].outcome;
+        outcome.deaths += 1;
+        outcome.lifespan_sum += age;
+    }
+
+    /// Compares every cohort with the control as of `tick`.
+    pub fn report(&self, tick: u64) -> ExperimentReport {
+        let control = &self.cohorts[0].outcome;
+        let ratio = |value: f64, base: f64| (base > 0.0).then(|| value / base);
+        ExperimentReport {
+            name: self.name.clone(),
+            ticks: tick.saturating_sub(self.start_tick),
+            cohorts: self
+                .cohorts
+                .iter()
+                .map(|c| CohortReport {
+                    name: c.name.clone(),
+                    modifiers: c.modifiers.clone(),
+                    population_ratio: ratio(c.outcome.population as f64, control.population as f64),
+                    birth_ratio: ratio(c.outcome.births as f64, control.births as f64),
+                    outcome: c.outcome.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// One cohort's outcome next to the control's.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CohortReport {
+    pub name: String,
+    pub modifiers: CohortModifiers,
+    pub outcome: CohortOutcome,
+    /// Population relative to the control; `None` if the control died out.
+    pub population_ratio: Option<f64>,
+    /// Births relative to the control; `None` if the control had none.
+    pub birth_ratio: Option<f64>,
+}
+
+/// Comparative report of an experiment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentReport {
+    pub name: String,
+    /// Ticks since the experiment started.
+    pub ticks: u64,
+    pub cohorts: Vec<CohortReport>,
+}
+
+impl fmt::Display for ExperimentReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ratio = |r: Option<f64>| r.map_or("-".to_string(), |r| format!("x{:.2}", r));
+        writeln!(f, "Experiment '{}' after {} ticks", self.name, self.ticks)?;
+        writeln!(
+            f,
+            "{:<12} {:>6} {:>6} {:>6} {:>7} {:>7} {:>8} {:>6} {:>8} {:>8}",
+            "cohort",
+            "mut",
+            "met",
+            "pop",
+            "births",
+            "deaths",
+            "lifespan",
+            "gen",
+            "pop/ctl",
+            "birth/ctl"
+        )?;
+        for c in &self.cohorts {
+            let o = &c.outcome;
+            writeln!(
+                f,
+                "{:<12} {:>6.2} {:>6.2} {:>6} {:>7} {:>7} {:>8} {:>6.1} {:>8} {:>8}",
+                c.name,
+                c.modifiers.mutation_rate,
+                c.modifiers.metabolic_rate,
+                o.population,
+                o.births,
+                o.deaths,
+                o.mean_lifespan()
+                    .map_or("-".to_string(), |l| format!("{:.0}", l)),
+                o.mean_generation,
+                ratio(c.population_ratio),
+                ratio(c.birth_ratio),
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+//! Heritable plant traits.
+//!
+//! Food carries a [`PlantGenome`]: its nutrient type, a toxicity that
+//! poisons eaters whose metabolic niche does not match it, and a regrowth
+//! vigour. Plants seed nearby fertile cells and their seedlings inherit a
+//! mutated copy of the genome, so the flora drifts under grazing pressure:
+//! heavily grazed nutrient types thin out while toxic or off-niche plants
+//! spread, and herbivore niches follow them. Toxicity is paid for in
+//! seeding, so unpressured flora sheds it again.
+
+use crate::config::FloraConfig;
+use primordium_data::PlantGenome;
+use rand::Rng;
+
+/// Lower bound of the regrowth gene, so no lineage of plants becomes
+/// sterile.
+pub const MIN_REGROWTH: f32 = 0.05;
+
+/// Copy of `genome` with each gene nudged by up to `mutation_amount` with
+/// probability `mutation_rate`.
+pub fn mutate(genome: PlantGenome, config: &FloraConfig, rng: &mut impl Rng) -> PlantGenome {
+    let mut gene = |value: f32, min: f32| {
+        if config.mutation_amount > 0.0 && rng.gen::<f32>() < config.mutation_rate {
+            let delta = rng.gen_range(-config.mutation_amount..=config.mutation_amount);
+            (value + delta).clamp(min, 1.0)
+        } else {
+            value
+        }
+    };
+    PlantGenome {
+        toxicity: gene(genome.toxicity, 0.0),
+        nutrient_type: gene(genome.nutrient_type, 0.0),
+        regrowth: gene(genome.regrowth, MIN_REGROWTH),
+    }
+}
+
+/// Chance per tick that a plant seeds a cell of the given fertility.
+pub fn seed_chance(genome: &PlantGenome, fertility: f32, config: &FloraConfig) -> f32 {
+    if fertility < config.min_fertility {
+        return 0.0;
+    }
+    let defence = (1.0 - genome.toxicity * config.toxicity_cost).max(0.0);
+    config.seed_chance * genome.regrowth * fertility.min(1.0) * defence
+}
+
+/// Energy a plant of `toxicity` costs an eater digesting it with niche
+/// efficiency `niche_efficiency` (1.0 for a perfect match, which is immune).
+pub fn toxin_damage(toxicity: f32, niche_efficiency: f32, config: &FloraConfig) -> f64 {
+    let mismatch = (1.0 - niche_efficiency).clamp(0.0, 1.0);
+    f64::from(toxicity.max(0.0) * mismatch) * config.toxin_damage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_mutation_stays_in_range_and_toxins_cost_seeding() {
+        let config = FloraConfig {
+            mutation_rate: 1.0,
+            mutation_amount: 0.5,
+            ..FloraConfig::default()
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut genome = PlantGenome::wild(0.2);
+        for _ in 0..200 {
+            genome = mutate(genome, &config, &mut rng);
+            assert!((0.0..=1.0).contains(&genome.toxicity));
+            assert!((0.0..=1.0).contains(&genome.nutrient_type));
+            assert!((MIN_REGROWTH..=1.0).contains(&genome.regrowth));
+        }
+        assert_ne!(genome, PlantGenome::wild(0.2));
+
+        let config = FloraConfig::default();
+        let wild = PlantGenome::wild(0.2);
+        let toxic = PlantGenome {
+            toxicity: 0.8,
+            ..wild
+        };
+        assert!(seed_chance(&toxic, 1.0, &config) < seed_chance(&wild, 1.0, &config));
+        assert_eq!(seed_chance(&wild, config.min_fertility / 2.0, &config), 0.0);
+
+        assert_eq!(toxin_damage(0.8, 1.0, &config), 0.0);
+        assert!(toxin_damage(0.8, 0.5, &config) > toxin_damage(0.4, 0.5, &config));
+    }
+}
@@ -0,0 +1,169 @@
+//! Grid-based food representation.
+//!
+//! With `food.representation = "field"` food is stored as a per-cell count
+//! instead of one ECS entity per item. Occupied cells are exposed to the rest
+//! of the pipeline as "food sites" so sensing and eating work unchanged, but
+//! spawning and consuming food no longer touch the ECS.
+
+use primordium_data::Food;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct FoodField {
+    pub width: u16,
+    pub height: u16,
+    /// Food units stored in each cell.
+    pub units: Vec<u16>,
+    /// Mean nutrient type of the units in each cell.
+    pub nutrient: Vec<f32>,
+    total: usize,
+    /// Cell index of each site returned by the last [`FoodField::index_sites`].
+    #[serde(skip)]
+    sites: Vec<usize>,
+}
+
+impl FoodField {
+    pub fn new(width: u16, height: u16) -> Self {
+        let size = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            units: vec![0; size],
+            nutrient: vec![0.0; size],
+            total: 0,
+            sites: Vec::new(),
+        }
+    }
+
+    /// Total number of food units in the field.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    #[inline]
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y as usize * self.width as usize + x as usize)
+    }
+
+    /// Adds one unit of food with the given nutrient type to a cell.
+    pub fn deposit(&mut self, x: u16, y: u16, nutrient_type: f32) -> bool {
+        let Some(idx) = self.index(x, y) else {
+            return false;
+        };
+        let count = self.units[idx];
+        if count == u16::MAX {
+            return false;
+        }
+        let n = count as f32;
+        self.nutrient[idx] = (self.nutrient[idx] * n + nutrient_type) / (n + 1.0);
+        self.units[idx] = count + 1;
+        self.total += 1;
+        true
+    }
+
+    /// Rebuilds the site list from occupied cells in row-major order and
+    /// returns `(x, y, nutrient_type)` for each site, matching the layout of
+    /// the entity-based food index.
+    pub fn index_sites(&mut self) -> Vec<(f64, f64, f32)> {
+        self.sites.clear();
+        let w = self.width as usize;
+        let mut data = Vec::new();
+        for (idx, &count) in self.units.iter().enumerate() {
+            if count > 0 {
+                self.sites.push(idx);
+                data.push(((idx % w) as f64, (idx / w) as f64, self.nutrient[idx]));
+            }
+        }
+        data
+    }
+
+    /// Consumes one unit from a site, returning its nutrient type.
+    pub fn consume_site(&mut self, site: usize) -> Option<f32> {
+        let idx = *self.sites.get(site)?;
+        let count = self.units.get_mut(idx)?;
+        if *count == 0 {
+            return None;
+        }
+        *count -= 1;
+        self.total -= 1;
+        Some(self.nutrient[idx])
+    }
+
+    /// Removes up to `amount` units, scanning cells in row-major order.
+    pub fn remove_units(&mut self, amount: usize) -> usize {
+        let mut removed = 0;
+        for count in &mut self.units {
+            if removed >= amount {
+                break;
+            }
+            let take = (*count as usize).min(amount - removed);
+            *count -= take as u16;
+            removed += take;
+        }
+        self.total -= removed;
+        removed
+    }
+
+    /// One renderable food item per occupied cell.
+    pub fn to_food_items(&self) -> Vec<Food> {
+        let w = self.width as usize;
+        self.units
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(idx, _)| Food::new((idx % w) as u16, (idx / w) as u16, self.nutrient[idx]))
+            .collect()
+    }
+
+    /// Approximate heap footprint of the field in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        self.units.capacity() * std::mem::size_of::<u16>()
+            + self.nutrient.capacity() * std::mem::size_of::<f32>()
+            + self.sites.capacity() * std::mem::size_of::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deposit_and_consume() {
+        let mut field = FoodField::new(10, 10);
+        assert!(field.deposit(3, 4, 0.2));
+        assert!(field.deposit(3, 4, 0.4));
+        assert!(field.deposit(7, 1, 0.9));
+        assert_eq!(field.total(), 3);
+
+        let sites = field.index_sites();
+        assert_eq!(sites.len(), 2);
+        assert_eq!(sites[0], (7.0, 1.0, 0.9));
+        assert!((sites[1].2 - 0.3).abs() < 1e-6);
+
+        assert!(field.consume_site(1).is_some());
+        assert!(field.consume_site(1).is_some());
+        assert!(field.consume_site(1).is_none());
+        assert_eq!(field.total(), 1);
+    }
+
+    #[test]
+    fn test_out_of_bounds_deposit_is_ignored() {
+        let mut field = FoodField::new(5, 5);
+        assert!(!field.deposit(5, 0, 0.5));
+        assert_eq!(field.total(), 0);
+    }
+
+    #[test]
+    fn test_remove_units() {
+        let mut field = FoodField::new(4, 4);
+        for _ in 0..3 {
+            field.deposit(1, 1, 0.5);
+        }
+        field.deposit(2, 2, 0.5);
+        assert_eq!(field.remove_units(2), 2);
+        assert_eq!(field.total(), 2);
+        assert_eq!(field.remove_units(10), 2);
+        assert_eq!(field.total(), 0);
+        assert!(field.to_food_items().is_empty());
+    }
+}
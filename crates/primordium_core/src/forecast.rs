@@ -0,0 +1,427 @@
+//! Population and food forecasting.
+//!
+//! Every `forecast.sample_interval` ticks the population, food count and
+//! mean soil fertility are sampled, keeping the last `forecast.window`
+//! samples. Each series is then fitted two ways and projected
+//! `forecast.horizon` ticks ahead:
+//!
+//! - **Logistic**: per-capita growth is regressed on population size, which
+//!   for a population nearing its limit falls in a line from the intrinsic
+//!   growth rate `r` at zero to nothing at the carrying capacity `K`.
+//! - **ARIMA-lite**: an ARIMA(1,1,0) with drift, i.e. each step's change is
+//!   the mean change plus a damped share of the last step's deviation from
+//!   it.
+//!
+//! Whichever model predicted the history one step ahead with the smaller
+//! error is projected. Confidence bands are 95% intervals: the random-walk
+//! spread of the logistic residuals, or the ARIMA forecast variance.
+//! Projections stay at or above zero, and fertility at or below one. A
+//! famine is forecast when projected food falls below
+//! `forecast.famine_food_per_head` items per living entity.
+
+use crate::config::ForecastConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Fewest samples a forecast is fitted on.
+pub const MIN_SAMPLES: usize = 8;
+
+/// Standard normal quantile of a two-sided 95% interval.
+const Z_95: f64 = 1.96;
+
+/// Largest autoregressive coefficient, keeping projections from compounding
+/// a trend without end.
+const MAX_PHI: f64 = 0.95;
+
+/// One sampled point of history.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ForecastSample {
+    pub tick: u64,
+    pub population: f64,
+    pub food: f64,
+    pub fertility: f64,
+}
+
+/// The model a projection follows, with its fitted parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TrendModel {
+    /// Logistic growth at `rate` per tick towards `capacity`.
+    Logistic { rate: f64, capacity: f64 },
+    /// ARIMA(1,1,0): changes of `drift` per tick, with deviations from it
+    /// carried over at `phi` per sample.
+    Arima { drift: f64, phi: f64 },
+}
+
+impl TrendModel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrendModel::Logistic { .. } => "logistic",
+            TrendModel::Arima { .. } => "ARIMA",
+        }
+    }
+}
+
+/// A projected value with its 95% confidence band.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ForecastPoint {
+    pub tick: u64,
+    pub mean: f64,
+    pub low: f64,
+    pub high: f64,
+}
+
+/// The projection of one series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Projection {
+    pub model: TrendModel,
+    /// One point per sampling interval up to the horizon.
+    pub points: Vec<ForecastPoint>,
+}
+
+impl Projection {
+    /// The last projected point.
+    pub fn end(&self) -> Option<&ForecastPoint> {
+        self.points.last()
+    }
+}
+
+/// When food is forecast to run short: where the central projection does,
+/// or failing that, the pessimistic edge of the bands.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Famine {
+    pub tick: u64,
+    /// Whether the central projection runs short, rather than only the
+    /// edge of the bands.
+    pub likely: bool,
+}
+
+/// The latest projections.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Forecast {
+    pub tick: u64,
+    pub population: Projection,
+    pub food: Projection,
+    pub fertility: Projection,
+    /// Carrying capacity from the logistic fit of the population, when it
+    /// shows growth slowing towards a limit.
+    pub carrying_capacity: Option<f64>,
+    pub famine: Option<Famine>,
+}
+
+/// Sampled history and the forecast fitted on it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Forecaster {
+    pub samples: VecDeque<ForecastSample>,
+    /// Refitted on every sample once there are enough of them.
+    pub latest: Option<Forecast>,
+}
+
+impl Forecaster {
+    /// Whether `tick` ends a sampling interval.
+    pub fn is_due(tick: u64, config: &ForecastConfig) -> bool {
+        tick.is_multiple_of(config.sample_interval)
+    }
+
+    /// Records `sample` and refits the forecast.
+    pub fn observe(&mut self, sample: ForecastSample, config: &ForecastConfig) {
+        self.samples.push_back(sample);
+        while self.samples.len() > config.window {
+            self.samples.pop_front();
+        }
+        self.latest = forecast(&self.samples, config);
+    }
+}
+
+/// Fits and projects `samples`, or `None` with fewer than [`MIN_SAMPLES`].
+pub fn forecast(samples: &VecDeque<ForecastSample>, config: &ForecastConfig) -> Option<Forecast> {
+    if samples.len() < MIN_SAMPLES {
+        return None;
+    }
+    let last = samples.back()?.tick;
+    let steps = config.horizon.div_ceil(config.sample_interval) as usize;
+    let ticks: Vec<u64> = (1..=steps as u64)
+        .map(|h| last + h * config.sample_interval)
+        .collect();
+    let series = |f: fn(&ForecastSample) -> f64| samples.iter().map(f).collect::<Vec<_>>();
+    let population = series(|s| s.population);
+    let food = series(|s| s.food);
+    let fertility = series(|s| s.fertility);
+
+    let logistic = fit_logistic(&population);
+    let population = project(&population, &ticks, config.sample_interval);
+    let food = project(&food, &ticks, config.sample_interval);
+    let mut fertility = project(&fertility, &ticks, config.sample_interval);
+    // Soil fertility is a fraction; no trend takes it past full.
+    for point in &mut fertility.points {
+        point.mean = point.mean.min(1.0);
+        point.low = point.low.min(1.0);
+        point.high = point.high.min(1.0);
+    }
+
+    let threshold = config.famine_food_per_head;
+    let short = |value: fn(&ForecastPoint) -> f64| {
+        population
+            .points
+            .iter()
+            .zip(&food.points)
+            .find(|(pop, food)| pop.mean >= 1.0 && value(food) < threshold * pop.mean)
+            .map(|(pop, _)| pop.tick)
+    };
+    let famine = match (short(|p| p.mean), short(|p| p.low)) {
+        (Some(tick), _) => Some(Famine { tick, likely: true }),
+        (None, Some(tick)) => Some(Famine {
+            tick,
+            likely: false,
+        }),
+        (None, None) => None,
+    };
+
+    Some(Forecast {
+        tick: last,
+        population,
+        food,
+        fertility,
+        carrying_capacity: logistic.map(|fit| fit.capacity()),
+        famine,
+    })
+}
+
+/// Logistic fit `x' = x + x (a + b x)`, one sample per step.
+#[derive(Debug, Clone, Copy)]
+struct LogisticFit {
+    a: f64,
+    b: f64,
+}
+
+impl LogisticFit {
+    fn capacity(&self) -> f64 {
+        -self.a / self.b
+    }
+
+    fn step(&self, x: f64) -> f64 {
+        (x + x * (self.a + self.b * x)).max(0.0)
+    }
+}
+
+/// ARIMA(1,1,0) fit: step changes `d` with `d - mu = phi (d_prev - mu) + e`.
+#[derive(Debug, Clone, Copy)]
+struct ArimaFit {
+    mu: f64,
+    phi: f64,
+    sigma: f64,
+}
+
+/// Least-squares slope and intercept of `y` on `x`.
+fn regress(x: &[f64], y: &[f64]) -> Option<(f64, f64)> {
+    let n = x.len() as f64;
+    if x.len() < 2 {
+        return None;
+    }
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let var: f64 = x.iter().map(|v| (v - mean_x).powi(2)).sum();
+    if var <= f64::EPSILON {
+        return None;
+    }
+    let cov: f64 = x
+        .iter()
+        .zip(y)
+        .map(|(a, b)| (a - mean_x) * (b - mean_y))
+        .sum();
+    let slope = cov / var;
+    Some((slope, mean_y - slope * mean_x))
+}
+
+/// Fits logistic growth to `xs`, or `None` when per-capita growth does not
+/// fall with size from a positive rate.
+fn fit_logistic(xs: &[f64]) -> Option<LogisticFit> {
+    let (sizes, growth): (Vec<f64>, Vec<f64>) = xs
+        .windows(2)
+        .filter(|w| w[0] > 0.0)
+        .map(|w| (w[0], (w[1] - w[0]) / w[0]))
+        .unzip();
+    let (b, a) = regress(&sizes, &growth)?;
+    (a > 0.0 && b < 0.0).then_some(LogisticFit { a, b })
+}
+
+fn fit_arima(xs: &[f64]) -> ArimaFit {
+    let diffs: Vec<f64> = xs.windows(2).map(|w| w[1] - w[0]).collect();
+    let mu = diffs.iter().sum::<f64>() / diffs.len().max(1) as f64;
+    let centred: Vec<f64> = diffs.iter().map(|d| d - mu).collect();
+    let (num, den) = centred
+        .windows(2)
+        .fold((0.0, 0.0), |(n, d), w| (n + w[1] * w[0], d + w[0] * w[0]));
+    let phi = if den > f64::EPSILON {
+        (num / den).clamp(-MAX_PHI, MAX_PHI)
+    } else {
+        0.0
+    };
+    let residuals: Vec<f64> = centred.windows(2).map(|w| w[1] - phi * w[0]).collect();
+    ArimaFit {
+        mu,
+        phi,
+        sigma: rms(&residuals),
+    }
+}
+
+fn rms(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    (values.iter().map(|v| v * v).sum::<f64>() / values.len() as f64).sqrt()
+}
+
+/// One-step-ahead errors of `predict` over `xs`, from the third sample on
+/// so both models are scored on the same steps.
+fn one_step_errors(xs: &[f64], predict: impl Fn(usize) -> f64) -> Vec<f64> {
+    (2..xs.len()).map(|i| xs[i] - predict(i - 1)).collect()
+}
+
+/// Projects `xs` to `ticks` with whichever model tracked it better.
+fn project(xs: &[f64], ticks: &[u64], interval: u64) -> Projection {
+    let arima = fit_arima(xs);
+    let arima_errors = one_step_errors(xs, |i| {
+        xs[i] + arima.mu + arima.phi * (xs[i] - xs[i - 1] - arima.mu)
+    });
+
+    if let Some(fit) = fit_logistic(xs) {
+        let errors = one_step_errors(xs, |i| fit.step(xs[i]));
+        if rms(&errors) < rms(&arima_errors) {
+            let sigma = rms(&errors);
+            let mut x = xs[xs.len() - 1];
+            let points = ticks
+                .iter()
+                .enumerate()
+                .map(|(h, &tick)| {
+                    x = fit.step(x);
+                    band(tick, x, sigma * ((h + 1) as f64).sqrt())
+                })
+                .collect();
+            return Projection {
+                model: TrendModel::Logistic {
+                    rate: fit.a / interval as f64,
+                    capacity: fit.capacity(),
+                },
+                points,
+            };
+        }
+    }
+
+    let mut x = xs[xs.len() - 1];
+    let mut deviation = xs[xs.len() - 1] - xs[xs.len() - 2] - arima.mu;
+    // The h-step error is the sum of the shocks still felt at h, shock j
+    // steps back weighing 1 + phi + ... + phi^j.
+    let (mut weight, mut variance) = (0.0, 0.0);
+    let points = ticks
+        .iter()
+        .map(|&tick| {
+            deviation *= arima.phi;
+            x += arima.mu + deviation;
+            weight = 1.0 + arima.phi * weight;
+            variance += weight * weight;
+            band(tick, x, arima.sigma * variance.sqrt())
+        })
+        .collect();
+    Projection {
+        model: TrendModel::Arima {
+            drift: arima.mu / interval as f64,
+            phi: arima.phi,
+        },
+        points,
+    }
+}
+
+/// A point at `mean` with a 95% band of `sd` either side, none of it below
+/// zero.
+fn band(tick: u64, mean: f64, sd: f64) -> ForecastPoint {
+    ForecastPoint {
+        tick,
+        mean: mean.max(0.0),
+        low: (mean - Z_95 * sd).max(0.0),
+        high: (mean + Z_95 * sd).max(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(population: impl Fn(f64) -> f64, food: impl Fn(f64) -> f64) -> Forecaster {
+        let config = ForecastConfig::default();
+        let mut forecaster = Forecaster::default();
+        for i in 0..config.window {
+            let t = i as f64;
+            forecaster.observe(
+                ForecastSample {
+                    tick: i as u64 * config.sample_interval,
+                    population: population(t),
+                    food: food(t),
+                    fertility: 0.5,
+                },
+                &config,
+            );
+        }
+        forecaster
+    }
+
+    #[test]
+    fn test_logistic_growth_finds_its_carrying_capacity() {
+        // Discrete logistic growth at 10% per sample towards 400.
+        let mut x = 20.0;
+        let mut pops = Vec::new();
+        for _ in 0..60 {
+            pops.push(x);
+            x += 0.1 * x * (1.0 - x / 400.0);
+        }
+        let forecaster = history(|t| pops[t as usize], |_| 300.0);
+        let forecast = forecaster.latest.unwrap();
+
+        let capacity = forecast.carrying_capacity.unwrap();
+        assert!((capacity - 400.0).abs() < 1.0, "capacity {capacity}");
+        assert!(matches!(
+            forecast.population.model,
+            TrendModel::Logistic { .. }
+        ));
+        let end = forecast.population.end().unwrap();
+        assert!(end.mean <= 400.0 && end.mean > pops[59]);
+        assert!(end.low <= end.mean && end.mean <= end.high);
+        assert_eq!(forecast.famine, None);
+    }
+
+    #[test]
+    fn test_dwindling_food_forecasts_a_famine() {
+        let forecaster = history(
+            |t| 100.0 + (t * 1.3).sin(),
+            |t| 220.0 - 3.0 * t + (t * 0.7).sin() * 2.0,
+        );
+        let forecast = forecaster.latest.unwrap();
+
+        assert!(matches!(forecast.food.model, TrendModel::Arima { .. }));
+        let famine = forecast.famine.unwrap();
+        assert!(famine.likely);
+        // Food runs down by about 3 a sample from 43 and falls below 25 for
+        // 100 mouths about six samples on.
+        assert!(famine.tick > forecast.tick + 3 * 20);
+        assert!(famine.tick <= forecast.tick + 8 * 20);
+        // Bands widen with the horizon.
+        let points = &forecast.food.points;
+        let spread = |p: &ForecastPoint| p.high - p.low;
+        assert!(spread(&points[0]) < spread(&points[5]));
+    }
+
+    #[test]
+    fn test_no_forecast_before_enough_samples() {
+        let config = ForecastConfig::default();
+        let mut forecaster = Forecaster::default();
+        for tick in 0..MIN_SAMPLES as u64 - 1 {
+            let sample = ForecastSample {
+                tick,
+                population: 10.0,
+                food: 10.0,
+                fertility: 0.5,
+            };
+            forecaster.observe(sample, &config);
+        }
+        assert!(forecaster.latest.is_none());
+    }
+}
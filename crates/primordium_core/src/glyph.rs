@@ -0,0 +1,259 @@
+//! Glyph themes: which symbol stands for an organism on the map.
+//!
+//! A theme is a TOML file shared by the terminal and web renderers. An
+//! organism's glyph is looked up in order:
+//!
+//! 1. `[status]`, for statuses that should stand out whatever the organism
+//!    is (starving, hunting, mating, ...). Statuses left out, or set to an
+//!    empty string, fall through.
+//! 2. `[specialization.<caste>]`, an `adult` and a `larva` glyph per caste.
+//! 3. `[trophic]`, by trophic role for unspecialised organisms: potentials
+//!    up to `herbivore_max` are herbivores, from `carnivore_min` carnivores.
+//!
+//! Glyphs are strings and may be two cells wide (e.g. emoji). Wide glyphs
+//! are only drawn when `wide_glyphs` is set, since not every terminal lays
+//! them out correctly; otherwise their first single-width character is used,
+//! or [`FALLBACK_GLYPH`] if there is none.
+
+use crate::snapshot::EntitySnapshot;
+use primordium_data::{EntityStatus, Specialization};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+
+/// Stand-in for a wide glyph that cannot be drawn.
+pub const FALLBACK_GLYPH: &str = "●";
+
+fn glyph(s: &str) -> Option<String> {
+    Some(s.to_string())
+}
+
+/// Glyphs that override everything else while an organism has a status.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct StatusGlyphs {
+    pub starving: Option<String>,
+    pub larva: Option<String>,
+    pub juvenile: Option<String>,
+    pub infected: Option<String>,
+    pub sharing: Option<String>,
+    pub mating: Option<String>,
+    pub hunting: Option<String>,
+    pub foraging: Option<String>,
+    pub soldier: Option<String>,
+    pub bonded: Option<String>,
+    pub in_transit: Option<String>,
+}
+
+impl Default for StatusGlyphs {
+    fn default() -> Self {
+        Self {
+            starving: glyph("†"),
+            larva: None,
+            juvenile: None,
+            infected: glyph("☣"),
+            sharing: glyph("♣"),
+            mating: glyph("♥"),
+            hunting: glyph("♦"),
+            foraging: None,
+            soldier: None,
+            bonded: glyph("⚭"),
+            in_transit: glyph("✈"),
+        }
+    }
+}
+
+impl StatusGlyphs {
+    fn get(&self, status: EntityStatus) -> Option<&str> {
+        let glyph = match status {
+            EntityStatus::Starving => &self.starving,
+            EntityStatus::Larva => &self.larva,
+            EntityStatus::Juvenile => &self.juvenile,
+            EntityStatus::Infected => &self.infected,
+            EntityStatus::Sharing => &self.sharing,
+            EntityStatus::Mating => &self.mating,
+            EntityStatus::Hunting => &self.hunting,
+            EntityStatus::Foraging => &self.foraging,
+            EntityStatus::Soldier => &self.soldier,
+            EntityStatus::Bonded => &self.bonded,
+            EntityStatus::InTransit => &self.in_transit,
+        };
+        glyph.as_deref().filter(|g| !g.is_empty())
+    }
+}
+
+/// Adult and larva glyphs of one caste.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CasteGlyphs {
+    pub adult: String,
+    pub larva: String,
+}
+
+impl CasteGlyphs {
+    fn new(adult: &str, larva: &str) -> Self {
+        Self {
+            adult: adult.to_string(),
+            larva: larva.to_string(),
+        }
+    }
+
+    fn get(&self, is_larva: bool) -> &str {
+        if is_larva {
+            &self.larva
+        } else {
+            &self.adult
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SpecializationGlyphs {
+    pub soldier: CasteGlyphs,
+    pub engineer: CasteGlyphs,
+    pub provider: CasteGlyphs,
+}
+
+impl Default for SpecializationGlyphs {
+    fn default() -> Self {
+        Self {
+            soldier: CasteGlyphs::new("▲", "△"),
+            engineer: CasteGlyphs::new("◈", "◇"),
+            provider: CasteGlyphs::new("◎", "○"),
+        }
+    }
+}
+
+/// Glyphs of unspecialised organisms by trophic role.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct TrophicGlyphs {
+    pub herbivore: String,
+    pub omnivore: String,
+    pub carnivore: String,
+    /// Larvae of every role.
+    pub larva: String,
+    pub herbivore_max: f32,
+    pub carnivore_min: f32,
+}
+
+impl Default for TrophicGlyphs {
+    fn default() -> Self {
+        Self {
+            herbivore: "●".to_string(),
+            omnivore: "◐".to_string(),
+            carnivore: "◉".to_string(),
+            larva: "·".to_string(),
+            herbivore_max: 0.3,
+            carnivore_min: 0.7,
+        }
+    }
+}
+
+/// A glyph theme, see the module docs.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct GlyphTheme {
+    pub wide_glyphs: bool,
+    pub status: StatusGlyphs,
+    pub specialization: SpecializationGlyphs,
+    pub trophic: TrophicGlyphs,
+}
+
+impl GlyphTheme {
+    pub fn from_toml(s: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Loads a theme file; a missing file gives the default theme.
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Self::from_toml(&s),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The glyph from `[status]` for `status`, if it overrides the others.
+    pub fn status_glyph(&self, status: EntityStatus) -> Option<&str> {
+        self.status.get(status)
+    }
+
+    /// The glyph for `entity`, as the theme spells it.
+    pub fn glyph_for(&self, entity: &EntitySnapshot) -> &str {
+        if let Some(glyph) = self.status_glyph(entity.status) {
+            return glyph;
+        }
+        match entity.specialization {
+            Some(Specialization::Soldier) => self.specialization.soldier.get(entity.is_larva),
+            Some(Specialization::Engineer) => self.specialization.engineer.get(entity.is_larva),
+            Some(Specialization::Provider) => self.specialization.provider.get(entity.is_larva),
+            None if entity.is_larva => &self.trophic.larva,
+            None if entity.trophic_potential <= self.trophic.herbivore_max => {
+                &self.trophic.herbivore
+            }
+            None if entity.trophic_potential >= self.trophic.carnivore_min => {
+                &self.trophic.carnivore
+            }
+            None => &self.trophic.omnivore,
+        }
+    }
+
+    /// `glyph` as it can be drawn: unchanged if it is one cell wide or wide
+    /// glyphs are on, otherwise cut down to one cell.
+    pub fn fit<'a>(&self, glyph: &'a str) -> &'a str {
+        if self.wide_glyphs || glyph.width() <= 1 {
+            return glyph;
+        }
+        glyph
+            .char_indices()
+            .map(|(i, c)| &glyph[i..i + c.len_utf8()])
+            .find(|c| c.width() == 1)
+            .unwrap_or(FALLBACK_GLYPH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_file_overrides_defaults() {
+        let theme = GlyphTheme::from_toml(
+            r#"
+            wide_glyphs = true
+            [status]
+            hunting = "🐺"
+            bonded = ""
+            [trophic]
+            carnivore = "C"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(theme.status_glyph(EntityStatus::Hunting), Some("🐺"));
+        assert_eq!(theme.status_glyph(EntityStatus::Bonded), None);
+        assert_eq!(theme.status_glyph(EntityStatus::Mating), Some("♥"));
+        assert_eq!(theme.trophic.carnivore, "C");
+        assert_eq!(theme.trophic.herbivore, "●");
+        assert_eq!(theme.fit("🐺"), "🐺");
+    }
+
+    #[test]
+    fn test_bundled_themes_parse() {
+        let default = include_str!("../../../assets/themes/default.toml");
+        assert_eq!(
+            GlyphTheme::from_toml(default).unwrap(),
+            GlyphTheme::default()
+        );
+        let emoji = include_str!("../../../assets/themes/emoji.toml");
+        assert!(GlyphTheme::from_toml(emoji).unwrap().wide_glyphs);
+    }
+
+    #[test]
+    fn test_wide_glyphs_are_narrowed_unless_enabled() {
+        let theme = GlyphTheme::default();
+        assert_eq!(theme.fit("◉"), "◉");
+        assert_eq!(theme.fit("🐺x"), "x");
+        assert_eq!(theme.fit("🐺"), FALLBACK_GLYPH);
+    }
+}
@@ -0,0 +1,202 @@
+//! Circuit breakers for runaway positive feedback loops.
+//!
+//! A misconfigured world (e.g. reproduction cheaper than a single meal) can
+//! grow populations or food stocks exponentially until the process runs out of
+//! memory. The guardrail checks per-tick counters against the limits in
+//! [`GuardrailConfig`] and, when one is exceeded, produces a [`GuardrailTrip`]
+//! describing which breaker fired and the configuration most likely at fault.
+
+use crate::config::{AppConfig, GuardrailConfig};
+use serde::{Deserialize, Serialize};
+
+/// The counter watched by a circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BreakerKind {
+    /// Total number of living entities.
+    EntityCount,
+    /// Total number of food items on the map.
+    FoodCount,
+    /// Number of births produced in a single tick.
+    BirthRate,
+}
+
+impl BreakerKind {
+    /// Human-readable label used in events and the TUI prompt.
+    pub fn label(self) -> &'static str {
+        match self {
+            BreakerKind::EntityCount => "Entity count",
+            BreakerKind::FoodCount => "Food count",
+            BreakerKind::BirthRate => "Births per tick",
+        }
+    }
+}
+
+/// Per-tick counters inspected by the breakers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GuardrailInput {
+    pub population: usize,
+    pub food_count: usize,
+    pub births: usize,
+}
+
+/// Diagnostic record of a tripped breaker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuardrailTrip {
+    pub kind: BreakerKind,
+    pub observed: usize,
+    pub limit: usize,
+    pub tick: u64,
+    /// Configuration key(s) most likely responsible for the runaway growth.
+    pub suspected_source: String,
+}
+
+/// Checks all breakers, returning the first one that tripped.
+///
+/// Breakers are evaluated in order of severity: births per tick (the earliest
+/// signal of an exponential loop), then entity count, then food count.
+pub fn check(config: &AppConfig, input: GuardrailInput, tick: u64) -> Option<GuardrailTrip> {
+    let limits = &config.guardrail;
+    if !limits.enabled {
+        return None;
+    }
+
+    [
+        (BreakerKind::BirthRate, input.births),
+        (BreakerKind::EntityCount, input.population),
+        (BreakerKind::FoodCount, input.food_count),
+    ]
+    .into_iter()
+    .find(|&(kind, observed)| observed > limit_for(limits, kind))
+    .map(|(kind, observed)| GuardrailTrip {
+        kind,
+        observed,
+        limit: limit_for(limits, kind),
+        tick,
+        suspected_source: diagnose(kind, config),
+    })
+}
+
+/// Returns the configured limit for a breaker.
+pub fn limit_for(limits: &GuardrailConfig, kind: BreakerKind) -> usize {
+    match kind {
+        BreakerKind::EntityCount => limits.max_entities,
+        BreakerKind::FoodCount => limits.max_food,
+        BreakerKind::BirthRate => limits.max_births_per_tick,
+    }
+}
+
+/// Doubles the limit of a tripped breaker so the user can resume deliberately.
+pub fn raise_limit(limits: &mut GuardrailConfig, kind: BreakerKind) {
+    let limit = match kind {
+        BreakerKind::EntityCount => &mut limits.max_entities,
+        BreakerKind::FoodCount => &mut limits.max_food,
+        BreakerKind::BirthRate => &mut limits.max_births_per_tick,
+    };
+    *limit = limit.saturating_mul(2);
+}
+
+fn diagnose(kind: BreakerKind, config: &AppConfig) -> String {
+    let met = &config.metabolism;
+    let eco = &config.ecosystem;
+    match kind {
+        BreakerKind::EntityCount | BreakerKind::BirthRate => {
+            if met.reproduction_threshold <= met.food_value {
+                format!(
+                    "metabolism.reproduction_threshold ({}) <= metabolism.food_value ({}): a single meal funds reproduction",
+                    met.reproduction_threshold, met.food_value
+                )
+            } else if !eco.spawn_rate_limit_enabled {
+                "ecosystem.spawn_rate_limit_enabled = false: births per tick are uncapped"
+                    .to_string()
+            } else if met.base_idle_cost + met.base_move_cost < 0.05 {
+                format!(
+                    "metabolism.base_idle_cost ({}) + base_move_cost ({}) too low: entities barely starve",
+                    met.base_idle_cost, met.base_move_cost
+                )
+            } else {
+                "reproduction outpacing mortality: review metabolism and ecosystem costs"
+                    .to_string()
+            }
+        }
+        BreakerKind::FoodCount => {
+            if config.world.max_food > config.guardrail.max_food {
+                format!(
+                    "world.max_food ({}) exceeds guardrail.max_food ({})",
+                    config.world.max_food, config.guardrail.max_food
+                )
+            } else {
+                format!(
+                    "food injected outside spawning (god mode, scenario or migration); ecosystem.base_spawn_chance = {}",
+                    eco.base_spawn_chance
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_trip_within_limits() {
+        let config = AppConfig::default();
+        let input = GuardrailInput {
+            population: 100,
+            food_count: 200,
+            births: 5,
+        };
+        assert!(check(&config, input, 1).is_none());
+    }
+
+    #[test]
+    fn test_birth_rate_trips_first() {
+        let mut config = AppConfig::default();
+        config.guardrail.max_entities = 10;
+        config.guardrail.max_births_per_tick = 2;
+        let input = GuardrailInput {
+            population: 50,
+            food_count: 0,
+            births: 3,
+        };
+        let trip = check(&config, input, 7).expect("breaker should trip");
+        assert_eq!(trip.kind, BreakerKind::BirthRate);
+        assert_eq!(trip.observed, 3);
+        assert_eq!(trip.limit, 2);
+        assert_eq!(trip.tick, 7);
+    }
+
+    #[test]
+    fn test_disabled_guardrail_never_trips() {
+        let mut config = AppConfig::default();
+        config.guardrail.enabled = false;
+        config.guardrail.max_entities = 1;
+        let input = GuardrailInput {
+            population: 1_000_000,
+            ..Default::default()
+        };
+        assert!(check(&config, input, 1).is_none());
+    }
+
+    #[test]
+    fn test_diagnosis_names_cheap_reproduction() {
+        let mut config = AppConfig::default();
+        config.guardrail.max_entities = 10;
+        config.metabolism.reproduction_threshold = 20.0;
+        let input = GuardrailInput {
+            population: 11,
+            ..Default::default()
+        };
+        let trip = check(&config, input, 1).expect("breaker should trip");
+        assert!(trip
+            .suspected_source
+            .contains("metabolism.reproduction_threshold"));
+    }
+
+    #[test]
+    fn test_raise_limit_doubles() {
+        let mut limits = GuardrailConfig::default();
+        raise_limit(&mut limits, BreakerKind::FoodCount);
+        assert_eq!(limits.max_food, 200_000);
+    }
+}
@@ -78,16 +78,13 @@ impl InfluenceGrid {
 
         for ((x, y), presence) in lineage_presence {
             let idx = y * self.width as usize + x;
-            let mut strongest_l = None;
-            let mut max_p = 0.0;
-            for (lid, p) in presence {
-                if p > max_p {
-                    max_p = p;
-                    strongest_l = Some(lid);
-                }
-            }
+            // Ties go to the lowest lineage ID, not to hash order, so
+            // deterministic runs stay reproducible.
+            let strongest = presence
+                .into_iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)));
 
-            if let Some(lid) = strongest_l {
+            if let Some((lid, max_p)) = strongest {
                 let cell = &mut self.cells[idx];
                 if cell.dominant_lineage == Some(lid) {
                     cell.intensity = (cell.intensity + max_p).min(5.0);
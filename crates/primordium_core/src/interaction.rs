@@ -1,4 +1,5 @@
-use primordium_data::{Entity, Pathogen};
+use primordium_data::{Entity, InteractionRates, Pathogen};
+use std::collections::{BTreeMap, VecDeque};
 use uuid::Uuid;
 
 #[derive(Debug)]
@@ -72,4 +73,273 @@ pub enum InteractionCommand {
     Metamorphosis {
         target_idx: usize,
     },
+    Groom {
+        groomer_idx: usize,
+        target_idx: usize,
+    },
+    Challenge {
+        challenger_idx: usize,
+        target_idx: usize,
+    },
+}
+
+/// Interaction commands of each tracked kind issued during one tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InteractionCounts {
+    pub kills: u32,
+    pub shares: u32,
+    pub births: u32,
+    pub eats: u32,
+    pub builds: u32,
+    pub digs: u32,
+    pub bonds: u32,
+    pub grooms: u32,
+}
+
+impl InteractionCounts {
+    pub fn from_commands(commands: &[InteractionCommand]) -> Self {
+        let mut counts = Self::default();
+        for cmd in commands {
+            match cmd {
+                InteractionCommand::Kill { .. } => counts.kills += 1,
+                // Sharing emits a debit for the giver and a credit for the receiver;
+                // count each transfer once via its credit.
+                InteractionCommand::TransferEnergy { amount, .. } if *amount > 0.0 => {
+                    counts.shares += 1
+                }
+                InteractionCommand::Birth { .. } => counts.births += 1,
+                InteractionCommand::EatFood { .. } => counts.eats += 1,
+                InteractionCommand::Build { .. } => counts.builds += 1,
+                InteractionCommand::Dig { .. } => counts.digs += 1,
+                InteractionCommand::Bond { .. } => counts.bonds += 1,
+                InteractionCommand::Groom { .. } => counts.grooms += 1,
+                _ => {}
+            }
+        }
+        counts
+    }
+}
+
+/// Rolling window of per-tick interaction counts for behavior analytics.
+#[derive(Debug, Clone, Default)]
+pub struct InteractionRecorder {
+    window: usize,
+    history: VecDeque<InteractionCounts>,
+}
+
+impl InteractionRecorder {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            history: VecDeque::with_capacity(window.max(1)),
+        }
+    }
+
+    /// Resizes the window, dropping the oldest ticks if it shrank.
+    pub fn set_window(&mut self, window: usize) {
+        self.window = window.max(1);
+        while self.history.len() > self.window {
+            self.history.pop_front();
+        }
+    }
+
+    pub fn record(&mut self, counts: InteractionCounts) {
+        if self.history.len() >= self.window.max(1) {
+            self.history.pop_front();
+        }
+        self.history.push_back(counts);
+    }
+
+    /// Counts recorded for the most recent tick.
+    pub fn latest(&self) -> InteractionCounts {
+        self.history.back().copied().unwrap_or_default()
+    }
+
+    /// Mean commands per tick over the recorded window.
+    pub fn rates(&self) -> InteractionRates {
+        if self.history.is_empty() {
+            return InteractionRates::default();
+        }
+        let n = self.history.len() as f32;
+        let mean =
+            |f: fn(&InteractionCounts) -> u32| self.history.iter().map(f).sum::<u32>() as f32 / n;
+        InteractionRates {
+            kills: mean(|c| c.kills),
+            shares: mean(|c| c.shares),
+            births: mean(|c| c.births),
+            eats: mean(|c| c.eats),
+            builds: mean(|c| c.builds),
+            digs: mean(|c| c.digs),
+            bonds: mean(|c| c.bonds),
+            grooms: mean(|c| c.grooms),
+        }
+    }
+}
+
+/// Kind of pairwise interaction recorded in an [`InteractionGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InteractionKind {
+    Groom,
+    Challenge,
+}
+
+impl InteractionKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            InteractionKind::Groom => "groom",
+            InteractionKind::Challenge => "challenge",
+        }
+    }
+}
+
+/// How often one entity did something to another, and when it last did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InteractionEdge {
+    pub count: u32,
+    pub last_tick: u64,
+}
+
+/// Directed who-did-what-to-whom network between individual entities,
+/// exported as GraphViz DOT or CSV for social network analysis.
+#[derive(Debug, Clone, Default)]
+pub struct InteractionGraph {
+    edges: BTreeMap<(Uuid, Uuid, InteractionKind), InteractionEdge>,
+}
+
+impl InteractionGraph {
+    pub fn record(&mut self, from: Uuid, to: Uuid, kind: InteractionKind, tick: u64) {
+        let edge = self
+            .edges
+            .entry((from, to, kind))
+            .or_insert(InteractionEdge {
+                count: 0,
+                last_tick: tick,
+            });
+        edge.count += 1;
+        edge.last_tick = tick;
+    }
+
+    /// Drops edges not renewed since `tick`.
+    pub fn forget_before(&mut self, tick: u64) {
+        self.edges.retain(|_, edge| edge.last_tick >= tick);
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn edges(
+        &self,
+    ) -> impl Iterator<Item = (Uuid, Uuid, InteractionKind, InteractionEdge)> + '_ {
+        self.edges
+            .iter()
+            .map(|(&(from, to, kind), &edge)| (from, to, kind, edge))
+    }
+
+    /// GraphViz DOT rendering, with edges labelled by kind and count and
+    /// drawn thicker the more often they occurred.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph Interactions {\n");
+        dot.push_str("  node [shape=point];\n");
+        for (from, to, kind, edge) in self.edges() {
+            let width = 1.0 + (edge.count as f32).ln();
+            dot.push_str(&format!(
+                "  \"{from}\" -> \"{to}\" [label=\"{} x{}\", penwidth={width:.2}];\n",
+                kind.label(),
+                edge.count,
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// One `from,to,kind,count,last_tick` row per edge, with a header.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("from,to,kind,count,last_tick\n");
+        for (from, to, kind, edge) in self.edges() {
+            csv.push_str(&format!(
+                "{from},{to},{},{},{}\n",
+                kind.label(),
+                edge.count,
+                edge.last_tick
+            ));
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_share_once_per_transfer() {
+        let commands = vec![
+            InteractionCommand::TransferEnergy {
+                target_idx: 0,
+                amount: -5.0,
+            },
+            InteractionCommand::TransferEnergy {
+                target_idx: 1,
+                amount: 5.0,
+            },
+            InteractionCommand::Dig {
+                x: 1.0,
+                y: 1.0,
+                attacker_idx: 0,
+            },
+        ];
+        let counts = InteractionCounts::from_commands(&commands);
+        assert_eq!(counts.shares, 1);
+        assert_eq!(counts.digs, 1);
+        assert_eq!(counts.kills, 0);
+    }
+
+    #[test]
+    fn test_recorder_rates_over_window() {
+        let mut recorder = InteractionRecorder::new(2);
+        for kills in [10, 2, 4] {
+            recorder.record(InteractionCounts {
+                kills,
+                ..Default::default()
+            });
+        }
+        assert_eq!(recorder.latest().kills, 4);
+        assert!((recorder.rates().kills - 3.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_graph_counts_repeat_grooming_and_forgets_stale_edges() {
+        let (a, b, c) = (Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3));
+        let mut graph = InteractionGraph::default();
+        graph.record(a, b, InteractionKind::Groom, 5);
+        graph.record(a, b, InteractionKind::Groom, 9);
+        graph.record(c, a, InteractionKind::Groom, 2);
+
+        let edges: Vec<_> = graph.edges().collect();
+        assert_eq!(edges.len(), 2);
+        assert_eq!(
+            edges[0],
+            (
+                a,
+                b,
+                InteractionKind::Groom,
+                InteractionEdge {
+                    count: 2,
+                    last_tick: 9
+                }
+            )
+        );
+        assert!(graph
+            .to_dot()
+            .contains(&format!("\"{a}\" -> \"{b}\" [label=\"groom x2\"")));
+        assert_eq!(graph.to_csv().lines().count(), 3);
+
+        graph.forget_before(5);
+        assert_eq!(graph.len(), 1);
+    }
 }
@@ -0,0 +1,194 @@
+//! ECS introspection for developers and modders.
+//!
+//! [`EcsReport`] lists the archetypes of a `hecs::World` (every distinct
+//! combination of components some entity carries) with how many entities
+//! share each one. Organisms are expected to carry the full
+//! [`ORGANISM_COMPONENTS`] set; archetypes that identify as organisms but lack
+//! part of it are flagged, which is how a spawn path forgetting a component
+//! (or leaking a half-despawned entity) shows up.
+
+use primordium_data::{
+    Appearance, Egg, Food, Health, Identity, Intel, MetabolicNiche, Metabolism, Physics, Position,
+    Tags, Velocity,
+};
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+
+/// Components every organism is spawned with.
+pub const ORGANISM_COMPONENTS: [&str; 8] = [
+    "Identity",
+    "Position",
+    "Velocity",
+    "Appearance",
+    "Physics",
+    "Metabolism",
+    "Health",
+    "Intel",
+];
+
+fn known_components() -> [(&'static str, TypeId); 12] {
+    [
+        ("Identity", TypeId::of::<Identity>()),
+        ("Position", TypeId::of::<Position>()),
+        ("Velocity", TypeId::of::<Velocity>()),
+        ("Appearance", TypeId::of::<Appearance>()),
+        ("Physics", TypeId::of::<Physics>()),
+        ("Metabolism", TypeId::of::<Metabolism>()),
+        ("Health", TypeId::of::<Health>()),
+        ("Intel", TypeId::of::<Intel>()),
+        ("Tags", TypeId::of::<Tags>()),
+        ("Food", TypeId::of::<Food>()),
+        ("MetabolicNiche", TypeId::of::<MetabolicNiche>()),
+        ("Egg", TypeId::of::<Egg>()),
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchetypeKind {
+    Organism,
+    Food,
+    Egg,
+    Other,
+}
+
+impl ArchetypeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ArchetypeKind::Organism => "Organism",
+            ArchetypeKind::Food => "Food",
+            ArchetypeKind::Egg => "Egg",
+            ArchetypeKind::Other => "Other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArchetypeReport {
+    pub kind: ArchetypeKind,
+    /// Names of the known components this archetype carries.
+    pub components: Vec<&'static str>,
+    /// Components this module has no name for (e.g. added by a mod).
+    pub unknown: usize,
+    /// Organism components this archetype lacks; empty for other kinds.
+    pub missing: Vec<&'static str>,
+    pub count: u32,
+}
+
+impl ArchetypeReport {
+    pub fn has(&self, component: &str) -> bool {
+        self.components.contains(&component)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EcsReport {
+    /// Non-empty archetypes, most populated first.
+    pub archetypes: Vec<ArchetypeReport>,
+    pub entities: u32,
+}
+
+impl EcsReport {
+    pub fn of(ecs: &hecs::World) -> Self {
+        let known = known_components();
+        let mut archetypes: Vec<ArchetypeReport> = ecs
+            .archetypes()
+            .filter(|archetype| !archetype.is_empty())
+            .map(|archetype| {
+                let components: Vec<&'static str> = known
+                    .iter()
+                    .filter(|(_, id)| archetype.has_dynamic(*id))
+                    .map(|(name, _)| *name)
+                    .collect();
+                let unknown = archetype.component_types().len() - components.len();
+                let kind = if components.contains(&"Identity") {
+                    ArchetypeKind::Organism
+                } else if components.contains(&"Food") {
+                    ArchetypeKind::Food
+                } else if components.contains(&"Egg") {
+                    ArchetypeKind::Egg
+                } else {
+                    ArchetypeKind::Other
+                };
+                let missing = if kind == ArchetypeKind::Organism {
+                    ORGANISM_COMPONENTS
+                        .iter()
+                        .copied()
+                        .filter(|name| !components.contains(name))
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                ArchetypeReport {
+                    kind,
+                    components,
+                    unknown,
+                    missing,
+                    count: archetype.len(),
+                }
+            })
+            .collect();
+        archetypes.sort_by_key(|a| std::cmp::Reverse(a.count));
+        Self {
+            archetypes,
+            entities: ecs.len(),
+        }
+    }
+
+    /// Number of entities carrying `component`.
+    pub fn count_with(&self, component: &str) -> u32 {
+        self.archetypes
+            .iter()
+            .filter(|a| a.has(component))
+            .map(|a| a.count)
+            .sum()
+    }
+
+    pub fn count_of(&self, kind: ArchetypeKind) -> u32 {
+        self.archetypes
+            .iter()
+            .filter(|a| a.kind == kind)
+            .map(|a| a.count)
+            .sum()
+    }
+
+    /// Organism archetypes missing part of [`ORGANISM_COMPONENTS`].
+    pub fn incomplete(&self) -> impl Iterator<Item = &ArchetypeReport> {
+        self.archetypes.iter().filter(|a| !a.missing.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_groups_archetypes_and_flags_incomplete_organisms() {
+        let mut ecs = hecs::World::new();
+        let food = Food::new(1, 1, 0.5);
+        for _ in 0..3 {
+            ecs.spawn((
+                Position { x: 1.0, y: 1.0 },
+                MetabolicNiche(0.5),
+                food.clone(),
+            ));
+        }
+        let identity = Identity {
+            id: uuid::Uuid::new_v4(),
+            parent_id: None,
+        };
+        ecs.spawn((identity, Position { x: 0.0, y: 0.0 }, 7u8));
+
+        let report = EcsReport::of(&ecs);
+        assert_eq!(report.entities, 4);
+        assert_eq!(report.archetypes.len(), 2);
+        assert_eq!(report.archetypes[0].kind, ArchetypeKind::Food);
+        assert_eq!(report.count_of(ArchetypeKind::Food), 3);
+        assert_eq!(report.count_with("Position"), 4);
+
+        let leaked: Vec<_> = report.incomplete().collect();
+        assert_eq!(leaked.len(), 1);
+        assert_eq!(leaked[0].unknown, 1);
+        assert!(leaked[0].missing.contains(&"Intel"));
+        assert!(!leaked[0].missing.contains(&"Position"));
+    }
+}
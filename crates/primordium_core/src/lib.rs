@@ -31,7 +31,7 @@
 //! let brain = Brain::new_random_with_rng(&mut rng);
 //!
 //! // Process inputs to get outputs
-//! let inputs = [0.5; 29];
+//! let inputs = [0.5; 36];
 //! let hidden = [0.0; 6];
 //! let (outputs, _) = brain.forward(inputs, hidden);
 //! ```
@@ -41,16 +41,42 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+/// Anomaly detection on population metrics
+pub mod anomaly;
+/// Biome classification and named biome regions
+pub mod biome;
+/// Body size trade-offs of multi-cell organisms
+pub mod body;
 /// Neural network brain implementation with NEAT-lite topology
 pub mod brain;
 /// Configuration management for simulation parameters
 pub mod config;
+/// Procedural lineage crests for the terminal, cards and SVG
+pub mod crest;
+/// War, truce and alliance between lineages
+pub mod diplomacy;
+/// Per-entity energy ledger for metabolism debugging
+pub mod energy_ledger;
 /// Environmental state management (climate, seasons, disasters)
 pub mod environment;
+/// Cohort experiments with per-cohort parameter modifiers
+pub mod experiment;
+/// Heritable plant traits for evolving flora
+pub mod flora;
+/// Per-cell food density field (alternative to food entities)
+pub mod food_field;
+/// Population and food projections with confidence bands
+pub mod forecast;
+/// Glyph themes shared by the terminal and web renderers
+pub mod glyph;
+/// Circuit breakers that halt runaway population or food growth
+pub mod guardrail;
 /// Influence maps for collective intelligence and social coordination
 pub mod influence;
 /// Entity interaction handling (combat, bonding, sharing)
 pub mod interaction;
+/// Archetype and component counts of the ECS world
+pub mod introspection;
 /// Entity lifecycle management (birth, growth, death)
 pub mod lifecycle;
 /// Lineage tracking and registry for macroevolution
@@ -59,12 +85,28 @@ pub mod lineage_registry;
 pub mod lineage_tree;
 /// Performance metrics collection and logging
 pub mod metrics;
+/// Monuments inscribed with lineage history
+pub mod monument;
+/// Decaying map of where newborns differ most from their parents
+pub mod mutation_heat;
+/// Era, climate and daylight colour grading for renderers
+pub mod palette;
+/// Terrain-aware path planning for long-distance travel
+pub mod pathfinding;
 /// Pathogen simulation with contagion and immunity
 pub mod pathogen;
+/// Read-back of what an entity sensed, for debugging behaviour
+pub mod perception;
 /// Pheromone grid for chemical communication
 pub mod pheromone;
 /// Hardware-coupled pressure system (CPU/RAM metrics)
 pub mod pressure;
+/// Roads worn into the terrain by repeated movement
+pub mod road;
+/// Recent positions of a tracked entity
+pub mod route_trail;
+/// Calendar of scheduled world events
+pub mod schedule;
 /// Entity snapshots for parallel processing
 pub mod snapshot;
 /// Sound propagation and acoustic communication
@@ -75,10 +117,15 @@ pub mod spatial_hash;
 pub mod systems;
 /// Terrain grid with biome simulation
 pub mod terrain;
+/// Simulation and background worker thread pools
+pub mod threading;
 
 pub use brain::{BrainLogic, GenotypeLogic};
 pub use influence::{InfluenceGrid, InfluenceSource};
 pub use metrics::{init_logging, Metrics};
 pub use primordium_data::{Connection, Node, NodeType};
+pub use systems::civilization;
 pub use terrain::TerrainLogic;
+/// Anchoring of history hashes on public timestamp services
+#[cfg(feature = "blockchain")]
 pub mod blockchain;
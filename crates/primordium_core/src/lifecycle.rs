@@ -94,11 +94,12 @@ pub fn create_entity_with_rng<R: Rng>(x: f64, y: f64, tick: u64, rng: &mut R) ->
             has_metamorphosed: false,
             is_in_transit: false,
             migration_id: None,
+            hydration: 1.0,
         },
         health: Health {
             pathogen: None,
-            infection_timer: 0,
             immunity: 0.0,
+            effects: Vec::new(),
         },
         intel: Intel {
             genotype: std::sync::Arc::new(genotype),
@@ -110,11 +111,14 @@ pub fn create_entity_with_rng<R: Rng>(x: f64, y: f64, tick: u64, rng: &mut R) ->
             reputation: 1.0,
             rank: 0.5,
             bonded_to: None,
-            last_inputs: [0.0; 29],
+            last_inputs: Vec::new(),
             last_activations: primordium_data::Activations::default(),
             specialization: None,
             spec_meters: HashMap::new(),
             ancestral_traits: HashSet::new(),
+            memory: Default::default(),
+            deceptions: 0,
+            dominance: 0.0,
         },
     }
 }
@@ -1,9 +1,15 @@
+use crate::config::AppConfig;
+use crate::diplomacy::Diplomacy;
+use crate::systems::migration::{MigrationRoute, MigrationTotals};
 use primordium_data::Genotype;
-use primordium_data::{AncestralTrait, LineageGoal};
+use primordium_data::{AncestralTrait, DeathCause, LineageGoal};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use uuid::Uuid;
 
+/// Member deaths remembered per lineage to classify its extinction.
+pub const RECENT_DEATH_CAUSES: usize = 16;
+
 /// High-level metrics for an ancestral line.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LineageRecord {
@@ -16,15 +22,71 @@ pub struct LineageRecord {
     pub total_energy_consumed: f64,
     pub first_appearance_tick: u64,
     pub is_extinct: bool,
+    /// Tick at which the last member died (`None` while alive or for records
+    /// saved before extinction ticks were tracked).
+    #[serde(default)]
+    pub extinct_since_tick: Option<u64>,
+    /// Lineage this one speciated from, if known.
+    #[serde(default)]
+    pub parent_lineage_id: Option<Uuid>,
+    /// Number of tiny sibling records folded into this one by garbage collection.
+    #[serde(default)]
+    pub merged_siblings: usize,
+    /// Members that lived to maturity.
+    #[serde(default)]
+    pub juveniles_matured: usize,
+    /// Members that died before reaching maturity.
+    #[serde(default)]
+    pub juvenile_deaths: usize,
+    /// Causes of the most recent member deaths, newest last.
+    #[serde(default)]
+    pub recent_death_causes: VecDeque<DeathCause>,
     pub best_legend_id: Option<Uuid>,
     pub max_fitness_genotype: Option<Genotype>,
     pub completed_goals: std::collections::HashSet<LineageGoal>,
     pub ancestral_traits: std::collections::HashSet<AncestralTrait>,
     pub civilization_level: u32,
-    #[serde(skip, default = "crate::lineage_registry::create_shared_memory")]
+    /// Active long-distance migration, if any.
+    #[serde(default)]
+    pub migration: Option<MigrationRoute>,
+    #[serde(default = "crate::lineage_registry::create_shared_memory")]
     pub collective_memory: std::sync::Arc<std::sync::RwLock<HashMap<String, f32>>>,
 }
 
+impl LineageRecord {
+    /// Share of resolved juveniles that reached maturity, or `None` before
+    /// any has matured or died.
+    pub fn survival_to_maturity(&self) -> Option<f64> {
+        let resolved = self.juveniles_matured + self.juvenile_deaths;
+        (resolved > 0).then(|| self.juveniles_matured as f64 / resolved as f64)
+    }
+
+    /// Most common cause among the recent member deaths, ties going to the
+    /// more recent one; [`DeathCause::Unknown`] if none were recorded.
+    pub fn extinction_cause(&self) -> DeathCause {
+        DeathCause::ALL
+            .into_iter()
+            .filter_map(|cause| {
+                let count = self
+                    .recent_death_causes
+                    .iter()
+                    .filter(|&&c| c == cause)
+                    .count();
+                let last = self.recent_death_causes.iter().rposition(|&c| c == cause)?;
+                Some(((count, last), cause))
+            })
+            .max_by_key(|(key, _)| *key)
+            .map_or(DeathCause::Unknown, |(_, cause)| cause)
+    }
+
+    /// Value stored under `key` in the collective memory, or 0.
+    pub fn memory_value(&self, key: &str) -> f32 {
+        self.collective_memory
+            .read()
+            .map_or(0.0, |mem| *mem.get(key).unwrap_or(&0.0))
+    }
+}
+
 pub fn create_shared_memory() -> std::sync::Arc<std::sync::RwLock<HashMap<String, f32>>> {
     std::sync::Arc::new(std::sync::RwLock::new(HashMap::new()))
 }
@@ -41,20 +103,93 @@ impl Default for LineageRecord {
             total_energy_consumed: 0.0,
             first_appearance_tick: 0,
             is_extinct: false,
+            extinct_since_tick: None,
+            parent_lineage_id: None,
+            merged_siblings: 0,
+            juveniles_matured: 0,
+            juvenile_deaths: 0,
+            recent_death_causes: VecDeque::new(),
             best_legend_id: None,
             max_fitness_genotype: None,
             completed_goals: std::collections::HashSet::new(),
             ancestral_traits: std::collections::HashSet::new(),
             civilization_level: 0,
+            migration: None,
             collective_memory: create_shared_memory(),
         }
     }
 }
 
+/// Retention rules applied when garbage-collecting dead lineages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Extinct lineages that produced fewer members than this are "tiny".
+    pub min_entities_produced: usize,
+    /// Fold tiny extinct siblings (same parent lineage) into one record
+    /// instead of dropping them outright.
+    pub merge_tiny_siblings: bool,
+    /// Ticks an unreferenced lineage is kept after going extinct.
+    pub extinct_retention_ticks: u64,
+    /// Soft cap on the number of records; referenced lineages may exceed it.
+    pub max_lineages: usize,
+}
+
+impl RetentionPolicy {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            min_entities_produced: config.lineage_gc.min_entities_produced,
+            merge_tiny_siblings: config.lineage_gc.merge_tiny_siblings,
+            extinct_retention_ticks: config.world.lineage_extinction_age_threshold,
+            max_lineages: config.world.max_lineages,
+        }
+    }
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self::from_config(&AppConfig::default())
+    }
+}
+
+/// Number of lineage records removed by a garbage-collection pass.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineageGcReport {
+    /// Tiny extinct records folded into a sibling.
+    pub merged: usize,
+    /// Tiny extinct records dropped.
+    pub pruned_tiny: usize,
+    /// Records dropped after outliving the extinction retention window.
+    pub pruned_expired: usize,
+    /// Records evicted to respect the lineage cap.
+    pub pruned_over_capacity: usize,
+}
+
+impl LineageGcReport {
+    pub fn total(&self) -> usize {
+        self.merged + self.pruned_tiny + self.pruned_expired + self.pruned_over_capacity
+    }
+
+    fn accumulate(&mut self, other: &LineageGcReport) {
+        self.merged += other.merged;
+        self.pruned_tiny += other.pruned_tiny;
+        self.pruned_expired += other.pruned_expired;
+        self.pruned_over_capacity += other.pruned_over_capacity;
+    }
+}
+
 /// Persistent registry of all lineages that have ever existed in the world.
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct LineageRegistry {
     pub lineages: HashMap<Uuid, LineageRecord>,
+    /// Cumulative garbage-collection totals since the world was created.
+    #[serde(default)]
+    pub gc_totals: LineageGcReport,
+    /// Cumulative migration and path-planning counters.
+    #[serde(default)]
+    pub migration_totals: MigrationTotals,
+    /// War, truce and alliance between lineages.
+    #[serde(default)]
+    pub diplomacy: Diplomacy,
 }
 
 impl LineageRegistry {
@@ -78,6 +213,19 @@ impl LineageRegistry {
             entry.max_generation = gen;
         }
         entry.is_extinct = false;
+        entry.extinct_since_tick = None;
+    }
+
+    /// Links a freshly speciated lineage to the lineage it split from.
+    pub fn record_speciation(&mut self, child: Uuid, parent: Uuid) {
+        if child == parent {
+            return;
+        }
+        if let Some(record) = self.lineages.get_mut(&child) {
+            if record.parent_lineage_id.is_none() {
+                record.parent_lineage_id = Some(parent);
+            }
+        }
     }
 
     pub fn record_migration_in(&mut self, id: Uuid, gen: u32, tick: u64) {
@@ -95,13 +243,41 @@ impl LineageRegistry {
             entry.max_generation = gen;
         }
         entry.is_extinct = false;
+        entry.extinct_since_tick = None;
     }
 
-    pub fn record_death(&mut self, id: Uuid) {
+    pub fn record_death(&mut self, id: Uuid, tick: u64) {
         if let Some(record) = self.lineages.get_mut(&id) {
             record.current_population = record.current_population.saturating_sub(1);
             if record.current_population == 0 {
                 record.is_extinct = true;
+                record.extinct_since_tick = Some(tick);
+            }
+        }
+    }
+
+    /// Counts a member of `id` reaching maturity.
+    pub fn record_maturity(&mut self, id: Uuid) {
+        if let Some(record) = self.lineages.get_mut(&id) {
+            record.juveniles_matured += 1;
+        }
+    }
+
+    /// Counts a member of `id` dying before maturity. Call alongside
+    /// [`Self::record_death`].
+    pub fn record_juvenile_death(&mut self, id: Uuid) {
+        if let Some(record) = self.lineages.get_mut(&id) {
+            record.juvenile_deaths += 1;
+        }
+    }
+
+    /// Remembers why a member of `id` died. Call alongside
+    /// [`Self::record_death`].
+    pub fn record_death_cause(&mut self, id: Uuid, cause: DeathCause) {
+        if let Some(record) = self.lineages.get_mut(&id) {
+            record.recent_death_causes.push_back(cause);
+            if record.recent_death_causes.len() > RECENT_DEATH_CAUSES {
+                record.recent_death_causes.pop_front();
             }
         }
     }
@@ -128,12 +304,9 @@ impl LineageRegistry {
     }
 
     pub fn get_memory_value(&self, id: &Uuid, key: &str) -> f32 {
-        if let Some(record) = self.lineages.get(id) {
-            if let Ok(mem) = record.collective_memory.read() {
-                return *mem.get(key).unwrap_or(&0.0);
-            }
-        }
-        0.0
+        self.lineages
+            .get(id)
+            .map_or(0.0, |record| record.memory_value(key))
     }
 
     pub fn set_memory_value(&self, id: &Uuid, key: &str, value: f32) {
@@ -153,6 +326,33 @@ impl LineageRegistry {
         }
     }
 
+    /// Sends a living lineage toward `target`. The route is planned on the
+    /// next migration pass. Returns `false` for unknown or extinct lineages.
+    pub fn set_migration_target(&mut self, id: &Uuid, target: (u16, u16)) -> bool {
+        match self.lineages.get_mut(id) {
+            Some(record) if !record.is_extinct => {
+                record.migration = Some(MigrationRoute::new(target));
+                self.migration_totals.started += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Number of lineages with an active migration.
+    pub fn migrating_count(&self) -> usize {
+        self.lineages
+            .values()
+            .filter(|r| r.migration.is_some())
+            .count()
+    }
+
+    pub fn clear_migration(&mut self, id: &Uuid) {
+        if let Some(record) = self.lineages.get_mut(id) {
+            record.migration = None;
+        }
+    }
+
     pub fn get_traits(&self, id: &Uuid) -> std::collections::HashSet<AncestralTrait> {
         self.lineages
             .get(id)
@@ -250,51 +450,260 @@ impl LineageRegistry {
         }
     }
 
-    pub fn prune(&mut self) {
-        // Prune lineages that are extinct AND have produced less than 3 entities
-        // AND have no legendary representative (best_legend_id).
-        self.lineages.retain(|_, record| {
-            !record.is_extinct
-                || record.total_entities_produced >= 3
-                || record.best_legend_id.is_some()
-        });
-    }
+    /// Drops tiny extinct lineages, first folding tiny siblings together when
+    /// the policy allows it. Lineages in `protected` (those referenced by
+    /// fossils or legends) are never touched.
+    pub fn prune(
+        &mut self,
+        policy: &RetentionPolicy,
+        protected: &HashSet<Uuid>,
+    ) -> LineageGcReport {
+        let mut report = LineageGcReport::default();
+        let is_tiny = |id: &Uuid, record: &LineageRecord| {
+            record.is_extinct
+                && record.total_entities_produced < policy.min_entities_produced
+                && record.merged_siblings == 0
+                && record.best_legend_id.is_none()
+                && !protected.contains(id)
+        };
 
-    pub fn prune_extinct_old(&mut self, current_tick: u64, extinction_age_threshold: u64) {
-        let extinct_age = |record: &LineageRecord, tick: u64| -> u64 {
-            if !record.is_extinct {
-                return 0;
+        if policy.merge_tiny_siblings {
+            let mut families: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+            for (id, record) in &self.lineages {
+                if let Some(parent) = record.parent_lineage_id {
+                    if is_tiny(id, record) {
+                        families.entry(parent).or_default().push(*id);
+                    }
+                }
             }
-            tick.saturating_sub(record.first_appearance_tick)
+            for mut siblings in families.into_values().filter(|s| s.len() > 1) {
+                siblings.sort_by_key(|id| (self.lineages[id].first_appearance_tick, *id));
+                let survivor = siblings[0];
+                for id in &siblings[1..] {
+                    if let Some(absorbed) = self.lineages.remove(id) {
+                        if let Some(target) = self.lineages.get_mut(&survivor) {
+                            target.absorb(&absorbed);
+                        }
+                        self.reparent(*id, Some(survivor));
+                        report.merged += 1;
+                    }
+                }
+            }
+        }
+
+        let doomed: Vec<Uuid> = self
+            .lineages
+            .iter()
+            .filter(|(id, record)| is_tiny(id, record))
+            .map(|(id, _)| *id)
+            .collect();
+        report.pruned_tiny = self.remove_all(&doomed);
+
+        self.gc_totals.accumulate(&report);
+        report
+    }
+
+    /// Drops unreferenced lineages that have been extinct for longer than the
+    /// retention window, then evicts the least significant extinct lineages
+    /// until the registry fits within `max_lineages`.
+    pub fn prune_expired(
+        &mut self,
+        current_tick: u64,
+        policy: &RetentionPolicy,
+        protected: &HashSet<Uuid>,
+    ) -> LineageGcReport {
+        let mut report = LineageGcReport::default();
+        let evictable = |id: &Uuid, record: &LineageRecord| {
+            record.is_extinct && record.best_legend_id.is_none() && !protected.contains(id)
         };
 
-        self.lineages.retain(|_, record| {
-            !record.is_extinct
-                || record.best_legend_id.is_some()
-                || extinct_age(record, current_tick) < extinction_age_threshold
-        });
+        let expired: Vec<Uuid> = self
+            .lineages
+            .iter()
+            .filter(|(id, record)| {
+                let extinct_at = record
+                    .extinct_since_tick
+                    .unwrap_or(record.first_appearance_tick);
+                evictable(id, record)
+                    && current_tick.saturating_sub(extinct_at) >= policy.extinct_retention_ticks
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        report.pruned_expired = self.remove_all(&expired);
+
+        if self.lineages.len() > policy.max_lineages {
+            let mut candidates: Vec<_> = self
+                .lineages
+                .iter()
+                .filter(|(id, record)| evictable(id, record))
+                .map(|(id, record)| (record.total_entities_produced, *id))
+                .collect();
+            candidates.sort();
+            let excess = self.lineages.len() - policy.max_lineages;
+            let doomed: Vec<Uuid> = candidates
+                .into_iter()
+                .take(excess)
+                .map(|(_, id)| id)
+                .collect();
+            report.pruned_over_capacity = self.remove_all(&doomed);
+        }
+
+        self.gc_totals.accumulate(&report);
+        report
     }
 
-    pub fn prune_by_count(&mut self, max_lineages: usize) {
-        if self.lineages.len() <= max_lineages {
-            return;
+    /// Removes the given lineages and re-links their children to the nearest
+    /// surviving ancestor so no `parent_lineage_id` dangles.
+    fn remove_all(&mut self, ids: &[Uuid]) -> usize {
+        let mut removed = 0;
+        for id in ids {
+            if let Some(record) = self.lineages.remove(id) {
+                self.reparent(*id, record.parent_lineage_id);
+                removed += 1;
+            }
         }
+        removed
+    }
 
-        let mut lineage_vec: Vec<_> = self.lineages.iter().collect();
-        lineage_vec.sort_by_key(|&(_, record)| {
-            let is_active = !record.is_extinct;
-            let has_legend = record.best_legend_id.is_some();
-            let total_entities = record.total_entities_produced;
+    fn reparent(&mut self, from: Uuid, to: Option<Uuid>) {
+        let to = to.filter(|p| *p != from && self.lineages.contains_key(p));
+        for record in self.lineages.values_mut() {
+            if record.parent_lineage_id == Some(from) {
+                record.parent_lineage_id = to.filter(|p| *p != record.id);
+            }
+        }
+    }
+}
 
-            (!is_active, !has_legend, std::cmp::Reverse(total_entities))
-        });
+impl LineageRecord {
+    /// Folds the statistics of another (merged) record into this one.
+    fn absorb(&mut self, other: &LineageRecord) {
+        self.total_entities_produced += other.total_entities_produced;
+        self.peak_population = self.peak_population.max(other.peak_population);
+        self.max_generation = self.max_generation.max(other.max_generation);
+        self.total_energy_consumed += other.total_energy_consumed;
+        self.first_appearance_tick = self.first_appearance_tick.min(other.first_appearance_tick);
+        self.extinct_since_tick = self.extinct_since_tick.max(other.extinct_since_tick);
+        self.merged_siblings += other.merged_siblings + 1;
+        self.juveniles_matured += other.juveniles_matured;
+        self.juvenile_deaths += other.juvenile_deaths;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let keep_count = max_lineages;
-        let mut keep_ids: std::collections::HashSet<Uuid> = std::collections::HashSet::new();
-        for &(id, _) in lineage_vec.iter().take(keep_count) {
-            keep_ids.insert(*id);
+    fn extinct(
+        registry: &mut LineageRegistry,
+        parent: Option<Uuid>,
+        births: usize,
+        tick: u64,
+    ) -> Uuid {
+        let id = Uuid::new_v4();
+        for _ in 0..births {
+            registry.record_birth(id, 1, tick);
+        }
+        for _ in 0..births {
+            registry.record_death(id, tick + 10);
         }
+        if let Some(parent) = parent {
+            registry.record_speciation(id, parent);
+        }
+        id
+    }
+
+    #[test]
+    fn test_survival_to_maturity_counts_resolved_juveniles() {
+        let mut registry = LineageRegistry::new();
+        let id = Uuid::new_v4();
+        for _ in 0..4 {
+            registry.record_birth(id, 1, 0);
+        }
+        assert_eq!(registry.lineages[&id].survival_to_maturity(), None);
+        registry.record_maturity(id);
+        registry.record_juvenile_death(id);
+        registry.record_juvenile_death(id);
+        registry.record_juvenile_death(id);
+        assert_eq!(registry.lineages[&id].survival_to_maturity(), Some(0.25));
+    }
+
+    #[test]
+    fn test_prune_keeps_protected_lineages() {
+        let mut registry = LineageRegistry::new();
+        let fossilized = extinct(&mut registry, None, 1, 0);
+        let tiny = extinct(&mut registry, None, 1, 0);
+        let protected: HashSet<Uuid> = [fossilized].into_iter().collect();
+
+        let report = registry.prune(&RetentionPolicy::default(), &protected);
+
+        assert_eq!(report.pruned_tiny, 1);
+        assert!(registry.lineages.contains_key(&fossilized));
+        assert!(!registry.lineages.contains_key(&tiny));
+        assert_eq!(registry.gc_totals.pruned_tiny, 1);
+    }
+
+    #[test]
+    fn test_tiny_siblings_are_merged() {
+        let mut registry = LineageRegistry::new();
+        let parent = extinct(&mut registry, None, 10, 0);
+        let first = extinct(&mut registry, Some(parent), 1, 5);
+        let second = extinct(&mut registry, Some(parent), 2, 7);
+        let grandchild = extinct(&mut registry, Some(second), 10, 9);
+
+        let report = registry.prune(&RetentionPolicy::default(), &HashSet::new());
+
+        assert_eq!(report.merged, 1);
+        assert_eq!(report.pruned_tiny, 0);
+        let survivor = &registry.lineages[&first];
+        assert_eq!(survivor.total_entities_produced, 3);
+        assert_eq!(survivor.merged_siblings, 1);
+        assert!(!registry.lineages.contains_key(&second));
+        assert_eq!(
+            registry.lineages[&grandchild].parent_lineage_id,
+            Some(first),
+            "children of a merged record must point at the survivor"
+        );
+    }
+
+    #[test]
+    fn test_expiry_counts_from_extinction_tick() {
+        let mut registry = LineageRegistry::new();
+        let id = extinct(&mut registry, None, 10, 0);
+        let policy = RetentionPolicy {
+            extinct_retention_ticks: 100,
+            ..Default::default()
+        };
+
+        let report = registry.prune_expired(105, &policy, &HashSet::new());
+        assert_eq!(report.pruned_expired, 0, "extinct for only 95 ticks");
+
+        let report = registry.prune_expired(110, &policy, &HashSet::new());
+        assert_eq!(report.pruned_expired, 1);
+        assert!(!registry.lineages.contains_key(&id));
+    }
+
+    #[test]
+    fn test_capacity_never_evicts_living_or_protected() {
+        let mut registry = LineageRegistry::new();
+        let alive = Uuid::new_v4();
+        registry.record_birth(alive, 1, 0);
+        let fossilized = extinct(&mut registry, None, 5, 0);
+        let small = extinct(&mut registry, None, 5, 0);
+        let large = extinct(&mut registry, None, 50, 0);
+        let policy = RetentionPolicy {
+            max_lineages: 2,
+            extinct_retention_ticks: u64::MAX,
+            ..Default::default()
+        };
+        let protected: HashSet<Uuid> = [fossilized].into_iter().collect();
+
+        let report = registry.prune_expired(20, &policy, &protected);
 
-        self.lineages.retain(|id, _| keep_ids.contains(id));
+        assert_eq!(report.pruned_over_capacity, 2);
+        assert!(registry.lineages.contains_key(&alive));
+        assert!(registry.lineages.contains_key(&fossilized));
+        assert!(!registry.lineages.contains_key(&small));
+        assert!(!registry.lineages.contains_key(&large));
     }
 }
@@ -1,7 +1,8 @@
+use crate::lineage_registry::LineageRegistry;
 use petgraph::graph::{DiGraph, NodeIndex};
 use primordium_data::Entity;
 use primordium_data::Legend;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// A node in the Ancestry Tree representing an organism (living or dead).
@@ -136,3 +137,296 @@ impl AncestryTree {
         dot
     }
 }
+
+/// A lineage in the speciation tree.
+pub struct LineageNode {
+    pub id: Uuid,
+    pub name: String,
+    pub first_appearance_tick: u64,
+    pub current_population: usize,
+    pub is_extinct: bool,
+    /// Lineages that speciated from this one, oldest first.
+    pub children: Vec<Uuid>,
+}
+
+/// The speciation tree of every lineage in a [`LineageRegistry`]: each
+/// lineage hangs under the lineage it split from. Branch lengths of the
+/// exported tree are the ticks between a lineage's appearance and its
+/// parent's.
+pub struct LineageTree {
+    nodes: HashMap<Uuid, LineageNode>,
+    /// Lineages with no known parent, oldest first.
+    pub roots: Vec<Uuid>,
+}
+
+impl LineageTree {
+    /// Builds the tree from `registry`. Lineages whose parent was pruned
+    /// from the registry become roots.
+    pub fn build(registry: &LineageRegistry) -> Self {
+        let mut records: Vec<_> = registry.lineages.values().collect();
+        records.sort_by_key(|r| (r.first_appearance_tick, r.id));
+
+        let mut nodes: HashMap<Uuid, LineageNode> = records
+            .iter()
+            .map(|r| {
+                let node = LineageNode {
+                    id: r.id,
+                    name: r.name.clone(),
+                    first_appearance_tick: r.first_appearance_tick,
+                    current_population: r.current_population,
+                    is_extinct: r.is_extinct,
+                    children: Vec::new(),
+                };
+                (r.id, node)
+            })
+            .collect();
+
+        let mut roots = Vec::new();
+        for r in &records {
+            match r.parent_lineage_id.filter(|p| nodes.contains_key(p)) {
+                Some(parent) => nodes.get_mut(&parent).unwrap().children.push(r.id),
+                None => roots.push(r.id),
+            }
+        }
+
+        let mut tree = Self { nodes, roots };
+        // A parent cycle has no root to reach it from; break it at its
+        // oldest lineage.
+        let mut reached = HashSet::new();
+        for root in tree.roots.clone() {
+            tree.mark(root, &mut reached);
+        }
+        for record in &records {
+            if !reached.contains(&record.id) {
+                for node in tree.nodes.values_mut() {
+                    node.children.retain(|c| *c != record.id);
+                }
+                tree.roots.push(record.id);
+                tree.mark(record.id, &mut reached);
+            }
+        }
+        tree
+    }
+
+    fn mark(&self, id: Uuid, reached: &mut HashSet<Uuid>) {
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            if reached.insert(id) {
+                stack.extend(self.nodes[&id].children.iter().copied());
+            }
+        }
+    }
+
+    pub fn node(&self, id: &Uuid) -> Option<&LineageNode> {
+        self.nodes.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Living members of the lineage `id` and of every lineage descended
+    /// from it.
+    pub fn clade_population(&self, id: &Uuid) -> usize {
+        let mut total = 0;
+        let mut stack = vec![*id];
+        while let Some(id) = stack.pop() {
+            if let Some(node) = self.nodes.get(&id) {
+                total += node.current_population;
+                stack.extend(node.children.iter().copied());
+            }
+        }
+        total
+    }
+
+    fn branch_length(&self, node: &LineageNode, parent: Option<&LineageNode>) -> u64 {
+        node.first_appearance_tick
+            .saturating_sub(parent.map_or(0, |p| p.first_appearance_tick))
+    }
+
+    /// Export the tree in Newick format. Several roots hang under an
+    /// unnamed root at tick 0.
+    pub fn to_newick(&self) -> String {
+        let mut out = String::new();
+        if let [root] = self.roots.as_slice() {
+            self.write_newick(&mut out, &self.nodes[root], None);
+        } else {
+            out.push('(');
+            for (i, root) in self.roots.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                let node = &self.nodes[root];
+                self.write_newick(&mut out, node, None);
+                out.push_str(&format!(":{}", self.branch_length(node, None)));
+            }
+            out.push(')');
+        }
+        out.push_str(";\n");
+        out
+    }
+
+    fn write_newick(&self, out: &mut String, node: &LineageNode, parent: Option<&LineageNode>) {
+        if !node.children.is_empty() {
+            out.push('(');
+            for (i, child) in node.children.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                self.write_newick(out, &self.nodes[child], Some(node));
+            }
+            out.push(')');
+        }
+        out.push_str(&newick_label(&node.name));
+        if parent.is_some() {
+            out.push_str(&format!(":{}", self.branch_length(node, parent)));
+        }
+    }
+
+    /// Export the tree as a PhyloXML document, with each lineage's id,
+    /// population and extinction as clade properties.
+    pub fn to_phyloxml(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<phyloxml xmlns=\"http://www.phyloxml.org\">\n");
+        out.push_str("  <phylogeny rooted=\"true\">\n");
+        out.push_str("    <name>Primordium</name>\n");
+        if let [root] = self.roots.as_slice() {
+            self.write_clade(&mut out, &self.nodes[root], None, 2);
+        } else {
+            out.push_str("    <clade>\n");
+            for root in &self.roots {
+                self.write_clade(&mut out, &self.nodes[root], None, 3);
+            }
+            out.push_str("    </clade>\n");
+        }
+        out.push_str("  </phylogeny>\n</phyloxml>\n");
+        out
+    }
+
+    fn write_clade(
+        &self,
+        out: &mut String,
+        node: &LineageNode,
+        parent: Option<&LineageNode>,
+        depth: usize,
+    ) {
+        let pad = "  ".repeat(depth);
+        out.push_str(&format!("{pad}<clade>\n"));
+        out.push_str(&format!("{pad}  <name>{}</name>\n", xml_escape(&node.name)));
+        if parent.is_some() || self.roots.len() > 1 {
+            out.push_str(&format!(
+                "{pad}  <branch_length>{}</branch_length>\n",
+                self.branch_length(node, parent)
+            ));
+        }
+        let properties = [
+            ("id", "xsd:string", node.id.to_string()),
+            (
+                "population",
+                "xsd:integer",
+                node.current_population.to_string(),
+            ),
+            ("extinct", "xsd:boolean", node.is_extinct.to_string()),
+        ];
+        for (name, datatype, value) in properties {
+            out.push_str(&format!(
+                "{pad}  <property ref=\"primordium:{name}\" datatype=\"{datatype}\" applies_to=\"clade\">{value}</property>\n"
+            ));
+        }
+        for child in &node.children {
+            self.write_clade(out, &self.nodes[child], Some(node), depth + 1);
+        }
+        out.push_str(&format!("{pad}</clade>\n"));
+    }
+}
+
+/// `name` as a Newick label, quoted if it has characters Newick reserves.
+fn newick_label(name: &str) -> String {
+    if name
+        .chars()
+        .any(|c| c.is_whitespace() || "()[]':;,".contains(c))
+    {
+        format!("'{}'", name.replace('\'', "''"))
+    } else {
+        name.to_string()
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> (LineageRegistry, [Uuid; 4]) {
+        let ids = [1, 2, 3, 4].map(Uuid::from_u128);
+        let mut registry = LineageRegistry::new();
+        for (i, id) in ids.iter().enumerate() {
+            registry.record_birth(*id, 1, i as u64 * 100);
+        }
+        registry.record_speciation(ids[1], ids[0]);
+        registry.record_speciation(ids[2], ids[1]);
+        for (id, name) in ids.iter().zip(["Root", "Branch", "Deep Sea", "Loner"]) {
+            registry.lineages.get_mut(id).unwrap().name = name.to_string();
+        }
+        registry.record_death(ids[1], 250);
+        (registry, ids)
+    }
+
+    #[test]
+    fn test_newick_nests_lineages_under_their_parents() {
+        let (registry, _) = registry();
+        let tree = LineageTree::build(&registry);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(
+            tree.to_newick(),
+            "((('Deep Sea':100)Branch:100)Root:0,Loner:300);\n"
+        );
+    }
+
+    #[test]
+    fn test_phyloxml_carries_population_and_extinction() {
+        let (registry, ids) = registry();
+        let xml = LineageTree::build(&registry).to_phyloxml();
+        assert_eq!(xml.matches("<clade>").count(), 5);
+        assert_eq!(xml.matches("</clade>").count(), 5);
+        assert!(xml.contains("<name>Deep Sea</name>"));
+        assert!(xml.contains(&format!(">{}</property>", ids[3])));
+        assert!(xml.contains(
+            "\"primordium:extinct\" datatype=\"xsd:boolean\" applies_to=\"clade\">true<"
+        ));
+    }
+
+    #[test]
+    fn test_clade_population_sums_descendants() {
+        let (registry, ids) = registry();
+        let tree = LineageTree::build(&registry);
+        // Branch died out; Root and Deep Sea keep one member each.
+        assert_eq!(tree.clade_population(&ids[0]), 2);
+        assert_eq!(tree.clade_population(&ids[1]), 1);
+        assert_eq!(tree.clade_population(&ids[3]), 1);
+        assert_eq!(tree.clade_population(&Uuid::nil()), 0);
+    }
+
+    #[test]
+    fn test_parent_cycles_are_broken() {
+        let (mut registry, ids) = registry();
+        registry
+            .lineages
+            .get_mut(&ids[0])
+            .unwrap()
+            .parent_lineage_id = Some(ids[2]);
+        let tree = LineageTree::build(&registry);
+        assert_eq!(tree.roots, vec![ids[3], ids[0]]);
+        assert!(tree.to_newick().ends_with(";\n"));
+    }
+}
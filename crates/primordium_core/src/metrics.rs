@@ -1,19 +1,37 @@
 //! Performance metrics collection for the simulation.
 //!
 //! Provides structured logging and metrics tracking for monitoring
-//! simulation performance and health.
+//! simulation performance and health. Named counters, gauges and latency
+//! histograms can be exported in the Prometheus text format.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+/// Upper bounds, in seconds, of the buckets of latency histograms.
+pub const LATENCY_BUCKETS: [f64; 10] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.5, 1.0,
+];
+
+/// Observations of one latency, bucketed by [`LATENCY_BUCKETS`].
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Observations at most each bound; later ones only count towards `count`.
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
 /// Global metrics collector for simulation statistics.
 pub struct Metrics {
     tick_count: AtomicU64,
     entity_count: AtomicU64,
     food_count: AtomicU64,
     pub counters: Mutex<HashMap<String, AtomicU64>>,
+    gauges: Mutex<BTreeMap<String, f64>>,
+    histograms: Mutex<BTreeMap<String, Histogram>>,
     start_time: Instant,
 }
 
@@ -32,6 +50,8 @@ impl Metrics {
             entity_count: AtomicU64::new(0),
             food_count: AtomicU64::new(0),
             counters: Mutex::new(HashMap::new()),
+            gauges: Mutex::new(BTreeMap::new()),
+            histograms: Mutex::new(BTreeMap::new()),
             start_time: Instant::now(),
         }
     }
@@ -57,11 +77,100 @@ impl Metrics {
 
     /// Increments a named counter.
     pub fn increment_counter(&self, name: &str) {
+        self.add_counter(name, 1);
+    }
+
+    /// Adds `amount` to a named counter.
+    pub fn add_counter(&self, name: &str, amount: u64) {
         let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
         counters
             .entry(name.to_string())
             .or_insert_with(|| AtomicU64::new(0))
-            .fetch_add(1, Ordering::Relaxed);
+            .fetch_add(amount, Ordering::Relaxed);
+    }
+
+    /// Current value of a named counter; zero if it was never incremented.
+    #[must_use]
+    pub fn counter(&self, name: &str) -> u64 {
+        let counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        counters.get(name).map_or(0, |c| c.load(Ordering::Relaxed))
+    }
+
+    /// Sets a named gauge.
+    pub fn set_gauge(&self, name: &str, value: f64) {
+        let mut gauges = self.gauges.lock().unwrap_or_else(|e| e.into_inner());
+        gauges.insert(name.to_string(), value);
+    }
+
+    /// Records one observation of a named latency.
+    pub fn observe(&self, name: &str, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        let mut histograms = self.histograms.lock().unwrap_or_else(|e| e.into_inner());
+        let histogram = histograms.entry(name.to_string()).or_default();
+        for (bucket, bound) in histogram.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if secs <= bound {
+                *bucket += 1;
+            }
+        }
+        histogram.count += 1;
+        histogram.sum += secs;
+    }
+
+    /// Counters, gauges and latencies in the Prometheus text format, each
+    /// name prefixed with `prefix`. Names must be valid Prometheus metric
+    /// names; latencies are exported as histograms in seconds.
+    #[must_use]
+    pub fn render_prometheus(&self, prefix: &str) -> String {
+        let mut out = String::new();
+        // Writing into a String cannot fail.
+        let _ = writeln!(out, "# TYPE {prefix}uptime_seconds gauge");
+        let _ = writeln!(
+            out,
+            "{prefix}uptime_seconds {}",
+            self.elapsed().as_secs_f64()
+        );
+
+        let counters: BTreeMap<String, u64> = {
+            let counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+            counters
+                .iter()
+                .map(|(name, c)| (name.clone(), c.load(Ordering::Relaxed)))
+                .collect()
+        };
+        for (name, value) in counters {
+            let _ = writeln!(out, "# TYPE {prefix}{name} counter");
+            let _ = writeln!(out, "{prefix}{name} {value}");
+        }
+
+        let gauges = self
+            .gauges
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for (name, value) in gauges {
+            let _ = writeln!(out, "# TYPE {prefix}{name} gauge");
+            let _ = writeln!(out, "{prefix}{name} {value}");
+        }
+
+        let histograms = self
+            .histograms
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for (name, histogram) in histograms {
+            let _ = writeln!(out, "# TYPE {prefix}{name} histogram");
+            for (count, bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(out, "{prefix}{name}_bucket{{le=\"{bound}\"}} {count}");
+            }
+            let _ = writeln!(
+                out,
+                "{prefix}{name}_bucket{{le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(out, "{prefix}{name}_sum {}", histogram.sum);
+            let _ = writeln!(out, "{prefix}{name}_count {}", histogram.count);
+        }
+        out
     }
 
     /// Gets the current tick count.
@@ -135,6 +244,28 @@ mod tests {
         let metrics = Metrics::new();
         metrics.increment_counter("test");
         metrics.increment_counter("test");
-        // Counter value is not directly accessible, but function should not panic
+        metrics.add_counter("test", 3);
+        assert_eq!(metrics.counter("test"), 5);
+        assert_eq!(metrics.counter("missing"), 0);
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        let metrics = Metrics::new();
+        metrics.add_counter("relayed_total", 7);
+        metrics.set_gauge("peers", 3.0);
+        metrics.observe("query_seconds", Duration::from_millis(2));
+        metrics.observe("query_seconds", Duration::from_secs(2));
+
+        let text = metrics.render_prometheus("relay_");
+        assert!(text.contains("# TYPE relay_relayed_total counter\nrelay_relayed_total 7\n"));
+        assert!(text.contains("# TYPE relay_peers gauge\nrelay_peers 3\n"));
+        assert!(text.contains("# TYPE relay_query_seconds histogram\n"));
+        assert!(text.contains("relay_query_seconds_bucket{le=\"0.001\"} 0\n"));
+        assert!(text.contains("relay_query_seconds_bucket{le=\"0.0025\"} 1\n"));
+        assert!(text.contains("relay_query_seconds_bucket{le=\"1\"} 1\n"));
+        assert!(text.contains("relay_query_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(text.contains("relay_query_seconds_count 2\n"));
+        assert!(text.contains("relay_uptime_seconds "));
     }
 }
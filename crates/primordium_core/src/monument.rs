@@ -0,0 +1,181 @@
+//! Monuments raised by civilised lineages.
+//!
+//! Engineers of a lineage that has reached
+//! [`MonumentConfig::min_civilization_level`] raise monuments on their
+//! lineage's outposts. Each monument is inscribed with the lineage's
+//! achievements at the time. Monuments outlast their builders: when a lineage
+//! dies out its monuments stay on the map as ruins and are copied into the
+//! fossil registry, so a world accumulates a readable deep history.
+
+use crate::config::MonumentConfig;
+use crate::lineage_registry::{LineageRecord, LineageRegistry};
+use primordium_data::{LineageGoal, Monument};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const GOALS: [LineageGoal; 3] = [
+    LineageGoal::Expansion,
+    LineageGoal::Dominance,
+    LineageGoal::Resilience,
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MonumentRegistry {
+    /// Standing monuments and ruins, oldest first.
+    pub monuments: Vec<Monument>,
+}
+
+impl MonumentRegistry {
+    pub fn len(&self) -> usize {
+        self.monuments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.monuments.is_empty()
+    }
+
+    /// Monument on cell `(x, y)`, if any.
+    pub fn at(&self, x: u16, y: u16) -> Option<&Monument> {
+        self.monuments.iter().find(|m| m.x == x && m.y == y)
+    }
+
+    /// Closest monument within `radius` cells of `(x, y)`.
+    pub fn nearest(&self, x: f64, y: f64, radius: f64) -> Option<&Monument> {
+        self.monuments
+            .iter()
+            .map(|m| (m, (f64::from(m.x) - x).hypot(f64::from(m.y) - y)))
+            .filter(|(_, d)| *d <= radius)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(m, _)| m)
+    }
+
+    /// Monuments of `lineage_id` that are not ruins.
+    pub fn standing(&self, lineage_id: Uuid) -> usize {
+        self.monuments
+            .iter()
+            .filter(|m| m.lineage_id == lineage_id && !m.is_ruin())
+            .count()
+    }
+
+    /// Whether `record`'s lineage may raise a monument on `(x, y)`.
+    pub fn can_raise(
+        &self,
+        record: &LineageRecord,
+        x: u16,
+        y: u16,
+        config: &MonumentConfig,
+    ) -> bool {
+        config.enabled
+            && !record.is_extinct
+            && record.civilization_level >= config.min_civilization_level
+            && self.standing(record.id) < config.max_per_lineage
+            && self
+                .nearest(f64::from(x), f64::from(y), config.min_spacing)
+                .is_none()
+    }
+
+    /// Raises a monument inscribed with `record`'s achievements.
+    pub fn raise(
+        &mut self,
+        record: &LineageRecord,
+        color_rgb: (u8, u8, u8),
+        x: u16,
+        y: u16,
+        tick: u64,
+    ) -> &Monument {
+        self.monuments.push(Monument {
+            lineage_id: record.id,
+            name: record.name.clone(),
+            color_rgb,
+            x,
+            y,
+            raised_tick: tick,
+            civilization_level: record.civilization_level,
+            max_generation: record.max_generation,
+            population: record.current_population,
+            peak_population: record.peak_population,
+            total_offspring: record.total_entities_produced as u32,
+            goals: GOALS
+                .into_iter()
+                .filter(|g| record.completed_goals.contains(g))
+                .collect(),
+            extinct_tick: None,
+        });
+        &self.monuments[self.monuments.len() - 1]
+    }
+
+    /// Turns the monuments of lineages that have died out (or been pruned
+    /// from `registry`) into ruins, returning the newly ruined ones.
+    pub fn weather(&mut self, registry: &LineageRegistry, tick: u64) -> Vec<Monument> {
+        let mut ruined = Vec::new();
+        for monument in self.monuments.iter_mut().filter(|m| !m.is_ruin()) {
+            let record = registry.lineages.get(&monument.lineage_id);
+            if record.is_none_or(|r| r.is_extinct) {
+                monument.extinct_tick =
+                    Some(record.and_then(|r| r.extinct_since_tick).unwrap_or(tick));
+                ruined.push(monument.clone());
+            }
+        }
+        ruined
+    }
+
+    /// Scales every monument's position by `(sx, sy)`, keeping it on a
+    /// `width` x `height` map.
+    pub fn rescale(&mut self, sx: f64, sy: f64, width: u16, height: u16) {
+        for m in &mut self.monuments {
+            m.x = ((f64::from(m.x) * sx) as u16).min(width - 1);
+            m.y = ((f64::from(m.y) * sy) as u16).min(height - 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn civilised(registry: &mut LineageRegistry, id: Uuid) {
+        registry.record_birth(id, 4, 0);
+        let record = registry.lineages.get_mut(&id).unwrap();
+        record.civilization_level = 2;
+        record.completed_goals.insert(LineageGoal::Resilience);
+    }
+
+    #[test]
+    fn test_raise_respects_level_quota_and_spacing() {
+        let config = MonumentConfig::default();
+        let mut registry = LineageRegistry::new();
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        civilised(&mut registry, a);
+        registry.record_birth(b, 1, 0);
+        let mut monuments = MonumentRegistry::default();
+
+        assert!(!monuments.can_raise(&registry.lineages[&b], 50, 50, &config));
+        assert!(monuments.can_raise(&registry.lineages[&a], 50, 50, &config));
+        let raised = monuments.raise(&registry.lineages[&a], (1, 2, 3), 50, 50, 7);
+        assert_eq!(raised.goals, vec![LineageGoal::Resilience]);
+        assert_eq!(raised.max_generation, 4);
+
+        assert!(!monuments.can_raise(&registry.lineages[&a], 55, 50, &config));
+        for i in 1..config.max_per_lineage as u16 {
+            monuments.raise(&registry.lineages[&a], (1, 2, 3), 50 + i * 20, 50, 8);
+        }
+        assert!(!monuments.can_raise(&registry.lineages[&a], 50, 100, &config));
+    }
+
+    #[test]
+    fn test_monuments_of_extinct_lineages_become_ruins_once() {
+        let mut registry = LineageRegistry::new();
+        let id = Uuid::from_u128(1);
+        civilised(&mut registry, id);
+        let mut monuments = MonumentRegistry::default();
+        monuments.raise(&registry.lineages[&id], (1, 2, 3), 5, 5, 10);
+
+        assert!(monuments.weather(&registry, 20).is_empty());
+        registry.record_death(id, 30);
+        let ruined = monuments.weather(&registry, 31);
+        assert_eq!(ruined.len(), 1);
+        assert_eq!(ruined[0].extinct_tick, Some(30));
+        assert!(monuments.at(5, 5).is_some_and(Monument::is_ruin));
+        assert!(monuments.weather(&registry, 40).is_empty());
+    }
+}
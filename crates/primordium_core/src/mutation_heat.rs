@@ -0,0 +1,123 @@
+//! Mutation heat: where evolution is happening.
+//!
+//! Every birth deposits the newborn's genetic distance from its parent into
+//! the cell it is born in, along with a unit of birth weight. Both fade by
+//! half every `mutation_heat.half_life` ticks, so a cell's heat is the
+//! recency-weighted genetic change produced there and hot cells mark where
+//! lineages are currently adapting. The world-wide ratio of heat to birth
+//! weight is the recency-weighted mean distance of newborns, which drives
+//! the `evolutionary_velocity` stat.
+
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Birth weight below which recent births are too few to speak for the
+/// population, and velocity reads zero.
+const MIN_BIRTH_WEIGHT: f32 = 0.01;
+
+/// Heat below which a cell is cleared.
+const MIN_HEAT: f32 = 1e-4;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct MutationHeatGrid {
+    pub width: u16,
+    pub height: u16,
+    /// Decayed sum of newborns' genetic distances, per cell.
+    pub heat: Vec<f32>,
+    /// Decayed count of births, per cell.
+    pub births: Vec<f32>,
+}
+
+impl Default for MutationHeatGrid {
+    fn default() -> Self {
+        Self::new(100, 100)
+    }
+}
+
+impl MutationHeatGrid {
+    pub fn new(width: u16, height: u16) -> Self {
+        let size = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            heat: vec![0.0; size],
+            births: vec![0.0; size],
+        }
+    }
+
+    fn index(&self, x: f64, y: f64) -> usize {
+        let ix = (x.max(0.0) as usize).min(self.width as usize - 1);
+        let iy = (y.max(0.0) as usize).min(self.height as usize - 1);
+        iy * self.width as usize + ix
+    }
+
+    /// Records a birth at `(x, y)` with the newborn's genetic `distance`
+    /// from its parent.
+    pub fn record(&mut self, x: f64, y: f64, distance: f32) {
+        let idx = self.index(x, y);
+        self.heat[idx] += distance.max(0.0);
+        self.births[idx] += 1.0;
+    }
+
+    /// Fades every cell by one tick of a `half_life`-tick half-life.
+    pub fn decay(&mut self, half_life: f32) {
+        let factor = 0.5f32.powf(1.0 / half_life);
+        for (heat, births) in self.heat.iter_mut().zip(&mut self.births) {
+            *heat *= factor;
+            *births *= factor;
+            if *heat < MIN_HEAT && *births < MIN_HEAT {
+                *heat = 0.0;
+                *births = 0.0;
+            }
+        }
+    }
+
+    /// Heat of the cell at `(x, y)`.
+    pub fn get(&self, x: f64, y: f64) -> f32 {
+        self.heat[self.index(x, y)]
+    }
+
+    /// Hottest cell's heat, for scaling the overlay.
+    pub fn max_heat(&self) -> f32 {
+        self.heat.iter().copied().fold(0.0, f32::max)
+    }
+
+    /// Recency-weighted mean genetic distance of newborns across the world,
+    /// or zero when there have been no recent births.
+    pub fn velocity(&self) -> f32 {
+        let births: f32 = self.births.iter().sum();
+        if births < MIN_BIRTH_WEIGHT {
+            return 0.0;
+        }
+        self.heat.iter().sum::<f32>() / births
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat_fades_by_half_each_half_life() {
+        let mut grid = MutationHeatGrid::new(10, 10);
+        grid.record(2.5, 3.5, 0.8);
+        grid.record(2.1, 3.9, 0.4);
+        grid.record(7.0, 7.0, 0.1);
+        assert!((grid.get(2.0, 3.0) - 1.2).abs() < 1e-6);
+        assert!((grid.velocity() - 1.3 / 3.0).abs() < 1e-6);
+
+        for _ in 0..100 {
+            grid.decay(100.0);
+        }
+        assert!((grid.get(2.0, 3.0) - 0.6).abs() < 1e-4);
+        // Heat and birth weight fade together, so the mean holds.
+        assert!((grid.velocity() - 1.3 / 3.0).abs() < 1e-4);
+
+        for _ in 0..2_000 {
+            grid.decay(100.0);
+        }
+        assert_eq!(grid.velocity(), 0.0);
+        assert_eq!(grid.max_heat(), 0.0);
+    }
+}
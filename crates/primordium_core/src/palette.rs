@@ -0,0 +1,119 @@
+//! Environmental colour grading of the world view.
+//!
+//! The climate, era and time of day change how the world plays but not how
+//! it looks. A [`Palette`] derived from the environment grades every colour
+//! a renderer draws: scorching climates push towards red, ice ages towards
+//! blue, each era adds a faint cast of its own and night dims everything.
+//! The extreme regimes also wash empty ground in their colour so they read
+//! at a glance. Both the terminal and the browser renderer apply it.
+
+use crate::environment::{ClimateState, Environment, Era};
+
+/// Number of steps daylight is graded in, so a palette changes a handful of
+/// times a day rather than every tick.
+const LIGHT_STEPS: f32 = 8.0;
+
+/// Brightness of the darkest night, as a fraction of full daylight.
+const NIGHT_BRIGHTNESS: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    /// Gain of the red, green and blue channels.
+    pub gain: [f32; 3],
+    /// Background of empty cells, if the regime has one.
+    pub wash: Option<(u8, u8, u8)>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+impl Palette {
+    /// Leaves every colour as it is.
+    pub const NEUTRAL: Palette = Palette {
+        gain: [1.0; 3],
+        wash: None,
+    };
+
+    /// Palette of the current climate, era and time of day.
+    #[must_use]
+    pub fn from_environment(env: &Environment) -> Self {
+        let (gain, wash) = if env.is_ice_age() {
+            ([0.7, 0.85, 1.3], Some((0, 16, 48)))
+        } else {
+            match env.climate() {
+                ClimateState::Scorching => ([1.3, 0.8, 0.6], Some((48, 10, 0))),
+                ClimateState::Hot => ([1.15, 0.92, 0.8], None),
+                ClimateState::Temperate | ClimateState::Warm => ([1.0; 3], None),
+            }
+        };
+        let cast = match env.current_era {
+            Era::Primordial => [0.95, 1.05, 0.95],
+            Era::Expansion => [1.0; 3],
+            Era::Strife => [1.05, 0.95, 0.95],
+            Era::Enlightenment => [0.97, 1.0, 1.06],
+        };
+        let daylight = (env.light_level().clamp(0.0, 1.0) * LIGHT_STEPS).round() / LIGHT_STEPS;
+        let brightness = NIGHT_BRIGHTNESS + (1.0 - NIGHT_BRIGHTNESS) * daylight;
+
+        let dim = |c: u8| (f32::from(c) * brightness).round() as u8;
+        Self {
+            gain: std::array::from_fn(|i| gain[i] * cast[i] * brightness),
+            wash: wash.map(|(r, g, b)| (dim(r), dim(g), dim(b))),
+        }
+    }
+
+    /// `rgb` graded by the palette.
+    #[must_use]
+    pub fn apply(&self, (r, g, b): (u8, u8, u8)) -> (u8, u8, u8) {
+        let grade = |c: u8, gain: f32| (f32::from(c) * gain).round().clamp(0.0, 255.0) as u8;
+        (
+            grade(r, self.gain[0]),
+            grade(g, self.gain[1]),
+            grade(b, self.gain[2]),
+        )
+    }
+
+    #[must_use]
+    pub fn is_neutral(&self) -> bool {
+        *self == Self::NEUTRAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noon() -> Environment {
+        let mut env = Environment {
+            current_era: Era::Expansion,
+            ..Environment::default()
+        };
+        env.world_time = env.day_cycle_ticks / 4;
+        env
+    }
+
+    #[test]
+    fn test_regimes_grade_colours() {
+        assert!(Palette::from_environment(&noon()).is_neutral());
+
+        let mut scorching = noon();
+        scorching.god_climate_override = Some(ClimateState::Scorching);
+        let (r, _, b) = Palette::from_environment(&scorching).apply((100, 100, 100));
+        assert!(r > 100 && b < 100);
+
+        let mut ice_age = noon();
+        ice_age.ice_age_timer = 60;
+        let palette = Palette::from_environment(&ice_age);
+        let (r, _, b) = palette.apply((100, 100, 100));
+        assert!(b > 100 && r < 100);
+        assert!(palette.wash.is_some());
+
+        let mut night = noon();
+        night.world_time = night.day_cycle_ticks * 3 / 4;
+        let (r, g, b) = Palette::from_environment(&night).apply((200, 200, 200));
+        assert!(r < 200 && r == g && g == b);
+    }
+}
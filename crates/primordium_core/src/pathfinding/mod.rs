@@ -0,0 +1,242 @@
+//! Path planning over the terrain cost map.
+//!
+//! Entering a cell costs `1 / movement_modifier` of its terrain, so rivers are
+//! cheap, mountains are expensive and walls cannot be entered. Moves are
+//! 8-connected; diagonals cost `√2` times as much and may not cut a wall
+//! corner.
+//!
+//! [`plan`] is a best-first search whose heuristic is scaled by a weight. A
+//! weight of 1 gives optimal paths; larger weights make the search greedy, so
+//! it expands far fewer cells at the price of slightly longer routes.
+//! Returned paths are compressed to the cells where the direction changes, as
+//! in jump-point search. When the expansion budget runs out, the path leads
+//! to the explored cell closest to the goal instead.
+//!
+//! Systems that plan many paths go through the budgeted, caching
+//! [`PathService`] rather than calling [`plan`] directly.
+
+pub mod service;
+
+pub use service::{PathQuery, PathService, PathServiceStats};
+
+use crate::terrain::{TerrainGrid, TerrainLogic};
+use primordium_data::TerrainType;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Heuristic weight used by [`plan_greedy`].
+pub const GREEDY_WEIGHT: f32 = 2.0;
+
+/// Cheapest possible cost of entering a cell (a river).
+const MIN_STEP_COST: f32 = 1.0 / 1.5;
+
+/// What a single planning query cost and produced.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlanMetrics {
+    /// Cells taken off the open list.
+    pub expanded: usize,
+    /// Terrain cost of the returned path.
+    pub cost: f32,
+    /// Cell-to-cell moves along the returned path.
+    pub steps: usize,
+    /// Whether the path ends at the goal rather than the closest explored cell.
+    pub reached_goal: bool,
+}
+
+/// A planned route, excluding the start cell.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathPlan {
+    /// Cells where the route turns, ending with its last cell.
+    pub waypoints: Vec<(u16, u16)>,
+    pub metrics: PlanMetrics,
+}
+
+/// Cost of entering `(x, y)`, or `None` if it is impassable.
+pub fn step_cost(terrain: &TerrainGrid, x: u16, y: u16) -> Option<f32> {
+    let terrain_type = terrain.get_cell(x, y).terrain_type;
+    if terrain_type == TerrainType::Wall {
+        return None;
+    }
+    Some(1.0 / terrain_type.movement_modifier().max(0.1) as f32)
+}
+
+/// [`plan`] with [`GREEDY_WEIGHT`].
+pub fn plan_greedy(
+    terrain: &TerrainGrid,
+    start: (u16, u16),
+    goal: (u16, u16),
+    max_expansions: usize,
+) -> PathPlan {
+    plan(terrain, start, goal, GREEDY_WEIGHT, max_expansions)
+}
+
+/// Plans a route from `start` to `goal`, expanding at most `max_expansions`
+/// cells. Ties are broken by cell index, so results are deterministic.
+pub fn plan(
+    terrain: &TerrainGrid,
+    start: (u16, u16),
+    goal: (u16, u16),
+    heuristic_weight: f32,
+    max_expansions: usize,
+) -> PathPlan {
+    let (width, height) = (terrain.width, terrain.height);
+    if width == 0 || height == 0 {
+        return PathPlan::default();
+    }
+    let clamp = |(x, y): (u16, u16)| (x.min(width - 1), y.min(height - 1));
+    let (start, goal) = (clamp(start), clamp(goal));
+    let index = |(x, y): (u16, u16)| y as u32 * width as u32 + x as u32;
+    let cell = |i: u32| ((i % width as u32) as u16, (i / width as u32) as u16);
+    let heuristic = |c: (u16, u16)| octile(c, goal) * MIN_STEP_COST * heuristic_weight;
+
+    // Best known cost and parent per reached cell.
+    let mut nodes: HashMap<u32, (f32, u32)> = HashMap::new();
+    let mut open = BinaryHeap::new();
+    let start_idx = index(start);
+    nodes.insert(start_idx, (0.0, start_idx));
+    open.push(OpenNode {
+        priority: heuristic(start),
+        idx: start_idx,
+    });
+
+    let mut expanded = 0;
+    let mut closest = (heuristic(start), start_idx);
+    let mut reached_goal = false;
+    while let Some(OpenNode { priority, idx }) = open.pop() {
+        let g = nodes[&idx].0;
+        let c = cell(idx);
+        if priority > g + heuristic(c) + f32::EPSILON {
+            continue; // Stale entry.
+        }
+        let h = heuristic(c);
+        if h < closest.0 {
+            closest = (h, idx);
+        }
+        if c == goal {
+            closest = (0.0, idx);
+            reached_goal = true;
+            break;
+        }
+        if expanded >= max_expansions {
+            break;
+        }
+        expanded += 1;
+
+        for (dx, dy) in NEIGHBORS {
+            let nx = c.0 as i32 + dx;
+            let ny = c.1 as i32 + dy;
+            if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
+                continue;
+            }
+            let next = (nx as u16, ny as u16);
+            let Some(cost) = step_cost(terrain, next.0, next.1) else {
+                continue;
+            };
+            let diagonal = dx != 0 && dy != 0;
+            if diagonal
+                && (step_cost(terrain, next.0, c.1).is_none()
+                    || step_cost(terrain, c.0, next.1).is_none())
+            {
+                continue;
+            }
+            let next_g = g + if diagonal {
+                cost * std::f32::consts::SQRT_2
+            } else {
+                cost
+            };
+            let next_idx = index(next);
+            if nodes
+                .get(&next_idx)
+                .is_some_and(|(known, _)| *known <= next_g)
+            {
+                continue;
+            }
+            nodes.insert(next_idx, (next_g, idx));
+            open.push(OpenNode {
+                priority: next_g + heuristic(next),
+                idx: next_idx,
+            });
+        }
+    }
+
+    let end = closest.1;
+    let mut cells = Vec::new();
+    let mut cur = end;
+    while cur != start_idx {
+        cells.push(cell(cur));
+        cur = nodes[&cur].1;
+    }
+    cells.reverse();
+
+    PathPlan {
+        metrics: PlanMetrics {
+            expanded,
+            cost: nodes[&end].0,
+            steps: cells.len(),
+            reached_goal,
+        },
+        waypoints: compress(start, &cells),
+    }
+}
+
+/// Keeps only the cells where the direction of travel changes, plus the last.
+fn compress(start: (u16, u16), cells: &[(u16, u16)]) -> Vec<(u16, u16)> {
+    let direction = |a: (u16, u16), b: (u16, u16)| {
+        (
+            (b.0 as i32 - a.0 as i32).signum(),
+            (b.1 as i32 - a.1 as i32).signum(),
+        )
+    };
+    let mut waypoints = Vec::new();
+    let mut prev = start;
+    for (i, &c) in cells.iter().enumerate() {
+        match cells.get(i + 1) {
+            Some(&next) if direction(prev, c) == direction(c, next) => {}
+            _ => waypoints.push(c),
+        }
+        prev = c;
+    }
+    waypoints
+}
+
+fn octile(a: (u16, u16), b: (u16, u16)) -> f32 {
+    let dx = (a.0 as f32 - b.0 as f32).abs();
+    let dy = (a.1 as f32 - b.1 as f32).abs();
+    dx.max(dy) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dy)
+}
+
+const NEIGHBORS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+#[derive(PartialEq)]
+struct OpenNode {
+    priority: f32,
+    idx: u32,
+}
+
+impl Eq for OpenNode {}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Min-heap on priority, then on index for determinism.
+        other
+            .priority
+            .total_cmp(&self.priority)
+            .then_with(|| other.idx.cmp(&self.idx))
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,174 @@
+//! Shared, budget-limited path planning.
+//!
+//! Systems ask a [`PathService`] for paths instead of planning inline. A query
+//! that is not cached is queued, and [`PathService::process`] runs queued A*
+//! queries once per tick until `pathfinding.queries_per_tick` queries or
+//! `pathfinding.expansions_per_tick` expanded cells are used up, so planning
+//! cost stays bounded however many entities want a path.
+//!
+//! Finished paths are cached by query. A path is dropped when the terrain
+//! type of any cell changes in a chunk it crosses; changes elsewhere may make
+//! a cached path suboptimal but never impassable, so they are ignored.
+
+use super::{plan, PathPlan};
+use crate::config::PathfindingConfig;
+use crate::terrain::{TerrainChunk, TerrainGrid, CHUNK_ROWS};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+/// A request for a path between two cells.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PathQuery {
+    pub start: (u16, u16),
+    pub goal: (u16, u16),
+}
+
+/// Cumulative service counters.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct PathServiceStats {
+    /// Queries planned.
+    pub queries: u64,
+    /// Requests answered from the cache.
+    pub cache_hits: u64,
+    /// Cached paths dropped after a terrain change.
+    pub invalidated: u64,
+    /// Cells expanded over all queries.
+    pub expanded: u64,
+    /// Ticks that ended with queries still waiting for budget.
+    pub backlogged_ticks: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct PathService {
+    queue: VecDeque<PathQuery>,
+    queued: HashSet<PathQuery>,
+    cache: HashMap<PathQuery, Arc<PathPlan>>,
+    /// Cached queries, oldest first.
+    cache_order: VecDeque<PathQuery>,
+    /// Terrain type fingerprint per chunk when last processed.
+    chunk_types: Vec<u64>,
+    pub stats: PathServiceStats,
+}
+
+impl PathService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached path for `query`, or queues it and returns `None`.
+    /// Queuing the same query again before it is planned has no effect.
+    pub fn request(&mut self, query: PathQuery) -> Option<Arc<PathPlan>> {
+        if let Some(path) = self.cache.get(&query) {
+            self.stats.cache_hits += 1;
+            return Some(path.clone());
+        }
+        if self.queued.insert(query) {
+            self.queue.push_back(query);
+        }
+        None
+    }
+
+    /// Queries waiting to be planned.
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Paths held in the cache.
+    pub fn cached(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Drops paths invalidated by terrain changes, then plans queued queries
+    /// in request order within the per-tick budget. Returns how many ran.
+    pub fn process(&mut self, terrain: &TerrainGrid, config: &PathfindingConfig) -> usize {
+        self.invalidate_changed(terrain);
+
+        let mut budget = config.expansions_per_tick;
+        let mut ran = 0;
+        while ran < config.queries_per_tick && budget >= config.max_expansions {
+            let Some(query) = self.queue.pop_front() else {
+                break;
+            };
+            self.queued.remove(&query);
+            let path = plan(terrain, query.start, query.goal, 1.0, config.max_expansions);
+            budget -= path.metrics.expanded;
+            ran += 1;
+            self.stats.queries += 1;
+            self.stats.expanded += path.metrics.expanded as u64;
+            self.insert(query, path, config.cache_capacity);
+        }
+        if !self.queue.is_empty() {
+            self.stats.backlogged_ticks += 1;
+        }
+        ran
+    }
+
+    fn insert(&mut self, query: PathQuery, path: PathPlan, capacity: usize) {
+        if self.cache.insert(query, Arc::new(path)).is_none() {
+            self.cache_order.push_back(query);
+        }
+        while self.cache.len() > capacity {
+            let Some(oldest) = self.cache_order.pop_front() else {
+                break;
+            };
+            self.cache.remove(&oldest);
+        }
+    }
+
+    fn invalidate_changed(&mut self, terrain: &TerrainGrid) {
+        if self.cache.is_empty() && self.queue.is_empty() {
+            self.chunk_types.clear();
+            return;
+        }
+        let fingerprints: Vec<u64> = terrain
+            .chunks()
+            .iter()
+            .map(|c| type_fingerprint(c))
+            .collect();
+        let changed: Vec<bool> = if self.chunk_types.len() == fingerprints.len() {
+            fingerprints
+                .iter()
+                .zip(&self.chunk_types)
+                .map(|(new, old)| new != old)
+                .collect()
+        } else {
+            vec![true; fingerprints.len()]
+        };
+        self.chunk_types = fingerprints;
+        if !changed.contains(&true) {
+            return;
+        }
+
+        let before = self.cache.len();
+        self.cache.retain(|query, path| {
+            let (first, last) = chunk_span(query, path);
+            !(first..=last).any(|i| changed.get(i).copied().unwrap_or(true))
+        });
+        let cache = &self.cache;
+        self.cache_order.retain(|query| cache.contains_key(query));
+        self.stats.invalidated += (before - self.cache.len()) as u64;
+    }
+}
+
+/// Chunks a path passes through. Paths are continuous, so every row between
+/// the lowest and highest waypoint is crossed.
+fn chunk_span(query: &PathQuery, path: &PathPlan) -> (usize, usize) {
+    let rows = path
+        .waypoints
+        .iter()
+        .map(|&(_, y)| y)
+        .chain([query.start.1]);
+    let (min, max) = rows.fold((u16::MAX, 0), |(lo, hi), y| (lo.min(y), hi.max(y)));
+    ((min / CHUNK_ROWS) as usize, (max / CHUNK_ROWS) as usize)
+}
+
+/// FNV-1a over the terrain types of a chunk.
+fn type_fingerprint(chunk: &TerrainChunk) -> u64 {
+    chunk
+        .cells
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, cell| {
+            (hash ^ cell.terrain_type as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+}
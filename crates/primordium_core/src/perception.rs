@@ -0,0 +1,109 @@
+//! What one entity sensed on its last tick, read back for the perception
+//! overlay.
+//!
+//! Input values come from the brain's own input nodes, so they are exactly
+//! what the network saw; the geometry around them (sensing radius,
+//! pheromone gradients) is measured again from the current world.
+
+use crate::brain::{BRAIN_INPUTS, INPUT_LABELS};
+use crate::pheromone::SENSED_INPUTS;
+use primordium_data::Brain;
+
+/// How far walls are felt, in cells.
+pub const WALL_SENSE_RANGE: f64 = 5.0;
+/// How far drinkable water is sensed, in cells.
+pub const WATER_SENSE_RANGE: f64 = 6.0;
+/// Cells per unit of the `FoodDX` and `FoodDY` inputs.
+pub const FOOD_VECTOR_SCALE: f64 = 20.0;
+
+/// Brain inputs fed by the pheromone channels `[pheromone] sensed` maps to
+/// them, in overlay order.
+pub const PHEROMONE_INPUTS: [&str; SENSED_INPUTS] = ["Phero", "Tribe", "SA", "SB"];
+
+/// A snapshot of one entity's senses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Perception {
+    pub x: f64,
+    pub y: f64,
+    /// Sensing radius after gene regulation.
+    pub sensing_range: f64,
+    /// Values fed to the brain on the last tick, indexed like
+    /// [`INPUT_LABELS`].
+    pub inputs: [f32; BRAIN_INPUTS],
+    /// Channel feeding each of [`PHEROMONE_INPUTS`], if any.
+    pub pheromone_channels: [Option<String>; SENSED_INPUTS],
+    /// Direction of rising strength of each of those channels at the edge of
+    /// the radius pheromones are sensed over.
+    pub pheromone_gradients: [(f32, f32); SENSED_INPUTS],
+}
+
+impl Perception {
+    /// The input named `label` in [`INPUT_LABELS`].
+    pub fn input(&self, label: &str) -> f32 {
+        INPUT_LABELS
+            .iter()
+            .position(|l| *l == label)
+            .map_or(0.0, |i| self.inputs[i])
+    }
+
+    /// Offset to the nearest food in cells, if any was in range.
+    pub fn food_offset(&self) -> Option<(f64, f64)> {
+        let dx = f64::from(self.input("FoodDX")) * FOOD_VECTOR_SCALE;
+        let dy = f64::from(self.input("FoodDY")) * FOOD_VECTOR_SCALE;
+        (dx != 0.0 || dy != 0.0).then_some((dx, dy))
+    }
+
+    /// Unit direction to the lineage's centroid, if kin were sensed.
+    pub fn kin_direction(&self) -> Option<(f32, f32)> {
+        let (kx, ky) = (self.input("KX"), self.input("KY"));
+        (kx != 0.0 || ky != 0.0).then_some((kx, ky))
+    }
+
+    /// Distance to the nearest wall in cells, if one was felt.
+    pub fn wall_distance(&self) -> Option<f64> {
+        let wall = self.input("WL");
+        (wall > 0.0).then(|| (1.0 - f64::from(wall)) * WALL_SENSE_RANGE)
+    }
+}
+
+/// The values held by `brain`'s input nodes, given its activations indexed
+/// like `brain.nodes`. Inputs without a node read as zero.
+pub fn brain_inputs(brain: &Brain, activations: &[f32]) -> [f32; BRAIN_INPUTS] {
+    let mut inputs = [0.0; BRAIN_INPUTS];
+    for (id, input) in inputs.iter_mut().enumerate() {
+        if let Some(&idx) = brain.node_idx_map.get(&id) {
+            *input = activations.get(idx).copied().unwrap_or(0.0);
+        }
+    }
+    inputs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brain::BrainLogic;
+
+    #[test]
+    fn test_brain_inputs_read_back_what_the_network_saw() {
+        let brain = Brain::new_random();
+        let mut fed = [0.0; BRAIN_INPUTS];
+        fed[0] = 0.25;
+        fed[1] = -0.5;
+        fed[10] = 0.6;
+        let mut activations = primordium_data::Activations::default();
+        let _ = brain.forward_internal(fed, [0.0; 6], &mut activations);
+
+        let perception = Perception {
+            x: 10.0,
+            y: 10.0,
+            sensing_range: 8.0,
+            inputs: brain_inputs(&brain, &activations.0),
+            pheromone_channels: Default::default(),
+            pheromone_gradients: [(0.0, 0.0); SENSED_INPUTS],
+        };
+        assert_eq!(perception.inputs, fed);
+        assert_eq!(perception.food_offset(), Some((5.0, -10.0)));
+        assert_eq!(perception.kin_direction(), None);
+        assert!((perception.wall_distance().unwrap() - 2.0).abs() < 1e-6);
+    }
+}
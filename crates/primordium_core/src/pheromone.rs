@@ -1,16 +1,80 @@
+//! Pheromone layers laid and sensed by organisms.
+//!
+//! The grid holds one layer per [`PheromoneChannel`] configured under
+//! `[pheromone]`. Each channel fades by its own decay factor every tick and
+//! may diffuse into neighbouring cells. The simulation itself lays the
+//! channels named by [`PheromoneType`]; brains emit on and sense the
+//! channels their config maps them to.
+
 use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU32, Ordering};
 
+/// Most channels a grid may have.
+pub const MAX_CHANNELS: usize = 16;
+
+/// Brain outputs that deposit pheromones (Emit A, Emit B).
+pub const EMIT_OUTPUTS: usize = 2;
+
+/// Brain inputs that sense pheromones (Phero, Tribe, SA, SB).
+pub const SENSED_INPUTS: usize = 4;
+
+/// Strengths below this are cleared.
+const MIN_STRENGTH: f32 = 0.01;
+
+/// Channels the simulation lays itself, found by name.
 #[derive(
     Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize,
 )]
 #[archive(check_bytes)]
 pub enum PheromoneType {
+    /// Left where an entity ate, or faked by deceivers.
     Food,
-    Danger,
-    SignalA,
-    SignalB,
+    /// Left where an entity died of infection.
+    Death,
+}
+
+impl PheromoneType {
+    /// Name of the channel this pheromone is laid on.
+    pub fn channel_name(self) -> &'static str {
+        match self {
+            PheromoneType::Food => "food",
+            PheromoneType::Death => "death",
+        }
+    }
+}
+
+/// One layer of the grid.
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+pub struct PheromoneChannel {
+    pub name: String,
+    /// Factor the strength is multiplied by every tick.
+    pub decay: f32,
+    /// Share of a cell's strength that spreads evenly to its neighbours
+    /// every tick.
+    #[serde(default)]
+    pub diffusion: f32,
+}
+
+impl PheromoneChannel {
+    pub fn new(name: impl Into<String>, decay: f32, diffusion: f32) -> Self {
+        Self {
+            name: name.into(),
+            decay,
+            diffusion,
+        }
+    }
+
+    /// The channels grids had before they became configurable.
+    pub fn defaults() -> Vec<Self> {
+        ["food", "danger", "signal_a", "signal_b", "death"]
+            .into_iter()
+            .map(|name| Self::new(name, 0.995, 0.0))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, Archive, RkyvSerialize, RkyvDeserialize)]
@@ -18,43 +82,27 @@ pub enum PheromoneType {
 pub struct PheromoneDeposit {
     pub x: f64,
     pub y: f64,
-    pub ptype: PheromoneType,
+    /// Index of the channel in the grid.
+    pub channel: usize,
     pub amount: f32,
 }
 
-#[derive(
-    Debug, Clone, Copy, Default, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize,
-)]
-#[archive(check_bytes)]
-pub struct PheromoneCell {
-    pub food_strength: f32,
-    pub danger_strength: f32,
-    pub sig_a_strength: f32,
-    pub sig_b_strength: f32,
-}
-
 #[derive(Serialize, Deserialize, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
+#[serde(from = "StoredPheromoneGrid")]
 #[archive(check_bytes)]
 pub struct PheromoneGrid {
-    pub cells: Vec<PheromoneCell>,
+    pub channels: Vec<PheromoneChannel>,
+    /// Strength of every channel in every cell, cell by cell: channel `c` of
+    /// cell `i` is at `i * channels.len() + c`.
+    pub cells: Vec<f32>,
     #[serde(skip)]
     #[with(rkyv::with::Skip)]
-    pub back_buffer: Vec<PheromoneCell>,
+    pub back_buffer: Vec<f32>,
     #[serde(skip)]
     #[with(rkyv::with::Skip)]
-    atomic_food: Vec<AtomicU32>,
-    #[serde(skip)]
-    #[with(rkyv::with::Skip)]
-    atomic_danger: Vec<AtomicU32>,
-    #[serde(skip)]
-    #[with(rkyv::with::Skip)]
-    atomic_sig_a: Vec<AtomicU32>,
-    #[serde(skip)]
-    #[with(rkyv::with::Skip)]
-    atomic_sig_b: Vec<AtomicU32>,
+    deposits: Vec<AtomicU32>,
     pub width: u16,
     pub height: u16,
-    pub decay_rate: f32,
     #[serde(skip)]
     #[with(rkyv::with::Skip)]
     pub is_dirty: bool,
@@ -62,17 +110,13 @@ pub struct PheromoneGrid {
 
 impl Clone for PheromoneGrid {
     fn clone(&self) -> Self {
-        let size = self.width as usize * self.height as usize;
         Self {
+            channels: self.channels.clone(),
             cells: self.cells.clone(),
             back_buffer: self.back_buffer.clone(),
-            atomic_food: (0..size).map(|_| AtomicU32::new(0)).collect(),
-            atomic_danger: (0..size).map(|_| AtomicU32::new(0)).collect(),
-            atomic_sig_a: (0..size).map(|_| AtomicU32::new(0)).collect(),
-            atomic_sig_b: (0..size).map(|_| AtomicU32::new(0)).collect(),
+            deposits: (0..self.cells.len()).map(|_| AtomicU32::new(0)).collect(),
             width: self.width,
             height: self.height,
-            decay_rate: self.decay_rate,
             is_dirty: self.is_dirty,
         }
     }
@@ -85,55 +129,75 @@ impl Default for PheromoneGrid {
 }
 
 impl PheromoneGrid {
+    /// A grid with the [default channels](PheromoneChannel::defaults).
     pub fn new(width: u16, height: u16) -> Self {
-        let size = width as usize * height as usize;
-        let cells = vec![PheromoneCell::default(); size];
+        Self::with_channels(width, height, PheromoneChannel::defaults())
+    }
+
+    pub fn with_channels(width: u16, height: u16, channels: Vec<PheromoneChannel>) -> Self {
+        let size = width as usize * height as usize * channels.len();
         Self {
-            cells,
-            back_buffer: vec![PheromoneCell::default(); size],
-            atomic_food: (0..size).map(|_| AtomicU32::new(0)).collect(),
-            atomic_danger: (0..size).map(|_| AtomicU32::new(0)).collect(),
-            atomic_sig_a: (0..size).map(|_| AtomicU32::new(0)).collect(),
-            atomic_sig_b: (0..size).map(|_| AtomicU32::new(0)).collect(),
+            channels,
+            cells: vec![0.0; size],
+            back_buffer: vec![0.0; size],
+            deposits: (0..size).map(|_| AtomicU32::new(0)).collect(),
             width,
             height,
-            decay_rate: 0.995,
             is_dirty: true,
         }
     }
 
+    /// Index of the channel called `name`.
+    pub fn channel(&self, name: &str) -> Option<usize> {
+        self.channels.iter().position(|c| c.name == name)
+    }
+
+    /// Indices of the channels called `names`, padded with `None`.
+    pub fn channels_named<const N: usize>(&self, names: &[String]) -> [Option<usize>; N] {
+        std::array::from_fn(|i| names.get(i).and_then(|name| self.channel(name)))
+    }
+
+    /// Index of the channel `ptype` is laid on, if the grid has one.
+    pub fn channel_of(&self, ptype: PheromoneType) -> Option<usize> {
+        self.channel(ptype.channel_name())
+    }
+
     #[inline(always)]
     fn index(&self, x: u16, y: u16) -> usize {
         (y as usize * self.width as usize) + x as usize
     }
 
-    pub fn deposit(&mut self, x: f64, y: f64, ptype: PheromoneType, amount: f32) {
+    /// Position in `cells` of `channel` at `(x, y)`, clamped to the grid.
+    fn slot(&self, x: f64, y: f64, channel: usize) -> Option<usize> {
+        if channel >= self.channels.len() {
+            return None;
+        }
         let ix = (x as u16).min(self.width - 1);
         let iy = (y as u16).min(self.height - 1);
-        let idx = self.index(ix, iy);
-        let cell = &mut self.cells[idx];
-        match ptype {
-            PheromoneType::Food => cell.food_strength = (cell.food_strength + amount).min(1.0),
-            PheromoneType::Danger => {
-                cell.danger_strength = (cell.danger_strength + amount).min(1.0)
-            }
-            PheromoneType::SignalA => cell.sig_a_strength = (cell.sig_a_strength + amount).min(1.0),
-            PheromoneType::SignalB => cell.sig_b_strength = (cell.sig_b_strength + amount).min(1.0),
+        Some(self.index(ix, iy) * self.channels.len() + channel)
+    }
+
+    /// Lays `ptype`; a grid without its channel ignores it.
+    pub fn deposit(&mut self, x: f64, y: f64, ptype: PheromoneType, amount: f32) {
+        if let Some(channel) = self.channel_of(ptype) {
+            self.deposit_channel(x, y, channel, amount);
         }
-        self.is_dirty = true;
     }
 
-    pub fn deposit_parallel(&self, x: f64, y: f64, ptype: PheromoneType, amount: f32) {
-        let ix = (x as u16).min(self.width - 1);
-        let iy = (y as u16).min(self.height - 1);
-        let idx = self.index(ix, iy);
-        let target = match ptype {
-            PheromoneType::Food => &self.atomic_food[idx],
-            PheromoneType::Danger => &self.atomic_danger[idx],
-            PheromoneType::SignalA => &self.atomic_sig_a[idx],
-            PheromoneType::SignalB => &self.atomic_sig_b[idx],
-        };
+    pub fn deposit_channel(&mut self, x: f64, y: f64, channel: usize, amount: f32) {
+        if let Some(slot) = self.slot(x, y, channel) {
+            let cell = &mut self.cells[slot];
+            *cell = (*cell + amount).min(1.0);
+            self.is_dirty = true;
+        }
+    }
 
+    /// Queues a deposit from a parallel system; it lands on the next
+    /// [`Self::update`].
+    pub fn deposit_parallel(&self, x: f64, y: f64, channel: usize, amount: f32) {
+        let Some(target) = self.slot(x, y, channel).and_then(|s| self.deposits.get(s)) else {
+            return;
+        };
         let mut current = target.load(Ordering::Relaxed);
         loop {
             let f = f32::from_bits(current);
@@ -145,89 +209,316 @@ impl PheromoneGrid {
         }
     }
 
-    pub fn sense_all(&self, x: f64, y: f64, radius: f64) -> (f32, f32, f32, f32) {
-        let cx = x as i32;
-        let cy = y as i32;
-        let r = radius as i32;
-        let mut food_sum = 0.0f32;
-        let mut danger_sum = 0.0f32;
-        let mut sig_a_sum = 0.0f32;
-        let mut sig_b_sum = 0.0f32;
+    /// Mean strength of each of `channels` over the square of `radius`
+    /// around `(x, y)`, in one pass. Channels that are `None` or missing read
+    /// as zero.
+    pub fn sense_channels<const N: usize>(
+        &self,
+        x: f64,
+        y: f64,
+        radius: f64,
+        channels: [Option<usize>; N],
+    ) -> [f32; N] {
+        let n = self.channels.len();
+        let (cx, cy, r) = (x as i32, y as i32, radius as i32);
+        let mut sums = [0.0f32; N];
         let mut count = 0;
         for dy in -r..=r {
             for dx in -r..=r {
-                let nx = cx + dx;
-                let ny = cy + dy;
+                let (nx, ny) = (cx + dx, cy + dy);
                 if nx >= 0 && nx < self.width as i32 && ny >= 0 && ny < self.height as i32 {
-                    let idx = self.index(nx as u16, ny as u16);
-                    let cell = &self.back_buffer[idx];
-                    food_sum += cell.food_strength;
-                    danger_sum += cell.danger_strength;
-                    sig_a_sum += cell.sig_a_strength;
-                    sig_b_sum += cell.sig_b_strength;
+                    let base = self.index(nx as u16, ny as u16) * n;
+                    for (sum, channel) in sums.iter_mut().zip(channels) {
+                        if let Some(c) = channel.filter(|&c| c < n) {
+                            *sum += self.back_buffer.get(base + c).copied().unwrap_or(0.0);
+                        }
+                    }
                     count += 1;
                 }
             }
         }
         if count > 0 {
-            (
-                food_sum / count as f32,
-                danger_sum / count as f32,
-                sig_a_sum / count as f32,
-                sig_b_sum / count as f32,
-            )
+            sums.map(|sum| sum / count as f32)
         } else {
-            (0.0, 0.0, 0.0, 0.0)
+            [0.0; N]
         }
     }
 
-    pub fn sense(&self, x: f64, y: f64, radius: f64) -> (f32, f32) {
-        let (f, d, _, _) = self.sense_all(x, y, radius);
-        (f, d)
+    /// Direction of rising death scent around `(x, y)`: the difference in
+    /// strength between the cells `radius` away on either side along each
+    /// axis. Cells off the grid count as scentless.
+    pub fn death_gradient(&self, x: f64, y: f64, radius: f64) -> (f32, f32) {
+        self.channel_of(PheromoneType::Death)
+            .map_or((0.0, 0.0), |c| self.gradient(x, y, radius, c))
+    }
+
+    /// Direction of rising scent of `channel` around `(x, y)`, measured like
+    /// [`Self::death_gradient`].
+    pub fn gradient(&self, x: f64, y: f64, radius: f64, channel: usize) -> (f32, f32) {
+        let n = self.channels.len();
+        let r = radius.max(1.0) as i32;
+        let (cx, cy) = (x as i32, y as i32);
+        let at = |nx: i32, ny: i32| {
+            if channel < n
+                && nx >= 0
+                && nx < self.width as i32
+                && ny >= 0
+                && ny < self.height as i32
+            {
+                self.back_buffer
+                    .get(self.index(nx as u16, ny as u16) * n + channel)
+                    .copied()
+                    .unwrap_or(0.0)
+            } else {
+                0.0
+            }
+        };
+        (
+            at(cx + r, cy) - at(cx - r, cy),
+            at(cx, cy + r) - at(cx, cy - r),
+        )
+    }
+
+    /// Rebuilds the sensing and deposit buffers, which are not serialized.
+    pub fn restore_buffers(&mut self) {
+        self.back_buffer = self.cells.clone();
+        self.deposits = (0..self.cells.len()).map(|_| AtomicU32::new(0)).collect();
     }
 
     pub fn update(&mut self) {
         self.is_dirty = true;
-        let size = self.cells.len();
-        if self.atomic_food.len() != size {
-            self.atomic_food = (0..size).map(|_| AtomicU32::new(0)).collect();
-            self.atomic_danger = (0..size).map(|_| AtomicU32::new(0)).collect();
-            self.atomic_sig_a = (0..size).map(|_| AtomicU32::new(0)).collect();
-            self.atomic_sig_b = (0..size).map(|_| AtomicU32::new(0)).collect();
-        }
-
-        let rate = self.decay_rate;
-        for i in 0..size {
-            let f = f32::from_bits(self.atomic_food[i].swap(0, Ordering::SeqCst));
-            let d = f32::from_bits(self.atomic_danger[i].swap(0, Ordering::SeqCst));
-            let sa = f32::from_bits(self.atomic_sig_a[i].swap(0, Ordering::SeqCst));
-            let sb = f32::from_bits(self.atomic_sig_b[i].swap(0, Ordering::SeqCst));
-
-            let cell = &mut self.cells[i];
-            cell.food_strength = (cell.food_strength * rate + f).min(1.0);
-            cell.danger_strength = (cell.danger_strength * rate + d).min(1.0);
-            cell.sig_a_strength = (cell.sig_a_strength * rate + sa).min(1.0);
-            cell.sig_b_strength = (cell.sig_b_strength * rate + sb).min(1.0);
-
-            if cell.food_strength < 0.01 {
-                cell.food_strength = 0.0;
-            }
-            if cell.danger_strength < 0.01 {
-                cell.danger_strength = 0.0;
-            }
-            if cell.sig_a_strength < 0.01 {
-                cell.sig_a_strength = 0.0;
-            }
-            if cell.sig_b_strength < 0.01 {
-                cell.sig_b_strength = 0.0;
-            }
+        let n = self.channels.len();
+        if n == 0 {
+            return;
+        }
+        if self.deposits.len() != self.cells.len() || self.back_buffer.len() != self.cells.len() {
+            self.restore_buffers();
+        }
+
+        for (i, value) in self.cells.iter_mut().enumerate() {
+            let deposited = f32::from_bits(self.deposits[i].swap(0, Ordering::SeqCst));
+            let next = (*value * self.channels[i % n].decay + deposited).min(1.0);
+            *value = if next < MIN_STRENGTH { 0.0 } else { next };
+        }
+        if self.channels.iter().any(|c| c.diffusion > 0.0) {
+            self.diffuse();
         }
         self.back_buffer.copy_from_slice(&self.cells);
     }
 
-    pub fn get_cell(&self, x: u16, y: u16) -> &PheromoneCell {
+    /// Spreads the `diffusion` share of every cell evenly over its
+    /// neighbours on the grid, so no strength leaks off the edges.
+    fn diffuse(&mut self) {
+        let n = self.channels.len();
+        let (w, h) = (self.width as i32, self.height as i32);
+        self.back_buffer.copy_from_slice(&self.cells);
+        let source = &self.back_buffer;
+        let neighbours = |x: i32, y: i32| {
+            [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                .into_iter()
+                .filter(move |&(nx, ny)| nx >= 0 && nx < w && ny >= 0 && ny < h)
+        };
+        for y in 0..h {
+            for x in 0..w {
+                let base = (y * w + x) as usize * n;
+                for (c, channel) in self.channels.iter().enumerate() {
+                    let d = channel.diffusion;
+                    if d <= 0.0 {
+                        continue;
+                    }
+                    let inflow: f32 = neighbours(x, y)
+                        .map(|(nx, ny)| {
+                            let share = d / neighbours(nx, ny).count() as f32;
+                            source[(ny * w + nx) as usize * n + c] * share
+                        })
+                        .sum();
+                    let next = (source[base + c] * (1.0 - d) + inflow).min(1.0);
+                    self.cells[base + c] = if next < MIN_STRENGTH { 0.0 } else { next };
+                }
+            }
+        }
+    }
+
+    /// Strengths of every channel at `(x, y)`, clamped to the grid.
+    pub fn get_cell(&self, x: u16, y: u16) -> &[f32] {
+        let n = self.channels.len();
         let ix = x.min(self.width - 1);
         let iy = y.min(self.height - 1);
-        &self.cells[self.index(ix, iy)]
+        let base = self.index(ix, iy) * n;
+        &self.cells[base..base + n]
+    }
+
+    /// Strength of `channel` at `(x, y)`; zero for a missing channel.
+    pub fn strength(&self, x: u16, y: u16, channel: usize) -> f32 {
+        self.get_cell(x, y).get(channel).copied().unwrap_or(0.0)
+    }
+}
+
+/// Cell of the five fixed channels grids had before channels became
+/// configurable.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+pub struct LegacyPheromoneCell {
+    pub food_strength: f32,
+    pub danger_strength: f32,
+    pub sig_a_strength: f32,
+    pub sig_b_strength: f32,
+    #[serde(default)]
+    pub death_strength: f32,
+}
+
+/// Stored layout of grids before channels became configurable, kept to read
+/// old saves and snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct LegacyPheromoneGrid {
+    pub cells: Vec<LegacyPheromoneCell>,
+    pub width: u16,
+    pub height: u16,
+    pub decay_rate: f32,
+}
+
+impl From<LegacyPheromoneGrid> for PheromoneGrid {
+    fn from(legacy: LegacyPheromoneGrid) -> Self {
+        let channels = PheromoneChannel::defaults()
+            .into_iter()
+            .map(|c| PheromoneChannel {
+                decay: legacy.decay_rate,
+                ..c
+            })
+            .collect();
+        let cells = legacy
+            .cells
+            .iter()
+            .flat_map(|c| {
+                [
+                    c.food_strength,
+                    c.danger_strength,
+                    c.sig_a_strength,
+                    c.sig_b_strength,
+                    c.death_strength,
+                ]
+            })
+            .collect();
+        let mut grid = Self {
+            channels,
+            cells,
+            back_buffer: Vec::new(),
+            deposits: Vec::new(),
+            width: legacy.width,
+            height: legacy.height,
+            is_dirty: true,
+        };
+        grid.restore_buffers();
+        grid
+    }
+}
+
+/// A grid as found in a save, in either layout.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredPheromoneGrid {
+    Current {
+        channels: Vec<PheromoneChannel>,
+        cells: Vec<f32>,
+        width: u16,
+        height: u16,
+    },
+    Legacy(LegacyPheromoneGrid),
+}
+
+impl From<StoredPheromoneGrid> for PheromoneGrid {
+    fn from(stored: StoredPheromoneGrid) -> Self {
+        match stored {
+            StoredPheromoneGrid::Current {
+                channels,
+                cells,
+                width,
+                height,
+            } => {
+                let mut grid = Self {
+                    channels,
+                    cells,
+                    back_buffer: Vec::new(),
+                    deposits: Vec::new(),
+                    width,
+                    height,
+                    is_dirty: true,
+                };
+                grid.restore_buffers();
+                grid
+            }
+            StoredPheromoneGrid::Legacy(legacy) => legacy.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channels_decay_at_their_own_rate() {
+        let channels = vec![
+            PheromoneChannel::new("food", 0.5, 0.0),
+            PheromoneChannel::new("death", 0.9, 0.0),
+        ];
+        let mut grid = PheromoneGrid::with_channels(4, 4, channels);
+        grid.deposit(1.0, 1.0, PheromoneType::Food, 0.8);
+        grid.deposit(1.0, 1.0, PheromoneType::Death, 0.8);
+        grid.update();
+        assert!((grid.strength(1, 1, 0) - 0.4).abs() < 1e-6);
+        assert!((grid.strength(1, 1, 1) - 0.72).abs() < 1e-6);
+        let [food, death] = grid.sense_channels(1.0, 1.0, 0.0, [Some(0), Some(1)]);
+        assert!((food - 0.4).abs() < 1e-6 && (death - 0.72).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_diffusion_spreads_without_leaking() {
+        let channels = vec![
+            PheromoneChannel::new("still", 1.0, 0.0),
+            PheromoneChannel::new("spread", 1.0, 0.4),
+        ];
+        let mut grid = PheromoneGrid::with_channels(5, 5, channels);
+        grid.deposit_channel(0.0, 0.0, 1, 0.8);
+        grid.deposit_channel(2.0, 2.0, 0, 0.8);
+        grid.update();
+
+        // A corner shares its 40% with its two neighbours.
+        assert!((grid.strength(0, 0, 1) - 0.48).abs() < 1e-6);
+        assert!((grid.strength(1, 0, 1) - 0.16).abs() < 1e-6);
+        assert!((grid.strength(0, 1, 1) - 0.16).abs() < 1e-6);
+        let total: f32 = (0..grid.cells.len() / 2)
+            .map(|i| grid.cells[i * 2 + 1])
+            .sum();
+        assert!((total - 0.8).abs() < 1e-5);
+        assert_eq!(grid.strength(2, 3, 0), 0.0);
+        assert!(grid.gradient(1.0, 1.0, 1.0, 1).0 < 0.0);
+    }
+
+    #[test]
+    fn test_legacy_grids_load_into_default_channels() {
+        let json = serde_json::json!({
+            "cells": [
+                {"food_strength": 0.1, "danger_strength": 0.2, "sig_a_strength": 0.3, "sig_b_strength": 0.4},
+                {"food_strength": 0.0, "danger_strength": 0.0, "sig_a_strength": 0.0, "sig_b_strength": 0.0, "death_strength": 0.5},
+            ],
+            "width": 2,
+            "height": 1,
+            "decay_rate": 0.9,
+        });
+        let grid: PheromoneGrid = serde_json::from_value(json).unwrap();
+        assert_eq!(grid.channels.len(), 5);
+        assert!(grid.channels.iter().all(|c| c.decay == 0.9));
+        assert_eq!(grid.get_cell(0, 0), &[0.1, 0.2, 0.3, 0.4, 0.0]);
+        let death = grid.channel_of(PheromoneType::Death).unwrap();
+        assert_eq!(grid.strength(1, 0, death), 0.5);
+
+        let saved = serde_json::to_value(&grid).unwrap();
+        let reloaded: PheromoneGrid = serde_json::from_value(saved).unwrap();
+        assert_eq!(reloaded.cells, grid.cells);
+        assert_eq!(reloaded.channels, grid.channels);
     }
 }
@@ -0,0 +1,144 @@
+//! Roads worn into the ground by repeated movement.
+//!
+//! Every tick, each moving entity wears `roads.wear_per_step` into the cell
+//! it stands on. A cell belongs to the lineage with the most traffic through
+//! it; traffic from another lineage erodes the wear instead, and takes the
+//! cell over once it is worn away. Roads are territorial: a lineage lays no
+//! road where another lineage holds the influence grid. Wear fades by
+//! `roads.decay` per tick and is capped at `roads.max_wear`.
+//!
+//! Members of the owning lineage move faster along their roads, and roads
+//! worn past `roads.link_threshold` carry outpost supply lines like rivers.
+
+use crate::config::RoadConfig;
+use crate::influence::InfluenceGrid;
+use crate::snapshot::InternalEntitySnapshot;
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Speed below which an entity is standing still and wears no road.
+const MIN_TRAVEL_SPEED: f64 = 0.01;
+
+#[derive(
+    Serialize, Deserialize, Clone, Default, Debug, Archive, RkyvSerialize, RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+pub struct RoadCell {
+    pub lineage: Option<Uuid>,
+    pub wear: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct RoadGrid {
+    pub width: u16,
+    pub height: u16,
+    pub cells: Vec<RoadCell>,
+}
+
+impl Default for RoadGrid {
+    fn default() -> Self {
+        Self::new(100, 100)
+    }
+}
+
+impl RoadGrid {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![RoadCell::default(); width as usize * height as usize],
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        entities: &[InternalEntitySnapshot],
+        influence: &InfluenceGrid,
+        config: &RoadConfig,
+    ) {
+        for cell in &mut self.cells {
+            cell.wear -= config.decay;
+            if cell.wear <= 0.0 {
+                cell.lineage = None;
+                cell.wear = 0.0;
+            }
+        }
+        if !config.enabled {
+            return;
+        }
+
+        let mut traffic: HashMap<usize, HashMap<Uuid, f32>> = HashMap::new();
+        for e in entities {
+            if e.vx.hypot(e.vy) < MIN_TRAVEL_SPEED {
+                continue;
+            }
+            let (ex, ey) = (e.x as usize, e.y as usize);
+            if ex < self.width as usize && ey < self.height as usize {
+                let idx = ey * self.width as usize + ex;
+                *traffic
+                    .entry(idx)
+                    .or_default()
+                    .entry(e.lineage_id)
+                    .or_default() += config.wear_per_step;
+            }
+        }
+
+        for (idx, presence) in traffic {
+            // Ties go to the lowest lineage ID, as in the influence grid.
+            let busiest = presence
+                .into_iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)));
+            let Some((lid, wear)) = busiest else {
+                continue;
+            };
+            let x = (idx % self.width as usize) as f64;
+            let y = (idx / self.width as usize) as f64;
+            let cell = &mut self.cells[idx];
+            if cell.lineage.is_some_and(|owner| owner != lid) {
+                cell.wear -= wear;
+                if cell.wear <= 0.0 {
+                    cell.lineage = None;
+                    cell.wear = 0.0;
+                }
+                continue;
+            }
+            if influence
+                .get_influence(x, y)
+                .0
+                .is_some_and(|holder| holder != lid)
+            {
+                continue;
+            }
+            cell.lineage = Some(lid);
+            cell.wear = (cell.wear + wear).min(config.max_wear);
+        }
+    }
+
+    pub fn get_road(&self, x: f64, y: f64) -> (Option<Uuid>, f32) {
+        let ix = (x.max(0.0) as usize).min(self.width as usize - 1);
+        let iy = (y.max(0.0) as usize).min(self.height as usize - 1);
+        let cell = &self.cells[iy * self.width as usize + ix];
+        (cell.lineage, cell.wear)
+    }
+
+    /// Speed multiplier for a member of `lineage_id` at `(x, y)`: up to
+    /// `1 + roads.speed_bonus` on a fully worn road of its own lineage.
+    pub fn speed_factor(&self, x: f64, y: f64, lineage_id: Uuid, config: &RoadConfig) -> f64 {
+        match self.get_road(x, y) {
+            (Some(owner), wear) if owner == lineage_id && config.max_wear > 0.0 => {
+                1.0 + config.speed_bonus * f64::from(wear / config.max_wear)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Whether the cell at `idx` is worn enough to carry supply lines.
+    pub fn is_link(&self, idx: usize, config: &RoadConfig) -> bool {
+        self.cells
+            .get(idx)
+            .is_some_and(|c| c.lineage.is_some() && c.wear >= config.link_threshold)
+    }
+}
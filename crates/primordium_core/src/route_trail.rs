@@ -0,0 +1,100 @@
+//! Route history of a tracked entity.
+//!
+//! While an entity is tracked the action system reports where it ended up
+//! each tick and the world keeps the last `N` of those positions in a ring
+//! buffer. The terminal draws them as a fading trail behind the entity, and
+//! the trail can be written out as CSV for offline analysis of movement
+//! behaviour.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrailPoint {
+    pub tick: u64,
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteTrail {
+    pub entity_id: Uuid,
+    pub length: usize,
+    points: VecDeque<TrailPoint>,
+}
+
+impl RouteTrail {
+    pub fn new(entity_id: Uuid, length: usize) -> Self {
+        let length = length.max(1);
+        Self {
+            entity_id,
+            length,
+            points: VecDeque::with_capacity(length),
+        }
+    }
+
+    /// Appends the entity's position at `tick`, dropping the oldest point
+    /// once the trail is full.
+    pub fn record(&mut self, tick: u64, x: f64, y: f64) {
+        if !x.is_finite() || !y.is_finite() {
+            return;
+        }
+        if self.points.len() == self.length {
+            self.points.pop_front();
+        }
+        self.points.push_back(TrailPoint { tick, x, y });
+    }
+
+    /// Recorded positions, oldest first.
+    pub fn points(&self) -> &VecDeque<TrailPoint> {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Age of the point at `index` as a fraction of the trail: 0.0 for the
+    /// newest point, approaching 1.0 for the oldest a full trail keeps.
+    pub fn age(&self, index: usize) -> f32 {
+        let newest = self.points.len().saturating_sub(1);
+        newest.saturating_sub(index) as f32 / self.length as f32
+    }
+
+    /// The trail as `tick,x,y` rows under a header, oldest first.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("tick,x,y\n");
+        for p in &self.points {
+            out.push_str(&format!("{},{:.3},{:.3}\n", p.tick, p.x, p.y));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trail_keeps_the_newest_points() {
+        let mut trail = RouteTrail::new(Uuid::nil(), 3);
+        for tick in 1..=5 {
+            trail.record(tick, tick as f64, 2.0);
+        }
+        trail.record(6, f64::NAN, 2.0);
+
+        assert_eq!(trail.len(), 3);
+        assert_eq!(trail.points().front().map(|p| p.tick), Some(3));
+        assert_eq!(trail.age(2), 0.0);
+        assert!(trail.age(0) > trail.age(1));
+        assert_eq!(
+            trail.to_csv(),
+            "tick,x,y\n3,3.000,2.000\n4,4.000,2.000\n5,5.000,2.000\n"
+        );
+    }
+}
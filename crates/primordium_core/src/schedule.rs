@@ -0,0 +1,208 @@
+//! Calendar of scheduled world events.
+//!
+//! The [`Schedule`] lives in the [`Environment`](crate::environment::Environment)
+//! and holds every event the world knows is coming: one-off events at a
+//! fixed tick, such as disasters scripted by a scenario, and recurring ones
+//! such as season changes, fossil saves and power-grid resolution, which
+//! come round on every multiple of their interval. Systems take the events
+//! due at the current tick instead of keeping their own timers, and what
+//! fired is kept in a short history so the terminal can show both what is
+//! coming and what just happened.
+
+use primordium_data::Disaster;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// What happens when a scheduled event comes due.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum EventKind {
+    /// A new season begins.
+    SeasonChange,
+    /// A disaster strikes.
+    Disaster { disaster: Disaster },
+    /// Lineages and fossils are saved.
+    Fossil,
+    /// Outpost power grids share their energy.
+    PowerGrid,
+}
+
+impl EventKind {
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::SeasonChange => "Season change",
+            EventKind::Disaster { disaster } => disaster.name(),
+            EventKind::Fossil => "Fossil record",
+            EventKind::PowerGrid => "Power grid",
+        }
+    }
+}
+
+/// One entry of the calendar. Recurring events come round every `every`
+/// ticks; `every` is 0 for one-off events.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledEvent {
+    pub tick: u64,
+    pub kind: EventKind,
+    #[serde(default)]
+    pub every: u64,
+}
+
+impl ScheduledEvent {
+    #[must_use]
+    pub fn once(tick: u64, kind: EventKind) -> Self {
+        Self {
+            tick,
+            kind,
+            every: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn is_recurring(&self) -> bool {
+        self.every > 0
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Events still to come, soonest first.
+    upcoming: Vec<ScheduledEvent>,
+    /// Events that fired, newest last. Only the latest occurrence of each
+    /// recurring event is kept.
+    recent: VecDeque<ScheduledEvent>,
+}
+
+impl Schedule {
+    /// Number of fired events kept in the history.
+    pub const HISTORY: usize = 16;
+
+    /// Schedules a one-off `kind` at `tick`, after any events already due
+    /// then.
+    pub fn at(&mut self, tick: u64, kind: EventKind) {
+        self.insert(ScheduledEvent::once(tick, kind));
+    }
+
+    /// Keeps `kind` recurring on every multiple of `interval`, the next one
+    /// at or after `tick`. Leaves the entry alone while the interval stays
+    /// the same; an interval of 0 cancels it.
+    pub fn every(&mut self, kind: EventKind, interval: u64, tick: u64) {
+        if self
+            .upcoming
+            .iter()
+            .any(|e| e.kind == kind && e.every == interval)
+        {
+            return;
+        }
+        self.cancel(kind);
+        if interval > 0 {
+            self.insert(ScheduledEvent {
+                tick: tick.div_ceil(interval) * interval,
+                kind,
+                every: interval,
+            });
+        }
+    }
+
+    /// Drops every upcoming `kind`.
+    pub fn cancel(&mut self, kind: EventKind) {
+        self.upcoming.retain(|e| e.kind != kind);
+    }
+
+    /// Removes the events matching `filter` that are due by `tick`, soonest
+    /// first, and brings recurring ones round again after `tick`. The
+    /// caller records what it acts on with [`Schedule::record`].
+    pub fn take(&mut self, tick: u64, filter: impl Fn(&EventKind) -> bool) -> Vec<ScheduledEvent> {
+        let (due, rest): (Vec<_>, Vec<_>) = self
+            .upcoming
+            .drain(..)
+            .partition(|e| e.tick <= tick && filter(&e.kind));
+        self.upcoming = rest;
+        for event in due.iter().filter(|e| e.is_recurring()) {
+            self.insert(ScheduledEvent {
+                tick: (tick / event.every + 1) * event.every,
+                ..*event
+            });
+        }
+        due
+    }
+
+    /// Whether a `kind` is due by `tick`, recording it as fired if so.
+    pub fn fires(&mut self, tick: u64, kind: EventKind) -> bool {
+        let due = self.take(tick, |k| *k == kind);
+        for event in &due {
+            self.record(tick, *event);
+        }
+        !due.is_empty()
+    }
+
+    /// Adds `event` to the history as having fired at `tick`.
+    pub fn record(&mut self, tick: u64, event: ScheduledEvent) {
+        if event.is_recurring() {
+            self.recent.retain(|e| e.kind != event.kind);
+        }
+        self.recent.push_back(ScheduledEvent { tick, ..event });
+        while self.recent.len() > Self::HISTORY {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Events still to come, soonest first.
+    pub fn upcoming(&self) -> &[ScheduledEvent] {
+        &self.upcoming
+    }
+
+    /// Events that fired, newest first.
+    pub fn recent(&self) -> impl Iterator<Item = &ScheduledEvent> {
+        self.recent.iter().rev()
+    }
+
+    fn insert(&mut self, event: ScheduledEvent) {
+        let at = self.upcoming.partition_point(|e| e.tick <= event.tick);
+        self.upcoming.insert(at, event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recurring_events_come_round_and_one_offs_fire_once() {
+        let flare = EventKind::Disaster {
+            disaster: Disaster::SolarFlare { duration: 50 },
+        };
+        let mut schedule = Schedule::default();
+        schedule.every(EventKind::Fossil, 100, 1);
+        schedule.at(150, flare);
+        schedule.every(EventKind::Fossil, 100, 2);
+
+        let ticks: Vec<u64> = schedule.upcoming().iter().map(|e| e.tick).collect();
+        assert_eq!(ticks, vec![100, 150]);
+
+        assert!(!schedule.fires(99, EventKind::Fossil));
+        assert!(schedule.fires(100, EventKind::Fossil));
+        assert!(!schedule.fires(100, EventKind::Fossil));
+        assert_eq!(schedule.upcoming()[1].tick, 200);
+
+        let due = schedule.take(160, |k| matches!(k, EventKind::Disaster { .. }));
+        assert_eq!(due.len(), 1);
+        assert!(schedule.take(1000, |k| *k == flare).is_empty());
+
+        assert!(schedule.fires(200, EventKind::Fossil));
+        assert_eq!(schedule.recent().count(), 1);
+        assert_eq!(schedule.recent().next().map(|e| e.tick), Some(200));
+    }
+
+    #[test]
+    fn test_changing_an_interval_reschedules_it() {
+        let mut schedule = Schedule::default();
+        schedule.every(EventKind::PowerGrid, 10, 5);
+        assert_eq!(schedule.upcoming()[0].tick, 10);
+        schedule.every(EventKind::PowerGrid, 25, 5);
+        assert_eq!(schedule.upcoming()[0].tick, 25);
+        schedule.every(EventKind::PowerGrid, 0, 5);
+        assert!(schedule.upcoming().is_empty());
+    }
+}
@@ -1,17 +1,23 @@
 use crate::influence::InfluenceGrid;
+use crate::mutation_heat::MutationHeatGrid;
 use crate::pheromone::PheromoneGrid;
 use crate::pressure::PressureGrid;
+use crate::road::RoadGrid;
 use crate::sound::SoundGrid;
 use crate::terrain::TerrainGrid;
 use primordium_data::Food;
-use primordium_data::{EntityStatus, HallOfFame, PopulationStats, Specialization};
+use primordium_data::{
+    EntityStatus, HallOfFame, Monument, PopulationStats, Specialization, StatusEffect,
+};
 use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Clone, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
+#[derive(
+    Serialize, Deserialize, Clone, Debug, PartialEq, Archive, RkyvSerialize, RkyvDeserialize,
+)]
 #[archive(check_bytes)]
 pub struct EntitySnapshot {
     pub id: Uuid,
@@ -23,6 +29,9 @@ pub struct EntitySnapshot {
     pub b: u8,
     pub energy: f64,
     pub max_energy: f64,
+    /// Water reserve (0.0-1.0).
+    #[serde(default)]
+    pub hydration: f32,
     pub generation: u32,
     pub age: u64,
     pub offspring: u32,
@@ -37,14 +46,47 @@ pub struct EntitySnapshot {
     pub genotype_hex: Option<String>,
     pub specialization: Option<Specialization>,
     pub is_larva: bool,
+    #[serde(default)]
+    pub effects: Vec<StatusEffect>,
+    /// User tags followed by system tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Body size in cells across, see [`crate::body`].
+    #[serde(default = "default_body_size")]
+    pub body_size: f32,
+}
+
+fn default_body_size() -> f32 {
+    primordium_data::MIN_BODY_SIZE
+}
+
+/// An incubating egg as drawn by the renderers.
+#[derive(
+    Serialize, Deserialize, Clone, Debug, PartialEq, Archive, RkyvSerialize, RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+pub struct EggSnapshot {
+    pub x: f64,
+    pub y: f64,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    /// Incubation progress, 0.0 when laid to 1.0 at hatching.
+    pub progress: f32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct InternalEntitySnapshot {
     pub id: Uuid,
     pub lineage_id: Uuid,
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
     pub x: f64,
     pub y: f64,
+    #[serde(default)]
+    pub vx: f64,
+    #[serde(default)]
+    pub vy: f64,
     pub energy: f64,
     pub birth_tick: u64,
     pub offspring_count: u32,
@@ -58,6 +100,21 @@ pub struct InternalEntitySnapshot {
     pub trophic_potential: f32,
     #[serde(skip)]
     pub genotype: Option<Arc<primordium_data::Genotype>>,
+    /// Episodic memories written this life.
+    #[serde(default)]
+    pub memory_writes: u32,
+    /// Ticks on which a stored memory was recalled.
+    #[serde(default)]
+    pub memory_recalls: u32,
+    /// Deceptive food trails laid this life.
+    #[serde(default)]
+    pub deceptions: u32,
+    /// Ticks left on the entity's infection, 0 when healthy.
+    #[serde(default)]
+    pub infection_remaining: u32,
+    /// Standing in the lineage's dominance ladder, 0.0 when unranked.
+    #[serde(default)]
+    pub dominance: f32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
@@ -66,6 +123,8 @@ pub struct WorldSnapshot {
     pub tick: u64,
     pub entities: Vec<EntitySnapshot>,
     pub food: Vec<Food>,
+    #[serde(default)]
+    pub eggs: Vec<EggSnapshot>,
     pub stats: Arc<PopulationStats>,
     pub hall_of_fame: Arc<HallOfFame>,
     pub terrain: Arc<TerrainGrid>,
@@ -73,8 +132,70 @@ pub struct WorldSnapshot {
     pub sound: Arc<SoundGrid>,
     pub pressure: Arc<PressureGrid>,
     pub influence: Arc<InfluenceGrid>,
+    #[serde(default)]
+    pub roads: Arc<RoadGrid>,
+    #[serde(default)]
+    pub mutation_heat: Arc<MutationHeatGrid>,
+    /// Monuments and ruins on the map.
+    #[serde(default)]
+    pub monuments: Vec<Monument>,
     pub social_grid: Arc<Vec<u8>>,
     pub rank_grid: Arc<Vec<f32>>,
     pub width: u16,
     pub height: u16,
 }
+
+/// Summary of the living entities carrying one tag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagStats {
+    pub count: usize,
+    pub mean_energy: f64,
+    pub mean_generation: f64,
+    pub mean_age: f64,
+    /// Mean position of the cohort.
+    pub centroid: (f64, f64),
+    /// Region the cohort occupies, as `(min_x, min_y, max_x, max_y)`.
+    pub bounds: (f64, f64, f64, f64),
+}
+
+impl WorldSnapshot {
+    /// Entities carrying `tag`.
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a EntitySnapshot> {
+        self.entities
+            .iter()
+            .filter(move |e| e.tags.iter().any(|t| t == tag))
+    }
+
+    /// Statistics of the entities carrying `tag`, or `None` if none are alive.
+    pub fn tag_stats(&self, tag: &str) -> Option<TagStats> {
+        let mut count = 0usize;
+        let (mut energy, mut generation, mut age, mut sx, mut sy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        let mut bounds = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+        for e in self.tagged(tag) {
+            count += 1;
+            energy += e.energy;
+            generation += f64::from(e.generation);
+            age += e.age as f64;
+            sx += e.x;
+            sy += e.y;
+            bounds = (
+                bounds.0.min(e.x),
+                bounds.1.min(e.y),
+                bounds.2.max(e.x),
+                bounds.3.max(e.y),
+            );
+        }
+        if count == 0 {
+            return None;
+        }
+        let n = count as f64;
+        Some(TagStats {
+            count,
+            mean_energy: energy / n,
+            mean_generation: generation / n,
+            mean_age: age / n,
+            centroid: (sx / n, sy / n),
+            bounds,
+        })
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::SoundConfig;
+use crate::terrain::{TerrainGrid, TerrainType};
 use rayon::prelude::*;
 use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use serde::{Deserialize, Serialize};
@@ -21,6 +23,11 @@ pub struct SoundGrid {
     #[serde(skip)]
     #[with(rkyv::with::Skip)]
     atomic_deposits: Vec<AtomicU32>,
+    /// Share of the sound reaching each cell that it passes on; empty when
+    /// sound is not occluded.
+    #[serde(skip)]
+    #[with(rkyv::with::Skip)]
+    transmission: Vec<f32>,
     pub width: u16,
     pub height: u16,
     #[serde(skip)]
@@ -35,6 +42,7 @@ impl Clone for SoundGrid {
             cells: self.cells.clone(),
             back_buffer: self.back_buffer.clone(),
             atomic_deposits: (0..size).map(|_| AtomicU32::new(0)).collect(),
+            transmission: self.transmission.clone(),
             width: self.width,
             height: self.height,
             is_dirty: self.is_dirty,
@@ -56,12 +64,20 @@ impl SoundGrid {
             cells: vec![0.0; size],
             back_buffer: vec![0.0; size],
             atomic_deposits: (0..size).map(|_| AtomicU32::new(0)).collect(),
+            transmission: Vec::new(),
             width,
             height,
             is_dirty: true,
         }
     }
 
+    /// Rebuilds the buffers a save leaves out.
+    pub fn restore_buffers(&mut self) {
+        let size = self.cells.len();
+        self.back_buffer = vec![0.0; size];
+        self.atomic_deposits = (0..size).map(|_| AtomicU32::new(0)).collect();
+    }
+
     #[inline(always)]
     fn index(&self, x: u16, y: u16) -> usize {
         (y as usize * self.width as usize) + x as usize
@@ -92,6 +108,21 @@ impl SoundGrid {
         }
     }
 
+    /// Takes the transmission of every cell from `terrain` before the next
+    /// [`update`](Self::update), or turns occlusion off if `config` does.
+    pub fn occlude(&mut self, terrain: &TerrainGrid, config: &SoundConfig) {
+        self.transmission.clear();
+        if !config.occlusion || terrain.len() != self.cells.len() {
+            return;
+        }
+        self.transmission
+            .extend(terrain.cells().map(|cell| match cell.terrain_type {
+                TerrainType::Wall => config.wall_transmission,
+                TerrainType::Mountain => config.mountain_transmission,
+                _ => 1.0,
+            }));
+    }
+
     pub fn update(&mut self) {
         self.is_dirty = true;
         let size = self.cells.len();
@@ -105,6 +136,7 @@ impl SoundGrid {
         let height = self.height;
 
         let atomics = &self.atomic_deposits;
+        let transmission = &self.transmission;
 
         self.cells
             .par_iter_mut()
@@ -134,7 +166,8 @@ impl SoundGrid {
                     0.0
                 };
                 let dep = f32::from_bits(atomics[idx].swap(0, Ordering::SeqCst));
-                *cell = (old_cells[idx] * 0.4 + diffused * 0.6 + dep) * 0.7;
+                let passed = transmission.get(idx).copied().unwrap_or(1.0);
+                *cell = ((old_cells[idx] * 0.4 + diffused * 0.6) * passed + dep) * 0.7;
                 if *cell < 0.01 {
                     *cell = 0.0;
                 }
@@ -192,4 +225,40 @@ mod tests {
         assert!(neighbor > 0.0, "Sound should propagate");
         assert!(center > neighbor, "Center should be louder");
     }
+
+    #[test]
+    fn test_walls_occlude_sound() {
+        let mut terrain = TerrainGrid::generate(20, 9, 7);
+        for y in 0..9 {
+            for x in 0..20 {
+                let t = if x == 10 {
+                    TerrainType::Wall
+                } else {
+                    TerrainType::Plains
+                };
+                terrain.set_cell_type(x, y, t);
+            }
+        }
+        let loudness = |occlusion: bool| {
+            let mut grid = SoundGrid::new(20, 9);
+            grid.occlude(
+                &terrain,
+                &SoundConfig {
+                    occlusion,
+                    ..SoundConfig::default()
+                },
+            );
+            for _ in 0..6 {
+                grid.deposit(9.0, 4.0, 1.0);
+                grid.update();
+            }
+            (grid.get_cell(9, 4), grid.get_cell(11, 4))
+        };
+
+        let (open_near, open_far) = loudness(false);
+        let (near, far) = loudness(true);
+        assert!(open_far > 0.0);
+        assert!(far < open_far * 0.2, "{far} vs {open_far}");
+        assert!((near - open_near).abs() < open_near * 0.2);
+    }
 }
@@ -22,6 +22,7 @@ use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 /// - `entity_indices`: Compact storage of all entity indices, sorted by cell
 /// - `lineage_centroids`: Cached kin centroids per lineage
 /// - `lineage_density`: Per-cell lineage density maps
+/// - `body_radii`: Body radius of each entity, empty when all are single cells
 ///
 /// # Implementation Notes
 /// - Uses "offset array" pattern (like compressed sparse rows)
@@ -51,6 +52,8 @@ pub struct SpatialHash {
     pub entity_indices: Vec<usize>,
     pub lineage_centroids: HashMap<uuid::Uuid, (f64, f64, usize)>,
     pub lineage_density: Vec<HashMap<uuid::Uuid, f32>>,
+    pub body_radii: Vec<f64>,
+    pub max_body_radius: f64,
 }
 
 impl SpatialHash {
@@ -83,6 +86,8 @@ impl SpatialHash {
             entity_indices: Vec::new(),
             lineage_centroids: HashMap::new(),
             lineage_density: vec![HashMap::new(); cols * rows],
+            body_radii: Vec::new(),
+            max_body_radius: 0.0,
         }
     }
 
@@ -143,6 +148,8 @@ impl SpatialHash {
     }
 
     pub fn build_with_lineage(&mut self, data: &[(f64, f64, uuid::Uuid)], width: u16, height: u16) {
+        self.body_radii.clear();
+        self.max_body_radius = 0.0;
         self.width = width;
         self.height = height;
         self.cols = (f64::from(width) / self.cell_size).ceil() as usize;
@@ -210,6 +217,39 @@ impl SpatialHash {
             });
     }
 
+    /// Records the body radius of each entity, in the order they were built
+    /// with; see [`crate::body::radius`].
+    pub fn set_body_radii(&mut self, radii: impl IntoIterator<Item = f64>) {
+        self.body_radii.clear();
+        self.body_radii.extend(
+            radii
+                .into_iter()
+                .map(|r| if r.is_finite() { r.max(0.0) } else { 0.0 }),
+        );
+        self.max_body_radius = self.body_radii.iter().copied().fold(0.0, f64::max);
+    }
+
+    /// Body radius of the entity at `idx`, 0.0 for a single cell.
+    pub fn body_radius(&self, idx: usize) -> f64 {
+        self.body_radii.get(idx).copied().unwrap_or(0.0)
+    }
+
+    /// Like [`Self::query_callback`] for a body of radius `reach` at
+    /// `(x, y)`: the query widens by that radius and by the largest body
+    /// indexed, so large bodies are found from their edge rather than their
+    /// centre.
+    pub fn query_bodies<F>(&self, x: f64, y: f64, radius: f64, reach: f64, callback: F)
+    where
+        F: FnMut(usize),
+    {
+        self.query_callback(
+            x,
+            y,
+            radius + reach.max(0.0) + self.max_body_radius,
+            callback,
+        );
+    }
+
     pub fn get_lineage_density(&self, x: f64, y: f64, lid: uuid::Uuid) -> f32 {
         if let Some(idx) = self.get_cell_idx(x, y) {
             let mut total = 0.0;
@@ -436,6 +476,29 @@ pub mod tests {
         assert_eq!(count, 1);
     }
 
+    #[test]
+    fn test_spatial_hash_query_bodies_reaches_large_bodies() {
+        let mut sh = SpatialHash::new(5.0, 20, 20);
+        let data = vec![
+            (4.0, 4.0, uuid::Uuid::new_v4()),
+            (6.5, 4.0, uuid::Uuid::new_v4()),
+        ];
+        sh.build_with_lineage(&data, 20, 20);
+
+        let mut count = 0;
+        sh.query_bodies(4.0, 4.0, 0.5, 0.0, |_| count += 1);
+        assert_eq!(count, 1);
+
+        sh.set_body_radii([0.0, 1.0]);
+        assert_eq!(sh.body_radius(1), 1.0);
+        let mut count = 0;
+        sh.query_bodies(4.0, 4.0, 0.5, 0.0, |_| count += 1);
+        assert_eq!(count, 2);
+
+        sh.build_with_lineage(&data, 20, 20);
+        assert_eq!(sh.max_body_radius, 0.0);
+    }
+
     #[test]
     fn test_spatial_hash_clear() {
         let mut sh = SpatialHash::new(5.0, 20, 20);
@@ -1,18 +1,34 @@
+pub mod steering;
+
+use crate::brain::BrainLogic;
 use crate::config::AppConfig;
 use crate::environment::Environment;
+use crate::pheromone::{PheromoneDeposit, PheromoneType, EMIT_OUTPUTS};
+use crate::systems::migration::MigrationRoute;
+use crate::systems::status;
 use crate::terrain::TerrainGrid;
-use primordium_data::{Entity, Health, Intel, Metabolism, Physics, Specialization};
+use primordium_data::{Entity, Health, Intel, Metabolism, Physics, Specialization, StatusKind};
 use std::collections::HashMap;
+use steering::{SteeringForces, SteeringWeights};
+
+/// Kin closer than this are kept apart by flocking separation.
+const FLOCK_SEPARATION_RADIUS: f64 = 1.0;
+/// How much higher a stranger's trophic potential must be to count as a threat.
+const THREAT_TROPHIC_MARGIN: f32 = 0.2;
+/// `Deceive` output above which a false food trail is laid.
+pub const DECEPTION_GATE: f32 = 0.5;
 
 pub struct ActionContext<'a> {
     pub env: &'a Environment,
     pub config: &'a AppConfig,
     pub terrain: &'a TerrainGrid,
     pub influence: &'a crate::influence::InfluenceGrid,
+    pub roads: &'a crate::road::RoadGrid,
     pub snapshots: &'a [crate::snapshot::InternalEntitySnapshot],
     pub entity_id_map: &'a HashMap<uuid::Uuid, usize>,
     pub spatial_hash: &'a crate::spatial_hash::SpatialHash,
     pub pressure: &'a crate::pressure::PressureGrid,
+    pub pheromones: &'a crate::pheromone::PheromoneGrid,
     pub width: u16,
     pub height: u16,
 }
@@ -25,6 +41,8 @@ pub struct ActionEntity<'a> {
     pub metabolism: &'a mut Metabolism,
     pub intel: &'a mut Intel,
     pub health: &'a mut Health,
+    /// Route of the lineage migration, if one is active.
+    pub migration: Option<&'a MigrationRoute>,
 }
 
 pub struct ActionOutput {
@@ -33,6 +51,14 @@ pub struct ActionOutput {
     pub pressure: Vec<crate::pressure::PressureDeposit>,
     pub oxygen_drain: f64,
     pub overmind_broadcast: Option<(uuid::Uuid, f32)>,
+    /// Energy spent this tick on movement, signalling and exertion.
+    pub movement_cost: f64,
+    /// Energy spent this tick on idle metabolism, brain upkeep and crowding.
+    pub basal_cost: f64,
+    /// Energy lost this tick to trampling in a dense crowd.
+    pub trample_damage: f64,
+    /// Where the entity ended up after moving this tick.
+    pub position: (f64, f64),
 }
 
 impl Default for ActionOutput {
@@ -43,6 +69,10 @@ impl Default for ActionOutput {
             pressure: Vec::with_capacity(2),
             oxygen_drain: 0.0,
             overmind_broadcast: None,
+            movement_cost: 0.0,
+            basal_cost: 0.0,
+            trample_damage: 0.0,
+            position: (0.0, 0.0),
         }
     }
 }
@@ -74,12 +104,10 @@ fn handle_terraforming(ctx: &mut TerraformContext) {
             );
 
             if max_press > 0.2 {
-                let dx = tx - ctx.position.x;
-                let dy = ty - ctx.position.y;
-                let dist = (dx * dx + dy * dy).sqrt().max(1.0);
+                let (fx, fy) = steering::seek((ctx.position.x, ctx.position.y), (tx, ty));
                 let attr_force = if is_near_river { 0.3 } else { 0.15 };
-                ctx.velocity.vx += (dx / dist) * attr_force;
-                ctx.velocity.vy += (dy / dist) * attr_force;
+                ctx.velocity.vx += fx * attr_force;
+                ctx.velocity.vy += fy * attr_force;
             }
 
             if is_near_river
@@ -102,10 +130,12 @@ fn handle_terraforming(ctx: &mut TerraformContext) {
 pub fn action_system_components_with_modifiers(
     entity: &mut ActionEntity,
     eff_max_speed: f64,
-    outputs: [f32; 12],
+    outputs: [f32; 15],
     ctx: &mut ActionContext,
     output: &mut ActionOutput,
 ) {
+    let eff_max_speed = eff_max_speed
+        * crate::body::speed_factor(entity.intel.genotype.body_size, &ctx.config.body);
     let speed_mult = (1.0 + f64::midpoint(f64::from(outputs[2]), 1.0)) * eff_max_speed;
     let predation_mode = f64::midpoint(f64::from(outputs[3]), 1.0) > 0.5;
 
@@ -153,7 +183,7 @@ pub fn action_system_components_with_modifiers(
         count
     };
 
-    let total_cost = calculate_metabolic_cost(MetabolicCostInput {
+    let cost = calculate_metabolic_cost(MetabolicCostInput {
         intel: entity.intel,
         metabolism: entity.metabolism,
         ctx,
@@ -161,14 +191,20 @@ pub fn action_system_components_with_modifiers(
         predation_mode,
         signal_strength: outputs[5].abs(),
         activity_drain,
-        cell,
-        effective_metabolism_mult,
+        nest_shield: status::magnitude(&entity.health.effects, StatusKind::NestShield),
+        effective_metabolism_mult: effective_metabolism_mult
+            * f64::from(status::factor(
+                &entity.health.effects,
+                StatusKind::MetabolicRate,
+            )),
         x: entity.position.x,
         y: entity.position.y,
         neighbor_count,
     });
 
-    entity.metabolism.energy -= total_cost;
+    entity.metabolism.energy -= cost.total;
+    output.movement_cost = cost.movement;
+    output.basal_cost = cost.basal;
 
     apply_social_forces(
         &mut BondContext {
@@ -182,7 +218,13 @@ pub fn action_system_components_with_modifiers(
         ctx,
     );
 
-    handle_emissions(entity.position, outputs, entity.intel, output);
+    let emit = ctx.pheromones.channels_named(&ctx.config.pheromone.emit);
+    handle_emissions(entity.position, outputs, emit, output);
+
+    if outputs[14] > DECEPTION_GATE {
+        let food = ctx.pheromones.channel_of(PheromoneType::Food);
+        handle_deception(entity, ctx.config, food, output);
+    }
 
     handle_terraforming(&mut TerraformContext {
         position: entity.position,
@@ -199,19 +241,71 @@ pub fn action_system_components_with_modifiers(
         output.overmind_broadcast = Some((*entity.id, outputs[11]));
     }
 
+    if let Some(route) = entity.migration {
+        let (fx, fy) = steering::follow_flow((entity.position.x, entity.position.y), |x, y| {
+            route
+                .next_waypoint(x, y)
+                .map(|waypoint| steering::seek((x, y), waypoint))
+        });
+        let force = ctx.config.migration.steering_force;
+        entity.velocity.vx += fx * force;
+        entity.velocity.vy += fy * force;
+    }
+
+    handle_flocking(entity, &outputs, ctx);
+
+    handle_death_avoidance(entity, ctx);
+
     handle_repulsion(entity.position, entity.velocity, entity.id, ctx);
 
+    let crowd = crate::systems::crowd::crowd_response(
+        entity.position.x,
+        entity.position.y,
+        entity.id,
+        ctx.snapshots,
+        ctx.spatial_hash,
+        &ctx.config.crowd,
+    );
+    entity.velocity.vx += crowd.push.0;
+    entity.velocity.vy += crowd.push.1;
+    entity.metabolism.energy -= crowd.trample_damage;
+    output.trample_damage = crowd.trample_damage;
+
+    // Roads speed travel up without raising its energy cost.
+    let road_factor = ctx.roads.speed_factor(
+        entity.position.x,
+        entity.position.y,
+        entity.metabolism.lineage_id,
+        &ctx.config.roads,
+    );
     handle_movement_components(MovementContext {
         position: entity.position,
         velocity: entity.velocity,
-        speed: speed_mult,
+        speed: speed_mult * road_factor,
         terrain: ctx.terrain,
         width: ctx.width,
         height: ctx.height,
     });
+    output.position = (entity.position.x, entity.position.y);
     output.oxygen_drain = activity_drain;
 }
 
+/// Pushes the entity away from rising death scent, as hard as its
+/// `death_avoidance` gene dictates.
+fn handle_death_avoidance(entity: &mut ActionEntity, ctx: &ActionContext) {
+    let config = &ctx.config.death_scent;
+    let aversion = f64::from(entity.intel.genotype.death_avoidance) * config.avoidance_force;
+    if !config.enabled || aversion <= 0.0 {
+        return;
+    }
+    let (gx, gy) =
+        ctx.pheromones
+            .death_gradient(entity.position.x, entity.position.y, config.radius);
+    let (fx, fy) = steering::clamp_unit((-f64::from(gx), -f64::from(gy)));
+    entity.velocity.vx += fx * aversion;
+    entity.velocity.vy += fy * aversion;
+}
+
 struct MetabolicCostInput<'a, 'b> {
     intel: &'a Intel,
     metabolism: &'a Metabolism,
@@ -220,18 +314,28 @@ struct MetabolicCostInput<'a, 'b> {
     predation_mode: bool,
     signal_strength: f32,
     activity_drain: f64,
-    cell: &'a crate::terrain::TerrainCell,
+    /// Idle cost reduction from the nest shield effect.
+    nest_shield: f32,
     effective_metabolism_mult: f64,
     x: f64,
     y: f64,
     neighbor_count: usize,
 }
 
-fn calculate_metabolic_cost<'a, 'b>(input: MetabolicCostInput<'a, 'b>) -> f64 {
+struct MetabolicCost {
+    total: f64,
+    movement: f64,
+    basal: f64,
+}
+
+fn calculate_metabolic_cost<'a, 'b>(input: MetabolicCostInput<'a, 'b>) -> MetabolicCost {
     let oxygen_factor = (input.ctx.env.oxygen_level / 21.0).max(0.1);
     let aerobic_boost = oxygen_factor.sqrt();
+    let body_factor =
+        crate::body::metabolic_factor(input.intel.genotype.body_size, &input.ctx.config.body);
 
     let mut move_cost = input.ctx.config.metabolism.base_move_cost
+        * body_factor
         * input.effective_metabolism_mult
         * input.speed_mult
         / aerobic_boost;
@@ -242,12 +346,13 @@ fn calculate_metabolic_cost<'a, 'b>(input: MetabolicCostInput<'a, 'b>) -> f64 {
 
     let signal_cost =
         f64::from(input.signal_strength) * input.ctx.config.social.sharing_fraction * 2.0;
-    let brain_maintenance = (input.intel.genotype.brain.nodes.len() as f64
-        * input.ctx.config.brain.hidden_node_cost)
-        + (input.intel.genotype.brain.connections.len() as f64
-            * input.ctx.config.brain.connection_cost);
+    let brain_maintenance = input
+        .intel
+        .genotype
+        .brain
+        .maintenance_cost(&input.ctx.config.brain);
 
-    let mut base_idle = input.ctx.config.metabolism.base_idle_cost;
+    let mut base_idle = input.ctx.config.metabolism.base_idle_cost * body_factor;
 
     if input
         .intel
@@ -257,9 +362,7 @@ fn calculate_metabolic_cost<'a, 'b>(input: MetabolicCostInput<'a, 'b>) -> f64 {
         base_idle *= 0.8;
     }
 
-    if matches!(input.cell.terrain_type, primordium_data::TerrainType::Nest) {
-        base_idle *= 1.0 - f64::from(input.ctx.config.ecosystem.corpse_fertility_mult);
-    }
+    base_idle *= 1.0 - f64::from(input.nest_shield).clamp(0.0, 1.0);
 
     // Phase 67 Task C: Apply DDA multiplier to idle cost
     let mut idle_cost = (base_idle + brain_maintenance)
@@ -296,7 +399,11 @@ fn calculate_metabolic_cost<'a, 'b>(input: MetabolicCostInput<'a, 'b>) -> f64 {
         * input.ctx.config.metabolism.crowding_cost;
     idle_cost += crowding_tax;
 
-    move_cost + signal_cost + idle_cost + input.activity_drain
+    MetabolicCost {
+        total: move_cost + signal_cost + idle_cost + input.activity_drain,
+        movement: move_cost + signal_cost + input.activity_drain,
+        basal: idle_cost,
+    }
 }
 
 pub struct BondContext<'a> {
@@ -354,48 +461,39 @@ fn apply_social_forces(ctx: &mut BondContext, action_ctx: &ActionContext) {
             },
         );
 
-        if let Some((ax, ay)) = best_alpha_pos {
-            let dx = ax - ctx.position.x;
-            let dy = ay - ctx.position.y;
-            let dist = (dx * dx + dy * dy).sqrt().max(1.0);
-            ctx.velocity.vx = ctx.velocity.vx * 0.7 + (dx / dist) * 0.3;
-            ctx.velocity.vy = ctx.velocity.vy * 0.7 + (dy / dist) * 0.3;
+        if let Some(alpha) = best_alpha_pos {
+            let (fx, fy) = steering::seek((ctx.position.x, ctx.position.y), alpha);
+            ctx.velocity.vx = ctx.velocity.vx * 0.7 + fx * 0.3;
+            ctx.velocity.vy = ctx.velocity.vy * 0.7 + fy * 0.3;
         }
     }
 }
 
+/// Emits sound for each active Emit output, and pheromone on the channel
+/// `emit` maps it to.
 fn handle_emissions(
     position: &primordium_data::Position,
-    outputs: [f32; 12],
-    _intel: &Intel,
+    outputs: [f32; 15],
+    emit: [Option<usize>; EMIT_OUTPUTS],
     output: &mut ActionOutput,
 ) {
-    if outputs[6].abs() > 0.1 {
-        output.sounds.push(crate::sound::SoundDeposit {
-            x: position.x,
-            y: position.y,
-            amount: outputs[6].abs(),
-        });
-        output.pheromones.push(crate::pheromone::PheromoneDeposit {
-            x: position.x,
-            y: position.y,
-            ptype: crate::pheromone::PheromoneType::SignalA,
-            amount: outputs[6].abs(),
-        });
-    }
-
-    if outputs[7].abs() > 0.1 {
+    for (amount, channel) in [outputs[6].abs(), outputs[7].abs()].into_iter().zip(emit) {
+        if amount <= 0.1 {
+            continue;
+        }
         output.sounds.push(crate::sound::SoundDeposit {
             x: position.x,
             y: position.y,
-            amount: outputs[7].abs(),
-        });
-        output.pheromones.push(crate::pheromone::PheromoneDeposit {
-            x: position.x,
-            y: position.y,
-            ptype: crate::pheromone::PheromoneType::SignalB,
-            amount: outputs[7].abs(),
+            amount,
         });
+        if let Some(channel) = channel {
+            output.pheromones.push(PheromoneDeposit {
+                x: position.x,
+                y: position.y,
+                channel,
+                amount,
+            });
+        }
     }
 
     if outputs[9] > 0.5 {
@@ -416,9 +514,31 @@ fn handle_emissions(
     }
 }
 
+/// Lays a food pheromone trail where the entity has not eaten, luring
+/// trusting foragers away (or towards the deceiver) at an energy cost.
+fn handle_deception(
+    entity: &mut ActionEntity,
+    config: &AppConfig,
+    food_channel: Option<usize>,
+    output: &mut ActionOutput,
+) {
+    let cost = config.social.deception_cost;
+    entity.metabolism.energy -= cost;
+    output.movement_cost += cost;
+    entity.intel.deceptions += 1;
+    if let Some(channel) = food_channel {
+        output.pheromones.push(PheromoneDeposit {
+            x: entity.position.x,
+            y: entity.position.y,
+            channel,
+            amount: config.social.deception_strength,
+        });
+    }
+}
+
 pub fn action_system_components(
     entity: &mut ActionEntity,
-    outputs: [f32; 12],
+    outputs: [f32; 15],
     ctx: &mut ActionContext,
     output: &mut ActionOutput,
 ) {
@@ -451,6 +571,73 @@ pub struct MovementContext<'a> {
     pub height: u16,
 }
 
+/// Blends the flocking behaviours selected by the brain outputs. Cohesion,
+/// alignment and separation act on kin within sensing range; flee acts on the
+/// nearest stranger that out-ranks the entity in the food chain.
+fn handle_flocking(entity: &mut ActionEntity, outputs: &[f32; 15], ctx: &ActionContext) {
+    let strength = ctx.config.steering.flocking_force;
+    if strength <= 0.0 {
+        return;
+    }
+
+    let position = (entity.position.x, entity.position.y);
+    let velocity = (entity.velocity.vx, entity.velocity.vy);
+    let range = entity.physics.sensing_range;
+    let mut kin = 0usize;
+    let (mut centre, mut mean_velocity, mut separation) = ((0.0, 0.0), (0.0, 0.0), (0.0, 0.0));
+    let mut threat: Option<(f64, (f64, f64))> = None;
+
+    ctx.spatial_hash
+        .query_callback(position.0, position.1, range, |idx| {
+            let s = &ctx.snapshots[idx];
+            if s.id == *entity.id {
+                return;
+            }
+            let dist_sq = (s.x - position.0).powi(2) + (s.y - position.1).powi(2);
+            if dist_sq >= range * range {
+                return;
+            }
+            if s.lineage_id == entity.metabolism.lineage_id {
+                kin += 1;
+                centre = (centre.0 + s.x, centre.1 + s.y);
+                mean_velocity = (mean_velocity.0 + s.vx, mean_velocity.1 + s.vy);
+                let (fx, fy) = steering::separation(
+                    position,
+                    [(s.x, s.y)],
+                    FLOCK_SEPARATION_RADIUS,
+                    (0.0, 0.0),
+                );
+                separation = (separation.0 + fx, separation.1 + fy);
+            } else if s.trophic_potential
+                > entity.metabolism.trophic_potential + THREAT_TROPHIC_MARGIN
+                && threat.is_none_or(|(d, _)| dist_sq < d)
+            {
+                threat = Some((dist_sq, (s.x, s.y)));
+            }
+        });
+
+    let (hi, lo) = entity.id.as_u64_pair();
+    let seed = (hi ^ lo ^ position.0.to_bits().rotate_left(17) ^ position.1.to_bits())
+        .wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        >> 32;
+    let mut forces = SteeringForces {
+        flee: threat.map_or((0.0, 0.0), |(_, at)| steering::flee(position, at)),
+        wander: steering::wander(velocity, seed),
+        separation: steering::clamp_unit(separation),
+        ..SteeringForces::default()
+    };
+    if kin > 0 {
+        let n = kin as f64;
+        forces.cohesion = steering::cohesion(position, (centre.0 / n, centre.1 / n));
+        forces.alignment =
+            steering::alignment(velocity, (mean_velocity.0 / n, mean_velocity.1 / n));
+    }
+
+    let (fx, fy) = SteeringWeights::from_outputs(outputs).combine(&forces, strength);
+    entity.velocity.vx += fx;
+    entity.velocity.vy += fy;
+}
+
 fn handle_repulsion(
     position: &primordium_data::Position,
     velocity: &mut primordium_data::Velocity,
@@ -463,30 +650,23 @@ fn handle_repulsion(
 
     let radius = 0.5; // Repulsion radius
     let force_mult = ctx.config.world.repulsion_force;
+    // Exact overlaps have no direction to push along; derive one from the id
+    // so the split stays deterministic.
+    let angle = f64::from(id.as_bytes()[0]) * 0.1;
+    let overlap = (angle.cos(), angle.sin());
 
     ctx.spatial_hash
         .query_callback(position.x, position.y, radius, |idx| {
             let neighbor = &ctx.snapshots[idx];
             if neighbor.id != *id {
-                let dx = position.x - neighbor.x;
-                let dy = position.y - neighbor.y;
-                let dist_sq = dx * dx + dy * dy;
-
-                if dist_sq < radius * radius && dist_sq > 0.0001 {
-                    let dist = dist_sq.sqrt();
-                    let push = (radius - dist) / radius; // 0.0 to 1.0
-                    let force = push * force_mult;
-
-                    velocity.vx += (dx / dist) * force;
-                    velocity.vy += (dy / dist) * force;
-                } else if dist_sq <= 0.0001 {
-                    // Exact overlap - push in random direction based on ID hash or pseudo-random
-                    // To be deterministic, use ID byte logic
-                    let hash = id.as_bytes()[0] as f64;
-                    let angle = hash * 0.1;
-                    velocity.vx += angle.cos() * force_mult * 0.5;
-                    velocity.vy += angle.sin() * force_mult * 0.5;
-                }
+                let (fx, fy) = steering::separation(
+                    (position.x, position.y),
+                    [(neighbor.x, neighbor.y)],
+                    radius,
+                    overlap,
+                );
+                velocity.vx += fx * force_mult;
+                velocity.vy += fy * force_mult;
             }
         });
 }
@@ -550,7 +730,7 @@ pub fn handle_game_modes_ecs(
 
 pub fn action_system(
     entity: &mut Entity,
-    outputs: [f32; 12],
+    outputs: [f32; 15],
     ctx: &mut ActionContext,
     output: &mut ActionOutput,
 ) {
@@ -562,6 +742,7 @@ pub fn action_system(
         metabolism: &mut entity.metabolism,
         intel: &mut entity.intel,
         health: &mut entity.health,
+        migration: None,
     };
     action_system_components(&mut action_entity, outputs, ctx, output);
 }
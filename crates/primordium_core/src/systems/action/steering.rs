@@ -0,0 +1,168 @@
+//! Steering behaviours.
+//!
+//! Each behaviour turns what an entity perceives into a velocity change and
+//! knows nothing about the world, so movement rules can be tested on plain
+//! vectors. Scripted forces (bond springs, alpha following, engineers,
+//! migration routes) call [`seek`] and [`follow_flow`] directly; the flocking
+//! behaviours are blended by [`SteeringWeights`], which the brain selects
+//! through its existing outputs, so evolution can shape how entities move as a
+//! group without dedicating new outputs to it.
+
+/// A velocity change.
+pub type Force = (f64, f64);
+
+/// Within this distance of a target, [`seek`] stops pushing.
+pub const ARRIVAL_DISTANCE: f64 = 0.5;
+
+/// Largest turn [`wander`] makes away from the current heading, in radians.
+pub const WANDER_JITTER: f64 = std::f64::consts::FRAC_PI_4;
+
+pub fn length((x, y): Force) -> f64 {
+    x.hypot(y)
+}
+
+/// Scales `force` down to at most unit length.
+pub fn clamp_unit(force: Force) -> Force {
+    let len = length(force);
+    if len > 1.0 {
+        (force.0 / len, force.1 / len)
+    } else {
+        force
+    }
+}
+
+/// Toward `target`: unit length from one cell out, shrinking linearly inside
+/// it and zero within [`ARRIVAL_DISTANCE`].
+pub fn seek(from: (f64, f64), target: (f64, f64)) -> Force {
+    let (dx, dy) = (target.0 - from.0, target.1 - from.1);
+    let dist = dx.hypot(dy);
+    if dist <= ARRIVAL_DISTANCE {
+        return (0.0, 0.0);
+    }
+    let scale = dist.min(1.0) / dist;
+    (dx * scale, dy * scale)
+}
+
+/// Unit force directly away from `threat`; zero when on top of it.
+pub fn flee(from: (f64, f64), threat: (f64, f64)) -> Force {
+    let (dx, dy) = (from.0 - threat.0, from.1 - threat.1);
+    let dist = dx.hypot(dy);
+    if dist < 1e-9 {
+        return (0.0, 0.0);
+    }
+    (dx / dist, dy / dist)
+}
+
+/// Unit force along `heading` turned by up to [`WANDER_JITTER`], or in any
+/// direction when standing still. The turn comes from `seed`, so the same
+/// seed always wanders the same way.
+pub fn wander(heading: Force, seed: u64) -> Force {
+    let unit = (seed % 10_000) as f64 / 10_000.0;
+    let angle = if length(heading) < 1e-9 {
+        unit * std::f64::consts::TAU
+    } else {
+        heading.1.atan2(heading.0) + (unit * 2.0 - 1.0) * WANDER_JITTER
+    };
+    (angle.cos(), angle.sin())
+}
+
+/// Samples a flow field at `position`, clamped to unit length. Cells the
+/// field has no direction for produce no force.
+pub fn follow_flow(position: (f64, f64), field: impl Fn(f64, f64) -> Option<Force>) -> Force {
+    field(position.0, position.1).map_or((0.0, 0.0), clamp_unit)
+}
+
+/// Away from each neighbour within `radius`, weighted by how close it is:
+/// one unit per neighbour at zero distance, nothing at the edge. Neighbours
+/// exactly on top of the entity push along `overlap` at half strength, since
+/// there is no direction to flee from.
+pub fn separation(
+    position: (f64, f64),
+    neighbours: impl IntoIterator<Item = (f64, f64)>,
+    radius: f64,
+    overlap: Force,
+) -> Force {
+    let mut force = (0.0, 0.0);
+    for (nx, ny) in neighbours {
+        let (dx, dy) = (position.0 - nx, position.1 - ny);
+        let dist_sq = dx * dx + dy * dy;
+        if dist_sq <= 0.0001 {
+            force.0 += overlap.0 * 0.5;
+            force.1 += overlap.1 * 0.5;
+        } else if dist_sq < radius * radius {
+            let dist = dist_sq.sqrt();
+            let push = (radius - dist) / radius;
+            force.0 += dx / dist * push;
+            force.1 += dy / dist * push;
+        }
+    }
+    force
+}
+
+/// Toward the centre of the group.
+pub fn cohesion(position: (f64, f64), centre: (f64, f64)) -> Force {
+    seek(position, centre)
+}
+
+/// Toward the group's mean velocity, clamped to unit length.
+pub fn alignment(velocity: Force, mean_velocity: Force) -> Force {
+    clamp_unit((mean_velocity.0 - velocity.0, mean_velocity.1 - velocity.1))
+}
+
+/// One force per flocking behaviour, each of at most unit length.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SteeringForces {
+    pub flee: Force,
+    pub wander: Force,
+    pub separation: Force,
+    pub cohesion: Force,
+    pub alignment: Force,
+}
+
+/// How strongly an entity follows each flocking behaviour, each in `0..=1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SteeringWeights {
+    pub flee: f64,
+    pub wander: f64,
+    pub separation: f64,
+    pub cohesion: f64,
+    pub alignment: f64,
+}
+
+impl SteeringWeights {
+    /// Reads the weights off the brain outputs that already express the
+    /// matching intent:
+    ///
+    /// - `flee`: timidity, one minus aggression (output 3)
+    /// - `separation`: aggression (output 3)
+    /// - `cohesion`: share intent (output 4)
+    /// - `alignment`: vocalization (outputs 6 and 7)
+    /// - `wander`: lack of a movement intent (outputs 0 and 1)
+    pub fn from_outputs(outputs: &[f32; 15]) -> Self {
+        let unit = |o: f32| f64::midpoint(f64::from(o), 1.0).clamp(0.0, 1.0);
+        let aggression = unit(outputs[3]);
+        let intent = f64::from(outputs[0]).hypot(f64::from(outputs[1])).min(1.0);
+        Self {
+            flee: 1.0 - aggression,
+            wander: 1.0 - intent,
+            separation: aggression,
+            cohesion: unit(outputs[4]),
+            alignment: (f64::from(outputs[6] + outputs[7]) + 2.0) / 4.0,
+        }
+    }
+
+    /// Weighted sum of `forces`, scaled by `strength`.
+    pub fn combine(&self, forces: &SteeringForces, strength: f64) -> Force {
+        let terms = [
+            (self.flee, forces.flee),
+            (self.wander, forces.wander),
+            (self.separation, forces.separation),
+            (self.cohesion, forces.cohesion),
+            (self.alignment, forces.alignment),
+        ];
+        let (x, y) = terms.iter().fold((0.0, 0.0), |(x, y), &(w, (fx, fy))| {
+            (x + w * fx, y + w * fy)
+        });
+        (x * strength, y * strength)
+    }
+}
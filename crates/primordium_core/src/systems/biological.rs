@@ -1,9 +1,10 @@
 //! Biological system - handles infection, pathogen emergence, and death processing.
 
+use crate::brain::BrainLogic;
 use crate::config::AppConfig;
 use crate::spatial_hash::SpatialHash;
-use crate::systems::social;
-use primordium_data::{Health, Intel, Metabolism, Pathogen, Physics, Specialization};
+use crate::systems::{social, status};
+use primordium_data::{Health, Intel, Metabolism, Pathogen, Physics, Specialization, StatusKind};
 use rand::Rng;
 use std::collections::HashSet;
 
@@ -40,14 +41,8 @@ pub fn biological_system_components<R: Rng>(
     _physics: &Physics,
     context: &mut BiologicalContext<'_, R>,
 ) -> f64 {
-    let mut metabolic_consumption = 0.0;
+    let mut metabolic_consumption = status::process_status_effects(health, metabolism);
 
-    // Track pathogen consumption (before processing to capture lethality)
-    if let Some(p) = &health.pathogen {
-        metabolic_consumption += f64::from(p.lethality);
-    }
-
-    process_infection_components(health, metabolism);
     update_reputation_progress(intel);
     apply_genetic_drift(intel, context.population_count, context.config, context.rng);
     update_specialization_progress(intel, context.config);
@@ -62,9 +57,7 @@ pub fn biological_system_components<R: Rng>(
         1.0
     };
 
-    let brain_maintenance = (intel.genotype.brain.nodes.len() as f64
-        * context.config.brain.hidden_node_cost)
-        + (intel.genotype.brain.connections.len() as f64 * context.config.brain.connection_cost);
+    let brain_maintenance = intel.genotype.brain.maintenance_cost(&context.config.brain);
     let adjusted_maintenance = brain_maintenance * protection_multiplier.max(0.1);
     metabolic_consumption += adjusted_maintenance;
 
@@ -145,9 +138,7 @@ fn apply_metabolic_maintenance(
 
     let age = tick - metabolism.birth_tick;
 
-    let brain_maintenance = (intel.genotype.brain.nodes.len() as f64
-        * config.brain.hidden_node_cost)
-        + (intel.genotype.brain.connections.len() as f64 * config.brain.connection_cost);
+    let brain_maintenance = intel.genotype.brain.maintenance_cost(&config.brain);
 
     // Apply protection multiplier for neonates (gradual ramp-up)
     let protection_multiplier = if age < NEONATE_PROTECTION_TICKS {
@@ -168,25 +159,20 @@ pub fn try_infect_components<R: Rng>(
     if health.pathogen.is_some() {
         return false;
     }
-    let chance = (pathogen.virulence - health.immunity).max(0.01);
+    let resistance = health.immunity + status::magnitude(&health.effects, StatusKind::Blessing);
+    let chance = (pathogen.virulence - resistance).max(0.01);
     if rng.gen::<f32>() < chance {
-        health.pathogen = Some(pathogen.clone());
-        health.infection_timer = pathogen.duration;
+        infect_components(health, pathogen);
         return true;
     }
     false
 }
 
-pub fn process_infection_components(health: &mut Health, metabolism: &mut Metabolism) {
-    if let Some(p) = &health.pathogen {
-        metabolism.energy -= f64::from(p.lethality);
-        if health.infection_timer > 0 {
-            health.infection_timer -= 1;
-        } else {
-            health.pathogen = None;
-            health.immunity = (health.immunity + 0.1).min(1.0);
-        }
-    }
+/// Infects `health` with `pathogen`, replacing any current infection.
+pub fn infect_components(health: &mut Health, pathogen: &Pathogen) {
+    health.pathogen = Some(pathogen.clone());
+    status::remove(&mut health.effects, StatusKind::Infection);
+    status::apply(&mut health.effects, status::infection(pathogen));
 }
 
 pub fn handle_pathogen_emergence<R: Rng>(active_pathogens: &mut Vec<Pathogen>, rng: &mut R) {
@@ -215,7 +201,7 @@ pub fn handle_infection_ecs<R: Rng, S: std::hash::BuildHasher>(
         world.get::<&Physics>(handle),
         world.get::<&primordium_data::Identity>(handle),
     ) {
-        process_infection_components(&mut health, &mut metabolism);
+        status::process_status_effects(&mut health, &mut metabolism);
         for p in active_pathogens {
             if rng.gen_bool(0.005) {
                 try_infect_components(&mut health, p, rng);
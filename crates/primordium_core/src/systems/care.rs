@@ -0,0 +1,69 @@
+//! Parental care.
+//!
+//! The `parental_care` gene sets how long a parent keeps feeding its young:
+//! offspring younger than [`care_ticks`] within `care.radius` receive up to
+//! `care.provision_rate` energy per tick from the parent's surplus. The energy
+//! comes straight out of the parent, so long care trades the parent's own
+//! reproduction for juveniles that are more likely to reach maturity; the
+//! lineage registry records how often they do.
+
+use crate::config::AppConfig;
+use crate::snapshot::InternalEntitySnapshot;
+use crate::spatial_hash::SpatialHash;
+use primordium_data::Genotype;
+
+/// Ticks after birth during which a parent with `genotype` feeds its young.
+pub fn care_ticks(genotype: &Genotype, config: &AppConfig) -> u64 {
+    (config.metabolism.maturity_age as f32 * genotype.parental_care.clamp(0.0, 1.0)) as u64
+}
+
+/// Snapshot indices and amounts of energy the parent at snapshot `parent`
+/// hands to its juveniles this tick. Juveniles are served lowest index first
+/// until the parent's surplus above `care.reserve_fraction` of its maximum
+/// energy runs out, and never beyond their own maximum energy.
+pub fn provisions(
+    parent: usize,
+    care_ticks: u64,
+    tick: u64,
+    snapshots: &[InternalEntitySnapshot],
+    spatial_hash: &SpatialHash,
+    config: &AppConfig,
+) -> Vec<(usize, f64)> {
+    let care = &config.care;
+    let p = &snapshots[parent];
+    let (x, y) = (p.x, p.y);
+    let mut surplus = p.energy - p.max_energy * care.reserve_fraction;
+    if !care.enabled || care_ticks == 0 || surplus <= 0.0 {
+        return Vec::new();
+    }
+
+    let radius_sq = care.radius * care.radius;
+    let mut juveniles = Vec::new();
+    spatial_hash.query_callback(x, y, care.radius, |idx| {
+        let s = &snapshots[idx];
+        if s.parent_id == Some(p.id)
+            && tick.saturating_sub(s.birth_tick) < care_ticks
+            && (s.x - x).powi(2) + (s.y - y).powi(2) <= radius_sq
+        {
+            juveniles.push(idx);
+        }
+    });
+    juveniles.sort_unstable();
+
+    let mut fed = Vec::with_capacity(juveniles.len());
+    for idx in juveniles {
+        let s = &snapshots[idx];
+        let amount = care
+            .provision_rate
+            .min(s.max_energy - s.energy)
+            .min(surplus);
+        if amount > 0.0 {
+            surplus -= amount;
+            fed.push((idx, amount));
+        }
+        if surplus <= 0.0 {
+            break;
+        }
+    }
+    fed
+}
@@ -1,10 +1,134 @@
+use crate::config::RoadConfig;
 use crate::lineage_registry::LineageRegistry;
+use crate::road::RoadGrid;
 use crate::spatial_hash::SpatialHash;
 use crate::terrain::{OutpostSpecialization, TerrainGrid, TerrainType};
 use primordium_data::{Entity, Metabolism};
 use rayon::prelude::*;
 use uuid::Uuid;
 
+/// Enemy power must exceed the owner's by this factor to take an outpost.
+pub const TAKEOVER_RATIO: f64 = 2.5;
+/// Fewest enemy entities that must be present to take an outpost.
+pub const TAKEOVER_MIN_CHALLENGERS: usize = 3;
+
+/// The strongest rival lineage around an owned outpost.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutpostChallenge {
+    /// Terrain cell index of the outpost.
+    pub idx: usize,
+    pub owner: Uuid,
+    pub challenger: Uuid,
+    /// Challenger power over owner power.
+    pub ratio: f64,
+    /// Challenger entities near the outpost.
+    pub challenger_count: usize,
+}
+
+impl OutpostChallenge {
+    /// Whether ownership transfers on the next contest resolution.
+    pub fn is_imminent(&self) -> bool {
+        self.ratio > TAKEOVER_RATIO && self.challenger_count >= TAKEOVER_MIN_CHALLENGERS
+    }
+}
+
+/// Compares tribal power around the outpost at `idx` (radius 5) and returns
+/// the strongest enemy lineage, if any.
+fn outpost_challenge(
+    terrain: &TerrainGrid,
+    idx: usize,
+    width: u16,
+    spatial_hash: &SpatialHash,
+    snapshots: &[crate::snapshot::InternalEntitySnapshot],
+    lineage_registry: &LineageRegistry,
+) -> Option<OutpostChallenge> {
+    let current_owner = terrain.cell(idx).owner_id?;
+    let (ox, oy) = ((idx % width as usize) as f64, (idx / width as usize) as f64);
+
+    let mut power_map: std::collections::HashMap<Uuid, (f64, usize)> =
+        std::collections::HashMap::new();
+
+    spatial_hash.query_callback(ox, oy, 5.0, |e_idx| {
+        let Some(snap) = snapshots.get(e_idx) else {
+            return;
+        };
+        // Only count entities with sufficient energy (valid combatants)
+        if snap.energy > 20.0 {
+            let entry = power_map.entry(snap.lineage_id).or_insert((0.0, 0));
+            entry.0 += snap.energy;
+            entry.1 += 1;
+        }
+    });
+
+    // Get current owner's power
+    let (owner_energy, _owner_count) = power_map.get(&current_owner).copied().unwrap_or((0.0, 0));
+
+    // Check if any enemy lineage significantly outpowers the owner
+    let mut strongest_enemy: Option<(Uuid, usize)> = None;
+    let mut strongest_enemy_power = 0.0;
+
+    for (lineage_id, (energy, count)) in &power_map {
+        if *lineage_id == current_owner {
+            continue;
+        }
+
+        // Get enemy civilization level (higher level = better organization)
+        let enemy_level = lineage_registry
+            .lineages
+            .get(lineage_id)
+            .map(|r| r.civilization_level)
+            .unwrap_or(0);
+
+        // Power factor considers both energy and civilization level
+        // Level 2+ civilizations get 20% power bonus
+        let power_factor = if enemy_level >= 2 { 1.2 } else { 1.0 };
+        let adjusted_power = energy * power_factor;
+
+        if adjusted_power > strongest_enemy_power {
+            strongest_enemy_power = adjusted_power;
+            strongest_enemy = Some((*lineage_id, *count));
+        }
+    }
+
+    let (challenger, challenger_count) = strongest_enemy?;
+    let owner_power = owner_energy.max(50.0); // Minimum defense threshold
+    Some(OutpostChallenge {
+        idx,
+        owner: current_owner,
+        challenger,
+        ratio: strongest_enemy_power / owner_power,
+        challenger_count,
+    })
+}
+
+/// Owned outposts where a rival lineage currently outpowers the owner,
+/// most threatened first.
+pub fn contested_outposts(
+    terrain: &TerrainGrid,
+    width: u16,
+    spatial_hash: &SpatialHash,
+    snapshots: &[crate::snapshot::InternalEntitySnapshot],
+    lineage_registry: &LineageRegistry,
+) -> Vec<OutpostChallenge> {
+    let mut contested: Vec<OutpostChallenge> = terrain
+        .outpost_indices
+        .iter()
+        .filter_map(|&idx| {
+            outpost_challenge(
+                terrain,
+                idx,
+                width,
+                spatial_hash,
+                snapshots,
+                lineage_registry,
+            )
+        })
+        .filter(|c| c.ratio > 1.0)
+        .collect();
+    contested.sort_by(|a, b| b.ratio.total_cmp(&a.ratio).then(a.idx.cmp(&b.idx)));
+    contested
+}
+
 /// Phase 66: Contested Ownership Logic
 /// Detects when enemy Alphas challenge outpost ownership and transfers
 /// control if enemy tribal power significantly exceeds defender power.
@@ -22,79 +146,31 @@ pub fn resolve_contested_ownership(
     let mut transfers: Vec<(usize, Option<Uuid>)> = Vec::new();
 
     for &idx in &outpost_indices {
-        let owner_id = terrain.cells[idx].owner_id;
-        let Some(current_owner) = owner_id else {
+        let Some(challenge) = outpost_challenge(
+            terrain,
+            idx,
+            width,
+            spatial_hash,
+            snapshots,
+            lineage_registry,
+        ) else {
             continue;
         };
 
-        let (ox, oy) = ((idx % width as usize) as f64, (idx / width as usize) as f64);
-
-        // Calculate tribal power around the outpost (radius = 5)
-        let mut power_map: std::collections::HashMap<Uuid, (f64, usize)> =
-            std::collections::HashMap::new();
-
-        spatial_hash.query_callback(ox, oy, 5.0, |e_idx| {
-            let snap = &snapshots[e_idx];
-            // Only count entities with sufficient energy (valid combatants)
-            if snap.energy > 20.0 {
-                let entry = power_map.entry(snap.lineage_id).or_insert((0.0, 0));
-                entry.0 += snap.energy;
-                entry.1 += 1;
-            }
-        });
-
-        // Get current owner's power
-        let (owner_energy, _owner_count) =
-            power_map.get(&current_owner).copied().unwrap_or((0.0, 0));
-
-        // Check if any enemy lineage significantly outpowers the owner
-        let mut strongest_enemy: Option<(Uuid, f64, usize)> = None;
-        let mut strongest_enemy_power = 0.0;
-
-        for (lineage_id, (energy, count)) in &power_map {
-            if *lineage_id == current_owner {
-                continue;
-            }
-
-            // Get enemy civilization level (higher level = better organization)
-            let enemy_level = lineage_registry
-                .lineages
-                .get(lineage_id)
-                .map(|r| r.civilization_level)
-                .unwrap_or(0);
-
-            // Power factor considers both energy and civilization level
-            // Level 2+ civilizations get 20% power bonus
-            let power_factor = if enemy_level >= 2 { 1.2 } else { 1.0 };
-            let adjusted_power = energy * power_factor;
-
-            if adjusted_power > strongest_enemy_power {
-                strongest_enemy_power = adjusted_power;
-                strongest_enemy = Some((*lineage_id, *energy, *count));
-            }
-        }
-
-        // Transfer ownership if enemy power > 2.5x owner power
-        // AND enemy has at least 3 entities present
-        if let Some((enemy_id, _enemy_energy, enemy_count)) = strongest_enemy {
-            let owner_power = owner_energy.max(50.0); // Minimum defense threshold
-            let power_ratio = strongest_enemy_power / owner_power;
+        if challenge.is_imminent() {
+            // Ownership transfer
+            transfers.push((idx, Some(challenge.challenger)));
 
-            if power_ratio > 2.5 && enemy_count >= 3 {
-                // Ownership transfer
-                transfers.push((idx, Some(enemy_id)));
-
-                // Clear energy store during transition (represents pillaging/disruption)
-                terrain.cells[idx].energy_store *= 0.5;
-            }
+            // Clear energy store during transition (represents pillaging/disruption)
+            terrain.cell_mut(idx).energy_store *= 0.5;
         }
     }
 
     // Apply transfers
     for (idx, new_owner) in transfers {
-        terrain.cells[idx].owner_id = new_owner;
+        terrain.cell_mut(idx).owner_id = new_owner;
         // Reset specialization to Standard after takeover
-        terrain.cells[idx].outpost_spec = OutpostSpecialization::Standard;
+        terrain.cell_mut(idx).outpost_spec = OutpostSpecialization::Standard;
     }
 }
 
@@ -111,7 +187,7 @@ pub fn resolve_outpost_upgrades(
     let outpost_indices: Vec<usize> = terrain.outpost_indices.iter().copied().collect();
 
     for &idx in &outpost_indices {
-        let owner_id = terrain.cells[idx].owner_id;
+        let owner_id = terrain.cell(idx).owner_id;
         let Some(current_owner) = owner_id else {
             continue;
         };
@@ -128,8 +204,8 @@ pub fn resolve_outpost_upgrades(
             continue;
         }
 
-        let current_spec = terrain.cells[idx].outpost_spec;
-        let stored = terrain.cells[idx].energy_store;
+        let current_spec = terrain.cell(idx).outpost_spec;
+        let stored = terrain.cell(idx).energy_store;
 
         let (ox, oy) = ((idx % width as usize) as f64, (idx / width as usize) as f64);
 
@@ -163,12 +239,12 @@ pub fn resolve_outpost_upgrades(
                     // build Silo for storage. Otherwise build Nursery for healing.
                     if avg_tribe_energy > 60.0 {
                         // Upgrade to Silo
-                        terrain.cells[idx].outpost_spec = OutpostSpecialization::Silo;
-                        terrain.cells[idx].energy_store -= upgrade_cost;
+                        terrain.cell_mut(idx).outpost_spec = OutpostSpecialization::Silo;
+                        terrain.cell_mut(idx).energy_store -= upgrade_cost;
                     } else if nearby_count >= 3 {
                         // Upgrade to Nursery (need enough members to benefit)
-                        terrain.cells[idx].outpost_spec = OutpostSpecialization::Nursery;
-                        terrain.cells[idx].energy_store -= upgrade_cost;
+                        terrain.cell_mut(idx).outpost_spec = OutpostSpecialization::Nursery;
+                        terrain.cell_mut(idx).energy_store -= upgrade_cost;
                     }
                 }
             }
@@ -180,20 +256,18 @@ pub fn resolve_outpost_upgrades(
     }
 }
 
-/// Phase 62: Outpost Power Grid (Civ Level 2)
-/// Connected outposts (via canals/rivers) automatically balance and share energy stores.
-pub fn resolve_power_grid(
-    terrain: &mut TerrainGrid,
+/// Groups outposts that share a power grid: outposts linked to each other
+/// through adjacent outposts, rivers and worn roads. Only outposts owned by
+/// Level 2+ lineages extend the grid.
+fn power_grid_groups(
+    terrain: &TerrainGrid,
     width: u16,
     height: u16,
     lineage_registry: &LineageRegistry,
-) {
-    let outpost_indices: Vec<usize> = terrain.outpost_indices.iter().copied().collect();
-    if outpost_indices.len() < 2 {
-        return;
-    }
-
-    let mut parent: Vec<usize> = (0..terrain.cells.len()).collect();
+    roads: &RoadGrid,
+    road_config: &RoadConfig,
+) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..terrain.len()).collect();
     fn find(i: usize, p: &mut [usize]) -> usize {
         if p[i] == i {
             i
@@ -213,7 +287,7 @@ pub fn resolve_power_grid(
     for y in 0..height {
         for x in 0..width {
             let idx = (y as usize * width as usize) + x as usize;
-            let cell = &terrain.cells[idx];
+            let cell = terrain.cell(idx);
 
             // Check if cell can participate in power grid
             let can_connect =
@@ -234,7 +308,7 @@ pub fn resolve_power_grid(
                         true // Rivers always connect
                     }
                 } else {
-                    false
+                    roads.is_link(idx, road_config)
                 };
 
             if !can_connect {
@@ -246,11 +320,12 @@ pub fn resolve_power_grid(
                 let ny = y as i32 + dy;
                 if nx >= 0 && nx < width as i32 && ny >= 0 && ny < height as i32 {
                     let nidx = (ny as usize * width as usize) + nx as usize;
-                    let ncell = &terrain.cells[nidx];
+                    let ncell = terrain.cell(nidx);
                     if matches!(
                         ncell.terrain_type,
                         TerrainType::Outpost | TerrainType::River
-                    ) {
+                    ) || roads.is_link(nidx, road_config)
+                    {
                         union(idx, nidx, &mut parent);
                     }
                 }
@@ -259,51 +334,206 @@ pub fn resolve_power_grid(
     }
 
     let mut groups: std::collections::HashMap<usize, Vec<usize>> = std::collections::HashMap::new();
-    for &idx in &outpost_indices {
+    for &idx in &terrain.outpost_indices {
         let root = find(idx, &mut parent);
         groups.entry(root).or_default().push(idx);
     }
 
-    let group_data: Vec<Vec<usize>> = groups.into_values().filter(|g| g.len() > 1).collect();
+    groups.into_values().collect()
+}
+
+/// Energy each outpost of a grid group gains (or loses, if negative) on the
+/// next balance.
+fn grid_flows(terrain: &TerrainGrid, group: &[usize]) -> Vec<(usize, f32)> {
+    let total_energy: f32 = group.iter().map(|&i| terrain.cell(i).energy_store).sum();
+    let avg_energy = total_energy / group.len() as f32;
+
+    group
+        .iter()
+        .map(|&i| {
+            let current = terrain.cell(i).energy_store;
+            let flow = (avg_energy - current) * 0.1;
+
+            if flow < 0.0 {
+                let clamped = flow.max(-current);
+                (i, clamped)
+            } else {
+                (i, flow)
+            }
+        })
+        .collect()
+}
+
+/// Phase 62: Outpost Power Grid (Civ Level 2)
+/// Connected outposts (via canals/rivers/roads) automatically balance and share energy stores.
+pub fn resolve_power_grid(
+    terrain: &mut TerrainGrid,
+    width: u16,
+    height: u16,
+    lineage_registry: &LineageRegistry,
+    roads: &RoadGrid,
+    road_config: &RoadConfig,
+) {
+    if terrain.outpost_indices.len() < 2 {
+        return;
+    }
+
+    let group_data: Vec<Vec<usize>> =
+        power_grid_groups(terrain, width, height, lineage_registry, roads, road_config)
+            .into_iter()
+            .filter(|g| g.len() > 1)
+            .collect();
 
     let terrain_ref = &*terrain;
     let changes: Vec<(usize, f32)> = group_data
         .par_iter()
-        .flat_map(|group| {
-            let total_energy: f32 = group
-                .iter()
-                .map(|&i| terrain_ref.cells[i].energy_store)
-                .sum();
-            let avg_energy = total_energy / group.len() as f32;
+        .flat_map(|group| grid_flows(terrain_ref, group))
+        .collect();
 
-            group
-                .iter()
-                .map(move |&i| {
-                    let current = terrain_ref.cells[i].energy_store;
-                    let flow = (avg_energy - current) * 0.1;
+    for (i, flow) in changes {
+        let new_energy = terrain.cell(i).energy_store + flow;
+        terrain.cell_mut(i).energy_store = new_energy.max(0.0);
+    }
+}
 
-                    if flow < 0.0 {
-                        let clamped = flow.max(-current);
-                        (i, clamped)
-                    } else {
-                        (i, flow)
-                    }
-                })
-                .collect::<Vec<_>>()
+/// An outpost in an [`OutpostNetwork`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutpostNode {
+    /// Terrain cell index of the outpost.
+    pub idx: usize,
+    pub x: u16,
+    pub y: u16,
+    pub owner: Option<Uuid>,
+    pub energy_store: f32,
+    pub spec: OutpostSpecialization,
+}
+
+/// A power-grid link between two nodes of an [`OutpostNetwork`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridLink {
+    /// Index into [`OutpostNetwork::nodes`].
+    pub from: usize,
+    /// Index into [`OutpostNetwork::nodes`].
+    pub to: usize,
+    /// Energy the next grid balance moves from `from` to `to`; negative
+    /// values flow the other way.
+    pub flow: f32,
+}
+
+/// Outposts as a graph whose links are the power-grid supply lines.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutpostNetwork {
+    pub nodes: Vec<OutpostNode>,
+    pub links: Vec<GridLink>,
+}
+
+impl OutpostNetwork {
+    pub fn node_at(&self, idx: usize) -> Option<&OutpostNode> {
+        self.nodes
+            .binary_search_by_key(&idx, |n| n.idx)
+            .ok()
+            .map(|i| &self.nodes[i])
+    }
+
+    /// Links between outposts owned by `lineage_id`.
+    pub fn links_of(&self, lineage_id: Uuid) -> impl Iterator<Item = &GridLink> {
+        self.links
+            .iter()
+            .filter(move |l| self.nodes[l.from].owner == Some(lineage_id))
+    }
+}
+
+/// Builds the outpost graph of the world: every outpost as a node, and within
+/// each power grid a spanning tree of the shortest links between its
+/// outposts. Link flows mirror what [`resolve_power_grid`] will move next.
+pub fn outpost_network(
+    terrain: &TerrainGrid,
+    width: u16,
+    height: u16,
+    lineage_registry: &LineageRegistry,
+    roads: &RoadGrid,
+    road_config: &RoadConfig,
+) -> OutpostNetwork {
+    let mut outposts: Vec<usize> = terrain.outpost_indices.iter().copied().collect();
+    outposts.sort_unstable();
+    let position = |idx: usize| ((idx % width as usize) as u16, (idx / width as usize) as u16);
+
+    let nodes: Vec<OutpostNode> = outposts
+        .iter()
+        .map(|&idx| {
+            let cell = terrain.cell(idx);
+            let (x, y) = position(idx);
+            OutpostNode {
+                idx,
+                x,
+                y,
+                owner: cell.owner_id,
+                energy_store: cell.energy_store,
+                spec: cell.outpost_spec,
+            }
         })
         .collect();
+    let node_of = |idx: usize| outposts.binary_search(&idx).unwrap_or_default();
+
+    let mut groups: Vec<Vec<usize>> =
+        power_grid_groups(terrain, width, height, lineage_registry, roads, road_config)
+            .into_iter()
+            .filter(|g| g.len() > 1)
+            .collect();
+    for group in &mut groups {
+        group.sort_unstable();
+    }
+    groups.sort_unstable();
+
+    let mut links = Vec::new();
+    for group in &groups {
+        let gain: std::collections::HashMap<usize, f32> =
+            grid_flows(terrain, group).into_iter().collect();
+
+        // Prim's algorithm over straight-line distance.
+        let dist = |a: usize, b: usize| {
+            let ((ax, ay), (bx, by)) = (position(a), position(b));
+            let (dx, dy) = (ax as i32 - bx as i32, ay as i32 - by as i32);
+            dx * dx + dy * dy
+        };
+        let mut order = vec![group[0]];
+        let mut parent: Vec<(usize, usize)> = Vec::new();
+        let mut remaining: Vec<usize> = group[1..].to_vec();
+        while !remaining.is_empty() {
+            let (pos, from, _) = remaining
+                .iter()
+                .enumerate()
+                .flat_map(|(pos, &to)| order.iter().map(move |&from| (pos, from, dist(from, to))))
+                .min_by_key(|&(_, _, d)| d)
+                .unwrap_or((0, group[0], 0));
+            let to = remaining.swap_remove(pos);
+            order.push(to);
+            parent.push((from, to));
+        }
 
-    for (i, flow) in changes {
-        let new_energy = terrain.cells[i].energy_store + flow;
-        terrain.cells[i].energy_store = new_energy.max(0.0);
+        // Whatever a subtree gains has to cross the link that joins it to
+        // the rest of the grid.
+        let mut subtree_gain = gain.clone();
+        for &(from, to) in parent.iter().rev() {
+            let flow = subtree_gain[&to];
+            *subtree_gain.entry(from).or_default() += flow;
+            links.push(GridLink {
+                from: node_of(from),
+                to: node_of(to),
+                flow,
+            });
+        }
     }
+    links.sort_by_key(|l| (l.from, l.to));
+
+    OutpostNetwork { nodes, links }
 }
 
 pub fn count_outposts_by_lineage(terrain: &TerrainGrid) -> std::collections::HashMap<Uuid, usize> {
     let mut counts = std::collections::HashMap::new();
     let outpost_indices = &terrain.outpost_indices;
     for &idx in outpost_indices {
-        let cell = &terrain.cells[idx];
+        let cell = terrain.cell(idx);
         if let Some(id) = cell.owner_id {
             *counts.entry(id).or_insert(0) += 1;
         }
@@ -335,16 +565,16 @@ pub fn handle_outposts_ecs(
 ) {
     let outpost_indices: Vec<usize> = terrain.outpost_indices.iter().copied().collect();
 
-    let actions: Vec<OutpostAction> = outpost_indices
+    let mut actions: Vec<OutpostAction> = outpost_indices
         .par_iter()
         .fold(Vec::new, |mut acc: Vec<OutpostAction>, &idx| {
             let (ox, oy) = (
                 (idx % ctx.width as usize) as f64,
                 (idx / ctx.width as usize) as f64,
             );
-            let owner_id = terrain.cells[idx].owner_id;
-            let stored = terrain.cells[idx].energy_store;
-            let spec = terrain.cells[idx].outpost_spec;
+            let owner_id = terrain.cell(idx).owner_id;
+            let stored = terrain.cell(idx).energy_store;
+            let spec = terrain.cell(idx).outpost_spec;
 
             ctx.spatial_hash.query_callback(ox, oy, 3.0, |e_idx| {
                 let snap = &ctx.snapshots[e_idx];
@@ -393,6 +623,13 @@ pub fn handle_outposts_ecs(
             a.extend(b);
             a
         });
+    // Higher dominance standing draws on a shared store first.
+    actions.sort_by(|a, b| {
+        let standing = |action: &OutpostAction| match *action {
+            OutpostAction::TransferEnergy { entity_idx, .. } => ctx.snapshots[entity_idx].dominance,
+        };
+        standing(b).total_cmp(&standing(a))
+    });
 
     for action in actions {
         match action {
@@ -404,30 +641,30 @@ pub fn handle_outposts_ecs(
                 let handle = ctx.entity_handles[entity_idx];
                 if let Ok(mut met) = world.get::<&mut Metabolism>(handle) {
                     let actual_transfer = if amount > 0.0 {
-                        amount.min(terrain.cells[outpost_idx].energy_store as f64)
+                        amount.min(terrain.cell(outpost_idx).energy_store as f64)
                     } else {
                         amount
                     };
                     met.energy = (met.energy + actual_transfer).clamp(0.0, met.max_energy);
-                    terrain.cells[outpost_idx].energy_store -= actual_transfer as f32;
+                    terrain.cell_mut(outpost_idx).energy_store -= actual_transfer as f32;
                 }
             }
         }
     }
 
     for &idx in &outpost_indices {
-        let max_cap = match terrain.cells[idx].outpost_spec {
+        let max_cap = match terrain.cell(idx).outpost_spec {
             OutpostSpecialization::Silo => ctx.silo_cap,
             _ => ctx.outpost_cap,
         };
         // Apply passive decay (entropy) to all outposts
-        let decay = if terrain.cells[idx].owner_id.is_some() {
+        let decay = if terrain.cell(idx).owner_id.is_some() {
             0.05 // Maintained outposts decay slowly
         } else {
             0.5 // Abandoned outposts decay quickly
         };
-        terrain.cells[idx].energy_store =
-            (terrain.cells[idx].energy_store - decay).clamp(0.0, max_cap);
+        let cell = terrain.cell_mut(idx);
+        cell.energy_store = (cell.energy_store - decay).clamp(0.0, max_cap);
     }
 }
 
@@ -443,10 +680,10 @@ pub fn handle_outposts(
 
     for &idx in &outpost_indices {
         let (ox, oy) = ((idx % width as usize) as f64, (idx / width as usize) as f64);
-        let owner_id = terrain.cells[idx].owner_id;
+        let owner_id = terrain.cell(idx).owner_id;
 
-        let mut stored = terrain.cells[idx].energy_store;
-        let spec = terrain.cells[idx].outpost_spec;
+        let mut stored = terrain.cell(idx).energy_store;
+        let spec = terrain.cell(idx).outpost_spec;
 
         spatial_hash.query_callback(ox, oy, 3.0, |e_idx| {
             let e = &mut entities[e_idx];
@@ -486,6 +723,6 @@ pub fn handle_outposts(
             OutpostSpecialization::Silo => silo_cap,
             _ => outpost_cap,
         };
-        terrain.cells[idx].energy_store = stored.min(max_cap);
+        terrain.cell_mut(idx).energy_store = stored.min(max_cap);
     }
 }
@@ -0,0 +1,170 @@
+//! Dominance contests.
+//!
+//! Rank (`Intel::rank`) is a score recomputed every tick. Standing is
+//! earned: each lineage keeps a ladder of its ranked members, alpha first.
+//! An entity whose `Aggro` output is above `dominance.challenge_threshold`
+//! challenges the nearest lineage mate above it within `dominance.radius`.
+//! The contest is ritualized. Both sides pay `dominance.contest_cost`,
+//! nobody is hurt, and the higher rank score wins. A winning challenger
+//! swaps places with the defender. Unranked members join the ladder through
+//! contests, or just below a ranked parent when they are born.
+//!
+//! Standing buys first claim on the lineage's outpost energy and priority as
+//! a mate. The length of a lineage's ladder is its hierarchy depth.
+
+use crate::config::AppConfig;
+use crate::snapshot::InternalEntitySnapshot;
+use crate::spatial_hash::SpatialHash;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Per-lineage dominance ladders, alpha first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DominanceHierarchy {
+    ladders: HashMap<Uuid, Vec<Uuid>>,
+}
+
+impl DominanceHierarchy {
+    /// Settles a contest between two members of `lineage`. A winner below
+    /// the loser swaps places with it, and an unranked winner takes the
+    /// loser's place. An unranked loser joins at the bottom.
+    pub fn contest(&mut self, lineage: Uuid, winner: Uuid, loser: Uuid) {
+        let ladder = self.ladders.entry(lineage).or_default();
+        let position = |id| ladder.iter().position(|&m| m == id);
+        match (position(winner), position(loser)) {
+            (Some(w), Some(l)) if w > l => ladder.swap(w, l),
+            (Some(_), Some(_)) => {}
+            (None, Some(l)) => ladder.insert(l, winner),
+            (Some(_), None) => ladder.push(loser),
+            (None, None) => ladder.extend([winner, loser]),
+        }
+    }
+
+    /// Slots `child` in just below `parent`, if the parent is ranked in
+    /// `lineage` and the child is not.
+    pub fn inherit(&mut self, lineage: Uuid, parent: Uuid, child: Uuid) {
+        if let Some(ladder) = self.ladders.get_mut(&lineage) {
+            if ladder.contains(&child) {
+                return;
+            }
+            if let Some(p) = ladder.iter().position(|&m| m == parent) {
+                ladder.insert(p + 1, child);
+            }
+        }
+    }
+
+    /// Drops members for which `keep(lineage, id)` is false, and empty
+    /// ladders with them.
+    pub fn retain(&mut self, mut keep: impl FnMut(Uuid, Uuid) -> bool) {
+        for (&lineage, ladder) in &mut self.ladders {
+            ladder.retain(|&id| keep(lineage, id));
+        }
+        self.ladders.retain(|_, ladder| !ladder.is_empty());
+    }
+
+    pub fn is_ranked(&self, lineage: Uuid, id: Uuid) -> bool {
+        self.ladders
+            .get(&lineage)
+            .is_some_and(|ladder| ladder.contains(&id))
+    }
+
+    /// Standing of every ranked entity: 1.0 for an alpha, falling to
+    /// `1 / depth` at the bottom of its ladder. Unranked entities are absent.
+    pub fn standings(&self) -> HashMap<Uuid, f32> {
+        self.ladders
+            .values()
+            .flat_map(|ladder| {
+                let depth = ladder.len() as f32;
+                ladder
+                    .iter()
+                    .enumerate()
+                    .map(move |(p, &id)| (id, 1.0 - p as f32 / depth))
+            })
+            .collect()
+    }
+
+    /// Number of ranked members in `lineage`.
+    pub fn depth(&self, lineage: Uuid) -> usize {
+        self.ladders.get(&lineage).map_or(0, Vec::len)
+    }
+
+    /// Ranked members of `lineage`, alpha first.
+    pub fn ladder(&self, lineage: Uuid) -> &[Uuid] {
+        self.ladders.get(&lineage).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Snapshot index of the lineage mate the entity at snapshot `challenger`
+/// challenges this tick, if it wants to and can afford to: the nearest one
+/// standing above it. Two unranked members may challenge each other.
+pub fn challenge_target(
+    challenger: usize,
+    aggression: f32,
+    snapshots: &[InternalEntitySnapshot],
+    spatial_hash: &SpatialHash,
+    config: &AppConfig,
+) -> Option<usize> {
+    let dominance = &config.dominance;
+    let c = &snapshots[challenger];
+    if !dominance.enabled
+        || aggression <= dominance.challenge_threshold
+        || c.energy <= dominance.contest_cost
+    {
+        return None;
+    }
+
+    let radius_sq = dominance.radius * dominance.radius;
+    let mut best: Option<(f64, usize)> = None;
+    spatial_hash.query_callback(c.x, c.y, dominance.radius, |idx| {
+        let s = &snapshots[idx];
+        if idx == challenger || s.lineage_id != c.lineage_id {
+            return;
+        }
+        if s.dominance <= c.dominance && !(s.dominance == 0.0 && c.dominance == 0.0) {
+            return;
+        }
+        let dist_sq = (s.x - c.x).powi(2) + (s.y - c.y).powi(2);
+        if dist_sq > radius_sq {
+            return;
+        }
+        if best.is_none_or(|b| (dist_sq, idx) < b) {
+            best = Some((dist_sq, idx));
+        }
+    });
+    best.map(|(_, idx)| idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contests_build_and_reorder_the_ladder() {
+        let lineage = Uuid::from_u128(1);
+        let [a, b, c, d] = [2, 3, 4, 5].map(Uuid::from_u128);
+        let mut hierarchy = DominanceHierarchy::default();
+
+        hierarchy.contest(lineage, a, b);
+        assert_eq!(hierarchy.ladder(lineage), [a, b]);
+        hierarchy.contest(lineage, c, a);
+        assert_eq!(hierarchy.ladder(lineage), [c, a, b]);
+        hierarchy.contest(lineage, b, c);
+        assert_eq!(hierarchy.ladder(lineage), [b, a, c]);
+        hierarchy.contest(lineage, b, d);
+        assert_eq!(hierarchy.ladder(lineage), [b, a, c, d]);
+
+        hierarchy.inherit(lineage, a, Uuid::from_u128(6));
+        assert_eq!(hierarchy.depth(lineage), 5);
+        assert_eq!(hierarchy.ladder(lineage)[2], Uuid::from_u128(6));
+
+        let standings = hierarchy.standings();
+        assert_eq!(standings[&b], 1.0);
+        assert!((standings[&d] - 0.2).abs() < 1e-6);
+
+        hierarchy.retain(|_, id| id != b);
+        assert_eq!(hierarchy.ladder(lineage)[0], a);
+        hierarchy.retain(|_, _| false);
+        assert_eq!(hierarchy.depth(lineage), 0);
+    }
+}
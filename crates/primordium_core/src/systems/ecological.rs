@@ -1,6 +1,8 @@
 //! Ecological system - handles food spawning and consumption.
 
 use crate::environment::Environment;
+use crate::flora;
+use crate::food_field::FoodField;
 use crate::pheromone::{PheromoneGrid, PheromoneType};
 use crate::spatial_hash::SpatialHash;
 use crate::terrain::TerrainGrid;
@@ -27,6 +29,8 @@ pub struct SpawnFoodContext<'a> {
     pub width: u16,
     pub height: u16,
     pub food_count_ptr: &'a std::sync::atomic::AtomicUsize,
+    /// When set, food is deposited into the density field instead of spawned as entities.
+    pub food_field: Option<&'a mut FoodField>,
 }
 
 pub fn spawn_food_ecs(ctx: &mut SpawnFoodContext, rng: &mut impl Rng) {
@@ -65,15 +69,18 @@ pub fn spawn_food_ecs(ctx: &mut SpawnFoodContext, rng: &mut impl Rng) {
                     | primordium_data::TerrainType::River => rng.gen_range(0.6..1.0),
                     _ => rng.gen_range(0.0..0.4),
                 };
-                let new_food = Food::new(x, y, nutrient_type);
-                ctx.world.spawn((
-                    new_food,
-                    primordium_data::Position {
-                        x: x as f64,
-                        y: y as f64,
-                    },
-                    primordium_data::MetabolicNiche(nutrient_type),
-                ));
+                if let Some(field) = ctx.food_field.as_deref_mut() {
+                    field.deposit(x, y, nutrient_type);
+                } else {
+                    ctx.world.spawn((
+                        Food::new(x, y, nutrient_type),
+                        primordium_data::Position {
+                            x: x as f64,
+                            y: y as f64,
+                        },
+                        primordium_data::MetabolicNiche(nutrient_type),
+                    ));
+                }
                 ctx.food_count_ptr
                     .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
@@ -83,6 +90,74 @@ pub fn spawn_food_ecs(ctx: &mut SpawnFoodContext, rng: &mut impl Rng) {
     }
 }
 
+/// Lets plants seed fertile cells around them; see [`crate::flora`].
+///
+/// Does nothing while food lives in the density field, whose units carry no
+/// genome.
+pub fn spread_flora(ctx: &mut SpawnFoodContext, rng: &mut impl Rng) {
+    let config = &ctx.config.flora;
+    if !config.enabled || ctx.food_field.is_some() {
+        return;
+    }
+    let cost = ctx.config.metabolism.food_energy_cost;
+    let radius = i32::from(config.spread_radius);
+    let mut plants: Vec<Food> = ctx
+        .world
+        .query::<&Food>()
+        .iter()
+        .map(|(_, food)| food.clone())
+        .collect();
+    // Saves reorder food entities; roll for plants in a canonical order so a
+    // loaded world seeds exactly like the one it was saved from.
+    plants.sort_by(|a, b| {
+        (a.y, a.x)
+            .cmp(&(b.y, b.x))
+            .then(a.nutrient_type.total_cmp(&b.nutrient_type))
+            .then(a.toxicity.total_cmp(&b.toxicity))
+            .then(a.regrowth.total_cmp(&b.regrowth))
+    });
+
+    for plant in plants {
+        let food_count = ctx
+            .food_count_ptr
+            .load(std::sync::atomic::Ordering::Relaxed);
+        if food_count >= ctx.config.world.max_food || ctx.env.available_energy < cost {
+            break;
+        }
+        // One roll per plant; most fail against the best case and need no
+        // target cell.
+        let genome = plant.genome();
+        let roll = rng.gen::<f32>();
+        if roll >= flora::seed_chance(&genome, 1.0, config) {
+            continue;
+        }
+        let x = i32::from(plant.x) + rng.gen_range(-radius..=radius);
+        let y = i32::from(plant.y) + rng.gen_range(-radius..=radius);
+        if x < 1 || y < 1 || x >= i32::from(ctx.width) - 1 || y >= i32::from(ctx.height) - 1 {
+            continue;
+        }
+        let (x, y) = (x as u16, y as u16);
+        let fertility = ctx.terrain.get_cell(x, y).fertility;
+        if ctx.terrain.food_spawn_modifier(f64::from(x), f64::from(y)) <= 0.0
+            || roll >= flora::seed_chance(&genome, fertility, config)
+        {
+            continue;
+        }
+        let seedling = flora::mutate(genome, config, rng);
+        ctx.world.spawn((
+            Food::from_genome(x, y, seedling),
+            primordium_data::Position {
+                x: f64::from(x),
+                y: f64::from(y),
+            },
+            primordium_data::MetabolicNiche(seedling.nutrient_type),
+        ));
+        ctx.food_count_ptr
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        ctx.env.available_energy -= cost;
+    }
+}
+
 /// Spawn new food items based on environment and terrain.
 pub fn spawn_food(
     food: &mut Vec<Food>,
@@ -123,11 +198,12 @@ pub fn spawn_food(
 /// Optimized feeding handler using spatial hashing.
 pub fn handle_feeding_optimized(idx: usize, entities: &mut [Entity], ctx: &mut FeedingContext) {
     let sensing_radius = entities[idx].physics.sensing_range;
+    let reach = 1.5 + crate::body::radius(entities[idx].intel.genotype.body_size);
     let mut nearby_food = Vec::new();
     ctx.food_hash.query_into(
         entities[idx].physics.x,
         entities[idx].physics.y,
-        1.5f64.max(sensing_radius / 4.0),
+        reach.max(sensing_radius / 4.0),
         &mut nearby_food,
     );
 
@@ -137,7 +213,7 @@ pub fn handle_feeding_optimized(idx: usize, entities: &mut [Entity], ctx: &mut F
             let f = &ctx.food[f_idx];
             let dx = f64::from(f.x) - entities[idx].physics.x;
             let dy = f64::from(f.y) - entities[idx].physics.y;
-            if (dx * dx + dy * dy).sqrt() < 1.5 {
+            if (dx * dx + dy * dy).sqrt() < reach {
                 eaten_idx = Some(f_idx);
                 break;
             }
@@ -157,7 +233,8 @@ pub fn handle_feeding_optimized(idx: usize, entities: &mut [Entity], ctx: &mut F
             .clamp(0.2, 1.2)
             * trophic_efficiency;
 
-        let energy_gain = ctx.config.metabolism.food_value * efficiency;
+        let toxin = flora::toxin_damage(f.toxicity, niche_match as f32, &ctx.config.flora);
+        let energy_gain = ctx.config.metabolism.food_value * efficiency - toxin;
         entities[idx].metabolism.energy = (entities[idx].metabolism.energy + energy_gain)
             .min(entities[idx].metabolism.max_energy);
 
@@ -0,0 +1,118 @@
+//! Egg laying and incubation.
+//!
+//! An oviparous parent pays for one offspring as usual, but the offspring is
+//! split into a clutch by [`lay_clutch`]: more eggs mean weaker hatchlings.
+//! Eggs sit in the world as `(Position, Egg)` ECS entities, gain
+//! [`incubation_rate`] each tick and hatch at `eggs.incubation_ticks`. Until
+//! then they are exposed to any stranger that [`egg_predator`] finds nearby,
+//! so cold climates and nest-less ground cost a lineage more of its clutch.
+
+use crate::config::EggConfig;
+use crate::environment::{ClimateState, Environment, Season};
+use crate::snapshot::InternalEntitySnapshot;
+use crate::spatial_hash::SpatialHash;
+use crate::terrain::TerrainGrid;
+use primordium_data::{Egg, Entity, TerrainType};
+use rand::Rng;
+use uuid::Uuid;
+
+/// Splits `offspring` into `clutch_size` eggs sharing its energy. The first
+/// egg keeps the offspring's id; the others get ids from `rng` and are
+/// scattered within half a cell, kept inside `width` × `height`.
+pub fn lay_clutch<R: Rng>(
+    offspring: Entity,
+    clutch_size: u8,
+    tick: u64,
+    (width, height): (u16, u16),
+    rng: &mut R,
+) -> Vec<Egg> {
+    let clutch_size = clutch_size.max(1);
+    let mut hatchling = offspring;
+    hatchling.metabolism.energy /= f64::from(clutch_size);
+    hatchling.metabolism.peak_energy = hatchling.metabolism.energy;
+
+    (0..clutch_size)
+        .map(|i| {
+            let mut sibling = hatchling.clone();
+            if i > 0 {
+                sibling.identity.id = Uuid::from_u128(rng.gen::<u128>());
+                let x = (sibling.position.x + rng.gen_range(-0.5..0.5))
+                    .clamp(0.0, f64::from(width) - 0.1);
+                let y = (sibling.position.y + rng.gen_range(-0.5..0.5))
+                    .clamp(0.0, f64::from(height) - 0.1);
+                sibling.position.x = x;
+                sibling.position.y = y;
+                sibling.physics.x = x;
+                sibling.physics.y = y;
+                sibling.physics.home_x = x;
+                sibling.physics.home_y = y;
+            }
+            Egg {
+                hatchling: Box::new(sibling),
+                laid_tick: tick,
+                incubation: 0.0,
+            }
+        })
+        .collect()
+}
+
+/// How the climate scales incubation: warmth speeds it up, heat waves,
+/// winters and ice ages slow it down.
+pub fn climate_factor(env: &Environment) -> f32 {
+    let climate = match env.climate() {
+        ClimateState::Temperate | ClimateState::Hot => 1.0,
+        ClimateState::Warm => 1.25,
+        ClimateState::Scorching => 0.5,
+    };
+    let season = if env.current_season == Season::Winter {
+        0.75
+    } else {
+        1.0
+    };
+    let ice_age = if env.is_ice_age() { 0.5 } else { 1.0 };
+    climate * season * ice_age
+}
+
+/// Incubation an egg at `(x, y)` gains this tick.
+pub fn incubation_rate(
+    terrain: &TerrainGrid,
+    env: &Environment,
+    x: f64,
+    y: f64,
+    config: &EggConfig,
+) -> f32 {
+    let nest = if terrain.get(x, y).terrain_type == TerrainType::Nest {
+        config.nest_bonus
+    } else if terrain.has_neighbor_type(x as u16, y as u16, TerrainType::Nest) {
+        1.0 + (config.nest_bonus - 1.0) / 2.0
+    } else {
+        1.0
+    };
+    nest * climate_factor(env)
+}
+
+/// Snapshot index of the entity that eats an egg of `lineage_id` at
+/// `(x, y)`: the nearest stranger carnivorous enough within
+/// `config.predation_radius`, lowest index first on ties.
+pub fn egg_predator(
+    x: f64,
+    y: f64,
+    lineage_id: Uuid,
+    snapshots: &[InternalEntitySnapshot],
+    spatial_hash: &SpatialHash,
+    config: &EggConfig,
+) -> Option<usize> {
+    let radius_sq = config.predation_radius * config.predation_radius;
+    let mut best: Option<(f64, usize)> = None;
+    spatial_hash.query_callback(x, y, config.predation_radius, |idx| {
+        let s = &snapshots[idx];
+        if s.lineage_id == lineage_id || s.trophic_potential < config.predator_trophic_min {
+            return;
+        }
+        let dist_sq = (s.x - x).powi(2) + (s.y - y).powi(2);
+        if dist_sq < radius_sq && best.is_none_or(|(d, i)| (dist_sq, idx) < (d, i)) {
+            best = Some((dist_sq, idx));
+        }
+    });
+    best.map(|(_, idx)| idx)
+}
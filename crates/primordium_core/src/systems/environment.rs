@@ -1,20 +1,26 @@
-use crate::config::AppConfig;
-use crate::environment::Environment;
+use crate::config::{AppConfig, SeasonModifiers, SeasonsConfig};
+use crate::environment::{Environment, Era, Season};
+use crate::schedule::EventKind;
 use crate::terrain::TerrainGrid;
-use primordium_data::PopulationStats;
+use primordium_data::{Disaster, PopulationStats};
 use rand::Rng;
 
-/// Handle global environmental disasters with population-scaled catastrophe conservation.
+/// Stream of the world seed that disasters draw from, apart from every
+/// other system so that their timeline does not shift when those change.
+pub const DISASTER_STREAM: u64 = 0xD15A_57E2;
+
+/// Roll for a global environmental disaster with population-scaled
+/// catastrophe conservation. Returns the disaster to strike, if any.
 pub fn handle_disasters(
     env: &Environment,
     entity_count: usize,
-    terrain: &mut TerrainGrid,
     rng: &mut impl Rng,
     config: &AppConfig,
-) {
+) -> Option<Disaster> {
     // Phase 67 Task C: Catastrophe Conservation - scale disaster chance with population density
     // Base chance increases non-linearly as population approaches carrying capacity
     let base_chance = config.world.disaster_chance as f64;
+    let era_chance = (base_chance * env.era_modifiers.disaster_frequency as f64).min(1.0);
     // Only apply scaling for reasonable base chances (< 0.9), preserve guaranteed triggers
     let scaled_disaster_chance = if base_chance >= 0.9 {
         base_chance // Keep guaranteed triggers (test compatibility)
@@ -22,14 +28,36 @@ pub fn handle_disasters(
         // Non-linear scaling: probability increases faster as population grows
         let excess = (entity_count - 200) as f64 / 500.0;
         let population_density_factor = 1.0 + excess.powf(1.5);
-        (base_chance * population_density_factor).min(0.9) // Cap at 90% to never guarantee
+        (era_chance * population_density_factor).min(0.9) // Cap at 90% to never guarantee
     } else {
-        base_chance
+        era_chance
     };
 
     // Trigger Dust Bowl disaster
     if env.is_heat_wave() && entity_count > 300 && rng.gen_bool(scaled_disaster_chance) {
-        terrain.trigger_dust_bowl(500);
+        return Some(Disaster::DustBowl { duration: 500 });
+    }
+    None
+}
+
+/// Disasters on `env.schedule` due by `tick`.
+pub fn scheduled_disasters(env: &mut Environment, tick: u64) -> Vec<Disaster> {
+    env.schedule
+        .take(tick, |kind| matches!(kind, EventKind::Disaster { .. }))
+        .into_iter()
+        .filter_map(|event| match event.kind {
+            EventKind::Disaster { disaster } => Some(disaster),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Make `disaster` strike.
+pub fn apply_disaster(disaster: Disaster, env: &mut Environment, terrain: &mut TerrainGrid) {
+    match disaster {
+        Disaster::DustBowl { duration } => terrain.trigger_dust_bowl(duration),
+        Disaster::SolarFlare { duration } => env.radiation_timer = duration,
+        Disaster::DeepFreeze { duration } => env.ice_age_timer = duration,
     }
 }
 
@@ -58,42 +86,110 @@ pub fn update_events(env: &mut Environment, config: &AppConfig) {
     }
 }
 
-/// Update simulation era and season cycle.
+/// Apply the day/night cycle of `config.circadian` to `env` before `tick`.
+/// Deterministic worlds take the time of day from the tick; otherwise it
+/// advances with [`Environment::tick`]. While the cycle is disabled it is
+/// always noon.
+pub fn update_day(env: &mut Environment, tick: u64, config: &AppConfig) {
+    let circadian = &config.circadian;
+    env.day_cycle_ticks = circadian.day_length;
+    env.night_metabolism = circadian.night_metabolism;
+    if !circadian.enabled {
+        env.world_time = circadian.day_length / 4;
+    } else if config.world.deterministic {
+        env.world_time = tick % circadian.day_length;
+    } else {
+        env.world_time %= circadian.day_length;
+    }
+}
+
+/// Move the season cycle to `tick`. Returns the season entered, if a new
+/// one began, after blending `env.season_modifiers` for the tick. While the
+/// cycle is disabled the season stands still and has no effect.
+pub fn update_season(env: &mut Environment, tick: u64, config: &SeasonsConfig) -> Option<Season> {
+    if !config.enabled {
+        env.schedule.cancel(EventKind::SeasonChange);
+        env.season_modifiers = SeasonModifiers::default();
+        return None;
+    }
+    env.schedule
+        .every(EventKind::SeasonChange, config.duration, tick);
+    let due = env
+        .schedule
+        .take(tick, |kind| *kind == EventKind::SeasonChange);
+    let previous = env.current_season;
+    env.season_duration = config.duration;
+    env.transition_duration = config.transition;
+    env.season_tick = tick % config.duration;
+    env.current_season = Season::nth(tick / config.duration);
+    env.next_season = env.current_season.next();
+
+    let transition_start = config.duration - config.transition;
+    let progress = if env.season_tick >= transition_start && config.transition > 0 {
+        (env.season_tick - transition_start) as f64 / config.transition as f64
+    } else {
+        0.0
+    };
+    let smooth = progress * progress * (3.0 - 2.0 * progress);
+    env.season_modifiers = config
+        .modifiers(env.current_season)
+        .lerp(&config.modifiers(env.next_season), smooth);
+    if env.current_season == previous {
+        return None;
+    }
+    for event in due {
+        env.schedule.record(tick, event);
+    }
+    Some(env.current_season)
+}
+
+/// Keep the recurring events of `config` on `env.schedule`, in step with
+/// their intervals as of `tick`.
+pub fn update_schedule(env: &mut Environment, tick: u64, config: &AppConfig) {
+    env.schedule
+        .every(EventKind::Fossil, config.world.fossil_interval, tick);
+    env.schedule
+        .every(EventKind::PowerGrid, config.world.power_grid_interval, tick);
+}
+
+/// Update the simulation era. Returns the era entered, if the era changed,
+/// after switching `env.era_modifiers` to its modifier set.
 pub fn update_era(
     env: &mut Environment,
     tick: u64,
     pop_stats: &PopulationStats,
     config: &AppConfig,
-) {
-    // Season cycling with smooth transitions
-    env.season_tick += 1;
-    if env.season_tick >= env.season_duration {
-        env.season_tick = 0;
-        env.current_season = env.next_season;
-        env.next_season = env.current_season.next();
-    }
-
+) -> Option<Era> {
     // Era Transition Logic
-    use crate::environment::Era;
-    if env.current_era == Era::Primordial {
-        // Dawn of Life: Needs either stability or a critical mass of biomass
-        if (tick > 5000 && pop_stats.avg_lifespan > 200.0) || pop_stats.biomass_h > 2000.0 {
-            env.current_era = Era::DawnOfLife;
+    let previous = env.current_era;
+    let predation = pop_stats.biomass_c / pop_stats.biomass_h.max(1.0);
+    match env.current_era {
+        // Expansion: Needs either stability or a critical mass of biomass
+        Era::Primordial => {
+            if (tick > 5000 && pop_stats.avg_lifespan > 200.0) || pop_stats.biomass_h > 2000.0 {
+                env.current_era = Era::Expansion;
+            }
         }
-    } else if env.current_era == Era::DawnOfLife {
-        // Flourishing: Needs biodiversity hotspots and healthy population
-        if pop_stats.biodiversity_hotspots >= 2 && pop_stats.population > 150 {
-            env.current_era = Era::Flourishing;
+        // Strife: Triggered by resource scarcity or high carbon (climate stress)
+        Era::Expansion => {
+            if env.carbon_level > 800.0 || predation > 0.3 {
+                env.current_era = Era::Strife;
+            }
         }
-    } else if env.current_era == Era::Flourishing {
-        // Dominance War: Triggered by resource scarcity or high carbon (climate stress)
-        if env.carbon_level > 800.0 || (pop_stats.biomass_c / pop_stats.biomass_h.max(1.0)) > 0.3 {
-            env.current_era = Era::DominanceWar;
+        // Back to Expansion once both pressures have clearly eased
+        Era::Strife => {
+            if env.carbon_level < 600.0 && predation < 0.2 {
+                env.current_era = Era::Expansion;
+            }
         }
+        Era::Enlightenment => {}
     }
 
-    // Apex Era: Peak fitness reached
+    // Enlightenment: Peak fitness reached
     if pop_stats.top_fitness > config.world.apex_fitness_req {
-        env.current_era = Era::ApexEra;
+        env.current_era = Era::Enlightenment;
     }
+
+    env.era_modifiers = config.eras.modifiers(env.current_era);
+    (env.current_era != previous).then_some(env.current_era)
 }
@@ -0,0 +1,74 @@
+//! Grooming.
+//!
+//! An entity whose `Share` output is above `grooming.intent_threshold` picks
+//! parasites off the nearest infected neighbour within `grooming.radius`.
+//! Members of its own tribe (colour distance below
+//! `social.tribe_color_threshold`) come before strangers. The groomer pays
+//! `grooming.energy_cost` and the neighbour's infection loses
+//! `grooming.relief_ticks`, so tribes that groom shake off outbreaks sooner.
+//! That is a concrete return on cooperating. Who groomed whom is recorded in
+//! the interaction graph.
+
+use crate::config::AppConfig;
+use crate::snapshot::InternalEntitySnapshot;
+use crate::spatial_hash::SpatialHash;
+use primordium_data::{Health, StatusKind};
+
+/// Whether two snapshots are close enough in colour to share a tribe.
+pub fn same_tribe(a: &InternalEntitySnapshot, b: &InternalEntitySnapshot, threshold: i32) -> bool {
+    let dist = (i32::from(a.r) - i32::from(b.r)).abs()
+        + (i32::from(a.g) - i32::from(b.g)).abs()
+        + (i32::from(a.b) - i32::from(b.b)).abs();
+    dist < threshold
+}
+
+/// Snapshot index of the neighbour the entity at snapshot `groomer` grooms
+/// this tick, if it wants to and can afford to: the nearest infected tribe
+/// mate, or failing that the nearest infected stranger.
+pub fn groom_target(
+    groomer: usize,
+    intent: f32,
+    snapshots: &[InternalEntitySnapshot],
+    spatial_hash: &SpatialHash,
+    config: &AppConfig,
+) -> Option<usize> {
+    let grooming = &config.grooming;
+    let g = &snapshots[groomer];
+    if !grooming.enabled || intent <= grooming.intent_threshold || g.energy <= grooming.energy_cost
+    {
+        return None;
+    }
+
+    let radius_sq = grooming.radius * grooming.radius;
+    let mut best: Option<(bool, f64, usize)> = None;
+    spatial_hash.query_callback(g.x, g.y, grooming.radius, |idx| {
+        let s = &snapshots[idx];
+        if idx == groomer || s.infection_remaining == 0 {
+            return;
+        }
+        let dist_sq = (s.x - g.x).powi(2) + (s.y - g.y).powi(2);
+        if dist_sq > radius_sq {
+            return;
+        }
+        let key = (
+            !same_tribe(g, s, config.social.tribe_color_threshold),
+            dist_sq,
+            idx,
+        );
+        if best.is_none_or(|b| key < b) {
+            best = Some(key);
+        }
+    });
+    best.map(|(_, _, idx)| idx)
+}
+
+/// Takes `ticks` off the infection in `health`. At least one tick is left,
+/// so a groomed-out infection still ends through the usual recovery, which
+/// clears the pathogen and raises immunity.
+pub fn relieve(health: &mut Health, ticks: u32) {
+    for effect in &mut health.effects {
+        if effect.kind == StatusKind::Infection {
+            effect.remaining = effect.remaining.saturating_sub(ticks).max(1);
+        }
+    }
+}
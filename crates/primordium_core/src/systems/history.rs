@@ -1,3 +1,4 @@
+use crate::environment::Environment;
 use crate::lineage_registry::LineageRegistry;
 use primordium_data::{Fossil, FossilRegistry, Legend};
 use std::collections::HashMap;
@@ -7,6 +8,7 @@ pub fn handle_fossilization(
     lineage_registry: &LineageRegistry,
     fossil_registry: &mut FossilRegistry,
     best_legends: &mut HashMap<Uuid, Legend>,
+    env: &Environment,
     tick: u64,
 ) {
     let extinct = lineage_registry.get_extinct_lineages();
@@ -24,6 +26,9 @@ pub fn handle_fossilization(
                         extinct_tick: tick,
                         peak_population: record.peak_population,
                         genotype: legend.genotype.clone(),
+                        era: env.current_era.name().to_string(),
+                        climate: env.climate().name().to_string(),
+                        extinction_cause: record.extinction_cause(),
                     });
                 }
             }
@@ -1,12 +1,12 @@
 use crate::brain::BrainLogic;
-use primordium_data::Brain;
+use primordium_data::{Brain, MAX_BODY_SIZE, MAX_CLUTCH_SIZE, MIN_BODY_SIZE};
 use rand::Rng;
 
 pub fn brain_forward(
     brain: &Brain,
-    inputs: [f32; 29],
+    inputs: [f32; 36],
     last_hidden: [f32; 6],
-) -> ([f32; 12], [f32; 6]) {
+) -> ([f32; 15], [f32; 6]) {
     brain.forward(inputs, last_hidden)
 }
 
@@ -139,7 +139,13 @@ pub fn mutate_genotype<R: Rng>(
             rng.gen_range(-effective_mutation_amount..effective_mutation_amount);
     }
     genotype.maturity_gene = genotype.maturity_gene.clamp(0.5, 2.0);
-    genotype.max_energy = (200.0 * genotype.maturity_gene as f64).clamp(100.0, 500.0);
+
+    if rng.gen::<f32>() < effective_mutation_rate {
+        genotype.body_size += rng.gen_range(-effective_mutation_amount..effective_mutation_amount);
+    }
+    genotype.body_size = genotype.body_size.clamp(MIN_BODY_SIZE, MAX_BODY_SIZE);
+    genotype.max_energy = (200.0 * genotype.maturity_gene as f64).clamp(100.0, 500.0)
+        * crate::body::energy_capacity(genotype.body_size);
 
     if rng.gen::<f32>() < effective_mutation_rate {
         genotype.metabolic_niche +=
@@ -171,6 +177,37 @@ pub fn mutate_genotype<R: Rng>(
     }
     genotype.pairing_bias = genotype.pairing_bias.clamp(0.0, 1.0);
 
+    if rng.gen::<f32>() < effective_mutation_rate {
+        genotype.oviparity += rng.gen_range(-effective_mutation_amount..effective_mutation_amount);
+    }
+    genotype.oviparity = genotype.oviparity.clamp(0.0, 1.0);
+
+    if rng.gen::<f32>() < effective_mutation_rate {
+        genotype.clutch_size = if rng.gen_bool(0.5) {
+            genotype.clutch_size.saturating_add(1)
+        } else {
+            genotype.clutch_size.saturating_sub(1)
+        };
+    }
+    genotype.clutch_size = genotype.clutch_size.clamp(1, MAX_CLUTCH_SIZE);
+
+    if rng.gen::<f32>() < effective_mutation_rate {
+        genotype.parental_care +=
+            rng.gen_range(-effective_mutation_amount..effective_mutation_amount);
+    }
+    genotype.parental_care = genotype.parental_care.clamp(0.0, 1.0);
+
+    if rng.gen::<f32>() < effective_mutation_rate {
+        genotype.skepticism += rng.gen_range(-effective_mutation_amount..effective_mutation_amount);
+    }
+    genotype.skepticism = genotype.skepticism.clamp(0.0, 1.0);
+
+    if rng.gen::<f32>() < effective_mutation_rate {
+        genotype.death_avoidance +=
+            rng.gen_range(-effective_mutation_amount..effective_mutation_amount);
+    }
+    genotype.death_avoidance = genotype.death_avoidance.clamp(0.0, 1.0);
+
     for bias in &mut genotype.specialization_bias {
         if rng.gen::<f32>() < effective_mutation_rate {
             *bias = (*bias + rng.gen_range(-effective_mutation_amount..effective_mutation_amount))
@@ -300,6 +337,36 @@ pub fn crossover_genotypes<R: Rng>(
         } else {
             p2.regulatory_rules.clone()
         },
+        oviparity: if rng.gen_bool(0.5) {
+            p1.oviparity
+        } else {
+            p2.oviparity
+        },
+        clutch_size: if rng.gen_bool(0.5) {
+            p1.clutch_size
+        } else {
+            p2.clutch_size
+        },
+        parental_care: if rng.gen_bool(0.5) {
+            p1.parental_care
+        } else {
+            p2.parental_care
+        },
+        skepticism: if rng.gen_bool(0.5) {
+            p1.skepticism
+        } else {
+            p2.skepticism
+        },
+        death_avoidance: if rng.gen_bool(0.5) {
+            p1.death_avoidance
+        } else {
+            p2.death_avoidance
+        },
+        body_size: if rng.gen_bool(0.5) {
+            p1.body_size
+        } else {
+            p2.body_size
+        },
     }
 }
 
@@ -353,7 +420,11 @@ mod tests {
 
         assert!(genotype.sensing_range >= 3.0 && genotype.sensing_range <= 15.0);
         assert!(genotype.max_speed >= 0.5 && genotype.max_speed <= 3.0);
-        assert!(genotype.max_energy >= 100.0 && genotype.max_energy <= 500.0);
+        assert!((MIN_BODY_SIZE..=MAX_BODY_SIZE).contains(&genotype.body_size));
+        assert!(
+            genotype.max_energy >= 100.0
+                && genotype.max_energy <= 500.0 * f64::from(genotype.body_size)
+        );
         assert!(genotype.maturity_gene >= 0.5 && genotype.maturity_gene <= 2.0);
         assert!(genotype.metabolic_niche >= 0.0 && genotype.metabolic_niche <= 1.0);
         assert!(genotype.trophic_potential >= 0.0 && genotype.trophic_potential <= 1.0);
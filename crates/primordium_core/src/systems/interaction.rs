@@ -1,13 +1,16 @@
 use crate::brain::BrainLogic;
 use crate::config::AppConfig;
+use crate::energy_ledger::EnergySource;
 use crate::environment::Environment;
+use crate::food_field::FoodField;
 use crate::interaction::InteractionCommand;
 use crate::lifecycle;
 use crate::lineage_registry::LineageRegistry;
+use crate::mutation_heat::MutationHeatGrid;
 use crate::systems::{biological, social};
 use crate::terrain::{TerrainGrid, TerrainType};
 use chrono::Utc;
-use primordium_data::{Entity, Health, Intel, Metabolism, Physics, Specialization};
+use primordium_data::{Egg, Entity, Health, Identity, Intel, Metabolism, Physics, Specialization};
 use primordium_data::{FossilRegistry, LiveEvent, PopulationStats};
 use rand::{Rng, SeedableRng};
 use std::collections::HashSet;
@@ -24,12 +27,19 @@ pub struct InteractionContext<'a, R: Rng> {
     pub width: u16,
     pub height: u16,
     pub social_grid: &'a mut [u8],
+    /// Births deposit their genetic distance here.
+    pub mutation_heat: &'a mut MutationHeatGrid,
     pub lineage_consumption: &'a mut Vec<(Uuid, f64)>,
     pub food_handles: &'a [hecs::Entity],
+    /// Food sites are consumed from this field instead of despawning `food_handles`.
+    pub food_field: Option<&'a mut FoodField>,
     pub spatial_hash: &'a crate::spatial_hash::SpatialHash,
     pub rng: &'a mut R,
     pub food_count: &'a std::sync::atomic::AtomicUsize,
     pub world_seed: u64,
+    /// Entity whose per-command energy changes are reported in
+    /// [`InteractionResult::ledger_deltas`].
+    pub ledger_target: Option<hecs::Entity>,
 }
 
 pub struct InteractionResult {
@@ -37,6 +47,50 @@ pub struct InteractionResult {
     pub killed_ids: HashSet<uuid::Uuid>,
     pub eaten_food_indices: HashSet<usize>,
     pub new_babies: Vec<Entity>,
+    /// Clutches laid by oviparous parents instead of live births.
+    pub new_eggs: Vec<Egg>,
+    pub ledger_deltas: Vec<(EnergySource, f64)>,
+    /// Groomer and groomed entity IDs of each grooming carried out.
+    pub grooms: Vec<(Uuid, Uuid)>,
+    /// Dominance contests fought.
+    pub contests: Vec<Contest>,
+}
+
+/// A dominance contest between two members of one lineage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contest {
+    pub lineage: Uuid,
+    pub challenger: Uuid,
+    pub defender: Uuid,
+    pub challenger_won: bool,
+}
+
+fn energy_of(world: &hecs::World, handle: hecs::Entity) -> Option<f64> {
+    world.get::<&Metabolism>(handle).ok().map(|m| m.energy)
+}
+
+/// The contest `challenger` picks with `defender`, if both are alive, share a
+/// lineage and the challenger can pay `cost`. The higher rank score wins.
+fn contest_of(
+    world: &hecs::World,
+    challenger: hecs::Entity,
+    defender: hecs::Entity,
+    cost: f64,
+) -> Option<Contest> {
+    let side = |handle| {
+        let id = world.get::<&Identity>(handle).ok()?.id;
+        let met = world.get::<&Metabolism>(handle).ok()?;
+        let rank = world.get::<&Intel>(handle).ok()?.rank;
+        Some((id, met.lineage_id, met.energy, rank))
+    };
+    let (c_id, lineage, c_energy, c_rank) = side(challenger)?;
+    let (d_id, d_lineage, _, d_rank) = side(defender)?;
+    (lineage == d_lineage && c_energy > cost).then_some(Contest {
+        lineage,
+        challenger: c_id,
+        defender: d_id,
+        challenger_won: c_rank > d_rank,
+    })
 }
 
 pub fn process_interaction_commands_ecs<R: Rng>(
@@ -49,8 +103,16 @@ pub fn process_interaction_commands_ecs<R: Rng>(
     let mut killed_ids = HashSet::new();
     let mut eaten_food_indices = HashSet::new();
     let mut new_babies = Vec::new();
+    let mut new_eggs = Vec::new();
+    let mut ledger_deltas = Vec::new();
+    let mut grooms = Vec::new();
+    let mut contests = Vec::new();
 
     for cmd in commands {
+        let probe = ctx.ledger_target.and_then(|h| {
+            energy_of(world, h).map(|before| (h, EnergySource::of_command(&cmd), before))
+        });
+
         match cmd {
             InteractionCommand::Kill {
                 target_idx,
@@ -148,36 +210,60 @@ pub fn process_interaction_commands_ecs<R: Rng>(
                 mut baby,
                 genetic_distance,
             } => {
-                ctx.lineage_registry.record_birth(
-                    baby.metabolism.lineage_id,
-                    baby.metabolism.generation,
-                    ctx.tick,
-                );
+                let parent_handle = entity_handles[parent_idx];
+                if let Ok(parent) = world.get::<&Identity>(parent_handle) {
+                    baby.identity.parent_id = Some(parent.id);
+                }
+                let clutch_size = world
+                    .get::<&Intel>(parent_handle)
+                    .ok()
+                    .filter(|intel| ctx.config.eggs.enabled && intel.genotype.is_oviparous())
+                    .map(|intel| intel.genotype.clutch_size);
                 crate::systems::stats::record_stat_birth_distance(ctx.pop_stats, genetic_distance);
-                let ev = LiveEvent::Birth {
-                    id: baby.identity.id,
-                    parent_id: baby.identity.parent_id,
-                    gen: baby.metabolism.generation,
-                    tick: ctx.tick,
-                    timestamp: chrono::Utc::now().to_rfc3339(),
-                    x: Some(baby.physics.x),
-                    y: Some(baby.physics.y),
-                };
-                events.push(ev);
+                ctx.mutation_heat
+                    .record(baby.position.x, baby.position.y, genetic_distance);
+                let baby_lineage_id = baby.metabolism.lineage_id;
 
-                let terrain_type = ctx.terrain.get(baby.physics.x, baby.physics.y).terrain_type;
-                if matches!(terrain_type, TerrainType::Nest) {
-                    baby.metabolism.energy *= ctx.config.metabolism.birth_energy_multiplier;
-                    baby.metabolism.peak_energy = baby.metabolism.energy;
-                }
+                if let Some(clutch_size) = clutch_size {
+                    // Births are recorded when the eggs hatch.
+                    new_eggs.extend(crate::systems::eggs::lay_clutch(
+                        *baby,
+                        clutch_size,
+                        ctx.tick,
+                        (ctx.width, ctx.height),
+                        ctx.rng,
+                    ));
+                } else {
+                    ctx.lineage_registry.record_birth(
+                        baby.metabolism.lineage_id,
+                        baby.metabolism.generation,
+                        ctx.tick,
+                    );
+                    let ev = LiveEvent::Birth {
+                        id: baby.identity.id,
+                        parent_id: baby.identity.parent_id,
+                        gen: baby.metabolism.generation,
+                        tick: ctx.tick,
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                        x: Some(baby.physics.x),
+                        y: Some(baby.physics.y),
+                    };
+                    events.push(ev);
 
-                new_babies.push(*baby);
+                    let terrain_type = ctx.terrain.get(baby.physics.x, baby.physics.y).terrain_type;
+                    if matches!(terrain_type, TerrainType::Nest) {
+                        baby.metabolism.energy *= ctx.config.metabolism.birth_energy_multiplier;
+                        baby.metabolism.peak_energy = baby.metabolism.energy;
+                    }
+                    new_babies.push(*baby);
+                }
 
-                let parent_handle = entity_handles[parent_idx];
                 if let (Ok(mut parent_met), Ok(parent_intel)) = (
                     world.get::<&mut Metabolism>(parent_handle),
                     world.get::<&Intel>(parent_handle),
                 ) {
+                    ctx.lineage_registry
+                        .record_speciation(baby_lineage_id, parent_met.lineage_id);
                     let inv = parent_intel.genotype.reproductive_investment as f64;
                     let c_e = parent_met.energy * inv;
                     parent_met.energy -= c_e;
@@ -192,22 +278,29 @@ pub fn process_interaction_commands_ecs<R: Rng>(
                 precalculated_energy_gain,
             } => {
                 if !eaten_food_indices.contains(&food_index) {
-                    let food_handle = ctx.food_handles[food_index];
                     let handle = entity_handles[attacker_idx];
 
                     eaten_food_indices.insert(food_index);
-                    let _ = world.despawn(food_handle);
-                    ctx.food_count
-                        .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
-                    if let Ok(mut met_mut) = world.get::<&mut Metabolism>(handle) {
-                        met_mut.energy =
-                            (met_mut.energy + precalculated_energy_gain).min(met_mut.max_energy);
-                        let lid = met_mut.lineage_id;
-                        ctx.lineage_registry.boost_memory_value(&lid, "goal", 0.2);
-                        ctx.terrain
-                            .deplete(x, y, ctx.config.ecosystem.soil_depletion_unit);
-                        ctx.lineage_consumption
-                            .push((lid, precalculated_energy_gain));
+                    let consumed = match ctx.food_field.as_deref_mut() {
+                        Some(field) => field.consume_site(food_index).is_some(),
+                        None => {
+                            let _ = world.despawn(ctx.food_handles[food_index]);
+                            true
+                        }
+                    };
+                    if consumed {
+                        ctx.food_count
+                            .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+                        if let Ok(mut met_mut) = world.get::<&mut Metabolism>(handle) {
+                            met_mut.energy = (met_mut.energy + precalculated_energy_gain)
+                                .min(met_mut.max_energy);
+                            let lid = met_mut.lineage_id;
+                            ctx.lineage_registry.boost_memory_value(&lid, "goal", 0.2);
+                            ctx.terrain
+                                .deplete(x, y, ctx.config.ecosystem.soil_depletion_unit);
+                            ctx.lineage_consumption
+                                .push((lid, precalculated_energy_gain));
+                        }
                     }
                 }
             }
@@ -234,6 +327,51 @@ pub fn process_interaction_commands_ecs<R: Rng>(
                     biological::try_infect_components(&mut health, &pathogen, ctx.rng);
                 }
             }
+            InteractionCommand::Groom {
+                groomer_idx,
+                target_idx,
+            } => {
+                let groomer_handle = entity_handles[groomer_idx];
+                let target_handle = entity_handles[target_idx];
+                let cost = ctx.config.grooming.energy_cost;
+                let paid = match world.get::<&mut Metabolism>(groomer_handle) {
+                    Ok(mut met) if met.energy > cost => {
+                        met.energy -= cost;
+                        true
+                    }
+                    _ => false,
+                };
+                if paid {
+                    if let Ok(mut health) = world.get::<&mut Health>(target_handle) {
+                        crate::systems::grooming::relieve(
+                            &mut health,
+                            ctx.config.grooming.relief_ticks,
+                        );
+                    }
+                    if let (Ok(groomer), Ok(target)) = (
+                        world.get::<&Identity>(groomer_handle),
+                        world.get::<&Identity>(target_handle),
+                    ) {
+                        grooms.push((groomer.id, target.id));
+                    }
+                }
+            }
+            InteractionCommand::Challenge {
+                challenger_idx,
+                target_idx,
+            } => {
+                let challenger_handle = entity_handles[challenger_idx];
+                let defender_handle = entity_handles[target_idx];
+                let cost = ctx.config.dominance.contest_cost;
+                if let Some(contest) = contest_of(world, challenger_handle, defender_handle, cost) {
+                    for handle in [challenger_handle, defender_handle] {
+                        if let Ok(mut met) = world.get::<&mut Metabolism>(handle) {
+                            met.energy = (met.energy - cost).max(0.0);
+                        }
+                    }
+                    contests.push(contest);
+                }
+            }
             InteractionCommand::UpdateReputation { target_idx, delta } => {
                 let handle = entity_handles[target_idx];
                 if let Ok(mut intel) = world.get::<&mut Intel>(handle) {
@@ -337,7 +475,8 @@ pub fn process_interaction_commands_ecs<R: Rng>(
                         met.energy -= energy_cost;
                         let idx = ctx.terrain.index(x as u16, y as u16);
                         ctx.terrain.set_cell_type(x as u16, y as u16, new_type);
-                        if let Some(c) = ctx.terrain.cells.get_mut(idx) {
+                        if idx < ctx.terrain.len() {
+                            let c = ctx.terrain.cell_mut(idx);
                             c.owner_id = Some(met.lineage_id);
                             if is_outpost {
                                 if let Some(s) = outpost_spec {
@@ -394,11 +533,14 @@ pub fn process_interaction_commands_ecs<R: Rng>(
                     phys.r = new_color.0;
                     phys.g = new_color.1;
                     phys.b = new_color.2;
+                    let old_lineage_id = met.lineage_id;
                     let new_lineage_id = Uuid::from_u128(ctx.rng.gen());
                     std::sync::Arc::make_mut(&mut intel.genotype).lineage_id = new_lineage_id;
                     met.lineage_id = new_lineage_id;
                     ctx.lineage_registry
                         .record_birth(met.lineage_id, met.generation, ctx.tick);
+                    ctx.lineage_registry
+                        .record_speciation(new_lineage_id, old_lineage_id);
                     if let Ok(identity) = world.get::<&primordium_data::Identity>(handle) {
                         let id = identity.id;
                         let ev = LiveEvent::TribalSplit {
@@ -412,6 +554,14 @@ pub fn process_interaction_commands_ecs<R: Rng>(
                 }
             }
         }
+
+        if let Some((h, source, before)) = probe {
+            if let Some(after) = energy_of(world, h) {
+                if after != before {
+                    ledger_deltas.push((source, after - before));
+                }
+            }
+        }
     }
 
     InteractionResult {
@@ -419,5 +569,9 @@ pub fn process_interaction_commands_ecs<R: Rng>(
         killed_ids,
         eaten_food_indices,
         new_babies,
+        new_eggs,
+        ledger_deltas,
+        grooms,
+        contests,
     }
 }
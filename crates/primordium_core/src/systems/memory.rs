@@ -0,0 +1,140 @@
+//! Episodic memory: a few addressable slots beyond the recurrent hidden
+//! state, so an organism can return to a place or a partner long after its
+//! hidden values have moved on.
+//!
+//! The `MemAddr` output picks a slot. Next tick that slot is read into the
+//! `Recall*` inputs, and whenever `MemWrite` is above the gate threshold the
+//! organism's position and bonded partner are written into it.
+
+use primordium_data::{EpisodicMemory, MemorySlot};
+use uuid::Uuid;
+
+/// `MemWrite` output above which a memory is stored.
+pub const WRITE_GATE: f32 = 0.5;
+
+/// Distance at which the recall direction inputs saturate, as for food.
+const RECALL_RANGE: f64 = 20.0;
+
+/// Slot addressed by a `MemAddr` output in `[-1, 1]`.
+#[must_use]
+pub fn address_slot(address: f32, slots: usize) -> usize {
+    let fraction = ((address + 1.0) * 0.5).clamp(0.0, 1.0);
+    ((fraction * slots as f32) as usize).min(slots.saturating_sub(1))
+}
+
+/// Reads the addressed slot as the `RecallDX`, `RecallDY`, `RecallAge` and
+/// `RecallPartner` inputs: the direction back to the remembered spot, how
+/// fresh the memory is (1.0 when just written) and whether the remembered
+/// partner is still alive. All zeros for an empty or faded slot.
+pub fn recall(
+    memory: &mut EpisodicMemory,
+    slots: usize,
+    horizon: u64,
+    (x, y): (f64, f64),
+    tick: u64,
+    is_alive: impl Fn(Uuid) -> bool,
+) -> [f32; 4] {
+    memory.slots.resize(slots, None);
+    memory.cursor = memory.cursor.min(slots.saturating_sub(1));
+    let Some(slot) = memory.slots.get_mut(memory.cursor) else {
+        return [0.0; 4];
+    };
+    let Some(stored) = *slot else {
+        return [0.0; 4];
+    };
+    let age = tick.saturating_sub(stored.tick);
+    if age >= horizon {
+        *slot = None;
+        return [0.0; 4];
+    }
+    memory.recalls += 1;
+    [
+        ((stored.x - x) / RECALL_RANGE).clamp(-1.0, 1.0) as f32,
+        ((stored.y - y) / RECALL_RANGE).clamp(-1.0, 1.0) as f32,
+        1.0 - age as f32 / horizon as f32,
+        if stored.partner.is_some_and(is_alive) {
+            1.0
+        } else {
+            0.0
+        },
+    ]
+}
+
+/// Applies the `MemWrite` and `MemAddr` outputs: moves the cursor and, if
+/// the write gate is open, stores the current episode in the new slot.
+pub fn store(
+    memory: &mut EpisodicMemory,
+    write: f32,
+    address: f32,
+    (x, y): (f64, f64),
+    partner: Option<Uuid>,
+    tick: u64,
+) {
+    if memory.slots.is_empty() {
+        return;
+    }
+    memory.cursor = address_slot(address, memory.slots.len());
+    if write > WRITE_GATE {
+        memory.slots[memory.cursor] = Some(MemorySlot {
+            x,
+            y,
+            partner,
+            tick,
+        });
+        memory.writes += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_recalls_written_slot_until_it_fades() {
+        let mut memory = EpisodicMemory::default();
+        let partner = Uuid::from_u128(7);
+        assert_eq!(
+            recall(&mut memory, 4, 100, (0.0, 0.0), 0, |_| true),
+            [0.0; 4]
+        );
+        assert_eq!(memory.slots.len(), 4);
+
+        // A closed gate only moves the cursor.
+        store(&mut memory, 0.0, 1.0, (10.0, 10.0), Some(partner), 1);
+        assert_eq!((memory.cursor, memory.writes), (3, 0));
+        store(&mut memory, 0.9, -1.0, (10.0, 10.0), Some(partner), 1);
+        assert_eq!((memory.cursor, memory.writes), (0, 1));
+
+        let recalled = recall(&mut memory, 4, 100, (20.0, 0.0), 51, |id| id == partner);
+        assert_eq!(recalled, [-0.5, 0.5, 0.5, 1.0]);
+        assert_eq!(
+            recall(&mut memory, 4, 100, (20.0, 0.0), 51, |_| false)[3],
+            0.0
+        );
+        assert_eq!(memory.recalls, 2);
+
+        // Another slot is empty, and the written one fades at the horizon.
+        store(&mut memory, 0.0, 0.0, (0.0, 0.0), None, 60);
+        assert_eq!(
+            recall(&mut memory, 4, 100, (0.0, 0.0), 60, |_| true),
+            [0.0; 4]
+        );
+        store(&mut memory, 0.0, -1.0, (0.0, 0.0), None, 60);
+        assert_eq!(
+            recall(&mut memory, 4, 100, (0.0, 0.0), 101, |_| true),
+            [0.0; 4]
+        );
+        assert!(memory.slots[0].is_none());
+
+        // Shrinking the memory keeps the cursor in range; zero slots disable it.
+        memory.cursor = 3;
+        recall(&mut memory, 2, 100, (0.0, 0.0), 101, |_| true);
+        assert_eq!(memory.cursor, 1);
+        assert_eq!(
+            recall(&mut memory, 0, 100, (0.0, 0.0), 101, |_| true),
+            [0.0; 4]
+        );
+        store(&mut memory, 1.0, 0.0, (0.0, 0.0), None, 102);
+        assert_eq!(memory.writes, 1);
+    }
+}
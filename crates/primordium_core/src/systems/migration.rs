@@ -0,0 +1,213 @@
+//! Long-distance lineage migration.
+//!
+//! Brain outputs only move entities locally, so a lineage cannot cross a
+//! mountain range or a river on its own. When its collective "threat" memory
+//! crosses `migration.threat_trigger`, or a target is set explicitly with
+//! [`LineageRegistry::set_migration_target`], the lineage gets a migration
+//! target. [`update_migrations`] then requests a route from the lineage
+//! centroid from the shared [`PathService`] every `migration.replan_interval`
+//! ticks, and the action system steers members toward the next waypoint on it.
+
+use crate::config::MigrationConfig;
+use crate::lineage_registry::LineageRegistry;
+use crate::pathfinding::{self, PathQuery, PathService, PlanMetrics};
+use crate::snapshot::InternalEntitySnapshot;
+use crate::terrain::{TerrainGrid, TerrainLogic};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Spacing of the cells sampled when choosing a migration target.
+const TARGET_SAMPLE_STRIDE: u16 = 4;
+
+/// Where a lineage is heading and the route planned to get there.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MigrationRoute {
+    pub target: (u16, u16),
+    /// Turning points of the last planned route; empty until first planned.
+    pub waypoints: Vec<(u16, u16)>,
+    pub planned_tick: Option<u64>,
+    pub metrics: PlanMetrics,
+    /// Route requested from the path service and not yet planned.
+    #[serde(default)]
+    pub pending: Option<PathQuery>,
+}
+
+impl MigrationRoute {
+    pub fn new(target: (u16, u16)) -> Self {
+        Self {
+            target,
+            ..Self::default()
+        }
+    }
+
+    /// Point an entity at `(x, y)` should head for: the waypoint after the
+    /// nearest one, so members join the route and then follow it forward.
+    pub fn next_waypoint(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let nearest = self
+            .waypoints
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance_sq(x, y, **a).total_cmp(&distance_sq(x, y, **b)))?
+            .0;
+        let (wx, wy) = self.waypoints[(nearest + 1).min(self.waypoints.len() - 1)];
+        Some((f64::from(wx) + 0.5, f64::from(wy) + 0.5))
+    }
+}
+
+/// Cumulative migration and planning counters since the world was created.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct MigrationTotals {
+    pub started: u64,
+    pub arrived: u64,
+    pub plans: u64,
+    /// Plans whose budget ran out before reaching the target.
+    pub incomplete_plans: u64,
+    /// Cells expanded over all plans.
+    pub expanded: u64,
+}
+
+impl MigrationTotals {
+    pub fn mean_expanded(&self) -> f64 {
+        if self.plans == 0 {
+            0.0
+        } else {
+            self.expanded as f64 / self.plans as f64
+        }
+    }
+}
+
+/// Mean position of the living members of each lineage.
+pub fn lineage_centroids(snapshots: &[InternalEntitySnapshot]) -> HashMap<Uuid, (f64, f64)> {
+    let mut sums: HashMap<Uuid, (f64, f64, usize)> = HashMap::new();
+    for s in snapshots {
+        let sum = sums.entry(s.lineage_id).or_default();
+        sum.0 += s.x;
+        sum.1 += s.y;
+        sum.2 += 1;
+    }
+    sums.into_iter()
+        .map(|(id, (x, y, n))| (id, (x / n as f64, y / n as f64)))
+        .collect()
+}
+
+/// Most fertile passable cell at least `min_distance` away from `from`,
+/// sampled on a coarse grid. Nearer cells win close calls.
+pub fn choose_target(
+    terrain: &TerrainGrid,
+    from: (f64, f64),
+    min_distance: f64,
+) -> Option<(u16, u16)> {
+    let diagonal = f64::from(terrain.width).hypot(f64::from(terrain.height));
+    let mut best: Option<(f64, (u16, u16))> = None;
+    for y in (0..terrain.height).step_by(TARGET_SAMPLE_STRIDE as usize) {
+        for x in (0..terrain.width).step_by(TARGET_SAMPLE_STRIDE as usize) {
+            if pathfinding::step_cost(terrain, x, y).is_none() {
+                continue;
+            }
+            let distance = distance_sq(from.0, from.1, (x, y)).sqrt();
+            if distance < min_distance {
+                continue;
+            }
+            let cell = terrain.get_cell(x, y);
+            let score = f64::from(cell.fertility) * cell.terrain_type.food_spawn_modifier()
+                - 0.2 * distance / diagonal.max(1.0);
+            if best.is_none_or(|(s, _)| score > s) {
+                best = Some((score, (x, y)));
+            }
+        }
+    }
+    best.map(|(_, cell)| cell)
+}
+
+/// Starts, re-plans and ends lineage migrations. Call every tick: lineages
+/// are checked and new routes requested from `paths` every
+/// `config.replan_interval` ticks, and each route is picked up as soon as the
+/// service has planned it. The season's `pressure` scales the threat a
+/// lineage feels before it sets out. Lineages are visited in id order so the request
+/// order, and with it the simulation, stays deterministic.
+pub fn update_migrations(
+    registry: &mut LineageRegistry,
+    paths: &mut PathService,
+    terrain: &TerrainGrid,
+    snapshots: &[InternalEntitySnapshot],
+    config: &MigrationConfig,
+    pressure: f32,
+    tick: u64,
+) {
+    let replan = tick.is_multiple_of(config.replan_interval);
+    let LineageRegistry {
+        lineages,
+        migration_totals: totals,
+        ..
+    } = registry;
+    if !replan
+        && !lineages
+            .values()
+            .any(|r| r.migration.as_ref().is_some_and(|m| m.pending.is_some()))
+    {
+        return;
+    }
+    let centroids = if replan {
+        lineage_centroids(snapshots)
+    } else {
+        HashMap::new()
+    };
+    let mut ids: Vec<Uuid> = lineages.keys().copied().collect();
+    ids.sort_unstable();
+
+    for id in ids {
+        let Some(record) = lineages.get_mut(&id) else {
+            continue;
+        };
+        if replan {
+            let Some(&centroid) = centroids.get(&id) else {
+                record.migration = None;
+                continue;
+            };
+            if record.migration.is_none()
+                && record.memory_value("threat") * pressure >= config.threat_trigger
+            {
+                if let Some(target) = choose_target(terrain, centroid, config.arrival_radius * 2.0)
+                {
+                    record.migration = Some(MigrationRoute::new(target));
+                    totals.started += 1;
+                }
+            }
+            if let Some(route) = record.migration.as_mut() {
+                if distance_sq(centroid.0, centroid.1, route.target).sqrt() <= config.arrival_radius
+                {
+                    record.migration = None;
+                    totals.arrived += 1;
+                    continue;
+                }
+                route.pending = Some(PathQuery {
+                    start: (centroid.0 as u16, centroid.1 as u16),
+                    goal: route.target,
+                });
+            }
+        }
+
+        let Some(route) = record.migration.as_mut() else {
+            continue;
+        };
+        let Some(path) = route.pending.and_then(|query| paths.request(query)) else {
+            continue;
+        };
+        totals.plans += 1;
+        totals.expanded += path.metrics.expanded as u64;
+        if !path.metrics.reached_goal {
+            totals.incomplete_plans += 1;
+        }
+        route.waypoints = path.waypoints.clone();
+        route.metrics = path.metrics;
+        route.planned_tick = Some(tick);
+        route.pending = None;
+    }
+}
+
+fn distance_sq(x: f64, y: f64, cell: (u16, u16)) -> f64 {
+    let dx = f64::from(cell.0) + 0.5 - x;
+    let dy = f64::from(cell.1) + 0.5 - y;
+    dx * dx + dy * dy
+}
@@ -1,11 +1,19 @@
 pub mod action;
 pub mod audio;
 pub mod biological;
+pub mod care;
 pub mod civilization;
+pub mod crowd;
+pub mod dominance;
 pub mod ecological;
+pub mod eggs;
 pub mod environment;
+pub mod grooming;
 pub mod history;
 pub mod intel;
 pub mod interaction;
+pub mod memory;
+pub mod migration;
 pub mod social;
 pub mod stats;
+pub mod status;
@@ -72,11 +72,12 @@ pub fn reproduce_asexual_parallel_components_decomposed<R: Rng>(
                     has_metamorphosed: false,
                     is_in_transit: false,
                     migration_id: None,
+                    hydration: 1.0,
                 },
                 health: primordium_data::Health {
                     pathogen: None,
-                    infection_timer: 0,
                     immunity: 0.0,
+                    effects: Vec::new(),
                 },
                 intel: primordium_data::Intel {
                     genotype: std::sync::Arc::new(input.genotype.clone()),
@@ -88,11 +89,14 @@ pub fn reproduce_asexual_parallel_components_decomposed<R: Rng>(
                     reputation: 1.0,
                     rank: 0.5,
                     bonded_to: None,
-                    last_inputs: [0.0; 29],
+                    last_inputs: Vec::new(),
                     last_activations: primordium_data::Activations::default(),
                     specialization: None,
                     spec_meters: std::collections::HashMap::new(),
                     ancestral_traits: input.ctx.traits.clone(),
+                    memory: Default::default(),
+                    deceptions: 0,
+                    dominance: 0.0,
                 },
             },
             0.0,
@@ -161,11 +165,12 @@ pub fn reproduce_asexual_parallel_components_decomposed<R: Rng>(
                     has_metamorphosed: false,
                     is_in_transit: false,
                     migration_id: None,
+                    hydration: 1.0,
                 },
                 health: primordium_data::Health {
                     pathogen: None,
-                    infection_timer: 0,
                     immunity: 0.0,
+                    effects: Vec::new(),
                 },
                 intel: primordium_data::Intel {
                     genotype: std::sync::Arc::new(input.genotype.clone()),
@@ -177,11 +182,14 @@ pub fn reproduce_asexual_parallel_components_decomposed<R: Rng>(
                     reputation: 1.0,
                     rank: 0.5,
                     bonded_to: None,
-                    last_inputs: [0.0; 29],
+                    last_inputs: Vec::new(),
                     last_activations: primordium_data::Activations::default(),
                     specialization: None,
                     spec_meters: std::collections::HashMap::new(),
                     ancestral_traits: input.ctx.traits.clone(),
+                    memory: Default::default(),
+                    deceptions: 0,
+                    dominance: 0.0,
                 },
             },
             0.0,
@@ -239,11 +247,12 @@ pub fn reproduce_asexual_parallel_components_decomposed<R: Rng>(
                     has_metamorphosed: false,
                     is_in_transit: false,
                     migration_id: None,
+                    hydration: 1.0,
                 },
                 health: primordium_data::Health {
                     pathogen: None,
-                    infection_timer: 0,
                     immunity: 0.0,
+                    effects: Vec::new(),
                 },
                 intel: primordium_data::Intel {
                     genotype: std::sync::Arc::new(input.genotype.clone()),
@@ -255,11 +264,14 @@ pub fn reproduce_asexual_parallel_components_decomposed<R: Rng>(
                     reputation: 1.0,
                     rank: 0.5,
                     bonded_to: None,
-                    last_inputs: [0.0; 29],
+                    last_inputs: Vec::new(),
                     last_activations: primordium_data::Activations::default(),
                     specialization: None,
                     spec_meters: std::collections::HashMap::new(),
                     ancestral_traits: input.ctx.traits.clone(),
+                    memory: Default::default(),
+                    deceptions: 0,
+                    dominance: 0.0,
                 },
             },
             0.0,
@@ -330,11 +342,12 @@ pub fn reproduce_asexual_parallel_components_decomposed<R: Rng>(
             has_metamorphosed: false,
             is_in_transit: false,
             migration_id: None,
+            hydration: 1.0,
         },
         health: Health {
             pathogen: None,
-            infection_timer: 0,
             immunity: (input.energy / 200.0) as f32,
+            effects: Vec::new(),
         },
         intel: Intel {
             genotype: child_genotype,
@@ -346,11 +359,14 @@ pub fn reproduce_asexual_parallel_components_decomposed<R: Rng>(
             reputation: 1.0,
             rank: 0.5,
             bonded_to: None,
-            last_inputs: [0.0; 29],
+            last_inputs: Vec::new(),
             last_activations: primordium_data::Activations::default(),
             specialization: None,
             spec_meters: std::collections::HashMap::new(),
             ancestral_traits: input.ctx.traits.clone(),
+            memory: Default::default(),
+            deceptions: 0,
+            dominance: 0.0,
         },
     };
 
@@ -423,11 +439,12 @@ pub fn reproduce_sexual_parallel_components_decomposed<R: Rng>(
                     has_metamorphosed: false,
                     is_in_transit: false,
                     migration_id: None,
+                    hydration: 1.0,
                 },
                 health: primordium_data::Health {
                     pathogen: None,
-                    infection_timer: 0,
                     immunity: 0.0,
+                    effects: Vec::new(),
                 },
                 intel: primordium_data::Intel {
                     genotype: std::sync::Arc::new(p1.genotype.clone()),
@@ -439,11 +456,14 @@ pub fn reproduce_sexual_parallel_components_decomposed<R: Rng>(
                     reputation: 1.0,
                     rank: 0.5,
                     bonded_to: None,
-                    last_inputs: [0.0; 29],
+                    last_inputs: Vec::new(),
                     last_activations: primordium_data::Activations::default(),
                     specialization: None,
                     spec_meters: std::collections::HashMap::new(),
                     ancestral_traits: ctx.traits.clone(),
+                    memory: Default::default(),
+                    deceptions: 0,
+                    dominance: 0.0,
                 },
             },
             0.0,
@@ -501,11 +521,12 @@ pub fn reproduce_sexual_parallel_components_decomposed<R: Rng>(
                     has_metamorphosed: false,
                     is_in_transit: false,
                     migration_id: None,
+                    hydration: 1.0,
                 },
                 health: primordium_data::Health {
                     pathogen: None,
-                    infection_timer: 0,
                     immunity: 0.0,
+                    effects: Vec::new(),
                 },
                 intel: primordium_data::Intel {
                     genotype: std::sync::Arc::new(p1.genotype.clone()),
@@ -517,11 +538,14 @@ pub fn reproduce_sexual_parallel_components_decomposed<R: Rng>(
                     reputation: 1.0,
                     rank: 0.5,
                     bonded_to: None,
-                    last_inputs: [0.0; 29],
+                    last_inputs: Vec::new(),
                     last_activations: primordium_data::Activations::default(),
                     specialization: None,
                     spec_meters: std::collections::HashMap::new(),
                     ancestral_traits: ctx.traits.clone(),
+                    memory: Default::default(),
+                    deceptions: 0,
+                    dominance: 0.0,
                 },
             },
             0.0,
@@ -595,11 +619,12 @@ pub fn reproduce_sexual_parallel_components_decomposed<R: Rng>(
                     has_metamorphosed: false,
                     is_in_transit: false,
                     migration_id: None,
+                    hydration: 1.0,
                 },
                 health: primordium_data::Health {
                     pathogen: None,
-                    infection_timer: 0,
                     immunity: 0.0,
+                    effects: Vec::new(),
                 },
                 intel: primordium_data::Intel {
                     genotype: std::sync::Arc::new(p1.genotype.clone()),
@@ -611,11 +636,14 @@ pub fn reproduce_sexual_parallel_components_decomposed<R: Rng>(
                     reputation: 1.0,
                     rank: 0.5,
                     bonded_to: None,
-                    last_inputs: [0.0; 29],
+                    last_inputs: Vec::new(),
                     last_activations: primordium_data::Activations::default(),
                     specialization: None,
                     spec_meters: std::collections::HashMap::new(),
                     ancestral_traits: ctx.traits.clone(),
+                    memory: Default::default(),
+                    deceptions: 0,
+                    dominance: 0.0,
                 },
             },
             dist,
@@ -665,11 +693,12 @@ pub fn reproduce_sexual_parallel_components_decomposed<R: Rng>(
             has_metamorphosed: false,
             is_in_transit: false,
             migration_id: None,
+            hydration: 1.0,
         },
         health: Health {
             pathogen: None,
-            infection_timer: 0,
             immunity: (p1.energy / 200.0) as f32,
+            effects: Vec::new(),
         },
         intel: Intel {
             genotype: child_genotype,
@@ -681,11 +710,14 @@ pub fn reproduce_sexual_parallel_components_decomposed<R: Rng>(
             reputation: 1.0,
             rank: 0.5,
             bonded_to: None,
-            last_inputs: [0.0; 29],
+            last_inputs: Vec::new(),
             last_activations: primordium_data::Activations::default(),
             specialization: None,
             spec_meters: std::collections::HashMap::new(),
             ancestral_traits: ctx.traits.clone(),
+            memory: Default::default(),
+            deceptions: 0,
+            dominance: 0.0,
         },
     };
 
@@ -24,12 +24,14 @@ pub struct PredationContext<'a> {
 pub fn handle_symbiosis_components(
     idx: usize,
     snapshots: &[InternalEntitySnapshot],
-    outputs: [f32; 12],
+    outputs: [f32; 15],
     spatial_hash: &SpatialHash,
     config: &AppConfig,
 ) -> Option<Uuid> {
     if outputs[8] > 0.5 {
-        let mut partner_id = None;
+        // Higher dominance standing wins priority as a mate; among equals
+        // the first one found is kept.
+        let mut partner: Option<(f32, Uuid)> = None;
         let self_snap = &snapshots[idx];
 
         spatial_hash.query_callback(
@@ -37,8 +39,8 @@ pub fn handle_symbiosis_components(
             self_snap.y,
             config.social.territorial_range,
             |t_idx| {
-                if idx != t_idx && partner_id.is_none() {
-                    let target_snap = &snapshots[t_idx];
+                let target_snap = &snapshots[t_idx];
+                if idx != t_idx && partner.is_none_or(|(d, _)| target_snap.dominance > d) {
                     let color_dist = (self_snap.r as i32 - target_snap.r as i32).abs()
                         + (self_snap.g as i32 - target_snap.g as i32).abs()
                         + (self_snap.b as i32 - target_snap.b as i32).abs();
@@ -46,12 +48,12 @@ pub fn handle_symbiosis_components(
                     if color_dist < config.social.tribe_color_threshold
                         && target_snap.status != primordium_data::EntityStatus::Bonded
                     {
-                        partner_id = Some(target_snap.id);
+                        partner = Some((target_snap.dominance, target_snap.id));
                     }
                 }
             },
         );
-        partner_id
+        partner.map(|(_, id)| id)
     } else {
         None
     }
@@ -1,5 +1,6 @@
+use crate::biome::Biome;
 use crate::brain::BrainLogic;
-use primordium_data::{Entity, HallOfFame, PopulationStats};
+use primordium_data::{Brain, Entity, HallOfFame, PopulationStats};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
@@ -32,6 +33,44 @@ pub fn update_population_stats(ctx: StatsContext<Entity>) {
     ctx.stats.biomass_c = 0.0;
     ctx.stats.biodiversity_hotspots = 0;
 
+    (ctx.stats.avg_brain_nodes, ctx.stats.avg_brain_connections) =
+        mean_brain_size(ctx.entities.iter().map(|e| &e.intel.genotype.brain));
+    update_memory_usage(
+        ctx.stats,
+        ctx.entities.iter().map(|e| MemoryUse {
+            lineage_id: e.metabolism.lineage_id,
+            age: ctx.tick.saturating_sub(e.metabolism.birth_tick),
+            writes: e.intel.memory.writes,
+            recalls: e.intel.memory.recalls,
+        }),
+    );
+    update_deception(
+        ctx.stats,
+        ctx.entities.iter().map(|e| Deception {
+            lineage_id: e.metabolism.lineage_id,
+            age: ctx.tick.saturating_sub(e.metabolism.birth_tick),
+            deceptions: e.intel.deceptions,
+            skepticism: e.intel.genotype.skepticism,
+        }),
+    );
+    update_hierarchy_depth(
+        ctx.stats,
+        ctx.entities
+            .iter()
+            .map(|e| (e.metabolism.lineage_id, e.intel.dominance)),
+    );
+    update_death_avoidance(
+        ctx.stats,
+        ctx.entities
+            .iter()
+            .map(|e| (e.metabolism.lineage_id, e.intel.genotype.death_avoidance)),
+    );
+    update_biome_population(
+        ctx.stats,
+        ctx.terrain,
+        ctx.entities.iter().map(|e| (e.physics.x, e.physics.y)),
+    );
+
     if ctx.entities.is_empty() {
         ctx.stats.avg_brain_entropy = 0.0;
         ctx.stats.species_count = 0;
@@ -116,6 +155,168 @@ pub fn update_population_stats(ctx: StatsContext<Entity>) {
     ctx.stats.top_fitness = max_fitness;
 }
 
+/// One living entity's use of its episodic memory.
+struct MemoryUse {
+    lineage_id: Uuid,
+    age: u64,
+    writes: u32,
+    recalls: u32,
+}
+
+/// Sets the memory usage stats: how many entities, overall and per lineage,
+/// have written a memory, and how much of their lives they spent recalling
+/// one. Rising values mean lineages are evolving to use the memory.
+fn update_memory_usage(stats: &mut PopulationStats, entities: impl Iterator<Item = MemoryUse>) {
+    let mut lineages: HashMap<Uuid, (usize, usize)> = HashMap::new();
+    let (mut count, mut writers, mut ticks, mut recalls) = (0usize, 0usize, 0u64, 0u64);
+    for e in entities {
+        let lineage = lineages.entry(e.lineage_id).or_default();
+        lineage.0 += 1;
+        count += 1;
+        if e.writes > 0 {
+            lineage.1 += 1;
+            writers += 1;
+        }
+        ticks += e.age.max(1);
+        recalls += u64::from(e.recalls);
+    }
+    stats.memory_writers = if count == 0 {
+        0.0
+    } else {
+        writers as f64 / count as f64
+    };
+    stats.memory_recall_rate = if ticks == 0 {
+        0.0
+    } else {
+        (recalls as f64 / ticks as f64).min(1.0)
+    };
+    stats.lineage_memory_writers = lineages
+        .into_iter()
+        .map(|(id, (members, writers))| (id, writers as f64 / members as f64))
+        .collect();
+}
+
+/// One living entity's deceptive signalling and its guard against it.
+struct Deception {
+    lineage_id: Uuid,
+    age: u64,
+    deceptions: u32,
+    skepticism: f32,
+}
+
+/// Sets the deception stats: the share of lifetime ticks spent laying false
+/// food trails, overall and per lineage, and the mean skepticism. The two
+/// rising together is a signalling arms race.
+fn update_deception(stats: &mut PopulationStats, entities: impl Iterator<Item = Deception>) {
+    let mut lineages: HashMap<Uuid, (u64, u64)> = HashMap::new();
+    let (mut count, mut skepticism, mut ticks, mut deceptions) = (0usize, 0.0, 0u64, 0u64);
+    for e in entities {
+        let age = e.age.max(1);
+        let lineage = lineages.entry(e.lineage_id).or_default();
+        lineage.0 += age;
+        lineage.1 += u64::from(e.deceptions);
+        count += 1;
+        skepticism += f64::from(e.skepticism);
+        ticks += age;
+        deceptions += u64::from(e.deceptions);
+    }
+    let rate = |deceptions: u64, ticks: u64| {
+        if ticks == 0 {
+            0.0
+        } else {
+            (deceptions as f64 / ticks as f64).min(1.0)
+        }
+    };
+    stats.deception_rate = rate(deceptions, ticks);
+    stats.avg_skepticism = if count == 0 {
+        0.0
+    } else {
+        skepticism / count as f64
+    };
+    stats.lineage_deception_rate = lineages
+        .into_iter()
+        .map(|(id, (ticks, deceptions))| (id, rate(deceptions, ticks)))
+        .collect();
+}
+
+/// Sets the hierarchy depth stats from each living entity's lineage and
+/// dominance standing: how many members of each lineage hold a place in its
+/// ladder, and the deepest ladder overall.
+fn update_hierarchy_depth(
+    stats: &mut PopulationStats,
+    entities: impl Iterator<Item = (Uuid, f32)>,
+) {
+    let mut depths: HashMap<Uuid, usize> = HashMap::new();
+    for (lineage_id, dominance) in entities {
+        if dominance > 0.0 {
+            *depths.entry(lineage_id).or_default() += 1;
+        }
+    }
+    stats.max_hierarchy_depth = depths.values().copied().max().unwrap_or(0);
+    stats.lineage_hierarchy_depth = depths;
+}
+
+/// Sets the death avoidance stats from each living entity's lineage and
+/// gene: the mean overall and per lineage. Lineages hit by epidemics should
+/// drift upwards as avoiders outlive their neighbours.
+fn update_death_avoidance(
+    stats: &mut PopulationStats,
+    entities: impl Iterator<Item = (Uuid, f32)>,
+) {
+    let mut lineages: HashMap<Uuid, (usize, f64)> = HashMap::new();
+    let (mut count, mut total) = (0usize, 0.0);
+    for (lineage_id, avoidance) in entities {
+        let lineage = lineages.entry(lineage_id).or_default();
+        lineage.0 += 1;
+        lineage.1 += f64::from(avoidance);
+        count += 1;
+        total += f64::from(avoidance);
+    }
+    stats.avg_death_avoidance = if count == 0 {
+        0.0
+    } else {
+        total / count as f64
+    };
+    stats.lineage_death_avoidance = lineages
+        .into_iter()
+        .map(|(id, (members, total))| (id, total / members as f64))
+        .collect();
+}
+
+/// Counts living entities by the biome of the cell they stand on.
+fn update_biome_population(
+    stats: &mut PopulationStats,
+    terrain: &crate::terrain::TerrainGrid,
+    positions: impl Iterator<Item = (f64, f64)>,
+) {
+    stats.biome_population.clear();
+    for (x, y) in positions {
+        if let Some(biome) = Biome::classify(terrain.get(x, y)) {
+            *stats
+                .biome_population
+                .entry(biome.name().to_string())
+                .or_default() += 1;
+        }
+    }
+}
+
+/// Mean node and connection counts of `brains`, or zeros for none.
+fn mean_brain_size<'a>(brains: impl Iterator<Item = &'a Brain>) -> (f64, f64) {
+    let (mut count, mut nodes, mut connections) = (0usize, 0usize, 0usize);
+    for brain in brains {
+        count += 1;
+        nodes += brain.nodes.len();
+        connections += brain.connections.len();
+    }
+    if count == 0 {
+        return (0.0, 0.0);
+    }
+    (
+        nodes as f64 / count as f64,
+        connections as f64 / count as f64,
+    )
+}
+
 pub fn record_stat_death(stats: &mut PopulationStats, lifespan: u64) {
     stats.recent_deaths.push_back(lifespan as f64);
     if stats.recent_deaths.len() > 100 {
@@ -127,15 +328,13 @@ pub fn record_stat_death(stats: &mut PopulationStats, lifespan: u64) {
     }
 }
 
+/// Keeps the genetic distances of the last 100 newborns. The velocity stat
+/// comes from the mutation heat map instead, which weighs births by recency.
 pub fn record_stat_birth_distance(stats: &mut PopulationStats, distance: f32) {
     stats.recent_distances.push_back(distance);
     if stats.recent_distances.len() > 100 {
         stats.recent_distances.pop_front();
     }
-    if !stats.recent_distances.is_empty() {
-        stats.evolutionary_velocity =
-            stats.recent_distances.iter().sum::<f32>() / stats.recent_distances.len() as f32;
-    }
 }
 
 pub fn update_hall_of_fame(hof: &mut HallOfFame, entities: &[Entity], tick: u64) {
@@ -198,6 +397,48 @@ pub fn update_population_stats_snapshots(
     ctx.stats.biomass_c = 0.0;
     ctx.stats.biodiversity_hotspots = 0;
 
+    (ctx.stats.avg_brain_nodes, ctx.stats.avg_brain_connections) = mean_brain_size(
+        ctx.entities
+            .iter()
+            .filter_map(|e| e.genotype.as_ref().map(|g| &g.brain)),
+    );
+    update_memory_usage(
+        ctx.stats,
+        ctx.entities.iter().map(|e| MemoryUse {
+            lineage_id: e.lineage_id,
+            age: ctx.tick.saturating_sub(e.birth_tick),
+            writes: e.memory_writes,
+            recalls: e.memory_recalls,
+        }),
+    );
+    update_deception(
+        ctx.stats,
+        ctx.entities.iter().map(|e| Deception {
+            lineage_id: e.lineage_id,
+            age: ctx.tick.saturating_sub(e.birth_tick),
+            deceptions: e.deceptions,
+            skepticism: e.genotype.as_ref().map_or(0.0, |g| g.skepticism),
+        }),
+    );
+    update_hierarchy_depth(
+        ctx.stats,
+        ctx.entities.iter().map(|e| (e.lineage_id, e.dominance)),
+    );
+    update_death_avoidance(
+        ctx.stats,
+        ctx.entities.iter().map(|e| {
+            (
+                e.lineage_id,
+                e.genotype.as_ref().map_or(0.0, |g| g.death_avoidance),
+            )
+        }),
+    );
+    update_biome_population(
+        ctx.stats,
+        ctx.terrain,
+        ctx.entities.iter().map(|e| (e.x, e.y)),
+    );
+
     if ctx.entities.is_empty() {
         ctx.stats.avg_brain_entropy = 0.0;
         ctx.stats.species_count = 0;
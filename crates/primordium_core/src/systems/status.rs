@@ -0,0 +1,209 @@
+//! Status effects.
+//!
+//! Buffs and debuffs live on [`Health::effects`] as [`StatusEffect`]s with a
+//! kind, a magnitude, a timer and a [`Stacking`] policy. Effects are applied
+//! through [`apply`], worn down once per tick by [`process_status_effects`]
+//! and read by the systems they modify through [`magnitude`] and [`has`]:
+//!
+//! - `Infection` drains energy each tick; it wears off into immunity.
+//! - `Radiation` is picked up during radiation storms and lingers for
+//!   [`RADIATION_EXPOSURE_TICKS`], boosting mutation in offspring.
+//! - `NestShield` lowers idle cost while resting on a nest.
+//! - `Blessing` is granted with divine relief and adds to immunity.
+//! - `MutationRate` and `MetabolicRate` are refreshed each tick by a running
+//!   cohort experiment and scale the matching parameter (see [`factor`]).
+
+use crate::config::AppConfig;
+use crate::environment::Environment;
+use primordium_data::{
+    Health, Metabolism, Pathogen, Stacking, StatusEffect, StatusKind, TerrainType,
+};
+use std::borrow::Cow;
+
+/// Ticks radiation exposure lingers after leaving a storm.
+pub const RADIATION_EXPOSURE_TICKS: u32 = 20;
+
+/// Ticks a blessing lasts.
+pub const BLESSING_TICKS: u32 = 200;
+
+/// Immunity a blessing adds.
+pub const BLESSING_IMMUNITY: f32 = 0.5;
+
+/// Applies `effect` to `effects` according to its stacking policy.
+pub fn apply(effects: &mut Vec<StatusEffect>, effect: StatusEffect) {
+    let active = effects.iter().position(|e| e.kind == effect.kind);
+    match (effect.stacking, active) {
+        (_, None) => effects.push(effect),
+        (Stacking::Ignore, Some(_)) => {}
+        (Stacking::Refresh, Some(i)) => effects[i] = effect,
+        (Stacking::Strongest, Some(i)) => {
+            let current = &effects[i];
+            if (effect.magnitude, effect.remaining) > (current.magnitude, current.remaining) {
+                effects[i] = effect;
+            }
+        }
+        (Stacking::Stack { max }, Some(_)) => {
+            let count = effects.iter().filter(|e| e.kind == effect.kind).count();
+            if count < usize::from(max) {
+                effects.push(effect);
+            } else if let Some(weakest) = effects
+                .iter_mut()
+                .filter(|e| e.kind == effect.kind)
+                .min_by_key(|e| e.remaining)
+            {
+                *weakest = effect;
+            }
+        }
+    }
+}
+
+/// Removes every effect of `kind`.
+pub fn remove(effects: &mut Vec<StatusEffect>, kind: StatusKind) {
+    effects.retain(|e| e.kind != kind);
+}
+
+/// Whether an effect of `kind` is active.
+pub fn has(effects: &[StatusEffect], kind: StatusKind) -> bool {
+    effects.iter().any(|e| e.kind == kind)
+}
+
+/// Combined magnitude of the active effects of `kind`, or 0.
+pub fn magnitude(effects: &[StatusEffect], kind: StatusKind) -> f32 {
+    effects
+        .iter()
+        .filter(|e| e.kind == kind)
+        .map(|e| e.magnitude)
+        .sum()
+}
+
+/// Ticks left on the longest active effect of `kind`, or 0.
+pub fn remaining(effects: &[StatusEffect], kind: StatusKind) -> u32 {
+    effects
+        .iter()
+        .filter(|e| e.kind == kind)
+        .map(|e| e.remaining)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Product of the magnitudes of the active effects of `kind`, or 1.
+pub fn factor(effects: &[StatusEffect], kind: StatusKind) -> f32 {
+    effects
+        .iter()
+        .filter(|e| e.kind == kind)
+        .map(|e| e.magnitude)
+        .product()
+}
+
+/// `config` with its mutation rate scaled by any `MutationRate` effect and
+/// by the current era's `era_scale`.
+pub fn mutation_config<'a>(
+    config: &'a AppConfig,
+    effects: &[StatusEffect],
+    era_scale: f32,
+) -> Cow<'a, AppConfig> {
+    let factor = factor(effects, StatusKind::MutationRate) * era_scale;
+    if factor == 1.0 {
+        return Cow::Borrowed(config);
+    }
+    let mut scaled = config.clone();
+    scaled.evolution.mutation_rate = (scaled.evolution.mutation_rate * factor).clamp(0.0, 1.0);
+    Cow::Owned(scaled)
+}
+
+/// A treatment of `kind` for this tick, scaling its parameter by `factor`.
+pub fn treatment(kind: StatusKind, factor: f32) -> StatusEffect {
+    StatusEffect {
+        kind,
+        magnitude: factor,
+        remaining: 1,
+        stacking: Stacking::Refresh,
+    }
+}
+
+/// Infection by `pathogen` for its full duration.
+pub fn infection(pathogen: &Pathogen) -> StatusEffect {
+    StatusEffect {
+        kind: StatusKind::Infection,
+        magnitude: pathogen.lethality,
+        remaining: pathogen.duration,
+        stacking: Stacking::Ignore,
+    }
+}
+
+pub fn radiation() -> StatusEffect {
+    StatusEffect {
+        kind: StatusKind::Radiation,
+        magnitude: 1.0,
+        remaining: RADIATION_EXPOSURE_TICKS,
+        stacking: Stacking::Refresh,
+    }
+}
+
+/// Shelter for this tick, cutting idle cost by `reduction`.
+pub fn nest_shield(reduction: f32) -> StatusEffect {
+    StatusEffect {
+        kind: StatusKind::NestShield,
+        magnitude: reduction,
+        remaining: 1,
+        stacking: Stacking::Strongest,
+    }
+}
+
+pub fn blessing() -> StatusEffect {
+    StatusEffect {
+        kind: StatusKind::Blessing,
+        magnitude: BLESSING_IMMUNITY,
+        remaining: BLESSING_TICKS,
+        stacking: Stacking::Refresh,
+    }
+}
+
+/// Applies the effects an entity picks up from its surroundings this tick:
+/// radiation during storms and shelter on nests.
+pub fn apply_environment(
+    health: &mut Health,
+    env: &Environment,
+    terrain_type: TerrainType,
+    config: &AppConfig,
+) {
+    if env.is_radiation_storm() {
+        apply(&mut health.effects, radiation());
+    }
+    if terrain_type == TerrainType::Nest {
+        apply(
+            &mut health.effects,
+            nest_shield(config.ecosystem.corpse_fertility_mult),
+        );
+    }
+}
+
+/// Runs one tick of every active effect and returns the energy they drained.
+///
+/// Expired effects are removed; an expired infection clears the pathogen and
+/// raises immunity. A pathogen without a matching infection effect (as in
+/// saves from before effects existed) starts a fresh one.
+pub fn process_status_effects(health: &mut Health, metabolism: &mut Metabolism) -> f64 {
+    if let Some(pathogen) = &health.pathogen {
+        if !has(&health.effects, StatusKind::Infection) {
+            let effect = infection(pathogen);
+            apply(&mut health.effects, effect);
+        }
+    }
+
+    let drained = f64::from(magnitude(&health.effects, StatusKind::Infection));
+    metabolism.energy -= drained;
+
+    let mut recovered = false;
+    health.effects.retain_mut(|e| {
+        e.remaining = e.remaining.saturating_sub(1);
+        let expired = e.remaining == 0;
+        recovered |= expired && e.kind == StatusKind::Infection;
+        !expired
+    });
+    if recovered {
+        health.pathogen = None;
+        health.immunity = (health.immunity + 0.1).min(1.0);
+    }
+    drained
+}
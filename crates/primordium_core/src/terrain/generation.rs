@@ -2,7 +2,6 @@ use super::{TerrainCell, TerrainGrid, TerrainType};
 use rand::Rng;
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
-use std::collections::HashSet;
 
 impl TerrainGrid {
     pub fn generate(width: u16, height: u16, seed: u64) -> Self {
@@ -61,19 +60,7 @@ impl TerrainGrid {
             attempts += 1;
         }
 
-        Self {
-            cells,
-            width,
-            height,
-            dust_bowl_timer: 0,
-            is_dirty: true,
-            outpost_indices: HashSet::new(),
-            type_buffer: vec![TerrainType::Plains; width as usize * height as usize],
-            hydration_buffer: vec![false; width as usize * height as usize],
-            moisture_buffer: vec![0.5; width as usize * height as usize],
-            cooling_buffer: vec![0.0; width as usize * height as usize],
-            outpost_buffer: vec![false; width as usize * height as usize],
-        }
+        Self::from_cells(width, height, cells)
     }
 
     pub(crate) fn value_noise(x: f32, y: f32, seed: u64) -> f32 {
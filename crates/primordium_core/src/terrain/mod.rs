@@ -2,10 +2,12 @@ pub use primordium_data::{OutpostSpecialization, TerrainType};
 use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::sync::Arc;
 
 pub mod disasters;
 pub mod generation;
 pub mod succession;
+pub mod water;
 
 pub trait TerrainLogic {
     fn movement_modifier(&self) -> f64;
@@ -60,7 +62,9 @@ impl TerrainLogic for TerrainType {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize,
+)]
 #[archive(check_bytes)]
 pub struct TerrainCell {
     pub terrain_type: TerrainType,
@@ -96,48 +100,242 @@ impl Default for TerrainCell {
     }
 }
 
+/// Number of map rows stored in one copy-on-write terrain chunk.
+pub const CHUNK_ROWS: u16 = 8;
+
+/// A horizontal band of up to [`CHUNK_ROWS`] full map rows.
+///
+/// Chunks are shared (`Arc`) between the live grid and any snapshot taken
+/// from it; a write only copies the chunk that contains the edited cell.
 #[derive(
-    Serialize, Deserialize, Clone, Debug, Default, Archive, RkyvSerialize, RkyvDeserialize,
+    Serialize,
+    Deserialize,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Archive,
+    RkyvSerialize,
+    RkyvDeserialize,
 )]
 #[archive(check_bytes)]
-pub struct TerrainGrid {
+pub struct TerrainChunk {
+    /// Map row of the first cell in this chunk.
+    pub origin_y: u16,
+    /// Row-major cells, `width * rows` long.
     pub cells: Vec<TerrainCell>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Archive, RkyvSerialize, RkyvDeserialize)]
+#[serde(from = "FlatTerrainGrid", into = "FlatTerrainGrid")]
+#[archive(check_bytes)]
+pub struct TerrainGrid {
+    chunks: Vec<Arc<TerrainChunk>>,
     pub width: u16,
     pub height: u16,
     pub dust_bowl_timer: u32,
-    #[serde(skip)]
+    /// Standing water per cell (0.0-1.0), row-major.
+    pub(crate) water: Vec<f32>,
     #[with(rkyv::with::Skip)]
     pub is_dirty: bool,
-    #[serde(skip)]
     #[with(rkyv::with::Skip)]
     pub outpost_indices: HashSet<usize>,
-    #[serde(skip)]
     #[with(rkyv::with::Skip)]
     pub(crate) type_buffer: Vec<TerrainType>,
-    #[serde(skip)]
     #[with(rkyv::with::Skip)]
     pub(crate) hydration_buffer: Vec<bool>,
-    #[serde(skip)]
     #[with(rkyv::with::Skip)]
     pub(crate) moisture_buffer: Vec<f32>,
-    #[serde(skip)]
     #[with(rkyv::with::Skip)]
     pub(crate) cooling_buffer: Vec<f32>,
-    #[serde(skip)]
     #[with(rkyv::with::Skip)]
     pub(crate) outpost_buffer: Vec<bool>,
 }
 
+/// Serde representation of [`TerrainGrid`]: a flat cell list, so JSON saves
+/// are independent of the in-memory chunk layout.
+#[derive(Serialize, Deserialize)]
+struct FlatTerrainGrid {
+    cells: Vec<TerrainCell>,
+    width: u16,
+    height: u16,
+    dust_bowl_timer: u32,
+    #[serde(default)]
+    water: Vec<f32>,
+}
+
+impl From<FlatTerrainGrid> for TerrainGrid {
+    fn from(flat: FlatTerrainGrid) -> Self {
+        let mut grid = TerrainGrid::from_cells(flat.width, flat.height, flat.cells);
+        grid.dust_bowl_timer = flat.dust_bowl_timer;
+        // Saves from before the water layer are seeded from the terrain.
+        if flat.water.len() == grid.len() {
+            grid.water = flat.water;
+        }
+        grid.restore_buffers();
+        grid
+    }
+}
+
+impl From<TerrainGrid> for FlatTerrainGrid {
+    fn from(grid: TerrainGrid) -> Self {
+        Self {
+            cells: grid.cells().copied().collect(),
+            width: grid.width,
+            height: grid.height,
+            dust_bowl_timer: grid.dust_bowl_timer,
+            water: grid.water,
+        }
+    }
+}
+
+impl Clone for TerrainGrid {
+    /// Shares every chunk with `self`; only the persistent diffusion buffers
+    /// are copied. Per-tick scratch buffers are rebuilt on the next update.
+    fn clone(&self) -> Self {
+        Self {
+            chunks: self.chunks.clone(),
+            width: self.width,
+            height: self.height,
+            dust_bowl_timer: self.dust_bowl_timer,
+            water: self.water.clone(),
+            is_dirty: self.is_dirty,
+            outpost_indices: self.outpost_indices.clone(),
+            type_buffer: Vec::new(),
+            hydration_buffer: Vec::new(),
+            moisture_buffer: self.moisture_buffer.clone(),
+            cooling_buffer: self.cooling_buffer.clone(),
+            outpost_buffer: Vec::new(),
+        }
+    }
+}
+
 impl TerrainGrid {
+    /// Builds a grid from row-major cells, splitting them into chunks.
+    pub fn from_cells(width: u16, height: u16, cells: Vec<TerrainCell>) -> Self {
+        let chunk_len = (CHUNK_ROWS as usize * width as usize).max(1);
+        let chunks = cells
+            .chunks(chunk_len)
+            .enumerate()
+            .map(|(i, c)| {
+                Arc::new(TerrainChunk {
+                    origin_y: (i * CHUNK_ROWS as usize) as u16,
+                    cells: c.to_vec(),
+                })
+            })
+            .collect();
+        let size = width as usize * height as usize;
+        let mut grid = Self {
+            chunks,
+            width,
+            height,
+            dust_bowl_timer: 0,
+            water: Vec::new(),
+            is_dirty: true,
+            outpost_indices: HashSet::new(),
+            type_buffer: vec![TerrainType::Plains; size],
+            hydration_buffer: vec![false; size],
+            moisture_buffer: vec![0.5; size],
+            cooling_buffer: vec![0.0; size],
+            outpost_buffer: vec![false; size],
+        };
+        grid.seed_water();
+        grid
+    }
+
+    /// Rebuilds the buffers and outpost indices that saves leave out after
+    /// loading.
+    pub fn restore_buffers(&mut self) {
+        let size = self.width as usize * self.height as usize;
+        // The diffusion buffers are copied into every cell on each update,
+        // so the cells carry them through a save.
+        self.moisture_buffer = self.cells().map(|c| c.local_moisture).collect();
+        self.cooling_buffer = self.cells().map(|c| c.local_cooling).collect();
+        self.type_buffer = vec![TerrainType::Plains; size];
+        self.hydration_buffer = vec![false; size];
+        self.outpost_buffer = vec![false; size];
+        self.outpost_indices = self
+            .cells()
+            .enumerate()
+            .filter(|(_, c)| c.terrain_type == TerrainType::Outpost)
+            .map(|(idx, _)| idx)
+            .collect();
+        self.is_dirty = true;
+    }
+
     #[inline(always)]
     pub fn index(&self, x: u16, y: u16) -> usize {
         (y as usize * self.width as usize) + x as usize
     }
 
+    #[inline(always)]
+    fn locate(&self, idx: usize) -> (usize, usize) {
+        let chunk_len = CHUNK_ROWS as usize * self.width as usize;
+        (idx / chunk_len, idx % chunk_len)
+    }
+
+    /// Total number of cells.
+    pub fn len(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Cell at a row-major index.
+    #[inline]
+    pub fn cell(&self, idx: usize) -> &TerrainCell {
+        let (chunk, offset) = self.locate(idx);
+        &self.chunks[chunk].cells[offset]
+    }
+
+    /// Mutable cell at a row-major index. Copies the owning chunk first if it
+    /// is shared with a snapshot.
+    #[inline]
+    pub fn cell_mut(&mut self, idx: usize) -> &mut TerrainCell {
+        let (chunk, offset) = self.locate(idx);
+        &mut Arc::make_mut(&mut self.chunks[chunk]).cells[offset]
+    }
+
+    /// Iterates all cells in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = &TerrainCell> + '_ {
+        self.chunks.iter().flat_map(|c| c.cells.iter())
+    }
+
+    /// Iterates all cells mutably in row-major order, unsharing every chunk.
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut TerrainCell> + '_ {
+        self.chunks
+            .iter_mut()
+            .flat_map(|c| Arc::make_mut(c).cells.iter_mut())
+    }
+
+    /// The copy-on-write chunks backing this grid, top to bottom.
+    pub fn chunks(&self) -> &[Arc<TerrainChunk>] {
+        &self.chunks
+    }
+
+    /// Replaces the chunk at position `index` in [`TerrainGrid::chunks`].
+    pub fn replace_chunk(&mut self, index: usize, chunk: Arc<TerrainChunk>) {
+        if let Some(slot) = self.chunks.get_mut(index) {
+            *slot = chunk;
+            self.is_dirty = true;
+        }
+    }
+
+    /// Number of chunks physically shared with `other` (e.g. a snapshot).
+    pub fn shared_chunks(&self, other: &TerrainGrid) -> usize {
+        self.chunks
+            .iter()
+            .zip(&other.chunks)
+            .filter(|(a, b)| Arc::ptr_eq(a, b))
+            .count()
+    }
+
     pub fn get(&self, x: f64, y: f64) -> &TerrainCell {
         let ix = x.max(0.0).min(self.width as f64 - 1.0) as u16;
         let iy = y.max(0.0).min(self.height as f64 - 1.0) as u16;
-        &self.cells[self.index(ix, iy)]
+        self.cell(self.index(ix, iy))
     }
 
     pub fn movement_modifier(&self, x: f64, y: f64) -> f64 {
@@ -151,7 +349,7 @@ impl TerrainGrid {
     pub fn get_cell(&self, x: u16, y: u16) -> &TerrainCell {
         let ix = x.min(self.width.wrapping_sub(1));
         let iy = y.min(self.height.wrapping_sub(1));
-        &self.cells[self.index(ix, iy)]
+        self.cell(self.index(ix, iy))
     }
 
     pub fn sense_wall(&self, x: f64, y: f64, range: f64) -> f32 {
@@ -165,7 +363,7 @@ impl TerrainGrid {
                 let nx = ix + dx;
                 let ny = iy + dy;
                 if nx >= 0 && nx < self.width as i32 && ny >= 0 && ny < self.height as i32 {
-                    let cell = &self.cells[ny as usize * self.width as usize + nx as usize];
+                    let cell = self.cell(ny as usize * self.width as usize + nx as usize);
                     if cell.terrain_type == TerrainType::Wall {
                         let dist = ((dx * dx + dy * dy) as f64).sqrt();
                         if dist < min_dist {
@@ -183,15 +381,15 @@ impl TerrainGrid {
         let iy = y.min(self.height - 1);
         let idx = self.index(ix, iy);
 
-        if self.cells[idx].terrain_type == TerrainType::Outpost {
+        if self.cell(idx).terrain_type == TerrainType::Outpost {
             self.outpost_indices.remove(&idx);
-            self.cells[idx].energy_store = 0.0;
+            self.cell_mut(idx).energy_store = 0.0;
         }
         if t == TerrainType::Outpost {
             self.outpost_indices.insert(idx);
         }
 
-        self.cells[idx].terrain_type = t;
+        self.cell_mut(idx).terrain_type = t;
         self.is_dirty = true;
     }
 
@@ -199,24 +397,24 @@ impl TerrainGrid {
         let ix = x.min(self.width - 1);
         let iy = y.min(self.height - 1);
         let idx = self.index(ix, iy);
-        self.cells[idx].fertility = f.clamp(0.0, 1.0);
+        self.cell_mut(idx).fertility = f.clamp(0.0, 1.0);
         self.is_dirty = true;
     }
 
     pub fn average_fertility(&self) -> f32 {
-        if self.cells.is_empty() {
+        if self.is_empty() {
             return 0.0;
         }
-        let sum: f32 = self.cells.iter().map(|c| c.fertility).sum();
-        sum / self.cells.len() as f32
+        let sum: f32 = self.cells().map(|c| c.fertility).sum();
+        sum / self.len() as f32
     }
 
     pub fn add_global_fertility(&mut self, amount: f32) {
-        if self.cells.is_empty() {
+        if self.is_empty() {
             return;
         }
-        let per_cell = amount / self.cells.len() as f32;
-        for cell in &mut self.cells {
+        let per_cell = amount / self.len() as f32;
+        for cell in self.cells_mut() {
             cell.fertility = (cell.fertility + per_cell).clamp(0.0, 1.0);
         }
         self.is_dirty = true;
@@ -226,7 +424,8 @@ impl TerrainGrid {
         let ix = (x as u16).min(self.width - 1);
         let iy = (y as u16).min(self.height - 1);
         let idx = self.index(ix, iy);
-        self.cells[idx].fertility = (self.cells[idx].fertility - amount).max(0.0);
+        let cell = self.cell_mut(idx);
+        cell.fertility = (cell.fertility - amount).max(0.0);
         self.is_dirty = true;
     }
 
@@ -234,7 +433,8 @@ impl TerrainGrid {
         let ix = (x as u16).min(self.width - 1);
         let iy = (y as u16).min(self.height - 1);
         let idx = self.index(ix, iy);
-        self.cells[idx].fertility = (self.cells[idx].fertility + amount).min(1.0);
+        let cell = self.cell_mut(idx);
+        cell.fertility = (cell.fertility + amount).min(1.0);
         self.is_dirty = true;
     }
 
@@ -242,7 +442,7 @@ impl TerrainGrid {
         let ix = (x as u16).min(self.width - 1);
         let iy = (y as u16).min(self.height - 1);
         let idx = self.index(ix, iy);
-        self.cells[idx].biomass_accumulation += amount;
+        self.cell_mut(idx).biomass_accumulation += amount;
         self.is_dirty = true;
     }
 
@@ -262,7 +462,9 @@ impl TerrainGrid {
                     && nx < w
                     && ny >= 0
                     && ny < h
-                    && self.cells[(ny as usize * self.width as usize) + nx as usize].terrain_type
+                    && self
+                        .cell((ny as usize * self.width as usize) + nx as usize)
+                        .terrain_type
                         == t
                 {
                     return true;
@@ -272,3 +474,28 @@ impl TerrainGrid {
         false
     }
 }
+
+/// Stored layout of grids before the water layer, kept to read old
+/// snapshots.
+#[derive(Debug, Clone, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct LegacyTerrainGrid {
+    pub chunks: Vec<Arc<TerrainChunk>>,
+    pub width: u16,
+    pub height: u16,
+    pub dust_bowl_timer: u32,
+}
+
+impl From<LegacyTerrainGrid> for TerrainGrid {
+    fn from(legacy: LegacyTerrainGrid) -> Self {
+        let cells = legacy
+            .chunks
+            .iter()
+            .flat_map(|c| c.cells.iter().copied())
+            .collect();
+        let mut grid = TerrainGrid::from_cells(legacy.width, legacy.height, cells);
+        grid.dust_bowl_timer = legacy.dust_bowl_timer;
+        grid.restore_buffers();
+        grid
+    }
+}
@@ -2,16 +2,27 @@ use super::{TerrainGrid, TerrainType};
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use rayon::prelude::*;
+use std::sync::Arc;
 
 impl TerrainGrid {
-    pub fn update(&mut self, herbivore_biomass: f64, tick: u64, world_seed: u64) -> (f64, f64) {
+    /// Advances moisture, fertility and succession by one tick. Positive
+    /// fertility gains are scaled by `regrowth`, the season's
+    /// `fertility_regrowth`. Returns total plant biomass and forest carbon
+    /// sequestration.
+    pub fn update(
+        &mut self,
+        herbivore_biomass: f64,
+        regrowth: f64,
+        tick: u64,
+        world_seed: u64,
+    ) -> (f64, f64) {
         if self.is_dirty {
-            self.outpost_indices.clear();
-            for (i, c) in self.cells.iter().enumerate() {
-                if matches!(c.terrain_type, TerrainType::Outpost) {
-                    self.outpost_indices.insert(i);
-                }
-            }
+            self.outpost_indices = self
+                .cells()
+                .enumerate()
+                .filter(|(_, c)| matches!(c.terrain_type, TerrainType::Outpost))
+                .map(|(i, _)| i)
+                .collect();
             self.is_dirty = false;
         }
 
@@ -20,21 +31,25 @@ impl TerrainGrid {
         }
 
         let pressure = (herbivore_biomass / 5000.0) as f32;
+        let regrowth = regrowth.max(0.0);
         let global_recovery_rate = (0.001 - pressure).max(-0.01);
         let is_dust_bowl = self.dust_bowl_timer > 0;
 
         let w = self.width;
         let h = self.height;
 
-        if self.type_buffer.len() != w as usize * h as usize {
-            self.type_buffer = vec![TerrainType::Plains; w as usize * h as usize];
-            self.hydration_buffer = vec![false; w as usize * h as usize];
-            self.moisture_buffer = vec![0.5; w as usize * h as usize];
-            self.cooling_buffer = vec![0.0; w as usize * h as usize];
-            self.outpost_buffer = vec![false; w as usize * h as usize];
+        let size = w as usize * h as usize;
+        if self.moisture_buffer.len() != size || self.cooling_buffer.len() != size {
+            self.moisture_buffer = vec![0.5; size];
+            self.cooling_buffer = vec![0.0; size];
         }
+        // Scratch buffers are not carried across clones, so size them lazily.
+        self.type_buffer.resize(size, TerrainType::Plains);
+        self.hydration_buffer.resize(size, false);
+        self.outpost_buffer.resize(size, false);
 
-        for (i, cell) in self.cells.iter().enumerate() {
+        let cells = self.chunks.iter().flat_map(|c| c.cells.iter());
+        for (i, cell) in cells.enumerate() {
             self.type_buffer[i] = cell.terrain_type;
             self.hydration_buffer[i] = false;
             self.outpost_buffer[i] = false;
@@ -115,128 +130,105 @@ impl TerrainGrid {
         let cooling_map = &self.cooling_buffer;
         let outposts = &self.outpost_buffer;
 
-        type TransitionVec = Vec<Vec<(u16, u16, TerrainType)>>;
-        let (stats, transitions): (Vec<(f64, f64)>, TransitionVec) = self
-            .cells
-            .par_chunks_mut(w as usize)
-            .enumerate()
-            .map(|(y, row)| {
-                let mut row_biomass = 0.0;
-                let mut row_sequestration = 0.0;
-                let mut row_transitions = Vec::new();
-                let mut rng = ChaCha8Rng::seed_from_u64(world_seed ^ tick ^ (y as u64));
+        // Each chunk is updated independently from the read-only buffers above.
+        // Cells are worked out on copies first, and `make_mut` is only called
+        // on chunks where one actually changed, so chunks still shared with a
+        // snapshot are not copied just to write back the same values. Type
+        // transitions are collected and applied afterwards.
+        type RowResult = ((f64, f64), Vec<(u16, u16, TerrainType)>);
+        let rows: Vec<Vec<RowResult>> = self
+            .chunks
+            .par_iter_mut()
+            .map(|chunk| {
+                let origin_y = chunk.origin_y as usize;
+                let mut changed = Vec::new();
+                let rows = chunk
+                    .cells
+                    .chunks(w as usize)
+                    .enumerate()
+                    .map(|(row_offset, row)| {
+                        let y = origin_y + row_offset;
+                        let mut row_biomass = 0.0;
+                        let mut row_sequestration = 0.0;
+                        let mut row_transitions = Vec::new();
+                        let mut rng = ChaCha8Rng::seed_from_u64(world_seed ^ tick ^ (y as u64));
 
-                for (x, cell) in row.iter_mut().enumerate() {
-                    let idx = y * w as usize + x;
-                    cell.local_moisture = moisture_map[idx];
-                    cell.local_cooling = cooling_map[idx];
+                        for (x, &old) in row.iter().enumerate() {
+                            let mut cell = old;
+                            let idx = y * w as usize + x;
+                            cell.local_moisture = moisture_map[idx];
+                            cell.local_cooling = cooling_map[idx];
 
-                    let x_u16 = x as u16;
-                    let y_u16 = y as u16;
-                    row_biomass += cell.plant_biomass as f64;
+                            let x_u16 = x as u16;
+                            let y_u16 = y as u16;
+                            row_biomass += cell.plant_biomass as f64;
 
-                    let is_near_outpost = if cell.terrain_type == TerrainType::Forest {
-                        let mut found = false;
-                        for dy in -2..=2 {
-                            for dx in -2..=2 {
-                                let nx = x as i32 + dx;
-                                let ny = y as i32 + dy;
-                                if nx >= 0
-                                    && nx < w as i32
-                                    && ny >= 0
-                                    && ny < h as i32
-                                    && outposts[(ny as usize * w as usize) + nx as usize]
-                                {
-                                    found = true;
-                                    break;
+                            let is_near_outpost = if cell.terrain_type == TerrainType::Forest {
+                                let mut found = false;
+                                for dy in -2..=2 {
+                                    for dx in -2..=2 {
+                                        let nx = x as i32 + dx;
+                                        let ny = y as i32 + dy;
+                                        if nx >= 0
+                                            && nx < w as i32
+                                            && ny >= 0
+                                            && ny < h as i32
+                                            && outposts[(ny as usize * w as usize) + nx as usize]
+                                        {
+                                            found = true;
+                                            break;
+                                        }
+                                    }
+
+                                    if found {
+                                        break;
+                                    }
                                 }
-                            }
+                                found
+                            } else {
+                                false
+                            };
 
-                            if found {
-                                break;
+                            let seq_mult = if is_near_outpost { 2.5 } else { 1.0 };
+                            if cell.terrain_type == TerrainType::Forest {
+                                row_sequestration += cell.plant_biomass as f64
+                                    * seq_mult
+                                    * (1.0 + cell.local_moisture as f64);
                             }
-                        }
-                        found
-                    } else {
-                        false
-                    };
-
-                    let seq_mult = if is_near_outpost { 2.5 } else { 1.0 };
-                    if cell.terrain_type == TerrainType::Forest {
-                        row_sequestration += cell.plant_biomass as f64
-                            * seq_mult
-                            * (1.0 + cell.local_moisture as f64);
-                    }
-
-                    let mut fertility_gain =
-                        (global_recovery_rate + (cell.plant_biomass * 0.0001)).max(-0.05);
 
-                    fertility_gain += cell.local_moisture * 0.01;
+                            let mut fertility_gain =
+                                (global_recovery_rate + (cell.plant_biomass * 0.0001)).max(-0.05);
 
-                    if hydration_map[idx] {
-                        fertility_gain += 0.005;
-                    }
+                            fertility_gain += cell.local_moisture * 0.01;
 
-                    let plant_loss = if cell.terrain_type != TerrainType::Barren
-                        && cell.terrain_type != TerrainType::Desert
-                    {
-                        cell.plant_biomass * 0.00005
-                    } else {
-                        0.0
-                    };
-
-                    cell.fertility = (cell.fertility + fertility_gain - plant_loss).clamp(0.0, 1.0);
-
-                    cell.biomass_accumulation *= 0.999;
-                    if is_dust_bowl && cell.terrain_type == TerrainType::Plains {
-                        cell.fertility = (cell.fertility - 0.05).max(0.0);
-                    }
-
-                    if cell.energy_store > 1000.0 {
-                        cell.fertility *= 0.99;
-                    }
-
-                    let mut forest_neighbors = 0;
-                    for dy in -1..=1 {
-                        for dx in -1..=1 {
-                            if dx == 0 && dy == 0 {
-                                continue;
+                            if hydration_map[idx] {
+                                fertility_gain += 0.005;
                             }
-                            let nx = x as i32 + dx;
-                            let ny = y as i32 + dy;
-                            if nx >= 0
-                                && nx < w as i32
-                                && ny >= 0
-                                && ny < h as i32
-                                && type_grid[(ny as usize * w as usize) + nx as usize]
-                                    == TerrainType::Forest
-                            {
-                                forest_neighbors += 1;
+                            if fertility_gain > 0.0 {
+                                fertility_gain *= regrowth as f32;
                             }
-                        }
-                    }
 
-                    match cell.terrain_type {
-                        TerrainType::Plains => {
-                            let chance = 0.001 + (forest_neighbors as f64 * 0.01);
-                            if cell.plant_biomass > 60.0
-                                && cell.fertility > 0.6
-                                && rng.gen_bool(chance.min(1.0))
+                            let plant_loss = if cell.terrain_type != TerrainType::Barren
+                                && cell.terrain_type != TerrainType::Desert
                             {
-                                row_transitions.push((x_u16, y_u16, TerrainType::Forest));
-                            } else if cell.fertility < 0.05 {
-                                row_transitions.push((x_u16, y_u16, TerrainType::Desert));
-                            } else if cell.fertility < 0.15 {
-                                row_transitions.push((x_u16, y_u16, TerrainType::Barren));
+                                cell.plant_biomass * 0.00005
+                            } else {
+                                0.0
+                            };
+
+                            cell.fertility =
+                                (cell.fertility + fertility_gain - plant_loss).clamp(0.0, 1.0);
+
+                            cell.biomass_accumulation *= 0.999;
+                            if is_dust_bowl && cell.terrain_type == TerrainType::Plains {
+                                cell.fertility = (cell.fertility - 0.05).max(0.0);
                             }
-                        }
-                        TerrainType::Forest => {
-                            if cell.fertility < 0.3 || cell.plant_biomass < 20.0 {
-                                row_transitions.push((x_u16, y_u16, TerrainType::Plains));
+
+                            if cell.energy_store > 1000.0 {
+                                cell.fertility *= 0.99;
                             }
-                        }
-                        TerrainType::River => {
-                            let mut river_neighbors = 0;
+
+                            let mut forest_neighbors = 0;
                             for dy in -1..=1 {
                                 for dx in -1..=1 {
                                     if dx == 0 && dy == 0 {
@@ -249,32 +241,86 @@ impl TerrainGrid {
                                         && ny >= 0
                                         && ny < h as i32
                                         && type_grid[(ny as usize * w as usize) + nx as usize]
-                                            == TerrainType::River
+                                            == TerrainType::Forest
                                     {
-                                        river_neighbors += 1;
+                                        forest_neighbors += 1;
                                     }
                                 }
                             }
-                            if river_neighbors == 0 && cell.fertility < 0.2 && rng.gen_bool(0.01) {
-                                row_transitions.push((x_u16, y_u16, TerrainType::Plains));
-                            }
-                        }
-                        TerrainType::Desert => {
-                            if cell.fertility > 0.3 {
-                                row_transitions.push((x_u16, y_u16, TerrainType::Plains));
+
+                            match cell.terrain_type {
+                                TerrainType::Plains => {
+                                    let chance = 0.001 + (forest_neighbors as f64 * 0.01);
+                                    if cell.plant_biomass > 60.0
+                                        && cell.fertility > 0.6
+                                        && rng.gen_bool(chance.min(1.0))
+                                    {
+                                        row_transitions.push((x_u16, y_u16, TerrainType::Forest));
+                                    } else if cell.fertility < 0.05 {
+                                        row_transitions.push((x_u16, y_u16, TerrainType::Desert));
+                                    } else if cell.fertility < 0.15 {
+                                        row_transitions.push((x_u16, y_u16, TerrainType::Barren));
+                                    }
+                                }
+                                TerrainType::Forest
+                                    if (cell.fertility < 0.3 || cell.plant_biomass < 20.0) =>
+                                {
+                                    row_transitions.push((x_u16, y_u16, TerrainType::Plains));
+                                }
+                                TerrainType::River => {
+                                    let mut river_neighbors = 0;
+                                    for dy in -1..=1 {
+                                        for dx in -1..=1 {
+                                            if dx == 0 && dy == 0 {
+                                                continue;
+                                            }
+                                            let nx = x as i32 + dx;
+                                            let ny = y as i32 + dy;
+                                            if nx >= 0
+                                                && nx < w as i32
+                                                && ny >= 0
+                                                && ny < h as i32
+                                                && type_grid
+                                                    [(ny as usize * w as usize) + nx as usize]
+                                                    == TerrainType::River
+                                            {
+                                                river_neighbors += 1;
+                                            }
+                                        }
+                                    }
+                                    if river_neighbors == 0
+                                        && cell.fertility < 0.2
+                                        && rng.gen_bool(0.01)
+                                    {
+                                        row_transitions.push((x_u16, y_u16, TerrainType::Plains));
+                                    }
+                                }
+                                TerrainType::Desert if cell.fertility > 0.3 => {
+                                    row_transitions.push((x_u16, y_u16, TerrainType::Plains));
+                                }
+                                TerrainType::Barren if cell.fertility > 0.4 => {
+                                    row_transitions.push((x_u16, y_u16, cell.original_type));
+                                }
+                                _ => {}
                             }
-                        }
-                        TerrainType::Barren => {
-                            if cell.fertility > 0.4 {
-                                row_transitions.push((x_u16, y_u16, cell.original_type));
+
+                            if cell != old {
+                                changed.push((row_offset * w as usize + x, cell));
                             }
                         }
-                        _ => {}
+                        ((row_biomass, row_sequestration), row_transitions)
+                    })
+                    .collect();
+                if !changed.is_empty() {
+                    let cells = &mut Arc::make_mut(chunk).cells;
+                    for (i, cell) in changed {
+                        cells[i] = cell;
                     }
                 }
-                ((row_biomass, row_sequestration), row_transitions)
+                rows
             })
-            .unzip();
+            .collect();
+        let (stats, transitions): (Vec<(f64, f64)>, Vec<_>) = rows.into_iter().flatten().unzip();
 
         for row_list in transitions {
             for (x, y, t) in row_list {
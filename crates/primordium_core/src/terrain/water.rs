@@ -0,0 +1,149 @@
+use super::{TerrainGrid, TerrainType};
+use crate::config::HydrationConfig;
+
+impl TerrainGrid {
+    /// Standing water a cell of `terrain` starts with: full for rivers and
+    /// oases, none on bare rock and sand, otherwise its moisture.
+    fn initial_water(terrain: TerrainType, moisture: f32) -> f32 {
+        match terrain {
+            TerrainType::River | TerrainType::Oasis => 1.0,
+            TerrainType::Desert | TerrainType::Mountain | TerrainType::Wall => 0.0,
+            _ => moisture.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Fills the water layer from the terrain, discarding what it held.
+    pub fn seed_water(&mut self) {
+        self.water = self
+            .cells()
+            .map(|c| Self::initial_water(c.terrain_type, c.local_moisture))
+            .collect();
+    }
+
+    /// Advances the water layer by one tick: rivers and oases refill,
+    /// deserts drain and every other cell seeps towards its moisture.
+    pub fn update_water(&mut self, config: &HydrationConfig) {
+        if self.water.len() != self.len() {
+            self.seed_water();
+        }
+        let cells = self.chunks.iter().flat_map(|c| c.cells.iter());
+        for (water, cell) in self.water.iter_mut().zip(cells) {
+            *water = match cell.terrain_type {
+                TerrainType::River | TerrainType::Oasis => 1.0,
+                TerrainType::Desert => *water - config.desert_drain,
+                TerrainType::Wall => 0.0,
+                _ => *water + (cell.local_moisture - *water) * config.seepage,
+            }
+            .clamp(0.0, 1.0);
+        }
+    }
+
+    /// The water layer, row-major.
+    pub fn water(&self) -> &[f32] {
+        &self.water
+    }
+
+    /// Replaces the water layer; a layer of the wrong size is reseeded on
+    /// the next update.
+    pub fn set_water(&mut self, water: Vec<f32>) {
+        self.water = water;
+    }
+
+    /// Water standing at `(x, y)`, 0.0-1.0.
+    pub fn water_at(&self, x: f64, y: f64) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let ix = x.max(0.0).min(self.width as f64 - 1.0) as u16;
+        let iy = y.max(0.0).min(self.height as f64 - 1.0) as u16;
+        self.water.get(self.index(ix, iy)).copied().unwrap_or(0.0)
+    }
+
+    /// Takes up to `amount` of water from `(x, y)` and returns how much was
+    /// taken. Rivers and oases are never depleted.
+    pub fn drink(&mut self, x: f64, y: f64, amount: f32) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let ix = x.max(0.0).min(self.width as f64 - 1.0) as u16;
+        let iy = y.max(0.0).min(self.height as f64 - 1.0) as u16;
+        let idx = self.index(ix, iy);
+        let endless = matches!(
+            self.cell(idx).terrain_type,
+            TerrainType::River | TerrainType::Oasis
+        );
+        let Some(water) = self.water.get_mut(idx) else {
+            return 0.0;
+        };
+        let taken = amount.clamp(0.0, *water);
+        if !endless {
+            *water -= taken;
+        }
+        taken
+    }
+
+    /// Proximity of the nearest cell holding at least `min_water`, 1.0 when
+    /// standing on one and 0.0 when none is within `range`.
+    pub fn sense_water(&self, x: f64, y: f64, range: f64, min_water: f32) -> f32 {
+        let mut min_dist = range;
+        let ix = x as i32;
+        let iy = y as i32;
+        let r = range as i32;
+
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let nx = ix + dx;
+                let ny = iy + dy;
+                if nx >= 0 && nx < self.width as i32 && ny >= 0 && ny < self.height as i32 {
+                    let idx = ny as usize * self.width as usize + nx as usize;
+                    if self.water.get(idx).is_some_and(|&w| w >= min_water) {
+                        let dist = ((dx * dx + dy * dy) as f64).sqrt();
+                        if dist < min_dist {
+                            min_dist = dist;
+                        }
+                    }
+                }
+            }
+        }
+        (1.0 - (min_dist / range)).clamp(0.0, 1.0) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::TerrainCell;
+
+    fn strip(types: &[TerrainType]) -> TerrainGrid {
+        let cells = types
+            .iter()
+            .map(|&t| TerrainCell {
+                terrain_type: t,
+                original_type: t,
+                ..TerrainCell::default()
+            })
+            .collect();
+        TerrainGrid::from_cells(types.len() as u16, 1, cells)
+    }
+
+    #[test]
+    fn test_rivers_refill_and_deserts_drain() {
+        let mut grid = strip(&[TerrainType::River, TerrainType::Plains, TerrainType::Desert]);
+        let config = HydrationConfig::default();
+        grid.water = vec![0.0, 0.0, 0.5];
+        grid.update_water(&config);
+
+        assert_eq!(grid.water_at(0.0, 0.0), 1.0);
+        assert!(grid.water_at(1.0, 0.0) > 0.0);
+        assert!(grid.water_at(2.0, 0.0) < 0.5);
+
+        assert_eq!(grid.drink(0.0, 0.0, 0.3), 0.3);
+        assert_eq!(grid.water_at(0.0, 0.0), 1.0);
+        let before = grid.water_at(1.0, 0.0);
+        assert_eq!(grid.drink(1.0, 0.0, 1.0), before);
+        assert_eq!(grid.water_at(1.0, 0.0), 0.0);
+
+        assert_eq!(grid.sense_water(0.0, 0.0, 5.0, 0.2), 1.0);
+        assert!((grid.sense_water(2.0, 0.0, 5.0, 0.5) - 0.6).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,138 @@
+//! Worker thread pools for simulation and background work.
+//!
+//! The simulation runs inside a dedicated rayon pool sized by
+//! [`ThreadingConfig::sim_threads`], so every `par_iter` in the systems is
+//! bounded by it. The global rayon pool is reserved for background tasks (IO,
+//! networking, history queries) and sized by [`ThreadingConfig::io_threads`].
+//! Both are built once at startup; the resulting sizes are published as a
+//! [`PoolReport`].
+
+use crate::config::ThreadingConfig;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Effective pool sizes after startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PoolReport {
+    pub sim_threads: usize,
+    pub io_threads: usize,
+    /// Number of simulation workers successfully pinned to a core.
+    pub pinned: usize,
+}
+
+/// The simulation pool plus a report of both pools.
+pub struct ThreadPools {
+    sim: rayon::ThreadPool,
+    report: PoolReport,
+    pinned: Arc<AtomicUsize>,
+}
+
+impl ThreadPools {
+    /// Builds the simulation pool and sizes the global (IO) pool.
+    ///
+    /// The global pool can only be configured once per process; if it is
+    /// already running, its current size is reported instead.
+    pub fn build(config: &ThreadingConfig) -> anyhow::Result<Self> {
+        let cores = available_cores();
+        let sim_threads = if config.sim_threads == 0 {
+            cores
+        } else {
+            config.sim_threads
+        };
+
+        let _ = rayon::ThreadPoolBuilder::new()
+            .num_threads(config.io_threads)
+            .thread_name(|i| format!("primordium-io-{i}"))
+            .build_global();
+
+        let pinned = Arc::new(AtomicUsize::new(0));
+        let mut builder = rayon::ThreadPoolBuilder::new()
+            .num_threads(sim_threads)
+            .thread_name(|i| format!("primordium-sim-{i}"));
+        if config.pin_workers {
+            let first_core = config.first_core;
+            let pinned = pinned.clone();
+            builder = builder.start_handler(move |i| {
+                if pin_current_thread((first_core + i) % cores) {
+                    pinned.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+        let sim = builder.build()?;
+
+        let report = PoolReport {
+            sim_threads: sim.current_num_threads(),
+            io_threads: rayon::current_num_threads(),
+            pinned: 0,
+        };
+        Ok(Self {
+            sim,
+            report,
+            pinned,
+        })
+    }
+
+    /// Runs `op` inside the simulation pool.
+    pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        self.sim.install(op)
+    }
+
+    /// Effective pool sizes. Workers pin themselves lazily on start-up, so
+    /// `pinned` may grow shortly after the pools are built.
+    pub fn report(&self) -> PoolReport {
+        PoolReport {
+            pinned: self.pinned.load(Ordering::Relaxed),
+            ..self.report
+        }
+    }
+}
+
+/// Number of logical cores available to this process.
+pub fn available_cores() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Pins the calling thread to `core`. Returns `false` if pinning failed or is
+/// unsupported on this platform.
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(core: usize) -> bool {
+    // SAFETY: `cpu_set_t` is a plain bitmask for which all-zeroes is a valid
+    // value, and `sched_setaffinity(0, ..)` only affects the calling thread.
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_core: usize) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sim_pool_uses_configured_threads() {
+        let config = ThreadingConfig {
+            sim_threads: 3,
+            ..Default::default()
+        };
+        let pools = ThreadPools::build(&config).unwrap();
+        assert_eq!(pools.report().sim_threads, 3);
+        assert_eq!(pools.install(rayon::current_num_threads), 3);
+    }
+
+    #[test]
+    fn test_zero_sim_threads_uses_all_cores() {
+        let pools = ThreadPools::build(&ThreadingConfig::default()).unwrap();
+        assert_eq!(pools.report().sim_threads, available_cores());
+        assert!(pools.report().io_threads > 0);
+    }
+}
@@ -0,0 +1,70 @@
+//! Compact binary encoding of genotypes.
+//!
+//! DNA strings travel through the clipboard, `.dna` files, migrations and
+//! marketplace offers. They used to be the hex of the genotype's JSON,
+//! which spells out every field name of every node and connection. The
+//! compact form is a two-byte header, a magic byte and the format version,
+//! followed by the genotype as zstd-compressed CBOR. CBOR keeps field names
+//! like JSON does, so genes added later with `#[serde(default)]` still
+//! decode from older strings, and zstd folds the repeated names away.
+//!
+//! Positional formats such as postcard or bincode would be a little
+//! smaller, but they drop the field names: every new gene would break every
+//! DNA string already shared unless it came with a format version bump and
+//! a migration, and genes are added far more often than that is worth.
+//!
+//! [`decode`] also accepts the old JSON bytes: JSON always opens with `{`,
+//! which is never the magic byte.
+
+use super::genotype::Genotype;
+use std::io::Read;
+
+/// First byte of every compact DNA payload.
+pub const MAGIC: u8 = 0xD7;
+
+/// Current version of the compact format.
+pub const VERSION: u8 = 1;
+
+/// zstd level used for DNA. Genotypes are a few kilobytes, so the slowest
+/// levels still take well under a millisecond.
+const LEVEL: i32 = 19;
+
+/// Largest decompressed payload accepted. DNA arrives from relays, peers
+/// and migrations, and a few hundred kilobytes of compressed zeros would
+/// otherwise expand to gigabytes. Genotypes at the brain size limits are
+/// tens of kilobytes.
+pub const MAX_DNA_BYTES: usize = 1 << 20;
+
+/// Encodes `genotype` in the current compact format.
+pub fn encode(genotype: &Genotype) -> anyhow::Result<Vec<u8>> {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(genotype, &mut cbor)?;
+    let mut bytes = vec![MAGIC, VERSION];
+    bytes.extend(zstd::bulk::compress(&cbor, LEVEL)?);
+    Ok(bytes)
+}
+
+/// Decodes DNA in the compact format or the legacy JSON one.
+pub fn decode(bytes: &[u8]) -> anyhow::Result<Genotype> {
+    match bytes {
+        [] => anyhow::bail!("Empty DNA"),
+        [MAGIC, VERSION, payload @ ..] => {
+            let mut cbor = Vec::new();
+            zstd::Decoder::new(payload)?
+                .take(MAX_DNA_BYTES as u64 + 1)
+                .read_to_end(&mut cbor)?;
+            anyhow::ensure!(
+                cbor.len() <= MAX_DNA_BYTES,
+                "DNA expands past {} bytes",
+                MAX_DNA_BYTES
+            );
+            Ok(ciborium::from_reader(cbor.as_slice())?)
+        }
+        [MAGIC, version, ..] => anyhow::bail!(
+            "DNA format v{} is newer than this build supports (v{})",
+            version,
+            VERSION
+        ),
+        _ => Ok(serde_json::from_slice(bytes)?),
+    }
+}
@@ -1,8 +1,9 @@
 use super::environment::AncestralTrait;
-use super::genotype::{Activations, Genotype, Specialization};
+use super::genotype::{Activations, Genotype, LegacyGenotype, Specialization};
+use super::status::StatusEffect;
 use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use uuid::Uuid;
 
 /// World position of an entity.
@@ -110,8 +111,8 @@ pub struct Metabolism {
     pub trophic_potential: f32,
     /// Current energy level.
     pub energy: f64,
-    /// Previous energy level (not serialized).
-    #[serde(skip)]
+    /// Energy level before the current tick.
+    #[serde(default)]
     pub prev_energy: f64,
     /// Maximum energy capacity.
     pub max_energy: f64,
@@ -133,6 +134,13 @@ pub struct Metabolism {
     /// Migration batch identifier.
     #[serde(default)]
     pub migration_id: Option<Uuid>,
+    /// Water reserve (0.0-1.0); the organism dies of dehydration at 0.0.
+    #[serde(default = "full_hydration")]
+    pub hydration: f32,
+}
+
+fn full_hydration() -> f32 {
+    1.0
 }
 
 /// Pathogen state for infection simulation.
@@ -157,12 +165,38 @@ pub struct Pathogen {
 #[derive(Clone, Debug, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize)]
 #[archive(check_bytes)]
 pub struct Health {
-    /// Active pathogen infection, if any.
+    /// Active pathogen infection, if any. Its course is tracked by the
+    /// matching [`super::status::StatusKind::Infection`] effect.
     pub pathogen: Option<Pathogen>,
-    /// Time remaining in current infection.
-    pub infection_timer: u32,
     /// Immunity level (0.0-1.0).
     pub immunity: f32,
+    /// Active buffs and debuffs.
+    #[serde(default)]
+    pub effects: Vec<StatusEffect>,
+}
+
+/// A remembered episode: where the organism stood and who it was bonded to.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MemorySlot {
+    pub x: f64,
+    pub y: f64,
+    /// Bonded partner at the time of writing.
+    pub partner: Option<Uuid>,
+    /// Tick the memory was written.
+    pub tick: u64,
+}
+
+/// Addressable short-term memory written and read through the brain.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EpisodicMemory {
+    /// Memory slots, `None` until written or once faded.
+    pub slots: Vec<Option<MemorySlot>>,
+    /// Slot addressed by the last `MemAddr` output.
+    pub cursor: usize,
+    /// Memories written this life.
+    pub writes: u32,
+    /// Ticks on which a stored memory was recalled.
+    pub recalls: u32,
 }
 
 /// The cognitive state of an organism.
@@ -173,58 +207,71 @@ pub struct Health {
 pub struct Intel {
     /// Neural network genotype.
     pub genotype: std::sync::Arc<Genotype>,
-    /// Hidden layer values from previous tick (not serialized).
-    #[serde(skip)]
+    /// Hidden layer values from previous tick.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub last_hidden: [f32; 6],
-    /// Last aggression output (not serialized).
-    #[serde(skip)]
+    /// Last aggression output.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub last_aggression: f32,
-    /// Last share intent output (not serialized).
-    #[serde(skip)]
+    /// Last share intent output.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub last_share_intent: f32,
-    /// Last signal output (not serialized).
-    #[serde(skip)]
+    /// Last signal output.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub last_signal: f32,
-    /// Last vocalization output (not serialized).
-    #[serde(skip)]
+    /// Last vocalization output.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub last_vocalization: f32,
-    /// Social reputation score (not serialized).
-    #[serde(skip)]
+    /// Social reputation score.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub reputation: f32,
-    /// Social rank (not serialized).
-    #[serde(skip)]
+    /// Social rank.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub rank: f32,
-    /// Bonded partner ID (not serialized).
-    #[serde(skip)]
+    /// Bonded partner ID.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub bonded_to: Option<Uuid>,
-    /// Last neural network inputs (not serialized).
-    #[serde(skip)]
+    /// Last neural network inputs.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
-    pub last_inputs: [f32; 29],
-    /// Last neural network activations (not serialized).
-    #[serde(skip)]
+    pub last_inputs: Vec<f32>,
+    /// Last neural network activations.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub last_activations: Activations,
-    /// Current caste specialization (not serialized).
-    #[serde(skip)]
+    /// Current caste specialization.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub specialization: Option<Specialization>,
-    /// Specialization progress meters (not serialized).
-    #[serde(skip)]
+    /// Specialization progress meters.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub spec_meters: HashMap<Specialization, f32>,
-    /// Inherited ancestral traits (not serialized).
-    #[serde(skip)]
+    /// Inherited ancestral traits.
+    #[serde(default)]
     #[with(rkyv::with::Skip)]
     pub ancestral_traits: HashSet<AncestralTrait>,
+    /// Episodic memory slots.
+    #[serde(default)]
+    #[with(rkyv::with::Skip)]
+    pub memory: EpisodicMemory,
+    /// Deceptive food trails laid so far.
+    #[serde(default)]
+    #[with(rkyv::with::Skip)]
+    pub deceptions: u32,
+    /// Standing in the lineage's dominance ladder, 1.0 for its alpha and
+    /// 0.0 when unranked.
+    #[serde(default)]
+    #[with(rkyv::with::Skip)]
+    pub dominance: f32,
 }
 
 /// A complete organism entity.
@@ -250,6 +297,130 @@ pub struct Entity {
     pub intel: Intel,
 }
 
+/// Archived layout of [`Intel`] before the body size gene, kept to read old
+/// snapshots. Only the genotype is archived.
+#[derive(Clone, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct LegacyIntel {
+    pub genotype: std::sync::Arc<LegacyGenotype>,
+}
+
+/// Archived layout of [`Entity`] before the body size gene, kept to read old
+/// snapshots.
+#[derive(Clone, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct LegacyEntity {
+    pub identity: Identity,
+    pub position: Position,
+    pub velocity: Velocity,
+    pub appearance: Appearance,
+    pub physics: Physics,
+    pub metabolism: Metabolism,
+    pub health: Health,
+    pub intel: LegacyIntel,
+}
+
+impl From<LegacyEntity> for Entity {
+    fn from(legacy: LegacyEntity) -> Self {
+        let genotype = std::sync::Arc::unwrap_or_clone(legacy.intel.genotype);
+        Self {
+            identity: legacy.identity,
+            position: legacy.position,
+            velocity: legacy.velocity,
+            appearance: legacy.appearance,
+            physics: legacy.physics,
+            metabolism: legacy.metabolism,
+            health: legacy.health,
+            intel: Intel {
+                genotype: std::sync::Arc::new(genotype.into()),
+                last_hidden: Default::default(),
+                last_aggression: 0.0,
+                last_share_intent: 0.0,
+                last_signal: 0.0,
+                last_vocalization: 0.0,
+                reputation: 0.0,
+                rank: 0.0,
+                bonded_to: None,
+                last_inputs: Vec::new(),
+                last_activations: Activations::default(),
+                specialization: None,
+                spec_meters: HashMap::new(),
+                ancestral_traits: HashSet::new(),
+                memory: EpisodicMemory::default(),
+                deceptions: 0,
+                dominance: 0.0,
+            },
+        }
+    }
+}
+
+/// An incubating egg.
+///
+/// The hatchling is formed when the egg is laid and spawned once
+/// `incubation` reaches the configured incubation time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Egg {
+    /// Entity that hatches from the egg.
+    pub hatchling: Box<Entity>,
+    /// Tick the egg was laid.
+    pub laid_tick: u64,
+    /// Incubation accumulated so far, in ticks at the base rate.
+    pub incubation: f32,
+}
+
+/// Who attached a tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TagSource {
+    /// Set by hand, e.g. "protagonist".
+    User,
+    /// Set by the simulation or an experiment, e.g. "study-group-A".
+    System,
+}
+
+/// Labels attached to an entity.
+///
+/// Only entities with at least one tag carry this component. Tags are not
+/// inherited by offspring.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tags {
+    #[serde(default)]
+    pub user: BTreeSet<String>,
+    #[serde(default)]
+    pub system: BTreeSet<String>,
+}
+
+impl Tags {
+    pub fn insert(&mut self, tag: &str, source: TagSource) -> bool {
+        match source {
+            TagSource::User => self.user.insert(tag.to_string()),
+            TagSource::System => self.system.insert(tag.to_string()),
+        }
+    }
+
+    /// Removes `tag` whatever its source.
+    pub fn remove(&mut self, tag: &str) -> bool {
+        let user = self.user.remove(tag);
+        let system = self.system.remove(tag);
+        user || system
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.user.contains(tag) || self.system.contains(tag)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.user.is_empty() && self.system.is_empty()
+    }
+
+    /// User tags followed by system tags, each in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.user
+            .iter()
+            .chain(self.system.iter())
+            .map(String::as_str)
+    }
+}
+
 /// Enumeration of possible entity life stages and states.
 #[derive(
     Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize,
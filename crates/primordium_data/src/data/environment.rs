@@ -1,5 +1,6 @@
-use super::entity::Entity;
+use super::entity::{Entity, LegacyEntity};
 use super::genotype::Genotype;
+use super::provenance::RunManifest;
 use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
@@ -52,6 +53,40 @@ pub enum LineageGoal {
     Resilience,
 }
 
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Archive,
+    RkyvSerialize,
+    RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+/// Mean interaction commands issued per tick over the recorder's rolling window.
+pub struct InteractionRates {
+    /// Predation attempts.
+    pub kills: f32,
+    /// Energy shared with another entity.
+    pub shares: f32,
+    /// Offspring produced.
+    pub births: f32,
+    /// Food items eaten.
+    pub eats: f32,
+    /// Structures built (nests and outposts).
+    pub builds: f32,
+    /// Terrain dug.
+    pub digs: f32,
+    /// Pair bonds formed.
+    pub bonds: f32,
+    /// Infected neighbours groomed.
+    #[serde(default)]
+    pub grooms: f32,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Archive, RkyvSerialize, RkyvDeserialize)]
 #[archive(check_bytes)]
 /// Aggregated macro-level statistics for the entire simulation population.
@@ -82,7 +117,8 @@ pub struct PopulationStats {
     pub biodiversity_hotspots: usize,
     /// Population-aware mutation multiplier (0.5×–3.0×).
     pub mutation_scale: f32,
-    /// Rate of phenotypic change across generations.
+    /// Mean genetic distance of newborns from their parents, weighted by
+    /// how recent the birth is (see the mutation heat map).
     pub evolutionary_velocity: f32,
     /// Average soil fertility across all terrain cells.
     pub global_fertility: f32,
@@ -90,8 +126,50 @@ pub struct PopulationStats {
     pub max_generation: u32,
     /// Rolling window of recent death ages for mortality analysis.
     pub recent_deaths: VecDeque<f64>,
-    /// Rolling window of recent migration distances.
+    /// Genetic distances of the last 100 newborns from their parents.
     pub recent_distances: VecDeque<f32>,
+    /// Rolling interaction rates; zero unless `analytics.record_interactions` is enabled.
+    #[serde(default)]
+    pub interaction_rates: InteractionRates,
+    /// Mean node count of living entities' brains.
+    #[serde(default)]
+    pub avg_brain_nodes: f64,
+    /// Mean connection count of living entities' brains, disabled ones included.
+    #[serde(default)]
+    pub avg_brain_connections: f64,
+    /// Fraction of living entities that have written an episodic memory.
+    #[serde(default)]
+    pub memory_writers: f64,
+    /// Fraction of living entities' lifetimes spent recalling a stored memory.
+    #[serde(default)]
+    pub memory_recall_rate: f64,
+    /// Per-lineage fraction of members that have written an episodic memory.
+    #[serde(default)]
+    pub lineage_memory_writers: HashMap<Uuid, f64>,
+    /// Fraction of living entities' lifetimes spent laying deceptive trails.
+    #[serde(default)]
+    pub deception_rate: f64,
+    /// Per-lineage fraction of members' lifetimes spent laying deceptive trails.
+    #[serde(default)]
+    pub lineage_deception_rate: HashMap<Uuid, f64>,
+    /// Mean skepticism gene of living entities.
+    #[serde(default)]
+    pub avg_skepticism: f64,
+    /// Per-lineage number of members ranked in its dominance ladder.
+    #[serde(default)]
+    pub lineage_hierarchy_depth: HashMap<Uuid, usize>,
+    /// Deepest dominance ladder of any lineage.
+    #[serde(default)]
+    pub max_hierarchy_depth: usize,
+    /// Mean death avoidance gene of living entities.
+    #[serde(default)]
+    pub avg_death_avoidance: f64,
+    /// Per-lineage mean death avoidance gene.
+    #[serde(default)]
+    pub lineage_death_avoidance: HashMap<Uuid, f64>,
+    /// Living entities per biome, by biome name.
+    #[serde(default)]
+    pub biome_population: HashMap<String, usize>,
 }
 
 impl Default for PopulationStats {
@@ -115,6 +193,20 @@ impl Default for PopulationStats {
             max_generation: 0,
             recent_deaths: VecDeque::with_capacity(100),
             recent_distances: VecDeque::with_capacity(100),
+            interaction_rates: InteractionRates::default(),
+            avg_brain_nodes: 0.0,
+            avg_brain_connections: 0.0,
+            memory_writers: 0.0,
+            memory_recall_rate: 0.0,
+            lineage_memory_writers: HashMap::new(),
+            deception_rate: 0.0,
+            lineage_deception_rate: HashMap::new(),
+            avg_skepticism: 0.0,
+            lineage_hierarchy_depth: HashMap::new(),
+            max_hierarchy_depth: 0,
+            avg_death_avoidance: 0.0,
+            lineage_death_avoidance: HashMap::new(),
+            biome_population: HashMap::new(),
         }
     }
 }
@@ -161,6 +253,36 @@ pub enum LiveEvent {
         tick: u64,
         timestamp: String,
     },
+    /// Two lineages went to war.
+    WarDeclared {
+        lineage_a: Uuid,
+        lineage_b: Uuid,
+        tick: u64,
+        timestamp: String,
+    },
+    /// Two lineages at war made peace, or two at peace became allies.
+    PeaceTreaty {
+        lineage_a: Uuid,
+        lineage_b: Uuid,
+        alliance: bool,
+        tick: u64,
+        timestamp: String,
+    },
+    /// A lineage raised a monument.
+    MonumentRaised {
+        lineage_id: Uuid,
+        x: u16,
+        y: u16,
+        tick: u64,
+        timestamp: String,
+    },
+    /// The season cycle moved on (e.g. Summer → Fall).
+    SeasonChanged {
+        from: String,
+        to: String,
+        tick: u64,
+        timestamp: String,
+    },
     /// Global climate state transitioned (e.g. Temperate → Warm).
     ClimateShift {
         from: String,
@@ -183,7 +305,16 @@ pub enum LiveEvent {
     /// Periodic macro-state snapshot for history browsing.
     Snapshot {
         tick: u64,
-        stats: PopulationStats,
+        stats: Box<PopulationStats>,
+        timestamp: String,
+    },
+    /// A guardrail circuit breaker halted the simulation.
+    CircuitBreaker {
+        breaker: String,
+        observed: usize,
+        limit: usize,
+        suspected_source: String,
+        tick: u64,
         timestamp: String,
     },
     /// AI narrator commentary on current world state.
@@ -192,7 +323,238 @@ pub enum LiveEvent {
         text: String,
         severity: f32,
         timestamp: String,
+        /// The data the narration was drawn from.
+        #[serde(default)]
+        refs: NarrationRefs,
     },
+    /// The user marked a tick on the timeline.
+    Bookmark {
+        tick: u64,
+        note: String,
+        timestamp: String,
+    },
+    /// A run started or resumed; logged first so the log is attributable.
+    Provenance {
+        tick: u64,
+        provenance: RunManifest,
+        timestamp: String,
+    },
+}
+
+impl LiveEvent {
+    /// Tick the event happened at.
+    pub fn tick(&self) -> u64 {
+        match self {
+            LiveEvent::Birth { tick, .. }
+            | LiveEvent::Death { tick, .. }
+            | LiveEvent::Metamorphosis { tick, .. }
+            | LiveEvent::TribalSplit { tick, .. }
+            | LiveEvent::WarDeclared { tick, .. }
+            | LiveEvent::PeaceTreaty { tick, .. }
+            | LiveEvent::MonumentRaised { tick, .. }
+            | LiveEvent::SeasonChanged { tick, .. }
+            | LiveEvent::ClimateShift { tick, .. }
+            | LiveEvent::Extinction { tick, .. }
+            | LiveEvent::EcoAlert { tick, .. }
+            | LiveEvent::Snapshot { tick, .. }
+            | LiveEvent::CircuitBreaker { tick, .. }
+            | LiveEvent::Narration { tick, .. }
+            | LiveEvent::Bookmark { tick, .. }
+            | LiveEvent::Provenance { tick, .. } => *tick,
+        }
+    }
+
+    /// The `event` tag the event is logged under.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LiveEvent::Birth { .. } => "Birth",
+            LiveEvent::Death { .. } => "Death",
+            LiveEvent::Metamorphosis { .. } => "Metamorphosis",
+            LiveEvent::TribalSplit { .. } => "TribalSplit",
+            LiveEvent::WarDeclared { .. } => "WarDeclared",
+            LiveEvent::PeaceTreaty { .. } => "PeaceTreaty",
+            LiveEvent::MonumentRaised { .. } => "MonumentRaised",
+            LiveEvent::SeasonChanged { .. } => "SeasonChanged",
+            LiveEvent::ClimateShift { .. } => "ClimateShift",
+            LiveEvent::Extinction { .. } => "Extinction",
+            LiveEvent::EcoAlert { .. } => "EcoAlert",
+            LiveEvent::Snapshot { .. } => "Snapshot",
+            LiveEvent::CircuitBreaker { .. } => "CircuitBreaker",
+            LiveEvent::Narration { .. } => "Narration",
+            LiveEvent::Bookmark { .. } => "Bookmark",
+            LiveEvent::Provenance { .. } => "Provenance",
+        }
+    }
+}
+
+/// A single narrative entry describing a simulation event.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Narration {
+    /// The simulation tick when this event occurred.
+    pub tick: u64,
+    /// The category or type of event (e.g., "ExtinctionEvent", "NewEra").
+    pub event_type: String,
+    /// The human-readable narrative text describing the event.
+    pub text: String,
+    /// The severity or importance of the event (0.0 to 1.0).
+    pub severity: f32,
+}
+
+/// Which narrations a subscriber to the scribe receives.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct NarrationFilter {
+    /// Narrations less severe than this are skipped.
+    pub min_severity: f32,
+    /// Event types to receive; every type when empty.
+    pub event_types: Vec<String>,
+}
+
+impl NarrationFilter {
+    /// Every narration at least as severe as `min_severity`.
+    pub fn min_severity(min_severity: f32) -> Self {
+        Self {
+            min_severity,
+            ..Self::default()
+        }
+    }
+
+    /// Narrows the filter to the given event types.
+    pub fn event_types<S: Into<String>>(mut self, types: impl IntoIterator<Item = S>) -> Self {
+        self.event_types = types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn matches(&self, narration: &Narration) -> bool {
+        narration.severity >= self.min_severity
+            && (self.event_types.is_empty() || self.event_types.contains(&narration.event_type))
+    }
+}
+
+/// A world-wide catastrophe and how long it lasts, in ticks.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "kind")]
+pub enum Disaster {
+    /// Fertility collapses across the terrain.
+    DustBowl { duration: u32 },
+    /// Radiation raises mutation rates.
+    SolarFlare { duration: u32 },
+    /// A forced ice age.
+    DeepFreeze { duration: u32 },
+}
+
+impl Disaster {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Disaster::DustBowl { .. } => "DustBowl",
+            Disaster::SolarFlare { .. } => "SolarFlare",
+            Disaster::DeepFreeze { .. } => "DeepFreeze",
+        }
+    }
+}
+
+/// A disaster that struck at `tick`, as kept in a run's replay.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisasterRecord {
+    pub tick: u64,
+    #[serde(flatten)]
+    pub disaster: Disaster,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Archive,
+    RkyvSerialize,
+    RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+/// Ties a narration to the logged events and figures it was drawn from.
+pub struct NarrationRefs {
+    /// First tick of the period the narration covers.
+    pub from_tick: u64,
+    /// Last tick of the period the narration covers.
+    pub to_tick: u64,
+    /// Lineages the narration is about.
+    #[serde(default)]
+    pub lineages: Vec<Uuid>,
+    /// Logged events that prompted it.
+    #[serde(default)]
+    pub events: Vec<EventRef>,
+    /// Figures it rests on, one per line (e.g. `population: 12`).
+    #[serde(default)]
+    pub data: Vec<String>,
+}
+
+impl NarrationRefs {
+    /// References covering the single tick `tick`.
+    pub fn at(tick: u64) -> Self {
+        Self {
+            from_tick: tick,
+            to_tick: tick,
+            ..Self::default()
+        }
+    }
+
+    /// The references as indented lines, for the TUI and the report.
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.from_tick == self.to_tick {
+            lines.push(format!("ticks: {}", self.from_tick));
+        } else {
+            lines.push(format!("ticks: {}..={}", self.from_tick, self.to_tick));
+        }
+        for lineage in &self.lineages {
+            lines.push(format!("lineage: {}", lineage));
+        }
+        lines.extend(self.data.iter().cloned());
+        for event in &self.events {
+            lines.push(event.to_string());
+        }
+        lines
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, PartialEq, Archive, RkyvSerialize, RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+/// Identifies one logged event by its tag, tick and subject.
+pub struct EventRef {
+    /// The event's `event` tag, e.g. `Death`.
+    pub event: String,
+    pub tick: u64,
+    /// The entity the event is about, for births, deaths and the like.
+    pub id: Option<Uuid>,
+}
+
+impl From<&LiveEvent> for EventRef {
+    fn from(event: &LiveEvent) -> Self {
+        let id = match event {
+            LiveEvent::Birth { id, .. }
+            | LiveEvent::Death { id, .. }
+            | LiveEvent::Metamorphosis { id, .. }
+            | LiveEvent::TribalSplit { id, .. } => Some(*id),
+            _ => None,
+        };
+        Self {
+            event: event.kind().to_string(),
+            tick: event.tick(),
+            id,
+        }
+    }
+}
+
+impl std::fmt::Display for EventRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.id {
+            Some(id) => write!(f, "{} #{} at tick {}", self.event, id, self.tick),
+            None => write!(f, "{} at tick {}", self.event, self.tick),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Archive, RkyvSerialize, RkyvDeserialize)]
@@ -211,6 +573,38 @@ impl Default for HallOfFame {
     }
 }
 
+/// Archived layout of [`HallOfFame`] before the body size gene, kept to
+/// read old snapshots.
+#[derive(Debug, Clone, Default, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct LegacyHallOfFame {
+    pub top_living: Vec<(f64, LegacyEntity)>,
+}
+
+impl From<LegacyHallOfFame> for HallOfFame {
+    fn from(legacy: LegacyHallOfFame) -> Self {
+        Self {
+            top_living: legacy
+                .top_living
+                .into_iter()
+                .map(|(fitness, entity)| (fitness, entity.into()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, PartialEq, Archive, RkyvSerialize, RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+/// User note attached to a tick of the timeline, saved with the world.
+pub struct Bookmark {
+    /// Tick the bookmark was placed at.
+    pub tick: u64,
+    /// Free-form annotation; may be empty.
+    pub note: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Archive, RkyvSerialize, RkyvDeserialize)]
 #[archive(check_bytes)]
 /// Preserved record of an extinct lineage archived in the fossil registry.
@@ -233,6 +627,189 @@ pub struct Fossil {
     pub peak_population: usize,
     /// Representative genotype preserved for atavistic recall.
     pub genotype: Genotype,
+    /// Geological era the world was in when the lineage died out (empty for
+    /// fossils recorded before eras were tagged).
+    #[serde(default)]
+    pub era: String,
+    /// Climate state at extinction (empty for older fossils).
+    #[serde(default)]
+    pub climate: String,
+    /// What killed the lineage's last members.
+    #[serde(default)]
+    pub extinction_cause: DeathCause,
+}
+
+impl Fossil {
+    /// Body plan metrics derived from the preserved genotype.
+    #[must_use]
+    pub fn morphology(&self) -> FossilMorphology {
+        FossilMorphology::of(&self.genotype)
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, PartialEq, Archive, RkyvSerialize, RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+/// Structure raised by a civilised lineage, inscribed with the lineage's
+/// achievements as they stood when it was built.
+pub struct Monument {
+    /// UUID of the lineage that raised it.
+    pub lineage_id: Uuid,
+    /// Display name of the lineage.
+    pub name: String,
+    /// RGB colour of the builder.
+    pub color_rgb: (u8, u8, u8),
+    /// X grid coordinate.
+    pub x: u16,
+    /// Y grid coordinate.
+    pub y: u16,
+    /// Tick the monument was raised.
+    pub raised_tick: u64,
+    /// Civilization level of the lineage when raised.
+    pub civilization_level: u32,
+    /// Highest generation the lineage had reached.
+    pub max_generation: u32,
+    /// Living members when raised.
+    pub population: usize,
+    /// Largest population the lineage had achieved.
+    pub peak_population: usize,
+    /// Members the lineage had produced.
+    pub total_offspring: u32,
+    /// Goals the lineage had completed.
+    pub goals: Vec<LineageGoal>,
+    /// Tick the lineage died out; `None` while it survives.
+    pub extinct_tick: Option<u64>,
+}
+
+impl Monument {
+    /// True once the lineage that raised it is extinct.
+    #[must_use]
+    pub fn is_ruin(&self) -> bool {
+        self.extinct_tick.is_some()
+    }
+
+    /// Lines carved into the monument.
+    #[must_use]
+    pub fn inscription(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Raised by the {} at tick {}", self.name, self.raised_tick),
+            format!(
+                "Civilization level {}, generation {}",
+                self.civilization_level, self.max_generation
+            ),
+            format!(
+                "{} living of {} born, at most {} at once",
+                self.population, self.total_offspring, self.peak_population
+            ),
+        ];
+        if !self.goals.is_empty() {
+            let goals: Vec<String> = self.goals.iter().map(|g| format!("{:?}", g)).collect();
+            lines.push(format!("Achieved {}", goals.join(", ")));
+        }
+        if let Some(tick) = self.extinct_tick {
+            lines.push(format!("The {} fell silent at tick {}", self.name, tick));
+        }
+        lines
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Archive,
+    RkyvSerialize,
+    RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+/// Why an organism died; for a [`Fossil`], why its lineage went extinct.
+pub enum DeathCause {
+    /// Not recorded (older fossils).
+    #[default]
+    Unknown,
+    /// Ran out of energy under a mild climate.
+    Starvation,
+    /// Killed by another organism.
+    Predation,
+    /// Died while carrying a pathogen.
+    Disease,
+    /// Ran out of energy while the climate was hot or scorching.
+    Climate,
+    /// Ran out of water.
+    Dehydration,
+}
+
+impl DeathCause {
+    pub const ALL: [DeathCause; 6] = [
+        DeathCause::Unknown,
+        DeathCause::Starvation,
+        DeathCause::Predation,
+        DeathCause::Disease,
+        DeathCause::Climate,
+        DeathCause::Dehydration,
+    ];
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            DeathCause::Unknown => "Unknown",
+            DeathCause::Starvation => "Starvation",
+            DeathCause::Predation => "Predation",
+            DeathCause::Disease => "Disease",
+            DeathCause::Climate => "Climate",
+            DeathCause::Dehydration => "Dehydration",
+        }
+    }
+}
+
+/// Derived body plan metrics of a genotype, used to compare fossils.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FossilMorphology {
+    /// Total brain nodes, including sensors and actuators.
+    pub brain_nodes: usize,
+    /// Enabled brain connections.
+    pub brain_connections: usize,
+    /// Position on the herbivore (0.0) to carnivore (1.0) axis.
+    pub trophic_potential: f32,
+    /// Top speed per unit of sensing range; high values are fast but
+    /// short-sighted.
+    pub speed_sense_ratio: f64,
+}
+
+impl FossilMorphology {
+    #[must_use]
+    pub fn of(genotype: &Genotype) -> Self {
+        Self {
+            brain_nodes: genotype.brain.nodes.len(),
+            brain_connections: genotype
+                .brain
+                .connections
+                .iter()
+                .filter(|c| c.enabled)
+                .count(),
+            trophic_potential: genotype.trophic_potential,
+            speed_sense_ratio: genotype.max_speed / genotype.sensing_range.max(f64::EPSILON),
+        }
+    }
+
+    /// Diet class by trophic potential, using the lineage tree's thresholds.
+    #[must_use]
+    pub fn diet(&self) -> &'static str {
+        if self.trophic_potential < 0.3 {
+            "Herbivore"
+        } else if self.trophic_potential > 0.7 {
+            "Carnivore"
+        } else {
+            "Omnivore"
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Archive, RkyvSerialize, RkyvDeserialize)]
@@ -275,6 +852,9 @@ pub struct Legend {
 pub struct FossilRegistry {
     /// Archived fossils, ordered by `total_offspring` descending after cap enforcement.
     pub fossils: Vec<Fossil>,
+    /// Monuments left behind by extinct lineages, oldest ruin first.
+    #[serde(default)]
+    pub ruins: Vec<Monument>,
 }
 
 impl FossilRegistry {
@@ -283,8 +863,17 @@ impl FossilRegistry {
         self.fossils.push(fossil);
         if self.fossils.len() > 100 {
             self.fossils
-                .sort_by(|a, b| b.total_offspring.cmp(&a.total_offspring));
+                .sort_by_key(|f| std::cmp::Reverse(f.total_offspring));
             self.fossils.truncate(100);
         }
     }
+
+    /// Archives the monument of an extinct lineage, evicting the oldest ruin
+    /// when capacity exceeds 100.
+    pub fn add_ruin(&mut self, monument: Monument) {
+        self.ruins.push(monument);
+        if self.ruins.len() > 100 {
+            self.ruins.remove(0);
+        }
+    }
 }
@@ -17,6 +17,66 @@ pub enum NodeType {
     Output,
 }
 
+/// Activation function of a neural node.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Archive,
+    RkyvSerialize,
+    RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+pub enum Activation {
+    /// Hyperbolic tangent, in `[-1, 1]`.
+    #[default]
+    Tanh,
+    /// Rectified linear, capped at 1 to stay in the tanh range.
+    Relu,
+    /// Sine, for periodic patterns.
+    Sine,
+    /// Gaussian bump `exp(-x²)`, for symmetric patterns.
+    Gaussian,
+}
+
+impl Activation {
+    /// All activation functions, in gene order.
+    pub const ALL: [Activation; 4] = [
+        Activation::Tanh,
+        Activation::Relu,
+        Activation::Sine,
+        Activation::Gaussian,
+    ];
+
+    /// Applies the function to a node's summed input.
+    #[inline]
+    #[must_use]
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Activation::Tanh => x.tanh(),
+            Activation::Relu => x.clamp(0.0, 1.0),
+            Activation::Sine => x.sin(),
+            Activation::Gaussian => (-x * x).exp(),
+        }
+    }
+
+    /// Short name for display.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Activation::Tanh => "tanh",
+            Activation::Relu => "relu",
+            Activation::Sine => "sin",
+            Activation::Gaussian => "gauss",
+        }
+    }
+}
+
 /// A node in the neural network brain.
 #[derive(
     Clone, Debug, Serialize, Deserialize, PartialEq, Archive, RkyvSerialize, RkyvDeserialize,
@@ -29,6 +89,10 @@ pub struct Node {
     pub node_type: NodeType,
     /// Optional descriptive label.
     pub label: Option<String>,
+    /// Activation function; only hidden nodes evolve one, and saves from
+    /// before activation genes load as tanh.
+    #[serde(default)]
+    pub activation: Activation,
 }
 
 /// A connection between two nodes in the neural network.
@@ -99,6 +163,10 @@ pub struct Brain {
     #[serde(skip, default = "Vec::new")]
     #[with(rkyv::with::Skip)]
     pub incoming_offsets: Vec<usize>,
+    /// Fingerprint of the wiring the forward pass follows (not serialized).
+    #[serde(skip, default)]
+    #[with(rkyv::with::Skip)]
+    pub wiring_hash: u64,
 }
 
 /// Caste specialization for evolved entities.
@@ -223,6 +291,15 @@ pub struct RegulatoryRule {
     pub modifier: f32,
 }
 
+/// Largest clutch the clutch size gene can encode.
+pub const MAX_CLUTCH_SIZE: u8 = 8;
+
+/// Smallest body the body size gene can encode: a single cell.
+pub const MIN_BODY_SIZE: f32 = 1.0;
+
+/// Largest body the body size gene can encode, in cells across.
+pub const MAX_BODY_SIZE: f32 = 3.0;
+
 /// Complete genetic blueprint of an organism.
 #[derive(
     Clone, Debug, Serialize, Deserialize, PartialEq, Archive, RkyvSerialize, RkyvDeserialize,
@@ -255,6 +332,89 @@ pub struct Genotype {
     pub specialization_bias: [f32; 3],
     /// Genetic regulation rules.
     pub regulatory_rules: Vec<RegulatoryRule>,
+    /// Tendency to lay eggs instead of giving live birth (0.0-1.0); eggs are
+    /// laid above 0.5.
+    #[serde(default)]
+    pub oviparity: f32,
+    /// Eggs per clutch (1-8); the clutch shares one offspring's energy.
+    #[serde(default = "default_clutch_size")]
+    pub clutch_size: u8,
+    /// How long parents provision their young (0.0-1.0), as a fraction of
+    /// the maturity age.
+    #[serde(default)]
+    pub parental_care: f32,
+    /// Distrust of food pheromone trails (0.0-1.0); sensed trails are
+    /// weakened by this fraction, guarding against deceptive signals at the
+    /// price of ignoring honest ones.
+    #[serde(default)]
+    pub skepticism: f32,
+    /// Aversion to the death scent left by infection victims (0.0-1.0);
+    /// scales the push away from rising scent, quarantining epidemic areas.
+    #[serde(default)]
+    pub death_avoidance: f32,
+    /// Body size in terrain cells across (1.0-3.0); larger bodies store
+    /// more energy and win fights but burn more and move slower.
+    #[serde(default = "default_body_size")]
+    pub body_size: f32,
+}
+
+fn default_clutch_size() -> u8 {
+    1
+}
+
+fn default_body_size() -> f32 {
+    MIN_BODY_SIZE
+}
+
+/// Archived layout of [`Genotype`] before the body size gene, kept to read
+/// old snapshots.
+#[derive(Clone, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct LegacyGenotype {
+    pub brain: Brain,
+    pub sensing_range: f64,
+    pub max_speed: f64,
+    pub max_energy: f64,
+    pub lineage_id: Uuid,
+    pub metabolic_niche: f32,
+    pub trophic_potential: f32,
+    pub reproductive_investment: f32,
+    pub maturity_gene: f32,
+    pub mate_preference: f32,
+    pub pairing_bias: f32,
+    pub specialization_bias: [f32; 3],
+    pub regulatory_rules: Vec<RegulatoryRule>,
+    pub oviparity: f32,
+    pub clutch_size: u8,
+    pub parental_care: f32,
+    pub skepticism: f32,
+    pub death_avoidance: f32,
+}
+
+impl From<LegacyGenotype> for Genotype {
+    fn from(legacy: LegacyGenotype) -> Self {
+        Self {
+            brain: legacy.brain,
+            sensing_range: legacy.sensing_range,
+            max_speed: legacy.max_speed,
+            max_energy: legacy.max_energy,
+            lineage_id: legacy.lineage_id,
+            metabolic_niche: legacy.metabolic_niche,
+            trophic_potential: legacy.trophic_potential,
+            reproductive_investment: legacy.reproductive_investment,
+            maturity_gene: legacy.maturity_gene,
+            mate_preference: legacy.mate_preference,
+            pairing_bias: legacy.pairing_bias,
+            specialization_bias: legacy.specialization_bias,
+            regulatory_rules: legacy.regulatory_rules,
+            oviparity: legacy.oviparity,
+            clutch_size: legacy.clutch_size,
+            parental_care: legacy.parental_care,
+            skepticism: legacy.skepticism,
+            death_avoidance: legacy.death_avoidance,
+            body_size: MIN_BODY_SIZE,
+        }
+    }
 }
 
 /// Neural network activation buffers.
@@ -298,17 +458,18 @@ impl Brain {
 }
 
 impl Genotype {
-    /// Serialize genotype to hex string.
+    /// Whether offspring are laid as eggs.
     #[must_use]
-    pub fn to_hex(&self) -> String {
-        let bytes = serde_json::to_vec(self).unwrap_or_default();
-        hex::encode(bytes)
+    pub fn is_oviparous(&self) -> bool {
+        self.oviparity > 0.5
     }
 
-    /// Deserialize genotype from hex string.
-    pub fn from_hex(hex_str: &str) -> anyhow::Result<Self> {
-        let bytes = hex::decode(hex_str)?;
-        let genotype = serde_json::from_slice(&bytes)?;
-        Ok(genotype)
+    /// Serialize genotype to a hex DNA string in the compact format of
+    /// [`dna`](super::dna).
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        super::dna::encode(self)
+            .map(hex::encode)
+            .unwrap_or_default()
     }
 }
@@ -1,6 +1,9 @@
 //! Core data structures for the Primordium simulation.
 
+pub mod dna;
 pub mod entity;
 pub mod environment;
 pub mod genotype;
+pub mod provenance;
+pub mod status;
 pub mod terrain;
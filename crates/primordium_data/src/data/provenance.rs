@@ -0,0 +1,46 @@
+//! Run provenance stamped into saves, logs, replays and submissions.
+
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, PartialEq, Archive, RkyvSerialize, RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+/// What produced a run's outputs: enough to reproduce the run and to tell
+/// which build and config a result came from.
+pub struct RunManifest {
+    /// SHA-256 of the full config the run started with.
+    pub config_hash: String,
+    /// `world.seed`, if one was set.
+    pub seed: Option<u64>,
+    /// Version of the `primordium` crate.
+    pub version: String,
+    /// Commit the binary was built from, when built from a git checkout.
+    #[serde(default)]
+    pub git_commit: Option<String>,
+    /// Cargo features compiled in.
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// RFC 3339 time the run started.
+    pub started_at: String,
+}
+
+impl fmt::Display for RunManifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "config hash: {}", self.config_hash)?;
+        match self.seed {
+            Some(seed) => writeln!(f, "seed:        {}", seed)?,
+            None => writeln!(f, "seed:        none")?,
+        }
+        writeln!(f, "version:     {}", self.version)?;
+        writeln!(
+            f,
+            "git commit:  {}",
+            self.git_commit.as_deref().unwrap_or("unknown")
+        )?;
+        writeln!(f, "features:    {}", self.features.join(", "))?;
+        write!(f, "started at:  {}", self.started_at)
+    }
+}
@@ -0,0 +1,83 @@
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Kind of a timed status effect.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Archive,
+    RkyvSerialize,
+    RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+pub enum StatusKind {
+    /// Pathogen infection; magnitude is the energy drained per tick.
+    Infection,
+    /// Radiation exposure; boosts mutation in offspring.
+    Radiation,
+    /// Shelter from resting on a nest; magnitude is the idle cost reduction.
+    NestShield,
+    /// Divine favour; magnitude is added to immunity.
+    Blessing,
+    /// Experimental treatment; magnitude multiplies offspring mutation rate.
+    MutationRate,
+    /// Experimental treatment; magnitude multiplies metabolic costs.
+    MetabolicRate,
+}
+
+impl StatusKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            StatusKind::Infection => "Infection",
+            StatusKind::Radiation => "Radiation",
+            StatusKind::NestShield => "Nest Shield",
+            StatusKind::Blessing => "Blessing",
+            StatusKind::MutationRate => "Mutation Rate",
+            StatusKind::MetabolicRate => "Metabolic Rate",
+        }
+    }
+}
+
+/// What happens when an effect is applied while one of its kind is active.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Archive,
+    RkyvSerialize,
+    RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+pub enum Stacking {
+    /// The new application is ignored.
+    Ignore,
+    /// The new application replaces the active one.
+    Refresh,
+    /// The stronger one is kept; equal magnitudes keep the longer timer.
+    Strongest,
+    /// Applications add up as separate instances, at most `max` at once.
+    Stack { max: u8 },
+}
+
+/// A buff or debuff that wears off after `remaining` ticks.
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize,
+)]
+#[archive(check_bytes)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub magnitude: f32,
+    /// Ticks until the effect wears off.
+    pub remaining: u32,
+    pub stacking: Stacking,
+}
@@ -63,6 +63,37 @@ pub enum OutpostSpecialization {
     Nursery,
 }
 
+/// Heritable traits of a plant. Food carries them and passes them, mutated,
+/// to the seedlings it spreads.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlantGenome {
+    /// Poison dealt to eaters whose niche does not match (0.0-1.0).
+    pub toxicity: f32,
+    /// Nutrient type (0.0=green, 1.0=blue).
+    pub nutrient_type: f32,
+    /// Seeding vigour (0.0-1.0).
+    pub regrowth: f32,
+}
+
+impl PlantGenome {
+    /// Regrowth of wild plants and of food saved before plants evolved.
+    pub const WILD_REGROWTH: f32 = 0.5;
+
+    /// An untoxic wild plant of the given nutrient type.
+    #[must_use]
+    pub fn wild(nutrient_type: f32) -> Self {
+        Self {
+            toxicity: 0.0,
+            nutrient_type,
+            regrowth: Self::WILD_REGROWTH,
+        }
+    }
+}
+
+fn wild_regrowth() -> f32 {
+    PlantGenome::WILD_REGROWTH
+}
+
 /// Food resource in the world.
 #[derive(Clone, Debug, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize)]
 #[archive(check_bytes)]
@@ -79,16 +110,30 @@ pub struct Food {
     pub color_rgb: (u8, u8, u8),
     /// Nutrient type (0.0=green, 1.0=blue).
     pub nutrient_type: f32,
+    /// Toxicity gene, see [`PlantGenome`].
+    #[serde(default)]
+    pub toxicity: f32,
+    /// Regrowth gene, see [`PlantGenome`].
+    #[serde(default = "wild_regrowth")]
+    pub regrowth: f32,
 }
 
 impl Food {
     /// Create new food at position with nutrient type.
     #[must_use]
     pub fn new(x: u16, y: u16, nutrient_type: f32) -> Self {
-        let color = if nutrient_type < 0.5 {
-            (0, 255, 0)
+        Self::from_genome(x, y, PlantGenome::wild(nutrient_type))
+    }
+
+    /// Create a plant at position expressing `genome`.
+    #[must_use]
+    pub fn from_genome(x: u16, y: u16, genome: PlantGenome) -> Self {
+        // Toxic plants blush red.
+        let red = (genome.toxicity.clamp(0.0, 1.0) * 255.0) as u8;
+        let color = if genome.nutrient_type < 0.5 {
+            (red, 255, 0)
         } else {
-            (0, 100, 255)
+            (red, 100, 255)
         };
 
         Self {
@@ -97,7 +142,47 @@ impl Food {
             value: 50.0,
             symbol: '*',
             color_rgb: color,
-            nutrient_type,
+            nutrient_type: genome.nutrient_type,
+            toxicity: genome.toxicity,
+            regrowth: genome.regrowth,
+        }
+    }
+
+    /// The plant's heritable traits.
+    #[must_use]
+    pub fn genome(&self) -> PlantGenome {
+        PlantGenome {
+            toxicity: self.toxicity,
+            nutrient_type: self.nutrient_type,
+            regrowth: self.regrowth,
+        }
+    }
+}
+
+/// Archived layout of [`Food`] before plants evolved, kept to read old
+/// snapshots.
+#[derive(Clone, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct LegacyFood {
+    pub x: u16,
+    pub y: u16,
+    pub value: f64,
+    pub symbol: char,
+    pub color_rgb: (u8, u8, u8),
+    pub nutrient_type: f32,
+}
+
+impl From<LegacyFood> for Food {
+    fn from(legacy: LegacyFood) -> Self {
+        Self {
+            x: legacy.x,
+            y: legacy.y,
+            value: legacy.value,
+            symbol: legacy.symbol,
+            color_rgb: legacy.color_rgb,
+            nutrient_type: legacy.nutrient_type,
+            toxicity: 0.0,
+            regrowth: PlantGenome::WILD_REGROWTH,
         }
     }
 }
@@ -8,4 +8,6 @@ pub mod data;
 pub use data::entity::*;
 pub use data::environment::*;
 pub use data::genotype::*;
+pub use data::provenance::*;
+pub use data::status::*;
 pub use data::terrain::*;
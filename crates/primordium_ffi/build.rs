@@ -0,0 +1,18 @@
+//! Regenerates `include/primordium.h` from the exported functions.
+
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml"))
+        .expect("cbindgen.toml should parse");
+    match cbindgen::generate_with_config(&crate_dir, config) {
+        Ok(bindings) => {
+            bindings.write_to_file(format!("{crate_dir}/include/primordium.h"));
+        }
+        // Keep the checked-in header rather than failing the build, e.g.
+        // while the source is mid-edit and does not parse.
+        Err(e) => println!("cargo:warning=primordium.h not regenerated: {e}"),
+    }
+}
@@ -0,0 +1,107 @@
+#ifndef PRIMORDIUM_H
+#define PRIMORDIUM_H
+
+/* Generated by cbindgen from crates/primordium_ffi. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Outcome of a fallible call.
+typedef enum PrimordiumStatus {
+  PRIMORDIUM_STATUS_OK = 0,
+  // A required pointer argument was null.
+  PRIMORDIUM_STATUS_NULL_ARGUMENT = 1,
+  // A string argument was not valid UTF-8, or its value was rejected.
+  PRIMORDIUM_STATUS_INVALID_ARGUMENT = 2,
+  // The simulation failed; see [`primordium_last_error`].
+  PRIMORDIUM_STATUS_FAILED = 3,
+} PrimordiumStatus;
+
+// A running simulation: a world and the environment it evolves in.
+typedef struct PrimordiumSim PrimordiumSim;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Version of the library, as a static string the caller must not free.
+const char *primordium_version(void);
+
+// Message of the last failed call on this thread, or null if none has
+// failed. Valid until the next failing call on the same thread; do not free.
+const char *primordium_last_error(void);
+
+// Creates a simulation of `initial_population` random entities.
+//
+// `config_toml` is the contents of a `config.toml`, or null for the
+// defaults. Nothing is written to disk. Returns null on failure.
+//
+// # Safety
+// `config_toml` must be null or point to a NUL-terminated string.
+struct PrimordiumSim *primordium_sim_new(const char *config_toml, uint32_t initial_population);
+
+// Destroys a simulation. Null is ignored.
+//
+// # Safety
+// `sim` must be null or a handle from [`primordium_sim_new`] that has not
+// been freed.
+void primordium_sim_free(struct PrimordiumSim *sim);
+
+// Advances the simulation by `ticks` ticks.
+//
+// # Safety
+// `sim` must be null or a live handle from [`primordium_sim_new`].
+enum PrimordiumStatus primordium_sim_step(struct PrimordiumSim *sim, uint32_t ticks);
+
+// Current tick, or 0 for a null handle.
+//
+// # Safety
+// `sim` must be null or a live handle from [`primordium_sim_new`].
+uint64_t primordium_sim_tick(const struct PrimordiumSim *sim);
+
+// The current state as JSON: tick, size, entities, food, eggs and
+// population stats. Returns null on failure; free the result with
+// [`primordium_string_free`].
+//
+// # Safety
+// `sim` must be null or a live handle from [`primordium_sim_new`].
+char *primordium_sim_snapshot_json(struct PrimordiumSim *sim);
+
+// Releases a string returned by this library. Null is ignored.
+//
+// # Safety
+// `s` must be null or a string returned by this library that has not been
+// freed.
+void primordium_string_free(char *s);
+
+// Spawns `count` entities from a genotype hex string (the DNA format of
+// exports and the clipboard) at `(x, y)`. A NaN coordinate is replaced by a
+// random one for each entity.
+//
+// # Safety
+// `sim` must be null or a live handle from [`primordium_sim_new`], and
+// `dna` null or a NUL-terminated string.
+enum PrimordiumStatus primordium_sim_inject_genome(struct PrimordiumSim *sim,
+                                                   const char *dna,
+                                                   double x,
+                                                   double y,
+                                                   uint32_t count);
+
+// Sets the config value at a dotted `key` such as `world.max_food`.
+// `value` is a TOML literal (`500`, `true`, `"field"`). The world size is
+// fixed once the simulation exists.
+//
+// # Safety
+// `sim` must be null or a live handle from [`primordium_sim_new`], and
+// `key` and `value` null or NUL-terminated strings.
+enum PrimordiumStatus primordium_sim_set_config(struct PrimordiumSim *sim,
+                                                const char *key,
+                                                const char *value);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PRIMORDIUM_H */
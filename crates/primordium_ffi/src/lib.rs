@@ -0,0 +1,357 @@
+//! # Primordium FFI
+//!
+//! A stable C interface for embedding the simulation in game engines and
+//! other languages. The generated header is `include/primordium.h`.
+//!
+//! A simulation is an opaque [`PrimordiumSim`] handle created with
+//! [`primordium_sim_new`] and released with [`primordium_sim_free`].
+//! Functions that can fail return a [`PrimordiumStatus`]; the message of the
+//! last failure on the calling thread is available from
+//! [`primordium_last_error`]. Strings returned to the caller are owned by it
+//! and must be released with [`primordium_string_free`].
+//!
+//! A handle is not thread-safe: calls on one handle must not overlap.
+
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::environment::Environment;
+use primordium_lib::model::world::scenario::ScenarioEvent;
+use primordium_lib::model::world::World;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Outcome of a fallible call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimordiumStatus {
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullArgument = 1,
+    /// A string argument was not valid UTF-8, or its value was rejected.
+    InvalidArgument = 2,
+    /// The simulation failed; see [`primordium_last_error`].
+    Failed = 3,
+}
+
+/// A running simulation: a world and the environment it evolves in.
+pub struct PrimordiumSim {
+    world: World,
+    env: Environment,
+}
+
+/// What [`primordium_sim_snapshot_json`] returns. The per-cell grids of the
+/// full world snapshot are left out to keep it small enough to poll every
+/// frame.
+#[derive(Serialize)]
+struct FfiSnapshot<'a> {
+    tick: u64,
+    width: u16,
+    height: u16,
+    entities: &'a [primordium_core::snapshot::EntitySnapshot],
+    food: &'a [primordium_data::Food],
+    eggs: &'a [primordium_core::snapshot::EggSnapshot],
+    stats: &'a primordium_data::PopulationStats,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl std::fmt::Display) {
+    let message = CString::new(message.to_string().replace('\0', " "))
+        .unwrap_or_else(|_| CString::from(c"error"));
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+/// Runs `f`, recording its error or panic as the last error.
+fn guard(f: impl FnOnce() -> Result<(), PrimordiumStatus>) -> PrimordiumStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => PrimordiumStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => {
+            set_last_error("the simulation panicked");
+            PrimordiumStatus::Failed
+        }
+    }
+}
+
+fn failed(e: impl std::fmt::Display) -> PrimordiumStatus {
+    set_last_error(e);
+    PrimordiumStatus::Failed
+}
+
+fn invalid(e: impl std::fmt::Display) -> PrimordiumStatus {
+    set_last_error(e);
+    PrimordiumStatus::InvalidArgument
+}
+
+/// Reads a required C string argument.
+///
+/// # Safety
+/// `ptr` must be null or point to a NUL-terminated string.
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, PrimordiumStatus> {
+    if ptr.is_null() {
+        set_last_error(format!("{name} is null"));
+        return Err(PrimordiumStatus::NullArgument);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| invalid(format!("{name} is not valid UTF-8")))
+}
+
+/// # Safety
+/// `sim` must be null or a live handle from [`primordium_sim_new`].
+unsafe fn sim_mut<'a>(sim: *mut PrimordiumSim) -> Result<&'a mut PrimordiumSim, PrimordiumStatus> {
+    sim.as_mut().ok_or_else(|| {
+        set_last_error("sim is null");
+        PrimordiumStatus::NullArgument
+    })
+}
+
+/// Version of the library, as a static string the caller must not free.
+#[no_mangle]
+pub extern "C" fn primordium_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Message of the last failed call on this thread, or null if none has
+/// failed. Valid until the next failing call on the same thread; do not free.
+#[no_mangle]
+pub extern "C" fn primordium_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(std::ptr::null(), |m| m.as_ptr()))
+}
+
+/// Creates a simulation of `initial_population` random entities.
+///
+/// `config_toml` is the contents of a `config.toml`, or null for the
+/// defaults. Nothing is written to disk. Returns null on failure.
+///
+/// # Safety
+/// `config_toml` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn primordium_sim_new(
+    config_toml: *const c_char,
+    initial_population: u32,
+) -> *mut PrimordiumSim {
+    let mut sim = None;
+    guard(|| {
+        let config = if config_toml.is_null() {
+            AppConfig::default()
+        } else {
+            AppConfig::from_toml(read_str(config_toml, "config_toml")?).map_err(invalid)?
+        };
+        let world = World::new_ephemeral(initial_population as usize, config).map_err(failed)?;
+        sim = Some(PrimordiumSim {
+            world,
+            env: Environment::default(),
+        });
+        Ok(())
+    });
+    sim.map_or(std::ptr::null_mut(), |s| Box::into_raw(Box::new(s)))
+}
+
+/// Destroys a simulation. Null is ignored.
+///
+/// # Safety
+/// `sim` must be null or a handle from [`primordium_sim_new`] that has not
+/// been freed.
+#[no_mangle]
+pub unsafe extern "C" fn primordium_sim_free(sim: *mut PrimordiumSim) {
+    if !sim.is_null() {
+        drop(Box::from_raw(sim));
+    }
+}
+
+/// Advances the simulation by `ticks` ticks.
+///
+/// # Safety
+/// `sim` must be null or a live handle from [`primordium_sim_new`].
+#[no_mangle]
+pub unsafe extern "C" fn primordium_sim_step(
+    sim: *mut PrimordiumSim,
+    ticks: u32,
+) -> PrimordiumStatus {
+    guard(|| {
+        let sim = sim_mut(sim)?;
+        for _ in 0..ticks {
+            sim.world.update(&mut sim.env).map_err(failed)?;
+        }
+        Ok(())
+    })
+}
+
+/// Current tick, or 0 for a null handle.
+///
+/// # Safety
+/// `sim` must be null or a live handle from [`primordium_sim_new`].
+#[no_mangle]
+pub unsafe extern "C" fn primordium_sim_tick(sim: *const PrimordiumSim) -> u64 {
+    sim.as_ref().map_or(0, |s| s.world.tick)
+}
+
+/// The current state as JSON: tick, size, entities, food, eggs and
+/// population stats. Returns null on failure; free the result with
+/// [`primordium_string_free`].
+///
+/// # Safety
+/// `sim` must be null or a live handle from [`primordium_sim_new`].
+#[no_mangle]
+pub unsafe extern "C" fn primordium_sim_snapshot_json(sim: *mut PrimordiumSim) -> *mut c_char {
+    let mut json = None;
+    guard(|| {
+        let sim = sim_mut(sim)?;
+        let snapshot = sim.world.create_snapshot(None);
+        let body = serde_json::to_string(&FfiSnapshot {
+            tick: snapshot.tick,
+            width: snapshot.width,
+            height: snapshot.height,
+            entities: &snapshot.entities,
+            food: &snapshot.food,
+            eggs: &snapshot.eggs,
+            stats: &snapshot.stats,
+        })
+        .map_err(failed)?;
+        json = Some(CString::new(body).map_err(failed)?);
+        Ok(())
+    });
+    json.map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by this library. Null is ignored.
+///
+/// # Safety
+/// `s` must be null or a string returned by this library that has not been
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn primordium_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Spawns `count` entities from a genotype hex string (the DNA format of
+/// exports and the clipboard) at `(x, y)`. A NaN coordinate is replaced by a
+/// random one for each entity.
+///
+/// # Safety
+/// `sim` must be null or a live handle from [`primordium_sim_new`], and
+/// `dna` null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn primordium_sim_inject_genome(
+    sim: *mut PrimordiumSim,
+    dna: *const c_char,
+    x: f64,
+    y: f64,
+    count: u32,
+) -> PrimordiumStatus {
+    guard(|| {
+        let sim = sim_mut(sim)?;
+        let event = ScenarioEvent::Spawn {
+            dna: read_str(dna, "dna")?.to_string(),
+            count: count as usize,
+            x: (!x.is_nan()).then_some(x),
+            y: (!y.is_nan()).then_some(y),
+        };
+        sim.world
+            .apply_scenario_event(&mut sim.env, &event)
+            .map_err(invalid)?;
+        Ok(())
+    })
+}
+
+/// Sets the config value at a dotted `key` such as `world.max_food`.
+/// `value` is a TOML literal (`500`, `true`, `"field"`). The world size is
+/// fixed once the simulation exists.
+///
+/// # Safety
+/// `sim` must be null or a live handle from [`primordium_sim_new`], and
+/// `key` and `value` null or NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn primordium_sim_set_config(
+    sim: *mut PrimordiumSim,
+    key: *const c_char,
+    value: *const c_char,
+) -> PrimordiumStatus {
+    guard(|| {
+        let sim = sim_mut(sim)?;
+        let key = read_str(key, "key")?;
+        let value = read_str(value, "value")?;
+        if matches!(key, "world.width" | "world.height") {
+            return Err(invalid("the world size cannot change after creation"));
+        }
+        sim.world.config.set_value(key, value).map_err(invalid)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primordium_lib::model::brain::create_genotype_random_with_rng;
+
+    fn error() -> String {
+        unsafe { CStr::from_ptr(primordium_last_error()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_simulation_lifecycle_through_the_c_api() {
+        unsafe {
+            let sim = primordium_sim_new(std::ptr::null(), 5);
+            assert!(!sim.is_null());
+            assert_eq!(primordium_sim_step(sim, 3), PrimordiumStatus::Ok);
+            assert_eq!(primordium_sim_tick(sim), 3);
+
+            let dna =
+                CString::new(create_genotype_random_with_rng(&mut rand::thread_rng()).to_hex())
+                    .unwrap();
+            let before = (*sim).world.get_population_count();
+            let status = primordium_sim_inject_genome(sim, dna.as_ptr(), 10.0, f64::NAN, 2);
+            assert_eq!(status, PrimordiumStatus::Ok);
+            assert_eq!((*sim).world.get_population_count(), before + 2);
+
+            let json = primordium_sim_snapshot_json(sim);
+            let value: serde_json::Value =
+                serde_json::from_str(CStr::from_ptr(json).to_str().unwrap()).unwrap();
+            assert_eq!(value["tick"], 3);
+            assert_eq!(value["entities"].as_array().unwrap().len(), before + 2);
+            primordium_string_free(json);
+
+            primordium_sim_free(sim);
+        }
+    }
+
+    #[test]
+    fn test_errors_are_reported_not_raised() {
+        unsafe {
+            assert_eq!(
+                primordium_sim_step(std::ptr::null_mut(), 1),
+                PrimordiumStatus::NullArgument
+            );
+            assert_eq!(error(), "sim is null");
+
+            let bad = CString::new("[world]\nwidth = 0").unwrap();
+            assert!(primordium_sim_new(bad.as_ptr(), 0).is_null());
+
+            let sim = primordium_sim_new(std::ptr::null(), 0);
+            let dna = CString::new("not dna").unwrap();
+            let status = primordium_sim_inject_genome(sim, dna.as_ptr(), 1.0, 1.0, 1);
+            assert_eq!(status, PrimordiumStatus::InvalidArgument);
+
+            let key = CString::new("world.max_food").unwrap();
+            let value = CString::new("42").unwrap();
+            assert_eq!(
+                primordium_sim_set_config(sim, key.as_ptr(), value.as_ptr()),
+                PrimordiumStatus::Ok
+            );
+            assert_eq!((*sim).world.config.world.max_food, 42);
+            let key = CString::new("world.width").unwrap();
+            assert_eq!(
+                primordium_sim_set_config(sim, key.as_ptr(), value.as_ptr()),
+                PrimordiumStatus::InvalidArgument
+            );
+            primordium_sim_free(sim);
+        }
+    }
+}
@@ -0,0 +1,495 @@
+//! Shareable "creature cards".
+//!
+//! A [`CreatureCard`](crate::card::CreatureCard) sums up one organism: its
+//! vital statistics, the genes that set it apart, a miniature of its
+//! brain's wiring and its lineage's
+//! [`Crest`](primordium_core::crest::Crest), which relatives share. Cards
+//! render as plain text for chat and as a PNG for image posts.
+//!
+//! A genotype's hex runs to tens of kilobytes, far beyond what a QR code
+//! holds, so the card shows a short fingerprint and a barcode of the DNA
+//! hash instead. The full hex follows the text card on request and always
+//! travels inside the PNG as a compressed `DNA` text chunk, from where
+//! [`dna_from_png`](crate::card::dna_from_png) recovers it.
+
+use primordium_core::crest::Crest;
+use primordium_core::lifecycle;
+use primordium_data::{Brain, Entity, NodeType};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Cells per side of the brain miniature.
+pub const MINIATURE_SIZE: usize = 12;
+
+/// PNG text chunk holding the DNA hex.
+pub const DNA_CHUNK: &str = "DNA";
+
+/// Pixels per font pixel, crest cell and miniature cell of the PNG.
+const SCALE: u32 = 2;
+const MARGIN: u32 = 8 * SCALE;
+const COLUMNS: u32 = 44;
+const CHAR_WIDTH: u32 = 4 * SCALE;
+const LINE_HEIGHT: u32 = 7 * SCALE;
+const CREST_CELL: u32 = 6 * SCALE;
+const MINIATURE_CELL: u32 = 4 * SCALE;
+const BARCODE_HEIGHT: u32 = 12 * SCALE;
+
+const BACKGROUND: [u8; 3] = [20, 22, 28];
+const TEXT: [u8; 3] = [225, 225, 225];
+const LABEL: [u8; 3] = [140, 140, 155];
+const EXCITATORY: [u8; 3] = [80, 220, 120];
+const INHIBITORY: [u8; 3] = [230, 80, 80];
+
+/// One organism, ready to be rendered as a card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreatureCard {
+    pub name: String,
+    pub id: Uuid,
+    pub lineage_id: Uuid,
+    pub crest: Crest,
+    pub generation: u32,
+    pub age: u64,
+    pub energy: f64,
+    pub max_energy: f64,
+    pub offspring: u32,
+    pub trophic_potential: f32,
+    /// Notable genes as label and formatted value.
+    pub genes: Vec<(&'static str, String)>,
+    pub brain_nodes: usize,
+    pub brain_connections: usize,
+    /// Summed weights between groups of nodes: rows group the inputs then
+    /// hidden nodes, columns the hidden nodes then outputs.
+    pub miniature: [[f32; MINIATURE_SIZE]; MINIATURE_SIZE],
+    pub dna: String,
+}
+
+impl CreatureCard {
+    /// Builds the card of `entity` at `tick`.
+    pub fn new(entity: &Entity, tick: u64) -> Self {
+        let genotype = &entity.intel.genotype;
+        let brain = &genotype.brain;
+        Self {
+            name: lifecycle::get_name(entity),
+            id: entity.identity.id,
+            lineage_id: entity.metabolism.lineage_id,
+            crest: Crest::new(entity.metabolism.lineage_id, Some(genotype)),
+            generation: entity.metabolism.generation,
+            age: tick.saturating_sub(entity.metabolism.birth_tick),
+            energy: entity.metabolism.energy,
+            max_energy: entity.metabolism.max_energy,
+            offspring: entity.metabolism.offspring_count,
+            trophic_potential: entity.metabolism.trophic_potential,
+            genes: vec![
+                ("Speed", format!("{:.2}", genotype.max_speed)),
+                ("Sensing", format!("{:.1}", genotype.sensing_range)),
+                ("Stamina", format!("{:.0}", genotype.max_energy)),
+                ("Size", format!("{:.2}", genotype.body_size)),
+                ("Niche", format!("{:.2}", genotype.metabolic_niche)),
+                ("Maturity", format!("{:.2}", genotype.maturity_gene)),
+                ("Invest", format!("{:.2}", genotype.reproductive_investment)),
+                ("Egg-lay", format!("{:.2}", genotype.oviparity)),
+                ("Care", format!("{:.2}", genotype.parental_care)),
+            ],
+            brain_nodes: brain.nodes.len(),
+            brain_connections: brain.connections.iter().filter(|c| c.enabled).count(),
+            miniature: miniature(brain),
+            dna: genotype.to_hex(),
+        }
+    }
+
+    pub fn role(&self) -> &'static str {
+        if self.trophic_potential < 0.35 {
+            "Herbivore"
+        } else if self.trophic_potential > 0.65 {
+            "Carnivore"
+        } else {
+            "Omnivore"
+        }
+    }
+
+    /// First 16 hex digits of the DNA's SHA-256.
+    pub fn fingerprint(&self) -> String {
+        hex::encode(&self.dna_hash()[..8])
+    }
+
+    fn dna_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.dna.as_bytes()).into()
+    }
+
+    /// Statistics beside the crest, one per crest row.
+    fn stats(&self) -> [(&'static str, String); 5] {
+        [
+            ("Lineage", self.lineage_id.to_string()[..8].to_string()),
+            ("Role", self.role().to_string()),
+            ("Age", format!("{} ticks", self.age)),
+            (
+                "Energy",
+                format!("{:.0}/{:.0}", self.energy, self.max_energy),
+            ),
+            ("Offspring", self.offspring.to_string()),
+        ]
+    }
+
+    /// The card as text; `with_dna` appends the full DNA hex.
+    pub fn to_text(&self, with_dna: bool) -> String {
+        let [field, charge] = self.crest.emblem();
+        let mut out = format!("== {field}{charge} {} ==\n", self.name);
+        for (row, (label, value)) in self.crest.pattern.iter().zip(self.stats()) {
+            let cells: String = row.iter().map(|&on| if on { "██" } else { "  " }).collect();
+            out.push_str(&format!("  {cells}  {label:<10}{value}\n"));
+        }
+        out.push_str("\nGenes\n");
+        for pair in self.genes.chunks(2) {
+            let line: Vec<String> = pair
+                .iter()
+                .map(|(label, value)| format!("{label:<10}{value:<8}"))
+                .collect();
+            out.push_str(&format!("  {}\n", line.join("  ").trim_end()));
+        }
+        out.push_str(&format!(
+            "\nBrain ({} nodes, {} connections)\n",
+            self.brain_nodes, self.brain_connections
+        ));
+        for row in &self.miniature {
+            let cells: String = row.iter().map(|&w| weight_char(w)).collect();
+            out.push_str(&format!("  {}\n", cells.trim_end()));
+        }
+        out.push_str(&format!(
+            "\nDNA {} ({} hex digits)\n",
+            self.fingerprint(),
+            self.dna.len()
+        ));
+        if with_dna {
+            out.push_str(&self.dna);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The card as an RGB PNG carrying the DNA hex in a `DNA` text chunk.
+    pub fn to_png(&self) -> anyhow::Result<Vec<u8>> {
+        let canvas = self.draw();
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, canvas.width, canvas.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.add_ztxt_chunk(DNA_CHUNK.to_string(), self.dna.clone())?;
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(canvas.pixels.as_flattened())?;
+        writer.finish()?;
+        Ok(png)
+    }
+
+    fn draw(&self) -> Canvas {
+        let crest = &self.crest;
+        let width = 2 * MARGIN + COLUMNS * CHAR_WIDTH;
+        let crest_side = 5 * CREST_CELL;
+        let miniature_side = MINIATURE_SIZE as u32 * MINIATURE_CELL;
+        let gene_rows = self.genes.len().div_ceil(2) as u32;
+        let height = MARGIN
+            + 2 * LINE_HEIGHT
+            + crest_side.max(5 * LINE_HEIGHT)
+            + (2 + gene_rows) * LINE_HEIGHT
+            + 2 * LINE_HEIGHT
+            + miniature_side
+            + 2 * LINE_HEIGHT
+            + BARCODE_HEIGHT
+            + MARGIN;
+        let mut canvas = Canvas::new(width, height);
+
+        // Name on a band in the lineage colour.
+        canvas.fill(0, 0, width, MARGIN + LINE_HEIGHT, crest.accent);
+        canvas.text(MARGIN, MARGIN / 2, &self.name, TEXT);
+        let mut y = MARGIN + 2 * LINE_HEIGHT;
+
+        for (row, cells) in crest.pattern.iter().enumerate() {
+            for (col, &on) in cells.iter().enumerate() {
+                if on {
+                    canvas.fill(
+                        MARGIN + col as u32 * CREST_CELL,
+                        y + row as u32 * CREST_CELL,
+                        CREST_CELL,
+                        CREST_CELL,
+                        crest.tincture,
+                    );
+                }
+            }
+        }
+        let stats_x = MARGIN + crest_side + 2 * CHAR_WIDTH;
+        for (row, (label, value)) in self.stats().iter().enumerate() {
+            let line_y = y + row as u32 * LINE_HEIGHT;
+            canvas.text(stats_x, line_y, label, LABEL);
+            canvas.text(stats_x + 10 * CHAR_WIDTH, line_y, value, TEXT);
+        }
+        y += crest_side.max(5 * LINE_HEIGHT) + LINE_HEIGHT;
+
+        canvas.text(MARGIN, y, "GENES", LABEL);
+        y += LINE_HEIGHT;
+        for (i, (label, value)) in self.genes.iter().enumerate() {
+            let x = MARGIN + (i as u32 % 2) * 22 * CHAR_WIDTH;
+            let line_y = y + (i as u32 / 2) * LINE_HEIGHT;
+            canvas.text(x, line_y, label, LABEL);
+            canvas.text(x + 10 * CHAR_WIDTH, line_y, value, TEXT);
+        }
+        y += (gene_rows + 1) * LINE_HEIGHT;
+
+        canvas.text(
+            MARGIN,
+            y,
+            &format!(
+                "BRAIN  {} NODES  {} CONNECTIONS",
+                self.brain_nodes, self.brain_connections
+            ),
+            LABEL,
+        );
+        y += LINE_HEIGHT;
+        let strongest = self
+            .miniature
+            .iter()
+            .flatten()
+            .fold(0.0f32, |m, w| m.max(w.abs()))
+            .max(f32::EPSILON);
+        for (row, cells) in self.miniature.iter().enumerate() {
+            for (col, &w) in cells.iter().enumerate() {
+                let tint = if w >= 0.0 { EXCITATORY } else { INHIBITORY };
+                canvas.fill(
+                    MARGIN + col as u32 * MINIATURE_CELL,
+                    y + row as u32 * MINIATURE_CELL,
+                    MINIATURE_CELL - 1,
+                    MINIATURE_CELL - 1,
+                    blend(dim(BACKGROUND), tint, (w.abs() / strongest).sqrt()),
+                );
+            }
+        }
+        y += miniature_side + LINE_HEIGHT;
+
+        canvas.text(MARGIN, y, &format!("DNA {}", self.fingerprint()), LABEL);
+        y += LINE_HEIGHT;
+        let bar = (width - 2 * MARGIN) / 256;
+        for (i, bit) in self
+            .dna_hash()
+            .iter()
+            .flat_map(|byte| (0..8).map(move |b| byte >> (7 - b) & 1 == 1))
+            .enumerate()
+        {
+            if bit {
+                canvas.fill(MARGIN + i as u32 * bar, y, bar, BARCODE_HEIGHT, TEXT);
+            }
+        }
+        canvas
+    }
+}
+
+/// Reads the DNA hex out of a PNG card.
+pub fn dna_from_png(bytes: &[u8]) -> anyhow::Result<String> {
+    let reader = png::Decoder::new(bytes).read_info()?;
+    let info = reader.info();
+    for chunk in &info.compressed_latin1_text {
+        if chunk.keyword == DNA_CHUNK {
+            return Ok(chunk.get_text()?);
+        }
+    }
+    info.uncompressed_latin1_text
+        .iter()
+        .find(|chunk| chunk.keyword == DNA_CHUNK)
+        .map(|chunk| chunk.text.clone())
+        .ok_or_else(|| anyhow::anyhow!("PNG carries no {DNA_CHUNK} chunk"))
+}
+
+fn miniature(brain: &Brain) -> [[f32; MINIATURE_SIZE]; MINIATURE_SIZE] {
+    let layer = |t: &NodeType| match t {
+        NodeType::Input => 0,
+        NodeType::Hidden => 1,
+        NodeType::Output => 2,
+    };
+    let mut order: Vec<_> = brain.nodes.iter().collect();
+    order.sort_by_key(|n| (layer(&n.node_type), n.id));
+    // Inputs only send and outputs only receive, so each axis skips one.
+    let buckets = |skip: NodeType| -> HashMap<usize, usize> {
+        let axis: Vec<usize> = order
+            .iter()
+            .filter(|n| n.node_type != skip)
+            .map(|n| n.id)
+            .collect();
+        axis.iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i * MINIATURE_SIZE / axis.len()))
+            .collect()
+    };
+    let (sources, targets) = (buckets(NodeType::Output), buckets(NodeType::Input));
+    let mut grid = [[0.0; MINIATURE_SIZE]; MINIATURE_SIZE];
+    for c in brain.connections.iter().filter(|c| c.enabled) {
+        if let (Some(&from), Some(&to)) = (sources.get(&c.from), targets.get(&c.to)) {
+            grid[from][to] += c.weight;
+        }
+    }
+    grid
+}
+
+fn weight_char(w: f32) -> char {
+    match (w >= 0.0, w.abs()) {
+        (_, m) if m < 0.05 => ' ',
+        (true, m) if m < 0.5 => '.',
+        (true, m) if m < 1.5 => '+',
+        (true, _) => '#',
+        (false, m) if m < 0.5 => ',',
+        (false, m) if m < 1.5 => '-',
+        (false, _) => '=',
+    }
+}
+
+fn dim([r, g, b]: [u8; 3]) -> [u8; 3] {
+    [r / 3, g / 3, b / 3]
+}
+
+fn blend(a: [u8; 3], b: [u8; 3], t: f32) -> [u8; 3] {
+    let t = t.clamp(0.0, 1.0);
+    std::array::from_fn(|i| (f32::from(a[i]) + (f32::from(b[i]) - f32::from(a[i])) * t) as u8)
+}
+
+/// An RGB image to draw the PNG card on.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![BACKGROUND; (width * height) as usize],
+        }
+    }
+
+    fn fill(&mut self, x: u32, y: u32, w: u32, h: u32, color: [u8; 3]) {
+        for py in y..(y + h).min(self.height) {
+            for px in x..(x + w).min(self.width) {
+                self.pixels[(py * self.width + px) as usize] = color;
+            }
+        }
+    }
+
+    /// Draws `text` in the built-in 3x5 font; letters print as capitals.
+    fn text(&mut self, x: u32, y: u32, text: &str, color: [u8; 3]) {
+        for (i, c) in text.chars().enumerate() {
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..3 {
+                    if bits >> (2 - col) & 1 == 1 {
+                        self.fill(
+                            x + i as u32 * CHAR_WIDTH + col * SCALE,
+                            y + row as u32 * SCALE,
+                            SCALE,
+                            SCALE,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Rows of a 3x5 glyph, most significant bit leftmost.
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        ' ' => [0, 0, 0, 0, 0],
+        '0' => [7, 5, 5, 5, 7],
+        '1' => [2, 6, 2, 2, 7],
+        '2' => [7, 1, 7, 4, 7],
+        '3' => [7, 1, 7, 1, 7],
+        '4' => [5, 5, 7, 1, 1],
+        '5' => [7, 4, 7, 1, 7],
+        '6' => [7, 4, 7, 5, 7],
+        '7' => [7, 1, 1, 2, 2],
+        '8' => [7, 5, 7, 5, 7],
+        '9' => [7, 5, 7, 1, 7],
+        'A' => [2, 5, 7, 5, 5],
+        'B' => [6, 5, 6, 5, 6],
+        'C' => [3, 4, 4, 4, 3],
+        'D' => [6, 5, 5, 5, 6],
+        'E' => [7, 4, 6, 4, 7],
+        'F' => [7, 4, 6, 4, 4],
+        'G' => [3, 4, 5, 5, 3],
+        'H' => [5, 5, 7, 5, 5],
+        'I' => [7, 2, 2, 2, 7],
+        'J' => [1, 1, 1, 5, 2],
+        'K' => [5, 5, 6, 5, 5],
+        'L' => [4, 4, 4, 4, 7],
+        'M' => [5, 7, 7, 5, 5],
+        'N' => [6, 5, 5, 5, 5],
+        'O' => [2, 5, 5, 5, 2],
+        'P' => [6, 5, 6, 4, 4],
+        'Q' => [2, 5, 5, 6, 3],
+        'R' => [6, 5, 6, 5, 5],
+        'S' => [3, 4, 2, 1, 6],
+        'T' => [7, 2, 2, 2, 2],
+        'U' => [5, 5, 5, 5, 7],
+        'V' => [5, 5, 5, 5, 2],
+        'W' => [5, 5, 7, 7, 5],
+        'X' => [5, 5, 2, 5, 5],
+        'Y' => [5, 5, 2, 2, 2],
+        'Z' => [7, 1, 2, 4, 7],
+        '.' => [0, 0, 0, 0, 2],
+        ',' => [0, 0, 0, 2, 4],
+        ':' => [0, 2, 0, 2, 0],
+        '-' => [0, 0, 7, 0, 0],
+        '+' => [0, 2, 7, 2, 0],
+        '=' => [0, 7, 0, 7, 0],
+        '/' => [1, 1, 2, 4, 4],
+        '%' => [5, 1, 2, 4, 5],
+        '(' => [1, 2, 2, 2, 1],
+        ')' => [4, 2, 2, 2, 4],
+        '#' => [5, 7, 5, 7, 5],
+        '_' => [0, 0, 0, 0, 7],
+        _ => [7, 1, 2, 0, 2],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn card() -> CreatureCard {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut entity = lifecycle::create_entity_with_rng(10.0, 10.0, 0, &mut rng);
+        entity.metabolism.generation = 4;
+        entity.metabolism.offspring_count = 2;
+        CreatureCard::new(&entity, 120)
+    }
+
+    #[test]
+    fn test_text_card_shows_stats_brain_and_dna() {
+        let card = card();
+        let text = card.to_text(false);
+        assert!(text.starts_with("== ") && text.lines().next().unwrap().contains(&card.name));
+        assert!(text.contains("Age       120 ticks"));
+        assert!(text.contains("Offspring 2"));
+        assert!(text.contains(&format!("{} nodes", card.brain_nodes)));
+        assert!(text.contains(&card.fingerprint()));
+        assert!(!text.contains(&card.dna));
+        assert!(card.to_text(true).trim_end().ends_with(&card.dna));
+        assert!(card.miniature.iter().flatten().any(|&w| w != 0.0));
+    }
+
+    #[test]
+    fn test_png_card_carries_its_dna() {
+        let card = card();
+        let png = card.to_png().unwrap();
+        assert_eq!(dna_from_png(&png).unwrap(), card.dna);
+
+        let decoder = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        assert_eq!(decoder.info().width, 2 * MARGIN + COLUMNS * CHAR_WIDTH);
+        assert!(dna_from_png(
+            &crate::thumbnail::Thumbnail::render(
+                &primordium_core::terrain::TerrainGrid::generate(8, 8, 1),
+                []
+            )
+            .to_png()
+        )
+        .is_err());
+    }
+}
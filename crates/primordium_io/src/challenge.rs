@@ -0,0 +1,244 @@
+//! Community challenge scenarios shared through the registry.
+//!
+//! A challenge is a TOML file naming a seed, a run length, config overrides
+//! and the targets a run has to reach. Runs are deterministic, so everyone
+//! who runs a challenge starts from the same world, and a result can be
+//! checked against the scenario text it claims to come from.
+//!
+//! ```toml
+//! id = "crowded-garden"
+//! title = "Crowded Garden"
+//! seed = 42
+//! ticks = 2000
+//! score = "population"
+//!
+//! [[targets]]
+//! metric = "species"
+//! at_least = 3
+//!
+//! [config.world]
+//! width = 60
+//! height = 30
+//! ```
+
+use primordium_core::config::AppConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Longest run a challenge may ask for.
+pub const MAX_CHALLENGE_TICKS: u64 = 1_000_000;
+
+/// A measurement taken from the world when a challenge run ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChallengeMetric {
+    Population,
+    /// Lineages still alive.
+    Species,
+    /// Highest generation reached at any point in the run.
+    MaxGeneration,
+    TopFitness,
+    AvgFitness,
+    CarbonLevel,
+    /// Ticks simulated before the run ended or the population died out.
+    SurvivedTicks,
+}
+
+impl ChallengeMetric {
+    pub fn label(self) -> &'static str {
+        match self {
+            ChallengeMetric::Population => "population",
+            ChallengeMetric::Species => "species",
+            ChallengeMetric::MaxGeneration => "max_generation",
+            ChallengeMetric::TopFitness => "top_fitness",
+            ChallengeMetric::AvgFitness => "avg_fitness",
+            ChallengeMetric::CarbonLevel => "carbon_level",
+            ChallengeMetric::SurvivedTicks => "survived_ticks",
+        }
+    }
+}
+
+/// A bound a metric has to meet for a run to pass.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Target {
+    pub metric: ChallengeMetric,
+    pub at_least: Option<f64>,
+    pub at_most: Option<f64>,
+}
+
+impl Target {
+    #[must_use]
+    pub fn is_met(&self, value: f64) -> bool {
+        self.at_least.is_none_or(|min| value >= min) && self.at_most.is_none_or(|max| value <= max)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Challenge {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub seed: u64,
+    pub ticks: u64,
+    /// Metric runs are ranked by on the leaderboard; higher is better.
+    pub score: ChallengeMetric,
+    #[serde(default)]
+    pub targets: Vec<Target>,
+    /// Overrides applied on top of the default config.
+    #[serde(default)]
+    pub config: toml::Table,
+}
+
+/// Score and pass/fail of a run's metrics against a challenge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Evaluation {
+    pub score: f64,
+    pub passed: bool,
+}
+
+/// What a challenge run reports to the registry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChallengeResult {
+    pub ticks: u64,
+    /// `World::deterministic_hash` of the final state.
+    pub world_hash: String,
+    pub metrics: BTreeMap<ChallengeMetric, f64>,
+    /// [`result_signature`] over the scenario, final state and metrics.
+    pub signature: String,
+    /// The run that produced the result; not covered by the signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<primordium_data::RunManifest>,
+}
+
+impl Challenge {
+    pub fn from_toml(content: &str) -> anyhow::Result<Self> {
+        let challenge: Challenge = toml::from_str(content)?;
+        anyhow::ensure!(
+            (1..=MAX_CHALLENGE_TICKS).contains(&challenge.ticks),
+            "Challenge '{}' must run between 1 and {} ticks",
+            challenge.id,
+            MAX_CHALLENGE_TICKS
+        );
+        for target in &challenge.targets {
+            anyhow::ensure!(
+                target.at_least.is_some() || target.at_most.is_some(),
+                "Target on {} in challenge '{}' needs at_least or at_most",
+                target.metric.label(),
+                challenge.id
+            );
+        }
+        challenge.app_config()?;
+        Ok(challenge)
+    }
+
+    /// The default config with this challenge's overrides, seed and
+    /// deterministic mode applied.
+    pub fn app_config(&self) -> anyhow::Result<AppConfig> {
+        let mut table = toml::Table::try_from(AppConfig::default())?;
+        merge_tables(&mut table, &self.config);
+        let mut config: AppConfig = table.try_into()?;
+        config.world.seed = Some(self.seed);
+        config.world.deterministic = true;
+        config.validate()?;
+        Ok(config)
+    }
+
+    #[must_use]
+    pub fn evaluate(&self, metrics: &BTreeMap<ChallengeMetric, f64>) -> Evaluation {
+        Evaluation {
+            score: metrics.get(&self.score).copied().unwrap_or(0.0),
+            passed: self
+                .targets
+                .iter()
+                .all(|t| metrics.get(&t.metric).is_some_and(|&v| t.is_met(v))),
+        }
+    }
+}
+
+/// Overwrites the values in `base` with those in `overrides`, descending
+/// into tables present in both.
+pub fn merge_tables(base: &mut toml::Table, overrides: &toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overrides)) => {
+                merge_tables(base, overrides)
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Ties a result to the exact scenario text and final world state it came
+/// from. Anyone holding the scenario can recompute it.
+#[must_use]
+pub fn result_signature(
+    scenario_toml: &str,
+    world_hash: &str,
+    metrics: &BTreeMap<ChallengeMetric, f64>,
+) -> String {
+    let metrics: Vec<String> = metrics
+        .iter()
+        .map(|(metric, value)| format!("{}={value}", metric.label()))
+        .collect();
+    primordium_net::fingerprint([scenario_toml, world_hash, &metrics.join(";")])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENARIO: &str = r#"
+        id = "crowded-garden"
+        title = "Crowded Garden"
+        seed = 42
+        ticks = 200
+        score = "population"
+
+        [[targets]]
+        metric = "species"
+        at_least = 3
+
+        [config.world]
+        width = 60
+        initial_population = 20
+    "#;
+
+    #[test]
+    fn test_challenge_overrides_config_and_evaluates_targets() {
+        let challenge = Challenge::from_toml(SCENARIO).unwrap();
+        let config = challenge.app_config().unwrap();
+        assert_eq!(config.world.width, 60);
+        assert_eq!(config.world.height, AppConfig::default().world.height);
+        assert_eq!(config.world.initial_population, 20);
+        assert_eq!(config.world.seed, Some(42));
+        assert!(config.world.deterministic);
+
+        let mut metrics = BTreeMap::from([
+            (ChallengeMetric::Population, 35.0),
+            (ChallengeMetric::Species, 2.0),
+        ]);
+        let evaluation = challenge.evaluate(&metrics);
+        assert_eq!((evaluation.score, evaluation.passed), (35.0, false));
+        metrics.insert(ChallengeMetric::Species, 3.0);
+        assert!(challenge.evaluate(&metrics).passed);
+
+        let signature = result_signature(SCENARIO, "abc", &metrics);
+        assert_eq!(signature, result_signature(SCENARIO, "abc", &metrics));
+        assert_ne!(signature, result_signature(SCENARIO, "abd", &metrics));
+        metrics.insert(ChallengeMetric::Population, 36.0);
+        assert_ne!(signature, result_signature(SCENARIO, "abc", &metrics));
+    }
+
+    #[test]
+    fn test_challenge_rejects_unbounded_targets_and_bad_overrides() {
+        let unbounded = SCENARIO.replace("at_least = 3", "");
+        assert!(Challenge::from_toml(&unbounded).is_err());
+        let mistyped = SCENARIO.replace("width = 60", "width = \"wide\"");
+        assert!(Challenge::from_toml(&mistyped).is_err());
+        let endless = SCENARIO.replace("ticks = 200", "ticks = 0");
+        assert!(Challenge::from_toml(&endless).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! The full narrative history of a run, kept as an append-only JSONL file.
+//!
+//! The scribe keeps only its most recent narrations in memory. With a
+//! [`ChronicleWriter`](crate::chronicle::ChronicleWriter) every narration
+//! is also appended to `chronicle.jsonl` in the run's log directory, one
+//! JSON object per line, and
+//! [`Chronicle::load`](crate::chronicle::Chronicle::load) reads the whole
+//! story back, e.g. for the archeology view.
+
+use anyhow::Context;
+use primordium_data::Narration;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the chronicle inside a log directory.
+pub const CHRONICLE_FILE: &str = "chronicle.jsonl";
+
+/// Appends narrations to a chronicle file.
+#[derive(Debug)]
+pub struct ChronicleWriter {
+    path: PathBuf,
+    file: File,
+}
+
+impl ChronicleWriter {
+    /// Opens the chronicle at `path` for appending, creating it and its
+    /// directory if needed.
+    pub fn open(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Cannot open chronicle {}", path.display()))?;
+        Ok(Self { path, file })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes `narration` as one line, in a single write so that a crash
+    /// leaves at most a truncated last line.
+    pub fn append(&mut self, narration: &Narration) -> anyhow::Result<()> {
+        let mut line = serde_json::to_vec(narration)?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        Ok(())
+    }
+}
+
+/// Every narration of a run, oldest first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Chronicle {
+    pub narrations: Vec<Narration>,
+}
+
+impl Chronicle {
+    /// Reads the chronicle at `path`. A missing file is an empty chronicle
+    /// and lines that do not parse, such as one cut short by a crash, are
+    /// skipped.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut narrations = Vec::new();
+        for line in BufReader::new(file).lines() {
+            if let Ok(narration) = serde_json::from_str::<Narration>(&line?) {
+                narrations.push(narration);
+            }
+        }
+        Ok(Self { narrations })
+    }
+
+    /// The chronicle of the run logging to `log_dir`.
+    pub fn load_from_dir(log_dir: &str) -> anyhow::Result<Self> {
+        Self::load(&Path::new(log_dir).join(CHRONICLE_FILE))
+    }
+
+    /// Narrations written at or before `tick`.
+    pub fn until(&self, tick: u64) -> &[Narration] {
+        let end = self.narrations.partition_point(|n| n.tick <= tick);
+        &self.narrations[..end]
+    }
+
+    pub fn len(&self) -> usize {
+        self.narrations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.narrations.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn narration(tick: u64, text: &str) -> Narration {
+        Narration {
+            tick,
+            event_type: "NewEra".to_string(),
+            text: text.to_string(),
+            severity: 0.7,
+        }
+    }
+
+    #[test]
+    fn test_chronicle_appends_across_writers_and_skips_torn_lines() {
+        let dir =
+            std::env::temp_dir().join(format!("primordium_chronicle_{}", uuid::Uuid::new_v4()));
+        let path = dir.join(CHRONICLE_FILE);
+
+        let mut writer = ChronicleWriter::open(&path).unwrap();
+        writer.append(&narration(10, "A dawn")).unwrap();
+        drop(writer);
+        let mut writer = ChronicleWriter::open(&path).unwrap();
+        writer.append(&narration(40, "A dusk")).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"tick\":50,\"event_")
+            .unwrap();
+
+        let chronicle = Chronicle::load_from_dir(dir.to_str().unwrap()).unwrap();
+        assert_eq!(
+            chronicle.narrations,
+            vec![narration(10, "A dawn"), narration(40, "A dusk")]
+        );
+        assert_eq!(chronicle.until(39).len(), 1);
+        assert!(Chronicle::load(&dir.join("missing.jsonl"))
+            .unwrap()
+            .is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,135 @@
+//! Periodic full-genome dumps of the living population.
+//!
+//! Each dump holds every living genotype at one tick as gzip-compressed
+//! JSON in `tick_<tick>.json.gz`. The archive directory's `index.jsonl`
+//! lists one [`GenomeDumpEntry`](crate::genome_archive::GenomeDumpEntry)
+//! per dump, so offline tools can walk the evolutionary trajectory without
+//! opening every file, and a world can be reseeded from any dump with
+//! [`GenomeArchive::load_at_or_before`](crate::genome_archive::GenomeArchive::load_at_or_before).
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use primordium_core::brain::topology;
+use primordium_data::Genotype;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const INDEX_FILE: &str = "index.jsonl";
+
+/// One living organism in a dump.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedGenome {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub generation: u32,
+    pub genotype: Genotype,
+}
+
+/// Index line describing one dump.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenomeDumpEntry {
+    pub tick: u64,
+    /// File name of the dump, relative to the archive directory.
+    pub file: String,
+    pub population: usize,
+    pub lineages: usize,
+}
+
+/// Directory of genome dumps and their index.
+#[derive(Debug, Clone)]
+pub struct GenomeArchive {
+    dir: PathBuf,
+}
+
+impl GenomeArchive {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes the dump for `tick` and appends it to the index.
+    pub fn write(&self, tick: u64, genomes: &[ArchivedGenome]) -> Result<GenomeDumpEntry> {
+        std::fs::create_dir_all(&self.dir)?;
+        let file = format!("tick_{tick:010}.json.gz");
+        let path = self.dir.join(&file);
+        let tmp_path = path.with_extension("tmp");
+        {
+            let mut encoder = GzEncoder::new(File::create(&tmp_path)?, Compression::default());
+            serde_json::to_writer(&mut encoder, genomes)?;
+            encoder.finish()?;
+        }
+        std::fs::rename(tmp_path, &path)?;
+
+        let lineages: HashSet<Uuid> = genomes.iter().map(|g| g.genotype.lineage_id).collect();
+        let entry = GenomeDumpEntry {
+            tick,
+            file,
+            population: genomes.len(),
+            lineages: lineages.len(),
+        };
+        let mut index = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(INDEX_FILE))?;
+        writeln!(index, "{}", serde_json::to_string(&entry)?)?;
+        Ok(entry)
+    }
+
+    /// All dumps in tick order; empty if nothing was archived yet.
+    pub fn index(&self) -> Result<Vec<GenomeDumpEntry>> {
+        let path = self.dir.join(INDEX_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut entries = Vec::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if let Ok(entry) = serde_json::from_str::<GenomeDumpEntry>(&line) {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by_key(|e| e.tick);
+        Ok(entries)
+    }
+
+    /// Reads one dump.
+    pub fn load(&self, entry: &GenomeDumpEntry) -> Result<Vec<ArchivedGenome>> {
+        let path = self.dir.join(&entry.file);
+        let file = File::open(&path)
+            .with_context(|| format!("Failed to open genome dump {}", path.display()))?;
+        let mut genomes: Vec<ArchivedGenome> = serde_json::from_reader(GzDecoder::new(file))
+            .with_context(|| format!("Failed to decode genome dump {}", path.display()))?;
+        for genome in &mut genomes {
+            topology::pad_to_current(&mut genome.genotype.brain);
+        }
+        Ok(genomes)
+    }
+
+    /// Reads the last dump taken at or before `tick`, with its index entry.
+    pub fn load_at_or_before(&self, tick: u64) -> Result<(GenomeDumpEntry, Vec<ArchivedGenome>)> {
+        let entry = self
+            .index()?
+            .into_iter()
+            .rev()
+            .find(|e| e.tick <= tick)
+            .with_context(|| {
+                format!(
+                    "No genome dump at or before tick {tick} in {}",
+                    self.dir.display()
+                )
+            })?;
+        let genomes = self.load(&entry)?;
+        Ok((entry, genomes))
+    }
+}
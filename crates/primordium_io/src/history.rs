@@ -7,7 +7,7 @@ use flate2::Compression;
 use primordium_data::{Entity, FossilRegistry, Legend, LiveEvent, PopulationStats};
 use sha2::{Digest, Sha256};
 use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 
 /// Trait for adding persistence capabilities to [`FossilRegistry`].
 pub trait FossilPersistence {
@@ -63,11 +63,76 @@ impl FossilPersistence for FossilRegistry {
     }
 }
 
+/// Location of one [`LiveEvent::Snapshot`] record in `live.jsonl`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotEntry {
+    pub tick: u64,
+    /// Byte offset of the record's line.
+    pub offset: u64,
+    /// Length of the line in bytes, without the newline.
+    pub len: u64,
+}
+
+/// Byte index of the snapshots in `live.jsonl`.
+///
+/// Built with [`HistoryLogger::index_snapshots`], which only scans the part of
+/// the log written since the last call, so individual snapshots can be read on
+/// demand with [`HistoryLogger::read_snapshot`] instead of loading the whole
+/// history.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotIndex {
+    /// Snapshots in log order.
+    pub entries: Vec<SnapshotEntry>,
+    /// Bytes of the log already scanned.
+    scanned: u64,
+    /// Whether the last scan reached the end of the log.
+    complete: bool,
+}
+
+impl SnapshotIndex {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, position: usize) -> Option<&SnapshotEntry> {
+        self.entries.get(position)
+    }
+
+    /// Whether the last scan reached the end of the log.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Position of the last snapshot taken at or before `tick`.
+    pub fn position_at_or_before(&self, tick: u64) -> Option<usize> {
+        self.entries
+            .partition_point(|e| e.tick <= tick)
+            .checked_sub(1)
+    }
+}
+
+/// Just enough of a snapshot record to index it.
+#[derive(serde::Deserialize)]
+struct SnapshotHeader {
+    tick: u64,
+}
+
+const SNAPSHOT_PREFIX: &[u8] = br#"{"event":"Snapshot""#;
+
 use std::sync::mpsc::{self, Sender};
 use std::thread;
 
+use crate::genome_archive::{ArchivedGenome, GenomeArchive};
 use crate::registry::LineagePersistence;
+use crate::rotation::{LogRotator, LogUsage, UsageGauge, LIVE_LOG};
+use primordium_core::config::LogsConfig;
+use primordium_core::interaction::InteractionGraph;
 use primordium_core::lineage_registry::LineageRegistry;
+use primordium_core::snapshot::WorldSnapshot;
 
 /// Commands for the background logging thread.
 pub enum LogCommand {
@@ -81,6 +146,10 @@ pub enum LogCommand {
     SaveFossils(FossilRegistry, String),
     /// Synchronise both registries to the SQLite storage backend.
     SyncToStorage(LineageRegistry, FossilRegistry),
+    /// Write a full-genome dump for the given tick.
+    ArchiveGenomes(GenomeArchive, u64, Vec<ArchivedGenome>),
+    /// Write the interaction graph as `interaction_graph.dot` and `.csv`.
+    SaveInteractionGraph(InteractionGraph),
     /// Shutdown the logging thread.
     Stop,
 }
@@ -88,9 +157,12 @@ pub enum LogCommand {
 /// Asynchronous logger for simulation events and historical records.
 ///
 /// Uses a background thread to prevent disk I/O from blocking the main simulation loop.
+/// `live.jsonl` is rotated and the directory kept under quota as described in
+/// [`crate::rotation`].
 pub struct HistoryLogger {
     sender: Option<Sender<LogCommand>>,
     log_dir: String,
+    usage: Option<UsageGauge>,
     /// Handle to the SQLite storage manager, if available.
     pub storage: Option<StorageManager>,
 }
@@ -103,6 +175,12 @@ impl HistoryLogger {
 
     /// Creates a new logger at the specified directory.
     pub fn new_at(dir: &str) -> Result<Self> {
+        Self::with_rotation(dir, LogsConfig::default())
+    }
+
+    /// Creates a new logger at the specified directory that rotates its logs
+    /// as `logs` configures.
+    pub fn with_rotation(dir: &str, logs: LogsConfig) -> Result<Self> {
         if !std::path::Path::new(dir).exists() {
             std::fs::create_dir_all(dir)?;
         }
@@ -112,15 +190,24 @@ impl HistoryLogger {
 
         let storage = StorageManager::new(format!("{}/world.db", dir)).ok();
         let storage_sender = storage.as_ref().map(|s| s.clone_sender());
+        let usage = UsageGauge::new(&logs);
+        let gauge = usage.clone();
 
         thread::spawn(move || {
-            let file_path = format!("{}/live.jsonl", dir_clone);
-            let mut live_file = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(file_path)
-                .map(BufWriter::new)
-                .ok();
+            let file_path = format!("{}/{}", dir_clone, LIVE_LOG);
+            let open_live = || {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&file_path)
+                    .map(BufWriter::new)
+                    .ok()
+            };
+            let mut live_file = open_live();
+            let mut rotator = LogRotator::new(&dir_clone, logs, gauge);
+            // Repeated at the head of every rotated log so each one is
+            // self-describing.
+            let mut provenance: Option<String> = None;
 
             let legend_path = format!("{}/legends.json", dir_clone);
             let mut legend_file = OpenOptions::new()
@@ -137,6 +224,26 @@ impl HistoryLogger {
                             if let Ok(json) = serde_json::to_string(&ev) {
                                 let _ = writeln!(f, "{}", json);
                                 let _ = f.flush();
+                                rotator.record(json.len() as u64 + 1);
+                                if let LiveEvent::Provenance { .. } = ev {
+                                    provenance = Some(json);
+                                }
+                            }
+                        }
+                        if rotator.rotation_due() {
+                            drop(live_file.take());
+                            if let Err(e) = rotator.rotate() {
+                                eprintln!("HistoryLogger: Error rotating live log: {}", e);
+                            }
+                            live_file = open_live();
+                            if let (Some(f), Some(json)) = (live_file.as_mut(), &provenance) {
+                                let _ = writeln!(f, "{}", json);
+                                let _ = f.flush();
+                                rotator.record(json.len() as u64 + 1);
+                            }
+                        } else if rotator.quota_check_due() {
+                            if let Err(e) = rotator.enforce_quota() {
+                                eprintln!("HistoryLogger: Error enforcing log quota: {}", e);
                             }
                         }
                     }
@@ -174,6 +281,20 @@ impl HistoryLogger {
                                 tx.send(crate::storage::StorageCommand::BatchSyncFossils(fos_reg));
                         }
                     }
+                    LogCommand::ArchiveGenomes(archive, tick, genomes) => {
+                        if let Err(e) = archive.write(tick, &genomes) {
+                            eprintln!("HistoryLogger: Error archiving genomes: {}", e);
+                        }
+                    }
+                    LogCommand::SaveInteractionGraph(graph) => {
+                        let dot_path = format!("{}/interaction_graph.dot", dir_clone);
+                        let csv_path = format!("{}/interaction_graph.csv", dir_clone);
+                        if let Err(e) = std::fs::write(dot_path, graph.to_dot())
+                            .and_then(|_| std::fs::write(csv_path, graph.to_csv()))
+                        {
+                            eprintln!("HistoryLogger: Error saving interaction graph: {}", e);
+                        }
+                    }
                     LogCommand::Stop => break,
                 }
             }
@@ -182,6 +303,7 @@ impl HistoryLogger {
         Ok(Self {
             sender: Some(tx),
             log_dir: dir.to_string(),
+            usage: Some(usage),
             storage,
         })
     }
@@ -191,10 +313,17 @@ impl HistoryLogger {
         Self {
             sender: None,
             log_dir: "".to_string(),
+            usage: None,
             storage: None,
         }
     }
 
+    /// Size of the logs directory as last measured by the logging thread.
+    /// `None` for a dummy logger.
+    pub fn disk_usage(&self) -> Option<LogUsage> {
+        self.usage.as_ref().map(UsageGauge::get)
+    }
+
     /// Queues a live event for logging.
     pub fn log_event(&self, event: LiveEvent) -> Result<()> {
         if let Some(ref tx) = self.sender {
@@ -227,6 +356,27 @@ impl HistoryLogger {
         Ok(())
     }
 
+    /// Triggers an asynchronous full-genome dump.
+    pub fn archive_genomes_async(
+        &self,
+        archive: GenomeArchive,
+        tick: u64,
+        genomes: Vec<ArchivedGenome>,
+    ) -> Result<()> {
+        if let Some(ref tx) = self.sender {
+            let _ = tx.send(LogCommand::ArchiveGenomes(archive, tick, genomes));
+        }
+        Ok(())
+    }
+
+    /// Triggers an asynchronous export of the interaction graph.
+    pub fn save_interaction_graph_async(&self, graph: InteractionGraph) -> Result<()> {
+        if let Some(ref tx) = self.sender {
+            let _ = tx.send(LogCommand::SaveInteractionGraph(graph));
+        }
+        Ok(())
+    }
+
     /// Synchronises registries with persistent storage in the background.
     pub fn sync_to_storage_async(
         &self,
@@ -286,12 +436,87 @@ impl HistoryLogger {
             if let Ok(LiveEvent::Snapshot { tick, stats, .. }) =
                 serde_json::from_str::<LiveEvent>(&l)
             {
-                snapshots.push((tick, stats));
+                snapshots.push((tick, *stats));
             }
         }
         Ok(snapshots)
     }
 
+    /// Extends `index` with the snapshots written since it was last updated,
+    /// scanning at most about `max_bytes` of the log. Returns how many
+    /// snapshots were added.
+    ///
+    /// A partially written last line is left for the next call. If the log
+    /// was truncated or replaced the index is rebuilt from the start.
+    pub fn index_snapshots(&self, index: &mut SnapshotIndex, max_bytes: u64) -> Result<usize> {
+        let file_path = format!("{}/live.jsonl", self.log_dir);
+        let mut file = match File::open(file_path) {
+            Ok(f) => f,
+            Err(_) => {
+                *index = SnapshotIndex::default();
+                index.complete = true;
+                return Ok(0);
+            }
+        };
+        let file_len = file.metadata()?.len();
+        if file_len < index.scanned {
+            *index = SnapshotIndex::default();
+        }
+        file.seek(SeekFrom::Start(index.scanned))?;
+
+        let mut reader = BufReader::new(file);
+        let mut line = Vec::new();
+        let before = index.entries.len();
+        let start = index.scanned;
+        index.complete = false;
+        loop {
+            if index.scanned - start >= max_bytes {
+                return Ok(index.entries.len() - before);
+            }
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)? as u64;
+            if read == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            if line.starts_with(SNAPSHOT_PREFIX) {
+                if let Ok(header) = serde_json::from_slice::<SnapshotHeader>(&line) {
+                    index.entries.push(SnapshotEntry {
+                        tick: header.tick,
+                        offset: index.scanned,
+                        len: read - 1,
+                    });
+                }
+            }
+            index.scanned += read;
+        }
+        index.complete = true;
+        Ok(index.entries.len() - before)
+    }
+
+    /// Reads the snapshot recorded at `entry`.
+    pub fn read_snapshot(&self, entry: &SnapshotEntry) -> Result<PopulationStats> {
+        let file_path = format!("{}/live.jsonl", self.log_dir);
+        let mut file = File::open(file_path)?;
+        file.seek(SeekFrom::Start(entry.offset))?;
+        let mut line = vec![0; entry.len as usize];
+        file.read_exact(&mut line)?;
+        match serde_json::from_slice::<LiveEvent>(&line)? {
+            LiveEvent::Snapshot { stats, .. } => Ok(*stats),
+            _ => anyhow::bail!("No snapshot at offset {}", entry.offset),
+        }
+    }
+
+    /// Rebuilds the last world snapshot stored at or before `tick`, waiting
+    /// for the storage thread. `None` without storage or stored snapshots.
+    pub fn load_world_snapshot(&self, tick: u64) -> Option<WorldSnapshot> {
+        self.storage
+            .as_ref()?
+            .query_snapshot_async(tick)?
+            .recv()
+            .ok()
+            .flatten()
+    }
+
     /// Loads the most recent N snapshots from the event log.
     pub fn get_snapshots_recent(&self, limit: usize) -> Result<Vec<(u64, PopulationStats)>> {
         let file_path = format!("{}/live.jsonl", self.log_dir);
@@ -305,7 +530,7 @@ impl HistoryLogger {
             if let Ok(LiveEvent::Snapshot { tick, stats, .. }) =
                 serde_json::from_str::<LiveEvent>(&l)
             {
-                all_snapshots.push((tick, stats));
+                all_snapshots.push((tick, *stats));
             }
         }
         if all_snapshots.len() > limit {
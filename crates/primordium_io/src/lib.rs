@@ -9,8 +9,16 @@
 //! - Historical data logging
 //! - Network communication protocols
 
+/// Shareable text and PNG cards of a single organism
+pub mod card;
+/// Community challenge scenarios, their targets and result signatures
+pub mod challenge;
+/// Append-only JSONL record of every narration in a run
+pub mod chronicle;
 /// Error types and result aliases for I/O operations
 pub mod error;
+/// Periodic compressed dumps of every living genotype, with an index
+pub mod genome_archive;
 /// Historical event logging, fossil records, and simulation history storage
 pub mod history;
 /// Lineage tracking, dynastic success metrics, and shared memory persistence
@@ -19,12 +27,26 @@ pub mod lineage;
 pub mod network;
 /// Core persistence utilities and save file management
 pub mod persistence;
+/// Cumulative player statistics, personal records and achievements
+pub mod profile;
 /// In-memory and on-disk registries for entities and lineages
 pub mod registry;
+/// Checkpointed recordings of deterministic runs for bit-exact re-simulation
+pub mod replay;
+/// Rotation, compression and disk quota for the logs directory
+pub mod rotation;
+/// Scored gallery of the most interesting seeds from finished runs
+pub mod seed_gallery;
 /// Validated serialization helpers for JSON and HexDNA formats
 pub mod serialization;
+/// Locality-sensitive hashing for finding near-duplicate genomes
+pub mod similarity;
+/// Keyframe and delta encoding of stored world snapshots
+pub mod snapshot_delta;
 /// Abstract storage backends including file-system and future database integrations
 pub mod storage;
+/// PNG and ASCII previews of a world for the seed marketplace
+pub mod thumbnail;
 
 pub use error::{IoError, Result};
 pub use serialization::{
@@ -1,5 +1,4 @@
 use anyhow::{Context, Result};
-use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use quinn::crypto::rustls::QuicClientConfig;
 use quinn::rustls::pki_types::{CertificateDer, PrivateKeyDer, UnixTime};
 use quinn::Endpoint;
@@ -7,73 +6,10 @@ use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, Server
 use rustls::pki_types::CertificateDer as RustlsCertificateDer;
 use rustls::pki_types::PrivatePkcs8KeyDer;
 use rustls::DigitallySignedStruct;
-use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-/// Data structure for secure transfer of entity authority between worlds.
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct AuthorityTransfer {
-    /// Unique identifier of the entity being transferred.
-    pub entity_id: uuid::Uuid,
-    /// Hex-encoded genotype data.
-    pub dna: String,
-    /// Cryptographic signature ensuring data integrity and origin.
-    pub signature: Vec<u8>,
-    /// Creation timestamp of the transfer request.
-    pub timestamp: u64,
-    /// Anti-replay nonce.
-    pub nonce: u64,
-}
-
-impl AuthorityTransfer {
-    /// Signs the transfer data using the provided Ed25519 secret key.
-    pub fn sign(&mut self, secret_key_bytes: &[u8]) -> Result<()> {
-        let key_array: [u8; 32] = secret_key_bytes
-            .try_into()
-            .map_err(|_| anyhow::anyhow!("Invalid secret key length: expected 32 bytes"))?;
-        let signing_key = SigningKey::from_bytes(&key_array);
-        let message = self.get_message();
-        let signature = signing_key.sign(&message);
-        self.signature = signature.to_bytes().to_vec();
-        Ok(())
-    }
-
-    /// Verifies the signature using the provided Ed25519 public key.
-    pub fn verify(&self, public_key_bytes: &[u8]) -> bool {
-        let Ok(key_array) = public_key_bytes.try_into() else {
-            return false;
-        };
-        let Ok(verifying_key) = VerifyingKey::from_bytes(key_array) else {
-            return false;
-        };
-        let Ok(signature) = Signature::from_slice(&self.signature) else {
-            return false;
-        };
-        let message = self.get_message();
-        verifying_key.verify(&message, &signature).is_ok()
-    }
-
-    fn get_message(&self) -> Vec<u8> {
-        let mut msg = Vec::new();
-        msg.extend_from_slice(self.entity_id.as_bytes());
-        msg.extend_from_slice(self.dna.as_bytes());
-        msg.extend_from_slice(&self.timestamp.to_le_bytes());
-        msg.extend_from_slice(&self.nonce.to_le_bytes());
-        msg
-    }
-}
-
-/// Message types for the QUIC-based P2P protocol.
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum NetMessage {
-    /// Initial connection handshake.
-    Handshake { version: String, magic: u64 },
-    /// Discovery broadcast to announce peer existence.
-    PeerAnnounce { id: uuid::Uuid, address: String },
-    /// Secure entity migration.
-    MigrateEntity(AuthorityTransfer),
-}
+pub use primordium_net::p2p::{AuthorityTransfer, P2pMessage};
 
 /// Persistent QUIC server for handling incoming P2P connections.
 pub struct QuicServer {
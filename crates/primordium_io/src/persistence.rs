@@ -23,11 +23,7 @@ where
     T: Archive,
     P: AsRef<Path>,
 {
-    let mut serializer = AllocSerializer::<4096>::default();
-    serializer
-        .serialize_value(data)
-        .map_err(|e| IoError::rkyv(format!("serialization failed: {:?}", e)))?;
-    let bytes = serializer.into_serializer().into_inner();
+    let bytes = encode_rkyv(data)?;
     let mut file = File::create(&path).map_err(|e| {
         IoError::FileSystem(e).with_context(format!("creating file: {:?}", path.as_ref()))
     })?;
@@ -57,12 +53,36 @@ where
     file.read_to_end(&mut bytes).map_err(|e| {
         IoError::FileSystem(e).with_context(format!("reading file: {:?}", path.as_ref()))
     })?;
+    decode_rkyv(&bytes)
+}
 
-    let mut aligned = AlignedVec::with_capacity(bytes.len());
-    aligned.extend_from_slice(&bytes);
+/// Serializes data with rkyv.
+///
+/// # Errors
+/// Returns `IoError` if serialization fails.
+pub fn encode_rkyv<T>(data: &T) -> Result<AlignedVec>
+where
+    T: Serialize<AllocSerializer<4096>>,
+{
+    let mut serializer = AllocSerializer::<4096>::default();
+    serializer
+        .serialize_value(data)
+        .map_err(|e| IoError::rkyv(format!("serialization failed: {:?}", e)))?;
+    Ok(serializer.into_serializer().into_inner())
+}
 
-    // Ensure the AlignedVec has at least 8-byte alignment
-    // (AlignedVec usually has 16, but let's be sure or check if it's the culprit)
+/// Validates and deserializes rkyv bytes, which need not be aligned.
+///
+/// # Errors
+/// Returns `IoError` if validation or deserialization fails.
+pub fn decode_rkyv<T>(bytes: &[u8]) -> Result<T>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, SharedDeserializeMap>
+        + for<'a> rkyv::CheckBytes<rkyv::validation::validators::DefaultValidator<'a>>,
+{
+    let mut aligned = AlignedVec::with_capacity(bytes.len());
+    aligned.extend_from_slice(bytes);
 
     let archived = rkyv::check_archived_root::<T>(&aligned)
         .map_err(|e| IoError::rkyv(format!("validation failed: {:?}", e)))?;
@@ -72,3 +92,190 @@ where
         .map_err(|e| IoError::rkyv(format!("deserialization failed: {:?}", e)))?;
     Ok(deserialized)
 }
+
+/// Opening bytes of a versioned snapshot.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"PRIMSNAP";
+
+/// Length of the snapshot header: [`SNAPSHOT_MAGIC`], the schema version as
+/// a little-endian `u32` and four reserved bytes.
+pub const SNAPSHOT_HEADER_LEN: usize = 16;
+
+/// Upgrades the rkyv payload of one schema version to the next.
+pub type SnapshotMigration = Box<dyn Fn(&[u8]) -> Result<Vec<u8>> + Send + Sync>;
+
+/// Migration hooks that upgrade old snapshot payloads, one schema version
+/// at a time, to the version this build reads.
+///
+/// A hook registered for version `n` decodes a version `n` payload with the
+/// type that schema used and re-encodes it as version `n + 1`.
+pub struct SnapshotMigrations {
+    current: u32,
+    steps: std::collections::BTreeMap<u32, SnapshotMigration>,
+}
+
+impl SnapshotMigrations {
+    /// Hooks for payloads up to schema version `current`.
+    pub fn new(current: u32) -> Self {
+        Self {
+            current,
+            steps: std::collections::BTreeMap::new(),
+        }
+    }
+
+    /// The schema version this build writes and reads.
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// Registers the upgrade from schema version `from` to `from + 1`.
+    #[must_use]
+    pub fn with(
+        mut self,
+        from: u32,
+        migrate: impl Fn(&[u8]) -> Result<Vec<u8>> + Send + Sync + 'static,
+    ) -> Self {
+        self.steps.insert(from, Box::new(migrate));
+        self
+    }
+
+    /// Upgrades a payload of schema `version` to the current schema.
+    ///
+    /// # Errors
+    /// Returns `IoError::Validation` for a version newer than this build or
+    /// one without a registered upgrade path, or the error of a hook.
+    pub fn migrate(&self, version: u32, payload: &[u8]) -> Result<Vec<u8>> {
+        if version > self.current {
+            return Err(IoError::validation(format!(
+                "snapshot schema {version} is newer than supported schema {}",
+                self.current
+            )));
+        }
+        let mut payload = payload.to_vec();
+        for from in version..self.current {
+            let step = self.steps.get(&from).ok_or_else(|| {
+                IoError::validation(format!(
+                    "no migration from snapshot schema {from} to {}",
+                    from + 1
+                ))
+            })?;
+            payload = step(&payload).map_err(|e| {
+                e.with_context(format!("migrating snapshot schema {from} to {}", from + 1))
+            })?;
+        }
+        Ok(payload)
+    }
+}
+
+/// Encodes data as a versioned snapshot: the header, then the rkyv bytes.
+///
+/// # Errors
+/// Returns `IoError` if serialization fails.
+pub fn encode_snapshot<T>(data: &T, schema_version: u32) -> Result<Vec<u8>>
+where
+    T: Serialize<AllocSerializer<4096>>,
+{
+    let payload = encode_rkyv(data)?;
+    let mut bytes = Vec::with_capacity(SNAPSHOT_HEADER_LEN + payload.len());
+    bytes.extend_from_slice(&SNAPSHOT_MAGIC);
+    bytes.extend_from_slice(&schema_version.to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    bytes.extend_from_slice(&payload);
+    Ok(bytes)
+}
+
+/// The schema version of a snapshot, or `None` if `bytes` are not one.
+pub fn snapshot_version(bytes: &[u8]) -> Option<u32> {
+    if bytes.len() < SNAPSHOT_HEADER_LEN || bytes[..8] != SNAPSHOT_MAGIC {
+        return None;
+    }
+    Some(u32::from_le_bytes([
+        bytes[8], bytes[9], bytes[10], bytes[11],
+    ]))
+}
+
+/// Decodes a versioned snapshot, upgrading an older payload through
+/// `migrations` first.
+///
+/// # Errors
+/// Returns `IoError` if `bytes` are not a snapshot, cannot be migrated, or
+/// fail validation.
+pub fn decode_snapshot<T>(bytes: &[u8], migrations: &SnapshotMigrations) -> Result<T>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, SharedDeserializeMap>
+        + for<'a> rkyv::CheckBytes<rkyv::validation::validators::DefaultValidator<'a>>,
+{
+    let version =
+        snapshot_version(bytes).ok_or_else(|| IoError::validation("not a versioned snapshot"))?;
+    let payload = &bytes[SNAPSHOT_HEADER_LEN..];
+    if version == migrations.current() {
+        decode_rkyv(payload)
+    } else {
+        decode_rkyv(&migrations.migrate(version, payload)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[archive(check_bytes)]
+    struct V1 {
+        population: u32,
+    }
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[archive(check_bytes)]
+    struct V2 {
+        population: u32,
+        name: String,
+    }
+
+    fn migrations() -> SnapshotMigrations {
+        SnapshotMigrations::new(2).with(1, |payload| {
+            let old: V1 = decode_rkyv(payload)?;
+            Ok(encode_rkyv(&V2 {
+                population: old.population,
+                name: "unnamed".to_string(),
+            })?
+            .into_vec())
+        })
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip_and_migration() {
+        let current = V2 {
+            population: 7,
+            name: "eden".to_string(),
+        };
+        let bytes = encode_snapshot(&current, 2).unwrap();
+        assert_eq!(snapshot_version(&bytes), Some(2));
+        assert_eq!(
+            decode_snapshot::<V2>(&bytes, &migrations()).unwrap(),
+            current
+        );
+
+        let old = encode_snapshot(&V1 { population: 3 }, 1).unwrap();
+        assert_eq!(
+            decode_snapshot::<V2>(&old, &migrations()).unwrap(),
+            V2 {
+                population: 3,
+                name: "unnamed".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_snapshot_rejects_unknown_versions() {
+        assert_eq!(snapshot_version(b"{\"version\": 1}"), None);
+
+        let newer = encode_snapshot(&V1 { population: 3 }, 3).unwrap();
+        let err = decode_snapshot::<V2>(&newer, &migrations()).unwrap_err();
+        assert!(err.to_string().contains("newer"), "{err}");
+
+        let no_path = encode_snapshot(&V1 { population: 3 }, 1).unwrap();
+        let err = decode_snapshot::<V2>(&no_path, &SnapshotMigrations::new(2)).unwrap_err();
+        assert!(err.to_string().contains("no migration"), "{err}");
+    }
+}
@@ -0,0 +1,194 @@
+//! Cumulative player statistics and personal records across runs.
+//!
+//! A [`PlayerProfile`](crate::profile::PlayerProfile) is persisted as a
+//! single JSON row through the
+//! [`StorageManager`](crate::storage::StorageManager). The app folds the
+//! progress of the current run into it periodically via
+//! [`SessionStats`](crate::profile::SessionStats) and announces any
+//! [`Achievement`](crate::profile::Achievement) that becomes earned.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Personal best for the longest-lived lineage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineageBest {
+    pub id: Uuid,
+    pub name: String,
+    /// Ticks between the lineage's first appearance and its extinction (or
+    /// the last observed tick while it was alive).
+    pub ticks: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Achievement {
+    /// 100,000 ticks simulated in total.
+    Centurion,
+    /// 1,000,000 ticks simulated in total.
+    Marathon,
+    /// 500 entities alive at once.
+    Crowded,
+    /// 2,000 entities alive at once.
+    Metropolis,
+    /// A lineage that survived 10,000 ticks.
+    Dynasty,
+    /// A lineage that survived 100,000 ticks.
+    ImmortalLine,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 6] = [
+        Achievement::Centurion,
+        Achievement::Marathon,
+        Achievement::Crowded,
+        Achievement::Metropolis,
+        Achievement::Dynasty,
+        Achievement::ImmortalLine,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Achievement::Centurion => "Centurion",
+            Achievement::Marathon => "Marathon",
+            Achievement::Crowded => "Crowded",
+            Achievement::Metropolis => "Metropolis",
+            Achievement::Dynasty => "Dynasty",
+            Achievement::ImmortalLine => "Immortal Line",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::Centurion => "Simulate 100,000 ticks in total",
+            Achievement::Marathon => "Simulate 1,000,000 ticks in total",
+            Achievement::Crowded => "Reach a population of 500",
+            Achievement::Metropolis => "Reach a population of 2,000",
+            Achievement::Dynasty => "Keep a lineage alive for 10,000 ticks",
+            Achievement::ImmortalLine => "Keep a lineage alive for 100,000 ticks",
+        }
+    }
+
+    fn is_met(self, profile: &PlayerProfile) -> bool {
+        let lineage_ticks = profile.longest_lineage.as_ref().map_or(0, |l| l.ticks);
+        match self {
+            Achievement::Centurion => profile.total_ticks >= 100_000,
+            Achievement::Marathon => profile.total_ticks >= 1_000_000,
+            Achievement::Crowded => profile.max_population >= 500,
+            Achievement::Metropolis => profile.max_population >= 2_000,
+            Achievement::Dynasty => lineage_ticks >= 10_000,
+            Achievement::ImmortalLine => lineage_ticks >= 100_000,
+        }
+    }
+}
+
+/// Progress of the current run since it was last folded into the profile.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionStats {
+    pub ticks: u64,
+    pub max_population: usize,
+    pub longest_lineage: Option<LineageBest>,
+}
+
+/// The user's cumulative statistics across all runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerProfile {
+    /// Number of interactive sessions started.
+    pub runs: u32,
+    pub total_ticks: u64,
+    pub max_population: usize,
+    pub longest_lineage: Option<LineageBest>,
+    /// Earned achievements in the order they were earned.
+    pub achievements: Vec<Achievement>,
+}
+
+impl PlayerProfile {
+    /// Folds `session` into the cumulative totals and returns the achievements
+    /// earned as a result.
+    pub fn record(&mut self, session: &SessionStats) -> Vec<Achievement> {
+        self.total_ticks += session.ticks;
+        self.max_population = self.max_population.max(session.max_population);
+        if let Some(lineage) = &session.longest_lineage {
+            let best = self.longest_lineage.as_ref().map_or(0, |l| l.ticks);
+            if lineage.ticks > best {
+                self.longest_lineage = Some(lineage.clone());
+            }
+        }
+
+        let earned: Vec<Achievement> = Achievement::ALL
+            .into_iter()
+            .filter(|a| !self.achievements.contains(a) && a.is_met(self))
+            .collect();
+        self.achievements.extend(&earned);
+        earned
+    }
+
+    pub fn has(&self, achievement: Achievement) -> bool {
+        self.achievements.contains(&achievement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StorageManager;
+    use std::time::Duration;
+
+    #[test]
+    fn test_record_accumulates_and_awards_once() {
+        let mut profile = PlayerProfile::default();
+        let earned = profile.record(&SessionStats {
+            ticks: 60_000,
+            max_population: 600,
+            longest_lineage: Some(LineageBest {
+                id: Uuid::new_v4(),
+                name: "Alpha".into(),
+                ticks: 12_000,
+            }),
+        });
+        assert_eq!(earned, vec![Achievement::Crowded, Achievement::Dynasty]);
+
+        let earned = profile.record(&SessionStats {
+            ticks: 50_000,
+            max_population: 100,
+            longest_lineage: Some(LineageBest {
+                id: Uuid::new_v4(),
+                name: "Beta".into(),
+                ticks: 500,
+            }),
+        });
+        assert_eq!(earned, vec![Achievement::Centurion]);
+        assert_eq!(profile.total_ticks, 110_000);
+        assert_eq!(profile.max_population, 600);
+        assert_eq!(profile.longest_lineage.as_ref().unwrap().name, "Alpha");
+        assert!(profile.record(&SessionStats::default()).is_empty());
+    }
+
+    #[test]
+    fn test_profile_roundtrip_through_storage() {
+        let storage = StorageManager::new(":memory:").unwrap();
+        let empty = storage
+            .query_profile_async()
+            .unwrap()
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(empty, PlayerProfile::default());
+
+        let mut profile = PlayerProfile {
+            runs: 3,
+            ..Default::default()
+        };
+        profile.record(&SessionStats {
+            ticks: 200_000,
+            ..Default::default()
+        });
+        storage.save_profile(&profile);
+        let loaded = storage
+            .query_profile_async()
+            .unwrap()
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(loaded, profile);
+        assert!(loaded.has(Achievement::Centurion));
+    }
+}
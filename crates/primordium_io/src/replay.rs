@@ -0,0 +1,292 @@
+//! Recordings of deterministic runs that can be re-simulated and checked.
+//!
+//! A recording is a directory holding:
+//! - `manifest.json`: the seed, checkpoint interval and provenance of the run;
+//! - `stats.jsonl`: a hash of the
+//!   [`PopulationStats`](primordium_data::PopulationStats) after every
+//!   tick;
+//! - `inputs.jsonl`: whatever the run fed into the world, with its tick;
+//! - `checkpoints/<tick>.rkyv`: a [`Checkpoint`](crate::replay::Checkpoint)
+//!   of the full state every interval ticks.
+//!
+//! Re-simulating from the checkpoint at or before a tick and comparing each
+//! tick's stats hash with the recorded one shows whether the range came out
+//! bit for bit the same.
+
+use crate::persistence::{load_rkyv, save_rkyv};
+use anyhow::Context;
+use primordium_data::{PopulationStats, RunManifest};
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const STATS_FILE: &str = "stats.jsonl";
+pub const INPUTS_FILE: &str = "inputs.jsonl";
+pub const CHECKPOINT_DIR: &str = "checkpoints";
+
+/// How a recording was made.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayManifest {
+    pub seed: u64,
+    /// Ticks between checkpoints.
+    pub checkpoint_interval: u64,
+    pub provenance: Option<RunManifest>,
+}
+
+/// The full simulation state after `tick`.
+#[derive(Debug, Clone, PartialEq, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct Checkpoint {
+    pub tick: u64,
+    /// [`stats_hash`] of the state.
+    pub stats_hash: u64,
+    /// The state itself, encoded by the simulation.
+    pub state: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StatsLine {
+    tick: u64,
+    hash: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct InputLine<T> {
+    tick: u64,
+    input: T,
+}
+
+/// A hash of every field of `stats`, independent of map ordering, so two
+/// runs hash the same exactly when their statistics are identical.
+pub fn stats_hash(stats: &PopulationStats) -> u64 {
+    let value = serde_json::to_value(stats)
+        .map(canonical)
+        .unwrap_or_default();
+    let digest = Sha256::digest(value.to_string().as_bytes());
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// `value` with the keys of every object sorted.
+fn canonical(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let sorted: BTreeMap<String, serde_json::Value> =
+                map.into_iter().map(|(k, v)| (k, canonical(v))).collect();
+            serde_json::Value::Object(sorted.into_iter().collect())
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(canonical).collect())
+        }
+        other => other,
+    }
+}
+
+fn checkpoint_path(dir: &Path, tick: u64) -> PathBuf {
+    dir.join(CHECKPOINT_DIR).join(format!("{tick:012}.rkyv"))
+}
+
+/// Writes a recording as the run goes.
+pub struct ReplayWriter {
+    dir: PathBuf,
+    stats: File,
+    inputs: File,
+}
+
+impl ReplayWriter {
+    /// Starts a recording in `dir`, replacing any recording already there.
+    pub fn create(dir: impl Into<PathBuf>, manifest: &ReplayManifest) -> anyhow::Result<Self> {
+        let dir = dir.into();
+        let checkpoints = dir.join(CHECKPOINT_DIR);
+        if checkpoints.exists() {
+            std::fs::remove_dir_all(&checkpoints)?;
+        }
+        std::fs::create_dir_all(&checkpoints)
+            .with_context(|| format!("Cannot create recording {}", dir.display()))?;
+        std::fs::write(
+            dir.join(MANIFEST_FILE),
+            serde_json::to_string_pretty(manifest)?,
+        )?;
+        Ok(Self {
+            stats: File::create(dir.join(STATS_FILE))?,
+            inputs: File::create(dir.join(INPUTS_FILE))?,
+            dir,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Records the statistics after `tick` and returns their hash.
+    pub fn record_tick(&mut self, tick: u64, stats: &PopulationStats) -> anyhow::Result<u64> {
+        let hash = stats_hash(stats);
+        append_line(&mut self.stats, &StatsLine { tick, hash })?;
+        Ok(hash)
+    }
+
+    /// Records `input` as fed into the world at `tick`.
+    pub fn record_input<T: Serialize>(&mut self, tick: u64, input: &T) -> anyhow::Result<()> {
+        append_line(&mut self.inputs, &InputLine { tick, input })
+    }
+
+    pub fn write_checkpoint(&self, checkpoint: &Checkpoint) -> anyhow::Result<()> {
+        save_rkyv(checkpoint, checkpoint_path(&self.dir, checkpoint.tick))?;
+        Ok(())
+    }
+}
+
+fn append_line(file: &mut File, value: &impl Serialize) -> anyhow::Result<()> {
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    file.write_all(&line)?;
+    Ok(())
+}
+
+/// A recording read back for re-simulation.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub dir: PathBuf,
+    pub manifest: ReplayManifest,
+    /// Recorded stats hash by tick.
+    pub hashes: BTreeMap<u64, u64>,
+}
+
+impl Recording {
+    pub fn open(dir: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let dir = dir.into();
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let manifest: ReplayManifest = serde_json::from_str(
+            &std::fs::read_to_string(&manifest_path)
+                .with_context(|| format!("Cannot read {}", manifest_path.display()))?,
+        )?;
+        let hashes = read_lines::<StatsLine>(&dir.join(STATS_FILE))?
+            .into_iter()
+            .map(|line| (line.tick, line.hash))
+            .collect();
+        Ok(Self {
+            dir,
+            manifest,
+            hashes,
+        })
+    }
+
+    pub fn hash_at(&self, tick: u64) -> Option<u64> {
+        self.hashes.get(&tick).copied()
+    }
+
+    /// Last tick with a recorded hash.
+    pub fn last_tick(&self) -> Option<u64> {
+        self.hashes.keys().next_back().copied()
+    }
+
+    /// Ticks that have a checkpoint, in order.
+    pub fn checkpoint_ticks(&self) -> anyhow::Result<Vec<u64>> {
+        let mut ticks: Vec<u64> = std::fs::read_dir(self.dir.join(CHECKPOINT_DIR))?
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                name.to_str()?.strip_suffix(".rkyv")?.parse().ok()
+            })
+            .collect();
+        ticks.sort_unstable();
+        Ok(ticks)
+    }
+
+    /// The latest checkpoint at or before `tick`.
+    pub fn checkpoint_at_or_before(&self, tick: u64) -> anyhow::Result<Option<Checkpoint>> {
+        let Some(&at) = self.checkpoint_ticks()?.iter().rfind(|&&t| t <= tick) else {
+            return Ok(None);
+        };
+        Ok(Some(load_rkyv(checkpoint_path(&self.dir, at))?))
+    }
+
+    /// The recorded inputs, oldest first.
+    pub fn inputs<T: DeserializeOwned>(&self) -> anyhow::Result<Vec<(u64, T)>> {
+        Ok(read_lines::<InputLine<T>>(&self.dir.join(INPUTS_FILE))?
+            .into_iter()
+            .map(|line| (line.tick, line.input))
+            .collect())
+    }
+}
+
+/// The JSON lines of `path`, skipping any that do not parse, such as one cut
+/// short by a crash. A missing file has none.
+fn read_lines<T: DeserializeOwned>(path: &Path) -> anyhow::Result<Vec<T>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut values = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(value) = serde_json::from_str(&line?) {
+            values.push(value);
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_recording_round_trips_hashes_inputs_and_checkpoints() {
+        let dir = std::env::temp_dir().join(format!("primordium_replay_{}", Uuid::new_v4()));
+        let manifest = ReplayManifest {
+            seed: 7,
+            checkpoint_interval: 2,
+            provenance: None,
+        };
+        let mut writer = ReplayWriter::create(&dir, &manifest).unwrap();
+        let mut stats = PopulationStats::default();
+        let mut hashes = Vec::new();
+        for tick in 1..=4 {
+            stats.population = tick as usize;
+            stats
+                .lineage_counts
+                .insert(Uuid::from_u128(tick as u128), 1);
+            hashes.push(writer.record_tick(tick, &stats).unwrap());
+            if tick % 2 == 0 {
+                writer
+                    .write_checkpoint(&Checkpoint {
+                        tick,
+                        stats_hash: hashes[tick as usize - 1],
+                        state: vec![tick as u8; 3],
+                    })
+                    .unwrap();
+            }
+        }
+        writer.record_input(3, &"flare".to_string()).unwrap();
+
+        let recording = Recording::open(&dir).unwrap();
+        assert_eq!(recording.manifest, manifest);
+        assert_eq!(recording.hash_at(4), Some(stats_hash(&stats)));
+        assert_eq!(recording.last_tick(), Some(4));
+        assert_eq!(recording.checkpoint_ticks().unwrap(), vec![2, 4]);
+        let checkpoint = recording.checkpoint_at_or_before(3).unwrap().unwrap();
+        assert_eq!((checkpoint.tick, checkpoint.state), (2, vec![2; 3]));
+        assert!(recording.checkpoint_at_or_before(1).unwrap().is_none());
+        assert_eq!(
+            recording.inputs::<String>().unwrap(),
+            vec![(3, "flare".to_string())]
+        );
+        // Distinct statistics hash differently.
+        assert_eq!(
+            hashes
+                .iter()
+                .collect::<std::collections::HashSet<_>>()
+                .len(),
+            4
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
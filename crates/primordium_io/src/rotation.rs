@@ -0,0 +1,293 @@
+//! Rotation and disk quota for the logs directory.
+//!
+//! [`LogRotator`](crate::rotation::LogRotator) runs on the logging thread.
+//! It counts the bytes appended to `live.jsonl` and, once
+//! [`LogsConfig::max_file_mb`](primordium_core::config::LogsConfig::max_file_mb) or
+//! [`LogsConfig::max_age_hours`](primordium_core::config::LogsConfig::max_age_hours)
+//! is reached, moves the file aside as `live-<timestamp>.jsonl` and
+//! compresses it to `live-<timestamp>.jsonl.zst`. Timestamps sort, so
+//! rotated logs list oldest first by name.
+//!
+//! Every [`QUOTA_CHECK_SECS`](crate::rotation::QUOTA_CHECK_SECS) and after
+//! each rotation the whole directory is measured. While it holds more than
+//! [`LogsConfig::quota_mb`](primordium_core::config::LogsConfig::quota_mb),
+//! rotated logs are deleted oldest first; the live log, registries and
+//! database are never evicted. The measured size is published through a
+//! [`LogUsage`](crate::rotation::LogUsage) gauge so the UI can warn before
+//! the quota is reached.
+
+use anyhow::{Context, Result};
+use primordium_core::config::LogsConfig;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub const LIVE_LOG: &str = "live.jsonl";
+const ROTATED_PREFIX: &str = "live-";
+const ROTATED_SUFFIX: &str = ".jsonl";
+const COMPRESSED_SUFFIX: &str = ".jsonl.zst";
+const MB: u64 = 1024 * 1024;
+/// How often the directory is measured between rotations.
+pub const QUOTA_CHECK_SECS: u64 = 30;
+
+/// Size of the logs directory against its quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LogUsage {
+    pub used_bytes: u64,
+    /// 0 when there is no quota.
+    pub quota_bytes: u64,
+}
+
+impl LogUsage {
+    /// Share of the quota in use; 0 without a quota.
+    pub fn fraction(&self) -> f64 {
+        if self.quota_bytes == 0 {
+            0.0
+        } else {
+            self.used_bytes as f64 / self.quota_bytes as f64
+        }
+    }
+}
+
+/// Last measured size of a logs directory, shared with the logging thread.
+#[derive(Debug, Clone, Default)]
+pub struct UsageGauge {
+    used_bytes: Arc<AtomicU64>,
+    quota_bytes: u64,
+}
+
+impl UsageGauge {
+    pub fn new(config: &LogsConfig) -> Self {
+        Self {
+            used_bytes: Arc::default(),
+            quota_bytes: config.quota_mb * MB,
+        }
+    }
+
+    pub fn get(&self) -> LogUsage {
+        LogUsage {
+            used_bytes: self.used_bytes.load(Ordering::Relaxed),
+            quota_bytes: self.quota_bytes,
+        }
+    }
+
+    fn set(&self, used_bytes: u64) {
+        self.used_bytes.store(used_bytes, Ordering::Relaxed);
+    }
+}
+
+/// Decides when `live.jsonl` is rotated and keeps the directory under quota.
+pub struct LogRotator {
+    dir: PathBuf,
+    config: LogsConfig,
+    gauge: UsageGauge,
+    written: u64,
+    opened: Instant,
+    measured: Instant,
+}
+
+impl LogRotator {
+    /// Picks up the size of an existing live log, so a resumed run rotates
+    /// on schedule, and measures the directory.
+    pub fn new(dir: impl Into<PathBuf>, config: LogsConfig, gauge: UsageGauge) -> Self {
+        let dir = dir.into();
+        let written = fs::metadata(dir.join(LIVE_LOG))
+            .map(|m| m.len())
+            .unwrap_or(0);
+        let mut rotator = Self {
+            dir,
+            config,
+            gauge,
+            written,
+            opened: Instant::now(),
+            measured: Instant::now(),
+        };
+        if let Err(e) = rotator.enforce_quota() {
+            eprintln!("HistoryLogger: Error enforcing log quota: {}", e);
+        }
+        rotator
+    }
+
+    /// Counts `bytes` appended to the live log.
+    pub fn record(&mut self, bytes: u64) {
+        self.written += bytes;
+    }
+
+    /// Whether the live log has reached its size or age limit.
+    pub fn rotation_due(&self) -> bool {
+        let too_big = self.config.max_file_mb > 0 && self.written >= self.config.max_file_mb * MB;
+        let too_old = self.config.max_age_hours > 0
+            && self.opened.elapsed() >= Duration::from_secs(self.config.max_age_hours * 3600);
+        self.written > 0 && (too_big || too_old)
+    }
+
+    /// Whether the directory is due to be measured again.
+    pub fn quota_check_due(&self) -> bool {
+        self.measured.elapsed() >= Duration::from_secs(QUOTA_CHECK_SECS)
+    }
+
+    /// Moves the live log aside and compresses it, then enforces the quota.
+    /// The caller must have flushed and closed the live log, and reopens it
+    /// afterwards. Returns the rotated file.
+    pub fn rotate(&mut self) -> Result<PathBuf> {
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%9fZ");
+        let plain = self
+            .dir
+            .join(format!("{ROTATED_PREFIX}{stamp}{ROTATED_SUFFIX}"));
+        fs::rename(self.dir.join(LIVE_LOG), &plain).context("Failed to move the live log")?;
+        self.written = 0;
+        self.opened = Instant::now();
+
+        let compressed = plain.with_extension("jsonl.zst");
+        let rotated = match compress(&plain, &compressed, self.config.zstd_level) {
+            Ok(()) => {
+                fs::remove_file(&plain)?;
+                compressed
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&compressed);
+                eprintln!(
+                    "HistoryLogger: Error compressing {}: {}",
+                    plain.display(),
+                    e
+                );
+                plain
+            }
+        };
+        self.enforce_quota()?;
+        Ok(rotated)
+    }
+
+    /// Measures the directory and deletes rotated logs, oldest first, while
+    /// it is over quota. Returns the deleted files.
+    pub fn enforce_quota(&mut self) -> Result<Vec<PathBuf>> {
+        self.measured = Instant::now();
+        let mut used = dir_size(&self.dir)?;
+        let quota = self.config.quota_mb * MB;
+        let mut evicted = Vec::new();
+        if quota > 0 {
+            for path in rotated_logs(&self.dir)? {
+                if used <= quota {
+                    break;
+                }
+                let len = fs::metadata(&path)?.len();
+                fs::remove_file(&path)?;
+                used = used.saturating_sub(len);
+                evicted.push(path);
+            }
+        }
+        self.gauge.set(used);
+        Ok(evicted)
+    }
+}
+
+fn compress(from: &Path, to: &Path, level: i32) -> Result<()> {
+    let input = BufReader::new(File::open(from)?);
+    let output = File::create(to)?;
+    zstd::stream::copy_encode(input, output, level)?;
+    Ok(())
+}
+
+fn dir_size(dir: &Path) -> Result<u64> {
+    let mut total = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        total += if meta.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            meta.len()
+        };
+    }
+    Ok(total)
+}
+
+/// Rotated live logs in `dir`, oldest first.
+pub fn rotated_logs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut logs: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with(ROTATED_PREFIX)
+                        && (name.ends_with(ROTATED_SUFFIX) || name.ends_with(COMPRESSED_SUFFIX))
+                })
+        })
+        .collect();
+    logs.sort();
+    Ok(logs)
+}
+
+/// Reads a live log, decompressing it if it was rotated.
+pub fn read_log(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut text = String::new();
+    if path.to_string_lossy().ends_with(COMPRESSED_SUFFIX) {
+        zstd::stream::read::Decoder::new(file)?.read_to_string(&mut text)?;
+    } else {
+        file.read_to_string(&mut text)?;
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("primordium_rotation_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_rotation_compresses_and_quota_evicts_oldest() {
+        let dir = temp_dir();
+        let config = LogsConfig {
+            max_file_mb: 1,
+            quota_mb: 1,
+            ..LogsConfig::default()
+        };
+        let gauge = UsageGauge::new(&config);
+        let mut rotator = LogRotator::new(&dir, config, gauge.clone());
+        assert!(!rotator.rotation_due());
+
+        // Hex noise compresses to about half, so four rotations overrun the quota.
+        let mut rotated = Vec::new();
+        for round in 0..4u64 {
+            let line: String = (0..MB / 16)
+                .map(|i| format!("{:016x}", (i ^ round).wrapping_mul(0x9e3779b97f4a7c15)))
+                .collect();
+            fs::write(dir.join(LIVE_LOG), &line).unwrap();
+            rotator.record(line.len() as u64);
+            assert!(rotator.rotation_due());
+            rotated.push(rotator.rotate().unwrap());
+            assert!(!rotator.rotation_due());
+        }
+
+        assert!(rotated
+            .iter()
+            .all(|p| p.to_string_lossy().ends_with(".zst")));
+        let kept = rotated_logs(&dir).unwrap();
+        assert!(!kept.is_empty() && kept.len() < rotated.len());
+        assert_eq!(kept.last(), rotated.last());
+        assert!(!kept.contains(&rotated[0]));
+        assert!(gauge.get().used_bytes <= MB);
+        assert_eq!(read_log(kept.last().unwrap()).unwrap().len() as u64, MB);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_usage_fraction_without_quota_is_zero() {
+        let usage = LogUsage {
+            used_bytes: 10,
+            quota_bytes: 0,
+        };
+        assert_eq!(usage.fraction(), 0.0);
+    }
+}
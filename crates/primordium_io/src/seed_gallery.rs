@@ -0,0 +1,185 @@
+//! A gallery of the most interesting seeds seen in finished runs.
+//!
+//! Each finished run is summarised as
+//! [`RunHighlights`](crate::seed_gallery::RunHighlights) and scored by
+//! [`RunHighlights::score`](crate::seed_gallery::RunHighlights::score); the
+//! best-scoring seeds are kept in a small JSON file, one entry per seed.
+//! `primordium new --random-interesting` and the screensaver draw from it
+//! with [`SeedGallery::pick`](crate::seed_gallery::SeedGallery::pick),
+//! favouring higher scores.
+
+use anyhow::Context;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Score of one lineage split.
+const SPECIATION_WEIGHT: f64 = 1.0;
+/// Score of one outbreak of war.
+const WAR_WEIGHT: f64 = 3.0;
+/// Score of each civilization level the most advanced lineage reached.
+const CIVILIZATION_WEIGHT: f64 = 5.0;
+/// Score of each lineage alive at the most diverse moment.
+const LINEAGE_WEIGHT: f64 = 0.5;
+/// Score of each generation reached.
+const GENERATION_WEIGHT: f64 = 0.05;
+
+/// What happened over a run, as far as interestingness goes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunHighlights {
+    pub ticks: u64,
+    /// Lineages that split off an existing one during the run.
+    pub speciations: usize,
+    /// Times war zones broke out on a peaceful map.
+    pub wars: usize,
+    pub max_civilization_level: u32,
+    /// Most lineages alive at once.
+    pub peak_lineages: usize,
+    pub max_generation: u32,
+}
+
+impl RunHighlights {
+    pub fn score(&self) -> f64 {
+        self.speciations as f64 * SPECIATION_WEIGHT
+            + self.wars as f64 * WAR_WEIGHT
+            + f64::from(self.max_civilization_level) * CIVILIZATION_WEIGHT
+            + self.peak_lineages as f64 * LINEAGE_WEIGHT
+            + f64::from(self.max_generation) * GENERATION_WEIGHT
+    }
+
+    /// One line for gallery listings, e.g. "12 speciations, 2 wars,
+    /// civilization level 3, 9 lineages at peak, generation 41 over 20000
+    /// ticks".
+    pub fn summary(&self) -> String {
+        let plural = |n: usize, word: &str| format!("{n} {word}{}", if n == 1 { "" } else { "s" });
+        format!(
+            "{}, {}, civilization level {}, {} lineages at peak, generation {} over {} ticks",
+            plural(self.speciations, "speciation"),
+            plural(self.wars, "war"),
+            self.max_civilization_level,
+            self.peak_lineages,
+            self.max_generation,
+            self.ticks
+        )
+    }
+}
+
+/// A seed worth replaying and why.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GalleryEntry {
+    pub seed: u64,
+    pub score: f64,
+    pub highlights: RunHighlights,
+    /// RFC 3339 time the run was recorded.
+    pub recorded_at: String,
+}
+
+impl GalleryEntry {
+    pub fn new(seed: u64, highlights: RunHighlights) -> Self {
+        Self {
+            seed,
+            score: highlights.score(),
+            highlights,
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// The top seeds, best first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeedGallery {
+    pub entries: Vec<GalleryEntry>,
+}
+
+impl SeedGallery {
+    /// Reads the gallery at `path`; a missing file is an empty gallery.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid seed gallery {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Adds `entry` if it beats the seed's previous entry and ranks among
+    /// the best `capacity`. Returns its rank (0 is best) when kept.
+    pub fn record(&mut self, entry: GalleryEntry, capacity: usize) -> Option<usize> {
+        if let Some(pos) = self.entries.iter().position(|e| e.seed == entry.seed) {
+            if self.entries[pos].score >= entry.score {
+                return None;
+            }
+            self.entries.remove(pos);
+        }
+        let (seed, score) = (entry.seed, entry.score);
+        self.entries.push(entry);
+        self.entries.sort_by(|a, b| b.score.total_cmp(&a.score));
+        self.entries.truncate(capacity);
+        self.entries
+            .iter()
+            .position(|e| e.seed == seed && e.score == score)
+    }
+
+    /// A random entry, each as likely as its share of the total score.
+    pub fn pick(&self, rng: &mut impl Rng) -> Option<&GalleryEntry> {
+        // Every entry keeps some chance, even one that scored nothing.
+        let weight = |e: &GalleryEntry| e.score.max(0.0) + 1.0;
+        let total: f64 = self.entries.iter().map(weight).sum();
+        let mut roll = rng.gen_range(0.0..total.max(f64::MIN_POSITIVE));
+        for entry in &self.entries {
+            roll -= weight(entry);
+            if roll < 0.0 {
+                return Some(entry);
+            }
+        }
+        self.entries.last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn entry(seed: u64, wars: usize) -> GalleryEntry {
+        GalleryEntry::new(
+            seed,
+            RunHighlights {
+                wars,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_gallery_keeps_best_run_per_seed_within_capacity() {
+        let mut gallery = SeedGallery::default();
+        assert_eq!(gallery.record(entry(1, 1), 2), Some(0));
+        assert_eq!(gallery.record(entry(2, 5), 2), Some(0));
+        assert_eq!(gallery.record(entry(1, 0), 2), None);
+        assert_eq!(gallery.record(entry(3, 0), 2), None);
+        assert_eq!(gallery.record(entry(1, 9), 2), Some(0));
+
+        let seeds: Vec<u64> = gallery.entries.iter().map(|e| e.seed).collect();
+        assert_eq!(seeds, vec![1, 2]);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let picks = (0..1000)
+            .filter(|_| gallery.pick(&mut rng).unwrap().seed == 1)
+            .count();
+        assert!(
+            picks > 500,
+            "seed 1 scores higher but was picked {picks} times"
+        );
+        assert!(SeedGallery::default().pick(&mut rng).is_none());
+    }
+}
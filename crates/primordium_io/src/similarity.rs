@@ -0,0 +1,214 @@
+//! Near-duplicate detection for marketplace genomes.
+//!
+//! A genotype is reduced to a fixed-length vector: its enabled connection
+//! weights, feature-hashed by innovation number, followed by its traits
+//! centred on their usual values. Genomes are compared by the cosine of
+//! their vectors. [`SimilarityIndex`](crate::similarity::SimilarityIndex)
+//! finds candidates with random-hyperplane locality-sensitive hashing, so a
+//! lookup only scores the few stored genomes that share a signature band
+//! with the query.
+
+use primordium_data::Genotype;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Buckets the connection weights are hashed into.
+pub const WEIGHT_BUCKETS: usize = 256;
+
+/// Length of a genome vector: the weight buckets plus the traits.
+pub const VECTOR_LEN: usize = WEIGHT_BUCKETS + 16;
+
+/// Signature bits, split into `BANDS` bands of `BAND_BITS` each.
+const BANDS: usize = 8;
+const BAND_BITS: usize = 8;
+
+/// Seed of the hyperplanes, so signatures are the same in every run.
+const PLANE_SEED: u64 = 0x5eed_9e0e;
+
+/// Similarity scores above which a submission is treated as a copy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SimilarityThresholds {
+    /// Submissions at least this similar to a stored genome are stored but
+    /// flagged as a duplicate of it.
+    pub flag: f32,
+    /// Submissions at least this similar are not stored at all; they count
+    /// towards the existing genome instead.
+    pub merge: f32,
+}
+
+impl Default for SimilarityThresholds {
+    fn default() -> Self {
+        Self {
+            flag: 0.95,
+            merge: 0.9999,
+        }
+    }
+}
+
+/// A stored genome and how similar it is to the one looked up.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SimilarMatch {
+    pub id: Uuid,
+    pub similarity: f32,
+}
+
+/// The comparison vector of `genotype`.
+#[must_use]
+pub fn genome_vector(genotype: &Genotype) -> Vec<f32> {
+    let mut vector = vec![0.0; VECTOR_LEN];
+    for conn in genotype.brain.connections.iter().filter(|c| c.enabled) {
+        // Knuth's multiplicative hash spreads consecutive innovations apart.
+        let hash = (conn.innovation as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[(hash >> 32) as usize % WEIGHT_BUCKETS] += sign * conn.weight;
+    }
+    let traits = [
+        (genotype.sensing_range as f32 - 10.0) / 10.0,
+        genotype.max_speed as f32 - 1.0,
+        (genotype.max_energy as f32 - 500.0) / 500.0,
+        genotype.metabolic_niche - 0.5,
+        genotype.trophic_potential - 0.5,
+        genotype.reproductive_investment - 0.5,
+        genotype.maturity_gene - 1.0,
+        genotype.mate_preference - 0.5,
+        genotype.pairing_bias - 0.5,
+        genotype.specialization_bias[0] - 1.0 / 3.0,
+        genotype.specialization_bias[1] - 1.0 / 3.0,
+        genotype.specialization_bias[2] - 1.0 / 3.0,
+        genotype.oviparity - 0.5,
+        (f32::from(genotype.clutch_size) - 1.0) / 8.0,
+        genotype.parental_care - 0.5,
+        genotype.brain.learning_rate,
+    ];
+    vector[WEIGHT_BUCKETS..].copy_from_slice(&traits);
+    vector
+}
+
+/// Cosine similarity of two vectors, 0.0 if either is all zeros.
+#[must_use]
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom == 0.0 {
+        0.0
+    } else {
+        (dot / denom).clamp(-1.0, 1.0)
+    }
+}
+
+/// In-memory locality-sensitive hash index over genome vectors.
+pub struct SimilarityIndex {
+    planes: Vec<Vec<f32>>,
+    vectors: HashMap<Uuid, Vec<f32>>,
+    buckets: HashMap<(usize, u64), Vec<Uuid>>,
+}
+
+impl Default for SimilarityIndex {
+    fn default() -> Self {
+        let mut rng = StdRng::seed_from_u64(PLANE_SEED);
+        let planes = (0..BANDS * BAND_BITS)
+            .map(|_| (0..VECTOR_LEN).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect();
+        Self {
+            planes,
+            vectors: HashMap::new(),
+            buckets: HashMap::new(),
+        }
+    }
+}
+
+impl SimilarityIndex {
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+
+    pub fn vector(&self, id: Uuid) -> Option<&[f32]> {
+        self.vectors.get(&id).map(Vec::as_slice)
+    }
+
+    /// The band keys of `vector`: one bit per hyperplane, set when the
+    /// vector lies on its positive side.
+    fn bands(&self, vector: &[f32]) -> impl Iterator<Item = (usize, u64)> + '_ {
+        let bits: Vec<bool> = self
+            .planes
+            .iter()
+            .map(|plane| plane.iter().zip(vector).map(|(p, v)| p * v).sum::<f32>() >= 0.0)
+            .collect();
+        (0..BANDS).map(move |band| {
+            let key = bits[band * BAND_BITS..(band + 1) * BAND_BITS]
+                .iter()
+                .fold(0u64, |key, &bit| key << 1 | u64::from(bit));
+            (band, key)
+        })
+    }
+
+    pub fn insert(&mut self, id: Uuid, vector: Vec<f32>) {
+        let bands: Vec<_> = self.bands(&vector).collect();
+        for band in bands {
+            self.buckets.entry(band).or_default().push(id);
+        }
+        self.vectors.insert(id, vector);
+    }
+
+    /// Stored genomes at least `min_similarity` similar to `vector`, most
+    /// similar first. Approximate: a genome sharing no band with `vector`
+    /// is missed, which becomes likely below a similarity of about 0.8.
+    #[must_use]
+    pub fn nearest(&self, vector: &[f32], min_similarity: f32) -> Vec<SimilarMatch> {
+        let candidates: HashSet<Uuid> = self
+            .bands(vector)
+            .filter_map(|band| self.buckets.get(&band))
+            .flatten()
+            .copied()
+            .collect();
+        let mut matches: Vec<SimilarMatch> = candidates
+            .into_iter()
+            .filter_map(|id| {
+                let similarity = cosine(vector, &self.vectors[&id]);
+                (similarity >= min_similarity).then_some(SimilarMatch { id, similarity })
+            })
+            .collect();
+        matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primordium_core::brain::GenotypeLogic;
+
+    #[test]
+    fn test_index_finds_mutants_but_not_strangers() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let original = Genotype::new_random_with_rng(&mut rng);
+        let mut mutant = original.clone();
+        for conn in mutant.brain.connections.iter_mut().step_by(3) {
+            conn.weight += 0.2;
+        }
+        let stranger = Genotype::new_random_with_rng(&mut rng);
+
+        let mut index = SimilarityIndex::default();
+        let (original_id, stranger_id) = (Uuid::new_v4(), Uuid::new_v4());
+        index.insert(original_id, genome_vector(&original));
+        index.insert(stranger_id, genome_vector(&stranger));
+        assert_eq!(index.len(), 2);
+
+        let found = index.nearest(&genome_vector(&mutant), 0.95);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, original_id);
+        assert!(found[0].similarity < SimilarityThresholds::default().merge);
+
+        let exact = index.nearest(&genome_vector(&original), 0.9999);
+        assert_eq!(exact[0].id, original_id);
+        assert!(cosine(&genome_vector(&original), &genome_vector(&stranger)) < 0.5);
+    }
+}
@@ -0,0 +1,351 @@
+//! Delta encoding for stored world snapshots.
+//!
+//! Consecutive world snapshots share most of their entities and terrain, so
+//! only every
+//! [`KEYFRAME_INTERVAL`](crate::snapshot_delta::KEYFRAME_INTERVAL)th
+//! snapshot is stored in full. The others are stored as a
+//! [`SnapshotDelta`](crate::snapshot_delta::SnapshotDelta) against the
+//! previous snapshot: changed and removed entities plus changed terrain
+//! chunks. The remaining fields are small or change every tick and are
+//! stored whole. Any snapshot can be rebuilt by replaying the deltas after
+//! its keyframe with [`decode_chain`](crate::snapshot_delta::decode_chain).
+//!
+//! The same keyframes and deltas make up the live-spectator stream; see
+//! [`SpectateDecoder`](crate::snapshot_delta::SpectateDecoder).
+
+use crate::error::{IoError, Result};
+use primordium_core::influence::InfluenceGrid;
+use primordium_core::mutation_heat::MutationHeatGrid;
+use primordium_core::pheromone::PheromoneGrid;
+use primordium_core::pressure::PressureGrid;
+use primordium_core::road::RoadGrid;
+use primordium_core::snapshot::{EggSnapshot, EntitySnapshot, WorldSnapshot};
+use primordium_core::sound::SoundGrid;
+use primordium_core::terrain::TerrainChunk;
+use primordium_data::{Food, HallOfFame, Monument, PopulationStats};
+use primordium_net::spectate::{FrameKind, SpectateFrame};
+use rkyv::de::deserializers::SharedDeserializeMap;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::ser::Serializer;
+use rkyv::util::AlignedVec;
+use rkyv::{Archive, Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Stored snapshots between two keyframes, counting the keyframe.
+pub const KEYFRAME_INTERVAL: u32 = 10;
+
+/// Changes from one world snapshot to the next.
+#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+#[archive(check_bytes)]
+pub struct SnapshotDelta {
+    pub tick: u64,
+    /// Tick of the snapshot this delta applies to.
+    pub base_tick: u64,
+    /// Entities that are new or changed since the base snapshot.
+    pub upserted: Vec<EntitySnapshot>,
+    pub removed: Vec<Uuid>,
+    /// Changed terrain chunks by position in the grid.
+    pub chunks: Vec<(u32, TerrainChunk)>,
+    pub dust_bowl_timer: u32,
+    /// The terrain's water layer, which seeps every tick.
+    pub water: Vec<f32>,
+    pub food: Vec<Food>,
+    pub eggs: Vec<EggSnapshot>,
+    pub stats: Arc<PopulationStats>,
+    pub hall_of_fame: Arc<HallOfFame>,
+    pub pheromones: Arc<PheromoneGrid>,
+    pub sound: Arc<SoundGrid>,
+    pub pressure: Arc<PressureGrid>,
+    pub influence: Arc<InfluenceGrid>,
+    pub roads: Arc<RoadGrid>,
+    pub mutation_heat: Arc<MutationHeatGrid>,
+    pub monuments: Vec<Monument>,
+    pub social_grid: Arc<Vec<u8>>,
+    pub rank_grid: Arc<Vec<f32>>,
+}
+
+impl SnapshotDelta {
+    /// Diffs `next` against `base`. Both must have the same dimensions.
+    pub fn between(base: &WorldSnapshot, next: &WorldSnapshot) -> Self {
+        let previous: HashMap<Uuid, &EntitySnapshot> =
+            base.entities.iter().map(|e| (e.id, e)).collect();
+        let current: HashSet<Uuid> = next.entities.iter().map(|e| e.id).collect();
+
+        let upserted = next
+            .entities
+            .iter()
+            .filter(|e| previous.get(&e.id).is_none_or(|old| *old != *e))
+            .cloned()
+            .collect();
+        let removed = base
+            .entities
+            .iter()
+            .map(|e| e.id)
+            .filter(|id| !current.contains(id))
+            .collect();
+
+        let chunks = next
+            .terrain
+            .chunks()
+            .iter()
+            .zip(base.terrain.chunks())
+            .enumerate()
+            .filter(|(_, (new, old))| !Arc::ptr_eq(new, old) && new != old)
+            .map(|(i, (new, _))| (i as u32, (**new).clone()))
+            .collect();
+
+        Self {
+            tick: next.tick,
+            base_tick: base.tick,
+            upserted,
+            removed,
+            chunks,
+            dust_bowl_timer: next.terrain.dust_bowl_timer,
+            water: next.terrain.water().to_vec(),
+            food: next.food.clone(),
+            eggs: next.eggs.clone(),
+            stats: next.stats.clone(),
+            hall_of_fame: next.hall_of_fame.clone(),
+            pheromones: next.pheromones.clone(),
+            sound: next.sound.clone(),
+            pressure: next.pressure.clone(),
+            influence: next.influence.clone(),
+            roads: next.roads.clone(),
+            mutation_heat: next.mutation_heat.clone(),
+            monuments: next.monuments.clone(),
+            social_grid: next.social_grid.clone(),
+            rank_grid: next.rank_grid.clone(),
+        }
+    }
+
+    /// Rebuilds the snapshot this delta was taken from.
+    ///
+    /// Unchanged entities keep their order from `base`; new ones follow.
+    pub fn apply(self, base: &WorldSnapshot) -> WorldSnapshot {
+        let removed: HashSet<Uuid> = self.removed.into_iter().collect();
+        let mut upserted: HashMap<Uuid, EntitySnapshot> =
+            self.upserted.into_iter().map(|e| (e.id, e)).collect();
+        let mut entities: Vec<EntitySnapshot> = base
+            .entities
+            .iter()
+            .filter(|e| !removed.contains(&e.id))
+            .map(|e| upserted.remove(&e.id).unwrap_or_else(|| e.clone()))
+            .collect();
+        let mut added: Vec<EntitySnapshot> = upserted.into_values().collect();
+        added.sort_by_key(|e| e.id);
+        entities.extend(added);
+
+        let mut terrain = (*base.terrain).clone();
+        for (i, chunk) in self.chunks {
+            terrain.replace_chunk(i as usize, Arc::new(chunk));
+        }
+        terrain.dust_bowl_timer = self.dust_bowl_timer;
+        terrain.set_water(self.water);
+
+        WorldSnapshot {
+            tick: self.tick,
+            entities,
+            food: self.food,
+            eggs: self.eggs,
+            stats: self.stats,
+            hall_of_fame: self.hall_of_fame,
+            terrain: Arc::new(terrain),
+            pheromones: self.pheromones,
+            sound: self.sound,
+            pressure: self.pressure,
+            influence: self.influence,
+            roads: self.roads,
+            mutation_heat: self.mutation_heat,
+            monuments: self.monuments,
+            social_grid: self.social_grid,
+            rank_grid: self.rank_grid,
+            width: base.width,
+            height: base.height,
+        }
+    }
+}
+
+/// A snapshot ready to be stored.
+#[derive(Debug)]
+pub enum EncodedSnapshot {
+    /// A full snapshot.
+    Keyframe(Vec<u8>),
+    /// A [`SnapshotDelta`] against the snapshot stored at `base_tick`.
+    Delta { base_tick: u64, bytes: Vec<u8> },
+}
+
+impl EncodedSnapshot {
+    pub fn is_keyframe(&self) -> bool {
+        matches!(self, EncodedSnapshot::Keyframe(_))
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            EncodedSnapshot::Keyframe(bytes) | EncodedSnapshot::Delta { bytes, .. } => bytes,
+        }
+    }
+}
+
+/// Encodes a stream of snapshots as keyframes and deltas.
+#[derive(Debug)]
+pub struct SnapshotEncoder {
+    keyframe_interval: u32,
+    since_keyframe: u32,
+    last: Option<WorldSnapshot>,
+}
+
+impl Default for SnapshotEncoder {
+    fn default() -> Self {
+        Self::new(KEYFRAME_INTERVAL)
+    }
+}
+
+impl SnapshotEncoder {
+    pub fn new(keyframe_interval: u32) -> Self {
+        Self {
+            keyframe_interval: keyframe_interval.max(1),
+            since_keyframe: 0,
+            last: None,
+        }
+    }
+
+    /// Encodes `snapshot` against the previously encoded one. A keyframe is
+    /// written every `keyframe_interval` snapshots, for the first snapshot,
+    /// and whenever the world size changes.
+    pub fn encode(&mut self, snapshot: WorldSnapshot) -> Result<EncodedSnapshot> {
+        let base = self.last.as_ref().filter(|last| {
+            self.since_keyframe < self.keyframe_interval
+                && last.width == snapshot.width
+                && last.height == snapshot.height
+                && last.terrain.chunks().len() == snapshot.terrain.chunks().len()
+                && last.tick < snapshot.tick
+        });
+        let encoded = match base {
+            Some(base) => {
+                let delta = SnapshotDelta::between(base, &snapshot);
+                EncodedSnapshot::Delta {
+                    base_tick: base.tick,
+                    bytes: to_bytes(&delta)?,
+                }
+            }
+            None => EncodedSnapshot::Keyframe(to_bytes(&snapshot)?),
+        };
+        self.since_keyframe = if encoded.is_keyframe() {
+            1
+        } else {
+            self.since_keyframe + 1
+        };
+        self.last = Some(snapshot);
+        Ok(encoded)
+    }
+}
+
+/// Rebuilds the last snapshot of a chain: a keyframe followed by the deltas
+/// stored after it, in tick order.
+pub fn decode_chain<'a>(
+    keyframe: &[u8],
+    deltas: impl IntoIterator<Item = &'a [u8]>,
+) -> Result<WorldSnapshot> {
+    let mut snapshot: WorldSnapshot = from_bytes(keyframe)?;
+    for bytes in deltas {
+        let delta: SnapshotDelta = from_bytes(bytes)?;
+        if delta.base_tick != snapshot.tick {
+            return Err(IoError::rkyv(format!(
+                "delta for tick {} expects base tick {}, found {}",
+                delta.tick, delta.base_tick, snapshot.tick
+            )));
+        }
+        snapshot = delta.apply(&snapshot);
+    }
+    Ok(snapshot)
+}
+
+/// Largest snapshot a spectator inflates from one frame.
+pub const MAX_SPECTATE_SNAPSHOT: usize = 256 * 1024 * 1024;
+
+impl EncodedSnapshot {
+    /// This snapshot, taken at `tick`, as a frame of a live-spectator
+    /// stream.
+    pub fn to_spectate_frame(&self, tick: u64) -> SpectateFrame {
+        match self {
+            EncodedSnapshot::Keyframe(bytes) => {
+                SpectateFrame::pack(FrameKind::Keyframe, tick, tick, bytes)
+            }
+            EncodedSnapshot::Delta { base_tick, bytes } => {
+                SpectateFrame::pack(FrameKind::Delta, tick, *base_tick, bytes)
+            }
+        }
+    }
+}
+
+/// Rebuilds a publisher's world from its spectator stream.
+///
+/// Frames at or before the current tick are skipped, so a stream replayed
+/// from an earlier keyframe after the spectator fell behind picks up where
+/// it left off.
+#[derive(Debug, Default)]
+pub struct SpectateDecoder {
+    current: Option<WorldSnapshot>,
+}
+
+impl SpectateDecoder {
+    /// The latest rebuilt snapshot, once a keyframe has arrived.
+    pub fn current(&self) -> Option<&WorldSnapshot> {
+        self.current.as_ref()
+    }
+
+    /// Applies `frame`, returning the new snapshot or `None` if the frame
+    /// was skipped. A delta that does not follow the current snapshot is an
+    /// error: frames were lost and the spectator should reconnect.
+    pub fn apply(&mut self, frame: &SpectateFrame) -> Result<Option<&WorldSnapshot>> {
+        let current_tick = self.current.as_ref().map(|s| s.tick);
+        if current_tick.is_some_and(|tick| frame.tick <= tick) {
+            return Ok(None);
+        }
+        let raw = frame
+            .unpack(MAX_SPECTATE_SNAPSHOT)
+            .map_err(|e| IoError::compression(e.to_string()))?;
+        let next = match (frame.kind, self.current.take()) {
+            (FrameKind::Keyframe, _) => from_bytes::<WorldSnapshot>(&raw)?,
+            (FrameKind::Delta, Some(base)) if base.tick == frame.base_tick => {
+                from_bytes::<SnapshotDelta>(&raw)?.apply(&base)
+            }
+            (FrameKind::Delta, base) => {
+                self.current = base;
+                return Err(IoError::validation(format!(
+                    "delta for tick {} expects base tick {}, have {:?}",
+                    frame.tick, frame.base_tick, current_tick
+                )));
+            }
+        };
+        Ok(Some(self.current.insert(next)))
+    }
+}
+
+fn to_bytes<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: Serialize<AllocSerializer<4096>>,
+{
+    let mut serializer = AllocSerializer::<4096>::default();
+    serializer
+        .serialize_value(value)
+        .map_err(|e| IoError::rkyv(format!("serialization failed: {:?}", e)))?;
+    Ok(serializer.into_serializer().into_inner().to_vec())
+}
+
+fn from_bytes<T>(bytes: &[u8]) -> Result<T>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, SharedDeserializeMap>
+        + for<'a> rkyv::CheckBytes<rkyv::validation::validators::DefaultValidator<'a>>,
+{
+    let mut aligned = AlignedVec::with_capacity(bytes.len());
+    aligned.extend_from_slice(bytes);
+    let archived = rkyv::check_archived_root::<T>(&aligned)
+        .map_err(|e| IoError::rkyv(format!("validation failed: {:?}", e)))?;
+    archived
+        .deserialize(&mut SharedDeserializeMap::default())
+        .map_err(|e| IoError::rkyv(format!("deserialization failed: {:?}", e)))
+}
@@ -1,13 +1,88 @@
+use crate::challenge::{ChallengeMetric, Target};
+use crate::profile::PlayerProfile;
+use crate::similarity::{genome_vector, SimilarMatch, SimilarityIndex, SimilarityThresholds};
+use crate::snapshot_delta::{decode_chain, EncodedSnapshot, SnapshotEncoder};
 use anyhow::Result;
+use primordium_core::brain::GenotypeLogic;
 use primordium_core::lineage_registry::LineageRegistry;
-use primordium_data::FossilRegistry;
-use rusqlite::{params, Connection};
+use primordium_core::snapshot::WorldSnapshot;
+use primordium_data::{FossilRegistry, Genotype, RunManifest};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::mpsc::{self, Sender};
 use std::thread;
 use uuid::Uuid;
 
+/// Where a marketplace submission is in moderation.
+///
+/// Submissions start `Pending` on servers that require approval and
+/// `Approved` otherwise, and only approved ones are public. A moderator may
+/// later take an approved submission down or reinstate a rejected one, but
+/// nothing goes back to `Pending`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationStatus {
+    Pending,
+    /// The default for records from servers that predate moderation.
+    #[default]
+    Approved,
+    Rejected,
+}
+
+impl ModerationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ModerationStatus::Pending => "pending",
+            ModerationStatus::Approved => "approved",
+            ModerationStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(ModerationStatus::Pending),
+            "approved" => Some(ModerationStatus::Approved),
+            "rejected" => Some(ModerationStatus::Rejected),
+            _ => None,
+        }
+    }
+
+    /// Whether a moderator may move a submission from `self` to `next`.
+    pub fn can_become(self, next: ModerationStatus) -> bool {
+        next != self && next != ModerationStatus::Pending
+    }
+}
+
+impl std::fmt::Display for ModerationStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Why a moderation decision could not be applied.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ModerationError {
+    #[error("no submission with that id")]
+    NotFound,
+    #[error("a {from} submission cannot become {to}")]
+    InvalidTransition {
+        from: ModerationStatus,
+        to: ModerationStatus,
+    },
+    #[error("storage error: {0}")]
+    Storage(String),
+}
+
+/// The moderation state of one submission, as shown to its submitter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmissionState {
+    pub id: Uuid,
+    pub status: ModerationStatus,
+    /// The moderator's reason, if one was given.
+    pub note: Option<String>,
+}
+
 /// A genome record in the marketplace.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenomeRecord {
@@ -23,6 +98,66 @@ pub struct GenomeRecord {
     pub tick: u64,
     pub downloads: u32,
     pub created_at: String,
+    #[serde(default)]
+    pub status: ModerationStatus,
+    #[serde(default)]
+    pub moderation_note: Option<String>,
+    /// The earlier genome this one was flagged as a near copy of.
+    #[serde(default)]
+    pub duplicate_of: Option<Uuid>,
+    /// How similar it is to `duplicate_of`.
+    #[serde(default)]
+    pub similarity: Option<f32>,
+    /// Later submissions merged into this one as copies.
+    #[serde(default)]
+    pub merged_count: u32,
+    /// The run it came from, if the client reported one.
+    #[serde(default)]
+    pub provenance: Option<RunManifest>,
+}
+
+/// What became of a genome submission after the duplicate check.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "lowercase")]
+pub enum GenomeSubmitOutcome {
+    /// Stored under its own id, flagged if it resembles a stored genome.
+    Stored {
+        id: Uuid,
+        duplicate_of: Option<SimilarMatch>,
+    },
+    /// Not stored: it is a copy of `into`, which is in state `status`.
+    Merged {
+        into: SimilarMatch,
+        status: ModerationStatus,
+    },
+}
+
+/// A public genome and how similar it is to the one looked up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarGenome {
+    #[serde(flatten)]
+    pub genome: GenomeRecord,
+    pub similarity: f32,
+}
+
+/// What to find genomes similar to.
+#[derive(Debug, Clone)]
+pub enum SimilarityQuery {
+    /// A stored genome submission.
+    Submission(Uuid),
+    /// A hex-encoded genotype.
+    Genotype(String),
+}
+
+/// Why a similarity search could not run.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SimilarityError {
+    #[error("no genome with that id")]
+    NotFound,
+    #[error("invalid genotype: {0}")]
+    InvalidGenotype(String),
+    #[error("storage error: {0}")]
+    Storage(String),
 }
 
 /// A seed (simulation config) record in the marketplace.
@@ -39,6 +174,55 @@ pub struct SeedRecord {
     pub performance_summary: String,
     pub downloads: u32,
     pub created_at: String,
+    #[serde(default)]
+    pub status: ModerationStatus,
+    #[serde(default)]
+    pub moderation_note: Option<String>,
+    /// Whether a preview PNG was published with the seed.
+    #[serde(default)]
+    pub has_thumbnail: bool,
+    /// The run it was published from, if the client reported one.
+    #[serde(default)]
+    pub provenance: Option<RunManifest>,
+}
+
+/// A challenge scenario in the registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioRecord {
+    pub id: Uuid,
+    pub author: String,
+    pub name: String,
+    pub description: String,
+    /// The challenge file, as published.
+    pub scenario_toml: String,
+    pub ticks: u64,
+    pub score_metric: ChallengeMetric,
+    pub targets: Vec<Target>,
+    pub downloads: u32,
+    pub created_at: String,
+}
+
+/// A player's best run of a scenario.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub player: String,
+    pub score: f64,
+    pub passed: bool,
+    pub ticks: u64,
+    pub signature: String,
+    pub created_at: String,
+    /// The run behind the result, if the client reported one.
+    #[serde(default)]
+    pub provenance: Option<RunManifest>,
+}
+
+/// Why a scenario result could not be recorded.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ScenarioError {
+    #[error("no scenario with that id")]
+    NotFound,
+    #[error("storage error: {0}")]
+    Storage(String),
 }
 
 /// Commands for the background storage management thread.
@@ -58,13 +242,13 @@ pub enum StorageCommand {
         genotype: String,
         reason: String,
     },
-    /// Saves a macro-state snapshot of the world.
+    /// Saves a macro-state snapshot of the world, delta-encoded against the
+    /// previously saved one.
     SaveSnapshot {
-        tick: u64,
         pop_count: u32,
         carbon_level: f64,
         energy_total: f64,
-        world_data: Vec<u8>,
+        snapshot: Box<WorldSnapshot>,
     },
     /// Batch synchronises the entire lineage registry.
     BatchSyncLineages(LineageRegistry),
@@ -74,9 +258,9 @@ pub enum StorageCommand {
     QueryFossils(Uuid, Sender<Vec<(u64, String)>>),
     /// Queries the top lineages for the Hall of Fame.
     QueryHallOfFame(Sender<Vec<(Uuid, u32, bool)>>),
-    /// Queries a specific world snapshot by tick.
-    QuerySnapshot(u64, Sender<Option<Vec<u8>>>),
-    /// Submits a genome to the marketplace.
+    /// Rebuilds the last world snapshot stored at or before a tick.
+    QuerySnapshot(u64, Sender<Option<WorldSnapshot>>),
+    /// Submits a genome to the marketplace unless it copies a stored one.
     SubmitGenome {
         id: Uuid,
         lineage_id: Option<Uuid>,
@@ -88,6 +272,10 @@ pub enum StorageCommand {
         fitness_score: f64,
         offspring_count: u32,
         tick: u64,
+        status: ModerationStatus,
+        thresholds: SimilarityThresholds,
+        provenance: Option<RunManifest>,
+        reply_tx: Option<Sender<GenomeSubmitOutcome>>,
     },
     /// Submits a seed (simulation config) to the marketplace.
     SubmitSeed {
@@ -100,6 +288,14 @@ pub enum StorageCommand {
         avg_tick_time: f64,
         max_pop: u32,
         performance_summary: String,
+        thumbnail: Option<Vec<u8>>,
+        status: ModerationStatus,
+        provenance: Option<RunManifest>,
+    },
+    /// Query the preview PNG of an approved seed.
+    QuerySeedThumbnail {
+        id: Uuid,
+        reply_tx: Sender<Option<Vec<u8>>>,
     },
     /// Query genomes from marketplace.
     QueryGenomes {
@@ -113,6 +309,59 @@ pub enum StorageCommand {
         sort_by: Option<String>, // 'pop', 'downloads'
         reply_tx: Sender<Vec<SeedRecord>>,
     },
+    /// Finds approved genomes at least `min_similarity` similar to `query`,
+    /// most similar first.
+    FindSimilarGenomes {
+        query: SimilarityQuery,
+        min_similarity: f32,
+        limit: usize,
+        reply_tx: Sender<Result<Vec<SimilarGenome>, SimilarityError>>,
+    },
+    /// Publishes a challenge scenario.
+    PublishScenario(ScenarioSubmit),
+    /// Queries published scenarios, newest first.
+    QueryScenarios {
+        limit: Option<usize>,
+        reply_tx: Sender<Vec<ScenarioRecord>>,
+    },
+    /// Queries one scenario, counting a download if `download` is set.
+    QueryScenario {
+        id: Uuid,
+        download: bool,
+        reply_tx: Sender<Option<ScenarioRecord>>,
+    },
+    /// Records a run of a scenario, keeping only each player's best. Replies
+    /// with the player's rank.
+    SubmitScenarioResult {
+        result: ScenarioResultSubmit,
+        reply_tx: Sender<Result<usize, ScenarioError>>,
+    },
+    /// Queries the best runs of a scenario: passing runs first, then by score.
+    QueryLeaderboard {
+        scenario_id: Uuid,
+        limit: usize,
+        reply_tx: Sender<Vec<LeaderboardEntry>>,
+    },
+    /// Queries the genome and seed submissions in one moderation state,
+    /// oldest first.
+    QuerySubmissions {
+        status: ModerationStatus,
+        reply_tx: Sender<(Vec<GenomeRecord>, Vec<SeedRecord>)>,
+    },
+    /// Moves a submission to a new moderation state. Replies with the state
+    /// it left.
+    ModerateSubmission {
+        id: Uuid,
+        status: ModerationStatus,
+        note: Option<String>,
+        reply_tx: Sender<Result<ModerationStatus, ModerationError>>,
+    },
+    /// Queries the moderation state of one genome or seed submission.
+    QuerySubmissionState(Uuid, Sender<Option<SubmissionState>>),
+    /// Replaces the stored player profile.
+    SaveProfile(PlayerProfile),
+    /// Queries the player profile (the default profile if none is stored).
+    QueryProfile(Sender<PlayerProfile>),
     /// Shutdown the storage thread.
     Stop,
 }
@@ -137,6 +386,32 @@ pub struct GenomeSubmit {
     pub fitness_score: f64,
     pub offspring_count: u32,
     pub tick: u64,
+    /// `Pending` holds it back from public queries until approved.
+    pub status: ModerationStatus,
+    /// When it counts as a copy of a stored genome.
+    pub thresholds: SimilarityThresholds,
+    pub provenance: Option<RunManifest>,
+}
+
+impl GenomeSubmit {
+    fn into_command(self, reply_tx: Option<Sender<GenomeSubmitOutcome>>) -> StorageCommand {
+        StorageCommand::SubmitGenome {
+            id: self.id,
+            lineage_id: self.lineage_id,
+            genotype: self.genotype,
+            author: self.author,
+            name: self.name,
+            description: self.description,
+            tags: self.tags,
+            fitness_score: self.fitness_score,
+            offspring_count: self.offspring_count,
+            tick: self.tick,
+            status: self.status,
+            thresholds: self.thresholds,
+            provenance: self.provenance,
+            reply_tx,
+        }
+    }
 }
 
 /// Parameters for submitting a seed to the marketplace.
@@ -150,6 +425,35 @@ pub struct SeedSubmit {
     pub avg_tick_time: f64,
     pub max_pop: u32,
     pub performance_summary: String,
+    /// Preview PNG of the world the seed came from.
+    pub thumbnail: Option<Vec<u8>>,
+    /// `Pending` holds it back from public queries until approved.
+    pub status: ModerationStatus,
+    pub provenance: Option<RunManifest>,
+}
+
+/// Parameters for publishing a challenge scenario.
+pub struct ScenarioSubmit {
+    pub id: Uuid,
+    pub author: String,
+    pub name: String,
+    pub description: String,
+    pub scenario_toml: String,
+    pub ticks: u64,
+    pub score_metric: ChallengeMetric,
+    pub targets: Vec<Target>,
+}
+
+/// Parameters for recording a run of a scenario.
+pub struct ScenarioResultSubmit {
+    pub scenario_id: Uuid,
+    pub player: String,
+    pub score: f64,
+    pub passed: bool,
+    pub ticks: u64,
+    pub world_hash: String,
+    pub signature: String,
+    pub provenance: Option<RunManifest>,
 }
 
 impl StorageManager {
@@ -181,6 +485,12 @@ impl StorageManager {
             let _ = conn.execute("PRAGMA synchronous = NORMAL", []);
             let _ = conn.execute("PRAGMA mmap_size = 30000000000", []);
 
+            let mut encoder = SnapshotEncoder::default();
+            let mut similarity = load_similarity_index(&conn).unwrap_or_else(|e| {
+                eprintln!("Failed to index genome submissions: {}", e);
+                SimilarityIndex::default()
+            });
+
             while let Ok(cmd) = rx.recv() {
                 match cmd {
                     StorageCommand::UpsertLineage {
@@ -213,17 +523,33 @@ impl StorageManager {
                         );
                     }
                     StorageCommand::SaveSnapshot {
-                        tick,
                         pop_count,
                         carbon_level,
                         energy_total,
-                        world_data,
+                        snapshot,
                     } => {
-                        let _ = conn.execute(
-                            "INSERT INTO world_snapshots (tick, pop_count, carbon_level, energy_total, world_data)
-                              VALUES (?1, ?2, ?3, ?4, ?5)",
-                            params![tick, pop_count, carbon_level, energy_total, world_data],
+                        let tick = snapshot.tick;
+                        let encoded = match encoder.encode(*snapshot) {
+                            Ok(e) => e,
+                            Err(e) => {
+                                eprintln!("Failed to encode snapshot: {}", e);
+                                encoder = SnapshotEncoder::default();
+                                continue;
+                            }
+                        };
+                        let base_tick = match &encoded {
+                            EncodedSnapshot::Keyframe(_) => None,
+                            EncodedSnapshot::Delta { base_tick, .. } => Some(*base_tick),
+                        };
+                        let inserted = conn.execute(
+                            "INSERT INTO world_snapshots (tick, pop_count, carbon_level, energy_total, world_data, base_tick)
+                              VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                            params![tick, pop_count, carbon_level, energy_total, encoded.bytes(), base_tick],
                         );
+                        // The next delta must not refer to a row that was not written.
+                        if inserted.is_err() {
+                            encoder = SnapshotEncoder::default();
+                        }
                     }
 
                     StorageCommand::BatchSyncLineages(registry) => {
@@ -306,15 +632,13 @@ impl StorageManager {
                         }
                     }
                     StorageCommand::QuerySnapshot(tick, reply_tx) => {
-                        let mut stmt = match conn
-                            .prepare("SELECT world_data FROM world_snapshots WHERE tick = ?1")
-                        {
-                            Ok(s) => s,
-                            Err(_) => continue,
+                        let result = match load_snapshot(&conn, tick) {
+                            Ok(snapshot) => snapshot,
+                            Err(e) => {
+                                eprintln!("Failed to load snapshot at tick {}: {}", tick, e);
+                                None
+                            }
                         };
-
-                        let result: Option<Vec<u8>> =
-                            stmt.query_row(params![tick], |row| row.get(0)).ok();
                         let _ = reply_tx.send(result);
                     }
                     StorageCommand::SubmitGenome {
@@ -328,16 +652,49 @@ impl StorageManager {
                         fitness_score,
                         offspring_count,
                         tick,
+                        status,
+                        thresholds,
+                        provenance,
+                        reply_tx,
                     } => {
-                        let lineage_id_str =
-                            lineage_id.map(|id| id.to_string()).unwrap_or_default();
-                        let _ = conn.execute(
-                            "INSERT INTO genome_submissions (id, lineage_id, genotype, author, name, description, tags, fitness_score, offspring_count, tick)
-                              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-                            params![
-                                id, lineage_id_str, genotype, author, name, description, tags, fitness_score, offspring_count, tick
-                            ],
-                        );
+                        let vector = Genotype::from_hex(&genotype)
+                            .ok()
+                            .map(|g| genome_vector(&g));
+                        let nearest = vector
+                            .as_ref()
+                            .and_then(|v| nearest_live(&conn, &similarity, v, thresholds.flag));
+                        let outcome = match nearest {
+                            Some((m, existing)) if m.similarity >= thresholds.merge => {
+                                let _ = conn.execute(
+                                    "UPDATE genome_submissions SET merged_count = merged_count + 1 WHERE id = ?1",
+                                    params![m.id],
+                                );
+                                GenomeSubmitOutcome::Merged {
+                                    into: m,
+                                    status: existing,
+                                }
+                            }
+                            _ => {
+                                let duplicate_of = nearest.map(|(m, _)| m);
+                                // NULL rather than "" keeps the lineage foreign key satisfied.
+                                let lineage_id_str = lineage_id.map(|id| id.to_string());
+                                let _ = conn.execute(
+                                    "INSERT INTO genome_submissions (id, lineage_id, genotype, author, name, description, tags, fitness_score, offspring_count, tick, status, duplicate_of, similarity, provenance)
+                                      VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                                    params![
+                                        id, lineage_id_str, genotype, author, name, description, tags, fitness_score, offspring_count, tick, status.as_str(),
+                                        duplicate_of.map(|m| m.id), duplicate_of.map(|m| m.similarity), provenance_json(provenance.as_ref())
+                                    ],
+                                );
+                                if let Some(vector) = vector {
+                                    similarity.insert(id, vector);
+                                }
+                                GenomeSubmitOutcome::Stored { id, duplicate_of }
+                            }
+                        };
+                        if let Some(reply_tx) = reply_tx {
+                            let _ = reply_tx.send(outcome);
+                        }
                     }
                     StorageCommand::SubmitSeed {
                         id,
@@ -349,15 +706,32 @@ impl StorageManager {
                         avg_tick_time,
                         max_pop,
                         performance_summary,
+                        thumbnail,
+                        status,
+                        provenance,
                     } => {
                         let _ = conn.execute(
-                            "INSERT INTO seed_submissions (id, author, name, description, tags, config_json, avg_tick_time, max_pop, performance_summary)
-                              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                            "INSERT INTO seed_submissions (id, author, name, description, tags, config_json, avg_tick_time, max_pop, performance_summary, thumbnail, status, provenance)
+                              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
                             params![
-                                id, author, name, description, tags, config_json, avg_tick_time, max_pop, performance_summary
+                                id, author, name, description, tags, config_json, avg_tick_time, max_pop, performance_summary, thumbnail, status.as_str(),
+                                provenance_json(provenance.as_ref())
                             ],
                         );
                     }
+                    StorageCommand::QuerySeedThumbnail { id, reply_tx } => {
+                        let thumbnail = conn
+                            .query_row(
+                                "SELECT thumbnail FROM seed_submissions WHERE id = ?1 AND status = 'approved'",
+                                params![id],
+                                |row| row.get(0),
+                            )
+                            .optional()
+                            .ok()
+                            .flatten()
+                            .flatten();
+                        let _ = reply_tx.send(thumbnail);
+                    }
                     StorageCommand::QueryGenomes {
                         limit,
                         sort_by,
@@ -373,37 +747,11 @@ impl StorageManager {
                         let limit_clause =
                             limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
                         let query = format!(
-                            "SELECT id, lineage_id, genotype, author, name, description, tags, fitness_score, offspring_count, tick, downloads, created_at
-                             FROM genome_submissions ORDER BY {}{}",
+                            "SELECT {GENOME_COLUMNS} FROM genome_submissions
+                             WHERE status = 'approved' ORDER BY {}{}",
                             order_by, limit_clause
                         );
-
-                        let mut stmt = match conn.prepare(&query) {
-                            Ok(s) => s,
-                            Err(_) => continue,
-                        };
-
-                        let rows = stmt.query_map([], |row| {
-                            let lineage_id_str: Option<String> = row.get(1)?;
-                            let lineage_id = lineage_id_str.and_then(|s| Uuid::parse_str(&s).ok());
-                            Ok(GenomeRecord {
-                                id: row.get(0)?,
-                                lineage_id,
-                                genotype: row.get(2)?,
-                                author: row.get(3)?,
-                                name: row.get(4)?,
-                                description: row.get(5)?,
-                                tags: row.get(6)?,
-                                fitness_score: row.get(7)?,
-                                offspring_count: row.get(8)?,
-                                tick: row.get(9)?,
-                                downloads: row.get(10)?,
-                                created_at: row.get(11)?,
-                            })
-                        });
-
-                        if let Ok(iter) = rows {
-                            let results: Vec<GenomeRecord> = iter.filter_map(Result::ok).collect();
+                        if let Ok(results) = query_records(&conn, &query, genome_from_row) {
                             let _ = reply_tx.send(results);
                         }
                     }
@@ -421,37 +769,156 @@ impl StorageManager {
                         let limit_clause =
                             limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
                         let query = format!(
-                            "SELECT id, author, name, description, tags, config_json, avg_tick_time, max_pop, performance_summary, downloads, created_at
-                             FROM seed_submissions ORDER BY {}{}",
+                            "SELECT {SEED_COLUMNS} FROM seed_submissions
+                             WHERE status = 'approved' ORDER BY {}{}",
                             order_by, limit_clause
                         );
-
-                        let mut stmt = match conn.prepare(&query) {
-                            Ok(s) => s,
-                            Err(_) => continue,
-                        };
-
-                        let rows = stmt.query_map([], |row| {
-                            Ok(SeedRecord {
-                                id: row.get(0)?,
-                                author: row.get(1)?,
-                                name: row.get(2)?,
-                                description: row.get(3)?,
-                                tags: row.get(4)?,
-                                config_json: row.get(5)?,
-                                avg_tick_time: row.get(6)?,
-                                max_pop: row.get(7)?,
-                                performance_summary: row.get(8)?,
-                                downloads: row.get(9)?,
-                                created_at: row.get(10)?,
-                            })
-                        });
-
-                        if let Ok(iter) = rows {
-                            let results: Vec<SeedRecord> = iter.filter_map(Result::ok).collect();
+                        if let Ok(results) = query_records(&conn, &query, seed_from_row) {
+                            let _ = reply_tx.send(results);
+                        }
+                    }
+                    StorageCommand::FindSimilarGenomes {
+                        query,
+                        min_similarity,
+                        limit,
+                        reply_tx,
+                    } => {
+                        let _ = reply_tx.send(find_similar(
+                            &conn,
+                            &similarity,
+                            query,
+                            min_similarity,
+                            limit,
+                        ));
+                    }
+                    StorageCommand::PublishScenario(scenario) => {
+                        let targets = serde_json::to_string(&scenario.targets).unwrap_or_default();
+                        let _ = conn.execute(
+                            "INSERT INTO scenarios (id, author, name, description, scenario_toml, ticks, score_metric, targets)
+                              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                            params![
+                                scenario.id, scenario.author, scenario.name, scenario.description, scenario.scenario_toml,
+                                scenario.ticks, scenario.score_metric.label(), targets
+                            ],
+                        );
+                    }
+                    StorageCommand::QueryScenarios { limit, reply_tx } => {
+                        let limit_clause =
+                            limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
+                        let query = format!(
+                            "SELECT {SCENARIO_COLUMNS} FROM scenarios ORDER BY created_at DESC{}",
+                            limit_clause
+                        );
+                        if let Ok(results) = query_records(&conn, &query, scenario_from_row) {
                             let _ = reply_tx.send(results);
                         }
                     }
+                    StorageCommand::QueryScenario {
+                        id,
+                        download,
+                        reply_tx,
+                    } => {
+                        if download {
+                            let _ = conn.execute(
+                                "UPDATE scenarios SET downloads = downloads + 1 WHERE id = ?1",
+                                params![id],
+                            );
+                        }
+                        let scenario = conn
+                            .query_row(
+                                &format!("SELECT {SCENARIO_COLUMNS} FROM scenarios WHERE id = ?1"),
+                                params![id],
+                                scenario_from_row,
+                            )
+                            .optional()
+                            .ok()
+                            .flatten();
+                        let _ = reply_tx.send(scenario);
+                    }
+                    StorageCommand::SubmitScenarioResult { result, reply_tx } => {
+                        let _ = reply_tx.send(record_scenario_result(&conn, &result));
+                    }
+                    StorageCommand::QueryLeaderboard {
+                        scenario_id,
+                        limit,
+                        reply_tx,
+                    } => {
+                        let leaderboard = conn
+                            .prepare(
+                                "SELECT player, score, passed, ticks, signature, created_at, provenance FROM scenario_results
+                                 WHERE scenario_id = ?1 ORDER BY passed DESC, score DESC, created_at LIMIT ?2",
+                            )
+                            .and_then(|mut stmt| {
+                                stmt.query_map(params![scenario_id, limit], |row| {
+                                    Ok(LeaderboardEntry {
+                                        player: row.get(0)?,
+                                        score: row.get(1)?,
+                                        passed: row.get(2)?,
+                                        ticks: row.get(3)?,
+                                        signature: row.get(4)?,
+                                        created_at: row.get(5)?,
+                                        provenance: provenance_from_row(row, 6)?,
+                                    })
+                                })?
+                                .collect::<rusqlite::Result<Vec<_>>>()
+                            });
+                        let _ = reply_tx.send(leaderboard.unwrap_or_default());
+                    }
+                    StorageCommand::QuerySubmissions { status, reply_tx } => {
+                        let genomes = query_records(
+                            &conn,
+                            &format!(
+                                "SELECT {GENOME_COLUMNS} FROM genome_submissions
+                                 WHERE status = '{status}' ORDER BY created_at"
+                            ),
+                            genome_from_row,
+                        );
+                        let seeds = query_records(
+                            &conn,
+                            &format!(
+                                "SELECT {SEED_COLUMNS} FROM seed_submissions
+                                 WHERE status = '{status}' ORDER BY created_at"
+                            ),
+                            seed_from_row,
+                        );
+                        let _ =
+                            reply_tx.send((genomes.unwrap_or_default(), seeds.unwrap_or_default()));
+                    }
+                    StorageCommand::ModerateSubmission {
+                        id,
+                        status,
+                        note,
+                        reply_tx,
+                    } => {
+                        let _ = reply_tx.send(moderate(&conn, id, status, note));
+                    }
+                    StorageCommand::QuerySubmissionState(id, reply_tx) => {
+                        let state = submission_state(&conn, id).ok().flatten();
+                        let _ = reply_tx.send(state.map(|(_, status, note)| SubmissionState {
+                            id,
+                            status,
+                            note,
+                        }));
+                    }
+                    StorageCommand::SaveProfile(profile) => {
+                        if let Ok(data) = serde_json::to_string(&profile) {
+                            let _ = conn.execute(
+                                "INSERT INTO profile (id, data) VALUES (1, ?1)
+                                  ON CONFLICT(id) DO UPDATE SET data = excluded.data",
+                                params![data],
+                            );
+                        }
+                    }
+                    StorageCommand::QueryProfile(reply_tx) => {
+                        let profile = conn
+                            .query_row("SELECT data FROM profile WHERE id = 1", [], |row| {
+                                row.get::<_, String>(0)
+                            })
+                            .ok()
+                            .and_then(|data| serde_json::from_str(&data).ok())
+                            .unwrap_or_default();
+                        let _ = reply_tx.send(profile);
+                    }
                     StorageCommand::Stop => break,
                 }
             }
@@ -488,21 +955,19 @@ impl StorageManager {
         });
     }
 
-    /// Queues a world snapshot save.
+    /// Queues a world snapshot save. Encoding happens on the storage thread.
     pub fn save_snapshot(
         &self,
-        tick: u64,
+        snapshot: WorldSnapshot,
         pop_count: u32,
         carbon_level: f64,
         energy_total: f64,
-        world_data: Vec<u8>,
     ) {
         let _ = self.sender.send(StorageCommand::SaveSnapshot {
-            tick,
             pop_count,
             carbon_level,
             energy_total,
-            world_data,
+            snapshot: Box::new(snapshot),
         });
     }
 
@@ -549,8 +1014,9 @@ impl StorageManager {
         }
     }
 
-    /// Asynchronously queries a world snapshot by tick.
-    pub fn query_snapshot_async(&self, tick: u64) -> Option<mpsc::Receiver<Option<Vec<u8>>>> {
+    /// Asynchronously rebuilds the last world snapshot stored at or before
+    /// `tick`.
+    pub fn query_snapshot_async(&self, tick: u64) -> Option<mpsc::Receiver<Option<WorldSnapshot>>> {
         let (tx, rx) = mpsc::channel();
         if self
             .sender
@@ -567,18 +1033,21 @@ impl StorageManager {
 
     /// Submits a genome to the marketplace.
     pub fn submit_genome(&self, params: GenomeSubmit) {
-        let _ = self.sender.send(StorageCommand::SubmitGenome {
-            id: params.id,
-            lineage_id: params.lineage_id,
-            genotype: params.genotype,
-            author: params.author,
-            name: params.name,
-            description: params.description,
-            tags: params.tags,
-            fitness_score: params.fitness_score,
-            offspring_count: params.offspring_count,
-            tick: params.tick,
-        });
+        let _ = self.sender.send(params.into_command(None));
+    }
+
+    /// Submits a genome and reports whether it was stored, flagged as a
+    /// near copy or merged into a stored copy.
+    pub fn submit_genome_async(
+        &self,
+        params: GenomeSubmit,
+    ) -> Option<mpsc::Receiver<GenomeSubmitOutcome>> {
+        let (tx, rx) = mpsc::channel();
+        if self.sender.send(params.into_command(Some(tx))).is_ok() {
+            Some(rx)
+        } else {
+            None
+        }
     }
 
     /// Submits a seed (simulation config) to the marketplace.
@@ -593,9 +1062,26 @@ impl StorageManager {
             avg_tick_time: params.avg_tick_time,
             max_pop: params.max_pop,
             performance_summary: params.performance_summary,
+            thumbnail: params.thumbnail,
+            status: params.status,
+            provenance: params.provenance,
         });
     }
 
+    /// Asynchronously queries the preview PNG of an approved seed.
+    pub fn query_seed_thumbnail_async(&self, id: Uuid) -> Option<mpsc::Receiver<Option<Vec<u8>>>> {
+        let (tx, rx) = mpsc::channel();
+        if self
+            .sender
+            .send(StorageCommand::QuerySeedThumbnail { id, reply_tx: tx })
+            .is_ok()
+        {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+
     /// Asynchronously queries genomes from marketplace.
     pub fn query_genomes_async(
         &self,
@@ -639,6 +1125,509 @@ impl StorageManager {
             None
         }
     }
+
+    /// Asynchronously finds the approved genomes most similar to `query`.
+    pub fn find_similar_genomes_async(
+        &self,
+        query: SimilarityQuery,
+        min_similarity: f32,
+        limit: usize,
+    ) -> Option<mpsc::Receiver<Result<Vec<SimilarGenome>, SimilarityError>>> {
+        let (tx, rx) = mpsc::channel();
+        if self
+            .sender
+            .send(StorageCommand::FindSimilarGenomes {
+                query,
+                min_similarity,
+                limit,
+                reply_tx: tx,
+            })
+            .is_ok()
+        {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+
+    /// Publishes a challenge scenario.
+    pub fn publish_scenario(&self, scenario: ScenarioSubmit) {
+        let _ = self.sender.send(StorageCommand::PublishScenario(scenario));
+    }
+
+    /// Asynchronously queries published scenarios.
+    pub fn query_scenarios_async(
+        &self,
+        limit: Option<usize>,
+    ) -> Option<mpsc::Receiver<Vec<ScenarioRecord>>> {
+        let (tx, rx) = mpsc::channel();
+        if self
+            .sender
+            .send(StorageCommand::QueryScenarios {
+                limit,
+                reply_tx: tx,
+            })
+            .is_ok()
+        {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+
+    /// Asynchronously queries one scenario, counting a download if
+    /// `download` is set.
+    pub fn query_scenario_async(
+        &self,
+        id: Uuid,
+        download: bool,
+    ) -> Option<mpsc::Receiver<Option<ScenarioRecord>>> {
+        let (tx, rx) = mpsc::channel();
+        if self
+            .sender
+            .send(StorageCommand::QueryScenario {
+                id,
+                download,
+                reply_tx: tx,
+            })
+            .is_ok()
+        {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+
+    /// Asynchronously records a scenario run and replies with the player's
+    /// rank.
+    pub fn submit_scenario_result_async(
+        &self,
+        result: ScenarioResultSubmit,
+    ) -> Option<mpsc::Receiver<Result<usize, ScenarioError>>> {
+        let (tx, rx) = mpsc::channel();
+        if self
+            .sender
+            .send(StorageCommand::SubmitScenarioResult {
+                result,
+                reply_tx: tx,
+            })
+            .is_ok()
+        {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+
+    /// Asynchronously queries the leaderboard of a scenario.
+    pub fn query_leaderboard_async(
+        &self,
+        scenario_id: Uuid,
+        limit: usize,
+    ) -> Option<mpsc::Receiver<Vec<LeaderboardEntry>>> {
+        let (tx, rx) = mpsc::channel();
+        if self
+            .sender
+            .send(StorageCommand::QueryLeaderboard {
+                scenario_id,
+                limit,
+                reply_tx: tx,
+            })
+            .is_ok()
+        {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+
+    /// Asynchronously queries the genomes and seeds in one moderation state.
+    pub fn query_submissions_async(
+        &self,
+        status: ModerationStatus,
+    ) -> Option<mpsc::Receiver<(Vec<GenomeRecord>, Vec<SeedRecord>)>> {
+        let (tx, rx) = mpsc::channel();
+        if self
+            .sender
+            .send(StorageCommand::QuerySubmissions {
+                status,
+                reply_tx: tx,
+            })
+            .is_ok()
+        {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+
+    /// Asynchronously moves a submission to `status`, recording the
+    /// moderator's `note`.
+    pub fn moderate_submission_async(
+        &self,
+        id: Uuid,
+        status: ModerationStatus,
+        note: Option<String>,
+    ) -> Option<mpsc::Receiver<Result<ModerationStatus, ModerationError>>> {
+        let (tx, rx) = mpsc::channel();
+        if self
+            .sender
+            .send(StorageCommand::ModerateSubmission {
+                id,
+                status,
+                note,
+                reply_tx: tx,
+            })
+            .is_ok()
+        {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+
+    /// Asynchronously queries the moderation state of one submission.
+    pub fn query_submission_state_async(
+        &self,
+        id: Uuid,
+    ) -> Option<mpsc::Receiver<Option<SubmissionState>>> {
+        let (tx, rx) = mpsc::channel();
+        if self
+            .sender
+            .send(StorageCommand::QuerySubmissionState(id, tx))
+            .is_ok()
+        {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+
+    /// Queues a save of the player profile.
+    pub fn save_profile(&self, profile: &PlayerProfile) {
+        let _ = self
+            .sender
+            .send(StorageCommand::SaveProfile(profile.clone()));
+    }
+
+    /// Asynchronously queries the player profile.
+    pub fn query_profile_async(&self) -> Option<mpsc::Receiver<PlayerProfile>> {
+        let (tx, rx) = mpsc::channel();
+        if self.sender.send(StorageCommand::QueryProfile(tx)).is_ok() {
+            Some(rx)
+        } else {
+            None
+        }
+    }
+}
+
+const GENOME_COLUMNS: &str = "id, lineage_id, genotype, author, name, description, tags, \
+    fitness_score, offspring_count, tick, downloads, created_at, status, moderation_note, \
+    duplicate_of, similarity, merged_count, provenance";
+
+const SEED_COLUMNS: &str = "id, author, name, description, tags, config_json, avg_tick_time, \
+    max_pop, performance_summary, downloads, created_at, status, moderation_note, \
+    thumbnail IS NOT NULL, provenance";
+
+const SCENARIO_COLUMNS: &str = "id, author, name, description, scenario_toml, ticks, \
+    score_metric, targets, downloads, created_at";
+
+const SUBMISSION_TABLES: [&str; 2] = ["genome_submissions", "seed_submissions"];
+
+fn status_from_row(row: &rusqlite::Row<'_>, idx: usize) -> rusqlite::Result<ModerationStatus> {
+    let status: String = row.get(idx)?;
+    ModerationStatus::parse(&status).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            idx,
+            rusqlite::types::Type::Text,
+            format!("unknown moderation status {status}").into(),
+        )
+    })
+}
+
+/// Manifests are stored as JSON text.
+fn provenance_json(provenance: Option<&RunManifest>) -> Option<String> {
+    provenance.and_then(|p| serde_json::to_string(p).ok())
+}
+
+/// A stored manifest; unreadable ones are dropped rather than failing the row.
+fn provenance_from_row(
+    row: &rusqlite::Row<'_>,
+    idx: usize,
+) -> rusqlite::Result<Option<RunManifest>> {
+    let json: Option<String> = row.get(idx)?;
+    Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+}
+
+fn genome_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<GenomeRecord> {
+    let lineage_id_str: Option<String> = row.get(1)?;
+    let lineage_id = lineage_id_str.and_then(|s| Uuid::parse_str(&s).ok());
+    Ok(GenomeRecord {
+        id: row.get(0)?,
+        lineage_id,
+        genotype: row.get(2)?,
+        author: row.get(3)?,
+        name: row.get(4)?,
+        description: row.get(5)?,
+        tags: row.get(6)?,
+        fitness_score: row.get(7)?,
+        offspring_count: row.get(8)?,
+        tick: row.get(9)?,
+        downloads: row.get(10)?,
+        created_at: row.get(11)?,
+        status: status_from_row(row, 12)?,
+        moderation_note: row.get(13)?,
+        duplicate_of: row.get(14)?,
+        similarity: row.get(15)?,
+        merged_count: row.get(16)?,
+        provenance: provenance_from_row(row, 17)?,
+    })
+}
+
+fn seed_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SeedRecord> {
+    Ok(SeedRecord {
+        id: row.get(0)?,
+        author: row.get(1)?,
+        name: row.get(2)?,
+        description: row.get(3)?,
+        tags: row.get(4)?,
+        config_json: row.get(5)?,
+        avg_tick_time: row.get(6)?,
+        max_pop: row.get(7)?,
+        performance_summary: row.get(8)?,
+        downloads: row.get(9)?,
+        created_at: row.get(10)?,
+        status: status_from_row(row, 11)?,
+        moderation_note: row.get(12)?,
+        has_thumbnail: row.get(13)?,
+        provenance: provenance_from_row(row, 14)?,
+    })
+}
+
+fn scenario_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ScenarioRecord> {
+    let decode = |idx: usize, e: serde_json::Error| {
+        rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, e.into())
+    };
+    let score_metric: String = row.get(6)?;
+    let targets: String = row.get(7)?;
+    Ok(ScenarioRecord {
+        id: row.get(0)?,
+        author: row.get(1)?,
+        name: row.get(2)?,
+        description: row.get(3)?,
+        scenario_toml: row.get(4)?,
+        ticks: row.get(5)?,
+        score_metric: serde_json::from_value(serde_json::Value::String(score_metric))
+            .map_err(|e| decode(6, e))?,
+        targets: serde_json::from_str(&targets).map_err(|e| decode(7, e))?,
+        downloads: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+/// Keeps `result` if it beats the player's previous best, and returns the
+/// player's rank on the leaderboard.
+fn record_scenario_result(
+    conn: &Connection,
+    result: &ScenarioResultSubmit,
+) -> Result<usize, ScenarioError> {
+    let storage = |e: rusqlite::Error| ScenarioError::Storage(e.to_string());
+    let exists = conn
+        .query_row(
+            "SELECT 1 FROM scenarios WHERE id = ?1",
+            params![result.scenario_id],
+            |_| Ok(()),
+        )
+        .optional()
+        .map_err(storage)?;
+    if exists.is_none() {
+        return Err(ScenarioError::NotFound);
+    }
+    conn.execute(
+        "INSERT INTO scenario_results (scenario_id, player, score, passed, ticks, world_hash, signature, provenance)
+          VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+          ON CONFLICT(scenario_id, player) DO UPDATE SET
+             score = excluded.score,
+             passed = excluded.passed,
+             ticks = excluded.ticks,
+             world_hash = excluded.world_hash,
+             signature = excluded.signature,
+             provenance = excluded.provenance,
+             created_at = CURRENT_TIMESTAMP
+          WHERE excluded.passed > scenario_results.passed
+             OR (excluded.passed = scenario_results.passed AND excluded.score > scenario_results.score)",
+        params![
+            result.scenario_id,
+            result.player,
+            result.score,
+            result.passed,
+            result.ticks,
+            result.world_hash,
+            result.signature,
+            provenance_json(result.provenance.as_ref())
+        ],
+    )
+    .map_err(storage)?;
+    let ahead: usize = conn
+        .query_row(
+            "SELECT COUNT(*) FROM scenario_results AS other, scenario_results AS mine
+              WHERE mine.scenario_id = ?1 AND mine.player = ?2 AND other.scenario_id = ?1
+                AND (other.passed > mine.passed
+                     OR (other.passed = mine.passed AND other.score > mine.score))",
+            params![result.scenario_id, result.player],
+            |row| row.get(0),
+        )
+        .map_err(storage)?;
+    Ok(ahead + 1)
+}
+
+/// Runs `query` and keeps the rows `from_row` can decode.
+fn query_records<T>(
+    conn: &Connection,
+    query: &str,
+    from_row: fn(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
+) -> rusqlite::Result<Vec<T>> {
+    let mut stmt = conn.prepare(query)?;
+    let rows = stmt.query_map([], from_row)?;
+    Ok(rows.filter_map(Result::ok).collect())
+}
+
+/// The table, moderation state and note of the submission with `id`.
+fn submission_state(
+    conn: &Connection,
+    id: Uuid,
+) -> rusqlite::Result<Option<(&'static str, ModerationStatus, Option<String>)>> {
+    for table in SUBMISSION_TABLES {
+        let found = conn
+            .query_row(
+                &format!("SELECT status, moderation_note FROM {table} WHERE id = ?1"),
+                params![id],
+                |row| Ok((status_from_row(row, 0)?, row.get(1)?)),
+            )
+            .optional()?;
+        if let Some((status, note)) = found {
+            return Ok(Some((table, status, note)));
+        }
+    }
+    Ok(None)
+}
+
+fn moderate(
+    conn: &Connection,
+    id: Uuid,
+    status: ModerationStatus,
+    note: Option<String>,
+) -> Result<ModerationStatus, ModerationError> {
+    let storage = |e: rusqlite::Error| ModerationError::Storage(e.to_string());
+    let (table, current, _) = submission_state(conn, id)
+        .map_err(storage)?
+        .ok_or(ModerationError::NotFound)?;
+    if !current.can_become(status) {
+        return Err(ModerationError::InvalidTransition {
+            from: current,
+            to: status,
+        });
+    }
+    conn.execute(
+        &format!("UPDATE {table} SET status = ?2, moderation_note = ?3 WHERE id = ?1"),
+        params![id, status.as_str(), note],
+    )
+    .map_err(storage)?;
+    Ok(current)
+}
+
+/// Indexes every stored genome whose genotype decodes.
+fn load_similarity_index(conn: &Connection) -> rusqlite::Result<SimilarityIndex> {
+    let mut index = SimilarityIndex::default();
+    let mut stmt = conn.prepare("SELECT id, genotype FROM genome_submissions")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, Uuid>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for (id, genotype) in rows.filter_map(Result::ok) {
+        if let Ok(genotype) = Genotype::from_hex(&genotype) {
+            index.insert(id, genome_vector(&genotype));
+        }
+    }
+    Ok(index)
+}
+
+/// The most similar stored genome at or above `threshold`, with its state.
+/// Rejected genomes neither attract flags nor absorb copies.
+fn nearest_live(
+    conn: &Connection,
+    index: &SimilarityIndex,
+    vector: &[f32],
+    threshold: f32,
+) -> Option<(SimilarMatch, ModerationStatus)> {
+    index
+        .nearest(vector, threshold)
+        .into_iter()
+        .find_map(|m| match submission_state(conn, m.id) {
+            Ok(Some((_, status, _))) if status != ModerationStatus::Rejected => Some((m, status)),
+            _ => None,
+        })
+}
+
+fn find_similar(
+    conn: &Connection,
+    index: &SimilarityIndex,
+    query: SimilarityQuery,
+    min_similarity: f32,
+    limit: usize,
+) -> Result<Vec<SimilarGenome>, SimilarityError> {
+    let (vector, exclude) = match query {
+        SimilarityQuery::Submission(id) => (
+            index.vector(id).ok_or(SimilarityError::NotFound)?.to_vec(),
+            Some(id),
+        ),
+        SimilarityQuery::Genotype(hex) => {
+            let genotype = Genotype::from_hex(&hex)
+                .map_err(|e| SimilarityError::InvalidGenotype(e.to_string()))?;
+            (genome_vector(&genotype), None)
+        }
+    };
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {GENOME_COLUMNS} FROM genome_submissions
+             WHERE id = ?1 AND status = 'approved'"
+        ))
+        .map_err(|e| SimilarityError::Storage(e.to_string()))?;
+    Ok(index
+        .nearest(&vector, min_similarity)
+        .into_iter()
+        .filter(|m| Some(m.id) != exclude)
+        .filter_map(|m| {
+            let genome = stmt.query_row(params![m.id], genome_from_row).ok()?;
+            Some(SimilarGenome {
+                genome,
+                similarity: m.similarity,
+            })
+        })
+        .take(limit)
+        .collect())
+}
+
+/// Rebuilds the last snapshot stored at or before `tick` from its keyframe
+/// and the deltas that follow it.
+fn load_snapshot(conn: &Connection, tick: u64) -> Result<Option<WorldSnapshot>> {
+    let keyframe: Option<u64> = conn.query_row(
+        "SELECT MAX(tick) FROM world_snapshots WHERE tick <= ?1 AND base_tick IS NULL",
+        params![tick],
+        |row| row.get(0),
+    )?;
+    let Some(keyframe) = keyframe else {
+        return Ok(None);
+    };
+    let mut stmt = conn.prepare(
+        "SELECT world_data FROM world_snapshots WHERE tick >= ?1 AND tick <= ?2 ORDER BY tick",
+    )?;
+    let rows: Vec<Vec<u8>> = stmt
+        .query_map(params![keyframe, tick], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    let Some((first, deltas)) = rows.split_first() else {
+        return Ok(None);
+    };
+    Ok(Some(decode_chain(first, deltas.iter().map(Vec::as_slice))?))
 }
 
 fn init_db(conn: &mut Connection) -> Result<()> {
@@ -671,10 +1660,21 @@ fn init_db(conn: &mut Connection) -> Result<()> {
             pop_count INTEGER NOT NULL,
             carbon_level REAL NOT NULL,
             energy_total REAL NOT NULL,
-            world_data BLOB NOT NULL
+            world_data BLOB NOT NULL,
+            base_tick INTEGER
         )",
         [],
     )?;
+    // Databases from before delta encoding only hold keyframes.
+    let has_base_tick = conn
+        .prepare("SELECT base_tick FROM world_snapshots LIMIT 0")
+        .is_ok();
+    if !has_base_tick {
+        conn.execute(
+            "ALTER TABLE world_snapshots ADD COLUMN base_tick INTEGER",
+            [],
+        )?;
+    }
 
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_lineages_civ ON lineages(civilization_level)",
@@ -700,6 +1700,12 @@ fn init_db(conn: &mut Connection) -> Result<()> {
             tick INTEGER,
             downloads INTEGER DEFAULT 0,
             created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            status TEXT NOT NULL DEFAULT 'approved',
+            moderation_note TEXT,
+            duplicate_of TEXT,
+            similarity REAL,
+            merged_count INTEGER NOT NULL DEFAULT 0,
+            provenance TEXT,
             FOREIGN KEY(lineage_id) REFERENCES lineages(id)
         )",
         [],
@@ -717,11 +1723,57 @@ fn init_db(conn: &mut Connection) -> Result<()> {
             max_pop INTEGER,
             performance_summary TEXT,
             created_at TEXT DEFAULT CURRENT_TIMESTAMP,
-            downloads INTEGER DEFAULT 0
+            downloads INTEGER DEFAULT 0,
+            status TEXT NOT NULL DEFAULT 'approved',
+            moderation_note TEXT,
+            thumbnail BLOB,
+            provenance TEXT
         )",
         [],
     )?;
 
+    // Registries from before moderation only hold published submissions.
+    for table in SUBMISSION_TABLES {
+        let has_status = conn
+            .prepare(&format!("SELECT status FROM {table} LIMIT 0"))
+            .is_ok();
+        if !has_status {
+            conn.execute(
+                &format!("ALTER TABLE {table} ADD COLUMN status TEXT NOT NULL DEFAULT 'approved'"),
+                [],
+            )?;
+            conn.execute(
+                &format!("ALTER TABLE {table} ADD COLUMN moderation_note TEXT"),
+                [],
+            )?;
+        }
+    }
+
+    // Registries from before deduplication hold no flags or merges.
+    let has_duplicate_of = conn
+        .prepare("SELECT duplicate_of FROM genome_submissions LIMIT 0")
+        .is_ok();
+    if !has_duplicate_of {
+        for column in [
+            "duplicate_of TEXT",
+            "similarity REAL",
+            "merged_count INTEGER NOT NULL DEFAULT 0",
+        ] {
+            conn.execute(
+                &format!("ALTER TABLE genome_submissions ADD COLUMN {column}"),
+                [],
+            )?;
+        }
+    }
+
+    // Registries from before thumbnails hold seeds without previews.
+    let has_thumbnail = conn
+        .prepare("SELECT thumbnail FROM seed_submissions LIMIT 0")
+        .is_ok();
+    if !has_thumbnail {
+        conn.execute("ALTER TABLE seed_submissions ADD COLUMN thumbnail BLOB", [])?;
+    }
+
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_genomes_fitness ON genome_submissions(fitness_score DESC)",
         [],
@@ -732,5 +1784,59 @@ fn init_db(conn: &mut Connection) -> Result<()> {
         [],
     )?;
 
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scenarios (
+            id TEXT PRIMARY KEY,
+            author TEXT,
+            name TEXT NOT NULL,
+            description TEXT,
+            scenario_toml TEXT NOT NULL,
+            ticks INTEGER NOT NULL,
+            score_metric TEXT NOT NULL,
+            targets TEXT NOT NULL,
+            downloads INTEGER DEFAULT 0,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scenario_results (
+            scenario_id TEXT NOT NULL,
+            player TEXT NOT NULL,
+            score REAL NOT NULL,
+            passed BOOLEAN NOT NULL,
+            ticks INTEGER NOT NULL,
+            world_hash TEXT NOT NULL,
+            signature TEXT NOT NULL,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            provenance TEXT,
+            PRIMARY KEY(scenario_id, player),
+            FOREIGN KEY(scenario_id) REFERENCES scenarios(id)
+        )",
+        [],
+    )?;
+
+    // Registries from before provenance hold unattributed submissions.
+    for table in ["genome_submissions", "seed_submissions", "scenario_results"] {
+        let has_provenance = conn
+            .prepare(&format!("SELECT provenance FROM {table} LIMIT 0"))
+            .is_ok();
+        if !has_provenance {
+            conn.execute(
+                &format!("ALTER TABLE {table} ADD COLUMN provenance TEXT"),
+                [],
+            )?;
+        }
+    }
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS profile (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            data TEXT NOT NULL
+        )",
+        [],
+    )?;
+
     Ok(())
 }
@@ -0,0 +1,218 @@
+//! Low-resolution previews of a world for the seed marketplace.
+//!
+//! A thumbnail shows the terrain, dimmed and coloured by type, with the
+//! population drawn over it as white-hot heat. It travels as a PNG and is
+//! shown in the terminal as ASCII art by brightness, where the dim terrain
+//! falls at the quiet end of the ramp and populated areas at the loud end.
+
+use primordium_core::terrain::TerrainGrid;
+use primordium_data::TerrainType;
+
+/// Width of a rendered thumbnail; its height follows the world's aspect.
+pub const THUMBNAIL_WIDTH: u32 = 64;
+
+/// Largest thumbnail the registry accepts, encoded and per side.
+pub const MAX_THUMBNAIL_BYTES: usize = 32 * 1024;
+pub const MAX_THUMBNAIL_SIDE: u32 = 256;
+
+/// Characters from darkest to brightest.
+const ASCII_RAMP: &[u8] = b" .:-=+*#%@";
+
+const HEAT_COLOR: [u8; 3] = [255, 250, 230];
+
+/// An RGB image, row-major.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 3]>,
+}
+
+fn terrain_color(terrain: TerrainType) -> [u8; 3] {
+    let [r, g, b] = match terrain {
+        TerrainType::Plains => [90, 110, 70],
+        TerrainType::Mountain => [100, 100, 100],
+        TerrainType::River => [70, 130, 180],
+        TerrainType::Oasis => [50, 205, 50],
+        TerrainType::Barren => [139, 69, 19],
+        TerrainType::Wall => [60, 60, 60],
+        TerrainType::Forest => [34, 139, 34],
+        TerrainType::Desert => [210, 180, 140],
+        TerrainType::Nest => [255, 215, 0],
+        TerrainType::Outpost => [255, 69, 0],
+    };
+    // Dimmed so even the brightest terrain stays below any population.
+    [r / 2, g / 2, b / 2]
+}
+
+fn blend(a: [u8; 3], b: [u8; 3], t: f32) -> [u8; 3] {
+    std::array::from_fn(|i| (f32::from(a[i]) + (f32::from(b[i]) - f32::from(a[i])) * t) as u8)
+}
+
+fn luminance([r, g, b]: [u8; 3]) -> f32 {
+    (0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b)) / 255.0
+}
+
+impl Thumbnail {
+    /// Renders `terrain` with the organisms at `positions` as a heat map.
+    pub fn render(terrain: &TerrainGrid, positions: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let width = THUMBNAIL_WIDTH.min(u32::from(terrain.width)).max(1);
+        let height = (u32::from(terrain.height) * width / u32::from(terrain.width).max(1)).max(1);
+        let scale_x = f64::from(terrain.width) / f64::from(width);
+        let scale_y = f64::from(terrain.height) / f64::from(height);
+
+        let mut heat = vec![0u32; (width * height) as usize];
+        for (x, y) in positions {
+            let px = ((x / scale_x) as u32).min(width - 1);
+            let py = ((y / scale_y) as u32).min(height - 1);
+            heat[(py * width + px) as usize] += 1;
+        }
+        let hottest = heat.iter().copied().max().unwrap_or(0).max(1) as f32;
+
+        let pixels = (0..height)
+            .flat_map(|py| (0..width).map(move |px| (px, py)))
+            .zip(&heat)
+            .map(|((px, py), &count)| {
+                let cell = terrain.get(
+                    (f64::from(px) + 0.5) * scale_x,
+                    (f64::from(py) + 0.5) * scale_y,
+                );
+                let base = terrain_color(cell.terrain_type);
+                if count == 0 {
+                    base
+                } else {
+                    blend(base, HEAT_COLOR, 0.5 + 0.5 * count as f32 / hottest)
+                }
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Encodes the thumbnail as an 8-bit RGB PNG.
+    #[must_use]
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .expect("writing to a Vec cannot fail");
+        writer
+            .write_image_data(self.pixels.as_flattened())
+            .expect("pixel count matches the thumbnail dimensions");
+        writer.finish().expect("writing to a Vec cannot fail");
+        png
+    }
+
+    /// Decodes a PNG, rejecting any larger than the registry accepts.
+    pub fn from_png(bytes: &[u8]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            bytes.len() <= MAX_THUMBNAIL_BYTES,
+            "Thumbnail is {} bytes; the limit is {}",
+            bytes.len(),
+            MAX_THUMBNAIL_BYTES
+        );
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let (width, height) = (reader.info().width, reader.info().height);
+        anyhow::ensure!(
+            width <= MAX_THUMBNAIL_SIDE && height <= MAX_THUMBNAIL_SIDE,
+            "Thumbnail is {width}x{height}; the limit is {MAX_THUMBNAIL_SIDE} per side"
+        );
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let frame = reader.next_frame(&mut buf)?;
+        let data = &buf[..frame.buffer_size()];
+        let pixels = match frame.color_type {
+            png::ColorType::Rgb => data.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect(),
+            png::ColorType::Rgba => data.chunks_exact(4).map(|p| [p[0], p[1], p[2]]).collect(),
+            png::ColorType::Grayscale => data.iter().map(|&g| [g, g, g]).collect(),
+            png::ColorType::GrayscaleAlpha => data.chunks_exact(2).map(|p| [p[0]; 3]).collect(),
+            png::ColorType::Indexed => anyhow::bail!("Indexed PNG was not expanded"),
+        };
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Renders the thumbnail as `rows` lines of `cols` characters, each
+    /// picked by the average brightness of the pixels it covers.
+    #[must_use]
+    pub fn to_ascii(&self, cols: usize, rows: usize) -> Vec<String> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        if width == 0 || height == 0 {
+            return Vec::new();
+        }
+        (0..rows)
+            .map(|row| {
+                let (y0, y1) = (
+                    row * height / rows,
+                    ((row + 1) * height / rows).max(row * height / rows + 1),
+                );
+                (0..cols)
+                    .map(|col| {
+                        let (x0, x1) = (
+                            col * width / cols,
+                            ((col + 1) * width / cols).max(col * width / cols + 1),
+                        );
+                        let block: Vec<f32> = (y0..y1.min(height))
+                            .flat_map(|y| (x0..x1.min(width)).map(move |x| (x, y)))
+                            .map(|(x, y)| luminance(self.pixels[y * width + x]))
+                            .collect();
+                        let brightness = block.iter().sum::<f32>() / block.len().max(1) as f32;
+                        let level = (brightness * (ASCII_RAMP.len() - 1) as f32).round() as usize;
+                        ASCII_RAMP[level.min(ASCII_RAMP.len() - 1)] as char
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_round_trips_and_shows_population() {
+        let terrain = TerrainGrid::generate(100, 50, 42);
+        let crowd = (0..40).map(|i| (80.0 + f64::from(i % 5), 10.0 + f64::from(i / 5)));
+        let thumbnail = Thumbnail::render(&terrain, crowd);
+        assert_eq!((thumbnail.width, thumbnail.height), (64, 32));
+
+        let png = thumbnail.to_png();
+        assert!(png.len() <= MAX_THUMBNAIL_BYTES);
+        assert_eq!(Thumbnail::from_png(&png).unwrap(), thumbnail);
+
+        let ascii = thumbnail.to_ascii(32, 8);
+        assert_eq!(ascii.len(), 8);
+        assert!(ascii.iter().all(|line| line.chars().count() == 32));
+        // The crowd covers pixels 50..54 x 6..11, so only the cells over
+        // it are brighter than the dimmed terrain.
+        let lit: Vec<(usize, usize)> = ascii
+            .iter()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| "=+*#%@".contains(*c))
+                    .map(move |(col, _)| (row, col))
+            })
+            .collect();
+        assert!(!lit.is_empty(), "{ascii:#?}");
+        assert!(
+            lit.iter()
+                .all(|&(row, col)| (1..=2).contains(&row) && (25..=27).contains(&col)),
+            "{ascii:#?}"
+        );
+
+        assert!(Thumbnail::from_png(b"not a png").is_err());
+    }
+}
@@ -0,0 +1,77 @@
+//! Optional deflate compression of relay frames.
+//!
+//! WebSocket frames carry [`crate::NetMessage`] JSON as text. A peer that
+//! connects with [`COMPRESSION_QUERY`] in its URL also accepts binary frames
+//! holding the same JSON deflated; either side only compresses messages of at
+//! least [`COMPRESSION_THRESHOLD`] bytes, so both kinds of frame must be read.
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// Query parameter a client adds to the relay URL to opt in to compression.
+pub const COMPRESSION_QUERY: &str = "compression=deflate";
+
+/// Messages shorter than this are sent as plain text.
+pub const COMPRESSION_THRESHOLD: usize = 512;
+
+/// Deflates a JSON message for a binary frame.
+pub fn compress(text: &str) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+    // Writing into a Vec cannot fail.
+    let _ = encoder.write_all(text.as_bytes());
+    encoder.finish().unwrap_or_default()
+}
+
+/// Inflates a binary frame, refusing anything that expands past `max_len`
+/// bytes so a small frame cannot exhaust memory.
+pub fn decompress(bytes: &[u8], max_len: usize) -> anyhow::Result<String> {
+    let mut text = String::new();
+    DeflateDecoder::new(bytes)
+        .take(max_len as u64 + 1)
+        .read_to_string(&mut text)?;
+    anyhow::ensure!(
+        text.len() <= max_len,
+        "Compressed message expands past {max_len} bytes"
+    );
+    Ok(text)
+}
+
+/// Whether a message of this length is worth compressing.
+pub fn should_compress(text: &str) -> bool {
+    text.len() >= COMPRESSION_THRESHOLD
+}
+
+/// `url` with [`COMPRESSION_QUERY`] appended.
+pub fn with_compression(url: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}{COMPRESSION_QUERY}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_roundtrip_and_limit() {
+        let text = "{\"type\":\"PeerList\"}".repeat(100);
+        let packed = compress(&text);
+        assert!(packed.len() < text.len());
+        assert_eq!(decompress(&packed, text.len()).unwrap(), text);
+        assert!(decompress(&packed, text.len() - 1).is_err());
+        assert!(decompress(b"not deflate", 1024).is_err());
+    }
+
+    #[test]
+    fn test_compression_query() {
+        assert_eq!(
+            with_compression("ws://127.0.0.1:3000/ws"),
+            "ws://127.0.0.1:3000/ws?compression=deflate"
+        );
+        assert_eq!(
+            with_compression("ws://relay/ws?room=a"),
+            "ws://relay/ws?room=a&compression=deflate"
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Checksums and fingerprints that peers compare before accepting data.
+
+use sha2::{Digest, Sha256};
+
+/// Hex SHA-256 over `parts` in order.
+///
+/// A world's rules fingerprint is this over its rule sections; migrants are
+/// only accepted between worlds whose fingerprints match.
+pub fn fingerprint<I, P>(parts: I) -> String
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<[u8]>,
+{
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_ref());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Checksum carried by [`crate::NetMessage::MigrateEntity`] over the fields
+/// the receiver uses to rebuild the migrant.
+pub fn migration_checksum(dna: &str, energy: f32, generation: u32) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(dna.as_bytes());
+    hasher.update(energy.to_be_bytes());
+    hasher.update(generation.to_be_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_depends_on_every_part() {
+        let a = fingerprint(["metabolism", "evolution"]);
+        assert_eq!(a, fingerprint(["metabolism", "evolution"]));
+        assert_ne!(a, fingerprint(["metabolism", "evolution2"]));
+        assert_eq!(a.len(), 64);
+    }
+
+    #[test]
+    fn test_migration_checksum_covers_all_fields() {
+        let sum = migration_checksum("ABCD", 10.0, 3);
+        assert_eq!(sum, migration_checksum("ABCD", 10.0, 3));
+        assert_ne!(sum, migration_checksum("ABCE", 10.0, 3));
+        assert_ne!(sum, migration_checksum("ABCD", 10.5, 3));
+        assert_ne!(sum, migration_checksum("ABCD", 10.0, 4));
+    }
+}
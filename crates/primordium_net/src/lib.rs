@@ -1,6 +1,16 @@
+//! Wire protocol shared by the relay server, the native client and the WASM
+//! build, plus the checksums peers use to validate what they receive.
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub mod codec;
+pub mod integrity;
+pub mod p2p;
+pub mod spectate;
+
+pub use integrity::{fingerprint, migration_checksum};
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PeerInfo {
     pub peer_id: Uuid,
@@ -17,7 +27,7 @@ pub enum TradeResource {
     Biomass,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct TradeProposal {
     pub id: Uuid,
     pub sender_id: Uuid,
@@ -27,11 +37,25 @@ pub struct TradeProposal {
     pub request_amount: f32,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Which relay limit a peer ran into.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimit {
+    /// Too many messages per second.
+    Messages,
+    /// The address used up its migrations for the day.
+    DailyMigrations,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type", content = "payload")]
 pub enum NetMessage {
+    /// The relay's greeting. `publish_secret` lets this connection publish
+    /// its world for spectators (see [`spectate::with_publish`]); relays that
+    /// predate spectating send none.
     Handshake {
         client_id: Uuid,
+        #[serde(default)]
+        publish_secret: String,
     },
     Welcome {
         server_message: String,
@@ -61,6 +85,11 @@ pub enum NetMessage {
     PeerList {
         peers: Vec<PeerInfo>,
     },
+    /// A peer left the relay; `reason` is `"closed"` or `"timed out"`.
+    PeerDropped {
+        peer_id: Uuid,
+        reason: String,
+    },
     TradeOffer(TradeProposal),
     TradeAccept {
         proposal_id: Uuid,
@@ -79,6 +108,37 @@ pub enum NetMessage {
         severity: f32,
         seed: u64,
     },
+    /// Sent by the relay to a peer whose message it dropped. The peer should
+    /// wait `retry_after_ms` before sending more; `migration_id` names a
+    /// dropped migration, whose entity never left.
+    RateLimited {
+        limit: RateLimit,
+        retry_after_ms: u64,
+        migration_id: Option<Uuid>,
+    },
+}
+
+impl NetMessage {
+    /// A [`NetMessage::MigrateEntity`] with its checksum filled in.
+    pub fn migrate_entity(
+        migration_id: Uuid,
+        dna: String,
+        energy: f32,
+        generation: u32,
+        species_name: String,
+        fingerprint: String,
+    ) -> Self {
+        let checksum = migration_checksum(&dna, energy, generation);
+        NetMessage::MigrateEntity {
+            migration_id,
+            dna,
+            energy,
+            generation,
+            species_name,
+            fingerprint,
+            checksum,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -215,7 +275,10 @@ mod tests {
     #[test]
     fn test_handshake_serialization() {
         let client_id = Uuid::new_v4();
-        let msg = NetMessage::Handshake { client_id };
+        let msg = NetMessage::Handshake {
+            client_id,
+            publish_secret: "s3cret".to_string(),
+        };
 
         let json = serde_json::to_string(&msg).expect("Failed to serialize");
         assert!(json.contains("\"type\":\"Handshake\""));
@@ -223,9 +286,11 @@ mod tests {
         let parsed: NetMessage = serde_json::from_str(&json).expect("Failed to deserialize");
         if let NetMessage::Handshake {
             client_id: parsed_id,
+            publish_secret,
         } = parsed
         {
             assert_eq!(parsed_id, client_id);
+            assert_eq!(publish_secret, "s3cret");
         } else {
             panic!("Expected Handshake message");
         }
@@ -269,4 +334,92 @@ mod tests {
             panic!("Expected TradeOffer message");
         }
     }
+
+    #[test]
+    fn test_every_message_roundtrips() {
+        let id = Uuid::new_v4();
+        let proposal = TradeProposal {
+            id,
+            sender_id: Uuid::new_v4(),
+            offer_resource: TradeResource::SoilFertility,
+            offer_amount: 1.5,
+            request_resource: TradeResource::Biomass,
+            request_amount: 2.0,
+        };
+        let messages = [
+            NetMessage::Handshake {
+                client_id: id,
+                publish_secret: String::new(),
+            },
+            NetMessage::Welcome {
+                server_message: "hi".to_string(),
+                online_count: 3,
+            },
+            NetMessage::migrate_entity(
+                id,
+                "ABCD".to_string(),
+                12.5,
+                4,
+                "Alpha".to_string(),
+                "fp".to_string(),
+            ),
+            NetMessage::MigrateAck { migration_id: id },
+            NetMessage::StatsUpdate {
+                online_count: 2,
+                total_migrations: 9,
+            },
+            NetMessage::PeerAnnounce {
+                entity_count: 1,
+                migrations_sent: 2,
+                migrations_received: 3,
+            },
+            NetMessage::PeerList { peers: vec![] },
+            NetMessage::PeerDropped {
+                peer_id: id,
+                reason: "timed out".to_string(),
+            },
+            NetMessage::TradeOffer(proposal),
+            NetMessage::TradeAccept {
+                proposal_id: id,
+                acceptor_id: Uuid::new_v4(),
+            },
+            NetMessage::TradeRevoke { proposal_id: id },
+            NetMessage::Relief {
+                lineage_id: id,
+                amount: 50.0,
+                sender_id: Uuid::new_v4(),
+            },
+            NetMessage::GlobalEvent {
+                event_type: "Meteor".to_string(),
+                severity: 0.5,
+                seed: 7,
+            },
+            NetMessage::RateLimited {
+                limit: RateLimit::DailyMigrations,
+                retry_after_ms: 1500,
+                migration_id: Some(Uuid::new_v4()),
+            },
+        ];
+        for msg in messages {
+            let json = serde_json::to_string(&msg).expect("Failed to serialize");
+            let parsed: NetMessage = serde_json::from_str(&json).expect("Failed to deserialize");
+            assert_eq!(parsed, msg);
+        }
+    }
+
+    #[test]
+    fn test_migrate_entity_carries_checksum() {
+        let msg = NetMessage::migrate_entity(
+            Uuid::new_v4(),
+            "ABCD".to_string(),
+            12.5,
+            4,
+            "Alpha".to_string(),
+            "fp".to_string(),
+        );
+        let NetMessage::MigrateEntity { checksum, .. } = msg else {
+            panic!("Expected MigrateEntity message");
+        };
+        assert_eq!(checksum, migration_checksum("ABCD", 12.5, 4));
+    }
 }
@@ -0,0 +1,117 @@
+//! Messages of the QUIC peer-to-peer transport.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Data structure for secure transfer of entity authority between worlds.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AuthorityTransfer {
+    /// Unique identifier of the entity being transferred.
+    pub entity_id: uuid::Uuid,
+    /// Hex-encoded genotype data.
+    pub dna: String,
+    /// Cryptographic signature ensuring data integrity and origin.
+    pub signature: Vec<u8>,
+    /// Creation timestamp of the transfer request.
+    pub timestamp: u64,
+    /// Anti-replay nonce.
+    pub nonce: u64,
+}
+
+impl AuthorityTransfer {
+    /// Signs the transfer data using the provided Ed25519 secret key.
+    pub fn sign(&mut self, secret_key_bytes: &[u8]) -> anyhow::Result<()> {
+        let key_array: [u8; 32] = secret_key_bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid secret key length: expected 32 bytes"))?;
+        let signing_key = SigningKey::from_bytes(&key_array);
+        let message = self.get_message();
+        let signature = signing_key.sign(&message);
+        self.signature = signature.to_bytes().to_vec();
+        Ok(())
+    }
+
+    /// Verifies the signature using the provided Ed25519 public key.
+    pub fn verify(&self, public_key_bytes: &[u8]) -> bool {
+        let Ok(key_array) = public_key_bytes.try_into() else {
+            return false;
+        };
+        let Ok(verifying_key) = VerifyingKey::from_bytes(key_array) else {
+            return false;
+        };
+        let Ok(signature) = Signature::from_slice(&self.signature) else {
+            return false;
+        };
+        let message = self.get_message();
+        verifying_key.verify(&message, &signature).is_ok()
+    }
+
+    fn get_message(&self) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(self.entity_id.as_bytes());
+        msg.extend_from_slice(self.dna.as_bytes());
+        msg.extend_from_slice(&self.timestamp.to_le_bytes());
+        msg.extend_from_slice(&self.nonce.to_le_bytes());
+        msg
+    }
+}
+
+/// Message types for the QUIC-based P2P protocol.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum P2pMessage {
+    /// Initial connection handshake.
+    Handshake { version: String, magic: u64 },
+    /// Discovery broadcast to announce peer existence.
+    PeerAnnounce { id: uuid::Uuid, address: String },
+    /// Secure entity migration.
+    MigrateEntity(AuthorityTransfer),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer() -> AuthorityTransfer {
+        AuthorityTransfer {
+            entity_id: uuid::Uuid::new_v4(),
+            dna: "ABCD1234".to_string(),
+            signature: Vec::new(),
+            timestamp: 1_700_000_000,
+            nonce: 42,
+        }
+    }
+
+    #[test]
+    fn test_authority_transfer_sign_and_verify() {
+        let secret = [7u8; 32];
+        let public = SigningKey::from_bytes(&secret).verifying_key().to_bytes();
+        let mut t = transfer();
+        t.sign(&secret).expect("Failed to sign");
+        assert!(t.verify(&public));
+
+        t.nonce += 1;
+        assert!(!t.verify(&public));
+        assert!(t.sign(&[0u8; 3]).is_err());
+    }
+
+    #[test]
+    fn test_p2p_message_roundtrip() {
+        let mut t = transfer();
+        t.sign(&[1u8; 32]).expect("Failed to sign");
+        for msg in [
+            P2pMessage::Handshake {
+                version: "1".to_string(),
+                magic: 0xC0FFEE,
+            },
+            P2pMessage::PeerAnnounce {
+                id: uuid::Uuid::new_v4(),
+                address: "127.0.0.1:4433".to_string(),
+            },
+            P2pMessage::MigrateEntity(t),
+        ] {
+            let json = serde_json::to_string(&msg).expect("Failed to serialize");
+            let parsed: P2pMessage = serde_json::from_str(&json).expect("Failed to deserialize");
+            assert_eq!(parsed, msg);
+        }
+    }
+}
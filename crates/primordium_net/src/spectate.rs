@@ -0,0 +1,150 @@
+//! Frames of the live-spectator stream (`/ws/spectate/:peer_id`).
+//!
+//! A publishing peer streams its world as binary websocket frames: a
+//! [`HEADER_LEN`]-byte header (kind, tick, base tick) followed by a deflated
+//! payload. A keyframe carries a full world snapshot, a delta the changes
+//! since the snapshot at `base_tick`. The relay only reads headers; the
+//! payload encoding belongs to the two ends of the stream.
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// Bytes before the payload: kind, tick and base tick.
+pub const HEADER_LEN: usize = 17;
+
+/// Largest frame a relay accepts from a publisher.
+pub const MAX_FRAME_SIZE: usize = 8 * 1024 * 1024;
+
+/// Query a peer adds to the spectate URL to publish instead of watch.
+pub const PUBLISH_QUERY: &str = "role=publish";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameKind {
+    /// A full snapshot; spectators can start from it.
+    Keyframe,
+    /// Changes since the snapshot at the frame's `base_tick`.
+    Delta,
+}
+
+/// One frame of a spectator stream, with its payload still deflated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpectateFrame {
+    pub kind: FrameKind,
+    pub tick: u64,
+    /// Tick the payload applies to; equals `tick` for keyframes.
+    pub base_tick: u64,
+    pub payload: Vec<u8>,
+}
+
+impl SpectateFrame {
+    /// Deflates `raw` into a frame.
+    pub fn pack(kind: FrameKind, tick: u64, base_tick: u64, raw: &[u8]) -> Self {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+        // Writing into a Vec cannot fail.
+        let _ = encoder.write_all(raw);
+        Self {
+            kind,
+            tick,
+            base_tick,
+            payload: encoder.finish().unwrap_or_default(),
+        }
+    }
+
+    /// Inflates the payload, refusing anything that expands past `max_len`
+    /// bytes.
+    pub fn unpack(&self, max_len: usize) -> anyhow::Result<Vec<u8>> {
+        let mut raw = Vec::new();
+        DeflateDecoder::new(self.payload.as_slice())
+            .take(max_len as u64 + 1)
+            .read_to_end(&mut raw)?;
+        anyhow::ensure!(
+            raw.len() <= max_len,
+            "Spectate payload expands past {max_len} bytes"
+        );
+        Ok(raw)
+    }
+
+    /// The frame as sent over the websocket.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.payload.len());
+        bytes.push(match self.kind {
+            FrameKind::Keyframe => 0,
+            FrameKind::Delta => 1,
+        });
+        bytes.extend_from_slice(&self.tick.to_le_bytes());
+        bytes.extend_from_slice(&self.base_tick.to_le_bytes());
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+
+    /// Parses a websocket frame without inflating its payload.
+    pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            bytes.len() >= HEADER_LEN,
+            "Spectate frame of {} bytes is shorter than its header",
+            bytes.len()
+        );
+        let kind = match bytes[0] {
+            0 => FrameKind::Keyframe,
+            1 => FrameKind::Delta,
+            other => anyhow::bail!("Unknown spectate frame kind {other}"),
+        };
+        let word = |at: usize| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes[at..at + 8]);
+            u64::from_le_bytes(buf)
+        };
+        let (tick, base_tick) = (word(1), word(9));
+        anyhow::ensure!(
+            match kind {
+                FrameKind::Keyframe => base_tick == tick,
+                FrameKind::Delta => base_tick < tick,
+            },
+            "Spectate frame for tick {tick} has base tick {base_tick}"
+        );
+        Ok(Self {
+            kind,
+            tick,
+            base_tick,
+            payload: bytes[HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+/// `url` with [`PUBLISH_QUERY`] and the `publish_secret` of the peer's
+/// relay handshake appended.
+pub fn with_publish(url: &str, publish_secret: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}{PUBLISH_QUERY}&secret={publish_secret}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let raw = b"snapshot bytes ".repeat(200);
+        let frame = SpectateFrame::pack(FrameKind::Delta, 12, 11, &raw);
+        assert!(frame.payload.len() < raw.len());
+
+        let decoded = SpectateFrame::decode(&frame.encode()).unwrap();
+        assert_eq!(decoded, frame);
+        assert_eq!(decoded.unpack(raw.len()).unwrap(), raw);
+        assert!(decoded.unpack(raw.len() - 1).is_err());
+    }
+
+    #[test]
+    fn test_malformed_frames_are_rejected() {
+        assert!(SpectateFrame::decode(&[0; HEADER_LEN - 1]).is_err());
+
+        let mut bytes = SpectateFrame::pack(FrameKind::Keyframe, 5, 5, b"x").encode();
+        bytes[0] = 7;
+        assert!(SpectateFrame::decode(&bytes).is_err());
+
+        let backwards = SpectateFrame::pack(FrameKind::Delta, 5, 5, b"x").encode();
+        assert!(SpectateFrame::decode(&backwards).is_err());
+    }
+}
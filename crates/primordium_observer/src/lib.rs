@@ -1,24 +1,29 @@
 //! The Silicon Scribe narrative system for the Primordium simulation.
 //!
 //! Provides async narration generation and history management via mpsc channels.
+//! Narration comes from fixed templates ([`HeuristicNarrator`]) or from a
+//! language model behind an OpenAI-compatible API ([`LlmNarrator`]). A
+//! scribe made with [`SiliconScribe::with_chronicle`] also appends every
+//! narration to a [`ChronicleWriter`], keeping the full story of a run.
+//!
+//! Consumers that each want their own share of the narrations, such as an
+//! event log and a websocket broadcaster, call [`SiliconScribe::subscribe`]
+//! with a [`NarrationFilter`] rather than racing on
+//! [`SiliconScribe::consume_narrations`].
+
+mod llm;
+
+pub use llm::{LlmNarrator, LlmSettings};
 
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+pub use primordium_data::{Narration, NarrationFilter};
+use primordium_io::chronicle::ChronicleWriter;
 use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
-
-/// A single narrative entry describing a simulation event.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Narration {
-    /// The simulation tick when this event occurred.
-    pub tick: u64,
-    /// The category or type of event (e.g., "ExtinctionEvent", "NewEra").
-    pub event_type: String,
-    /// The human-readable narrative text describing the event.
-    pub text: String,
-    /// The severity or importance of the event (0.0 to 1.0).
-    pub severity: f32,
-}
+use tokio::sync::{broadcast, mpsc};
+
+/// Narrations a subscriber may fall behind by before it starts missing
+/// them.
+pub const SUBSCRIBER_CAPACITY: usize = 64;
 
 /// Trait for generating narrative text from simulation events.
 #[async_trait]
@@ -78,6 +83,22 @@ impl Narrator for HeuristicNarrator {
                 "{} Tribal Strife: conflict has erupted between dominant lineages. (Tick {})",
                 prefix, tick
             ),
+            "PeaceTreaty" => format!(
+                "{} The Laying Down of Arms: {} (Tick {})",
+                prefix, description, tick
+            ),
+            "Monument" => format!(
+                "{} Words in Stone: {} (Tick {})",
+                prefix, description, tick
+            ),
+            "SeasonChange" => format!(
+                "{} The Turning Year: {} (Tick {})",
+                prefix, description, tick
+            ),
+            "Digest" => format!(
+                "{} Chronicle of Turmoil: {} (Tick {})",
+                prefix, description, tick
+            ),
             "CivilizationLevelUp" => format!(
                 "{} Civilizational Leap: a lineage has achieved a new tier of organization. (Tick {})",
                 prefix, tick
@@ -94,6 +115,12 @@ pub struct SiliconScribe {
     /// Maximum number of narrations to retain in history.
     pub max_history: usize,
     tx: mpsc::UnboundedSender<NarrationRequest>,
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+}
+
+struct Subscriber {
+    filter: NarrationFilter,
+    tx: broadcast::Sender<Narration>,
 }
 
 struct NarrationRequest {
@@ -112,10 +139,23 @@ impl Default for SiliconScribe {
 impl SiliconScribe {
     /// Creates a new SiliconScribe with the given narrator implementation.
     pub fn new(narrator: Box<dyn Narrator>) -> Self {
+        Self::spawn(narrator, None)
+    }
+
+    /// A scribe that also appends every narration to `chronicle`. The
+    /// in-memory history stays capped; the chronicle is not.
+    pub fn with_chronicle(narrator: Box<dyn Narrator>, chronicle: ChronicleWriter) -> Self {
+        Self::spawn(narrator, Some(chronicle))
+    }
+
+    fn spawn(narrator: Box<dyn Narrator>, mut chronicle: Option<ChronicleWriter>) -> Self {
         let narrations = Arc::new(Mutex::new(Vec::new()));
         let (tx, mut rx) = mpsc::unbounded_channel::<NarrationRequest>();
 
+        let subscribers: Arc<Mutex<Vec<Subscriber>>> = Arc::default();
+
         let narrations_clone = Arc::clone(&narrations);
+        let subscribers_clone = Arc::clone(&subscribers);
         let max_history = 100;
 
         if let Ok(handle) = tokio::runtime::Handle::try_current() {
@@ -137,6 +177,23 @@ impl SiliconScribe {
                         severity: req.severity,
                     };
 
+                    if let Some(writer) = &mut chronicle {
+                        if let Err(e) = writer.append(&narration) {
+                            eprintln!(
+                                "Warning: Failed to write chronicle {}: {e}",
+                                writer.path().display()
+                            );
+                            chronicle = None;
+                        }
+                    }
+
+                    if let Ok(mut subs) = subscribers_clone.lock() {
+                        subs.retain(|sub| sub.tx.receiver_count() > 0);
+                        for sub in subs.iter().filter(|sub| sub.filter.matches(&narration)) {
+                            let _ = sub.tx.send(narration.clone());
+                        }
+                    }
+
                     if let Ok(mut list) = narrations_clone.lock() {
                         if list.len() >= max_history {
                             list.remove(0);
@@ -151,6 +208,7 @@ impl SiliconScribe {
             narrations,
             max_history,
             tx,
+            subscribers,
         }
     }
 
@@ -164,6 +222,19 @@ impl SiliconScribe {
         });
     }
 
+    /// Receives every narration generated from now on that `filter`
+    /// matches. Each subscriber has its own queue of
+    /// [`SUBSCRIBER_CAPACITY`]; one that falls further behind gets
+    /// [`broadcast::error::RecvError::Lagged`] and skips the oldest.
+    /// Dropping the receiver ends the subscription.
+    pub fn subscribe(&self, filter: NarrationFilter) -> broadcast::Receiver<Narration> {
+        let (tx, rx) = broadcast::channel(SUBSCRIBER_CAPACITY);
+        if let Ok(mut subs) = self.subscribers.lock() {
+            subs.push(Subscriber { filter, tx });
+        }
+        rx
+    }
+
     /// Consumes and returns all generated narrations, clearing the history.
     pub fn consume_narrations(&self) -> Vec<Narration> {
         if let Ok(mut list) = self.narrations.lock() {
@@ -288,6 +359,47 @@ mod tests {
         assert_eq!(narrations[0].text, "Custom");
     }
 
+    #[tokio::test]
+    async fn test_chronicled_scribe_keeps_every_narration() {
+        let dir = std::env::temp_dir().join(format!("primordium_scribe_{}", uuid::Uuid::new_v4()));
+        let path = dir.join(primordium_io::chronicle::CHRONICLE_FILE);
+        let scribe = SiliconScribe::with_chronicle(
+            Box::new(HeuristicNarrator),
+            ChronicleWriter::open(&path).unwrap(),
+        );
+        for tick in [10, 20, 30] {
+            scribe.narrate(tick, "NewEra", "Beginning", 0.8);
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        let chronicle = primordium_io::chronicle::Chronicle::load(&path).unwrap();
+        let ticks: Vec<u64> = chronicle.narrations.iter().map(|n| n.tick).collect();
+        assert_eq!(ticks, vec![10, 20, 30]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_only_matching_narrations() {
+        let scribe = SiliconScribe::default();
+        let mut severe = scribe.subscribe(NarrationFilter::min_severity(0.8));
+        let mut eras = scribe.subscribe(NarrationFilter::default().event_types(["NewEra"]));
+        let dropped = scribe.subscribe(NarrationFilter::default());
+        drop(dropped);
+
+        scribe.narrate(1, "ClimateShift", "Warming", 0.6);
+        scribe.narrate(2, "NewEra", "Beginning", 0.7);
+        scribe.narrate(3, "ExtinctionEvent", "Collapse", 0.9);
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        assert_eq!(severe.try_recv().unwrap().tick, 3);
+        assert!(severe.try_recv().is_err());
+        assert_eq!(eras.try_recv().unwrap().tick, 2);
+        assert!(eras.try_recv().is_err());
+        // Subscribers do not take narrations from the history.
+        assert_eq!(scribe.consume_narrations().len(), 3);
+        assert_eq!(scribe.subscribers.lock().unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_narration_severity_filtering_concept() {
         let scribe = SiliconScribe::default();
@@ -0,0 +1,232 @@
+//! Narration by a language model behind an OpenAI-compatible API.
+
+use crate::{HeuristicNarrator, Narrator};
+use anyhow::Context;
+use async_trait::async_trait;
+use serde_json::json;
+use std::time::Duration;
+
+/// Steers the model towards the scribe's voice.
+const SYSTEM_PROMPT: &str = "You are the Silicon Scribe, chronicler of an evolving digital \
+ecosystem. Given a macro event, write one or two vivid sentences of history about it. \
+Do not invent numbers that are not in the event.";
+
+/// Where and how an [`LlmNarrator`] asks for prose.
+#[derive(Debug, Clone)]
+pub struct LlmSettings {
+    /// API root, e.g. `https://api.openai.com/v1`; `/chat/completions` is
+    /// appended.
+    pub base_url: String,
+    pub model: String,
+    /// Sent as a bearer token when set.
+    pub api_key: Option<String>,
+    /// Longest wait for a complete narration before falling back.
+    pub timeout: Duration,
+    pub max_tokens: u32,
+}
+
+/// Narrates through a chat completions endpoint, streaming the reply, and
+/// falls back to another narrator when the request fails, times out or
+/// comes back empty.
+pub struct LlmNarrator {
+    client: reqwest::Client,
+    settings: LlmSettings,
+    fallback: Box<dyn Narrator>,
+}
+
+impl LlmNarrator {
+    /// A narrator falling back to [`HeuristicNarrator`].
+    pub fn new(settings: LlmSettings) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            settings,
+            fallback: Box::new(HeuristicNarrator),
+        }
+    }
+
+    /// Replaces the narrator used when the model does not answer.
+    pub fn with_fallback(mut self, fallback: Box<dyn Narrator>) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Asks the model to narrate the event, passing each piece of prose to
+    /// `on_delta` as it streams in, and returns the whole narration.
+    pub async fn stream_narration(
+        &self,
+        tick: u64,
+        event_type: &str,
+        description: &str,
+        severity: f32,
+        mut on_delta: impl FnMut(&str) + Send,
+    ) -> anyhow::Result<String> {
+        let url = format!(
+            "{}/chat/completions",
+            self.settings.base_url.trim_end_matches('/')
+        );
+        let body = json!({
+            "model": self.settings.model,
+            "stream": true,
+            "max_tokens": self.settings.max_tokens,
+            "messages": [
+                {"role": "system", "content": SYSTEM_PROMPT},
+                {
+                    "role": "user",
+                    "content": format!(
+                        "Tick {tick}. Event: {event_type} (severity {severity:.2}). {description}"
+                    ),
+                },
+            ],
+        });
+        let mut request = self.client.post(&url).json(&body);
+        if let Some(key) = &self.settings.api_key {
+            request = request.bearer_auth(key);
+        }
+        let mut response = request
+            .send()
+            .await
+            .with_context(|| format!("Narrator request to {url} failed"))?
+            .error_for_status()?;
+
+        let mut text = String::new();
+        // Bytes, not text: a chunk may end inside a multi-byte character.
+        let mut pending = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            pending.extend_from_slice(&chunk);
+            while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = pending.drain(..=end).collect();
+                match parse_stream_line(&String::from_utf8_lossy(&line))? {
+                    StreamLine::Delta(delta) => {
+                        on_delta(&delta);
+                        text.push_str(&delta);
+                    }
+                    StreamLine::Done => return Ok(text.trim().to_string()),
+                    StreamLine::Other => {}
+                }
+            }
+        }
+        Ok(text.trim().to_string())
+    }
+}
+
+#[async_trait]
+impl Narrator for LlmNarrator {
+    async fn generate_narration(
+        &self,
+        tick: u64,
+        event_type: &str,
+        description: &str,
+        severity: f32,
+    ) -> String {
+        let reply = tokio::time::timeout(
+            self.settings.timeout,
+            self.stream_narration(tick, event_type, description, severity, |_| {}),
+        )
+        .await;
+        match reply {
+            Ok(Ok(text)) if !text.is_empty() => text,
+            _ => {
+                self.fallback
+                    .generate_narration(tick, event_type, description, severity)
+                    .await
+            }
+        }
+    }
+}
+
+/// One line of a server-sent event stream.
+#[derive(Debug, PartialEq)]
+enum StreamLine {
+    Delta(String),
+    Done,
+    /// Blank lines, comments, role announcements and the like.
+    Other,
+}
+
+fn parse_stream_line(line: &str) -> anyhow::Result<StreamLine> {
+    let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+        return Ok(StreamLine::Other);
+    };
+    if data == "[DONE]" {
+        return Ok(StreamLine::Done);
+    }
+    let event: serde_json::Value =
+        serde_json::from_str(data).context("Malformed narrator stream event")?;
+    Ok(event["choices"][0]["delta"]["content"]
+        .as_str()
+        .filter(|delta| !delta.is_empty())
+        .map_or(StreamLine::Other, |delta| {
+            StreamLine::Delta(delta.to_string())
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serves one HTTP response with `body` as an event stream, after
+    /// `delay`.
+    async fn serve_once(body: &'static str, delay: Duration) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 8192];
+            let _ = socket.read(&mut request).await;
+            tokio::time::sleep(delay).await;
+            let head = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncontent-length: {}\r\n\r\n",
+                body.len()
+            );
+            let _ = socket.write_all(head.as_bytes()).await;
+            let _ = socket.write_all(body.as_bytes()).await;
+        });
+        format!("http://{addr}/v1")
+    }
+
+    fn settings(base_url: String, timeout: Duration) -> LlmSettings {
+        LlmSettings {
+            base_url,
+            model: "test".to_string(),
+            api_key: Some("key".to_string()),
+            timeout,
+            max_tokens: 64,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_llm_narrator_streams_deltas() {
+        let url = serve_once(
+            concat!(
+                "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"The hungry \"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"years began.\"}}]}\n\n",
+                "data: [DONE]\n\n",
+            ),
+            Duration::ZERO,
+        )
+        .await;
+        let narrator = LlmNarrator::new(settings(url, Duration::from_secs(5)));
+
+        let mut deltas = Vec::new();
+        let text = narrator
+            .stream_narration(500, "GreatFamine", "Famine", 0.8, |d| {
+                deltas.push(d.to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(text, "The hungry years began.");
+        assert_eq!(deltas, vec!["The hungry ", "years began."]);
+    }
+
+    #[tokio::test]
+    async fn test_llm_narrator_falls_back_on_timeout() {
+        let url = serve_once("data: [DONE]\n\n", Duration::from_secs(5)).await;
+        let narrator = LlmNarrator::new(settings(url, Duration::from_millis(100)));
+        let text = narrator
+            .generate_narration(100, "ExtinctionEvent", "Collapse", 0.9)
+            .await;
+        assert!(text.contains("The Great Thinning"));
+    }
+}
@@ -0,0 +1,463 @@
+//! Moderation endpoints under `/admin`.
+//!
+//! They are disabled unless `PRIMORDIUM_ADMIN_KEY` is set, and every request
+//! must then carry that key as a Bearer token; an address that keeps
+//! presenting a wrong key is refused for a while. `primordium_server admin`
+//! (see [`crate::dashboard`]) is the terminal client for them.
+
+use crate::{bearer_token, broadcast, metrics, unix_now, AppState};
+use axum::{
+    extract::{ConnectInfo, Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Json, Router,
+};
+use primordium_io::storage::{
+    GenomeRecord, ModerationError, ModerationStatus, SeedRecord, SubmissionState,
+};
+use primordium_net::{NetMessage, PeerInfo, TradeProposal};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+/// Trades older than this are stale when a purge gives no age.
+pub const DEFAULT_STALE_TRADE_SECS: u64 = 600;
+
+/// Why and when an address was banned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ban {
+    pub reason: String,
+    /// Seconds since the Unix epoch.
+    pub since: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminPeer {
+    #[serde(flatten)]
+    pub info: PeerInfo,
+    pub addr: IpAddr,
+    pub connected_secs: u64,
+    pub idle_secs: u64,
+    pub compression: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminTrade {
+    #[serde(flatten)]
+    pub proposal: TradeProposal,
+    pub age_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BanEntry {
+    pub ip: IpAddr,
+    #[serde(flatten)]
+    pub ban: Ban,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Metrics {
+    pub uptime_secs: u64,
+    pub online_count: usize,
+    pub total_migrations: usize,
+    pub active_trades: usize,
+    pub bans: usize,
+    pub messages_relayed: u64,
+    pub peers_kicked: u64,
+    pub pending_submissions: usize,
+}
+
+/// Marketplace submissions in one moderation state, oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Submissions {
+    pub genomes: Vec<GenomeRecord>,
+    pub seeds: Vec<SeedRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BanRequest {
+    pub ip: IpAddr,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PurgeParams {
+    older_than_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusParams {
+    status: Option<ModerationStatus>,
+}
+
+/// Optional body of an approve or reject request.
+#[derive(Debug, Default, Deserialize)]
+pub struct ModerationRequest {
+    /// Shown to the submitter, e.g. why it was rejected.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// A failed admin request, rendered as `{"error": message}`.
+#[derive(Debug)]
+struct AdminError {
+    status: StatusCode,
+    message: String,
+}
+
+impl IntoResponse for AdminError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(serde_json::json!({ "error": self.message })),
+        )
+            .into_response()
+    }
+}
+
+type AdminResult<T> = Result<Json<T>, AdminError>;
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/admin/peers", get(list_peers))
+        .route("/admin/peers/:id/kick", post(kick_peer))
+        .route("/admin/trades", get(list_trades))
+        .route("/admin/trades/purge", post(purge_trades))
+        .route("/admin/bans", get(list_bans).post(add_ban))
+        .route("/admin/bans/:ip", delete(remove_ban))
+        .route("/admin/metrics", get(metrics))
+        .route("/admin/submissions", get(list_submissions))
+        .route("/admin/submissions/:id/approve", post(approve_submission))
+        .route("/admin/submissions/:id/reject", post(reject_submission))
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> AdminError {
+    AdminError {
+        status,
+        message: message.into(),
+    }
+}
+
+fn internal(what: impl Into<String>) -> AdminError {
+    let what = what.into();
+    tracing::error!("Admin request failed: {}", what);
+    error(StatusCode::INTERNAL_SERVER_ERROR, what)
+}
+
+/// Unlike `check_auth`, there is no open mode: without a configured key the
+/// endpoints do not exist.
+fn authorize(state: &AppState, remote: IpAddr, headers: &HeaderMap) -> Result<(), AdminError> {
+    let Some(expected) = state.admin_key.as_deref() else {
+        return Err(error(StatusCode::NOT_FOUND, "admin endpoints are disabled"));
+    };
+    let now = Instant::now();
+    let mut failures = state
+        .admin_failures
+        .lock()
+        .map_err(|_| internal("admin failures lock"))?;
+    // Refused before the key is looked at, so it cannot be guessed at speed.
+    if let Err(wait) = failures.check(remote, now) {
+        return Err(error(
+            StatusCode::TOO_MANY_REQUESTS,
+            format!(
+                "too many failed admin logins, retry in {}s",
+                wait.as_secs().max(1)
+            ),
+        ));
+    }
+    let valid = bearer_token(headers)
+        .is_some_and(|token| bool::from(token.as_bytes().ct_eq(expected.as_bytes())));
+    if valid {
+        Ok(())
+    } else {
+        failures.record(remote, now);
+        tracing::warn!(
+            "Rejected admin request from {}: invalid or missing admin key",
+            remote
+        );
+        Err(error(
+            StatusCode::UNAUTHORIZED,
+            "invalid or missing admin key",
+        ))
+    }
+}
+
+async fn list_peers(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> AdminResult<Vec<AdminPeer>> {
+    authorize(&state, remote.ip(), &headers)?;
+    let peers = state.peers.lock().map_err(|_| internal("peers lock"))?;
+    let connections = state
+        .connections
+        .lock()
+        .map_err(|_| internal("connections lock"))?;
+    let mut list: Vec<AdminPeer> = connections
+        .iter()
+        .filter_map(|(id, connection)| {
+            Some(AdminPeer {
+                info: peers.get(id)?.clone(),
+                addr: connection.addr,
+                connected_secs: connection.connected_at.elapsed().as_secs(),
+                idle_secs: connection.last_seen.elapsed().as_secs(),
+                compression: connection.compression,
+            })
+        })
+        .collect();
+    list.sort_by_key(|p| std::cmp::Reverse(p.connected_secs));
+    Ok(Json(list))
+}
+
+async fn kick_peer(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> AdminResult<serde_json::Value> {
+    authorize(&state, remote.ip(), &headers)?;
+    let connections = state
+        .connections
+        .lock()
+        .map_err(|_| internal("connections lock"))?;
+    let Some(connection) = connections.get(&id) else {
+        return Err(error(StatusCode::NOT_FOUND, "no such peer"));
+    };
+    connection.kick.notify_one();
+    state.metrics.increment_counter(metrics::KICKS);
+    tracing::info!("Admin kicked peer {}", id);
+    Ok(Json(serde_json::json!({ "kicked": id })))
+}
+
+async fn list_trades(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> AdminResult<Vec<AdminTrade>> {
+    authorize(&state, remote.ip(), &headers)?;
+    let trades = state
+        .active_trades
+        .lock()
+        .map_err(|_| internal("trades lock"))?;
+    let mut list: Vec<AdminTrade> = trades
+        .values()
+        .map(|trade| AdminTrade {
+            proposal: (*trade.proposal).clone(),
+            age_secs: trade.posted_at.elapsed().as_secs(),
+        })
+        .collect();
+    list.sort_by_key(|m| std::cmp::Reverse(m.age_secs));
+    Ok(Json(list))
+}
+
+/// Revokes every trade offer at least `older_than_secs` old.
+async fn purge_trades(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(params): Query<PurgeParams>,
+) -> AdminResult<serde_json::Value> {
+    authorize(&state, remote.ip(), &headers)?;
+    let max_age = params.older_than_secs.unwrap_or(DEFAULT_STALE_TRADE_SECS);
+    let purged: Vec<Uuid> = {
+        let mut trades = state
+            .active_trades
+            .lock()
+            .map_err(|_| internal("trades lock"))?;
+        let stale: Vec<Uuid> = trades
+            .iter()
+            .filter(|(_, t)| t.posted_at.elapsed().as_secs() >= max_age)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in &stale {
+            trades.remove(id);
+        }
+        stale
+    };
+    for id in &purged {
+        broadcast(&state, &NetMessage::TradeRevoke { proposal_id: *id });
+    }
+    tracing::info!(
+        "Admin purged {} trades older than {}s",
+        purged.len(),
+        max_age
+    );
+    Ok(Json(serde_json::json!({ "purged": purged.len() })))
+}
+
+async fn list_bans(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> AdminResult<Vec<BanEntry>> {
+    authorize(&state, remote.ip(), &headers)?;
+    let bans = state.bans.lock().map_err(|_| internal("bans lock"))?;
+    let mut list: Vec<BanEntry> = bans
+        .iter()
+        .map(|(ip, ban)| BanEntry {
+            ip: *ip,
+            ban: ban.clone(),
+        })
+        .collect();
+    list.sort_by_key(|entry| entry.ban.since);
+    Ok(Json(list))
+}
+
+/// Bans an address and kicks every peer connected from it.
+async fn add_ban(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(request): Json<BanRequest>,
+) -> AdminResult<serde_json::Value> {
+    authorize(&state, remote.ip(), &headers)?;
+    let reason = request.reason.unwrap_or_else(|| "banned".to_string());
+    state
+        .bans
+        .lock()
+        .map_err(|_| internal("bans lock"))?
+        .insert(
+            request.ip,
+            Ban {
+                reason: reason.clone(),
+                since: unix_now(),
+            },
+        );
+    let kicked = {
+        let connections = state
+            .connections
+            .lock()
+            .map_err(|_| internal("connections lock"))?;
+        let mut kicked = 0;
+        for connection in connections.values().filter(|c| c.addr == request.ip) {
+            connection.kick.notify_one();
+            kicked += 1;
+        }
+        kicked
+    };
+    state.metrics.add_counter(metrics::KICKS, kicked);
+    tracing::info!(
+        "Admin banned {} ({}), kicking {} peers",
+        request.ip,
+        reason,
+        kicked
+    );
+    Ok(Json(
+        serde_json::json!({ "banned": request.ip, "kicked": kicked }),
+    ))
+}
+
+async fn remove_ban(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(ip): Path<IpAddr>,
+) -> AdminResult<serde_json::Value> {
+    authorize(&state, remote.ip(), &headers)?;
+    let removed = state
+        .bans
+        .lock()
+        .map_err(|_| internal("bans lock"))?
+        .remove(&ip);
+    match removed {
+        Some(_) => {
+            tracing::info!("Admin unbanned {}", ip);
+            Ok(Json(serde_json::json!({ "unbanned": ip })))
+        }
+        None => Err(error(StatusCode::NOT_FOUND, "address is not banned")),
+    }
+}
+
+fn submissions(state: &AppState, status: ModerationStatus) -> Result<Submissions, AdminError> {
+    let (genomes, seeds) = state
+        .query(|storage| storage.query_submissions_async(status))
+        .ok_or_else(|| internal("failed to query submissions"))?;
+    Ok(Submissions { genomes, seeds })
+}
+
+async fn metrics(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> AdminResult<Metrics> {
+    authorize(&state, remote.ip(), &headers)?;
+    let pending = submissions(&state, ModerationStatus::Pending)?;
+    Ok(Json(Metrics {
+        uptime_secs: state.started_at.elapsed().as_secs(),
+        online_count: state.peers.lock().map(|p| p.len()).unwrap_or(0),
+        total_migrations: state.total_migrations(),
+        active_trades: state.active_trades.lock().map(|t| t.len()).unwrap_or(0),
+        bans: state.bans.lock().map(|b| b.len()).unwrap_or(0),
+        messages_relayed: state.metrics.counter(metrics::MESSAGES),
+        peers_kicked: state.metrics.counter(metrics::KICKS),
+        pending_submissions: pending.genomes.len() + pending.seeds.len(),
+    }))
+}
+
+/// Lists the submissions in `?status=` (pending by default).
+async fn list_submissions(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(params): Query<StatusParams>,
+) -> AdminResult<Submissions> {
+    authorize(&state, remote.ip(), &headers)?;
+    let status = params.status.unwrap_or(ModerationStatus::Pending);
+    Ok(Json(submissions(&state, status)?))
+}
+
+fn moderate(
+    state: &AppState,
+    id: Uuid,
+    status: ModerationStatus,
+    note: Option<String>,
+) -> AdminResult<SubmissionState> {
+    let outcome = state
+        .query(|storage| storage.moderate_submission_async(id, status, note.clone()))
+        .ok_or_else(|| internal("failed to moderate submission"))?;
+    match outcome {
+        Ok(previous) => {
+            tracing::info!(
+                "Admin moved submission {} from {} to {}",
+                id,
+                previous,
+                status
+            );
+            Ok(Json(SubmissionState { id, status, note }))
+        }
+        Err(e @ ModerationError::NotFound) => Err(error(StatusCode::NOT_FOUND, e.to_string())),
+        Err(e @ ModerationError::InvalidTransition { .. }) => {
+            Err(error(StatusCode::CONFLICT, e.to_string()))
+        }
+        Err(ModerationError::Storage(e)) => Err(internal(e)),
+    }
+}
+
+async fn approve_submission(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    body: Option<Json<ModerationRequest>>,
+) -> AdminResult<SubmissionState> {
+    authorize(&state, remote.ip(), &headers)?;
+    let note = body.and_then(|Json(b)| b.note);
+    moderate(&state, id, ModerationStatus::Approved, note)
+}
+
+async fn reject_submission(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    body: Option<Json<ModerationRequest>>,
+) -> AdminResult<SubmissionState> {
+    authorize(&state, remote.ip(), &headers)?;
+    let note = body.and_then(|Json(b)| b.note);
+    moderate(&state, id, ModerationStatus::Rejected, note)
+}
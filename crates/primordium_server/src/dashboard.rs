@@ -0,0 +1,460 @@
+//! `primordium_server admin`: a terminal dashboard for moderating a relay.
+//!
+//! It is a thin client over the [`crate::admin`] endpoints. It polls them
+//! every few seconds, and each action key maps to one request.
+
+use crate::admin::{
+    AdminPeer, AdminTrade, BanEntry, Metrics, Submissions, DEFAULT_STALE_TRADE_SECS,
+};
+use anyhow::{bail, Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use primordium_tui::Tui;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Tabs},
+    Frame,
+};
+use serde::de::DeserializeOwned;
+use std::time::{Duration, Instant};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+const INPUT_POLL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Peers,
+    Trades,
+    Bans,
+    Submissions,
+}
+
+impl Tab {
+    const ALL: [Tab; 4] = [Tab::Peers, Tab::Trades, Tab::Bans, Tab::Submissions];
+
+    fn title(self) -> &'static str {
+        match self {
+            Tab::Peers => "Peers",
+            Tab::Trades => "Trades",
+            Tab::Bans => "Bans",
+            Tab::Submissions => "Submissions",
+        }
+    }
+
+    fn help(self) -> &'static str {
+        match self {
+            Tab::Peers => "[k] kick  [b] ban address",
+            Tab::Trades => "[p] purge stale trades",
+            Tab::Bans => "[u] unban",
+            Tab::Submissions => "[a] approve  [x] reject",
+        }
+    }
+
+    fn index(self) -> usize {
+        Tab::ALL.iter().position(|t| *t == self).unwrap_or(0)
+    }
+}
+
+/// Authenticated requests against one relay.
+struct Client {
+    http: reqwest::Client,
+    base: String,
+    key: String,
+}
+
+impl Client {
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let response = request.bearer_auth(&self.key).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            match body.get("error").and_then(|e| e.as_str()) {
+                Some(error) => bail!("{status}: {error}"),
+                None => bail!("{status}"),
+            }
+        }
+        Ok(response)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{}{path}", self.base);
+        Ok(self.send(self.http.get(url)).await?.json().await?)
+    }
+
+    async fn post(&self, path: &str, body: Option<serde_json::Value>) -> Result<serde_json::Value> {
+        let mut request = self.http.post(format!("{}{path}", self.base));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        Ok(self.send(request).await?.json().await?)
+    }
+
+    async fn delete(&self, path: &str) -> Result<serde_json::Value> {
+        let url = format!("{}{path}", self.base);
+        Ok(self.send(self.http.delete(url)).await?.json().await?)
+    }
+}
+
+#[derive(Default)]
+struct Dashboard {
+    tab: Option<Tab>,
+    table: TableState,
+    metrics: Metrics,
+    peers: Vec<AdminPeer>,
+    trades: Vec<AdminTrade>,
+    bans: Vec<BanEntry>,
+    submissions: Submissions,
+    status: String,
+}
+
+impl Dashboard {
+    fn tab(&self) -> Tab {
+        self.tab.unwrap_or(Tab::Peers)
+    }
+
+    fn rows(&self) -> usize {
+        match self.tab() {
+            Tab::Peers => self.peers.len(),
+            Tab::Trades => self.trades.len(),
+            Tab::Bans => self.bans.len(),
+            Tab::Submissions => self.submissions.genomes.len() + self.submissions.seeds.len(),
+        }
+    }
+
+    fn select_tab(&mut self, tab: Tab) {
+        self.tab = Some(tab);
+        self.table.select(Some(0));
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let rows = self.rows();
+        if rows == 0 {
+            self.table.select(None);
+            return;
+        }
+        let current = self.table.selected().unwrap_or(0) as isize;
+        self.table
+            .select(Some((current + delta).clamp(0, rows as isize - 1) as usize));
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.table.selected().filter(|&i| i < self.rows())
+    }
+
+    /// Id of the selected submission, genomes listed before seeds.
+    fn selected_submission(&self) -> Option<uuid::Uuid> {
+        let i = self.selected()?;
+        let genomes = &self.submissions.genomes;
+        match genomes.get(i) {
+            Some(genome) => Some(genome.id),
+            None => self.submissions.seeds.get(i - genomes.len()).map(|s| s.id),
+        }
+    }
+
+    async fn refresh(&mut self, client: &Client) -> Result<()> {
+        self.metrics = client.get("/admin/metrics").await?;
+        self.peers = client.get("/admin/peers").await?;
+        self.trades = client.get("/admin/trades").await?;
+        self.bans = client.get("/admin/bans").await?;
+        self.submissions = client.get("/admin/submissions").await?;
+        self.move_selection(0);
+        Ok(())
+    }
+
+    /// Runs the action bound to `key` on the current tab, if any.
+    async fn act(&mut self, client: &Client, key: char) -> Result<Option<String>> {
+        let done = match (self.tab(), key) {
+            (Tab::Peers, 'k') => match self.selected().map(|i| self.peers[i].info.peer_id) {
+                Some(id) => {
+                    client
+                        .post(&format!("/admin/peers/{id}/kick"), None)
+                        .await?;
+                    format!("Kicked {id}")
+                }
+                None => return Ok(None),
+            },
+            (Tab::Peers, 'b') => match self.selected().map(|i| self.peers[i].addr) {
+                Some(ip) => {
+                    let body = serde_json::json!({ "ip": ip, "reason": "banned from dashboard" });
+                    client.post("/admin/bans", Some(body)).await?;
+                    format!("Banned {ip}")
+                }
+                None => return Ok(None),
+            },
+            (Tab::Trades, 'p') => {
+                let path =
+                    format!("/admin/trades/purge?older_than_secs={DEFAULT_STALE_TRADE_SECS}");
+                let reply = client.post(&path, None).await?;
+                format!("Purged {} stale trades", reply["purged"])
+            }
+            (Tab::Bans, 'u') => match self.selected().map(|i| self.bans[i].ip) {
+                Some(ip) => {
+                    client.delete(&format!("/admin/bans/{ip}")).await?;
+                    format!("Unbanned {ip}")
+                }
+                None => return Ok(None),
+            },
+            (Tab::Submissions, 'a' | 'x') => match self.selected_submission() {
+                Some(id) => {
+                    let action = if key == 'a' { "approve" } else { "reject" };
+                    client
+                        .post(&format!("/admin/submissions/{id}/{action}"), None)
+                        .await?;
+                    format!("Submission {id}: {action}d")
+                }
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(done))
+    }
+
+    fn render(&mut self, f: &mut Frame, base: &str) {
+        let [header, tabs, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(2),
+        ])
+        .areas(f.area());
+
+        let m = &self.metrics;
+        let summary = format!(
+            "up {}s | {} online | {} migrations | {} trades | {} bans | {} relayed | {} kicked | {} pending",
+            m.uptime_secs,
+            m.online_count,
+            m.total_migrations,
+            m.active_trades,
+            m.bans,
+            m.messages_relayed,
+            m.peers_kicked,
+            m.pending_submissions
+        );
+        f.render_widget(
+            Paragraph::new(summary).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Relay admin: {base} ")),
+            ),
+            header,
+        );
+
+        let titles: Vec<Line> = Tab::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, t)| Line::from(format!("{} {}", i + 1, t.title())))
+            .collect();
+        f.render_widget(
+            Tabs::new(titles)
+                .select(self.tab().index())
+                .highlight_style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            tabs,
+        );
+
+        let (columns, widths, rows) = self.table_rows();
+        let table = Table::new(rows, widths)
+            .header(Row::new(columns).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(Block::default().borders(Borders::ALL))
+            .row_highlight_style(Style::default().bg(Color::DarkGray));
+        f.render_stateful_widget(table, body, &mut self.table);
+
+        let help = format!(
+            "{}  |  [1-4/Tab] switch  [↑↓] select  [r] refresh  [q] quit\n{}",
+            self.tab().help(),
+            self.status
+        );
+        f.render_widget(Paragraph::new(help), footer);
+    }
+
+    fn table_rows(&self) -> (Vec<&'static str>, Vec<Constraint>, Vec<Row<'static>>) {
+        match self.tab() {
+            Tab::Peers => (
+                vec![
+                    "Peer", "Address", "Entities", "Sent", "Recv", "Online", "Idle", "Deflate",
+                ],
+                vec![
+                    Constraint::Length(36),
+                    Constraint::Length(16),
+                    Constraint::Length(9),
+                    Constraint::Length(6),
+                    Constraint::Length(6),
+                    Constraint::Length(8),
+                    Constraint::Length(6),
+                    Constraint::Length(7),
+                ],
+                self.peers
+                    .iter()
+                    .map(|p| {
+                        Row::new(vec![
+                            p.info.peer_id.to_string(),
+                            p.addr.to_string(),
+                            p.info.entity_count.to_string(),
+                            p.info.migrations_sent.to_string(),
+                            p.info.migrations_received.to_string(),
+                            format!("{}s", p.connected_secs),
+                            format!("{}s", p.idle_secs),
+                            if p.compression { "yes" } else { "no" }.to_string(),
+                        ])
+                    })
+                    .collect(),
+            ),
+            Tab::Trades => (
+                vec!["Trade", "Sender", "Offer", "Request", "Age"],
+                vec![
+                    Constraint::Length(36),
+                    Constraint::Length(36),
+                    Constraint::Length(18),
+                    Constraint::Length(18),
+                    Constraint::Length(8),
+                ],
+                self.trades
+                    .iter()
+                    .map(|t| {
+                        let p = &t.proposal;
+                        Row::new(vec![
+                            p.id.to_string(),
+                            p.sender_id.to_string(),
+                            format!("{:.0} {:?}", p.offer_amount, p.offer_resource),
+                            format!("{:.0} {:?}", p.request_amount, p.request_resource),
+                            format!("{}s", t.age_secs),
+                        ])
+                    })
+                    .collect(),
+            ),
+            Tab::Bans => (
+                vec!["Address", "Reason", "Since (unix)"],
+                vec![
+                    Constraint::Length(40),
+                    Constraint::Min(20),
+                    Constraint::Length(12),
+                ],
+                self.bans
+                    .iter()
+                    .map(|b| {
+                        Row::new(vec![
+                            b.ip.to_string(),
+                            b.ban.reason.clone(),
+                            b.ban.since.to_string(),
+                        ])
+                    })
+                    .collect(),
+            ),
+            Tab::Submissions => (
+                vec!["Kind", "Name", "Author", "Submitted", "Summary"],
+                vec![
+                    Constraint::Length(6),
+                    Constraint::Length(24),
+                    Constraint::Length(16),
+                    Constraint::Length(20),
+                    Constraint::Min(20),
+                ],
+                self.submissions
+                    .genomes
+                    .iter()
+                    .map(|g| {
+                        let mut summary =
+                            format!("fitness {:.1}, tick {}", g.fitness_score, g.tick);
+                        if let (Some(original), Some(similarity)) = (g.duplicate_of, g.similarity) {
+                            summary.push_str(&format!(
+                                ", {:.0}% like {}",
+                                similarity * 100.0,
+                                &original.to_string()[..8]
+                            ));
+                        }
+                        Row::new(vec![
+                            "genome".to_string(),
+                            g.name.clone(),
+                            g.author.clone(),
+                            g.created_at.clone(),
+                            summary,
+                        ])
+                    })
+                    .chain(self.submissions.seeds.iter().map(|s| {
+                        Row::new(vec![
+                            "seed".to_string(),
+                            s.name.clone(),
+                            s.author.clone(),
+                            s.created_at.clone(),
+                            format!("max pop {}, {}", s.max_pop, s.performance_summary),
+                        ])
+                    }))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Opens the dashboard against the relay at `url` until the user quits.
+pub async fn run(url: String, key: String) -> Result<()> {
+    let client = Client {
+        http: reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()?,
+        base: url.trim_end_matches('/').to_string(),
+        key,
+    };
+    let mut dashboard = Dashboard::default();
+    dashboard
+        .refresh(&client)
+        .await
+        .with_context(|| format!("Cannot reach the admin endpoints at {}", client.base))?;
+
+    let mut tui = Tui::new()?;
+    tui.init()?;
+    let mut last_refresh = Instant::now();
+    loop {
+        tui.terminal.draw(|f| dashboard.render(f, &client.base))?;
+
+        if event::poll(INPUT_POLL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                let outcome = match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Char(c @ '1'..='4') => {
+                        dashboard.select_tab(Tab::ALL[c as usize - '1' as usize]);
+                        Ok(None)
+                    }
+                    KeyCode::Tab => {
+                        let next = (dashboard.tab().index() + 1) % Tab::ALL.len();
+                        dashboard.select_tab(Tab::ALL[next]);
+                        Ok(None)
+                    }
+                    KeyCode::Up => {
+                        dashboard.move_selection(-1);
+                        Ok(None)
+                    }
+                    KeyCode::Down => {
+                        dashboard.move_selection(1);
+                        Ok(None)
+                    }
+                    KeyCode::Char('r') => Ok(Some("Refreshed".to_string())),
+                    KeyCode::Char(c) => dashboard.act(&client, c).await,
+                    _ => Ok(None),
+                };
+                match outcome {
+                    Ok(Some(message)) => {
+                        dashboard.status = message;
+                        last_refresh = Instant::now() - REFRESH_INTERVAL;
+                    }
+                    Ok(None) => {}
+                    Err(e) => dashboard.status = format!("Error: {e:#}"),
+                }
+            }
+        }
+
+        if last_refresh.elapsed() >= REFRESH_INTERVAL {
+            if let Err(e) = dashboard.refresh(&client).await {
+                dashboard.status = format!("Refresh failed: {e:#}");
+            }
+            last_refresh = Instant::now();
+        }
+    }
+    tui.exit()
+}
@@ -0,0 +1,237 @@
+//! Per-peer rate limits of the relay websocket.
+//!
+//! Every connection gets a token bucket for its messages, and every address
+//! a daily quota of migrations, so reconnecting does not reset it. A dropped
+//! message is answered with [`NetMessage::RateLimited`] so the peer can back
+//! off; while a connection stays over its message rate only the first drop
+//! is answered. Failed admin logins are limited per address as well.
+
+use crate::{metrics, unix_now, AppState};
+use primordium_io::network::flow_control::TokenBucket;
+use primordium_net::{NetMessage, RateLimit};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const SECS_PER_DAY: u64 = 86_400;
+/// Failed admin logins an address may make in a row.
+const LOGIN_FAILURE_BURST: f64 = 5.0;
+/// Seconds for an address to earn back one failed admin login.
+const LOGIN_FAILURE_REFILL_SECS: f64 = 60.0;
+
+/// Limits read from `PRIMORDIUM_MESSAGES_PER_SEC`,
+/// `PRIMORDIUM_MESSAGE_BURST` and `PRIMORDIUM_DAILY_MIGRATIONS` (0 = no
+/// quota).
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitConfig {
+    /// Sustained messages per second of one connection.
+    pub messages_per_sec: f64,
+    /// Messages a connection may send at once after being quiet.
+    pub burst: f64,
+    /// Migrations one address may send per UTC day; 0 for no quota.
+    pub daily_migrations: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            messages_per_sec: 20.0,
+            burst: 40.0,
+            daily_migrations: 5000,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok();
+        let rate = |name: &str| {
+            var(name)
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|&v| v > 0.0)
+        };
+        let default = Self::default();
+        let messages_per_sec =
+            rate("PRIMORDIUM_MESSAGES_PER_SEC").unwrap_or(default.messages_per_sec);
+        let burst = rate("PRIMORDIUM_MESSAGE_BURST").unwrap_or(default.burst);
+        let daily_migrations = var("PRIMORDIUM_DAILY_MIGRATIONS")
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(default.daily_migrations);
+        Self {
+            messages_per_sec,
+            // A burst below one message would refuse everything.
+            burst: burst.max(1.0),
+            daily_migrations,
+        }
+    }
+}
+
+/// Migrations an address has sent on `day` (days since the Unix epoch).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DailyQuota {
+    day: u64,
+    used: u32,
+}
+
+/// Message rate limit of one connection.
+pub struct PeerLimiter {
+    bucket: TokenBucket,
+    retry_after_ms: u64,
+    /// Whether the peer was told about its current run of dropped messages.
+    notified: bool,
+}
+
+impl PeerLimiter {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            bucket: TokenBucket::new(config.burst, config.messages_per_sec),
+            retry_after_ms: (1000.0 / config.messages_per_sec).ceil() as u64,
+            notified: false,
+        }
+    }
+
+    /// Takes one message from the bucket. `Err` with the reply to send, if
+    /// any, when the message must be dropped.
+    pub fn admit(&mut self, migration_id: Option<Uuid>) -> Result<(), Option<NetMessage>> {
+        if self.bucket.try_acquire(1.0) {
+            self.notified = false;
+            return Ok(());
+        }
+        // A dropped migration is always answered: its entity is waiting.
+        if self.notified && migration_id.is_none() {
+            return Err(None);
+        }
+        self.notified = true;
+        Err(Some(NetMessage::RateLimited {
+            limit: RateLimit::Messages,
+            retry_after_ms: self.retry_after_ms,
+            migration_id,
+        }))
+    }
+}
+
+/// Counts a migration against `addr`'s daily quota, or returns the reply
+/// refusing it.
+pub fn take_migration(
+    state: &AppState,
+    addr: IpAddr,
+    migration_id: Uuid,
+) -> Result<(), NetMessage> {
+    let quota = state.rate_limits.daily_migrations;
+    if quota == 0 {
+        return Ok(());
+    }
+    let now = unix_now();
+    let today = now / SECS_PER_DAY;
+    let Ok(mut quotas) = state.migration_quotas.lock() else {
+        return Ok(());
+    };
+    let entry = quotas.entry(addr).or_default();
+    if entry.day != today {
+        *entry = DailyQuota {
+            day: today,
+            used: 0,
+        };
+    }
+    if entry.used < quota {
+        entry.used += 1;
+        return Ok(());
+    }
+    state.metrics.increment_counter(metrics::OVER_QUOTA);
+    Err(NetMessage::RateLimited {
+        limit: RateLimit::DailyMigrations,
+        retry_after_ms: (SECS_PER_DAY - now % SECS_PER_DAY) * 1000,
+        migration_id: Some(migration_id),
+    })
+}
+
+/// Failed login allowance of every address that failed recently. Addresses
+/// whose allowance has refilled are forgotten, so the map only holds the
+/// ones still paying for their failures.
+#[derive(Debug, Default)]
+pub struct LoginFailures {
+    allowances: HashMap<IpAddr, (f64, Instant)>,
+}
+
+impl LoginFailures {
+    fn allowance(&self, addr: IpAddr, now: Instant) -> f64 {
+        self.allowances
+            .get(&addr)
+            .map_or(LOGIN_FAILURE_BURST, |&(left, since)| {
+                refill(left, since, now)
+            })
+    }
+
+    /// `Err` with how long `addr` must wait once it used up its failures.
+    pub fn check(&self, addr: IpAddr, now: Instant) -> Result<(), Duration> {
+        let left = self.allowance(addr, now);
+        if left >= 1.0 {
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - left) * LOGIN_FAILURE_REFILL_SECS,
+            ))
+        }
+    }
+
+    /// Counts a failed login of `addr`.
+    pub fn record(&mut self, addr: IpAddr, now: Instant) {
+        let left = self.allowance(addr, now) - 1.0;
+        self.allowances
+            .retain(|_, &mut (left, since)| refill(left, since, now) < LOGIN_FAILURE_BURST);
+        self.allowances.insert(addr, (left, now));
+    }
+}
+
+fn refill(left: f64, since: Instant, now: Instant) -> f64 {
+    let earned = now.saturating_duration_since(since).as_secs_f64() / LOGIN_FAILURE_REFILL_SECS;
+    (left + earned).min(LOGIN_FAILURE_BURST)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limiter_answers_the_first_drop_of_a_run() {
+        let mut limiter = PeerLimiter::new(&RateLimitConfig {
+            messages_per_sec: 0.001,
+            burst: 2.0,
+            daily_migrations: 0,
+        });
+        assert!(limiter.admit(None).is_ok());
+        assert!(limiter.admit(None).is_ok());
+        let Err(Some(NetMessage::RateLimited { retry_after_ms, .. })) = limiter.admit(None) else {
+            panic!("expected a RateLimited reply");
+        };
+        assert_eq!(retry_after_ms, 1_000_000);
+        assert!(matches!(limiter.admit(None), Err(None)));
+
+        let id = Uuid::new_v4();
+        let Err(Some(NetMessage::RateLimited { migration_id, .. })) = limiter.admit(Some(id))
+        else {
+            panic!("dropped migrations are always answered");
+        };
+        assert_eq!(migration_id, Some(id));
+    }
+
+    #[test]
+    fn test_login_failures_lock_out_and_forget_addresses() {
+        let mut failures = LoginFailures::default();
+        let start = Instant::now();
+        let attacker: IpAddr = [10, 0, 0, 1].into();
+        for _ in 0..5 {
+            assert!(failures.check(attacker, start).is_ok());
+            failures.record(attacker, start);
+        }
+        let wait = failures.check(attacker, start).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(60));
+        assert!(failures.check([10, 0, 0, 2].into(), start).is_ok());
+        assert!(failures.check(attacker, start + wait).is_ok());
+
+        // Once the attacker's allowance refilled, another failure drops it.
+        failures.record([10, 0, 0, 2].into(), start + Duration::from_secs(300));
+        assert_eq!(failures.allowances.len(), 1);
+    }
+}
@@ -1,26 +1,143 @@
+mod admin;
+mod dashboard;
+mod limits;
+mod metrics;
+mod spectate;
+
+use admin::Ban;
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        ConnectInfo, Path, Query, State,
     },
     http::{HeaderMap, StatusCode},
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
+use clap::{Parser, Subcommand};
 use futures::{sink::SinkExt, stream::StreamExt};
-use primordium_io::storage::{GenomeSubmit, SeedSubmit, StorageManager};
+use limits::{PeerLimiter, RateLimitConfig};
+use primordium_core::Metrics;
+use primordium_io::challenge::{result_signature, Challenge, ChallengeResult};
+use primordium_io::similarity::SimilarityThresholds;
+use primordium_io::storage::{
+    GenomeSubmit, GenomeSubmitOutcome, ModerationStatus, ScenarioError, ScenarioResultSubmit,
+    ScenarioSubmit, SeedSubmit, SimilarityError, SimilarityQuery, StorageManager,
+};
+use primordium_io::thumbnail::Thumbnail;
 use std::{
     collections::HashMap,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
 };
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc, Notify};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use uuid::Uuid;
 
 // Re-use the shared network protocol from the main library
-use primordium_net::{NetMessage, PeerInfo, TradeProposal};
+use primordium_net::{codec, NetMessage, PeerInfo, TradeProposal};
+
+/// Maximum message size: 100KB to prevent DoS
+const MAX_MESSAGE_SIZE: usize = 100 * 1024;
+
+/// How often peers are pinged and how long one may stay silent before it is
+/// dropped. Read from `PRIMORDIUM_HEARTBEAT_SECS` and
+/// `PRIMORDIUM_PEER_TIMEOUT_SECS`.
+#[derive(Debug, Clone, Copy)]
+struct HeartbeatConfig {
+    interval: Duration,
+    timeout: Duration,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(15),
+            timeout: Duration::from_secs(45),
+        }
+    }
+}
+
+impl HeartbeatConfig {
+    fn from_env() -> Self {
+        let secs = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|&v| v > 0)
+                .map(Duration::from_secs)
+        };
+        let default = Self::default();
+        let interval = secs("PRIMORDIUM_HEARTBEAT_SECS").unwrap_or(default.interval);
+        let timeout = secs("PRIMORDIUM_PEER_TIMEOUT_SECS").unwrap_or(default.timeout);
+        Self {
+            interval,
+            // A peer gets at least one ping before it can time out.
+            timeout: timeout.max(interval * 2),
+        }
+    }
+}
+
+/// Reads the duplicate thresholds from `PRIMORDIUM_DUPLICATE_THRESHOLD` and
+/// `PRIMORDIUM_MERGE_THRESHOLD`, each a similarity between 0 and 1.
+fn similarity_thresholds_from_env() -> SimilarityThresholds {
+    let similarity = |name: &str| {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.parse::<f32>().ok())
+            .filter(|v| (0.0..=1.0).contains(v))
+    };
+    let default = SimilarityThresholds::default();
+    let flag = similarity("PRIMORDIUM_DUPLICATE_THRESHOLD").unwrap_or(default.flag);
+    let merge = similarity("PRIMORDIUM_MERGE_THRESHOLD").unwrap_or(default.merge);
+    SimilarityThresholds {
+        flag,
+        // Anything close enough to merge is close enough to flag.
+        merge: merge.max(flag),
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "primordium-server", about = "Primordium relay server")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the relay (the default)
+    Serve,
+    /// Open the moderation dashboard of a running relay
+    Admin {
+        /// Base URL of the relay
+        #[arg(long, default_value = "http://127.0.0.1:3000")]
+        url: String,
+        /// Admin key; defaults to PRIMORDIUM_ADMIN_KEY
+        #[arg(long, env = "PRIMORDIUM_ADMIN_KEY", hide_env_values = true)]
+        key: String,
+    },
+}
+
+/// What the relay knows about one open websocket, beyond its `PeerInfo`.
+struct Connection {
+    addr: IpAddr,
+    connected_at: Instant,
+    last_seen: Instant,
+    compression: bool,
+    /// Sent in the handshake; required to publish the peer's world.
+    publish_secret: String,
+    /// Woken to make the connection close itself.
+    kick: Arc<Notify>,
+}
+
+/// A trade offer waiting to be accepted, and when it was posted.
+struct ActiveTrade {
+    proposal: Arc<TradeProposal>,
+    posted_at: Instant,
+}
 
 /// Server state tracking connected peers and their info
 struct AppState {
@@ -28,23 +145,115 @@ struct AppState {
     tx: broadcast::Sender<String>,
     /// Connected peers with their metadata
     peers: Arc<Mutex<HashMap<Uuid, PeerInfo>>>,
-    /// Total migrations processed by server
-    total_migrations: Arc<Mutex<usize>>,
-    active_trades: Arc<Mutex<HashMap<Uuid, Arc<TradeProposal>>>>,
+    active_trades: Arc<Mutex<HashMap<Uuid, ActiveTrade>>>,
+    /// Open websockets, keyed like `peers`
+    connections: Arc<Mutex<HashMap<Uuid, Connection>>>,
+    /// Addresses refused at connect time, until the relay restarts
+    bans: Arc<Mutex<HashMap<IpAddr, Ban>>>,
+    /// Message rates and migration quotas of peers
+    rate_limits: RateLimitConfig,
+    /// Migrations each address sent today
+    migration_quotas: Arc<Mutex<HashMap<IpAddr, limits::DailyQuota>>>,
+    /// Live-spectator streams, keyed by the publishing peer
+    spectate: Arc<Mutex<HashMap<Uuid, spectate::Channel>>>,
     /// Persistent storage for Hall of Fame and marketplace
     storage: StorageManager,
     /// API key for write endpoints (None = open mode)
     api_key: Option<String>,
+    /// Key for the `/admin` endpoints (None = admin disabled)
+    admin_key: Option<String>,
+    /// Failed admin logins of each address
+    admin_failures: Arc<Mutex<limits::LoginFailures>>,
+    /// Hold marketplace submissions back until a moderator approves them
+    moderate_submissions: bool,
+    /// When a genome submission is flagged or merged as a copy
+    similarity: SimilarityThresholds,
+    heartbeat: HeartbeatConfig,
+    started_at: Instant,
+    /// Relay counters and storage latencies since start, see [`metrics`]
+    metrics: Metrics,
+}
+
+impl AppState {
+    /// Open-mode state with admin disabled and default heartbeats.
+    fn new(storage: StorageManager) -> Self {
+        let (tx, _rx) = broadcast::channel::<String>(100);
+        Self {
+            tx,
+            peers: Arc::new(Mutex::new(HashMap::new())),
+            active_trades: Arc::new(Mutex::new(HashMap::new())),
+            connections: Arc::new(Mutex::new(HashMap::new())),
+            bans: Arc::new(Mutex::new(HashMap::new())),
+            rate_limits: RateLimitConfig::default(),
+            migration_quotas: Arc::new(Mutex::new(HashMap::new())),
+            spectate: Arc::new(Mutex::new(HashMap::new())),
+            storage,
+            api_key: None,
+            admin_key: None,
+            admin_failures: Arc::new(Mutex::new(limits::LoginFailures::default())),
+            moderate_submissions: false,
+            similarity: SimilarityThresholds::default(),
+            heartbeat: HeartbeatConfig::default(),
+            started_at: Instant::now(),
+            metrics: Metrics::new(),
+        }
+    }
+
+    /// Submits a storage query with `submit` and waits for its answer,
+    /// recording how long that took. `None` if storage is unavailable.
+    fn query<T>(
+        &self,
+        submit: impl FnOnce(&StorageManager) -> Option<std::sync::mpsc::Receiver<T>>,
+    ) -> Option<T> {
+        let started = Instant::now();
+        let answer = submit(&self.storage).and_then(|rx| rx.recv().ok());
+        self.metrics
+            .observe(metrics::STORAGE_QUERY, started.elapsed());
+        answer
+    }
+
+    /// Migrations relayed since start.
+    fn total_migrations(&self) -> usize {
+        self.metrics.counter(metrics::MIGRATIONS) as usize
+    }
+
+    /// Moderation state new marketplace submissions start in.
+    fn initial_status(&self) -> ModerationStatus {
+        if self.moderate_submissions {
+            ModerationStatus::Pending
+        } else {
+            ModerationStatus::Approved
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn env_key(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|k| !k.is_empty())
 }
+
 #[tokio::main]
 async fn main() {
+    if let Some(Command::Admin { url, key }) = Cli::parse().command {
+        if let Err(e) = dashboard::run(url, key).await {
+            eprintln!("Admin dashboard failed: {e:#}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     tracing_subscriber::registry()
         .with(tracing_subscriber::EnvFilter::new(
             std::env::var("RUST_LOG").unwrap_or_else(|_| "server=info".into()),
         ))
         .with(tracing_subscriber::fmt::layer())
         .init();
-    let (tx, _rx) = broadcast::channel::<String>(100);
     let storage: StorageManager = match StorageManager::new("./registry.db") {
         Ok(s) => {
             tracing::info!("Initialized persistent storage: registry.db");
@@ -56,9 +265,7 @@ async fn main() {
         }
     };
 
-    let api_key = std::env::var("PRIMORDIUM_API_KEY")
-        .ok()
-        .filter(|k| !k.is_empty());
+    let api_key = env_key("PRIMORDIUM_API_KEY");
     if api_key.is_some() {
         tracing::info!("API key authentication enabled for write endpoints");
     } else {
@@ -67,32 +274,57 @@ async fn main() {
         );
     }
 
+    let admin_key = env_key("PRIMORDIUM_ADMIN_KEY");
+    if admin_key.is_some() {
+        tracing::info!("Admin endpoints enabled under /admin");
+    }
+    let moderate_submissions = std::env::var("PRIMORDIUM_MODERATE_SUBMISSIONS")
+        .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+    if moderate_submissions {
+        tracing::info!("Marketplace submissions are held for moderator approval");
+    }
+
+    let similarity = similarity_thresholds_from_env();
+    tracing::info!(
+        "Genome submissions flagged above {} similarity, merged above {}",
+        similarity.flag,
+        similarity.merge
+    );
+
+    let heartbeat = HeartbeatConfig::from_env();
+    tracing::info!(
+        "Heartbeat every {:?}, peers dropped after {:?} of silence",
+        heartbeat.interval,
+        heartbeat.timeout
+    );
+
+    let rate_limits = RateLimitConfig::from_env();
+    tracing::info!(
+        "Peers limited to {}/s messages (bursts of {}) and {} migrations a day (0 = no quota)",
+        rate_limits.messages_per_sec,
+        rate_limits.burst,
+        rate_limits.daily_migrations
+    );
+
     let app_state = Arc::new(AppState {
-        tx,
-        peers: Arc::new(Mutex::new(HashMap::new())),
-        total_migrations: Arc::new(Mutex::new(0)),
-        active_trades: Arc::new(Mutex::new(HashMap::new())),
-        storage,
         api_key,
+        admin_key,
+        moderate_submissions,
+        similarity,
+        heartbeat,
+        rate_limits,
+        ..AppState::new(storage)
     });
 
-    let app = Router::new()
-        .route("/ws", get(websocket_handler))
-        .route("/api/peers", get(get_peers))
-        .route("/api/stats", get(get_stats))
-        .route("/api/registry/hall_of_fame", get(get_hall_of_fame))
-        .route(
-            "/api/registry/genomes",
-            get(get_genomes).post(submit_genome),
-        )
-        .route("/api/registry/seeds", get(get_seeds).post(submit_seed))
-        .with_state(app_state);
+    let app = router(app_state);
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
 
     tracing::info!("Primordium Relay Server listening on {}", addr);
     tracing::info!("    WebSocket: ws://{}/ws", addr);
+    tracing::info!("    Spectate:  ws://{}/ws/spectate/<peer_id>", addr);
     tracing::info!("    Peers API: http://{}/api/peers", addr);
     tracing::info!("    Stats API: http://{}/api/stats", addr);
+    tracing::info!("    Metrics:   http://{}/metrics", addr);
 
     let listener = match tokio::net::TcpListener::bind(addr).await {
         Ok(l) => l,
@@ -102,12 +334,50 @@ async fn main() {
         }
     };
 
-    if let Err(e) = axum::serve(listener, app).await {
+    let service = app.into_make_service_with_connect_info::<SocketAddr>();
+    if let Err(e) = axum::serve(listener, service).await {
         tracing::error!("Server error: {}", e);
         std::process::exit(1);
     }
 }
 
+fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/ws", get(websocket_handler))
+        .route("/api/peers", get(get_peers))
+        .route("/api/stats", get(get_stats))
+        .route("/api/registry/hall_of_fame", get(get_hall_of_fame))
+        .route(
+            "/api/registry/genomes",
+            get(get_genomes).post(submit_genome),
+        )
+        .route("/api/registry/genomes/similar", post(find_similar_genomes))
+        .route(
+            "/api/registry/genomes/:id/similar",
+            get(get_similar_genomes),
+        )
+        .route("/api/registry/seeds", get(get_seeds).post(submit_seed))
+        .route("/api/registry/seeds/:id/thumbnail", get(get_seed_thumbnail))
+        .route("/api/registry/submissions/:id", get(get_submission_state))
+        .route(
+            "/api/registry/scenarios",
+            get(get_scenarios).post(publish_scenario),
+        )
+        .route("/api/registry/scenarios/:id", get(get_scenario))
+        .route(
+            "/api/registry/scenarios/:id/leaderboard",
+            get(get_leaderboard),
+        )
+        .route(
+            "/api/registry/scenarios/:id/results",
+            post(submit_scenario_result),
+        )
+        .merge(admin::routes())
+        .merge(metrics::routes())
+        .merge(spectate::routes())
+        .with_state(state)
+}
+
 /// REST endpoint: Get list of connected peers
 async fn get_peers(State(state): State<Arc<AppState>>) -> Json<Vec<PeerInfo>> {
     match state.peers.lock() {
@@ -129,69 +399,39 @@ async fn get_stats(State(state): State<Arc<AppState>>) -> Json<serde_json::Value
         }
     };
 
-    let total_migrations = match state.total_migrations.lock() {
-        Ok(migrations) => *migrations,
-        Err(e) => {
-            tracing::error!("Failed to lock migrations mutex: {}", e);
-            0
-        }
-    };
-
     Json(serde_json::json!({
         "online_count": online_count,
-        "total_migrations": total_migrations,
+        "total_migrations": state.total_migrations(),
         "peers": peers_data
     }))
 }
 
 /// REST endpoint: Get Hall of Fame (Global Registry)
 async fn get_hall_of_fame(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let rx = match state.storage.query_hall_of_fame_async() {
-        Some(r) => r,
-        None => {
-            return Json(serde_json::json!({
-                "error": "failed to query storage"
-            }))
-            .into_response();
-        }
-    };
-
-    match rx.recv() {
-        Ok(hall_of_fame) => Json(serde_json::json!({
+    match state.query(|storage| storage.query_hall_of_fame_async()) {
+        Some(hall_of_fame) => Json(serde_json::json!({
             "hall_of_fame": hall_of_fame.iter().map(|(id, civ_level, is_extinct)| serde_json::json!({
                 "id": id.to_string(),
                 "civilization_level": civ_level,
                 "is_extinct": is_extinct
             })).collect::<Vec<_>>()
         })).into_response(),
-        Err(e) => Json(serde_json::json!({
-            "error": format!("failed to receive hall of fame: {}", e)
+        None => Json(serde_json::json!({
+            "error": "failed to query storage"
         })).into_response(),
     }
 }
 
 /// REST endpoint: Get genomes from marketplace
 async fn get_genomes(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let rx = match state
-        .storage
-        .query_genomes_async(Some(100), Some("fitness".to_string()))
+    match state.query(|storage| storage.query_genomes_async(Some(100), Some("fitness".to_string())))
     {
-        Some(r) => r,
-        None => {
-            return Json(serde_json::json!({
-                "error": "failed to query genomes"
-            }))
-            .into_response();
-        }
-    };
-
-    match rx.recv() {
-        Ok(genomes) => Json(serde_json::json!({
+        Some(genomes) => Json(serde_json::json!({
             "genomes": genomes
         }))
         .into_response(),
-        Err(e) => Json(serde_json::json!({
-            "error": format!("failed to receive genomes: {}", e)
+        None => Json(serde_json::json!({
+            "error": "failed to query genomes"
         }))
         .into_response(),
     }
@@ -202,16 +442,7 @@ async fn get_genomes(State(state): State<Arc<AppState>>) -> impl IntoResponse {
 fn check_auth(state: &AppState, headers: &HeaderMap) -> Option<axum::response::Response> {
     let expected = state.api_key.as_ref()?;
 
-    let auth_header = headers
-        .get("authorization")
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("");
-
-    let token = auth_header
-        .strip_prefix("Bearer ")
-        .or_else(|| auth_header.strip_prefix("bearer "));
-
-    match token {
+    match bearer_token(headers) {
         Some(t) if t == expected => None,
         _ => {
             tracing::warn!("Rejected write request: invalid or missing API key");
@@ -226,6 +457,22 @@ fn check_auth(state: &AppState, headers: &HeaderMap) -> Option<axum::response::R
     }
 }
 
+/// The token of a `Bearer` Authorization header.
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let auth_header = headers.get("authorization")?.to_str().ok()?;
+    auth_header
+        .strip_prefix("Bearer ")
+        .or_else(|| auth_header.strip_prefix("bearer "))
+}
+
+/// The run manifest a client attached to a submission; malformed ones are
+/// dropped rather than rejecting the submission.
+fn provenance_of(payload: &serde_json::Value) -> Option<primordium_data::RunManifest> {
+    payload
+        .get("provenance")
+        .and_then(|p| serde_json::from_value(p.clone()).ok())
+}
+
 /// REST endpoint: Submit genome to marketplace
 async fn submit_genome(
     State(state): State<Arc<AppState>>,
@@ -271,52 +518,302 @@ async fn submit_genome(
         .unwrap_or(0) as u32;
     let tick = payload.get("tick").and_then(|v| v.as_u64()).unwrap_or(0);
 
-    state.storage.submit_genome(GenomeSubmit {
-        id,
-        lineage_id: None,
-        genotype,
-        author,
-        name,
-        description,
-        tags,
-        fitness_score,
-        offspring_count,
-        tick,
+    let outcome = state.query(|storage| {
+        storage.submit_genome_async(GenomeSubmit {
+            id,
+            lineage_id: None,
+            genotype,
+            author,
+            name,
+            description,
+            tags,
+            fitness_score,
+            offspring_count,
+            tick,
+            status: state.initial_status(),
+            thresholds: state.similarity,
+            provenance: provenance_of(&payload),
+        })
     });
 
-    Json(serde_json::json!({
-        "success": true,
-        "id": id.to_string()
-    }))
-    .into_response()
+    match outcome {
+        Some(GenomeSubmitOutcome::Stored { id, duplicate_of }) => Json(serde_json::json!({
+            "success": true,
+            "id": id.to_string(),
+            "status": state.initial_status(),
+            "duplicate_of": duplicate_of
+        }))
+        .into_response(),
+        // The submitter is pointed at the copy that was already there.
+        Some(GenomeSubmitOutcome::Merged { into, status }) => Json(serde_json::json!({
+            "success": true,
+            "id": into.id.to_string(),
+            "status": status,
+            "merged": true,
+            "similarity": into.similarity
+        }))
+        .into_response(),
+        None => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": "failed to store genome" })),
+        )
+            .into_response(),
+    }
 }
 
-/// REST endpoint: Get seeds from marketplace
-async fn get_seeds(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let rx = match state
-        .storage
-        .query_seeds_async(Some(100), Some("pop".to_string()))
-    {
-        Some(r) => r,
-        None => {
-            return Json(serde_json::json!({
-                "error": "failed to query seeds"
-            }))
+/// Similarity below which genomes are not reported as similar, by default.
+const DEFAULT_MIN_SIMILARITY: f32 = 0.8;
+
+/// Most similar genomes returned by one query.
+const MAX_SIMILAR_GENOMES: usize = 100;
+
+#[derive(serde::Deserialize)]
+struct SimilarParams {
+    genotype: Option<String>,
+    limit: Option<usize>,
+    min_similarity: Option<f32>,
+}
+
+/// REST endpoint: Approved genomes similar to a stored one
+async fn get_similar_genomes(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SimilarParams>,
+) -> impl IntoResponse {
+    similar_genomes(&state, SimilarityQuery::Submission(id), &params)
+}
+
+/// REST endpoint: Approved genomes similar to a posted genotype
+async fn find_similar_genomes(
+    State(state): State<Arc<AppState>>,
+    Json(params): Json<SimilarParams>,
+) -> impl IntoResponse {
+    let Some(genotype) = params.genotype.clone() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": "missing genotype" })),
+        )
             .into_response();
+    };
+    similar_genomes(&state, SimilarityQuery::Genotype(genotype), &params)
+}
+
+fn similar_genomes(
+    state: &AppState,
+    query: SimilarityQuery,
+    params: &SimilarParams,
+) -> axum::response::Response {
+    let limit = params.limit.unwrap_or(10).min(MAX_SIMILAR_GENOMES);
+    let min_similarity = params.min_similarity.unwrap_or(DEFAULT_MIN_SIMILARITY);
+    let result =
+        state.query(|storage| storage.find_similar_genomes_async(query, min_similarity, limit));
+    let (status, message) = match result {
+        Some(Ok(genomes)) => {
+            return Json(serde_json::json!({ "genomes": genomes })).into_response()
+        }
+        Some(Err(e @ SimilarityError::NotFound)) => (StatusCode::NOT_FOUND, e.to_string()),
+        Some(Err(e @ SimilarityError::InvalidGenotype(_))) => {
+            (StatusCode::BAD_REQUEST, e.to_string())
         }
+        Some(Err(e @ SimilarityError::Storage(_))) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        }
+        None => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to query storage".to_string(),
+        ),
     };
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
 
-    match rx.recv() {
-        Ok(seeds) => Json(serde_json::json!({
+/// REST endpoint: Get seeds from marketplace
+async fn get_seeds(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    match state.query(|storage| storage.query_seeds_async(Some(100), Some("pop".to_string()))) {
+        Some(seeds) => Json(serde_json::json!({
             "seeds": seeds
         }))
         .into_response(),
-        Err(e) => Json(serde_json::json!({
-            "error": format!("failed to receive seeds: {}", e)
+        None => Json(serde_json::json!({
+            "error": "failed to query seeds"
+        }))
+        .into_response(),
+    }
+}
+/// REST endpoint: Moderation state of a genome or seed submission, so its
+/// submitter can see whether it was approved
+async fn get_submission_state(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.query(|storage| storage.query_submission_state_async(id)) {
+        Some(Some(submission)) => Json(submission).into_response(),
+        Some(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "no submission with that id" })),
+        )
+            .into_response(),
+        None => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": "failed to query storage" })),
+        )
+            .into_response(),
+    }
+}
+
+fn json_error(status: StatusCode, message: impl Into<String>) -> axum::response::Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+/// REST endpoint: Published challenge scenarios
+async fn get_scenarios(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    match state.query(|storage| storage.query_scenarios_async(Some(100))) {
+        Some(scenarios) => Json(serde_json::json!({ "scenarios": scenarios })).into_response(),
+        None => json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to query scenarios",
+        ),
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct PublishScenario {
+    scenario: String,
+    author: Option<String>,
+}
+
+/// REST endpoint: Publish a challenge scenario (TOML)
+async fn publish_scenario(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(payload): Json<PublishScenario>,
+) -> impl IntoResponse {
+    if let Some(resp) = check_auth(&state, &headers) {
+        return resp;
+    }
+    let challenge = match Challenge::from_toml(&payload.scenario) {
+        Ok(c) => c,
+        Err(e) => return json_error(StatusCode::BAD_REQUEST, format!("invalid scenario: {e}")),
+    };
+    let id = Uuid::new_v4();
+    state.storage.publish_scenario(ScenarioSubmit {
+        id,
+        author: payload.author.unwrap_or_else(|| "anonymous".to_string()),
+        name: challenge.title,
+        description: challenge.description,
+        scenario_toml: payload.scenario,
+        ticks: challenge.ticks,
+        score_metric: challenge.score,
+        targets: challenge.targets,
+    });
+    Json(serde_json::json!({ "success": true, "id": id.to_string() })).into_response()
+}
+
+/// REST endpoint: Download one challenge scenario
+async fn get_scenario(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.query(|storage| storage.query_scenario_async(id, true)) {
+        Some(Some(scenario)) => Json(scenario).into_response(),
+        Some(None) => json_error(StatusCode::NOT_FOUND, "no scenario with that id"),
+        None => json_error(StatusCode::INTERNAL_SERVER_ERROR, "failed to query storage"),
+    }
+}
+
+/// REST endpoint: Best runs of a challenge scenario
+async fn get_leaderboard(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.query(|storage| storage.query_leaderboard_async(id, 50)) {
+        Some(leaderboard) => {
+            Json(serde_json::json!({ "leaderboard": leaderboard })).into_response()
+        }
+        None => json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to query leaderboard",
+        ),
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct SubmitScenarioResult {
+    player: String,
+    result: ChallengeResult,
+}
+
+/// REST endpoint: Submit a run of a challenge scenario. The score and
+/// pass/fail are recomputed from the reported metrics, and the signature
+/// must match the scenario as published.
+async fn submit_scenario_result(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<SubmitScenarioResult>,
+) -> impl IntoResponse {
+    if let Some(resp) = check_auth(&state, &headers) {
+        return resp;
+    }
+    let scenario = match state.query(|storage| storage.query_scenario_async(id, false)) {
+        Some(Some(scenario)) => scenario,
+        Some(None) => return json_error(StatusCode::NOT_FOUND, "no scenario with that id"),
+        None => return json_error(StatusCode::INTERNAL_SERVER_ERROR, "failed to query storage"),
+    };
+    let challenge = match Challenge::from_toml(&scenario.scenario_toml) {
+        Ok(c) => c,
+        Err(e) => return json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    };
+    let result = payload.result;
+    let expected = result_signature(&scenario.scenario_toml, &result.world_hash, &result.metrics);
+    if result.signature != expected || result.ticks > challenge.ticks {
+        tracing::warn!(
+            "Rejected result for scenario {} from {}",
+            id,
+            payload.player
+        );
+        return json_error(
+            StatusCode::BAD_REQUEST,
+            "result does not match the published scenario",
+        );
+    }
+    let evaluation = challenge.evaluate(&result.metrics);
+    let rank = state.query(|storage| {
+        storage.submit_scenario_result_async(ScenarioResultSubmit {
+            scenario_id: id,
+            player: payload.player,
+            score: evaluation.score,
+            passed: evaluation.passed,
+            ticks: result.ticks,
+            world_hash: result.world_hash,
+            signature: result.signature,
+            provenance: result.provenance,
+        })
+    });
+    match rank {
+        Some(Ok(rank)) => Json(serde_json::json!({
+            "rank": rank,
+            "score": evaluation.score,
+            "passed": evaluation.passed
         }))
         .into_response(),
+        Some(Err(e @ ScenarioError::NotFound)) => json_error(StatusCode::NOT_FOUND, e.to_string()),
+        Some(Err(e)) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        None => json_error(StatusCode::INTERNAL_SERVER_ERROR, "failed to query storage"),
     }
 }
+
+/// REST endpoint: Preview PNG of an approved seed
+async fn get_seed_thumbnail(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.query(|storage| storage.query_seed_thumbnail_async(id)) {
+        Some(Some(png)) => ([(axum::http::header::CONTENT_TYPE, "image/png")], png).into_response(),
+        Some(None) => json_error(StatusCode::NOT_FOUND, "no thumbnail for that seed"),
+        None => json_error(StatusCode::INTERNAL_SERVER_ERROR, "failed to query storage"),
+    }
+}
+
 /// REST endpoint: Submit seed to marketplace
 async fn submit_seed(
     State(state): State<Arc<AppState>>,
@@ -362,6 +859,18 @@ async fn submit_seed(
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_string();
+    let thumbnail = match payload.get("thumbnail").and_then(|v| v.as_str()) {
+        Some(encoded) => match hex::decode(encoded)
+            .map_err(anyhow::Error::from)
+            .and_then(|png| Thumbnail::from_png(&png).map(|_| png))
+        {
+            Ok(png) => Some(png),
+            Err(e) => {
+                return json_error(StatusCode::BAD_REQUEST, format!("invalid thumbnail: {e}"))
+            }
+        },
+        None => None,
+    };
 
     state.storage.submit_seed(SeedSubmit {
         id,
@@ -373,201 +882,334 @@ async fn submit_seed(
         avg_tick_time,
         max_pop,
         performance_summary,
+        thumbnail,
+        status: state.initial_status(),
+        provenance: provenance_of(&payload),
     });
 
     Json(serde_json::json!({
         "success": true,
-        "id": id.to_string()
+        "id": id.to_string(),
+        "status": state.initial_status()
     }))
     .into_response()
 }
+#[derive(serde::Deserialize)]
+struct WsParams {
+    compression: Option<String>,
+}
+
 async fn websocket_handler(
     ws: WebSocketUpgrade,
+    Query(params): Query<WsParams>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
     State(state): State<Arc<AppState>>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| websocket(socket, state))
+    if is_banned(&state, remote.ip()) {
+        tracing::info!("Refused connection from banned address {}", remote.ip());
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let compress = params.compression.as_deref() == Some("deflate");
+    ws.on_upgrade(move |socket| websocket(socket, state, remote.ip(), compress))
+}
+
+fn is_banned(state: &AppState, addr: IpAddr) -> bool {
+    state
+        .bans
+        .lock()
+        .map(|bans| bans.contains_key(&addr))
+        .unwrap_or(false)
+}
+
+/// A frame carrying `text`, deflated if the peer asked for compression and
+/// the message is large enough to benefit.
+fn encode_frame(text: String, compress: bool) -> Message {
+    if compress && codec::should_compress(&text) {
+        Message::Binary(codec::compress(&text))
+    } else {
+        Message::Text(text)
+    }
+}
+
+fn broadcast(state: &AppState, msg: &NetMessage) {
+    if let Ok(msg_str) = serde_json::to_string(msg) {
+        relay(state, msg_str);
+    }
+}
+
+/// Sends an already serialized message to the whole room.
+fn relay(state: &AppState, text: String) {
+    state.metrics.increment_counter(metrics::MESSAGES);
+    let _ = state.tx.send(text);
+}
+
+fn peer_list(state: &AppState) -> Option<NetMessage> {
+    match state.peers.lock() {
+        Ok(peers) => Some(NetMessage::PeerList {
+            peers: peers.values().cloned().collect(),
+        }),
+        Err(e) => {
+            tracing::error!("Failed to lock peers mutex: {}", e);
+            None
+        }
+    }
 }
 
-async fn websocket(stream: WebSocket, state: Arc<AppState>) {
+async fn websocket(stream: WebSocket, state: Arc<AppState>, addr: IpAddr, compress: bool) {
     let (mut sender, mut receiver) = stream.split();
     let client_id = Uuid::new_v4();
+    let kick = Arc::new(Notify::new());
+    // A v4 UUID holds 122 bits from the OS random generator.
+    let publish_secret = Uuid::new_v4().simple().to_string();
+    if let Ok(mut connections) = state.connections.lock() {
+        connections.insert(
+            client_id,
+            Connection {
+                addr,
+                connected_at: Instant::now(),
+                last_seen: Instant::now(),
+                compression: compress,
+                publish_secret: publish_secret.clone(),
+                kick: kick.clone(),
+            },
+        );
+    }
 
-    // Initialize peer info and get initial peer list message
-    let initial_peer_list_msg = {
-        match state.peers.lock() {
-            Ok(mut peers) => {
-                peers.insert(
-                    client_id,
-                    PeerInfo {
-                        peer_id: client_id,
-                        entity_count: 0,
-                        migrations_sent: 0,
-                        migrations_received: 0,
-                    },
-                );
-                tracing::info!(
-                    "Client connected: {}. Total peers: {}",
-                    client_id,
-                    peers.len()
-                );
-                // Create peer list message
-                let peer_list = NetMessage::PeerList {
-                    peers: peers.values().cloned().collect(),
-                };
-                serde_json::to_string(&peer_list).ok()
-            }
-            Err(e) => {
-                tracing::error!("Failed to lock peers mutex: {}", e);
-                None
-            }
+    match state.peers.lock() {
+        Ok(mut peers) => {
+            peers.insert(
+                client_id,
+                PeerInfo {
+                    peer_id: client_id,
+                    entity_count: 0,
+                    migrations_sent: 0,
+                    migrations_received: 0,
+                },
+            );
+            tracing::info!(
+                "Client connected: {} (compression: {}). Total peers: {}",
+                client_id,
+                compress,
+                peers.len()
+            );
         }
-    };
-
-    // Send Handshake with client ID
-    let handshake = NetMessage::Handshake { client_id };
-    if let Ok(msg_str) = serde_json::to_string(&handshake) {
-        let _ = sender.send(Message::Text(msg_str)).await;
+        Err(e) => tracing::error!("Failed to lock peers mutex: {}", e),
     }
 
-    // Send initial peer list
-    if let Some(peer_list_msg) = initial_peer_list_msg {
-        let _ = sender.send(Message::Text(peer_list_msg)).await;
+    // Send Handshake with client ID, then the initial peer list
+    let handshake = NetMessage::Handshake {
+        client_id,
+        publish_secret,
+    };
+    let greeting = std::iter::once(handshake).chain(peer_list(&state));
+    for msg in greeting {
+        if let Ok(msg_str) = serde_json::to_string(&msg) {
+            let _ = sender.send(encode_frame(msg_str, compress)).await;
+        }
     }
 
-    // Subscribe to broadcast channel
+    // Forward room broadcasts and this connection's own frames (pings) to the client
     let mut rx = state.tx.subscribe();
-
-    // Spawn task to forward broadcasts to this client
+    let (direct_tx, mut direct_rx) = mpsc::unbounded_channel::<Message>();
     let send_task = tokio::spawn(async move {
-        while let Ok(msg) = rx.recv().await {
-            if sender.send(Message::Text(msg)).await.is_err() {
+        loop {
+            let frame = tokio::select! {
+                msg = rx.recv() => match msg {
+                    Ok(text) => encode_frame(text, compress),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                Some(frame) = direct_rx.recv() => frame,
+                else => break,
+            };
+            if sender.send(frame).await.is_err() {
                 break;
             }
         }
     });
 
-    let tx = state.tx.clone();
-    let peers_clone = state.peers.clone();
-    let total_migrations_clone = state.total_migrations.clone();
-    let active_trades_clone = state.active_trades.clone();
-    let id_clone = client_id;
-
-    // Maximum message size: 100KB to prevent DoS
-    const MAX_MESSAGE_SIZE: usize = 100 * 1024;
-
-    // Process incoming messages
-    while let Some(Ok(result)) = receiver.next().await {
-        if let Message::Text(text) = result {
-            // Check message size to prevent memory exhaustion
-            if text.len() > MAX_MESSAGE_SIZE {
-                tracing::warn!(
-                    "Client {} sent oversized message: {} bytes (max: {})",
-                    client_id,
-                    text.len(),
-                    MAX_MESSAGE_SIZE
-                );
-                continue;
-            }
+    let mut heartbeat = tokio::time::interval(state.heartbeat.interval);
+    heartbeat.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last_seen = Instant::now();
+    let mut limiter = PeerLimiter::new(&state.rate_limits);
 
-            if let Ok(msg) = serde_json::from_str::<NetMessage>(&text) {
-                match msg {
-                    NetMessage::MigrateEntity { .. } => {
-                        // Update migration stats
-                        {
-                            if let Ok(mut peers) = peers_clone.lock() {
-                                if let Some(peer) = peers.get_mut(&id_clone) {
-                                    peer.migrations_sent += 1;
-                                }
-                            } else {
-                                tracing::warn!("Failed to lock peers mutex for migration stats");
-                            }
-                            if let Ok(mut total) = total_migrations_clone.lock() {
-                                *total += 1;
-                            } else {
-                                tracing::warn!("Failed to lock migrations mutex");
-                            }
-                        }
-                        tracing::info!("Relaying migration from {}", id_clone);
-                        let _ = tx.send(text);
+    // Process incoming messages until the peer closes or goes silent
+    let reason = loop {
+        tokio::select! {
+            frame = receiver.next() => {
+                let Some(Ok(frame)) = frame else {
+                    break "closed";
+                };
+                last_seen = Instant::now();
+                if let Ok(mut connections) = state.connections.lock() {
+                    if let Some(connection) = connections.get_mut(&client_id) {
+                        connection.last_seen = last_seen;
                     }
-                    NetMessage::TradeOffer(proposal) => {
-                        if let Ok(mut trades) = active_trades_clone.lock() {
-                            trades.insert(proposal.id, Arc::new(proposal));
-                        } else {
-                            tracing::warn!("Failed to lock trades mutex for trade offer");
+                }
+                let text = match frame {
+                    Message::Text(text) => text,
+                    Message::Binary(bytes) => match codec::decompress(&bytes, MAX_MESSAGE_SIZE) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            tracing::warn!("Client {} sent a bad compressed frame: {}", client_id, e);
+                            continue;
                         }
-                        tracing::info!("Relaying trade offer from {}", id_clone);
-                        let _ = tx.send(text);
-                    }
-                    NetMessage::TradeAccept { proposal_id, .. } => {
-                        let is_valid = if let Ok(mut trades) = active_trades_clone.lock() {
-                            trades.remove(&proposal_id).is_some()
-                        } else {
-                            tracing::warn!("Failed to lock trades mutex for trade acceptance");
-                            false
-                        };
-
-                        if is_valid {
-                            tracing::info!("Relaying valid trade acceptance for {}", proposal_id);
-                            let _ = tx.send(text);
-                        } else {
-                            tracing::warn!("Blocked double-acceptance for trade {}", proposal_id);
-                        }
-                    }
-                    NetMessage::TradeRevoke { proposal_id } => {
-                        if let Ok(mut trades) = active_trades_clone.lock() {
-                            trades.remove(&proposal_id);
-                        } else {
-                            tracing::warn!("Failed to lock trades mutex for trade revoke");
-                        }
-                        let _ = tx.send(text);
-                    }
-                    NetMessage::MigrateAck { .. } => {
-                        tracing::info!("Relaying migration ACK for {}", id_clone);
-                        let _ = tx.send(text);
-                    }
-                    NetMessage::PeerAnnounce {
-                        entity_count,
-                        migrations_sent,
-                        migrations_received,
-                    } => {
-                        // Update peer info and broadcast
-                        let peer_list_msg = if let Ok(mut peers) = peers_clone.lock() {
-                            if let Some(peer) = peers.get_mut(&id_clone) {
-                                peer.entity_count = entity_count;
-                                peer.migrations_sent = migrations_sent;
-                                peer.migrations_received = migrations_received;
-                            }
-                            tracing::debug!(
-                                "Peer {} announced: {} entities",
-                                id_clone,
-                                entity_count
-                            );
-                            let peer_list = NetMessage::PeerList {
-                                peers: peers.values().cloned().collect(),
-                            };
-                            serde_json::to_string(&peer_list).ok()
-                        } else {
-                            tracing::warn!("Failed to lock peers mutex for PeerAnnounce");
-                            None
-                        };
-                        if let Some(msg_str) = peer_list_msg {
-                            let _ = tx.send(msg_str);
-                        }
-                    }
-                    _ => {}
+                    },
+                    Message::Close(_) => break "closed",
+                    Message::Ping(_) | Message::Pong(_) => continue,
+                };
+                let reply = handle_message(&state, client_id, addr, &mut limiter, text);
+                if let Some(msg_str) = reply.and_then(|r| serde_json::to_string(&r).ok()) {
+                    let _ = direct_tx.send(encode_frame(msg_str, compress));
                 }
             }
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() > state.heartbeat.timeout {
+                    tracing::warn!(
+                        "Client {} silent for {:?}, dropping",
+                        client_id,
+                        last_seen.elapsed()
+                    );
+                    break "timed out";
+                }
+                if direct_tx.send(Message::Ping(Vec::new())).is_err() {
+                    break "closed";
+                }
+            }
+            _ = kick.notified() => break "kicked",
         }
-    }
+    };
 
     // Cleanup on disconnect
     send_task.abort();
+    disconnect(&state, client_id, reason);
+}
+
+/// Acts on one message from a peer; returns a reply for that peer alone.
+fn handle_message(
+    state: &AppState,
+    client_id: Uuid,
+    addr: IpAddr,
+    limiter: &mut PeerLimiter,
+    text: String,
+) -> Option<NetMessage> {
+    // Check message size to prevent memory exhaustion
+    if text.len() > MAX_MESSAGE_SIZE {
+        tracing::warn!(
+            "Client {} sent oversized message: {} bytes (max: {})",
+            client_id,
+            text.len(),
+            MAX_MESSAGE_SIZE
+        );
+        return None;
+    }
+
+    let msg = serde_json::from_str::<NetMessage>(&text).ok()?;
+    let migration_id = match &msg {
+        NetMessage::MigrateEntity { migration_id, .. } => Some(*migration_id),
+        _ => None,
+    };
+    if let Err(reply) = limiter.admit(migration_id) {
+        state.metrics.increment_counter(metrics::RATE_LIMITED);
+        tracing::debug!("Dropped a message from {} over its rate limit", client_id);
+        return reply;
+    }
+    match msg {
+        NetMessage::MigrateEntity { migration_id, .. } => {
+            if let Err(reply) = limits::take_migration(state, addr, migration_id) {
+                tracing::info!(
+                    "Refused a migration from {} over its daily quota",
+                    client_id
+                );
+                return Some(reply);
+            }
+            // Update migration stats
+            if let Ok(mut peers) = state.peers.lock() {
+                if let Some(peer) = peers.get_mut(&client_id) {
+                    peer.migrations_sent += 1;
+                }
+            } else {
+                tracing::warn!("Failed to lock peers mutex for migration stats");
+            }
+            state.metrics.increment_counter(metrics::MIGRATIONS);
+            tracing::info!("Relaying migration from {}", client_id);
+            relay(state, text);
+        }
+        NetMessage::TradeOffer(proposal) => {
+            if let Ok(mut trades) = state.active_trades.lock() {
+                trades.insert(
+                    proposal.id,
+                    ActiveTrade {
+                        proposal: Arc::new(proposal),
+                        posted_at: Instant::now(),
+                    },
+                );
+            } else {
+                tracing::warn!("Failed to lock trades mutex for trade offer");
+            }
+            tracing::info!("Relaying trade offer from {}", client_id);
+            relay(state, text);
+        }
+        NetMessage::TradeAccept { proposal_id, .. } => {
+            let is_valid = if let Ok(mut trades) = state.active_trades.lock() {
+                trades.remove(&proposal_id).is_some()
+            } else {
+                tracing::warn!("Failed to lock trades mutex for trade acceptance");
+                false
+            };
+
+            if is_valid {
+                tracing::info!("Relaying valid trade acceptance for {}", proposal_id);
+                relay(state, text);
+            } else {
+                tracing::warn!("Blocked double-acceptance for trade {}", proposal_id);
+            }
+        }
+        NetMessage::TradeRevoke { proposal_id } => {
+            if let Ok(mut trades) = state.active_trades.lock() {
+                trades.remove(&proposal_id);
+            } else {
+                tracing::warn!("Failed to lock trades mutex for trade revoke");
+            }
+            relay(state, text);
+        }
+        NetMessage::MigrateAck { .. } => {
+            tracing::info!("Relaying migration ACK for {}", client_id);
+            relay(state, text);
+        }
+        NetMessage::PeerAnnounce {
+            entity_count,
+            migrations_sent,
+            migrations_received,
+        } => {
+            // Update peer info and broadcast
+            if let Ok(mut peers) = state.peers.lock() {
+                if let Some(peer) = peers.get_mut(&client_id) {
+                    peer.entity_count = entity_count;
+                    peer.migrations_sent = migrations_sent;
+                    peer.migrations_received = migrations_received;
+                }
+                tracing::debug!("Peer {} announced: {} entities", client_id, entity_count);
+            } else {
+                tracing::warn!("Failed to lock peers mutex for PeerAnnounce");
+            }
+            if let Some(list) = peer_list(state) {
+                broadcast(state, &list);
+            }
+        }
+        _ => {}
+    }
+    None
+}
 
+/// Forgets a peer and tells the room: its trade offers are revoked and its
+/// spectator stream closed, then the drop, the new peer list and fresh stats
+/// are broadcast.
+fn disconnect(state: &AppState, client_id: Uuid, reason: &str) {
     let revoked_ids = if let Ok(mut trades) = state.active_trades.lock() {
         let to_remove: Vec<Uuid> = trades
             .iter()
-            .filter(|(_, p)| p.sender_id == client_id)
+            .filter(|(_, t)| t.proposal.sender_id == client_id)
             .map(|(id, _)| *id)
             .collect();
 
@@ -581,30 +1223,45 @@ async fn websocket(stream: WebSocket, state: Arc<AppState>) {
     };
 
     for id in revoked_ids {
-        let revoke = NetMessage::TradeRevoke { proposal_id: id };
-        if let Ok(msg_str) = serde_json::to_string(&revoke) {
-            let _ = tx.send(msg_str);
-        }
+        broadcast(state, &NetMessage::TradeRevoke { proposal_id: id });
+    }
+    if let Ok(mut connections) = state.connections.lock() {
+        connections.remove(&client_id);
     }
+    spectate::close(state, client_id);
 
-    let disconnect_peer_list_msg = if let Ok(mut peers) = peers_clone.lock() {
-        peers.remove(&id_clone);
+    let online_count = if let Ok(mut peers) = state.peers.lock() {
+        peers.remove(&client_id);
         tracing::info!(
-            "Client disconnected: {}. Total peers: {}",
-            id_clone,
+            "Client disconnected ({}): {}. Total peers: {}",
+            reason,
+            client_id,
             peers.len()
         );
-        let peer_list = NetMessage::PeerList {
-            peers: peers.values().cloned().collect(),
-        };
-        serde_json::to_string(&peer_list).ok()
+        peers.len()
     } else {
         tracing::warn!("Failed to lock peers mutex during disconnect");
-        None
+        return;
     };
-    if let Some(msg_str) = disconnect_peer_list_msg {
-        let _ = tx.send(msg_str);
+    let total_migrations = state.total_migrations();
+
+    broadcast(
+        state,
+        &NetMessage::PeerDropped {
+            peer_id: client_id,
+            reason: reason.to_string(),
+        },
+    );
+    if let Some(list) = peer_list(state) {
+        broadcast(state, &list);
     }
+    broadcast(
+        state,
+        &NetMessage::StatsUpdate {
+            online_count,
+            total_migrations,
+        },
+    );
 }
 
 #[cfg(test)]
@@ -614,19 +1271,11 @@ mod tests {
     use tower::util::ServiceExt;
 
     fn create_app() -> Router {
-        let (tx, _rx) = broadcast::channel::<String>(100);
         let storage = StorageManager::new(":memory:").unwrap_or_else(|e| {
             eprintln!("Failed to create in-memory storage: {}", e);
             std::process::exit(1);
         });
-        let app_state = Arc::new(AppState {
-            tx,
-            peers: Arc::new(Mutex::new(HashMap::new())),
-            total_migrations: Arc::new(Mutex::new(0)),
-            active_trades: Arc::new(Mutex::new(HashMap::new())),
-            storage,
-            api_key: None,
-        });
+        let app_state = Arc::new(AppState::new(storage));
         Router::new()
             .route("/api/peers", get(get_peers))
             .route("/api/stats", get(get_stats))
@@ -676,18 +1325,13 @@ mod tests {
     }
 
     fn create_app_with_auth(key: &str) -> Router {
-        let (tx, _rx) = broadcast::channel::<String>(100);
         let storage = StorageManager::new(":memory:").unwrap_or_else(|e| {
             eprintln!("Failed to create in-memory storage: {}", e);
             std::process::exit(1);
         });
         let app_state = Arc::new(AppState {
-            tx,
-            peers: Arc::new(Mutex::new(HashMap::new())),
-            total_migrations: Arc::new(Mutex::new(0)),
-            active_trades: Arc::new(Mutex::new(HashMap::new())),
-            storage,
             api_key: Some(key.to_string()),
+            ..AppState::new(storage)
         });
         Router::new()
             .route(
@@ -793,4 +1437,918 @@ mod tests {
         // GET endpoints remain public even when auth is configured
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    async fn serve(state: AppState) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let service = router(Arc::new(state)).into_make_service_with_connect_info::<SocketAddr>();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, service).await;
+        });
+        addr
+    }
+
+    fn memory_state() -> AppState {
+        AppState::new(StorageManager::new(":memory:").expect("in-memory storage"))
+    }
+
+    #[tokio::test]
+    async fn test_websocket_compression_and_dead_peer_detection() {
+        use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+        let addr = serve(AppState {
+            heartbeat: HeartbeatConfig {
+                interval: Duration::from_millis(50),
+                timeout: Duration::from_millis(200),
+            },
+            ..memory_state()
+        })
+        .await;
+        let url = format!("ws://{addr}/ws");
+
+        let (mut live, _) = connect_async(codec::with_compression(&url)).await.unwrap();
+        // Never read from, so it never answers pings.
+        let (_silent, _) = connect_async(url.as_str()).await.unwrap();
+
+        let migration = NetMessage::migrate_entity(
+            Uuid::new_v4(),
+            "AB".repeat(2048),
+            10.0,
+            1,
+            "Wanderer".to_string(),
+            "fp".to_string(),
+        );
+        let text = serde_json::to_string(&migration).unwrap();
+        live.send(WsMessage::Binary(codec::compress(&text)))
+            .await
+            .unwrap();
+
+        let mut relayed_compressed = false;
+        let mut dropped = None;
+        let mut peers_after_drop = None;
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        // Keep reading (and so answering pings) until the peer list that
+        // follows the drop arrives.
+        while peers_after_drop.is_none() && tokio::time::Instant::now() < deadline {
+            let frame = tokio::time::timeout(Duration::from_secs(1), live.next())
+                .await
+                .ok()
+                .flatten();
+            let text = match frame {
+                Some(Ok(WsMessage::Text(text))) => text,
+                Some(Ok(WsMessage::Binary(bytes))) => {
+                    let text = codec::decompress(&bytes, MAX_MESSAGE_SIZE).unwrap();
+                    if serde_json::from_str::<NetMessage>(&text).ok() == Some(migration.clone()) {
+                        relayed_compressed = true;
+                    }
+                    text
+                }
+                _ => continue,
+            };
+            match serde_json::from_str(&text) {
+                Ok(NetMessage::PeerDropped { peer_id, reason }) => {
+                    dropped = Some((peer_id, reason))
+                }
+                Ok(NetMessage::PeerList { peers }) if dropped.is_some() => {
+                    peers_after_drop = Some(peers);
+                }
+                _ => {}
+            }
+        }
+
+        assert!(relayed_compressed);
+        let (peer_id, reason) = dropped.expect("silent peer should be dropped");
+        assert_eq!(reason, "timed out");
+        let peers = peers_after_drop.expect("peer list after the drop");
+        assert_eq!(peers.len(), 1);
+        assert_ne!(peers[0].peer_id, peer_id);
+    }
+
+    #[tokio::test]
+    async fn test_spectators_watch_a_published_world_read_only() {
+        use primordium_net::spectate::{with_publish, FrameKind, SpectateFrame};
+        use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+        let addr = serve(memory_state()).await;
+        let (mut peer, _) = connect_async(format!("ws://{addr}/ws")).await.unwrap();
+        let (peer_id, secret) = loop {
+            if let Some(Ok(WsMessage::Text(text))) = peer.next().await {
+                if let Ok(NetMessage::Handshake {
+                    client_id,
+                    publish_secret,
+                }) = serde_json::from_str(&text)
+                {
+                    break (client_id, publish_secret);
+                }
+            }
+        };
+        let url = format!("ws://{addr}/ws/spectate/{peer_id}");
+
+        let unknown = format!("ws://{addr}/ws/spectate/{}", Uuid::new_v4());
+        assert!(connect_async(unknown).await.is_err());
+        // The peer's address alone does not make a publisher.
+        assert!(connect_async(format!("{url}?role=publish")).await.is_err());
+        assert!(connect_async(with_publish(&url, "guess")).await.is_err());
+
+        let (mut publisher, _) = connect_async(with_publish(&url, &secret)).await.unwrap();
+        // One publisher per peer.
+        assert!(connect_async(with_publish(&url, &secret)).await.is_err());
+        let (mut early, _) = connect_async(url.as_str()).await.unwrap();
+
+        let frames: Vec<Vec<u8>> = [
+            SpectateFrame::pack(FrameKind::Keyframe, 10, 10, b"world at 10"),
+            SpectateFrame::pack(FrameKind::Delta, 11, 10, b"changes to 11"),
+            SpectateFrame::pack(FrameKind::Delta, 12, 11, b"changes to 12"),
+        ]
+        .iter()
+        .map(SpectateFrame::encode)
+        .collect();
+        async fn next_binary(
+            socket: &mut tokio_tungstenite::WebSocketStream<
+                tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+            >,
+        ) -> Option<Vec<u8>> {
+            loop {
+                match tokio::time::timeout(Duration::from_secs(2), socket.next()).await {
+                    Ok(Some(Ok(WsMessage::Binary(bytes)))) => return Some(bytes),
+                    Ok(Some(Ok(WsMessage::Close(_)))) | Ok(None) | Ok(Some(Err(_))) | Err(_) => {
+                        return None
+                    }
+                    Ok(Some(Ok(_))) => continue,
+                }
+            }
+        }
+
+        for frame in &frames[..2] {
+            publisher
+                .send(WsMessage::Binary(frame.clone()))
+                .await
+                .unwrap();
+        }
+        // Malformed frames are not relayed.
+        publisher.send(WsMessage::Binary(vec![9; 4])).await.unwrap();
+        assert_eq!(next_binary(&mut early).await.as_ref(), Some(&frames[0]));
+        assert_eq!(next_binary(&mut early).await.as_ref(), Some(&frames[1]));
+
+        // A late spectator starts from the keyframe; what it sends is ignored.
+        let (mut late, _) = connect_async(url.as_str()).await.unwrap();
+        assert_eq!(next_binary(&mut late).await.as_ref(), Some(&frames[0]));
+        assert_eq!(next_binary(&mut late).await.as_ref(), Some(&frames[1]));
+        let bogus = SpectateFrame::pack(FrameKind::Keyframe, 99, 99, b"forged");
+        late.send(WsMessage::Binary(bogus.encode())).await.unwrap();
+
+        publisher
+            .send(WsMessage::Binary(frames[2].clone()))
+            .await
+            .unwrap();
+        for spectator in [&mut early, &mut late] {
+            assert_eq!(next_binary(spectator).await.as_ref(), Some(&frames[2]));
+        }
+
+        // The stream ends when the peer leaves the relay.
+        peer.close(None).await.unwrap();
+        assert_eq!(next_binary(&mut early).await, None);
+        assert_eq!(next_binary(&mut late).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_exports_relay_activity() {
+        use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+        let addr = serve(memory_state()).await;
+        let http = reqwest::Client::new();
+        let (mut peer, _) = connect_async(format!("ws://{addr}/ws")).await.unwrap();
+        let migration = NetMessage::migrate_entity(
+            Uuid::new_v4(),
+            "AB".repeat(64),
+            10.0,
+            1,
+            "Wanderer".to_string(),
+            "fp".to_string(),
+        );
+        peer.send(WsMessage::Text(serde_json::to_string(&migration).unwrap()))
+            .await
+            .unwrap();
+        http.get(format!("http://{addr}/api/registry/genomes"))
+            .send()
+            .await
+            .unwrap();
+
+        let scrape = || async {
+            let response = http
+                .get(format!("http://{addr}/metrics"))
+                .send()
+                .await
+                .unwrap();
+            assert!(response.headers()["content-type"]
+                .to_str()
+                .unwrap()
+                .starts_with("text/plain"));
+            response.text().await.unwrap()
+        };
+        let mut text = String::new();
+        for _ in 0..50 {
+            text = scrape().await;
+            if text.contains("primordium_relay_migrations_relayed_total 1\n") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(
+            text.contains("primordium_relay_migrations_relayed_total 1\n"),
+            "{text}"
+        );
+        assert!(text.contains("# TYPE primordium_relay_connected_peers gauge\n"));
+        assert!(text.contains("primordium_relay_connected_peers 1\n"));
+        assert!(text.contains("primordium_relay_active_trades 0\n"));
+        assert!(text.contains("# TYPE primordium_relay_storage_query_seconds histogram\n"));
+        assert!(text.contains("primordium_relay_storage_query_seconds_count 1\n"));
+
+        peer.close(None).await.unwrap();
+        for _ in 0..50 {
+            text = scrape().await;
+            if text.contains("primordium_relay_connected_peers 0\n") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(
+            text.contains("primordium_relay_connected_peers 0\n"),
+            "{text}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_peers_over_their_limits_are_told_to_back_off() {
+        use primordium_net::RateLimit;
+        use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+        let addr = serve(AppState {
+            rate_limits: RateLimitConfig {
+                messages_per_sec: 0.001,
+                burst: 4.0,
+                daily_migrations: 1,
+            },
+            ..memory_state()
+        })
+        .await;
+        let (mut peer, _) = connect_async(format!("ws://{addr}/ws")).await.unwrap();
+        let migrate = |id: Uuid| {
+            let msg = NetMessage::migrate_entity(
+                id,
+                "AB".repeat(64),
+                10.0,
+                1,
+                "Wanderer".to_string(),
+                "fp".to_string(),
+            );
+            WsMessage::Text(serde_json::to_string(&msg).unwrap())
+        };
+        let announce = WsMessage::Text(
+            serde_json::to_string(&NetMessage::PeerAnnounce {
+                entity_count: 1,
+                migrations_sent: 0,
+                migrations_received: 0,
+            })
+            .unwrap(),
+        );
+        async fn next_limit(
+            socket: &mut tokio_tungstenite::WebSocketStream<
+                tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+            >,
+        ) -> (RateLimit, Option<Uuid>, u64) {
+            loop {
+                let frame = tokio::time::timeout(Duration::from_secs(5), socket.next())
+                    .await
+                    .expect("no RateLimited reply")
+                    .unwrap()
+                    .unwrap();
+                if let WsMessage::Text(text) = frame {
+                    if let Ok(NetMessage::RateLimited {
+                        limit,
+                        migration_id,
+                        retry_after_ms,
+                    }) = serde_json::from_str(&text)
+                    {
+                        return (limit, migration_id, retry_after_ms);
+                    }
+                }
+            }
+        }
+
+        // The second migration of the day is refused; the first was relayed.
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        peer.send(migrate(first)).await.unwrap();
+        peer.send(migrate(second)).await.unwrap();
+        let (limit, migration_id, retry_after_ms) = next_limit(&mut peer).await;
+        assert_eq!(limit, RateLimit::DailyMigrations);
+        assert_eq!(migration_id, Some(second));
+        assert!(retry_after_ms <= 86_400_000);
+
+        // Two more messages use up the burst, and only the first drop of the
+        // run after that is answered.
+        for _ in 0..4 {
+            peer.send(announce.clone()).await.unwrap();
+        }
+        let (limit, migration_id, _) = next_limit(&mut peer).await;
+        assert_eq!(limit, RateLimit::Messages);
+        assert_eq!(migration_id, None);
+        peer.send(migrate(Uuid::new_v4())).await.unwrap();
+        let (limit, migration_id, _) = next_limit(&mut peer).await;
+        assert_eq!(limit, RateLimit::Messages);
+        assert!(migration_id.is_some());
+
+        let metrics = reqwest::get(format!("http://{addr}/metrics"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(metrics.contains("primordium_relay_migrations_relayed_total 1\n"));
+        assert!(metrics.contains("primordium_relay_migrations_over_quota_total 1\n"));
+        assert!(metrics.contains("primordium_relay_messages_rate_limited_total 3\n"));
+    }
+
+    fn admin_state(moderate: bool) -> AppState {
+        AppState {
+            admin_key: Some("admin-secret".to_string()),
+            moderate_submissions: moderate,
+            ..memory_state()
+        }
+    }
+
+    async fn admin_request(
+        app: Router,
+        method: &str,
+        uri: &str,
+        key: Option<&str>,
+    ) -> (StatusCode, serde_json::Value) {
+        admin_request_from(app, [127, 0, 0, 1], method, uri, key).await
+    }
+
+    async fn admin_request_from(
+        app: Router,
+        remote: [u8; 4],
+        method: &str,
+        uri: &str,
+        key: Option<&str>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .extension(ConnectInfo(SocketAddr::from((remote, 40_000))));
+        if let Some(key) = key {
+            request = request.header("authorization", format!("Bearer {key}"));
+        }
+        let response = app
+            .oneshot(request.body(axum::body::Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_admin_endpoints_require_admin_key() {
+        let disabled = router(Arc::new(memory_state()));
+        let (status, _) = admin_request(disabled, "GET", "/admin/metrics", Some("x")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let app = router(Arc::new(admin_state(false)));
+        let (status, _) = admin_request(app.clone(), "GET", "/admin/peers", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = admin_request(app.clone(), "GET", "/admin/peers", Some("wrong")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, metrics) =
+            admin_request(app, "GET", "/admin/metrics", Some("admin-secret")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(metrics["online_count"], 0);
+        assert_eq!(metrics["pending_submissions"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_admin_locks_out_addresses_guessing_the_key() {
+        let app = router(Arc::new(admin_state(false)));
+        let guesser = [10, 0, 0, 7];
+        for _ in 0..5 {
+            let (status, _) =
+                admin_request_from(app.clone(), guesser, "GET", "/admin/peers", Some("guess"))
+                    .await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+        // Even the right key is refused until the lockout passes.
+        let (status, body) = admin_request_from(
+            app.clone(),
+            guesser,
+            "GET",
+            "/admin/peers",
+            Some("admin-secret"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert!(body["error"].as_str().unwrap().contains("retry in"));
+
+        let (status, _) = admin_request(app, "GET", "/admin/peers", Some("admin-secret")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_admin_moderates_marketplace_submissions() {
+        let app = router(Arc::new(admin_state(true)));
+        let submit = |name: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/registry/genomes")
+                .header("content-type", "application/json")
+                .body(axum::body::Body::from(format!(
+                    r#"{{"genotype":"AABB","name":"{name}"}}"#
+                )))
+                .unwrap()
+        };
+        for name in ["keep", "drop"] {
+            let response = app.clone().oneshot(submit(name)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let (_, public) = admin_request(app.clone(), "GET", "/api/registry/genomes", None).await;
+        assert_eq!(public["genomes"].as_array().unwrap().len(), 0);
+
+        let (_, pending) = admin_request(
+            app.clone(),
+            "GET",
+            "/admin/submissions",
+            Some("admin-secret"),
+        )
+        .await;
+        let genomes = pending["genomes"].as_array().unwrap();
+        assert_eq!(genomes.len(), 2);
+        let id_of = |name: &str| {
+            genomes.iter().find(|g| g["name"] == name).unwrap()["id"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        let (keep, drop) = (id_of("keep"), id_of("drop"));
+
+        let (_, state) = admin_request(
+            app.clone(),
+            "GET",
+            &format!("/api/registry/submissions/{keep}"),
+            None,
+        )
+        .await;
+        assert_eq!(state["status"], "pending");
+
+        let approve = format!("/admin/submissions/{keep}/approve");
+        let (status, _) = admin_request(app.clone(), "POST", &approve, Some("admin-secret")).await;
+        assert_eq!(status, StatusCode::OK);
+        // Approved stays approved: approving again is not a transition.
+        let (status, _) = admin_request(app.clone(), "POST", &approve, Some("admin-secret")).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let reject = Request::builder()
+            .method("POST")
+            .uri(format!("/admin/submissions/{drop}/reject"))
+            .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 40_000))))
+            .header("authorization", "Bearer admin-secret")
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(r#"{"note":"duplicate"}"#))
+            .unwrap();
+        let response = app.clone().oneshot(reject).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // The submitter sees why, and the moderator can still find it.
+        let (_, state) = admin_request(
+            app.clone(),
+            "GET",
+            &format!("/api/registry/submissions/{drop}"),
+            None,
+        )
+        .await;
+        assert_eq!(state["status"], "rejected");
+        assert_eq!(state["note"], "duplicate");
+        let (_, rejected) = admin_request(
+            app.clone(),
+            "GET",
+            "/admin/submissions?status=rejected",
+            Some("admin-secret"),
+        )
+        .await;
+        assert_eq!(rejected["genomes"].as_array().unwrap().len(), 1);
+        let unknown = format!("/api/registry/submissions/{}", Uuid::new_v4());
+        let (status, _) = admin_request(app.clone(), "GET", &unknown, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (_, public) = admin_request(app.clone(), "GET", "/api/registry/genomes", None).await;
+        let public = public["genomes"].as_array().unwrap();
+        assert_eq!(public.len(), 1);
+        assert_eq!(public[0]["name"], "keep");
+        let (_, metrics) = admin_request(app, "GET", "/admin/metrics", Some("admin-secret")).await;
+        assert_eq!(metrics["pending_submissions"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_admin_kicks_bans_and_purges_over_the_wire() {
+        use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+        let addr = serve(admin_state(false)).await;
+        let url = format!("ws://{addr}/ws");
+        let http = reqwest::Client::new();
+        let admin = |method: reqwest::Method, path: &str| {
+            http.request(method, format!("http://{addr}{path}"))
+                .bearer_auth("admin-secret")
+        };
+
+        let (mut peer, _) = connect_async(url.as_str()).await.unwrap();
+        let offer = NetMessage::TradeOffer(TradeProposal {
+            id: Uuid::new_v4(),
+            sender_id: Uuid::new_v4(),
+            offer_resource: primordium_net::TradeResource::Energy,
+            offer_amount: 10.0,
+            request_resource: primordium_net::TradeResource::Oxygen,
+            request_amount: 5.0,
+        });
+        peer.send(WsMessage::Text(serde_json::to_string(&offer).unwrap()))
+            .await
+            .unwrap();
+
+        let mut trades: Vec<admin::AdminTrade> = Vec::new();
+        for _ in 0..50 {
+            trades = admin(reqwest::Method::GET, "/admin/trades")
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            if !trades.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(trades.len(), 1);
+        let purged: serde_json::Value = admin(
+            reqwest::Method::POST,
+            "/admin/trades/purge?older_than_secs=0",
+        )
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(purged["purged"], 1);
+
+        let peers: Vec<admin::AdminPeer> = admin(reqwest::Method::GET, "/admin/peers")
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(peers.len(), 1);
+        assert!(peers[0].addr.is_loopback());
+        let kick = format!("/admin/peers/{}/kick", peers[0].info.peer_id);
+        let status = admin(reqwest::Method::POST, &kick)
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, reqwest::StatusCode::OK);
+
+        // The kicked socket is closed by the relay.
+        let closed = tokio::time::timeout(Duration::from_secs(5), async {
+            while let Some(Ok(frame)) = peer.next().await {
+                if frame.is_close() {
+                    break;
+                }
+            }
+        })
+        .await;
+        assert!(closed.is_ok());
+
+        let status = admin(reqwest::Method::POST, "/admin/bans")
+            .json(&serde_json::json!({ "ip": "127.0.0.1", "reason": "spam" }))
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, reqwest::StatusCode::OK);
+        assert!(connect_async(url.as_str()).await.is_err());
+
+        let status = admin(reqwest::Method::DELETE, "/admin/bans/127.0.0.1")
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, reqwest::StatusCode::OK);
+        assert!(connect_async(url.as_str()).await.is_ok());
+
+        let metrics: admin::Metrics = admin(reqwest::Method::GET, "/admin/metrics")
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(metrics.peers_kicked, 1);
+        assert_eq!(metrics.bans, 0);
+        assert!(metrics.messages_relayed >= 2);
+    }
+
+    #[tokio::test]
+    async fn test_genome_duplicates_are_flagged_merged_and_searchable() {
+        use primordium_core::brain::GenotypeLogic;
+        use primordium_data::Genotype;
+
+        let app = router(Arc::new(memory_state()));
+        let submit = |genotype: &Genotype| {
+            let app = app.clone();
+            let body = serde_json::json!({ "genotype": genotype.to_hex() }).to_string();
+            async move {
+                let request = Request::builder()
+                    .method("POST")
+                    .uri("/api/registry/genomes")
+                    .header("content-type", "application/json")
+                    .body(axum::body::Body::from(body))
+                    .unwrap();
+                let response = app.oneshot(request).await.unwrap();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        let original = Genotype::new_random();
+        let mut mutant = original.clone();
+        for conn in mutant.brain.connections.iter_mut().step_by(3) {
+            conn.weight += 0.2;
+        }
+
+        let first = submit(&original).await;
+        assert!(first["duplicate_of"].is_null());
+        let original_id = first["id"].as_str().unwrap().to_string();
+
+        let flagged = submit(&mutant).await;
+        assert_eq!(flagged["duplicate_of"]["id"], original_id.as_str());
+        assert_ne!(flagged["id"], original_id.as_str());
+
+        let copy = submit(&original).await;
+        assert_eq!(copy["merged"], true);
+        assert_eq!(copy["id"], original_id.as_str());
+
+        let stranger = submit(&Genotype::new_random()).await;
+        assert!(stranger["duplicate_of"].is_null());
+
+        let (status, similar) = admin_request(
+            app.clone(),
+            "GET",
+            &format!("/api/registry/genomes/{original_id}/similar"),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let similar = similar["genomes"].as_array().unwrap();
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0]["id"], flagged["id"]);
+        assert_eq!(similar[0]["duplicate_of"], original_id.as_str());
+
+        let (_, public) = admin_request(app.clone(), "GET", "/api/registry/genomes", None).await;
+        let listed = public["genomes"].as_array().unwrap();
+        assert_eq!(listed.len(), 3);
+        let merged = listed.iter().find(|g| g["id"] == original_id.as_str());
+        assert_eq!(merged.unwrap()["merged_count"], 1);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/registry/genomes/similar")
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(
+                serde_json::json!({ "genotype": mutant.to_hex(), "limit": 1 }).to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let found: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(found["genomes"][0]["id"], flagged["id"]);
+
+        let (status, _) = admin_request(
+            app,
+            "GET",
+            &format!("/api/registry/genomes/{}/similar", Uuid::new_v4()),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_seed_thumbnails_are_validated_and_served() {
+        let app = router(Arc::new(memory_state()));
+        let publish = |thumbnail: Option<String>| {
+            let app = app.clone();
+            let body = serde_json::json!({ "name": "garden", "thumbnail": thumbnail });
+            async move {
+                let request = Request::builder()
+                    .method("POST")
+                    .uri("/api/registry/seeds")
+                    .header("content-type", "application/json")
+                    .body(axum::body::Body::from(body.to_string()))
+                    .unwrap();
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                (status, body["id"].as_str().map(str::to_string))
+            }
+        };
+        let terrain = primordium_core::terrain::TerrainGrid::generate(40, 20, 1);
+        let png = Thumbnail::render(&terrain, [(10.0, 10.0)]).to_png();
+
+        let (status, _) = publish(Some("not hex".to_string())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = publish(Some(hex::encode(b"not a png"))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (_, with) = publish(Some(hex::encode(&png))).await;
+        let (_, without) = publish(None).await;
+
+        let (_, seeds) = admin_request(app.clone(), "GET", "/api/registry/seeds", None).await;
+        let flags: HashMap<&str, bool> = seeds["seeds"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| {
+                (
+                    s["id"].as_str().unwrap(),
+                    s["has_thumbnail"].as_bool().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(flags.len(), 2);
+        assert!(flags[with.as_deref().unwrap()]);
+        assert!(!flags[without.as_deref().unwrap()]);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/registry/seeds/{}/thumbnail", with.unwrap()))
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "image/png");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), png.as_slice());
+
+        let (status, _) = admin_request(
+            app,
+            "GET",
+            &format!("/api/registry/seeds/{}/thumbnail", without.unwrap()),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_challenge_scenarios_publish_and_rank_signed_results() {
+        use primordium_io::challenge::ChallengeMetric;
+        use std::collections::BTreeMap;
+
+        let app = router(Arc::new(memory_state()));
+        let post = |uri: String, body: serde_json::Value| {
+            let app = app.clone();
+            async move {
+                let request = Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(axum::body::Body::from(body.to_string()))
+                    .unwrap();
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+                )
+            }
+        };
+        let scenario = "id = \"tiny\"\ntitle = \"Tiny\"\nseed = 1\nticks = 50\n\
+                        score = \"population\"\n[[targets]]\nmetric = \"species\"\nat_least = 2\n";
+
+        let (status, _) = post(
+            "/api/registry/scenarios".into(),
+            serde_json::json!({ "scenario": "ticks = \"many\"" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, published) = post(
+            "/api/registry/scenarios".into(),
+            serde_json::json!({ "scenario": scenario, "author": "alice" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let id = published["id"].as_str().unwrap().to_string();
+
+        let (status, fetched) = admin_request(
+            app.clone(),
+            "GET",
+            &format!("/api/registry/scenarios/{id}"),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(fetched["scenario_toml"], scenario);
+        assert_eq!(fetched["name"], "Tiny");
+        assert_eq!(fetched["downloads"], 1);
+
+        let result = |population: f64, species: f64| {
+            let metrics = BTreeMap::from([
+                (ChallengeMetric::Population, population),
+                (ChallengeMetric::Species, species),
+            ]);
+            ChallengeResult {
+                ticks: 50,
+                world_hash: "hash".to_string(),
+                signature: result_signature(scenario, "hash", &metrics),
+                metrics,
+                provenance: None,
+            }
+        };
+        let results = format!("/api/registry/scenarios/{id}/results");
+        let (status, ranked) = post(
+            results.clone(),
+            serde_json::json!({ "player": "bob", "result": result(30.0, 2.0) }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            ranked,
+            serde_json::json!({ "rank": 1, "score": 30.0, "passed": true })
+        );
+
+        let (_, ranked) = post(
+            results.clone(),
+            serde_json::json!({ "player": "carol", "result": result(40.0, 1.0) }),
+        )
+        .await;
+        // Failing runs rank below every passing one.
+        assert_eq!(ranked["rank"], 2);
+        assert_eq!(ranked["passed"], false);
+
+        let mut forged = result(30.0, 2.0);
+        forged.metrics.insert(ChallengeMetric::Population, 90.0);
+        let (status, _) = post(
+            results.clone(),
+            serde_json::json!({ "player": "mallory", "result": forged }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (_, ranked) = post(
+            results,
+            serde_json::json!({ "player": "bob", "result": result(10.0, 2.0) }),
+        )
+        .await;
+        // A worse run does not replace the player's best.
+        assert_eq!(ranked["rank"], 1);
+
+        let (_, leaderboard) = admin_request(
+            app.clone(),
+            "GET",
+            &format!("/api/registry/scenarios/{id}/leaderboard"),
+            None,
+        )
+        .await;
+        let leaderboard = leaderboard["leaderboard"].as_array().unwrap();
+        let players: Vec<_> = leaderboard.iter().map(|e| e["player"].clone()).collect();
+        assert_eq!(players, ["bob", "carol"]);
+        assert_eq!(leaderboard[0]["score"], 30.0);
+
+        let (status, _) = admin_request(
+            app,
+            "GET",
+            &format!("/api/registry/scenarios/{}", Uuid::new_v4()),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
 }
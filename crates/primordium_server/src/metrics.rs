@@ -0,0 +1,64 @@
+//! Prometheus metrics under `/metrics`.
+//!
+//! Counters are kept in the relay's [`Metrics`] as things happen, storage
+//! latencies as each query is answered; the gauges are sampled from the
+//! relay state on every scrape. The endpoint only exposes counts, so it is
+//! open like `/api/stats`.
+
+use crate::{spectate, AppState};
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use primordium_core::Metrics;
+use std::sync::Arc;
+
+/// Prepended to every exported metric name.
+const PREFIX: &str = "primordium_relay_";
+
+/// Entity migrations relayed between peers.
+pub const MIGRATIONS: &str = "migrations_relayed_total";
+/// Messages broadcast to the room.
+pub const MESSAGES: &str = "messages_relayed_total";
+/// Peers kicked by a moderator.
+pub const KICKS: &str = "peers_kicked_total";
+/// Messages dropped over a peer's rate limit.
+pub const RATE_LIMITED: &str = "messages_rate_limited_total";
+/// Migrations refused over an address's daily quota.
+pub const OVER_QUOTA: &str = "migrations_over_quota_total";
+/// Time from submitting a storage query to its answer.
+pub const STORAGE_QUERY: &str = "storage_query_seconds";
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/metrics", get(metrics))
+}
+
+/// Samples the gauges into `metrics` from the current relay state.
+fn sample(state: &AppState, metrics: &Metrics) {
+    let count = |n: Option<usize>| n.unwrap_or(0) as f64;
+    metrics.set_gauge(
+        "connected_peers",
+        count(state.peers.lock().ok().map(|p| p.len())),
+    );
+    metrics.set_gauge(
+        "active_trades",
+        count(state.active_trades.lock().ok().map(|t| t.len())),
+    );
+    metrics.set_gauge(
+        "banned_addresses",
+        count(state.bans.lock().ok().map(|b| b.len())),
+    );
+    metrics.set_gauge("spectate_streams", spectate::publishers(state) as f64);
+}
+
+async fn metrics(State(state): State<Arc<AppState>>) -> Response {
+    sample(&state, &state.metrics);
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render_prometheus(PREFIX),
+    )
+        .into_response()
+}
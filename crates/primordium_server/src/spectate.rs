@@ -0,0 +1,333 @@
+//! Live spectating under `/ws/spectate/:peer_id`.
+//!
+//! A connected peer publishes its world there (with
+//! [`primordium_net::spectate::with_publish`] and the secret from its relay
+//! handshake) as compressed snapshot
+//! keyframes and deltas, and anyone may connect without the query to watch
+//! it read-only. The relay never inflates the frames: it checks their
+//! headers, fans them out, and keeps the latest keyframe plus the deltas
+//! since so a spectator joining mid-stream can rebuild the current world.
+
+use crate::{is_banned, json_error, AppState};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        ConnectInfo, Path, Query, State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use futures::{sink::SinkExt, stream::StreamExt};
+use primordium_net::spectate::{FrameKind, SpectateFrame, MAX_FRAME_SIZE};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Frames a spectator may fall behind before it is sent the backlog again.
+const CHANNEL_CAPACITY: usize = 16;
+
+/// Bytes of keyframe and deltas kept per stream. Past it the backlog is
+/// dropped, and late spectators wait for the publisher's next keyframe.
+const MAX_BACKLOG_BYTES: usize = 2 * MAX_FRAME_SIZE;
+
+/// A frame as received from the publisher.
+type Frame = Arc<Vec<u8>>;
+
+/// The stream of one peer's world.
+pub struct Channel {
+    tx: broadcast::Sender<Frame>,
+    /// Whether a publisher is connected.
+    publishing: bool,
+    /// The latest keyframe and the deltas after it, as received.
+    backlog: Vec<Frame>,
+    /// Total length of `backlog`.
+    backlog_bytes: usize,
+}
+
+impl Channel {
+    fn new() -> Self {
+        let (tx, _rx) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            tx,
+            publishing: false,
+            backlog: Vec::new(),
+            backlog_bytes: 0,
+        }
+    }
+
+    /// Adds a published frame to the backlog, starting over at keyframes.
+    fn record(&mut self, kind: FrameKind, frame: &Frame) {
+        match kind {
+            FrameKind::Keyframe => {
+                self.backlog = vec![frame.clone()];
+                self.backlog_bytes = frame.len();
+            }
+            FrameKind::Delta
+                if !self.backlog.is_empty()
+                    && self.backlog_bytes + frame.len() <= MAX_BACKLOG_BYTES =>
+            {
+                self.backlog.push(frame.clone());
+                self.backlog_bytes += frame.len();
+            }
+            FrameKind::Delta => self.clear_backlog(),
+        }
+    }
+
+    fn clear_backlog(&mut self) {
+        self.backlog.clear();
+        self.backlog_bytes = 0;
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct SpectateParams {
+    role: Option<String>,
+    secret: Option<String>,
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/ws/spectate/:peer_id", get(spectate_handler))
+}
+
+async fn spectate_handler(
+    ws: WebSocketUpgrade,
+    Path(peer_id): Path<Uuid>,
+    Query(params): Query<SpectateParams>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    if is_banned(&state, remote.ip()) {
+        tracing::info!("Refused spectator from banned address {}", remote.ip());
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    match params.role.as_deref() {
+        None | Some("watch") => match subscribe(&state, peer_id) {
+            Some((rx, backlog)) => ws
+                .on_upgrade(move |socket| watch(socket, state, peer_id, rx, backlog))
+                .into_response(),
+            None => json_error(StatusCode::NOT_FOUND, "Peer is not connected"),
+        },
+        Some("publish") => match claim(&state, peer_id, remote, params.secret.as_deref()) {
+            Ok(()) => ws
+                .max_message_size(MAX_FRAME_SIZE)
+                .on_upgrade(move |socket| publish(socket, state, peer_id))
+                .into_response(),
+            Err((status, message)) => json_error(status, message),
+        },
+        Some(other) => json_error(
+            StatusCode::BAD_REQUEST,
+            format!("Unknown spectate role `{other}`"),
+        ),
+    }
+}
+
+/// Makes the caller the publisher of `peer_id`'s stream. Only the peer's own
+/// address presenting the secret from its handshake may publish, and only
+/// one publisher at a time.
+fn claim(
+    state: &AppState,
+    peer_id: Uuid,
+    remote: SocketAddr,
+    secret: Option<&str>,
+) -> Result<(), (StatusCode, &'static str)> {
+    let Ok(mut channels) = state.spectate.lock() else {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Spectate state unavailable",
+        ));
+    };
+    let owner = state.connections.lock().ok().and_then(|connections| {
+        let connection = connections.get(&peer_id)?;
+        let secret_matches = secret.is_some_and(|secret| {
+            bool::from(
+                secret
+                    .as_bytes()
+                    .ct_eq(connection.publish_secret.as_bytes()),
+            )
+        });
+        Some(connection.addr == remote.ip() && secret_matches)
+    });
+    match owner {
+        None => return Err((StatusCode::NOT_FOUND, "Peer is not connected")),
+        Some(false) => {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Only the peer itself may publish its world",
+            ))
+        }
+        Some(true) => {}
+    }
+    let channel = channels.entry(peer_id).or_insert_with(Channel::new);
+    if channel.publishing {
+        return Err((StatusCode::CONFLICT, "Peer is already publishing"));
+    }
+    channel.publishing = true;
+    Ok(())
+}
+
+/// A receiver for `peer_id`'s stream and the backlog to send first, or
+/// `None` if the peer is not connected.
+fn subscribe(state: &AppState, peer_id: Uuid) -> Option<(broadcast::Receiver<Frame>, Vec<Frame>)> {
+    let mut channels = state.spectate.lock().ok()?;
+    // Checked under the channel lock so a departing peer's channel is not
+    // recreated after `close` removed it.
+    let connected = state
+        .connections
+        .lock()
+        .is_ok_and(|connections| connections.contains_key(&peer_id));
+    if !connected {
+        return None;
+    }
+    let channel = channels.entry(peer_id).or_insert_with(Channel::new);
+    Some((channel.tx.subscribe(), channel.backlog.clone()))
+}
+
+fn backlog(state: &AppState, peer_id: Uuid) -> Vec<Frame> {
+    state
+        .spectate
+        .lock()
+        .ok()
+        .and_then(|channels| channels.get(&peer_id).map(|c| c.backlog.clone()))
+        .unwrap_or_default()
+}
+
+/// Streams with a publisher connected.
+pub fn publishers(state: &AppState) -> usize {
+    state
+        .spectate
+        .lock()
+        .map(|channels| channels.values().filter(|c| c.publishing).count())
+        .unwrap_or(0)
+}
+
+/// Ends `peer_id`'s stream when the peer leaves; spectators are closed.
+pub fn close(state: &AppState, peer_id: Uuid) {
+    if let Ok(mut channels) = state.spectate.lock() {
+        channels.remove(&peer_id);
+    }
+}
+
+async fn publish(mut socket: WebSocket, state: Arc<AppState>, peer_id: Uuid) {
+    tracing::info!("Peer {} started publishing its world", peer_id);
+    while let Some(Ok(message)) = socket.recv().await {
+        let bytes = match message {
+            Message::Binary(bytes) => bytes,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let kind = match SpectateFrame::decode(&bytes) {
+            Ok(frame) => frame.kind,
+            Err(e) => {
+                tracing::warn!("Peer {} published a bad frame: {}", peer_id, e);
+                continue;
+            }
+        };
+        if !forward(&state, peer_id, kind, bytes) {
+            break;
+        }
+    }
+    if let Ok(mut channels) = state.spectate.lock() {
+        if let Some(channel) = channels.get_mut(&peer_id) {
+            channel.publishing = false;
+            channel.clear_backlog();
+        }
+    }
+    tracing::info!("Peer {} stopped publishing", peer_id);
+}
+
+/// Sends a frame to `peer_id`'s spectators. `false` once the stream is
+/// gone.
+fn forward(state: &AppState, peer_id: Uuid, kind: FrameKind, bytes: Vec<u8>) -> bool {
+    let Ok(mut channels) = state.spectate.lock() else {
+        return false;
+    };
+    let Some(channel) = channels.get_mut(&peer_id) else {
+        return false;
+    };
+    let bytes = Arc::new(bytes);
+    channel.record(kind, &bytes);
+    let _ = channel.tx.send(bytes);
+    true
+}
+
+async fn watch(
+    socket: WebSocket,
+    state: Arc<AppState>,
+    peer_id: Uuid,
+    mut rx: broadcast::Receiver<Frame>,
+    initial: Vec<Frame>,
+) {
+    let (mut sender, mut receiver) = socket.split();
+    for frame in initial {
+        if sender
+            .send(Message::Binary((*frame).clone()))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+    loop {
+        let frames = tokio::select! {
+            msg = rx.recv() => match msg {
+                Ok(frame) => vec![frame],
+                // Replaying from the keyframe lets the spectator catch up;
+                // it skips frames it already has.
+                Err(broadcast::error::RecvError::Lagged(_)) => backlog(&state, peer_id),
+                Err(broadcast::error::RecvError::Closed) => {
+                    let _ = sender.send(Message::Close(None)).await;
+                    break;
+                }
+            },
+            // Spectators are read-only: anything but a close is ignored.
+            incoming = receiver.next() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+        for frame in frames {
+            if sender
+                .send(Message::Binary((*frame).clone()))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backlog_is_capped_by_bytes_and_resyncs_at_a_keyframe() {
+        let frame = |len: usize| Arc::new(vec![0u8; len]);
+        let mut channel = Channel::new();
+        channel.record(FrameKind::Delta, &frame(10));
+        assert!(channel.backlog.is_empty(), "deltas need a keyframe first");
+
+        channel.record(FrameKind::Keyframe, &frame(MAX_FRAME_SIZE));
+        channel.record(FrameKind::Delta, &frame(MAX_FRAME_SIZE / 2));
+        channel.record(FrameKind::Delta, &frame(MAX_FRAME_SIZE / 2));
+        assert_eq!(channel.backlog.len(), 3);
+        assert_eq!(channel.backlog_bytes, MAX_BACKLOG_BYTES);
+
+        // One byte over drops everything, and deltas stay out until the
+        // next keyframe.
+        channel.record(FrameKind::Delta, &frame(1));
+        channel.record(FrameKind::Delta, &frame(1));
+        assert!(channel.backlog.is_empty());
+        assert_eq!(channel.backlog_bytes, 0);
+
+        channel.record(FrameKind::Keyframe, &frame(100));
+        channel.record(FrameKind::Delta, &frame(10));
+        assert_eq!(channel.backlog.len(), 2);
+        assert_eq!(channel.backlog_bytes, 110);
+    }
+}
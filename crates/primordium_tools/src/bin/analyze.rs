@@ -39,6 +39,9 @@ fn main() -> anyhow::Result<()> {
     let mut death_count = 0;
     let mut total_age = 0;
     let mut max_gen = 0;
+    let mut bookmarks = Vec::new();
+    let mut narrations = Vec::new();
+    let mut provenance = Vec::new();
 
     for line in reader.lines() {
         let l = line?;
@@ -60,6 +63,14 @@ fn main() -> anyhow::Result<()> {
                     death_count += 1;
                     total_age += age;
                 }
+                LiveEvent::Bookmark { tick, note, .. } => bookmarks.push((tick, note)),
+                LiveEvent::Narration {
+                    tick, text, refs, ..
+                } => narrations.push((tick, text, refs)),
+                LiveEvent::Provenance {
+                    provenance: manifest,
+                    ..
+                } => provenance.push(manifest),
                 _ => {}
             }
         }
@@ -99,12 +110,34 @@ fn main() -> anyhow::Result<()> {
         - **Total Deaths**: {}\n\
         - **Average Lifespan**: {:.2} ticks\n\
         - **Max Generation**: {}\n\n\
+        ## Provenance ({})\n\
+        {}\n\
         ## Legendary Organisms ({})\n\
+        {}\n\
+        ## Bookmarks ({})\n\
+        {}\n\
+        ## Narrations ({})\n\
         {}\n",
         birth_count,
         death_count,
         avg_lifespan,
         max_gen,
+        provenance.len(),
+        provenance
+            .iter()
+            .map(|m| {
+                format!(
+                    "- **{}**: v{} ({}), config `{}`, seed {}, features: {}\n",
+                    m.started_at,
+                    m.version,
+                    m.git_commit.as_deref().unwrap_or("unknown commit"),
+                    m.config_hash,
+                    m.seed.map_or("none".to_string(), |s| s.to_string()),
+                    m.features.join(", ")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(""),
         legends.len(),
         legends
             .iter()
@@ -120,6 +153,25 @@ fn main() -> anyhow::Result<()> {
                 )
             })
             .collect::<Vec<_>>()
+            .join(""),
+        bookmarks.len(),
+        bookmarks
+            .iter()
+            .map(|(tick, note)| format!("- **Tick {}**: {}\n", tick, note))
+            .collect::<Vec<_>>()
+            .join(""),
+        narrations.len(),
+        narrations
+            .iter()
+            .map(|(tick, text, refs)| {
+                let details: String = refs
+                    .describe()
+                    .iter()
+                    .map(|line| format!("  - {}\n", line))
+                    .collect();
+                format!("- **Tick {}**: {}\n{}", tick, text, details)
+            })
+            .collect::<Vec<_>>()
             .join("")
     );
 
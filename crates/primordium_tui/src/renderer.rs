@@ -1,13 +1,43 @@
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::{Block, Borders, Widget};
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
+use primordium_core::body;
+use primordium_core::glyph::GlyphTheme;
+use primordium_core::palette::Palette;
+use primordium_core::route_trail::RouteTrail;
 use primordium_core::snapshot::{EntitySnapshot, WorldSnapshot};
 use primordium_core::terrain::{TerrainLogic, TerrainType};
 use primordium_data::EntityStatus;
 
+/// Road wear below which a trail is too faint to draw.
+const TRAIL_MIN_WEAR: f32 = 0.2;
+
+/// Route trail glyphs from the newest position to the oldest.
+const ROUTE_GLYPHS: [&str; 3] = ["•", "∙", "·"];
+
+/// Fill of the cells a large body covers around its glyph.
+const BODY_GLYPH: &str = "▒";
+
+/// Hydration below which the hydration view marks an organism as parched.
+const PARCHED_HYDRATION: f32 = 0.3;
+
+/// Colour of full strength in the pheromone view, by channel index; the
+/// fifth is the default death scent channel.
+const PHEROMONE_COLORS: [(u8, u8, u8); 8] = [
+    (40, 200, 60),
+    (220, 120, 0),
+    (60, 120, 255),
+    (200, 60, 220),
+    (128, 0, 42),
+    (0, 200, 200),
+    (220, 220, 40),
+    (200, 200, 200),
+];
+
 pub struct WorldWidget<'a> {
     snapshot: &'a WorldSnapshot,
     screensaver: bool,
@@ -16,6 +46,16 @@ pub struct WorldWidget<'a> {
     glow_enabled: bool,
     glow_intensity: f32,
     density_variation: bool,
+    tag_filter: Option<&'a str>,
+    pheromone_channel: usize,
+    route_trail: Option<&'a RouteTrail>,
+    glyphs: &'a GlyphTheme,
+    palette: Palette,
+}
+
+fn default_glyphs() -> &'static GlyphTheme {
+    static DEFAULT: OnceLock<GlyphTheme> = OnceLock::new();
+    DEFAULT.get_or_init(GlyphTheme::default)
 }
 
 impl<'a> WorldWidget<'a> {
@@ -36,6 +76,160 @@ impl<'a> WorldWidget<'a> {
             glow_enabled,
             glow_intensity,
             density_variation,
+            tag_filter: None,
+            pheromone_channel: 0,
+            route_trail: None,
+            glyphs: default_glyphs(),
+            palette: Palette::NEUTRAL,
+        }
+    }
+
+    /// Draws organisms with `glyphs` instead of the built-in theme.
+    pub fn with_glyphs(mut self, glyphs: &'a GlyphTheme) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Draws pheromone `channel` in the pheromone view.
+    pub fn with_pheromone_channel(mut self, channel: usize) -> Self {
+        self.pheromone_channel = channel;
+        self
+    }
+
+    /// Highlights the entities carrying `tag` and summarises them in the title.
+    pub fn with_tag_filter(mut self, tag: Option<&'a str>) -> Self {
+        self.tag_filter = tag;
+        self
+    }
+
+    /// Draws `trail` behind its entity as glyphs that fade with age.
+    pub fn with_route_trail(mut self, trail: Option<&'a RouteTrail>) -> Self {
+        self.route_trail = trail;
+        self
+    }
+
+    /// Grades the world's colours by `palette`.
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Grades every colour drawn on the map by the palette and washes bare
+    /// ground in the regime's colour.
+    fn apply_palette(&self, area: Rect, buf: &mut Buffer) {
+        if self.palette.is_neutral() {
+            return;
+        }
+        let grade = |color: Color| match color {
+            Color::Rgb(r, g, b) => {
+                let (r, g, b) = self.palette.apply((r, g, b));
+                Color::Rgb(r, g, b)
+            }
+            other => other,
+        };
+        let wash = self.palette.wash.map(|(r, g, b)| Color::Rgb(r, g, b));
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = &mut buf[(x, y)];
+                let fg = grade(cell.fg);
+                let bg = match (cell.bg, wash) {
+                    (Color::Reset, Some(wash)) => wash,
+                    (bg, _) => grade(bg),
+                };
+                cell.set_fg(fg).set_bg(bg);
+            }
+        }
+    }
+
+    /// Draws the route trail on empty cells, newest point first so it wins
+    /// a cell visited more than once.
+    fn draw_route_trail(&self, area: Rect, buf: &mut Buffer) {
+        let Some(trail) = self.route_trail else {
+            return;
+        };
+        for (i, point) in trail.points().iter().enumerate().rev() {
+            let Some((x, y)) = Self::world_to_screen(point.x, point.y, area, self.screensaver)
+            else {
+                continue;
+            };
+            let cell = &mut buf[(x, y)];
+            if cell.symbol() != " " {
+                continue;
+            }
+            let age = trail.age(i);
+            let glyph = ROUTE_GLYPHS
+                [((age * ROUTE_GLYPHS.len() as f32) as usize).min(ROUTE_GLYPHS.len() - 1)];
+            let shade = (230.0 * (1.0 - 0.75 * age)) as u8;
+            cell.set_symbol(glyph);
+            cell.set_fg(Color::Rgb(shade / 3, shade, shade));
+        }
+    }
+
+    fn title(&self) -> String {
+        let mut title = format!("World (Tick: {})", self.snapshot.tick);
+        if let Some(tag) = self.tag_filter {
+            match self.snapshot.tag_stats(tag) {
+                Some(stats) => title.push_str(&format!(
+                    " | #{}: {} alive, E {:.0}, gen {:.1}, around ({:.0},{:.0})",
+                    tag,
+                    stats.count,
+                    stats.mean_energy,
+                    stats.mean_generation,
+                    stats.centroid.0,
+                    stats.centroid.1
+                )),
+                None => title.push_str(&format!(" | #{}: none alive", tag)),
+            }
+        }
+        title
+    }
+
+    /// Fills the rest of a large body's footprint around its glyph at
+    /// `(x, y)`, leaving the glyphs of organisms already drawn alone.
+    fn draw_body(entity: &EntitySnapshot, (x, y): (u16, u16), inner: Rect, buf: &mut Buffer) {
+        let side = body::footprint(entity.body_size);
+        let start = (side - 1) / 2;
+        for dy in 0..side {
+            for dx in 0..side {
+                let (Some(bx), Some(by)) =
+                    ((x + dx).checked_sub(start), (y + dy).checked_sub(start))
+                else {
+                    continue;
+                };
+                if (bx, by) == (x, y)
+                    || bx < inner.left()
+                    || bx >= inner.right()
+                    || by < inner.top()
+                    || by >= inner.bottom()
+                {
+                    continue;
+                }
+                let cell = &mut buf[(bx, by)];
+                if cell.symbol() == " " {
+                    cell.set_symbol(BODY_GLYPH);
+                }
+            }
+        }
+    }
+
+    /// Marks tagged entities: those matching the filter are highlighted,
+    /// other tagged entities are underlined.
+    fn mark_tagged(&self, entity: &EntitySnapshot, cell: &mut ratatui::buffer::Cell) {
+        if entity.tags.is_empty() {
+            return;
+        }
+        let matches = self
+            .tag_filter
+            .is_some_and(|tag| entity.tags.iter().any(|t| t == tag));
+        if matches {
+            cell.set_style(
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Rgb(160, 0, 160))
+                    .add_modifier(Modifier::BOLD),
+            );
+        } else {
+            cell.set_style(Style::default().add_modifier(Modifier::UNDERLINED));
         }
     }
 
@@ -72,39 +266,10 @@ impl<'a> WorldWidget<'a> {
         }
     }
 
-    pub fn symbol_for_status(entity: &EntitySnapshot) -> char {
-        if entity.status == EntityStatus::InTransit {
-            return '✈';
-        }
-        if entity.status == EntityStatus::Starving {
-            return '†';
-        }
-        if entity.status == EntityStatus::Infected {
-            return '☣';
-        }
-        if entity.status == EntityStatus::Hunting {
-            return '♦';
-        }
-        if entity.status == EntityStatus::Mating {
-            return '♥';
-        }
-        if entity.status == EntityStatus::Sharing {
-            return '♣';
-        }
-        if entity.status == EntityStatus::Bonded {
-            return '⚭';
-        }
-
-        match (entity.specialization, entity.is_larva) {
-            (Some(primordium_data::Specialization::Soldier), true) => '△',
-            (Some(primordium_data::Specialization::Soldier), false) => '▲',
-            (Some(primordium_data::Specialization::Engineer), true) => '◇',
-            (Some(primordium_data::Specialization::Engineer), false) => '◈',
-            (Some(primordium_data::Specialization::Provider), true) => '○',
-            (Some(primordium_data::Specialization::Provider), false) => '◎',
-            (None, true) => '·',
-            (None, false) => '●',
-        }
+    /// The glyph `glyphs` draws for `entity`, narrowed to one cell unless
+    /// the theme allows wide glyphs.
+    pub fn symbol_for_status<'g>(glyphs: &'g GlyphTheme, entity: &EntitySnapshot) -> &'g str {
+        glyphs.fit(glyphs.glyph_for(entity))
     }
 
     pub fn symbol_for_terrain(t: TerrainType) -> char {
@@ -238,6 +403,17 @@ impl<'a> WorldWidget<'a> {
         }
     }
 
+    /// Ember colour for mutation heat `t` relative to the hottest cell; the
+    /// square root lifts faint activity into view.
+    pub fn color_for_heat(t: f32) -> Color {
+        let s = t.clamp(0.0, 1.0).sqrt();
+        Color::Rgb(
+            (230.0 * s) as u8,
+            (170.0 * s * s) as u8,
+            (40.0 * s * s * s) as u8,
+        )
+    }
+
     pub fn color_for_terrain(t: TerrainType) -> Color {
         match t {
             TerrainType::Plains => Color::Reset,
@@ -291,9 +467,7 @@ impl<'a> WorldWidget<'a> {
 impl<'a> Widget for WorldWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         if !self.screensaver {
-            let block = Block::default()
-                .title(format!("World (Tick: {})", self.snapshot.tick))
-                .borders(Borders::ALL);
+            let block = Block::default().title(self.title()).borders(Borders::ALL);
             block.render(area, buf);
         }
 
@@ -335,28 +509,25 @@ impl<'a> Widget for WorldWidget<'a> {
                     if self.glow_enabled && Self::entity_is_bright(entity) {
                         bright_positions.push((x, y));
                     }
+                    Self::draw_body(entity, (x, y), inner, buf);
                     let status = entity.status;
                     let cell = &mut buf[(x, y)];
-                    if self.density_enabled {
-                        let density = Self::density_from_energy(entity.energy, entity.max_energy);
-                        let status_symbol = Self::symbol_for_status(entity);
-                        let symbol = match entity.status {
-                            EntityStatus::InTransit
-                            | EntityStatus::Starving
-                            | EntityStatus::Infected
-                            | EntityStatus::Hunting
-                            | EntityStatus::Mating
-                            | EntityStatus::Sharing => status_symbol,
-                            _ => Self::density_char(density),
-                        };
-                        cell.set_symbol(std::str::from_utf8(&[symbol as u8]).unwrap_or("?"));
-                    } else {
-                        cell.set_symbol(
-                            std::str::from_utf8(&[Self::symbol_for_status(entity) as u8])
-                                .unwrap_or("?"),
-                        );
-                    }
-                    if self.view_mode >= 2 {
+                    // Status glyphs show through density rendering.
+                    let mut scratch = [0; 4];
+                    let glyph = match self.glyphs.status_glyph(status) {
+                        None if self.density_enabled => {
+                            let density =
+                                Self::density_from_energy(entity.energy, entity.max_energy);
+                            &*Self::density_char(density).encode_utf8(&mut scratch)
+                        }
+                        _ => Self::symbol_for_status(self.glyphs, entity),
+                    };
+                    cell.set_symbol(glyph);
+                    if self.view_mode == 9 {
+                        if entity.hydration < PARCHED_HYDRATION {
+                            cell.set_bg(Color::Rgb(110, 50, 0));
+                        }
+                    } else if self.view_mode >= 2 {
                         if entity.rank > 0.9 {
                             cell.set_bg(Color::Rgb(100, 100, 0));
                         } else if status == EntityStatus::Soldier {
@@ -366,10 +537,13 @@ impl<'a> Widget for WorldWidget<'a> {
                     if entity.bonded_to.is_some() {
                         cell.set_bg(Color::Rgb(80, 80, 0));
                     }
+                    self.mark_tagged(entity, cell);
                 }
             }
         }
 
+        self.draw_route_trail(area, buf);
+
         let map_w = self.snapshot.terrain.width;
         let map_h = self.snapshot.terrain.height;
 
@@ -378,9 +552,25 @@ impl<'a> Widget for WorldWidget<'a> {
         let start_y = 0;
         let end_y = inner.height.min(map_h);
 
-        for y in start_y..end_y {
-            for x in start_x..end_x {
-                let terrain = self.snapshot.terrain.get_cell(x, y);
+        // The mutation heat view scales to the hottest cell.
+        let max_heat = if self.view_mode == 10 {
+            self.snapshot.mutation_heat.max_heat()
+        } else {
+            0.0
+        };
+
+        // Walk the snapshot's terrain chunk by chunk; rows are contiguous
+        // within a chunk so no per-cell index translation is needed.
+        for chunk in self.snapshot.terrain.chunks() {
+            if chunk.origin_y >= end_y {
+                break;
+            }
+            for (i, terrain) in chunk.cells.iter().enumerate() {
+                let x = (i % map_w as usize) as u16;
+                let y = chunk.origin_y + (i / map_w as usize) as u16;
+                if x < start_x || x >= end_x || y < start_y || y >= end_y {
+                    continue;
+                }
                 let screen_x = inner.x + x;
                 let screen_y = inner.y + y;
 
@@ -422,6 +612,27 @@ impl<'a> Widget for WorldWidget<'a> {
                             let intensity = (sound_val.min(1.0) * 255.0) as u8;
                             cell.set_bg(Color::Rgb(intensity, intensity, 0));
                         }
+                        8 => {
+                            let channel = self.pheromone_channel;
+                            let strength =
+                                self.snapshot.pheromones.strength(x, y, channel).min(1.0);
+                            let (r, g, b) = PHEROMONE_COLORS[channel % PHEROMONE_COLORS.len()];
+                            let scale = |c: u8| (f32::from(c) * strength) as u8;
+                            cell.set_bg(Color::Rgb(scale(r), scale(g), scale(b)));
+                        }
+                        9 => {
+                            let water = self.snapshot.terrain.water_at(x as f64, y as f64);
+                            let depth = (water.clamp(0.0, 1.0) * 160.0) as u8;
+                            cell.set_bg(Color::Rgb(0, depth / 3, depth));
+                        }
+                        10 => {
+                            let heat = self.snapshot.mutation_heat.get(x as f64, y as f64);
+                            cell.set_bg(Self::color_for_heat(if max_heat > 0.0 {
+                                heat / max_heat
+                            } else {
+                                0.0
+                            }));
+                        }
                         _ => {
                             let sm = self.snapshot.social_grid
                                 [(y as usize * self.snapshot.width as usize) + x as usize];
@@ -442,6 +653,13 @@ impl<'a> Widget for WorldWidget<'a> {
                             std::str::from_utf8(&[terrain_symbol as u8]).unwrap_or("?"),
                         );
                         cell.set_fg(Self::color_for_terrain(terrain.terrain_type));
+                    } else {
+                        let wear = self.snapshot.roads.get_road(x as f64, y as f64).1;
+                        if wear >= TRAIL_MIN_WEAR {
+                            let shade = 60 + (wear.min(1.0) * 120.0) as u8;
+                            cell.set_symbol("·");
+                            cell.set_fg(Color::Rgb(shade, shade * 4 / 5, shade / 2));
+                        }
                     }
                 }
             }
@@ -461,6 +679,39 @@ impl<'a> Widget for WorldWidget<'a> {
             }
         }
 
+        // Monuments in their builder's colour; ruins in stone grey.
+        for monument in &self.snapshot.monuments {
+            if let Some((x, y)) = Self::world_to_screen(
+                f64::from(monument.x),
+                f64::from(monument.y),
+                area,
+                self.screensaver,
+            ) {
+                let cell = &mut buf[(x, y)];
+                cell.set_symbol("Π");
+                cell.set_fg(if monument.is_ruin() {
+                    Color::Gray
+                } else {
+                    let (r, g, b) = monument.color_rgb;
+                    Color::Rgb(r, g, b)
+                });
+            }
+        }
+
+        // Eggs take the hatchling's colour, dim when fresh and bright near hatching.
+        for egg in &self.snapshot.eggs {
+            if let Some((x, y)) = Self::world_to_screen(egg.x, egg.y, area, self.screensaver) {
+                let shade = 0.5 + 0.5 * egg.progress;
+                let cell = &mut buf[(x, y)];
+                cell.set_symbol(if egg.progress < 0.5 { "o" } else { "O" });
+                cell.set_fg(Color::Rgb(
+                    (f32::from(egg.r) * shade) as u8,
+                    (f32::from(egg.g) * shade) as u8,
+                    (f32::from(egg.b) * shade) as u8,
+                ));
+            }
+        }
+
         // Render bond lines between paired entities (uses positions collected in first pass)
         for entity in &self.snapshot.entities {
             if let Some(partner_id) = entity.bonded_to {
@@ -512,6 +763,7 @@ impl<'a> Widget for WorldWidget<'a> {
         if self.glow_enabled && !bright_positions.is_empty() {
             Self::apply_glow(buf, area, &bright_positions, self.glow_intensity);
         }
+        self.apply_palette(inner, buf);
     }
 }
 
@@ -532,6 +784,7 @@ mod tests {
             b: 0,
             energy: 100.0,
             max_energy: 100.0,
+            hydration: 1.0,
             generation: 1,
             age: 0,
             offspring: 0,
@@ -546,6 +799,9 @@ mod tests {
             genotype_hex: None,
             specialization: None,
             is_larva: false,
+            effects: Vec::new(),
+            tags: Vec::new(),
+            body_size: 1.0,
         }
     }
 
@@ -575,32 +831,47 @@ mod tests {
 
     #[test]
     fn test_symbol_for_status() {
+        let glyphs = GlyphTheme::default();
+        let symbol = |entity: &EntitySnapshot| WorldWidget::symbol_for_status(&glyphs, entity);
         let mut entity = create_dummy_entity();
 
-        // Adult Foraging
-        assert_eq!(WorldWidget::symbol_for_status(&entity), '●');
+        // Adult Foraging, by trophic role
+        assert_eq!(symbol(&entity), "◐");
+        entity.trophic_potential = 0.1;
+        assert_eq!(symbol(&entity), "●");
+        entity.trophic_potential = 0.9;
+        assert_eq!(symbol(&entity), "◉");
 
         // Larva Foraging
         entity.is_larva = true;
-        assert_eq!(WorldWidget::symbol_for_status(&entity), '·');
+        assert_eq!(symbol(&entity), "·");
 
         // Starving
         entity.status = EntityStatus::Starving;
-        assert_eq!(WorldWidget::symbol_for_status(&entity), '†');
+        assert_eq!(symbol(&entity), "†");
 
         // Hunting
         entity.status = EntityStatus::Hunting;
-        assert_eq!(WorldWidget::symbol_for_status(&entity), '♦');
+        assert_eq!(symbol(&entity), "♦");
 
         // Soldier (Normal state overrides status if it's just Foraging, but special statuses like Hunting override Soldier)
         entity.status = EntityStatus::Hunting;
         entity.is_larva = false;
         entity.specialization = Some(Specialization::Soldier);
-        assert_eq!(WorldWidget::symbol_for_status(&entity), '♦');
+        assert_eq!(symbol(&entity), "♦");
 
         // Soldier (Foraging)
         entity.status = EntityStatus::Foraging;
-        assert_eq!(WorldWidget::symbol_for_status(&entity), '▲');
+        assert_eq!(symbol(&entity), "▲");
+    }
+
+    #[test]
+    fn test_wide_glyphs_fill_the_cell_when_enabled() {
+        let mut glyphs = GlyphTheme::from_toml("[status]\nforaging = \"🐺\"").unwrap();
+        let entity = create_dummy_entity();
+        assert_eq!(WorldWidget::symbol_for_status(&glyphs, &entity), "●");
+        glyphs.wide_glyphs = true;
+        assert_eq!(WorldWidget::symbol_for_status(&glyphs, &entity), "🐺");
     }
 
     #[test]
@@ -612,39 +883,22 @@ mod tests {
         );
     }
 
-    /// Verify single-pass rendering correctly handles bonded entities after optimization
-    #[test]
-    fn test_single_pass_rendering_with_bonds() {
+    fn snapshot_of(entities: Vec<EntitySnapshot>) -> WorldSnapshot {
         use primordium_core::influence::InfluenceGrid;
         use primordium_core::pheromone::PheromoneGrid;
         use primordium_core::pressure::PressureGrid;
-        use primordium_core::snapshot::WorldSnapshot;
         use primordium_core::sound::SoundGrid;
         use primordium_core::terrain::TerrainGrid;
 
-        let entity1_id = uuid::Uuid::new_v4();
-        let entity2_id = uuid::Uuid::new_v4();
-
-        let mut entity1 = create_dummy_entity();
-        entity1.id = entity1_id;
-        entity1.x = 5.0;
-        entity1.y = 5.0;
-        entity1.bonded_to = Some(entity2_id);
-
-        let mut entity2 = create_dummy_entity();
-        entity2.id = entity2_id;
-        entity2.x = 10.0;
-        entity2.y = 10.0;
-        entity2.bonded_to = Some(entity1_id);
-
         let terrain = TerrainGrid::generate(20, 20, 0);
         let pheromones = PheromoneGrid::new(20, 20);
         let sound = SoundGrid::new(20, 20);
 
-        let snapshot = WorldSnapshot {
+        WorldSnapshot {
             tick: 0,
-            entities: vec![entity1, entity2],
+            entities,
             food: vec![],
+            eggs: vec![],
             stats: std::sync::Arc::new(primordium_data::PopulationStats::default()),
             hall_of_fame: std::sync::Arc::new(primordium_data::HallOfFame::default()),
             terrain: std::sync::Arc::new(terrain),
@@ -652,11 +906,37 @@ mod tests {
             sound: std::sync::Arc::new(sound),
             pressure: std::sync::Arc::new(PressureGrid::new(20, 20)),
             influence: std::sync::Arc::new(InfluenceGrid::new(20, 20)),
+            roads: std::sync::Arc::new(primordium_core::road::RoadGrid::new(20, 20)),
+            mutation_heat: std::sync::Arc::new(
+                primordium_core::mutation_heat::MutationHeatGrid::new(20, 20),
+            ),
+            monuments: Vec::new(),
             social_grid: std::sync::Arc::new(vec![0u8; 20 * 20]),
             rank_grid: std::sync::Arc::new(vec![0.0f32; 20 * 20]),
             width: 20,
             height: 20,
-        };
+        }
+    }
+
+    /// Verify single-pass rendering correctly handles bonded entities after optimization
+    #[test]
+    fn test_single_pass_rendering_with_bonds() {
+        let entity1_id = uuid::Uuid::new_v4();
+        let entity2_id = uuid::Uuid::new_v4();
+
+        let mut entity1 = create_dummy_entity();
+        entity1.id = entity1_id;
+        entity1.x = 5.0;
+        entity1.y = 5.0;
+        entity1.bonded_to = Some(entity2_id);
+
+        let mut entity2 = create_dummy_entity();
+        entity2.id = entity2_id;
+        entity2.x = 10.0;
+        entity2.y = 10.0;
+        entity2.bonded_to = Some(entity1_id);
+
+        let snapshot = snapshot_of(vec![entity1, entity2]);
 
         let widget = WorldWidget::new(&snapshot, true, 0, false, false, 0.5, false);
         let mut buf = ratatui::buffer::Buffer::empty(ratatui::layout::Rect::new(0, 0, 20, 20));
@@ -673,4 +953,103 @@ mod tests {
             "Entity 2 should be rendered at (10, 10)"
         );
     }
+
+    #[test]
+    fn test_large_bodies_cover_their_footprint() {
+        let mut small = create_dummy_entity();
+        small.x = 5.0;
+        small.y = 5.0;
+        let mut large = create_dummy_entity();
+        large.x = 10.0;
+        large.y = 10.0;
+        large.body_size = 3.0;
+        let snapshot = snapshot_of(vec![small, large]);
+
+        let area = Rect::new(0, 0, 20, 20);
+        let mut buf = Buffer::empty(area);
+        WorldWidget::new(&snapshot, true, 0, false, false, 0.5, false).render(area, &mut buf);
+
+        assert_ne!(buf[(10, 10)].symbol(), BODY_GLYPH);
+        for (x, y) in [(9, 9), (11, 9), (9, 11), (11, 11), (10, 9)] {
+            assert_eq!(buf[(x, y)].symbol(), BODY_GLYPH);
+        }
+        assert_ne!(buf[(12, 10)].symbol(), BODY_GLYPH);
+        assert_ne!(buf[(6, 5)].symbol(), BODY_GLYPH);
+    }
+
+    #[test]
+    fn test_route_trail_fades_behind_its_entity() {
+        let mut entity = create_dummy_entity();
+        entity.x = 6.0;
+        entity.y = 1.0;
+        let snapshot = snapshot_of(vec![entity.clone()]);
+        let mut trail = RouteTrail::new(entity.id, 6);
+        for x in 1..=6 {
+            trail.record(x, x as f64, 1.0);
+        }
+
+        let area = Rect::new(0, 0, 20, 20);
+        let mut buf = Buffer::empty(area);
+        WorldWidget::new(&snapshot, true, 0, false, false, 0.5, false)
+            .with_route_trail(Some(&trail))
+            .render(area, &mut buf);
+
+        assert_eq!(buf[(6, 1)].symbol(), "◐", "Trail drawn over its entity");
+        assert_eq!(buf[(5, 1)].symbol(), ROUTE_GLYPHS[0]);
+        assert_eq!(buf[(1, 1)].symbol(), ROUTE_GLYPHS[2]);
+        let brightness = |x: u16| match buf[(x, 1)].fg {
+            Color::Rgb(_, g, _) => g,
+            _ => 0,
+        };
+        assert!(brightness(5) > brightness(1));
+    }
+
+    #[test]
+    fn test_mutation_heat_view_glows_where_newborns_differ() {
+        let mut snapshot = snapshot_of(vec![]);
+        let heat = std::sync::Arc::make_mut(&mut snapshot.mutation_heat);
+        heat.record(4.5, 6.5, 0.8);
+        heat.record(12.5, 6.5, 0.2);
+
+        let area = Rect::new(0, 0, 20, 20);
+        let mut buf = Buffer::empty(area);
+        WorldWidget::new(&snapshot, true, 10, false, false, 0.5, false).render(area, &mut buf);
+
+        assert_eq!(buf[(4, 6)].bg, WorldWidget::color_for_heat(1.0));
+        assert_eq!(buf[(12, 6)].bg, WorldWidget::color_for_heat(0.25));
+        assert_eq!(buf[(8, 6)].bg, Color::Rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn test_palette_grades_colours_and_washes_bare_ground() {
+        let snapshot = snapshot_of(vec![]);
+        let area = Rect::new(0, 0, 20, 20);
+        let render = |palette: Palette| {
+            let mut buf = Buffer::empty(area);
+            WorldWidget::new(&snapshot, true, 0, false, false, 0.5, false)
+                .with_palette(palette)
+                .render(area, &mut buf);
+            buf
+        };
+
+        let plain = render(Palette::NEUTRAL);
+        let icy = render(Palette {
+            gain: [0.5, 1.0, 1.0],
+            wash: Some((0, 0, 40)),
+        });
+        let (drawn, (r, g, b)) = area
+            .positions()
+            .find_map(|p| match plain[p].fg {
+                Color::Rgb(r, g, b) => Some((p, (r, g, b))),
+                _ => None,
+            })
+            .expect("terrain is drawn in RGB");
+        let r = (f32::from(r) * 0.5).round() as u8;
+        assert_eq!(icy[drawn].fg, Color::Rgb(r, g, b));
+        let bare = area
+            .positions()
+            .find(|&p| plain[p].bg == Color::Reset)
+            .expect("some ground is bare");
+        assert_eq!(icy[bare].bg, Color::Rgb(0, 0, 40));
+    }
 }
@@ -1,10 +1,20 @@
+use super::civilization::crest_spans;
+use primordium_core::crest::Crest;
+use primordium_core::lineage_registry::{LineageGcReport, LineageRegistry};
 use primordium_core::snapshot::WorldSnapshot;
+use primordium_core::systems::migration::MigrationTotals;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, Borders, Paragraph, Widget};
 
 pub struct AncestryWidget<'a> {
     pub snapshot: &'a WorldSnapshot,
+    /// Source of per-lineage survival-to-maturity rates.
+    pub registry: &'a LineageRegistry,
+    pub gc_totals: &'a LineageGcReport,
+    pub migration_totals: &'a MigrationTotals,
+    /// Lineages currently migrating.
+    pub migrating: usize,
 }
 
 impl<'a> Widget for AncestryWidget<'a> {
@@ -19,13 +29,32 @@ impl<'a> Widget for AncestryWidget<'a> {
         top_lineages.sort_by(|a, b| b.1.cmp(a.1));
 
         for (id, count) in top_lineages.iter().take(5) {
+            let genotype = self
+                .registry
+                .lineages
+                .get(id)
+                .and_then(|record| record.max_fitness_genotype.as_ref());
+            let [field, charge] = crest_spans(&Crest::new(**id, genotype));
             lines.push(ratatui::text::Line::from(vec![
+                field,
+                charge,
                 ratatui::text::Span::styled(
                     format!(" Dynasty #{} ", &id.to_string()[..4]),
                     Style::default().bg(Color::Blue).fg(Color::White),
                 ),
                 ratatui::text::Span::raw(format!(" ({} alive)", count)),
             ]));
+            if let Some(survival) = self
+                .registry
+                .lineages
+                .get(id)
+                .and_then(|record| record.survival_to_maturity())
+            {
+                lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
+                    format!("   {:.0}% of young reach maturity", survival * 100.0),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
 
             let members: Vec<_> = self
                 .snapshot
@@ -51,6 +80,28 @@ impl<'a> Widget for AncestryWidget<'a> {
             }
             lines.push(ratatui::text::Line::from(""));
         }
+        if self.gc_totals.total() > 0 {
+            lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
+                format!(
+                    " Archived: {} merged, {} pruned",
+                    self.gc_totals.merged,
+                    self.gc_totals.total() - self.gc_totals.merged
+                ),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        if self.migration_totals.started > 0 {
+            lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
+                format!(
+                    " Migrations: {} active, {} arrived | {} plans, {:.0} cells/plan",
+                    self.migrating,
+                    self.migration_totals.arrived,
+                    self.migration_totals.plans,
+                    self.migration_totals.mean_expanded()
+                ),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
         lines.push(ratatui::text::Line::from(" [Shift+A] Export full DOT tree"));
         Paragraph::new(lines).block(tree_block).render(area, buf);
     }
@@ -1,16 +1,37 @@
-use primordium_data::Fossil;
+use super::civilization::crest_spans;
+use primordium_core::crest::Crest;
 use primordium_data::PopulationStats;
+use primordium_data::{Bookmark, Fossil, Monument, Narration};
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, Borders, Paragraph, Widget};
 
 pub struct ArcheologyWidget<'a> {
-    pub snapshots: &'a [(u64, PopulationStats)],
+    /// Tick of the snapshot on display and its stats, once loaded.
+    pub snapshot: Option<(u64, Option<&'a PopulationStats>)>,
     pub index: usize,
-    pub fossils: &'a [Fossil],
+    /// Snapshots on the timeline.
+    pub total: usize,
+    pub next_tick: Option<u64>,
+    /// Fossils passing the browser's filter, in display order.
+    pub fossils: Vec<&'a Fossil>,
     pub selected_fossil_index: usize,
+    /// Current era filter and ranking, shown in the fossil header.
+    pub filter_label: String,
+    /// Bookmarks, ordered by tick; those up to the next snapshot are shown.
+    pub bookmarks: &'a [Bookmark],
+    /// Chronicled narrations up to the snapshot on display, oldest first;
+    /// the latest are shown.
+    pub narrations: &'a [Narration],
+    /// Monuments of extinct lineages, oldest ruin first.
+    pub ruins: &'a [Monument],
 }
 
+/// Chronicle entries shown under the timeline.
+const CHRONICLE_LINES: usize = 4;
+/// Most recent ruins listed under the fossil record.
+const RUIN_LINES: usize = 5;
+
 impl<'a> Widget for ArcheologyWidget<'a> {
     fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
         let arch_block = Block::default()
@@ -18,23 +39,52 @@ impl<'a> Widget for ArcheologyWidget<'a> {
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Rgb(205, 133, 63)));
         let mut lines = Vec::new();
-        if self.snapshots.is_empty() {
-            lines.push(ratatui::text::Line::from(" No history snapshots found. "));
-        } else {
-            let (tick, stats) = &self.snapshots[self.index];
+        if let Some((tick, stats)) = self.snapshot {
             lines.push(ratatui::text::Line::from(format!(
                 " Timeline: Tick {} ({}/{})",
                 tick,
                 self.index + 1,
-                self.snapshots.len()
+                self.total
             )));
+            lines.push(ratatui::text::Line::from(match stats {
+                Some(stats) => format!(
+                    "  Pop: {} | Species: {}",
+                    stats.population, stats.species_count
+                ),
+                None => "  Loading snapshot...".to_string(),
+            }));
+            let next_tick = self.next_tick.unwrap_or(u64::MAX);
+            for bookmark in self
+                .bookmarks
+                .iter()
+                .filter(|b| b.tick >= tick && b.tick < next_tick)
+            {
+                lines.push(ratatui::text::Line::styled(
+                    format!("  🔖 {}: {}", bookmark.tick, bookmark.note),
+                    Style::default().fg(Color::LightBlue),
+                ));
+            }
+        } else {
+            lines.push(ratatui::text::Line::from(" No history snapshots found. "));
+        }
+        if !self.narrations.is_empty() {
             lines.push(ratatui::text::Line::from(format!(
-                "  Pop: {} | Species: {}",
-                stats.population, stats.species_count
+                " 📜 Chronicle ({} so far)",
+                self.narrations.len()
             )));
+            let skip = self.narrations.len().saturating_sub(CHRONICLE_LINES);
+            for narration in &self.narrations[skip..] {
+                lines.push(ratatui::text::Line::styled(
+                    format!("  {}: {}", narration.tick, narration.text),
+                    Style::default().fg(Color::Rgb(222, 184, 135)),
+                ));
+            }
         }
         lines.push(ratatui::text::Line::from(""));
-        lines.push(ratatui::text::Line::from(" 🦴 Fossil Record "));
+        lines.push(ratatui::text::Line::from(format!(
+            " 🦴 Fossil Record ({}) ",
+            self.filter_label
+        )));
         if self.fossils.is_empty() {
             lines.push(ratatui::text::Line::from("  No fossils excavated yet."));
         } else {
@@ -48,17 +98,88 @@ impl<'a> Widget for ArcheologyWidget<'a> {
                         fossil.color_rgb.2,
                     ))
                 };
+                let [field, charge] =
+                    crest_spans(&Crest::new(fossil.lineage_id, Some(&fossil.genotype)));
                 lines.push(ratatui::text::Line::from(vec![
                     ratatui::text::Span::raw(if i == self.selected_fossil_index {
                         " > "
                     } else {
                         "   "
                     }),
+                    field,
+                    charge,
+                    ratatui::text::Span::raw(" "),
                     ratatui::text::Span::styled(&fossil.name, style),
                     ratatui::text::Span::raw(format!(" (Gen: {})", fossil.max_generation)),
                 ]));
+                if i == self.selected_fossil_index {
+                    let m = fossil.morphology();
+                    let detail = Style::default().fg(Color::Gray);
+                    lines.push(ratatui::text::Line::styled(
+                        format!(
+                            "     Era: {} | Climate: {} | Cause: {}",
+                            or_unknown(&fossil.era),
+                            or_unknown(&fossil.climate),
+                            fossil.extinction_cause.label()
+                        ),
+                        detail,
+                    ));
+                    lines.push(ratatui::text::Line::styled(
+                        format!(
+                            "     Brain: {}n/{}c | {} ({:.2}) | Speed/Sense: {:.3}",
+                            m.brain_nodes,
+                            m.brain_connections,
+                            m.diet(),
+                            m.trophic_potential,
+                            m.speed_sense_ratio
+                        ),
+                        detail,
+                    ));
+                    for ruin in self
+                        .ruins
+                        .iter()
+                        .filter(|r| r.lineage_id == fossil.lineage_id)
+                    {
+                        lines.push(ratatui::text::Line::styled(
+                            format!(
+                                "     🗿 Monument at ({}, {}), raised tick {}",
+                                ruin.x, ruin.y, ruin.raised_tick
+                            ),
+                            detail,
+                        ));
+                    }
+                }
+            }
+        }
+        if !self.ruins.is_empty() {
+            lines.push(ratatui::text::Line::from(""));
+            lines.push(ratatui::text::Line::from(format!(
+                " 🗿 Ruins ({}) ",
+                self.ruins.len()
+            )));
+            for ruin in self.ruins.iter().rev().take(RUIN_LINES) {
+                lines.push(ratatui::text::Line::styled(
+                    format!(
+                        "  {} ({}, {}) | civ {} | ticks {}-{}",
+                        ruin.name,
+                        ruin.x,
+                        ruin.y,
+                        ruin.civilization_level,
+                        ruin.raised_tick,
+                        ruin.extinct_tick.unwrap_or(ruin.raised_tick)
+                    ),
+                    Style::default().fg(Color::Gray),
+                ));
             }
         }
         Paragraph::new(lines).block(arch_block).render(area, buf);
     }
 }
+
+fn or_unknown(text: &str) -> &str {
+    if text.is_empty() {
+        "?"
+    } else {
+        text
+    }
+}
@@ -59,6 +59,27 @@ impl<'a> Widget for BrainWidget<'a> {
                     entity.rank
                 )));
 
+                if !entity.effects.is_empty() {
+                    let effects: Vec<String> = entity
+                        .effects
+                        .iter()
+                        .map(|e| {
+                            format!("{} {:.2} ({}t)", e.kind.label(), e.magnitude, e.remaining)
+                        })
+                        .collect();
+                    lines.push(ratatui::text::Line::from(format!(
+                        " Effects: {}",
+                        effects.join(", ")
+                    )));
+                }
+
+                if !entity.tags.is_empty() {
+                    lines.push(ratatui::text::Line::from(format!(
+                        " Tags: {}",
+                        entity.tags.join(", ")
+                    )));
+                }
+
                 lines.push(ratatui::text::Line::from(" Brain Activity:"));
                 let mut out_spans = vec![ratatui::text::Span::raw(" Out: ")];
                 for i in 29..41 {
@@ -0,0 +1,110 @@
+use primordium_core::environment::Environment;
+use primordium_core::schedule::{EventKind, ScheduledEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget};
+
+/// Recent events shown below the upcoming ones.
+const RECENT_ROWS: usize = 6;
+
+pub struct CalendarWidget<'a> {
+    pub env: &'a Environment,
+    pub tick: u64,
+}
+
+fn kind_color(kind: &EventKind) -> Color {
+    match kind {
+        EventKind::SeasonChange => Color::Green,
+        EventKind::Disaster { .. } => Color::Red,
+        EventKind::Fossil => Color::Yellow,
+        EventKind::PowerGrid => Color::Cyan,
+    }
+}
+
+impl CalendarWidget<'_> {
+    fn label(&self, event: &ScheduledEvent, upcoming: bool) -> String {
+        match event.kind {
+            EventKind::SeasonChange => {
+                let season = if upcoming {
+                    self.env.next_season
+                } else {
+                    self.env.current_season
+                };
+                format!("{} → {}", event.kind.name(), season.name())
+            }
+            _ => event.kind.name().to_string(),
+        }
+    }
+
+    fn row(&self, event: &ScheduledEvent, when: String, upcoming: bool) -> Line<'static> {
+        let mut spans = vec![
+            Span::styled(
+                format!(" {:<8}", when),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                self.label(event, upcoming),
+                Style::default().fg(kind_color(&event.kind)),
+            ),
+        ];
+        if event.is_recurring() {
+            spans.push(Span::styled(
+                format!(" ↻{}", event.every),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Widget for CalendarWidget<'_> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let block = Block::default()
+            .title(" 📅 Calendar ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green));
+        let schedule = &self.env.schedule;
+        let heading = |text: &'static str| {
+            Line::from(Span::styled(
+                text,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        };
+
+        let mut lines = vec![
+            Line::from(format!(" Tick {}", self.tick)),
+            Line::from(""),
+            heading(" Upcoming"),
+        ];
+        let capacity = (area.height as usize).saturating_sub(lines.len() + RECENT_ROWS + 5);
+        for event in schedule.upcoming().iter().take(capacity) {
+            let when = format!("in {}", event.tick.saturating_sub(self.tick));
+            lines.push(self.row(event, when, true));
+        }
+        if schedule.upcoming().is_empty() {
+            lines.push(Line::from(Span::styled(
+                " Nothing scheduled",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading(" Recent"));
+        let mut recent = schedule.recent().take(RECENT_ROWS).peekable();
+        if recent.peek().is_none() {
+            lines.push(Line::from(Span::styled(
+                " Nothing yet",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for event in recent {
+            let when = format!("t-{}", self.tick.saturating_sub(event.tick));
+            lines.push(self.row(event, when, false));
+        }
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
@@ -1,10 +1,137 @@
+use crate::renderer::WorldWidget;
+use primordium_core::civilization::{OutpostChallenge, OutpostNetwork};
+use primordium_core::crest::Crest;
 use primordium_core::lineage_registry::LineageRegistry;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::{Block, Borders, Paragraph, Widget};
+use uuid::Uuid;
+
+/// Flows smaller than this are drawn as idle supply lines.
+const IDLE_FLOW: f32 = 0.05;
+
+/// Stable display colour for a lineage.
+pub fn lineage_color(id: Uuid) -> Color {
+    let b = id.as_bytes();
+    Color::Rgb(b[0] | 0x60, b[1] | 0x60, b[2] | 0x60)
+}
+
+/// A lineage's crest as two cells: the divided field, then the charge.
+pub fn crest_spans(crest: &Crest) -> [ratatui::text::Span<'static>; 2] {
+    let rgb = |[r, g, b]: [u8; 3]| Color::Rgb(r, g, b);
+    let [field, charge] = crest.emblem();
+    [
+        ratatui::text::Span::styled(
+            field.to_string(),
+            Style::default()
+                .fg(rgb(crest.tincture))
+                .bg(rgb(crest.accent)),
+        ),
+        ratatui::text::Span::styled(charge.to_string(), Style::default().fg(rgb(crest.tincture))),
+    ]
+}
+
+/// Outpost glyph, growing with the stored energy.
+pub fn outpost_glyph(energy_store: f32) -> &'static str {
+    match energy_store {
+        e if e < 50.0 => "∘",
+        e if e < 200.0 => "o",
+        e if e < 500.0 => "O",
+        _ => "◉",
+    }
+}
 
 pub struct CivilizationWidget<'a> {
     pub registry: &'a LineageRegistry,
+    pub network: &'a OutpostNetwork,
+    pub contested: &'a [OutpostChallenge],
+}
+
+impl CivilizationWidget<'_> {
+    fn lineage_name(&self, id: Uuid) -> String {
+        self.registry
+            .lineages
+            .get(&id)
+            .map_or_else(|| id.to_string()[..8].to_string(), |r| r.name.clone())
+    }
+
+    fn crest(&self, id: Uuid) -> Crest {
+        let genotype = self
+            .registry
+            .lineages
+            .get(&id)
+            .and_then(|r| r.max_fitness_genotype.as_ref());
+        Crest::new(id, genotype)
+    }
+
+    /// Outpost count, stored energy, links and flow per owning lineage.
+    fn network_lines(&self) -> Vec<ratatui::text::Line<'static>> {
+        let mut owners: Vec<Uuid> = self.network.nodes.iter().filter_map(|n| n.owner).collect();
+        owners.sort_unstable();
+        owners.dedup();
+
+        let mut lines = vec![ratatui::text::Line::from(ratatui::text::Span::styled(
+            " Outpost Networks",
+            Style::default().add_modifier(Modifier::BOLD),
+        ))];
+        if owners.is_empty() {
+            lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
+                "  No outposts founded yet",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for id in owners {
+            let (count, store) = self
+                .network
+                .nodes
+                .iter()
+                .filter(|n| n.owner == Some(id))
+                .fold((0, 0.0), |(c, s), n| (c + 1, s + n.energy_store));
+            let (links, flow) = self
+                .network
+                .links_of(id)
+                .fold((0, 0.0), |(c, f), l| (c + 1, f + l.flow.abs()));
+            let [field, charge] = crest_spans(&self.crest(id));
+            lines.push(ratatui::text::Line::from(vec![
+                ratatui::text::Span::raw("  "),
+                field,
+                charge,
+                ratatui::text::Span::styled(
+                    format!(" {} ", self.lineage_name(id)),
+                    Style::default().fg(lineage_color(id)),
+                ),
+                ratatui::text::Span::raw(format!(
+                    "{} outposts | store {:.0} | {} links | flow {:.1}/t",
+                    count, store, links, flow
+                )),
+            ]));
+        }
+
+        for challenge in self.contested {
+            let Some(node) = self.network.node_at(challenge.idx) else {
+                continue;
+            };
+            let (marker, color) = if challenge.is_imminent() {
+                ("⚠ FALLING", Color::Red)
+            } else {
+                ("⚠ contested", Color::Yellow)
+            };
+            lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
+                format!(
+                    "  {} ({}, {}) {} vs {} x{:.1}",
+                    marker,
+                    node.x,
+                    node.y,
+                    self.lineage_name(challenge.owner),
+                    self.lineage_name(challenge.challenger),
+                    challenge.ratio
+                ),
+                Style::default().fg(color),
+            )));
+        }
+        lines.push(ratatui::text::Line::from(""));
+        lines
+    }
 }
 
 impl<'a> Widget for CivilizationWidget<'a> {
@@ -14,7 +141,7 @@ impl<'a> Widget for CivilizationWidget<'a> {
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Yellow));
 
-        let mut lines = Vec::new();
+        let mut lines = self.network_lines();
         let top_lineages = self.registry.get_top_lineages(5);
 
         if top_lineages.is_empty() {
@@ -22,9 +149,12 @@ impl<'a> Widget for CivilizationWidget<'a> {
                 " No dominant civilizations detected. ",
             ));
         } else {
-            for (_id, record) in top_lineages {
+            for (id, record) in top_lineages {
                 let color = Color::Cyan;
+                let [field, charge] = crest_spans(&self.crest(*id));
                 lines.push(ratatui::text::Line::from(vec![
+                    field,
+                    charge,
                     ratatui::text::Span::styled(
                         format!(" {} ", record.name),
                         Style::default()
@@ -91,3 +221,100 @@ impl<'a> Widget for CivilizationWidget<'a> {
         Paragraph::new(lines).block(civ_block).render(area, buf);
     }
 }
+
+/// Draws the outpost network over the world map: supply lines between
+/// linked outposts with moving flow arrows, outposts sized by their energy
+/// store, and contested outposts highlighted.
+pub struct OutpostNetworkOverlay<'a> {
+    pub network: &'a OutpostNetwork,
+    pub contested: &'a [OutpostChallenge],
+    pub tick: u64,
+    pub screensaver: bool,
+}
+
+impl OutpostNetworkOverlay<'_> {
+    pub(crate) fn arrow(dx: i32, dy: i32) -> &'static str {
+        match (dx.signum(), dy.signum()) {
+            (1, 0) => "→",
+            (-1, 0) => "←",
+            (0, 1) => "↓",
+            (0, -1) => "↑",
+            (1, 1) => "↘",
+            (-1, 1) => "↙",
+            (1, -1) => "↗",
+            _ => "↖",
+        }
+    }
+
+    /// Cells strictly between two points, walked from `a` to `b`.
+    pub(crate) fn line_cells(a: (i32, i32), b: (i32, i32)) -> Vec<(i32, i32)> {
+        let (dx, dy) = ((b.0 - a.0).abs(), -(b.1 - a.1).abs());
+        let (sx, sy) = ((b.0 - a.0).signum(), (b.1 - a.1).signum());
+        let (mut x, mut y, mut err) = (a.0, a.1, dx + dy);
+        let mut cells = Vec::new();
+        while (x, y) != b {
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+            if (x, y) != b {
+                cells.push((x, y));
+            }
+        }
+        cells
+    }
+}
+
+impl Widget for OutpostNetworkOverlay<'_> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let to_screen = |x: i32, y: i32| {
+            WorldWidget::world_to_screen(f64::from(x), f64::from(y), area, self.screensaver)
+        };
+
+        for link in &self.network.links {
+            let (from, to) = (&self.network.nodes[link.from], &self.network.nodes[link.to]);
+            let (mut a, mut b) = ((from.x as i32, from.y as i32), (to.x as i32, to.y as i32));
+            if link.flow < 0.0 {
+                std::mem::swap(&mut a, &mut b);
+            }
+            let color = from.owner.map_or(Color::DarkGray, lineage_color);
+            let flowing = link.flow.abs() >= IDLE_FLOW;
+            let arrow = Self::arrow(b.0 - a.0, b.1 - a.1);
+            for (step, (x, y)) in Self::line_cells(a, b).into_iter().enumerate() {
+                let Some((sx, sy)) = to_screen(x, y) else {
+                    continue;
+                };
+                let cell = &mut buf[(sx, sy)];
+                // Every fourth cell carries an arrow that advances with time.
+                if flowing && (step as u64 + 4 - self.tick % 4).is_multiple_of(4) {
+                    cell.set_symbol(arrow).set_fg(Color::Yellow);
+                } else {
+                    cell.set_symbol("·").set_fg(color);
+                }
+            }
+        }
+
+        for node in &self.network.nodes {
+            let Some((sx, sy)) = to_screen(node.x as i32, node.y as i32) else {
+                continue;
+            };
+            let cell = &mut buf[(sx, sy)];
+            cell.set_symbol(outpost_glyph(node.energy_store))
+                .set_fg(node.owner.map_or(Color::DarkGray, lineage_color));
+            if let Some(challenge) = self.contested.iter().find(|c| c.idx == node.idx) {
+                let bg = if challenge.is_imminent() {
+                    Color::Red
+                } else {
+                    Color::Rgb(120, 90, 0)
+                };
+                cell.set_bg(bg)
+                    .set_style(Style::default().add_modifier(Modifier::BOLD));
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use primordium_core::introspection::{ArchetypeKind, EcsReport};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget, Wrap};
+
+/// Optional components worth a headline count.
+const OPTIONAL_COMPONENTS: [&str; 1] = ["Tags"];
+
+pub struct EcsReportWidget<'a> {
+    pub report: &'a EcsReport,
+}
+
+fn kind_color(kind: ArchetypeKind) -> Color {
+    match kind {
+        ArchetypeKind::Organism => Color::Green,
+        ArchetypeKind::Food => Color::Yellow,
+        ArchetypeKind::Egg => Color::Magenta,
+        ArchetypeKind::Other => Color::Gray,
+    }
+}
+
+impl<'a> Widget for EcsReportWidget<'a> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let block = Block::default()
+            .title(" 🧩 ECS Archetypes ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let report = self.report;
+
+        let mut lines = vec![Line::from(format!(
+            " {} entities in {} archetypes",
+            report.entities,
+            report.archetypes.len()
+        ))];
+        let optional: Vec<String> = OPTIONAL_COMPONENTS
+            .iter()
+            .map(|name| format!("{} {}", name, report.count_with(name)))
+            .collect();
+        lines.push(Line::from(Span::styled(
+            format!(" Optional: {}", optional.join(", ")),
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        let incomplete: u32 = report.incomplete().map(|a| a.count).sum();
+        if incomplete > 0 {
+            lines.push(Line::from(Span::styled(
+                format!(" ⚠ {} organisms missing components", incomplete),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )));
+        }
+        lines.push(Line::from(""));
+
+        for archetype in &report.archetypes {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(" {:<9}", archetype.kind.label()),
+                    Style::default().fg(kind_color(archetype.kind)),
+                ),
+                Span::styled(
+                    format!("{:>6}", archetype.count),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            let mut components = archetype.components.join(" ");
+            if archetype.unknown > 0 {
+                components.push_str(&format!(" +{} unknown", archetype.unknown));
+            }
+            lines.push(Line::from(Span::styled(
+                format!("   {}", components),
+                Style::default().fg(Color::DarkGray),
+            )));
+            if !archetype.missing.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    format!("   missing: {}", archetype.missing.join(" ")),
+                    Style::default().fg(Color::Red),
+                )));
+            }
+        }
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(block)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,267 @@
+use primordium_core::forecast::{
+    Forecast, ForecastPoint, ForecastSample, Forecaster, Projection, TrendModel, MIN_SAMPLES,
+};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::Marker;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Widget};
+
+/// Projected population and food with their confidence bands, the carrying
+/// capacity and any famine on the horizon.
+pub struct ForecastWidget<'a> {
+    pub forecaster: &'a Forecaster,
+    pub tick: u64,
+}
+
+/// Chart series of one quantity: its history, projection and band edges.
+struct Series {
+    history: Vec<(f64, f64)>,
+    mean: Vec<(f64, f64)>,
+    low: Vec<(f64, f64)>,
+    high: Vec<(f64, f64)>,
+}
+
+impl Series {
+    fn new(
+        samples: &[ForecastSample],
+        value: fn(&ForecastSample) -> f64,
+        projection: &Projection,
+    ) -> Self {
+        let history: Vec<(f64, f64)> = samples.iter().map(|s| (s.tick as f64, value(s))).collect();
+        // Projections start from the last sample so the lines join up.
+        let from = history.last().copied().into_iter();
+        let along = |f: fn(&ForecastPoint) -> f64| {
+            from.clone()
+                .chain(projection.points.iter().map(|p| (p.tick as f64, f(p))))
+                .collect()
+        };
+        Self {
+            mean: along(|p| p.mean),
+            low: along(|p| p.low),
+            high: along(|p| p.high),
+            history,
+        }
+    }
+
+    fn max(&self) -> f64 {
+        self.history
+            .iter()
+            .chain(&self.high)
+            .map(|(_, y)| *y)
+            .fold(1.0, f64::max)
+    }
+}
+
+fn chart<'a>(
+    title: &'a str,
+    series: &'a Series,
+    color: Color,
+    capacity: Option<&'a [(f64, f64)]>,
+) -> Chart<'a> {
+    let (start, end) = (
+        series.history.first().map_or(0.0, |p| p.0),
+        series.mean.last().map_or(1.0, |p| p.0),
+    );
+    let top = capacity
+        .and_then(|line| line.first())
+        .map_or(series.max(), |k| series.max().max(k.1))
+        * 1.05;
+    let line = |data: &'a [(f64, f64)], style: Style| {
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(style)
+            .data(data)
+    };
+    let band = Style::default().fg(Color::DarkGray);
+    let mut datasets = vec![
+        line(&series.low, band),
+        line(&series.high, band),
+        line(&series.history, Style::default().fg(color)),
+        line(&series.mean, Style::default().fg(Color::White)),
+    ];
+    if let Some(capacity) = capacity {
+        datasets.push(line(capacity, Style::default().fg(Color::Magenta)));
+    }
+    Chart::new(datasets)
+        .block(Block::default().title(Span::styled(title, Style::default().fg(color))))
+        .x_axis(Axis::default().bounds([start, end]))
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, top])
+                .labels([Span::raw("0"), Span::raw(format!("{:.0}", top))]),
+        )
+}
+
+fn model_label(projection: &Projection) -> String {
+    match projection.model {
+        TrendModel::Logistic { rate, .. } => format!("logistic r={:.4}/t", rate),
+        TrendModel::Arima { drift, .. } => format!("ARIMA {:+.3}/t", drift),
+    }
+}
+
+fn outlook(label: &str, projection: &Projection, now: f64, precision: usize) -> Line<'static> {
+    let Some(end) = projection.end() else {
+        return Line::from("");
+    };
+    Line::from(vec![
+        Span::raw(format!(
+            " {:<6}{:.p$} → {:.p$} ({:.p$}–{:.p$})",
+            label,
+            now,
+            end.mean,
+            end.low,
+            end.high,
+            p = precision
+        )),
+        Span::styled(
+            format!(" {}", model_label(projection)),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+impl ForecastWidget<'_> {
+    fn summary(&self, forecast: &Forecast, last: &ForecastSample) -> Vec<Line<'static>> {
+        let horizon = forecast
+            .population
+            .end()
+            .map_or(0, |p| p.tick.saturating_sub(forecast.tick));
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(" Next {} ticks, 95% bands", horizon),
+                Style::default().fg(Color::DarkGray),
+            )),
+            outlook("Pop", &forecast.population, last.population, 0),
+            outlook("Food", &forecast.food, last.food, 0),
+            outlook("Soil", &forecast.fertility, last.fertility, 2),
+        ];
+        lines.push(match forecast.carrying_capacity {
+            Some(k) => Line::from(Span::styled(
+                format!(" Carrying capacity ≈ {:.0}", k),
+                Style::default().fg(Color::Magenta),
+            )),
+            None => Line::from(Span::styled(
+                " Carrying capacity: no limit in sight",
+                Style::default().fg(Color::DarkGray),
+            )),
+        });
+        lines.push(match forecast.famine {
+            Some(famine) => {
+                let (text, color) = if famine.likely {
+                    ("Famine likely", Color::Red)
+                } else {
+                    ("Famine risk", Color::Yellow)
+                };
+                Line::from(Span::styled(
+                    format!(
+                        " ⚠ {} by tick {} (in {})",
+                        text,
+                        famine.tick,
+                        famine.tick.saturating_sub(self.tick)
+                    ),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ))
+            }
+            None => Line::from(Span::styled(
+                " No famine forecast",
+                Style::default().fg(Color::Green),
+            )),
+        });
+        lines
+    }
+}
+
+impl Widget for ForecastWidget<'_> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let block = Block::default()
+            .title(" 🔮 Forecast ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let samples: Vec<ForecastSample> = self.forecaster.samples.iter().copied().collect();
+        let (Some(forecast), Some(last)) = (&self.forecaster.latest, samples.last()) else {
+            Paragraph::new(Line::from(Span::styled(
+                format!(
+                    " Gathering history ({}/{} samples)",
+                    samples.len(),
+                    MIN_SAMPLES
+                ),
+                Style::default().fg(Color::DarkGray),
+            )))
+            .render(inner, buf);
+            return;
+        };
+
+        let summary = self.summary(forecast, last);
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(summary.len() as u16 + 1),
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ])
+            .split(inner);
+        Paragraph::new(summary).render(layout[0], buf);
+
+        let population = Series::new(&samples, |s| s.population, &forecast.population);
+        let capacity: Option<Vec<(f64, f64)>> = forecast.carrying_capacity.map(|k| {
+            let start = population.history.first().map_or(0.0, |p| p.0);
+            let end = population.mean.last().map_or(start, |p| p.0);
+            vec![(start, k), (end, k)]
+        });
+        chart(
+            " Population ",
+            &population,
+            Color::Green,
+            capacity.as_deref(),
+        )
+        .render(layout[1], buf);
+
+        let food = Series::new(&samples, |s| s.food, &forecast.food);
+        chart(" Food ", &food, Color::Yellow, None).render(layout[2], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primordium_core::config::ForecastConfig;
+    use ratatui::buffer::Buffer;
+
+    fn text(forecaster: &Forecaster) -> String {
+        let area = Rect::new(0, 0, 60, 40);
+        let mut buf = Buffer::empty(area);
+        ForecastWidget {
+            forecaster,
+            tick: forecaster.samples.back().map_or(0, |s| s.tick),
+        }
+        .render(area, &mut buf);
+        buf.content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn test_panel_warns_of_a_famine_once_it_has_history() {
+        let config = ForecastConfig::default();
+        let mut forecaster = Forecaster::default();
+        for i in 0..MIN_SAMPLES as u64 {
+            assert!(text(&forecaster).contains(&format!("({}/{} samples)", i, MIN_SAMPLES)));
+            let t = i as f64;
+            let sample = ForecastSample {
+                tick: i * config.sample_interval,
+                population: 100.0 + (t * 1.3).sin(),
+                food: 60.0 - 4.0 * t + (t * 0.7).sin(),
+                fertility: 0.5,
+            };
+            forecaster.observe(sample, &config);
+        }
+
+        let text = text(&forecaster);
+        assert!(text.contains("Famine likely by tick"));
+        assert!(text.contains("Population"));
+        assert!(text.contains("Food"));
+    }
+}
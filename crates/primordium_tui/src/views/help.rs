@@ -16,19 +16,43 @@ impl HelpWidget {
                 " [Q]       Quit simulation",
                 " [Space]   Pause / Resume",
                 " [z/Z]     Toggle Cinematic Mode",
+                " [Tab]     Cycle layout preset",
+                " [U]       Layout editor (resize/hide panes)",
                 " [b]       Toggle Brain panel",
                 " [a]       Toggle Ancestry Tree",
+                " [Ctrl+A]  Export phylogeny (Newick + PhyloXML)",
+                " [H]       Phylogeny browser (fold, follow lineage)",
+                " [e]       Toggle Energy Ledger (selected)",
+                " [t]       Toggle Route Trail (selected)",
+                " [g]       Toggle Weather Palette (climate tint)",
+                " [d]       Toggle Perception overlay (selected)",
+                " [~]       Toggle ECS Archetypes debug panel",
+                " [Y]       Toggle Calendar (scheduled events)",
+                " [?]       Toggle Forecast (population, food, famines)",
                 " [y]       Toggle Archeology Tool",
                 " [+/-]     Speed up / Slow down (or edit gene)",
                 " [[/]]     Archeology Seek (Time)",
                 " [↑/↓]     Fossil Select (in Archeology)",
+                " [←/→]     Fossil Era Filter (in Archeology)",
+                " [s]       Fossil Sort Metric (in Archeology)",
                 " [g/G]     Resurrect Fossil (Cloning)",
-                " [1-8]     Switch View modes",
+                " [n]       Bookmark current tick with a note",
+                " [N]       Bookmark list (Enter jumps in time)",
+                " [E]       Narrations (Enter shows the data behind one)",
+                " [*]       Tag / untag selected entity",
+                " [/]       Search by tag (highlight cohort)",
+                " [1-9]     Switch View modes",
                 " [j/J]     Toggle Social Brush (Peace/War)",
                 " [h]       Toggle this Help",
                 " [x/X]     Genetic Surge (mutate all)",
                 " [c]       Export selected DNA",
                 " [v/V]     Import DNA from file",
+                " [Ctrl+C]  Copy selected DNA to clipboard",
+                " [Ctrl+V]  Spawn from clipboard DNA",
+                " [Ctrl+E]  Export selected creature card (PNG + text)",
+                " [Ctrl+T]  Export selected route trail (CSV)",
+                " [M]       Marketplace browser",
+                " [D]       Pathogen designer",
                 "",
                 " ⏺️  RECORDING & REPLAY",
                 " ─────────────────────────────────",
@@ -96,22 +120,22 @@ impl HelpWidget {
                 "",
                 " 📜 WORLD ERAS",
                 " ─────────────────────────────────",
-                " 🌀 Primordial  - Chaos adaptation",
+                " 🌀 Primordial    - Chaos adaptation",
                 "",
-                " 🌱 DawnOfLife  - Stability or",
+                " 🌱 Expansion     - Stability or",
                 "    High Herbivore Biomass",
+                "    Mutation and food x1.2",
                 "",
-                " 🌸 Flourishing - Biodiversity",
-                "    hotspots and healthy pop",
-                "",
-                " ⚔️  DominanceWar - High Carbon",
+                " ⚔️  Strife        - High Carbon",
                 "    or Predator dominance",
+                "    Disasters x2, food x0.7",
                 "",
-                " 👑 ApexEra     - Fitness > 8000",
+                " 💡 Enlightenment - Fitness > 8000",
+                "    Mutation x0.6, disasters x0.5",
             ],
             4 => vec![
                 "",
-                " 👁️  VISUALIZATION MODES [1-8]",
+                " 👁️  VISUALIZATION MODES [1-0, )]",
                 " ─────────────────────────────────",
                 " [1] Normal      - Default view",
                 " [2] Fertility   - Soil health (G:Healthy, R:Depleted)",
@@ -121,6 +145,9 @@ impl HelpWidget {
                 " [6] Market      - Multiverse trade offers",
                 " [7] Research    - Neural plasticity heatmap",
                 " [8] Civilization- Global Dynasty dashboard",
+                " [9] Pheromones  - Scent channels; press again to cycle",
+                " [0] Hydration   - Standing water; parched organisms in brown",
+                " [)] Mutation    - Where newborns differ most from parents",
                 "",
                 " 🪖  SPECIAL INDICATORS",
                 " ─────────────────────────────────",
@@ -149,6 +176,11 @@ impl HelpWidget {
                 " View mode [8] tracks the macro-progress of",
                 " the most successful lineages (Dynasties).",
                 "",
+                " Ψ Outpost network: ∘ o O ◉ grow with the",
+                "    energy store, · lines are grid links and",
+                "    moving arrows show supply flowing.",
+                "    Amber/red outposts are contested/falling.",
+                "",
                 " 🏆 Levels: Ownership of outposts and high",
                 "    population grants civilization buffs.",
                 "",
@@ -259,12 +291,15 @@ mod tests {
             "[B]",
             "[a]",
             "[A]",
+            "[e]",
+            "[d]",
+            "[~]",
             "[y]",
             "[+/-]",
             "[[/]]",
             "[↑/↓]",
             "[g/G]",
-            "[1-8]",
+            "[1-9]",
             "[j/J]",
             "[h]",
             "[x/X]",
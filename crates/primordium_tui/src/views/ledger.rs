@@ -0,0 +1,89 @@
+use primordium_core::energy_ledger::EnergyLedger;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget};
+
+pub struct EnergyLedgerWidget<'a> {
+    pub ledger: Option<&'a EnergyLedger>,
+    pub tick: u64,
+}
+
+fn delta_color(delta: f64) -> Color {
+    if delta >= 0.0 {
+        Color::Green
+    } else {
+        Color::Red
+    }
+}
+
+impl<'a> Widget for EnergyLedgerWidget<'a> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let block = Block::default()
+            .title(" ⚡ Energy Ledger ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        let Some(ledger) = self.ledger else {
+            Paragraph::new(" Select an entity to record its energy ledger.")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block)
+                .render(area, buf);
+            return;
+        };
+
+        let mut lines = vec![
+            Line::from(format!(
+                " Entity {} | last {} ticks",
+                &ledger.entity_id.to_string()[..8],
+                ledger.ticks
+            )),
+            Line::from(""),
+        ];
+
+        for (source, total) in ledger.totals() {
+            lines.push(Line::from(vec![
+                Span::raw(format!(" {:<13}", source.label())),
+                Span::styled(
+                    format!("{:>+9.2}", total),
+                    Style::default().fg(delta_color(total)),
+                ),
+            ]));
+        }
+        let net = ledger.net();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {:<13}", "Net"),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{:>+9.2}", net),
+                Style::default()
+                    .fg(delta_color(net))
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Recent entries",
+            Style::default().fg(Color::Cyan),
+        )));
+
+        let capacity = (area.height as usize).saturating_sub(lines.len() + 2);
+        for entry in ledger.entries().iter().rev().take(capacity) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(" t-{:<4}", self.tick.saturating_sub(entry.tick)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(format!("{:<13}", entry.source.label())),
+                Span::styled(
+                    format!("{:>+9.3}", entry.delta),
+                    Style::default().fg(delta_color(entry.delta)),
+                ),
+            ]));
+        }
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
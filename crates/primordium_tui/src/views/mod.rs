@@ -1,11 +1,18 @@
 pub mod ancestry;
 pub mod archeology;
 pub mod brain;
+pub mod calendar;
 pub mod chronicle;
 pub mod civilization;
+pub mod ecs;
+pub mod forecast;
 pub mod help;
 pub mod hof;
+pub mod ledger;
 pub mod market;
+pub mod network;
+pub mod perception;
+pub mod phylogeny;
 pub mod registry;
 pub mod research;
 pub mod sparklines;
@@ -14,12 +21,22 @@ pub mod status;
 pub use ancestry::AncestryWidget;
 pub use archeology::ArcheologyWidget;
 pub use brain::BrainWidget;
+pub use calendar::CalendarWidget;
 pub use chronicle::ChronicleWidget;
-pub use civilization::CivilizationWidget;
+pub use civilization::{CivilizationWidget, OutpostNetworkOverlay};
+pub use ecs::EcsReportWidget;
+pub use forecast::ForecastWidget;
 pub use help::HelpWidget;
+pub use ledger::EnergyLedgerWidget;
 pub mod overlays;
 pub use market::MarketWidget;
-pub use overlays::{CinematicOverlayWidget, LegendWidget};
+pub use network::NetworkWidget;
+pub use overlays::{
+    AttractCaptionWidget, CinematicOverlayWidget, GuardrailPromptWidget, LegendWidget,
+    ProfileRecordsWidget, StartMenuWidget,
+};
+pub use perception::PerceptionOverlay;
+pub use phylogeny::PhylogenyWidget;
 pub use registry::{draw_registry, RegistryWidget};
 pub use research::ResearchWidget;
 pub use sparklines::SparklinesWidget;
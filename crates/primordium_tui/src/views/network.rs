@@ -0,0 +1,40 @@
+use primordium_net::NetworkState;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget};
+
+/// Relay connection, migration counters and known peers.
+pub struct NetworkWidget<'a> {
+    pub state: &'a NetworkState,
+}
+
+impl<'a> Widget for NetworkWidget<'a> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let block = Block::default()
+            .title(" 🌐 Network ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue));
+        let mut lines = vec![
+            ratatui::text::Line::from(match self.state.client_id {
+                Some(id) => format!(" Online as #{}", &id.to_string()[..8]),
+                None => " Offline".to_string(),
+            }),
+            ratatui::text::Line::from(format!(
+                " Migrants: {} sent | {} received",
+                self.state.migrations_sent, self.state.migrations_received
+            )),
+            ratatui::text::Line::from(""),
+            ratatui::text::Line::from(format!(" Peers ({})", self.state.peers.len())),
+        ];
+        for peer in &self.state.peers {
+            lines.push(ratatui::text::Line::from(format!(
+                "  #{}  pop {}  ⇄ {}/{}",
+                &peer.peer_id.to_string()[..8],
+                peer.entity_count,
+                peer.migrations_sent,
+                peer.migrations_received
+            )));
+        }
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
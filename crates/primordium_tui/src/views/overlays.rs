@@ -82,3 +82,218 @@ impl Widget for CinematicOverlayWidget {
         Paragraph::new(footer_text).render(footer_area, buf);
     }
 }
+
+pub struct GuardrailPromptWidget<'a> {
+    pub breaker: &'a str,
+    pub observed: usize,
+    pub limit: usize,
+    pub tick: u64,
+    pub suspected_source: &'a str,
+}
+
+impl<'a> Widget for GuardrailPromptWidget<'a> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let prompt_width = 70.min(area.width.saturating_sub(4));
+        let prompt_height = 11.min(area.height.saturating_sub(4));
+        let prompt_area = Rect::new(
+            area.x + (area.width - prompt_width) / 2,
+            area.y + (area.height - prompt_height) / 2,
+            prompt_width,
+            prompt_height,
+        );
+
+        Clear.render(prompt_area, buf);
+        let prompt_text = vec![
+            ratatui::text::Line::from(ratatui::text::Span::styled(
+                " Simulation paused by circuit breaker ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )),
+            ratatui::text::Line::from(""),
+            ratatui::text::Line::from(format!(
+                " {}: {} exceeded limit {} at tick {}",
+                self.breaker, self.observed, self.limit, self.tick
+            )),
+            ratatui::text::Line::from(""),
+            ratatui::text::Line::from(" Suspected source:"),
+            ratatui::text::Line::from(format!(" {}", self.suspected_source)),
+            ratatui::text::Line::from(""),
+            ratatui::text::Line::from(" [C] Continue (double limit)  [Q] Quit "),
+        ];
+
+        Paragraph::new(prompt_text)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .block(
+                Block::default()
+                    .title(" Guardrail ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Red)),
+            )
+            .render(prompt_area, buf);
+    }
+}
+
+/// Title bar and current chronicle caption shown during attract mode.
+pub struct AttractCaptionWidget<'a> {
+    pub title: &'a str,
+    pub tick: u64,
+    pub caption: Option<&'a str>,
+}
+
+impl<'a> Widget for AttractCaptionWidget<'a> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        if area.width < 8 || area.height < 4 {
+            return;
+        }
+        let title_area = Rect::new(area.x + 2, area.y + 1, area.width - 4, 1);
+        Paragraph::new(ratatui::text::Line::from(vec![
+            ratatui::text::Span::styled(
+                " PRIMORDIUM ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            ratatui::text::Span::raw(format!(" {} | Tick: {} ", self.title, self.tick)),
+        ]))
+        .render(title_area, buf);
+
+        if let Some(caption) = self.caption {
+            let caption_area = Rect::new(
+                area.x + 2,
+                area.bottom().saturating_sub(4),
+                area.width - 4,
+                3,
+            );
+            Clear.render(caption_area, buf);
+            Paragraph::new(caption)
+                .alignment(ratatui::layout::Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(ratatui::widgets::BorderType::Rounded)
+                        .border_style(Style::default().fg(Color::DarkGray)),
+                )
+                .render(caption_area, buf);
+        }
+    }
+}
+
+/// Player records panel shown beside the start menu.
+pub struct ProfileRecordsWidget<'a> {
+    pub runs: u32,
+    pub total_ticks: u64,
+    pub max_population: usize,
+    /// Name and lifespan in ticks of the longest-lived lineage.
+    pub longest_lineage: Option<(&'a str, u64)>,
+    /// Every achievement title with whether it has been earned.
+    pub achievements: Vec<(&'a str, bool)>,
+}
+
+impl<'a> Widget for ProfileRecordsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let label = Style::default().fg(Color::DarkGray);
+        let row = |name: &'static str, value: String| {
+            ratatui::text::Line::from(vec![
+                ratatui::text::Span::styled(format!(" {:<18}", name), label),
+                ratatui::text::Span::raw(value),
+            ])
+        };
+        let earned = self.achievements.iter().filter(|(_, e)| *e).count();
+
+        let mut lines = vec![
+            ratatui::text::Line::from(""),
+            row("Runs", self.runs.to_string()),
+            row("Ticks simulated", self.total_ticks.to_string()),
+            row("Peak population", self.max_population.to_string()),
+            row(
+                "Longest lineage",
+                self.longest_lineage
+                    .map_or("-".to_string(), |(name, ticks)| {
+                        format!("{} ({} ticks)", name, ticks)
+                    }),
+            ),
+            ratatui::text::Line::from(""),
+            ratatui::text::Line::from(ratatui::text::Span::styled(
+                format!(" Achievements {}/{}", earned, self.achievements.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+        ];
+        lines.extend(self.achievements.iter().map(|(title, earned)| {
+            if *earned {
+                ratatui::text::Line::from(ratatui::text::Span::styled(
+                    format!("  ★ {}", title),
+                    Style::default().fg(Color::Yellow),
+                ))
+            } else {
+                ratatui::text::Line::from(ratatui::text::Span::styled(
+                    format!("  ☆ {}", title),
+                    label,
+                ))
+            }
+        }));
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(" Your Records ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            )
+            .render(area, buf);
+    }
+}
+
+/// Rows of the start menu or one of its sub-pages.
+pub struct StartMenuWidget<'a> {
+    pub title: &'a str,
+    /// Row text and whether the row can be chosen.
+    pub rows: Vec<(String, bool)>,
+    pub selected: usize,
+    pub hint: &'a str,
+    pub error: Option<&'a str>,
+}
+
+impl<'a> Widget for StartMenuWidget<'a> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let mut lines = vec![ratatui::text::Line::from("")];
+        lines.extend(self.rows.iter().enumerate().map(|(i, (text, enabled))| {
+            let style = if i == self.selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else if *enabled {
+                Style::default()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let marker = if i == self.selected { "›" } else { " " };
+            ratatui::text::Line::from(ratatui::text::Span::styled(
+                format!(" {} {} ", marker, text),
+                style,
+            ))
+        }));
+        lines.push(ratatui::text::Line::from(""));
+        if let Some(error) = self.error {
+            lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
+                format!(" {}", error),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
+            format!(" {}", self.hint),
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .block(
+                Block::default()
+                    .title(format!(" {} ", self.title))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,264 @@
+use super::civilization::OutpostNetworkOverlay;
+use crate::renderer::WorldWidget;
+use primordium_core::perception::{Perception, PHEROMONE_INPUTS};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+
+/// Gradients weaker than this are drawn as a dot rather than an arrow.
+const FLAT_GRADIENT: f32 = 0.005;
+/// Cells the kin arrow extends from the entity.
+const KIN_ARROW_LENGTH: f64 = 4.0;
+
+/// Draws what the selected entity senses over the world canvas: its
+/// sensing radius, the vector to the nearest food, the direction of its kin
+/// and how close walls are, with a legend giving the brain input behind
+/// each.
+pub struct PerceptionOverlay<'a> {
+    pub perception: &'a Perception,
+    pub name: &'a str,
+}
+
+impl PerceptionOverlay<'_> {
+    /// A channel name as a title: `signal_a` reads "Signal A".
+    fn pheromone_label(channel: Option<&str>) -> String {
+        let Some(channel) = channel else {
+            return "(none)".to_string();
+        };
+        channel
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn gradient_arrow((gx, gy): (f32, f32)) -> &'static str {
+        if gx.abs().max(gy.abs()) < FLAT_GRADIENT {
+            return "·";
+        }
+        // Round to one of eight directions, ignoring the weaker axis when it
+        // is under half the stronger.
+        let axis = |v: f32, other: f32| {
+            if v.abs() * 2.0 < other.abs() {
+                0
+            } else {
+                v.signum() as i32
+            }
+        };
+        OutpostNetworkOverlay::arrow(axis(gx, gy), axis(gy, gx))
+    }
+
+    /// Marks the cells about `radius` from the entity.
+    fn draw_ring(&self, area: Rect, buf: &mut ratatui::buffer::Buffer, radius: f64, color: Color) {
+        let p = self.perception;
+        let r = radius.ceil() as i32;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let dist = f64::from(dx * dx + dy * dy).sqrt();
+                if (dist - radius).abs() >= 0.5 {
+                    continue;
+                }
+                let (x, y) = (p.x + f64::from(dx), p.y + f64::from(dy));
+                if x < 0.0 || y < 0.0 {
+                    continue;
+                }
+                if let Some((sx, sy)) = WorldWidget::world_to_screen(x, y, area, false) {
+                    buf[(sx, sy)].set_symbol("·").set_fg(color);
+                }
+            }
+        }
+    }
+
+    fn draw_line(
+        &self,
+        area: Rect,
+        buf: &mut ratatui::buffer::Buffer,
+        (dx, dy): (f64, f64),
+        color: Color,
+        head: &str,
+    ) {
+        let p = self.perception;
+        let from = (p.x as i32, p.y as i32);
+        let to = ((p.x + dx) as i32, (p.y + dy) as i32);
+        let mut cells = OutpostNetworkOverlay::line_cells(from, to);
+        cells.push(to);
+        let last = cells.len() - 1;
+        for (i, (x, y)) in cells.into_iter().enumerate() {
+            if x < 0 || y < 0 {
+                continue;
+            }
+            if let Some((sx, sy)) =
+                WorldWidget::world_to_screen(f64::from(x), f64::from(y), area, false)
+            {
+                let symbol = if i == last { head } else { "·" };
+                buf[(sx, sy)]
+                    .set_symbol(symbol)
+                    .set_fg(color)
+                    .set_style(Style::default().add_modifier(Modifier::BOLD));
+            }
+        }
+    }
+
+    fn legend(&self) -> Vec<Line<'static>> {
+        let p = self.perception;
+        let label = Style::default().fg(Color::DarkGray);
+        let row = |color: Color, name: &str, value: String| {
+            Line::from(vec![
+                Span::styled("■ ", Style::default().fg(color)),
+                Span::styled(format!("{:<11}", name), label),
+                Span::raw(value),
+            ])
+        };
+        let mut lines = vec![
+            row(
+                Color::Cyan,
+                "Sensing",
+                format!("r {:.1} cells", p.sensing_range),
+            ),
+            row(
+                Color::Green,
+                "Food",
+                format!(
+                    "FoodDX {:+.2} FoodDY {:+.2}{}",
+                    p.input("FoodDX"),
+                    p.input("FoodDY"),
+                    if p.food_offset().is_none() {
+                        " (none)"
+                    } else {
+                        ""
+                    }
+                ),
+            ),
+            row(
+                Color::Magenta,
+                "Kin",
+                format!("KX {:+.2} KY {:+.2}", p.input("KX"), p.input("KY")),
+            ),
+            row(
+                Color::Red,
+                "Wall",
+                format!(
+                    "WL {:.2}{}",
+                    p.input("WL"),
+                    p.wall_distance()
+                        .map(|d| format!(" (~{d:.1} cells)"))
+                        .unwrap_or_default()
+                ),
+            ),
+            row(
+                Color::Yellow,
+                "Hearing",
+                format!("Hear {:.2}", p.input("Hear")),
+            ),
+        ];
+        for ((channel, gradient), input) in p
+            .pheromone_channels
+            .iter()
+            .zip(p.pheromone_gradients)
+            .zip(PHEROMONE_INPUTS)
+        {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{} ", Self::gradient_arrow(gradient)),
+                    Style::default().fg(Color::LightBlue),
+                ),
+                Span::styled(
+                    format!("{:<11}", Self::pheromone_label(channel.as_deref())),
+                    label,
+                ),
+                Span::raw(format!("{input} {:.2}", p.input(input))),
+            ]));
+        }
+        lines
+    }
+}
+
+impl Widget for PerceptionOverlay<'_> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let p = self.perception;
+        self.draw_ring(area, buf, p.sensing_range, Color::Cyan);
+        if let Some(distance) = p.wall_distance() {
+            self.draw_ring(area, buf, distance.max(1.0), Color::Red);
+        }
+        if let Some((kx, ky)) = p.kin_direction() {
+            let offset = (
+                f64::from(kx) * KIN_ARROW_LENGTH,
+                f64::from(ky) * KIN_ARROW_LENGTH,
+            );
+            let head = OutpostNetworkOverlay::arrow(offset.0 as i32, offset.1 as i32);
+            self.draw_line(area, buf, offset, Color::Magenta, head);
+        }
+        if let Some(offset) = p.food_offset() {
+            self.draw_line(area, buf, offset, Color::Green, "✚");
+        }
+
+        let lines = self.legend();
+        let inner = WorldWidget::get_inner_area(area, false);
+        let width = 40.min(inner.width);
+        let height = (lines.len() as u16 + 2).min(inner.height);
+        // Keep the legend in the corner away from the entity.
+        let x = if p.x < f64::from(inner.width / 2) {
+            inner.right().saturating_sub(width)
+        } else {
+            inner.x
+        };
+        let legend_area = Rect::new(x, inner.bottom().saturating_sub(height), width, height);
+        Clear.render(legend_area, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!(" 👁 {} ", self.name))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .render(legend_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primordium_core::brain::BRAIN_INPUTS;
+    use ratatui::buffer::Buffer;
+
+    #[test]
+    fn test_overlay_draws_food_vector_and_labels_inputs() {
+        let mut inputs = [0.0; BRAIN_INPUTS];
+        inputs[0] = 0.25; // FoodDX: 5 cells east.
+        let perception = Perception {
+            x: 10.0,
+            y: 10.0,
+            sensing_range: 3.0,
+            inputs,
+            pheromone_channels: [Some("food".to_string()), None, None, None],
+            pheromone_gradients: [(0.5, 0.0), (0.0, 0.0), (0.0, 0.0), (0.0, 0.0)],
+        };
+        let area = Rect::new(0, 0, 80, 30);
+        let mut buf = Buffer::empty(area);
+        PerceptionOverlay {
+            perception: &perception,
+            name: "Ada",
+        }
+        .render(area, &mut buf);
+
+        // The world starts inside the canvas border.
+        assert_eq!(buf[(16, 11)].symbol(), "✚");
+        assert_eq!(buf[(12, 11)].symbol(), "·");
+        let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("FoodDX +0.25"));
+        assert!(text.contains("→ Food"));
+        assert!(text.contains("(none)"));
+        assert_eq!(
+            PerceptionOverlay::pheromone_label(Some("signal_a")),
+            "Signal A"
+        );
+    }
+}
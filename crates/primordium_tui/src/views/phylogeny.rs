@@ -0,0 +1,174 @@
+use primordium_core::lineage_tree::LineageTree;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// One visible line of the phylogeny browser.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhylogenyRow {
+    pub id: Uuid,
+    pub depth: usize,
+    /// Box-drawing guides leading up to the lineage.
+    pub guide: String,
+    pub has_children: bool,
+    pub collapsed: bool,
+}
+
+/// Flattens `tree` into the rows shown by the browser, skipping the
+/// descendants of `collapsed` lineages.
+pub fn visible_rows(tree: &LineageTree, collapsed: &HashSet<Uuid>) -> Vec<PhylogenyRow> {
+    let mut rows = Vec::new();
+    // (lineage, depth, guide of its children, its own guide)
+    let mut stack: Vec<(Uuid, usize, String, String)> = tree
+        .roots
+        .iter()
+        .rev()
+        .map(|id| (*id, 0, String::new(), String::new()))
+        .collect();
+    while let Some((id, depth, prefix, guide)) = stack.pop() {
+        let Some(node) = tree.node(&id) else {
+            continue;
+        };
+        let is_collapsed = collapsed.contains(&id);
+        rows.push(PhylogenyRow {
+            id,
+            depth,
+            guide,
+            has_children: !node.children.is_empty(),
+            collapsed: is_collapsed,
+        });
+        if is_collapsed {
+            continue;
+        }
+        let last = node.children.len().saturating_sub(1);
+        for (i, child) in node.children.iter().enumerate().rev() {
+            let (branch, rest) = if i == last {
+                ("└─ ", "   ")
+            } else {
+                ("├─ ", "│  ")
+            };
+            stack.push((
+                *child,
+                depth + 1,
+                format!("{prefix}{rest}"),
+                format!("{prefix}{branch}"),
+            ));
+        }
+    }
+    rows
+}
+
+/// Collapsible speciation tree with the living population of every
+/// lineage and, for lineages that branched, of their whole clade.
+pub struct PhylogenyWidget<'a> {
+    pub tree: &'a LineageTree,
+    pub rows: &'a [PhylogenyRow],
+    pub selected: usize,
+}
+
+impl Widget for PhylogenyWidget<'_> {
+    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        Clear.render(area, buf);
+        let block = Block::default()
+            .title(format!(" 🧬 Phylogeny ({} lineages) ", self.tree.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green));
+
+        let capacity = (area.height as usize).saturating_sub(4).max(1);
+        // Scroll so the selected lineage stays in view.
+        let first = self.selected.saturating_sub(capacity - 1);
+        let mut lines: Vec<Line> = Vec::new();
+        for (i, row) in self.rows.iter().enumerate().skip(first).take(capacity) {
+            let Some(node) = self.tree.node(&row.id) else {
+                continue;
+            };
+            let marker = match (row.has_children, row.collapsed) {
+                (false, _) => "• ",
+                (true, false) => "▾ ",
+                (true, true) => "▸ ",
+            };
+            let mut name_style = if node.is_extinct {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            if i == self.selected {
+                name_style = name_style.add_modifier(Modifier::REVERSED);
+            }
+            let mut spans = vec![
+                Span::styled(
+                    format!(" {}", row.guide),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(marker, Style::default().fg(Color::Green)),
+                Span::styled(node.name.clone(), name_style),
+            ];
+            if node.is_extinct {
+                spans.push(Span::styled(" †", Style::default().fg(Color::DarkGray)));
+            } else {
+                spans.push(Span::styled(
+                    format!(" {} alive", node.current_population),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            if row.has_children {
+                spans.push(Span::styled(
+                    format!(" (clade {})", self.tree.clade_population(&row.id)),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        if self.rows.is_empty() {
+            lines.push(Line::from(Span::styled(
+                " No lineages yet",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " [↑/↓] Select  [←/→] Fold  [Enter] Follow a member  [Esc] Close",
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primordium_core::lineage_registry::LineageRegistry;
+
+    #[test]
+    fn test_rows_draw_guides_and_hide_collapsed_clades() {
+        let ids = [1, 2, 3, 4].map(Uuid::from_u128);
+        let mut registry = LineageRegistry::new();
+        for (i, id) in ids.iter().enumerate() {
+            registry.record_birth(*id, 1, i as u64);
+        }
+        registry.record_speciation(ids[1], ids[0]);
+        registry.record_speciation(ids[2], ids[0]);
+        registry.record_speciation(ids[3], ids[1]);
+        let tree = LineageTree::build(&registry);
+
+        let rows = visible_rows(&tree, &HashSet::new());
+        let shape: Vec<(Uuid, &str)> = rows.iter().map(|r| (r.id, r.guide.as_str())).collect();
+        assert_eq!(
+            shape,
+            vec![
+                (ids[0], ""),
+                (ids[1], "├─ "),
+                (ids[3], "│  └─ "),
+                (ids[2], "└─ "),
+            ]
+        );
+
+        let rows = visible_rows(&tree, &HashSet::from([ids[1]]));
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].collapsed);
+    }
+}
@@ -5,6 +5,8 @@
 //! - Genome marketplace (browse/submit genomes)
 //! - Seed marketplace (browse/submit simulation configs)
 
+use super::civilization::crest_spans;
+use primordium_core::crest::Crest;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
@@ -23,6 +25,8 @@ pub struct GenomeRecord {
     pub tick: u64,
     pub downloads: u32,
     pub created_at: String,
+    /// Crest of the genome's lineage, if its DNA decodes.
+    pub crest: Option<Crest>,
 }
 
 /// A seed (simulation config) record from the marketplace.
@@ -39,6 +43,8 @@ pub struct SeedRecord {
     pub performance_summary: String,
     pub downloads: u32,
     pub created_at: String,
+    /// ASCII rendering of the seed's thumbnail; empty if it has none.
+    pub preview: Vec<String>,
 }
 
 /// Hall of Fame entry.
@@ -49,6 +55,17 @@ pub struct HallOfFameEntry {
     pub is_extinct: bool,
 }
 
+/// A genome this player submitted, with its moderation state.
+#[derive(Debug, Clone)]
+pub struct SubmissionEntry {
+    pub id: String,
+    pub name: String,
+    /// "pending", "approved" or "rejected".
+    pub status: String,
+    /// The moderator's reason, if one was given.
+    pub note: Option<String>,
+}
+
 /// Registry connection status.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RegistryStatus {
@@ -68,6 +85,8 @@ pub struct RegistryWidget<'a> {
     pub genomes: &'a [GenomeRecord],
     /// Seed marketplace entries
     pub seeds: &'a [SeedRecord],
+    /// This player's submissions, newest last
+    pub submissions: &'a [SubmissionEntry],
     /// Selected index in current list
     pub selected_index: usize,
     /// Connection status
@@ -108,7 +127,16 @@ impl<'a> Widget for RegistryWidget<'a> {
             );
         title.render(chunks[0], buf);
 
-        let content_area = chunks[1];
+        let mut content_area = chunks[1];
+        if self.tab > 0 && !self.submissions.is_empty() {
+            let rows = self.submissions.len().min(5) as u16 + 2;
+            let split = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(rows)])
+                .split(content_area);
+            content_area = split[0];
+            self.render_submissions(split[1], buf);
+        }
         match self.tab {
             0 => self.render_hall_of_fame(content_area, buf),
             1 => self.render_genomes(content_area, buf),
@@ -171,7 +199,7 @@ impl<'a> RegistryWidget<'a> {
     fn render_genomes(&self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
         let inner = Block::default()
             .borders(Borders::ALL)
-            .title(" Genome Marketplace ")
+            .title(" Genome Marketplace · [I] trial import · [S] submit selected organism ")
             .border_style(Style::default().fg(Color::Magenta));
 
         let inner_area = inner.inner(area);
@@ -194,8 +222,8 @@ impl<'a> RegistryWidget<'a> {
                 "  "
             };
             let line = format!(
-                "{}{} - Fitness: {:.1} | By: {}",
-                prefix, genome.name, genome.fitness_score, genome.author
+                " {} - Fitness: {:.1} | By: {}",
+                genome.name, genome.fitness_score, genome.author
             );
             let style = if i == self.selected_index {
                 Style::default()
@@ -204,7 +232,13 @@ impl<'a> RegistryWidget<'a> {
             } else {
                 Style::default().fg(Color::White)
             };
-            lines.push(Line::from(Span::styled(line, style)));
+            let mut spans = vec![Span::raw(prefix)];
+            match &genome.crest {
+                Some(crest) => spans.extend(crest_spans(crest)),
+                None => spans.push(Span::raw("  ")),
+            }
+            spans.push(Span::styled(line, style));
+            lines.push(Line::from(spans));
         }
 
         if self.selected_index < self.genomes.len() {
@@ -227,10 +261,48 @@ impl<'a> RegistryWidget<'a> {
         list.render(inner_area, buf);
     }
 
+    fn render_submissions(&self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Your Submissions ")
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let skip = self.submissions.len().saturating_sub(5);
+        let lines: Vec<Line> = self
+            .submissions
+            .iter()
+            .skip(skip)
+            .map(|entry| {
+                let color = match entry.status.as_str() {
+                    "approved" => Color::Green,
+                    "rejected" => Color::Red,
+                    _ => Color::Yellow,
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{:<9}", entry.status),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(entry.name.clone()),
+                ];
+                if let Some(note) = &entry.note {
+                    spans.push(Span::styled(
+                        format!(" ({})", note),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect();
+        Paragraph::new(lines).render(inner_area, buf);
+    }
+
     fn render_seeds(&self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
         let inner = Block::default()
             .borders(Borders::ALL)
-            .title(" Seed Marketplace ")
+            .title(" Seed Marketplace · [S] publish this world ")
             .border_style(Style::default().fg(Color::Green));
 
         let inner_area = inner.inner(area);
@@ -266,22 +338,49 @@ impl<'a> RegistryWidget<'a> {
             lines.push(Line::from(Span::styled(line, style)));
         }
 
-        if self.selected_index < self.seeds.len() {
-            let seed = &self.seeds[self.selected_index];
-            lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
+        let mut list_area = inner_area;
+        if let Some(seed) = self.seeds.get(self.selected_index) {
+            let perf = if seed.avg_tick_time > 0.0 {
                 format!(
                     "Perf: {} | Tick: {:.2}ms",
                     seed.performance_summary, seed.avg_tick_time
-                ),
+                )
+            } else {
+                format!("Perf: {}", seed.performance_summary)
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                perf,
                 Style::default().fg(Color::DarkGray),
             )));
+
+            // The preview goes below the list, unwrapped, if it fits.
+            let rows = seed.preview.len() as u16;
+            if rows > 0 && inner_area.height >= rows + lines.len() as u16 {
+                let [top, bottom] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(rows)])
+                        .areas(inner_area);
+                list_area = top;
+                let preview: Vec<Line> = seed
+                    .preview
+                    .iter()
+                    .map(|row| {
+                        Line::from(Span::styled(
+                            row.as_str(),
+                            Style::default().fg(Color::Green),
+                        ))
+                    })
+                    .collect();
+                Paragraph::new(preview)
+                    .alignment(Alignment::Center)
+                    .render(bottom, buf);
+            }
         }
 
         let list = Paragraph::new(lines)
             .block(Block::default())
             .wrap(Wrap { trim: true });
-        list.render(inner_area, buf);
+        list.render(list_area, buf);
     }
 }
 
@@ -291,6 +390,7 @@ pub struct RegistryViewData<'a> {
     pub hall_of_fame: &'a [HallOfFameEntry],
     pub genomes: &'a [GenomeRecord],
     pub seeds: &'a [SeedRecord],
+    pub submissions: &'a [SubmissionEntry],
     pub selected_index: usize,
     pub status: &'a RegistryStatus,
 }
@@ -302,6 +402,7 @@ pub fn draw_registry(f: &mut Frame, data: &RegistryViewData, area: Rect) {
         hall_of_fame: data.hall_of_fame,
         genomes: data.genomes,
         seeds: data.seeds,
+        submissions: data.submissions,
         selected_index: data.selected_index,
         status: data.status,
     };
@@ -18,10 +18,9 @@ impl<'a> Widget for SparklinesWidget<'a> {
 
         let era_color = match self.current_era {
             Era::Primordial => Color::Green,
-            Era::DawnOfLife => Color::Cyan,
-            Era::Flourishing => Color::Yellow,
-            Era::DominanceWar => Color::Red,
-            Era::ApexEra => Color::Magenta,
+            Era::Expansion => Color::Cyan,
+            Era::Strife => Color::Red,
+            Era::Enlightenment => Color::Magenta,
         };
 
         Sparkline::default()
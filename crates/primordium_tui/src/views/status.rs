@@ -1,3 +1,4 @@
+use primordium_core::config::EraModifiers;
 use primordium_core::environment::Era;
 use primordium_core::snapshot::WorldSnapshot;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
@@ -10,14 +11,24 @@ pub struct StatusWidget<'a> {
     pub ram_usage_percent: f32,
     pub app_memory_usage_mb: f64,
     pub current_era: Era,
+    /// Rule modifiers of the current era, listed when they differ from 1.
+    pub era_modifiers: EraModifiers,
     pub oxygen_level: f64,
     pub view_mode: u8,
+    /// Channel drawn by the pheromone view.
+    pub pheromone_channel: usize,
     pub peer_count: usize,
     pub migrations_received: u64,
     pub migrations_sent: u64,
     pub is_online: bool,
     pub resource_icon: String,
     pub available_energy: f64,
+    /// Show rolling interaction rates from `snapshot.stats.interaction_rates`.
+    pub show_interaction_rates: bool,
+    /// Size of the simulation worker pool (0 = unknown).
+    pub sim_threads: usize,
+    /// Share of the logs quota in use, once it is high enough to warn about.
+    pub log_quota_warning: Option<f64>,
 }
 
 impl<'a> Widget for StatusWidget<'a> {
@@ -39,25 +50,43 @@ impl<'a> Widget for StatusWidget<'a> {
             .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
             .split(status_lines[0]);
 
+        let cpu_label = if self.sim_threads > 0 {
+            format!("CPU: {:.1}% ({} sim)", self.cpu_usage, self.sim_threads)
+        } else {
+            format!("CPU: {:.1}%", self.cpu_usage)
+        };
         let cpu_gauge = Gauge::default()
             .gauge_style(Style::default().fg(Color::Yellow))
             .percent(self.cpu_usage as u16)
-            .label(format!("CPU: {:.1}%", self.cpu_usage));
+            .label(cpu_label);
         cpu_gauge.render(line1[0], buf);
 
-        let era_info = match self.current_era {
-            Era::Primordial => ("Primordial", Color::Green),
-            Era::DawnOfLife => ("Dawn of Life", Color::Cyan),
-            Era::Flourishing => ("Flourishing", Color::Yellow),
-            Era::DominanceWar => ("Dominance War", Color::Red),
-            Era::ApexEra => ("Apex Era", Color::Magenta),
+        let era_color = match self.current_era {
+            Era::Primordial => Color::Green,
+            Era::Expansion => Color::Cyan,
+            Era::Strife => Color::Red,
+            Era::Enlightenment => Color::Magenta,
+        };
+        let modifiers: Vec<String> = [
+            ("Mut", self.era_modifiers.mutation_scale),
+            ("Dis", self.era_modifiers.disaster_frequency),
+            ("Food", self.era_modifiers.food_richness),
+        ]
+        .into_iter()
+        .filter(|(_, scale)| *scale != 1.0)
+        .map(|(label, scale)| format!("{}x{:.1}", label, scale))
+        .collect();
+        let era_label = if modifiers.is_empty() {
+            self.current_era.name().to_string()
+        } else {
+            format!("{} ({})", self.current_era.name(), modifiers.join(" "))
         };
 
         Paragraph::new(format!(
             " | Era: {} | Tick: {}",
-            era_info.0, self.snapshot.tick
+            era_label, self.snapshot.tick
         ))
-        .style(Style::default().fg(era_info.1))
+        .style(Style::default().fg(era_color))
         .render(line1[1], buf);
 
         let line2 = Layout::default()
@@ -74,7 +103,22 @@ impl<'a> Widget for StatusWidget<'a> {
             ));
         ram_gauge.render(line2[0], buf);
 
-        Paragraph::new(format!(" | Resources: {}", self.resource_icon)).render(line2[1], buf);
+        let mut resources = vec![ratatui::text::Span::raw(format!(
+            " | Resources: {}",
+            self.resource_icon
+        ))];
+        if let Some(fraction) = self.log_quota_warning {
+            let color = if fraction >= 1.0 {
+                Color::Red
+            } else {
+                Color::Yellow
+            };
+            resources.push(ratatui::text::Span::styled(
+                format!(" | Logs: {:.0}% of quota", fraction * 100.0),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+        }
+        Paragraph::new(ratatui::text::Line::from(resources)).render(line2[1], buf);
 
         let total_biomass = self.snapshot.stats.biomass_h + self.snapshot.stats.biomass_c;
         let h_percent = if total_biomass > 0.0 {
@@ -93,14 +137,28 @@ impl<'a> Widget for StatusWidget<'a> {
         biomass_bar.push(']');
 
         let view_str = match self.view_mode {
-            1 => " [Fertility] ",
-            2 => " [Social] ",
-            3 => " [Rank] ",
-            4 => " [Vocal] ",
-            5 => " [Market] ",
-            6 => " [Research] ",
-            7 => " [Civilization] ",
-            _ => " [Normal] ",
+            1 => " [Fertility] ".to_string(),
+            2 => " [Social] ".to_string(),
+            3 => " [Rank] ".to_string(),
+            4 => " [Vocal] ".to_string(),
+            5 => " [Market] ".to_string(),
+            6 => " [Research] ".to_string(),
+            7 => " [Civilization] ".to_string(),
+            8 => match self
+                .snapshot
+                .pheromones
+                .channels
+                .get(self.pheromone_channel)
+            {
+                Some(channel) => format!(" [Pheromone: {}] ", channel.name),
+                None => " [Pheromone] ".to_string(),
+            },
+            9 => " [Hydration] ".to_string(),
+            10 => format!(
+                " [Mutation Heat: v {:.3}] ",
+                self.snapshot.stats.evolutionary_velocity
+            ),
+            _ => " [Normal] ".to_string(),
         };
 
         let world_stats = vec![
@@ -109,10 +167,15 @@ impl<'a> Widget for StatusWidget<'a> {
             ratatui::text::Span::styled("Biomass: ", Style::default().add_modifier(Modifier::BOLD)),
             ratatui::text::Span::styled(biomass_bar, Style::default().fg(Color::Yellow)),
             ratatui::text::Span::raw(format!(
-                " | Species: {} | Gen: {} | AvgLife: {:.0} | CO2: {:.0} | O2: {:.1}% | Soil: {:.2}",
+                " | Species: {} | Gen: {} | AvgLife: {:.0} | Brain: {:.0}n/{:.0}c | Mem: {:.0}% | Lies: {:.1}% | Hier: {} | CO2: {:.0} | O2: {:.1}% | Soil: {:.2}",
                 self.snapshot.stats.species_count,
                 self.snapshot.stats.max_generation,
                 self.snapshot.stats.avg_lifespan,
+                self.snapshot.stats.avg_brain_nodes,
+                self.snapshot.stats.avg_brain_connections,
+                self.snapshot.stats.memory_writers * 100.0,
+                self.snapshot.stats.deception_rate * 100.0,
+                self.snapshot.stats.max_hierarchy_depth,
                 self.snapshot.stats.carbon_level,
                 self.oxygen_level,
                 self.snapshot.stats.global_fertility,
@@ -138,10 +201,44 @@ impl<'a> Widget for StatusWidget<'a> {
 
         Paragraph::new(ratatui::text::Line::from(hive_stats)).render(status_lines[4], buf);
 
-        let energy_info = vec![
+        let mut energy_info = vec![
             ratatui::text::Span::styled("⚡ Energy: ", Style::default().fg(Color::Yellow)),
             ratatui::text::Span::raw(format!("{:.0}", self.available_energy)),
         ];
+        let mut biomes: Vec<_> = self.snapshot.stats.biome_population.iter().collect();
+        biomes.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        if !biomes.is_empty() {
+            energy_info.push(ratatui::text::Span::styled(
+                " | Biomes: ",
+                Style::default().fg(Color::Green),
+            ));
+            energy_info.push(ratatui::text::Span::raw(
+                biomes
+                    .iter()
+                    .take(3)
+                    .map(|(name, count)| format!("{} {}", name, count))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ));
+        }
+        if self.show_interaction_rates {
+            let rates = &self.snapshot.stats.interaction_rates;
+            energy_info.push(ratatui::text::Span::styled(
+                " | Per tick: ",
+                Style::default().fg(Color::Magenta),
+            ));
+            energy_info.push(ratatui::text::Span::raw(format!(
+                "Kill {:.2} Share {:.2} Birth {:.2} Eat {:.2} Build {:.2} Dig {:.2} Bond {:.2} Groom {:.2}",
+                rates.kills,
+                rates.shares,
+                rates.births,
+                rates.eats,
+                rates.builds,
+                rates.digs,
+                rates.bonds,
+                rates.grooms,
+            )));
+        }
         Paragraph::new(ratatui::text::Line::from(energy_info))
             .style(Style::default().fg(Color::DarkGray))
             .render(status_lines[3], buf);
@@ -0,0 +1,219 @@
+//! Archeology timeline.
+//!
+//! Rather than loading every history snapshot up front, the timeline keeps a
+//! byte index of the snapshots in `live.jsonl` and reads each one only when it
+//! is shown. Recently viewed snapshots stay in a small LRU cache, so stepping
+//! back and forth is cheap and histories of any length fit in memory.
+//!
+//! The chronicle of every narration in the run is read when the view opens,
+//! and the narrations up to the snapshot on display are replayed beside it.
+//!
+//! The fossil browser next to the timeline can be narrowed to one era and
+//! ranked by a morphology metric with [`FossilFilter`].
+
+use std::collections::VecDeque;
+
+use primordium_data::{Fossil, Narration, PopulationStats};
+use primordium_io::chronicle::Chronicle;
+use primordium_io::history::{HistoryLogger, SnapshotEntry, SnapshotIndex};
+
+use crate::app::state::App;
+
+/// Snapshots kept in memory at once.
+pub const SNAPSHOT_CACHE_CAPACITY: usize = 64;
+/// Log bytes indexed per frame while the archeology view is open, so a huge
+/// log is indexed over several frames instead of freezing the UI.
+pub const INDEX_BYTES_PER_FRAME: u64 = 32 * 1024 * 1024;
+
+#[derive(Debug, Default)]
+pub struct ArcheologyTimeline {
+    pub index: SnapshotIndex,
+    /// Most recently used first.
+    cache: VecDeque<(SnapshotEntry, PopulationStats)>,
+    pub chronicle: Chronicle,
+}
+
+impl ArcheologyTimeline {
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn tick(&self, position: usize) -> Option<u64> {
+        self.index.get(position).map(|e| e.tick)
+    }
+
+    /// Stats of the snapshot at `position`, if it has been loaded.
+    pub fn cached(&self, position: usize) -> Option<&PopulationStats> {
+        let entry = self.index.get(position)?;
+        self.cache
+            .iter()
+            .find(|(e, _)| e == entry)
+            .map(|(_, stats)| stats)
+    }
+
+    /// Makes sure the snapshot at `position` is cached, reading it from the
+    /// log if needed.
+    pub fn load(&mut self, logger: &HistoryLogger, position: usize) -> anyhow::Result<()> {
+        let Some(entry) = self.index.get(position).copied() else {
+            return Ok(());
+        };
+        if let Some(hit) = self.cache.iter().position(|(e, _)| *e == entry) {
+            if let Some(item) = self.cache.remove(hit) {
+                self.cache.push_front(item);
+            }
+            return Ok(());
+        }
+        let stats = logger.read_snapshot(&entry)?;
+        self.cache.push_front((entry, stats));
+        self.cache.truncate(SNAPSHOT_CACHE_CAPACITY);
+        Ok(())
+    }
+
+    /// Narrations up to the snapshot at `position`, or all of them when
+    /// there are no snapshots.
+    pub fn narrations(&self, position: usize) -> &[Narration] {
+        match self.tick(position) {
+            Some(tick) => self.chronicle.until(tick),
+            None => &self.chronicle.narrations,
+        }
+    }
+
+    /// Number of snapshots currently held in memory.
+    pub fn cached_len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+/// Metric the fossil browser ranks by, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FossilSort {
+    #[default]
+    Offspring,
+    BrainSize,
+    Trophic,
+    SpeedSense,
+}
+
+impl FossilSort {
+    pub fn next(self) -> Self {
+        match self {
+            FossilSort::Offspring => FossilSort::BrainSize,
+            FossilSort::BrainSize => FossilSort::Trophic,
+            FossilSort::Trophic => FossilSort::SpeedSense,
+            FossilSort::SpeedSense => FossilSort::Offspring,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FossilSort::Offspring => "Offspring",
+            FossilSort::BrainSize => "Brain size",
+            FossilSort::Trophic => "Trophic level",
+            FossilSort::SpeedSense => "Speed/sense",
+        }
+    }
+
+    fn key(self, fossil: &Fossil) -> f64 {
+        let m = fossil.morphology();
+        match self {
+            FossilSort::Offspring => f64::from(fossil.total_offspring),
+            FossilSort::BrainSize => (m.brain_nodes + m.brain_connections) as f64,
+            FossilSort::Trophic => f64::from(m.trophic_potential),
+            FossilSort::SpeedSense => m.speed_sense_ratio,
+        }
+    }
+}
+
+/// Era filter and ranking of the fossil browser.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FossilFilter {
+    /// Only fossils of this era are listed; `None` lists all.
+    pub era: Option<String>,
+    pub sort: FossilSort,
+}
+
+impl FossilFilter {
+    /// Fossils passing the filter, in ranking order.
+    pub fn apply<'a>(&self, fossils: &'a [Fossil]) -> Vec<&'a Fossil> {
+        let mut visible: Vec<&Fossil> = fossils
+            .iter()
+            .filter(|f| self.era.as_ref().is_none_or(|era| &f.era == era))
+            .collect();
+        visible.sort_by(|a, b| self.sort.key(b).total_cmp(&self.sort.key(a)));
+        visible
+    }
+
+    /// Steps the era filter through "all" and every era found in `fossils`,
+    /// in order of first extinction.
+    pub fn cycle_era(&mut self, fossils: &[Fossil], forward: bool) {
+        let mut by_tick: Vec<&Fossil> = fossils.iter().filter(|f| !f.era.is_empty()).collect();
+        by_tick.sort_by_key(|f| f.extinct_tick);
+        let mut eras: Vec<Option<String>> = vec![None];
+        for fossil in by_tick {
+            if !eras.iter().flatten().any(|e| *e == fossil.era) {
+                eras.push(Some(fossil.era.clone()));
+            }
+        }
+        let current = eras.iter().position(|e| *e == self.era).unwrap_or(0);
+        let next = if forward {
+            (current + 1) % eras.len()
+        } else {
+            (current + eras.len() - 1) % eras.len()
+        };
+        self.era = eras[next].clone();
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "Era: {} | Sort: {}",
+            self.era.as_deref().unwrap_or("All"),
+            self.sort.label()
+        )
+    }
+}
+
+impl App {
+    /// Fossils listed by the archeology browser, in display order.
+    pub fn visible_fossils(&self) -> Vec<&Fossil> {
+        self.fossil_filter
+            .apply(&self.world.fossil_registry.fossils)
+    }
+
+    /// Indexes up to `max_bytes` of newly written log.
+    pub fn refresh_archeology(&mut self, max_bytes: u64) {
+        if let Err(e) = self
+            .world
+            .logger
+            .index_snapshots(&mut self.archeology.index, max_bytes)
+        {
+            tracing::warn!("Failed to index history snapshots: {}", e);
+        }
+    }
+
+    pub fn toggle_archeology(&mut self) {
+        self.show_archeology = !self.show_archeology;
+        if self.show_archeology {
+            self.refresh_archeology(INDEX_BYTES_PER_FRAME);
+            self.archeology_index = self.archeology.len().saturating_sub(1);
+            self.archeology.chronicle = Chronicle::load_from_dir(&self.world.log_dir)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to read chronicle: {}", e);
+                    Chronicle::default()
+                });
+        }
+    }
+
+    /// Reads the snapshot on display if it is not cached yet.
+    pub fn load_archeology_snapshot(&mut self) {
+        if let Err(e) = self
+            .archeology
+            .load(&self.world.logger, self.archeology_index)
+        {
+            tracing::warn!("Failed to read history snapshot: {}", e);
+        }
+    }
+}
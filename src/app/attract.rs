@@ -0,0 +1,239 @@
+//! Attract mode: loops bundled recordings of interesting runs for demos.
+//!
+//! A reel stores the configuration and seed of a deterministic run together
+//! with the chronicle captions it produced. Playback re-simulates the run from
+//! the seed and shows each caption when its tick is reached, so the bundled
+//! files stay a few kilobytes instead of carrying full world states.
+
+use anyhow::Result;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use primordium_data::LiveEvent;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+use crate::app::state::App;
+use crate::app::LiveEventExt;
+use crate::model::config::AppConfig;
+use crate::model::environment::Environment;
+use crate::model::world::World;
+
+/// Minimum ticks between two recorded captions, and how long each caption
+/// stays on screen, so bursts of events do not flash past unread.
+const CAPTION_SPACING: u64 = 300;
+
+/// Compressed reels shipped with the binary.
+const BUNDLED_REELS: &[&[u8]] = &[
+    include_bytes!("../../assets/attract/tribal_dawn.json.gz"),
+    include_bytes!("../../assets/attract/boom_and_bust.json.gz"),
+    include_bytes!("../../assets/attract/crowded_world.json.gz"),
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Caption {
+    pub tick: u64,
+    pub text: String,
+}
+
+/// A pre-recorded deterministic run.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AttractReel {
+    pub title: String,
+    pub ticks: u64,
+    pub config: AppConfig,
+    pub captions: Vec<Caption>,
+    /// The run the reel was recorded from.
+    #[serde(default)]
+    pub provenance: Option<primordium_data::RunManifest>,
+}
+
+impl AttractReel {
+    /// Simulates `ticks` ticks of a deterministic run and records the
+    /// chronicle captions it produces.
+    pub fn record(title: &str, mut config: AppConfig, ticks: u64) -> Result<Self> {
+        config.world.deterministic = true;
+        config.world.seed.get_or_insert(42);
+        let mut world = World::new_ephemeral(config.world.initial_population, config.clone())?;
+        let mut env = Environment::default();
+        let mut captions: Vec<Caption> = Vec::new();
+
+        while world.tick < ticks {
+            let events = world.update(&mut env)?;
+            for ev in events {
+                let Some(text) = caption_for(&ev) else {
+                    continue;
+                };
+                let spaced = captions
+                    .last()
+                    .is_none_or(|c| world.tick >= c.tick + CAPTION_SPACING);
+                if spaced {
+                    captions.push(Caption {
+                        tick: world.tick,
+                        text,
+                    });
+                }
+            }
+            if world.get_population_count() == 0 {
+                break;
+            }
+        }
+
+        Ok(Self {
+            title: title.to_string(),
+            ticks: world.tick,
+            config,
+            captions,
+            provenance: world.provenance.clone(),
+        })
+    }
+
+    /// Gzip-compressed JSON, the format of the bundled reels.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&serde_json::to_vec(self)?)?;
+        Ok(encoder.finish()?)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut json = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut json)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// All reels bundled with the binary.
+    pub fn bundled() -> Result<Vec<Self>> {
+        BUNDLED_REELS
+            .iter()
+            .map(|bytes| Self::decode(bytes))
+            .collect()
+    }
+
+    /// The caption to display at `tick`, if any.
+    pub fn caption_at(&self, tick: u64) -> Option<&Caption> {
+        self.captions
+            .iter()
+            .rev()
+            .find(|c| c.tick <= tick)
+            .filter(|c| tick < c.tick + CAPTION_SPACING)
+    }
+}
+
+/// Chronicle events worth a caption; births, deaths and snapshots are noise.
+fn caption_for(event: &LiveEvent) -> Option<String> {
+    match event {
+        LiveEvent::Birth { .. }
+        | LiveEvent::Death { .. }
+        | LiveEvent::Snapshot { .. }
+        | LiveEvent::CircuitBreaker { .. } => None,
+        _ => Some(event.to_ui_message().0),
+    }
+}
+
+/// Playback position within the bundled reels.
+pub struct AttractState {
+    pub reels: Vec<AttractReel>,
+    pub current: usize,
+}
+
+impl AttractState {
+    pub fn reel(&self) -> &AttractReel {
+        &self.reels[self.current]
+    }
+}
+
+impl App {
+    /// Starts the app in attract mode, looping over the bundled reels.
+    ///
+    /// Like the screensaver, attract mode hides all panels, ignores input other
+    /// than quitting and never writes to disk.
+    pub fn new_attract() -> Result<Self> {
+        let reels = AttractReel::bundled()?;
+        anyhow::ensure!(!reels.is_empty(), "No attract reels bundled");
+        let mut config = reels[0].config.clone();
+        config.screensaver.suppress_file_writes = true;
+        config.screensaver.view_rotation_secs = 0;
+        let world = World::new_ephemeral(0, config.clone())?;
+        let mut app = Self::with_world(config, world)?;
+        app.screensaver = true;
+        app.tutorial = None;
+        app.attract = Some(AttractState { reels, current: 0 });
+        app.start_attract_reel()?;
+        Ok(app)
+    }
+
+    /// Rebuilds the world for the current reel.
+    pub fn start_attract_reel(&mut self) -> Result<()> {
+        let Some(attract) = &self.attract else {
+            return Ok(());
+        };
+        let config = attract.reel().config.clone();
+        self.world = World::new_ephemeral(config.world.initial_population, config.clone())?;
+        self.config.world = config.world;
+        self.env = Environment::default();
+        self.tick_count = 0;
+        self.view_mode = 0;
+        self.latest_snapshot = Some(self.world.create_snapshot(None));
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Advances to the next reel once the current one has played out.
+    pub fn update_attract(&mut self) -> Result<()> {
+        let Some(attract) = &mut self.attract else {
+            return Ok(());
+        };
+        if self.world.tick >= attract.reel().ticks || self.world.get_population_count() == 0 {
+            attract.current = (attract.current + 1) % attract.reels.len();
+            self.start_attract_reel()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_reels_decode() {
+        let reels = AttractReel::bundled().unwrap();
+        assert_eq!(reels.len(), BUNDLED_REELS.len());
+        for reel in &reels {
+            assert!(reel.ticks > 0);
+            assert!(reel.config.world.deterministic);
+            assert!(reel.captions.windows(2).all(|w| w[0].tick < w[1].tick));
+        }
+    }
+
+    #[test]
+    fn test_reel_roundtrip_and_caption_lookup() {
+        let mut config = AppConfig::default();
+        config.world.initial_population = 10;
+        let reel = AttractReel::record("tiny", config, 20).unwrap();
+        let decoded = AttractReel::decode(&reel.encode().unwrap()).unwrap();
+        assert_eq!(decoded.title, "tiny");
+        assert_eq!(decoded.ticks, reel.ticks);
+        assert_eq!(decoded.captions, reel.captions);
+        assert!(decoded.provenance.is_some());
+        assert_eq!(decoded.provenance, reel.provenance);
+
+        let reel = AttractReel {
+            captions: vec![
+                Caption {
+                    tick: 10,
+                    text: "a".into(),
+                },
+                Caption {
+                    tick: 200,
+                    text: "b".into(),
+                },
+            ],
+            ..decoded
+        };
+        assert!(reel.caption_at(5).is_none());
+        assert_eq!(reel.caption_at(150).unwrap().text, "a");
+        assert_eq!(reel.caption_at(250).unwrap().text, "b");
+        assert!(reel.caption_at(600).is_none());
+    }
+}
@@ -152,9 +152,14 @@ impl AudioSystem {
                 );
             }
             LiveEvent::Narration { .. } => self.queue_event(AudioEvent::AmbientShift),
-            LiveEvent::Extinction { .. } | LiveEvent::EcoAlert { .. } => {
-                self.queue_event(AudioEvent::AmbientShift)
-            }
+            LiveEvent::Bookmark { .. } | LiveEvent::Provenance { .. } => {}
+            LiveEvent::Extinction { .. }
+            | LiveEvent::EcoAlert { .. }
+            | LiveEvent::WarDeclared { .. }
+            | LiveEvent::PeaceTreaty { .. }
+            | LiveEvent::MonumentRaised { .. }
+            | LiveEvent::SeasonChanged { .. }
+            | LiveEvent::CircuitBreaker { .. } => self.queue_event(AudioEvent::AmbientShift),
         }
     }
 
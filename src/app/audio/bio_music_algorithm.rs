@@ -88,6 +88,7 @@ mod tests {
                 fast_forward_order: Default::default(),
                 incoming_flat: Default::default(),
                 incoming_offsets: Default::default(),
+                wiring_hash: Default::default(),
             },
             sensing_range: 0.5,
             max_speed: 0.5,
@@ -100,6 +101,12 @@ mod tests {
             mate_preference: 0.5,
             pairing_bias: 0.5,
             regulatory_rules: Default::default(),
+            oviparity: 0.0,
+            clutch_size: 1,
+            parental_care: 0.0,
+            skepticism: 0.0,
+            death_avoidance: 0.0,
+            body_size: 1.0,
             specialization_bias: Default::default(),
         }
     }
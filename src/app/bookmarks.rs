@@ -0,0 +1,100 @@
+//! Timeline bookmarks.
+//!
+//! `n` marks the current tick with a note and `N` lists the world's
+//! bookmarks, from where `Enter` jumps the archeology timeline to the nearest
+//! history snapshot. Each bookmark is also written to the chronicle so it
+//! shows up in exported logs and the `analyze` report.
+
+use chrono::Utc;
+use primordium_data::LiveEvent;
+use ratatui::style::Color;
+
+use crate::app::state::App;
+use crate::app::LiveEventExt;
+
+/// Longest note accepted, in characters.
+pub const MAX_NOTE_LEN: usize = 80;
+
+impl App {
+    /// Starts typing a note for the current tick.
+    pub fn begin_bookmark(&mut self) {
+        self.bookmark_note = Some((self.world.tick, String::new()));
+    }
+
+    /// Saves the note being typed as a bookmark and records it in the
+    /// chronicle.
+    pub fn commit_bookmark(&mut self) {
+        let Some((tick, note)) = self.bookmark_note.take() else {
+            return;
+        };
+        let note = note.trim().to_string();
+        self.world.add_bookmark(tick, note.clone());
+
+        let event = LiveEvent::Bookmark {
+            tick,
+            note,
+            timestamp: Utc::now().to_rfc3339(),
+        };
+        let _ = self.world.logger.log_event(event.clone());
+        self.event_log.push_back(event.to_ui_message());
+        if self.event_log.len() > 15 {
+            self.event_log.pop_front();
+        }
+    }
+
+    pub fn toggle_bookmarks(&mut self) {
+        self.show_bookmarks = !self.show_bookmarks;
+        self.bookmark_index = self
+            .bookmark_index
+            .min(self.world.bookmarks.len().saturating_sub(1));
+    }
+
+    pub fn delete_bookmark(&mut self) {
+        if self.bookmark_index < self.world.bookmarks.len() {
+            self.world.bookmarks.remove(self.bookmark_index);
+            self.bookmark_index = self
+                .bookmark_index
+                .min(self.world.bookmarks.len().saturating_sub(1));
+        }
+    }
+
+    /// Opens archeology mode at the last history snapshot taken at or before
+    /// the selected bookmark.
+    pub fn jump_to_bookmark(&mut self) {
+        let Some(tick) = self
+            .world
+            .bookmarks
+            .get(self.bookmark_index)
+            .map(|b| b.tick)
+        else {
+            return;
+        };
+        // Make sure the index covers the whole log before searching it.
+        self.refresh_archeology(u64::MAX);
+        if self.archeology.is_empty() {
+            self.event_log.push_back((
+                "No history snapshots to jump to yet".to_string(),
+                Color::Yellow,
+            ));
+            return;
+        }
+        let position = self
+            .archeology
+            .index
+            .position_at_or_before(tick)
+            .unwrap_or(0);
+
+        self.archeology_index = position;
+        self.show_archeology = true;
+        self.auto_play_history = false;
+        self.show_bookmarks = false;
+        self.event_log.push_back((
+            format!(
+                "Timeline moved to tick {} for bookmark at tick {}",
+                self.archeology.tick(position).unwrap_or_default(),
+                tick
+            ),
+            Color::Cyan,
+        ));
+    }
+}
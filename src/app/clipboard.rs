@@ -0,0 +1,149 @@
+//! System clipboard exchange of DNA strings.
+//!
+//! `Ctrl+C` copies the selected entity's genotype as hex and `Ctrl+V` spawns
+//! a new entity from hex on the clipboard. The system clipboard is only
+//! reachable when built with the `clipboard` feature; without it both actions
+//! report that support is missing.
+
+use crate::model::brain::GenotypeLogic;
+use primordium_data::Genotype;
+use ratatui::style::Color;
+use std::sync::Arc;
+
+use crate::app::state::App;
+
+/// Upper bound on the length of a DNA hex string moved through the clipboard.
+/// Real genotypes are a few tens of kilobytes; anything far larger is almost
+/// certainly not DNA and would stall the UI while parsing.
+pub const MAX_DNA_HEX_LEN: usize = 1 << 20;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DnaPasteError {
+    #[error("Clipboard is empty")]
+    Empty,
+    #[error("Clipboard content too large ({0} KB)")]
+    TooLarge(usize),
+    #[error("Clipboard does not contain valid DNA")]
+    Invalid,
+}
+
+/// Validates and decodes a DNA hex string taken from the clipboard.
+pub fn parse_dna(text: &str) -> Result<Genotype, DnaPasteError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(DnaPasteError::Empty);
+    }
+    if text.len() > MAX_DNA_HEX_LEN {
+        return Err(DnaPasteError::TooLarge(text.len() / 1024));
+    }
+    Genotype::from_hex(text).map_err(|_| DnaPasteError::Invalid)
+}
+
+#[cfg(feature = "clipboard")]
+fn read_clipboard() -> anyhow::Result<String> {
+    Ok(arboard::Clipboard::new()?.get_text()?)
+}
+
+#[cfg(feature = "clipboard")]
+fn write_clipboard(text: &str) -> anyhow::Result<()> {
+    arboard::Clipboard::new()?.set_text(text)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn read_clipboard() -> anyhow::Result<String> {
+    anyhow::bail!("Clipboard support not built (enable the `clipboard` feature)")
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn write_clipboard(_text: &str) -> anyhow::Result<()> {
+    anyhow::bail!("Clipboard support not built (enable the `clipboard` feature)")
+}
+
+impl App {
+    /// Copies the selected entity's genotype to the system clipboard.
+    pub fn copy_dna_to_clipboard(&mut self) {
+        let Some(genotype) = self.selected_genotype() else {
+            self.event_log.push_back((
+                "Select an entity to copy its DNA".to_string(),
+                Color::Yellow,
+            ));
+            return;
+        };
+        let dna = genotype.to_hex();
+        if dna.len() > MAX_DNA_HEX_LEN {
+            self.event_log.push_back((
+                format!("DNA too large to copy ({} KB)", dna.len() / 1024),
+                Color::Red,
+            ));
+            return;
+        }
+        match write_clipboard(&dna) {
+            Ok(()) => self.event_log.push_back((
+                format!("DNA copied to clipboard ({} KB)", dna.len().div_ceil(1024)),
+                Color::Cyan,
+            )),
+            Err(e) => self
+                .event_log
+                .push_back((format!("Copy failed: {}", e), Color::Red)),
+        }
+    }
+
+    /// Spawns an entity from DNA on the system clipboard, next to the selected
+    /// entity if there is one.
+    pub fn paste_dna_from_clipboard(&mut self) {
+        let text = match read_clipboard() {
+            Ok(text) => text,
+            Err(e) => {
+                self.event_log
+                    .push_back((format!("Paste failed: {}", e), Color::Red));
+                return;
+            }
+        };
+        self.spawn_from_dna_text(&text);
+    }
+
+    /// Validates `text` as DNA and spawns an entity from it.
+    pub fn spawn_from_dna_text(&mut self, text: &str) {
+        match parse_dna(text) {
+            Ok(genotype) => {
+                let (x, y) = self.selected_position().unwrap_or((
+                    f64::from(self.world.width) / 2.0,
+                    f64::from(self.world.height) / 2.0,
+                ));
+                self.spawn_with_genotype(Arc::new(genotype), x, y);
+                self.event_log
+                    .push_back(("AVATAR INFUSED from clipboard".to_string(), Color::Green));
+            }
+            Err(e) => self.event_log.push_back((e.to_string(), Color::Red)),
+        }
+    }
+
+    fn selected_position(&self) -> Option<(f64, f64)> {
+        let id = self.selected_entity?;
+        self.world
+            .ecs
+            .query::<(&primordium_data::Identity, &primordium_data::Physics)>()
+            .iter()
+            .find(|(_, (identity, _))| identity.id == id)
+            .map(|(_, (_, phys))| (phys.x, phys.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dna_checks_size_and_format() {
+        let genotype =
+            crate::model::brain::create_genotype_random_with_rng(&mut rand::thread_rng());
+        let parsed = parse_dna(&format!("  {}\n", genotype.to_hex())).unwrap();
+        assert_eq!(parsed.lineage_id, genotype.lineage_id);
+
+        assert!(matches!(parse_dna(" \n"), Err(DnaPasteError::Empty)));
+        assert!(matches!(parse_dna("not dna"), Err(DnaPasteError::Invalid)));
+        let huge = "0".repeat(MAX_DNA_HEX_LEN + 2);
+        assert!(matches!(parse_dna(&huge), Err(DnaPasteError::TooLarge(_))));
+    }
+}
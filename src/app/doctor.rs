@@ -0,0 +1,414 @@
+//! Self-diagnostics behind `primordium doctor`.
+//!
+//! Each check looks at one thing the simulation depends on and reports what
+//! to do about a problem rather than just that there is one. [`run_all`] is
+//! the full set; [`run_startup`] is the fast subset run before every launch,
+//! whose problems are shown as warnings in the event log.
+
+use crate::model::config::AppConfig;
+use crate::model::lineage_registry::LineageRegistry;
+use primordium_data::FossilRegistry;
+use primordium_io::genome_archive::GenomeArchive;
+use primordium_io::history::FossilPersistence;
+use primordium_io::registry::LineagePersistence;
+use std::fmt;
+use std::io::IsTerminal;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+/// Smallest terminal the default layouts render without clipping.
+pub const MIN_TERMINAL_SIZE: (u16, u16) = (80, 24);
+
+/// Rough memory cost of one map cell across all grids.
+pub const BYTES_PER_CELL: u64 = 128;
+
+/// Rough memory cost of one organism, brain and history included.
+pub const BYTES_PER_ENTITY: u64 = 32 * 1024;
+
+/// Peak population assumed when sizing memory, as a multiple of the initial one.
+pub const POPULATION_HEADROOM: u64 = 10;
+
+const RELAY_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl Status {
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Warn => "warn",
+            Status::Fail => "FAIL",
+        }
+    }
+}
+
+/// Result of one check, with advice when it did not pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+    pub advice: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Ok,
+            detail: detail.into(),
+            advice: None,
+        }
+    }
+
+    fn problem(
+        name: &'static str,
+        status: Status,
+        detail: impl Into<String>,
+        advice: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            advice: Some(advice.into()),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:>4}] {}: {}",
+            self.status.label(),
+            self.name,
+            self.detail
+        )?;
+        if let Some(advice) = &self.advice {
+            write!(f, "\n       -> {advice}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads and validates the config at `path`, falling back to the defaults
+/// when it is missing or invalid. The check says which happened.
+pub fn check_config(path: &str) -> (AppConfig, Check) {
+    const NAME: &str = "config";
+    match std::fs::read_to_string(path) {
+        Err(_) => (
+            AppConfig::default(),
+            Check::problem(
+                NAME,
+                Status::Warn,
+                format!("{path} not found, using defaults"),
+                "Copy config.example.toml to config.toml to customise the simulation",
+            ),
+        ),
+        Ok(content) => match AppConfig::from_toml(&content) {
+            Ok(config) => (config, Check::ok(NAME, format!("{path} is valid"))),
+            Err(e) => (
+                AppConfig::default(),
+                Check::problem(
+                    NAME,
+                    Status::Fail,
+                    format!("{path} is invalid: {e}"),
+                    "Fix the value named above; the simulation runs on defaults until then",
+                ),
+            ),
+        },
+    }
+}
+
+pub fn check_terminal() -> Check {
+    const NAME: &str = "terminal";
+    if !std::io::stdout().is_terminal() {
+        return Check::problem(
+            NAME,
+            Status::Warn,
+            "stdout is not a terminal",
+            "Run the TUI in an interactive terminal, or use --mode headless",
+        );
+    }
+    let (min_w, min_h) = MIN_TERMINAL_SIZE;
+    match crossterm::terminal::size() {
+        Ok((w, h)) if w < min_w || h < min_h => Check::problem(
+            NAME,
+            Status::Warn,
+            format!("{w}x{h} is smaller than {min_w}x{min_h}"),
+            "Enlarge the window or reduce the font size",
+        ),
+        Ok((w, h)) => {
+            let color = match std::env::var("COLORTERM").as_deref() {
+                Ok("truecolor" | "24bit") => "truecolor",
+                _ => "256-color or less",
+            };
+            Check::ok(NAME, format!("{w}x{h}, {color}"))
+        }
+        Err(e) => Check::problem(
+            NAME,
+            Status::Warn,
+            format!("size unknown: {e}"),
+            "Check that TERM is set correctly",
+        ),
+    }
+}
+
+/// Checks that `dir` can be created and written to.
+pub fn check_writable(name: &'static str, dir: &str) -> Check {
+    let probe = Path::new(dir).join(".doctor_probe");
+    let written = std::fs::create_dir_all(dir).and_then(|()| std::fs::write(&probe, b"ok"));
+    let _ = std::fs::remove_file(&probe);
+    match written {
+        Ok(()) => Check::ok(name, format!("{dir}/ is writable")),
+        Err(e) => Check::problem(
+            name,
+            Status::Fail,
+            format!("cannot write to {dir}/: {e}"),
+            format!("Fix the permissions of {dir}/ or run from a writable directory"),
+        ),
+    }
+}
+
+/// Estimated peak memory use of a world configured by `config`, in bytes.
+#[must_use]
+pub fn estimated_memory(config: &AppConfig) -> u64 {
+    let cells = u64::from(config.world.width) * u64::from(config.world.height);
+    let population = config.world.initial_population as u64 * POPULATION_HEADROOM;
+    cells * BYTES_PER_CELL + population * BYTES_PER_ENTITY
+}
+
+/// Compares the estimated peak memory use with `available` bytes.
+#[must_use]
+pub fn check_memory(config: &AppConfig, available: u64) -> Check {
+    const NAME: &str = "memory";
+    const MIB: u64 = 1024 * 1024;
+    let needed = estimated_memory(config);
+    let detail = format!(
+        "~{} MiB needed for {}x{} with {} organisms, {} MiB available",
+        needed / MIB,
+        config.world.width,
+        config.world.height,
+        config.world.initial_population,
+        available / MIB
+    );
+    if needed > available {
+        Check::problem(
+            NAME,
+            Status::Fail,
+            detail,
+            "Reduce world.width/height or world.initial_population",
+        )
+    } else if needed > available / 2 {
+        Check::problem(
+            NAME,
+            Status::Warn,
+            detail,
+            "Close other programs or shrink the world to leave headroom",
+        )
+    } else {
+        Check::ok(NAME, detail)
+    }
+}
+
+fn available_memory() -> u64 {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    sys.available_memory()
+}
+
+/// `host:port` of a relay URL such as `ws://127.0.0.1:3000/ws`.
+#[must_use]
+pub fn relay_address(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let host = rest.split('/').next().filter(|h| !h.is_empty())?;
+    if host.contains(':') {
+        Some(host.to_string())
+    } else {
+        let port = if scheme == "wss" || scheme == "https" {
+            443
+        } else {
+            80
+        };
+        Some(format!("{host}:{port}"))
+    }
+}
+
+pub fn check_relay(url: &str) -> Check {
+    const NAME: &str = "relay";
+    let Some(address) = relay_address(url) else {
+        return Check::problem(
+            NAME,
+            Status::Fail,
+            format!("{url} is not a relay URL"),
+            "Use the form ws://host:port/ws",
+        );
+    };
+    let reachable = address
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .is_some_and(|addr| TcpStream::connect_timeout(&addr, RELAY_TIMEOUT).is_ok());
+    if reachable {
+        Check::ok(NAME, format!("{address} is reachable"))
+    } else {
+        Check::problem(
+            NAME,
+            Status::Warn,
+            format!("cannot connect to {address}"),
+            "Start the relay with `cargo run -p primordium_server` or check the URL",
+        )
+    }
+}
+
+/// Loads every persisted artefact the simulation would read back.
+pub fn check_storage(config: &AppConfig, log_dir: &str) -> Vec<Check> {
+    const NAME: &str = "storage";
+    let mut checks = Vec::new();
+    let corrupt = |what: String, e: anyhow::Error| {
+        Check::problem(
+            NAME,
+            Status::Fail,
+            format!("{what} is unreadable: {e:#}"),
+            format!("Restore {what} from a backup or move it aside to start fresh"),
+        )
+    };
+
+    if Path::new("save.json").exists() {
+        checks.push(match crate::model::persistence::load_world("save.json") {
+            Ok(world) => {
+                let report = crate::model::compat::CompatibilityReport::check(&world, config);
+                if report.can_adapt() {
+                    Check::ok(NAME, format!("save.json loads (tick {})", world.tick))
+                } else {
+                    Check::problem(
+                        NAME,
+                        Status::Fail,
+                        "save.json was written by an incompatible build",
+                        "Run `primordium save check` for details",
+                    )
+                }
+            }
+            Err(e) => corrupt("save.json".to_string(), e),
+        });
+    }
+
+    let fossils = format!("{log_dir}/fossils.json.gz");
+    if Path::new(&fossils).exists() {
+        checks.push(match FossilRegistry::load(&fossils) {
+            Ok(registry) => Check::ok(
+                NAME,
+                format!("{fossils} loads ({} fossils)", registry.fossils.len()),
+            ),
+            Err(e) => corrupt(fossils, e),
+        });
+    }
+
+    let lineages = format!("{log_dir}/lineages.json");
+    if Path::new(&lineages).exists() {
+        checks.push(match LineageRegistry::load(&lineages) {
+            Ok(registry) => Check::ok(
+                NAME,
+                format!("{lineages} loads ({} lineages)", registry.lineages.len()),
+            ),
+            Err(e) => corrupt(lineages, e),
+        });
+    }
+
+    let archive = GenomeArchive::new(&config.archive.dir);
+    match archive.index() {
+        Ok(entries) => {
+            if let Some(missing) = entries
+                .iter()
+                .find(|e| !archive.dir().join(&e.file).exists())
+            {
+                checks.push(Check::problem(
+                    NAME,
+                    Status::Warn,
+                    format!(
+                        "genome dump {} listed in the index is missing",
+                        missing.file
+                    ),
+                    "Reseeding from that tick will fail; remove its line from index.jsonl",
+                ));
+            } else if !entries.is_empty() {
+                checks.push(Check::ok(
+                    NAME,
+                    format!("genome archive indexes {} dumps", entries.len()),
+                ));
+            }
+        }
+        Err(e) => checks.push(corrupt(format!("{}/index.jsonl", config.archive.dir), e)),
+    }
+
+    if checks.is_empty() {
+        checks.push(Check::ok(NAME, "nothing saved yet"));
+    }
+    checks
+}
+
+/// Every check, as run by `primordium doctor`.
+pub fn run_all(config_path: &str, relay: Option<&str>) -> Vec<Check> {
+    let (config, config_check) = check_config(config_path);
+    let mut checks = vec![
+        config_check,
+        check_terminal(),
+        check_writable("logs", "logs"),
+        check_memory(&config, available_memory()),
+    ];
+    checks.extend(check_storage(&config, "logs"));
+    checks.push(match relay {
+        Some(url) => check_relay(url),
+        None => Check::ok("relay", "none given (pass --relay <URL> to test one)"),
+    });
+    checks
+}
+
+/// The fast checks run at every launch: config, log permissions and memory.
+pub fn run_startup(config_path: &str) -> Vec<Check> {
+    let (config, config_check) = check_config(config_path);
+    vec![
+        config_check,
+        check_writable("logs", "logs"),
+        check_memory(&config, available_memory()),
+    ]
+}
+
+impl crate::app::App {
+    /// Runs [`run_startup`] and shows every problem in the event log.
+    /// Returns the checks that did not pass.
+    pub fn run_startup_checks(&mut self) -> Vec<Check> {
+        if self.writes_suppressed() {
+            return Vec::new();
+        }
+        let problems: Vec<Check> = run_startup(&self.config_path)
+            .into_iter()
+            .filter(|c| c.status != Status::Ok)
+            .collect();
+        for check in &problems {
+            let color = match check.status {
+                Status::Fail => ratatui::style::Color::Red,
+                _ => ratatui::style::Color::Yellow,
+            };
+            tracing::warn!("{check}");
+            self.event_log.push_back((
+                format!(
+                    "Doctor: {} {} ({})",
+                    check.name,
+                    check.detail,
+                    check.advice.as_deref().unwrap_or("")
+                ),
+                color,
+            ));
+        }
+        problems
+    }
+}
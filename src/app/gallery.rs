@@ -0,0 +1,47 @@
+use ratatui::style::Color;
+
+use crate::app::state::App;
+use crate::model::gallery::InterestDetector;
+
+impl App {
+    /// Per-tick interest detection. When the world is replaced by another
+    /// run, the finished one is scored into the seed gallery.
+    pub fn update_gallery(&mut self) {
+        if !self.config.gallery.enabled || self.writes_suppressed() || self.attract.is_some() {
+            return;
+        }
+        match &mut self.interest {
+            Some(detector) if !detector.is_stale(&self.world) => {
+                detector.observe(&self.world, &self.config.gallery);
+            }
+            _ => {
+                let _ = self.record_gallery_run();
+                self.interest = Some(InterestDetector::new(&self.world));
+            }
+        }
+    }
+
+    /// Scores the watched run into the seed gallery and stops watching it.
+    /// Returns the announcement when the seed made the gallery.
+    pub fn record_gallery_run(&mut self) -> Option<String> {
+        let detector = self.interest.take()?;
+        match detector.record(&self.config.gallery) {
+            Ok(Some((entry, rank))) => {
+                let message = format!(
+                    "Seed {} entered the gallery at #{}: {}",
+                    entry.seed,
+                    rank + 1,
+                    entry.highlights.summary()
+                );
+                self.event_log
+                    .push_back((message.clone(), Color::LightMagenta));
+                Some(message)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Failed to record seed gallery: {}", e);
+                None
+            }
+        }
+    }
+}
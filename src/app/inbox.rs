@@ -0,0 +1,162 @@
+//! Drop-in directory for live injection into a running world.
+//!
+//! External tools orchestrate a run by writing files into `[inbox] dir`:
+//! a `*.dna` file spawns one organism from the genotype hex it holds, a
+//! `*.json` file applies one [`ScenarioEvent`] or an array of them. Files are
+//! handled in name order, then moved to `processed/` or `failed/` so they are
+//! applied exactly once. Every result is shown in the event log and appended
+//! to `results.jsonl` in the inbox, which is what a script waits on.
+
+use crate::app::state::App;
+use crate::model::brain::GenotypeLogic;
+use crate::model::world::scenario::ScenarioEvent;
+use anyhow::Context;
+use primordium_data::Genotype;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// How often the run loops scan the inbox.
+pub const POLL_INTERVAL_MS: u64 = 250;
+
+/// Outcome of handling one inbox file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InboxResult {
+    pub tick: u64,
+    pub file: String,
+    pub ok: bool,
+    pub message: String,
+}
+
+/// Inbox files waiting to be handled, in the order they will be applied.
+fn pending_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("dna" | "json")
+            )
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn move_into(path: &Path, subdir: &str) -> anyhow::Result<()> {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let target = parent.join(subdir);
+    fs::create_dir_all(&target)?;
+    let name = path.file_name().context("Inbox entry has no file name")?;
+    fs::rename(path, target.join(name))?;
+    Ok(())
+}
+
+impl App {
+    /// Applies every file waiting in the inbox and returns the results.
+    ///
+    /// Does nothing when the inbox is disabled, its directory does not exist,
+    /// or the world is driven automatically (screensaver, attract, replay).
+    pub fn poll_inbox(&mut self) -> Vec<InboxResult> {
+        if !self.config.inbox.enabled
+            || self.screensaver
+            || self.attract.is_some()
+            || self.replay_mode
+        {
+            return Vec::new();
+        }
+        let dir = PathBuf::from(&self.config.inbox.dir);
+        if !dir.is_dir() {
+            return Vec::new();
+        }
+
+        let mut results = Vec::new();
+        for path in pending_files(&dir) {
+            let file = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let outcome = self.apply_inbox_file(&path);
+            let result = InboxResult {
+                tick: self.world.tick,
+                file,
+                ok: outcome.is_ok(),
+                message: match outcome {
+                    Ok(message) => message,
+                    Err(e) => format!("{e:#}"),
+                },
+            };
+            if let Err(e) = move_into(&path, if result.ok { "processed" } else { "failed" }) {
+                tracing::warn!("Failed to move inbox file {}: {e}", path.display());
+            }
+            self.record_inbox_result(&dir, &result);
+            results.push(result);
+        }
+        results
+    }
+
+    fn apply_inbox_file(&mut self, path: &Path) -> anyhow::Result<String> {
+        let content = fs::read_to_string(path).context("Failed to read inbox file")?;
+        let events = if path.extension().is_some_and(|ext| ext == "dna") {
+            vec![ScenarioEvent::Spawn {
+                dna: content,
+                count: 1,
+                x: None,
+                y: None,
+            }]
+        } else {
+            ScenarioEvent::parse_many(&content).context("Invalid scenario file")?
+        };
+        self.apply_scenario_events(&events)
+    }
+
+    /// Applies `events` in order, or none of them when one holds invalid DNA.
+    pub fn apply_scenario_events(&mut self, events: &[ScenarioEvent]) -> anyhow::Result<String> {
+        // Validate everything first so a bad entry leaves the world untouched.
+        for event in events {
+            if let ScenarioEvent::Spawn { dna, .. } = event {
+                Genotype::from_hex(dna.trim()).context("Invalid DNA")?;
+            }
+        }
+        let mut messages = Vec::with_capacity(events.len());
+        for event in events {
+            messages.push(self.world.apply_scenario_event(&mut self.env, event)?);
+        }
+        Ok(messages.join("; "))
+    }
+
+    fn record_inbox_result(&mut self, dir: &Path, result: &InboxResult) {
+        let (text, color) = if result.ok {
+            (
+                format!("Inbox {}: {}", result.file, result.message),
+                Color::Cyan,
+            )
+        } else {
+            (
+                format!("Inbox {} failed: {}", result.file, result.message),
+                Color::Red,
+            )
+        };
+        tracing::info!("{text}");
+        self.event_log.push_back((text, color));
+
+        let line = match serde_json::to_string(result) {
+            Ok(line) => line,
+            Err(_) => return,
+        };
+        let written = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("results.jsonl"))
+            .and_then(|mut f| writeln!(f, "{line}"));
+        if let Err(e) = written {
+            tracing::warn!("Failed to write inbox results: {e}");
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::app::bookmarks::MAX_NOTE_LEN;
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+impl App {
+    /// Handles typing a bookmark note.
+    pub fn handle_bookmark_note_key(&mut self, key: KeyEvent) {
+        let Some((_, note)) = &mut self.bookmark_note else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => self.commit_bookmark(),
+            KeyCode::Esc => self.bookmark_note = None,
+            KeyCode::Backspace => {
+                note.pop();
+            }
+            KeyCode::Char(c) if note.chars().count() < MAX_NOTE_LEN => note.push(c),
+            _ => {}
+        }
+    }
+
+    /// Handles the bookmark list; other keys are ignored while it is open.
+    pub fn handle_bookmarks_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('N') => self.show_bookmarks = false,
+            KeyCode::Up => self.bookmark_index = self.bookmark_index.saturating_sub(1),
+            KeyCode::Down if self.bookmark_index + 1 < self.world.bookmarks.len() => {
+                self.bookmark_index += 1;
+            }
+            KeyCode::Enter => self.jump_to_bookmark(),
+            KeyCode::Char('x') | KeyCode::Delete => self.delete_bookmark(),
+            KeyCode::Char('q') => self.running = false,
+            _ => {}
+        }
+    }
+}
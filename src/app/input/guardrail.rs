@@ -0,0 +1,22 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::style::Color;
+
+impl App {
+    /// Handles the circuit breaker prompt. Only continue or quit are accepted
+    /// while a guardrail trip is pending.
+    pub fn handle_guardrail_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                self.world.acknowledge_guardrail();
+                self.paused = false;
+                self.event_log.push_back((
+                    "Guardrail limit doubled - simulation resumed".to_string(),
+                    Color::Yellow,
+                ));
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => self.running = false,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::app::layout::Pane;
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::style::Color;
+
+impl App {
+    /// Handles the layout editor; panes change live while it is open.
+    pub fn handle_layout_editor_key(&mut self, key: KeyEvent) {
+        let Some(selected) = self.layout_editor else {
+            return;
+        };
+        let pane = Pane::ALL[selected];
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('U') => self.layout_editor = None,
+            KeyCode::Up => self.layout_editor = Some(selected.saturating_sub(1)),
+            KeyCode::Down => self.layout_editor = Some((selected + 1).min(Pane::ALL.len() - 1)),
+            KeyCode::Left => self.resize_pane(pane, -1),
+            KeyCode::Right => self.resize_pane(pane, 1),
+            KeyCode::Char(' ') => self.toggle_pane(pane),
+            KeyCode::Tab => self.cycle_layout_preset(),
+            KeyCode::Char('s') => {
+                let msg = match self.save_layouts() {
+                    Ok(()) => (
+                        format!("Layouts saved to {}", self.config_path),
+                        Color::Green,
+                    ),
+                    Err(e) => (format!("Saving layouts failed: {}", e), Color::Red),
+                };
+                self.event_log.push_back(msg);
+            }
+            KeyCode::Char('q') => self.running = false,
+            _ => {}
+        }
+    }
+}
@@ -1,5 +1,16 @@
+pub mod bookmarks;
 pub mod genetic_edit;
+pub mod guardrail;
+pub mod layout;
+pub mod narrations;
 pub mod normal;
+pub mod pathogen_designer;
+pub mod phylogeny;
+pub mod registry;
+pub mod sandbox;
+pub mod screensaver;
+pub mod start_menu;
+pub mod tags;
 pub mod terrain_edit;
 
 use crate::app::state::App;
@@ -7,15 +18,66 @@ use crossterm::event::KeyEvent;
 
 impl App {
     pub fn handle_key(&mut self, key: KeyEvent) {
+        if self.world.guardrail_trip.is_some() {
+            self.handle_guardrail_key(key);
+            return;
+        }
+        if self.screensaver {
+            self.handle_screensaver_key(key);
+            return;
+        }
+        if self.start_menu.is_some() {
+            self.handle_start_menu_key(key);
+            return;
+        }
+        if self.import_trial.is_some() {
+            self.handle_import_trial_key(key);
+            return;
+        }
+        if self.show_registry {
+            self.handle_registry_key(key);
+            return;
+        }
+        if self.bookmark_note.is_some() {
+            self.handle_bookmark_note_key(key);
+            return;
+        }
+        if self.tag_prompt.is_some() {
+            self.handle_tag_prompt_key(key);
+            return;
+        }
+        if self.show_bookmarks {
+            self.handle_bookmarks_key(key);
+            return;
+        }
+        if self.show_narrations {
+            self.handle_narrations_key(key);
+            return;
+        }
+        if self.show_phylogeny {
+            self.handle_phylogeny_key(key);
+            return;
+        }
+        if self.pathogen_designer.is_some() {
+            self.handle_pathogen_designer_key(key);
+            return;
+        }
+        if self.layout_editor.is_some() {
+            self.handle_layout_editor_key(key);
+            return;
+        }
+        if self.tutorial_key(key) {
+            return;
+        }
         self.handle_normal_key(key);
+        self.update_tutorial();
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
-    use crate::app::state::UiMode;
-    use crate::model::config::AppConfig;
+    use crate::model::config::{AppConfig, ThreadingConfig};
     use crate::model::environment::Environment;
     use crate::model::world::World;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -23,7 +85,7 @@ mod tests {
     use std::time::Instant;
     use sysinfo::System;
 
-    fn create_test_app() -> App {
+    pub(crate) fn create_test_app() -> App {
         let config = AppConfig::default();
         let world = World::new(0, config.clone()).expect("World creation should not fail in tests");
 
@@ -42,6 +104,13 @@ mod tests {
             sys: System::new_all(),
             env: Environment::default(),
             cpu_history: VecDeque::new(),
+            thread_pools: std::sync::Arc::new(
+                crate::model::threading::ThreadPools::build(&ThreadingConfig {
+                    sim_threads: 1,
+                    ..Default::default()
+                })
+                .unwrap(),
+            ),
             pop_history: VecDeque::new(),
             o2_history: VecDeque::new(),
             show_brain: false,
@@ -51,30 +120,47 @@ mod tests {
             social_brush: 0,
             is_social_brush: false,
             show_ancestry: false,
+            show_energy_ledger: false,
+            show_route_trail: false,
+            show_palette: true,
+            show_ecs_report: false,
+            show_calendar: false,
+            show_forecast: false,
+            show_perception: false,
             last_climate: None,
             last_anchor_time: Instant::now(),
             anchor_interval: std::time::Duration::from_secs(3600),
             is_anchoring: false,
-            ui_mode: UiMode::default(),
+            layout_preset: 0,
+            layout_editor: None,
             screensaver: false,
+            screensaver_state: Default::default(),
+            attract: None,
             cinematic_mode: false,
             show_help: false,
             show_legend: true,
             help_tab: 0,
             show_archeology: false,
             auto_play_history: false,
-            archeology_snapshots: Vec::new(),
+            archeology: Default::default(),
             archeology_index: 0,
             selected_fossil_index: 0,
-            onboarding_step: None,
+            fossil_filter: Default::default(),
+            tutorial: None,
+            profile: None,
+            interest: None,
+            start_menu: None,
             view_mode: 0,
+            pheromone_channel: 0,
             last_world_rect: ratatui::layout::Rect::default(),
             last_sidebar_rect: ratatui::layout::Rect::default(),
             gene_editor_offset: 0,
             event_log: VecDeque::new(),
+            narration_feed: Default::default(),
             network_state: primordium_net::NetworkState::default(),
             latest_snapshot: None,
             network: None,
+            migration_backoff: None,
             hof_query_rx: None,
             cached_hall_of_fame: Vec::new(),
             // Phase 70: Registry
@@ -85,6 +171,26 @@ mod tests {
             cached_registry_genomes: Vec::new(),
             cached_registry_seeds: Vec::new(),
             registry_selected_index: 0,
+            registry_rx: None,
+            cached_registry_submissions: Vec::new(),
+            registry_submit_rx: None,
+            import_trial_rx: None,
+            import_trial: None,
+            pathogen_designer: None,
+            bookmark_note: None,
+            show_bookmarks: false,
+            bookmark_index: 0,
+            narrations: VecDeque::new(),
+            show_narrations: false,
+            narration_index: 0,
+            narration_expanded: false,
+            show_phylogeny: false,
+            phylogeny_index: 0,
+            phylogeny_collapsed: std::collections::HashSet::new(),
+            log_quota_warned: false,
+            tag_prompt: None,
+            tag_filter: None,
+            glyphs: Default::default(),
             input_log: Vec::new(),
             replay_queue: VecDeque::new(),
             replay_mode: false,
@@ -122,10 +228,806 @@ mod tests {
         app.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::empty()));
         assert!(app.show_ancestry);
 
+        app.handle_key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::empty()));
+        assert!(app.show_energy_ledger);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::empty()));
+        assert!(app.show_route_trail);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::empty()));
+        assert!(!app.show_palette);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('~'), KeyModifiers::empty()));
+        assert!(app.show_ecs_report);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('Y'), KeyModifiers::empty()));
+        assert!(app.show_calendar);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::empty()));
+        assert!(app.show_forecast);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::empty()));
+        assert!(app.show_perception);
+
         app.handle_key(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::empty()));
         assert!(app.show_help);
     }
 
+    #[test]
+    fn test_guardrail_prompt_captures_keys() {
+        use crate::model::guardrail::{BreakerKind, GuardrailTrip};
+
+        let mut app = create_test_app();
+        app.paused = true;
+        app.world.guardrail_trip = Some(GuardrailTrip {
+            kind: BreakerKind::EntityCount,
+            observed: 60_000,
+            limit: 50_000,
+            tick: 10,
+            suspected_source: "test".to_string(),
+        });
+
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::empty()));
+        assert!(app.paused);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::empty()));
+        assert!(!app.paused);
+        assert!(app.world.guardrail_trip.is_none());
+        assert_eq!(app.world.config.guardrail.max_entities, 100_000);
+    }
+
+    #[test]
+    fn test_screensaver_only_accepts_quit() {
+        let mut app = create_test_app();
+        app.screensaver = true;
+
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::empty()));
+        assert!(!app.paused);
+        assert!(!app.show_brain);
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn test_screensaver_restart_cycles_seeds_and_views() {
+        let mut app = create_test_app();
+        app.screensaver = true;
+        app.config.screensaver.seeds = vec![7, 8];
+        app.config.screensaver.views = vec![0, 2];
+        app.config.screensaver.gallery_share = 0.0;
+
+        app.restart_screensaver_run().unwrap();
+        assert_eq!(app.screensaver_state.current_seed, Some(7));
+        assert_eq!(app.world.config.world.seed, Some(7));
+        app.restart_screensaver_run().unwrap();
+        app.restart_screensaver_run().unwrap();
+        assert_eq!(app.screensaver_state.current_seed, Some(7));
+        assert_eq!(app.screensaver_state.runs, 3);
+        assert_eq!(app.world.tick, 0);
+
+        app.rotate_screensaver_view();
+        assert_eq!(app.view_mode, 2);
+        app.rotate_screensaver_view();
+        assert_eq!(app.view_mode, 0);
+    }
+
+    #[test]
+    fn test_tutorial_waits_for_scripted_triggers() {
+        use crate::app::tutorial::{Lesson, Tutorial};
+
+        let mut app = create_test_app();
+        let lesson = Lesson::from_toml(
+            r#"
+            id = "test"
+            title = "Test"
+
+            [[steps]]
+            title = "Intro"
+            text = ["Hello"]
+
+            [[steps]]
+            title = "Pause"
+            text = ["Pause it"]
+            wait_for = { action = "paused" }
+            spawn = [{ kind = "predator", x = 0.5, y = 0.5, count = 2 }]
+
+            [[steps]]
+            title = "Brain"
+            text = ["Open the brain"]
+            wait_for = { key = "b" }
+            "#,
+        )
+        .unwrap();
+        app.tutorial = Some(Tutorial::new(lesson));
+        app.update_tutorial();
+        assert_eq!(app.world.get_population_count(), 0);
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.tutorial.as_ref().unwrap().step, 1);
+        assert_eq!(app.world.get_population_count(), 2);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::empty()));
+        assert!(app.paused);
+        assert_eq!(app.tutorial.as_ref().unwrap().step, 2);
+
+        // Key triggers keep their normal effect.
+        app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::empty()));
+        assert!(app.show_brain);
+        assert!(app.tutorial.is_none());
+    }
+
+    #[test]
+    fn test_tutorial_can_be_skipped() {
+        let mut app = create_test_app();
+        assert!(app.start_tutorial("predation"));
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert!(app.tutorial.is_none());
+        assert!(!app.start_tutorial("no-such-lesson"));
+    }
+
+    #[test]
+    fn test_clipboard_dna_keys_in_gene_editor() {
+        let mut app = create_test_app();
+        let genotype =
+            crate::model::brain::create_genotype_random_with_rng(&mut rand::thread_rng());
+        app.spawn_with_genotype(std::sync::Arc::new(genotype.clone()), 10.0, 10.0);
+        let id = app
+            .world
+            .ecs
+            .query::<&primordium_data::Identity>()
+            .iter()
+            .next()
+            .map(|(_, identity)| identity.id);
+        app.selected_entity = id;
+        app.show_brain = true;
+        app.focused_gene = Some(primordium_data::GeneType::Speed);
+
+        // Headless test runs have no clipboard, so either outcome is reported.
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        let msg = &app.event_log.back().unwrap().0;
+        assert!(msg.starts_with("DNA copied") || msg.starts_with("Copy failed"));
+        assert!(!std::path::Path::new("exported_dna.txt").exists());
+
+        app.spawn_from_dna_text(&genotype.to_hex());
+        assert_eq!(app.world.get_population_count(), 2);
+        assert_eq!(
+            app.selected_genotype().unwrap().lineage_id,
+            genotype.lineage_id
+        );
+
+        app.spawn_from_dna_text("garbage");
+        assert_eq!(app.world.get_population_count(), 2);
+        assert!(app.event_log.back().unwrap().0.contains("valid DNA"));
+    }
+
+    #[test]
+    fn test_session_profile_counts_ticks() {
+        let mut app = create_test_app();
+        app.profile = crate::app::profile::ProfileSession::open(":memory:", &app.world);
+
+        for _ in 0..5 {
+            app.world.update(&mut app.env).unwrap();
+            app.update_profile();
+        }
+        app.close_profile();
+        let profile = &app.profile.as_ref().unwrap().profile;
+        assert_eq!(profile.runs, 1);
+        assert_eq!(profile.total_ticks, 5);
+    }
+
+    #[test]
+    fn test_start_menu_new_world_wizard() {
+        let mut app = create_test_app();
+        app.open_start_menu();
+        let press = |app: &mut App, code: KeyCode| {
+            app.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        };
+
+        // Keys are captured by the menu.
+        press(&mut app, KeyCode::Char('b'));
+        assert!(!app.show_brain);
+
+        if app.start_menu.as_ref().unwrap().has_save {
+            press(&mut app, KeyCode::Down);
+        }
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Down);
+        for c in "42".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Enter);
+
+        assert!(app.start_menu.is_none());
+        assert_eq!(app.world.config.world.seed, Some(42));
+        assert_eq!((app.world.width, app.world.height), (60, 30));
+        assert!(matches!(
+            app.world.config.game_mode,
+            crate::model::config::GameMode::Cooperative
+        ));
+    }
+
+    #[test]
+    fn test_start_menu_reports_missing_replay() {
+        let mut app = create_test_app();
+        app.open_start_menu();
+        app.activate_menu_item(crate::app::start_menu::MenuItem::LoadReplay);
+        if std::path::Path::new(crate::app::start_menu::REPLAY_FILE).exists() {
+            return;
+        }
+        assert!(app.start_menu.as_ref().unwrap().error.is_some());
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert!(app.start_menu.is_none());
+    }
+
+    #[test]
+    fn test_marketplace_navigation() {
+        use crate::client::registry::{HallOfFameEntry, RegistryFetch};
+
+        let mut app = create_test_app();
+        app.show_registry = true;
+        app.apply_registry_fetch(RegistryFetch {
+            hall_of_fame: Ok((0..3)
+                .map(|i| HallOfFameEntry {
+                    id: i.to_string(),
+                    civilization_level: i,
+                    is_extinct: false,
+                })
+                .collect()),
+            genomes: Ok(Vec::new()),
+            seeds: Err("offline".to_string()),
+            submissions: Vec::new(),
+            thumbnails: Default::default(),
+        });
+        assert_eq!(app.cached_registry_hof.len(), 3);
+        assert!(app.event_log.back().unwrap().0.contains("offline"));
+
+        for _ in 0..5 {
+            app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        }
+        assert_eq!(app.registry_selected_index, 2);
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()));
+        assert_eq!((app.registry_tab, app.registry_selected_index), (1, 0));
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::empty()));
+        assert!(!app.paused);
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert!(!app.show_registry);
+    }
+
+    #[test]
+    fn test_marketplace_previews_seed_thumbnails() {
+        use crate::client::registry::{RegistryFetch, SeedRecord};
+        use primordium_io::thumbnail::Thumbnail;
+
+        let mut app = create_test_app();
+        let seed = |id: &str| SeedRecord {
+            id: id.to_string(),
+            author: "a".to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            tags: String::new(),
+            config_json: "{}".to_string(),
+            avg_tick_time: 0.0,
+            max_pop: 10,
+            performance_summary: String::new(),
+            downloads: 0,
+            created_at: String::new(),
+            has_thumbnail: id == "pictured",
+        };
+        let png = Thumbnail::render(&app.world.terrain, [(5.0, 5.0)]).to_png();
+        app.apply_registry_fetch(RegistryFetch {
+            hall_of_fame: Ok(Vec::new()),
+            genomes: Ok(Vec::new()),
+            seeds: Ok(vec![seed("pictured"), seed("plain")]),
+            submissions: Vec::new(),
+            thumbnails: [("pictured".to_string(), png)].into(),
+        });
+
+        let previews: Vec<_> = app
+            .cached_registry_seeds
+            .iter()
+            .map(|s| s.preview.len())
+            .collect();
+        assert_eq!(previews, [8, 0]);
+        assert_eq!(app.cached_registry_seeds[0].preview[0].chars().count(), 32);
+    }
+
+    #[test]
+    fn test_marketplace_genomes_are_trialled_before_release() {
+        use primordium_tui::views::registry::GenomeRecord;
+
+        let mut app = create_test_app();
+        app.config.sandbox.ticks = 10;
+        app.config.sandbox.natives = 10;
+        let genotype =
+            crate::model::brain::create_genotype_random_with_rng(&mut rand::thread_rng());
+        app.show_registry = true;
+        app.registry_tab = 1;
+        app.cached_registry_genomes = vec![GenomeRecord {
+            id: "g".to_string(),
+            lineage_id: None,
+            genotype: genotype.to_hex(),
+            author: "a".to_string(),
+            name: "Gen 9".to_string(),
+            description: String::new(),
+            tags: String::new(),
+            fitness_score: 0.0,
+            offspring_count: 0,
+            tick: 0,
+            downloads: 0,
+            created_at: String::new(),
+            crest: None,
+        }];
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('I'), KeyModifiers::empty()));
+        let deadline = Instant::now() + std::time::Duration::from_secs(60);
+        while app.import_trial.is_none() && Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            app.poll_registry();
+        }
+        let trial = app.import_trial.as_ref().expect("trial finished");
+        assert_eq!(trial.report.as_ref().unwrap().founders, 5);
+        assert!(trial.rows().len() >= 6);
+        assert_eq!(app.world.get_population_count(), 0);
+
+        // The report holds every key until the genome is released or discarded.
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()));
+        assert_eq!(app.registry_tab, 1);
+        app.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::empty()));
+        assert!(app.import_trial.is_none());
+        assert_eq!(app.world.get_population_count(), 5);
+        assert!(app.event_log.back().unwrap().0.contains("Released Gen 9"));
+    }
+
+    #[test]
+    fn test_marketplace_tracks_submission_moderation() {
+        use crate::client::registry::{
+            ModerationStatus, RegistryFetch, SubmissionReceipt, SubmissionState,
+        };
+
+        let mut app = create_test_app();
+        let id = uuid::Uuid::new_v4();
+        app.record_submission((
+            "Gen 3".to_string(),
+            Ok(SubmissionReceipt {
+                id: id.to_string(),
+                status: ModerationStatus::Pending,
+            }),
+        ));
+        assert_eq!(app.cached_registry_submissions[0].status, "pending");
+        assert!(app.event_log.back().unwrap().0.contains("awaits"));
+
+        app.apply_registry_fetch(RegistryFetch {
+            hall_of_fame: Ok(Vec::new()),
+            genomes: Ok(Vec::new()),
+            seeds: Ok(Vec::new()),
+            submissions: vec![Ok(SubmissionState {
+                id,
+                status: ModerationStatus::Rejected,
+                note: Some("duplicate".to_string()),
+            })],
+            thumbnails: Default::default(),
+        });
+        let entry = &app.cached_registry_submissions[0];
+        assert_eq!(entry.status, "rejected");
+        assert_eq!(entry.note.as_deref(), Some("duplicate"));
+        assert!(app
+            .event_log
+            .iter()
+            .any(|(message, _)| message == "Registry: Gen 3 was rejected: duplicate"));
+    }
+
+    #[test]
+    fn test_pathogen_designer_releases_into_lineage() {
+        let mut app = create_test_app();
+        let genotype = std::sync::Arc::new(crate::model::brain::create_genotype_random_with_rng(
+            &mut rand::thread_rng(),
+        ));
+        app.spawn_with_genotype(genotype.clone(), 5.0, 5.0);
+        app.spawn_with_genotype(genotype.clone(), 40.0, 40.0);
+        app.spawn_with_genotype(
+            std::sync::Arc::new(crate::model::brain::create_genotype_random_with_rng(
+                &mut rand::thread_rng(),
+            )),
+            5.0,
+            5.0,
+        );
+        let press =
+            |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        let infected = |app: &App| {
+            app.world
+                .ecs
+                .query::<&primordium_data::Health>()
+                .iter()
+                .filter(|(_, health)| health.pathogen.is_some())
+                .count()
+        };
+
+        press(&mut app, KeyCode::Char('D'));
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Down);
+        }
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        let designer = app.pathogen_designer.as_ref().unwrap();
+        assert!(designer
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Select an entity"));
+
+        app.selected_entity = app
+            .world
+            .ecs
+            .query::<(&primordium_data::Identity, &primordium_data::Metabolism)>()
+            .iter()
+            .find(|(_, (_, met))| met.lineage_id == genotype.lineage_id)
+            .map(|(_, (identity, _))| identity.id);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(infected(&app), 0);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app.pathogen_designer.is_none());
+        assert_eq!(infected(&app), 2);
+        assert!(app.event_log.back().unwrap().0.contains("infecting 2"));
+
+        // Releasing at a position only reaches entities near it.
+        app.open_pathogen_designer();
+        app.begin_pathogen_release();
+        app.aim_pathogen_release(5.0, 5.0);
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app
+            .event_log
+            .back()
+            .unwrap()
+            .0
+            .contains("at (5, 5), infecting 2"));
+        assert_eq!(infected(&app), 3);
+    }
+
+    #[test]
+    fn test_bookmark_note_and_jump() {
+        let mut app = create_test_app();
+        let press =
+            |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        app.world.tick = 250;
+        press(&mut app, KeyCode::Char('n'));
+        for c in "bloom q".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Enter);
+        assert!(app.running);
+        assert_eq!(app.world.bookmarks.len(), 1);
+        assert_eq!(app.world.bookmarks[0].note, "bloom");
+        assert!(app.event_log.back().unwrap().0.contains("tick 250: bloom"));
+
+        app.world.tick = 100;
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.world.bookmarks[0].tick, 100);
+
+        let dir = std::env::temp_dir().join(format!("bookmarks_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut log = String::new();
+        for tick in [0, 200, 400] {
+            let stats = primordium_data::PopulationStats {
+                population: tick as usize,
+                ..Default::default()
+            };
+            let snapshot = primordium_data::LiveEvent::Snapshot {
+                tick,
+                stats: Box::new(stats),
+                timestamp: String::new(),
+            };
+            log.push_str(&serde_json::to_string(&snapshot).unwrap());
+            log.push('\n');
+        }
+        std::fs::write(dir.join("live.jsonl"), log).unwrap();
+        app.world.logger =
+            primordium_io::history::HistoryLogger::new_at(dir.to_str().unwrap()).unwrap();
+
+        press(&mut app, KeyCode::Char('N'));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert!(app.show_archeology);
+        assert!(!app.show_bookmarks);
+        assert_eq!(app.archeology_index, 1);
+        assert!(app.archeology.cached(1).is_none());
+        app.load_archeology_snapshot();
+        assert_eq!(app.archeology.cached(1).unwrap().population, 200);
+        assert_eq!(app.archeology.cached_len(), 1);
+
+        press(&mut app, KeyCode::Char('N'));
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.world.bookmarks.len(), 1);
+        assert_eq!(app.bookmark_index, 0);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_narration_expands_into_its_references() {
+        let mut app = create_test_app();
+        let press =
+            |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        let lineage = uuid::Uuid::new_v4();
+        for (tick, text) in [(10, "first"), (20, "second")] {
+            app.remember_narration(&primordium_data::LiveEvent::Narration {
+                tick,
+                text: text.to_string(),
+                severity: 0.5,
+                timestamp: String::new(),
+                refs: primordium_data::NarrationRefs {
+                    lineages: vec![lineage],
+                    data: vec![format!("population: {}", tick)],
+                    ..primordium_data::NarrationRefs::at(tick)
+                },
+            });
+        }
+
+        press(&mut app, KeyCode::Char('E'));
+        assert!(app.show_narrations);
+        assert_eq!(app.narration_rows().len(), 2);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        let rows = app.narration_rows();
+        assert!(rows[0].0.contains("second"));
+        assert!(rows[1].0.contains("first"));
+        assert!(rows[2].0.contains("ticks: 10"));
+        assert!(rows[3].0.contains(&lineage.to_string()));
+        assert!(rows[4].0.contains("population: 10"));
+        assert!(!rows[2].1);
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.show_narrations);
+        assert!(app.running);
+    }
+
+    #[test]
+    fn test_phylogeny_browser_folds_and_follows_a_lineage() {
+        let mut app = create_test_app();
+        let press =
+            |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        let mut lineages = Vec::new();
+        for x in [5.0, 10.0, 15.0] {
+            let entity = primordium_core::lifecycle::create_entity(x, 5.0, 0);
+            let lineage = entity.metabolism.lineage_id;
+            app.world.lineage_registry.record_birth(lineage, 1, 0);
+            app.world.spawn_entity(entity);
+            lineages.push(lineage);
+        }
+        let (parent, child) = (lineages[0], lineages[1]);
+        app.world.lineage_registry.record_speciation(child, parent);
+
+        press(&mut app, KeyCode::Char('H'));
+        assert!(app.show_phylogeny);
+        let rows = app.phylogeny_rows().1;
+        assert_eq!(rows.len(), lineages.len());
+        app.phylogeny_index = rows.iter().position(|r| r.id == parent).unwrap();
+
+        press(&mut app, KeyCode::Left);
+        assert!(app.phylogeny_collapsed.contains(&parent));
+        assert_eq!(app.phylogeny_rows().1.len(), lineages.len() - 1);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.phylogeny_rows().1[app.phylogeny_index].id, child);
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.phylogeny_rows().1[app.phylogeny_index].id, parent);
+
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.show_phylogeny);
+        let followed = app.selected_entity.unwrap();
+        let lineage = app
+            .world
+            .ecs
+            .query::<(&primordium_data::Identity, &primordium_data::Metabolism)>()
+            .iter()
+            .find(|(_, (identity, _))| identity.id == followed)
+            .map(|(_, (_, m))| m.lineage_id);
+        assert_eq!(lineage, Some(parent));
+    }
+
+    #[test]
+    fn test_fossil_browser_filters_by_era_and_sorts() {
+        let mut app = create_test_app();
+        let press =
+            |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        let genotype = crate::model::lifecycle::create_entity(0.0, 0.0, 0)
+            .intel
+            .genotype;
+        for (name, era, tick, offspring, trophic) in [
+            ("Old", "Primordial", 100, 5, 0.9),
+            ("Mid", "Flourishing", 200, 50, 0.1),
+            ("New", "Primordial", 300, 20, 0.5),
+        ] {
+            let mut genotype = (*genotype).clone();
+            genotype.trophic_potential = trophic;
+            app.world
+                .fossil_registry
+                .add_fossil(primordium_data::Fossil {
+                    lineage_id: uuid::Uuid::new_v4(),
+                    name: name.to_string(),
+                    color_rgb: (0, 0, 0),
+                    avg_lifespan: 0.0,
+                    max_generation: 1,
+                    total_offspring: offspring,
+                    extinct_tick: tick,
+                    peak_population: 1,
+                    genotype,
+                    era: era.to_string(),
+                    climate: "Temperate".to_string(),
+                    extinction_cause: primordium_data::DeathCause::Starvation,
+                });
+        }
+        let names = |app: &App| -> Vec<String> {
+            app.visible_fossils()
+                .iter()
+                .map(|f| f.name.clone())
+                .collect()
+        };
+        press(&mut app, KeyCode::Char('y'));
+        assert_eq!(names(&app), ["Mid", "New", "Old"]);
+
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.fossil_filter.era.as_deref(), Some("Primordial"));
+        assert_eq!(names(&app), ["New", "Old"]);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected_fossil_index, 1);
+
+        press(&mut app, KeyCode::Char('s'));
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(
+            app.fossil_filter.sort,
+            crate::app::archeology::FossilSort::Trophic
+        );
+        assert_eq!(app.selected_fossil_index, 0);
+        assert_eq!(names(&app), ["Old", "New"]);
+
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.fossil_filter.era.as_deref(), Some("Flourishing"));
+        assert_eq!(names(&app), ["Mid"]);
+    }
+
+    #[test]
+    fn test_tag_selected_and_search() {
+        let mut app = create_test_app();
+        let press =
+            |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        let type_text = |app: &mut App, text: &str| {
+            for c in text.chars() {
+                app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+            }
+            app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        };
+        let entity = primordium_core::lifecycle::create_entity(5.0, 5.0, 0);
+        let id = entity.identity.id;
+        app.world.spawn_entity(entity);
+
+        press(&mut app, KeyCode::Char('*'));
+        assert!(app.tag_prompt.is_none(), "nothing selected");
+        app.selected_entity = Some(id);
+        press(&mut app, KeyCode::Char('*'));
+        type_text(&mut app, "hero");
+        assert!(app.running);
+        assert_eq!(app.world.entities_tagged("hero"), vec![id]);
+
+        press(&mut app, KeyCode::Char('/'));
+        type_text(&mut app, "hero");
+        assert_eq!(app.tag_filter.as_deref(), Some("hero"));
+
+        press(&mut app, KeyCode::Char('*'));
+        type_text(&mut app, "hero");
+        assert!(app.world.tags_of(id).is_empty());
+
+        press(&mut app, KeyCode::Char('/'));
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Backspace);
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(app.tag_filter.is_none());
+    }
+
+    #[test]
+    fn test_layout_editor_edits_and_saves_view_layout() {
+        let mut app = create_test_app();
+        let press =
+            |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        let path = std::env::temp_dir().join(format!("layout_{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "target_fps = 30\n").unwrap();
+        app.config_path = path.to_string_lossy().into_owned();
+
+        press(&mut app, KeyCode::Char('U'));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Right);
+        let layout = app.current_layout();
+        assert_eq!((layout.chronicle, layout.sidebar), (0, 40));
+        assert_eq!(
+            layout.sidebar_panel,
+            primordium_core::config::SidebarPanel::Brain
+        );
+
+        press(&mut app, KeyCode::Char('s'));
+        let saved = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let table: toml::Table = saved.parse().unwrap();
+        assert_eq!(table["target_fps"].as_integer(), Some(30));
+        assert_eq!(
+            table["layout"]["views"]["0"]["chronicle"].as_integer(),
+            Some(0)
+        );
+
+        // Other view modes keep the preset; Tab drops the edited layout.
+        app.view_mode = 1;
+        assert_eq!(app.current_layout().chronicle, 7);
+        app.view_mode = 0;
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.current_layout().name, "Immersive");
+        assert!(app.config.layout.views.is_empty());
+        press(&mut app, KeyCode::Esc);
+        assert!(app.layout_editor.is_none());
+    }
+
+    #[test]
+    fn test_inbox_injects_dna_and_scenario_files() {
+        let mut app = create_test_app();
+        let dir = std::env::temp_dir().join(format!("inbox_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        app.config.inbox.dir = dir.to_string_lossy().into_owned();
+
+        let genotype =
+            crate::model::brain::create_genotype_random_with_rng(&mut rand::thread_rng());
+        std::fs::write(dir.join("01_avatar.dna"), genotype.to_hex()).unwrap();
+        std::fs::write(
+            dir.join("02_script.json"),
+            r#"[{"event": "climate", "state": "Scorching"},
+                {"event": "bookmark", "note": "heat wave"}]"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("03_bad.json"), r#"{"event": "meteor"}"#).unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let results = app.poll_inbox();
+        assert_eq!(
+            results.iter().map(|r| r.ok).collect::<Vec<_>>(),
+            vec![true, true, false]
+        );
+        assert_eq!(app.world.get_population_count(), 1);
+        assert_eq!(
+            app.env.god_climate_override,
+            Some(crate::model::environment::ClimateState::Scorching)
+        );
+        assert_eq!(app.world.bookmarks[0].note, "heat wave");
+        assert!(app
+            .event_log
+            .back()
+            .unwrap()
+            .0
+            .contains("03_bad.json failed"));
+
+        assert!(dir.join("processed/01_avatar.dna").exists());
+        assert!(dir.join("failed/03_bad.json").exists());
+        assert!(dir.join("notes.txt").exists());
+        let log = std::fs::read_to_string(dir.join("results.jsonl")).unwrap();
+        assert_eq!(log.lines().count(), 3);
+        assert!(app.poll_inbox().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_view_mode_switching() {
         let mut app = create_test_app();
@@ -133,6 +1035,8 @@ mod tests {
         assert_eq!(app.view_mode, 1);
         app.handle_key(KeyEvent::new(KeyCode::Char('3'), KeyModifiers::empty()));
         assert_eq!(app.view_mode, 2);
+        app.handle_key(KeyEvent::new(KeyCode::Char(')'), KeyModifiers::SHIFT));
+        assert_eq!(app.view_mode, 10);
     }
 
     #[test]
@@ -0,0 +1,22 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+impl App {
+    /// Handles the narration list; other keys are ignored while it is open.
+    pub fn handle_narrations_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('E') => self.show_narrations = false,
+            KeyCode::Up => {
+                self.narration_index = self.narration_index.saturating_sub(1);
+                self.narration_expanded = false;
+            }
+            KeyCode::Down if self.narration_index + 1 < self.narrations.len() => {
+                self.narration_index += 1;
+                self.narration_expanded = false;
+            }
+            KeyCode::Enter => self.narration_expanded = !self.narration_expanded,
+            KeyCode::Char('q') => self.running = false,
+            _ => {}
+        }
+    }
+}
@@ -1,8 +1,12 @@
 use crate::app::state::App;
+use crate::model::brain::GenotypeLogic;
 use crate::model::lifecycle;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::model::lineage_tree::LineageTree;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use primordium_core::pheromone::PheromoneType;
 use primordium_core::systems::intel;
 use primordium_data::TerrainType;
+use primordium_io::card::CreatureCard;
 use rand::Rng;
 use ratatui::style::Color;
 use std::fs;
@@ -11,47 +15,55 @@ impl App {
     pub fn handle_normal_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Char('q') => self.running = false,
-            KeyCode::Char(' ') => {
-                if self.onboarding_step.is_some() {
-                    self.advance_onboarding();
-                } else {
-                    self.paused = !self.paused;
-                }
-            }
+            KeyCode::Char(' ') => self.paused = !self.paused,
             KeyCode::Char('b') => self.show_brain = !self.show_brain,
-            KeyCode::Char('B') => {
-                if self.backup_state().is_ok() {
-                    self.event_log.push_back((
-                        "World state BACKED UP to backups/".to_string(),
-                        Color::Green,
-                    ));
-                }
+            KeyCode::Char('B') if self.backup_state().is_ok() => {
+                self.event_log.push_back((
+                    "World state BACKED UP to backups/".to_string(),
+                    Color::Green,
+                ));
             }
-            KeyCode::Char('a') => self.show_ancestry = !self.show_ancestry,
-            KeyCode::Char('y') => {
-                self.show_archeology = !self.show_archeology;
-                if self.show_archeology {
-                    if let Ok(snaps) = self.world.logger.get_snapshots_recent(100) {
-                        self.archeology_snapshots = snaps;
-                        self.archeology_index = self.archeology_snapshots.len().saturating_sub(1);
-                    }
-                }
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.export_phylogeny();
             }
+            KeyCode::Char('a') => self.show_ancestry = !self.show_ancestry,
+            KeyCode::Char('H') => self.toggle_phylogeny(),
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.export_creature_card();
+            }
+            KeyCode::Char('e') => self.show_energy_ledger = !self.show_energy_ledger,
+            KeyCode::Char('~') => self.show_ecs_report = !self.show_ecs_report,
+            KeyCode::Char('Y') => self.show_calendar = !self.show_calendar,
+            KeyCode::Char('?') => self.show_forecast = !self.show_forecast,
+            KeyCode::Char('d') => self.show_perception = !self.show_perception,
+            KeyCode::Char('y') => self.toggle_archeology(),
             KeyCode::Char('[') if self.show_archeology => {
                 self.archeology_index = self.archeology_index.saturating_sub(1);
             }
-            KeyCode::Char(']') if self.show_archeology => {
-                if self.archeology_index + 1 < self.archeology_snapshots.len() {
-                    self.archeology_index += 1;
-                }
+            KeyCode::Char(']')
+                if self.show_archeology && self.archeology_index + 1 < self.archeology.len() =>
+            {
+                self.archeology_index += 1;
             }
             KeyCode::Up if self.show_archeology => {
                 self.selected_fossil_index = self.selected_fossil_index.saturating_sub(1);
             }
-            KeyCode::Down if self.show_archeology => {
-                if self.selected_fossil_index + 1 < self.world.fossil_registry.fossils.len() {
-                    self.selected_fossil_index += 1;
-                }
+            KeyCode::Down
+                if self.show_archeology
+                    && self.selected_fossil_index + 1 < self.visible_fossils().len() =>
+            {
+                self.selected_fossil_index += 1;
+            }
+            KeyCode::Left | KeyCode::Right if self.show_archeology => {
+                self.fossil_filter.cycle_era(
+                    &self.world.fossil_registry.fossils,
+                    key.code == KeyCode::Right,
+                );
+                self.selected_fossil_index = 0;
+            }
+            KeyCode::Char('s') if self.show_archeology => {
+                self.fossil_filter.sort = self.fossil_filter.sort.next();
+                self.selected_fossil_index = 0;
             }
             KeyCode::Char('g') | KeyCode::Char('G') if self.show_archeology => {
                 self.handle_fossil_resurrection();
@@ -94,26 +106,15 @@ impl App {
                     ));
                 }
             }
+            KeyCode::Char('E') => self.toggle_narrations(),
             KeyCode::Char('A') => {
                 self.export_ancestry_tree();
             }
             KeyCode::Char('h') => {
                 self.show_help = !self.show_help;
-                if self.show_help {
-                    self.onboarding_step = None;
-                }
-            }
-            KeyCode::Tab => {
-                // Cycle through UI modes: Standard -> Immersive -> Expert -> Standard
-                use crate::app::state::UiMode;
-                self.ui_mode = match self.ui_mode {
-                    UiMode::Standard => UiMode::Immersive,
-                    UiMode::Immersive => UiMode::Expert,
-                    UiMode::Expert => UiMode::Standard,
-                };
-                self.event_log
-                    .push_back((format!("UI Mode: {:?}", self.ui_mode), Color::Cyan));
             }
+            KeyCode::Tab => self.cycle_layout_preset(),
+            KeyCode::Char('U') => self.layout_editor = Some(0),
             KeyCode::Char('u') => {
                 self.audio.toggle();
                 self.event_log.push_back((
@@ -138,46 +139,66 @@ impl App {
                 self.event_log
                     .push_back(("Audio volume increased".to_string(), Color::Cyan));
             }
-            KeyCode::Char('1') if !self.show_help && self.onboarding_step.is_none() => {
+            KeyCode::Char('1') if !self.show_help => {
                 self.view_mode = 0;
                 self.event_log
                     .push_back(("View: NORMAL".to_string(), Color::White));
             }
-            KeyCode::Char('2') if !self.show_help && self.onboarding_step.is_none() => {
+            KeyCode::Char('2') if !self.show_help => {
                 self.view_mode = 1;
                 self.event_log
                     .push_back(("View: FERTILITY HEATMAP".to_string(), Color::Green));
             }
-            KeyCode::Char('3') if !self.show_help && self.onboarding_step.is_none() => {
+            KeyCode::Char('3') if !self.show_help => {
                 self.view_mode = 2;
                 self.event_log
                     .push_back(("View: SOCIAL ZONES".to_string(), Color::Cyan));
             }
-            KeyCode::Char('4') if !self.show_help && self.onboarding_step.is_none() => {
+            KeyCode::Char('4') if !self.show_help => {
                 self.view_mode = 3;
                 self.event_log
                     .push_back(("View: RANK HEATMAP".to_string(), Color::Magenta));
             }
-            KeyCode::Char('5') if !self.show_help && self.onboarding_step.is_none() => {
+            KeyCode::Char('5') if !self.show_help => {
                 self.view_mode = 4;
                 self.event_log
                     .push_back(("View: VOCAL PROPAGATION".to_string(), Color::Yellow));
             }
-            KeyCode::Char('6') if !self.show_help && self.onboarding_step.is_none() => {
+            KeyCode::Char('6') if !self.show_help => {
                 self.view_mode = 5;
                 self.event_log
                     .push_back(("View: MULTIVERSE MARKET".to_string(), Color::Cyan));
             }
-            KeyCode::Char('7') if !self.show_help && self.onboarding_step.is_none() => {
+            KeyCode::Char('7') if !self.show_help => {
                 self.view_mode = 6;
                 self.event_log
                     .push_back(("View: NEURAL RESEARCH".to_string(), Color::Magenta));
             }
-            KeyCode::Char('8') if !self.show_help && self.onboarding_step.is_none() => {
+            KeyCode::Char('8') if !self.show_help => {
                 self.view_mode = 7;
                 self.event_log
                     .push_back(("View: CIVILIZATION".to_string(), Color::Yellow));
             }
+            KeyCode::Char('9') if !self.show_help => {
+                let channels = &self.world.pheromones.channels;
+                // Starts on death scent; pressing again steps through the channels.
+                self.pheromone_channel = if self.view_mode == 8 {
+                    (self.pheromone_channel + 1) % channels.len().max(1)
+                } else {
+                    self.world
+                        .pheromones
+                        .channel_of(PheromoneType::Death)
+                        .unwrap_or(0)
+                };
+                self.view_mode = 8;
+                let name = channels
+                    .get(self.pheromone_channel)
+                    .map_or("none", |c| c.name.as_str());
+                self.event_log.push_back((
+                    format!("View: PHEROMONE ({})", name.to_uppercase()),
+                    Color::Red,
+                ));
+            }
             KeyCode::Char('0') if self.view_mode == 6 => {
                 if let Some(id) = self.selected_entity {
                     self.world.clear_research_deltas(id);
@@ -195,16 +216,24 @@ impl App {
                 let idx = c.to_digit(10).map(|d| d as usize).unwrap_or(0);
                 self.accept_trade_offer(idx);
             }
-            KeyCode::Char('t') | KeyCode::Char('T') if self.view_mode == 5 => {
-                self.propose_random_trade();
+            KeyCode::Char('0') if !self.show_help => {
+                self.view_mode = 9;
+                self.event_log
+                    .push_back(("View: HYDRATION HEATMAP".to_string(), Color::Blue));
             }
-            KeyCode::Enter if self.onboarding_step.is_some() => {
-                self.advance_onboarding();
+            KeyCode::Char(')') => {
+                self.view_mode = 10;
+                self.event_log
+                    .push_back(("View: MUTATION HEAT".to_string(), Color::LightRed));
             }
-            KeyCode::Esc if self.onboarding_step.is_some() => {
-                let _ = fs::write(".primordium_onboarded", "1");
-                self.onboarding_step = None;
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.export_route_trail();
             }
+            KeyCode::Char('t') | KeyCode::Char('T') if self.view_mode == 5 => {
+                self.propose_random_trade();
+            }
+            KeyCode::Char('t') => self.show_route_trail = !self.show_route_trail,
+            KeyCode::Char('g') => self.show_palette = !self.show_palette,
             KeyCode::Char('+') | KeyCode::Char('=') => {
                 self.handle_increment_key();
             }
@@ -214,6 +243,12 @@ impl App {
             KeyCode::Char('x') | KeyCode::Char('X') => {
                 self.trigger_genetic_surge();
             }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.copy_dna_to_clipboard();
+            }
+            KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.paste_dna_from_clipboard();
+            }
             KeyCode::Char('c') => {
                 self.export_selected_dna();
             }
@@ -223,6 +258,12 @@ impl App {
             KeyCode::Char('v') | KeyCode::Char('V') => {
                 self.import_dna_infuse();
             }
+            KeyCode::Char('M') => self.open_marketplace(),
+            KeyCode::Char('D') => self.open_pathogen_designer(),
+            KeyCode::Char('n') => self.begin_bookmark(),
+            KeyCode::Char('N') => self.toggle_bookmarks(),
+            KeyCode::Char('/') => self.begin_tag_search(),
+            KeyCode::Char('*') => self.begin_tag_selected(),
             KeyCode::Char('j') | KeyCode::Char('J') => {
                 self.toggle_social_brush();
             }
@@ -277,19 +318,15 @@ impl App {
             KeyCode::Char('r') | KeyCode::Char('R') => {
                 self.trigger_resource_boom();
             }
-            KeyCode::Char('w') | KeyCode::Char('W') => {
-                if self.save_state().is_ok() {
-                    self.event_log
-                        .push_back(("World state SAVED to save.json".to_string(), Color::Green));
-                }
+            KeyCode::Char('w') | KeyCode::Char('W') if self.save_state().is_ok() => {
+                self.event_log
+                    .push_back(("World state SAVED to save.json".to_string(), Color::Green));
             }
-            KeyCode::Char('o') | KeyCode::Char('O') => {
-                if self.load_state().is_ok() {
-                    self.event_log.push_back((
-                        "World state LOADED from save.json".to_string(),
-                        Color::Green,
-                    ));
-                }
+            KeyCode::Char('o') | KeyCode::Char('O') if self.load_state().is_ok() => {
+                self.event_log.push_back((
+                    "World state LOADED from save.json".to_string(),
+                    Color::Green,
+                ));
             }
             _ => {}
         }
@@ -297,10 +334,9 @@ impl App {
 
     fn handle_fossil_resurrection(&mut self) {
         if let Some(fossil) = self
-            .world
-            .fossil_registry
-            .fossils
+            .visible_fossils()
             .get(self.selected_fossil_index)
+            .map(|f| (*f).clone())
         {
             let mut e = lifecycle::create_entity_with_rng(
                 50.0,
@@ -353,6 +389,26 @@ impl App {
         }
     }
 
+    fn export_phylogeny(&mut self) {
+        let tree = LineageTree::build(&self.world.lineage_registry);
+        let write = || -> anyhow::Result<()> {
+            fs::create_dir_all("logs")?;
+            fs::write("logs/phylogeny.nwk", tree.to_newick())?;
+            fs::write("logs/phylogeny.xml", tree.to_phyloxml())?;
+            Ok(())
+        };
+        self.event_log.push_back(match write() {
+            Ok(()) => (
+                format!(
+                    "Phylogeny of {} lineages exported to logs/phylogeny.nwk and .xml",
+                    tree.len()
+                ),
+                Color::Green,
+            ),
+            Err(e) => (format!("Phylogeny export failed: {}", e), Color::Red),
+        });
+    }
+
     fn accept_trade_offer(&mut self, idx: usize) {
         if let Some(offer) = self.network_state.trade_offers.get(idx).cloned() {
             self.world.apply_trade(
@@ -441,27 +497,81 @@ impl App {
     }
 
     fn export_selected_dna(&mut self) {
-        if let Some(id) = self.selected_entity {
-            let mut found_dna = None;
-            for (_handle, (identity, intel)) in self
-                .world
-                .ecs
-                .query::<(&primordium_data::Identity, &primordium_data::Intel)>()
-                .iter()
-            {
-                if identity.id == id {
-                    found_dna = Some(intel.genotype.to_hex());
-                    break;
-                }
-            }
-            if let Some(dna) = found_dna {
-                let _ = fs::write("exported_dna.txt", &dna);
-                self.event_log
-                    .push_back(("DNA exported to exported_dna.txt".to_string(), Color::Cyan));
-            }
+        if let Some(genotype) = self.selected_genotype() {
+            let _ = fs::write("exported_dna.txt", genotype.to_hex());
+            self.event_log
+                .push_back(("DNA exported to exported_dna.txt".to_string(), Color::Cyan));
         }
     }
 
+    /// Writes the selected entity's creature card as text and PNG.
+    fn export_creature_card(&mut self) {
+        let Some(id) = self.selected_entity else {
+            self.event_log.push_back((
+                "Select an entity to export its card".to_string(),
+                Color::Yellow,
+            ));
+            return;
+        };
+        let Ok(entity) = self.world.entity_of(&id.to_string()) else {
+            return;
+        };
+        let card = CreatureCard::new(&entity, self.world.tick);
+        let base = format!("logs/card_{}", id);
+        let write = || -> anyhow::Result<()> {
+            fs::create_dir_all("logs")?;
+            fs::write(format!("{base}.txt"), card.to_text(true))?;
+            fs::write(format!("{base}.png"), card.to_png()?)?;
+            Ok(())
+        };
+        self.event_log.push_back(match write() {
+            Ok(()) => (
+                format!("Creature card exported to {base}.png and .txt"),
+                Color::Cyan,
+            ),
+            Err(e) => (format!("Card export failed: {}", e), Color::Red),
+        });
+    }
+
+    fn export_route_trail(&mut self) {
+        let Some(trail) = self
+            .world
+            .route_trail
+            .as_ref()
+            .filter(|t| Some(t.entity_id) == self.selected_entity && !t.is_empty())
+        else {
+            self.event_log.push_back((
+                "Select an entity and show its trail [t] to export it".to_string(),
+                Color::Yellow,
+            ));
+            return;
+        };
+        let path = format!("logs/trail_{}.csv", trail.entity_id);
+        let write = || -> anyhow::Result<()> {
+            fs::create_dir_all("logs")?;
+            fs::write(&path, trail.to_csv())?;
+            Ok(())
+        };
+        self.event_log.push_back(match write() {
+            Ok(()) => (
+                format!("Route trail ({} points) exported to {path}", trail.len()),
+                Color::Cyan,
+            ),
+            Err(e) => (format!("Trail export failed: {}", e), Color::Red),
+        });
+    }
+
+    /// Genotype of the currently selected entity.
+    pub(crate) fn selected_genotype(&self) -> Option<std::sync::Arc<primordium_data::Genotype>> {
+        let id = self.selected_entity?;
+        self.world
+            .ecs
+            .query::<(&primordium_data::Identity, &primordium_data::Intel)>()
+            .iter()
+            .find(|(_, (identity, _))| identity.id == id)
+            .map(|(_, (_, intel))| intel.genotype.clone())
+    }
+
     fn export_selected_brain(&mut self) {
         if let Some(id) = self.selected_entity {
             let mut found_brain = None;
@@ -484,35 +594,22 @@ impl App {
                     Color::Magenta,
                 ));
             }
+            if let Ok((brain, values)) = self.world.brain_of(&id.to_string()) {
+                let filename = format!("logs/brain_{}.dot", id);
+                let dot = primordium_core::brain::export::to_dot(&brain, Some(&values));
+                let _ = fs::write(&filename, dot);
+                self.event_log.push_back((
+                    format!("Brain graph exported to {}", filename),
+                    Color::Magenta,
+                ));
+            }
         }
     }
 
     fn import_dna_infuse(&mut self) {
         if let Ok(dna) = fs::read_to_string("dna_infuse.txt") {
             if let Ok(genotype) = primordium_data::Genotype::from_hex(dna.trim()) {
-                let mut e = lifecycle::create_entity_with_rng(
-                    50.0,
-                    25.0,
-                    self.world.tick,
-                    &mut rand::thread_rng(),
-                );
-                e.intel.genotype = std::sync::Arc::new(genotype);
-                e.physics.sensing_range = e.intel.genotype.sensing_range;
-                e.physics.max_speed = e.intel.genotype.max_speed;
-                e.metabolism.max_energy = e.intel.genotype.max_energy;
-                e.metabolism.lineage_id = e.intel.genotype.lineage_id;
-
-                self.world.ecs.spawn((
-                    e.identity,
-                    crate::model::state::Position {
-                        x: e.physics.x,
-                        y: e.physics.y,
-                    },
-                    e.physics,
-                    e.metabolism,
-                    e.health,
-                    e.intel,
-                ));
+                self.spawn_with_genotype(std::sync::Arc::new(genotype), 50.0, 25.0);
                 self.event_log.push_back((
                     "AVATAR INFUSED from dna_infuse.txt".to_string(),
                     Color::Green,
@@ -522,6 +619,34 @@ impl App {
         }
     }
 
+    /// Spawns a fresh entity at `(x, y)` carrying `genotype`.
+    pub(crate) fn spawn_with_genotype(
+        &mut self,
+        genotype: std::sync::Arc<primordium_data::Genotype>,
+        x: f64,
+        y: f64,
+    ) {
+        let mut e =
+            lifecycle::create_entity_with_rng(x, y, self.world.tick, &mut rand::thread_rng());
+        e.intel.genotype = genotype;
+        e.physics.sensing_range = e.intel.genotype.sensing_range;
+        e.physics.max_speed = e.intel.genotype.max_speed;
+        e.metabolism.max_energy = e.intel.genotype.max_energy;
+        e.metabolism.lineage_id = e.intel.genotype.lineage_id;
+
+        self.world.ecs.spawn((
+            e.identity,
+            crate::model::state::Position {
+                x: e.physics.x,
+                y: e.physics.y,
+            },
+            e.physics,
+            e.metabolism,
+            e.health,
+            e.intel,
+        ));
+    }
+
     fn toggle_social_brush(&mut self) {
         self.is_social_brush = !self.is_social_brush;
         self.event_log.push_back((
@@ -690,23 +815,13 @@ impl App {
     }
 
     fn trigger_resource_boom(&mut self) {
-        use crate::model::state::{MetabolicNiche, Position};
-        use primordium_data::Food;
         let mut rng = rand::thread_rng();
         for _ in 0..100 {
             let fx = rng.gen_range(1..self.world.width - 1);
             let fy = rng.gen_range(1..self.world.height - 1);
             let n_type = rng.gen_range(0.0..1.0);
-            self.world.ecs.spawn((
-                Food::new(fx, fy, n_type),
-                Position {
-                    x: fx as f64,
-                    y: fy as f64,
-                },
-                MetabolicNiche(n_type),
-            ));
+            self.world.spawn_food_at(fx, fy, n_type);
         }
-        self.world.food_dirty = true;
         self.event_log
             .push_back(("GOD MODE: RESOURCE BOOM!".to_string(), Color::Green));
     }
@@ -0,0 +1,34 @@
+use crate::app::pathogen_designer::DesignerStage;
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+impl App {
+    /// Handles the pathogen designer; other keys are ignored while it is open.
+    pub fn handle_pathogen_designer_key(&mut self, key: KeyEvent) {
+        let Some(designer) = &mut self.pathogen_designer else {
+            return;
+        };
+        match designer.stage {
+            DesignerStage::Editing => match key.code {
+                KeyCode::Esc | KeyCode::Char('D') => self.pathogen_designer = None,
+                KeyCode::Up => designer.move_selection(-1),
+                KeyCode::Down => designer.move_selection(1),
+                KeyCode::Left => designer.adjust(-1),
+                KeyCode::Right => designer.adjust(1),
+                KeyCode::Enter => self.begin_pathogen_release(),
+                KeyCode::Char('q') => self.running = false,
+                _ => {}
+            },
+            DesignerStage::Aiming => {
+                if key.code == KeyCode::Esc {
+                    designer.stage = DesignerStage::Editing;
+                }
+            }
+            DesignerStage::Confirm(_) => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => self.confirm_pathogen_release(),
+                KeyCode::Char('n') | KeyCode::Esc => designer.stage = DesignerStage::Editing,
+                _ => {}
+            },
+        }
+    }
+}
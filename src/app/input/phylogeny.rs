@@ -0,0 +1,19 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+impl App {
+    /// Handles the phylogeny browser; other keys are ignored while it is
+    /// open.
+    pub fn handle_phylogeny_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('H') => self.show_phylogeny = false,
+            KeyCode::Up => self.move_phylogeny(-1),
+            KeyCode::Down => self.move_phylogeny(1),
+            KeyCode::Left => self.fold_phylogeny(false),
+            KeyCode::Right => self.fold_phylogeny(true),
+            KeyCode::Enter => self.follow_phylogeny(),
+            KeyCode::Char('q') => self.running = false,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+impl App {
+    /// Handles the marketplace browser; other keys are ignored while it is open.
+    pub fn handle_registry_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('M') => self.show_registry = false,
+            KeyCode::Tab | KeyCode::Right => {
+                self.registry_tab = (self.registry_tab + 1) % 3;
+                self.registry_selected_index = 0;
+            }
+            KeyCode::BackTab | KeyCode::Left => {
+                self.registry_tab = (self.registry_tab + 2) % 3;
+                self.registry_selected_index = 0;
+            }
+            KeyCode::Up => {
+                self.registry_selected_index = self.registry_selected_index.saturating_sub(1);
+            }
+            KeyCode::Down if self.registry_selected_index + 1 < self.registry_list_len() => {
+                self.registry_selected_index += 1;
+            }
+            KeyCode::Char('r') if self.registry_rx.is_none() => self.fetch_registry_data(),
+            KeyCode::Char('I') if self.registry_tab == 1 => self.trial_selected_genome(),
+            KeyCode::Char('S') if self.registry_tab == 2 => self.publish_world_seed(),
+            KeyCode::Char('S') => self.submit_selected_genome(),
+            KeyCode::Char('q') => self.running = false,
+            _ => {}
+        }
+    }
+}
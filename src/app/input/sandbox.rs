@@ -0,0 +1,14 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+impl App {
+    /// Handles the trial report; only release or discard are accepted
+    /// while it is shown.
+    pub fn handle_import_trial_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => self.release_import(),
+            KeyCode::Char('n') | KeyCode::Esc => self.discard_import(),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+impl App {
+    /// The screensaver runs unattended; only quitting is accepted.
+    pub fn handle_screensaver_key(&mut self, key: KeyEvent) {
+        if matches!(
+            key.code,
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc
+        ) {
+            self.running = false;
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::app::start_menu::{MenuItem, MenuPage, WizardField};
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+impl App {
+    /// Handles the start menu. All keys are captured until a choice closes it.
+    pub fn handle_start_menu_key(&mut self, key: KeyEvent) {
+        let Some(menu) = &mut self.start_menu else {
+            return;
+        };
+        match (&menu.page, key.code) {
+            (_, KeyCode::Up) => menu.move_selection(-1),
+            (_, KeyCode::Down) | (_, KeyCode::Tab) => menu.move_selection(1),
+            (MenuPage::Main, KeyCode::Enter) => {
+                let item = MenuItem::ALL[menu.selected];
+                self.activate_menu_item(item);
+            }
+            (MenuPage::Main, KeyCode::Char('q')) => self.running = false,
+            (MenuPage::Main, KeyCode::Esc) => self.start_menu = None,
+            (MenuPage::NewWorld, KeyCode::Left) => {
+                menu.settings.cycle(WizardField::ALL[menu.selected], -1)
+            }
+            (MenuPage::NewWorld, KeyCode::Right) => {
+                menu.settings.cycle(WizardField::ALL[menu.selected], 1)
+            }
+            (MenuPage::NewWorld, KeyCode::Enter) => self.create_world_from_menu(),
+            (MenuPage::RelayUrl, KeyCode::Enter) => self.connect_from_menu(),
+            (_, KeyCode::Esc) => menu.open(MenuPage::Main),
+            (_, KeyCode::Backspace) => menu.backspace(),
+            (_, KeyCode::Char(c)) => menu.type_char(c),
+            _ => {}
+        }
+    }
+}
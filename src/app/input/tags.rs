@@ -0,0 +1,21 @@
+use crate::app::state::App;
+use crate::app::tags::MAX_TAG_LEN;
+use crossterm::event::{KeyCode, KeyEvent};
+
+impl App {
+    /// Handles typing in the tag search bar or tag prompt.
+    pub fn handle_tag_prompt_key(&mut self, key: KeyEvent) {
+        let Some((_, text)) = &mut self.tag_prompt else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => self.commit_tag_prompt(),
+            KeyCode::Esc => self.tag_prompt = None,
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) if text.chars().count() < MAX_TAG_LEN => text.push(c),
+            _ => {}
+        }
+    }
+}
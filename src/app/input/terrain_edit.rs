@@ -7,6 +7,22 @@ use std::sync::Arc;
 
 impl App {
     pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.screensaver {
+            return;
+        }
+        if self.pathogen_designer.is_some() {
+            if matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left)) {
+                if let Some((wx, wy)) = WorldWidget::screen_to_world(
+                    mouse.column,
+                    mouse.row,
+                    self.last_world_rect,
+                    self.screensaver,
+                ) {
+                    self.aim_pathogen_release(wx, wy);
+                }
+            }
+            return;
+        }
         if self.show_brain && mouse.column >= self.last_sidebar_rect.x {
             if matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left)) {
                 let relative_y = mouse.row.saturating_sub(self.last_sidebar_rect.y + 1);
@@ -71,6 +87,12 @@ impl App {
                     self.selected_entity = Some(id);
                     self.show_brain = true;
                     false
+                } else if let Some(monument) = self.world.monuments.nearest(wx, wy, 1.0) {
+                    for line in monument.inscription() {
+                        self.event_log
+                            .push_back((format!("🗿 {}", line), Color::LightYellow));
+                    }
+                    false
                 } else {
                     true
                 }
@@ -100,15 +122,8 @@ impl App {
             self.last_world_rect,
             self.screensaver,
         ) {
-            use crate::model::state::{MetabolicNiche, Position};
-            use primordium_data::Food;
             let n_type = rand::thread_rng().gen_range(0.0..1.0);
-            self.world.ecs.spawn((
-                Food::new(wx as u16, wy as u16, n_type),
-                Position { x: wx, y: wy },
-                MetabolicNiche(n_type),
-            ));
-            self.world.food_dirty = true;
+            self.world.spawn_food_at(wx as u16, wy as u16, n_type);
             self.event_log
                 .push_back(("Divine Food Injected".to_string(), Color::Green));
         }
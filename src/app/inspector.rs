@@ -0,0 +1,537 @@
+//! HTTP inspector of headless runs.
+//!
+//! With `[inspector] enabled`, `--mode headless` serves the run on `bind`:
+//! - `GET /api/status`: tick, population and a few headline numbers;
+//! - `GET /api/stats`: the full population statistics;
+//! - `POST /api/command`: one scenario event or an array of them, applied
+//!   on the next tick like an inbox `*.json` file.
+//!
+//! Every request carries a configured token as `Authorization: Bearer`.
+//! The token names the client: `read` tokens get 403 on commands, and a
+//! client gets 429 once it uses up the `command_quota` of its token. Each
+//! remote address gets 429 once it exceeds `requests_per_minute`, whether
+//! or not its token is valid, so tokens cannot be guessed at full speed.
+
+use crate::app::state::App;
+use crate::model::config::{InspectorConfig, InspectorScope, InspectorToken};
+use crate::model::world::scenario::ScenarioEvent;
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use primordium_data::PopulationStats;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use tokio::sync::{mpsc, oneshot};
+
+/// Headline numbers of the run, as served by `GET /api/status`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InspectorStatus {
+    pub tick: u64,
+    pub population: usize,
+    pub species_count: usize,
+    pub food_count: usize,
+    pub max_generation: u32,
+    pub top_fitness: f64,
+    pub seed: Option<u64>,
+}
+
+/// Outcome of one command sent through the inspector.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InspectorResult {
+    pub tick: u64,
+    /// Name of the token that sent the command.
+    pub client: String,
+    pub ok: bool,
+    pub message: String,
+}
+
+/// Per-address token buckets holding up to a minute of requests. A bucket
+/// that has refilled is the same as none, so those are dropped whenever a new
+/// address arrives.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_minute: u32,
+    buckets: HashMap<IpAddr, (f64, Instant)>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute: per_minute.max(1),
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Takes one request from `client`'s bucket, or returns how long until
+    /// the next one is allowed.
+    pub fn check(&mut self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(self.per_minute);
+        let per_sec = capacity / 60.0;
+        if !self.buckets.contains_key(&client) {
+            self.buckets.retain(|_, &mut (tokens, updated)| {
+                let elapsed = now.saturating_duration_since(updated).as_secs_f64();
+                tokens + elapsed * per_sec < capacity
+            });
+        }
+        let (tokens, updated) = self.buckets.entry(client).or_insert((capacity, now));
+        let elapsed = now.saturating_duration_since(*updated).as_secs_f64();
+        *tokens = (*tokens + elapsed * per_sec).min(capacity);
+        *updated = now;
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - *tokens) / per_sec))
+        }
+    }
+}
+
+struct Command {
+    client: String,
+    events: Vec<ScenarioEvent>,
+    reply: oneshot::Sender<Result<String, String>>,
+}
+
+struct Shared {
+    tokens: Vec<InspectorToken>,
+    limiter: Mutex<RateLimiter>,
+    /// Commands accepted so far, by client.
+    commands_used: Mutex<HashMap<String, u32>>,
+    status: Mutex<InspectorStatus>,
+    stats: Mutex<Arc<PopulationStats>>,
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+/// The serving side of the inspector, fed by [`App::serve_inspector`].
+pub struct Inspector {
+    shared: Arc<Shared>,
+    commands: mpsc::UnboundedReceiver<Command>,
+    addr: Option<SocketAddr>,
+}
+
+impl Inspector {
+    /// An inspector whose endpoints are served by [`Inspector::router`].
+    pub fn new(config: &InspectorConfig) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            shared: Arc::new(Shared {
+                tokens: config.tokens.clone(),
+                limiter: Mutex::new(RateLimiter::new(config.requests_per_minute)),
+                commands_used: Mutex::new(HashMap::new()),
+                status: Mutex::new(InspectorStatus::default()),
+                stats: Mutex::new(Arc::default()),
+                commands: tx,
+            }),
+            commands: rx,
+            addr: None,
+        }
+    }
+
+    /// Binds `config.bind` and serves the endpoints in the background.
+    pub async fn start(config: &InspectorConfig) -> anyhow::Result<Self> {
+        let mut inspector = Self::new(config);
+        let listener = tokio::net::TcpListener::bind(&config.bind).await?;
+        inspector.addr = Some(listener.local_addr()?);
+        let router = inspector.router();
+        tokio::spawn(async move {
+            let service = router.into_make_service_with_connect_info::<SocketAddr>();
+            if let Err(e) = axum::serve(listener, service).await {
+                tracing::error!("Inspector stopped: {e}");
+            }
+        });
+        Ok(inspector)
+    }
+
+    /// Address the inspector listens on, once started.
+    pub fn addr(&self) -> Option<SocketAddr> {
+        self.addr
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/api/status", get(status))
+            .route("/api/stats", get(stats))
+            .route("/api/command", post(command))
+            .with_state(Arc::clone(&self.shared))
+    }
+}
+
+impl App {
+    /// Publishes the current state to the inspector and applies the
+    /// commands it received since the last call.
+    pub fn serve_inspector(&mut self, inspector: &mut Inspector) -> Vec<InspectorResult> {
+        let stats = &self.world.pop_stats;
+        if let Ok(mut status) = inspector.shared.status.lock() {
+            *status = InspectorStatus {
+                tick: self.world.tick,
+                population: stats.population,
+                species_count: stats.species_count,
+                food_count: stats.food_count,
+                max_generation: stats.max_generation,
+                top_fitness: stats.top_fitness,
+                seed: self.world.config.world.seed,
+            };
+        }
+        if let Ok(mut shared) = inspector.shared.stats.lock() {
+            *shared = Arc::clone(stats);
+        }
+
+        let mut results = Vec::new();
+        while let Ok(command) = inspector.commands.try_recv() {
+            let outcome = self
+                .apply_scenario_events(&command.events)
+                .map_err(|e| format!("{e:#}"));
+            let result = InspectorResult {
+                tick: self.world.tick,
+                client: command.client,
+                ok: outcome.is_ok(),
+                message: match &outcome {
+                    Ok(message) | Err(message) => message.clone(),
+                },
+            };
+            tracing::info!(
+                "Inspector command from {}: {}",
+                result.client,
+                result.message
+            );
+            let _ = command.reply.send(outcome);
+            results.push(result);
+        }
+        results
+    }
+}
+
+/// A refused inspector request, rendered as `{"error": message}`.
+#[derive(Debug)]
+struct InspectorError {
+    status: StatusCode,
+    message: String,
+    /// Seconds to send as `Retry-After`.
+    retry_after: Option<u64>,
+}
+
+impl IntoResponse for InspectorError {
+    fn into_response(self) -> Response {
+        let mut response = (
+            self.status,
+            Json(serde_json::json!({ "error": self.message })),
+        )
+            .into_response();
+        if let Some(secs) = self.retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, secs.into());
+        }
+        response
+    }
+}
+
+type InspectorResponse<T> = Result<Json<T>, InspectorError>;
+
+fn error(status: StatusCode, message: impl Into<String>) -> InspectorError {
+    InspectorError {
+        status,
+        message: message.into(),
+        retry_after: None,
+    }
+}
+
+/// The token a request from `remote` was made with, once it passed the
+/// rate limit.
+fn authorize<'a>(
+    shared: &'a Shared,
+    remote: SocketAddr,
+    headers: &HeaderMap,
+) -> Result<&'a InspectorToken, InspectorError> {
+    // Rate-limit before looking at the token, so that bad tokens cost a
+    // request like good ones.
+    let allowed = shared
+        .limiter
+        .lock()
+        .map_err(|_| error(StatusCode::INTERNAL_SERVER_ERROR, "rate limiter poisoned"))?
+        .check(remote.ip(), Instant::now());
+    if let Err(wait) = allowed {
+        return Err(InspectorError {
+            retry_after: Some(wait.as_secs().max(1)),
+            ..error(StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded")
+        });
+    }
+
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| {
+            v.strip_prefix("Bearer ")
+                .or_else(|| v.strip_prefix("bearer "))
+        });
+    // Compare against every token in constant time, so that response times
+    // tell nothing about how close a guess came.
+    let token = presented.and_then(|p| {
+        shared.tokens.iter().fold(None, |found, t| {
+            if bool::from(t.token.as_bytes().ct_eq(p.as_bytes())) {
+                Some(t)
+            } else {
+                found
+            }
+        })
+    });
+    token.ok_or_else(|| error(StatusCode::UNAUTHORIZED, "invalid or missing token"))
+}
+
+async fn status(
+    State(shared): State<Arc<Shared>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> InspectorResponse<InspectorStatus> {
+    authorize(&shared, remote, &headers)?;
+    let status = shared
+        .status
+        .lock()
+        .map_err(|_| error(StatusCode::INTERNAL_SERVER_ERROR, "status unavailable"))?;
+    Ok(Json(status.clone()))
+}
+
+async fn stats(
+    State(shared): State<Arc<Shared>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> InspectorResponse<Arc<PopulationStats>> {
+    authorize(&shared, remote, &headers)?;
+    let stats = shared
+        .stats
+        .lock()
+        .map_err(|_| error(StatusCode::INTERNAL_SERVER_ERROR, "stats unavailable"))?;
+    Ok(Json(Arc::clone(&stats)))
+}
+
+async fn command(
+    State(shared): State<Arc<Shared>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: String,
+) -> InspectorResponse<serde_json::Value> {
+    let token = authorize(&shared, remote, &headers)?;
+    if token.scope != InspectorScope::Control {
+        return Err(error(StatusCode::FORBIDDEN, "token is read-only"));
+    }
+    let events = ScenarioEvent::parse_many(&body)
+        .map_err(|e| error(StatusCode::BAD_REQUEST, format!("Invalid scenario: {e}")))?;
+
+    // Reserve a command from the quota, handing it back if the command fails.
+    let reserve = |delta: i32| {
+        let mut used = shared.commands_used.lock().ok()?;
+        let count = used.entry(token.name.clone()).or_insert(0);
+        if delta > 0 && token.command_quota > 0 && *count >= token.command_quota {
+            return None;
+        }
+        *count = count.saturating_add_signed(delta);
+        Some(())
+    };
+    if reserve(1).is_none() {
+        return Err(error(
+            StatusCode::TOO_MANY_REQUESTS,
+            format!("command quota of {} used up", token.command_quota),
+        ));
+    }
+
+    let (reply, outcome) = oneshot::channel();
+    let sent = shared.commands.send(Command {
+        client: token.name.clone(),
+        events,
+        reply,
+    });
+    let outcome = match sent {
+        Ok(()) => outcome.await.ok(),
+        Err(_) => None,
+    };
+    match outcome {
+        Some(Ok(message)) => Ok(Json(serde_json::json!({ "message": message }))),
+        Some(Err(message)) => {
+            reserve(-1);
+            Err(error(StatusCode::UNPROCESSABLE_ENTITY, message))
+        }
+        None => {
+            reserve(-1);
+            Err(error(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "the run has stopped",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::input::tests::create_test_app;
+    use axum::body::Body;
+    use axum::extract::connect_info::MockConnectInfo;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn config(requests_per_minute: u32) -> InspectorConfig {
+        let token = |name: &str, scope, command_quota| InspectorToken {
+            name: name.to_string(),
+            token: format!("{name}-secret"),
+            scope,
+            command_quota,
+        };
+        InspectorConfig {
+            enabled: true,
+            requests_per_minute,
+            tokens: vec![
+                token("student", InspectorScope::Read, 0),
+                token("teacher", InspectorScope::Control, 1),
+            ],
+            ..InspectorConfig::default()
+        }
+    }
+
+    /// The inspector's endpoints as seen from `127.0.0.<host>`.
+    fn router_from(inspector: &Inspector, host: u8) -> Router {
+        inspector.router().layer(MockConnectInfo(SocketAddr::from((
+            [127, 0, 0, host],
+            40000,
+        ))))
+    }
+
+    fn request(method: &str, uri: &str, token: Option<&str>, body: &str) -> Request<Body> {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header("authorization", format!("Bearer {token}"));
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tokens_scope_commands_and_quotas() {
+        let mut app = create_test_app();
+        let mut inspector = Inspector::new(&config(100));
+        app.serve_inspector(&mut inspector);
+        let router = router_from(&inspector, 1);
+
+        let response = router
+            .clone()
+            .oneshot(request("GET", "/api/status", None, ""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = router
+            .clone()
+            .oneshot(request("GET", "/api/status", Some("student-secret"), ""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let climate = r#"{"event": "climate", "state": "Scorching"}"#;
+        let response = router
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/command",
+                Some("student-secret"),
+                climate,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // The command waits for the run loop to apply it.
+        let pending = tokio::spawn(router.clone().oneshot(request(
+            "POST",
+            "/api/command",
+            Some("teacher-secret"),
+            climate,
+        )));
+        let mut results = Vec::new();
+        while results.is_empty() {
+            tokio::task::yield_now().await;
+            results = app.serve_inspector(&mut inspector);
+        }
+        assert_eq!(pending.await.unwrap().unwrap().status(), StatusCode::OK);
+        assert_eq!(results[0].client, "teacher");
+        assert_eq!(
+            app.env.god_climate_override,
+            Some(crate::model::environment::ClimateState::Scorching)
+        );
+
+        let response = router
+            .oneshot(request(
+                "POST",
+                "/api/command",
+                Some("teacher-secret"),
+                climate,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_clients_are_rate_limited_separately() {
+        let inspector = Inspector::new(&config(2));
+        let router = router_from(&inspector, 1);
+        for expected in [
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::TOO_MANY_REQUESTS,
+        ] {
+            let response = router
+                .clone()
+                .oneshot(request("GET", "/api/stats", Some("student-secret"), ""))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), expected);
+        }
+        let response = router_from(&inspector, 2)
+            .oneshot(request("GET", "/api/stats", Some("teacher-secret"), ""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut limiter = RateLimiter::new(60);
+        let start = Instant::now();
+        let a = IpAddr::from([10, 0, 0, 1]);
+        for _ in 0..60 {
+            assert!(limiter.check(a, start).is_ok());
+        }
+        assert!(limiter.check(a, start).is_err());
+        assert!(limiter.check(a, start + Duration::from_secs(1)).is_ok());
+
+        // Addresses that went quiet long enough to refill are forgotten.
+        let later = start + Duration::from_secs(61);
+        assert!(limiter.check(IpAddr::from([10, 0, 0, 2]), later).is_ok());
+        assert_eq!(limiter.buckets.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bad_tokens_are_rate_limited() {
+        let inspector = Inspector::new(&config(3));
+        let router = router_from(&inspector, 1);
+        for guess in ["guess-1", "guess-2", "guess-3"] {
+            let response = router
+                .clone()
+                .oneshot(request("GET", "/api/status", Some(guess), ""))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+        for token in [Some("guess-4"), None, Some("student-secret")] {
+            let response = router
+                .clone()
+                .oneshot(request("GET", "/api/status", token, ""))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+            assert!(response.headers().contains_key(header::RETRY_AFTER));
+        }
+    }
+}
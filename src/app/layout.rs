@@ -0,0 +1,189 @@
+//! Pane layout manager.
+//!
+//! The TUI is split into the status bar, trend sparklines, the map, the
+//! chronicle and a sidebar. Their sizes come from the `[layout]` config
+//! section: `Tab` cycles the presets, `U` opens the layout editor to resize or
+//! hide panes for the current view mode, and `s` in the editor writes the
+//! layouts back to `config.toml`.
+
+use anyhow::Result;
+use primordium_core::config::{PaneLayout, SidebarPanel};
+use ratatui::style::Color;
+
+use crate::app::state::App;
+
+/// Panes the layout editor can change, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Stats,
+    Trends,
+    Chronicle,
+    Sidebar,
+    SidebarPanel,
+}
+
+impl Pane {
+    pub const ALL: [Pane; 5] = [
+        Pane::Stats,
+        Pane::Trends,
+        Pane::Chronicle,
+        Pane::Sidebar,
+        Pane::SidebarPanel,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Pane::Stats => "Stats",
+            Pane::Trends => "Trends",
+            Pane::Chronicle => "Chronicle",
+            Pane::Sidebar => "Sidebar",
+            Pane::SidebarPanel => "Sidebar panel",
+        }
+    }
+
+    /// Largest size the editor allows.
+    fn max(self) -> u16 {
+        match self {
+            Pane::Stats | Pane::Trends => 12,
+            Pane::Chronicle => 20,
+            Pane::Sidebar => 90,
+            Pane::SidebarPanel => 0,
+        }
+    }
+
+    /// Size change per key press.
+    fn step(self) -> u16 {
+        if self == Pane::Sidebar {
+            5
+        } else {
+            1
+        }
+    }
+
+    fn size(self, layout: &mut PaneLayout) -> Option<&mut u16> {
+        match self {
+            Pane::Stats => Some(&mut layout.stats),
+            Pane::Trends => Some(&mut layout.trends),
+            Pane::Chronicle => Some(&mut layout.chronicle),
+            Pane::Sidebar => Some(&mut layout.sidebar),
+            Pane::SidebarPanel => None,
+        }
+    }
+
+    /// `(label, value)` for display in the layout editor.
+    pub fn describe(self, layout: &PaneLayout) -> (&'static str, String) {
+        let mut layout = layout.clone();
+        let value = match self.size(&mut layout) {
+            Some(0) => "hidden".to_string(),
+            Some(size) if self == Pane::Sidebar => format!("{} cols", size),
+            Some(size) => format!("{} rows", size),
+            None => format!("{:?}", layout.sidebar_panel),
+        };
+        (self.label(), value)
+    }
+}
+
+fn next_panel(panel: SidebarPanel, step: i16) -> SidebarPanel {
+    const ORDER: [SidebarPanel; 4] = [
+        SidebarPanel::Auto,
+        SidebarPanel::Brain,
+        SidebarPanel::Lineage,
+        SidebarPanel::Network,
+    ];
+    let i = ORDER.iter().position(|p| *p == panel).unwrap_or(0) as i16;
+    ORDER[(i + step).rem_euclid(ORDER.len() as i16) as usize]
+}
+
+impl App {
+    fn view_layout_key(&self) -> String {
+        self.view_mode.to_string()
+    }
+
+    /// Layout for the current view mode: its saved layout if it has one,
+    /// otherwise the active preset.
+    pub fn current_layout(&self) -> PaneLayout {
+        let layouts = &self.config.layout;
+        layouts
+            .views
+            .get(&self.view_layout_key())
+            .or_else(|| layouts.presets.get(self.layout_preset))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Switches to the next preset, dropping any layout edited for the
+    /// current view mode.
+    pub fn cycle_layout_preset(&mut self) {
+        let key = self.view_layout_key();
+        let layouts = &mut self.config.layout;
+        layouts.views.remove(&key);
+        self.layout_preset = (self.layout_preset + 1) % layouts.presets.len().max(1);
+        let name = self.current_layout().name;
+        self.event_log
+            .push_back((format!("Layout: {}", name), Color::Cyan));
+    }
+
+    /// The current view's own layout, created from the active preset on the
+    /// first edit.
+    fn edited_layout(&mut self) -> &mut PaneLayout {
+        let current = self.current_layout();
+        let key = self.view_layout_key();
+        self.config.layout.views.entry(key).or_insert(current)
+    }
+
+    /// Grows (`step > 0`) or shrinks a pane, or cycles the pinned sidebar
+    /// panel.
+    pub fn resize_pane(&mut self, pane: Pane, step: i16) {
+        let layout = self.edited_layout();
+        if pane == Pane::SidebarPanel {
+            layout.sidebar_panel = next_panel(layout.sidebar_panel, step);
+        } else if let Some(size) = pane.size(layout) {
+            let delta = pane.step() as i16 * step;
+            *size = (*size as i16 + delta).clamp(0, pane.max() as i16) as u16;
+        }
+    }
+
+    /// Hides a visible pane or restores a hidden one to its default size.
+    pub fn toggle_pane(&mut self, pane: Pane) {
+        let mut defaults = PaneLayout::default();
+        let default_size = pane.size(&mut defaults).map_or(0, |size| *size);
+        let layout = self.edited_layout();
+        if let Some(size) = pane.size(layout) {
+            *size = if *size == 0 { default_size } else { 0 };
+        }
+    }
+
+    /// Writes the `[layout]` section into the config file, keeping the rest
+    /// of the file as is.
+    pub fn save_layouts(&mut self) -> Result<()> {
+        let content = std::fs::read_to_string(&self.config_path).unwrap_or_default();
+        let mut table: toml::Table = content.parse()?;
+        table.insert(
+            "layout".to_string(),
+            toml::Value::try_from(&self.config.layout)?,
+        );
+        std::fs::write(&self.config_path, toml::to_string(&table)?)?;
+        // Our own write is not an external edit to reload.
+        self.config_last_modified = std::fs::metadata(&self.config_path)
+            .and_then(|m| m.modified())
+            .ok();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pane_describe_and_panel_cycle() {
+        let layout = PaneLayout {
+            trends: 0,
+            ..PaneLayout::default()
+        };
+        assert_eq!(Pane::Trends.describe(&layout).1, "hidden");
+        assert_eq!(Pane::Sidebar.describe(&layout).1, "45 cols");
+        assert_eq!(Pane::SidebarPanel.describe(&layout).1, "Auto");
+        assert_eq!(next_panel(SidebarPanel::Auto, -1), SidebarPanel::Network);
+    }
+}
@@ -0,0 +1,348 @@
+//! Marketplace (Registry) browser overlay.
+
+use crate::model::brain::GenotypeLogic;
+use primordium_tui::views::registry as view;
+use ratatui::style::Color;
+
+use crate::app::state::App;
+use crate::client::registry::{
+    ModerationStatus, RegistryClient, RegistryFetch, SubmissionReceipt, SubmitGenomeContext,
+    SubmitSeedContext,
+};
+use crate::model::crest::Crest;
+use primordium_data::{Genotype, Identity, Intel, Metabolism, Position};
+use primordium_io::thumbnail::Thumbnail;
+
+/// Size of the ASCII seed preview; each character covers 2x4 pixels of
+/// the thumbnail, roughly the shape of a terminal cell.
+const PREVIEW_COLS: usize = 32;
+const PREVIEW_ROWS: usize = 8;
+
+/// Outcome of a background genome submission: the name it was submitted
+/// under and the server's receipt.
+pub type SubmitResult = (String, Result<SubmissionReceipt, String>);
+
+impl App {
+    /// Opens the marketplace browser and refreshes its lists.
+    pub fn open_marketplace(&mut self) {
+        self.show_registry = true;
+        self.registry_selected_index = 0;
+        self.registry_client.get_or_insert_with(|| {
+            RegistryClient::new(None, std::env::var("PRIMORDIUM_API_KEY").ok())
+        });
+        self.fetch_registry_data();
+    }
+
+    /// Submits the selected organism's genome to the marketplace. Its
+    /// moderation state is then tracked on every refresh.
+    pub fn submit_selected_genome(&mut self) {
+        if self.registry_submit_rx.is_some() {
+            return;
+        }
+        let Some(client) = &self.registry_client else {
+            return;
+        };
+        let (server_url, api_key) = (
+            client.server_url().to_string(),
+            client.api_key().map(str::to_string),
+        );
+        let selected = self.selected_entity.and_then(|id| {
+            self.world
+                .ecs
+                .query::<(&Identity, &Intel, &Metabolism)>()
+                .iter()
+                .find(|(_, (identity, _, _))| identity.id == id)
+                .map(|(_, (_, intel, metabolism))| {
+                    (
+                        intel.genotype.to_hex(),
+                        metabolism.lineage_id,
+                        metabolism.generation,
+                        metabolism.peak_energy,
+                        metabolism.offspring_count,
+                    )
+                })
+        });
+        let Some((genotype, lineage_id, generation, fitness_score, offspring_count)) = selected
+        else {
+            self.event_log.push_back((
+                "Select an organism to submit its genome".to_string(),
+                Color::Yellow,
+            ));
+            return;
+        };
+
+        let name = format!(
+            "Gen {} of lineage {}",
+            generation,
+            &lineage_id.to_string()[..8]
+        );
+        self.event_log
+            .push_back((format!("Registry: Submitting {}...", name), Color::Cyan));
+        let tick = self.world.tick;
+        let provenance = self.world.provenance.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.registry_submit_rx = Some(rx);
+        tokio::spawn(async move {
+            let client = RegistryClient::new(Some(server_url), api_key);
+            let lineage = lineage_id.to_string();
+            let result = client
+                .submit_genome(SubmitGenomeContext {
+                    genotype: &genotype,
+                    author: "anonymous",
+                    name: &name,
+                    description: "",
+                    tags: "",
+                    lineage_id: Some(&lineage),
+                    fitness_score,
+                    offspring_count,
+                    tick,
+                    provenance: provenance.as_ref(),
+                })
+                .await;
+            let _ = tx.send((name, result));
+        });
+    }
+
+    /// Publishes the current world's config as a seed, with a thumbnail of
+    /// the world as it looks now.
+    pub fn publish_world_seed(&mut self) {
+        if self.registry_submit_rx.is_some() {
+            return;
+        }
+        let Some(client) = &self.registry_client else {
+            return;
+        };
+        let (server_url, api_key) = (
+            client.server_url().to_string(),
+            client.api_key().map(str::to_string),
+        );
+        let config_json = match serde_json::to_string(&self.world.config) {
+            Ok(json) => json,
+            Err(e) => {
+                self.event_log
+                    .push_back((format!("Registry: Cannot publish seed: {}", e), Color::Red));
+                return;
+            }
+        };
+        let positions: Vec<(f64, f64)> = self
+            .world
+            .ecs
+            .query::<&Position>()
+            .iter()
+            .map(|(_, p)| (p.x, p.y))
+            .collect();
+        let thumbnail = Thumbnail::render(&self.world.terrain, positions).to_png();
+        let name = format!(
+            "{}x{} world at tick {}",
+            self.world.config.world.width, self.world.config.world.height, self.world.tick
+        );
+        let max_pop = self.world.get_population_count() as u32;
+        let summary = format!(
+            "{} organisms, {} lineages",
+            max_pop, self.world.pop_stats.species_count
+        );
+
+        self.event_log
+            .push_back((format!("Registry: Publishing {}...", name), Color::Cyan));
+        let provenance = self.world.provenance.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.registry_submit_rx = Some(rx);
+        tokio::spawn(async move {
+            let client = RegistryClient::new(Some(server_url), api_key);
+            let result = client
+                .submit_seed(SubmitSeedContext {
+                    author: "anonymous",
+                    name: &name,
+                    description: "",
+                    tags: "",
+                    config_json: &config_json,
+                    avg_tick_time: 0.0,
+                    max_pop,
+                    performance_summary: &summary,
+                    thumbnail: Some(&thumbnail),
+                    provenance: provenance.as_ref(),
+                })
+                .await;
+            let _ = tx.send((name, result));
+        });
+    }
+
+    /// Starts tracking an accepted submission, or reports why it failed.
+    pub fn record_submission(&mut self, (name, result): SubmitResult) {
+        match result {
+            Ok(receipt) => {
+                let (text, color) = match receipt.status {
+                    ModerationStatus::Pending => {
+                        (format!("{} awaits moderator approval", name), Color::Yellow)
+                    }
+                    _ => (format!("{} is {}", name, receipt.status), Color::Green),
+                };
+                self.event_log
+                    .push_back((format!("Registry: {}", text), color));
+                self.cached_registry_submissions
+                    .push(view::SubmissionEntry {
+                        id: receipt.id,
+                        name,
+                        status: receipt.status.to_string(),
+                        note: None,
+                    });
+            }
+            Err(e) => self.event_log.push_back((
+                format!("Registry: Submitting {} failed: {}", name, e),
+                Color::Red,
+            )),
+        }
+        self.dirty = true;
+    }
+
+    /// Number of entries on the current marketplace tab.
+    pub fn registry_list_len(&self) -> usize {
+        match self.registry_tab {
+            0 => self.cached_registry_hof.len(),
+            1 => self.cached_registry_genomes.len(),
+            _ => self.cached_registry_seeds.len(),
+        }
+    }
+
+    pub fn registry_status(&self) -> view::RegistryStatus {
+        if self.registry_rx.is_some() {
+            view::RegistryStatus::Connecting
+        } else if self.cached_registry_hof.is_empty()
+            && self.cached_registry_genomes.is_empty()
+            && self.cached_registry_seeds.is_empty()
+        {
+            view::RegistryStatus::Disconnected
+        } else {
+            view::RegistryStatus::Connected
+        }
+    }
+
+    /// Picks up the result of a background registry fetch or submission,
+    /// if one arrived.
+    pub fn poll_registry(&mut self) {
+        self.poll_import_trial();
+        if let Some(Ok(submitted)) = self.registry_submit_rx.as_ref().map(|rx| rx.try_recv()) {
+            self.registry_submit_rx = None;
+            self.record_submission(submitted);
+        }
+        let Some(rx) = &self.registry_rx else {
+            return;
+        };
+        let Ok(fetch) = rx.try_recv() else {
+            return;
+        };
+        self.registry_rx = None;
+        self.apply_registry_fetch(fetch);
+    }
+
+    pub fn apply_registry_fetch(&mut self, fetch: RegistryFetch) {
+        let mut errors = Vec::new();
+        match fetch.hall_of_fame {
+            Ok(entries) => {
+                self.cached_registry_hof = entries
+                    .into_iter()
+                    .map(|e| view::HallOfFameEntry {
+                        id: e.id,
+                        civilization_level: e.civilization_level,
+                        is_extinct: e.is_extinct,
+                    })
+                    .collect()
+            }
+            Err(e) => errors.push(e),
+        }
+        match fetch.genomes {
+            Ok(genomes) => {
+                self.cached_registry_genomes = genomes
+                    .into_iter()
+                    .map(|g| view::GenomeRecord {
+                        crest: Genotype::from_hex(&g.genotype)
+                            .ok()
+                            .map(|genotype| Crest::of_genotype(&genotype)),
+                        id: g.id,
+                        lineage_id: g.lineage_id,
+                        genotype: g.genotype,
+                        author: g.author,
+                        name: g.name,
+                        description: g.description,
+                        tags: g.tags,
+                        fitness_score: g.fitness_score,
+                        offspring_count: g.offspring_count,
+                        tick: g.tick,
+                        downloads: g.downloads,
+                        created_at: g.created_at,
+                    })
+                    .collect()
+            }
+            Err(e) => errors.push(e),
+        }
+        match fetch.seeds {
+            Ok(seeds) => {
+                self.cached_registry_seeds = seeds
+                    .into_iter()
+                    .map(|s| {
+                        let preview = fetch
+                            .thumbnails
+                            .get(&s.id)
+                            .and_then(|png| Thumbnail::from_png(png).ok())
+                            .map(|t| t.to_ascii(PREVIEW_COLS, PREVIEW_ROWS))
+                            .unwrap_or_default();
+                        view::SeedRecord {
+                            id: s.id,
+                            author: s.author,
+                            name: s.name,
+                            description: s.description,
+                            tags: s.tags,
+                            config_json: s.config_json,
+                            avg_tick_time: s.avg_tick_time,
+                            max_pop: s.max_pop,
+                            performance_summary: s.performance_summary,
+                            downloads: s.downloads,
+                            created_at: s.created_at,
+                            preview,
+                        }
+                    })
+                    .collect()
+            }
+            Err(e) => errors.push(e),
+        }
+
+        for state in fetch.submissions.into_iter().flatten() {
+            let id = state.id.to_string();
+            let status = state.status.to_string();
+            let Some(entry) = self
+                .cached_registry_submissions
+                .iter_mut()
+                .find(|s| s.id == id && s.status != status)
+            else {
+                continue;
+            };
+            entry.status = status;
+            entry.note = state.note;
+            let color = match state.status {
+                ModerationStatus::Rejected => Color::Red,
+                _ => Color::Green,
+            };
+            let reason = entry
+                .note
+                .as_ref()
+                .map(|n| format!(": {}", n))
+                .unwrap_or_default();
+            self.event_log.push_back((
+                format!("Registry: {} was {}{}", entry.name, entry.status, reason),
+                color,
+            ));
+        }
+
+        if let Some(error) = errors.first() {
+            self.event_log
+                .push_back((format!("Registry: {}", error), Color::Red));
+        } else {
+            self.event_log
+                .push_back(("Registry: Data updated".to_string(), Color::Green));
+        }
+        self.registry_selected_index = self
+            .registry_selected_index
+            .min(self.registry_list_len().saturating_sub(1));
+        self.dirty = true;
+    }
+}
@@ -1,10 +1,29 @@
+pub mod archeology;
+pub mod attract;
 pub mod audio;
+pub mod bookmarks;
+pub mod clipboard;
+pub mod doctor;
 pub mod events;
+pub mod gallery;
+pub mod inbox;
 pub mod input;
-pub mod onboarding;
+pub mod inspector;
+pub mod layout;
+pub mod marketplace;
+pub mod narrations;
+pub mod pathogen_designer;
+pub mod phylogeny;
+pub mod profile;
+pub mod progress;
 pub mod render;
+pub mod sandbox;
+pub mod screensaver;
 pub mod shutdown;
+pub mod start_menu;
 pub mod state;
+pub mod tags;
+pub mod tutorial;
 
 pub use audio::AudioSystem;
 pub use events::{EventBus, WorldEvent};
@@ -19,7 +38,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use primordium_core::systems::environment as environment_system;
-use primordium_data::LiveEvent;
+use primordium_data::{Disaster, LiveEvent, NarrationFilter};
 use primordium_tui::Tui;
 use ratatui::style::Color;
 use sysinfo::Pid;
@@ -30,6 +49,7 @@ impl App {
         let mut last_tick = Instant::now();
         let tick_rate = Duration::from_millis(16);
         let mut last_config_check = Instant::now();
+        let mut last_inbox_poll = Instant::now();
 
         // Setup shutdown handler
         let shutdown = Arc::new(AtomicBool::new(false));
@@ -51,6 +71,13 @@ impl App {
                 last_config_check = Instant::now();
             }
 
+            if last_inbox_poll.elapsed() >= Duration::from_millis(inbox::POLL_INTERVAL_MS) {
+                if !self.poll_inbox().is_empty() {
+                    self.dirty = true;
+                }
+                last_inbox_poll = Instant::now();
+            }
+
             let effective_tick_rate =
                 Duration::from_secs_f64(tick_rate.as_secs_f64() / self.time_scale);
 
@@ -111,17 +138,34 @@ impl App {
             }
 
             if last_tick.elapsed() >= effective_tick_rate {
-                if !self.paused {
+                if !self.paused && self.start_menu.is_none() {
                     self.update_world()?;
+                    self.update_profile();
+                    self.update_gallery();
                     self.audio.process_queue();
                     self.dirty = true;
                 }
+                self.update_tutorial();
+                self.poll_registry();
+                if self.attract.is_some() {
+                    self.update_attract()?;
+                } else if self.screensaver {
+                    self.update_screensaver()?;
+                }
+
+                // Keep indexing a large log in the background, then pick up
+                // new snapshots now and then.
+                if self.show_archeology
+                    && (!self.archeology.index.is_complete() || self.frame_count.is_multiple_of(60))
+                {
+                    self.refresh_archeology(archeology::INDEX_BYTES_PER_FRAME);
+                }
 
                 if self.show_archeology
                     && self.auto_play_history
                     && self.frame_count.is_multiple_of(10)
                 {
-                    if self.archeology_index + 1 < self.archeology_snapshots.len() {
+                    if self.archeology_index + 1 < self.archeology.len() {
                         self.archeology_index += 1;
                     } else {
                         self.auto_play_history = false;
@@ -132,8 +176,11 @@ impl App {
             }
         }
 
+        self.close_profile();
+        let _ = self.record_gallery_run();
+
         // Perform graceful shutdown
-        if shutdown.load(Ordering::SeqCst) {
+        if shutdown.load(Ordering::SeqCst) && !self.writes_suppressed() {
             tracing::info!("Saving state before exit...");
             self.save_state()?;
             if !self.input_log.is_empty() {
@@ -147,6 +194,7 @@ impl App {
     fn update_hardware_metrics(&mut self) {
         self.fps = self.frame_count as f64;
         self.frame_count = 0;
+        self.check_log_quota();
 
         if !self.config.world.deterministic {
             self.sys.refresh_cpu();
@@ -165,13 +213,6 @@ impl App {
                 (self.sys.used_memory() as f32 / self.sys.total_memory() as f32) * 100.0;
         }
 
-        environment_system::update_era(
-            &mut self.env,
-            self.world.tick,
-            &self.world.pop_stats,
-            &self.config,
-        );
-
         let current_climate = self.env.climate();
         if let Some(last) = self.last_climate {
             if last != current_climate {
@@ -203,9 +244,59 @@ impl App {
         self.last_fps_update = Instant::now();
     }
 
+    /// Moves new scribe narrations into the event log, subscribing to the
+    /// world's scribe first if there is none or it has been replaced.
+    fn log_narrations(&mut self) {
+        if self.narration_feed.is_closed() {
+            self.narration_feed = self.world.observer.subscribe(NarrationFilter::min_severity(
+                self.config.narrator.event_log_severity,
+            ));
+        }
+        for narration in self.narration_feed.drain() {
+            self.event_log
+                .push_back((format!("📜 {}", narration.text), Color::LightMagenta));
+        }
+    }
+
+    /// Warns in the event log when the logs directory reaches
+    /// `logs.warn_at` of its quota, once each time it crosses that level.
+    fn check_log_quota(&mut self) {
+        let Some(usage) = self.world.logger.disk_usage() else {
+            return;
+        };
+        let near = usage.quota_bytes > 0 && usage.fraction() >= f64::from(self.config.logs.warn_at);
+        if near && !self.log_quota_warned {
+            self.event_log.push_back((
+                format!(
+                    "LOGS NEAR QUOTA: {} of {} MB used, oldest rotated logs will be deleted",
+                    usage.used_bytes / (1024 * 1024),
+                    usage.quota_bytes / (1024 * 1024)
+                ),
+                Color::Yellow,
+            ));
+        }
+        self.log_quota_warned = near;
+    }
+
     fn update_world(&mut self) -> Result<()> {
-        let events = self.world.update(&mut self.env)?;
+        self.world.track_energy(if self.show_energy_ledger {
+            self.selected_entity
+        } else {
+            None
+        });
+        self.world.track_route(if self.show_route_trail {
+            self.selected_entity
+        } else {
+            None
+        });
+        let world = &mut self.world;
+        let env = &mut self.env;
+        let events = self.thread_pools.install(|| world.update(env))?;
         self.latest_snapshot = Some(self.world.create_snapshot(self.selected_entity));
+        if self.world.guardrail_trip.is_some() {
+            self.paused = true;
+        }
+        self.log_narrations();
 
         for event in &events {
             let (x, y) = match event {
@@ -279,11 +370,52 @@ impl App {
                             Color::Yellow,
                         ));
                     }
+                    NetMessage::RateLimited {
+                        limit,
+                        retry_after_ms,
+                        migration_id,
+                    } => {
+                        // A refused migrant never left and stays home.
+                        if let Some(id) = migration_id {
+                            for (_handle, met) in self
+                                .world
+                                .ecs
+                                .query_mut::<&mut crate::model::state::Metabolism>()
+                            {
+                                if met.migration_id == Some(id) {
+                                    met.is_in_transit = false;
+                                    met.migration_id = None;
+                                }
+                            }
+                        }
+                        let retry = Duration::from_millis(retry_after_ms);
+                        if self
+                            .migration_backoff
+                            .is_none_or(|until| until < Instant::now())
+                        {
+                            self.event_log.push_back((
+                                format!(
+                                    "RELAY LIMIT ({:?}): migrations paused for {}s",
+                                    limit,
+                                    retry.as_secs().max(1)
+                                ),
+                                Color::Yellow,
+                            ));
+                        }
+                        self.migration_backoff = Some(Instant::now() + retry);
+                    }
                     NetMessage::GlobalEvent { event_type, .. } => {
-                        match event_type.as_str() {
-                            "SolarFlare" => self.env.radiation_timer = 500,
-                            "DeepFreeze" => self.env.ice_age_timer = 1000,
-                            _ => {}
+                        // A replay strikes the disasters of its recording instead.
+                        if !self.replay_mode {
+                            match event_type.as_str() {
+                                "SolarFlare" => self
+                                    .world
+                                    .queue_disaster(Disaster::SolarFlare { duration: 500 }),
+                                "DeepFreeze" => self
+                                    .world
+                                    .queue_disaster(Disaster::DeepFreeze { duration: 1000 }),
+                                _ => {}
+                            }
                         }
                         self.event_log.push_back((
                             format!("GLOBAL EVENT: {} detected across the Hive!", event_type),
@@ -298,6 +430,9 @@ impl App {
             let width = self.world.width as f64;
             let height = self.world.height as f64;
             let config_fingerprint = self.world.config.fingerprint();
+            let backing_off = self
+                .migration_backoff
+                .is_some_and(|until| Instant::now() < until);
 
             for (_handle, (identity, phys, met, intel)) in self
                 .world
@@ -310,7 +445,7 @@ impl App {
                 )>()
                 .iter()
             {
-                if met.is_in_transit {
+                if met.is_in_transit || backing_off {
                     continue;
                 }
 
@@ -321,33 +456,18 @@ impl App {
 
                 if leaving {
                     use primordium_net::NetMessage;
-                    use sha2::{Digest, Sha256};
-                    let dna = intel.genotype.to_hex();
-                    let energy = met.energy as f32;
-                    let generation = met.generation;
-
-                    let mut hasher = Sha256::new();
-                    hasher.update(dna.as_bytes());
-                    hasher.update(energy.to_be_bytes());
-                    hasher.update(generation.to_be_bytes());
-                    let checksum = hex::encode(hasher.finalize());
-
                     let migration_id = Uuid::new_v4();
                     met.is_in_transit = true;
                     met.migration_id = Some(migration_id);
 
-                    migrants.push(NetMessage::MigrateEntity {
+                    migrants.push(NetMessage::migrate_entity(
                         migration_id,
-                        dna,
-                        energy,
-                        generation,
-                        species_name: crate::model::lifecycle::get_name_components(
-                            &identity.id,
-                            met,
-                        ),
-                        fingerprint: config_fingerprint.clone(),
-                        checksum,
-                    });
+                        intel.genotype.to_hex(),
+                        met.energy as f32,
+                        met.generation,
+                        crate::model::lifecycle::get_name_components(&identity.id, met),
+                        config_fingerprint.clone(),
+                    ));
                 }
             }
 
@@ -366,6 +486,7 @@ impl App {
 
         for ev in events {
             let _ = self.world.logger.log_event(ev.clone());
+            self.remember_narration(&ev);
             let (msg, color) = ev.to_ui_message();
             self.event_log.push_back((msg, color));
             if self.event_log.len() > 15 {
@@ -430,6 +551,16 @@ impl LiveEventExt for LiveEvent {
                 };
                 (format!("Climate: {}", effect), Color::Yellow)
             }
+            LiveEvent::SeasonChanged { to, .. } => {
+                let icon = match to.as_str() {
+                    "Spring" => "🌸",
+                    "Summer" => "☀️",
+                    "Fall" => "🍂",
+                    "Winter" => "❄️",
+                    _ => "",
+                };
+                (format!("{} {} has come", icon, to), Color::LightGreen)
+            }
             LiveEvent::Extinction { tick, .. } => {
                 (format!("Extinction at tick {}", tick), Color::Magenta)
             }
@@ -441,11 +572,80 @@ impl LiveEventExt for LiveEvent {
                 format!("⚔️ #{} split into a new tribe!", &id.to_string()[..4]),
                 Color::Magenta,
             ),
+            LiveEvent::WarDeclared {
+                lineage_a,
+                lineage_b,
+                ..
+            } => (
+                format!(
+                    "⚔️ Lineages #{} and #{} are at WAR",
+                    &lineage_a.to_string()[..4],
+                    &lineage_b.to_string()[..4]
+                ),
+                Color::Red,
+            ),
+            LiveEvent::PeaceTreaty {
+                lineage_a,
+                lineage_b,
+                alliance,
+                ..
+            } => (
+                format!(
+                    "🕊️ Lineages #{} and #{} {}",
+                    &lineage_a.to_string()[..4],
+                    &lineage_b.to_string()[..4],
+                    if *alliance {
+                        "formed an alliance"
+                    } else {
+                        "made peace"
+                    }
+                ),
+                Color::LightGreen,
+            ),
+            LiveEvent::MonumentRaised {
+                lineage_id, x, y, ..
+            } => (
+                format!(
+                    "🗿 Lineage #{} raised a monument at ({}, {})",
+                    &lineage_id.to_string()[..4],
+                    x,
+                    y
+                ),
+                Color::LightYellow,
+            ),
             LiveEvent::Snapshot { tick, .. } => (
                 format!("🏛️ Snapshot saved at tick {}", tick),
                 Color::DarkGray,
             ),
             LiveEvent::Narration { text, .. } => (format!("📜 {}", text), Color::Green),
+            LiveEvent::Bookmark { tick, note, .. } => (
+                if note.is_empty() {
+                    format!("🔖 Bookmark at tick {}", tick)
+                } else {
+                    format!("🔖 Bookmark at tick {}: {}", tick, note)
+                },
+                Color::LightBlue,
+            ),
+            LiveEvent::CircuitBreaker {
+                breaker,
+                observed,
+                limit,
+                ..
+            } => (
+                format!(
+                    "🛑 CIRCUIT BREAKER: {} {} > {} - simulation paused",
+                    breaker, observed, limit
+                ),
+                Color::Red,
+            ),
+            LiveEvent::Provenance { provenance, .. } => (
+                format!(
+                    "Run stamped: v{}, config {}",
+                    provenance.version,
+                    &provenance.config_hash[..8.min(provenance.config_hash.len())]
+                ),
+                Color::DarkGray,
+            ),
         }
     }
 }
@@ -0,0 +1,70 @@
+//! Narration evidence.
+//!
+//! `E` lists recent narrations, newest first, and `Enter` expands the
+//! selected one into the ticks, lineages, figures and logged events it was
+//! drawn from. The same references are written to the chronicle with the
+//! narration, so they also show up in `live.jsonl` and the `analyze` report.
+
+use primordium_data::{LiveEvent, NarrationRefs};
+
+use crate::app::state::App;
+
+/// Most narrations kept for the list.
+pub const MAX_NARRATIONS: usize = 50;
+
+/// A narration as the list shows it.
+#[derive(Debug, Clone)]
+pub struct NarrationEntry {
+    pub tick: u64,
+    pub text: String,
+    pub refs: NarrationRefs,
+}
+
+impl App {
+    /// Keeps `event` for the narration list if it is a narration.
+    pub fn remember_narration(&mut self, event: &LiveEvent) {
+        let LiveEvent::Narration {
+            tick, text, refs, ..
+        } = event
+        else {
+            return;
+        };
+        if self.narrations.len() == MAX_NARRATIONS {
+            self.narrations.pop_back();
+        }
+        self.narrations.push_front(NarrationEntry {
+            tick: *tick,
+            text: text.clone(),
+            refs: refs.clone(),
+        });
+        // Keep the same narration selected while the list is open.
+        if self.show_narrations {
+            self.narration_index = (self.narration_index + 1).min(self.narrations.len() - 1);
+        }
+    }
+
+    pub fn toggle_narrations(&mut self) {
+        self.show_narrations = !self.show_narrations;
+        self.narration_index = 0;
+        self.narration_expanded = false;
+    }
+
+    /// Rows of the narration list: one per narration, followed by the
+    /// references of the selected one when it is expanded.
+    pub fn narration_rows(&self) -> Vec<(String, bool)> {
+        let mut rows = Vec::new();
+        for (i, entry) in self.narrations.iter().enumerate() {
+            rows.push((format!("{:>8}  {}", entry.tick, entry.text), true));
+            if self.narration_expanded && i == self.narration_index {
+                rows.extend(
+                    entry
+                        .refs
+                        .describe()
+                        .into_iter()
+                        .map(|line| (format!("          {}", line), false)),
+                );
+            }
+        }
+        rows
+    }
+}
@@ -0,0 +1,345 @@
+//! God-mode pathogen designer.
+//!
+//! `D` opens a panel to tune a custom [`Pathogen`] and release it either
+//! around a clicked world position or into the selected entity's lineage.
+//! Every release asks for confirmation and is narrated into the chronicle.
+
+use chrono::Utc;
+use primordium_data::{LiveEvent, NarrationRefs, Pathogen};
+use ratatui::style::Color;
+use uuid::Uuid;
+
+use crate::app::state::App;
+use crate::app::LiveEventExt;
+use crate::model::brain::OUTPUT_LABELS;
+
+/// Radius around the clicked position within which entities are infected.
+pub const RELEASE_RADIUS: f64 = 3.0;
+/// Brain outputs a pathogen can hijack. Perception offsets
+/// `outputs[node - 22]` for the first 11 outputs only.
+const MANIPULATION_NODE_BASE: usize = 22;
+const MANIPULABLE_OUTPUTS: usize = 11;
+
+/// Rows of the designer panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DesignerField {
+    Lethality,
+    Transmission,
+    Duration,
+    Manipulation,
+    Target,
+    Release,
+}
+
+impl DesignerField {
+    pub const ALL: [DesignerField; 6] = [
+        DesignerField::Lethality,
+        DesignerField::Transmission,
+        DesignerField::Duration,
+        DesignerField::Manipulation,
+        DesignerField::Target,
+        DesignerField::Release,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseTarget {
+    /// Everyone within [`RELEASE_RADIUS`] of a clicked position.
+    Cursor,
+    /// Every living member of the selected entity's lineage.
+    Lineage,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReleaseSite {
+    At { x: f64, y: f64 },
+    Lineage { id: Uuid, name: String },
+}
+
+impl ReleaseSite {
+    pub fn describe(&self) -> String {
+        match self {
+            ReleaseSite::At { x, y } => format!("at ({:.0}, {:.0})", x, y),
+            ReleaseSite::Lineage { name, .. } => format!("into lineage {}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DesignerStage {
+    Editing,
+    /// Waiting for a click in the world.
+    Aiming,
+    Confirm(ReleaseSite),
+}
+
+/// Tunable traits of a custom pathogen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathogenDesign {
+    /// Energy drained per tick while infected.
+    pub lethality: f32,
+    /// Chance of infecting a neighbour on contact, before immunity.
+    pub transmission: f32,
+    /// Ticks an infection lasts.
+    pub duration: u32,
+    /// Index into [`OUTPUT_LABELS`] of the brain output the pathogen pushes.
+    pub manipulation: Option<usize>,
+}
+
+impl Default for PathogenDesign {
+    fn default() -> Self {
+        Self {
+            lethality: 0.2,
+            transmission: 0.5,
+            duration: 400,
+            manipulation: None,
+        }
+    }
+}
+
+impl PathogenDesign {
+    pub fn to_pathogen(&self) -> Pathogen {
+        Pathogen {
+            id: Uuid::new_v4(),
+            lethality: self.lethality,
+            transmission: self.transmission,
+            duration: self.duration,
+            // Contact spread rolls against `virulence`, so the designed
+            // transmission drives it as well.
+            virulence: self.transmission,
+            behavior_manipulation: self
+                .manipulation
+                .map(|output| (MANIPULATION_NODE_BASE + output, 1.0)),
+        }
+    }
+
+    fn manipulation_label(&self) -> &'static str {
+        self.manipulation
+            .map_or("None", |output| OUTPUT_LABELS[output])
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "lethality {:.2}, transmission {:.2}, {} ticks, hijacks {}",
+            self.lethality,
+            self.transmission,
+            self.duration,
+            self.manipulation_label()
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PathogenDesigner {
+    pub design: PathogenDesign,
+    pub target: ReleaseTarget,
+    pub selected: usize,
+    pub stage: DesignerStage,
+    pub error: Option<String>,
+}
+
+impl Default for PathogenDesigner {
+    fn default() -> Self {
+        Self {
+            design: PathogenDesign::default(),
+            target: ReleaseTarget::Cursor,
+            selected: 0,
+            stage: DesignerStage::Editing,
+            error: None,
+        }
+    }
+}
+
+impl PathogenDesigner {
+    pub fn field(&self) -> DesignerField {
+        DesignerField::ALL[self.selected]
+    }
+
+    pub fn move_selection(&mut self, step: isize) {
+        let len = DesignerField::ALL.len() as isize;
+        self.selected = (self.selected as isize + step).rem_euclid(len) as usize;
+    }
+
+    /// `(label, value)` pairs for display, one per [`DesignerField`].
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let design = &self.design;
+        DesignerField::ALL
+            .iter()
+            .map(|field| match field {
+                DesignerField::Lethality => ("Lethality", format!("{:.2}", design.lethality)),
+                DesignerField::Transmission => {
+                    ("Transmission", format!("{:.2}", design.transmission))
+                }
+                DesignerField::Duration => ("Duration", format!("{} ticks", design.duration)),
+                DesignerField::Manipulation => ("Hijacks", design.manipulation_label().to_string()),
+                DesignerField::Target => (
+                    "Release",
+                    match self.target {
+                        ReleaseTarget::Cursor => "at cursor".into(),
+                        ReleaseTarget::Lineage => "into selected lineage".into(),
+                    },
+                ),
+                DesignerField::Release => ("", "[ Release ]".into()),
+            })
+            .collect()
+    }
+
+    /// Changes the value under the selected row by `step` increments.
+    pub fn adjust(&mut self, step: i32) {
+        let field = self.field();
+        let design = &mut self.design;
+        match field {
+            DesignerField::Lethality => {
+                design.lethality = (design.lethality + 0.05 * step as f32).clamp(0.01, 1.0);
+            }
+            DesignerField::Transmission => {
+                design.transmission = (design.transmission + 0.05 * step as f32).clamp(0.05, 1.5);
+            }
+            DesignerField::Duration => {
+                design.duration = (design.duration as i32 + 50 * step).clamp(50, 5_000) as u32;
+            }
+            DesignerField::Manipulation => {
+                // `None` sits before the first output in the cycle.
+                let len = MANIPULABLE_OUTPUTS as i32 + 1;
+                let current = design.manipulation.map_or(0, |output| output as i32 + 1);
+                let next = (current + step).rem_euclid(len);
+                design.manipulation = (next > 0).then(|| next as usize - 1);
+            }
+            DesignerField::Target => {
+                self.target = match self.target {
+                    ReleaseTarget::Cursor => ReleaseTarget::Lineage,
+                    ReleaseTarget::Lineage => ReleaseTarget::Cursor,
+                };
+            }
+            DesignerField::Release => {}
+        }
+    }
+}
+
+impl App {
+    pub fn open_pathogen_designer(&mut self) {
+        self.pathogen_designer = Some(PathogenDesigner::default());
+    }
+
+    /// Moves on from editing: either waits for a click or asks to confirm a
+    /// release into the selected lineage.
+    pub fn begin_pathogen_release(&mut self) {
+        let lineage = self.selected_genotype().map(|genotype| genotype.lineage_id);
+        let Some(designer) = &mut self.pathogen_designer else {
+            return;
+        };
+        designer.error = None;
+        match designer.target {
+            ReleaseTarget::Cursor => {
+                designer.stage = DesignerStage::Aiming;
+                self.event_log.push_back((
+                    "Click the world to release the pathogen, [Esc] to cancel".to_string(),
+                    Color::Yellow,
+                ));
+            }
+            ReleaseTarget::Lineage => match lineage {
+                Some(id) => {
+                    let name = self
+                        .world
+                        .lineage_registry
+                        .lineages
+                        .get(&id)
+                        .map_or_else(|| id.to_string()[..8].to_string(), |r| r.name.clone());
+                    designer.stage = DesignerStage::Confirm(ReleaseSite::Lineage { id, name });
+                }
+                None => {
+                    designer.error = Some("Select an entity to target its lineage".into());
+                }
+            },
+        }
+    }
+
+    /// Records the clicked world position while aiming.
+    pub fn aim_pathogen_release(&mut self, x: f64, y: f64) {
+        if let Some(designer) = &mut self.pathogen_designer {
+            if designer.stage == DesignerStage::Aiming {
+                designer.stage = DesignerStage::Confirm(ReleaseSite::At { x, y });
+            }
+        }
+    }
+
+    /// Releases the designed pathogen at the confirmed site, narrates the
+    /// outbreak and closes the designer.
+    pub fn confirm_pathogen_release(&mut self) {
+        let Some(designer) = self.pathogen_designer.take() else {
+            return;
+        };
+        let DesignerStage::Confirm(site) = &designer.stage else {
+            self.pathogen_designer = Some(designer);
+            return;
+        };
+        let pathogen = designer.design.to_pathogen();
+        let infected = match site {
+            ReleaseSite::At { x, y } => {
+                self.world
+                    .release_pathogen_at(&pathogen, *x, *y, RELEASE_RADIUS)
+            }
+            ReleaseSite::Lineage { id, .. } => {
+                self.world.release_pathogen_in_lineage(&pathogen, *id)
+            }
+        };
+
+        let event = LiveEvent::Narration {
+            tick: self.world.tick,
+            text: format!(
+                "A designed pathogen ({}) was released {}, infecting {}.",
+                designer.design.summary(),
+                site.describe(),
+                infected
+            ),
+            severity: pathogen.lethality,
+            timestamp: Utc::now().to_rfc3339(),
+            refs: NarrationRefs {
+                lineages: match site {
+                    ReleaseSite::Lineage { id, .. } => vec![*id],
+                    ReleaseSite::At { .. } => Vec::new(),
+                },
+                data: vec![
+                    format!("pathogen: {}", designer.design.summary()),
+                    format!("infected: {}", infected),
+                ],
+                ..NarrationRefs::at(self.world.tick)
+            },
+        };
+        let _ = self.world.logger.log_event(event.clone());
+        self.remember_narration(&event);
+        self.event_log.push_back(event.to_ui_message());
+        if self.event_log.len() > 15 {
+            self.event_log.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_design_cycles_and_builds_pathogen() {
+        let mut designer = PathogenDesigner::default();
+        designer.move_selection(-1);
+        assert_eq!(designer.field(), DesignerField::Release);
+
+        designer.selected = 3;
+        designer.adjust(-1);
+        assert_eq!(designer.design.manipulation, Some(MANIPULABLE_OUTPUTS - 1));
+        designer.adjust(1);
+        assert_eq!(designer.design.manipulation, None);
+        designer.adjust(4);
+
+        designer.selected = 0;
+        designer.adjust(100);
+        assert_eq!(designer.design.lethality, 1.0);
+
+        let pathogen = designer.design.to_pathogen();
+        assert_eq!(pathogen.virulence, pathogen.transmission);
+        // Perception maps the node back to the hijacked output.
+        let (node, _) = pathogen.behavior_manipulation.unwrap();
+        assert_eq!(OUTPUT_LABELS[node - MANIPULATION_NODE_BASE], "Aggro");
+    }
+}
@@ -0,0 +1,102 @@
+//! Phylogeny browser.
+//!
+//! `H` opens the speciation tree of the lineage registry as a collapsible
+//! outline with the living population of each lineage and clade. Arrows
+//! move through it and fold branches, and `Enter` selects the strongest
+//! living member of the highlighted lineage so the world view and brain
+//! panel follow it.
+
+use primordium_data::{Identity, Metabolism, Position};
+use primordium_tui::views::phylogeny::{visible_rows, PhylogenyRow};
+use ratatui::style::Color;
+use uuid::Uuid;
+
+use crate::app::state::App;
+use crate::model::lineage_tree::LineageTree;
+
+impl App {
+    pub fn toggle_phylogeny(&mut self) {
+        self.show_phylogeny = !self.show_phylogeny;
+        let rows = self.phylogeny_rows().1;
+        self.phylogeny_index = self.phylogeny_index.min(rows.len().saturating_sub(1));
+    }
+
+    /// The current speciation tree and its visible rows.
+    pub fn phylogeny_rows(&self) -> (LineageTree, Vec<PhylogenyRow>) {
+        let tree = LineageTree::build(&self.world.lineage_registry);
+        let rows = visible_rows(&tree, &self.phylogeny_collapsed);
+        (tree, rows)
+    }
+
+    /// Moves the selection by `delta` rows.
+    pub fn move_phylogeny(&mut self, delta: isize) {
+        let len = self.phylogeny_rows().1.len();
+        self.phylogeny_index = self
+            .phylogeny_index
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    /// Folds (`open == false`) or unfolds the selected lineage. Folding a
+    /// leaf or an already folded lineage moves to its parent instead, and
+    /// unfolding an open one moves to its first child.
+    pub fn fold_phylogeny(&mut self, open: bool) {
+        let rows = self.phylogeny_rows().1;
+        let Some(row) = rows.get(self.phylogeny_index) else {
+            return;
+        };
+        match (open, row.has_children && !row.collapsed) {
+            (false, true) => {
+                self.phylogeny_collapsed.insert(row.id);
+            }
+            (false, false) => {
+                if let Some(parent) = rows[..self.phylogeny_index]
+                    .iter()
+                    .rposition(|r| r.depth < row.depth)
+                {
+                    self.phylogeny_index = parent;
+                }
+            }
+            (true, _) if row.collapsed => {
+                self.phylogeny_collapsed.remove(&row.id);
+            }
+            (true, true) => self.phylogeny_index += 1,
+            (true, false) => {}
+        }
+    }
+
+    /// Selects the living member of the highlighted lineage with the most
+    /// energy and closes the browser.
+    pub fn follow_phylogeny(&mut self) {
+        let (tree, rows) = self.phylogeny_rows();
+        let Some(lineage) = rows.get(self.phylogeny_index).map(|r| r.id) else {
+            return;
+        };
+        let name = tree
+            .node(&lineage)
+            .map_or_else(String::new, |n| n.name.clone());
+        let member: Option<(Uuid, f64, f64)> = self
+            .world
+            .ecs
+            .query::<(&Identity, &Metabolism, &Position)>()
+            .iter()
+            .filter(|(_, (_, metabolism, _))| metabolism.lineage_id == lineage)
+            .max_by(|(_, (a, ma, _)), (_, (b, mb, _))| {
+                ma.energy.total_cmp(&mb.energy).then(b.id.cmp(&a.id))
+            })
+            .map(|(_, (identity, _, pos))| (identity.id, pos.x, pos.y));
+
+        let Some((id, x, y)) = member else {
+            self.event_log
+                .push_back((format!("{} has no living members", name), Color::Yellow));
+            return;
+        };
+        self.selected_entity = Some(id);
+        self.show_brain = true;
+        self.show_phylogeny = false;
+        self.event_log.push_back((
+            format!("Following a member of {} at ({:.0}, {:.0})", name, x, y),
+            Color::Cyan,
+        ));
+    }
+}
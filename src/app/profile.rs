@@ -0,0 +1,129 @@
+use primordium_io::profile::{LineageBest, PlayerProfile, SessionStats};
+use primordium_io::storage::StorageManager;
+use ratatui::style::Color;
+use std::time::Duration;
+
+use crate::app::state::App;
+use crate::model::world::World;
+
+/// SQLite file holding the player profile, next to `config.toml`.
+pub const PROFILE_DB: &str = "profile.db";
+/// Ticks between folding the running session into the stored profile.
+pub const PROFILE_FLUSH_TICKS: u64 = 1_000;
+
+/// The stored profile plus the not yet recorded progress of this run.
+pub struct ProfileSession {
+    storage: StorageManager,
+    pub profile: PlayerProfile,
+    session: SessionStats,
+    last_tick: u64,
+    last_flush_tick: u64,
+}
+
+impl ProfileSession {
+    /// Loads the profile stored at `path` and counts a new run.
+    pub fn open(path: &str, world: &World) -> Option<Self> {
+        let storage = StorageManager::new(path).ok()?;
+        let mut profile = storage
+            .query_profile_async()?
+            .recv_timeout(Duration::from_secs(2))
+            .ok()?;
+        profile.runs += 1;
+        storage.save_profile(&profile);
+        Some(Self {
+            storage,
+            profile,
+            session: SessionStats::default(),
+            last_tick: world.tick,
+            last_flush_tick: world.tick,
+        })
+    }
+
+    /// Accumulates the world's progress since the last observation.
+    pub fn observe(&mut self, world: &World) {
+        // A reset or loaded world starts counting afresh from its own tick.
+        self.session.ticks += world.tick.saturating_sub(self.last_tick);
+        self.last_tick = world.tick;
+        self.session.max_population = self
+            .session
+            .max_population
+            .max(world.get_population_count());
+    }
+
+    pub fn flush_due(&self, world: &World) -> bool {
+        world.tick < self.last_flush_tick
+            || world.tick - self.last_flush_tick >= PROFILE_FLUSH_TICKS
+    }
+
+    /// Folds the session into the profile, saves it and returns the titles of
+    /// newly earned achievements.
+    pub fn flush(&mut self, world: &World) -> Vec<&'static str> {
+        self.observe(world);
+        self.session.longest_lineage = world
+            .lineage_registry
+            .lineages
+            .values()
+            .map(|record| LineageBest {
+                id: record.id,
+                name: record.name.clone(),
+                ticks: record
+                    .extinct_since_tick
+                    .unwrap_or(world.tick)
+                    .saturating_sub(record.first_appearance_tick),
+            })
+            .max_by_key(|best| best.ticks);
+
+        let earned = self.profile.record(&self.session);
+        self.session = SessionStats::default();
+        self.last_flush_tick = world.tick;
+        self.storage.save_profile(&self.profile);
+        earned.into_iter().map(|a| a.title()).collect()
+    }
+
+    /// Blocks until the storage thread has written every queued save.
+    pub fn sync(&self) {
+        if let Some(rx) = self.storage.query_profile_async() {
+            let _ = rx.recv_timeout(Duration::from_secs(2));
+        }
+    }
+}
+
+impl App {
+    /// Opens the player profile and counts a new run.
+    pub fn open_profile(&mut self) {
+        self.profile = ProfileSession::open(PROFILE_DB, &self.world);
+    }
+
+    /// Per-tick profile bookkeeping; flushes and announces achievements
+    /// every [`PROFILE_FLUSH_TICKS`] ticks.
+    pub fn update_profile(&mut self) {
+        let Some(profile) = &mut self.profile else {
+            return;
+        };
+        profile.observe(&self.world);
+        if profile.flush_due(&self.world) {
+            self.flush_profile();
+        }
+    }
+
+    /// Records the session on exit and waits for it to reach disk.
+    pub fn close_profile(&mut self) {
+        self.flush_profile();
+        if let Some(profile) = &self.profile {
+            profile.sync();
+        }
+    }
+
+    /// Records the session into the profile immediately.
+    pub fn flush_profile(&mut self) {
+        let Some(profile) = &mut self.profile else {
+            return;
+        };
+        for title in profile.flush(&self.world) {
+            self.event_log.push_back((
+                format!("ACHIEVEMENT UNLOCKED: {}", title),
+                Color::LightYellow,
+            ));
+        }
+    }
+}
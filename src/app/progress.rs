@@ -0,0 +1,231 @@
+//! Periodic progress output of headless runs.
+//!
+//! `--mode headless` reports every `--status-interval` seconds: a readable
+//! status line by default, one JSON object per line with `--json-status`,
+//! or nothing with `--quiet`.
+
+use crate::app::state::App;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use sysinfo::Pid;
+
+/// How progress reports are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusFormat {
+    #[default]
+    Text,
+    Json,
+    Quiet,
+}
+
+/// One progress report of a headless run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressReport {
+    pub tick: u64,
+    /// Ticks simulated this run.
+    pub ticks_run: u64,
+    /// The `--ticks` target of this run, if any.
+    pub target_ticks: Option<u64>,
+    /// Ticks per second since the previous report.
+    pub ticks_per_sec: f64,
+    pub population: usize,
+    pub species_count: usize,
+    /// Seconds until `target_ticks` at the current rate.
+    pub eta_secs: Option<f64>,
+    /// Resident memory of the process.
+    pub memory_mb: f64,
+    pub elapsed_secs: f64,
+}
+
+impl ProgressReport {
+    /// The report as a one-line human-readable status.
+    pub fn status_line(&self) -> String {
+        let progress = match self.target_ticks {
+            Some(target) if target > 0 => format!(
+                "{}/{} ({:.1}%)",
+                self.ticks_run,
+                target,
+                self.ticks_run as f64 * 100.0 / target as f64
+            ),
+            _ => self.ticks_run.to_string(),
+        };
+        let eta = self
+            .eta_secs
+            .map(|secs| format!(" | ETA {}", format_duration(secs)))
+            .unwrap_or_default();
+        format!(
+            "[{}] tick {} | {progress} ticks | {:.1} t/s | pop {} | species {}{eta} | mem {:.0} MB",
+            format_duration(self.elapsed_secs),
+            self.tick,
+            self.ticks_per_sec,
+            self.population,
+            self.species_count,
+            self.memory_mb
+        )
+    }
+}
+
+/// `h:mm:ss` of a number of seconds.
+fn format_duration(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Tracks a headless run to report its progress every `interval`.
+#[derive(Debug, Clone)]
+pub struct Progress {
+    pub format: StatusFormat,
+    pub interval: Duration,
+    pub target_ticks: Option<u64>,
+    start_tick: u64,
+    started: Instant,
+    last_report: Instant,
+    last_tick: u64,
+}
+
+impl Progress {
+    pub fn new(
+        format: StatusFormat,
+        interval: Duration,
+        start_tick: u64,
+        target_ticks: Option<u64>,
+        now: Instant,
+    ) -> Self {
+        Self {
+            format,
+            interval,
+            target_ticks,
+            start_tick,
+            started: now,
+            last_report: now,
+            last_tick: start_tick,
+        }
+    }
+
+    /// Whether the next report is due.
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.format != StatusFormat::Quiet && now.duration_since(self.last_report) >= self.interval
+    }
+
+    /// Whether the run has simulated its `--ticks` target.
+    pub fn reached_target(&self, tick: u64) -> bool {
+        self.target_ticks
+            .is_some_and(|target| tick.saturating_sub(self.start_tick) >= target)
+    }
+
+    /// Measures the rate since the previous report and starts the next
+    /// interval.
+    pub fn report(
+        &mut self,
+        tick: u64,
+        population: usize,
+        species_count: usize,
+        memory_mb: f64,
+        now: Instant,
+    ) -> ProgressReport {
+        let window = now.duration_since(self.last_report).as_secs_f64();
+        let ticks_per_sec = if window > 0.0 {
+            tick.saturating_sub(self.last_tick) as f64 / window
+        } else {
+            0.0
+        };
+        let ticks_run = tick.saturating_sub(self.start_tick);
+        let eta_secs = self.target_ticks.and_then(|target| {
+            let remaining = target.saturating_sub(ticks_run);
+            (ticks_per_sec > 0.0).then(|| remaining as f64 / ticks_per_sec)
+        });
+        self.last_report = now;
+        self.last_tick = tick;
+        ProgressReport {
+            tick,
+            ticks_run,
+            target_ticks: self.target_ticks,
+            ticks_per_sec,
+            population,
+            species_count,
+            eta_secs,
+            memory_mb,
+            elapsed_secs: now.duration_since(self.started).as_secs_f64(),
+        }
+    }
+
+    /// The report formatted for output, or `None` when quiet.
+    pub fn render(&self, report: &ProgressReport) -> Option<String> {
+        match self.format {
+            StatusFormat::Text => Some(report.status_line()),
+            StatusFormat::Json => serde_json::to_string(report).ok(),
+            StatusFormat::Quiet => None,
+        }
+    }
+}
+
+impl App {
+    /// Reports the progress of a headless run, including the resident
+    /// memory of the process.
+    pub fn progress_report(&mut self, progress: &mut Progress) -> ProgressReport {
+        let pid = Pid::from_u32(std::process::id());
+        self.sys.refresh_process(pid);
+        let memory_mb = self
+            .sys
+            .process(pid)
+            .map_or(0.0, |process| process.memory() as f64 / 1024.0 / 1024.0);
+        let stats = &self.world.pop_stats;
+        progress.report(
+            self.world.tick,
+            stats.population,
+            stats.species_count,
+            memory_mb,
+            Instant::now(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_measures_rate_and_eta() {
+        let start = Instant::now();
+        let mut progress = Progress::new(
+            StatusFormat::Text,
+            Duration::from_secs(5),
+            1_000,
+            Some(10_000),
+            start,
+        );
+        assert!(!progress.is_due(start + Duration::from_secs(4)));
+        assert!(progress.is_due(start + Duration::from_secs(5)));
+
+        let report = progress.report(3_000, 120, 4, 64.0, start + Duration::from_secs(10));
+        assert_eq!(report.ticks_run, 2_000);
+        assert!((report.ticks_per_sec - 200.0).abs() < 1e-9);
+        assert!((report.eta_secs.unwrap() - 40.0).abs() < 1e-9);
+        assert_eq!(
+            report.status_line(),
+            "[0:00:10] tick 3000 | 2000/10000 (20.0%) ticks | 200.0 t/s | pop 120 | species 4 | ETA 0:00:40 | mem 64 MB"
+        );
+
+        // The next rate only covers the ticks since this report.
+        let report = progress.report(3_500, 120, 4, 64.0, start + Duration::from_secs(20));
+        assert!((report.ticks_per_sec - 50.0).abs() < 1e-9);
+        assert!(!progress.reached_target(10_999));
+        assert!(progress.reached_target(11_000));
+    }
+
+    #[test]
+    fn test_json_and_quiet_formats() {
+        let start = Instant::now();
+        let mut progress =
+            Progress::new(StatusFormat::Json, Duration::from_secs(1), 0, None, start);
+        let report = progress.report(50, 10, 1, 32.0, start + Duration::from_secs(1));
+        assert_eq!(report.eta_secs, None);
+        let line = progress.render(&report).unwrap();
+        let parsed: ProgressReport = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed, report);
+
+        progress.format = StatusFormat::Quiet;
+        assert!(progress.render(&report).is_none());
+        assert!(!progress.is_due(start + Duration::from_secs(60)));
+    }
+}
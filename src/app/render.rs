@@ -1,4 +1,7 @@
-use crate::app::state::{App, UiMode};
+use crate::app::state::App;
+use primordium_core::civilization::{self, OutpostChallenge, OutpostNetwork};
+use primordium_core::config::SidebarPanel;
+use primordium_core::palette::Palette;
 use primordium_tui::renderer::WorldWidget;
 use primordium_tui::views::*;
 use ratatui::layout::{Constraint, Direction, Layout};
@@ -17,11 +20,29 @@ impl App {
         };
         let snapshot = &snapshot;
 
+        if self.show_archeology {
+            self.load_archeology_snapshot();
+        }
+
         self.draw_background(f);
         let (main_layout_area, left_layout_vec) = self.create_layouts(f);
         self.draw_main_content(f, snapshot, &left_layout_vec);
+        if self.screensaver {
+            if let Some(attract) = &self.attract {
+                let reel = attract.reel();
+                f.render_widget(
+                    AttractCaptionWidget {
+                        title: &reel.title,
+                        tick: snapshot.tick,
+                        caption: reel.caption_at(snapshot.tick).map(|c| c.text.as_str()),
+                    },
+                    f.area(),
+                );
+            }
+            return;
+        }
         self.draw_sidebar(f, snapshot, &main_layout_area);
-        self.draw_overlays(f);
+        self.draw_overlays(f, &left_layout_vec, main_layout_area);
     }
 
     fn draw_background(&self, f: &mut Frame) {
@@ -34,19 +55,21 @@ impl App {
         &mut self,
         f: &mut Frame,
     ) -> (ratatui::layout::Rect, Vec<ratatui::layout::Rect>) {
+        let layout = self.current_layout();
+        let sidebar_open = self.show_brain
+            || self.show_ancestry
+            || self.show_energy_ledger
+            || self.show_ecs_report
+            || self.show_calendar
+            || self.show_forecast
+            || self.show_archeology
+            || self.view_mode >= 5
+            || layout.sidebar_panel != SidebarPanel::Auto;
         let main_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
                 Constraint::Min(0),
-                if self.show_brain
-                    || self.show_ancestry
-                    || self.show_archeology
-                    || self.view_mode >= 6
-                {
-                    Constraint::Length(45)
-                } else {
-                    Constraint::Length(0)
-                },
+                Constraint::Length(if sidebar_open { layout.sidebar } else { 0 }),
             ])
             .split(f.area());
 
@@ -54,7 +77,12 @@ impl App {
 
         let left_layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints(self.get_layout_constraints())
+            .constraints([
+                Constraint::Length(layout.stats),
+                Constraint::Length(layout.trends),
+                Constraint::Min(0),
+                Constraint::Length(layout.chronicle),
+            ])
             .split(main_layout[0]);
 
         self.last_world_rect = left_layout[2];
@@ -89,7 +117,12 @@ impl App {
             glow_enabled,
             glow_intensity,
             density_variation,
-        );
+        )
+        .with_tag_filter(self.tag_filter.as_deref())
+        .with_pheromone_channel(self.pheromone_channel)
+        .with_route_trail(self.route_trail())
+        .with_palette(self.palette())
+        .with_glyphs(&self.glyphs);
         f.render_widget(world_widget, f.area());
 
         if self.cinematic_mode {
@@ -128,14 +161,23 @@ impl App {
                 ram_usage_percent: self.env.ram_usage_percent,
                 app_memory_usage_mb: self.env.app_memory_usage_mb as f64,
                 current_era: self.env.current_era,
+                era_modifiers: self.env.era_modifiers,
                 oxygen_level: self.env.oxygen_level,
                 view_mode: self.view_mode,
+                pheromone_channel: self.pheromone_channel,
                 peer_count: self.network_state.peers.len(),
                 migrations_received: self.network_state.migrations_received as u64,
                 migrations_sent: self.network_state.migrations_sent as u64,
                 is_online: self.network_state.client_id.is_some(),
                 resource_icon: self.env.resource_state().icon().to_string(),
                 available_energy: self.env.available_energy,
+                show_interaction_rates: self.config.analytics.record_interactions,
+                sim_threads: self.thread_pools.report().sim_threads,
+                log_quota_warning: self
+                    .log_quota_warned
+                    .then(|| self.world.logger.disk_usage())
+                    .flatten()
+                    .map(|usage| usage.fraction()),
             },
             area,
         );
@@ -172,8 +214,83 @@ impl App {
             glow_enabled,
             glow_intensity,
             density_variation,
-        );
+        )
+        .with_tag_filter(self.tag_filter.as_deref())
+        .with_pheromone_channel(self.pheromone_channel)
+        .with_route_trail(self.route_trail())
+        .with_palette(self.palette())
+        .with_glyphs(&self.glyphs);
         f.render_widget(world_widget, area);
+
+        if self.view_mode == 7 {
+            let (network, contested) = self.outpost_view(snapshot);
+            f.render_widget(
+                OutpostNetworkOverlay {
+                    network: &network,
+                    contested: &contested,
+                    tick: snapshot.tick,
+                    screensaver: false,
+                },
+                area,
+            );
+        }
+
+        if self.show_perception {
+            let selected = self
+                .selected_entity
+                .and_then(|id| snapshot.entities.iter().find(|e| e.id == id));
+            if let Some(entity) = selected {
+                if let Some(perception) = self.world.perception_of(entity.id, &self.env) {
+                    f.render_widget(
+                        PerceptionOverlay {
+                            perception: &perception,
+                            name: &entity.name,
+                        },
+                        area,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Tint of the world view: the environment's, while [g] is on.
+    fn palette(&self) -> Palette {
+        if self.show_palette {
+            Palette::from_environment(&self.env)
+        } else {
+            Palette::NEUTRAL
+        }
+    }
+
+    /// Route trail to draw: the selected entity's, while [t] is on.
+    fn route_trail(&self) -> Option<&crate::model::route_trail::RouteTrail> {
+        self.world
+            .route_trail
+            .as_ref()
+            .filter(|t| self.show_route_trail && Some(t.entity_id) == self.selected_entity)
+    }
+
+    /// Outpost graph and contested outposts shown by the civilization view.
+    fn outpost_view(
+        &self,
+        snapshot: &crate::model::snapshot::WorldSnapshot,
+    ) -> (OutpostNetwork, Vec<OutpostChallenge>) {
+        let network = civilization::outpost_network(
+            &snapshot.terrain,
+            snapshot.width,
+            snapshot.height,
+            &self.world.lineage_registry,
+            &snapshot.roads,
+            &self.world.config.roads,
+        );
+        let contested = civilization::contested_outposts(
+            &snapshot.terrain,
+            snapshot.width,
+            &self.world.spatial_hash,
+            &self.world.entity_snapshots,
+            &self.world.lineage_registry,
+        );
+        (network, contested)
     }
 
     fn draw_chronicle(&self, f: &mut Frame, area: ratatui::layout::Rect) {
@@ -189,14 +306,63 @@ impl App {
     ) {
         let sidebar_area = *main_layout;
         if self.show_ancestry {
-            f.render_widget(AncestryWidget { snapshot }, sidebar_area);
+            f.render_widget(
+                AncestryWidget {
+                    snapshot,
+                    registry: &self.world.lineage_registry,
+                    gc_totals: &self.world.lineage_registry.gc_totals,
+                    migration_totals: &self.world.lineage_registry.migration_totals,
+                    migrating: self.world.lineage_registry.migrating_count(),
+                },
+                sidebar_area,
+            );
         } else if self.show_archeology {
             f.render_widget(
                 ArcheologyWidget {
-                    snapshots: &self.archeology_snapshots,
+                    snapshot: self
+                        .archeology
+                        .tick(self.archeology_index)
+                        .map(|tick| (tick, self.archeology.cached(self.archeology_index))),
                     index: self.archeology_index,
-                    fossils: &self.world.fossil_registry.fossils,
+                    total: self.archeology.len(),
+                    next_tick: self.archeology.tick(self.archeology_index + 1),
+                    fossils: self.visible_fossils(),
                     selected_fossil_index: self.selected_fossil_index,
+                    filter_label: self.fossil_filter.label(),
+                    bookmarks: &self.world.bookmarks,
+                    narrations: self.archeology.narrations(self.archeology_index),
+                    ruins: &self.world.fossil_registry.ruins,
+                },
+                sidebar_area,
+            );
+        } else if self.show_ecs_report {
+            f.render_widget(
+                EcsReportWidget {
+                    report: &self.world.ecs_report(),
+                },
+                sidebar_area,
+            );
+        } else if self.show_calendar {
+            f.render_widget(
+                CalendarWidget {
+                    env: &self.env,
+                    tick: snapshot.tick,
+                },
+                sidebar_area,
+            );
+        } else if self.show_forecast {
+            f.render_widget(
+                ForecastWidget {
+                    forecaster: &self.world.forecaster,
+                    tick: snapshot.tick,
+                },
+                sidebar_area,
+            );
+        } else if self.show_energy_ledger {
+            f.render_widget(
+                EnergyLedgerWidget {
+                    ledger: self.world.energy_ledger.as_ref(),
+                    tick: snapshot.tick,
                 },
                 sidebar_area,
             );
@@ -208,14 +374,14 @@ impl App {
                 },
                 sidebar_area,
             );
-        } else if self.view_mode == 6 {
+        } else if self.view_mode == 5 {
             f.render_widget(
                 MarketWidget {
                     trade_offers: &self.network_state.trade_offers,
                 },
                 sidebar_area,
             );
-        } else if self.view_mode == 7 {
+        } else if self.view_mode == 6 {
             f.render_widget(
                 ResearchWidget {
                     snapshot,
@@ -223,17 +389,62 @@ impl App {
                 },
                 sidebar_area,
             );
-        } else if self.view_mode == 8 {
+        } else if self.view_mode == 7 {
+            let (network, contested) = self.outpost_view(snapshot);
             f.render_widget(
                 CivilizationWidget {
                     registry: &self.world.lineage_registry,
+                    network: &network,
+                    contested: &contested,
                 },
                 sidebar_area,
             );
+        } else {
+            self.draw_pinned_panel(f, snapshot, sidebar_area);
         }
     }
 
-    fn draw_overlays(&self, f: &mut Frame) {
+    /// Draws the layout's pinned sidebar panel when nothing is toggled on.
+    fn draw_pinned_panel(
+        &self,
+        f: &mut Frame,
+        snapshot: &crate::model::snapshot::WorldSnapshot,
+        area: ratatui::layout::Rect,
+    ) {
+        match self.current_layout().sidebar_panel {
+            SidebarPanel::Auto => {}
+            SidebarPanel::Brain => f.render_widget(
+                BrainWidget {
+                    snapshot,
+                    selected_entity: self.selected_entity,
+                },
+                area,
+            ),
+            SidebarPanel::Lineage => f.render_widget(
+                AncestryWidget {
+                    snapshot,
+                    registry: &self.world.lineage_registry,
+                    gc_totals: &self.world.lineage_registry.gc_totals,
+                    migration_totals: &self.world.lineage_registry.migration_totals,
+                    migrating: self.world.lineage_registry.migrating_count(),
+                },
+                area,
+            ),
+            SidebarPanel::Network => f.render_widget(
+                NetworkWidget {
+                    state: &self.network_state,
+                },
+                area,
+            ),
+        }
+    }
+
+    fn draw_overlays(
+        &self,
+        f: &mut Frame,
+        left_layout: &[ratatui::layout::Rect],
+        sidebar_area: ratatui::layout::Rect,
+    ) {
         if self.show_help {
             f.render_widget(
                 HelpWidget {
@@ -243,13 +454,419 @@ impl App {
             );
         }
 
-        if let Some(_step) = self.onboarding_step {
-            self.render_onboarding(f);
-        }
+        self.render_tutorial(f, left_layout, sidebar_area);
 
         if self.show_legend {
             f.render_widget(LegendWidget, f.area());
         }
+
+        if self.show_registry {
+            let area = f.area();
+            let registry_area = ratatui::layout::Rect::new(
+                area.x + area.width / 10,
+                area.y + area.height / 10,
+                area.width - area.width / 5,
+                area.height - area.height / 5,
+            );
+            f.render_widget(ratatui::widgets::Clear, registry_area);
+            let status = self.registry_status();
+            primordium_tui::views::registry::draw_registry(
+                f,
+                &primordium_tui::views::registry::RegistryViewData {
+                    tab: self.registry_tab,
+                    hall_of_fame: &self.cached_registry_hof,
+                    genomes: &self.cached_registry_genomes,
+                    seeds: &self.cached_registry_seeds,
+                    submissions: &self.cached_registry_submissions,
+                    selected_index: self.registry_selected_index,
+                    status: &status,
+                },
+                registry_area,
+            );
+        }
+
+        if let Some(designer) = &self.pathogen_designer {
+            self.draw_pathogen_designer(f, designer);
+        }
+
+        if self.show_bookmarks || self.bookmark_note.is_some() {
+            self.draw_bookmarks(f);
+        }
+
+        if self.show_narrations {
+            self.draw_narrations(f);
+        }
+
+        if self.show_phylogeny {
+            self.draw_phylogeny(f);
+        }
+
+        if let Some((prompt, text)) = &self.tag_prompt {
+            self.draw_tag_prompt(f, *prompt, text);
+        }
+
+        if let Some(selected) = self.layout_editor {
+            self.draw_layout_editor(f, selected);
+        }
+
+        if let Some(menu) = &self.start_menu {
+            self.draw_start_menu(f, menu);
+        }
+
+        if let Some(trial) = &self.import_trial {
+            self.draw_import_trial(f, trial);
+        }
+
+        if let Some(trip) = &self.world.guardrail_trip {
+            f.render_widget(
+                GuardrailPromptWidget {
+                    breaker: trip.kind.label(),
+                    observed: trip.observed,
+                    limit: trip.limit,
+                    tick: trip.tick,
+                    suspected_source: &trip.suspected_source,
+                },
+                f.area(),
+            );
+        }
+    }
+
+    fn draw_layout_editor(&self, f: &mut Frame, selected: usize) {
+        use crate::app::layout::Pane;
+
+        let layout = self.current_layout();
+        let custom = self
+            .config
+            .layout
+            .views
+            .contains_key(&self.view_mode.to_string());
+        let title = format!(
+            "Layout: {}{} (view {})",
+            layout.name,
+            if custom { "*" } else { "" },
+            self.view_mode + 1
+        );
+        let rows = Pane::ALL
+            .iter()
+            .map(|pane| {
+                let (label, value) = pane.describe(&layout);
+                (format!("{:<14} {}", label, value), true)
+            })
+            .collect();
+
+        // Top-left corner, so the panes being resized stay visible.
+        let area = f.area();
+        f.render_widget(
+            StartMenuWidget {
+                title: &title,
+                rows,
+                selected,
+                hint: "[←/→] Resize [Space] Hide [Tab] Preset [s] Save [Esc] Done",
+                error: None,
+            },
+            ratatui::layout::Rect::new(area.x, area.y, 44.min(area.width), 12.min(area.height)),
+        );
+    }
+
+    fn draw_tag_prompt(&self, f: &mut Frame, prompt: crate::app::tags::TagPrompt, text: &str) {
+        use crate::app::tags::TagPrompt;
+
+        let (title, hint, known) = match prompt {
+            TagPrompt::Search => (
+                "Search by tag".to_string(),
+                "Type a tag  [Enter] Filter (empty clears)  [Esc] Cancel",
+                Vec::new(),
+            ),
+            TagPrompt::Tag(id) => (
+                format!("Tag {}", &id.to_string()[..8]),
+                "Type a tag  [Enter] Add, or remove if present  [Esc] Cancel",
+                self.world.tags_of(id).iter().map(String::from).collect(),
+            ),
+        };
+        let mut rows = vec![(format!("{}_", text), true)];
+        if !known.is_empty() {
+            rows.push((format!("Current: {}", known.join(", ")), false));
+        }
+
+        let area = f.area();
+        let width = 60u16.min(area.width);
+        let height = (rows.len() as u16 + 6).min(area.height);
+        f.render_widget(
+            StartMenuWidget {
+                title: &title,
+                rows,
+                selected: 0,
+                hint,
+                error: None,
+            },
+            ratatui::layout::Rect::new(
+                area.x + (area.width - width) / 2,
+                area.y + (area.height - height) / 2,
+                width,
+                height,
+            ),
+        );
+    }
+
+    fn draw_bookmarks(&self, f: &mut Frame) {
+        const BOOKMARK_ROWS: usize = 12;
+        let (title, rows, selected, hint) = if let Some((tick, note)) = &self.bookmark_note {
+            (
+                format!("Bookmark tick {}", tick),
+                vec![(format!("{}_", note), true)],
+                0,
+                "Type a note  [Enter] Save  [Esc] Cancel",
+            )
+        } else if self.world.bookmarks.is_empty() {
+            (
+                "Bookmarks".to_string(),
+                vec![("No bookmarks yet, press [n] to add one".to_string(), false)],
+                usize::MAX,
+                "[Esc] Close",
+            )
+        } else {
+            // Scroll so the selected bookmark stays in view.
+            let first = self.bookmark_index.saturating_sub(BOOKMARK_ROWS - 1);
+            (
+                format!("Bookmarks ({})", self.world.bookmarks.len()),
+                self.world
+                    .bookmarks
+                    .iter()
+                    .skip(first)
+                    .take(BOOKMARK_ROWS)
+                    .map(|b| (format!("{:>8}  {}", b.tick, b.note), true))
+                    .collect(),
+                self.bookmark_index - first,
+                "[↑/↓] Select  [Enter] Jump in archeology  [x] Delete  [Esc] Close",
+            )
+        };
+
+        let area = f.area();
+        let width = 60u16.min(area.width);
+        let height = (rows.len() as u16 + 6).min(area.height);
+        f.render_widget(
+            StartMenuWidget {
+                title: &title,
+                rows,
+                selected,
+                hint,
+                error: None,
+            },
+            ratatui::layout::Rect::new(
+                area.x + (area.width - width) / 2,
+                area.y + (area.height - height) / 2,
+                width,
+                height,
+            ),
+        );
+    }
+
+    fn draw_narrations(&self, f: &mut Frame) {
+        const NARRATION_ROWS: usize = 16;
+        let (title, rows, selected, hint) = if self.narrations.is_empty() {
+            (
+                "Narrations".to_string(),
+                vec![("Nothing narrated yet".to_string(), false)],
+                usize::MAX,
+                "[Esc] Close",
+            )
+        } else {
+            // Scroll so the selected narration and its references stay in view.
+            let rows = self.narration_rows();
+            let first = self.narration_index.saturating_sub(NARRATION_ROWS / 2);
+            (
+                format!("Narrations ({})", self.narrations.len()),
+                rows.into_iter().skip(first).take(NARRATION_ROWS).collect(),
+                self.narration_index - first,
+                "[↑/↓] Select  [Enter] Show / hide data  [Esc] Close",
+            )
+        };
+
+        let area = f.area();
+        let width = 90u16.min(area.width);
+        let height = (rows.len() as u16 + 6).min(area.height);
+        f.render_widget(
+            StartMenuWidget {
+                title: &title,
+                rows,
+                selected,
+                hint,
+                error: None,
+            },
+            ratatui::layout::Rect::new(
+                area.x + (area.width - width) / 2,
+                area.y + (area.height - height) / 2,
+                width,
+                height,
+            ),
+        );
+    }
+
+    fn draw_phylogeny(&self, f: &mut Frame) {
+        let (tree, rows) = self.phylogeny_rows();
+        let area = f.area();
+        let width = 80u16.min(area.width);
+        let height = (rows.len() as u16 + 5).clamp(8, 30).min(area.height);
+        f.render_widget(
+            PhylogenyWidget {
+                tree: &tree,
+                rows: &rows,
+                selected: self.phylogeny_index,
+            },
+            ratatui::layout::Rect::new(
+                area.x + (area.width - width) / 2,
+                area.y + (area.height - height) / 2,
+                width,
+                height,
+            ),
+        );
+    }
+
+    fn draw_import_trial(&self, f: &mut Frame, trial: &crate::app::sandbox::ImportTrial) {
+        let title = format!("Trial: {}", trial.name);
+        let rows = trial.rows();
+        let hint = if trial.report.is_ok() {
+            "[y] Release into this world  [n] Discard"
+        } else {
+            "[n] Close"
+        };
+        let area = f.area();
+        let width = 70u16.min(area.width);
+        let height = (rows.len() as u16 + 6).min(area.height);
+        f.render_widget(
+            StartMenuWidget {
+                title: &title,
+                rows,
+                selected: usize::MAX,
+                hint,
+                error: None,
+            },
+            ratatui::layout::Rect::new(
+                area.x + (area.width - width) / 2,
+                area.y + (area.height - height) / 2,
+                width,
+                height,
+            ),
+        );
+    }
+
+    fn draw_pathogen_designer(
+        &self,
+        f: &mut Frame,
+        designer: &crate::app::pathogen_designer::PathogenDesigner,
+    ) {
+        use crate::app::pathogen_designer::DesignerStage;
+
+        let hint = match &designer.stage {
+            // Keep the world visible while the user picks a spot.
+            DesignerStage::Aiming => return,
+            DesignerStage::Editing => {
+                "[↑/↓] Select  [←/→] Change  [Enter] Release  [Esc] Close".to_string()
+            }
+            DesignerStage::Confirm(_) => "Release this pathogen? [y] Confirm  [n] Back".to_string(),
+        };
+        let mut rows: Vec<(String, bool)> = designer
+            .rows()
+            .into_iter()
+            .map(|(label, value)| (format!("{:<13} {}", label, value), true))
+            .collect();
+        let selected = if let DesignerStage::Confirm(site) = &designer.stage {
+            rows.push((format!("Target: {}", site.describe()), true));
+            rows.len() - 1
+        } else {
+            designer.selected
+        };
+
+        let area = f.area();
+        let width = 52u16.min(area.width);
+        let height = 14u16.min(area.height);
+        f.render_widget(
+            StartMenuWidget {
+                title: "Pathogen Designer",
+                rows,
+                selected,
+                hint: &hint,
+                error: designer.error.as_deref(),
+            },
+            ratatui::layout::Rect::new(
+                area.x + (area.width - width) / 2,
+                area.y + (area.height - height) / 2,
+                width,
+                height,
+            ),
+        );
+    }
+
+    fn draw_start_menu(&self, f: &mut Frame, menu: &crate::app::start_menu::StartMenu) {
+        use crate::app::start_menu::{MenuItem, MenuPage};
+
+        let (title, rows, hint) = match menu.page {
+            MenuPage::Main => (
+                "PRIMORDIUM",
+                MenuItem::ALL
+                    .iter()
+                    .map(|item| (item.label().to_string(), menu.is_enabled(*item)))
+                    .collect::<Vec<_>>(),
+                "[↑/↓] Select  [Enter] Choose  [Esc] Skip",
+            ),
+            MenuPage::NewWorld => (
+                "New World",
+                menu.settings
+                    .rows()
+                    .into_iter()
+                    .map(|(label, value)| (format!("{:<10} {}", label, value), true))
+                    .collect(),
+                "[←/→] Change  [0-9] Seed  [Enter] Create  [Esc] Back",
+            ),
+            MenuPage::RelayUrl => (
+                "Connect to Relay",
+                vec![(format!("{}_", menu.relay_url), true)],
+                "Type the relay URL  [Enter] Connect  [Esc] Back",
+            ),
+        };
+
+        let area = f.area();
+        let records = self.profile.as_ref().filter(|p| p.profile.runs > 1);
+        let menu_width = 56u16.min(area.width);
+        let records_width = if records.is_some() && area.width >= menu_width + 44 {
+            44
+        } else {
+            0
+        };
+        let height = 20u16.min(area.height);
+        let x = area.x + (area.width - menu_width - records_width) / 2;
+        let y = area.y + (area.height - height) / 2;
+
+        f.render_widget(
+            StartMenuWidget {
+                title,
+                rows,
+                selected: menu.selected,
+                hint,
+                error: menu.error.as_deref(),
+            },
+            ratatui::layout::Rect::new(x, y, menu_width, height),
+        );
+
+        if let Some(session) = records.filter(|_| records_width > 0) {
+            let profile = &session.profile;
+            f.render_widget(
+                ProfileRecordsWidget {
+                    runs: profile.runs,
+                    total_ticks: profile.total_ticks,
+                    max_population: profile.max_population,
+                    longest_lineage: profile
+                        .longest_lineage
+                        .as_ref()
+                        .map(|l| (l.name.as_str(), l.ticks)),
+                    achievements: primordium_io::profile::Achievement::ALL
+                        .iter()
+                        .map(|a| (a.title(), profile.has(*a)))
+                        .collect(),
+                },
+                ratatui::layout::Rect::new(x + menu_width, y, records_width, height),
+            );
+        }
     }
 
     fn get_climate_bg_color(&self) -> Color {
@@ -266,36 +883,12 @@ impl App {
             Color::Rgb(5, 10, 5)
         }
     }
-
-    /// Get layout constraints based on current UI mode
-    fn get_layout_constraints(&self) -> Vec<Constraint> {
-        match self.ui_mode {
-            UiMode::Immersive => vec![
-                Constraint::Length(1), // Status
-                Constraint::Length(0), // Sparklines (hidden)
-                Constraint::Min(0),    // World (max)
-                Constraint::Length(0), // Chronicle (hidden)
-            ],
-            UiMode::Standard => vec![
-                Constraint::Length(6), // Status
-                Constraint::Length(4), // Sparklines
-                Constraint::Min(0),    // World
-                Constraint::Length(7), // Chronicle
-            ],
-            UiMode::Expert => vec![
-                Constraint::Length(2), // Status (compact)
-                Constraint::Length(2), // Sparklines (minimal)
-                Constraint::Min(0),    // World
-                Constraint::Length(0), // Chronicle (hidden)
-            ],
-        }
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::config::AppConfig;
+    use crate::model::config::{AppConfig, ThreadingConfig};
     use crate::model::environment::Environment;
     use crate::model::world::World;
     use ratatui::backend::TestBackend;
@@ -322,6 +915,13 @@ mod tests {
             sys: System::new_all(),
             env: Environment::default(),
             cpu_history: VecDeque::new(),
+            thread_pools: std::sync::Arc::new(
+                crate::model::threading::ThreadPools::build(&ThreadingConfig {
+                    sim_threads: 1,
+                    ..Default::default()
+                })
+                .unwrap(),
+            ),
             pop_history: VecDeque::new(),
             o2_history: VecDeque::new(),
             show_brain: false,
@@ -331,30 +931,47 @@ mod tests {
             social_brush: 0,
             is_social_brush: false,
             show_ancestry: false,
+            show_energy_ledger: false,
+            show_route_trail: false,
+            show_palette: true,
+            show_ecs_report: false,
+            show_calendar: false,
+            show_forecast: false,
+            show_perception: false,
             last_climate: None,
             last_anchor_time: Instant::now(),
             anchor_interval: std::time::Duration::from_secs(3600),
             is_anchoring: false,
-            ui_mode: UiMode::default(),
+            layout_preset: 0,
+            layout_editor: None,
             screensaver: false,
+            screensaver_state: Default::default(),
+            attract: None,
             cinematic_mode: false,
             show_help: false,
             show_legend: true,
             help_tab: 0,
             show_archeology: false,
             auto_play_history: false,
-            archeology_snapshots: Vec::new(),
+            archeology: Default::default(),
             archeology_index: 0,
             selected_fossil_index: 0,
-            onboarding_step: None,
+            fossil_filter: Default::default(),
+            tutorial: None,
+            profile: None,
+            interest: None,
+            start_menu: None,
             view_mode: 0,
+            pheromone_channel: 0,
             last_world_rect: ratatui::layout::Rect::default(),
             last_sidebar_rect: ratatui::layout::Rect::default(),
             gene_editor_offset: 0,
             event_log: VecDeque::new(),
+            narration_feed: Default::default(),
             network_state: primordium_net::NetworkState::default(),
             latest_snapshot: None,
             network: None,
+            migration_backoff: None,
             hof_query_rx: None,
             cached_hall_of_fame: Vec::new(),
             // Phase 70: Registry
@@ -365,6 +982,26 @@ mod tests {
             cached_registry_genomes: Vec::new(),
             cached_registry_seeds: Vec::new(),
             registry_selected_index: 0,
+            registry_rx: None,
+            cached_registry_submissions: Vec::new(),
+            registry_submit_rx: None,
+            import_trial_rx: None,
+            import_trial: None,
+            pathogen_designer: None,
+            bookmark_note: None,
+            show_bookmarks: false,
+            bookmark_index: 0,
+            narrations: VecDeque::new(),
+            show_narrations: false,
+            narration_index: 0,
+            narration_expanded: false,
+            show_phylogeny: false,
+            phylogeny_index: 0,
+            phylogeny_collapsed: std::collections::HashSet::new(),
+            log_quota_warned: false,
+            tag_prompt: None,
+            tag_filter: None,
+            glyphs: Default::default(),
             input_log: Vec::new(),
             replay_queue: VecDeque::new(),
             replay_mode: false,
@@ -463,6 +1100,26 @@ mod tests {
         assert!(help_found);
     }
 
+    #[tokio::test]
+    async fn test_draw_start_menu_with_records() {
+        let mut app = create_test_app();
+        app.profile = crate::app::profile::ProfileSession::open(":memory:", &app.world);
+        if let Some(session) = &mut app.profile {
+            session.profile.runs = 2;
+        }
+        app.open_start_menu();
+        let backend = TestBackend::new(120, 50);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        terminal.draw(|f| app.draw(f)).unwrap();
+
+        let content = format!("{:?}", terminal.backend().buffer());
+        assert!(content.contains("New world"));
+        assert!(content.contains("Marketplace"));
+        assert!(content.contains("Your Records"));
+        assert!(content.contains("Achievements 0/6"));
+    }
+
     #[tokio::test]
     async fn test_draw_sidebar_brain() {
         let mut app = create_test_app();
@@ -0,0 +1,120 @@
+//! Trial imports of marketplace genomes.
+//!
+//! `I` on the marketplace's Genomes tab runs the selected genome through a
+//! [`sandbox::trial`] on a background thread, so a genome that would wreck
+//! the ecosystem shows itself in a throwaway world first. The report is then
+//! shown until the user releases the genome into the world or discards it.
+
+use ratatui::style::Color;
+
+use crate::app::state::App;
+use crate::model::sandbox::{self, TrialReport};
+use crate::model::world::scenario::ScenarioEvent;
+
+/// A finished trial waiting for the user's decision.
+#[derive(Debug, Clone)]
+pub struct ImportTrial {
+    pub name: String,
+    pub genotype: String,
+    pub report: Result<TrialReport, String>,
+}
+
+impl ImportTrial {
+    /// Rows of the confirmation panel: the figures, then any warnings.
+    pub fn rows(&self) -> Vec<(String, bool)> {
+        match &self.report {
+            Ok(report) => {
+                let mut rows: Vec<(String, bool)> =
+                    report.summary().into_iter().map(|l| (l, true)).collect();
+                rows.push((String::new(), false));
+                let warnings = report.warnings();
+                if warnings.is_empty() {
+                    rows.push(("No warning signs".to_string(), false));
+                }
+                rows.extend(warnings.into_iter().map(|w| (format!("⚠ {}", w), true)));
+                rows
+            }
+            Err(e) => vec![(format!("Trial failed: {}", e), true)],
+        }
+    }
+}
+
+impl App {
+    /// Starts a trial of the genome selected on the Genomes tab.
+    pub fn trial_selected_genome(&mut self) {
+        if self.import_trial_rx.is_some() || self.import_trial.is_some() {
+            return;
+        }
+        let Some(record) = self
+            .cached_registry_genomes
+            .get(self.registry_selected_index)
+        else {
+            return;
+        };
+        let (name, genotype) = (record.name.clone(), record.genotype.clone());
+        let config = self.config.clone();
+        self.event_log.push_back((
+            format!(
+                "Sandbox: Trialling {} for {} ticks...",
+                name, config.sandbox.ticks
+            ),
+            Color::Cyan,
+        ));
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.import_trial_rx = Some(rx);
+        std::thread::spawn(move || {
+            let report = sandbox::trial(&genotype, &config).map_err(|e| format!("{:#}", e));
+            let _ = tx.send(ImportTrial {
+                name,
+                genotype,
+                report,
+            });
+        });
+    }
+
+    /// Picks up a finished trial for the user to decide on.
+    pub fn poll_import_trial(&mut self) {
+        let Some(Ok(trial)) = self.import_trial_rx.as_ref().map(|rx| rx.try_recv()) else {
+            return;
+        };
+        self.import_trial_rx = None;
+        self.import_trial = Some(trial);
+        self.dirty = true;
+    }
+
+    /// Spawns the trialled genome's founders into the world.
+    pub fn release_import(&mut self) {
+        let Some(trial) = self.import_trial.take() else {
+            return;
+        };
+        if trial.report.is_err() {
+            return;
+        }
+        let event = ScenarioEvent::Spawn {
+            dna: trial.genotype,
+            count: self.config.sandbox.founders,
+            x: None,
+            y: None,
+        };
+        let (text, color) = match self.world.apply_scenario_event(&mut self.env, &event) {
+            Ok(outcome) => (
+                format!("Sandbox: Released {}: {}", trial.name, outcome),
+                Color::Green,
+            ),
+            Err(e) => (
+                format!("Sandbox: Releasing {} failed: {}", trial.name, e),
+                Color::Red,
+            ),
+        };
+        self.event_log.push_back((text, color));
+    }
+
+    pub fn discard_import(&mut self) {
+        if let Some(trial) = self.import_trial.take() {
+            self.event_log.push_back((
+                format!("Sandbox: Discarded {}", trial.name),
+                Color::DarkGray,
+            ));
+        }
+    }
+}
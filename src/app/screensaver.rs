@@ -0,0 +1,148 @@
+use anyhow::Result;
+use rand::Rng;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::app::state::App;
+use crate::model::config::{AppConfig, ScreensaverConfig};
+use crate::model::gallery::SeedGallery;
+use crate::model::world::World;
+
+/// Progress of an autonomous screensaver session.
+#[derive(Debug, Clone)]
+pub struct ScreensaverState {
+    /// Index into `screensaver.seeds` of the next curated run.
+    pub next_seed: usize,
+    /// Seed of the run currently on screen.
+    pub current_seed: Option<u64>,
+    /// Index into `screensaver.views` of the current view.
+    pub view_index: usize,
+    pub last_rotation: Instant,
+    pub runs: u32,
+}
+
+impl Default for ScreensaverState {
+    fn default() -> Self {
+        Self {
+            next_seed: 0,
+            current_seed: None,
+            view_index: 0,
+            last_rotation: Instant::now(),
+            runs: 0,
+        }
+    }
+}
+
+/// Whether the current screensaver run should be replaced by a fresh one.
+pub fn needs_restart(config: &ScreensaverConfig, tick: u64, population: usize) -> bool {
+    population == 0 || (config.restart_after_ticks > 0 && tick >= config.restart_after_ticks)
+}
+
+impl App {
+    /// Starts the app in autonomous screensaver mode on the first curated seed.
+    ///
+    /// `config.toml` is read but never created, and no save file is loaded.
+    pub fn new_screensaver() -> Result<Self> {
+        let config = std::fs::read_to_string("config.toml")
+            .ok()
+            .and_then(|content| AppConfig::from_toml(&content).ok())
+            .unwrap_or_default();
+        let world = World::new_ephemeral(0, config.clone())?;
+        let mut app = Self::with_world(config, world)?;
+        app.screensaver = true;
+        app.tutorial = None;
+        app.restart_screensaver_run()?;
+        Ok(app)
+    }
+
+    /// True when the screensaver is running with file writes disabled.
+    pub fn writes_suppressed(&self) -> bool {
+        self.screensaver && self.config.screensaver.suppress_file_writes
+    }
+
+    /// Replaces the world with a fresh run seeded from the curated list, or
+    /// for a `gallery_share` of runs from the seed gallery.
+    pub fn restart_screensaver_run(&mut self) -> Result<()> {
+        let saver = &self.config.screensaver;
+        let mut rng = rand::thread_rng();
+        let gallery_seed = rng
+            .gen_bool(saver.gallery_share)
+            .then(|| SeedGallery::load(Path::new(&self.config.gallery.path)).ok())
+            .flatten()
+            .and_then(|gallery| gallery.pick(&mut rng).map(|entry| entry.seed));
+        let curated = gallery_seed.is_none();
+        let seed = gallery_seed
+            .unwrap_or_else(|| saver.seeds[self.screensaver_state.next_seed % saver.seeds.len()]);
+        let mut config = self.config.clone();
+        config.world.seed = Some(seed);
+
+        let population = config.world.initial_population;
+        self.world = if saver.suppress_file_writes {
+            World::new_ephemeral(population, config)?
+        } else {
+            World::new(population, config)?
+        };
+        self.tick_count = 0;
+        self.selected_entity = None;
+        self.latest_snapshot = Some(self.world.create_snapshot(None));
+
+        let state = &mut self.screensaver_state;
+        if curated {
+            state.next_seed = (state.next_seed + 1) % self.config.screensaver.seeds.len();
+        }
+        state.current_seed = Some(seed);
+        state.runs += 1;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Switches to the next view in the rotation.
+    pub fn rotate_screensaver_view(&mut self) {
+        let views = &self.config.screensaver.views;
+        let state = &mut self.screensaver_state;
+        state.view_index = (state.view_index + 1) % views.len();
+        state.last_rotation = Instant::now();
+        self.view_mode = views[state.view_index];
+        self.dirty = true;
+    }
+
+    /// Per-frame screensaver housekeeping: restarts finished runs and rotates
+    /// the view on its timer.
+    pub fn update_screensaver(&mut self) -> Result<()> {
+        let saver = &self.config.screensaver;
+        if needs_restart(saver, self.world.tick, self.world.get_population_count()) {
+            self.restart_screensaver_run()?;
+        }
+
+        let saver = &self.config.screensaver;
+        if saver.view_rotation_secs > 0
+            && self.screensaver_state.last_rotation.elapsed()
+                >= Duration::from_secs(saver.view_rotation_secs)
+        {
+            self.rotate_screensaver_view();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_on_extinction_or_tick_limit() {
+        let config = ScreensaverConfig {
+            restart_after_ticks: 100,
+            ..Default::default()
+        };
+        assert!(!needs_restart(&config, 50, 10));
+        assert!(needs_restart(&config, 50, 0));
+        assert!(needs_restart(&config, 100, 10));
+
+        let config = ScreensaverConfig {
+            restart_after_ticks: 0,
+            ..Default::default()
+        };
+        assert!(!needs_restart(&config, 1_000_000, 10));
+    }
+}
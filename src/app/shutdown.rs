@@ -40,6 +40,32 @@ impl ShutdownManager {
         tracing::info!("Shutdown requested");
     }
 
+    /// Requests shutdown once the process receives Ctrl+C or SIGTERM.
+    pub fn listen_for_signals(&self) {
+        let requested = self.shutdown_requested.clone();
+        tokio::spawn(async move {
+            #[cfg(unix)]
+            {
+                use tokio::signal::unix::{signal, SignalKind};
+                match signal(SignalKind::terminate()) {
+                    Ok(mut terminate) => {
+                        tokio::select! {
+                            _ = tokio::signal::ctrl_c() => {}
+                            _ = terminate.recv() => {}
+                        }
+                    }
+                    Err(_) => {
+                        tokio::signal::ctrl_c().await.ok();
+                    }
+                }
+            }
+            #[cfg(not(unix))]
+            tokio::signal::ctrl_c().await.ok();
+            tracing::info!("Termination signal received, initiating graceful shutdown...");
+            requested.store(true, Ordering::SeqCst);
+        });
+    }
+
     /// Checks if shutdown has been requested.
     pub fn is_shutdown_requested(&self) -> bool {
         self.shutdown_requested.load(Ordering::SeqCst)
@@ -0,0 +1,453 @@
+//! Start menu and new-world wizard shown when the TUI launches.
+//!
+//! The menu covers the common launch choices (continuing the last save,
+//! creating a world, replaying input, joining a relay, browsing the
+//! marketplace) so they no longer require editing `config.toml` or passing
+//! CLI flags.
+
+use anyhow::Result;
+use ratatui::style::Color;
+
+use crate::app::state::App;
+use crate::model::config::{AppConfig, GameMode};
+use crate::model::world::World;
+
+pub const SAVE_FILE: &str = "save.json";
+pub const REPLAY_FILE: &str = "logs/latest_replay.json";
+pub const DEFAULT_RELAY_URL: &str = "ws://127.0.0.1:3000/ws";
+
+/// Longest seed or URL accepted by the text fields.
+const MAX_INPUT_LEN: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuItem {
+    Continue,
+    NewWorld,
+    LoadReplay,
+    ConnectRelay,
+    Marketplace,
+    Quit,
+}
+
+impl MenuItem {
+    pub const ALL: [MenuItem; 6] = [
+        MenuItem::Continue,
+        MenuItem::NewWorld,
+        MenuItem::LoadReplay,
+        MenuItem::ConnectRelay,
+        MenuItem::Marketplace,
+        MenuItem::Quit,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MenuItem::Continue => "Continue last save",
+            MenuItem::NewWorld => "New world",
+            MenuItem::LoadReplay => "Load replay",
+            MenuItem::ConnectRelay => "Connect to relay",
+            MenuItem::Marketplace => "Marketplace",
+            MenuItem::Quit => "Quit",
+        }
+    }
+}
+
+/// Tuned starting conditions offered by the wizard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldPreset {
+    Balanced,
+    Lush,
+    Harsh,
+    Sparse,
+}
+
+impl WorldPreset {
+    pub const ALL: [WorldPreset; 4] = [
+        WorldPreset::Balanced,
+        WorldPreset::Lush,
+        WorldPreset::Harsh,
+        WorldPreset::Sparse,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WorldPreset::Balanced => "Balanced",
+            WorldPreset::Lush => "Lush (abundant food, no disasters)",
+            WorldPreset::Harsh => "Harsh (scarce food, frequent disasters)",
+            WorldPreset::Sparse => "Sparse (few founders)",
+        }
+    }
+
+    pub fn apply(self, config: &mut AppConfig) {
+        let world = &mut config.world;
+        match self {
+            WorldPreset::Balanced => {}
+            WorldPreset::Lush => {
+                world.initial_food *= 3;
+                world.max_food *= 3;
+                world.disaster_chance = 0.0;
+            }
+            WorldPreset::Harsh => {
+                world.initial_food /= 2;
+                world.max_food /= 2;
+                world.disaster_chance *= 5.0;
+            }
+            WorldPreset::Sparse => {
+                world.initial_population = (world.initial_population / 5).max(10);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl MapSize {
+    pub const ALL: [MapSize; 3] = [MapSize::Small, MapSize::Medium, MapSize::Large];
+
+    pub fn dimensions(self) -> (u16, u16) {
+        match self {
+            MapSize::Small => (60, 30),
+            MapSize::Medium => (100, 50),
+            MapSize::Large => (160, 80),
+        }
+    }
+
+    pub fn label(self) -> String {
+        let (w, h) = self.dimensions();
+        let name = match self {
+            MapSize::Small => "Small",
+            MapSize::Medium => "Medium",
+            MapSize::Large => "Large",
+        };
+        format!("{} ({}x{})", name, w, h)
+    }
+}
+
+const GAME_MODES: [GameMode; 3] = [
+    GameMode::Standard,
+    GameMode::Cooperative,
+    GameMode::BattleRoyale,
+];
+
+fn game_mode_label(mode: GameMode) -> &'static str {
+    match mode {
+        GameMode::Standard => "Standard",
+        GameMode::Cooperative => "Cooperative",
+        GameMode::BattleRoyale => "Battle Royale",
+    }
+}
+
+/// Rows of the new-world wizard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardField {
+    Preset,
+    Seed,
+    MapSize,
+    GameMode,
+    Create,
+}
+
+impl WizardField {
+    pub const ALL: [WizardField; 5] = [
+        WizardField::Preset,
+        WizardField::Seed,
+        WizardField::MapSize,
+        WizardField::GameMode,
+        WizardField::Create,
+    ];
+}
+
+/// Choices made in the new-world wizard.
+#[derive(Debug, Clone, Default)]
+pub struct NewWorldSettings {
+    pub preset: usize,
+    /// Seed as typed; empty means random.
+    pub seed: String,
+    pub map_size: usize,
+    pub game_mode: usize,
+}
+
+impl NewWorldSettings {
+    fn new() -> Self {
+        Self {
+            map_size: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Applies the choices on top of `base`.
+    pub fn config(&self, base: &AppConfig) -> Result<AppConfig> {
+        let mut config = base.clone();
+        WorldPreset::ALL[self.preset].apply(&mut config);
+        let (width, height) = MapSize::ALL[self.map_size].dimensions();
+        config.world.width = width;
+        config.world.height = height;
+        config.game_mode = GAME_MODES[self.game_mode];
+        config.world.seed = if self.seed.is_empty() {
+            None
+        } else {
+            Some(
+                self.seed
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Seed must be a whole number"))?,
+            )
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// `(label, value)` pairs for display, one per [`WizardField`].
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        WizardField::ALL
+            .iter()
+            .map(|field| match field {
+                WizardField::Preset => ("Preset", WorldPreset::ALL[self.preset].label().into()),
+                WizardField::Seed => (
+                    "Seed",
+                    if self.seed.is_empty() {
+                        "random".to_string()
+                    } else {
+                        self.seed.clone()
+                    },
+                ),
+                WizardField::MapSize => ("Map size", MapSize::ALL[self.map_size].label()),
+                WizardField::GameMode => (
+                    "Game mode",
+                    game_mode_label(GAME_MODES[self.game_mode]).into(),
+                ),
+                WizardField::Create => ("", "[ Create world ]".into()),
+            })
+            .collect()
+    }
+
+    /// Cycles the option under `field` by `step`.
+    pub fn cycle(&mut self, field: WizardField, step: isize) {
+        let (value, len) = match field {
+            WizardField::Preset => (&mut self.preset, WorldPreset::ALL.len()),
+            WizardField::MapSize => (&mut self.map_size, MapSize::ALL.len()),
+            WizardField::GameMode => (&mut self.game_mode, GAME_MODES.len()),
+            WizardField::Seed | WizardField::Create => return,
+        };
+        *value = (*value as isize + step).rem_euclid(len as isize) as usize;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuPage {
+    Main,
+    NewWorld,
+    RelayUrl,
+}
+
+/// Navigation state of the start menu.
+#[derive(Debug, Clone)]
+pub struct StartMenu {
+    pub page: MenuPage,
+    /// Selected row on the current page.
+    pub selected: usize,
+    pub has_save: bool,
+    pub settings: NewWorldSettings,
+    pub relay_url: String,
+    /// Last failure, shown under the menu.
+    pub error: Option<String>,
+}
+
+impl StartMenu {
+    pub fn new(has_save: bool) -> Self {
+        Self {
+            page: MenuPage::Main,
+            selected: if has_save { 0 } else { 1 },
+            has_save,
+            settings: NewWorldSettings::new(),
+            relay_url: DEFAULT_RELAY_URL.to_string(),
+            error: None,
+        }
+    }
+
+    pub fn is_enabled(&self, item: MenuItem) -> bool {
+        item != MenuItem::Continue || self.has_save
+    }
+
+    /// Number of rows on the current page.
+    pub fn rows(&self) -> usize {
+        match self.page {
+            MenuPage::Main => MenuItem::ALL.len(),
+            MenuPage::NewWorld => WizardField::ALL.len(),
+            MenuPage::RelayUrl => 1,
+        }
+    }
+
+    /// Moves the selection, skipping disabled main-menu entries.
+    pub fn move_selection(&mut self, step: isize) {
+        let rows = self.rows() as isize;
+        let mut next = self.selected as isize;
+        loop {
+            next = (next + step).rem_euclid(rows);
+            if self.page != MenuPage::Main || self.is_enabled(MenuItem::ALL[next as usize]) {
+                break;
+            }
+        }
+        self.selected = next as usize;
+    }
+
+    pub fn open(&mut self, page: MenuPage) {
+        self.page = page;
+        self.selected = 0;
+        self.error = None;
+    }
+
+    /// Appends typed text to the focused text field, if any.
+    pub fn type_char(&mut self, c: char) {
+        match self.page {
+            MenuPage::NewWorld
+                if WizardField::ALL[self.selected] == WizardField::Seed
+                    && c.is_ascii_digit()
+                    && self.settings.seed.len() < 20 =>
+            {
+                self.settings.seed.push(c);
+            }
+            MenuPage::RelayUrl if !c.is_control() && self.relay_url.len() < MAX_INPUT_LEN => {
+                self.relay_url.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        match self.page {
+            MenuPage::NewWorld if WizardField::ALL[self.selected] == WizardField::Seed => {
+                self.settings.seed.pop();
+            }
+            MenuPage::RelayUrl => {
+                self.relay_url.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+impl App {
+    /// Shows the start menu; the world stays paused until a choice is made.
+    pub fn open_start_menu(&mut self) {
+        self.start_menu = Some(StartMenu::new(std::path::Path::new(SAVE_FILE).exists()));
+    }
+
+    /// Carries out the selected main-menu entry.
+    pub fn activate_menu_item(&mut self, item: MenuItem) {
+        match item {
+            MenuItem::Continue => match self.load_state() {
+                Ok(()) => self.close_start_menu("World state LOADED from save.json"),
+                Err(e) => self.set_menu_error(format!("Could not load save: {}", e)),
+            },
+            MenuItem::NewWorld => self.open_menu_page(MenuPage::NewWorld),
+            MenuItem::ConnectRelay => self.open_menu_page(MenuPage::RelayUrl),
+            MenuItem::LoadReplay => match self.load_replay(REPLAY_FILE) {
+                Ok(()) => self.close_start_menu("Replay STARTED from logs/latest_replay.json"),
+                Err(e) => self.set_menu_error(format!("No replay at {}: {}", REPLAY_FILE, e)),
+            },
+            MenuItem::Marketplace => {
+                self.start_menu = None;
+                self.open_marketplace();
+            }
+            MenuItem::Quit => self.running = false,
+        }
+    }
+
+    /// Builds a fresh world from the wizard settings and leaves the menu.
+    pub fn create_world_from_menu(&mut self) {
+        let Some(menu) = &self.start_menu else {
+            return;
+        };
+        let result = menu.settings.config(&self.config).and_then(|config| {
+            let world = World::new(config.world.initial_population, config.clone())?;
+            Ok((config, world))
+        });
+        match result {
+            Ok((config, world)) => {
+                self.world = world;
+                self.config = config;
+                self.tick_count = 0;
+                self.selected_entity = None;
+                self.latest_snapshot = Some(self.world.create_snapshot(None));
+                self.close_start_menu("New world created");
+            }
+            Err(e) => self.set_menu_error(e.to_string()),
+        }
+    }
+
+    /// Connects to the relay URL typed in the menu.
+    pub fn connect_from_menu(&mut self) {
+        let Some(menu) = &self.start_menu else {
+            return;
+        };
+        let url = menu.relay_url.trim().to_string();
+        if url.is_empty() {
+            self.set_menu_error("Enter a relay URL".to_string());
+            return;
+        }
+        self.connect(&url);
+        self.close_start_menu(&format!("Connecting to relay {}", url));
+    }
+
+    fn open_menu_page(&mut self, page: MenuPage) {
+        if let Some(menu) = &mut self.start_menu {
+            menu.open(page);
+        }
+    }
+
+    fn set_menu_error(&mut self, error: String) {
+        if let Some(menu) = &mut self.start_menu {
+            menu.error = Some(error);
+        }
+    }
+
+    fn close_start_menu(&mut self, message: &str) {
+        self.start_menu = None;
+        self.event_log
+            .push_back((message.to_string(), Color::Green));
+        self.dirty = true;
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wizard_settings_build_config() {
+        let mut settings = NewWorldSettings::new();
+        settings.cycle(WizardField::Preset, 1);
+        settings.cycle(WizardField::MapSize, 1);
+        settings.cycle(WizardField::GameMode, -1);
+        settings.seed = "1234".to_string();
+
+        let base = AppConfig::default();
+        let config = settings.config(&base).unwrap();
+        assert_eq!((config.world.width, config.world.height), (160, 80));
+        assert_eq!(config.world.seed, Some(1234));
+        assert_eq!(config.world.max_food, base.world.max_food * 3);
+        assert!(matches!(config.game_mode, GameMode::BattleRoyale));
+
+        settings.seed = "12a".to_string();
+        assert!(settings.config(&base).is_err());
+    }
+
+    #[test]
+    fn test_menu_skips_continue_without_save() {
+        let mut menu = StartMenu::new(false);
+        assert_eq!(MenuItem::ALL[menu.selected], MenuItem::NewWorld);
+        menu.move_selection(-1);
+        assert_eq!(MenuItem::ALL[menu.selected], MenuItem::Quit);
+        menu.move_selection(1);
+        assert_eq!(MenuItem::ALL[menu.selected], MenuItem::NewWorld);
+
+        menu.open(MenuPage::NewWorld);
+        menu.move_selection(1);
+        menu.type_char('7');
+        menu.type_char('x');
+        assert_eq!(menu.settings.seed, "7");
+    }
+}
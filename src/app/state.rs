@@ -11,28 +11,33 @@ use uuid::Uuid;
 
 use crate::model::config::AppConfig;
 use crate::model::environment::{ClimateState, Environment};
+use crate::model::glyph::GlyphTheme;
 use crate::model::terrain::TerrainType;
+use crate::model::threading::ThreadPools;
 use crate::model::world::World;
 use primordium_data::GeneType;
 
-/// UI Display Mode - Controls information density and layout
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
-pub enum UiMode {
-    /// Immersive: Maximized world view, minimal UI
-    Immersive,
-    /// Standard: Balanced information and viewport
-    #[default]
-    Standard,
-    /// Expert: Full dashboard with semantic blocks
-    Expert,
-}
-
 #[derive(Serialize, Deserialize, Clone)]
 pub struct InputEvent {
     pub tick: u64,
     pub event: Event,
 }
 
+/// An input trace and the run it was recorded in.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InputTrace {
+    Stamped {
+        provenance: Option<primordium_data::RunManifest>,
+        events: Vec<InputEvent>,
+        /// Disasters that struck, replayed at the same ticks.
+        #[serde(default)]
+        disasters: Vec<primordium_data::DisasterRecord>,
+    },
+    /// Traces from before provenance was recorded.
+    Bare(Vec<InputEvent>),
+}
+
 pub struct App {
     pub running: bool,
     pub paused: bool,
@@ -50,6 +55,8 @@ pub struct App {
     pub sys: System,
     pub env: Environment,
     pub cpu_history: VecDeque<u64>,
+    // Simulation and background worker pools
+    pub thread_pools: Arc<ThreadPools>,
     // Population History
     pub pop_history: VecDeque<u64>,
     // NEW: Phase 56 - Atmospheric History
@@ -64,6 +71,20 @@ pub struct App {
     pub is_social_brush: bool, // NEW: Toggle between Terrain and Social brush
     // Phase 34: Ancestry View
     pub show_ancestry: bool,
+    // Energy ledger of the selected entity
+    pub show_energy_ledger: bool,
+    // Route trail of the selected entity
+    pub show_route_trail: bool,
+    // Climate, era and daylight tint of the world view
+    pub show_palette: bool,
+    // ECS archetype/component debug panel
+    pub show_ecs_report: bool,
+    // Calendar of upcoming and recent scheduled events
+    pub show_calendar: bool,
+    // Population and food forecast panel
+    pub show_forecast: bool,
+    // Perception overlay of the selected entity
+    pub show_perception: bool,
     // Last climate state for shift logging
     pub last_climate: Option<ClimateState>,
     // Blockchain Anchoring
@@ -71,8 +92,13 @@ pub struct App {
     pub anchor_interval: Duration,
     pub is_anchoring: bool,
     // Modes
-    pub ui_mode: UiMode,
+    /// Index into `config.layout.presets`.
+    pub layout_preset: usize,
+    /// Selected row of the open layout editor.
+    pub layout_editor: Option<usize>,
     pub screensaver: bool,
+    pub screensaver_state: crate::app::screensaver::ScreensaverState,
+    pub attract: Option<crate::app::attract::AttractState>,
     pub cinematic_mode: bool,
     pub show_help: bool,
     pub show_legend: bool,
@@ -80,21 +106,30 @@ pub struct App {
     // Phase 40: Archeology View
     pub show_archeology: bool,
     pub auto_play_history: bool, // NEW: Replay functionality
-    pub archeology_snapshots: Vec<(u64, primordium_data::PopulationStats)>,
+    pub archeology: crate::app::archeology::ArcheologyTimeline,
     pub archeology_index: usize,
-    pub selected_fossil_index: usize, // NEW
-    pub onboarding_step: Option<u8>,  // None=done, Some(0-2)=onboarding screens
+    pub selected_fossil_index: usize,                         // NEW
+    pub fossil_filter: crate::app::archeology::FossilFilter, // Era filter and ranking of the fossil browser
+    pub tutorial: Option<crate::app::tutorial::Tutorial>, // Running lesson, onboarding on first launch
+    pub profile: Option<crate::app::profile::ProfileSession>, // Cumulative stats across runs
+    pub interest: Option<crate::model::gallery::InterestDetector>, // Scores the run for the seed gallery
+    pub start_menu: Option<crate::app::start_menu::StartMenu>, // Shown at launch until a choice is made
     pub view_mode: u8,
+    pub pheromone_channel: usize, // Channel drawn by the pheromone view
     // Layout tracking
     pub last_world_rect: Rect,
     pub last_sidebar_rect: Rect,
     pub gene_editor_offset: u16, // NEW: Phase 59
     // Live Data
     pub event_log: VecDeque<(String, Color)>,
+    /// Scribe narrations for the event log.
+    pub narration_feed: crate::model::observer::NarrationFeed,
 
     pub network_state: primordium_net::NetworkState,
     pub latest_snapshot: Option<Arc<crate::model::snapshot::WorldSnapshot>>,
     pub network: Option<crate::client::manager::NetworkManager>,
+    /// No migrants are sent before this, after the relay rate limited us.
+    pub migration_backoff: Option<Instant>,
 
     pub hof_query_rx: Option<std::sync::mpsc::Receiver<Vec<(Uuid, u32, bool)>>>,
     pub cached_hall_of_fame: Vec<(Uuid, u32, bool)>,
@@ -106,6 +141,36 @@ pub struct App {
     pub cached_registry_genomes: Vec<primordium_tui::views::registry::GenomeRecord>,
     pub cached_registry_seeds: Vec<primordium_tui::views::registry::SeedRecord>,
     pub registry_selected_index: usize,
+    pub registry_rx: Option<std::sync::mpsc::Receiver<crate::client::registry::RegistryFetch>>,
+    pub cached_registry_submissions: Vec<primordium_tui::views::registry::SubmissionEntry>,
+    pub registry_submit_rx:
+        Option<std::sync::mpsc::Receiver<crate::app::marketplace::SubmitResult>>,
+    pub import_trial_rx: Option<std::sync::mpsc::Receiver<crate::app::sandbox::ImportTrial>>,
+    /// Finished marketplace genome trial awaiting release or discard.
+    pub import_trial: Option<crate::app::sandbox::ImportTrial>,
+    pub pathogen_designer: Option<crate::app::pathogen_designer::PathogenDesigner>,
+    /// Tick and note of a bookmark being typed.
+    pub bookmark_note: Option<(u64, String)>,
+    pub show_bookmarks: bool,
+    pub bookmark_index: usize,
+    /// Recent narrations, newest first, with the data behind them.
+    pub narrations: VecDeque<crate::app::narrations::NarrationEntry>,
+    pub show_narrations: bool,
+    pub narration_index: usize,
+    /// Whether the selected narration's references are shown.
+    pub narration_expanded: bool,
+    pub show_phylogeny: bool,
+    pub phylogeny_index: usize,
+    /// Lineages folded in the phylogeny browser.
+    pub phylogeny_collapsed: std::collections::HashSet<Uuid>,
+    /// Whether the logs directory is at its quota warning level.
+    pub log_quota_warned: bool,
+    /// Search bar or tag prompt being typed in.
+    pub tag_prompt: Option<(crate::app::tags::TagPrompt, String)>,
+    /// Tag highlighted in the world view.
+    pub tag_filter: Option<String>,
+    /// Glyphs organisms are drawn with, from `visual.glyph_theme`.
+    pub glyphs: GlyphTheme,
 
     pub input_log: Vec<InputEvent>,
     pub replay_queue: VecDeque<InputEvent>,
@@ -140,13 +205,16 @@ impl App {
     }
 
     pub fn new() -> Result<Self> {
-        let mut sys = System::new_all();
-        sys.refresh_all();
         let config = Self::load_config();
 
         let world = if std::path::Path::new("save.json").exists() {
-            match crate::model::persistence::load_world("save.json") {
-                Ok(w) => w,
+            match crate::model::persistence::load_world_adapted("save.json", &config) {
+                Ok((w, report)) => {
+                    for issue in &report.issues {
+                        tracing::warn!("Adapted save.json: {}", issue);
+                    }
+                    w
+                }
                 Err(e) => {
                     tracing::error!("Failed to load save file: {}", e);
                     World::new(config.world.initial_population, config.clone())?
@@ -156,6 +224,31 @@ impl App {
             World::new(config.world.initial_population, config.clone())?
         };
 
+        Self::with_world(config, world)
+    }
+
+    /// Starts a fresh world instead of resuming `save.json`, from `seed` if
+    /// given and otherwise from the configured one.
+    pub fn new_world(seed: Option<u64>) -> Result<Self> {
+        let mut config = Self::load_config();
+        if seed.is_some() {
+            config.world.seed = seed;
+        }
+        let world = World::new(config.world.initial_population, config.clone())?;
+        Self::with_world(config, world)
+    }
+
+    pub(crate) fn with_world(config: AppConfig, world: World) -> Result<Self> {
+        let mut sys = System::new_all();
+        sys.refresh_all();
+        let thread_pools = Arc::new(ThreadPools::build(&config.threading)?);
+        let pools = thread_pools.report();
+        tracing::info!(
+            sim_threads = pools.sim_threads,
+            io_threads = pools.io_threads,
+            "Thread pools ready"
+        );
+
         let latest_snapshot = Some(world.create_snapshot(None));
         let config_path = "config.toml".to_string();
         let config_last_modified = std::fs::metadata(&config_path)
@@ -165,6 +258,12 @@ impl App {
         let mut audio = crate::app::AudioSystem::new();
         audio.set_world_dimensions(world.width, world.height);
 
+        let glyphs = GlyphTheme::load(&config.visual.glyph_theme).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load {}: {}", config.visual.glyph_theme, e);
+            GlyphTheme::default()
+        });
+        let show_palette = config.visual.environment_palette;
+
         Ok(Self {
             running: true,
             paused: false,
@@ -180,6 +279,7 @@ impl App {
             sys,
             env: Environment::default(),
             cpu_history: VecDeque::from(vec![0; 60]),
+            thread_pools,
             pop_history: VecDeque::from(vec![0; 60]),
             o2_history: VecDeque::from(vec![0; 60]),
             show_brain: false,
@@ -189,34 +289,53 @@ impl App {
             social_brush: 0,
             is_social_brush: false,
             show_ancestry: false,
+            show_energy_ledger: false,
+            show_route_trail: false,
+            show_palette,
+            show_ecs_report: false,
+            show_calendar: false,
+            show_forecast: false,
+            show_perception: false,
             last_climate: None,
             last_anchor_time: Instant::now(),
             anchor_interval: Duration::from_secs(3600),
             is_anchoring: false,
-            ui_mode: UiMode::default(),
+            layout_preset: 0,
+            layout_editor: None,
             screensaver: false,
+            screensaver_state: Default::default(),
+            attract: None,
             cinematic_mode: false,
             show_help: false,
             show_legend: false,
             help_tab: 0,
             show_archeology: false,
             auto_play_history: false,
-            archeology_snapshots: Vec::new(),
+            archeology: Default::default(),
             archeology_index: 0,
             selected_fossil_index: 0,
-            onboarding_step: if std::path::Path::new(".primordium_onboarded").exists() {
+            fossil_filter: Default::default(),
+            tutorial: if std::path::Path::new(crate::app::tutorial::ONBOARDED_MARKER).exists() {
                 None
             } else {
-                Some(0) // Start onboarding for first-time users
+                // Start onboarding for first-time users
+                crate::app::tutorial::Lesson::load(crate::app::tutorial::ONBOARDING_LESSON)
+                    .map(crate::app::tutorial::Tutorial::new)
             },
+            profile: None,
+            interest: None,
+            start_menu: None,
             view_mode: 0,
+            pheromone_channel: 0,
             last_world_rect: Rect::default(),
             last_sidebar_rect: Rect::default(),
             gene_editor_offset: 20,
             event_log: VecDeque::with_capacity(15),
+            narration_feed: Default::default(),
             network_state: primordium_net::NetworkState::default(),
             latest_snapshot,
             network: None,
+            migration_backoff: None,
             hof_query_rx: None,
             cached_hall_of_fame: Vec::new(),
             // Phase 70: Registry
@@ -227,6 +346,26 @@ impl App {
             cached_registry_genomes: Vec::new(),
             cached_registry_seeds: Vec::new(),
             registry_selected_index: 0,
+            registry_rx: None,
+            cached_registry_submissions: Vec::new(),
+            registry_submit_rx: None,
+            import_trial_rx: None,
+            import_trial: None,
+            pathogen_designer: None,
+            bookmark_note: None,
+            show_bookmarks: false,
+            bookmark_index: 0,
+            narrations: VecDeque::new(),
+            show_narrations: false,
+            narration_index: 0,
+            narration_expanded: false,
+            show_phylogeny: false,
+            phylogeny_index: 0,
+            phylogeny_collapsed: std::collections::HashSet::new(),
+            log_quota_warned: false,
+            tag_prompt: None,
+            tag_filter: None,
+            glyphs,
             input_log: Vec::new(),
             replay_queue: VecDeque::new(),
             replay_mode: false,
@@ -237,6 +376,13 @@ impl App {
         })
     }
 
+    /// Rebuilds the simulation pool with `threads` workers (0 = all cores).
+    pub fn set_sim_threads(&mut self, threads: usize) -> Result<()> {
+        self.config.threading.sim_threads = threads;
+        self.thread_pools = Arc::new(ThreadPools::build(&self.config.threading)?);
+        Ok(())
+    }
+
     pub fn connect(&mut self, url: &str) {
         self.network = Some(crate::client::manager::NetworkManager::new(url));
     }
@@ -255,9 +401,16 @@ impl App {
     }
 
     pub fn load_state(&mut self) -> Result<()> {
-        let world = crate::model::persistence::load_world("save.json")?;
+        let (world, report) =
+            crate::model::persistence::load_world_adapted("save.json", &self.config)?;
         self.world = world;
         self.tick_count = self.world.tick;
+        for issue in report.issues {
+            self.event_log.push_back((
+                format!("Save adapted: {}", issue),
+                ratatui::style::Color::Yellow,
+            ));
+        }
         Ok(())
     }
 
@@ -265,7 +418,12 @@ impl App {
         let timestamp = chrono::Utc::now().format("%Y-%m-%d_%H-%M-%S");
         let filename = format!("logs/input_trace_{}.json", timestamp);
         std::fs::create_dir_all("logs")?;
-        let data = serde_json::to_string_pretty(&self.input_log)?;
+        let trace = InputTrace::Stamped {
+            provenance: self.world.provenance.clone(),
+            events: self.input_log.clone(),
+            disasters: self.world.disasters.clone(),
+        };
+        let data = serde_json::to_string_pretty(&trace)?;
         std::fs::write(&filename, data)?;
         tracing::info!("Input trace saved to {}", filename);
         Ok(())
@@ -273,7 +431,15 @@ impl App {
 
     pub fn load_replay(&mut self, path: &str) -> Result<()> {
         let data = std::fs::read_to_string(path)?;
-        let log: Vec<InputEvent> = serde_json::from_str(&data)?;
+        let log = match serde_json::from_str(&data)? {
+            InputTrace::Stamped {
+                events, disasters, ..
+            } => {
+                self.world.script_disasters(disasters);
+                events
+            }
+            InputTrace::Bare(events) => events,
+        };
         self.replay_queue = VecDeque::from(log);
         self.replay_mode = true;
         tracing::info!("Replay loaded: {} events", self.replay_queue.len());
@@ -296,6 +462,7 @@ impl App {
                 self.config.terraform = new_config.terraform;
                 self.config.ecosystem = new_config.ecosystem;
                 self.config.target_fps = new_config.target_fps;
+                self.config.layout = new_config.layout;
 
                 self.config_last_modified = Some(modified);
 
@@ -328,13 +495,37 @@ impl App {
             ratatui::style::Color::Cyan,
         ));
 
+        let tracked: Vec<String> = self
+            .cached_registry_submissions
+            .iter()
+            .map(|s| s.id.clone())
+            .collect();
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.registry_rx = Some(rx);
         tokio::spawn(async move {
             let mut client =
                 crate::client::registry::RegistryClient::new(Some(server_url.clone()), None);
 
-            let _hof = client.get_hall_of_fame().await;
-            let _genomes = client.get_genomes(Some(20), Some("downloads")).await;
-            let _seeds = client.get_seeds(Some(20), Some("downloads")).await;
+            let hall_of_fame = client.get_hall_of_fame().await;
+            let genomes = client.get_genomes(Some(20), Some("downloads")).await;
+            let seeds = client.get_seeds(Some(20), Some("downloads")).await;
+            let mut thumbnails = std::collections::HashMap::new();
+            for seed in seeds.iter().flatten().filter(|s| s.has_thumbnail) {
+                if let Ok(png) = client.get_seed_thumbnail(&seed.id).await {
+                    thumbnails.insert(seed.id.clone(), png);
+                }
+            }
+            let mut submissions = Vec::with_capacity(tracked.len());
+            for id in &tracked {
+                submissions.push(client.get_submission_state(id).await);
+            }
+            let _ = tx.send(crate::client::registry::RegistryFetch {
+                hall_of_fame,
+                genomes,
+                seeds,
+                submissions,
+                thumbnails,
+            });
         });
     }
 }
@@ -0,0 +1,56 @@
+//! Entity tags in the TUI.
+//!
+//! `*` toggles a user tag on the selected entity and `/` opens the search
+//! bar, which filters the world view by tag: matching entities are
+//! highlighted and the cohort's statistics are shown in the world title.
+
+use primordium_data::TagSource;
+use uuid::Uuid;
+
+use crate::app::state::App;
+
+/// Longest tag accepted, in characters.
+pub const MAX_TAG_LEN: usize = 32;
+
+/// What the tag prompt's text is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagPrompt {
+    /// Filter the world view by tag.
+    Search,
+    /// Toggle a tag on this entity.
+    Tag(Uuid),
+}
+
+impl App {
+    /// Opens the search bar, prefilled with the active filter.
+    pub fn begin_tag_search(&mut self) {
+        let text = self.tag_filter.clone().unwrap_or_default();
+        self.tag_prompt = Some((TagPrompt::Search, text));
+    }
+
+    /// Starts typing a tag for the selected entity.
+    pub fn begin_tag_selected(&mut self) {
+        if let Some(id) = self.selected_entity {
+            self.tag_prompt = Some((TagPrompt::Tag(id), String::new()));
+        }
+    }
+
+    /// Applies the typed text: sets or clears the filter, or toggles the tag.
+    pub fn commit_tag_prompt(&mut self) {
+        let Some((prompt, text)) = self.tag_prompt.take() else {
+            return;
+        };
+        let tag = text.trim();
+        match prompt {
+            TagPrompt::Search => {
+                self.tag_filter = (!tag.is_empty()).then(|| tag.to_string());
+            }
+            TagPrompt::Tag(id) => {
+                if !self.world.untag_entity(id, tag) {
+                    self.world.tag_entity(id, tag, TagSource::User);
+                }
+            }
+        }
+        self.dirty = true;
+    }
+}
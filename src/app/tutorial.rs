@@ -0,0 +1,478 @@
+//! Scripted tutorial lessons.
+//!
+//! A lesson is a TOML file with a list of steps. Each step shows a short text,
+//! can highlight a region of the UI, spawn entities to set up a demonstration
+//! and waits for a trigger (a key press or an observed action) before moving
+//! on. Lessons are bundled from `assets/tutorials/`; extra or replacement
+//! lessons are picked up from a `tutorials/` directory next to the binary's
+//! working directory, so new lessons need no code changes.
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::Rng;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::app::state::App;
+use crate::model::lifecycle;
+
+/// Lesson shown on first launch.
+pub const ONBOARDING_LESSON: &str = "basics";
+pub const ONBOARDED_MARKER: &str = ".primordium_onboarded";
+const USER_LESSON_DIR: &str = "tutorials";
+
+const BUNDLED_LESSONS: &[&str] = &[
+    include_str!("../../assets/tutorials/basics.toml"),
+    include_str!("../../assets/tutorials/predation.toml"),
+    include_str!("../../assets/tutorials/bonding.toml"),
+];
+
+/// UI areas a step can draw attention to.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UiRegion {
+    Status,
+    Sparklines,
+    World,
+    Chronicle,
+    Sidebar,
+}
+
+/// App state a step can wait for.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TutorialAction {
+    Paused,
+    Resumed,
+    EntitySelected,
+    BrainOpen,
+    HelpOpen,
+    AncestryOpen,
+}
+
+/// What completes a step.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    /// A key press, named as in the help screen (`"enter"`, `"space"`, `"b"`).
+    Key(String),
+    /// An observed change in app state.
+    Action(TutorialAction),
+    /// A number of simulated ticks since the step started.
+    Ticks(u64),
+}
+
+impl Default for Trigger {
+    fn default() -> Self {
+        Trigger::Key("enter".to_string())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpawnKind {
+    Herbivore,
+    Predator,
+    Food,
+}
+
+/// Entities placed when a step starts. Positions are fractions of the world
+/// size so lessons work with any map.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpawnSpec {
+    pub kind: SpawnKind,
+    pub x: f64,
+    pub y: f64,
+    #[serde(default = "default_count")]
+    pub count: usize,
+    pub energy: Option<f64>,
+    /// Give every spawned entity of this spec the same genotype and lineage.
+    #[serde(default)]
+    pub kin: bool,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TutorialStep {
+    pub title: String,
+    pub text: Vec<String>,
+    pub highlight: Option<UiRegion>,
+    #[serde(default)]
+    pub wait_for: Trigger,
+    #[serde(default)]
+    pub spawn: Vec<SpawnSpec>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Lesson {
+    pub id: String,
+    pub title: String,
+    pub steps: Vec<TutorialStep>,
+}
+
+impl Lesson {
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let lesson: Lesson = toml::from_str(content)?;
+        anyhow::ensure!(
+            !lesson.steps.is_empty(),
+            "Lesson '{}' has no steps",
+            lesson.id
+        );
+        Ok(lesson)
+    }
+
+    /// Bundled lessons plus any in `tutorials/*.toml`, keyed by id. A user
+    /// lesson with the same id as a bundled one replaces it.
+    pub fn load_all() -> BTreeMap<String, Lesson> {
+        let mut lessons = BTreeMap::new();
+        for content in BUNDLED_LESSONS {
+            match Lesson::from_toml(content) {
+                Ok(lesson) => {
+                    lessons.insert(lesson.id.clone(), lesson);
+                }
+                Err(e) => tracing::error!("Invalid bundled lesson: {}", e),
+            }
+        }
+        if let Ok(entries) = std::fs::read_dir(USER_LESSON_DIR) {
+            for path in entries.flatten().map(|e| e.path()) {
+                if path.extension().is_some_and(|ext| ext == "toml") {
+                    match std::fs::read_to_string(&path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|c| Lesson::from_toml(&c))
+                    {
+                        Ok(lesson) => {
+                            lessons.insert(lesson.id.clone(), lesson);
+                        }
+                        Err(e) => tracing::warn!("Skipping lesson {}: {}", path.display(), e),
+                    }
+                }
+            }
+        }
+        lessons
+    }
+
+    pub fn load(id: &str) -> Option<Lesson> {
+        Self::load_all().remove(id)
+    }
+}
+
+/// Progress through a running lesson.
+#[derive(Debug, Clone)]
+pub struct Tutorial {
+    pub lesson: Lesson,
+    pub step: usize,
+    /// World tick at which the current step started.
+    pub step_started: u64,
+    /// Whether the current step's entities have been spawned.
+    pub spawned: bool,
+}
+
+impl Tutorial {
+    pub fn new(lesson: Lesson) -> Self {
+        Self {
+            lesson,
+            step: 0,
+            step_started: 0,
+            spawned: false,
+        }
+    }
+
+    pub fn current(&self) -> &TutorialStep {
+        &self.lesson.steps[self.step]
+    }
+}
+
+/// Whether `key` matches a key name used in lesson files.
+pub fn key_matches(name: &str, key: &KeyEvent) -> bool {
+    match (name.to_ascii_lowercase().as_str(), key.code) {
+        ("enter", KeyCode::Enter) => true,
+        ("space", KeyCode::Char(' ')) => true,
+        ("tab", KeyCode::Tab) => true,
+        ("esc", KeyCode::Esc) => true,
+        (_, KeyCode::Char(c)) => name.chars().count() == 1 && name.starts_with(c),
+        _ => false,
+    }
+}
+
+impl App {
+    /// Starts a lesson by id. Returns false if no such lesson exists.
+    pub fn start_tutorial(&mut self, id: &str) -> bool {
+        match Lesson::load(id) {
+            Some(lesson) => {
+                self.tutorial = Some(Tutorial::new(lesson));
+                self.show_help = false;
+                self.update_tutorial();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Ends the running lesson. Finishing or skipping the onboarding lesson
+    /// marks onboarding as done.
+    pub fn end_tutorial(&mut self) {
+        if let Some(tutorial) = self.tutorial.take() {
+            if tutorial.lesson.id == ONBOARDING_LESSON && !self.writes_suppressed() {
+                let _ = std::fs::write(ONBOARDED_MARKER, "1");
+            }
+        }
+    }
+
+    fn advance_tutorial(&mut self) {
+        let Some(tutorial) = &mut self.tutorial else {
+            return;
+        };
+        if tutorial.step + 1 >= tutorial.lesson.steps.len() {
+            self.end_tutorial();
+            return;
+        }
+        tutorial.step += 1;
+        tutorial.step_started = self.world.tick;
+        tutorial.spawned = false;
+        self.update_tutorial();
+    }
+
+    /// Feeds a key press to the running lesson. Returns true if the key was
+    /// consumed by the tutorial and should not reach the normal handlers.
+    pub fn tutorial_key(&mut self, key: KeyEvent) -> bool {
+        let Some(tutorial) = &self.tutorial else {
+            return false;
+        };
+        if key.code == KeyCode::Esc {
+            self.end_tutorial();
+            return true;
+        }
+        if let Trigger::Key(name) = &tutorial.current().wait_for {
+            if key_matches(name, &key) {
+                // Enter only drives the tutorial; other keys keep their
+                // normal effect so a lesson can teach them.
+                let consumed = key.code == KeyCode::Enter;
+                self.advance_tutorial();
+                return consumed;
+            }
+        }
+        false
+    }
+
+    /// Spawns the current step's entities and advances when its action or
+    /// tick trigger is satisfied. Called every frame and after input.
+    pub fn update_tutorial(&mut self) {
+        let Some(tutorial) = &mut self.tutorial else {
+            return;
+        };
+        if !tutorial.spawned {
+            tutorial.spawned = true;
+            tutorial.step_started = self.world.tick;
+            let specs = tutorial.current().spawn.clone();
+            for spec in &specs {
+                self.spawn_for_tutorial(spec);
+            }
+        }
+
+        let Some(tutorial) = &self.tutorial else {
+            return;
+        };
+        let done = match &tutorial.current().wait_for {
+            Trigger::Key(_) => false,
+            Trigger::Ticks(n) => self.world.tick >= tutorial.step_started + n,
+            Trigger::Action(action) => match action {
+                TutorialAction::Paused => self.paused,
+                TutorialAction::Resumed => !self.paused,
+                TutorialAction::EntitySelected => self.selected_entity.is_some(),
+                TutorialAction::BrainOpen => self.show_brain,
+                TutorialAction::HelpOpen => self.show_help,
+                TutorialAction::AncestryOpen => self.show_ancestry,
+            },
+        };
+        if done {
+            self.advance_tutorial();
+        }
+    }
+
+    fn spawn_for_tutorial(&mut self, spec: &SpawnSpec) {
+        let w = f64::from(self.world.width);
+        let h = f64::from(self.world.height);
+        let cx = (spec.x.clamp(0.0, 1.0) * w).clamp(1.0, w - 2.0);
+        let cy = (spec.y.clamp(0.0, 1.0) * h).clamp(1.0, h - 2.0);
+        let mut rng = rand::thread_rng();
+
+        if spec.kind == SpawnKind::Food {
+            for _ in 0..spec.count {
+                let x = (cx + rng.gen_range(-2.0..=2.0)).clamp(1.0, w - 2.0);
+                let y = (cy + rng.gen_range(-2.0..=2.0)).clamp(1.0, h - 2.0);
+                self.world
+                    .spawn_food_at(x as u16, y as u16, rng.gen_range(0.0..1.0));
+            }
+            return;
+        }
+
+        let mut template: Option<primordium_data::Entity> = None;
+        for _ in 0..spec.count {
+            let x = (cx + rng.gen_range(-1.5..=1.5)).clamp(1.0, w - 2.0);
+            let y = (cy + rng.gen_range(-1.5..=1.5)).clamp(1.0, h - 2.0);
+            let mut e = lifecycle::create_entity_with_rng(x, y, self.world.tick, &mut rng);
+            if spec.kin {
+                if let Some(first) = &template {
+                    e.intel.genotype = first.intel.genotype.clone();
+                    e.metabolism.lineage_id = first.metabolism.lineage_id;
+                }
+            }
+            let trophic = match spec.kind {
+                SpawnKind::Predator => 1.0,
+                _ => 0.0,
+            };
+            std::sync::Arc::make_mut(&mut e.intel.genotype).trophic_potential = trophic;
+            e.metabolism.trophic_potential = trophic;
+            if let Some(energy) = spec.energy {
+                e.metabolism.energy = energy.min(e.metabolism.max_energy);
+            }
+            self.world
+                .lineage_registry
+                .record_birth(e.metabolism.lineage_id, 1, self.world.tick);
+            if template.is_none() {
+                template = Some(e.clone());
+            }
+            self.world.spawn_entity(e);
+        }
+    }
+
+    /// Draws the current step and its highlighted region.
+    pub fn render_tutorial(&self, f: &mut Frame, left_layout: &[Rect], sidebar: Rect) {
+        let Some(tutorial) = self.tutorial.as_ref().filter(|_| self.start_menu.is_none()) else {
+            return;
+        };
+        let step = tutorial.current();
+        let area = f.area();
+
+        if let Some(region) = step.highlight {
+            let rect = match region {
+                UiRegion::Status => left_layout.first().copied(),
+                UiRegion::Sparklines => left_layout.get(1).copied(),
+                UiRegion::World => left_layout.get(2).copied(),
+                UiRegion::Chronicle => left_layout.get(3).copied(),
+                UiRegion::Sidebar => Some(sidebar),
+            };
+            if let Some(rect) = rect.filter(|r| r.width > 0 && r.height > 0) {
+                // Recolour the existing border in place so panel titles stay readable.
+                let style = Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD);
+                let rect = rect.intersection(area);
+                let buf = f.buffer_mut();
+                for x in rect.left()..rect.right() {
+                    buf[(x, rect.top())].set_style(style);
+                    buf[(x, rect.bottom() - 1)].set_style(style);
+                }
+                for y in rect.top()..rect.bottom() {
+                    buf[(rect.left(), y)].set_style(style);
+                    buf[(rect.right() - 1, y)].set_style(style);
+                }
+            }
+        }
+
+        let hint = match &step.wait_for {
+            Trigger::Key(name) => format!(" Press [{}] to continue", name.to_uppercase()),
+            Trigger::Action(_) => " Try it now...".to_string(),
+            Trigger::Ticks(_) => " Watch the world...".to_string(),
+        };
+        let mut lines: Vec<ratatui::text::Line> = vec![ratatui::text::Line::from("")];
+        lines.extend(
+            step.text
+                .iter()
+                .map(|s| ratatui::text::Line::from(format!(" {}", s))),
+        );
+        lines.push(ratatui::text::Line::from(""));
+        lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
+            hint,
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        let modal_width = 55.min(area.width.saturating_sub(4));
+        let modal_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+        let modal_area = Rect::new(
+            area.x + (area.width - modal_width) / 2,
+            area.bottom().saturating_sub(modal_height + 1),
+            modal_width,
+            modal_height,
+        );
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .title(format!(
+                        " {} ({}/{}) ",
+                        step.title,
+                        tutorial.step + 1,
+                        tutorial.lesson.steps.len()
+                    ))
+                    .title_bottom(" [Esc] Skip ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            ),
+            modal_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_lessons_parse() {
+        for content in BUNDLED_LESSONS {
+            let lesson = Lesson::from_toml(content).unwrap();
+            assert!(!lesson.title.is_empty());
+        }
+        assert!(Lesson::load(ONBOARDING_LESSON).is_some());
+    }
+
+    #[test]
+    fn test_step_schema() {
+        let lesson = Lesson::from_toml(
+            r#"
+            id = "demo"
+            title = "Demo"
+
+            [[steps]]
+            title = "Pause"
+            text = ["Pause the world."]
+            highlight = "status"
+            wait_for = { action = "paused" }
+
+            [[steps]]
+            title = "Hunt"
+            text = ["Watch."]
+            wait_for = { ticks = 50 }
+            spawn = [{ kind = "predator", x = 0.5, y = 0.5, energy = 300.0 }]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(lesson.steps[0].highlight, Some(UiRegion::Status));
+        assert_eq!(
+            lesson.steps[0].wait_for,
+            Trigger::Action(TutorialAction::Paused)
+        );
+        assert_eq!(lesson.steps[1].wait_for, Trigger::Ticks(50));
+        assert_eq!(lesson.steps[1].spawn[0].count, 1);
+        assert!(Lesson::from_toml("id = \"x\"\ntitle = \"X\"\nsteps = []").is_err());
+    }
+
+    #[test]
+    fn test_key_names() {
+        use crossterm::event::KeyModifiers;
+        let key = |code| KeyEvent::new(code, KeyModifiers::empty());
+        assert!(key_matches("enter", &key(KeyCode::Enter)));
+        assert!(key_matches("space", &key(KeyCode::Char(' '))));
+        assert!(key_matches("b", &key(KeyCode::Char('b'))));
+        assert!(!key_matches("b", &key(KeyCode::Char('B'))));
+        assert!(!key_matches("space", &key(KeyCode::Enter)));
+    }
+}
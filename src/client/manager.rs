@@ -13,6 +13,8 @@ use futures::StreamExt;
 #[cfg(not(target_arch = "wasm32"))]
 use futures_util::sink::SinkExt;
 #[cfg(not(target_arch = "wasm32"))]
+use primordium_net::codec;
+#[cfg(not(target_arch = "wasm32"))]
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 
 #[derive(Clone)]
@@ -77,7 +79,7 @@ impl NetworkManager {
 
         let pending_clone = pending.clone();
         let state_clone = state.clone();
-        let url_string = url.to_string();
+        let url_string = primordium_net::codec::with_compression(url);
 
         tokio::spawn(async move {
             let (ws_stream, _) = match connect_async(&url_string).await {
@@ -89,12 +91,22 @@ impl NetworkManager {
             loop {
                 tokio::select! {
                     Some(msg) = rx.recv() => {
-                        if ws_sender.send(Message::Text(msg)).await.is_err() { break; }
+                        let frame = if codec::should_compress(&msg) {
+                            Message::Binary(codec::compress(&msg))
+                        } else {
+                            Message::Text(msg)
+                        };
+                        if ws_sender.send(frame).await.is_err() { break; }
                     }
                 Some(Ok(msg)) = ws_receiver.next() => {
-                    if let Message::Text(txt) = msg {
-                        // Security: Limit message size to prevent DoS (100KB max)
-                        const MAX_MESSAGE_SIZE: usize = 100 * 1024;
+                    // Security: Limit message size to prevent DoS (100KB max)
+                    const MAX_MESSAGE_SIZE: usize = 100 * 1024;
+                    let text = match msg {
+                        Message::Text(txt) => Some(txt),
+                        Message::Binary(bytes) => codec::decompress(&bytes, MAX_MESSAGE_SIZE).ok(),
+                        _ => None,
+                    };
+                    if let Some(txt) = text {
                         if txt.len() > MAX_MESSAGE_SIZE {
                             #[cfg(target_arch = "wasm32")]
                             web_sys::console::warn_1(&format!("Oversized message received: {} bytes", txt.len()).into());
@@ -142,12 +154,16 @@ impl NetworkManager {
                     p.push(msg);
                 }
             }
-            NetMessage::Handshake { client_id } => {
+            NetMessage::Handshake { client_id, .. } => {
                 s.client_id = Some(client_id);
             }
             NetMessage::PeerList { peers } => {
                 s.peers = peers;
             }
+            NetMessage::PeerDropped { peer_id, .. } => {
+                s.peers.retain(|p| p.peer_id != peer_id);
+                s.trade_offers.retain(|o| o.sender_id != peer_id);
+            }
             NetMessage::TradeOffer(proposal) => {
                 s.trade_offers.push(proposal);
             }
@@ -157,7 +173,7 @@ impl NetworkManager {
             NetMessage::TradeRevoke { proposal_id } => {
                 s.trade_offers.retain(|o| o.id != proposal_id);
             }
-            NetMessage::Relief { .. } => {
+            NetMessage::Relief { .. } | NetMessage::RateLimited { .. } => {
                 if let Ok(mut p) = pending.lock() {
                     p.push(msg);
                 }
@@ -244,7 +260,10 @@ mod tests {
         NetworkManager::handle_incoming_message(
             &state,
             &pending,
-            NetMessage::Handshake { client_id },
+            NetMessage::Handshake {
+                client_id,
+                publish_secret: String::new(),
+            },
         );
 
         let s = state.lock().unwrap();
@@ -358,6 +377,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_handle_incoming_rate_limited() {
+        let state = Arc::new(Mutex::new(NetworkState::default()));
+        let pending = Arc::new(Mutex::new(Vec::new()));
+        let msg = NetMessage::RateLimited {
+            limit: primordium_net::RateLimit::DailyMigrations,
+            retry_after_ms: 60_000,
+            migration_id: Some(Uuid::new_v4()),
+        };
+
+        NetworkManager::handle_incoming_message(&state, &pending, msg.clone());
+
+        assert_eq!(*pending.lock().unwrap(), vec![msg]);
+    }
+
     #[test]
     fn test_pop_pending_limited() {
         let manager = NetworkManager {
@@ -4,9 +4,16 @@
 //! - Hall of Fame (top lineages by civilization level)
 //! - Genome marketplace (browse/submit genomes)
 //! - Seed marketplace (browse/submit simulation configs)
+//! - Challenge scenarios (download, publish and submit runs)
 
+use primordium_data::RunManifest;
+pub use primordium_io::challenge::ChallengeResult;
+pub use primordium_io::storage::{
+    LeaderboardEntry, ModerationStatus, ScenarioRecord, SubmissionState,
+};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 use uuid::Uuid;
 
@@ -47,6 +54,9 @@ pub struct SeedRecord {
     pub performance_summary: String,
     pub downloads: u32,
     pub created_at: String,
+    /// Servers that predate thumbnails never have one.
+    #[serde(default)]
+    pub has_thumbnail: bool,
 }
 
 /// Hall of Fame entry.
@@ -57,6 +67,18 @@ pub struct HallOfFameEntry {
     pub is_extinct: bool,
 }
 
+/// Result of one background refresh of every marketplace list.
+#[derive(Debug)]
+pub struct RegistryFetch {
+    pub hall_of_fame: Result<Vec<HallOfFameEntry>, String>,
+    pub genomes: Result<Vec<GenomeRecord>, String>,
+    pub seeds: Result<Vec<SeedRecord>, String>,
+    /// Current state of each submission this client is tracking.
+    pub submissions: Vec<Result<SubmissionState, String>>,
+    /// Preview PNGs of the listed seeds that have one, by seed ID.
+    pub thumbnails: HashMap<String, Vec<u8>>,
+}
+
 /// Response wrapper for Hall of Fame.
 #[derive(Debug, Deserialize)]
 pub struct HallOfFameResponse {
@@ -91,6 +113,8 @@ struct SubmitGenomeRequest<'a> {
     fitness_score: f64,
     offspring_count: u32,
     tick: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provenance: Option<&'a RunManifest>,
 }
 
 /// Submit seed request payload.
@@ -100,10 +124,16 @@ struct SubmitSeedRequest<'a> {
     name: &'a str,
     description: &'a str,
     tags: &'a str,
+    #[serde(rename = "config")]
     config_json: &'a str,
     avg_tick_time: f64,
     max_pop: u32,
     performance_summary: &'a str,
+    /// Hex-encoded preview PNG.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provenance: Option<&'a RunManifest>,
 }
 
 /// Submit response.
@@ -111,10 +141,33 @@ struct SubmitSeedRequest<'a> {
 pub struct SubmitResponse {
     pub success: bool,
     pub id: Option<String>,
+    /// Servers that predate moderation publish every submission at once.
+    #[serde(default)]
+    pub status: ModerationStatus,
     #[serde(default)]
     pub error: Option<String>,
 }
 
+/// A submission the server accepted, and the moderation state it starts in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionReceipt {
+    pub id: String,
+    pub status: ModerationStatus,
+}
+
+impl SubmitResponse {
+    pub fn into_receipt(self) -> Result<SubmissionReceipt, String> {
+        if self.success {
+            Ok(SubmissionReceipt {
+                id: self.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                status: self.status,
+            })
+        } else {
+            Err(self.error.unwrap_or_else(|| "Unknown error".to_string()))
+        }
+    }
+}
+
 /// Submit genome context - bundles all submission parameters.
 #[derive(Debug, Clone)]
 pub struct SubmitGenomeContext<'a> {
@@ -127,6 +180,8 @@ pub struct SubmitGenomeContext<'a> {
     pub fitness_score: f64,
     pub offspring_count: u32,
     pub tick: u64,
+    /// The run the genome evolved in.
+    pub provenance: Option<&'a RunManifest>,
 }
 
 /// Submit seed context - bundles all submission parameters.
@@ -140,6 +195,55 @@ pub struct SubmitSeedContext<'a> {
     pub avg_tick_time: f64,
     pub max_pop: u32,
     pub performance_summary: &'a str,
+    /// Preview PNG of the world, from `Thumbnail::to_png`.
+    pub thumbnail: Option<&'a [u8]>,
+    /// The run the config was taken from.
+    pub provenance: Option<&'a RunManifest>,
+}
+
+/// Response wrapper for the scenario list.
+#[derive(Debug, Deserialize)]
+struct ScenariosResponse {
+    scenarios: Vec<ScenarioRecord>,
+}
+
+/// Response wrapper for a scenario leaderboard.
+#[derive(Debug, Deserialize)]
+struct LeaderboardResponse {
+    leaderboard: Vec<LeaderboardEntry>,
+}
+
+/// Publish scenario request payload.
+#[derive(Serialize)]
+struct PublishScenarioRequest<'a> {
+    scenario: &'a str,
+    author: &'a str,
+}
+
+/// Submit challenge result request payload.
+#[derive(Serialize)]
+struct SubmitResultRequest<'a> {
+    player: &'a str,
+    result: &'a ChallengeResult,
+}
+
+/// Where a submitted challenge run landed on the leaderboard.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChallengeRank {
+    pub rank: u32,
+    pub score: f64,
+    pub passed: bool,
+}
+
+/// The server's `error` message for a failed request, or its HTTP status.
+async fn error_of(response: reqwest::Response) -> String {
+    let status = response.status();
+    response
+        .json::<serde_json::Value>()
+        .await
+        .ok()
+        .and_then(|body| body["error"].as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("HTTP error: {}", status))
 }
 
 /// Connection status to the Registry server.
@@ -194,6 +298,11 @@ impl RegistryClient {
         self.api_key = key;
     }
 
+    /// Get the API key used for submissions.
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
     /// Query Hall of Fame from the server.
     pub async fn get_hall_of_fame(&mut self) -> Result<Vec<HallOfFameEntry>, String> {
         self.status = RegistryStatus::Connecting;
@@ -268,7 +377,10 @@ impl RegistryClient {
     }
 
     /// Submit a genome to the marketplace.
-    pub async fn submit_genome(&self, ctx: SubmitGenomeContext<'_>) -> Result<String, String> {
+    pub async fn submit_genome(
+        &self,
+        ctx: SubmitGenomeContext<'_>,
+    ) -> Result<SubmissionReceipt, String> {
         let url = format!("{}/api/registry/genomes", self.server_url);
 
         let request = SubmitGenomeRequest {
@@ -281,6 +393,7 @@ impl RegistryClient {
             fitness_score: ctx.fitness_score,
             offspring_count: ctx.offspring_count,
             tick: ctx.tick,
+            provenance: ctx.provenance,
         };
 
         let mut req = self.client.post(&url).json(&request);
@@ -296,12 +409,27 @@ impl RegistryClient {
         }
 
         let result: SubmitResponse = response.json().await.map_err(|e| e.to_string())?;
+        result.into_receipt()
+    }
 
-        if result.success {
-            Ok(result.id.unwrap_or_else(|| Uuid::new_v4().to_string()))
-        } else {
-            Err(result.error.unwrap_or_else(|| "Unknown error".to_string()))
+    /// Query the moderation state of a genome or seed submitted earlier.
+    pub async fn get_submission_state(&self, id: &str) -> Result<SubmissionState, String> {
+        let url = format!("{}/api/registry/submissions/{}", self.server_url, id);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP error: {}", response.status()));
         }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))
     }
 
     /// Query seeds from the marketplace.
@@ -351,8 +479,27 @@ impl RegistryClient {
         Ok(seeds.seeds)
     }
 
+    /// Download the preview PNG of a seed.
+    pub async fn get_seed_thumbnail(&self, id: &str) -> Result<Vec<u8>, String> {
+        let url = format!("{}/api/registry/seeds/{}/thumbnail", self.server_url, id);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(error_of(response).await);
+        }
+        let png = response.bytes().await.map_err(|e| e.to_string())?;
+        Ok(png.to_vec())
+    }
+
     /// Submit a seed (simulation config) to the marketplace.
-    pub async fn submit_seed(&self, ctx: SubmitSeedContext<'_>) -> Result<String, String> {
+    pub async fn submit_seed(
+        &self,
+        ctx: SubmitSeedContext<'_>,
+    ) -> Result<SubmissionReceipt, String> {
         let url = format!("{}/api/registry/seeds", self.server_url);
 
         let request = SubmitSeedRequest {
@@ -364,6 +511,8 @@ impl RegistryClient {
             avg_tick_time: ctx.avg_tick_time,
             max_pop: ctx.max_pop,
             performance_summary: ctx.performance_summary,
+            thumbnail: ctx.thumbnail.map(hex::encode),
+            provenance: ctx.provenance,
         };
 
         let mut req = self.client.post(&url).json(&request);
@@ -379,12 +528,111 @@ impl RegistryClient {
         }
 
         let result: SubmitResponse = response.json().await.map_err(|e| e.to_string())?;
+        result.into_receipt()
+    }
 
-        if result.success {
-            Ok(result.id.unwrap_or_else(|| Uuid::new_v4().to_string()))
-        } else {
-            Err(result.error.unwrap_or_else(|| "Unknown error".to_string()))
+    /// Query published challenge scenarios.
+    pub async fn get_scenarios(&self) -> Result<Vec<ScenarioRecord>, String> {
+        let url = format!("{}/api/registry/scenarios", self.server_url);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(error_of(response).await);
+        }
+        let scenarios: ScenariosResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        Ok(scenarios.scenarios)
+    }
+
+    /// Download a challenge scenario.
+    pub async fn get_scenario(&self, id: &str) -> Result<ScenarioRecord, String> {
+        let url = format!("{}/api/registry/scenarios/{}", self.server_url, id);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(error_of(response).await);
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))
+    }
+
+    /// Publish a challenge scenario file. Returns the scenario's id.
+    pub async fn publish_scenario(&self, scenario: &str, author: &str) -> Result<String, String> {
+        let url = format!("{}/api/registry/scenarios", self.server_url);
+        let mut req = self
+            .client
+            .post(&url)
+            .json(&PublishScenarioRequest { scenario, author });
+        if let Some(ref key) = self.api_key {
+            req = req.header("Authorization", format!("Bearer {}", key));
+        }
+
+        let response = req.send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(error_of(response).await);
         }
+        let result: SubmitResponse = response.json().await.map_err(|e| e.to_string())?;
+        result.into_receipt().map(|receipt| receipt.id)
+    }
+
+    /// Query the best runs of a challenge scenario.
+    pub async fn get_leaderboard(&self, id: &str) -> Result<Vec<LeaderboardEntry>, String> {
+        let url = format!(
+            "{}/api/registry/scenarios/{}/leaderboard",
+            self.server_url, id
+        );
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(error_of(response).await);
+        }
+        let leaderboard: LeaderboardResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        Ok(leaderboard.leaderboard)
+    }
+
+    /// Submit a run of a challenge scenario to its leaderboard.
+    pub async fn submit_challenge_result(
+        &self,
+        id: &str,
+        player: &str,
+        result: &ChallengeResult,
+    ) -> Result<ChallengeRank, String> {
+        let url = format!("{}/api/registry/scenarios/{}/results", self.server_url, id);
+        let mut req = self
+            .client
+            .post(&url)
+            .json(&SubmitResultRequest { player, result });
+        if let Some(ref key) = self.api_key {
+            req = req.header("Authorization", format!("Bearer {}", key));
+        }
+
+        let response = req.send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(error_of(response).await);
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))
     }
 }
 
@@ -407,6 +655,26 @@ mod tests {
         assert_eq!(client.server_url(), "http://test:8080");
     }
 
+    #[test]
+    fn test_submit_response_defaults_to_approved() {
+        let legacy: SubmitResponse =
+            serde_json::from_str(r#"{"success": true, "id": "abc"}"#).unwrap();
+        assert_eq!(
+            legacy.into_receipt(),
+            Ok(SubmissionReceipt {
+                id: "abc".to_string(),
+                status: ModerationStatus::Approved,
+            })
+        );
+
+        let moderated: SubmitResponse =
+            serde_json::from_str(r#"{"success": true, "id": "abc", "status": "pending"}"#).unwrap();
+        assert_eq!(
+            moderated.into_receipt().unwrap().status,
+            ModerationStatus::Pending
+        );
+    }
+
     #[test]
     fn test_default_url() {
         let client = RegistryClient::new(None, None);
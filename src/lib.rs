@@ -1,27 +1,32 @@
+#[cfg(feature = "tui")]
 pub mod app;
+#[cfg(feature = "net")]
 pub mod client;
 pub mod model;
 pub mod ui;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
 use crate::client::manager::NetworkManager;
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
 use crate::model::brain::{BrainLogic, GenotypeLogic};
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
 use wasm_bindgen::prelude::*;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
 use primordium_net::NetMessage;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
 #[wasm_bindgen]
 pub struct Simulation {
     world: model::world::World,
     env: model::state::environment::Environment,
     network: Option<crate::client::manager::NetworkManager>,
+    glyphs: model::glyph::GlyphTheme,
+    atlas: std::cell::RefCell<Option<crate::ui::web_renderer::GlyphAtlas>>,
+    palette_enabled: bool,
 }
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
 #[wasm_bindgen]
 impl Simulation {
     pub fn new() -> Result<Simulation, JsValue> {
@@ -35,6 +40,9 @@ impl Simulation {
             world,
             env: model::state::environment::Environment::default(),
             network: None,
+            glyphs: model::glyph::GlyphTheme::default(),
+            atlas: std::cell::RefCell::new(None),
+            palette_enabled: config.visual.environment_palette,
         })
     }
 
@@ -63,7 +71,7 @@ impl Simulation {
                     let _ =
                         self.world
                             .import_migrant(dna, energy, generation, &fingerprint, &checksum);
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
                     web_sys::console::log_1(&JsValue::from_str(
                         "Entity migrated into this universe!",
                     ));
@@ -79,26 +87,14 @@ impl Simulation {
                     || e.physics.y > (self.world.height as f64 - 2.0);
 
                 if leaving {
-                    let dna = e.intel.genotype.to_hex();
-                    let energy = e.metabolism.energy as f32;
-                    let generation = e.metabolism.generation;
-
-                    use sha2::{Digest, Sha256};
-                    let mut hasher = Sha256::new();
-                    hasher.update(dna.as_bytes());
-                    hasher.update(energy.to_be_bytes());
-                    hasher.update(generation.to_be_bytes());
-                    let checksum = hex::encode(hasher.finalize());
-
-                    migrants.push(NetMessage::MigrateEntity {
-                        dna,
-                        energy,
-                        generation,
-                        species_name: e.identity.name,
-                        fingerprint: self.world.config.fingerprint(),
-                        checksum,
-                    });
-                    migrants.push(msg);
+                    migrants.push(NetMessage::migrate_entity(
+                        uuid::Uuid::new_v4(),
+                        e.intel.genotype.to_hex(),
+                        e.metabolism.energy as f32,
+                        e.metabolism.generation,
+                        e.identity.name,
+                        self.world.config.fingerprint(),
+                    ));
                 }
 
                 !leaving
@@ -106,7 +102,7 @@ impl Simulation {
 
             for msg in migrants {
                 net.send(&msg);
-                #[cfg(target_arch = "wasm32")]
+                #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
                 web_sys::console::log_1(&JsValue::from_str("Entity migrated to another universe!"));
             }
         }
@@ -114,10 +110,35 @@ impl Simulation {
         Ok(())
     }
 
+    /// Replaces the glyph theme with the contents of a theme file.
+    pub fn set_glyph_theme(&mut self, toml: &str) -> Result<(), JsValue> {
+        self.glyphs = model::glyph::GlyphTheme::from_toml(toml)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.atlas.replace(None);
+        Ok(())
+    }
+
+    /// Turns tinting the world by climate, era and daylight on or off.
+    pub fn set_palette_enabled(&mut self, enabled: bool) {
+        self.palette_enabled = enabled;
+    }
+
     pub fn draw(&self, ctx: &web_sys::CanvasRenderingContext2d, width: f64, height: f64) {
-        use crate::ui::web_renderer::WebRenderer;
+        use crate::ui::web_renderer::{GlyphAtlas, WebRenderer};
+        let mut atlas = self.atlas.borrow_mut();
+        if atlas.is_none() {
+            *atlas = GlyphAtlas::new().ok();
+        }
+        let Some(atlas) = atlas.as_mut() else {
+            return;
+        };
+        let palette = if self.palette_enabled {
+            model::palette::Palette::from_environment(&self.env)
+        } else {
+            model::palette::Palette::NEUTRAL
+        };
         let renderer = WebRenderer::new(width, height, self.world.width, self.world.height);
-        renderer.render(ctx, &self.world);
+        renderer.render(ctx, &self.world, &self.glyphs, atlas, &palette);
     }
 
     pub fn get_stats(&self) -> js_sys::Object {
@@ -1,11 +1,33 @@
 use anyhow::Result;
 use clap::Parser;
-use primordium_lib::app::App;
+use primordium_io::card::CreatureCard;
+use primordium_lib::app::attract::AttractReel;
+use primordium_lib::app::doctor;
+use primordium_lib::app::inspector::Inspector;
+use primordium_lib::app::progress::{Progress, StatusFormat};
+use primordium_lib::app::tutorial::Lesson;
+use primordium_lib::app::{App, ShutdownManager};
+use primordium_lib::client::registry::RegistryClient;
+use primordium_lib::model::brain::export;
+use primordium_lib::model::challenge;
+use primordium_lib::model::compat::{self, CompatibilityReport};
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::environment::Environment;
+use primordium_lib::model::gallery::SeedGallery;
+use primordium_lib::model::lineage_tree::LineageTree;
+use primordium_lib::model::optimize;
+use primordium_lib::model::persistence::{load_world, save_world};
+use primordium_lib::model::provenance;
+use primordium_lib::model::replay;
+use primordium_lib::model::world::continuity::SurvivorSelection;
 use primordium_tui::Tui;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Mode to run the simulation in
     #[arg(short, long, value_enum, default_value = "standard")]
     mode: Mode,
@@ -22,11 +44,279 @@ struct Args {
     #[arg(long)]
     benchmark: bool,
 
+    /// Simulation worker threads (overrides `threading.sim_threads`; 0 = all cores)
+    #[arg(long)]
+    threads: Option<usize>,
+
+    /// Loop the bundled demo recordings with chronicle captions
+    #[arg(long)]
+    attract: bool,
+
+    /// Record an attract-mode reel from the current config to the given file
+    #[arg(long, value_name = "FILE")]
+    record_attract: Option<String>,
+
+    /// Ticks to simulate when recording an attract-mode reel
+    #[arg(long, default_value_t = 6000)]
+    attract_ticks: u64,
+
+    /// Skip the start menu and resume straight into the last world
+    #[arg(long)]
+    no_menu: bool,
+
+    /// Start a tutorial lesson (e.g. basics, predation, bonding)
+    #[arg(long, value_name = "LESSON")]
+    tutorial: Option<String>,
+
+    /// Replace the starting population with the genome dump taken at or before this tick
+    #[arg(long, value_name = "TICK")]
+    reseed_from_archive: Option<u64>,
+
     #[arg(long)]
     relay: Option<String>,
 
     #[arg(long)]
     replay: Option<String>,
+
+    /// Record a headless deterministic run into this directory for `replay verify`
+    #[arg(long, value_name = "DIR")]
+    record_replay: Option<String>,
+
+    /// Ticks between world checkpoints in a recorded replay
+    #[arg(long, default_value_t = replay::DEFAULT_CHECKPOINT_INTERVAL)]
+    checkpoint_interval: u64,
+
+    /// Stop a headless run after simulating this many ticks
+    #[arg(long, value_name = "N")]
+    ticks: Option<u64>,
+
+    /// Seconds between headless status lines
+    #[arg(long, default_value_t = 5)]
+    status_interval: u64,
+
+    /// Print no headless status lines
+    #[arg(long, conflicts_with = "json_status")]
+    quiet: bool,
+
+    /// Print headless status as one JSON object per line
+    #[arg(long)]
+    json_status: bool,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Inspect evolved brains
+    Brain {
+        #[command(subcommand)]
+        action: BrainCommand,
+    },
+    /// Start a fresh world instead of resuming the last save
+    New {
+        /// World seed (defaults to `world.seed` in the config)
+        #[arg(long, conflicts_with = "random_interesting")]
+        seed: Option<u64>,
+
+        /// Replay a seed from the gallery of interesting runs, favouring the best
+        #[arg(long)]
+        random_interesting: bool,
+
+        /// Seed the new world with survivors of this saved world, keeping its lineage records
+        #[arg(long, value_name = "SAVE")]
+        continue_from: Option<String>,
+
+        /// Survivors to carry over: `all` or the `top:<N>` fittest by `[continuity]`
+        #[arg(long, default_value = "all", requires = "continue_from")]
+        survivors: SurvivorSelection,
+    },
+    /// Inspect and migrate saved worlds
+    Save {
+        #[command(subcommand)]
+        action: SaveCommand,
+    },
+    /// Download, run and publish registry challenge scenarios
+    Challenge {
+        #[command(subcommand)]
+        action: ChallengeCommand,
+    },
+    /// Tune config parameters over successive headless runs to maximise a metric
+    Optimize {
+        /// Search file naming the objective and the parameters to tune
+        search: String,
+
+        /// Search state, saved after every trial and resumed if present
+        #[arg(long, default_value = "optimize_state.json")]
+        checkpoint: String,
+
+        /// Write the best config overrides found to this file
+        #[arg(long, default_value = "optimize_best.toml")]
+        best: String,
+    },
+    /// Export an entity as a shareable creature card
+    Card {
+        /// Entity ID, or a unique prefix of it
+        #[arg(long)]
+        entity: String,
+
+        /// Saved world to read the entity from
+        #[arg(long, default_value = "save.json")]
+        save: String,
+
+        /// Output file (`.png` renders an image, anything else text); stdout if omitted
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Append the full DNA hex to a text card
+        #[arg(long)]
+        dna: bool,
+    },
+    /// Export a lineage's crest as SVG
+    Crest {
+        /// Lineage ID, or a unique prefix of it
+        #[arg(long)]
+        lineage: String,
+
+        /// Saved world to read the lineage from
+        #[arg(long, default_value = "save.json")]
+        save: String,
+
+        /// Output file; stdout if omitted
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Width of the shield in pixels
+        #[arg(long, default_value_t = 120)]
+        size: u32,
+    },
+    /// Export the speciation tree of a saved world for phylogenetics tools
+    Phylogeny {
+        /// Saved world to read the lineages from
+        #[arg(long, default_value = "save.json")]
+        save: String,
+
+        /// Output file (`.xml` writes PhyloXML, anything else Newick); stdout if omitted
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Write PhyloXML instead of Newick
+        #[arg(long)]
+        phyloxml: bool,
+    },
+    /// Show which run, config and build produced a file
+    Provenance {
+        #[command(subcommand)]
+        action: ProvenanceCommand,
+    },
+    /// Re-simulate recorded runs from their checkpoints
+    Replay {
+        #[command(subcommand)]
+        action: ReplayCommand,
+    },
+    /// Check the environment, config and saved data, with advice for each problem
+    Doctor {
+        /// Also check that this relay URL is reachable
+        #[arg(long, value_name = "URL")]
+        relay: Option<String>,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum SaveCommand {
+    /// Compare a save with the current config and build, optionally writing an adapted copy
+    Check {
+        /// Saved world to check
+        #[arg(long, default_value = "save.json")]
+        save: String,
+
+        /// Write the save, adapted to the current config, to this file
+        #[arg(long, value_name = "FILE")]
+        migrate: Option<String>,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum ProvenanceCommand {
+    /// Print the run manifest of a save, log, replay, reel or challenge result
+    Show {
+        /// File to read
+        file: String,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum ReplayCommand {
+    /// Re-simulate a tick range of a recording and check it reproduces bit for bit
+    Verify {
+        /// Directory written by `--record-replay`
+        dir: String,
+
+        /// First tick to check
+        #[arg(long, default_value_t = 1)]
+        from: u64,
+
+        /// Last tick to check (defaults to the end of the recording)
+        #[arg(long)]
+        to: Option<u64>,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum ChallengeCommand {
+    /// List the scenarios published on the registry
+    List {
+        /// Registry server URL
+        #[arg(long, value_name = "URL")]
+        registry: Option<String>,
+    },
+    /// Run a scenario headless and print its result
+    Run {
+        /// Registry scenario ID, or a local scenario TOML file
+        scenario: String,
+
+        /// Submit the result to the scenario's leaderboard
+        #[arg(long)]
+        submit: bool,
+
+        /// Name to submit the result under
+        #[arg(long, default_value = "anonymous")]
+        player: String,
+
+        /// Registry server URL
+        #[arg(long, value_name = "URL")]
+        registry: Option<String>,
+    },
+    /// Publish a scenario TOML file to the registry
+    Publish {
+        file: String,
+
+        #[arg(long, default_value = "anonymous")]
+        author: String,
+
+        /// Registry server URL
+        #[arg(long, value_name = "URL")]
+        registry: Option<String>,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum BrainCommand {
+    /// Export an entity's brain as a GraphViz DOT or SVG figure
+    ExportDot {
+        /// Entity ID, or a unique prefix of it
+        #[arg(long)]
+        entity: String,
+
+        /// Saved world to read the entity from
+        #[arg(long, default_value = "save.json")]
+        save: String,
+
+        /// Output file (`.svg` renders SVG, anything else DOT); stdout if omitted
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Simulate one tick and color nodes by their current activation
+        #[arg(long)]
+        activations: bool,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -39,13 +329,115 @@ enum Mode {
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
+    // Seed of a fresh world requested with `new` (the inner None keeps the
+    // configured seed).
+    let mut new_world = None;
+    // Saved world whose survivors seed the fresh one.
+    let mut continuation = None;
+
+    match args.command {
+        Some(Command::Brain {
+            action:
+                BrainCommand::ExportDot {
+                    entity,
+                    save,
+                    output,
+                    activations,
+                },
+        }) => return export_brain(&entity, &save, output.as_deref(), activations),
+        Some(Command::Save {
+            action: SaveCommand::Check { save, migrate },
+        }) => return check_save(&save, &args.config, migrate.as_deref()),
+        Some(Command::Challenge { action }) => return challenge(action).await,
+        Some(Command::Optimize {
+            search,
+            checkpoint,
+            best,
+        }) => return optimize(&search, &checkpoint, &best),
+        Some(Command::Card {
+            entity,
+            save,
+            output,
+            dna,
+        }) => return export_card(&entity, &save, output.as_deref(), dna),
+        Some(Command::Crest {
+            lineage,
+            save,
+            output,
+            size,
+        }) => return export_crest(&lineage, &save, output.as_deref(), size),
+        Some(Command::Phylogeny {
+            save,
+            output,
+            phyloxml,
+        }) => return export_phylogeny(&save, output.as_deref(), phyloxml),
+        Some(Command::Provenance {
+            action: ProvenanceCommand::Show { file },
+        }) => return show_provenance(&file),
+        Some(Command::Replay {
+            action: ReplayCommand::Verify { dir, from, to },
+        }) => return verify_replay(&dir, from, to),
+        Some(Command::Doctor { relay }) => return doctor(&args.config, relay.as_deref()),
+        Some(Command::New {
+            seed,
+            random_interesting,
+            continue_from,
+            survivors,
+        }) => {
+            new_world = Some(if random_interesting {
+                Some(pick_interesting_seed()?)
+            } else {
+                seed
+            });
+            continuation = continue_from.map(|save| (save, survivors));
+        }
+        None => {}
+    }
+
+    if let Some(path) = args.record_attract {
+        let config = std::fs::read_to_string(&args.config)
+            .ok()
+            .and_then(|content| AppConfig::from_toml(&content).ok())
+            .unwrap_or_default();
+        let title = std::path::Path::new(&path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.split('.').next())
+            .unwrap_or("untitled")
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_uppercase().chain(chars).collect()
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!(
+            "Recording attract reel '{title}' ({} ticks)...",
+            args.attract_ticks
+        );
+        let reel = AttractReel::record(&title, config, args.attract_ticks)?;
+        std::fs::write(&path, reel.encode()?)?;
+        println!(
+            "Saved {} ticks with {} captions to {path}",
+            reel.ticks,
+            reel.captions.len()
+        );
+        return Ok(());
+    }
 
     if args.benchmark {
         println!("Running in BENCHMARK mode (500 ticks)...");
         let mut app = App::new()?;
+        if let Some(threads) = args.threads {
+            app.set_sim_threads(threads)?;
+        }
+        print_thread_pools(&app);
         let start = std::time::Instant::now();
         for _ in 0..500 {
-            if let Err(e) = app.world.update(&mut app.env) {
+            let (world, env) = (&mut app.world, &mut app.env);
+            if let Err(e) = app.thread_pools.install(|| world.update(env)) {
                 eprintln!("Sim error: {e}");
                 break;
             }
@@ -62,36 +454,206 @@ async fn main() -> Result<()> {
     match args.mode {
         Mode::Headless => {
             println!("Running in HEADLESS mode...");
-            let mut app = App::new()?;
+            let mut app = match new_world {
+                Some(seed) => App::new_world(seed)?,
+                None => App::new()?,
+            };
+            if let Some(threads) = args.threads {
+                app.set_sim_threads(threads)?;
+            }
+            print_thread_pools(&app);
+            for check in app.run_startup_checks() {
+                eprintln!("{check}");
+            }
+            if let Some((save, selection)) = &continuation {
+                println!("{}", continue_from(&mut app, save, *selection)?);
+            }
+            if let Some(tick) = args.reseed_from_archive {
+                println!("{}", reseed_from_archive(&mut app, tick)?);
+            }
             if let Some(url) = args.relay {
                 println!("Connecting to relay: {}...", url);
                 app.connect(&url);
             }
-            while app.running {
+            let mut recorder = match &args.record_replay {
+                Some(dir) => {
+                    let recorder = replay::ReplayRecorder::create(
+                        dir,
+                        &mut app.world,
+                        &app.env,
+                        args.checkpoint_interval,
+                    )?;
+                    println!("Recording replay to {dir}");
+                    Some(recorder)
+                }
+                None => None,
+            };
+            let mut inspector = if app.config.inspector.enabled {
+                let inspector = Inspector::start(&app.config.inspector).await?;
+                if let Some(addr) = inspector.addr() {
+                    println!("Inspector listening on http://{addr}");
+                }
+                Some(inspector)
+            } else {
+                None
+            };
+            let shutdown = ShutdownManager::new();
+            shutdown.listen_for_signals();
+            let format = if args.quiet {
+                StatusFormat::Quiet
+            } else if args.json_status {
+                StatusFormat::Json
+            } else {
+                StatusFormat::Text
+            };
+            let mut progress = Progress::new(
+                format,
+                std::time::Duration::from_secs(args.status_interval.max(1)),
+                app.world.tick,
+                args.ticks,
+                std::time::Instant::now(),
+            );
+            let mut last_inbox_poll = std::time::Instant::now();
+            while app.running
+                && !shutdown.is_shutdown_requested()
+                && !progress.reached_target(app.world.tick)
+            {
+                if last_inbox_poll.elapsed()
+                    >= std::time::Duration::from_millis(
+                        primordium_lib::app::inbox::POLL_INTERVAL_MS,
+                    )
+                {
+                    for result in app.poll_inbox() {
+                        println!(
+                            "Inbox {} at tick {}: {}",
+                            result.file, result.tick, result.message
+                        );
+                    }
+                    last_inbox_poll = std::time::Instant::now();
+                }
+                if let Some(inspector) = &mut inspector {
+                    for result in app.serve_inspector(inspector) {
+                        println!(
+                            "Inspector {} at tick {}: {}",
+                            result.client, result.tick, result.message
+                        );
+                    }
+                }
                 // Background tasks might need a small sleep to not peg CPU at 100% if sim is fast
                 // But for experiments, we want it fast.
-                if let Err(e) = app.world.update(&mut app.env) {
+                let (world, env) = (&mut app.world, &mut app.env);
+                if let Err(e) = app.thread_pools.install(|| world.update(env)) {
                     eprintln!("Sim error: {e}");
                     break;
                 }
-                // Periodic system poll (mocked or reduced frequency in headless)
-                // ... logic to handle headless termination etc.
+                if let Some(rec) = &mut recorder {
+                    if let Err(e) = rec.record(&mut app.world, &app.env) {
+                        eprintln!("Replay recording stopped: {e:#}");
+                        recorder = None;
+                    }
+                }
+                app.update_gallery();
+                if let Some(trip) = &app.world.guardrail_trip {
+                    eprintln!(
+                        "Circuit breaker tripped at tick {}: {} {} > {} (suspected source: {})",
+                        trip.tick,
+                        trip.kind.label(),
+                        trip.observed,
+                        trip.limit,
+                        trip.suspected_source
+                    );
+                    break;
+                }
+                if progress.is_due(std::time::Instant::now()) {
+                    let report = app.progress_report(&mut progress);
+                    if let Some(line) = progress.render(&report) {
+                        println!("{line}");
+                    }
+                }
                 if app.world.get_population_count() == 0 {
                     break;
                 }
             }
+            if format != StatusFormat::Quiet {
+                let report = app.progress_report(&mut progress);
+                if let Some(line) = progress.render(&report) {
+                    println!("{line}");
+                }
+            }
+            if shutdown.is_shutdown_requested() {
+                shutdown.cleanup(&mut app).await?;
+                println!("Saved state at tick {} before exit.", app.world.tick);
+            }
+            if let Some(message) = app.record_gallery_run() {
+                println!("{message}");
+            }
             println!("Headless simulation finished.");
         }
         _ => {
             let mut tui = Tui::new()?;
             tui.init()?;
 
-            let mut app = App::new()?;
+            let mut app = if args.attract {
+                App::new_attract()?
+            } else if matches!(args.mode, Mode::Screensaver) {
+                App::new_screensaver()?
+            } else if let Some(seed) = new_world {
+                App::new_world(seed)?
+            } else {
+                App::new()?
+            };
+            if let Some(threads) = args.threads {
+                app.set_sim_threads(threads)?;
+            }
+            app.run_startup_checks();
+
+            if !app.screensaver {
+                app.open_profile();
+                // Explicit launch choices on the command line skip the menu.
+                if !args.no_menu
+                    && new_world.is_none()
+                    && args.relay.is_none()
+                    && args.replay.is_none()
+                    && args.tutorial.is_none()
+                    && args.reseed_from_archive.is_none()
+                {
+                    app.open_start_menu();
+                }
+            }
+
+            if let Some((save, selection)) = &continuation {
+                match continue_from(&mut app, save, *selection) {
+                    Ok(message) => app
+                        .event_log
+                        .push_back((message, ratatui::style::Color::Green)),
+                    Err(e) => eprintln!("Failed to continue from {save}: {e}"),
+                }
+            }
+
+            if let Some(tick) = args.reseed_from_archive {
+                match reseed_from_archive(&mut app, tick) {
+                    Ok(message) => app
+                        .event_log
+                        .push_back((message, ratatui::style::Color::Green)),
+                    Err(e) => eprintln!("Failed to reseed from genome archive: {e}"),
+                }
+            }
 
             if let Some(url) = args.relay {
                 app.connect(&url);
             }
 
+            if let Some(lesson) = &args.tutorial {
+                if !app.start_tutorial(lesson) {
+                    let available: Vec<String> = Lesson::load_all().into_keys().collect();
+                    eprintln!(
+                        "Unknown lesson '{}'. Available: {}",
+                        lesson,
+                        available.join(", ")
+                    );
+                }
+            }
+
             if let Some(path) = args.replay {
                 if let Err(e) = app.load_replay(&path) {
                     eprintln!("Failed to load replay: {}", e);
@@ -112,9 +674,6 @@ async fn main() -> Result<()> {
                 }
                 _ => {}
             }
-            if matches!(args.mode, Mode::Screensaver) {
-                app.screensaver = true;
-            }
 
             let res = app.run(&mut tui).await;
 
@@ -130,3 +689,367 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+fn check_save(save: &str, config_path: &str, migrate: Option<&str>) -> Result<()> {
+    let config = std::fs::read_to_string(config_path)
+        .ok()
+        .and_then(|content| AppConfig::from_toml(&content).ok())
+        .unwrap_or_default();
+    let mut world = load_world(save)?;
+    let report = CompatibilityReport::check(&world, &config);
+    if report.is_compatible() {
+        println!("{save} is compatible with {config_path} and this build.");
+    }
+    for issue in &report.issues {
+        let marker = if issue.adaptable() { "~" } else { "x" };
+        println!(" {marker} {issue}");
+    }
+    if let Some(output) = migrate {
+        compat::adapt(&mut world, &config, &report)?;
+        save_world(&mut world, output)?;
+        println!("Adapted save written to {output}");
+    } else if !report.is_compatible() && report.can_adapt() {
+        println!("Run with --migrate <FILE> to write an adapted copy.");
+    }
+    Ok(())
+}
+
+fn optimize(search_path: &str, checkpoint: &str, best_path: &str) -> Result<()> {
+    let toml = std::fs::read_to_string(search_path)?;
+    let search = optimize::Search::from_toml(&toml)?;
+    let checkpoint = std::path::Path::new(checkpoint);
+    let mut state = optimize::SearchState::resume(checkpoint, &search, &toml)?;
+    if !state.trials.is_empty() {
+        println!(
+            "Resuming from {} ({} of {} trials done)",
+            checkpoint.display(),
+            state.trials.len(),
+            search.trials
+        );
+    }
+
+    let format_values = |values: &std::collections::BTreeMap<String, f64>| {
+        values
+            .iter()
+            .map(|(key, value)| format!("{key}={value:.4}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    while !state.is_done(&search) {
+        let n = state.trials.len() + 1;
+        let trial = state.advance(&search, |tick| {
+            if tick % 10000 == 0 {
+                eprintln!("trial {n}: tick {tick}");
+            }
+        })?;
+        let outcome = match (trial.score, &trial.error) {
+            (Some(score), _) => format!("{} {score:.2}", search.objective.label()),
+            (None, error) => format!("failed: {}", error.as_deref().unwrap_or("no score")),
+        };
+        println!(
+            "Trial {n}/{}: {outcome}  {}",
+            search.trials,
+            format_values(&trial.values)
+        );
+        state.save(checkpoint)?;
+    }
+
+    let best = state
+        .best(&search)
+        .ok_or_else(|| anyhow::anyhow!("Every trial failed"))?;
+    println!(
+        "Best {} {:.2}  {}",
+        search.objective.label(),
+        best.score.unwrap_or_default(),
+        format_values(&best.values)
+    );
+    for (metric, value) in &best.metrics {
+        println!("  {:<15} {value:.2}", metric.label());
+    }
+    let overrides = search.overrides(&best.values)?;
+    std::fs::write(best_path, toml::to_string(&overrides)?)?;
+    println!("Wrote the best config overrides to {best_path}");
+    Ok(())
+}
+
+fn show_provenance(file: &str) -> Result<()> {
+    let manifests = provenance::read(file)?;
+    if manifests.is_empty() {
+        anyhow::bail!("{file} carries no run manifest");
+    }
+    for (i, manifest) in manifests.iter().enumerate() {
+        if manifests.len() > 1 {
+            println!("Run {} of {}:", i + 1, manifests.len());
+        }
+        println!("{manifest}");
+    }
+    Ok(())
+}
+
+fn verify_replay(dir: &str, from: u64, to: Option<u64>) -> Result<()> {
+    let to = match to {
+        Some(to) => to,
+        None => replay::Recording::open(dir)?
+            .last_tick()
+            .ok_or_else(|| anyhow::anyhow!("{dir} has no recorded ticks"))?,
+    };
+    match replay::resimulate(dir, from, to)? {
+        replay::Verification::Verified {
+            checkpoint,
+            from,
+            to,
+        } => {
+            println!(
+                "Ticks {from}-{to} reproduced bit-exactly (re-simulated from the checkpoint at tick {checkpoint})"
+            );
+            Ok(())
+        }
+        replay::Verification::Diverged {
+            tick,
+            expected,
+            actual,
+        } => anyhow::bail!(
+            "Diverged at tick {tick}: stats hash {actual:016x}, recorded {expected:016x}"
+        ),
+    }
+}
+
+fn doctor(config_path: &str, relay: Option<&str>) -> Result<()> {
+    let checks = doctor::run_all(config_path, relay);
+    for check in &checks {
+        println!("{check}");
+    }
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == doctor::Status::Fail)
+        .count();
+    if failed > 0 {
+        anyhow::bail!("{failed} check(s) failed");
+    }
+    println!("No blocking problems found.");
+    Ok(())
+}
+
+fn export_brain(entity: &str, save: &str, output: Option<&str>, activations: bool) -> Result<()> {
+    let mut world = load_world(save)?;
+    if activations {
+        // Activations are not saved; one tick recomputes them from the saved state.
+        world.update(&mut Environment::default())?;
+    }
+    let (brain, values) = world.brain_of(entity)?;
+    let values = activations.then_some(values.as_slice());
+    match output {
+        Some(path) => {
+            let figure = if path.ends_with(".svg") {
+                export::to_svg(&brain, values)
+            } else {
+                export::to_dot(&brain, values)
+            };
+            std::fs::write(path, figure)?;
+            eprintln!(
+                "Exported brain ({} nodes, {} connections) to {path}",
+                brain.nodes.len(),
+                brain.connections.len()
+            );
+        }
+        None => print!("{}", export::to_dot(&brain, values)),
+    }
+    Ok(())
+}
+
+fn export_card(entity: &str, save: &str, output: Option<&str>, dna: bool) -> Result<()> {
+    let world = load_world(save)?;
+    let card = CreatureCard::new(&world.entity_of(entity)?, world.tick);
+    match output {
+        Some(path) if path.ends_with(".png") => {
+            std::fs::write(path, card.to_png()?)?;
+            eprintln!("Exported {}'s card to {path}", card.name);
+        }
+        Some(path) => {
+            std::fs::write(path, card.to_text(dna))?;
+            eprintln!("Exported {}'s card to {path}", card.name);
+        }
+        None => print!("{}", card.to_text(dna)),
+    }
+    Ok(())
+}
+
+/// A seed from the gallery of interesting runs, announced with its summary.
+fn pick_interesting_seed() -> Result<u64> {
+    let config = App::load_config();
+    let gallery = SeedGallery::load(std::path::Path::new(&config.gallery.path))?;
+    let entry = gallery.pick(&mut rand::thread_rng()).ok_or_else(|| {
+        anyhow::anyhow!(
+            "The seed gallery at {} is empty; finish a run of at least {} ticks first",
+            config.gallery.path,
+            config.gallery.min_ticks
+        )
+    })?;
+    println!(
+        "Seed {} (score {:.1}): {}",
+        entry.seed,
+        entry.score,
+        entry.highlights.summary()
+    );
+    Ok(entry.seed)
+}
+
+fn export_crest(lineage: &str, save: &str, output: Option<&str>, size: u32) -> Result<()> {
+    let world = load_world(save)?;
+    let crest = world.crest_of(lineage)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, crest.to_svg(size))?;
+            eprintln!("Exported crest of lineage {} to {path}", crest.lineage_id);
+        }
+        None => print!("{}", crest.to_svg(size)),
+    }
+    Ok(())
+}
+
+fn export_phylogeny(save: &str, output: Option<&str>, phyloxml: bool) -> Result<()> {
+    let world = load_world(save)?;
+    let tree = LineageTree::build(&world.lineage_registry);
+    let phyloxml = phyloxml || output.is_some_and(|path| path.ends_with(".xml"));
+    let text = if phyloxml {
+        tree.to_phyloxml()
+    } else {
+        tree.to_newick()
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(path, text)?;
+            eprintln!(
+                "Exported the phylogeny of {} lineages to {path}",
+                tree.len()
+            );
+        }
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+async fn challenge(action: ChallengeCommand) -> Result<()> {
+    let registry =
+        |url: Option<String>| RegistryClient::new(url, std::env::var("PRIMORDIUM_API_KEY").ok());
+    match action {
+        ChallengeCommand::List { registry: url } => {
+            let scenarios = registry(url)
+                .get_scenarios()
+                .await
+                .map_err(anyhow::Error::msg)?;
+            for s in &scenarios {
+                println!(
+                    "{}  {} by {} ({} ticks, scored on {}, {} downloads)",
+                    s.id,
+                    s.name,
+                    s.author,
+                    s.ticks,
+                    s.score_metric.label(),
+                    s.downloads
+                );
+            }
+            if scenarios.is_empty() {
+                println!("No scenarios published yet.");
+            }
+        }
+        ChallengeCommand::Run {
+            scenario,
+            submit,
+            player,
+            registry: url,
+        } => {
+            let client = registry(url);
+            let (toml, id) = if std::path::Path::new(&scenario).is_file() {
+                (std::fs::read_to_string(&scenario)?, None)
+            } else {
+                let record = client
+                    .get_scenario(&scenario)
+                    .await
+                    .map_err(anyhow::Error::msg)?;
+                (record.scenario_toml, Some(record.id.to_string()))
+            };
+            if submit && id.is_none() {
+                anyhow::bail!("Only scenarios downloaded from the registry can be submitted");
+            }
+
+            let (challenge, result) = challenge::run(&toml, |tick| {
+                if tick % 1000 == 0 {
+                    eprintln!("tick {tick}");
+                }
+            })?;
+            let evaluation = challenge.evaluate(&result.metrics);
+            println!("{} ({} ticks)", challenge.title, result.ticks);
+            for (metric, value) in &result.metrics {
+                println!("  {:<15} {value:.2}", metric.label());
+            }
+            for target in &challenge.targets {
+                let value = result.metrics.get(&target.metric).copied().unwrap_or(0.0);
+                let mark = if target.is_met(value) { "+" } else { "x" };
+                println!(" {mark} {} {value:.2}", target.metric.label());
+            }
+            println!(
+                "Score {:.2}, {}",
+                evaluation.score,
+                if evaluation.passed {
+                    "passed"
+                } else {
+                    "failed"
+                }
+            );
+            println!("Signature {}", result.signature);
+
+            if let Some(id) = id.filter(|_| submit) {
+                let rank = client
+                    .submit_challenge_result(&id, &player, &result)
+                    .await
+                    .map_err(anyhow::Error::msg)?;
+                println!("Submitted as {player}: rank #{}", rank.rank);
+            }
+        }
+        ChallengeCommand::Publish {
+            file,
+            author,
+            registry: url,
+        } => {
+            let toml = std::fs::read_to_string(&file)?;
+            challenge::Challenge::from_toml(&toml)?;
+            let id = registry(url)
+                .publish_scenario(&toml, &author)
+                .await
+                .map_err(anyhow::Error::msg)?;
+            println!("Published {file} as {id}");
+        }
+    }
+    Ok(())
+}
+
+fn reseed_from_archive(app: &mut App, tick: u64) -> Result<String> {
+    let dir = app.world.config.archive.dir.clone();
+    let entry = app.world.reseed_from_archive(&dir, tick)?;
+    Ok(format!(
+        "Reseeded {} organisms ({} lineages) from the genome dump at tick {}",
+        entry.population, entry.lineages, entry.tick
+    ))
+}
+
+fn continue_from(app: &mut App, save: &str, selection: SurvivorSelection) -> Result<String> {
+    let previous = load_world(save)?;
+    let summary = app.world.continue_from(previous, selection);
+    Ok(format!(
+        "Continued from {save} (tick {}): {} of {} survivors ({} lineages, {} lineage records)",
+        summary.source_tick,
+        summary.survivors,
+        summary.candidates,
+        summary.lineages,
+        summary.records
+    ))
+}
+
+fn print_thread_pools(app: &App) {
+    let pools = app.thread_pools.report();
+    println!(
+        "Thread pools: {} simulation, {} background",
+        pools.sim_threads, pools.io_threads
+    );
+}
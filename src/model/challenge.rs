@@ -0,0 +1,90 @@
+//! Headless runs of registry challenge scenarios.
+
+pub use primordium_io::challenge::*;
+
+use crate::model::config::AppConfig;
+use crate::model::environment::Environment;
+use crate::model::world::World;
+use std::collections::BTreeMap;
+
+/// A finished headless run and what was measured at its end.
+pub struct Run {
+    pub world: World,
+    pub env: Environment,
+    pub metrics: BTreeMap<ChallengeMetric, f64>,
+}
+
+/// Runs `config` headless for `ticks` ticks, or until the population dies
+/// out, and measures the final world. `on_tick` is called with the tick
+/// after every update.
+pub fn simulate(
+    config: AppConfig,
+    ticks: u64,
+    mut on_tick: impl FnMut(u64),
+) -> anyhow::Result<Run> {
+    let mut world = World::new_ephemeral(config.world.initial_population, config)?;
+    let mut env = Environment::default();
+    let mut max_generation = 0;
+
+    while world.tick < ticks && world.get_population_count() > 0 {
+        world.update(&mut env)?;
+        if let Some(trip) = &world.guardrail_trip {
+            anyhow::bail!(
+                "Circuit breaker tripped at tick {}: {} {} > {}",
+                trip.tick,
+                trip.kind.label(),
+                trip.observed,
+                trip.limit
+            );
+        }
+        max_generation = max_generation.max(world.pop_stats.max_generation);
+        on_tick(world.tick);
+    }
+
+    world.refresh_pop_stats(&env);
+    let stats = &world.pop_stats;
+    let max_generation = max_generation.max(stats.max_generation);
+    let metrics = BTreeMap::from([
+        (
+            ChallengeMetric::Population,
+            world.get_population_count() as f64,
+        ),
+        (ChallengeMetric::Species, stats.lineage_counts.len() as f64),
+        (ChallengeMetric::MaxGeneration, f64::from(max_generation)),
+        (ChallengeMetric::TopFitness, stats.top_fitness),
+        (ChallengeMetric::AvgFitness, stats.avg_fitness),
+        (ChallengeMetric::CarbonLevel, env.carbon_level),
+        (ChallengeMetric::SurvivedTicks, world.tick as f64),
+    ]);
+    Ok(Run {
+        world,
+        env,
+        metrics,
+    })
+}
+
+/// Runs the challenge in `scenario_toml` from its seed and measures the
+/// final world. `on_tick` is called with the tick after every update.
+pub fn run(
+    scenario_toml: &str,
+    on_tick: impl FnMut(u64),
+) -> anyhow::Result<(Challenge, ChallengeResult)> {
+    let challenge = Challenge::from_toml(scenario_toml)?;
+    let Run {
+        world,
+        env,
+        metrics,
+    } = simulate(challenge.app_config()?, challenge.ticks, on_tick)?;
+    let world_hash = world.deterministic_hash(&env);
+    let signature = result_signature(scenario_toml, &world_hash, &metrics);
+    Ok((
+        challenge,
+        ChallengeResult {
+            ticks: world.tick,
+            world_hash,
+            metrics,
+            signature,
+            provenance: world.provenance.clone(),
+        },
+    ))
+}
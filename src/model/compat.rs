@@ -0,0 +1,244 @@
+//! Compatibility of a saved world with the running config and build.
+//!
+//! A save carries its own config, but the map size configured now and the
+//! brain layout compiled into this build may differ from the ones it was
+//! written with. Running such a world panics on mismatched grids or feeds
+//! sensors into the wrong neurons. [`CompatibilityReport::check`] lists the
+//! differences and [`adapt`] resolves the ones that can be resolved.
+
+use crate::model::brain::{topology, BRAIN_INPUTS, BRAIN_OUTPUTS};
+use crate::model::config::AppConfig;
+use crate::model::world::World;
+use primordium_data::{Brain, Egg, Entity, Intel, NodeType};
+use std::fmt;
+use std::sync::Arc;
+
+/// One difference between a save and the current config or build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Incompatibility {
+    /// The map is a different size; positions are rescaled onto a freshly
+    /// generated map of the configured size.
+    MapSize {
+        saved: (u16, u16),
+        current: (u16, u16),
+    },
+    /// Brains have fewer sensors or outputs than this build, as
+    /// `(inputs, outputs)`; the missing ones are added unconnected.
+    MissingNodes {
+        saved: (usize, usize),
+        current: (usize, usize),
+    },
+    /// Brains have more sensors or outputs than this build, as
+    /// `(inputs, outputs)`; they cannot be adapted.
+    BrainLayout {
+        saved: (usize, usize),
+        current: (usize, usize),
+    },
+    /// The simulation rules (the sections covered by
+    /// [`AppConfig::fingerprint`]) differ; the config's rules are adopted.
+    Rules { saved: String, current: String },
+}
+
+impl Incompatibility {
+    #[must_use]
+    pub fn adaptable(&self) -> bool {
+        !matches!(self, Incompatibility::BrainLayout { .. })
+    }
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Incompatibility::MapSize { saved, current } => write!(
+                f,
+                "map is {}x{}, config has {}x{} (positions will be rescaled, terrain regenerated)",
+                saved.0, saved.1, current.0, current.1
+            ),
+            Incompatibility::MissingNodes { saved, current } => write!(
+                f,
+                "brains have {} inputs and {} outputs, this build has {} and {} (new nodes will be added unconnected)",
+                saved.0, saved.1, current.0, current.1
+            ),
+            Incompatibility::BrainLayout { saved, current } => write!(
+                f,
+                "brains have {} inputs and {} outputs, this build has {} and {} (cannot be adapted)",
+                saved.0, saved.1, current.0, current.1
+            ),
+            Incompatibility::Rules { saved, current } => write!(
+                f,
+                "rules fingerprint {} differs from config {} (config rules will be adopted)",
+                &saved[..8.min(saved.len())],
+                &current[..8.min(current.len())]
+            ),
+        }
+    }
+}
+
+/// Differences found between a loaded world and a config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompatibilityReport {
+    pub issues: Vec<Incompatibility>,
+}
+
+impl CompatibilityReport {
+    #[must_use]
+    pub fn check(world: &World, config: &AppConfig) -> Self {
+        let mut issues = Vec::new();
+
+        let saved = (world.width, world.height);
+        let current = (config.world.width, config.world.height);
+        if saved != current {
+            issues.push(Incompatibility::MapSize { saved, current });
+        }
+
+        let current = (BRAIN_INPUTS, BRAIN_OUTPUTS);
+        let mut fewest = current;
+        let mut unadaptable = None;
+        let mut inspect = |brain: &Brain| {
+            let layout = brain_layout(brain);
+            if layout.0 > BRAIN_INPUTS || layout.1 > BRAIN_OUTPUTS {
+                unadaptable.get_or_insert(layout);
+            } else {
+                fewest = (fewest.0.min(layout.0), fewest.1.min(layout.1));
+            }
+        };
+        for (_, intel) in world.ecs.query::<&Intel>().iter() {
+            inspect(&intel.genotype.brain);
+        }
+        for (_, egg) in world.ecs.query::<&Egg>().iter() {
+            inspect(&egg.hatchling.intel.genotype.brain);
+        }
+        if let Some(saved) = unadaptable {
+            issues.push(Incompatibility::BrainLayout { saved, current });
+        } else if fewest != current {
+            issues.push(Incompatibility::MissingNodes {
+                saved: fewest,
+                current,
+            });
+        }
+
+        let saved = world.config.fingerprint();
+        let current = config.fingerprint();
+        if saved != current {
+            issues.push(Incompatibility::Rules { saved, current });
+        }
+
+        Self { issues }
+    }
+
+    #[must_use]
+    pub fn is_compatible(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    #[must_use]
+    pub fn can_adapt(&self) -> bool {
+        self.issues.iter().all(Incompatibility::adaptable)
+    }
+}
+
+/// Number of input and output nodes of a brain.
+#[must_use]
+pub fn brain_layout(brain: &Brain) -> (usize, usize) {
+    let count = |kind: NodeType| brain.nodes.iter().filter(|n| n.node_type == kind).count();
+    (count(NodeType::Input), count(NodeType::Output))
+}
+
+/// Resolves every issue in `report`, or fails without touching the world if
+/// any of them cannot be adapted.
+pub fn adapt(
+    world: &mut World,
+    config: &AppConfig,
+    report: &CompatibilityReport,
+) -> anyhow::Result<()> {
+    if let Some(issue) = report.issues.iter().find(|i| !i.adaptable()) {
+        anyhow::bail!("Save is incompatible with this build: {issue}");
+    }
+    for issue in &report.issues {
+        match issue {
+            Incompatibility::Rules { .. } => adopt_rules(&mut world.config, config),
+            Incompatibility::MissingNodes { .. } => pad_brains(world),
+            Incompatibility::MapSize { current, .. } => {
+                rescale_map(world, current.0, current.1)?;
+            }
+            Incompatibility::BrainLayout { .. } => {}
+        }
+    }
+    Ok(())
+}
+
+fn adopt_rules(target: &mut AppConfig, config: &AppConfig) {
+    target.metabolism = config.metabolism.clone();
+    target.evolution = config.evolution.clone();
+    target.brain = config.brain.clone();
+    target.social = config.social.clone();
+    target.terraform = config.terraform.clone();
+    target.ecosystem = config.ecosystem.clone();
+}
+
+fn pad_brains(world: &mut World) {
+    for (_, intel) in world.ecs.query_mut::<&mut Intel>() {
+        topology::pad_to_current(&mut Arc::make_mut(&mut intel.genotype).brain);
+    }
+    for (_, egg) in world.ecs.query_mut::<&mut Egg>() {
+        topology::pad_to_current(&mut Arc::make_mut(&mut egg.hatchling.intel.genotype).brain);
+    }
+    for fossil in &mut world.fossil_registry.fossils {
+        topology::pad_to_current(&mut fossil.genotype.brain);
+    }
+    for record in world.lineage_registry.lineages.values_mut() {
+        if let Some(genotype) = &mut record.max_fitness_genotype {
+            topology::pad_to_current(&mut genotype.brain);
+        }
+    }
+}
+
+/// Moves the population onto a freshly generated `width` x `height` map,
+/// scaling every position by the change in size.
+fn rescale_map(world: &mut World, width: u16, height: u16) -> anyhow::Result<()> {
+    let sx = f64::from(width) / f64::from(world.width);
+    let sy = f64::from(height) / f64::from(world.height);
+    let max_x = f64::from(width) - 1.0;
+    let max_y = f64::from(height) - 1.0;
+    let scale = |e: &mut Entity| {
+        e.physics.x = (e.physics.x * sx).clamp(0.0, max_x);
+        e.physics.y = (e.physics.y * sy).clamp(0.0, max_y);
+        e.physics.home_x = (e.physics.home_x * sx).clamp(0.0, max_x);
+        e.physics.home_y = (e.physics.home_y * sy).clamp(0.0, max_y);
+        e.position.x = e.physics.x;
+        e.position.y = e.physics.y;
+    };
+
+    world.prepare_for_save();
+    world.entity_persist.iter_mut().for_each(scale);
+    for egg in &mut world.egg_persist {
+        scale(&mut egg.hatchling);
+    }
+    for food in &mut world.food_persist {
+        food.x = ((f64::from(food.x) * sx) as u16).min(width - 1);
+        food.y = ((f64::from(food.y) * sy) as u16).min(height - 1);
+    }
+    world.monuments.rescale(sx, sy, width, height);
+
+    world.config.world.width = width;
+    world.config.world.height = height;
+    let mut fresh_config = world.config.clone();
+    fresh_config.world.initial_food = 0;
+    let fresh = World::new_ephemeral(0, fresh_config)?;
+    world.width = width;
+    world.height = height;
+    world.terrain = fresh.terrain;
+    world.pheromones = fresh.pheromones;
+    world.sound = fresh.sound;
+    world.pressure = fresh.pressure;
+    world.influence = fresh.influence;
+    world.roads = fresh.roads;
+    world.mutation_heat = fresh.mutation_heat;
+    world.social_grid = fresh.social_grid;
+    world.cached_rank_grid = fresh.cached_rank_grid;
+    world.food_field = fresh.food_field;
+    world.spatial_hash = fresh.spatial_hash;
+    world.food_hash = fresh.food_hash;
+    world.post_load();
+    Ok(())
+}
@@ -0,0 +1,141 @@
+//! Watching runs for what makes them worth replaying.
+
+pub use primordium_io::seed_gallery::*;
+
+use crate::model::config::GalleryConfig;
+use crate::model::world::World;
+use std::collections::HashSet;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Social grid value of a war zone.
+const WAR_ZONE: u8 = 2;
+
+/// Samples a running world and tallies its [`RunHighlights`].
+#[derive(Debug, Clone)]
+pub struct InterestDetector {
+    seed: u64,
+    first_tick: u64,
+    last_tick: u64,
+    highlights: RunHighlights,
+    split_lineages: HashSet<Uuid>,
+    at_war: bool,
+}
+
+impl InterestDetector {
+    /// Starts watching `world` from its current tick.
+    pub fn new(world: &World) -> Self {
+        Self {
+            seed: world.config.world.seed.unwrap_or(0),
+            first_tick: world.tick,
+            last_tick: world.tick,
+            highlights: RunHighlights::default(),
+            split_lineages: HashSet::new(),
+            at_war: false,
+        }
+    }
+
+    /// Whether `world` is no longer the run being watched: it was reset,
+    /// reloaded or started from another seed.
+    pub fn is_stale(&self, world: &World) -> bool {
+        world.tick < self.last_tick || world.config.world.seed.unwrap_or(0) != self.seed
+    }
+
+    /// Samples the world every `config.sample_interval` ticks.
+    pub fn observe(&mut self, world: &World, config: &GalleryConfig) {
+        if !world.tick.is_multiple_of(config.sample_interval) {
+            return;
+        }
+        self.last_tick = world.tick;
+        let highlights = &mut self.highlights;
+        highlights.ticks = world.tick - self.first_tick;
+
+        for record in world.lineage_registry.lineages.values() {
+            if record.parent_lineage_id.is_some() && record.first_appearance_tick > self.first_tick
+            {
+                self.split_lineages.insert(record.id);
+            }
+            highlights.max_civilization_level = highlights
+                .max_civilization_level
+                .max(record.civilization_level);
+        }
+        highlights.speciations = self.split_lineages.len();
+
+        let at_war = world.social_grid.contains(&WAR_ZONE);
+        if at_war && !self.at_war {
+            highlights.wars += 1;
+        }
+        self.at_war = at_war;
+
+        highlights.peak_lineages = highlights
+            .peak_lineages
+            .max(world.pop_stats.lineage_counts.len());
+        highlights.max_generation = highlights
+            .max_generation
+            .max(world.pop_stats.max_generation);
+    }
+
+    pub fn highlights(&self) -> &RunHighlights {
+        &self.highlights
+    }
+
+    /// Scores the run and adds its seed to the gallery file if it ranks.
+    /// Returns the gallery entry and its rank when kept.
+    pub fn record(&self, config: &GalleryConfig) -> anyhow::Result<Option<(GalleryEntry, usize)>> {
+        if !config.enabled || self.highlights.ticks < config.min_ticks {
+            return Ok(None);
+        }
+        let path = Path::new(&config.path);
+        let mut gallery = SeedGallery::load(path)?;
+        let entry = GalleryEntry::new(self.seed, self.highlights.clone());
+        let Some(rank) = gallery.record(entry.clone(), config.capacity) else {
+            return Ok(None);
+        };
+        gallery.save(path)?;
+        Ok(Some((entry, rank)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::config::AppConfig;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_detector_counts_war_outbreaks_and_records_long_runs() {
+        let mut config = AppConfig::default();
+        config.world.seed = Some(99);
+        let mut world = World::new_ephemeral(0, config).unwrap();
+        let gallery = GalleryConfig {
+            path: std::env::temp_dir()
+                .join(format!("primordium_gallery_{}.json", Uuid::new_v4()))
+                .to_string_lossy()
+                .into_owned(),
+            min_ticks: 100,
+            sample_interval: 10,
+            ..Default::default()
+        };
+        let mut detector = InterestDetector::new(&world);
+
+        for (tick, war) in [(10, true), (20, true), (30, false), (40, true)] {
+            world.tick = tick;
+            Arc::make_mut(&mut world.social_grid)[0] = if war { WAR_ZONE } else { 0 };
+            detector.observe(&world, &gallery);
+        }
+        assert_eq!(detector.highlights().wars, 2);
+        assert_eq!(detector.highlights().ticks, 40);
+        assert!(detector.record(&gallery).unwrap().is_none());
+
+        world.tick = 200;
+        detector.observe(&world, &gallery);
+        let (entry, rank) = detector.record(&gallery).unwrap().unwrap();
+        assert_eq!((entry.seed, rank), (99, 0));
+        let saved = SeedGallery::load(Path::new(&gallery.path)).unwrap();
+        assert_eq!(saved.entries, vec![entry]);
+
+        world.tick = 0;
+        assert!(detector.is_stale(&world));
+        let _ = std::fs::remove_file(&gallery.path);
+    }
+}
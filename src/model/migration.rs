@@ -1,3 +1,4 @@
+use crate::model::brain::GenotypeLogic;
 use crate::model::lifecycle;
 use crate::model::world::World;
 use anyhow::anyhow;
@@ -39,13 +40,7 @@ impl World {
         }
 
         // 2. Validate Integrity
-        use sha2::{Digest, Sha256};
-        let mut hasher = Sha256::new();
-        hasher.update(dna_trimmed.as_bytes());
-        hasher.update(energy.to_be_bytes());
-        hasher.update(generation.to_be_bytes());
-        let real_checksum = hex::encode(hasher.finalize());
-        if checksum != real_checksum {
+        if checksum != primordium_net::migration_checksum(dna_trimmed, energy, generation) {
             anyhow::bail!("Migration checksum mismatch");
         }
 
@@ -42,12 +42,76 @@ pub mod lineage_registry {
     pub use primordium_core::lineage_registry::*;
 }
 
+pub mod energy_ledger {
+    pub use primordium_core::energy_ledger::*;
+}
+pub mod route_trail {
+    pub use primordium_core::route_trail::*;
+}
+pub mod introspection {
+    pub use primordium_core::introspection::*;
+}
+pub mod monument {
+    pub use primordium_core::monument::*;
+}
+pub mod experiment {
+    pub use primordium_core::experiment::*;
+}
+pub mod food_field {
+    pub use primordium_core::food_field::*;
+}
+pub mod guardrail {
+    pub use primordium_core::guardrail::*;
+}
+pub mod threading {
+    pub use primordium_core::threading::*;
+}
 pub mod influence {
     pub use primordium_core::influence::*;
 }
+pub mod road {
+    pub use primordium_core::road::*;
+}
+pub mod mutation_heat {
+    pub use primordium_core::mutation_heat::*;
+}
+pub mod biome {
+    pub use primordium_core::biome::*;
+}
+pub mod body {
+    pub use primordium_core::body::*;
+}
+pub mod schedule {
+    pub use primordium_core::schedule::*;
+}
+pub mod lineage_tree {
+    pub use primordium_core::lineage_tree::*;
+}
+pub mod crest {
+    pub use primordium_core::crest::*;
+}
+pub mod anomaly {
+    pub use primordium_core::anomaly::*;
+}
+pub mod forecast {
+    pub use primordium_core::forecast::*;
+}
+pub mod glyph {
+    pub use primordium_core::glyph::*;
+}
+pub mod palette {
+    pub use primordium_core::palette::*;
+}
+pub mod challenge;
+pub mod compat;
+pub mod gallery;
 pub mod migration;
 pub mod observer;
+pub mod optimize;
 pub mod persistence;
+pub mod provenance;
+pub mod replay;
+pub mod sandbox;
 pub mod world;
 
 pub mod state {
@@ -1,9 +1,19 @@
+#[cfg(feature = "llm")]
+use crate::model::config::NarratorBackend;
+use crate::model::config::{DigestConfig, NarratorConfig};
 use crate::model::environment::Environment;
 use crate::model::lineage_registry::LineageRegistry;
-use primordium_data::PopulationStats;
-use primordium_observer::SiliconScribe;
+use primordium_data::{LiveEvent, Narration, NarrationFilter, PopulationStats};
+#[cfg(feature = "llm")]
+use primordium_io::chronicle::{ChronicleWriter, CHRONICLE_FILE};
+#[cfg(feature = "llm")]
+use primordium_observer::{HeuristicNarrator, LlmNarrator, LlmSettings, Narrator, SiliconScribe};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+#[cfg(feature = "llm")]
+use std::path::Path;
+#[cfg(feature = "llm")]
+use tokio::sync::broadcast;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MacroEvent {
@@ -13,10 +23,72 @@ pub struct MacroEvent {
     pub severity: f32,
 }
 
+/// The narrations one consumer subscribed to, see
+/// [`WorldObserver::subscribe`]. Empty without the `llm` feature.
+pub struct NarrationFeed {
+    #[cfg(feature = "llm")]
+    rx: Option<broadcast::Receiver<Narration>>,
+    closed: bool,
+}
+
+impl Default for NarrationFeed {
+    /// A feed not subscribed to anything, which reports itself closed.
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "llm")]
+            rx: None,
+            closed: true,
+        }
+    }
+}
+
+impl NarrationFeed {
+    /// Narrations received since the last call, oldest first. Those missed
+    /// by falling too far behind are skipped.
+    pub fn drain(&mut self) -> Vec<Narration> {
+        #[allow(unused_mut)]
+        let mut received = Vec::new();
+        #[cfg(feature = "llm")]
+        if let Some(rx) = &mut self.rx {
+            loop {
+                match rx.try_recv() {
+                    Ok(narration) => received.push(narration),
+                    Err(broadcast::error::TryRecvError::Lagged(_)) => {}
+                    Err(broadcast::error::TryRecvError::Empty) => break,
+                    Err(broadcast::error::TryRecvError::Closed) => {
+                        self.closed = true;
+                        break;
+                    }
+                }
+            }
+        }
+        received
+    }
+
+    /// Whether the scribe behind the feed is gone, e.g. because the world
+    /// was replaced; subscribe again to the new one.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+/// Events held back while the observer is in digest mode, see
+/// [`DigestConfig`].
+#[derive(Debug, Clone, Default)]
+struct Digest {
+    start: u64,
+    events: Vec<MacroEvent>,
+}
+
 pub struct WorldObserver {
     pub history: VecDeque<MacroEvent>,
     pub max_history: usize,
+    #[cfg(feature = "llm")]
     pub scribe: SiliconScribe,
+    digest_config: DigestConfig,
+    /// Ticks of the events observed in the last digest window.
+    recent: VecDeque<u64>,
+    digest: Option<Digest>,
     last_population: usize,
     ticks_since_famine: u64,
     last_climate: Option<crate::model::environment::ClimateState>,
@@ -34,7 +106,11 @@ impl WorldObserver {
         Self {
             history: VecDeque::new(),
             max_history: 100,
+            #[cfg(feature = "llm")]
             scribe: SiliconScribe::default(),
+            digest_config: DigestConfig::default(),
+            recent: VecDeque::new(),
+            digest: None,
             last_population: 0,
             ticks_since_famine: 0,
             last_climate: None,
@@ -42,6 +118,62 @@ impl WorldObserver {
         }
     }
 
+    /// An observer narrating through the narrator `config` selects, keeping
+    /// a chronicle in `log_dir` when enabled. Worlds without a log directory
+    /// keep no chronicle.
+    pub fn with_config(config: &NarratorConfig, log_dir: &str) -> Self {
+        #[cfg(feature = "llm")]
+        {
+            let narrator: Box<dyn Narrator> = match config.backend {
+                NarratorBackend::Heuristic => Box::new(HeuristicNarrator),
+                NarratorBackend::Llm => Box::new(LlmNarrator::new(LlmSettings {
+                    base_url: config.base_url.clone(),
+                    model: config.model.clone(),
+                    api_key: Some(config.api_key.clone())
+                        .filter(|key| !key.is_empty())
+                        .or_else(|| std::env::var("PRIMORDIUM_LLM_API_KEY").ok()),
+                    timeout: std::time::Duration::from_millis(config.timeout_ms),
+                    max_tokens: config.max_tokens,
+                })),
+            };
+            let chronicle = (config.chronicle && !log_dir.is_empty())
+                .then(|| ChronicleWriter::open(Path::new(log_dir).join(CHRONICLE_FILE)))
+                .and_then(|writer| {
+                    writer
+                        .map_err(|e| eprintln!("Warning: {e:#}; narrations will not be chronicled"))
+                        .ok()
+                });
+            let scribe = match chronicle {
+                Some(writer) => SiliconScribe::with_chronicle(narrator, writer),
+                None => SiliconScribe::new(narrator),
+            };
+            Self {
+                scribe,
+                digest_config: config.digest,
+                ..Self::new()
+            }
+        }
+        #[cfg(not(feature = "llm"))]
+        {
+            // Without the narrator there is nothing to chronicle.
+            let _ = log_dir;
+            Self {
+                digest_config: config.digest,
+                ..Self::new()
+            }
+        }
+    }
+
+    /// Feeds the narrations `filter` matches to a consumer of its own.
+    #[cfg_attr(not(feature = "llm"), allow(unused_variables))]
+    pub fn subscribe(&self, filter: NarrationFilter) -> NarrationFeed {
+        NarrationFeed {
+            #[cfg(feature = "llm")]
+            rx: Some(self.scribe.subscribe(filter)),
+            closed: false,
+        }
+    }
+
     pub fn observe(
         &mut self,
         tick: u64,
@@ -49,6 +181,8 @@ impl WorldObserver {
         _registry: &LineageRegistry,
         env: &Environment,
     ) {
+        self.update_digest(tick);
+
         let current_pop = stats.population;
         let current_climate = env.climate();
         let current_era = env.current_era;
@@ -106,19 +240,150 @@ impl WorldObserver {
         self.last_population = current_pop;
     }
 
-    fn record_event(&mut self, tick: u64, etype: &str, desc: &str, severity: f32) {
-        if self.history.len() >= self.max_history {
-            self.history.pop_front();
+    /// Narrates the live events worth a story: wars and treaties between
+    /// lineages and raised monuments, named from `registry`.
+    pub fn observe_event(&mut self, event: &LiveEvent, registry: &LineageRegistry) {
+        let name = |id: &uuid::Uuid| {
+            registry
+                .lineages
+                .get(id)
+                .map_or_else(|| id.to_string()[..4].to_string(), |r| r.name.clone())
+        };
+        match event {
+            LiveEvent::WarDeclared {
+                lineage_a,
+                lineage_b,
+                tick,
+                ..
+            } => self.record_event(
+                *tick,
+                "WarEvent",
+                &format!("{} and {} went to war.", name(lineage_a), name(lineage_b)),
+                0.7,
+            ),
+            LiveEvent::PeaceTreaty {
+                lineage_a,
+                lineage_b,
+                alliance,
+                tick,
+                ..
+            } => self.record_event(
+                *tick,
+                "PeaceTreaty",
+                &format!(
+                    "{} and {} {}.",
+                    name(lineage_a),
+                    name(lineage_b),
+                    if *alliance {
+                        "formed an alliance"
+                    } else {
+                        "made peace"
+                    }
+                ),
+                0.5,
+            ),
+            LiveEvent::MonumentRaised {
+                lineage_id,
+                x,
+                y,
+                tick,
+                ..
+            } => self.record_event(
+                *tick,
+                "Monument",
+                &format!("{} raised a monument at ({}, {}).", name(lineage_id), x, y),
+                0.6,
+            ),
+            LiveEvent::SeasonChanged { from, to, tick, .. } => self.record_event(
+                *tick,
+                "SeasonChange",
+                &format!("{} gave way to {}.", from, to),
+                0.3,
+            ),
+            _ => {}
         }
-        self.history.push_back(MacroEvent {
+    }
+
+    /// Whether events are currently folded into digests rather than
+    /// narrated one by one.
+    pub fn is_digesting(&self) -> bool {
+        self.digest.is_some()
+    }
+
+    fn record_event(&mut self, tick: u64, etype: &str, desc: &str, severity: f32) {
+        let event = MacroEvent {
             tick,
             event_type: etype.to_string(),
             description: desc.to_string(),
             severity,
-        });
+        };
+        self.push_history(event.clone());
 
+        let config = self.digest_config;
+        self.recent.push_back(tick);
+        self.forget_before(tick.saturating_sub(config.window));
+        if config.enabled && self.digest.is_none() && self.recent.len() >= config.enter_events {
+            self.digest = Some(Digest {
+                start: tick,
+                events: Vec::new(),
+            });
+        }
+        match &mut self.digest {
+            Some(digest) => digest.events.push(event),
+            None => self.narrate(&event),
+        }
+    }
+
+    /// Narrates the digest gathered once its window has passed, and leaves
+    /// digest mode when the world has quietened down.
+    fn update_digest(&mut self, tick: u64) {
+        let window = self.digest_config.window;
+        self.forget_before(tick.saturating_sub(window));
+        if self.digest.as_ref().is_none_or(|d| tick < d.start + window) {
+            return;
+        }
+        let digest = self.digest.take().unwrap_or_default();
+        if !digest.events.is_empty() {
+            let summary = MacroEvent {
+                tick,
+                event_type: "Digest".to_string(),
+                description: summarize(&digest.events, tick - digest.start),
+                severity: digest.events.iter().map(|e| e.severity).fold(0.0, f32::max),
+            };
+            self.push_history(summary.clone());
+            self.narrate(&summary);
+        }
+        if self.digest_config.enabled && self.recent.len() >= self.digest_config.exit_events {
+            self.digest = Some(Digest {
+                start: tick,
+                events: Vec::new(),
+            });
+        }
+    }
+
+    fn forget_before(&mut self, tick: u64) {
+        while self.recent.front().is_some_and(|&t| t < tick) {
+            self.recent.pop_front();
+        }
+    }
+
+    fn push_history(&mut self, event: MacroEvent) {
+        if self.history.len() >= self.max_history {
+            self.history.pop_front();
+        }
+        self.history.push_back(event);
+    }
+
+    #[cfg_attr(not(feature = "llm"), allow(unused_variables))]
+    fn narrate(&self, event: &MacroEvent) {
         // Use Silicon Scribe for narration
-        self.scribe.narrate(tick, etype, desc, severity);
+        #[cfg(feature = "llm")]
+        self.scribe.narrate(
+            event.tick,
+            &event.event_type,
+            &event.description,
+            event.severity,
+        );
     }
 
     pub fn generate_macro_report(&self) -> String {
@@ -134,6 +399,13 @@ impl WorldObserver {
                 ));
             }
         }
+        #[cfg(feature = "llm")]
+        self.append_narrations(&mut report);
+        report
+    }
+
+    #[cfg(feature = "llm")]
+    fn append_narrations(&self, report: &mut String) {
         report.push_str("\n--- SILICON SCRIBE NARRATIONS ---\n");
         if let Ok(narrations) = self.scribe.narrations.lock() {
             if narrations.is_empty() {
@@ -146,10 +418,97 @@ impl WorldObserver {
         } else {
             report.push_str("Error: Narration lock is poisoned.\n");
         }
-        report
     }
 
+    #[cfg(feature = "llm")]
     pub fn consume_narrations(&mut self) -> Vec<primordium_observer::Narration> {
         self.scribe.consume_narrations()
     }
 }
+
+/// One sentence summing up `events` observed over `span` ticks, e.g. "In 500
+/// ticks of upheaval, three wars broke out and a monument was raised."
+fn summarize(events: &[MacroEvent], span: u64) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for event in events {
+        match counts.iter_mut().find(|(t, _)| *t == event.event_type) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((&event.event_type, 1)),
+        }
+    }
+    let mut others = 0;
+    let mut clauses: Vec<String> = Vec::new();
+    for (etype, n) in counts {
+        let (one, many) = match etype {
+            "ExtinctionEvent" => (
+                "the population collapsed",
+                "the population collapsed {} times",
+            ),
+            "GreatFamine" => ("famine struck", "famine struck {} times"),
+            "ClimateShift" => ("the climate shifted", "the climate shifted {} times"),
+            "NewEra" => ("a new era dawned", "{} eras came and went"),
+            "WarEvent" => ("a war broke out", "{} wars broke out"),
+            "PeaceTreaty" => ("a treaty was signed", "{} treaties were signed"),
+            "Monument" => ("a monument was raised", "{} monuments were raised"),
+            "SeasonChange" => ("the season turned", "{} seasons passed"),
+            _ => {
+                others += n;
+                continue;
+            }
+        };
+        clauses.push(if n == 1 {
+            one.to_string()
+        } else {
+            many.replace("{}", count_word(n).as_str())
+        });
+    }
+    match others {
+        0 => {}
+        1 => clauses.push("one more event unfolded".to_string()),
+        n => clauses.push(format!("{} more events unfolded", count_word(n))),
+    }
+    let list = match clauses.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        None => String::new(),
+    };
+    format!("In {} ticks of upheaval, {}.", span, list)
+}
+
+fn count_word(n: usize) -> String {
+    const WORDS: [&str; 11] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    WORDS
+        .get(n)
+        .map_or_else(|| n.to_string(), |w| w.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: &str) -> MacroEvent {
+        MacroEvent {
+            tick: 0,
+            event_type: event_type.to_string(),
+            description: String::new(),
+            severity: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_summarize_counts_events_by_kind() {
+        let events = [
+            event("WarEvent"),
+            event("Monument"),
+            event("WarEvent"),
+            event("WarEvent"),
+            event("Mystery"),
+        ];
+        assert_eq!(
+            summarize(&events, 500),
+            "In 500 ticks of upheaval, three wars broke out, a monument was raised and one more event unfolded."
+        );
+    }
+}
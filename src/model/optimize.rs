@@ -0,0 +1,423 @@
+//! Meta-optimisation of config parameters.
+//!
+//! A search file names config keys to tune, the range each may take, a
+//! [`ChallengeMetric`] to maximise (or minimise) and how long each
+//! headless run lasts:
+//!
+//! ```toml
+//! objective = "species"
+//! ticks = 50000
+//! trials = 40
+//! seed = 42
+//! runs = 2
+//!
+//! [[params]]
+//! key = "evolution.mutation_rate"
+//! min = 0.01
+//! max = 0.3
+//!
+//! [config.world]
+//! width = 80
+//! ```
+//!
+//! The search is an adaptive random search. The first trial measures the
+//! default value of every parameter; each later one perturbs the best values
+//! so far by up to `step` of their range. The step grows after an
+//! improvement and shrinks otherwise. A trial scores the mean objective over
+//! `runs` deterministic runs seeded from `seed`, so every trial faces the
+//! same worlds.
+//!
+//! Trial `n` draws its perturbation from `seed` and `n`, so a search
+//! resumed from its [`SearchState`] checkpoint continues exactly as an
+//! uninterrupted one would.
+
+use crate::model::challenge::{self, merge_tables, ChallengeMetric, MAX_CHALLENGE_TICKS};
+use crate::model::config::AppConfig;
+use anyhow::Context;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Smallest perturbation step, as a share of a parameter's range.
+const MIN_STEP: f64 = 0.01;
+
+/// A config value the search may change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Param {
+    /// Dotted config key, e.g. `evolution.mutation_rate`.
+    pub key: String,
+    pub min: f64,
+    pub max: f64,
+}
+
+fn default_runs() -> u64 {
+    1
+}
+
+fn default_step() -> f64 {
+    0.25
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Search {
+    pub objective: ChallengeMetric,
+    /// Lower objective values are better.
+    #[serde(default)]
+    pub minimize: bool,
+    /// Length of each run.
+    pub ticks: u64,
+    /// Trials to run in all, the baseline included.
+    pub trials: usize,
+    pub seed: u64,
+    /// Runs per trial, seeded `seed`, `seed + 1`, ...
+    #[serde(default = "default_runs")]
+    pub runs: u64,
+    /// Initial perturbation, as a share of each parameter's range.
+    #[serde(default = "default_step")]
+    pub step: f64,
+    pub params: Vec<Param>,
+    /// Fixed overrides applied on top of the default config.
+    #[serde(default)]
+    pub config: toml::Table,
+}
+
+impl Search {
+    pub fn from_toml(content: &str) -> anyhow::Result<Self> {
+        let search: Search = toml::from_str(content)?;
+        anyhow::ensure!(
+            (1..=MAX_CHALLENGE_TICKS).contains(&search.ticks),
+            "Search runs must last between 1 and {} ticks",
+            MAX_CHALLENGE_TICKS
+        );
+        anyhow::ensure!(
+            search.trials > 0 && search.runs > 0,
+            "Search trials and runs must be positive"
+        );
+        anyhow::ensure!(
+            search.step > 0.0 && search.step <= 1.0,
+            "Search step must be in (0, 1]"
+        );
+        anyhow::ensure!(!search.params.is_empty(), "Search needs at least one param");
+        for param in &search.params {
+            anyhow::ensure!(param.min < param.max, "Param {} needs min < max", param.key);
+        }
+        search.app_config(&search.baseline()?, search.seed)?;
+        Ok(search)
+    }
+
+    /// The default config with the fixed overrides applied.
+    fn base_table(&self) -> anyhow::Result<toml::Table> {
+        let mut table = toml::Table::try_from(AppConfig::default())?;
+        merge_tables(&mut table, &self.config);
+        Ok(table)
+    }
+
+    /// The value of every parameter before tuning, clamped to its range.
+    pub fn baseline(&self) -> anyhow::Result<BTreeMap<String, f64>> {
+        let table = self.base_table()?;
+        self.params
+            .iter()
+            .map(|param| {
+                let value = match lookup(&table, &param.key)? {
+                    toml::Value::Integer(i) => *i as f64,
+                    toml::Value::Float(f) => *f,
+                    other => anyhow::bail!(
+                        "Param {} is a {}, not a number",
+                        param.key,
+                        other.type_str()
+                    ),
+                };
+                Ok((param.key.clone(), value.clamp(param.min, param.max)))
+            })
+            .collect()
+    }
+
+    /// The fixed overrides with `values` set on top, as config TOML.
+    pub fn overrides(&self, values: &BTreeMap<String, f64>) -> anyhow::Result<toml::Table> {
+        let base = self.base_table()?;
+        let mut overrides = self.config.clone();
+        for (key, &value) in values {
+            let value = match lookup(&base, key)? {
+                toml::Value::Integer(_) => toml::Value::Integer(value.round() as i64),
+                _ => toml::Value::Float(value),
+            };
+            set(&mut overrides, key, value);
+        }
+        Ok(overrides)
+    }
+
+    /// The config of the run seeded `seed` with `values` applied.
+    pub fn app_config(
+        &self,
+        values: &BTreeMap<String, f64>,
+        seed: u64,
+    ) -> anyhow::Result<AppConfig> {
+        let mut table = self.base_table()?;
+        merge_tables(&mut table, &self.overrides(values)?);
+        let mut config: AppConfig = table.try_into()?;
+        config.world.seed = Some(seed);
+        config.world.deterministic = true;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Whether `score` beats `other`.
+    fn better(&self, score: f64, other: f64) -> bool {
+        if self.minimize {
+            score < other
+        } else {
+            score > other
+        }
+    }
+}
+
+fn lookup<'a>(table: &'a toml::Table, key: &str) -> anyhow::Result<&'a toml::Value> {
+    let mut parts = key.split('.');
+    let first = parts.next().unwrap_or_default();
+    let mut value = table.get(first);
+    for part in parts {
+        value = value.and_then(|v| v.as_table()).and_then(|t| t.get(part));
+    }
+    value.with_context(|| format!("Unknown config key {}", key))
+}
+
+fn set(table: &mut toml::Table, key: &str, value: toml::Value) {
+    match key.split_once('.') {
+        Some((head, rest)) => {
+            let entry = table
+                .entry(head)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if !entry.is_table() {
+                *entry = toml::Value::Table(toml::Table::new());
+            }
+            if let toml::Value::Table(inner) = entry {
+                set(inner, rest, value);
+            }
+        }
+        None => {
+            table.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// One measured point of the search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trial {
+    pub values: BTreeMap<String, f64>,
+    /// Mean objective over the runs; `None` if a run failed.
+    pub score: Option<f64>,
+    /// Mean of every metric over the runs.
+    #[serde(default)]
+    pub metrics: BTreeMap<ChallengeMetric, f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Runs `values` once per seed of `search` and averages the metrics.
+pub fn run_trial(
+    search: &Search,
+    values: BTreeMap<String, f64>,
+    mut on_tick: impl FnMut(u64),
+) -> Trial {
+    let mut totals: BTreeMap<ChallengeMetric, f64> = BTreeMap::new();
+    for seed in search.seed..search.seed + search.runs {
+        let run = search
+            .app_config(&values, seed)
+            .and_then(|config| challenge::simulate(config, search.ticks, &mut on_tick));
+        match run {
+            Ok(run) => {
+                for (metric, value) in run.metrics {
+                    *totals.entry(metric).or_default() += value;
+                }
+            }
+            Err(e) => {
+                return Trial {
+                    values,
+                    score: None,
+                    metrics: BTreeMap::new(),
+                    error: Some(format!("Seed {}: {:#}", seed, e)),
+                }
+            }
+        }
+    }
+    let metrics: BTreeMap<ChallengeMetric, f64> = totals
+        .into_iter()
+        .map(|(metric, total)| (metric, total / search.runs as f64))
+        .collect();
+    Trial {
+        values,
+        score: metrics.get(&search.objective).copied(),
+        metrics,
+        error: None,
+    }
+}
+
+/// Progress of a search, checkpointed after every trial.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchState {
+    /// Fingerprint of the search file, so a checkpoint is only resumed by
+    /// the search that wrote it.
+    pub search_hash: String,
+    pub trials: Vec<Trial>,
+    /// Current perturbation step.
+    pub step: f64,
+}
+
+impl SearchState {
+    pub fn new(search: &Search, search_toml: &str) -> Self {
+        Self {
+            search_hash: search_hash(search_toml),
+            trials: Vec::new(),
+            step: search.step,
+        }
+    }
+
+    /// Resumes the checkpoint at `path`, or starts afresh if there is none.
+    pub fn resume(path: &Path, search: &Search, search_toml: &str) -> anyhow::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::new(search, search_toml))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let state: SearchState = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid checkpoint {}", path.display()))?;
+        anyhow::ensure!(
+            state.search_hash == search_hash(search_toml),
+            "Checkpoint {} belongs to a different search; move it aside to start over",
+            path.display()
+        );
+        Ok(state)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    pub fn is_done(&self, search: &Search) -> bool {
+        self.trials.len() >= search.trials
+    }
+
+    /// The best scoring trial so far.
+    pub fn best(&self, search: &Search) -> Option<&Trial> {
+        self.trials
+            .iter()
+            .filter(|t| t.score.is_some())
+            .reduce(|best, t| {
+                if search.better(t.score.unwrap_or_default(), best.score.unwrap_or_default()) {
+                    t
+                } else {
+                    best
+                }
+            })
+    }
+
+    /// The parameter values of the next trial.
+    pub fn propose(&self, search: &Search) -> anyhow::Result<BTreeMap<String, f64>> {
+        let Some(best) = self.best(search) else {
+            return search.baseline();
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(search.seed ^ ((self.trials.len() as u64) << 32));
+        Ok(search
+            .params
+            .iter()
+            .map(|param| {
+                let range = param.max - param.min;
+                let from = best.values.get(&param.key).copied().unwrap_or(param.min);
+                let value = from + rng.gen_range(-1.0..=1.0) * self.step * range;
+                (param.key.clone(), value.clamp(param.min, param.max))
+            })
+            .collect())
+    }
+
+    /// Adds `trial`, widening the step if it beat the best so far and
+    /// narrowing it otherwise.
+    pub fn record(&mut self, search: &Search, trial: Trial) {
+        let improved = match (trial.score, self.best(search).and_then(|b| b.score)) {
+            (Some(score), Some(best)) => search.better(score, best),
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if !self.trials.is_empty() {
+            self.step = if improved {
+                (self.step * 1.5).min(1.0)
+            } else {
+                (self.step * 0.9).max(MIN_STEP)
+            };
+        }
+        self.trials.push(trial);
+    }
+
+    /// Proposes, runs and records the next trial.
+    pub fn advance(&mut self, search: &Search, on_tick: impl FnMut(u64)) -> anyhow::Result<&Trial> {
+        let values = self.propose(search)?;
+        let trial = run_trial(search, values, on_tick);
+        self.record(search, trial);
+        Ok(self.trials.last().expect("trial was just recorded"))
+    }
+}
+
+fn search_hash(search_toml: &str) -> String {
+    primordium_net::fingerprint([search_toml])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEARCH: &str = r#"
+        objective = "population"
+        ticks = 10
+        trials = 5
+        seed = 7
+
+        [[params]]
+        key = "world.initial_population"
+        min = 10
+        max = 60
+
+        [config.world]
+        width = 40
+        height = 20
+    "#;
+
+    #[test]
+    fn test_overrides_keep_integer_keys_integral() {
+        let search = Search::from_toml(SEARCH).unwrap();
+        let values = BTreeMap::from([("world.initial_population".to_string(), 23.6)]);
+        let config = search.app_config(&values, 3).unwrap();
+        assert_eq!(config.world.initial_population, 24);
+        assert_eq!(config.world.width, 40);
+        assert_eq!(config.world.seed, Some(3));
+        assert!(Search::from_toml(&SEARCH.replace("world.initial", "world.nope")).is_err());
+    }
+
+    #[test]
+    fn test_step_adapts_to_improvements() {
+        let search = Search::from_toml(SEARCH).unwrap();
+        let mut state = SearchState::new(&search, SEARCH);
+        let trial = |score| Trial {
+            values: BTreeMap::from([("world.initial_population".to_string(), 30.0)]),
+            score,
+            metrics: BTreeMap::new(),
+            error: None,
+        };
+        state.record(&search, trial(Some(5.0)));
+        assert_eq!(state.step, search.step);
+        state.record(&search, trial(Some(9.0)));
+        assert!(state.step > search.step);
+        let widened = state.step;
+        state.record(&search, trial(None));
+        assert!(state.step < widened);
+        assert_eq!(state.best(&search).unwrap().score, Some(9.0));
+
+        let next = state.propose(&search).unwrap();
+        assert!((10.0..=60.0).contains(&next["world.initial_population"]));
+    }
+}
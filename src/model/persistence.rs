@@ -1,11 +1,29 @@
+use crate::model::compat::{self, CompatibilityReport};
+use crate::model::config::AppConfig;
+use crate::model::influence::InfluenceGrid;
+use crate::model::pheromone::{LegacyPheromoneGrid, PheromoneGrid};
+use crate::model::pressure::PressureGrid;
+use crate::model::road::RoadGrid;
+use crate::model::sound::SoundGrid;
+use crate::model::terrain::{LegacyTerrainGrid, TerrainGrid};
 use crate::model::world::World;
 use anyhow::{Context, Result};
+use primordium_data::{Food, HallOfFame, LegacyFood, LegacyHallOfFame, PopulationStats};
+use primordium_io::persistence::{
+    decode_rkyv, decode_snapshot, encode_rkyv, encode_snapshot, snapshot_version,
+    SnapshotMigrations,
+};
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
 
 const CURRENT_SAVE_VERSION: u32 = 1;
 
+/// Schema of the binary snapshots this build writes.
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 5;
+
 /// Struct used for saving the world state with versioning metadata.
 /// Holds a reference to the world to avoid cloning.
 #[derive(Serialize)]
@@ -22,8 +40,177 @@ pub struct SaveState {
     pub world: World,
 }
 
-/// Saves the world to a file with versioning metadata.
+/// Binary snapshot of a world (schema 5): the grids, food and statistics as
+/// rkyv, everything else as a JSON save of the world without them.
+#[derive(Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+struct WorldSnapshotV5 {
+    food: Vec<Food>,
+    terrain: TerrainGrid,
+    pheromones: PheromoneGrid,
+    sound: SoundGrid,
+    pressure: PressureGrid,
+    influence: InfluenceGrid,
+    roads: RoadGrid,
+    pop_stats: PopulationStats,
+    hall_of_fame: HallOfFame,
+    world: Vec<u8>,
+}
+
+/// Schema 4, frozen: genotypes had no body size gene.
+#[derive(Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+struct WorldSnapshotV4 {
+    food: Vec<Food>,
+    terrain: TerrainGrid,
+    pheromones: PheromoneGrid,
+    sound: SoundGrid,
+    pressure: PressureGrid,
+    influence: InfluenceGrid,
+    roads: RoadGrid,
+    pop_stats: PopulationStats,
+    hall_of_fame: LegacyHallOfFame,
+    world: Vec<u8>,
+}
+
+/// Schema 3, frozen: food carried no plant genome.
+#[derive(Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+struct WorldSnapshotV3 {
+    food: Vec<LegacyFood>,
+    terrain: TerrainGrid,
+    pheromones: PheromoneGrid,
+    sound: SoundGrid,
+    pressure: PressureGrid,
+    influence: InfluenceGrid,
+    roads: RoadGrid,
+    pop_stats: PopulationStats,
+    hall_of_fame: LegacyHallOfFame,
+    world: Vec<u8>,
+}
+
+/// Schema 2, frozen: terrain had no water layer.
+#[derive(Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+struct WorldSnapshotV2 {
+    food: Vec<LegacyFood>,
+    terrain: LegacyTerrainGrid,
+    pheromones: PheromoneGrid,
+    sound: SoundGrid,
+    pressure: PressureGrid,
+    influence: InfluenceGrid,
+    roads: RoadGrid,
+    pop_stats: PopulationStats,
+    hall_of_fame: LegacyHallOfFame,
+    world: Vec<u8>,
+}
+
+/// Schema 1, frozen: pheromones had five fixed channels.
+#[derive(Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+struct WorldSnapshotV1 {
+    food: Vec<LegacyFood>,
+    terrain: LegacyTerrainGrid,
+    pheromones: LegacyPheromoneGrid,
+    sound: SoundGrid,
+    pressure: PressureGrid,
+    influence: InfluenceGrid,
+    roads: RoadGrid,
+    pop_stats: PopulationStats,
+    hall_of_fame: LegacyHallOfFame,
+    world: Vec<u8>,
+}
+
+/// Upgrades from older snapshot schemas.
+///
+/// A change to the archived types above breaks every snapshot written
+/// before it. Such a change bumps [`SNAPSHOT_SCHEMA_VERSION`], keeps a frozen
+/// copy of the old layout and registers a hook here that decodes it and
+/// re-encodes it in the new one. Every schema since 4 has a snapshot in
+/// `tests/fixtures`, so a change that skips this fails the tests.
+pub fn snapshot_migrations() -> SnapshotMigrations {
+    SnapshotMigrations::new(SNAPSHOT_SCHEMA_VERSION)
+        .with(1, |payload| {
+            let v1: WorldSnapshotV1 = decode_rkyv(payload)?;
+            let v2 = WorldSnapshotV2 {
+                food: v1.food,
+                terrain: v1.terrain,
+                pheromones: v1.pheromones.into(),
+                sound: v1.sound,
+                pressure: v1.pressure,
+                influence: v1.influence,
+                roads: v1.roads,
+                pop_stats: v1.pop_stats,
+                hall_of_fame: v1.hall_of_fame,
+                world: v1.world,
+            };
+            Ok(encode_rkyv(&v2)?.into_vec())
+        })
+        .with(2, |payload| {
+            let v2: WorldSnapshotV2 = decode_rkyv(payload)?;
+            let v3 = WorldSnapshotV3 {
+                food: v2.food,
+                terrain: v2.terrain.into(),
+                pheromones: v2.pheromones,
+                sound: v2.sound,
+                pressure: v2.pressure,
+                influence: v2.influence,
+                roads: v2.roads,
+                pop_stats: v2.pop_stats,
+                hall_of_fame: v2.hall_of_fame,
+                world: v2.world,
+            };
+            Ok(encode_rkyv(&v3)?.into_vec())
+        })
+        .with(3, |payload| {
+            let v3: WorldSnapshotV3 = decode_rkyv(payload)?;
+            let v4 = WorldSnapshotV4 {
+                food: v3.food.into_iter().map(Food::from).collect(),
+                terrain: v3.terrain,
+                pheromones: v3.pheromones,
+                sound: v3.sound,
+                pressure: v3.pressure,
+                influence: v3.influence,
+                roads: v3.roads,
+                pop_stats: v3.pop_stats,
+                hall_of_fame: v3.hall_of_fame,
+                world: v3.world,
+            };
+            Ok(encode_rkyv(&v4)?.into_vec())
+        })
+        .with(4, |payload| {
+            let v4: WorldSnapshotV4 = decode_rkyv(payload)?;
+            let v5 = WorldSnapshotV5 {
+                food: v4.food,
+                terrain: v4.terrain,
+                pheromones: v4.pheromones,
+                sound: v4.sound,
+                pressure: v4.pressure,
+                influence: v4.influence,
+                roads: v4.roads,
+                pop_stats: v4.pop_stats,
+                hall_of_fame: v4.hall_of_fame.into(),
+                world: v4.world,
+            };
+            Ok(encode_rkyv(&v5)?.into_vec())
+        })
+}
+
+/// Saves the world to a file with versioning metadata: a binary snapshot
+/// when the path ends in `.bin`, JSON otherwise.
 pub fn save_world(world: &mut World, path: impl AsRef<Path>) -> Result<()> {
+    let binary = path.as_ref().extension().is_some_and(|ext| ext == "bin");
+    let data = if binary {
+        encode_world_snapshot(world)?
+    } else {
+        encode_world(world)?
+    };
+    fs::write(path, data).context("Failed to write save file")?;
+    Ok(())
+}
+
+/// Encodes the world as [`save_world`] writes it.
+pub fn encode_world(world: &mut World) -> Result<Vec<u8>> {
     // Ensure transient state is prepared for serialization
     world.prepare_for_save();
 
@@ -32,27 +219,102 @@ pub fn save_world(world: &mut World, path: impl AsRef<Path>) -> Result<()> {
         world,
     };
 
-    let data = serde_json::to_string_pretty(&state).context("Failed to serialize save state")?;
+    let data = serde_json::to_vec_pretty(&state).context("Failed to serialize save state");
+    // Entities stay live in the ECS; drop the copies made for the save.
+    world.entity_persist.clear();
+    world.tag_persist.clear();
+    data
+}
 
-    fs::write(path, data).context("Failed to write save file")?;
+/// Encodes the world as a versioned binary snapshot.
+pub fn encode_world_snapshot(world: &mut World) -> Result<Vec<u8>> {
+    world.prepare_for_save();
 
-    Ok(())
+    // Move the bulky parts out so the JSON part leaves them empty.
+    let food = std::mem::take(&mut world.food_persist);
+    let terrain = std::mem::take(&mut world.terrain);
+    let pheromones = std::mem::take(&mut world.pheromones);
+    let sound = std::mem::take(&mut world.sound);
+    let pressure = std::mem::take(&mut world.pressure);
+    let influence = std::mem::take(&mut world.influence);
+    let roads = std::mem::take(&mut world.roads);
+    let pop_stats = std::mem::take(&mut world.pop_stats);
+    let hall_of_fame = std::mem::take(&mut world.hall_of_fame);
+    let json = serde_json::to_vec(&SaveStateRef {
+        version: CURRENT_SAVE_VERSION,
+        world,
+    });
+    world.entity_persist.clear();
+    world.tag_persist.clear();
+
+    let snapshot = json
+        .context("Failed to serialize save state")
+        .and_then(|json| {
+            let snapshot = WorldSnapshotV5 {
+                food: food.clone(),
+                terrain: (*terrain).clone(),
+                pheromones: (*pheromones).clone(),
+                sound: (*sound).clone(),
+                pressure: (*pressure).clone(),
+                influence: (*influence).clone(),
+                roads: (*roads).clone(),
+                pop_stats: (*pop_stats).clone(),
+                hall_of_fame: (*hall_of_fame).clone(),
+                world: json,
+            };
+            encode_snapshot(&snapshot, SNAPSHOT_SCHEMA_VERSION)
+                .context("Failed to encode world snapshot")
+        });
+
+    world.food_persist = food;
+    world.terrain = terrain;
+    world.pheromones = pheromones;
+    world.sound = sound;
+    world.pressure = pressure;
+    world.influence = influence;
+    world.roads = roads;
+    world.pop_stats = pop_stats;
+    world.hall_of_fame = hall_of_fame;
+    snapshot
+}
+
+/// Decodes a binary snapshot, migrating an older schema first.
+fn decode_world_snapshot(content: &[u8]) -> Result<World> {
+    let snapshot: WorldSnapshotV5 = decode_snapshot(content, &snapshot_migrations())
+        .context("Failed to decode world snapshot")?;
+    let mut world = decode_world(&snapshot.world)?;
+    world.food_persist = snapshot.food;
+    world.terrain = Arc::new(snapshot.terrain);
+    world.pheromones = Arc::new(snapshot.pheromones);
+    world.sound = Arc::new(snapshot.sound);
+    world.pressure = Arc::new(snapshot.pressure);
+    world.influence = Arc::new(snapshot.influence);
+    world.roads = Arc::new(snapshot.roads);
+    world.pop_stats = Arc::new(snapshot.pop_stats);
+    world.hall_of_fame = Arc::new(snapshot.hall_of_fame);
+    Ok(world)
 }
 
 /// Loads the world from a file, handling version migration.
 pub fn load_world(path: impl AsRef<Path>) -> Result<World> {
-    let content = fs::read_to_string(&path).context("Failed to read save file")?;
+    let content = fs::read(&path).context("Failed to read save file")?;
+    let mut world = decode_world(&content)?;
+    world.post_load();
+    Ok(world)
+}
 
+/// Decodes a JSON save or binary snapshot, handling version migration. The
+/// world still needs [`World::post_load`] before it can run.
+pub fn decode_world(content: &[u8]) -> Result<World> {
+    if snapshot_version(content).is_some() {
+        return decode_world_snapshot(content);
+    }
     // First try to deserialize as the current versioned format
-    match serde_json::from_str::<SaveState>(&content) {
+    match serde_json::from_slice::<SaveState>(content) {
         Ok(state) => {
             // Check version and migrate if necessary
             match state.version {
-                1 => {
-                    let mut world = state.world;
-                    world.post_load();
-                    Ok(world)
-                }
+                1 => Ok(state.world),
                 v if v > CURRENT_SAVE_VERSION => {
                     anyhow::bail!(
                         "Save file version {} is newer than supported version {}",
@@ -70,12 +332,24 @@ pub fn load_world(path: impl AsRef<Path>) -> Result<World> {
         Err(_) => {
             // If that fails, assume it's a legacy (v0) save file containing just the World
             tracing::info!("Failed to load as versioned save, attempting legacy load...");
-            let mut world: World =
-                serde_json::from_str(&content).context("Failed to deserialize legacy save file")?;
-
-            world.post_load();
+            let world: World = serde_json::from_slice(content)
+                .context("Failed to deserialize legacy save file")?;
             tracing::info!("Legacy save loaded successfully");
             Ok(world)
         }
     }
 }
+
+/// Loads the world from a file and adapts it to `config` and this build,
+/// returning the differences that were resolved.
+///
+/// Fails if the save has differences that cannot be adapted.
+pub fn load_world_adapted(
+    path: impl AsRef<Path>,
+    config: &AppConfig,
+) -> Result<(World, CompatibilityReport)> {
+    let mut world = load_world(path)?;
+    let report = CompatibilityReport::check(&world, config);
+    compat::adapt(&mut world, config, &report)?;
+    Ok((world, report))
+}
@@ -0,0 +1,149 @@
+//! Run provenance.
+//!
+//! [`manifest`] stamps a run with the hash of its config, its seed, the
+//! build it ran on and when it started. The manifest is saved with the
+//! world, logged first in `live.jsonl`, wrapped around input recordings and
+//! attract reels, and attached to marketplace submissions and challenge
+//! results. [`read`] digs it back out of any of those files for
+//! `primordium provenance show`.
+
+use crate::model::config::AppConfig;
+use anyhow::Result;
+use flate2::read::GzDecoder;
+use primordium_data::RunManifest;
+use serde_json::Value;
+use std::io::Read;
+use std::path::Path;
+
+/// Cargo features of this crate and whether they are compiled in.
+pub const FEATURES: [(&str, bool); 7] = [
+    ("tui", cfg!(feature = "tui")),
+    ("net", cfg!(feature = "net")),
+    ("wasm", cfg!(feature = "wasm")),
+    ("blockchain", cfg!(feature = "blockchain")),
+    ("llm", cfg!(feature = "llm")),
+    ("gpu", cfg!(feature = "gpu")),
+    ("clipboard", cfg!(feature = "clipboard")),
+];
+
+/// The manifest of a run starting now with `config`.
+pub fn manifest(config: &AppConfig) -> RunManifest {
+    RunManifest {
+        config_hash: config_hash(config),
+        seed: config.world.seed,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: option_env!("PRIMORDIUM_GIT_COMMIT").map(str::to_string),
+        features: FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_string())
+            .collect(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// SHA-256 over the whole config, unlike [`AppConfig::fingerprint`] which
+/// only covers the rules.
+pub fn config_hash(config: &AppConfig) -> String {
+    primordium_net::fingerprint([serde_json::to_string(config).unwrap_or_default()])
+}
+
+/// Every manifest stamped into a file, oldest first. Reads JSON documents
+/// and JSON lines, gzipped or not.
+pub fn read(path: impl AsRef<Path>) -> Result<Vec<RunManifest>> {
+    let bytes = std::fs::read(path)?;
+    let content = if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut content = String::new();
+        GzDecoder::new(bytes.as_slice()).read_to_string(&mut content)?;
+        content
+    } else {
+        String::from_utf8(bytes)?
+    };
+    Ok(find(&content))
+}
+
+/// Every manifest in `content`, a JSON document or JSON lines.
+pub fn find(content: &str) -> Vec<RunManifest> {
+    let mut manifests = Vec::new();
+    if let Ok(value) = serde_json::from_str::<Value>(content) {
+        collect(&value, &mut manifests);
+    } else {
+        for value in content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        {
+            collect(&value, &mut manifests);
+        }
+    }
+    manifests
+}
+
+fn collect(value: &Value, manifests: &mut Vec<RunManifest>) {
+    match value {
+        Value::Object(map) => {
+            let manifest = map
+                .get("provenance")
+                .and_then(|p| serde_json::from_value::<RunManifest>(p.clone()).ok());
+            if let Some(manifest) = manifest {
+                if !manifests.contains(&manifest) {
+                    manifests.push(manifest);
+                }
+                return;
+            }
+            for child in map.values() {
+                collect(child, manifests);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect(item, manifests);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primordium_data::LiveEvent;
+
+    #[test]
+    fn test_manifest_hashes_the_whole_config() {
+        let mut config = AppConfig::default();
+        config.world.seed = Some(7);
+        let first = manifest(&config);
+        assert_eq!(first.seed, Some(7));
+        assert_eq!(first.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            first.features.contains(&"tui".to_string()),
+            cfg!(feature = "tui")
+        );
+
+        // Sections outside the rules fingerprint still change the hash.
+        let rules = config.fingerprint();
+        config.visual.glyph_theme = "other.toml".to_string();
+        assert_eq!(config.fingerprint(), rules);
+        assert_ne!(manifest(&config).config_hash, first.config_hash);
+    }
+
+    #[test]
+    fn test_manifests_are_found_in_documents_and_logs() {
+        let stamp = manifest(&AppConfig::default());
+        let save = serde_json::json!({ "version": 1, "world": { "tick": 5, "provenance": stamp } });
+        assert_eq!(find(&save.to_string()), vec![stamp.clone()]);
+
+        let event = LiveEvent::Provenance {
+            tick: 0,
+            provenance: stamp.clone(),
+            timestamp: String::new(),
+        };
+        let log = format!(
+            "{}\n{{\"event\":\"Bookmark\",\"tick\":3,\"note\":\"\",\"timestamp\":\"\"}}\n{}\n",
+            serde_json::to_string(&event).unwrap(),
+            serde_json::to_string(&event).unwrap()
+        );
+        assert_eq!(find(&log), vec![stamp]);
+        assert!(find("[1, 2, 3]").is_empty());
+    }
+}
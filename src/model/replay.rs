@@ -0,0 +1,176 @@
+//! Recording deterministic runs and re-simulating them from checkpoints.
+//!
+//! [`ReplayRecorder`] follows a run with `world.deterministic` set, writing
+//! the hash of its statistics after every tick, the disasters that struck
+//! and a checkpoint of the world and environment every few ticks.
+//! [`resimulate`] restores the checkpoint at or before a tick, replays the
+//! recorded disasters and checks every tick of a range against the
+//! recording.
+//!
+//! Commands dropped into the inbox are not recorded; a run that received
+//! them diverges from the tick they landed on.
+
+pub use primordium_io::replay::*;
+
+use crate::model::environment::Environment;
+use crate::model::persistence;
+use crate::model::world::World;
+use anyhow::Context;
+use primordium_data::DisasterRecord;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Default ticks between checkpoints.
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 500;
+
+/// What a checkpoint's state holds: the world as a save and the
+/// environment it runs in.
+#[derive(Serialize, Deserialize)]
+struct CheckpointState {
+    world: Vec<u8>,
+    env: Environment,
+}
+
+fn encode_state(world: &mut World, env: &Environment) -> anyhow::Result<Vec<u8>> {
+    Ok(serde_json::to_vec(&CheckpointState {
+        world: persistence::encode_world(world)?,
+        env: env.clone(),
+    })?)
+}
+
+/// Restores a checkpoint as a world that writes nothing to the log
+/// directory of the recorded run.
+fn decode_state(state: &[u8]) -> anyhow::Result<(World, Environment)> {
+    let state: CheckpointState = serde_json::from_slice(state)?;
+    let mut world = persistence::decode_world(&state.world)?;
+    world.log_dir.clear();
+    world.post_load();
+    Ok((world, state.env))
+}
+
+/// Records a run as it goes.
+pub struct ReplayRecorder {
+    writer: ReplayWriter,
+    interval: u64,
+    /// Disasters of the world already written as inputs.
+    disasters_recorded: usize,
+}
+
+impl ReplayRecorder {
+    /// Starts recording `world` into `dir`, checkpointing its current state
+    /// and then every `interval` ticks.
+    pub fn create(
+        dir: impl AsRef<Path>,
+        world: &mut World,
+        env: &Environment,
+        interval: u64,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            world.config.world.deterministic,
+            "Replays can only be recorded with world.deterministic = true"
+        );
+        anyhow::ensure!(interval > 0, "Checkpoint interval must be positive");
+        let manifest = ReplayManifest {
+            seed: world.config.world.seed.unwrap_or(0),
+            checkpoint_interval: interval,
+            provenance: world.provenance.clone(),
+        };
+        let mut recorder = Self {
+            writer: ReplayWriter::create(dir.as_ref(), &manifest)?,
+            interval,
+            disasters_recorded: world.disasters.len(),
+        };
+        recorder.checkpoint(world, env)?;
+        Ok(recorder)
+    }
+
+    pub fn dir(&self) -> &Path {
+        self.writer.dir()
+    }
+
+    /// Records the tick `world` just finished.
+    pub fn record(&mut self, world: &mut World, env: &Environment) -> anyhow::Result<()> {
+        for record in &world.disasters[self.disasters_recorded..] {
+            self.writer.record_input(record.tick, record)?;
+        }
+        self.disasters_recorded = world.disasters.len();
+        self.writer.record_tick(world.tick, &world.pop_stats)?;
+        if world.tick.is_multiple_of(self.interval) {
+            self.checkpoint(world, env)?;
+        }
+        Ok(())
+    }
+
+    fn checkpoint(&mut self, world: &mut World, env: &Environment) -> anyhow::Result<()> {
+        self.writer.write_checkpoint(&Checkpoint {
+            tick: world.tick,
+            stats_hash: stats_hash(&world.pop_stats),
+            state: encode_state(world, env)?,
+        })
+    }
+}
+
+/// How a re-simulated range compared with its recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// Every tick from `from` to `to` matched. The run was restored from the
+    /// checkpoint at `checkpoint` and the ticks before `from` matched too.
+    Verified { checkpoint: u64, from: u64, to: u64 },
+    /// The statistics after `tick` differ from the recording.
+    Diverged {
+        tick: u64,
+        expected: u64,
+        actual: u64,
+    },
+}
+
+/// Re-simulates ticks `from..=to` of the recording in `dir` from the
+/// checkpoint at or before `from`, comparing every tick with the recorded
+/// statistics.
+pub fn resimulate(dir: impl AsRef<Path>, from: u64, to: u64) -> anyhow::Result<Verification> {
+    anyhow::ensure!(from <= to, "Empty tick range {from}-{to}");
+    let recording = Recording::open(dir.as_ref())?;
+    let last = recording.last_tick().unwrap_or(0);
+    anyhow::ensure!(to <= last, "The recording ends at tick {last}");
+    let checkpoint = recording
+        .checkpoint_at_or_before(from)?
+        .with_context(|| format!("No checkpoint at or before tick {from}"))?;
+    let (mut world, mut env) = decode_state(&checkpoint.state)?;
+
+    let expected = stats_hash(&world.pop_stats);
+    if expected != checkpoint.stats_hash {
+        return Ok(Verification::Diverged {
+            tick: checkpoint.tick,
+            expected: checkpoint.stats_hash,
+            actual: expected,
+        });
+    }
+
+    let disasters = recording
+        .inputs::<DisasterRecord>()?
+        .into_iter()
+        .map(|(_, record)| record)
+        .filter(|record| record.tick > checkpoint.tick)
+        .collect();
+    world.script_disasters(disasters);
+
+    while world.tick < to {
+        world.update(&mut env)?;
+        let actual = stats_hash(&world.pop_stats);
+        let expected = recording
+            .hash_at(world.tick)
+            .with_context(|| format!("Tick {} is missing from the recording", world.tick))?;
+        if actual != expected {
+            return Ok(Verification::Diverged {
+                tick: world.tick,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(Verification::Verified {
+        checkpoint: checkpoint.tick,
+        from,
+        to,
+    })
+}
@@ -0,0 +1,195 @@
+//! Trial runs of foreign genomes before they enter the main world.
+//!
+//! [`trial`] drops `sandbox.founders` copies of a genome into a small
+//! throwaway world and runs it for `sandbox.ticks` ticks beside a control
+//! world, built from the same seed, that never sees them. The
+//! [`TrialReport`] compares how the imports spread and behaved and what
+//! became of the natives.
+
+use crate::model::brain::GenotypeLogic;
+use crate::model::config::AppConfig;
+use crate::model::environment::Environment;
+use crate::model::world::scenario::ScenarioEvent;
+use crate::model::world::World;
+use anyhow::Context;
+use primordium_data::{Genotype, Intel, Metabolism};
+use uuid::Uuid;
+
+/// How a genome fared in its trial.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrialReport {
+    /// Ticks run; fewer than configured if the imports died out.
+    pub ticks: u64,
+    pub founders: usize,
+    /// Living members of the imported lineage at the end.
+    pub population: usize,
+    pub peak_population: usize,
+    /// Natives alive at the end, with and without the imports.
+    pub natives: usize,
+    pub control_natives: usize,
+    /// Mean aggression output of the imports and of the natives.
+    pub aggression: f32,
+    pub native_aggression: f32,
+}
+
+impl TrialReport {
+    /// Population growth per 100 ticks: 0.5 means half as many again, -1.0
+    /// extinction.
+    pub fn growth_rate(&self) -> f64 {
+        if self.ticks == 0 || self.founders == 0 {
+            return 0.0;
+        }
+        (self.population as f64 / self.founders as f64).powf(100.0 / self.ticks as f64) - 1.0
+    }
+
+    /// Change in native numbers against the control world: -0.5 means half
+    /// as many natives survived.
+    pub fn native_impact(&self) -> f64 {
+        if self.control_natives == 0 {
+            return 0.0;
+        }
+        self.natives as f64 / self.control_natives as f64 - 1.0
+    }
+
+    /// Reasons to think twice before a release.
+    pub fn warnings(&self) -> Vec<&'static str> {
+        let mut warnings = Vec::new();
+        if self.population == 0 {
+            warnings.push("Died out during the trial");
+        }
+        if self.growth_rate() > 1.0 {
+            warnings.push("Spreads explosively");
+        }
+        if self.native_impact() < -0.5 {
+            warnings.push("More than halved the natives");
+        }
+        if self.aggression > 0.5 && self.aggression > self.native_aggression * 1.5 {
+            warnings.push("Far more aggressive than the natives");
+        }
+        warnings
+    }
+
+    /// One line per figure, for the confirmation panel.
+    pub fn summary(&self) -> Vec<String> {
+        vec![
+            format!(
+                "Population: {} founders -> {} after {} ticks (peak {})",
+                self.founders, self.population, self.ticks, self.peak_population
+            ),
+            format!("Growth: {:+.0}% per 100 ticks", self.growth_rate() * 100.0),
+            format!(
+                "Aggression: {:.2} (natives {:.2})",
+                self.aggression, self.native_aggression
+            ),
+            format!(
+                "Natives: {} vs {} without it ({:+.0}%)",
+                self.natives,
+                self.control_natives,
+                self.native_impact() * 100.0
+            ),
+        ]
+    }
+}
+
+/// The throwaway world a trial runs in.
+fn mini_world(config: &AppConfig) -> anyhow::Result<(World, Environment)> {
+    let mut config = config.clone();
+    config.world.width = config.sandbox.width;
+    config.world.height = config.sandbox.height;
+    config.world.initial_population = config.sandbox.natives;
+    config.world.seed = Some(config.world.seed.unwrap_or(0));
+    config.archive.genome_interval = 0;
+    let world = World::new_ephemeral(config.sandbox.natives, config)?;
+    Ok((world, Environment::default()))
+}
+
+/// Living imports and natives, with the summed aggression of each.
+fn census(world: &World, lineage: Uuid) -> ((usize, f32), (usize, f32)) {
+    let mut imports = (0, 0.0);
+    let mut natives = (0, 0.0);
+    for (_, (metabolism, intel)) in world.ecs.query::<(&Metabolism, &Intel)>().iter() {
+        let side = if metabolism.lineage_id == lineage {
+            &mut imports
+        } else {
+            &mut natives
+        };
+        side.0 += 1;
+        side.1 += intel.last_aggression;
+    }
+    (imports, natives)
+}
+
+/// Trials the genome in `dna` as `config.sandbox` describes.
+pub fn trial(dna: &str, config: &AppConfig) -> anyhow::Result<TrialReport> {
+    let lineage = Genotype::from_hex(dna.trim())
+        .context("Invalid DNA")?
+        .lineage_id;
+    let sandbox = &config.sandbox;
+
+    let (mut world, mut env) = mini_world(config)?;
+    world.apply_scenario_event(
+        &mut env,
+        &ScenarioEvent::Spawn {
+            dna: dna.to_string(),
+            count: sandbox.founders,
+            x: None,
+            y: None,
+        },
+    )?;
+    let mut peak_population = sandbox.founders;
+    let (mut aggression, mut native_aggression) = ((0.0, 0), (0.0, 0));
+    let mut population = sandbox.founders;
+    while world.tick < sandbox.ticks && population > 0 {
+        world.update(&mut env)?;
+        let ((imports, import_aggression), (natives, natives_aggression)) = census(&world, lineage);
+        population = imports;
+        peak_population = peak_population.max(imports);
+        aggression = (aggression.0 + import_aggression, aggression.1 + imports);
+        native_aggression = (
+            native_aggression.0 + natives_aggression,
+            native_aggression.1 + natives,
+        );
+    }
+    let ticks = world.tick;
+    let natives = census(&world, lineage).1 .0;
+
+    let (mut control, mut control_env) = mini_world(config)?;
+    while control.tick < ticks {
+        control.update(&mut control_env)?;
+    }
+
+    let mean = |(sum, count): (f32, usize)| if count > 0 { sum / count as f32 } else { 0.0 };
+    Ok(TrialReport {
+        ticks,
+        founders: sandbox.founders,
+        population,
+        peak_population,
+        natives,
+        control_natives: control.get_population_count(),
+        aggression: mean(aggression),
+        native_aggression: mean(native_aggression),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_flags_an_invasive_genome() {
+        let report = TrialReport {
+            ticks: 100,
+            founders: 5,
+            population: 40,
+            peak_population: 40,
+            natives: 10,
+            control_natives: 40,
+            aggression: 0.9,
+            native_aggression: 0.2,
+        };
+        assert!((report.growth_rate() - 7.0).abs() < 1e-9);
+        assert_eq!(report.native_impact(), -0.75);
+        assert_eq!(report.warnings().len(), 3);
+        assert!(report.summary()[3].contains("-75%"));
+    }
+}
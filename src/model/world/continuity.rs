@@ -0,0 +1,137 @@
+//! Multi-epoch evolution: seeding a fresh world with the fittest survivors of
+//! a finished one (`new --continue-from <SAVE> --survivors top:<N>`).
+
+use crate::model::config::ContinuityConfig;
+use crate::model::world::World;
+use primordium_data::{Identity, Intel, Metabolism};
+use primordium_io::genome_archive::ArchivedGenome;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Which survivors of the previous world are carried over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurvivorSelection {
+    /// Every living organism.
+    All,
+    /// The `n` fittest living organisms.
+    Top(usize),
+}
+
+impl FromStr for SurvivorSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Self::All);
+        }
+        match s.strip_prefix("top:").map(str::parse::<usize>) {
+            Some(Ok(n)) if n > 0 => Ok(Self::Top(n)),
+            _ => Err(format!("expected `all` or `top:<N>` with N > 0, got `{s}`")),
+        }
+    }
+}
+
+impl fmt::Display for SurvivorSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::Top(n) => write!(f, "top:{n}"),
+        }
+    }
+}
+
+/// What [`World::continue_from`] carried over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Continuation {
+    /// Tick the previous world had reached.
+    pub source_tick: u64,
+    /// Organisms alive in the previous world.
+    pub candidates: usize,
+    pub survivors: usize,
+    /// Distinct lineages among the survivors.
+    pub lineages: usize,
+    /// Lineage records carried over, living or not.
+    pub records: usize,
+}
+
+/// Survivor fitness of an organism at `tick`; see [`ContinuityConfig`].
+pub fn survivor_fitness(metabolism: &Metabolism, tick: u64, weights: &ContinuityConfig) -> f64 {
+    let age = tick.saturating_sub(metabolism.birth_tick);
+    age as f64 * weights.age_weight
+        + metabolism.offspring_count as f64 * weights.offspring_weight
+        + metabolism.peak_energy * weights.energy_weight
+        + metabolism.generation as f64 * weights.generation_weight
+}
+
+impl World {
+    /// Every living genotype with its survivor fitness, fittest first (ties
+    /// broken by id, so the ranking is reproducible).
+    pub fn rank_survivors(&self, weights: &ContinuityConfig) -> Vec<(f64, ArchivedGenome)> {
+        let mut ranked: Vec<(f64, ArchivedGenome)> = self
+            .ecs
+            .query::<(&Identity, &Metabolism, &Intel)>()
+            .iter()
+            .map(|(_, (identity, met, intel))| {
+                (
+                    survivor_fitness(met, self.tick, weights),
+                    ArchivedGenome {
+                        id: identity.id,
+                        parent_id: identity.parent_id,
+                        generation: met.generation,
+                        genotype: (*intel.genotype).clone(),
+                    },
+                )
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.id.cmp(&b.1.id)));
+        ranked
+    }
+
+    /// Replaces the population of this (fresh) world with the survivors of
+    /// `previous` picked by `selection`, ranked by this world's
+    /// `[continuity]` fitness, and takes over the lineage registry of
+    /// `previous`.
+    ///
+    /// Survivors keep their ids, generation and lineage. Lineages without a
+    /// survivor are marked extinct as of this world's tick.
+    pub fn continue_from(&mut self, previous: World, selection: SurvivorSelection) -> Continuation {
+        let ranked = previous.rank_survivors(&self.config.continuity);
+        let candidates = ranked.len();
+        let keep = match selection {
+            SurvivorSelection::All => candidates,
+            SurvivorSelection::Top(n) => n.min(candidates),
+        };
+        let genomes = ranked
+            .into_iter()
+            .take(keep)
+            .map(|(_, genome)| genome)
+            .collect();
+        let spawned = self.replace_population(genomes);
+
+        let mut registry = previous.lineage_registry;
+        for record in registry.lineages.values_mut() {
+            record.current_population = 0;
+            record.is_extinct = true;
+            record.extinct_since_tick = Some(self.tick);
+            // Routes lead across the previous map.
+            record.migration = None;
+        }
+        for &(lineage_id, generation) in &spawned {
+            registry.record_migration_in(lineage_id, generation, self.tick);
+        }
+        self.lineage_registry = registry;
+
+        Continuation {
+            source_tick: previous.tick,
+            candidates,
+            survivors: spawned.len(),
+            lineages: spawned
+                .iter()
+                .map(|(lineage_id, _)| *lineage_id)
+                .collect::<HashSet<_>>()
+                .len(),
+            records: self.lineage_registry.lineages.len(),
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Cohort experiments on a live world; see [`crate::model::experiment`].
+//!
+//! Membership is a system tag, so cohorts show up in the tag search and are
+//! saved with the world. Offspring join their parent's cohort; hatchlings
+//! only do if their parent is still alive when the egg hatches.
+
+use crate::model::experiment::{Experiment, ExperimentReport};
+use crate::model::world::World;
+use primordium_core::systems::status;
+use primordium_data::{Entity, Health, Identity, Metabolism, TagSource, Tags};
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+impl World {
+    /// Starts `experiment` at the current tick.
+    pub fn start_experiment(&mut self, mut experiment: Experiment) -> anyhow::Result<()> {
+        if let Some(running) = &self.experiment {
+            anyhow::bail!("experiment '{}' is already running", running.name);
+        }
+        experiment.validate()?;
+        experiment.start_tick = self.tick;
+        self.experiment = Some(experiment);
+        Ok(())
+    }
+
+    /// Ends the running experiment, removes its cohort tags and returns the
+    /// final report.
+    pub fn stop_experiment(&mut self) -> Option<ExperimentReport> {
+        let experiment = self.experiment.take()?;
+        for tag in experiment.tags() {
+            for id in self.entities_tagged(&tag) {
+                self.untag_entity(id, &tag);
+            }
+        }
+        Some(experiment.report(self.tick))
+    }
+
+    pub fn experiment_report(&self) -> Option<ExperimentReport> {
+        self.experiment.as_ref().map(|e| e.report(self.tick))
+    }
+
+    /// Moves entity `id` into `cohort`. Returns false if no experiment is
+    /// running or the entity or cohort does not exist.
+    pub fn assign_cohort(&mut self, id: Uuid, cohort: &str) -> bool {
+        let Some(experiment) = &self.experiment else {
+            return false;
+        };
+        let Some(target) = experiment.cohort_index(cohort) else {
+            return false;
+        };
+        let tags = experiment.tags();
+
+        let previous = tags.iter().position(|tag| self.untag_entity(id, tag));
+        if !self.tag_entity(id, &tags[target], TagSource::System) {
+            return false;
+        }
+        if let Some(experiment) = &mut self.experiment {
+            if let Some(previous) = previous {
+                let outcome = &mut experiment.cohorts[previous].outcome;
+                outcome.assigned = outcome.assigned.saturating_sub(1);
+            }
+            experiment.cohorts[target].outcome.assigned += 1;
+        }
+        true
+    }
+
+    /// Moves every living member of `lineage_id` into `cohort` and returns
+    /// how many were moved.
+    pub fn assign_lineage_cohort(&mut self, lineage_id: Uuid, cohort: &str) -> usize {
+        let mut ids: Vec<Uuid> = self
+            .ecs
+            .query::<(&Identity, &Metabolism)>()
+            .iter()
+            .filter(|(_, (_, met))| met.lineage_id == lineage_id)
+            .map(|(_, (identity, _))| identity.id)
+            .collect();
+        ids.sort();
+        ids.into_iter()
+            .filter(|&id| self.assign_cohort(id, cohort))
+            .count()
+    }
+
+    /// Deals the living population at random into the cohorts in equal
+    /// shares and returns how many entities were assigned.
+    pub fn split_population(&mut self) -> usize {
+        let Some(experiment) = &self.experiment else {
+            return 0;
+        };
+        let names: Vec<String> = experiment.cohorts.iter().map(|c| c.name.clone()).collect();
+        let mut ids: Vec<Uuid> = self
+            .ecs
+            .query::<&Identity>()
+            .iter()
+            .map(|(_, identity)| identity.id)
+            .collect();
+        ids.sort();
+        ids.shuffle(&mut self.rng);
+        ids.into_iter()
+            .zip(names.iter().cycle())
+            .filter(|(id, name)| self.assign_cohort(*id, name))
+            .count()
+    }
+
+    /// Applies each cohort's modifiers to its members for this tick.
+    pub fn pass_experiment(&mut self) {
+        let Some(experiment) = self.experiment.as_mut() else {
+            return;
+        };
+        let cohort_tags = experiment.tags();
+        let effects: Vec<_> = experiment
+            .cohorts
+            .iter()
+            .map(|c| c.modifiers.effects())
+            .collect();
+
+        for (_h, (tags, health)) in self.ecs.query_mut::<(&Tags, &mut Health)>() {
+            if let Some(cohort) = cohort_tags.iter().position(|t| tags.system.contains(t)) {
+                for effect in &effects[cohort] {
+                    status::apply(&mut health.effects, effect.clone());
+                }
+            }
+        }
+    }
+
+    /// Records the cohorts' populations at the end of the tick.
+    pub fn observe_cohorts(&mut self) {
+        let Some(experiment) = self.experiment.as_mut() else {
+            return;
+        };
+        let cohort_tags = experiment.tags();
+        let members: Vec<_> = self
+            .ecs
+            .query_mut::<(&Tags, &Metabolism)>()
+            .into_iter()
+            .filter_map(|(_h, (tags, met))| {
+                let cohort = cohort_tags.iter().position(|t| tags.system.contains(t))?;
+                Some((cohort, met.energy, met.generation))
+            })
+            .collect();
+        experiment.observe(&members);
+    }
+
+    /// Cohorts the parents of `babies` belong to, by baby id.
+    pub fn offspring_cohorts(&self, babies: &[Entity]) -> Vec<(Uuid, usize)> {
+        let Some(experiment) = &self.experiment else {
+            return Vec::new();
+        };
+        let cohort_tags = experiment.tags();
+        let parents: HashMap<Uuid, usize> = self
+            .ecs
+            .query::<(&Identity, &Tags)>()
+            .iter()
+            .filter_map(|(_, (identity, tags))| {
+                let cohort = cohort_tags.iter().position(|t| tags.system.contains(t))?;
+                Some((identity.id, cohort))
+            })
+            .collect();
+        if parents.is_empty() {
+            return Vec::new();
+        }
+        babies
+            .iter()
+            .filter_map(|baby| {
+                let cohort = parents.get(&baby.identity.parent_id?)?;
+                Some((baby.identity.id, *cohort))
+            })
+            .collect()
+    }
+
+    /// Tags newborns with their parents' cohorts and counts the births.
+    pub fn enroll_offspring(&mut self, offspring: &[(Uuid, usize)]) {
+        let Some(experiment) = &self.experiment else {
+            return;
+        };
+        if offspring.is_empty() {
+            return;
+        }
+        let tags = experiment.tags();
+        let handles: HashMap<Uuid, hecs::Entity> = self
+            .ecs
+            .query::<&Identity>()
+            .iter()
+            .map(|(handle, identity)| (identity.id, handle))
+            .collect();
+
+        for &(id, cohort) in offspring {
+            // Babies over the spawn limit were never spawned.
+            let Some(&handle) = handles.get(&id) else {
+                continue;
+            };
+            let mut baby_tags = Tags::default();
+            baby_tags.insert(&tags[cohort], TagSource::System);
+            if self.ecs.insert_one(handle, baby_tags).is_ok() {
+                if let Some(experiment) = &mut self.experiment {
+                    experiment.record_birth(cohort);
+                }
+            }
+        }
+    }
+
+    /// Counts the death of the entity at `handle` against its cohort.
+    pub fn record_cohort_death(&mut self, handle: hecs::Entity, age: u64) {
+        let Some(experiment) = &mut self.experiment else {
+            return;
+        };
+        if let Ok(tags) = self.ecs.get::<&Tags>(handle) {
+            if let Some(cohort) = experiment.cohort_of(&tags) {
+                experiment.record_death(cohort, age);
+            }
+        }
+    }
+}
@@ -1,12 +1,28 @@
-use crate::model::environment::Environment;
+use crate::model::environment::{ClimateState, Environment};
 use crate::model::world::World;
 use chrono::Utc;
-use primordium_core::systems::{biological, civilization, history, social, stats};
-use primordium_data::LiveEvent;
-use primordium_data::{Entity, Health, Identity, Intel, Metabolism, Pathogen, Physics};
+use primordium_core::anomaly::AnomalyDetector;
+use primordium_core::biome::BiomeChange;
+use primordium_core::environment::Era;
+use primordium_core::forecast::{ForecastSample, Forecaster};
+use primordium_core::guardrail;
+use primordium_core::lifecycle;
+use primordium_core::lineage_registry::RetentionPolicy;
+use primordium_core::pheromone::PheromoneType;
+use primordium_core::schedule::EventKind;
+use primordium_core::systems::{
+    biological, civilization, environment as environment_system, history, social, stats,
+};
+use primordium_data::{DeathCause, LiveEvent, NarrationRefs};
+use primordium_data::{Entity, Health, Identity, Intel, Metabolism, Pathogen, Physics, Position};
 use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
+use std::collections::HashSet;
 use std::sync::Arc;
+use uuid::Uuid;
+
+/// Ticks between checks for a change of era.
+pub const ERA_INTERVAL: u64 = 60;
 
 type ProposalResult = (hecs::Entity, Vec<(hecs::Entity, Pathogen)>, bool, f64);
 
@@ -82,7 +98,9 @@ impl World {
                         });
                     }
 
-                    let is_dead = killed_ids.contains(&identity.id) || met.energy <= 0.0;
+                    let is_dead = killed_ids.contains(&identity.id)
+                        || met.energy <= 0.0
+                        || met.hydration <= 0.0;
 
                     (*handle, infections, is_dead, metabolic_consumption)
                 })
@@ -97,6 +115,8 @@ impl World {
             }
         }
 
+        let offspring_cohorts = self.offspring_cohorts(&new_babies);
+        self.record_maturations(tick);
         self.process_deaths(&proposals, tick, env, events);
 
         // Phase 67 Task B: Aggregate metabolic consumption (heat loss)
@@ -110,8 +130,12 @@ impl World {
         env.available_energy -= total_metabolic_consumption;
 
         self.process_births(new_babies);
+        self.enroll_offspring(&offspring_cohorts);
+        self.observe_cohorts();
         self.finalize_snapshots(env, events);
-        self.finalize_civilization(entity_handles);
+        self.archive_genomes();
+        self.export_interaction_graph();
+        self.finalize_civilization(env, entity_handles);
         self.finalize_stats(env, tick);
     }
 
@@ -129,21 +153,55 @@ impl World {
             }
         }
 
+        let harsh_climate = matches!(env.climate(), ClimateState::Hot | ClimateState::Scorching);
         for handle in dead_handles {
+            let infected = self
+                .ecs
+                .get::<&Health>(handle)
+                .is_ok_and(|h| h.pathogen.is_some());
             if let Ok((met, identity, phys, intel)) = self
                 .ecs
                 .remove::<(Metabolism, Identity, Physics, Intel)>(handle)
             {
-                self.lineage_registry.record_death(met.lineage_id);
+                let cause = if self.killed_ids.contains(&identity.id) {
+                    DeathCause::Predation
+                } else if infected {
+                    DeathCause::Disease
+                } else if met.hydration <= 0.0 {
+                    DeathCause::Dehydration
+                } else if harsh_climate {
+                    DeathCause::Climate
+                } else {
+                    DeathCause::Starvation
+                };
+                if cause == DeathCause::Disease && self.config.death_scent.enabled {
+                    Arc::make_mut(&mut self.pheromones).deposit(
+                        phys.x,
+                        phys.y,
+                        PheromoneType::Death,
+                        self.config.death_scent.deposit,
+                    );
+                }
+                self.lineage_registry.record_death(met.lineage_id, tick);
+                self.lineage_registry
+                    .record_death_cause(met.lineage_id, cause);
+                if !lifecycle::is_mature_components(
+                    &met,
+                    &intel,
+                    tick,
+                    self.config.metabolism.maturity_age,
+                ) {
+                    self.lineage_registry.record_juvenile_death(met.lineage_id);
+                }
 
-                // Create Death event for starvation deaths
+                // Create Death event with the classified cause
                 let ev = LiveEvent::Death {
                     id: identity.id,
                     age: tick - met.birth_tick,
                     offspring: met.offspring_count,
                     tick,
                     timestamp: Utc::now().to_rfc3339(),
-                    cause: "Starvation".to_string(),
+                    cause: cause.label().to_string(),
                     x: Some(phys.x),
                     y: Some(phys.y),
                 };
@@ -172,11 +230,23 @@ impl World {
                 terrain.fertilize(phys.x, phys.y, fertilize_amount);
                 terrain.add_biomass(phys.x, phys.y, fertilize_amount * 10.0);
 
+                self.record_cohort_death(handle, tick - met.birth_tick);
                 let _ = self.ecs.despawn(handle);
             }
         }
     }
 
+    /// Credits each entity that reaches maturity this tick to its lineage.
+    fn record_maturations(&mut self, tick: u64) {
+        let maturity_age = self.config.metabolism.maturity_age;
+        for (_handle, (met, intel)) in self.ecs.query_mut::<(&Metabolism, &Intel)>() {
+            let actual_maturity = (maturity_age as f32 * intel.genotype.maturity_gene) as u64;
+            if tick.saturating_sub(met.birth_tick) == actual_maturity {
+                self.lineage_registry.record_maturity(met.lineage_id);
+            }
+        }
+    }
+
     fn world_logger_archive_legend(&self, legend: primordium_data::Legend) -> anyhow::Result<()> {
         self.logger.archive_legend(legend)
     }
@@ -213,7 +283,7 @@ impl World {
     }
 
     pub fn finalize_snapshots(&mut self, env: &mut Environment, events: &mut Vec<LiveEvent>) {
-        if self.tick.is_multiple_of(self.config.world.fossil_interval) {
+        if env.schedule.fires(self.tick, EventKind::Fossil) {
             let outpost_counts = civilization::count_outposts_by_lineage(&self.terrain);
             self.lineage_registry.check_goals(
                 self.tick,
@@ -222,7 +292,6 @@ impl World {
                 self.height,
                 &outpost_counts,
             );
-            self.lineage_registry.prune();
             let reg_clone = self.lineage_registry.clone();
             let fossil_clone = self.fossil_registry.clone();
 
@@ -236,21 +305,15 @@ impl World {
             let _ = self.logger.sync_to_storage_async(reg_clone, fossil_clone);
             let snap_ev = LiveEvent::Snapshot {
                 tick: self.tick,
-                stats: (*self.pop_stats).clone(),
+                stats: Box::new((*self.pop_stats).clone()),
                 timestamp: Utc::now().to_rfc3339(),
             };
             if let Some(ref storage) = self.logger.storage {
-                let snapshot = self.create_snapshot(None);
-                let world_data =
-                    rkyv::to_bytes::<crate::model::snapshot::WorldSnapshot, 4096>(&snapshot)
-                        .map(|v| v.to_vec())
-                        .unwrap_or_default();
                 storage.save_snapshot(
-                    self.tick,
+                    std::sync::Arc::unwrap_or_clone(self.create_snapshot(None)),
                     self.pop_stats.population as u32,
                     env.carbon_level,
                     self.pop_stats.biomass_h + self.pop_stats.biomass_c,
-                    world_data,
                 );
             }
 
@@ -260,25 +323,41 @@ impl World {
                 &self.lineage_registry,
                 &mut self.fossil_registry,
                 &mut self.best_legends,
+                env,
                 self.tick,
             );
-            self.lineage_registry.prune();
+            let policy = RetentionPolicy::from_config(&self.config);
+            let protected = self.referenced_lineages();
+            self.lineage_registry.prune(&policy, &protected);
         }
 
         if self
             .tick
             .is_multiple_of(self.config.world.lineage_prune_interval)
         {
-            self.lineage_registry.prune_extinct_old(
-                self.tick,
-                self.config.world.lineage_extinction_age_threshold,
-            );
+            let policy = RetentionPolicy::from_config(&self.config);
+            let protected = self.referenced_lineages();
             self.lineage_registry
-                .prune_by_count(self.config.world.max_lineages);
+                .prune_expired(self.tick, &policy, &protected);
         }
     }
 
-    pub fn finalize_civilization(&mut self, entity_handles: &[hecs::Entity]) {
+    /// Lineage ids that fossils or pending legends still point at and that
+    /// must therefore survive garbage collection.
+    pub fn referenced_lineages(&self) -> HashSet<Uuid> {
+        self.fossil_registry
+            .fossils
+            .iter()
+            .map(|f| f.lineage_id)
+            .chain(self.best_legends.keys().copied())
+            .collect()
+    }
+
+    pub fn finalize_civilization(
+        &mut self,
+        env: &mut Environment,
+        entity_handles: &[hecs::Entity],
+    ) {
         civilization::handle_outposts_ecs(
             Arc::make_mut(&mut self.terrain),
             &mut self.ecs,
@@ -309,22 +388,38 @@ impl World {
             &self.lineage_registry,
         );
 
-        if self
-            .tick
-            .is_multiple_of(self.config.world.power_grid_interval)
-        {
+        if env.schedule.fires(self.tick, EventKind::PowerGrid) {
             civilization::resolve_power_grid(
                 Arc::make_mut(&mut self.terrain),
                 self.width,
                 self.height,
                 &self.lineage_registry,
+                &self.roads,
+                &self.config.roads,
             );
         }
     }
 
+    /// Recomputes `pop_stats` from the current population now, instead of
+    /// waiting for the next periodic refresh.
+    pub fn refresh_pop_stats(&mut self, env: &Environment) {
+        self.capture_entity_snapshots();
+        let food_count = self.get_food_count();
+        stats::update_population_stats_snapshots(stats::StatsContext {
+            stats: Arc::make_mut(&mut self.pop_stats),
+            entities: &self.entity_snapshots,
+            food_count,
+            top_fitness: 0.0,
+            carbon_level: env.carbon_level,
+            mutation_scale: 1.0,
+            terrain: &self.terrain,
+            tick: self.tick,
+        });
+    }
+
     pub fn finalize_stats(&mut self, env: &mut Environment, tick: u64) {
         // Optimization: update_stats only needs a slice of entity snapshots which we already have
-        let food_count = self.ecs.query::<&primordium_data::Food>().iter().count();
+        let food_count = self.get_food_count();
 
         stats::update_stats(
             &stats::StatsInput {
@@ -339,10 +434,16 @@ impl World {
             Arc::make_mut(&mut self.hall_of_fame),
         );
 
+        if self.config.analytics.record_interactions {
+            Arc::make_mut(&mut self.pop_stats).interaction_rates =
+                self.interaction_recorder.rates();
+        }
+
         history::handle_fossilization(
             &self.lineage_registry,
             &mut self.fossil_registry,
             &mut self.best_legends,
+            env,
             tick,
         );
 
@@ -350,4 +451,167 @@ impl World {
             self.update_rank_grid();
         }
     }
+
+    /// Feeds this tick's deaths to the anomaly detector and, at the end of a
+    /// sampling interval, narrates and bookmarks every metric that broke from
+    /// its trend.
+    pub fn check_anomalies(&mut self, events: &[LiveEvent]) -> Vec<LiveEvent> {
+        self.anomalies.record_deaths(events);
+        if !AnomalyDetector::is_due(self.tick, &self.config.anomaly) {
+            return Vec::new();
+        }
+        let population = self.get_population_count();
+        let biomass = self
+            .ecs
+            .query::<&Metabolism>()
+            .iter()
+            .map(|(_, met)| met.energy)
+            .sum();
+        let anomalies =
+            self.anomalies
+                .observe(self.tick, population, biomass, &self.config.anomaly);
+
+        let mut narrations = Vec::with_capacity(anomalies.len());
+        for anomaly in anomalies {
+            let text = anomaly.narration();
+            self.add_bookmark(anomaly.tick, text.clone());
+            let ev = LiveEvent::Narration {
+                tick: anomaly.tick,
+                text,
+                severity: if anomaly.is_decline() { 0.9 } else { 0.7 },
+                timestamp: Utc::now().to_rfc3339(),
+                refs: anomaly.refs,
+            };
+            let _ = self.logger.log_event(ev.clone());
+            narrations.push(ev);
+        }
+        narrations
+    }
+
+    /// Samples population, food and soil fertility every
+    /// `forecast.sample_interval` ticks and refits the forecast.
+    pub fn sample_forecast(&mut self) {
+        if !Forecaster::is_due(self.tick, &self.config.forecast) {
+            return;
+        }
+        let sample = ForecastSample {
+            tick: self.tick,
+            population: self.get_population_count() as f64,
+            food: self.get_food_count() as f64,
+            fertility: self.terrain.average_fertility() as f64,
+        };
+        self.forecaster.observe(sample, &self.config.forecast);
+    }
+
+    /// Reclassifies the biome regions every `biomes.interval` ticks and
+    /// narrates regions that emerge, vanish or empty.
+    pub fn check_biomes(&mut self) -> Vec<LiveEvent> {
+        if !self.tick.is_multiple_of(self.config.biomes.interval) {
+            return Vec::new();
+        }
+        let positions: Vec<(f64, f64)> = self
+            .ecs
+            .query::<&Position>()
+            .iter()
+            .map(|(_, pos)| (pos.x, pos.y))
+            .collect();
+        let changes = self
+            .biomes
+            .update(&self.terrain, positions, self.tick, &self.config.biomes);
+
+        let mut narrations = Vec::with_capacity(changes.len());
+        for change in changes {
+            let text = change.narration();
+            let region = change.region();
+            self.add_bookmark(self.tick, text.clone());
+            let ev = LiveEvent::Narration {
+                tick: self.tick,
+                text,
+                severity: match change {
+                    BiomeChange::Depopulated { .. } => 0.7,
+                    _ => 0.5,
+                },
+                timestamp: Utc::now().to_rfc3339(),
+                refs: NarrationRefs {
+                    data: vec![
+                        format!("biome: {}", region.biome.name()),
+                        format!("cells: {}", region.cells),
+                        format!("population: {}", region.population),
+                    ],
+                    ..NarrationRefs::at(self.tick)
+                },
+            };
+            let _ = self.logger.log_event(ev.clone());
+            narrations.push(ev);
+        }
+        narrations
+    }
+
+    /// Checks every [`ERA_INTERVAL`] ticks whether the world has entered a
+    /// new era, applying its modifiers and narrating the change with the
+    /// figures that brought it about and the rules it changes.
+    pub fn check_era(&mut self, env: &mut Environment) -> Option<LiveEvent> {
+        if !self.tick.is_multiple_of(ERA_INTERVAL) {
+            return None;
+        }
+        let era = environment_system::update_era(env, self.tick, &self.pop_stats, &self.config)?;
+        Some(self.narrate_era(era, env))
+    }
+
+    fn narrate_era(&mut self, era: Era, env: &Environment) -> LiveEvent {
+        let stats = &self.pop_stats;
+        let modifiers = env.era_modifiers;
+        let text = format!(
+            "{} (mutation x{:.1}, disasters x{:.1}, food x{:.1})",
+            era.narration(),
+            modifiers.mutation_scale,
+            modifiers.disaster_frequency,
+            modifiers.food_richness
+        );
+        let refs = NarrationRefs {
+            data: vec![
+                format!("population: {}", stats.population),
+                format!("avg lifespan: {:.0}", stats.avg_lifespan),
+                format!("herbivore biomass: {:.0}", stats.biomass_h),
+                format!("carnivore biomass: {:.0}", stats.biomass_c),
+                format!("carbon: {:.0}", env.carbon_level),
+                format!("top fitness: {:.0}", stats.top_fitness),
+            ],
+            ..NarrationRefs::at(self.tick)
+        };
+        self.add_bookmark(self.tick, format!("Era: {}", era.name()));
+        let ev = LiveEvent::Narration {
+            tick: self.tick,
+            text,
+            severity: 0.8,
+            timestamp: Utc::now().to_rfc3339(),
+            refs,
+        };
+        let _ = self.logger.log_event(ev.clone());
+        ev
+    }
+
+    /// Runs the guardrail circuit breakers against this tick's counters.
+    ///
+    /// On a trip the world halts (see [`World::acknowledge_guardrail`]) and a
+    /// `CircuitBreaker` event carrying the diagnosis is returned.
+    pub fn check_guardrails(&mut self, births: usize) -> Option<LiveEvent> {
+        let input = guardrail::GuardrailInput {
+            population: self.get_population_count(),
+            food_count: self.get_food_count(),
+            births,
+        };
+        let trip = guardrail::check(&self.config, input, self.tick)?;
+        let ev = LiveEvent::CircuitBreaker {
+            breaker: trip.kind.label().to_string(),
+            observed: trip.observed,
+            limit: trip.limit,
+            suspected_source: trip.suspected_source.clone(),
+            tick: trip.tick,
+            timestamp: Utc::now().to_rfc3339(),
+        };
+        let _ = self.logger.log_event(ev.clone());
+        self.guardrail_trip = Some(trip);
+        Some(ev)
+    }
 }
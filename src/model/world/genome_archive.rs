@@ -0,0 +1,94 @@
+//! Full-genome archive of a live world; see [`primordium_io::genome_archive`].
+//!
+//! Dumps are written by the history logger's background thread, so a world
+//! without a persistent logger (ephemeral worlds, the screensaver) never
+//! writes one.
+
+use crate::model::lifecycle;
+use crate::model::world::World;
+use crate::model::BrainLogic;
+use primordium_data::{Egg, Identity, Intel, Metabolism};
+use primordium_io::genome_archive::{ArchivedGenome, GenomeArchive, GenomeDumpEntry};
+use rand::Rng;
+use std::sync::Arc;
+use uuid::Uuid;
+
+impl World {
+    /// Dumps every living genotype when `archive.genome_interval` comes round.
+    pub fn archive_genomes(&self) {
+        let interval = self.config.archive.genome_interval;
+        if interval == 0 || !self.tick.is_multiple_of(interval) {
+            return;
+        }
+        let mut genomes: Vec<ArchivedGenome> = self
+            .ecs
+            .query::<(&Identity, &Metabolism, &Intel)>()
+            .iter()
+            .map(|(_, (identity, met, intel))| ArchivedGenome {
+                id: identity.id,
+                parent_id: identity.parent_id,
+                generation: met.generation,
+                genotype: (*intel.genotype).clone(),
+            })
+            .collect();
+        genomes.sort_by_key(|g| g.id);
+        let _ = self.logger.archive_genomes_async(
+            GenomeArchive::new(&self.config.archive.dir),
+            self.tick,
+            genomes,
+        );
+    }
+
+    /// Replaces the population (and any eggs) with the organisms of the last
+    /// dump in `dir` taken at or before `tick`, scattered at random positions.
+    pub fn reseed_from_archive(&mut self, dir: &str, tick: u64) -> anyhow::Result<GenomeDumpEntry> {
+        let (entry, genomes) = GenomeArchive::new(dir).load_at_or_before(tick)?;
+        for (lineage_id, generation) in self.replace_population(genomes) {
+            self.lineage_registry
+                .record_birth(lineage_id, generation, self.tick);
+        }
+        Ok(entry)
+    }
+
+    /// Despawns the population and any eggs, then spawns `genomes` at random
+    /// positions with full energy. Returns the lineage and generation of each
+    /// organism spawned; recording them in the lineage registry is up to the
+    /// caller.
+    pub(crate) fn replace_population(&mut self, genomes: Vec<ArchivedGenome>) -> Vec<(Uuid, u32)> {
+        let existing: Vec<hecs::Entity> = self
+            .ecs
+            .query::<&Identity>()
+            .iter()
+            .map(|(handle, _)| handle)
+            .chain(self.ecs.query::<&Egg>().iter().map(|(handle, _)| handle))
+            .collect();
+        for handle in existing {
+            let _ = self.ecs.despawn(handle);
+        }
+
+        let mut spawned = Vec::with_capacity(genomes.len());
+        for archived in genomes {
+            let x = self.rng.gen_range(1.0..self.width as f64 - 1.0);
+            let y = self.rng.gen_range(1.0..self.height as f64 - 1.0);
+            let mut e = lifecycle::create_entity_with_rng(x, y, self.tick, &mut self.rng);
+            let mut genotype = archived.genotype;
+            genotype.brain.initialize_node_idx_map();
+
+            e.identity.id = archived.id;
+            e.identity.parent_id = archived.parent_id;
+            e.physics.sensing_range = genotype.sensing_range;
+            e.physics.max_speed = genotype.max_speed;
+            e.metabolism.max_energy = genotype.max_energy;
+            e.metabolism.energy = genotype.max_energy;
+            e.metabolism.prev_energy = genotype.max_energy;
+            e.metabolism.peak_energy = genotype.max_energy;
+            e.metabolism.generation = archived.generation;
+            e.metabolism.lineage_id = genotype.lineage_id;
+            e.intel.genotype = Arc::new(genotype);
+
+            spawned.push((e.metabolism.lineage_id, e.metabolism.generation));
+            self.spawn_entity(e);
+        }
+        spawned
+    }
+}
@@ -1,4 +1,6 @@
-use crate::model::config::AppConfig;
+use crate::model::config::{AppConfig, FoodRepresentation};
+use crate::model::food_field::FoodField;
+use crate::model::interaction::InteractionRecorder;
 use crate::model::lifecycle;
 use crate::model::lineage_registry::LineageRegistry;
 use crate::model::observer::WorldObserver;
@@ -7,7 +9,8 @@ use crate::model::sound::SoundGrid;
 use crate::model::spatial_hash::SpatialHash;
 use crate::model::terrain::TerrainGrid;
 use crate::model::world::World;
-use primordium_data::{Food, MetabolicNiche, Position};
+use crate::model::BrainLogic;
+use primordium_data::{Egg, Food, Intel, LiveEvent, MetabolicNiche, Position, Tags};
 use primordium_data::{FossilRegistry, HallOfFame, PopulationStats};
 use primordium_io::history::{FossilPersistence, HistoryLogger};
 use primordium_io::registry::LineagePersistence;
@@ -15,23 +18,39 @@ use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use std::collections::HashMap;
 use std::sync::Arc;
+use uuid::Uuid;
 
 impl World {
     pub fn new_at(
         initial_population: usize,
         config: AppConfig,
         log_dir: &str,
+    ) -> anyhow::Result<Self> {
+        let logger = HistoryLogger::with_rotation(log_dir, config.logs.clone())
+            .unwrap_or_else(|_| {
+                eprintln!("Warning: Failed to create history logger at '{}'. Using dummy logger (no logging will occur).", log_dir);
+                HistoryLogger::new_dummy()
+            });
+        Self::with_logger(initial_population, config, logger, log_dir)
+    }
+
+    /// Creates a world that never touches the filesystem: history, legends,
+    /// lineages and fossils are discarded instead of persisted.
+    pub fn new_ephemeral(initial_population: usize, config: AppConfig) -> anyhow::Result<Self> {
+        Self::with_logger(initial_population, config, HistoryLogger::new_dummy(), "")
+    }
+
+    fn with_logger(
+        initial_population: usize,
+        config: AppConfig,
+        logger: HistoryLogger,
+        log_dir: &str,
     ) -> anyhow::Result<Self> {
         let mut rng = if let Some(seed) = config.world.seed {
             ChaCha8Rng::seed_from_u64(seed)
         } else {
             ChaCha8Rng::seed_from_u64(0)
         };
-        let logger = HistoryLogger::new_at(log_dir)
-            .unwrap_or_else(|_| {
-                eprintln!("Warning: Failed to create history logger at '{}'. Using dummy logger (no logging will occur).", log_dir);
-                HistoryLogger::new_dummy()
-            });
         let mut lineage_registry = LineageRegistry::new();
         let mut ecs = hecs::World::new();
         for _ in 0..initial_population {
@@ -53,10 +72,19 @@ impl World {
                 e.intel,
             ));
         }
+        let mut food_field = if config.food.representation == FoodRepresentation::Field {
+            FoodField::new(config.world.width, config.world.height)
+        } else {
+            FoodField::default()
+        };
         for _ in 0..config.world.initial_food {
             let fx = rng.gen_range(1..config.world.width - 1);
             let fy = rng.gen_range(1..config.world.height - 1);
             let n_type = rng.gen_range(0.0..1.0);
+            if config.food.representation == FoodRepresentation::Field {
+                food_field.deposit(fx, fy, n_type);
+                continue;
+            }
             ecs.spawn((
                 Position {
                     x: fx as f64,
@@ -71,21 +99,44 @@ impl World {
             config.world.height,
             config.world.seed.unwrap_or(42),
         );
-        let pheromones = PheromoneGrid::new(config.world.width, config.world.height);
+        let pheromones = PheromoneGrid::with_channels(
+            config.world.width,
+            config.world.height,
+            config.pheromone.channels.clone(),
+        );
         let sound = SoundGrid::new(config.world.width, config.world.height);
         let pressure =
             crate::model::pressure::PressureGrid::new(config.world.width, config.world.height);
         let influence =
             crate::model::influence::InfluenceGrid::new(config.world.width, config.world.height);
+        let roads = crate::model::road::RoadGrid::new(config.world.width, config.world.height);
+        let mutation_heat = crate::model::mutation_heat::MutationHeatGrid::new(
+            config.world.width,
+            config.world.height,
+        );
         let social_grid = vec![0; config.world.width as usize * config.world.height as usize];
 
+        let provenance = crate::model::provenance::manifest(&config);
+        let _ = logger.log_event(LiveEvent::Provenance {
+            tick: 0,
+            provenance: provenance.clone(),
+            timestamp: provenance.started_at.clone(),
+        });
+
         let initial_food = config.world.initial_food;
+        let interaction_recorder = InteractionRecorder::new(config.analytics.interaction_window);
+        let observer = WorldObserver::with_config(&config.narrator, log_dir);
+        let config_seed = config.world.seed.unwrap_or(0);
         Ok(Self {
             width: config.world.width,
             height: config.world.height,
             tick: 0,
             ecs,
             food_persist: Vec::new(),
+            egg_persist: Vec::new(),
+            entity_persist: Vec::new(),
+            tag_persist: Vec::new(),
+            food_field,
             logger,
             spatial_hash: SpatialHash::new(5.0, config.world.width, config.world.height),
             food_hash: SpatialHash::new(5.0, config.world.width, config.world.height),
@@ -101,19 +152,34 @@ impl World {
             sound: Arc::new(sound),
             pressure: Arc::new(pressure),
             influence: Arc::new(influence),
+            roads: Arc::new(roads),
+            mutation_heat: Arc::new(mutation_heat),
             social_grid: Arc::new(social_grid),
             lineage_registry,
             config,
             fossil_registry: FossilRegistry::default(),
             log_dir: log_dir.to_string(),
             active_pathogens: Vec::new(),
-            observer: WorldObserver::new(),
+            bookmarks: Vec::new(),
+            provenance: Some(provenance),
+            experiment: None,
+            dominance: Default::default(),
+            monuments: Default::default(),
+            observer,
             best_legends: HashMap::new(),
             rng,
+            disaster_rng: super::disaster_rng(config_seed),
+            disasters: Vec::new(),
+            pending_disasters: Vec::new(),
+            scripted_disasters: None,
             killed_ids: Default::default(),
             eaten_food_indices: Default::default(),
             decision_buffer: Vec::new(),
             interaction_buffer: Vec::new(),
+            interaction_recorder,
+            interaction_graph: Default::default(),
+            energy_ledger: None,
+            route_trail: None,
             lineage_consumption: Vec::new(),
             entity_snapshots: Vec::new(),
             food_dirty: true,
@@ -122,6 +188,11 @@ impl World {
             spatial_data_buffer: Vec::new(),
             spatial_sort_buffer: Vec::new(),
             food_positions_buffer: Vec::new(),
+            path_service: primordium_core::pathfinding::PathService::new(),
+            guardrail_trip: None,
+            anomalies: Default::default(),
+            forecaster: Default::default(),
+            biomes: Default::default(),
         })
     }
 
@@ -138,6 +209,18 @@ impl World {
 
     pub fn post_load(&mut self) {
         self.ecs = hecs::World::new();
+        self.observer = WorldObserver::with_config(&self.config.narrator, &self.log_dir);
+        self.disaster_rng =
+            super::disaster_rng(self.config.world.seed.unwrap_or(0).wrapping_add(self.tick));
+        if self.roads.cells.len() != self.width as usize * self.height as usize {
+            self.roads = Arc::new(crate::model::road::RoadGrid::new(self.width, self.height));
+        }
+        if self.mutation_heat.heat.len() != self.width as usize * self.height as usize {
+            self.mutation_heat = Arc::new(crate::model::mutation_heat::MutationHeatGrid::new(
+                self.width,
+                self.height,
+            ));
+        }
 
         for f in std::mem::take(&mut self.food_persist) {
             self.ecs.spawn((
@@ -149,6 +232,45 @@ impl World {
                 f,
             ));
         }
+        for egg in std::mem::take(&mut self.egg_persist) {
+            self.ecs.spawn((egg.hatchling.position, egg));
+        }
+        let mut tags: HashMap<Uuid, Tags> =
+            std::mem::take(&mut self.tag_persist).into_iter().collect();
+        for entity in std::mem::take(&mut self.entity_persist) {
+            let entity_tags = tags.remove(&entity.identity.id);
+            let handle = self.spawn_entity(entity);
+            if let Some(entity_tags) = entity_tags {
+                let _ = self.ecs.insert_one(handle, entity_tags);
+            }
+        }
+        self.post_load_rebuild();
+    }
+
+    /// Reconstructs every cache a save leaves out from the state it keeps,
+    /// so that a loaded world runs on exactly as the saved one would have:
+    /// - brain index maps of organisms and unhatched eggs (without them a
+    ///   brain stays silent),
+    /// - the terrain's buffers and outpost indices,
+    /// - the pheromone and sound buffers,
+    /// - the entity and food spatial hashes and the food count.
+    pub fn post_load_rebuild(&mut self) {
+        for (_, intel) in self.ecs.query_mut::<&mut Intel>() {
+            Arc::make_mut(&mut intel.genotype)
+                .brain
+                .initialize_node_idx_map();
+        }
+        for (_, egg) in self.ecs.query_mut::<&mut Egg>() {
+            Arc::make_mut(&mut egg.hatchling.intel.genotype)
+                .brain
+                .initialize_node_idx_map();
+        }
+        Arc::make_mut(&mut self.terrain).restore_buffers();
+        Arc::make_mut(&mut self.pheromones).restore_buffers();
+        Arc::make_mut(&mut self.sound).restore_buffers();
+        self.pass_spatial_indexing();
+        self.pass_food_indexing();
+        self.food_count = std::sync::atomic::AtomicUsize::new(self.get_food_count());
         self.food_dirty = true;
     }
 }
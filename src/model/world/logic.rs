@@ -1,6 +1,12 @@
+use crate::model::energy_ledger::EnergyLedger;
 use crate::model::environment::Environment;
+use crate::model::route_trail::RouteTrail;
 use crate::model::world::World;
-use primordium_data::{Entity, Food, GeneType, Identity, Intel, Metabolism, Physics, Position};
+use primordium_core::systems::{biological, status};
+use primordium_data::{
+    Bookmark, Disaster, DisasterRecord, Entity, Food, GeneType, Health, Identity, Intel,
+    Metabolism, Pathogen, Physics, Position,
+};
 use rand::Rng;
 
 impl World {
@@ -64,6 +70,8 @@ impl World {
         }
     }
 
+    /// Shares `amount` energy among the living members of `lineage_id` and
+    /// blesses them.
     pub fn apply_relief(&mut self, lineage_id: uuid::Uuid, amount: f32) {
         let mut targets = Vec::new();
         {
@@ -80,8 +88,84 @@ impl World {
                 if let Ok(mut met) = self.ecs.get::<&mut Metabolism>(h) {
                     met.energy = (met.energy + per_target).min(met.max_energy);
                 }
+                if let Ok(mut health) = self.ecs.get::<&mut Health>(h) {
+                    status::apply(&mut health.effects, status::blessing());
+                }
+            }
+        }
+    }
+
+    /// Strikes `disaster` on the next tick, where disasters are rolled, so
+    /// that it lands in the same place of the tick when replayed.
+    pub fn queue_disaster(&mut self, disaster: Disaster) {
+        self.pending_disasters.push(disaster);
+    }
+
+    /// Replays `disasters` at their ticks in place of rolling new ones or
+    /// striking queued ones.
+    pub fn script_disasters(&mut self, disasters: Vec<DisasterRecord>) {
+        self.scripted_disasters = Some(disasters.into());
+    }
+
+    /// Infects every entity within `radius` of `(x, y)` with `pathogen` and
+    /// returns how many were infected.
+    ///
+    /// Unlike contact spread, immunity is ignored and existing infections are
+    /// replaced, so a designed outbreak always starts from a known cohort.
+    pub fn release_pathogen_at(
+        &mut self,
+        pathogen: &Pathogen,
+        x: f64,
+        y: f64,
+        radius: f64,
+    ) -> usize {
+        self.infect_where(pathogen, |phys, _| {
+            let (dx, dy) = (phys.x - x, phys.y - y);
+            dx * dx + dy * dy <= radius * radius
+        })
+    }
+
+    /// Infects every living member of `lineage_id` with `pathogen` and returns
+    /// how many were infected. See [`World::release_pathogen_at`].
+    pub fn release_pathogen_in_lineage(
+        &mut self,
+        pathogen: &Pathogen,
+        lineage_id: uuid::Uuid,
+    ) -> usize {
+        self.infect_where(pathogen, |_, met| met.lineage_id == lineage_id)
+    }
+
+    fn infect_where(
+        &mut self,
+        pathogen: &Pathogen,
+        predicate: impl Fn(&Physics, &Metabolism) -> bool,
+    ) -> usize {
+        let mut infected = 0;
+        for (_handle, (phys, met, health)) in
+            self.ecs.query_mut::<(&Physics, &Metabolism, &mut Health)>()
+        {
+            if predicate(phys, met) {
+                biological::infect_components(health, pathogen);
+                infected += 1;
             }
         }
+        infected
+    }
+
+    /// Records a bookmark at `tick`, keeping bookmarks ordered by tick.
+    pub fn add_bookmark(&mut self, tick: u64, note: String) {
+        let at = self.bookmarks.partition_point(|b| b.tick <= tick);
+        self.bookmarks.insert(at, Bookmark { tick, note });
+    }
+
+    /// Clears a tripped guardrail so the simulation can resume.
+    ///
+    /// The limit of the breaker that fired is doubled so the world does not
+    /// immediately halt again on the next tick.
+    pub fn acknowledge_guardrail(&mut self) {
+        if let Some(trip) = self.guardrail_trip.take() {
+            crate::model::guardrail::raise_limit(&mut self.config.guardrail, trip.kind);
+        }
     }
 
     pub fn spawn_entity(&mut self, entity: Entity) -> hecs::Entity {
@@ -97,6 +181,12 @@ impl World {
         ))
     }
 
+    /// Archetypes of the ECS world with their component sets and entity
+    /// counts; see [`crate::model::introspection::EcsReport`].
+    pub fn ecs_report(&self) -> crate::model::introspection::EcsReport {
+        crate::model::introspection::EcsReport::of(&self.ecs)
+    }
+
     pub fn apply_trade(
         &mut self,
         env: &mut Environment,
@@ -130,15 +220,10 @@ impl World {
                         let fx = rng.gen_range(1..self.width - 1);
                         let fy = rng.gen_range(1..self.height - 1);
                         let n_type = rng.gen_range(0.0..1.0);
-                        self.ecs.spawn((
-                            Food::new(fx, fy, n_type),
-                            Position {
-                                x: fx as f64,
-                                y: fy as f64,
-                            },
-                            primordium_data::MetabolicNiche(n_type),
-                        ));
+                        self.spawn_food_at(fx, fy, n_type);
                     }
+                } else if self.uses_food_field() {
+                    self.food_field.remove_units(amount as usize);
                     self.food_dirty = true;
                 } else {
                     let mut handles = Vec::new();
@@ -157,6 +242,39 @@ impl World {
         }
     }
 
+    /// Starts recording the energy ledger of `entity_id`, or stops with `None`.
+    ///
+    /// Re-tracking the entity that is already tracked keeps its history.
+    pub fn track_energy(&mut self, entity_id: Option<uuid::Uuid>) {
+        match entity_id {
+            Some(id)
+                if self
+                    .energy_ledger
+                    .as_ref()
+                    .is_some_and(|l| l.entity_id == id) => {}
+            Some(id) => {
+                self.energy_ledger = Some(EnergyLedger::new(
+                    id,
+                    self.config.analytics.energy_ledger_ticks,
+                ));
+            }
+            None => self.energy_ledger = None,
+        }
+    }
+
+    /// Starts recording the route trail of `entity_id`, or stops with `None`.
+    ///
+    /// Re-tracking the entity that is already tracked keeps its trail.
+    pub fn track_route(&mut self, entity_id: Option<uuid::Uuid>) {
+        match entity_id {
+            Some(id) if self.route_trail.as_ref().is_some_and(|t| t.entity_id == id) => {}
+            Some(id) => {
+                self.route_trail = Some(RouteTrail::new(id, self.config.analytics.trail_length));
+            }
+            None => self.route_trail = None,
+        }
+    }
+
     pub fn clear_research_deltas(&mut self, entity_id: uuid::Uuid) {
         for (_handle, (identity, intel)) in self.ecs.query_mut::<(&Identity, &mut Intel)>() {
             if identity.id == entity_id {
@@ -230,9 +348,13 @@ impl World {
             hasher.update(f.1.to_bits().to_le_bytes());
             hasher.update(f.2.to_bits().to_le_bytes());
         }
+        for (units, nutrient) in self.food_field.units.iter().zip(&self.food_field.nutrient) {
+            hasher.update(units.to_le_bytes());
+            hasher.update(nutrient.to_bits().to_le_bytes());
+        }
 
         // 4. Terrain
-        for cell in &self.terrain.cells {
+        for cell in self.terrain.cells() {
             hasher.update((cell.terrain_type as u8).to_le_bytes());
             hasher.update(cell.fertility.to_bits().to_le_bytes());
         }
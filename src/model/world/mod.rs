@@ -1,7 +1,8 @@
 use crate::model::config::AppConfig;
+use crate::model::food_field::FoodField;
 use crate::model::lineage_registry::LineageRegistry;
 use crate::model::observer::WorldObserver;
-use crate::model::pheromone::PheromoneGrid;
+use crate::model::pheromone::{PheromoneGrid, SENSED_INPUTS};
 use crate::model::sound::SoundGrid;
 use crate::model::spatial_hash::SpatialHash;
 use crate::model::terrain::TerrainGrid;
@@ -17,11 +18,28 @@ fn default_rng() -> ChaCha8Rng {
     ChaCha8Rng::seed_from_u64(0)
 }
 
+/// The generator disasters roll on: its own stream of `seed`, so that other
+/// systems drawing more or fewer numbers never move the disaster timeline.
+pub(crate) fn disaster_rng(seed: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(primordium_core::systems::environment::DISASTER_STREAM);
+    rng
+}
+
+fn default_disaster_rng() -> ChaCha8Rng {
+    disaster_rng(0)
+}
+
+pub mod continuity;
+pub mod experiment;
 pub mod finalize;
+pub mod genome_archive;
 pub mod init;
 pub mod logic;
+pub mod scenario;
 pub mod state;
 pub mod systems;
+pub mod tags;
 pub mod update;
 
 pub use state::{EntityComponents, EntityDecision, InternalEntitySnapshot};
@@ -32,9 +50,13 @@ pub struct SystemContext<'a> {
     pub food_hash: &'a SpatialHash,
     pub spatial_hash: &'a SpatialHash,
     pub pheromones: &'a PheromoneGrid,
+    /// Channels feeding the brain's pheromone inputs; see
+    /// [`crate::model::config::PheromoneConfig`].
+    pub pheromone_inputs: [Option<usize>; SENSED_INPUTS],
     pub sound: &'a SoundGrid,
     pub pressure: &'a crate::model::pressure::PressureGrid,
     pub influence: &'a crate::model::influence::InfluenceGrid,
+    pub roads: &'a crate::model::road::RoadGrid,
     pub terrain: &'a TerrainGrid,
     pub tick: u64,
     pub registry: &'a LineageRegistry,
@@ -53,6 +75,18 @@ pub struct World {
     pub ecs: hecs::World,
 
     pub food_persist: Vec<primordium_data::Food>,
+    /// Incubating eggs, moved out of the ECS while saving.
+    #[serde(default)]
+    pub egg_persist: Vec<primordium_data::Egg>,
+    /// Living entities, moved out of the ECS while saving.
+    #[serde(default)]
+    pub entity_persist: Vec<primordium_data::Entity>,
+    /// Tags of the saved entities, by entity id.
+    #[serde(default)]
+    pub tag_persist: Vec<(uuid::Uuid, primordium_data::Tags)>,
+    /// Food storage when `food.representation = "field"`; empty otherwise.
+    #[serde(default)]
+    pub food_field: FoodField,
 
     #[serde(skip, default = "HistoryLogger::new_dummy")]
     pub logger: HistoryLogger,
@@ -67,18 +101,52 @@ pub struct World {
     pub sound: Arc<SoundGrid>,
     pub pressure: Arc<crate::model::pressure::PressureGrid>,
     pub influence: Arc<crate::model::influence::InfluenceGrid>,
+    /// Roads worn by lineage traffic; rebuilt when missing or mis-sized.
+    #[serde(default)]
+    pub roads: Arc<crate::model::road::RoadGrid>,
+    /// Recent genetic change of newborns by cell; rebuilt empty when
+    /// missing or mis-sized.
+    #[serde(default)]
+    pub mutation_heat: Arc<crate::model::mutation_heat::MutationHeatGrid>,
     pub social_grid: Arc<Vec<u8>>,
     pub lineage_registry: LineageRegistry,
     pub fossil_registry: FossilRegistry,
     pub config: AppConfig,
     pub log_dir: String,
     pub active_pathogens: Vec<primordium_data::Pathogen>,
+    /// User annotations on the timeline, ordered by tick.
+    #[serde(default)]
+    pub bookmarks: Vec<primordium_data::Bookmark>,
+    /// The run that produced this world; `None` in saves from before
+    /// provenance was recorded.
+    #[serde(default)]
+    pub provenance: Option<primordium_data::RunManifest>,
+    /// Running cohort experiment, if any.
+    #[serde(default)]
+    pub experiment: Option<crate::model::experiment::Experiment>,
+    /// Per-lineage dominance ladders settled by contests.
+    #[serde(default)]
+    pub dominance: primordium_core::systems::dominance::DominanceHierarchy,
+    /// Monuments raised by civilised lineages, including ruins.
+    #[serde(default)]
+    pub monuments: primordium_core::monument::MonumentRegistry,
     #[serde(skip, default = "WorldObserver::new")]
     pub observer: WorldObserver,
     #[serde(skip, default)]
     pub best_legends: HashMap<uuid::Uuid, primordium_data::Legend>,
     #[serde(skip, default = "default_rng")]
     pub rng: ChaCha8Rng,
+    #[serde(skip, default = "default_disaster_rng")]
+    pub disaster_rng: ChaCha8Rng,
+    /// Every disaster that has struck, oldest first.
+    #[serde(default)]
+    pub disasters: Vec<primordium_data::DisasterRecord>,
+    /// Disasters to replay instead of rolling new ones; set during playback.
+    /// Disasters queued by [`World::queue_disaster`] for the next tick.
+    #[serde(skip, default)]
+    pub pending_disasters: Vec<primordium_data::Disaster>,
+    #[serde(skip, default)]
+    pub scripted_disasters: Option<std::collections::VecDeque<primordium_data::DisasterRecord>>,
     #[serde(skip, default)]
     pub killed_ids: HashSet<uuid::Uuid>,
     #[serde(skip, default)]
@@ -89,6 +157,17 @@ pub struct World {
     #[serde(skip, default)]
     pub interaction_buffer: Vec<primordium_core::interaction::InteractionCommand>,
     #[serde(skip, default)]
+    pub interaction_recorder: primordium_core::interaction::InteractionRecorder,
+    /// Pairwise interactions recorded while `analytics.record_interactions` is set.
+    #[serde(skip, default)]
+    pub interaction_graph: primordium_core::interaction::InteractionGraph,
+    /// Energy ledger of the entity being debugged, if any.
+    #[serde(skip, default)]
+    pub energy_ledger: Option<primordium_core::energy_ledger::EnergyLedger>,
+    /// Route trail of the entity being followed, if any.
+    #[serde(skip, default)]
+    pub route_trail: Option<primordium_core::route_trail::RouteTrail>,
+    #[serde(skip, default)]
     pub lineage_consumption: Vec<(uuid::Uuid, f64)>,
     #[serde(skip, default)]
     pub entity_snapshots: Vec<crate::model::snapshot::InternalEntitySnapshot>,
@@ -103,9 +182,24 @@ pub struct World {
     #[serde(skip, default)]
     pub spatial_data_buffer: Vec<(f64, f64, uuid::Uuid)>,
     #[serde(skip, default)]
-    pub spatial_sort_buffer: Vec<(f64, f64, uuid::Uuid, uuid::Uuid)>,
+    pub spatial_sort_buffer: Vec<(f64, f64, uuid::Uuid, uuid::Uuid, f64)>,
     #[serde(skip, default)]
     pub food_positions_buffer: Vec<(f64, f64)>,
+    /// Budgeted path planning shared by all systems.
+    #[serde(skip, default)]
+    pub path_service: primordium_core::pathfinding::PathService,
+    /// Set when a guardrail circuit breaker halts the simulation.
+    #[serde(skip, default)]
+    pub guardrail_trip: Option<crate::model::guardrail::GuardrailTrip>,
+    #[serde(skip, default)]
+    pub anomalies: crate::model::anomaly::AnomalyDetector,
+    /// Population and food forecast, refitted every
+    /// `forecast.sample_interval` ticks.
+    #[serde(skip, default)]
+    pub forecaster: crate::model::forecast::Forecaster,
+    /// Named biome regions, reclassified every `biomes.interval` ticks.
+    #[serde(skip, default)]
+    pub biomes: crate::model::biome::BiomeMap,
 }
 
 #[cfg(test)]
@@ -0,0 +1,164 @@
+//! Scenario events: scripted interventions applied to a running world.
+//!
+//! Events are read from JSON, tagged by `event`, e.g.
+//! `{"event": "food_boom", "amount": 200}`. They are what the inbox (see
+//! `crate::app::inbox`) injects from files dropped in by external tools.
+
+use crate::model::brain::GenotypeLogic;
+use crate::model::environment::{ClimateState, Environment};
+use crate::model::lifecycle;
+use crate::model::pathogen;
+use crate::model::schedule::EventKind;
+use crate::model::world::World;
+use crate::model::BrainLogic;
+use anyhow::Context;
+use primordium_data::{Disaster, Genotype};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+fn one() -> usize {
+    1
+}
+
+fn default_radius() -> f64 {
+    10.0
+}
+
+/// One scripted intervention. Positions left out are chosen at random.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ScenarioEvent {
+    /// Spawns `count` organisms from a genotype hex string.
+    Spawn {
+        dna: String,
+        #[serde(default = "one")]
+        count: usize,
+        #[serde(default)]
+        x: Option<f64>,
+        #[serde(default)]
+        y: Option<f64>,
+    },
+    /// Scatters `amount` food items of random nutrient type.
+    FoodBoom { amount: usize },
+    /// Forces the climate, or hands it back to the carbon cycle with `null`.
+    Climate { state: Option<ClimateState> },
+    /// Sets the atmospheric carbon level.
+    Carbon { level: f64 },
+    /// Infects everything within `radius` of a point with a new pathogen.
+    Outbreak {
+        #[serde(default)]
+        x: Option<f64>,
+        #[serde(default)]
+        y: Option<f64>,
+        #[serde(default = "default_radius")]
+        radius: f64,
+    },
+    /// Records a bookmark at the current tick.
+    Bookmark { note: String },
+    /// Schedules `disaster` to strike `delay` ticks from now, on the next
+    /// tick at the earliest.
+    Disaster {
+        disaster: Disaster,
+        #[serde(default)]
+        delay: u64,
+    },
+}
+
+impl ScenarioEvent {
+    /// Parses a single event or a JSON array of events.
+    pub fn parse_many(json: &str) -> anyhow::Result<Vec<Self>> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        Ok(if value.is_array() {
+            serde_json::from_value(value)?
+        } else {
+            vec![serde_json::from_value(value)?]
+        })
+    }
+}
+
+impl World {
+    /// Applies `event` and returns a one-line description of what it did.
+    pub fn apply_scenario_event(
+        &mut self,
+        env: &mut Environment,
+        event: &ScenarioEvent,
+    ) -> anyhow::Result<String> {
+        match event {
+            ScenarioEvent::Spawn { dna, count, x, y } => {
+                let mut genotype = Genotype::from_hex(dna.trim()).context("Invalid DNA")?;
+                genotype.brain.initialize_node_idx_map();
+                let genotype = Arc::new(genotype);
+                for _ in 0..*count {
+                    let (px, py) = self.position_or_random(*x, *y);
+                    let mut e = lifecycle::create_entity_with_rng(px, py, self.tick, &mut self.rng);
+                    e.physics.sensing_range = genotype.sensing_range;
+                    e.physics.max_speed = genotype.max_speed;
+                    e.metabolism.max_energy = genotype.max_energy;
+                    e.metabolism.lineage_id = genotype.lineage_id;
+                    e.intel.genotype = genotype.clone();
+                    self.lineage_registry.record_birth(
+                        e.metabolism.lineage_id,
+                        e.metabolism.generation,
+                        self.tick,
+                    );
+                    self.spawn_entity(e);
+                }
+                Ok(format!(
+                    "spawned {count} of lineage {}",
+                    &genotype.lineage_id.to_string()[..8]
+                ))
+            }
+            ScenarioEvent::FoodBoom { amount } => {
+                for _ in 0..*amount {
+                    let fx = self.rng.gen_range(1..self.width - 1);
+                    let fy = self.rng.gen_range(1..self.height - 1);
+                    let n_type = self.rng.gen_range(0.0..1.0);
+                    self.spawn_food_at(fx, fy, n_type);
+                }
+                Ok(format!("spawned {amount} food"))
+            }
+            ScenarioEvent::Climate { state } => {
+                env.god_climate_override = *state;
+                Ok(match state {
+                    Some(state) => format!("climate forced to {}", state.name()),
+                    None => "climate restored".to_string(),
+                })
+            }
+            ScenarioEvent::Carbon { level } => {
+                env.carbon_level = level.clamp(0.0, 2000.0);
+                Ok(format!("carbon set to {:.0}", env.carbon_level))
+            }
+            ScenarioEvent::Outbreak { x, y, radius } => {
+                let (px, py) = self.position_or_random(*x, *y);
+                let pathogen = pathogen::create_random_pathogen_with_rng(&mut self.rng);
+                let infected = self.release_pathogen_at(&pathogen, px, py, *radius);
+                Ok(format!(
+                    "outbreak at ({px:.0}, {py:.0}) infected {infected}"
+                ))
+            }
+            ScenarioEvent::Bookmark { note } => {
+                self.add_bookmark(self.tick, note.clone());
+                Ok(format!("bookmarked \"{note}\""))
+            }
+            ScenarioEvent::Disaster { disaster, delay } => {
+                let tick = self.tick + (*delay).max(1);
+                env.schedule.at(
+                    tick,
+                    EventKind::Disaster {
+                        disaster: *disaster,
+                    },
+                );
+                Ok(format!("{} scheduled for tick {tick}", disaster.name()))
+            }
+        }
+    }
+
+    fn position_or_random(&mut self, x: Option<f64>, y: Option<f64>) -> (f64, f64) {
+        let max_x = f64::from(self.width) - 1.0;
+        let max_y = f64::from(self.height) - 1.0;
+        let px = x.unwrap_or_else(|| self.rng.gen_range(1.0..max_x));
+        let py = y.unwrap_or_else(|| self.rng.gen_range(1.0..max_y));
+        (px.clamp(0.0, max_x), py.clamp(0.0, max_y))
+    }
+}
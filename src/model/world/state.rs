@@ -1,7 +1,13 @@
+use crate::model::config::FoodRepresentation;
+use crate::model::crest::Crest;
+use crate::model::environment::Environment;
 use crate::model::lifecycle;
-use crate::model::snapshot::{EntitySnapshot, WorldSnapshot};
+use crate::model::snapshot::{EggSnapshot, EntitySnapshot, WorldSnapshot};
 use crate::model::world::World;
-use primordium_data::{Food, Physics};
+use primordium_core::body;
+use primordium_core::perception::{self, Perception};
+use primordium_core::systems::intel;
+use primordium_data::{Egg, Food, Identity, MetabolicNiche, Physics, Position, Tags};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -26,7 +32,7 @@ pub struct SpatialHashResult {
 
 #[derive(Clone, Default)]
 pub struct EntityDecision {
-    pub outputs: [f32; 12],
+    pub outputs: [f32; 15],
     pub nearby_count: usize,
     pub grn_speed_mod: f64,
     pub grn_sensing_mod: f64,
@@ -39,14 +45,17 @@ impl World {
         self.entity_snapshots.clear();
         for &handle in handles {
             if let Ok(mut query) = self.ecs.query_one::<EntityComponents>(handle) {
-                if let Some((identity, position, _velocity, physics, metabolism, intel, health)) =
+                if let Some((identity, position, velocity, physics, metabolism, intel, health)) =
                     query.get()
                 {
                     self.entity_snapshots.push(InternalEntitySnapshot {
                         id: identity.id,
                         lineage_id: metabolism.lineage_id,
+                        parent_id: identity.parent_id,
                         x: position.x,
                         y: position.y,
+                        vx: velocity.vx,
+                        vy: velocity.vy,
                         energy: metabolism.energy,
                         birth_tick: metabolism.birth_tick,
                         offspring_count: metabolism.offspring_count,
@@ -66,6 +75,14 @@ impl World {
                         ),
                         trophic_potential: metabolism.trophic_potential,
                         genotype: Some(Arc::clone(&intel.genotype)),
+                        memory_writes: intel.memory.writes,
+                        memory_recalls: intel.memory.recalls,
+                        deceptions: intel.deceptions,
+                        infection_remaining: primordium_core::systems::status::remaining(
+                            &health.effects,
+                            primordium_data::StatusKind::Infection,
+                        ),
+                        dominance: intel.dominance,
                     });
                 }
             }
@@ -89,7 +106,155 @@ impl World {
 
     /// Returns the current number of food items in the simulation.
     pub fn get_food_count(&self) -> usize {
-        self.ecs.query::<&Food>().iter().count()
+        self.ecs.query::<&Food>().iter().count() + self.food_field.total()
+    }
+
+    /// Whether food lives in the density field rather than as ECS entities.
+    pub fn uses_food_field(&self) -> bool {
+        self.config.food.representation == FoodRepresentation::Field
+    }
+
+    /// Places one food item at a cell using the configured representation.
+    pub fn spawn_food_at(&mut self, x: u16, y: u16, nutrient_type: f32) {
+        if self.uses_food_field() {
+            self.food_field.deposit(x, y, nutrient_type);
+        } else {
+            self.ecs.spawn((
+                Food::new(x, y, nutrient_type),
+                Position {
+                    x: x as f64,
+                    y: y as f64,
+                },
+                MetabolicNiche(nutrient_type),
+            ));
+        }
+        self.food_dirty = true;
+    }
+
+    /// Brain and last activations (indexed like `brain.nodes`) of the entity
+    /// whose ID starts with `id_prefix`. Fails if no entity or more than one
+    /// matches.
+    pub fn brain_of(&self, id_prefix: &str) -> anyhow::Result<(primordium_data::Brain, Vec<f32>)> {
+        let handle = self.handle_by_prefix(id_prefix)?;
+        let intel = self.ecs.get::<&primordium_data::Intel>(handle)?;
+        let brain = &intel.genotype.brain;
+        let mut values = intel.last_activations.0.clone();
+        values.resize(brain.nodes.len(), 0.0);
+        Ok((brain.clone(), values))
+    }
+
+    /// The living entity whose ID starts with `id_prefix`, as an owned struct.
+    pub fn entity_of(&self, id_prefix: &str) -> anyhow::Result<primordium_data::Entity> {
+        let handle = self.handle_by_prefix(id_prefix)?;
+        let mut query = self.ecs.query_one::<(
+            &Identity,
+            &primordium_data::Position,
+            &primordium_data::Velocity,
+            &primordium_data::Appearance,
+            &primordium_data::Physics,
+            &primordium_data::Metabolism,
+            &primordium_data::Health,
+            &primordium_data::Intel,
+        )>(handle)?;
+        let (identity, position, velocity, appearance, physics, metabolism, health, intel) = query
+            .get()
+            .ok_or_else(|| anyhow::anyhow!("Entity '{id_prefix}' is not a living organism"))?;
+        Ok(primordium_data::Entity {
+            identity: identity.clone(),
+            position: *position,
+            velocity: velocity.clone(),
+            appearance: appearance.clone(),
+            physics: physics.clone(),
+            metabolism: metabolism.clone(),
+            health: health.clone(),
+            intel: intel.clone(),
+        })
+    }
+
+    /// Crest of the lineage whose ID starts with `id_prefix`, charged from
+    /// its fittest recorded genotype or else a living member's.
+    pub fn crest_of(&self, id_prefix: &str) -> anyhow::Result<Crest> {
+        let prefix = id_prefix.trim().to_lowercase();
+        let mut matches = self
+            .lineage_registry
+            .lineages
+            .iter()
+            .filter(|(id, _)| id.to_string().starts_with(&prefix));
+        let Some((&lineage_id, record)) = matches.next() else {
+            anyhow::bail!("No lineage with ID starting with '{id_prefix}'");
+        };
+        if matches.next().is_some() {
+            anyhow::bail!("ID prefix '{id_prefix}' matches more than one lineage");
+        }
+        let genotype = record.max_fitness_genotype.clone().or_else(|| {
+            self.ecs
+                .query::<&primordium_data::Intel>()
+                .iter()
+                .find(|(_, intel)| intel.genotype.lineage_id == lineage_id)
+                .map(|(_, intel)| (*intel.genotype).clone())
+        });
+        Ok(Crest::new(lineage_id, genotype.as_ref()))
+    }
+
+    /// What entity `id` sensed on its last tick, or `None` if it is not a
+    /// living organism.
+    pub fn perception_of(&self, id: uuid::Uuid, env: &Environment) -> Option<Perception> {
+        let mut query = self.ecs.query::<(
+            &Identity,
+            &Position,
+            &Physics,
+            &primordium_data::Metabolism,
+            &primordium_data::Intel,
+        )>();
+        let (_, (_, pos, phys, met, intel)) =
+            query.iter().find(|(_, (identity, ..))| identity.id == id)?;
+        let nearby_kin = self.spatial_hash.count_nearby_kin_fast(
+            pos.x,
+            pos.y,
+            phys.sensing_range,
+            met.lineage_id,
+        );
+        let (_, sensing_mod, _) = intel::apply_grn_rules(intel::GrnContext {
+            genotype: &intel.genotype,
+            metabolism: met,
+            oxygen_level: env.oxygen_level,
+            carbon_level: env.carbon_level,
+            nearby_kin,
+            tick: self.tick,
+        });
+        let sensing_range = phys.sensing_range * sensing_mod;
+        // Pheromones are sensed over half the sensing range.
+        let sensed = &self.config.pheromone.sensed;
+        let pheromone_gradients = self.pheromones.channels_named(sensed).map(|channel| {
+            channel.map_or((0.0, 0.0), |c| {
+                self.pheromones
+                    .gradient(pos.x, pos.y, sensing_range / 2.0, c)
+            })
+        });
+        Some(Perception {
+            x: pos.x,
+            y: pos.y,
+            sensing_range,
+            inputs: perception::brain_inputs(&intel.genotype.brain, &intel.last_activations.0),
+            pheromone_channels: std::array::from_fn(|i| sensed.get(i).cloned()),
+            pheromone_gradients,
+        })
+    }
+
+    /// The one entity whose ID starts with `id_prefix`.
+    fn handle_by_prefix(&self, id_prefix: &str) -> anyhow::Result<hecs::Entity> {
+        let prefix = id_prefix.trim().to_lowercase();
+        let mut query = self.ecs.query::<(&Identity, &primordium_data::Intel)>();
+        let mut matches = query
+            .iter()
+            .filter(|(_, (identity, _))| identity.id.to_string().starts_with(&prefix));
+        let Some((handle, _)) = matches.next() else {
+            anyhow::bail!("No entity with ID starting with '{id_prefix}'");
+        };
+        if matches.next().is_some() {
+            anyhow::bail!("ID prefix '{id_prefix}' matches more than one entity");
+        }
+        Ok(handle)
     }
 
     pub fn get_sorted_handles(&self) -> Vec<hecs::Entity> {
@@ -155,14 +320,34 @@ impl World {
             self.food_persist.push(f.clone());
         }
         self.food_persist.sort_by_key(|f| (f.x, f.y));
+
+        self.egg_persist.clear();
+        for (_handle, egg) in self.ecs.query::<&Egg>().iter() {
+            self.egg_persist.push(egg.clone());
+        }
+        self.egg_persist.sort_by_key(|e| e.hatchling.identity.id);
+
+        self.entity_persist = self.get_all_entities();
+        self.tag_persist = self
+            .ecs
+            .query::<(&Identity, &Tags)>()
+            .iter()
+            .map(|(_, (identity, tags))| (identity.id, tags.clone()))
+            .collect();
+        self.tag_persist.sort_by_key(|(id, _)| *id);
     }
 
     pub fn create_snapshot(&self, selected_id: Option<uuid::Uuid>) -> Arc<WorldSnapshot> {
         let mut entities = Vec::new();
 
-        for (_handle, (identity, position, _velocity, physics, metabolism, intel, health)) in
+        for (handle, (identity, position, _velocity, physics, metabolism, intel, health)) in
             self.ecs.query::<EntityComponents>().iter()
         {
+            let tags = self
+                .ecs
+                .get::<&Tags>(handle)
+                .map(|t| t.iter().map(String::from).collect())
+                .unwrap_or_default();
             entities.push(EntitySnapshot {
                 id: identity.id,
                 name: lifecycle::get_name_components(&identity.id, metabolism),
@@ -173,6 +358,7 @@ impl World {
                 b: physics.b,
                 energy: metabolism.energy,
                 max_energy: metabolism.max_energy,
+                hydration: metabolism.hydration,
                 generation: metabolism.generation,
                 age: self.tick - metabolism.birth_tick,
                 offspring: metabolism.offspring_count,
@@ -213,6 +399,9 @@ impl World {
                 },
                 specialization: intel.specialization,
                 is_larva: !metabolism.has_metamorphosed,
+                effects: health.effects.clone(),
+                tags,
+                body_size: intel.genotype.body_size,
             });
         }
 
@@ -222,12 +411,37 @@ impl World {
         for (_handle, f) in self.ecs.query::<&Food>().iter() {
             food.push(f.clone());
         }
+        food.extend(self.food_field.to_food_items());
         food.sort_by_key(|f| (f.x, f.y));
 
+        let incubation_ticks = self.config.eggs.incubation_ticks as f32;
+        let mut eggs: Vec<_> = self
+            .ecs
+            .query::<(&Position, &Egg)>()
+            .iter()
+            .map(|(_, (pos, egg))| {
+                let physics = &egg.hatchling.physics;
+                (
+                    egg.hatchling.identity.id,
+                    EggSnapshot {
+                        x: pos.x,
+                        y: pos.y,
+                        r: physics.r,
+                        g: physics.g,
+                        b: physics.b,
+                        progress: (egg.incubation / incubation_ticks).min(1.0),
+                    },
+                )
+            })
+            .collect();
+        eggs.sort_by_key(|(id, _)| *id);
+        let eggs = eggs.into_iter().map(|(_, egg)| egg).collect();
+
         Arc::new(WorldSnapshot {
             tick: self.tick,
             entities,
             food,
+            eggs,
             stats: Arc::clone(&self.pop_stats),
             hall_of_fame: Arc::clone(&self.hall_of_fame),
             terrain: Arc::clone(&self.terrain),
@@ -235,6 +449,9 @@ impl World {
             sound: Arc::clone(&self.sound),
             pressure: Arc::clone(&self.pressure),
             influence: Arc::clone(&self.influence),
+            roads: Arc::clone(&self.roads),
+            mutation_heat: Arc::clone(&self.mutation_heat),
+            monuments: self.monuments.monuments.clone(),
             social_grid: Arc::clone(&self.social_grid),
             rank_grid: Arc::clone(&self.cached_rank_grid),
             width: self.width,
@@ -247,10 +464,20 @@ impl World {
             &primordium_data::Identity,
             &primordium_data::Position,
             &primordium_data::Metabolism,
+            &primordium_data::Intel,
         )>();
         let mut entity_data: Vec<_> = query
             .iter()
-            .map(|(h, (i, p, m))| (i.id, h, p.x, p.y, m.lineage_id))
+            .map(|(h, (i, p, m, intel))| {
+                (
+                    i.id,
+                    h,
+                    p.x,
+                    p.y,
+                    m.lineage_id,
+                    body::radius(intel.genotype.body_size),
+                )
+            })
             .collect();
         entity_data.sort_by_key(|d| d.0);
 
@@ -260,7 +487,7 @@ impl World {
         let mut entity_handles = Vec::new();
         let mut entity_id_map = HashMap::new();
 
-        for (id, handle, x, y, lid) in entity_data {
+        for &(id, handle, x, y, lid, _) in &entity_data {
             let idx = entity_handles.len();
             entity_id_map.insert(id, idx);
             entity_handles.push(handle);
@@ -269,6 +496,8 @@ impl World {
 
         self.spatial_hash
             .build_with_lineage(&spatial_data, self.width, self.height);
+        self.spatial_hash
+            .set_body_radii(entity_data.iter().map(|d| d.5));
 
         let mut food_handles = Vec::new();
         let mut food_positions = Vec::new();
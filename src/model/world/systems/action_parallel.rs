@@ -30,6 +30,12 @@ pub fn calculate_actions_parallel(
 
                 let eff_max_speed = phys.max_speed * grn_speed_mod;
 
+                let migration = ctx
+                    .registry
+                    .lineages
+                    .get(&met.lineage_id)
+                    .and_then(|record| record.migration.as_ref());
+
                 let mut action_entity = action::ActionEntity {
                     id: &identity.id,
                     position: pos,
@@ -38,6 +44,7 @@ pub fn calculate_actions_parallel(
                     metabolism: met,
                     intel,
                     health: _health,
+                    migration,
                 };
                 action::action_system_components_with_modifiers(
                     &mut action_entity,
@@ -48,10 +55,12 @@ pub fn calculate_actions_parallel(
                         config: ctx.config,
                         terrain: ctx.terrain,
                         influence: ctx.influence,
+                        roads: ctx.roads,
                         snapshots: ctx.snapshots,
                         entity_id_map,
                         spatial_hash: ctx.spatial_hash,
                         pressure: ctx.pressure,
+                        pheromones: ctx.pheromones,
                         width: ctx.config.world.width,
                         height: ctx.config.world.height,
                     },
@@ -81,7 +90,7 @@ pub fn apply_actions_sequential(
         }
 
         for p in output.pheromones {
-            pheromones.deposit(p.x, p.y, p.ptype, p.amount);
+            pheromones.deposit_channel(p.x, p.y, p.channel, p.amount);
         }
         for s in output.sounds {
             sound.deposit(s.x, s.y, s.amount);
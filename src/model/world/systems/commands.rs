@@ -1,10 +1,16 @@
 use crate::model::environment::Environment;
 use crate::model::lifecycle;
 use crate::model::world::{EntityDecision, SystemContext};
+use primordium_core::body;
+use primordium_core::flora;
 use primordium_core::interaction::InteractionCommand;
+use primordium_core::systems::care;
+use primordium_core::systems::dominance;
+use primordium_core::systems::grooming;
 use primordium_core::systems::social;
 use primordium_core::systems::social::ReproductionContext;
-use primordium_data::{Food, Position, Specialization};
+use primordium_core::systems::status;
+use primordium_data::{Position, Specialization, StatusKind};
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 use std::collections::HashMap;
@@ -25,6 +31,7 @@ pub struct BondContext<'a, R: rand::Rng> {
     pub pos: &'a primordium_data::Position,
     pub met: &'a primordium_data::Metabolism,
     pub intel: &'a primordium_data::Intel,
+    pub health: &'a primordium_data::Health,
     pub decision: &'a EntityDecision,
     pub ctx: &'a SystemContext<'a>,
     pub env: &'a Environment,
@@ -38,6 +45,7 @@ pub struct ReproductionParams<'a, R: rand::Rng> {
     pub pos: &'a primordium_data::Position,
     pub met: &'a primordium_data::Metabolism,
     pub intel: &'a primordium_data::Intel,
+    pub health: &'a primordium_data::Health,
     pub decision: &'a EntityDecision,
     pub ctx: &'a SystemContext<'a>,
     pub env: &'a Environment,
@@ -67,18 +75,27 @@ pub fn generate_eat_cmds(
 ) -> Vec<InteractionCommand> {
     let mut acc = Vec::new();
     if let Some((f_idx, dx_f, dy_f, _)) = decision.sensed_food {
-        if dx_f.abs() < 1.5 && dy_f.abs() < 1.5 {
-            let food_handle = ctx.food_handles[f_idx];
+        // Bodies feed from anywhere in their footprint.
+        let reach = 1.5 + body::radius(intel.genotype.body_size);
+        if dx_f.abs() < reach && dy_f.abs() < reach {
             let mut energy_gain = 0.0;
-            if let Ok(food_data) = ctx.ecs.get::<&Food>(food_handle) {
+            let mut edible = false;
+            if let Some(&(_, _, nutrient_type)) = ctx.food_data.get(f_idx) {
                 let trophic_eff = 1.0 - met.trophic_potential as f64;
                 if trophic_eff > 0.1 {
-                    let niche_eff =
-                        1.0 - (intel.genotype.metabolic_niche - food_data.nutrient_type).abs();
+                    let niche_eff = 1.0 - (intel.genotype.metabolic_niche - nutrient_type).abs();
                     energy_gain = ctx.config.metabolism.food_value * niche_eff as f64 * trophic_eff;
+                    edible = energy_gain > 0.0;
+                    // Toxic plants can leave a mismatched eater worse off.
+                    let toxicity = ctx
+                        .food_handles
+                        .get(f_idx)
+                        .and_then(|&h| ctx.ecs.get::<&primordium_data::Food>(h).ok())
+                        .map_or(0.0, |food| food.toxicity);
+                    energy_gain -= flora::toxin_damage(toxicity, niche_eff, &ctx.config.flora);
                 }
             }
-            if energy_gain > 0.0 {
+            if edible {
                 acc.push(InteractionCommand::EatFood {
                     food_index: f_idx,
                     attacker_idx: i,
@@ -114,12 +131,20 @@ pub fn generate_bond_cmds<R: rand::Rng>(input: BondContext<R>) -> Vec<Interactio
                             partner_id: p_id,
                         });
 
+                        let config = status::mutation_config(
+                            input.ctx.config,
+                            &input.health.effects,
+                            input.env.era_modifiers.mutation_scale,
+                        );
                         let mut repro_ctx = ReproductionContext {
                             tick: input.ctx.tick,
-                            config: input.ctx.config,
+                            config: &config,
                             population: input.pop_len,
                             traits: input.ctx.registry.get_traits(&input.met.lineage_id),
-                            is_radiation_storm: input.env.is_radiation_storm(),
+                            is_radiation_storm: status::has(
+                                &input.health.effects,
+                                StatusKind::Radiation,
+                            ),
                             rng: input.rng,
                             ancestral_genotype: input
                                 .ctx
@@ -232,6 +257,74 @@ pub fn generate_bond_cmds<R: rand::Rng>(input: BondContext<R>) -> Vec<Interactio
     acc
 }
 
+/// Energy a parent hands to each of its juveniles nearby, paid out of its
+/// own reserves.
+pub fn generate_care_cmds(
+    i: usize,
+    intel: &primordium_data::Intel,
+    ctx: &SystemContext,
+) -> Vec<InteractionCommand> {
+    let care_ticks = care::care_ticks(&intel.genotype, ctx.config);
+    let mut acc = Vec::new();
+    for (target_idx, amount) in care::provisions(
+        i,
+        care_ticks,
+        ctx.tick,
+        ctx.snapshots,
+        ctx.spatial_hash,
+        ctx.config,
+    ) {
+        acc.push(InteractionCommand::TransferEnergy { target_idx, amount });
+        acc.push(InteractionCommand::TransferEnergy {
+            target_idx: i,
+            amount: -amount,
+        });
+    }
+    acc
+}
+
+/// Dominance challenge of the lineage mate this entity's `Aggro` output picks.
+pub fn generate_challenge_cmds(
+    i: usize,
+    decision: &EntityDecision,
+    ctx: &SystemContext,
+) -> Vec<InteractionCommand> {
+    dominance::challenge_target(
+        i,
+        decision.outputs[3],
+        ctx.snapshots,
+        ctx.spatial_hash,
+        ctx.config,
+    )
+    .map(|target_idx| InteractionCommand::Challenge {
+        challenger_idx: i,
+        target_idx,
+    })
+    .into_iter()
+    .collect()
+}
+
+/// Grooming of the infected neighbour this entity's `Share` output picks.
+pub fn generate_grooming_cmds(
+    i: usize,
+    decision: &EntityDecision,
+    ctx: &SystemContext,
+) -> Vec<InteractionCommand> {
+    grooming::groom_target(
+        i,
+        decision.outputs[4],
+        ctx.snapshots,
+        ctx.spatial_hash,
+        ctx.config,
+    )
+    .map(|target_idx| InteractionCommand::Groom {
+        groomer_idx: i,
+        target_idx,
+    })
+    .into_iter()
+    .collect()
+}
+
 pub fn generate_predation_cmds(input: PredationContext) -> Vec<InteractionCommand> {
     let mut acc = Vec::new();
     let outputs = input.decision.outputs;
@@ -267,10 +360,13 @@ pub fn generate_predation_cmds(input: PredationContext) -> Vec<InteractionComman
     }
 
     if outputs[3] > 0.5 {
-        input
-            .ctx
-            .spatial_hash
-            .query_callback(input.pos.x, input.pos.y, 1.5, |t_idx| {
+        let size = input.intel.genotype.body_size;
+        input.ctx.spatial_hash.query_bodies(
+            input.pos.x,
+            input.pos.y,
+            1.5,
+            body::radius(size),
+            |t_idx| {
                 if input.i != t_idx {
                     let target_snap = &input.ctx.snapshots[t_idx];
                     let color_dist = (input.phys.r as i32 - target_snap.r as i32).abs()
@@ -280,8 +376,22 @@ pub fn generate_predation_cmds(input: PredationContext) -> Vec<InteractionComman
                     if target_snap.lineage_id == input.met.lineage_id {
                         return;
                     }
+                    let target_size = target_snap
+                        .genotype
+                        .as_ref()
+                        .map_or(primordium_data::MIN_BODY_SIZE, |g| g.body_size);
+                    if !body::can_overpower(size, target_size, &input.ctx.config.body) {
+                        return;
+                    }
 
-                    if color_dist >= input.ctx.config.social.tribe_color_threshold {
+                    let stance = input
+                        .ctx
+                        .registry
+                        .diplomacy
+                        .stance(input.met.lineage_id, target_snap.lineage_id);
+                    if stance
+                        .permits_attack(color_dist >= input.ctx.config.social.tribe_color_threshold)
+                    {
                         let mut multiplier = 1.0;
                         let attacker_status = lifecycle::calculate_status(
                             input.met,
@@ -306,7 +416,10 @@ pub fn generate_predation_cmds(input: PredationContext) -> Vec<InteractionComman
                         let defense_mult = (1.0
                             - allies * input.ctx.config.social.defense_per_ally_reduction)
                             .max(input.ctx.config.social.min_defense_multiplier);
-                        let success_chance = (multiplier * defense_mult).min(1.0) as f32;
+                        let success_chance = (multiplier
+                            * defense_mult
+                            * body::strength(size, target_size))
+                        .min(1.0) as f32;
 
                         let competition_mult = if input.biomass_c > 0.0 {
                             // Use logarithmic scaling to prevent extreme edge cases
@@ -339,7 +452,8 @@ pub fn generate_predation_cmds(input: PredationContext) -> Vec<InteractionComman
                         });
                     }
                 }
-            });
+            },
+        );
     }
     acc
 }
@@ -355,12 +469,17 @@ pub fn generate_reproduction_cmds<R: rand::Rng>(
         input.ctx.config.metabolism.maturity_age,
     ) && input.met.energy > input.ctx.config.metabolism.reproduction_threshold
     {
+        let config = status::mutation_config(
+            input.ctx.config,
+            &input.health.effects,
+            input.env.era_modifiers.mutation_scale,
+        );
         let mut repro_ctx = ReproductionContext {
             tick: input.ctx.tick,
-            config: input.ctx.config,
+            config: &config,
             population: input.pop_len,
             traits: input.ctx.registry.get_traits(&input.met.lineage_id),
-            is_radiation_storm: input.env.is_radiation_storm(),
+            is_radiation_storm: status::has(&input.health.effects, StatusKind::Radiation),
             rng: input.rng,
             ancestral_genotype: input
                 .ctx
@@ -432,6 +551,7 @@ pub fn generate_commands_for_entity(
         pos,
         met,
         intel,
+        health,
         decision,
         ctx,
         env,
@@ -439,6 +559,9 @@ pub fn generate_commands_for_entity(
         id_map,
         rng: &mut local_rng,
     }));
+    acc.extend(generate_care_cmds(i, intel, ctx));
+    acc.extend(generate_grooming_cmds(i, decision, ctx));
+    acc.extend(generate_challenge_cmds(i, decision, ctx));
     acc.extend(generate_predation_cmds(PredationContext {
         i,
         pos,
@@ -455,6 +578,7 @@ pub fn generate_commands_for_entity(
         pos,
         met,
         intel,
+        health,
         decision,
         ctx,
         env,
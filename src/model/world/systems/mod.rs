@@ -2,6 +2,8 @@ pub mod action_parallel;
 pub mod commands;
 pub mod perception;
 
+use crate::model::brain::batch::{self, BatchItem};
+use crate::model::brain::BRAIN_INPUTS;
 use crate::model::environment::Environment;
 use crate::model::world::{EntityComponents, EntityDecision, SystemContext};
 use primordium_core::interaction::InteractionCommand;
@@ -10,7 +12,7 @@ use std::collections::HashMap;
 
 pub use action_parallel::{apply_actions_sequential, calculate_actions_parallel};
 pub use commands::generate_commands_for_entity;
-pub use perception::perceive_one_entity;
+pub use perception::{act_on_brain_output, perceive_one_entity, sense_one_entity};
 
 pub fn perceive_and_decide_internal(
     ctx: &SystemContext,
@@ -26,12 +28,13 @@ pub fn perceive_and_decide_internal(
     interaction_commands.clear();
     let pop_len = entity_data.len();
 
+    let mut brain_inputs = vec![[0.0; BRAIN_INPUTS]; pop_len];
     entity_data
         .par_iter_mut()
         .zip(decision_buffer.par_iter_mut())
-        .enumerate()
+        .zip(brain_inputs.par_iter_mut())
         .for_each(
-            |(_i, ((_handle, (identity, pos, _vel, phys, met, intel, health)), decision))| {
+            |(((_handle, (identity, pos, _vel, phys, met, intel, health)), decision), inputs)| {
                 let input = perception::EntityPerceptionInput {
                     identity,
                     pos,
@@ -40,7 +43,38 @@ pub fn perceive_and_decide_internal(
                     intel,
                     health,
                 };
-                *decision = perception::perceive_one_entity(input, ctx, env, id_map);
+                (*inputs, *decision) = perception::sense_one_entity(input, ctx, env, id_map);
+            },
+        );
+
+    // Every brain runs in one batch, so brains sharing a topology can be
+    // evaluated together (see `brain::batch`).
+    let mut items: Vec<BatchItem> = entity_data
+        .iter_mut()
+        .zip(&brain_inputs)
+        .map(|((_handle, components), inputs)| {
+            let intel = &mut *components.5;
+            BatchItem {
+                brain: &intel.genotype.brain,
+                inputs: *inputs,
+                last_hidden: intel.last_hidden,
+                activations: &mut intel.last_activations,
+            }
+        })
+        .collect();
+    let brain_outputs = batch::forward_batch(&mut items);
+    drop(items);
+
+    entity_data
+        .par_iter_mut()
+        .zip(decision_buffer.par_iter_mut())
+        .zip(brain_outputs.into_par_iter())
+        .for_each(
+            |(
+                ((_handle, (_identity, pos, _vel, _phys, _met, intel, health)), decision),
+                output,
+            )| {
+                perception::act_on_brain_output(intel, health, pos, ctx, output, decision);
             },
         );
 
@@ -86,6 +120,8 @@ pub fn perceive_and_decide_internal(
         InteractionCommand::Build { attacker_idx, .. } => *attacker_idx,
         InteractionCommand::TribalSplit { target_idx, .. } => *target_idx,
         InteractionCommand::Metamorphosis { target_idx, .. } => *target_idx,
+        InteractionCommand::Groom { groomer_idx, .. } => *groomer_idx,
+        InteractionCommand::Challenge { challenger_idx, .. } => *challenger_idx,
         _ => 0,
     });
 }
@@ -1,7 +1,9 @@
-use crate::model::brain::BrainLogic;
+use crate::model::biome::Biome;
+use crate::model::brain::{BrainLogic, BRAIN_INPUTS, BRAIN_MEMORY, BRAIN_OUTPUTS};
 use crate::model::environment::Environment;
 use crate::model::world::{EntityDecision, SystemContext};
-use primordium_core::systems::{ecological, intel};
+use primordium_core::perception::{FOOD_VECTOR_SCALE, WALL_SENSE_RANGE, WATER_SENSE_RANGE};
+use primordium_core::systems::{ecological, intel, memory};
 use std::collections::HashMap;
 
 pub struct EntityPerceptionInput<'a> {
@@ -20,13 +22,52 @@ pub fn perceive_one_entity(
     id_map: &HashMap<uuid::Uuid, usize>,
 ) -> EntityDecision {
     let EntityPerceptionInput {
-        identity: _identity,
+        identity,
         pos,
         phys,
         met,
         intel,
         health,
     } = input;
+    let (inputs, mut decision) = sense_one_entity(
+        EntityPerceptionInput {
+            identity,
+            pos,
+            phys,
+            met,
+            intel: &mut *intel,
+            health,
+        },
+        ctx,
+        env,
+        id_map,
+    );
+    let brain_output = intel.genotype.brain.forward_internal(
+        inputs,
+        intel.last_hidden,
+        &mut intel.last_activations,
+    );
+    act_on_brain_output(intel, health, pos, ctx, brain_output, &mut decision);
+    decision
+}
+
+/// Gathers what the entity senses into its brain inputs. The returned
+/// decision has everything but the brain outputs, which
+/// [`act_on_brain_output`] fills in once the brain has run.
+pub fn sense_one_entity(
+    input: EntityPerceptionInput,
+    ctx: &SystemContext,
+    env: &Environment,
+    id_map: &HashMap<uuid::Uuid, usize>,
+) -> ([f32; BRAIN_INPUTS], EntityDecision) {
+    let EntityPerceptionInput {
+        identity: _identity,
+        pos,
+        phys,
+        met,
+        intel,
+        health: _,
+    } = input;
     let nearby_kin =
         ctx.spatial_hash
             .count_nearby_kin_fast(pos.x, pos.y, phys.sensing_range, met.lineage_id);
@@ -48,13 +89,21 @@ pub fn perceive_one_entity(
     let nearby_count = ctx
         .spatial_hash
         .count_nearby(pos.x, pos.y, eff_sensing_range);
-    let (ph_f, tribe_d, sa, sb) = ctx
-        .pheromones
-        .sense_all(pos.x, pos.y, eff_sensing_range / 2.0);
+    let [ph_f, tribe_d, sa, sb] =
+        ctx.pheromones
+            .sense_channels(pos.x, pos.y, eff_sensing_range / 2.0, ctx.pheromone_inputs);
+    // Skeptics discount food trails, which may have been laid by deceivers.
+    let ph_f = ph_f * (1.0 - intel.genotype.skepticism);
     let (kx, ky) = ctx
         .spatial_hash
         .sense_kin(pos.x, pos.y, eff_sensing_range, met.lineage_id);
-    let wall_dist = ctx.terrain.sense_wall(pos.x, pos.y, 5.0);
+    let wall_dist = ctx.terrain.sense_wall(pos.x, pos.y, WALL_SENSE_RANGE);
+    let water = ctx.terrain.sense_water(
+        pos.x,
+        pos.y,
+        WATER_SENSE_RANGE,
+        ctx.config.hydration.min_drinkable,
+    );
     let age_ratio = (ctx.tick - met.birth_tick) as f32 / 2000.0;
     let sound_sense = ctx.sound.sense(pos.x, pos.y, eff_sensing_range);
     let mut partner_energy = 0.0;
@@ -74,10 +123,18 @@ pub fn perceive_one_entity(
         lin_energy = (record.total_energy_consumed as f32 / 10000.0).min(1.0);
         overmind_signal = ctx.registry.get_memory_value(&met.lineage_id, "overmind");
     }
+    let [recall_dx, recall_dy, recall_age, recall_partner] = memory::recall(
+        &mut intel.memory,
+        ctx.config.brain.memory_slots,
+        ctx.config.brain.memory_horizon,
+        (pos.x, pos.y),
+        ctx.tick,
+        |id| id_map.contains_key(&id),
+    );
 
     let inputs = [
-        (dx_f / 20.0) as f32,
-        (dy_f / 20.0) as f32,
+        (dx_f / FOOD_VECTOR_SCALE) as f32,
+        (dy_f / FOOD_VECTOR_SCALE) as f32,
         (met.energy / met.max_energy.max(1.0)) as f32,
         (nearby_count as f32 / 10.0).min(1.0),
         ph_f,
@@ -105,13 +162,36 @@ pub fn perceive_one_entity(
         lin_pop,
         lin_energy,
         overmind_signal,
+        recall_dx,
+        recall_dy,
+        recall_age,
+        recall_partner,
+        Biome::sensor(Biome::classify(ctx.terrain.get(pos.x, pos.y))),
+        env.light_level(),
+        water,
     ];
 
-    let (mut outputs, next_hidden) = intel.genotype.brain.forward_internal(
-        inputs,
-        intel.last_hidden,
-        &mut intel.last_activations,
-    );
+    let decision = EntityDecision {
+        outputs: [0.0; BRAIN_OUTPUTS],
+        nearby_count,
+        grn_speed_mod: speed_mod,
+        grn_sensing_mod: sensing_mod,
+        grn_repro_mod: repro_mod,
+        sensed_food,
+    };
+    (inputs, decision)
+}
+
+/// Applies a pathogen's hold on the brain outputs, keeps the hidden state
+/// for the next tick and stores what the entity chose to remember.
+pub fn act_on_brain_output(
+    intel: &mut primordium_data::Intel,
+    health: &primordium_data::Health,
+    pos: &primordium_data::Position,
+    ctx: &SystemContext,
+    (mut outputs, next_hidden): ([f32; BRAIN_OUTPUTS], [f32; BRAIN_MEMORY]),
+    decision: &mut EntityDecision,
+) {
     if let Some(ref path) = health.pathogen {
         if let Some((idx, offset)) = path.behavior_manipulation {
             let out_idx = idx.saturating_sub(22);
@@ -121,12 +201,13 @@ pub fn perceive_one_entity(
         }
     }
     intel.last_hidden = next_hidden;
-    EntityDecision {
-        outputs,
-        nearby_count,
-        grn_speed_mod: speed_mod,
-        grn_sensing_mod: sensing_mod,
-        grn_repro_mod: repro_mod,
-        sensed_food,
-    }
+    memory::store(
+        &mut intel.memory,
+        outputs[12],
+        outputs[13],
+        (pos.x, pos.y),
+        intel.bonded_to,
+        ctx.tick,
+    );
+    decision.outputs = outputs;
 }
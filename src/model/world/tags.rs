@@ -0,0 +1,74 @@
+//! Entity tags.
+//!
+//! Tags mark entities for observation and cohort experiments: user tags are
+//! set by hand (e.g. "protagonist"), system tags by the simulation or an
+//! experiment (e.g. "study-group-A"). They live in a [`Tags`] component that
+//! only tagged entities carry, show up in snapshots and are saved with the
+//! world.
+
+use crate::model::world::World;
+use primordium_data::{Identity, TagSource, Tags};
+use uuid::Uuid;
+
+impl World {
+    fn handle_of(&self, id: Uuid) -> Option<hecs::Entity> {
+        self.ecs
+            .query::<&Identity>()
+            .iter()
+            .find(|(_, identity)| identity.id == id)
+            .map(|(handle, _)| handle)
+    }
+
+    /// Tags entity `id`. Returns false if the entity does not exist or
+    /// already carried the tag from `source`.
+    pub fn tag_entity(&mut self, id: Uuid, tag: &str, source: TagSource) -> bool {
+        let tag = tag.trim();
+        let Some(handle) = self.handle_of(id) else {
+            return false;
+        };
+        if tag.is_empty() {
+            return false;
+        }
+        if let Ok(mut tags) = self.ecs.get::<&mut Tags>(handle) {
+            return tags.insert(tag, source);
+        }
+        let mut tags = Tags::default();
+        tags.insert(tag, source);
+        self.ecs.insert_one(handle, tags).is_ok()
+    }
+
+    /// Removes `tag` from entity `id`. Returns false if it was not tagged.
+    pub fn untag_entity(&mut self, id: Uuid, tag: &str) -> bool {
+        let Some(handle) = self.handle_of(id) else {
+            return false;
+        };
+        let (removed, now_empty) = match self.ecs.get::<&mut Tags>(handle) {
+            Ok(mut tags) => (tags.remove(tag.trim()), tags.is_empty()),
+            Err(_) => return false,
+        };
+        if now_empty {
+            let _ = self.ecs.remove_one::<Tags>(handle);
+        }
+        removed
+    }
+
+    /// Tags of entity `id`; empty if it has none or does not exist.
+    pub fn tags_of(&self, id: Uuid) -> Tags {
+        self.handle_of(id)
+            .and_then(|handle| self.ecs.get::<&Tags>(handle).ok().map(|t| (*t).clone()))
+            .unwrap_or_default()
+    }
+
+    /// Ids of the living entities carrying `tag`, sorted.
+    pub fn entities_tagged(&self, tag: &str) -> Vec<Uuid> {
+        let mut ids: Vec<Uuid> = self
+            .ecs
+            .query::<(&Identity, &Tags)>()
+            .iter()
+            .filter(|(_, (_, tags))| tags.contains(tag))
+            .map(|(_, (identity, _))| identity.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+}
@@ -1,16 +1,23 @@
 use crate::model::environment::Environment;
-use crate::model::interaction::InteractionCommand;
+use crate::model::interaction::{InteractionCommand, InteractionCounts, InteractionKind};
 use hecs;
-use primordium_data::LiveEvent;
-use primordium_data::{Entity, Food, Identity, Intel, Metabolism, Physics, Position};
+use primordium_data::{DisasterRecord, LiveEvent, Specialization, TerrainType};
+use primordium_data::{Egg, Entity, Food, Health, Identity, Intel, Metabolism, Physics, Position};
 use rand::SeedableRng;
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::model::world::{systems, EntityComponents, SystemContext, World};
+use primordium_core::body;
 use primordium_core::brain::BrainLogic;
-use primordium_core::systems::{action, biological, ecological, environment, social};
+use primordium_core::diplomacy::Stance;
+use primordium_core::energy_ledger::EnergySource;
+use primordium_core::schedule::{EventKind, ScheduledEvent};
+use primordium_core::systems::{
+    action, biological, ecological, eggs, environment, migration, social, status,
+};
 
 impl World {
     /// Advances the simulation by one tick.
@@ -27,12 +34,26 @@ impl World {
     /// # Returns
     /// Vector of live events (births, deaths, fossilizations) that occurred this tick
     pub fn update(&mut self, env: &mut Environment) -> anyhow::Result<Vec<LiveEvent>> {
+        if self.guardrail_trip.is_some() {
+            return Ok(Vec::new());
+        }
+
         self.tick += 1;
         let world_seed = self.config.world.seed.unwrap_or(0);
+        let season = self.pass_seasons(env);
+        environment::update_schedule(env, self.tick, &self.config);
+        environment::update_day(env, self.tick, &self.config);
+
+        if let Some((_, energy)) = self.tracked_energy() {
+            if let Some(ledger) = self.energy_ledger.as_mut() {
+                ledger.open_tick(energy);
+            }
+        }
 
         if self.config.world.deterministic {
             let seed = world_seed.wrapping_add(self.tick).wrapping_add(0x5EED);
             self.rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+            self.disaster_rng = super::disaster_rng(seed);
             env.tick_deterministic(self.tick);
             self.update_environment_and_resources(env, seed);
         } else {
@@ -42,12 +63,25 @@ impl World {
         let (handles, id_map) = self.build_tick_indices();
 
         self.pass_social_ranks();
+        self.pass_status_effects(env);
+        self.pass_hydration(env);
+        self.pass_experiment();
         self.pass_spatial_indexing();
         let (food_handles, food_data) = self.pass_food_indexing();
         self.capture_entity_snapshots_with_handles(&handles);
         self.pass_learning();
+        self.pass_migration(env);
+        let (egg_events, hatchlings) = self.pass_eggs(env, &handles, &id_map);
 
         Arc::make_mut(&mut self.influence).update(&self.entity_snapshots);
+        Arc::make_mut(&mut self.roads).update(
+            &self.entity_snapshots,
+            &self.influence,
+            &self.config.roads,
+        );
+        let mutation_heat = Arc::make_mut(&mut self.mutation_heat);
+        mutation_heat.decay(self.config.mutation_heat.half_life);
+        Arc::make_mut(&mut self.pop_stats).evolutionary_velocity = mutation_heat.velocity();
 
         let overmind_broadcasts = {
             let mut query = self.ecs.query::<EntityComponents>();
@@ -75,9 +109,13 @@ impl World {
                     food_hash: &self.food_hash,
                     spatial_hash: &self.spatial_hash,
                     pheromones: &self.pheromones,
+                    pheromone_inputs: self
+                        .pheromones
+                        .channels_named(&self.config.pheromone.sensed),
                     sound: &self.sound,
                     pressure: &self.pressure,
                     influence: &self.influence,
+                    roads: &self.roads,
                     terrain: &self.terrain,
                     tick: self.tick,
                     registry: &self.lineage_registry,
@@ -105,6 +143,25 @@ impl World {
                     &mut decision_buffer,
                 );
 
+                if let Some(ledger) = self.energy_ledger.as_mut() {
+                    if let Some((output, _)) = id_map
+                        .get(&ledger.entity_id)
+                        .and_then(|&idx| all_outputs.get(idx))
+                    {
+                        ledger.record(self.tick, EnergySource::Movement, -output.movement_cost);
+                        ledger.record(self.tick, EnergySource::Basal, -output.basal_cost);
+                        ledger.record(self.tick, EnergySource::Trampling, -output.trample_damage);
+                    }
+                }
+                if let Some(trail) = self.route_trail.as_mut() {
+                    if let Some((output, _)) = id_map
+                        .get(&trail.entity_id)
+                        .and_then(|&idx| all_outputs.get(idx))
+                    {
+                        trail.record(self.tick, output.position.0, output.position.1);
+                    }
+                }
+
                 systems::apply_actions_sequential(
                     all_outputs,
                     Arc::make_mut(&mut self.pheromones),
@@ -124,15 +181,69 @@ impl World {
                 .set_memory_value(l_id, "overmind", *amount);
         }
 
-        let (mut events, new_babies) = self.pass_interactions(env, &food_handles, &handles);
+        let (mut events, mut new_babies) = self.pass_interactions(env, &food_handles, &handles);
+        events.extend(season);
+        events.extend(egg_events);
+        new_babies.extend(hatchlings);
+        let births = new_babies.len();
 
         self.finalize_tick(env, &mut events, &handles, new_babies);
 
-        self.update_grids_and_environment(env);
+        self.update_grids();
+
+        let treaties = self.pass_diplomacy();
+        events.extend(treaties);
+        let monuments = self.pass_monuments();
+        events.extend(monuments);
+
+        let anomalies = self.check_anomalies(&events);
+        events.extend(anomalies);
+        let biome_changes = self.check_biomes();
+        events.extend(biome_changes);
+        events.extend(self.check_era(env));
+        self.sample_forecast();
+        self.observer
+            .observe(self.tick, &self.pop_stats, &self.lineage_registry, env);
+
+        if let Some(ev) = self.check_guardrails(births) {
+            events.push(ev);
+        }
+
+        if let Some((_, energy)) = self.tracked_energy() {
+            if let Some(ledger) = self.energy_ledger.as_mut() {
+                ledger.close_tick(self.tick, energy);
+            }
+        }
 
         Ok(events)
     }
 
+    /// Moves the season cycle on, announcing the season when a new one
+    /// begins.
+    fn pass_seasons(&mut self, env: &mut Environment) -> Option<LiveEvent> {
+        let from = env.current_season;
+        let to = environment::update_season(env, self.tick, &self.config.seasons)?;
+        let event = LiveEvent::SeasonChanged {
+            from: from.name().to_string(),
+            to: to.name().to_string(),
+            tick: self.tick,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        let _ = self.logger.log_event(event.clone());
+        self.observer.observe_event(&event, &self.lineage_registry);
+        Some(event)
+    }
+
+    /// Handle and current energy of the entity tracked by the energy ledger.
+    fn tracked_energy(&self) -> Option<(hecs::Entity, f64)> {
+        let id = self.energy_ledger.as_ref()?.entity_id;
+        self.ecs
+            .query::<(&Identity, &Metabolism)>()
+            .iter()
+            .find(|(_, (identity, _))| identity.id == id)
+            .map(|(handle, (_, met))| (handle, met.energy))
+    }
+
     fn build_tick_indices(&mut self) -> (Vec<hecs::Entity>, HashMap<uuid::Uuid, usize>) {
         let mut data: Vec<_> = self
             .ecs
@@ -153,42 +264,308 @@ impl World {
         (handles, id_to_idx)
     }
 
+    fn pass_migration(&mut self, env: &Environment) {
+        if self.config.migration.enabled {
+            migration::update_migrations(
+                &mut self.lineage_registry,
+                &mut self.path_service,
+                &self.terrain,
+                &self.entity_snapshots,
+                &self.config.migration,
+                env.season_modifiers.migration_pressure as f32,
+                self.tick,
+            );
+        }
+        self.path_service
+            .process(&self.terrain, &self.config.pathfinding);
+    }
+
+    /// Incubates eggs, lets nearby predators eat them and hatches the ones
+    /// that are ready. Hatchlings are returned to be spawned with this tick's
+    /// births, so the entity indices of the tick stay valid.
+    fn pass_eggs(
+        &mut self,
+        env: &Environment,
+        handles: &[hecs::Entity],
+        id_map: &HashMap<uuid::Uuid, usize>,
+    ) -> (Vec<LiveEvent>, Vec<Entity>) {
+        let config = &self.config.eggs;
+        let mut eggs: Vec<_> = self
+            .ecs
+            .query_mut::<(&Position, &mut Egg)>()
+            .into_iter()
+            .collect();
+        if eggs.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+        eggs.sort_by_key(|(_, (_, egg))| egg.hatchling.identity.id);
+
+        let mut eaten = Vec::new();
+        let mut hatched = Vec::new();
+        for (handle, (pos, egg)) in eggs {
+            if let Some(idx) = eggs::egg_predator(
+                pos.x,
+                pos.y,
+                egg.hatchling.metabolism.lineage_id,
+                &self.entity_snapshots,
+                &self.spatial_hash,
+                config,
+            ) {
+                eaten.push((handle, idx, egg.hatchling.metabolism.energy));
+                continue;
+            }
+            egg.incubation += eggs::incubation_rate(&self.terrain, env, pos.x, pos.y, config);
+            if egg.incubation >= config.incubation_ticks as f32 {
+                hatched.push(handle);
+            }
+        }
+
+        let tracked = self.energy_ledger.as_ref().map(|l| l.entity_id);
+        for (handle, idx, energy) in eaten {
+            let _ = self.ecs.despawn(handle);
+            let gain = energy * config.energy_efficiency;
+            let predator = &self.entity_snapshots[idx];
+            if let Some(&eater) = id_map.get(&predator.id).and_then(|&i| handles.get(i)) {
+                if let Ok(mut met) = self.ecs.get::<&mut Metabolism>(eater) {
+                    let before = met.energy;
+                    met.energy = (met.energy + gain).min(met.max_energy);
+                    if tracked == Some(predator.id) {
+                        if let Some(ledger) = self.energy_ledger.as_mut() {
+                            ledger.record(self.tick, EnergySource::Predation, met.energy - before);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut events = Vec::with_capacity(hatched.len());
+        let mut hatchlings = Vec::with_capacity(hatched.len());
+        for handle in hatched {
+            let Ok(egg) = self.ecs.remove_one::<Egg>(handle) else {
+                continue;
+            };
+            let _ = self.ecs.despawn(handle);
+            let mut hatchling = *egg.hatchling;
+            hatchling.metabolism.birth_tick = self.tick;
+            self.lineage_registry.record_birth(
+                hatchling.metabolism.lineage_id,
+                hatchling.metabolism.generation,
+                self.tick,
+            );
+            events.push(LiveEvent::Birth {
+                id: hatchling.identity.id,
+                parent_id: hatchling.identity.parent_id,
+                gen: hatchling.metabolism.generation,
+                tick: self.tick,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                x: Some(hatchling.physics.x),
+                y: Some(hatchling.physics.y),
+            });
+            hatchlings.push(hatchling);
+        }
+        (events, hatchlings)
+    }
+
     fn pass_social_ranks(&mut self) {
+        self.sync_dominance();
+        let standings = self.dominance.standings();
         let tick = self.tick;
         let config = &self.config;
         let mut query = self.ecs.query::<(&Metabolism, &mut Intel, &Identity)>();
         let mut data: Vec<_> = query.iter().collect();
         data.sort_by_key(|(_h, (.., ident))| ident.id);
 
-        data.par_iter_mut().for_each(|(_, (met, intel, _ident))| {
+        data.par_iter_mut().for_each(|(_, (met, intel, ident))| {
             intel.rank = social::calculate_social_rank_components(met, intel, tick, config);
+            intel.dominance = standings.get(&ident.id).copied().unwrap_or(0.0);
         });
     }
 
-    fn pass_spatial_indexing(&mut self) {
+    /// Drops the dead and emigrated from the dominance ladders, then slots
+    /// the unranked children of ranked parents in just below them.
+    fn sync_dominance(&mut self) {
+        let mut members: Vec<(Uuid, Uuid, Option<Uuid>)> = self
+            .ecs
+            .query::<(&Identity, &Metabolism)>()
+            .iter()
+            .map(|(_, (ident, met))| (ident.id, met.lineage_id, ident.parent_id))
+            .collect();
+        members.sort_by_key(|&(id, ..)| id);
+
+        let lineages: HashMap<Uuid, Uuid> = members
+            .iter()
+            .map(|&(id, lineage, _)| (id, lineage))
+            .collect();
+        self.dominance
+            .retain(|lineage, id| lineages.get(&id) == Some(&lineage));
+        let ranked = self.dominance.standings();
+        for (id, lineage, parent) in members {
+            if let Some(parent) = parent.filter(|p| ranked.contains_key(p)) {
+                if !ranked.contains_key(&id) {
+                    self.dominance.inherit(lineage, parent, id);
+                }
+            }
+        }
+    }
+
+    /// Settles lineage relations after the tick's dealings, forgetting
+    /// lineages with no living members, and reports wars declared and
+    /// treaties made to the observer.
+    fn pass_diplomacy(&mut self) -> Vec<LiveEvent> {
+        let registry = &mut self.lineage_registry;
+        if !self.config.diplomacy.enabled {
+            registry.diplomacy = Default::default();
+            return Vec::new();
+        }
+        let living: HashSet<Uuid> = self.entity_snapshots.iter().map(|s| s.lineage_id).collect();
+        let changes = registry
+            .diplomacy
+            .settle(&self.config.diplomacy, |id| living.contains(&id));
+
+        let mut events = Vec::new();
+        for change in changes {
+            let (lineage_a, lineage_b) = (change.a, change.b);
+            let tick = self.tick;
+            let timestamp = chrono::Utc::now().to_rfc3339();
+            let event = match (change.from, change.to) {
+                (_, Stance::War) => LiveEvent::WarDeclared {
+                    lineage_a,
+                    lineage_b,
+                    tick,
+                    timestamp,
+                },
+                (Stance::War, _) | (_, Stance::Alliance) => LiveEvent::PeaceTreaty {
+                    lineage_a,
+                    lineage_b,
+                    alliance: change.to == Stance::Alliance,
+                    tick,
+                    timestamp,
+                },
+                _ => continue,
+            };
+            self.observer.observe_event(&event, registry);
+            events.push(event);
+        }
+        events
+    }
+
+    /// Lets engineers standing on their lineage's outposts raise monuments,
+    /// and archives the monuments of lineages that died out this tick with
+    /// the fossils.
+    fn pass_monuments(&mut self) -> Vec<LiveEvent> {
+        let config = &self.config.monuments;
+        let mut events = Vec::new();
+        if config.enabled {
+            for (_h, (phys, met, intel)) in
+                self.ecs.query_mut::<(&Physics, &mut Metabolism, &Intel)>()
+            {
+                if intel.specialization != Some(Specialization::Engineer)
+                    || met.energy <= config.energy_cost * 2.0
+                {
+                    continue;
+                }
+                let cell = self.terrain.get(phys.x, phys.y);
+                if cell.terrain_type != TerrainType::Outpost
+                    || cell.owner_id != Some(met.lineage_id)
+                {
+                    continue;
+                }
+                let Some(record) = self.lineage_registry.lineages.get(&met.lineage_id) else {
+                    continue;
+                };
+                let (x, y) = (phys.x as u16, phys.y as u16);
+                if !self.monuments.can_raise(record, x, y, config) {
+                    continue;
+                }
+                met.energy -= config.energy_cost;
+                self.monuments
+                    .raise(record, (phys.r, phys.g, phys.b), x, y, self.tick);
+                let event = LiveEvent::MonumentRaised {
+                    lineage_id: met.lineage_id,
+                    x,
+                    y,
+                    tick: self.tick,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                };
+                self.observer.observe_event(&event, &self.lineage_registry);
+                events.push(event);
+            }
+        }
+        for ruin in self.monuments.weather(&self.lineage_registry, self.tick) {
+            self.fossil_registry.add_ruin(ruin);
+        }
+        events
+    }
+
+    /// Hands out the status effects entities pick up from their surroundings.
+    fn pass_status_effects(&mut self, env: &Environment) {
+        let terrain = &self.terrain;
+        let config = &self.config;
+        for (_h, (pos, health)) in self.ecs.query_mut::<(&Position, &mut Health)>() {
+            let terrain_type = terrain.get(pos.x, pos.y).terrain_type;
+            status::apply_environment(health, env, terrain_type, config);
+        }
+    }
+
+    /// Entities grow thirsty at the pace of their metabolism and drink from
+    /// the water standing where they are.
+    fn pass_hydration(&mut self, env: &Environment) {
+        let config = &self.config.hydration;
+        if !config.enabled {
+            return;
+        }
+        let thirst = config.thirst * env.metabolism_multiplier() as f32;
+        let terrain = Arc::make_mut(&mut self.terrain);
+        for (_h, (pos, met)) in self.ecs.query_mut::<(&Position, &mut Metabolism)>() {
+            met.hydration = (met.hydration - thirst).max(0.0);
+            if terrain.water_at(pos.x, pos.y) >= config.min_drinkable {
+                let wanted = config.drink.min(1.0 - met.hydration);
+                met.hydration += terrain.drink(pos.x, pos.y, wanted);
+            }
+        }
+    }
+
+    pub(super) fn pass_spatial_indexing(&mut self) {
         let mut query = self.ecs.query::<EntityComponents>();
         let mut spatial_data_with_ids = std::mem::take(&mut self.spatial_sort_buffer);
         spatial_data_with_ids.clear();
-        spatial_data_with_ids.extend(
-            query
-                .iter()
-                .map(|(_h, (ident, pos, _, _, met, ..))| (pos.x, pos.y, met.lineage_id, ident.id)),
-        );
+        spatial_data_with_ids.extend(query.iter().map(
+            |(_h, (ident, pos, _, _, met, intel, _))| {
+                (
+                    pos.x,
+                    pos.y,
+                    met.lineage_id,
+                    ident.id,
+                    body::radius(intel.genotype.body_size),
+                )
+            },
+        ));
         spatial_data_with_ids.sort_by_key(|d| d.3);
 
         let mut spatial_data = std::mem::take(&mut self.spatial_data_buffer);
         spatial_data.clear();
-        for (x, y, lid, _) in &spatial_data_with_ids {
+        for (x, y, lid, ..) in &spatial_data_with_ids {
             spatial_data.push((*x, *y, *lid));
         }
 
         self.spatial_hash
             .build_with_lineage(&spatial_data, self.width, self.height);
+        self.spatial_hash
+            .set_body_radii(spatial_data_with_ids.iter().map(|d| d.4));
         self.spatial_data_buffer = spatial_data;
         self.spatial_sort_buffer = spatial_data_with_ids;
     }
 
-    fn pass_food_indexing(&mut self) -> (Vec<hecs::Entity>, Vec<(f64, f64, f32)>) {
+    pub(super) fn pass_food_indexing(&mut self) -> (Vec<hecs::Entity>, Vec<(f64, f64, f32)>) {
+        if self.uses_food_field() {
+            let sites = self.food_field.index_sites();
+            let positions: Vec<_> = sites.iter().map(|&(x, y, _)| (x, y)).collect();
+            self.food_hash
+                .build_parallel(&positions, self.width, self.height);
+            self.food_dirty = false;
+            return (Vec::new(), sites);
+        }
+
         let mut food_data: Vec<_> = self
             .ecs
             .query::<(&Position, &Food)>()
@@ -253,9 +630,10 @@ impl World {
         (interaction_events, new_babies)
     }
 
-    fn update_grids_and_environment(&mut self, env: &mut Environment) {
+    fn update_grids(&mut self) {
         let phero = Arc::make_mut(&mut self.pheromones);
         let snd = Arc::make_mut(&mut self.sound);
+        snd.occlude(&self.terrain, &self.config.sound);
         let press = Arc::make_mut(&mut self.pressure);
 
         rayon::join(
@@ -265,17 +643,44 @@ impl World {
             },
         );
 
-        if !self.config.world.deterministic {
-            env.tick();
-        } else {
-            env.world_time = 500;
-        }
-
         if self.tick.is_multiple_of(10) {
             self.update_rank_grid();
         }
     }
 
+    /// Strikes this tick's disasters: the scripted ones during playback,
+    /// otherwise any queued or scheduled ones and whatever the disaster
+    /// stream rolls.
+    fn pass_disasters(&mut self, env: &mut Environment, pop_count: usize) {
+        let scheduled = environment::scheduled_disasters(env, self.tick);
+        let mut due = std::mem::take(&mut self.pending_disasters);
+        if let Some(script) = self.scripted_disasters.as_mut() {
+            due.clear();
+            while script.front().is_some_and(|r| r.tick <= self.tick) {
+                due.extend(script.pop_front().map(|r| r.disaster));
+            }
+        } else {
+            due.extend(scheduled);
+            due.extend(environment::handle_disasters(
+                env,
+                pop_count,
+                &mut self.disaster_rng,
+                &self.config,
+            ));
+        }
+        for disaster in due {
+            environment::apply_disaster(disaster, env, Arc::make_mut(&mut self.terrain));
+            env.schedule.record(
+                self.tick,
+                ScheduledEvent::once(self.tick, EventKind::Disaster { disaster }),
+            );
+            self.disasters.push(DisasterRecord {
+                tick: self.tick,
+                disaster,
+            });
+        }
+    }
+
     fn update_environment_and_resources(&mut self, env: &mut Environment, world_seed: u64) {
         action::handle_game_modes_ecs(
             &mut self.ecs,
@@ -294,24 +699,21 @@ impl World {
         self.lineage_registry.decay_memory(0.99);
 
         let pop_count = self.get_population_count();
-        environment::handle_disasters(
-            env,
-            pop_count,
-            Arc::make_mut(&mut self.terrain),
-            &mut self.rng,
-            &self.config,
-        );
+        self.pass_disasters(env, pop_count);
 
         let (_total_plant_biomass, total_sequestration) = Arc::make_mut(&mut self.terrain).update(
             self.pop_stats.biomass_h,
+            env.season_modifiers.fertility_regrowth,
             self.tick,
             world_seed,
         );
+        if self.config.hydration.enabled {
+            Arc::make_mut(&mut self.terrain).update_water(&self.config.hydration);
+        }
 
         let total_owned_forests = self
             .terrain
-            .cells
-            .iter()
+            .cells()
             .filter(|c| {
                 c.terrain_type == primordium_data::TerrainType::Forest && c.owner_id.is_some()
             })
@@ -339,6 +741,7 @@ impl World {
 
         biological::handle_pathogen_emergence(&mut self.active_pathogens, &mut self.rng);
 
+        let use_food_field = self.uses_food_field();
         let mut spawn_ctx = ecological::SpawnFoodContext {
             world: &mut self.ecs,
             env,
@@ -347,10 +750,12 @@ impl World {
             width: self.width,
             height: self.height,
             food_count_ptr: &self.food_count,
+            food_field: use_food_field.then_some(&mut self.food_field),
         };
         ecological::spawn_food_ecs(&mut spawn_ctx, &mut self.rng);
+        ecological::spread_flora(&mut spawn_ctx, &mut self.rng);
 
-        if self.food_dirty {
+        if self.food_dirty && !use_food_field {
             let mut food_positions = std::mem::take(&mut self.food_positions_buffer);
             food_positions.clear();
             for (_handle, (pos, _)) in self
@@ -374,6 +779,21 @@ impl World {
         entity_handles: &[hecs::Entity],
         food_handles: &[hecs::Entity],
     ) -> (Vec<LiveEvent>, Vec<Entity>) {
+        if self.config.analytics.record_interactions {
+            self.interaction_recorder
+                .set_window(self.config.analytics.interaction_window);
+            self.interaction_recorder
+                .record(InteractionCounts::from_commands(&interaction_commands));
+        }
+
+        if self.config.diplomacy.enabled {
+            self.lineage_registry.diplomacy.observe(
+                &interaction_commands,
+                &self.entity_snapshots,
+                &self.config.diplomacy,
+            );
+        }
+
         let (state_cmds, struct_cmds): (Vec<_>, Vec<_>) =
             interaction_commands.into_iter().partition(|cmd| {
                 matches!(
@@ -384,6 +804,8 @@ impl World {
                 )
             });
 
+        let use_food_field = self.uses_food_field();
+        let ledger_target = self.tracked_energy().map(|(handle, _)| handle);
         let mut interaction_ctx = primordium_core::systems::interaction::InteractionContext {
             terrain: Arc::make_mut(&mut self.terrain),
             env,
@@ -395,12 +817,15 @@ impl World {
             width: self.width,
             height: self.height,
             social_grid: Arc::make_mut(&mut self.social_grid).as_mut_slice(),
+            mutation_heat: Arc::make_mut(&mut self.mutation_heat),
             lineage_consumption: &mut self.lineage_consumption,
             food_handles,
+            food_field: use_food_field.then_some(&mut self.food_field),
             spatial_hash: &self.spatial_hash,
             rng: &mut self.rng,
             food_count: &self.food_count,
             world_seed: self.config.world.seed.unwrap_or(0),
+            ledger_target,
         };
 
         let result1 = primordium_core::systems::interaction::process_interaction_commands_ecs(
@@ -421,6 +846,45 @@ impl World {
         let mut all_events = result1.events;
         all_events.extend(interaction_result.events);
 
+        if let Some(ledger) = self.energy_ledger.as_mut() {
+            for (source, delta) in result1
+                .ledger_deltas
+                .into_iter()
+                .chain(interaction_result.ledger_deltas)
+            {
+                ledger.record(self.tick, source, delta);
+            }
+        }
+
+        let contests: Vec<_> = result1
+            .contests
+            .into_iter()
+            .chain(interaction_result.contests)
+            .collect();
+        for contest in &contests {
+            let (winner, loser) = if contest.challenger_won {
+                (contest.challenger, contest.defender)
+            } else {
+                (contest.defender, contest.challenger)
+            };
+            self.dominance.contest(contest.lineage, winner, loser);
+        }
+
+        if self.config.analytics.record_interactions {
+            for (groomer, groomed) in result1.grooms.into_iter().chain(interaction_result.grooms) {
+                self.interaction_graph
+                    .record(groomer, groomed, InteractionKind::Groom, self.tick);
+            }
+            for contest in &contests {
+                self.interaction_graph.record(
+                    contest.challenger,
+                    contest.defender,
+                    InteractionKind::Challenge,
+                    self.tick,
+                );
+            }
+        }
+
         for (l_id, amount) in &self.lineage_consumption {
             self.lineage_registry.record_consumption(*l_id, *amount);
         }
@@ -428,10 +892,33 @@ impl World {
 
         self.killed_ids = interaction_result.killed_ids;
         self.eaten_food_indices = interaction_result.eaten_food_indices;
+        self.ecs.spawn_batch(
+            interaction_result
+                .new_eggs
+                .into_iter()
+                .map(|egg| (egg.hatchling.position, egg)),
+        );
 
         (all_events, interaction_result.new_babies)
     }
 
+    /// Writes the interaction graph when `analytics.graph_export_interval`
+    /// comes round, then drops edges that were not renewed during it.
+    pub fn export_interaction_graph(&mut self) {
+        let interval = self.config.analytics.graph_export_interval;
+        if !self.config.analytics.record_interactions
+            || interval == 0
+            || !self.tick.is_multiple_of(interval)
+        {
+            return;
+        }
+        let _ = self
+            .logger
+            .save_interaction_graph_async(self.interaction_graph.clone());
+        self.interaction_graph
+            .forget_before(self.tick.saturating_sub(interval));
+    }
+
     pub fn update_rank_grid(&mut self) {
         let width = self.width as usize;
         let height = self.height as usize;
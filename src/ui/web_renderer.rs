@@ -1,10 +1,86 @@
-use crate::model::lifecycle;
+use crate::model::glyph::GlyphTheme;
+use crate::model::palette::Palette;
 use crate::model::pheromone::PheromoneType;
 use crate::model::terrain::TerrainType;
 use crate::model::world::World;
 use primordium_data::EntityStatus;
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
-use web_sys::CanvasRenderingContext2d;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+/// Side of one atlas slot in pixels.
+const ATLAS_SLOT: f64 = 32.0;
+const ATLAS_COLUMNS: usize = 16;
+const ATLAS_ROWS: usize = 16;
+
+/// Pre-rendered organism glyphs. Each glyph and colour pair is drawn once
+/// into a slot of an offscreen canvas and blitted from there, which is far
+/// cheaper than laying out text for every organism every frame.
+pub struct GlyphAtlas {
+    canvas: HtmlCanvasElement,
+    ctx: CanvasRenderingContext2d,
+    slots: HashMap<(String, String), usize>,
+}
+
+impl GlyphAtlas {
+    pub fn new() -> Result<Self, JsValue> {
+        let document = web_sys::window()
+            .and_then(|w| w.document())
+            .ok_or_else(|| JsValue::from_str("no document"))?;
+        let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+        canvas.set_width((ATLAS_SLOT as usize * ATLAS_COLUMNS) as u32);
+        canvas.set_height((ATLAS_SLOT as usize * ATLAS_ROWS) as u32);
+        let ctx: CanvasRenderingContext2d = canvas
+            .get_context("2d")?
+            .ok_or_else(|| JsValue::from_str("no 2d context"))?
+            .dyn_into()?;
+        ctx.set_font(&format!("{}px sans-serif", ATLAS_SLOT * 0.8));
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        Ok(Self {
+            canvas,
+            ctx,
+            slots: HashMap::new(),
+        })
+    }
+
+    /// Top-left corner of the slot holding `glyph` in `color`, drawing it
+    /// first if needed. A full atlas is cleared and refilled.
+    fn slot(&mut self, glyph: &str, color: &str) -> (f64, f64) {
+        let key = (glyph.to_string(), color.to_string());
+        let index = match self.slots.get(&key) {
+            Some(&index) => index,
+            None => {
+                if self.slots.len() == ATLAS_COLUMNS * ATLAS_ROWS {
+                    self.slots.clear();
+                    self.ctx.clear_rect(
+                        0.0,
+                        0.0,
+                        f64::from(self.canvas.width()),
+                        f64::from(self.canvas.height()),
+                    );
+                }
+                let index = self.slots.len();
+                let (x, y) = Self::origin(index);
+                self.ctx.set_fill_style(&JsValue::from_str(color));
+                let _ = self
+                    .ctx
+                    .fill_text(glyph, x + ATLAS_SLOT / 2.0, y + ATLAS_SLOT / 2.0);
+                self.slots.insert(key, index);
+                index
+            }
+        };
+        Self::origin(index)
+    }
+
+    fn origin(index: usize) -> (f64, f64) {
+        (
+            (index % ATLAS_COLUMNS) as f64 * ATLAS_SLOT,
+            (index / ATLAS_COLUMNS) as f64 * ATLAS_SLOT,
+        )
+    }
+}
 
 pub struct WebRenderer {
     width: f64,
@@ -21,9 +97,27 @@ impl WebRenderer {
         }
     }
 
-    pub fn render(&self, ctx: &CanvasRenderingContext2d, world: &World) {
-        // Clear background
-        ctx.set_fill_style(&JsValue::from_str("#111111"));
+    pub fn render(
+        &self,
+        ctx: &CanvasRenderingContext2d,
+        world: &World,
+        glyphs: &GlyphTheme,
+        atlas: &mut GlyphAtlas,
+        palette: &Palette,
+    ) {
+        // Every colour goes through the palette; its slowly changing steps
+        // keep the glyph atlas small.
+        let css = |rgb: (u8, u8, u8)| {
+            let (r, g, b) = palette.apply(rgb);
+            format!("rgb({}, {}, {})", r, g, b)
+        };
+
+        // Clear background, washed in the regime's colour if it has one
+        let background = match palette.wash {
+            Some((r, g, b)) => format!("rgb({}, {}, {})", r, g, b),
+            None => css((17, 17, 17)),
+        };
+        ctx.set_fill_style(&JsValue::from_str(&background));
         ctx.fill_rect(0.0, 0.0, self.width, self.height);
 
         let scale_x = self.width / world.width as f64;
@@ -34,19 +128,19 @@ impl WebRenderer {
             for x in 0..world.width {
                 let cell = world.terrain.get_cell(x, y);
                 let color = match cell.terrain_type {
-                    TerrainType::Mountain => "#4a4a4a", // Dark Grey
-                    TerrainType::River => "#2b5a75",    // Blue
-                    TerrainType::Oasis => "#2ecc71",    // Green
-                    TerrainType::Plains => "#1a1a1a",   // Black/Dark
-                    TerrainType::Barren => "#8b4513",   // Saddle Brown
-                    TerrainType::Wall => "#2c3e50",     // Dark Slate
+                    TerrainType::Mountain => (0x4a, 0x4a, 0x4a), // Dark Grey
+                    TerrainType::River => (0x2b, 0x5a, 0x75),    // Blue
+                    TerrainType::Oasis => (0x2e, 0xcc, 0x71),    // Green
+                    TerrainType::Plains => (0x1a, 0x1a, 0x1a),   // Black/Dark
+                    TerrainType::Barren => (0x8b, 0x45, 0x13),   // Saddle Brown
+                    TerrainType::Wall => (0x2c, 0x3e, 0x50),     // Dark Slate
                 };
 
                 if matches!(cell.terrain_type, TerrainType::Plains) {
                     continue; // Optimize: don't draw plains over background
                 }
 
-                ctx.set_fill_style(&JsValue::from_str(color));
+                ctx.set_fill_style(&JsValue::from_str(&css(color)));
                 ctx.fill_rect(x as f64 * scale_x, y as f64 * scale_y, scale_x, scale_y);
             }
         }
@@ -56,11 +150,7 @@ impl WebRenderer {
 
         // Draw Food
         for food in &world.food {
-            let color = format!(
-                "rgb({}, {}, {})",
-                food.color_rgb.0, food.color_rgb.1, food.color_rgb.2
-            );
-            ctx.set_fill_style(&JsValue::from_str(&color));
+            ctx.set_fill_style(&JsValue::from_str(&css(food.color_rgb)));
             ctx.begin_path();
             let _ = ctx.arc(
                 food.x as f64 * scale_x + scale_x / 2.0,
@@ -72,32 +162,36 @@ impl WebRenderer {
             ctx.fill();
         }
 
-        // Draw Entities
-        for entity in &world.entities {
-            let status = lifecycle::get_entity_status(
-                entity,
-                world.config.brain.activation_threshold,
-                world.tick,
-                world.config.metabolism.maturity_age,
-            );
-            let color = match status {
-                EntityStatus::Starving => "#ff0000", // Red
-                EntityStatus::Juvenile => "#cccccc", // Silver
-                EntityStatus::Sharing => "#00ff00",  // Green
-                EntityStatus::Hunting => "#ff8c00",  // Orange
-                EntityStatus::Mating => "#ff69b4",   // Pink
-                EntityStatus::Foraging => "#00cc00", // Default Green
+        // Draw Entities as their theme glyphs; a canvas has room for wide ones
+        let snapshot = world.create_snapshot(None);
+        for entity in &snapshot.entities {
+            let color = match entity.status {
+                EntityStatus::Starving => (0xff, 0x00, 0x00), // Red
+                EntityStatus::Juvenile => (0xcc, 0xcc, 0xcc), // Silver
+                EntityStatus::Sharing => (0x00, 0xff, 0x00),  // Green
+                EntityStatus::Hunting => (0xff, 0x8c, 0x00),  // Orange
+                EntityStatus::Mating => (0xff, 0x69, 0xb4),   // Pink
+                _ => (0x00, 0xcc, 0x00),                      // Default Green
             };
+            let (sx, sy) = atlas.slot(glyphs.glyph_for(entity), &css(color));
 
-            ctx.set_fill_style(&JsValue::from_str(color));
-
-            let ex = entity.physics.x * scale_x;
-            let ey = entity.physics.y * scale_y;
-            let size = scale_x * 0.8; // Slightly smaller than cell
-
-            ctx.begin_path();
-            let _ = ctx.arc(ex, ey, size, 0.0, std::f64::consts::PI * 2.0);
-            ctx.fill();
+            // Glyphs read better slightly larger than a cell, and large
+            // bodies span several.
+            let size = scale_x * 1.6 * f64::from(entity.body_size);
+            let ex = entity.x * scale_x - size / 2.0;
+            let ey = entity.y * scale_y - size / 2.0;
+            let _ = ctx
+                .draw_image_with_html_canvas_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+                    &atlas.canvas,
+                    sx,
+                    sy,
+                    ATLAS_SLOT,
+                    ATLAS_SLOT,
+                    ex,
+                    ey,
+                    size,
+                    size,
+                );
 
             // Draw territorial range or interaction if needed? No, too cluttered.
         }
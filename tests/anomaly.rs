@@ -0,0 +1,53 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_data::LiveEvent;
+
+#[test]
+fn test_population_crash_is_narrated_and_bookmarked() {
+    let mut builder = WorldBuilder::new().with_config(|c| {
+        c.anomaly.sample_interval = 1;
+        c.anomaly.warmup_samples = 5;
+        c.anomaly.cooldown_samples = 0;
+    });
+    for i in 0..40 {
+        builder = builder.with_entity(
+            EntityBuilder::new()
+                .at(5.0 + (i % 8) as f64 * 10.0, 5.0 + (i / 8) as f64 * 10.0)
+                .energy(150.0)
+                .build(),
+        );
+    }
+    let (mut world, mut env) = builder.build();
+    for _ in 0..15 {
+        world.update(&mut env).unwrap();
+    }
+
+    let victims: Vec<_> = world.ecs.iter().map(|e| e.entity()).skip(5).collect();
+    for handle in victims {
+        world.ecs.despawn(handle).unwrap();
+    }
+    let events = world.update(&mut env).unwrap();
+
+    let (severity, refs) = events
+        .iter()
+        .find_map(|e| match e {
+            LiveEvent::Narration {
+                text,
+                severity,
+                refs,
+                ..
+            } if text.contains("Silent Collapse") => Some((*severity, refs)),
+            _ => None,
+        })
+        .unwrap();
+    assert!(severity > 0.8);
+    assert_eq!(refs.to_tick, world.tick);
+    assert!(refs
+        .data
+        .iter()
+        .any(|line| line.starts_with("population: ")));
+    assert!(world
+        .bookmarks
+        .iter()
+        .any(|b| b.tick == world.tick && b.note.contains("Silent Collapse")));
+}
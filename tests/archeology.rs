@@ -1,3 +1,5 @@
+use primordium_data::{DeathCause, LiveEvent, PopulationStats};
+use primordium_io::history::{HistoryLogger, SnapshotIndex};
 use primordium_lib::model::config::AppConfig;
 use primordium_lib::model::state::environment::Environment;
 use primordium_lib::model::world::World;
@@ -57,15 +59,20 @@ async fn test_fossilization_and_snapshots() {
         world.update(&mut env).unwrap();
     }
 
-    // 4. Check if fossil exists
-    assert!(
-        world
-            .fossil_registry
-            .fossils
-            .iter()
-            .any(|f| f.lineage_id == l_id),
-        "Fossil should be created for extinct legendary lineage"
-    );
+    // 4. Check if fossil exists, tagged with its era and cause of extinction
+    let fossil = world
+        .fossil_registry
+        .fossils
+        .iter()
+        .find(|f| f.lineage_id == l_id)
+        .expect("Fossil should be created for extinct legendary lineage");
+    assert_eq!(fossil.era, env.current_era.name());
+    assert!(!fossil.climate.is_empty());
+    assert!(matches!(
+        fossil.extinction_cause,
+        DeathCause::Starvation | DeathCause::Climate
+    ));
+    assert!(fossil.morphology().brain_nodes > 0);
 
     // 5. Check if Snapshot event was emitted
     std::thread::sleep(std::time::Duration::from_millis(100)); // Wait for async logger
@@ -75,3 +82,95 @@ async fn test_fossilization_and_snapshots() {
         "Snapshots should be recorded in the log file"
     );
 }
+
+fn snapshot_line(tick: u64) -> String {
+    let stats = PopulationStats {
+        population: tick as usize,
+        ..Default::default()
+    };
+    let event = LiveEvent::Snapshot {
+        tick,
+        stats: Box::new(stats),
+        timestamp: String::new(),
+    };
+    serde_json::to_string(&event).unwrap() + "\n"
+}
+
+#[test]
+fn test_snapshot_index_grows_with_log_and_reads_on_demand() {
+    let log_dir = "logs_test_archeology_index";
+    let _ = std::fs::remove_dir_all(log_dir);
+    std::fs::create_dir_all(log_dir).unwrap();
+    let path = format!("{}/live.jsonl", log_dir);
+    let narration = serde_json::to_string(&LiveEvent::Narration {
+        tick: 5,
+        text: "quiet".into(),
+        severity: 0.0,
+        timestamp: String::new(),
+        refs: Default::default(),
+    })
+    .unwrap()
+        + "\n";
+    let first = snapshot_line(1000);
+    std::fs::write(
+        &path,
+        format!("{}{}{}", first, narration, snapshot_line(2000)),
+    )
+    .unwrap();
+
+    let logger = HistoryLogger::new_at(log_dir).unwrap();
+    let mut index = SnapshotIndex::default();
+
+    // A small budget stops after the first line.
+    assert_eq!(logger.index_snapshots(&mut index, 1).unwrap(), 1);
+    assert!(!index.is_complete());
+    assert_eq!(logger.index_snapshots(&mut index, u64::MAX).unwrap(), 1);
+    assert!(index.is_complete());
+    assert_eq!(
+        index.get(1).unwrap().offset,
+        (first.len() + narration.len()) as u64
+    );
+
+    // Only complete lines are indexed; the rest is picked up later.
+    let third = snapshot_line(3000);
+    let (head, tail) = third.split_at(10);
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap();
+    std::io::Write::write_all(&mut file, head.as_bytes()).unwrap();
+    assert_eq!(logger.index_snapshots(&mut index, u64::MAX).unwrap(), 0);
+    std::io::Write::write_all(&mut file, tail.as_bytes()).unwrap();
+    assert_eq!(logger.index_snapshots(&mut index, u64::MAX).unwrap(), 1);
+
+    assert_eq!(index.position_at_or_before(2500), Some(1));
+    assert_eq!(index.position_at_or_before(999), None);
+    let stats = logger.read_snapshot(index.get(2).unwrap()).unwrap();
+    assert_eq!(stats.population, 3000);
+
+    let _ = std::fs::remove_dir_all(log_dir);
+}
+
+#[test]
+fn test_extinction_cause_is_most_common_recent_death() {
+    let mut registry = primordium_lib::model::lineage_registry::LineageRegistry::new();
+    let l_id = uuid::Uuid::new_v4();
+    registry.record_birth(l_id, 1, 0);
+    let cause = |r: &primordium_lib::model::lineage_registry::LineageRegistry| {
+        r.lineages.get(&l_id).unwrap().extinction_cause()
+    };
+    assert_eq!(cause(&registry), DeathCause::Unknown);
+
+    registry.record_death_cause(l_id, DeathCause::Predation);
+    registry.record_death_cause(l_id, DeathCause::Predation);
+    registry.record_death_cause(l_id, DeathCause::Disease);
+    assert_eq!(cause(&registry), DeathCause::Predation);
+
+    // Ties go to the most recent cause, and old deaths fall out of the window.
+    registry.record_death_cause(l_id, DeathCause::Disease);
+    assert_eq!(cause(&registry), DeathCause::Disease);
+    for _ in 0..16 {
+        registry.record_death_cause(l_id, DeathCause::Starvation);
+    }
+    assert_eq!(cause(&registry), DeathCause::Starvation);
+}
@@ -0,0 +1,117 @@
+mod common;
+use common::{EntityBuilder, TestBehavior, WorldBuilder};
+use primordium_data::Entity;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const HUNTERS: Uuid = Uuid::from_u128(777);
+const PREY: Uuid = Uuid::from_u128(888);
+
+fn sized(mut e: Entity, size: f32) -> Entity {
+    Arc::make_mut(&mut e.intel.genotype).body_size = size;
+    e
+}
+
+/// Whether a hunter of `hunter_size` kills a rooted prey of `prey_size`
+/// within 100 ticks.
+fn prey_is_taken(hunter_size: f32, prey_size: f32) -> bool {
+    let mut hunter = EntityBuilder::new()
+        .id(Uuid::from_u128(1))
+        .at(10.0, 10.0)
+        .energy(10000.0)
+        .max_energy(12000.0)
+        .color(255, 0, 0)
+        .with_behavior(TestBehavior::Aggressive)
+        .lineage(HUNTERS)
+        .build();
+    hunter.metabolism.trophic_potential = 1.0;
+
+    let mut prey = EntityBuilder::new()
+        .id(Uuid::from_u128(2))
+        .at(10.1, 10.1)
+        .energy(500.0)
+        .max_energy(1000.0)
+        .color(0, 0, 255)
+        .lineage(PREY)
+        .build();
+    prey.metabolism.trophic_potential = 0.0;
+    prey.physics.max_speed = 0.0;
+    Arc::make_mut(&mut prey.intel.genotype).max_speed = 0.0;
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_seed(789)
+        .with_config(|c| {
+            c.world.deterministic = true;
+            c.world.disaster_chance = 0.0;
+            c.metabolism.reproduction_threshold = 1000000.0;
+        })
+        .with_entity(sized(hunter, hunter_size))
+        .with_entity(sized(prey, prey_size))
+        .build();
+
+    for _ in 0..100 {
+        for (_, met) in world
+            .ecs
+            .query_mut::<&mut primordium_lib::model::state::Metabolism>()
+            .into_iter()
+            .filter(|(_, m)| m.trophic_potential > 0.9)
+        {
+            met.energy = 5000.0;
+        }
+        world.update(&mut env).expect("Update failed");
+        if world.get_population_count() == 1 {
+            return true;
+        }
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_prey_too_large_for_a_hunter_survives() {
+    assert!(prey_is_taken(1.0, 1.0));
+    assert!(prey_is_taken(2.0, 3.0));
+    assert!(!prey_is_taken(1.0, 3.0));
+}
+
+#[tokio::test]
+async fn test_large_bodies_burn_more_energy() {
+    // Twins: the builder seeds the brain from the id, which is then changed.
+    let rooted = |id: u128, x: f64, size: f32| {
+        let twin = EntityBuilder::new()
+            .id(Uuid::from_u128(3))
+            .at(x, 10.0)
+            .energy(1000.0)
+            .max_energy(2000.0)
+            .build();
+        let mut e = sized(twin, size);
+        e.identity.id = Uuid::from_u128(id);
+        e.physics.max_speed = 0.0;
+        Arc::make_mut(&mut e.intel.genotype).max_speed = 0.0;
+        e
+    };
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| {
+            c.metabolism.reproduction_threshold = 1000000.0;
+            c.hydration.enabled = false;
+        })
+        .with_entity(rooted(3, 10.0, 1.0))
+        .with_entity(rooted(4, 40.0, 3.0))
+        .build();
+    for _ in 0..10 {
+        world.update(&mut env).expect("Update failed");
+    }
+
+    let spent = |id: u128| {
+        world
+            .get_all_entities()
+            .iter()
+            .find(|e| e.identity.id == Uuid::from_u128(id))
+            .map(|e| 1000.0 - e.metabolism.energy)
+            .expect("entity survives")
+    };
+    let (small, large) = (spent(3), spent(4));
+    assert!(small > 0.0);
+    // Brain upkeep does not grow with the body, the rest of the bill does.
+    assert!(large > small * 1.2, "small spent {small}, large {large}");
+}
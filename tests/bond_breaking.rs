@@ -46,7 +46,7 @@ async fn test_bond_break_on_low_reciprocity() {
             .connections
             .push(primordium_lib::model::brain::Connection {
                 from: 2,
-                to: 37,
+                to: primordium_lib::model::brain::BRAIN_INPUTS + 8,
                 weight: -5.0,
                 enabled: true,
                 innovation: 1,
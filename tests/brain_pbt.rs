@@ -1,4 +1,6 @@
-use primordium_lib::model::brain::{Brain, BrainLogic, Connection};
+use primordium_lib::model::brain::{
+    Brain, BrainLogic, Connection, GenotypeLogic, BRAIN_HIDDEN_END, BRAIN_INPUTS,
+};
 use primordium_lib::model::state::entity::Genotype;
 use proptest::prelude::*;
 use uuid::Uuid;
@@ -18,7 +20,7 @@ prop_compose! {
 
 prop_compose! {
     fn arb_brain(max_conns: usize)(
-        connections in prop::collection::vec(arb_connection(47), 0..max_conns)
+        connections in prop::collection::vec(arb_connection(BRAIN_HIDDEN_END), 0..max_conns)
     ) -> Brain {
         let mut brain = Brain::new_random(); // Correct base initialization
         brain.connections = connections;
@@ -52,6 +54,12 @@ prop_compose! {
             pairing_bias: 0.5,
             specialization_bias: [0.33, 0.33, 0.34],
             regulatory_rules: Vec::new(),
+            oviparity: 0.0,
+            clutch_size: 1,
+            parental_care: 0.0,
+            skepticism: 0.0,
+            death_avoidance: 0.0,
+            body_size: 1.0,
         }
     }
 }
@@ -62,8 +70,9 @@ proptest! {
     #[test]
     fn test_brain_forward_no_nan(
         brain in arb_brain(50),
-        inputs in any::<[f32; 29]>() // Fixed input array generation
+        inputs in prop::collection::vec(any::<f32>(), BRAIN_INPUTS)
     ) {
+        let inputs: [f32; BRAIN_INPUTS] = inputs.try_into().unwrap();
         let mut activations = primordium_data::Activations::default();
         let (outputs, next_hidden) = brain.forward_internal(inputs, [0.0; 6], &mut activations);
 
@@ -9,7 +9,7 @@ fn test_brain_forward_outputs_are_finite() {
 
     // Test various input ranges
     for &input in &[-100.0, 0.0, 100.0] {
-        let inputs: [f32; 29] = [input; 29];
+        let inputs: [f32; 36] = [input; 36];
         let (outputs, next_hidden) =
             genotype
                 .brain
@@ -36,7 +36,7 @@ fn test_brain_forward_outputs_are_finite() {
 
 #[test]
 fn test_brain_forward_preserves_length() {
-    let inputs: [f32; 29] = [0.5; 29];
+    let inputs: [f32; 36] = [0.5; 36];
     let last_hidden: [f32; 6] = [0.0; 6];
     let genotype = primordium_data::Genotype::new_random();
     let mut activations = primordium_data::Activations::default();
@@ -46,13 +46,13 @@ fn test_brain_forward_preserves_length() {
             .brain
             .forward_internal(inputs, last_hidden, &mut activations);
 
-    assert_eq!(outputs.len(), 12, "Should have 12 outputs");
+    assert_eq!(outputs.len(), 15, "Should have 15 outputs");
     assert_eq!(next_hidden.len(), 6, "Should have 6 hidden values");
 }
 
 #[test]
 fn test_brain_forward_is_deterministic() {
-    let inputs: [f32; 29] = [0.5; 29];
+    let inputs: [f32; 36] = [0.5; 36];
     let last_hidden: [f32; 6] = [0.0; 6];
     let genotype = primordium_data::Genotype::new_random();
     let mut activations1 = primordium_data::Activations::default();
@@ -76,7 +76,7 @@ fn test_brain_forward_is_deterministic() {
 
 #[test]
 fn test_multiple_forward_calls_evolve_hidden() {
-    let mut inputs: [f32; 29] = [0.0; 29];
+    let mut inputs: [f32; 36] = [0.0; 36];
     for (i, input) in inputs.iter_mut().enumerate() {
         *input = (i as f32) / 29.0 - 0.5; // Variety in inputs
     }
@@ -96,7 +96,7 @@ fn test_multiple_forward_calls_evolve_hidden() {
 
 #[test]
 fn test_different_genotypes_different_outputs() {
-    let inputs: [f32; 29] = [0.5; 29];
+    let inputs: [f32; 36] = [0.5; 36];
     let last_hidden: [f32; 6] = [0.0; 6];
 
     let genotype1 = primordium_data::Genotype::new_random();
@@ -117,3 +117,275 @@ fn test_different_genotypes_different_outputs() {
     assert!(outputs1.iter().all(|&o| o.is_finite()));
     assert!(outputs2.iter().all(|&o| o.is_finite()));
 }
+
+#[test]
+fn test_pruning_removes_dead_weight() {
+    use primordium_data::{Activation, Connection, Node, NodeType};
+    use rand::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(7);
+    let mut brain = primordium_data::Brain::new_random_with_rng(&mut rng);
+    let (nodes, connections) = (brain.nodes.len(), brain.connections.len());
+    for c in &mut brain.connections {
+        c.weight = 1.0;
+    }
+    brain.connections[0].weight = 0.001;
+    brain.connections[1].enabled = false;
+    brain.nodes.push(Node {
+        id: 5000,
+        node_type: NodeType::Hidden,
+        label: None,
+        activation: Activation::Tanh,
+    });
+    brain.connections.push(Connection {
+        from: 0,
+        to: 5000,
+        weight: 0.0,
+        enabled: true,
+        innovation: 99,
+    });
+    brain.weight_deltas.insert(99, 0.5);
+    brain.initialize_node_idx_map();
+
+    assert_eq!(brain.prune(0.01), 3);
+    assert_eq!(brain.connections.len(), connections - 2);
+    assert_eq!(brain.nodes.len(), nodes, "orphaned evolved node is removed");
+    assert!(brain.weight_deltas.is_empty());
+    assert_eq!(brain.prune(0.01), 0);
+
+    let config = primordium_lib::model::config::AppConfig::default().brain;
+    let cost = brain.maintenance_cost(&config);
+    brain.connections.truncate(10);
+    assert!(brain.maintenance_cost(&config) < cost);
+}
+
+#[test]
+fn test_population_stats_track_brain_size() {
+    let mut world = primordium_lib::model::world::World::new(
+        0,
+        primordium_lib::model::config::AppConfig::default(),
+    )
+    .unwrap();
+    let mut env = primordium_lib::model::environment::Environment::default();
+    let entity = primordium_lib::model::lifecycle::create_entity(10.0, 10.0, 0);
+    let brain = entity.intel.genotype.brain.clone();
+    world.spawn_entity(entity);
+
+    while world.pop_stats.avg_brain_connections == 0.0 && world.tick < 120 {
+        world.update(&mut env).unwrap();
+    }
+    assert_eq!(world.pop_stats.avg_brain_nodes, brain.nodes.len() as f64);
+    assert!(world.pop_stats.avg_brain_connections > 0.0);
+}
+
+#[test]
+fn test_population_stats_track_episodic_memory_use() {
+    use primordium_lib::model::brain::BRAIN_INPUTS;
+
+    let mut config = primordium_lib::model::config::AppConfig::default();
+    // Offspring would dilute the per-entity rates.
+    config.metabolism.reproduction_threshold = f64::MAX;
+    let mut world = primordium_lib::model::world::World::new(0, config).unwrap();
+    let mut env = primordium_lib::model::environment::Environment::default();
+    let mut writer = primordium_lib::model::lifecycle::create_entity(10.0, 10.0, 0);
+    let mut idle = primordium_lib::model::lifecycle::create_entity(30.0, 30.0, 0);
+    // Hold the write gate open for one and shut for the other.
+    let mem_write = BRAIN_INPUTS + 12;
+    for (entity, weight) in [(&mut writer, 5.0), (&mut idle, -5.0)] {
+        let brain = &mut std::sync::Arc::make_mut(&mut entity.intel.genotype).brain;
+        brain.connections.retain(|c| c.to != mem_write);
+        brain.connections.push(primordium_data::Connection {
+            from: 2,
+            to: mem_write,
+            weight,
+            enabled: true,
+            innovation: primordium_lib::model::brain::topology::get_innovation_id(2, mem_write),
+        });
+        brain.initialize_node_idx_map();
+        entity.metabolism.energy = entity.metabolism.max_energy;
+    }
+    let (writer_lineage, idle_lineage) = (writer.metabolism.lineage_id, idle.metabolism.lineage_id);
+    world.spawn_entity(writer);
+    world.spawn_entity(idle);
+
+    while world.tick < 60 {
+        world.update(&mut env).unwrap();
+    }
+    let stats = &world.pop_stats;
+    assert_eq!(stats.memory_writers, 0.5);
+    assert!(stats.memory_recall_rate > 0.4 && stats.memory_recall_rate <= 0.5);
+    assert_eq!(stats.lineage_memory_writers[&writer_lineage], 1.0);
+    assert_eq!(stats.lineage_memory_writers[&idle_lineage], 0.0);
+}
+
+#[test]
+fn test_activation_genes_shape_the_forward_pass() {
+    use primordium_data::Activation;
+    use rand::SeedableRng;
+
+    assert_eq!(Activation::Relu.apply(-2.0), 0.0);
+    assert_eq!(Activation::Relu.apply(3.0), 1.0);
+    assert_eq!(Activation::Gaussian.apply(0.0), 1.0);
+    assert!(Activation::Sine.apply(std::f32::consts::PI).abs() < 1e-6);
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(11);
+    let mut brain = primordium_data::Brain::new_random_with_rng(&mut rng);
+    let inputs = [0.5; 36];
+    let (_, tanh_hidden) = brain.forward(inputs, [0.0; 6]);
+
+    let memory = brain
+        .nodes
+        .iter_mut()
+        .find(|n| n.id == primordium_lib::model::brain::BRAIN_HIDDEN_START)
+        .expect("first memory node");
+    memory.activation = Activation::Gaussian;
+    let (_, gauss_hidden) = brain.forward(inputs, [0.0; 6]);
+    assert_eq!(tanh_hidden[1..], gauss_hidden[1..]);
+    assert_ne!(tanh_hidden[0], gauss_hidden[0]);
+    assert!(gauss_hidden[0] > 0.0 && gauss_hidden[0] <= 1.0);
+
+    for _ in 0..20 {
+        primordium_core::brain::mutation::mutate_activation(&mut brain, &mut rng);
+    }
+    let evolved = brain.clone();
+    assert!(brain.nodes.iter().any(|n| n.activation != Activation::Tanh));
+    assert!(brain
+        .nodes
+        .iter()
+        .filter(|n| n.node_type != primordium_data::NodeType::Hidden)
+        .all(|n| n.activation == Activation::Tanh));
+
+    let restored: primordium_data::Brain =
+        serde_json::from_str(&serde_json::to_string(&evolved).unwrap()).unwrap();
+    assert_eq!(restored.nodes, evolved.nodes);
+}
+
+#[test]
+fn test_brains_saved_without_activation_genes_load_as_tanh() {
+    let brain = primordium_data::Brain::new_random();
+    let mut value = serde_json::to_value(&brain).unwrap();
+    for node in value["nodes"].as_array_mut().unwrap() {
+        node.as_object_mut().unwrap().remove("activation");
+    }
+    let old: primordium_data::Brain = serde_json::from_value(value).unwrap();
+    assert!(old
+        .nodes
+        .iter()
+        .all(|n| n.activation == primordium_data::Activation::Tanh));
+}
+
+#[test]
+fn test_brain_export_labels_weights_and_activations() {
+    use primordium_lib::model::brain::export;
+
+    let mut world = primordium_lib::model::world::World::new(
+        0,
+        primordium_lib::model::config::AppConfig::default(),
+    )
+    .unwrap();
+    let mut env = primordium_lib::model::environment::Environment::default();
+    let entity = primordium_lib::model::lifecycle::create_entity(10.0, 10.0, 0);
+    let id = entity.identity.id.to_string();
+    world.spawn_entity(entity);
+    world.update(&mut env).unwrap();
+
+    let (brain, values) = world.brain_of(&id[..8]).unwrap();
+    assert_eq!(values.len(), brain.nodes.len());
+    assert!(world.brain_of("not-an-id").is_err());
+    assert_eq!(
+        world.entity_of(&id[..8]).unwrap().identity.id.to_string(),
+        id
+    );
+
+    let plain = export::to_dot(&brain, None);
+    assert!(plain.starts_with("digraph Brain {"));
+    assert!(plain.contains("label=\"FoodDX\""));
+    assert!(plain.contains("label=\"MoveX\""));
+    assert!(plain.contains("#e1f5fe"));
+    assert_eq!(
+        plain.matches(" -> ").count(),
+        brain.connections.len(),
+        "one edge per connection"
+    );
+    let strongest = brain
+        .connections
+        .iter()
+        .map(|c| c.weight.abs().min(5.0))
+        .fold(0.0, f32::max);
+    assert!(plain.contains(&format!("penwidth={:.2}", 0.5 + strongest)));
+
+    let colored = export::to_dot(&brain, Some(&values));
+    assert!(colored.contains("label=\"MoveX\\n"));
+    assert_eq!(export::activation_color(0.0), "#ffffff");
+    assert_eq!(export::activation_color(1.0), "#2e7d32");
+    assert_eq!(export::activation_color(-1.0), "#c62828");
+
+    let svg = export::to_svg(&brain, Some(&values));
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<ellipse").count(), brain.nodes.len());
+}
+
+#[test]
+fn test_brains_from_builds_with_fewer_sensors_are_padded() {
+    use primordium_lib::model::brain::{
+        topology, BRAIN_HIDDEN_END, BRAIN_HIDDEN_START, BRAIN_INPUTS,
+    };
+    use primordium_lib::model::compat::brain_layout;
+
+    let current = primordium_data::Brain::new_random();
+    // Rebuild the brain as a build without the last two sensors and the
+    // last output wrote it.
+    let (missing_inputs, missing_outputs) = (2, 1);
+    let saved_inputs = BRAIN_INPUTS - missing_inputs;
+    let dropped_output = BRAIN_HIDDEN_START - missing_outputs;
+    let is_new = |id: usize| {
+        (saved_inputs..BRAIN_INPUTS).contains(&id)
+            || (dropped_output..BRAIN_HIDDEN_START).contains(&id)
+    };
+    let to_saved = |id: usize| {
+        if (BRAIN_INPUTS..dropped_output).contains(&id) {
+            id - missing_inputs
+        } else if (BRAIN_HIDDEN_START..BRAIN_HIDDEN_END).contains(&id) {
+            id - missing_inputs - missing_outputs
+        } else {
+            id
+        }
+    };
+    let mut saved = current.clone();
+    saved.nodes.retain(|n| !is_new(n.id));
+    saved
+        .connections
+        .retain(|c| !is_new(c.from) && !is_new(c.to));
+    for node in &mut saved.nodes {
+        node.id = to_saved(node.id);
+    }
+    for conn in &mut saved.connections {
+        conn.from = to_saved(conn.from);
+        conn.to = to_saved(conn.to);
+    }
+    let saved_layout = brain_layout(&saved);
+    assert_eq!(
+        saved_layout,
+        (saved_inputs, brain_layout(&current).1 - missing_outputs)
+    );
+
+    topology::pad_layout(&mut saved, saved_layout.0, saved_layout.1);
+    assert_eq!(brain_layout(&saved), brain_layout(&current));
+    let ids = |b: &primordium_data::Brain| {
+        let mut ids: Vec<usize> = b.nodes.iter().map(|n| n.id).collect();
+        ids.sort_unstable();
+        ids
+    };
+    assert_eq!(ids(&saved), ids(&current));
+    for conn in &saved.connections {
+        assert!(current.connections.iter().any(|c| c.from == conn.from
+            && c.to == conn.to
+            && c.weight == conn.weight
+            && c.innovation == conn.innovation));
+    }
+    assert!(saved
+        .connections
+        .iter()
+        .all(|c| !is_new(c.from) && !is_new(c.to)));
+}
@@ -0,0 +1,40 @@
+use primordium_lib::model::challenge::{self, ChallengeMetric};
+
+const CROWDED_GARDEN: &str = include_str!("../assets/challenges/crowded_garden.toml");
+
+#[test]
+fn test_challenge_runs_reproduce_their_signature() {
+    // A shortened run keeps the test fast; the scenario text is still signed as-is.
+    let scenario = CROWDED_GARDEN.replace("ticks = 2000", "ticks = 40");
+    let mut ticks = 0;
+    let (challenge, first) = challenge::run(&scenario, |_| ticks += 1).unwrap();
+    let (_, mut second) = challenge::run(&scenario, |_| {}).unwrap();
+
+    assert_eq!(challenge.id, "crowded-garden");
+    assert_eq!(first.ticks, ticks);
+    assert_eq!(
+        first.metrics[&ChallengeMetric::SurvivedTicks],
+        first.ticks as f64
+    );
+    // Stats are measured at the end of the run, not at the last periodic refresh.
+    assert_eq!(
+        first.metrics[&ChallengeMetric::Species] > 0.0,
+        first.metrics[&ChallengeMetric::Population] > 0.0
+    );
+    // Both runs are stamped with the same manifest, bar the start time.
+    let (stamp, other) = (
+        first.provenance.clone().unwrap(),
+        second.provenance.unwrap(),
+    );
+    assert_eq!(stamp.config_hash, other.config_hash);
+    second.provenance = Some(stamp);
+    assert_eq!(first, second);
+    assert_eq!(
+        first.signature,
+        challenge::result_signature(&scenario, &first.world_hash, &first.metrics)
+    );
+    assert_ne!(
+        first.signature,
+        challenge::result_signature(CROWDED_GARDEN, &first.world_hash, &first.metrics)
+    );
+}
@@ -0,0 +1,112 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_core::systems::environment as environment_system;
+use primordium_lib::model::brain::{
+    topology, BrainLogic, GenotypeLogic, BRAIN_HIDDEN_END, BRAIN_INPUTS, INPUT_LABELS,
+};
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::environment::{Environment, TimeOfDay};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_light_input_follows_the_day() {
+    let id = Uuid::from_u128(1);
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| {
+            c.world.deterministic = true;
+            c.circadian.day_length = 40;
+        })
+        .with_entity(EntityBuilder::new().id(id).at(10.0, 10.0).build())
+        .build();
+
+    let mut light = Vec::new();
+    for _ in 0..40 {
+        world.update(&mut env).expect("Update failed");
+        let perception = world.perception_of(id, &env).expect("entity alive");
+        light.push((env.time_of_day(), perception.input("Light")));
+    }
+
+    assert!(INPUT_LABELS.contains(&"Light"));
+    let noon = light[9].1;
+    let night = light[29].1;
+    assert_eq!(light[9].0, TimeOfDay::Day);
+    assert_eq!(light[29].0, TimeOfDay::Night);
+    assert!(noon > 0.9, "Noon light was {}", noon);
+    assert!((night - 0.1).abs() < 1e-6, "Night light was {}", night);
+}
+
+#[test]
+fn test_night_metabolism_discount_is_configurable() {
+    let mut config = AppConfig::default();
+    config.world.deterministic = true;
+    config.circadian.day_length = 100;
+    config.circadian.night_metabolism = 0.25;
+    let mut env = Environment::default();
+
+    environment_system::update_day(&mut env, 10, &config);
+    let day = env.metabolism_multiplier();
+    environment_system::update_day(&mut env, 60, &config);
+    assert_eq!(env.time_of_day(), TimeOfDay::Night);
+    assert!((env.metabolism_multiplier() - day * 0.25).abs() < 1e-9);
+
+    config.circadian.enabled = false;
+    environment_system::update_day(&mut env, 60, &config);
+    assert_eq!(env.time_of_day(), TimeOfDay::Day);
+    assert!(env.light_level() > 0.99);
+}
+
+#[test]
+fn test_dna_from_before_the_light_sensor_loads_with_it_unwired() {
+    let current = primordium_data::Genotype::new_random();
+    let light = INPUT_LABELS.iter().position(|&l| l == "Light").unwrap();
+    // Sensors added since are missing from such DNA as well.
+    let newer = light..BRAIN_INPUTS;
+    // Rewrite the genotype as a build without the Light sensor saved it.
+    let mut old = current.clone();
+    let to_old = |id: usize| {
+        if (BRAIN_INPUTS..BRAIN_HIDDEN_END).contains(&id) {
+            id - newer.len()
+        } else {
+            id
+        }
+    };
+    old.brain.nodes.retain(|n| !newer.contains(&n.id));
+    old.brain.connections.retain(|c| !newer.contains(&c.from));
+    for node in &mut old.brain.nodes {
+        node.id = to_old(node.id);
+    }
+    for conn in &mut old.brain.connections {
+        conn.from = to_old(conn.from);
+        conn.to = to_old(conn.to);
+        conn.innovation = topology::get_innovation_id(conn.from, conn.to);
+    }
+
+    let loaded = primordium_data::Genotype::from_hex(&old.to_hex()).expect("old DNA loads");
+    let node = loaded
+        .brain
+        .nodes
+        .iter()
+        .find(|n| n.id == light)
+        .expect("Light sensor added");
+    assert_eq!(node.label.as_deref(), Some("Light"));
+    assert!(loaded
+        .brain
+        .connections
+        .iter()
+        .all(|c| !newer.contains(&c.from)));
+    let wired = |g: &primordium_data::Genotype| {
+        let mut conns: Vec<_> = g
+            .brain
+            .connections
+            .iter()
+            .filter(|c| !newer.contains(&c.from))
+            .map(|c| (c.from, c.to, c.weight.to_bits()))
+            .collect();
+        conns.sort_unstable();
+        conns
+    };
+    assert_eq!(wired(&loaded), wired(&current));
+
+    let (outputs, _) = loaded.brain.forward([0.5; BRAIN_INPUTS], [0.0; 6]);
+    assert!(outputs.iter().all(|o| o.is_finite()));
+}
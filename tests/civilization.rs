@@ -46,7 +46,7 @@ async fn test_civilization_leveling_outposts() {
         let idx = world.terrain.index(i as u16, 0);
         let terrain = std::sync::Arc::make_mut(&mut world.terrain);
         terrain.set_cell_type(i as u16, 0, TerrainType::Outpost);
-        terrain.cells[idx].owner_id = Some(l_id);
+        terrain.cell_mut(idx).owner_id = Some(l_id);
     }
 
     let outpost_counts = civilization::count_outposts_by_lineage(&world.terrain);
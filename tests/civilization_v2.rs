@@ -34,7 +34,7 @@ async fn test_contested_ownership() {
 
     // Manually override outpost energy to match original test condition
     let idx = world.terrain.index(25, 25);
-    Arc::make_mut(&mut world.terrain).cells[idx].energy_store = 100.0;
+    Arc::make_mut(&mut world.terrain).cell_mut(idx).energy_store = 100.0;
 
     world.prepare_spatial_hash();
     world.capture_entity_snapshots();
@@ -49,21 +49,126 @@ async fn test_contested_ownership() {
     );
 
     assert_eq!(
-        world.terrain.cells[idx].owner_id,
+        world.terrain.cell(idx).owner_id,
         Some(lineage_b),
         "Ownership should transfer to lineage_b"
     );
     assert!(
-        world.terrain.cells[idx].energy_store < 100.0,
+        world.terrain.cell(idx).energy_store < 100.0,
         "Energy store should be reduced during transition"
     );
     assert_eq!(
-        world.terrain.cells[idx].outpost_spec,
+        world.terrain.cell(idx).outpost_spec,
         OutpostSpecialization::Standard,
         "Specialization should reset to Standard"
     );
 }
 
+#[tokio::test]
+async fn test_contested_outposts_warn_before_takeover() {
+    let lineage_a = Uuid::new_v4();
+    let lineage_b = Uuid::new_v4();
+
+    let mut world_builder = WorldBuilder::new().with_outpost(25, 25, lineage_a);
+    for _ in 0..2 {
+        world_builder = world_builder.with_entity(
+            EntityBuilder::new()
+                .at(26.0, 26.0)
+                .energy(100.0)
+                .lineage(lineage_b)
+                .build(),
+        );
+    }
+    let (mut world, _env) = world_builder.build();
+    world.prepare_spatial_hash();
+    world.capture_entity_snapshots();
+
+    let contested = civilization::contested_outposts(
+        &world.terrain,
+        world.width,
+        &world.spatial_hash,
+        &world.entity_snapshots,
+        &world.lineage_registry,
+    );
+    assert_eq!(contested.len(), 1);
+    let challenge = &contested[0];
+    assert_eq!(challenge.idx, world.terrain.index(25, 25));
+    assert_eq!(challenge.owner, lineage_a);
+    assert_eq!(challenge.challenger, lineage_b);
+    assert_eq!(challenge.ratio, 4.0);
+    assert!(
+        !challenge.is_imminent(),
+        "Two challengers are too few to take the outpost"
+    );
+
+    civilization::resolve_contested_ownership(
+        Arc::make_mut(&mut world.terrain),
+        world.width,
+        world.height,
+        &world.spatial_hash,
+        &world.entity_snapshots,
+        &world.lineage_registry,
+    );
+    assert_eq!(world.terrain.cell(challenge.idx).owner_id, Some(lineage_a));
+}
+
+#[tokio::test]
+async fn test_outpost_network_links_and_supply_flows() {
+    let l_id = Uuid::new_v4();
+    let (mut world, _env) = WorldBuilder::new()
+        .with_outpost(10, 10, l_id)
+        .with_outpost(11, 10, l_id)
+        .with_outpost(12, 10, l_id)
+        .with_outpost(30, 30, l_id)
+        .build();
+    world.lineage_registry.record_birth(l_id, 0, 0);
+    if let Some(record) = world.lineage_registry.lineages.get_mut(&l_id) {
+        record.civilization_level = 2;
+    }
+    let terrain = Arc::make_mut(&mut world.terrain);
+    for (x, energy) in [(10, 600.0), (11, 0.0), (12, 0.0)] {
+        let idx = terrain.index(x, 10);
+        terrain.cell_mut(idx).energy_store = energy;
+    }
+
+    let network = civilization::outpost_network(
+        &world.terrain,
+        world.width,
+        world.height,
+        &world.lineage_registry,
+        &world.roads,
+        &world.config.roads,
+    );
+    assert_eq!(network.nodes.len(), 4);
+    let lonely = network.node_at(world.terrain.index(30, 30)).unwrap();
+    assert_eq!(lonely.energy_store, 500.0);
+
+    // The grid averages 200 per outpost and moves a tenth of each gap per
+    // balance: 40 leaves (10, 10) and half of it carries on to (12, 10).
+    let links: Vec<_> = network.links_of(l_id).collect();
+    assert_eq!(links.len(), 2);
+    let endpoints = |l: &civilization::GridLink| {
+        (
+            network.nodes[l.from].x,
+            network.nodes[l.to].x,
+            l.flow.round(),
+        )
+    };
+    assert_eq!(endpoints(links[0]), (10, 11, 40.0));
+    assert_eq!(endpoints(links[1]), (11, 12, 20.0));
+
+    civilization::resolve_power_grid(
+        Arc::make_mut(&mut world.terrain),
+        world.width,
+        world.height,
+        &world.lineage_registry,
+        &world.roads,
+        &world.config.roads,
+    );
+    let stored = world.terrain.cell(world.terrain.index(10, 10)).energy_store;
+    assert_eq!(stored.round(), 560.0);
+}
+
 #[tokio::test]
 async fn test_outpost_upgrades() {
     let l_id = Uuid::new_v4();
@@ -104,7 +209,7 @@ async fn test_outpost_upgrades() {
 
         let idx = world.terrain.index(10, 10);
         assert_eq!(
-            world.terrain.cells[idx].outpost_spec,
+            world.terrain.cell(idx).outpost_spec,
             OutpostSpecialization::Silo,
             "Should upgrade to Silo for healthy tribe"
         );
@@ -146,7 +251,7 @@ async fn test_outpost_upgrades() {
 
         let idx = world.terrain.index(10, 10);
         assert_eq!(
-            world.terrain.cells[idx].outpost_spec,
+            world.terrain.cell(idx).outpost_spec,
             OutpostSpecialization::Nursery,
             "Should upgrade to Nursery for weak tribe"
         );
@@ -176,7 +281,7 @@ async fn test_dark_age_collapse_and_recovery() {
     let (mut world, mut env) = world_builder.build();
     let idx = world.terrain.index(10, 10);
 
-    assert!(world.terrain.cells[idx].energy_store > 0.0);
+    assert!(world.terrain.cell(idx).energy_store > 0.0);
     assert_eq!(
         world.lineage_registry.get_memory_value(&l_id, "knowledge"),
         1.0
@@ -196,7 +301,7 @@ async fn test_dark_age_collapse_and_recovery() {
     let knowledge = world.lineage_registry.get_memory_value(&l_id, "knowledge");
     assert!(knowledge < 0.1);
 
-    let cell = &world.terrain.cells[idx];
+    let cell = world.terrain.cell(idx);
     assert!(cell.energy_store < 500.0);
 
     // Phase 4: Recovery
@@ -211,6 +316,6 @@ async fn test_dark_age_collapse_and_recovery() {
         world.update(&mut env).expect("Recovery update failed");
     }
 
-    let cell_recovered = &world.terrain.cells[idx];
+    let cell_recovered = world.terrain.cell(idx);
     assert!(cell_recovered.energy_store > 0.0);
 }
@@ -77,8 +77,8 @@ async fn test_neural_topology_recursive_loop_bloat() {
             brain
                 .connections
                 .push(primordium_lib::model::brain::Connection {
-                    from: 41 + (i % 6),
-                    to: 41 + ((i + 1) % 6),
+                    from: primordium_lib::model::brain::BRAIN_HIDDEN_START + (i % 6),
+                    to: primordium_lib::model::brain::BRAIN_HIDDEN_START + ((i + 1) % 6),
                     weight: 1.0,
                     enabled: true,
                     innovation: 10000 + i,
@@ -1,6 +1,6 @@
 pub mod macros;
 
-use primordium_data::{Specialization, TerrainType};
+use primordium_data::{PlantGenome, Specialization, TerrainType};
 use primordium_lib::model::config::AppConfig;
 use primordium_lib::model::food::Food;
 use primordium_lib::model::lifecycle;
@@ -65,15 +65,15 @@ impl WorldBuilder {
             let idx = world.terrain.index(x, y);
             let terrain = Arc::make_mut(&mut world.terrain);
             terrain.set_cell_type(x, y, TerrainType::Outpost);
-            terrain.cells[idx].owner_id = Some(owner_id);
-            terrain.cells[idx].energy_store = 500.0;
+            terrain.cell_mut(idx).owner_id = Some(owner_id);
+            terrain.cell_mut(idx).energy_store = 500.0;
         }));
         self
     }
 
     pub fn with_fertility(mut self, fertility: f32) -> Self {
         self.terrain_mods.push(Box::new(move |world| {
-            for cell in Arc::make_mut(&mut world.terrain).cells.iter_mut() {
+            for cell in Arc::make_mut(&mut world.terrain).cells_mut() {
                 cell.fertility = fertility;
             }
         }));
@@ -87,13 +87,23 @@ impl WorldBuilder {
         self
     }
 
-    pub fn with_food(mut self, x: f64, y: f64, nutrient_type: f32) -> Self {
+    pub fn with_food(self, x: f64, y: f64, nutrient_type: f32) -> Self {
+        self.with_plant(x, y, PlantGenome::wild(nutrient_type))
+    }
+
+    pub fn with_plant(mut self, x: f64, y: f64, genome: PlantGenome) -> Self {
         self.terrain_mods.push(Box::new(move |world| {
-            world.ecs.spawn((
-                primordium_data::Position { x, y },
-                primordium_data::MetabolicNiche(nutrient_type),
-                Food::new(x as u16, y as u16, nutrient_type),
-            ));
+            if world.uses_food_field() {
+                world
+                    .food_field
+                    .deposit(x as u16, y as u16, genome.nutrient_type);
+            } else {
+                world.ecs.spawn((
+                    primordium_data::Position { x, y },
+                    primordium_data::MetabolicNiche(genome.nutrient_type),
+                    Food::from_genome(x as u16, y as u16, genome),
+                ));
+            }
             world
                 .food_count
                 .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
@@ -230,24 +240,26 @@ impl EntityBuilder {
     }
 
     pub fn with_behavior(mut self, behavior: TestBehavior) -> Self {
+        use primordium_lib::model::brain::{BRAIN_HIDDEN_START, BRAIN_INPUTS};
+        let (aggro, share, bond) = (BRAIN_INPUTS + 3, BRAIN_INPUTS + 4, BRAIN_INPUTS + 8);
         match behavior {
             TestBehavior::Aggressive => {
                 self = self
-                    .with_connection(0, 32, 10.0)
-                    .with_connection(2, 32, 10.0);
+                    .with_connection(0, aggro, 10.0)
+                    .with_connection(2, aggro, 10.0);
             }
             TestBehavior::Altruist => {
-                self = self.with_connection(2, 33, 10.0);
+                self = self.with_connection(2, share, 10.0);
             }
             TestBehavior::BondBreaker => {
                 self = self
-                    .with_connection(2, 41, -10.0)
-                    .with_connection(41, 37, 10.0);
+                    .with_connection(2, BRAIN_HIDDEN_START, -10.0)
+                    .with_connection(BRAIN_HIDDEN_START, bond, 10.0);
             }
             TestBehavior::SiegeSoldier => {
                 self = self
                     .specialization(Specialization::Soldier)
-                    .with_connection(5, 32, 10.0);
+                    .with_connection(5, aggro, 10.0);
             }
         }
         self
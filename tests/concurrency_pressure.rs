@@ -24,8 +24,8 @@ async fn test_high_density_interaction_safety() {
             .brain
             .connections
             .push(primordium_lib::model::brain::Connection {
-                from: 3, // Density
-                to: 32,  // Aggro
+                from: 3,                                            // Density
+                to: primordium_lib::model::brain::BRAIN_INPUTS + 3, // Aggro
                 weight: 10.0,
                 enabled: true,
                 innovation: 12345,
@@ -0,0 +1,93 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_data::Identity;
+use primordium_lib::model::persistence::{load_world, save_world};
+use primordium_lib::model::world::continuity::SurvivorSelection;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+#[test]
+fn test_survivor_selection_parses() {
+    assert_eq!("all".parse(), Ok(SurvivorSelection::All));
+    assert_eq!("top:100".parse(), Ok(SurvivorSelection::Top(100)));
+    assert_eq!(SurvivorSelection::Top(7).to_string(), "top:7");
+    for bad in ["top:0", "top:", "top:x", "best:3", ""] {
+        assert!(bad.parse::<SurvivorSelection>().is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn test_new_world_continues_from_fittest_survivors() {
+    let (lineage_a, lineage_b, lineage_c, lineage_d) = (
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+    );
+    let mut builder = WorldBuilder::new();
+    let mut expected = HashSet::new();
+    for (i, (lineage, offspring)) in [
+        (lineage_a, 5),
+        (lineage_a, 0),
+        (lineage_b, 3),
+        (lineage_c, 1),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let mut e = EntityBuilder::new()
+            .at(10.0 + 5.0 * i as f64, 10.0)
+            .lineage(lineage)
+            .build();
+        e.metabolism.offspring_count = offspring;
+        e.metabolism.peak_energy = 100.0;
+        if offspring >= 3 {
+            expected.insert(e.identity.id);
+        }
+        builder = builder.with_entity(e);
+    }
+    let (mut source, _) = builder.build();
+    source.tick = 5_000;
+    for lineage in [lineage_a, lineage_a, lineage_b, lineage_c] {
+        source.lineage_registry.record_birth(lineage, 0, 0);
+    }
+    source.lineage_registry.record_birth(lineage_d, 4, 100);
+    source.lineage_registry.record_death(lineage_d, 200);
+    let name_a = source.lineage_registry.lineages[&lineage_a].name.clone();
+
+    let path = std::env::temp_dir().join(format!("continue_{}.json", Uuid::new_v4()));
+    save_world(&mut source, &path).unwrap();
+    let previous = load_world(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_entity(EntityBuilder::new().at(30.0, 30.0).build())
+        .build();
+    let summary = world.continue_from(previous, SurvivorSelection::Top(2));
+    assert_eq!(summary.source_tick, 5_000);
+    assert_eq!(summary.candidates, 4);
+    assert_eq!(summary.survivors, 2);
+    assert_eq!(summary.lineages, 2);
+
+    let ids: HashSet<Uuid> = world
+        .ecs
+        .query::<&Identity>()
+        .iter()
+        .map(|(_, i)| i.id)
+        .collect();
+    assert_eq!(ids, expected);
+
+    // Every lineage record comes along; only the survivors' are alive.
+    let registry = &world.lineage_registry;
+    for lineage in [lineage_a, lineage_b, lineage_c, lineage_d] {
+        assert!(registry.lineages.contains_key(&lineage));
+    }
+    let a = &registry.lineages[&lineage_a];
+    assert_eq!(a.name, name_a);
+    assert_eq!(a.current_population, 1);
+    assert!(!a.is_extinct);
+    assert!(registry.lineages[&lineage_c].is_extinct);
+    assert_eq!(registry.lineages[&lineage_d].max_generation, 4);
+
+    world.update(&mut env).unwrap();
+}
@@ -0,0 +1,109 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_core::systems::crowd::crowd_response;
+use primordium_data::{Identity, Metabolism};
+use primordium_lib::model::world::World;
+use uuid::Uuid;
+
+fn crowd_world(positions: &[(f64, f64)], trample_damage: f64) -> World {
+    let mut builder = WorldBuilder::new().with_seed(11).with_config(|c| {
+        c.world.deterministic = true;
+        c.crowd.trample_damage = trample_damage;
+    });
+    for (i, &(x, y)) in positions.iter().enumerate() {
+        builder = builder.with_entity(
+            EntityBuilder::new()
+                .id(Uuid::from_u128(i as u128 + 1))
+                .at(x, y)
+                .energy(150.0)
+                .build(),
+        );
+    }
+    let (mut world, _env) = builder.build();
+    world.prepare_spatial_hash();
+    world.capture_entity_snapshots();
+    world
+}
+
+fn response_of(world: &World, idx: usize) -> primordium_core::systems::crowd::CrowdResponse {
+    let s = &world.entity_snapshots[idx];
+    crowd_response(
+        s.x,
+        s.y,
+        &s.id,
+        &world.entity_snapshots,
+        &world.spatial_hash,
+        &world.config.crowd,
+    )
+}
+
+#[test]
+fn test_comfortable_density_is_left_alone() {
+    let world = crowd_world(&[(20.0, 20.0), (20.5, 20.0), (21.0, 20.0)], 0.5);
+    let response = response_of(&world, 0);
+    assert_eq!(response.density, 2);
+    assert_eq!(response.push, (0.0, 0.0));
+    assert_eq!(response.trample_damage, 0.0);
+}
+
+#[test]
+fn test_dense_side_pushes_entity_away() {
+    let mut positions = vec![(20.0, 20.0)];
+    positions.extend((0..8).map(|i| (21.0, 19.6 + i as f64 * 0.1)));
+    let world = crowd_world(&positions, 0.5);
+    let idx = world
+        .entity_snapshots
+        .iter()
+        .position(|s| s.x == 20.0)
+        .unwrap();
+
+    let response = response_of(&world, idx);
+    assert_eq!(response.density, 8);
+    assert!(
+        response.push.0 < 0.0,
+        "pushed away from the crowd to the east"
+    );
+    assert!(response.push.1.abs() < response.push.0.abs());
+    assert_eq!(response.trample_damage, 0.0);
+}
+
+#[test]
+fn test_stacked_entities_trample_and_split_deterministically() {
+    let positions = vec![(30.0, 30.0); 15];
+    let world = crowd_world(&positions, 0.5);
+    let config = &world.config.crowd;
+
+    let first = response_of(&world, 0);
+    assert_eq!(first.density, 14);
+    assert_eq!(
+        first.trample_damage,
+        config.trample_damage * (14 - config.trample_density + 1) as f64
+    );
+    let strength = first.push.0.hypot(first.push.1);
+    assert!((strength - config.separation_force * 2.0).abs() < 1e-9);
+    assert_eq!(response_of(&world, 0), first);
+    assert_ne!(response_of(&world, 1).push, first.push);
+}
+
+#[test]
+fn test_trampling_drains_energy_in_the_world() {
+    let positions = vec![(30.0, 30.0); 15];
+    let total_energy = |trample_damage: f64| {
+        let mut world = crowd_world(&positions, trample_damage);
+        let mut env = primordium_lib::model::environment::Environment::default();
+        env.tick_deterministic(0);
+        world.update(&mut env).unwrap();
+        let mut energies: Vec<_> = world
+            .ecs
+            .query::<(&Identity, &Metabolism)>()
+            .iter()
+            .map(|(_, (i, m))| (i.id, m.energy))
+            .collect();
+        energies.sort_by_key(|e| e.0);
+        energies.iter().map(|e| e.1).sum::<f64>()
+    };
+    let calm = total_energy(0.0);
+    let trampled = total_energy(0.5);
+    // 15 entities, each with 14 neighbours: 3 over the limit at 0.5 each.
+    assert!((calm - trampled - 15.0 * 1.5).abs() < 1e-6);
+}
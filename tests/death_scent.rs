@@ -0,0 +1,59 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_core::pheromone::PheromoneType;
+use primordium_core::systems::biological;
+use primordium_data::{Pathogen, Position};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[test]
+fn test_infection_deaths_leave_death_scent() {
+    let mut victim = EntityBuilder::new().at(20.5, 20.5).energy(1.0).build();
+    let plague = Pathogen {
+        id: Uuid::from_u128(1),
+        lethality: 5.0,
+        transmission: 0.0,
+        duration: 50,
+        virulence: 1.0,
+        behavior_manipulation: None,
+    };
+    biological::infect_components(&mut victim.health, &plague);
+    let (mut world, mut env) = WorldBuilder::new().with_entity(victim).build();
+
+    world.update(&mut env).unwrap();
+
+    assert_eq!(world.get_population_count(), 0);
+    let death = world.pheromones.channel_of(PheromoneType::Death).unwrap();
+    assert!(world.pheromones.strength(20, 20, death) > 0.5);
+}
+
+#[test]
+fn test_avoiders_move_away_from_death_scent() {
+    let x_after = |avoidance: f32| {
+        let mut e = EntityBuilder::new()
+            .id(Uuid::from_u128(7))
+            .at(12.5, 20.5)
+            .energy(200.0)
+            .build();
+        Arc::make_mut(&mut e.intel.genotype).death_avoidance = avoidance;
+        let (mut world, mut env) = WorldBuilder::new()
+            .with_config(|c| c.world.deterministic = true)
+            .with_entity(e)
+            .build();
+        let pheromones = Arc::make_mut(&mut world.pheromones);
+        pheromones.deposit(9.5, 20.5, PheromoneType::Death, 1.0);
+        pheromones.update();
+
+        world.update(&mut env).unwrap();
+        let x = world
+            .ecs
+            .query::<&Position>()
+            .iter()
+            .next()
+            .map(|(_, p)| p.x)
+            .unwrap();
+        x
+    };
+
+    assert!(x_after(1.0) > x_after(0.0) + 0.1);
+}
@@ -0,0 +1,141 @@
+mod common;
+use common::{EntityBuilder, TestBehavior, WorldBuilder};
+use primordium_core::diplomacy::Stance;
+use primordium_data::{Entity, LiveEvent};
+use primordium_lib::model::world::World;
+use uuid::Uuid;
+
+const HUNTERS: Uuid = Uuid::from_u128(777);
+const PREY: Uuid = Uuid::from_u128(888);
+
+fn hunter(color: (u8, u8, u8)) -> Entity {
+    let mut e = EntityBuilder::new()
+        .id(Uuid::from_u128(1))
+        .at(10.0, 10.0)
+        .energy(10000.0)
+        .max_energy(12000.0)
+        .color(color.0, color.1, color.2)
+        .with_behavior(TestBehavior::Aggressive)
+        .lineage(HUNTERS)
+        .build();
+    e.metabolism.trophic_potential = 1.0;
+    e
+}
+
+fn prey(color: (u8, u8, u8)) -> Entity {
+    let mut e = EntityBuilder::new()
+        .id(Uuid::from_u128(2))
+        .at(10.1, 10.1)
+        .energy(500.0)
+        .max_energy(1000.0)
+        .color(color.0, color.1, color.2)
+        .lineage(PREY)
+        .build();
+    e.metabolism.trophic_potential = 0.0;
+    e.physics.max_speed = 0.0;
+    std::sync::Arc::make_mut(&mut e.intel.genotype).max_speed = 0.0;
+    e
+}
+
+fn hunt(hunter_color: (u8, u8, u8), prey_color: (u8, u8, u8), standing: f32) -> World {
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_seed(789)
+        .with_config(|c| {
+            c.world.deterministic = true;
+            c.world.disaster_chance = 0.0;
+            c.metabolism.reproduction_threshold = 1000000.0;
+        })
+        .with_entity(hunter(hunter_color))
+        .with_entity(prey(prey_color))
+        .build();
+    let diplomacy = &mut world.lineage_registry.diplomacy;
+    diplomacy.adjust(HUNTERS, PREY, standing);
+    diplomacy.settle(&world.config.diplomacy, |_| true);
+
+    for _ in 0..100 {
+        for (_, met) in world
+            .ecs
+            .query_mut::<&mut primordium_lib::model::state::Metabolism>()
+            .into_iter()
+            .filter(|(_, m)| m.trophic_potential > 0.9)
+        {
+            met.energy = 5000.0;
+        }
+        world.update(&mut env).expect("Update failed");
+        if world.get_population_count() == 1 {
+            break;
+        }
+    }
+    world
+}
+
+#[tokio::test]
+async fn test_allies_spare_each_other_across_tribe_colors() {
+    let world = hunt((255, 0, 0), (0, 0, 255), 1.0);
+
+    assert_eq!(
+        world.lineage_registry.diplomacy.stance(HUNTERS, PREY),
+        Stance::Alliance
+    );
+    assert_eq!(world.get_population_count(), 2, "Ally was preyed on");
+}
+
+#[tokio::test]
+async fn test_war_overrides_shared_tribe_colors() {
+    let world = hunt((100, 100, 100), (100, 100, 100), -1.0);
+
+    assert_eq!(world.get_population_count(), 1, "Enemy was spared");
+}
+
+#[tokio::test]
+async fn test_war_and_peace_are_narrated() {
+    // Rooted grazers, so the war runs its course without anyone dying in it.
+    let member = |id, lineage, x| {
+        let mut e = EntityBuilder::new()
+            .id(Uuid::from_u128(id))
+            .at(x, 10.0)
+            .energy(5000.0)
+            .max_energy(10000.0)
+            .lineage(lineage)
+            .build();
+        e.metabolism.trophic_potential = 0.0;
+        e.physics.max_speed = 0.0;
+        std::sync::Arc::make_mut(&mut e.intel.genotype).max_speed = 0.0;
+        e
+    };
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_seed(789)
+        .with_config(|c| c.metabolism.reproduction_threshold = 1000000.0)
+        .with_entity(member(1, HUNTERS, 5.0))
+        .with_entity(member(2, PREY, 40.0))
+        .build();
+    world.lineage_registry.diplomacy.adjust(HUNTERS, PREY, -0.6);
+
+    let events = world.update(&mut env).expect("Update failed");
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, LiveEvent::WarDeclared { .. })));
+
+    let mut treaty = Vec::new();
+    while treaty.is_empty() && world.tick < 1000 {
+        treaty = world.update(&mut env).expect("Update failed");
+        treaty.retain(|e| {
+            matches!(
+                e,
+                LiveEvent::PeaceTreaty {
+                    alliance: false,
+                    ..
+                }
+            )
+        });
+    }
+    assert_eq!(treaty.len(), 1);
+    let narrated: Vec<_> = world
+        .observer
+        .history
+        .iter()
+        .map(|e| e.event_type.as_str())
+        .collect();
+    assert!(narrated.contains(&"WarEvent"));
+    assert!(narrated.contains(&"PeaceTreaty"));
+}
@@ -1,4 +1,5 @@
 use primordium_core::systems::environment as environment_system;
+use primordium_data::{Disaster, DisasterRecord};
 use primordium_lib::model::config::AppConfig;
 use primordium_lib::model::lifecycle;
 use primordium_lib::model::state::environment::Environment;
@@ -82,3 +83,53 @@ async fn test_dust_bowl_trigger() {
         "Dust Bowl should trigger immediately under high heat and population with chance=1.0"
     );
 }
+
+#[tokio::test]
+async fn test_scripted_disasters_replay_recorded_timeline() {
+    let mut config = AppConfig::default();
+    config.world.initial_population = 0;
+    config.world.disaster_chance = 1.0;
+
+    let mut recorded = World::new(0, config.clone()).unwrap();
+    let mut env = Environment {
+        cpu_usage: 95.0,
+        ..Environment::default()
+    };
+    for _ in 0..11 {
+        environment_system::update_events(&mut env, &config);
+    }
+    for _ in 0..310 {
+        let mut e = lifecycle::create_entity(5.0, 5.0, 0);
+        e.metabolism.energy = 1000.0;
+        recorded.spawn_entity(e);
+    }
+    recorded.queue_disaster(Disaster::SolarFlare { duration: 500 });
+    for _ in 0..5 {
+        recorded.update(&mut env).unwrap();
+    }
+    assert_eq!(
+        recorded.disasters[0],
+        DisasterRecord {
+            tick: 1,
+            disaster: Disaster::SolarFlare { duration: 500 },
+        }
+    );
+    assert!(recorded
+        .disasters
+        .iter()
+        .any(|r| matches!(r.disaster, Disaster::DustBowl { .. })));
+
+    // No heat wave and no population: only the script can strike.
+    let mut replayed = World::new(0, config).unwrap();
+    replayed.script_disasters(recorded.disasters.clone());
+    let mut replay_env = Environment::default();
+    for _ in 0..5 {
+        replayed.update(&mut replay_env).unwrap();
+    }
+    assert_eq!(replayed.disasters, recorded.disasters);
+    assert_eq!(
+        replayed.terrain.dust_bowl_timer,
+        recorded.terrain.dust_bowl_timer
+    );
+    assert!(replay_env.radiation_timer > 0);
+}
@@ -0,0 +1,56 @@
+use primordium_data::data::dna;
+use primordium_data::Genotype;
+use primordium_lib::model::brain::{create_genotype_random_with_rng, GenotypeLogic};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn legacy_hex(genotype: &Genotype) -> String {
+    hex::encode(serde_json::to_vec(genotype).unwrap())
+}
+
+#[tokio::test]
+async fn test_compact_dna_is_a_fraction_of_the_json_hex() {
+    for seed in 0..8 {
+        let genotype = create_genotype_random_with_rng(&mut StdRng::seed_from_u64(seed));
+        let compact = genotype.to_hex();
+        let legacy = legacy_hex(&genotype);
+        assert!(
+            compact.len() * 5 < legacy.len(),
+            "compact DNA is {} chars, JSON hex {}",
+            compact.len(),
+            legacy.len()
+        );
+
+        let decoded = Genotype::from_hex(&compact).unwrap();
+        assert_eq!(decoded.to_hex(), compact);
+        assert_eq!(legacy_hex(&decoded), legacy);
+    }
+}
+
+#[tokio::test]
+async fn test_json_hex_dna_still_decodes() {
+    let genotype = create_genotype_random_with_rng(&mut StdRng::seed_from_u64(3));
+    let decoded = Genotype::from_hex(&legacy_hex(&genotype)).unwrap();
+    assert_eq!(decoded.to_hex(), genotype.to_hex());
+}
+
+#[tokio::test]
+async fn test_newer_dna_versions_are_rejected() {
+    let genotype = create_genotype_random_with_rng(&mut StdRng::seed_from_u64(5));
+    let mut bytes = dna::encode(&genotype).unwrap();
+    bytes[1] = dna::VERSION + 1;
+    let err = Genotype::from_hex(&hex::encode(bytes)).unwrap_err();
+    assert!(err.to_string().contains("newer"), "{err}");
+}
+
+#[tokio::test]
+async fn test_dna_that_expands_past_the_cap_is_rejected() {
+    // 64 MiB of zeros compresses to a few kilobytes.
+    let zeros = vec![0u8; 64 << 20];
+    let mut bytes = vec![dna::MAGIC, dna::VERSION];
+    bytes.extend(zstd::bulk::compress(&zeros, 19).unwrap());
+    assert!(bytes.len() < 64 << 10);
+
+    let err = dna::decode(&bytes).unwrap_err();
+    assert!(err.to_string().contains("expands past"), "{err}");
+}
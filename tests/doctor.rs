@@ -0,0 +1,80 @@
+use primordium_lib::app::doctor::{self, Status};
+use primordium_lib::model::config::AppConfig;
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("doctor_{}_{name}", uuid::Uuid::new_v4()))
+}
+
+#[test]
+fn test_config_check_reports_missing_and_invalid_files() {
+    let missing = temp_path("missing.toml");
+    let (_, check) = doctor::check_config(missing.to_str().unwrap());
+    assert_eq!(check.status, Status::Warn);
+
+    let invalid = temp_path("invalid.toml");
+    let mut bad = AppConfig::default();
+    bad.world.width = 0;
+    std::fs::write(&invalid, toml::to_string(&bad).unwrap()).unwrap();
+    let (config, check) = doctor::check_config(invalid.to_str().unwrap());
+    std::fs::remove_file(&invalid).unwrap();
+    assert_eq!(check.status, Status::Fail);
+    assert!(check.detail.contains("width"), "{}", check.detail);
+    assert!(check.advice.is_some());
+    assert_eq!(config.world.width, AppConfig::default().world.width);
+}
+
+#[test]
+fn test_memory_check_scales_with_world_size() {
+    let mut config = AppConfig::default();
+    let needed = doctor::estimated_memory(&config);
+    assert_eq!(doctor::check_memory(&config, needed * 4).status, Status::Ok);
+    assert_eq!(
+        doctor::check_memory(&config, needed + 1).status,
+        Status::Warn
+    );
+    assert_eq!(
+        doctor::check_memory(&config, needed - 1).status,
+        Status::Fail
+    );
+
+    config.world.width *= 2;
+    assert!(doctor::estimated_memory(&config) > needed);
+}
+
+#[test]
+fn test_writable_and_storage_checks() {
+    let dir = temp_path("logs");
+    let check = doctor::check_writable("logs", dir.to_str().unwrap());
+    assert_eq!(check.status, Status::Ok);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+    std::fs::write(dir.join("fossils.json.gz"), b"not gzip").unwrap();
+    let mut config = AppConfig::default();
+    config.archive.dir = dir.join("genomes").to_string_lossy().into_owned();
+    let checks = doctor::check_storage(&config, dir.to_str().unwrap());
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(checks
+        .iter()
+        .any(|c| c.status == Status::Fail && c.detail.contains("fossils.json.gz")));
+}
+
+#[test]
+fn test_relay_check() {
+    assert_eq!(
+        doctor::relay_address("ws://127.0.0.1:3000/ws").as_deref(),
+        Some("127.0.0.1:3000")
+    );
+    assert_eq!(
+        doctor::relay_address("wss://relay.example.org/ws").as_deref(),
+        Some("relay.example.org:443")
+    );
+    assert_eq!(doctor::relay_address("not a url"), None);
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let url = format!("ws://127.0.0.1:{port}/ws");
+    assert_eq!(doctor::check_relay(&url).status, Status::Ok);
+    drop(listener);
+    assert_eq!(doctor::check_relay(&url).status, Status::Warn);
+    assert_eq!(doctor::check_relay("not a url").status, Status::Fail);
+}
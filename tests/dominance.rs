@@ -0,0 +1,58 @@
+mod common;
+use common::{EntityBuilder, TestBehavior, WorldBuilder};
+use uuid::Uuid;
+
+const LINEAGE: Uuid = Uuid::from_u128(10);
+const CHALLENGER: Uuid = Uuid::from_u128(1);
+const DEFENDER: Uuid = Uuid::from_u128(2);
+
+fn member(id: Uuid, x: f64, energy: f64) -> EntityBuilder {
+    EntityBuilder::new()
+        .id(id)
+        .at(x, 10.0)
+        .energy(energy)
+        .max_energy(500.0)
+        .color(100, 100, 100)
+        .lineage(LINEAGE)
+        .trophic(0.0)
+}
+
+#[test]
+fn test_challenge_ranks_the_stronger_lineage_mate_first() {
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| c.world.deterministic = true)
+        .with_entity(
+            member(CHALLENGER, 10.0, 450.0)
+                .with_behavior(TestBehavior::Aggressive)
+                .build(),
+        )
+        .with_entity(member(DEFENDER, 11.0, 100.0).build())
+        .build();
+    world.update(&mut env).unwrap();
+    world.update(&mut env).unwrap();
+
+    assert_eq!(world.dominance.ladder(LINEAGE), [CHALLENGER, DEFENDER]);
+    world.refresh_pop_stats(&env);
+    assert_eq!(world.pop_stats.lineage_hierarchy_depth[&LINEAGE], 2);
+    assert_eq!(world.pop_stats.max_hierarchy_depth, 2);
+}
+
+#[test]
+fn test_children_inherit_a_place_below_their_parent() {
+    let child_id = Uuid::from_u128(3);
+    let mut child = member(child_id, 30.0, 100.0).build();
+    child.identity.parent_id = Some(DEFENDER);
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| c.dominance.enabled = false)
+        .with_entity(member(CHALLENGER, 10.0, 200.0).build())
+        .with_entity(member(DEFENDER, 20.0, 200.0).build())
+        .with_entity(child)
+        .build();
+    world.dominance.contest(LINEAGE, DEFENDER, CHALLENGER);
+    world.update(&mut env).unwrap();
+
+    assert_eq!(
+        world.dominance.ladder(LINEAGE),
+        [DEFENDER, child_id, CHALLENGER]
+    );
+}
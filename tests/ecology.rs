@@ -1,6 +1,7 @@
 mod common;
 use common::{EntityBuilder, WorldBuilder};
-use primordium_data::TerrainType;
+use primordium_data::{Food, TerrainType};
+use primordium_lib::model::config::FoodRepresentation;
 
 #[tokio::test]
 async fn test_terrain_fertility_cycle() {
@@ -39,7 +40,7 @@ async fn test_barren_transition() {
     for _ in 0..10 {
         std::sync::Arc::make_mut(&mut world.terrain).deplete(5.5, 5.5, 0.1);
     }
-    std::sync::Arc::make_mut(&mut world.terrain).update(0.0, 0, 42);
+    std::sync::Arc::make_mut(&mut world.terrain).update(0.0, 1.0, 0, 42);
     let terrain_type = world.terrain.get_cell(ix, iy).terrain_type;
     assert!(terrain_type == TerrainType::Barren || terrain_type == TerrainType::Desert);
 }
@@ -85,6 +86,40 @@ async fn test_trophic_diet_restrictions() {
     }
 }
 
+#[tokio::test]
+async fn test_food_field_representation_is_eaten() {
+    let herbivore = EntityBuilder::new()
+        .at(10.0, 10.0)
+        .energy(50.0)
+        .trophic(0.0)
+        .niche(0.5)
+        .build();
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| {
+            c.food.representation = FoodRepresentation::Field;
+            c.ecosystem.base_spawn_chance = 0.0;
+        })
+        .with_entity(herbivore)
+        .with_food(10.0, 10.0, 0.5)
+        .with_food(10.0, 10.0, 0.5)
+        .build();
+
+    assert_eq!(world.get_food_count(), 2);
+    assert_eq!(world.ecs.query::<&Food>().iter().count(), 0);
+
+    world.update(&mut env).expect("Update failed");
+    assert_eq!(
+        world.get_food_count(),
+        1,
+        "one unit eaten per site per tick"
+    );
+    assert_eq!(world.ecs.query::<&Food>().iter().count(), 0);
+
+    let snapshot = world.create_snapshot(None);
+    assert_eq!(snapshot.food.len(), 1);
+}
+
 #[tokio::test]
 async fn test_light_dependent_food_growth() {
     let mut day_food_count = 0;
@@ -51,7 +51,7 @@ async fn test_hunter_competition_impact() {
             .connections
             .push(primordium_lib::model::brain::Connection {
                 from: 2,
-                to: 32,
+                to: primordium_lib::model::brain::BRAIN_INPUTS + 3,
                 weight: 10.0,
                 enabled: true,
                 innovation: 999,
@@ -119,7 +119,7 @@ async fn test_hunter_competition_impact() {
                 .connections
                 .push(primordium_lib::model::brain::Connection {
                     from: 2,
-                    to: 32,
+                    to: primordium_lib::model::brain::BRAIN_INPUTS + 3,
                     weight: 10.0,
                     enabled: true,
                     innovation: 999,
@@ -0,0 +1,42 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_data::TagSource;
+use primordium_lib::model::introspection::ArchetypeKind;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_ecs_report_counts_archetypes_and_optional_components() {
+    let (mut world, _env) = WorldBuilder::new()
+        .with_entity(EntityBuilder::new().id(Uuid::from_u128(1)).build())
+        .with_entity(EntityBuilder::new().id(Uuid::from_u128(2)).build())
+        .with_food(5.0, 5.0, 0.5)
+        .build();
+    assert!(world.tag_entity(Uuid::from_u128(1), "protagonist", TagSource::User));
+
+    let report = world.ecs_report();
+    assert_eq!(report.entities, 3);
+    assert_eq!(report.count_of(ArchetypeKind::Organism), 2);
+    assert_eq!(report.count_of(ArchetypeKind::Food), 1);
+    assert_eq!(report.count_with("Tags"), 1);
+    assert_eq!(report.incomplete().count(), 0);
+}
+
+#[tokio::test]
+async fn test_ecs_report_flags_organisms_missing_components() {
+    let (mut world, _env) = WorldBuilder::new()
+        .with_entity(EntityBuilder::new().build())
+        .build();
+    let e = EntityBuilder::new().build();
+    world
+        .ecs
+        .spawn((e.identity, e.position, e.physics, e.metabolism));
+
+    let report = world.ecs_report();
+    let leaked: Vec<_> = report.incomplete().collect();
+    assert_eq!(leaked.len(), 1);
+    assert_eq!(leaked[0].count, 1);
+    assert_eq!(
+        leaked[0].missing,
+        vec!["Velocity", "Appearance", "Health", "Intel"]
+    );
+}
@@ -19,6 +19,7 @@ fn test_perception_division_nan_safety() {
         has_metamorphosed: false,
         is_in_transit: false,
         migration_id: None,
+        hydration: 1.0,
     };
 
     // This simulates the division in systems.rs line 100:
@@ -44,6 +45,7 @@ fn test_perception_division_zero_energy_max() {
         has_metamorphosed: false,
         is_in_transit: false,
         migration_id: None,
+        hydration: 1.0,
     };
 
     // This simulates the division in systems.rs line 100 with zero max_energy
@@ -68,6 +70,7 @@ fn test_perception_negative_energy_handling() {
         has_metamorphosed: false,
         is_in_transit: false,
         migration_id: None,
+        hydration: 1.0,
     };
 
     let result = met.energy / met.max_energy.max(1.0);
@@ -0,0 +1,188 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_core::config::EggConfig;
+use primordium_core::environment::{Environment, Season};
+use primordium_core::systems::eggs;
+use primordium_data::{Egg, Entity, LiveEvent, Metabolism, Position, TerrainType};
+use primordium_lib::model::world::World;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn oviparous(mut entity: Entity, clutch_size: u8) -> Entity {
+    let genotype = Arc::make_mut(&mut entity.intel.genotype);
+    genotype.oviparity = 1.0;
+    genotype.clutch_size = clutch_size;
+    entity
+}
+
+fn egg_count(world: &World) -> usize {
+    world.ecs.query::<&Egg>().iter().count()
+}
+
+fn lay(world: &mut World, hatchling: Entity) {
+    let egg = Egg {
+        hatchling: Box::new(hatchling),
+        laid_tick: world.tick,
+        incubation: 0.0,
+    };
+    world.ecs.spawn((egg.hatchling.position, egg));
+}
+
+#[test]
+fn test_clutch_shares_one_offspring_energy() {
+    let offspring = EntityBuilder::new().at(10.0, 10.0).energy(90.0).build();
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    let clutch = eggs::lay_clutch(offspring.clone(), 3, 7, (20, 20), &mut rng);
+
+    assert_eq!(clutch.len(), 3);
+    assert_eq!(clutch[0].hatchling.identity.id, offspring.identity.id);
+    let ids: HashSet<Uuid> = clutch.iter().map(|e| e.hatchling.identity.id).collect();
+    assert_eq!(ids.len(), 3);
+    for egg in &clutch {
+        assert_eq!(egg.laid_tick, 7);
+        assert_eq!(egg.hatchling.metabolism.energy, 30.0);
+        assert!((egg.hatchling.position.x - 10.0).abs() <= 0.5);
+        assert!((egg.hatchling.position.y - 10.0).abs() <= 0.5);
+    }
+}
+
+#[test]
+fn test_nests_and_warmth_speed_up_incubation() {
+    let (world, _) = WorldBuilder::new()
+        .with_terrain(5, 5, TerrainType::Nest)
+        .build();
+    let config = EggConfig::default();
+    let env = Environment::default();
+
+    let open = eggs::incubation_rate(&world.terrain, &env, 15.5, 15.5, &config);
+    let beside = eggs::incubation_rate(&world.terrain, &env, 6.5, 5.5, &config);
+    let on_nest = eggs::incubation_rate(&world.terrain, &env, 5.5, 5.5, &config);
+    assert!(open < beside && beside < on_nest);
+    assert!((on_nest / open - config.nest_bonus).abs() < 1e-6);
+
+    let winter = Environment {
+        current_season: Season::Winter,
+        ..Environment::default()
+    };
+    assert!(eggs::climate_factor(&winter) < eggs::climate_factor(&env));
+    let ice_age = Environment {
+        ice_age_timer: 100,
+        ..Environment::default()
+    };
+    assert!(eggs::climate_factor(&ice_age) < eggs::climate_factor(&env));
+}
+
+#[test]
+fn test_egg_hatches_after_incubation() {
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| c.eggs.incubation_ticks = 5)
+        .build();
+    let hatchling = EntityBuilder::new().at(20.0, 20.0).energy(50.0).build();
+    let id = hatchling.identity.id;
+    lay(&mut world, hatchling);
+
+    let mut hatched_on = None;
+    for _ in 0..10 {
+        let events = world.update(&mut env).unwrap();
+        if events
+            .iter()
+            .any(|e| matches!(e, LiveEvent::Birth { id: born, .. } if *born == id))
+        {
+            hatched_on = Some(world.tick);
+            break;
+        }
+    }
+    let tick = hatched_on.expect("egg should hatch");
+    assert!(tick >= 5);
+    assert_eq!(egg_count(&world), 0);
+    let birth_tick = world
+        .ecs
+        .query::<(&primordium_data::Identity, &Metabolism)>()
+        .iter()
+        .find(|(_, (i, _))| i.id == id)
+        .map(|(_, (_, m))| m.birth_tick);
+    assert_eq!(birth_tick, Some(tick));
+}
+
+#[test]
+fn test_strangers_eat_eggs_kin_do_not() {
+    let lineage = Uuid::from_u128(1);
+    let run = |predator_lineage: Uuid| {
+        let (mut world, mut env) = WorldBuilder::new()
+            .with_config(|c| c.world.deterministic = true)
+            .with_entity(
+                EntityBuilder::new()
+                    .at(30.0, 30.0)
+                    .energy(100.0)
+                    .max_energy(500.0)
+                    .trophic(1.0)
+                    .lineage(predator_lineage)
+                    .build(),
+            )
+            .build();
+        let egg = EntityBuilder::new()
+            .at(30.3, 30.0)
+            .energy(80.0)
+            .lineage(lineage)
+            .build();
+        lay(&mut world, egg);
+        world.update(&mut env).unwrap();
+        egg_count(&world)
+    };
+
+    assert_eq!(run(Uuid::from_u128(2)), 0);
+    assert_eq!(run(lineage), 1);
+}
+
+#[test]
+fn test_oviparous_parents_lay_clutches() {
+    let mut builder = WorldBuilder::new().with_config(|c| {
+        c.metabolism.maturity_age = 10;
+        c.eggs.incubation_ticks = 1_000;
+    });
+    for i in 0..10 {
+        builder = builder.with_entity(oviparous(
+            EntityBuilder::new()
+                .id(Uuid::from_u128(i + 1))
+                .at(10.0 + i as f64 * 3.0, 10.0)
+                .energy(200.0)
+                .max_energy(200.0)
+                .lineage(Uuid::from_u128(99))
+                // Herbivores, so no parent eats a clutch that speciated.
+                .trophic(0.0)
+                .build(),
+            3,
+        ));
+    }
+    let (mut world, mut env) = builder.build();
+
+    let mut births = 0;
+    for _ in 0..50 {
+        for (_, met) in world.ecs.query_mut::<&mut Metabolism>() {
+            met.energy = 200.0;
+        }
+        births += world
+            .update(&mut env)
+            .unwrap()
+            .iter()
+            .filter(|e| matches!(e, LiveEvent::Birth { .. }))
+            .count();
+    }
+
+    let eggs = egg_count(&world);
+    assert!(eggs > 0, "oviparous parents should lay eggs");
+    assert_eq!(eggs % 3, 0, "eggs come in clutches of three");
+    assert_eq!(births, 0, "no live births before the eggs hatch");
+    let mut incubating = 0;
+    for (_, (pos, egg)) in world.ecs.query::<(&Position, &Egg)>().iter() {
+        assert_eq!(pos.x, egg.hatchling.position.x);
+        if egg.laid_tick < world.tick {
+            assert!(egg.incubation > 0.0);
+            incubating += 1;
+        }
+    }
+    assert!(incubating > 0);
+}
@@ -0,0 +1,55 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_lib::model::energy_ledger::EnergySource;
+
+#[tokio::test]
+async fn test_energy_ledger_balances_selected_entity() {
+    let herbivore = EntityBuilder::new()
+        .at(10.0, 10.0)
+        .energy(50.0)
+        .trophic(0.0)
+        .niche(0.5)
+        .build();
+    let id = herbivore.identity.id;
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| c.analytics.energy_ledger_ticks = 3)
+        .with_entity(herbivore)
+        .with_food(10.0, 10.0, 0.5)
+        .build();
+
+    world.track_energy(Some(id));
+    let start = world
+        .get_all_entities()
+        .iter()
+        .find(|e| e.identity.id == id)
+        .map(|e| e.metabolism.energy)
+        .expect("tracked entity exists");
+
+    world.update(&mut env).expect("Update failed");
+
+    let ledger = world.energy_ledger.as_ref().expect("ledger is active");
+    let end = world
+        .get_all_entities()
+        .iter()
+        .find(|e| e.identity.id == id)
+        .map(|e| e.metabolism.energy)
+        .expect("tracked entity survives");
+    assert!((ledger.net() - (end - start)).abs() < 1e-9);
+
+    let totals: std::collections::HashMap<_, _> = ledger.totals().into_iter().collect();
+    assert!(
+        totals[&EnergySource::Food] > 0.0,
+        "meal should be booked as food"
+    );
+    assert!(totals[&EnergySource::Basal] < 0.0);
+
+    for _ in 0..5 {
+        world.update(&mut env).expect("Update failed");
+    }
+    let ledger = world.energy_ledger.as_ref().expect("ledger is active");
+    assert!(ledger.entries().iter().all(|e| e.tick + 3 > world.tick));
+
+    world.track_energy(None);
+    assert!(world.energy_ledger.is_none());
+}
@@ -33,25 +33,82 @@ async fn test_era_transitions() {
     // Start at Primordial
     assert_eq!(env.current_era, Era::Primordial);
 
-    // Transition to DawnOfLife
+    // Transition to Expansion
     stats.avg_lifespan = 250.0;
     environment_system::update_era(&mut env, 6000, &stats, &config);
-    assert_eq!(env.current_era, Era::DawnOfLife);
+    assert_eq!(env.current_era, Era::Expansion);
 
-    // Transition to Flourishing
-    stats.population = 300;
-    stats.species_count = 5;
-    stats.biodiversity_hotspots = 2; // Required for new Flourishing trigger
-    environment_system::update_era(&mut env, 7000, &stats, &config);
-    assert_eq!(env.current_era, Era::Flourishing);
-
-    // Transition to DominanceWar (High Carbon Level)
+    // Transition to Strife (High Carbon Level)
     env.carbon_level = 900.0;
     environment_system::update_era(&mut env, 8000, &stats, &config);
-    assert_eq!(env.current_era, Era::DominanceWar);
+    assert_eq!(env.current_era, Era::Strife);
+
+    // Back to Expansion once the carbon has cleared
+    env.carbon_level = 500.0;
+    environment_system::update_era(&mut env, 8500, &stats, &config);
+    assert_eq!(env.current_era, Era::Expansion);
 
-    // Transition to ApexEra
+    // Transition to Enlightenment
     stats.top_fitness = 9000.0; // Comfortably above 8000
     environment_system::update_era(&mut env, 9000, &stats, &config);
-    assert_eq!(env.current_era, Era::ApexEra);
+    assert_eq!(env.current_era, Era::Enlightenment);
+}
+
+#[tokio::test]
+async fn test_era_modifiers_change_the_rules() {
+    let mut config = AppConfig::default();
+    config.eras.strife.food_richness = 0.5;
+    let mut env = Environment::default();
+    let baseline = env.food_spawn_multiplier();
+
+    env.current_era = Era::Expansion;
+    env.carbon_level = 900.0;
+    let entered = environment_system::update_era(&mut env, 1, &PopulationStats::default(), &config);
+    assert_eq!(entered, Some(Era::Strife));
+    assert_eq!(env.era_modifiers, config.eras.strife);
+    assert!((env.food_spawn_multiplier() - baseline * 0.5).abs() < 1e-9);
+
+    let mutation = primordium_core::systems::status::mutation_config(
+        &config,
+        &[],
+        config.eras.enlightenment.mutation_scale,
+    );
+    assert!(mutation.evolution.mutation_rate < config.evolution.mutation_rate);
+
+    // Saves from before the eras were reworked still load.
+    let old: Era = serde_json::from_str("\"DominanceWar\"").unwrap();
+    assert_eq!(old, Era::Strife);
+}
+
+#[tokio::test]
+async fn test_headless_runs_change_era_on_the_tick() {
+    use primordium_data::LiveEvent;
+    use primordium_lib::model::world::finalize::ERA_INTERVAL;
+    use primordium_lib::model::world::World;
+
+    let mut config = AppConfig::default();
+    config.world.deterministic = true;
+    config.world.seed = Some(5);
+    config.world.apex_fitness_req = -1.0;
+    let mut world = World::new(10, config.clone()).expect("Failed to create world");
+    let mut env = Environment::default();
+
+    let mut narrated = Vec::new();
+    for _ in 0..ERA_INTERVAL {
+        assert_eq!(env.current_era, Era::Primordial);
+        for event in world.update(&mut env).unwrap() {
+            if let LiveEvent::Narration { tick, text, .. } = event {
+                narrated.push((tick, text));
+            }
+        }
+    }
+    assert_eq!(env.current_era, Era::Enlightenment);
+    assert_eq!(env.era_modifiers, config.eras.enlightenment);
+    assert!(narrated
+        .iter()
+        .any(|(tick, text)| *tick == ERA_INTERVAL && text.contains("mutation x")));
+    assert!(world
+        .bookmarks
+        .iter()
+        .any(|b| b.note == "Era: Enlightenment"));
 }
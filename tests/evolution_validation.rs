@@ -17,6 +17,7 @@ async fn test_r_vs_k_dominance_in_resource_boom() {
             c.metabolism.maturity_age = 50;
             c.world.disaster_chance = 0.0;
             c.metabolism.metamorphosis_trigger_maturity = 0.1;
+            c.roads.enabled = false;
         })
         .with_memory(lid_r, "goal", 1.0)
         .with_memory(lid_k, "threat", 1.0);
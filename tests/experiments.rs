@@ -0,0 +1,197 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_core::config::AppConfig;
+use primordium_core::systems::status;
+use primordium_data::{Identity, Intel, Metabolism, StatusKind};
+use primordium_lib::model::experiment::{CohortModifiers, Experiment};
+use primordium_lib::model::persistence;
+use primordium_lib::model::world::World;
+use uuid::Uuid;
+
+fn treatment(mutation_rate: f32, metabolic_rate: f32) -> CohortModifiers {
+    CohortModifiers {
+        mutation_rate,
+        metabolic_rate,
+    }
+}
+
+fn trial(modifiers: CohortModifiers) -> Experiment {
+    Experiment::new("trial", 0)
+        .with_cohort("control", CohortModifiers::default())
+        .with_cohort("treatment", modifiers)
+}
+
+fn world_of(n: u128) -> World {
+    let mut builder = WorldBuilder::new().with_config(|c| {
+        c.world.deterministic = true;
+        // Neighbours of one tribe would otherwise share energy and breed
+        // through bonds, blurring the cohorts' costs and populations.
+        c.social.sharing_threshold = 1.0;
+    });
+    for i in 1..=n {
+        builder = builder.with_entity(
+            EntityBuilder::new()
+                .id(Uuid::from_u128(i))
+                .at(5.0 + 4.0 * i as f64, 10.0)
+                .energy(300.0)
+                .max_energy(400.0)
+                .build(),
+        );
+    }
+    builder.build().0
+}
+
+fn energy_of(world: &World, id: Uuid) -> f64 {
+    world
+        .ecs
+        .query::<(&Identity, &Metabolism)>()
+        .iter()
+        .find(|(_, (i, _))| i.id == id)
+        .map(|(_, (_, m))| m.energy)
+        .unwrap()
+}
+
+#[test]
+fn test_experiments_are_validated() {
+    let mut world = world_of(1);
+    let lonely = Experiment::new("solo", 0).with_cohort("control", CohortModifiers::default());
+    assert!(world.start_experiment(lonely).is_err());
+    let twins = Experiment::new("twins", 0)
+        .with_cohort("a", CohortModifiers::default())
+        .with_cohort("a", CohortModifiers::default());
+    assert!(world.start_experiment(twins).is_err());
+    assert!(world.start_experiment(trial(treatment(1.0, 0.0))).is_err());
+
+    world.tick = 40;
+    world.start_experiment(trial(treatment(2.0, 1.0))).unwrap();
+    assert_eq!(world.experiment.as_ref().unwrap().start_tick, 40);
+    assert!(world.start_experiment(trial(treatment(2.0, 1.0))).is_err());
+}
+
+#[test]
+fn test_split_and_reassign() {
+    let mut world = world_of(6);
+    world.start_experiment(trial(treatment(2.0, 1.0))).unwrap();
+    assert_eq!(world.split_population(), 6);
+    assert_eq!(world.entities_tagged("exp:trial:control").len(), 3);
+    assert_eq!(world.entities_tagged("exp:trial:treatment").len(), 3);
+
+    let moved = world.entities_tagged("exp:trial:control")[0];
+    assert!(world.assign_cohort(moved, "treatment"));
+    assert!(!world.assign_cohort(moved, "placebo"));
+    assert_eq!(world.tags_of(moved).system.len(), 1);
+    let experiment = world.experiment.as_ref().unwrap();
+    assert_eq!(experiment.cohorts[0].outcome.assigned, 2);
+    assert_eq!(experiment.cohorts[1].outcome.assigned, 4);
+
+    let report = world.stop_experiment().unwrap();
+    assert_eq!(report.cohorts.len(), 2);
+    assert!(world.entities_tagged("exp:trial:treatment").is_empty());
+    assert!(world.experiment.is_none());
+}
+
+#[test]
+fn test_treatment_modifies_members_only() {
+    let (control, treated) = (Uuid::from_u128(1), Uuid::from_u128(2));
+    let mut world = world_of(2);
+    // One brain for both, so only the treatment sets their costs apart.
+    let genotype = world
+        .ecs
+        .query::<(&Identity, &Intel)>()
+        .iter()
+        .find(|(_, (i, _))| i.id == control)
+        .map(|(_, (_, intel))| intel.genotype.clone())
+        .unwrap();
+    for (_, intel) in world.ecs.query_mut::<&mut Intel>() {
+        intel.genotype = genotype.clone();
+    }
+    let mut env = primordium_lib::model::environment::Environment::default();
+    world.start_experiment(trial(treatment(2.0, 3.0))).unwrap();
+    world.assign_cohort(control, "control");
+    world.assign_cohort(treated, "treatment");
+
+    world.update(&mut env).unwrap();
+    let control_cost = 300.0 - energy_of(&world, control);
+    let treated_cost = 300.0 - energy_of(&world, treated);
+    assert!(treated_cost > control_cost * 2.0);
+
+    let outcome = &world.experiment.as_ref().unwrap().cohorts[1].outcome;
+    assert_eq!((outcome.population, outcome.peak_population), (1, 1));
+
+    let config = AppConfig::default();
+    let effects = treatment(2.0, 3.0).effects();
+    assert_eq!(status::factor(&effects, StatusKind::MetabolicRate), 3.0);
+    assert_eq!(
+        status::mutation_config(&config, &effects, 1.0)
+            .evolution
+            .mutation_rate,
+        config.evolution.mutation_rate * 2.0
+    );
+    assert!(CohortModifiers::default().effects().is_empty());
+}
+
+#[test]
+fn test_offspring_join_and_deaths_count_against_cohorts() {
+    let parent = Uuid::from_u128(1);
+    let doomed = Uuid::from_u128(2);
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| {
+            c.world.deterministic = true;
+            c.eggs.enabled = false;
+            c.metabolism.maturity_age = 1;
+            c.metabolism.reproduction_threshold = 10.0;
+        })
+        .with_entity(
+            EntityBuilder::new()
+                .id(parent)
+                .at(20.0, 20.0)
+                .energy(800.0)
+                .max_energy(1000.0)
+                .build(),
+        )
+        .with_entity(
+            EntityBuilder::new()
+                .id(doomed)
+                .at(40.0, 40.0)
+                .energy(0.0)
+                .build(),
+        )
+        .build();
+    world.start_experiment(trial(treatment(2.0, 1.0))).unwrap();
+    world.assign_cohort(parent, "treatment");
+    world.assign_cohort(doomed, "control");
+
+    for _ in 0..20 {
+        world.update(&mut env).unwrap();
+        if world.get_population_count() > 1 {
+            break;
+        }
+    }
+    let members = world.entities_tagged("exp:trial:treatment");
+    assert!(members.len() > 1, "offspring should join the treatment");
+
+    let report = world.experiment_report().unwrap();
+    let (control, treated) = (&report.cohorts[0], &report.cohorts[1]);
+    assert_eq!(control.outcome.deaths, 1);
+    assert_eq!(control.population_ratio, None);
+    assert_eq!(treated.outcome.births as usize, members.len() - 1);
+    assert!(report.to_string().contains("treatment"));
+}
+
+#[test]
+fn test_experiment_survives_save_and_load() {
+    let mut world = world_of(4);
+    world.start_experiment(trial(treatment(2.0, 1.0))).unwrap();
+    world.split_population();
+
+    let path = std::env::temp_dir().join(format!("experiment_{}.json", Uuid::new_v4()));
+    persistence::save_world(&mut world, &path).unwrap();
+    let loaded = persistence::load_world(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(loaded.experiment, world.experiment);
+    assert_eq!(
+        loaded.entities_tagged("exp:trial:treatment"),
+        world.entities_tagged("exp:trial:treatment")
+    );
+}
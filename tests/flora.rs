@@ -0,0 +1,124 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_data::{Food, PlantGenome, TerrainType};
+use std::sync::Arc;
+
+/// Plains around (20, 20), so seeds never land on rock.
+fn meadow(mut builder: WorldBuilder) -> WorldBuilder {
+    for x in 14..=26 {
+        for y in 14..=26 {
+            builder = builder.with_terrain(x, y, TerrainType::Plains);
+        }
+    }
+    builder
+}
+
+#[tokio::test]
+async fn test_plants_seed_nearby_fertile_cells_with_mutated_genomes() {
+    let parent = PlantGenome {
+        toxicity: 0.3,
+        nutrient_type: 0.5,
+        regrowth: 1.0,
+    };
+    let builder = WorldBuilder::new()
+        .with_seed(11)
+        .with_config(|c| {
+            c.ecosystem.base_spawn_chance = 0.0;
+            c.flora.seed_chance = 1.0;
+            c.flora.toxicity_cost = 0.0;
+            c.flora.mutation_rate = 1.0;
+            c.flora.mutation_amount = 0.1;
+        })
+        .with_fertility(1.0)
+        .with_plant(20.0, 20.0, parent);
+    let (mut world, mut env) = meadow(builder).build();
+
+    for _ in 0..3 {
+        world.update(&mut env).expect("Update failed");
+    }
+
+    let plants: Vec<Food> = world
+        .ecs
+        .query::<&Food>()
+        .iter()
+        .map(|(_, f)| f.clone())
+        .collect();
+    assert!(plants.len() > 1, "the parent plant never seeded");
+    assert_eq!(world.get_food_count(), plants.len());
+    // Three generations of seeding with a radius of two.
+    assert!(plants
+        .iter()
+        .all(|f| f.x.abs_diff(20) <= 6 && f.y.abs_diff(20) <= 6));
+    assert!(plants.iter().any(|f| f.genome() != parent));
+}
+
+#[tokio::test]
+async fn test_barren_soil_stops_plants_seeding() {
+    let builder = WorldBuilder::new()
+        .with_config(|c| {
+            c.ecosystem.base_spawn_chance = 0.0;
+            c.flora.seed_chance = 1.0;
+        })
+        .with_fertility(0.0)
+        .with_food(20.0, 20.0, 0.5);
+    let (mut world, mut env) = meadow(builder).build();
+
+    for _ in 0..5 {
+        world.update(&mut env).expect("Update failed");
+    }
+    assert_eq!(world.ecs.query::<&Food>().iter().count(), 1);
+}
+
+/// Energy of a rooted grazer of `niche` after one tick next to a plant of
+/// nutrient type 0.0 and `toxicity`.
+fn energy_after_meal(niche: f32, toxicity: f32) -> f64 {
+    let mut grazer = EntityBuilder::new()
+        .at(10.0, 10.0)
+        .energy(100.0)
+        .max_energy(500.0)
+        .trophic(0.0)
+        .niche(niche)
+        .build();
+    grazer.physics.max_speed = 0.0;
+    Arc::make_mut(&mut grazer.intel.genotype).max_speed = 0.0;
+    let id = grazer.identity.id;
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_seed(5)
+        .with_config(|c| {
+            c.ecosystem.base_spawn_chance = 0.0;
+            c.flora.seed_chance = 0.0;
+        })
+        .with_entity(grazer)
+        .with_plant(
+            10.0,
+            10.0,
+            PlantGenome {
+                toxicity,
+                ..PlantGenome::wild(0.0)
+            },
+        )
+        .build();
+
+    world.update(&mut env).expect("Update failed");
+    assert_eq!(world.get_food_count(), 0, "the plant was not eaten");
+    world
+        .get_all_entities()
+        .iter()
+        .find(|e| e.identity.id == id)
+        .map(|e| e.metabolism.energy)
+        .expect("grazer survives")
+}
+
+#[tokio::test]
+async fn test_toxic_plants_poison_off_niche_grazers_only() {
+    let toxin_damage = primordium_lib::model::config::FloraConfig::default().toxin_damage;
+
+    // Half a niche away, a fully toxic plant costs half the toxin damage.
+    let wild = energy_after_meal(0.5, 0.0);
+    let toxic = energy_after_meal(0.5, 1.0);
+    assert!((wild - toxic - toxin_damage * 0.5).abs() < 1e-6);
+
+    // A matched specialist digests the toxin.
+    assert_eq!(energy_after_meal(0.0, 1.0), energy_after_meal(0.0, 0.0));
+}
@@ -0,0 +1,53 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_lib::model::forecast::MIN_SAMPLES;
+
+#[test]
+fn test_world_samples_history_and_projects_it_with_bands() {
+    let mut builder = WorldBuilder::new().with_config(|c| {
+        c.forecast.sample_interval = 5;
+        c.forecast.window = 12;
+        c.forecast.horizon = 100;
+        // Far more food than any world holds, so every forecast is a famine.
+        c.forecast.famine_food_per_head = 1e6;
+    });
+    for i in 0..40 {
+        builder = builder.with_entity(
+            EntityBuilder::new()
+                .at(5.0 + (i % 8) as f64 * 10.0, 5.0 + (i / 8) as f64 * 10.0)
+                .energy(150.0)
+                .build(),
+        );
+    }
+    let (mut world, mut env) = builder.build();
+
+    for _ in 0..5 * (MIN_SAMPLES as u64 - 1) {
+        world.update(&mut env).unwrap();
+    }
+    assert_eq!(world.forecaster.samples.len(), MIN_SAMPLES - 1);
+    assert!(world.forecaster.latest.is_none());
+
+    for _ in 0..40 {
+        world.update(&mut env).unwrap();
+    }
+    let samples = &world.forecaster.samples;
+    assert_eq!(samples.len(), 12);
+    assert!(samples.iter().all(|s| s.tick % 5 == 0));
+    let last = samples.back().unwrap();
+    assert_eq!(last.tick, world.tick);
+    assert_eq!(last.population, world.get_population_count() as f64);
+    assert_eq!(last.food, world.get_food_count() as f64);
+
+    let forecast = world.forecaster.latest.as_ref().unwrap();
+    assert_eq!(forecast.tick, world.tick);
+    for projection in [&forecast.population, &forecast.food, &forecast.fertility] {
+        assert_eq!(projection.points.len(), 20);
+        for (h, point) in projection.points.iter().enumerate() {
+            assert_eq!(point.tick, world.tick + 5 * (h as u64 + 1));
+            assert!(point.low <= point.mean && point.mean <= point.high);
+        }
+    }
+    let famine = forecast.famine.unwrap();
+    assert!(famine.likely);
+    assert_eq!(famine.tick, world.tick + 5);
+}
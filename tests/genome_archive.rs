@@ -0,0 +1,92 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_data::Identity;
+use primordium_io::genome_archive::{ArchivedGenome, GenomeArchive};
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::world::World;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+fn living_genomes(world: &World) -> Vec<ArchivedGenome> {
+    world
+        .get_all_entities()
+        .into_iter()
+        .map(|e| ArchivedGenome {
+            id: e.identity.id,
+            parent_id: e.identity.parent_id,
+            generation: e.metabolism.generation,
+            genotype: (*e.intel.genotype).clone(),
+        })
+        .collect()
+}
+
+fn ids(world: &World) -> HashSet<Uuid> {
+    world
+        .ecs
+        .query::<&Identity>()
+        .iter()
+        .map(|(_, i)| i.id)
+        .collect()
+}
+
+#[test]
+fn test_archive_roundtrip_and_reseed() {
+    let dir = std::env::temp_dir().join(format!("genomes_{}", Uuid::new_v4()));
+    let archive = GenomeArchive::new(&dir);
+    assert!(archive.index().unwrap().is_empty());
+
+    let mut builder = WorldBuilder::new();
+    for i in 0..4 {
+        builder = builder.with_entity(EntityBuilder::new().at(10.0 + 5.0 * i as f64, 10.0).build());
+    }
+    let (source, _) = builder.build();
+    let genomes = living_genomes(&source);
+    archive.write(10, &genomes[..2]).unwrap();
+    let entry = archive.write(20, &genomes).unwrap();
+    assert_eq!(entry.population, 4);
+    assert_eq!(entry.lineages, 4);
+    assert_eq!(archive.index().unwrap().len(), 2);
+
+    let (at_15, loaded) = archive.load_at_or_before(15).unwrap();
+    assert_eq!(at_15.tick, 10);
+    assert_eq!(loaded.len(), 2);
+    assert!(archive.load_at_or_before(5).is_err());
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_entity(EntityBuilder::new().at(30.0, 30.0).build())
+        .build();
+    let entry = world
+        .reseed_from_archive(dir.to_str().unwrap(), 100)
+        .unwrap();
+    assert_eq!(entry.tick, 20);
+    assert_eq!(ids(&world), ids(&source));
+    world.update(&mut env).unwrap();
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_world_dumps_genomes_every_interval() {
+    let dir = std::env::temp_dir().join(format!("genomes_{}", Uuid::new_v4()));
+    let logs = std::env::temp_dir().join(format!("logs_{}", Uuid::new_v4()));
+    let mut config = AppConfig::default();
+    config.archive.genome_interval = 2;
+    config.archive.dir = dir.to_str().unwrap().to_string();
+    let mut world = World::new_at(5, config, logs.to_str().unwrap()).unwrap();
+    let mut env = primordium_lib::model::environment::Environment::default();
+    for _ in 0..4 {
+        world.update(&mut env).unwrap();
+    }
+
+    let archive = GenomeArchive::new(&dir);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while archive.index().unwrap().len() < 2 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    let ticks: Vec<u64> = archive.index().unwrap().iter().map(|e| e.tick).collect();
+    assert_eq!(ticks, vec![2, 4]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = std::fs::remove_dir_all(&logs);
+}
@@ -0,0 +1,272 @@
+//! Golden-file regression suite.
+//!
+//! Each scenario runs seeded, deterministic worlds for thousands of ticks and
+//! records checkpoints (population, food and the world's deterministic hash)
+//! that are compared with `tests/golden/<scenario>_seed<seed>.golden`. Any
+//! change to simulation behaviour moves the hashes and fails here with the
+//! checkpoints that changed. When the change is intended, bless the new
+//! goldens and commit them together with it:
+//!
+//! ```bash
+//! PRIMORDIUM_BLESS=1 cargo test --test golden
+//! ```
+
+use primordium_io::persistence::snapshot_version;
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::environment::{ClimateState, Environment};
+use primordium_lib::model::lifecycle;
+use primordium_lib::model::persistence::{
+    decode_world, encode_world_snapshot, SNAPSHOT_SCHEMA_VERSION,
+};
+use primordium_lib::model::world::scenario::ScenarioEvent;
+use primordium_lib::model::world::World;
+use primordium_net::NetMessage;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const BLESS_VAR: &str = "PRIMORDIUM_BLESS";
+const SEEDS: [u64; 2] = [7, 42];
+
+/// Checkpoints of one scenario run, checked against its golden file.
+struct Golden {
+    scenario: &'static str,
+    seed: u64,
+    checkpoints: Vec<String>,
+}
+
+impl Golden {
+    fn new(scenario: &'static str, seed: u64) -> Self {
+        Self {
+            scenario,
+            seed,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(format!("{}_seed{}.golden", self.scenario, self.seed))
+    }
+
+    fn checkpoint(&mut self, label: &str, world: &World, env: &Environment) {
+        self.checkpoints.push(format!(
+            "{} tick={} population={} food={} hash={}",
+            label,
+            world.tick,
+            world.get_population_count(),
+            world.get_food_count(),
+            world.deterministic_hash(env)
+        ));
+    }
+
+    /// Writes the golden file when blessing, otherwise fails on any
+    /// checkpoint that differs from it.
+    fn verify(self) {
+        let path = self.path();
+        if std::env::var_os(BLESS_VAR).is_some() {
+            let mut content = format!(
+                "# Golden checkpoints of the `{}` scenario, seed {}.\n\
+                 # Bless with: {}=1 cargo test --test golden\n",
+                self.scenario, self.seed, BLESS_VAR
+            );
+            for line in &self.checkpoints {
+                content.push_str(line);
+                content.push('\n');
+            }
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, content).unwrap();
+            return;
+        }
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            panic!(
+                "No golden file at {}; create it with {}=1 cargo test --test golden",
+                path.display(),
+                BLESS_VAR
+            );
+        };
+        let expected: Vec<&str> = content
+            .lines()
+            .filter(|l| !l.starts_with('#') && !l.trim().is_empty())
+            .collect();
+        let mut changes = Vec::new();
+        for i in 0..expected.len().max(self.checkpoints.len()) {
+            let (want, got) = (
+                expected.get(i).copied().unwrap_or("<none>"),
+                self.checkpoints.get(i).map_or("<none>", String::as_str),
+            );
+            if want != got {
+                changes.push(format!("  expected {want}\n  actual   {got}"));
+            }
+        }
+        assert!(
+            changes.is_empty(),
+            "Simulation behaviour changed in scenario `{}` (seed {}):\n{}\n\
+             If the change is intended, bless the new goldens with \
+             `{}=1 cargo test --test golden` and commit them.",
+            self.scenario,
+            self.seed,
+            changes.join("\n"),
+            BLESS_VAR
+        );
+    }
+}
+
+fn config(seed: u64) -> AppConfig {
+    let mut config = AppConfig::default();
+    config.world.width = 48;
+    config.world.height = 48;
+    config.world.initial_food = 60;
+    config.world.seed = Some(seed);
+    config.world.deterministic = true;
+    config
+}
+
+/// Ticks between founder waves.
+const WAVE_TICKS: u64 = 100;
+
+/// Waves between checkpoints.
+const WAVES_PER_CHECKPOINT: u64 = 5;
+
+fn script(world: &mut World, env: &mut Environment, event: &ScenarioEvent) {
+    world
+        .apply_scenario_event(env, event)
+        .expect("Failed to apply scenario event");
+}
+
+/// Settles a wave of 16 founders of one lineage, seeded from the scenario
+/// seed and the wave, then runs the world until the next wave is due.
+/// Unevolved founders rarely outlive a few hundred ticks, so scenarios
+/// settle a wave every [`WAVE_TICKS`] to keep the worlds populated.
+fn wave(world: &mut World, env: &mut Environment, seed: u64) {
+    let wave = world.tick / WAVE_TICKS;
+    let mut rng = StdRng::seed_from_u64(seed.wrapping_mul(1000).wrapping_add(wave));
+    let founder = primordium_lib::model::brain::create_genotype_random_with_rng(&mut rng);
+    script(
+        world,
+        env,
+        &ScenarioEvent::Spawn {
+            dna: founder.to_hex(),
+            count: 16,
+            x: None,
+            y: None,
+        },
+    );
+    for _ in 0..WAVE_TICKS {
+        world.update(env).expect("Update failed");
+    }
+}
+
+fn world(seed: u64) -> (World, Environment) {
+    let world = World::new(0, config(seed)).expect("Failed to create world");
+    (world, Environment::default())
+}
+
+/// Evolves a world through a food boom, a heat wave and its recovery.
+fn evolve(seed: u64) {
+    let mut golden = Golden::new("evolve", seed);
+    let (mut world, mut env) = world(seed);
+    let script_at = |wave: u64| match wave {
+        5 => Some(ScenarioEvent::FoodBoom { amount: 60 }),
+        10 => Some(ScenarioEvent::Climate {
+            state: Some(ClimateState::Hot),
+        }),
+        15 => Some(ScenarioEvent::Climate { state: None }),
+        _ => None,
+    };
+    for n in 0..4 * WAVES_PER_CHECKPOINT {
+        if let Some(event) = script_at(n) {
+            script(&mut world, &mut env, &event);
+        }
+        wave(&mut world, &mut env, seed);
+        if (n + 1) % WAVES_PER_CHECKPOINT == 0 {
+            golden.checkpoint("world", &world, &env);
+        }
+    }
+    golden.verify();
+}
+
+/// Evolves a world, saves it as a binary snapshot and loads it back, sends
+/// its oldest organism through a relay message into a second world and
+/// evolves both.
+fn roundtrip(seed: u64) {
+    let mut golden = Golden::new("roundtrip", seed);
+    let (mut origin, mut env) = world(seed);
+    for _ in 0..2 * WAVES_PER_CHECKPOINT {
+        wave(&mut origin, &mut env, seed);
+    }
+    golden.checkpoint("origin", &origin, &env);
+
+    let bytes = encode_world_snapshot(&mut origin).expect("Failed to save world");
+    assert_eq!(snapshot_version(&bytes), Some(SNAPSHOT_SCHEMA_VERSION));
+    let mut loaded = decode_world(&bytes).expect("Failed to load world");
+    loaded.post_load();
+    assert_eq!(
+        loaded.deterministic_hash(&env),
+        origin.deterministic_hash(&env),
+        "the loaded world differs from the saved one"
+    );
+    golden.checkpoint("loaded", &loaded, &env);
+
+    let (mut colony, mut colony_env) = world(seed + 1);
+    let mut settlers = loaded.get_all_entities();
+    settlers.sort_by_key(|e| (e.metabolism.birth_tick, e.identity.id));
+    let settler = settlers.first().expect("the origin world died out");
+    {
+        let message = NetMessage::migrate_entity(
+            Uuid::nil(),
+            settler.intel.genotype.to_hex(),
+            settler.metabolism.energy as f32,
+            settler.metabolism.generation,
+            lifecycle::get_name_components(&settler.identity.id, &settler.metabolism),
+            loaded.config.fingerprint(),
+        );
+        let wire = serde_json::to_string(&message).expect("Failed to encode message");
+        let NetMessage::MigrateEntity {
+            dna,
+            energy,
+            generation,
+            fingerprint,
+            checksum,
+            ..
+        } = serde_json::from_str(&wire).expect("Failed to decode message")
+        else {
+            panic!("the relay changed the message kind");
+        };
+        colony
+            .import_migrant(dna, energy, generation, &fingerprint, &checksum)
+            .expect("Failed to import migrant");
+    }
+    golden.checkpoint("colony", &colony, &colony_env);
+
+    for _ in 0..2 * WAVES_PER_CHECKPOINT {
+        wave(&mut loaded, &mut env, seed);
+        wave(&mut colony, &mut colony_env, seed + 1);
+    }
+    golden.checkpoint("origin", &loaded, &env);
+    golden.checkpoint("colony", &colony, &colony_env);
+    golden.verify();
+}
+
+#[tokio::test]
+async fn test_golden_evolve_seed_7() {
+    evolve(SEEDS[0]);
+}
+
+#[tokio::test]
+async fn test_golden_evolve_seed_42() {
+    evolve(SEEDS[1]);
+}
+
+#[tokio::test]
+async fn test_golden_roundtrip_seed_7() {
+    roundtrip(SEEDS[0]);
+}
+
+#[tokio::test]
+async fn test_golden_roundtrip_seed_42() {
+    roundtrip(SEEDS[1]);
+}
@@ -0,0 +1,99 @@
+mod common;
+use common::{EntityBuilder, TestBehavior, WorldBuilder};
+use primordium_core::systems::{biological, status};
+use primordium_data::{Health, Metabolism, Pathogen, StatusKind};
+use primordium_lib::model::interaction::InteractionKind;
+use uuid::Uuid;
+
+const GROOMER: Uuid = Uuid::from_u128(1);
+const PATIENT: Uuid = Uuid::from_u128(2);
+
+fn infected_patient() -> primordium_data::Entity {
+    let mut patient = EntityBuilder::new()
+        .id(PATIENT)
+        .at(10.5, 10.0)
+        .energy(200.0)
+        .max_energy(500.0)
+        .color(100, 100, 100)
+        .trophic(0.0)
+        .build();
+    let pathogen = Pathogen {
+        id: Uuid::from_u128(9),
+        lethality: 0.0,
+        transmission: 0.0,
+        duration: 200,
+        virulence: 0.0,
+        behavior_manipulation: None,
+    };
+    biological::infect_components(&mut patient.health, &pathogen);
+    patient
+}
+
+fn run(grooming: bool, ticks: usize) -> primordium_lib::model::world::World {
+    let groomer = EntityBuilder::new()
+        .id(GROOMER)
+        .at(10.0, 10.0)
+        .energy(200.0)
+        .max_energy(500.0)
+        .color(100, 100, 100)
+        .trophic(0.0)
+        .with_behavior(TestBehavior::Altruist)
+        .build();
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| {
+            c.world.deterministic = true;
+            c.grooming.enabled = grooming;
+            c.analytics.record_interactions = true;
+        })
+        .with_entity(groomer)
+        .with_entity(infected_patient())
+        .build();
+    for _ in 0..ticks {
+        world.update(&mut env).unwrap();
+    }
+    world
+}
+
+fn infection_left(world: &primordium_lib::model::world::World) -> u32 {
+    world
+        .ecs
+        .query::<(&primordium_data::Identity, &Health)>()
+        .iter()
+        .find(|(_, (id, _))| id.id == PATIENT)
+        .map(|(_, (_, h))| status::remaining(&h.effects, StatusKind::Infection))
+        .unwrap()
+}
+
+fn groomer_energy(world: &primordium_lib::model::world::World) -> f64 {
+    world
+        .ecs
+        .query::<(&primordium_data::Identity, &Metabolism)>()
+        .iter()
+        .find(|(_, (id, _))| id.id == GROOMER)
+        .map(|(_, (_, m))| m.energy)
+        .unwrap()
+}
+
+#[test]
+fn test_grooming_shortens_a_tribe_mates_infection() {
+    let groomed = run(true, 5);
+    let ungroomed = run(false, 5);
+
+    assert!(infection_left(&groomed) + 20 <= infection_left(&ungroomed));
+    assert!(groomer_energy(&groomed) < groomer_energy(&ungroomed));
+}
+
+#[test]
+fn test_grooming_network_is_recorded() {
+    let world = run(true, 3);
+    let edge = world
+        .interaction_graph
+        .edges()
+        .find(|&(from, to, kind, _)| {
+            from == GROOMER && to == PATIENT && kind == InteractionKind::Groom
+        })
+        .map(|(_, _, _, edge)| edge)
+        .expect("grooming should be recorded");
+    assert!(edge.count >= 2);
+    assert!(world.interaction_graph.to_csv().contains("groom"));
+}
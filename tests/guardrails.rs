@@ -0,0 +1,74 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_data::LiveEvent;
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::guardrail::BreakerKind;
+use primordium_lib::model::state::environment::Environment;
+use primordium_lib::model::world::World;
+
+fn crowded_world(configure: impl FnOnce(&mut AppConfig)) -> (World, Environment) {
+    let mut builder = WorldBuilder::new().with_seed(42).with_config(configure);
+    for i in 0..20 {
+        builder = builder.with_entity(
+            EntityBuilder::new()
+                .at(5.0 + i as f64, 5.0)
+                .energy(100.0)
+                .build(),
+        );
+    }
+    builder.build()
+}
+
+#[tokio::test]
+async fn test_entity_breaker_halts_world() {
+    let (mut world, mut env) = crowded_world(|c| {
+        c.guardrail.max_entities = 10;
+    });
+
+    let events = world.update(&mut env).unwrap();
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, LiveEvent::CircuitBreaker { .. })));
+
+    let trip = world.guardrail_trip.clone().expect("breaker should trip");
+    assert_eq!(trip.kind, BreakerKind::EntityCount);
+    assert_eq!(trip.limit, 10);
+    assert!(!trip.suspected_source.is_empty());
+
+    let frozen_tick = world.tick;
+    for _ in 0..5 {
+        assert!(world.update(&mut env).unwrap().is_empty());
+    }
+    assert_eq!(world.tick, frozen_tick, "tripped world must not advance");
+}
+
+#[tokio::test]
+async fn test_acknowledge_doubles_limit_and_resumes() {
+    let (mut world, mut env) = crowded_world(|c| {
+        c.guardrail.max_entities = 10;
+    });
+
+    world.update(&mut env).unwrap();
+    assert!(world.guardrail_trip.is_some());
+
+    world.acknowledge_guardrail();
+    assert!(world.guardrail_trip.is_none());
+    assert_eq!(world.config.guardrail.max_entities, 20);
+
+    let tick = world.tick;
+    world.update(&mut env).unwrap();
+    assert_eq!(world.tick, tick + 1);
+}
+
+#[tokio::test]
+async fn test_disabled_guardrail_never_halts() {
+    let (mut world, mut env) = crowded_world(|c| {
+        c.guardrail.enabled = false;
+        c.guardrail.max_entities = 1;
+    });
+
+    for _ in 0..3 {
+        world.update(&mut env).unwrap();
+    }
+    assert!(world.guardrail_trip.is_none());
+}
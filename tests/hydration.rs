@@ -0,0 +1,88 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_data::{DeathCause, Entity, LiveEvent, TerrainType};
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn rooted(id: u128, x: f64, y: f64, hydration: f32) -> Entity {
+    let mut e = EntityBuilder::new()
+        .id(Uuid::from_u128(id))
+        .at(x, y)
+        .energy(5000.0)
+        .max_energy(10000.0)
+        .build();
+    e.metabolism.hydration = hydration;
+    e.physics.max_speed = 0.0;
+    Arc::make_mut(&mut e.intel.genotype).max_speed = 0.0;
+    e
+}
+
+#[tokio::test]
+async fn test_organisms_die_of_dehydration_in_the_desert() {
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| c.hydration.thirst = 0.3)
+        .with_terrain(10, 10, TerrainType::Desert)
+        .with_entity(rooted(1, 10.5, 10.5, 1.0))
+        .build();
+
+    let mut cause = None;
+    for _ in 0..10 {
+        for event in world.update(&mut env).expect("Update failed") {
+            if let LiveEvent::Death { cause: c, .. } = event {
+                cause = Some(c);
+            }
+        }
+    }
+
+    assert_eq!(world.get_population_count(), 0);
+    assert_eq!(cause.as_deref(), Some(DeathCause::Dehydration.label()));
+}
+
+#[tokio::test]
+async fn test_rivers_quench_thirst_and_are_sensed() {
+    let id = Uuid::from_u128(2);
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_terrain(20, 20, TerrainType::River)
+        .with_entity(rooted(2, 20.5, 20.5, 0.5))
+        .build();
+
+    for _ in 0..5 {
+        world.update(&mut env).expect("Update failed");
+    }
+
+    let hydration = world
+        .get_all_entities()
+        .iter()
+        .find(|e| e.identity.id == id)
+        .map(|e| e.metabolism.hydration)
+        .expect("drinker survives");
+    assert!(hydration > 0.7, "Hydration was {}", hydration);
+    assert_eq!(world.terrain.water_at(20.5, 20.5), 1.0);
+
+    let perception = world.perception_of(id, &env).expect("entity alive");
+    assert_eq!(perception.input("Water"), 1.0);
+}
+
+#[tokio::test]
+async fn test_disabled_hydration_keeps_organisms_watered() {
+    let id = Uuid::from_u128(3);
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| {
+            c.hydration.enabled = false;
+            c.hydration.thirst = 0.3;
+        })
+        .with_terrain(10, 10, TerrainType::Desert)
+        .with_entity(rooted(3, 10.5, 10.5, 0.8))
+        .build();
+
+    for _ in 0..10 {
+        world.update(&mut env).expect("Update failed");
+    }
+
+    let hydration = world
+        .get_all_entities()
+        .iter()
+        .find(|e| e.identity.id == id)
+        .map(|e| e.metabolism.hydration);
+    assert_eq!(hydration, Some(0.8));
+}
@@ -0,0 +1,52 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+
+#[tokio::test]
+async fn test_recorder_reports_eat_rate() {
+    let herbivore = EntityBuilder::new()
+        .at(10.0, 10.0)
+        .energy(50.0)
+        .trophic(0.0)
+        .niche(0.5)
+        .build();
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| {
+            c.analytics.record_interactions = true;
+            c.analytics.interaction_window = 4;
+        })
+        .with_entity(herbivore)
+        .with_food(10.0, 10.0, 0.5)
+        .build();
+
+    world.update(&mut env).expect("Update failed");
+    assert_eq!(world.interaction_recorder.latest().eats, 1);
+    assert!((world.pop_stats.interaction_rates.eats - 1.0).abs() < f32::EPSILON);
+
+    for _ in 0..3 {
+        world.update(&mut env).expect("Update failed");
+    }
+    assert!(
+        world.pop_stats.interaction_rates.eats <= 0.25 + f32::EPSILON,
+        "rate should decay once food runs out"
+    );
+}
+
+#[tokio::test]
+async fn test_recorder_disabled_by_default() {
+    let herbivore = EntityBuilder::new()
+        .at(10.0, 10.0)
+        .energy(50.0)
+        .trophic(0.0)
+        .niche(0.5)
+        .build();
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_entity(herbivore)
+        .with_food(10.0, 10.0, 0.5)
+        .build();
+
+    world.update(&mut env).expect("Update failed");
+    assert_eq!(world.interaction_recorder.latest().eats, 0);
+    assert_eq!(world.pop_stats.interaction_rates.eats, 0.0);
+}
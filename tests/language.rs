@@ -1,3 +1,4 @@
+use primordium_core::brain::BrainLogic;
 use primordium_core::systems::action::{action_system, ActionContext, ActionOutput};
 use primordium_lib::model::config::AppConfig;
 use primordium_lib::model::state::environment::Environment;
@@ -13,17 +14,21 @@ async fn test_semantic_pheromone_roundtrip() {
     // 1. Entity A: Emits Signal A
     let mut e_emitter = primordium_lib::model::lifecycle::create_entity(10.0, 10.0, 0);
     // [movX, movY, speed, aggro, share, color, emitA, emitB, bond, dig, build, overmind]
-    let outputs = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+    let outputs = [
+        0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ];
 
     let mut ctx = ActionContext {
         env: &env,
         config: &config,
         terrain: &world.terrain,
         influence: &world.influence,
+        roads: &world.roads,
         snapshots: &[],
         entity_id_map: &std::collections::HashMap::new(),
         spatial_hash: &primordium_lib::model::spatial_hash::SpatialHash::new(5.0, 100, 100),
         pressure: &world.pressure,
+        pheromones: &world.pheromones,
         width: 100,
         height: 100,
     };
@@ -33,7 +38,8 @@ async fn test_semantic_pheromone_roundtrip() {
         out
     };
     for d in res.pheromones {
-        std::sync::Arc::make_mut(&mut world.pheromones).deposit(d.x, d.y, d.ptype, d.amount);
+        std::sync::Arc::make_mut(&mut world.pheromones)
+            .deposit_channel(d.x, d.y, d.channel, d.amount);
     }
     for d in res.sounds {
         std::sync::Arc::make_mut(&mut world.sound).deposit(d.x, d.y, d.amount);
@@ -42,12 +48,157 @@ async fn test_semantic_pheromone_roundtrip() {
     std::sync::Arc::make_mut(&mut world.sound).update();
 
     // 2. Verify Signal A is in the grid
-    let cell = world.pheromones.get_cell(10, 10);
-    assert!(cell.sig_a_strength > 0.4);
-    assert_eq!(cell.sig_b_strength, 0.0);
+    let signal_a = world.pheromones.channel("signal_a");
+    let signal_b = world.pheromones.channel("signal_b");
+    assert!(world.pheromones.strength(10, 10, signal_a.unwrap()) > 0.4);
+    assert_eq!(world.pheromones.strength(10, 10, signal_b.unwrap()), 0.0);
 
-    // 3. Verify sense_all logic used in world.rs
-    let (_, _, sa, sb) = world.pheromones.sense_all(10.0, 10.0, 1.0);
+    // 3. Verify the sensing used by the perception system
+    let [sa, sb] = world
+        .pheromones
+        .sense_channels(10.0, 10.0, 1.0, [signal_a, signal_b]);
     assert!(sa > 0.0);
     assert_eq!(sb, 0.0);
 }
+
+#[test]
+fn test_deceptive_trail_costs_energy_and_marks_food() {
+    let config = AppConfig::default();
+    let world = World::new(0, config.clone()).unwrap();
+    let env = Environment::default();
+    let mut ctx = ActionContext {
+        env: &env,
+        config: &config,
+        terrain: &world.terrain,
+        influence: &world.influence,
+        roads: &world.roads,
+        snapshots: &[],
+        entity_id_map: &std::collections::HashMap::new(),
+        spatial_hash: &primordium_lib::model::spatial_hash::SpatialHash::new(5.0, 100, 100),
+        pressure: &world.pressure,
+        pheromones: &world.pheromones,
+        width: 100,
+        height: 100,
+    };
+
+    let mut honest = primordium_lib::model::lifecycle::create_entity(10.0, 10.0, 0);
+    let mut liar = honest.clone();
+    let mut outputs = [0.0; 15];
+    let mut honest_out = ActionOutput::default();
+    action_system(&mut honest, outputs, &mut ctx, &mut honest_out);
+    outputs[14] = 1.0;
+    let mut liar_out = ActionOutput::default();
+    action_system(&mut liar, outputs, &mut ctx, &mut liar_out);
+
+    assert!(honest_out.pheromones.is_empty());
+    assert_eq!(honest.intel.deceptions, 0);
+    assert_eq!(liar.intel.deceptions, 1);
+    let trail = &liar_out.pheromones[0];
+    assert_eq!(
+        Some(trail.channel),
+        world
+            .pheromones
+            .channel_of(primordium_core::pheromone::PheromoneType::Food)
+    );
+    assert_eq!(trail.amount, config.social.deception_strength);
+    assert!(
+        (honest.metabolism.energy - liar.metabolism.energy - config.social.deception_cost).abs()
+            < 1e-9
+    );
+}
+
+#[test]
+fn test_skeptics_ignore_food_trails_and_deception_is_tracked_per_lineage() {
+    use primordium_lib::model::brain::BRAIN_INPUTS;
+
+    let mut world = World::new(0, AppConfig::default()).unwrap();
+    let mut env = Environment::default();
+    let deceive = BRAIN_INPUTS + 14;
+    // Both lie whenever they sense a food trail, but only one trusts trails.
+    let mut lineages = Vec::new();
+    for (x, skepticism) in [(10.0, 0.0), (40.0, 1.0)] {
+        let mut entity = primordium_lib::model::lifecycle::create_entity(x, 10.0, 0);
+        let genotype = std::sync::Arc::make_mut(&mut entity.intel.genotype);
+        genotype.skepticism = skepticism;
+        genotype.brain.connections.retain(|c| c.to != deceive);
+        genotype
+            .brain
+            .connections
+            .push(primordium_data::Connection {
+                from: 4,
+                to: deceive,
+                weight: 20.0,
+                enabled: true,
+                innovation: primordium_lib::model::brain::topology::get_innovation_id(4, deceive),
+            });
+        genotype.brain.initialize_node_idx_map();
+        entity.metabolism.energy = entity.metabolism.max_energy;
+        lineages.push(entity.metabolism.lineage_id);
+        let pheromones = std::sync::Arc::make_mut(&mut world.pheromones);
+        for dx in -3..=3 {
+            for dy in -3..=3 {
+                pheromones.deposit(
+                    x + f64::from(dx),
+                    10.0 + f64::from(dy),
+                    primordium_core::pheromone::PheromoneType::Food,
+                    1.0,
+                );
+            }
+        }
+        world.spawn_entity(entity);
+    }
+    std::sync::Arc::make_mut(&mut world.pheromones).update();
+
+    while world.tick < 20 {
+        world.update(&mut env).unwrap();
+    }
+    world.refresh_pop_stats(&env);
+    let stats = &world.pop_stats;
+    assert!(stats.lineage_deception_rate[&lineages[0]] > 0.5);
+    assert_eq!(stats.lineage_deception_rate[&lineages[1]], 0.0);
+    assert!(stats.deception_rate > 0.25 && stats.deception_rate <= 0.5);
+    assert_eq!(stats.avg_skepticism, 0.5);
+}
+
+#[test]
+fn test_emit_outputs_follow_the_configured_channels() {
+    use primordium_lib::model::pheromone::PheromoneChannel;
+
+    let mut config = AppConfig::default();
+    config
+        .pheromone
+        .channels
+        .push(PheromoneChannel::new("alarm", 0.9, 0.1));
+    config.pheromone.emit = vec!["alarm".to_string()];
+    config.validate().unwrap();
+    let world = World::new(0, config.clone()).unwrap();
+    let env = Environment::default();
+    let mut ctx = ActionContext {
+        env: &env,
+        config: &config,
+        terrain: &world.terrain,
+        influence: &world.influence,
+        roads: &world.roads,
+        snapshots: &[],
+        entity_id_map: &std::collections::HashMap::new(),
+        spatial_hash: &primordium_lib::model::spatial_hash::SpatialHash::new(5.0, 100, 100),
+        pressure: &world.pressure,
+        pheromones: &world.pheromones,
+        width: 100,
+        height: 100,
+    };
+
+    let mut emitter = primordium_lib::model::lifecycle::create_entity(10.0, 10.0, 0);
+    let mut outputs = [0.0; 15];
+    outputs[6] = 1.0;
+    outputs[7] = 1.0;
+    let mut out = ActionOutput::default();
+    action_system(&mut emitter, outputs, &mut ctx, &mut out);
+
+    // Emit B is unmapped, so only Emit A leaves scent.
+    assert_eq!(out.pheromones.len(), 1);
+    assert_eq!(
+        Some(out.pheromones[0].channel),
+        world.pheromones.channel("alarm")
+    );
+}
@@ -86,12 +86,15 @@ async fn test_outpost_construction() {
         width: world.width,
         height: world.height,
         social_grid: std::sync::Arc::make_mut(&mut world.social_grid).as_mut_slice(),
+        mutation_heat: std::sync::Arc::make_mut(&mut world.mutation_heat),
         lineage_consumption: &mut lineage_cons,
         food_handles: &[],
+        food_field: None,
         spatial_hash: &world.spatial_hash,
         rng: &mut rng,
         food_count: &world.food_count,
         world_seed: 0,
+        ledger_target: None,
     };
 
     let cmd = InteractionCommand::Build {
@@ -125,13 +128,15 @@ async fn test_outpost_energy_capacitor() {
 
     let idx = world.terrain.index(10, 10);
     std::sync::Arc::make_mut(&mut world.terrain).set_cell_type(10, 10, TerrainType::Outpost);
-    std::sync::Arc::make_mut(&mut world.terrain).cells[idx].owner_id = Some(l_id);
+    std::sync::Arc::make_mut(&mut world.terrain)
+        .cell_mut(idx)
+        .owner_id = Some(l_id);
 
     world.update(&mut _env).unwrap();
 
     let idx = world.terrain.index(10, 10);
     assert!(
-        world.terrain.cells[idx].energy_store > 0.0,
+        world.terrain.cell(idx).energy_store > 0.0,
         "Outpost should collect energy"
     );
 
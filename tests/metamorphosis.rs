@@ -33,14 +33,14 @@ async fn test_larval_gating_logic() {
         for i in 0..26 {
             brain.connections.push(Connection {
                 from: i,
-                to: 38,
+                to: primordium_lib::model::brain::BRAIN_INPUTS + 9,
                 weight: 1.0,
                 enabled: true,
                 innovation: 10000 + i,
             });
             brain.connections.push(Connection {
                 from: i,
-                to: 39,
+                to: primordium_lib::model::brain::BRAIN_INPUTS + 10,
                 weight: 1.0,
                 enabled: true,
                 innovation: 11000 + i,
@@ -114,7 +114,7 @@ async fn test_metamorphosis_transition_and_remodeling() {
         .brain
         .connections
         .iter()
-        .any(|c| c.to == 38 && c.enabled);
+        .any(|c| c.to == primordium_lib::model::brain::BRAIN_INPUTS + 9 && c.enabled);
     assert!(
         has_dig_conn,
         "Adult brain should have Dig connections after remodeling"
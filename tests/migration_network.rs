@@ -1,3 +1,4 @@
+use primordium_lib::model::brain::GenotypeLogic;
 use primordium_lib::model::config::AppConfig;
 use primordium_lib::model::world::World;
 use primordium_net::{NetMessage, PeerInfo};
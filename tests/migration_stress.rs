@@ -27,6 +27,12 @@ async fn test_migration_burst_stability() {
         pairing_bias: 0.5,
         specialization_bias: [0.33, 0.33, 0.34],
         regulatory_rules: Vec::new(),
+        oviparity: 0.0,
+        clutch_size: 1,
+        parental_care: 0.0,
+        skepticism: 0.0,
+        death_avoidance: 0.0,
+        body_size: 1.0,
     };
     let dna_template = genotype.to_hex();
 
@@ -0,0 +1,106 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_data::{LiveEvent, Specialization};
+use primordium_lib::model::world::World;
+use uuid::Uuid;
+
+const BUILDERS: Uuid = Uuid::from_u128(42);
+
+fn civilised_world(energy: f64) -> (World, primordium_lib::model::environment::Environment) {
+    let mut engineer = EntityBuilder::new()
+        .id(Uuid::from_u128(1))
+        .at(20.5, 20.5)
+        .energy(energy)
+        .max_energy(2000.0)
+        .lineage(BUILDERS)
+        .specialization(Specialization::Engineer)
+        .build();
+    engineer.physics.max_speed = 0.0;
+    std::sync::Arc::make_mut(&mut engineer.intel.genotype).max_speed = 0.0;
+
+    let (mut world, env) = WorldBuilder::new()
+        .with_seed(7)
+        .with_config(|c| {
+            c.world.disaster_chance = 0.0;
+            c.metabolism.reproduction_threshold = 1000000.0;
+        })
+        .with_outpost(20, 20, BUILDERS)
+        .with_entity(engineer)
+        .build();
+    world.lineage_registry.record_birth(BUILDERS, 5, 0);
+    world
+        .lineage_registry
+        .lineages
+        .get_mut(&BUILDERS)
+        .expect("lineage recorded")
+        .civilization_level = 2;
+    (world, env)
+}
+
+#[tokio::test]
+async fn test_engineer_raises_inscribed_monument_on_outpost() {
+    let (mut world, mut env) = civilised_world(1500.0);
+
+    let events = world.update(&mut env).expect("Update failed");
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, LiveEvent::MonumentRaised { x: 20, y: 20, .. })));
+
+    let monument = world.monuments.at(20, 20).expect("monument raised");
+    assert_eq!(monument.lineage_id, BUILDERS);
+    assert_eq!(monument.civilization_level, 2);
+    assert_eq!(monument.max_generation, 5);
+    assert!(!monument.is_ruin());
+
+    world.update(&mut env).expect("Update failed");
+    assert_eq!(
+        world.monuments.len(),
+        1,
+        "Second monument crowded the first"
+    );
+    assert!(world
+        .observer
+        .history
+        .iter()
+        .any(|e| e.event_type == "Monument"));
+}
+
+#[tokio::test]
+async fn test_poor_or_uncivilised_engineers_build_nothing() {
+    let (mut world, mut env) = civilised_world(1500.0);
+    world.config.monuments.energy_cost = 1000.0;
+    world.update(&mut env).expect("Update failed");
+    assert!(world.monuments.is_empty());
+
+    let (mut world, mut env) = civilised_world(1500.0);
+    world
+        .lineage_registry
+        .lineages
+        .get_mut(&BUILDERS)
+        .expect("lineage recorded")
+        .civilization_level = 1;
+    world.update(&mut env).expect("Update failed");
+    assert!(world.monuments.is_empty());
+}
+
+#[tokio::test]
+async fn test_monuments_outlive_their_lineage_as_archived_ruins() {
+    let (mut world, mut env) = civilised_world(1500.0);
+    world.update(&mut env).expect("Update failed");
+    assert_eq!(world.monuments.len(), 1);
+
+    world.ecs.clear();
+    world.lineage_registry.record_death(BUILDERS, world.tick);
+    world.update(&mut env).expect("Update failed");
+
+    assert!(world.monuments.at(20, 20).is_some_and(|m| m.is_ruin()));
+    assert_eq!(world.fossil_registry.ruins.len(), 1);
+    assert!(world.fossil_registry.ruins[0]
+        .inscription()
+        .iter()
+        .any(|line| line.contains("fell silent")));
+
+    let json = serde_json::to_string(&world).expect("serialize");
+    let loaded: World = serde_json::from_str(&json).expect("deserialize");
+    assert!(loaded.monuments.at(20, 20).is_some_and(|m| m.is_ruin()));
+}
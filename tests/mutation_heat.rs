@@ -0,0 +1,72 @@
+use primordium_core::systems::interaction;
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::lifecycle;
+use primordium_lib::model::state::environment::Environment;
+use primordium_lib::model::state::interaction::InteractionCommand;
+use primordium_lib::model::world::World;
+use std::sync::Arc;
+
+fn birth(world: &mut World, parent: hecs::Entity, (x, y): (f64, f64), distance: f32) {
+    let baby = lifecycle::create_entity(x, y, 0);
+    let mut lineage_consumption = Vec::new();
+    let mut rng = rand::thread_rng();
+    let mut ctx = interaction::InteractionContext {
+        terrain: Arc::make_mut(&mut world.terrain),
+        env: &mut Environment::default(),
+        pop_stats: Arc::make_mut(&mut world.pop_stats),
+        lineage_registry: &mut world.lineage_registry,
+        fossil_registry: &mut world.fossil_registry,
+        config: &world.config,
+        tick: 0,
+        width: world.width,
+        height: world.height,
+        social_grid: Arc::make_mut(&mut world.social_grid).as_mut_slice(),
+        mutation_heat: Arc::make_mut(&mut world.mutation_heat),
+        lineage_consumption: &mut lineage_consumption,
+        food_handles: &[],
+        food_field: None,
+        spatial_hash: &world.spatial_hash,
+        rng: &mut rng,
+        food_count: &world.food_count,
+        world_seed: 0,
+        ledger_target: None,
+    };
+    let cmd = InteractionCommand::Birth {
+        parent_idx: 0,
+        baby: Box::new(baby),
+        genetic_distance: distance,
+    };
+    interaction::process_interaction_commands_ecs(&mut world.ecs, &[parent], vec![cmd], &mut ctx);
+}
+
+#[tokio::test]
+async fn test_births_heat_their_cell_and_drive_evolutionary_velocity() {
+    let mut config = AppConfig::default();
+    config.mutation_heat.half_life = 10.0;
+    let mut world = World::new(0, config).unwrap();
+    let mut env = Environment::default();
+    let parent = world.spawn_entity(lifecycle::create_entity(20.0, 20.0, 0));
+
+    birth(&mut world, parent, (12.5, 7.5), 0.6);
+    birth(&mut world, parent, (12.2, 7.9), 0.2);
+    birth(&mut world, parent, (30.0, 30.0), 0.1);
+    assert!((world.mutation_heat.get(12.0, 7.0) - 0.8).abs() < 1e-6);
+    assert_eq!(world.mutation_heat.get(20.0, 20.0), 0.0);
+
+    world.update(&mut env).unwrap();
+    let velocity = world.pop_stats.evolutionary_velocity;
+    assert!((velocity - 0.3).abs() < 1e-4, "velocity {velocity}");
+    assert!(world.mutation_heat.get(12.0, 7.0) < 0.8);
+    assert_eq!(
+        world.create_snapshot(None).mutation_heat.get(12.0, 7.0),
+        world.mutation_heat.get(12.0, 7.0)
+    );
+
+    // Without further births the map cools and the velocity falls away.
+    world.ecs.despawn(parent).unwrap();
+    for _ in 0..200 {
+        world.update(&mut env).unwrap();
+    }
+    assert_eq!(world.mutation_heat.max_heat(), 0.0);
+    assert_eq!(world.pop_stats.evolutionary_velocity, 0.0);
+}
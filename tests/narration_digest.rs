@@ -0,0 +1,107 @@
+use primordium_data::{LiveEvent, PopulationStats};
+use primordium_lib::model::config::NarratorConfig;
+use primordium_lib::model::environment::Environment;
+use primordium_lib::model::lineage_registry::LineageRegistry;
+use primordium_lib::model::observer::WorldObserver;
+use uuid::Uuid;
+
+fn war(tick: u64) -> LiveEvent {
+    LiveEvent::WarDeclared {
+        lineage_a: Uuid::from_u128(1),
+        lineage_b: Uuid::from_u128(tick as u128 + 2),
+        tick,
+        timestamp: String::new(),
+    }
+}
+
+fn observer() -> WorldObserver {
+    let mut config = NarratorConfig::default();
+    config.digest.window = 100;
+    config.digest.enter_events = 3;
+    config.digest.exit_events = 2;
+    WorldObserver::with_config(&config, "")
+}
+
+fn tick(observer: &mut WorldObserver, tick: u64) {
+    observer.observe(
+        tick,
+        &PopulationStats::default(),
+        &LineageRegistry::new(),
+        &Environment::default(),
+    );
+}
+
+fn digests(observer: &WorldObserver) -> Vec<String> {
+    observer
+        .history
+        .iter()
+        .filter(|e| e.event_type == "Digest")
+        .map(|e| e.description.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_busy_stretch_is_narrated_as_one_digest() {
+    let registry = LineageRegistry::new();
+    let mut observer = observer();
+
+    observer.observe_event(&war(10), &registry);
+    observer.observe_event(&war(11), &registry);
+    assert!(!observer.is_digesting());
+    for t in 12..17 {
+        observer.observe_event(&war(t), &registry);
+    }
+    assert!(observer.is_digesting());
+
+    tick(&mut observer, 111);
+    assert!(observer.is_digesting(), "Digest closed before its window");
+    tick(&mut observer, 112);
+
+    assert_eq!(
+        digests(&observer),
+        vec!["In 100 ticks of upheaval, five wars broke out.".to_string()]
+    );
+    assert_eq!(
+        observer
+            .history
+            .iter()
+            .filter(|e| e.event_type == "WarEvent")
+            .count(),
+        7,
+        "Digested events left out of the history"
+    );
+}
+
+#[tokio::test]
+async fn test_digest_mode_ends_once_the_world_quietens() {
+    let registry = LineageRegistry::new();
+    let mut observer = observer();
+    for t in 0..3 {
+        observer.observe_event(&war(t), &registry);
+    }
+    assert!(observer.is_digesting());
+
+    tick(&mut observer, 102);
+    assert!(!observer.is_digesting());
+    assert_eq!(digests(&observer).len(), 1);
+
+    observer.observe_event(&war(150), &registry);
+    tick(&mut observer, 400);
+    assert!(!observer.is_digesting());
+    assert_eq!(digests(&observer).len(), 1);
+}
+
+#[tokio::test]
+async fn test_disabled_digest_narrates_every_event() {
+    let registry = LineageRegistry::new();
+    let mut config = NarratorConfig::default();
+    config.digest.enabled = false;
+    config.digest.enter_events = 1;
+    let mut observer = WorldObserver::with_config(&config, "");
+    for t in 0..20 {
+        observer.observe_event(&war(t), &registry);
+    }
+    tick(&mut observer, 1000);
+    assert!(!observer.is_digesting());
+    assert!(digests(&observer).is_empty());
+}
@@ -0,0 +1,49 @@
+use primordium_lib::model::optimize::{Search, SearchState};
+
+const DIVERSITY: &str = include_str!("../assets/searches/diversity.toml");
+
+#[test]
+fn test_resumed_search_matches_an_uninterrupted_one() {
+    // A shortened search keeps the test fast.
+    let toml = DIVERSITY
+        .replace("ticks = 50000", "ticks = 20")
+        .replace("trials = 40", "trials = 3")
+        .replace("runs = 2", "runs = 1");
+    let search = Search::from_toml(&toml).unwrap();
+
+    let mut straight = SearchState::new(&search, &toml);
+    while !straight.is_done(&search) {
+        straight.advance(&search, |_| {}).unwrap();
+    }
+    assert_eq!(straight.trials[0].values, search.baseline().unwrap());
+    assert!(straight.trials.iter().all(|t| t.score.is_some()));
+
+    let checkpoint =
+        std::env::temp_dir().join(format!("primordium_search_{}.json", uuid::Uuid::new_v4()));
+    let mut interrupted = SearchState::new(&search, &toml);
+    interrupted.advance(&search, |_| {}).unwrap();
+    interrupted.save(&checkpoint).unwrap();
+    let mut resumed = SearchState::resume(&checkpoint, &search, &toml).unwrap();
+    while !resumed.is_done(&search) {
+        resumed.advance(&search, |_| {}).unwrap();
+    }
+    // Metrics pass through JSON, which may round their last digit.
+    let outline = |state: &SearchState| {
+        state
+            .trials
+            .iter()
+            .map(|t| (t.values.clone(), t.score))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(outline(&resumed), outline(&straight));
+    assert_eq!(resumed.step, straight.step);
+
+    // A checkpoint is only resumed by the search that wrote it.
+    assert!(SearchState::resume(&checkpoint, &search, DIVERSITY).is_err());
+    let _ = std::fs::remove_file(&checkpoint);
+
+    let best = straight.best(&search).unwrap();
+    let overrides = search.overrides(&best.values).unwrap();
+    assert_eq!(overrides["world"]["width"].as_integer(), Some(80));
+    assert!(overrides["evolution"]["mutation_rate"].is_float());
+}
@@ -0,0 +1,220 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_core::config::AppConfig;
+use primordium_core::snapshot::InternalEntitySnapshot;
+use primordium_core::spatial_hash::SpatialHash;
+use primordium_core::systems::care;
+use primordium_data::{EntityStatus, Identity, Metabolism};
+use primordium_lib::model::world::World;
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn snapshot(id: u128, parent: Option<u128>, x: f64, birth_tick: u64) -> InternalEntitySnapshot {
+    InternalEntitySnapshot {
+        id: Uuid::from_u128(id),
+        lineage_id: Uuid::nil(),
+        parent_id: parent.map(Uuid::from_u128),
+        x,
+        y: 10.0,
+        vx: 0.0,
+        vy: 0.0,
+        energy: 50.0,
+        birth_tick,
+        offspring_count: 0,
+        generation: 1,
+        max_energy: 100.0,
+        r: 0,
+        g: 0,
+        b: 0,
+        rank: 0.0,
+        status: EntityStatus::Juvenile,
+        trophic_potential: 0.5,
+        genotype: None,
+        memory_writes: 0,
+        memory_recalls: 0,
+        deceptions: 0,
+        infection_remaining: 0,
+        dominance: 0.0,
+    }
+}
+
+fn hashed(snapshots: &[InternalEntitySnapshot]) -> SpatialHash {
+    let mut hash = SpatialHash::new(5.0, 40, 40);
+    let positions: Vec<_> = snapshots.iter().map(|s| (s.x, s.y)).collect();
+    hash.build_parallel(&positions, 40, 40);
+    hash
+}
+
+fn energy_of(world: &World, id: Uuid) -> f64 {
+    world
+        .ecs
+        .query::<(&Identity, &Metabolism)>()
+        .iter()
+        .find(|(_, (i, _))| i.id == id)
+        .map(|(_, (_, m))| m.energy)
+        .unwrap()
+}
+
+#[test]
+fn test_care_lasts_a_fraction_of_maturity() {
+    let config = AppConfig::default();
+    let mut genotype = (*EntityBuilder::new().build().intel.genotype).clone();
+    genotype.parental_care = 0.0;
+    assert_eq!(care::care_ticks(&genotype, &config), 0);
+    genotype.parental_care = 0.5;
+    assert_eq!(
+        care::care_ticks(&genotype, &config),
+        config.metabolism.maturity_age / 2
+    );
+}
+
+#[test]
+fn test_parents_feed_only_their_young_juveniles() {
+    let config = AppConfig::default();
+    let mut parent = snapshot(1, None, 10.0, 0);
+    parent.energy = 200.0;
+    parent.max_energy = 200.0;
+    let snapshots = vec![
+        parent,
+        snapshot(2, Some(1), 11.0, 90),  // own young
+        snapshot(3, None, 11.0, 90),     // stranger's young
+        snapshot(4, Some(1), 11.0, 10),  // own, past the care window
+        snapshot(5, Some(1), 30.0, 90),  // own, out of reach
+        snapshot(6, Some(1), 10.5, 100), // own, newborn
+    ];
+    let hash = hashed(&snapshots);
+
+    let fed = care::provisions(0, 50, 100, &snapshots, &hash, &config);
+    let rate = config.care.provision_rate;
+    assert_eq!(fed, vec![(1, rate), (5, rate)]);
+}
+
+#[test]
+fn test_parents_keep_their_reserve() {
+    let config = AppConfig::default();
+    let mut parent = snapshot(1, None, 10.0, 0);
+    parent.energy = 101.5;
+    parent.max_energy = 200.0;
+    let snapshots = vec![
+        parent.clone(),
+        snapshot(2, Some(1), 11.0, 90),
+        snapshot(3, Some(1), 11.0, 90),
+    ];
+    let hash = hashed(&snapshots);
+    let fed = care::provisions(0, 50, 100, &snapshots, &hash, &config);
+    assert_eq!(fed, vec![(1, 1.0), (2, 0.5)]);
+
+    let mut starving = snapshots;
+    starving[0].energy = 90.0;
+    assert!(care::provisions(0, 50, 100, &starving, &hash, &config).is_empty());
+}
+
+#[test]
+fn test_care_moves_energy_from_parent_to_child() {
+    let parent_id = Uuid::from_u128(1);
+    let child_id = Uuid::from_u128(2);
+    let run = |enabled: bool| {
+        let mut parent = EntityBuilder::new()
+            .id(parent_id)
+            .at(20.0, 20.0)
+            .energy(400.0)
+            .max_energy(400.0)
+            .build();
+        Arc::make_mut(&mut parent.intel.genotype).parental_care = 1.0;
+        let mut child = EntityBuilder::new()
+            .id(child_id)
+            .at(21.0, 20.0)
+            .energy(30.0)
+            .max_energy(200.0)
+            .build();
+        child.identity.parent_id = Some(parent_id);
+        let (mut world, mut env) = WorldBuilder::new()
+            .with_config(|c| {
+                c.world.deterministic = true;
+                c.care.enabled = enabled;
+            })
+            .with_entity(parent)
+            .with_entity(child)
+            .build();
+        world.update(&mut env).unwrap();
+        (energy_of(&world, parent_id), energy_of(&world, child_id))
+    };
+
+    let (parent_without, child_without) = run(false);
+    let (parent_with, child_with) = run(true);
+    assert!(child_with > child_without);
+    assert!(parent_with < parent_without);
+}
+
+#[test]
+fn test_lineages_track_survival_to_maturity() {
+    let lineage = Uuid::from_u128(7);
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| c.metabolism.maturity_age = 5)
+        .with_entity(
+            EntityBuilder::new()
+                .id(Uuid::from_u128(1))
+                .at(10.0, 10.0)
+                .energy(400.0)
+                .max_energy(400.0)
+                .lineage(lineage)
+                .build(),
+        )
+        .with_entity(
+            EntityBuilder::new()
+                .id(Uuid::from_u128(2))
+                .at(30.0, 30.0)
+                .energy(0.0)
+                .lineage(lineage)
+                .build(),
+        )
+        .build();
+    for (_, met) in world.ecs.query_mut::<&mut Metabolism>() {
+        world.lineage_registry.record_birth(met.lineage_id, 0, 0);
+        met.birth_tick = 0;
+    }
+    for (_, intel) in world.ecs.query_mut::<&mut primordium_data::Intel>() {
+        Arc::make_mut(&mut intel.genotype).maturity_gene = 1.0;
+    }
+
+    for _ in 0..6 {
+        world.update(&mut env).unwrap();
+    }
+    let record = &world.lineage_registry.lineages[&lineage];
+    assert_eq!(record.juvenile_deaths, 1);
+    assert_eq!(record.juveniles_matured, 1);
+    assert_eq!(record.survival_to_maturity(), Some(0.5));
+}
+
+#[test]
+fn test_newborns_know_their_parent() {
+    let parent_id = Uuid::from_u128(1);
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| {
+            c.world.deterministic = true;
+            c.eggs.enabled = false;
+            c.metabolism.maturity_age = 1;
+            c.metabolism.reproduction_threshold = 10.0;
+        })
+        .with_entity(
+            EntityBuilder::new()
+                .id(parent_id)
+                .at(20.0, 20.0)
+                .energy(800.0)
+                .max_energy(1000.0)
+                .build(),
+        )
+        .build();
+    for _ in 0..20 {
+        world.update(&mut env).unwrap();
+        if world.get_population_count() > 1 {
+            break;
+        }
+    }
+    let child = world
+        .get_all_entities()
+        .into_iter()
+        .find(|e| e.identity.id != parent_id)
+        .expect("parent should have reproduced");
+    assert_eq!(child.identity.parent_id, Some(parent_id));
+}
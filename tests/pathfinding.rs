@@ -0,0 +1,220 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_core::config::PathfindingConfig;
+use primordium_core::pathfinding::{self, PathQuery, PathService};
+use primordium_core::terrain::{TerrainCell, TerrainGrid};
+use primordium_data::TerrainType;
+use uuid::Uuid;
+
+fn plains(width: u16, height: u16) -> TerrainGrid {
+    let cell = TerrainCell {
+        terrain_type: TerrainType::Plains,
+        ..TerrainCell::default()
+    };
+    TerrainGrid::from_cells(width, height, vec![cell; width as usize * height as usize])
+}
+
+/// A wall down column 10 with a single gap at the bottom row.
+fn walled(width: u16, height: u16) -> TerrainGrid {
+    let mut terrain = plains(width, height);
+    for y in 0..height - 1 {
+        terrain.set_cell_type(10, y, TerrainType::Wall);
+    }
+    terrain
+}
+
+#[test]
+fn test_plan_detours_through_wall_gap() {
+    let terrain = walled(20, 10);
+    let plan = pathfinding::plan_greedy(&terrain, (2, 2), (17, 2), 10_000);
+
+    assert!(plan.metrics.reached_goal);
+    assert_eq!(plan.waypoints.last(), Some(&(17, 2)));
+    assert!(plan.waypoints.iter().any(|&(_, y)| y == 9));
+    assert!(plan
+        .waypoints
+        .iter()
+        .all(|&(x, y)| pathfinding::step_cost(&terrain, x, y).is_some()));
+    // Compressed to turning points, far fewer than the cells walked.
+    assert!(plan.waypoints.len() < plan.metrics.steps);
+    assert!(plan.metrics.cost > 15.0);
+}
+
+#[test]
+fn test_greedy_plan_expands_less_than_optimal() {
+    let terrain = walled(40, 30);
+    let optimal = pathfinding::plan(&terrain, (2, 2), (37, 2), 1.0, 100_000);
+    let greedy = pathfinding::plan_greedy(&terrain, (2, 2), (37, 2), 100_000);
+
+    assert!(optimal.metrics.reached_goal && greedy.metrics.reached_goal);
+    assert!(greedy.metrics.expanded < optimal.metrics.expanded);
+    assert!(optimal.metrics.cost <= greedy.metrics.cost + 1e-3);
+}
+
+#[test]
+fn test_plan_prefers_rivers_over_mountains() {
+    let mut terrain = plains(20, 5);
+    for x in 0..20 {
+        terrain.set_cell_type(x, 2, TerrainType::Mountain);
+        terrain.set_cell_type(x, 4, TerrainType::River);
+    }
+    let plan = pathfinding::plan(&terrain, (0, 3), (19, 3), 1.0, 10_000);
+
+    assert!(plan.metrics.reached_goal);
+    assert!(plan.waypoints.iter().any(|&(_, y)| y == 4));
+    assert!(plan.metrics.cost < 19.0);
+}
+
+#[test]
+fn test_plan_budget_returns_partial_path() {
+    let terrain = walled(40, 30);
+    let plan = pathfinding::plan_greedy(&terrain, (2, 2), (37, 2), 5);
+
+    assert!(!plan.metrics.reached_goal);
+    assert_eq!(plan.metrics.expanded, 5);
+    assert!(!plan.waypoints.is_empty());
+}
+
+#[test]
+fn test_threatened_lineage_migrates_along_route() {
+    let lineage = Uuid::new_v4();
+    let mut builder = WorldBuilder::new()
+        .with_seed(7)
+        .with_config(|c| {
+            c.world.deterministic = true;
+            c.migration.replan_interval = 5;
+            c.migration.steering_force = 1.0;
+            // Keep the huddled group from bonding and breeding out of the lineage.
+            c.social.sharing_threshold = 1.0;
+        })
+        .with_memory(lineage, "threat", 5.0);
+    for i in 0..5 {
+        builder = builder.with_entity(
+            EntityBuilder::new()
+                .at(10.0 + i as f64, 10.0)
+                .energy(500.0)
+                .max_energy(1000.0)
+                .lineage(lineage)
+                .build(),
+        );
+    }
+    let (mut world, mut env) = builder.build();
+
+    // Requested on tick 5, planned by the path service at the end of that
+    // tick and picked up on the next.
+    for _ in 0..6 {
+        world.update(&mut env).unwrap();
+    }
+    let totals = world.lineage_registry.migration_totals;
+    assert_eq!(totals.started, 1);
+    assert_eq!(totals.plans, 1);
+    let route = world.lineage_registry.lineages[&lineage]
+        .migration
+        .clone()
+        .expect("threatened lineage should be migrating");
+    assert!(!route.waypoints.is_empty());
+    assert_eq!(route.planned_tick, Some(6));
+    assert_eq!(world.path_service.stats.queries, 1);
+
+    let distance = |world: &primordium_lib::model::world::World| {
+        let (x, y) = world
+            .entity_snapshots
+            .iter()
+            .fold((0.0, 0.0), |acc, s| (acc.0 + s.x / 5.0, acc.1 + s.y / 5.0));
+        (x - f64::from(route.target.0)).hypot(y - f64::from(route.target.1))
+    };
+    let before = distance(&world);
+    for _ in 0..20 {
+        world.update(&mut env).unwrap();
+    }
+    assert!(distance(&world) < before);
+}
+
+#[test]
+fn test_path_service_bounds_queries_per_tick() {
+    let terrain = walled(40, 30);
+    let config = PathfindingConfig {
+        queries_per_tick: 3,
+        ..PathfindingConfig::default()
+    };
+    let mut service = PathService::new();
+    let queries: Vec<PathQuery> = (0..10)
+        .map(|i| PathQuery {
+            start: (2, i),
+            goal: (37, 2),
+        })
+        .collect();
+    for &q in &queries {
+        assert!(service.request(q).is_none());
+    }
+    // Re-requesting a queued query does not queue it twice.
+    assert!(service.request(queries[0]).is_none());
+    assert_eq!(service.pending(), 10);
+
+    assert_eq!(service.process(&terrain, &config), 3);
+    assert_eq!(service.pending(), 7);
+    assert_eq!(service.stats.backlogged_ticks, 1);
+
+    // Queries are answered in request order.
+    assert!(service.request(queries[0]).is_some());
+    assert!(service.request(queries[3]).is_none());
+    assert_eq!(service.stats.cache_hits, 1);
+
+    while service.pending() > 0 {
+        assert!(service.process(&terrain, &config) <= 3);
+    }
+    assert!(queries.iter().all(|&q| service.request(q).is_some()));
+}
+
+#[test]
+fn test_path_service_expansion_budget_defers_queries() {
+    let terrain = walled(40, 30);
+    let config = PathfindingConfig {
+        queries_per_tick: 100,
+        expansions_per_tick: 1_000,
+        max_expansions: 1_000,
+        ..PathfindingConfig::default()
+    };
+    let mut service = PathService::new();
+    for y in 0..4 {
+        service.request(PathQuery {
+            start: (2, y),
+            goal: (37, 2),
+        });
+    }
+    // Each detour query expands hundreds of cells, so one fits per tick.
+    assert_eq!(service.process(&terrain, &config), 1);
+    assert_eq!(service.pending(), 3);
+}
+
+#[test]
+fn test_path_service_invalidates_paths_on_crossed_terrain_change() {
+    let mut terrain = walled(40, 30);
+    let config = PathfindingConfig::default();
+    let mut service = PathService::new();
+    let top = PathQuery {
+        start: (2, 2),
+        goal: (8, 2),
+    };
+    let detour = PathQuery {
+        start: (2, 2),
+        goal: (37, 2),
+    };
+    service.request(top);
+    service.request(detour);
+    service.process(&terrain, &config);
+    assert_eq!(service.cached(), 2);
+
+    // Raise a mountain in the bottom rows: only the detour crosses them.
+    terrain.set_cell_type(20, 28, TerrainType::Mountain);
+    service.process(&terrain, &config);
+    assert_eq!(service.stats.invalidated, 1);
+    assert!(service.request(top).is_some());
+    assert!(service.request(detour).is_none());
+
+    // Other fields of the cells changing does not invalidate anything.
+    let idx = terrain.index(3, 2);
+    terrain.cell_mut(idx).fertility = 0.9;
+    service.process(&terrain, &config);
+    assert_eq!(service.stats.invalidated, 1);
+}
@@ -1,3 +1,4 @@
+use primordium_core::systems::{biological, status};
 use primordium_data::Pathogen;
 use primordium_lib::model::config::{AppConfig, GameMode};
 use primordium_lib::model::lifecycle;
@@ -25,8 +26,7 @@ async fn test_pathogen_transmission() {
         virulence: 2.0, // High virulence
         behavior_manipulation: None,
     };
-    patient_zero.health.pathogen = Some(pathogen.clone());
-    patient_zero.health.infection_timer = pathogen.duration;
+    biological::infect_components(&mut patient_zero.health, &pathogen);
     world.spawn_entity(patient_zero);
 
     // 2. Setup Victim nearby (same position to be sure)
@@ -69,12 +69,8 @@ async fn test_immunity_gain() {
         behavior_manipulation: None,
     };
 
-    entity.health.pathogen = Some(pathogen);
-    entity.health.infection_timer = 1;
-
-    use primordium_core::systems::biological;
-    biological::process_infection_components(&mut entity.health, &mut entity.metabolism); // timer -> 0
-    biological::process_infection_components(&mut entity.health, &mut entity.metabolism); // recovered
+    biological::infect_components(&mut entity.health, &pathogen);
+    status::process_status_effects(&mut entity.health, &mut entity.metabolism); // recovered
 
     assert!(entity.health.pathogen.is_none());
     assert!(
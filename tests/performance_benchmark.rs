@@ -125,7 +125,7 @@ async fn benchmark_brain_forward_pass() {
     let genotype = primordium_data::Genotype::new_random();
 
     let mut activations = primordium_data::Activations::default();
-    let inputs: [f32; 29] = [0.1; 29];
+    let inputs: [f32; 36] = [0.1; 36];
     let last_hidden: [f32; 6] = [0.05; 6];
 
     let forward_start = Instant::now();
@@ -174,3 +174,52 @@ async fn benchmark_full_tick_scaling() {
         );
     }
 }
+
+#[tokio::test]
+#[ignore = "Micro-benchmark - run manually with --ignored"]
+async fn benchmark_food_representation() {
+    use primordium_lib::model::config::FoodRepresentation;
+
+    for representation in [FoodRepresentation::Entities, FoodRepresentation::Field] {
+        let log_dir = format!("logs_test_bench_food_{:?}", representation);
+        let _ = std::fs::remove_dir_all(&log_dir);
+
+        let mut config = AppConfig::default();
+        config.world.width = 400;
+        config.world.height = 400;
+        config.world.initial_food = 50_000;
+        config.world.max_food = 60_000;
+        config.food.representation = representation;
+
+        let mut world = World::new_at(500, config, &log_dir).unwrap();
+        let mut env = Environment::default();
+
+        let entity_food_bytes = world
+            .ecs
+            .query::<&primordium_lib::model::state::Food>()
+            .iter()
+            .count()
+            * (std::mem::size_of::<primordium_lib::model::state::Food>()
+                + std::mem::size_of::<primordium_lib::model::state::Position>()
+                + std::mem::size_of::<primordium_lib::model::state::MetabolicNiche>());
+        let field_bytes = world.food_field.memory_bytes();
+
+        let iterations = 20;
+        let benchmark_start = Instant::now();
+        for _ in 0..iterations {
+            world.update(&mut env).unwrap();
+        }
+        let avg_tick = benchmark_start
+            .elapsed()
+            .checked_div(iterations as u32)
+            .unwrap_or(Duration::ZERO);
+        println!(
+            "{:?}: {} food, avg tick {:?}, ~{} KiB food storage",
+            representation,
+            world.get_food_count(),
+            avg_tick,
+            (entity_food_bytes + field_bytes) / 1024
+        );
+        let _ = std::fs::remove_dir_all(&log_dir);
+    }
+}
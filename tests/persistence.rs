@@ -37,11 +37,452 @@ async fn test_world_persistence_cycle() {
     let _ = serde_json::to_string(&world.pheromones).expect("Failed to serialize Pheromones");
     println!("Pheromones OK");
 
+    world.add_bookmark(7, "second".to_string());
+    world.add_bookmark(3, "first".to_string());
+
     println!("Starting full World serialization...");
     let serialized = serde_json::to_string(&world).expect("Failed to serialize World");
     println!("World OK, size: {}", serialized.len());
 
-    let _deserialized: World =
+    let deserialized: World =
         serde_json::from_str(&serialized).expect("Failed to deserialize World");
     println!("Deserialization OK");
+    assert_eq!(deserialized.bookmarks, world.bookmarks);
+    assert_eq!(deserialized.bookmarks[0].note, "first");
+}
+
+#[tokio::test]
+async fn test_loaded_world_keeps_simulating_with_live_brains() {
+    use primordium_lib::model::persistence::{load_world, save_world};
+
+    let mut world = World::new(5, AppConfig::default()).expect("Failed to create world");
+    let path = std::env::temp_dir().join(format!("primordium_{}.json", uuid::Uuid::new_v4()));
+    save_world(&mut world, &path).expect("Failed to save world");
+    let mut loaded = load_world(&path).expect("Failed to load world");
+    let _ = std::fs::remove_file(&path);
+
+    loaded
+        .update(&mut Environment::default())
+        .expect("Loaded world failed to update");
+    let id = loaded
+        .get_all_entities()
+        .first()
+        .map(|e| e.identity.id.to_string())
+        .expect("entities survive the first tick");
+    let (_, activations) = loaded.brain_of(&id).unwrap();
+    assert!(activations.iter().any(|v| *v != 0.0));
+}
+
+#[tokio::test]
+async fn test_save_compatibility_check_and_adaptation() {
+    use primordium_lib::model::compat::{CompatibilityReport, Incompatibility};
+    use primordium_lib::model::persistence::{load_world_adapted, save_world};
+
+    let mut config = AppConfig::default();
+    config.world.width = 40;
+    config.world.height = 30;
+    let mut world = World::new_ephemeral(6, config.clone()).expect("Failed to create world");
+    let path = std::env::temp_dir().join(format!("primordium_{}.json", uuid::Uuid::new_v4()));
+    save_world(&mut world, &path).expect("Failed to save world");
+    let saved_positions: std::collections::HashMap<_, _> = world
+        .get_all_entities()
+        .into_iter()
+        .map(|e| (e.identity.id, (e.physics.x, e.physics.y)))
+        .collect();
+
+    let (_, report) = load_world_adapted(&path, &config).unwrap();
+    assert!(report.is_compatible());
+
+    let mut current = config.clone();
+    current.world.width = 80;
+    current.world.height = 60;
+    current.metabolism.base_move_cost *= 2.0;
+    let (mut loaded, report) = load_world_adapted(&path, &current).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(report.can_adapt());
+    assert!(report.issues.contains(&Incompatibility::MapSize {
+        saved: (40, 30),
+        current: (80, 60),
+    }));
+    assert!(report
+        .issues
+        .iter()
+        .any(|i| matches!(i, Incompatibility::Rules { .. })));
+
+    assert_eq!((loaded.width, loaded.height), (80, 60));
+    assert_eq!(loaded.terrain.width, 80);
+    assert_eq!(loaded.config.fingerprint(), current.fingerprint());
+    assert!(CompatibilityReport::check(&loaded, &current).is_compatible());
+    for e in loaded.get_all_entities() {
+        let (x, y) = saved_positions[&e.identity.id];
+        assert!((e.physics.x - x * 2.0).abs() < 1e-9 || e.physics.x == 79.0);
+        assert!((e.physics.y - y * 2.0).abs() < 1e-9 || e.physics.y == 59.0);
+    }
+    loaded
+        .update(&mut Environment::default())
+        .expect("Adapted world failed to update");
+}
+
+#[tokio::test]
+async fn test_saves_with_unknown_brain_layouts_are_refused() {
+    use primordium_lib::model::compat::{self, CompatibilityReport, Incompatibility};
+
+    let config = AppConfig::default();
+    let mut world = World::new_ephemeral(3, config.clone()).expect("Failed to create world");
+    for (_, intel) in world.ecs.query_mut::<&mut primordium_data::Intel>() {
+        // An output this build does not know about.
+        let brain = &mut std::sync::Arc::make_mut(&mut intel.genotype).brain;
+        brain.nodes.push(primordium_data::Node {
+            id: brain.next_node_id,
+            node_type: primordium_data::NodeType::Output,
+            label: None,
+            activation: primordium_data::Activation::Tanh,
+        });
+    }
+    let report = CompatibilityReport::check(&world, &config);
+    assert!(!report.can_adapt());
+    assert!(matches!(
+        report.issues[0],
+        Incompatibility::BrainLayout { .. }
+    ));
+    assert!(compat::adapt(&mut world, &config, &report).is_err());
+}
+
+#[tokio::test]
+async fn test_saves_carry_the_run_manifest() {
+    use primordium_lib::model::persistence::{load_world, save_world};
+    use primordium_lib::model::provenance;
+
+    let mut config = AppConfig::default();
+    config.world.seed = Some(99);
+    let mut world = World::new_ephemeral(3, config.clone()).expect("Failed to create world");
+    let stamp = world.provenance.clone().expect("new worlds are stamped");
+    assert_eq!(stamp.seed, Some(99));
+    assert_eq!(stamp.config_hash, provenance::config_hash(&config));
+
+    let path = std::env::temp_dir().join(format!("primordium_{}.json", uuid::Uuid::new_v4()));
+    save_world(&mut world, &path).expect("Failed to save world");
+    let manifests = provenance::read(&path).unwrap();
+    let loaded = load_world(&path).expect("Failed to load world");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(manifests, vec![stamp.clone()]);
+    assert_eq!(loaded.provenance, Some(stamp));
+}
+
+#[tokio::test]
+async fn test_binary_snapshots_resume_like_json_saves() {
+    use primordium_io::replay::stats_hash;
+    use primordium_lib::model::persistence::{load_world, save_world};
+
+    let mut config = AppConfig::default();
+    config.world.deterministic = true;
+    config.world.seed = Some(11);
+    let mut world = World::new(20, config).expect("Failed to create world");
+    let mut env = Environment::default();
+    for _ in 0..30 {
+        world.update(&mut env).unwrap();
+    }
+
+    let dir = std::env::temp_dir().join(format!("primordium_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (json, bin) = (dir.join("save.json"), dir.join("save.bin"));
+    save_world(&mut world, &json).unwrap();
+    save_world(&mut world, &bin).unwrap();
+    assert!(std::fs::metadata(&bin).unwrap().len() < std::fs::metadata(&json).unwrap().len());
+    let mut from_json = load_world(&json).unwrap();
+    let mut from_bin = load_world(&bin).unwrap();
+
+    // A snapshot from a newer build is refused rather than misread.
+    let mut newer = std::fs::read(&bin).unwrap();
+    newer[8..12].copy_from_slice(&99u32.to_le_bytes());
+    std::fs::write(&bin, newer).unwrap();
+    let Err(err) = load_world(&bin) else {
+        panic!("loaded a snapshot of a newer schema");
+    };
+    assert!(format!("{err:#}").contains("newer"), "{err:#}");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(from_bin.tick, world.tick);
+    let (mut env_json, mut env_bin) = (env.clone(), env.clone());
+    for _ in 0..90 {
+        world.update(&mut env).unwrap();
+        from_json.update(&mut env_json).unwrap();
+        from_bin.update(&mut env_bin).unwrap();
+    }
+    assert_eq!(
+        stats_hash(&from_bin.pop_stats),
+        stats_hash(&world.pop_stats)
+    );
+    assert_eq!(
+        stats_hash(&from_bin.pop_stats),
+        stats_hash(&from_json.pop_stats)
+    );
+    assert_eq!(
+        from_bin.get_population_count(),
+        world.get_population_count()
+    );
+}
+
+/// Hash of everything a tick can observably change: the organisms, the
+/// food and the population statistics.
+fn state_hash(world: &World) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut entities = world.get_all_entities();
+    entities.sort_by_key(|e| e.identity.id);
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    // Through a `Value` to order the keys of hash maps.
+    serde_json::to_value(&entities)
+        .unwrap()
+        .to_string()
+        .hash(&mut hasher);
+    world.get_food_count().hash(&mut hasher);
+    primordium_io::replay::stats_hash(&world.pop_stats).hash(&mut hasher);
+    hasher.finish()
+}
+
+#[tokio::test]
+async fn test_post_load_rebuild_restores_derived_caches() {
+    use primordium_lib::model::persistence::{decode_world, encode_world};
+    use primordium_lib::model::terrain::TerrainType;
+
+    let mut config = AppConfig::default();
+    config.world.deterministic = true;
+    config.world.seed = Some(23);
+    let mut world = World::new(25, config).expect("Failed to create world");
+    std::sync::Arc::make_mut(&mut world.terrain).set_cell_type(12, 9, TerrainType::Outpost);
+    let mut env = Environment::default();
+    for _ in 0..40 {
+        world.update(&mut env).unwrap();
+    }
+
+    let bytes = encode_world(&mut world).unwrap();
+    let mut loaded = decode_world(&bytes).unwrap();
+    loaded.post_load();
+    assert!(!loaded.terrain.outpost_indices.is_empty());
+    assert_eq!(
+        loaded.terrain.outpost_indices,
+        world.terrain.outpost_indices
+    );
+    assert_eq!(loaded.get_food_count(), world.get_food_count());
+    let e = &world.get_all_entities()[0];
+    let mut near = 0;
+    loaded
+        .spatial_hash
+        .query_callback(e.position.x, e.position.y, 1.0, |_| near += 1);
+    assert!(near > 0, "the spatial hash is empty before the first tick");
+    assert_eq!(state_hash(&loaded), state_hash(&world));
+
+    let mut loaded_env = env.clone();
+    for tick in 0..60 {
+        world.update(&mut env).unwrap();
+        loaded.update(&mut loaded_env).unwrap();
+        assert_eq!(
+            state_hash(&loaded),
+            state_hash(&world),
+            "diverged {} ticks after loading",
+            tick + 1
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_schema_1_snapshots_load_into_pheromone_channels() {
+    use primordium_data::{
+        Food, HallOfFame, LegacyFood, LegacyHallOfFame, PlantGenome, PopulationStats,
+    };
+    use primordium_io::persistence::{decode_snapshot, encode_snapshot, SnapshotMigrations};
+    use primordium_lib::model::influence::InfluenceGrid;
+    use primordium_lib::model::persistence::{decode_world, encode_world_snapshot};
+    use primordium_lib::model::pheromone::{
+        LegacyPheromoneCell, LegacyPheromoneGrid, PheromoneGrid, PheromoneType,
+    };
+    use primordium_lib::model::pressure::PressureGrid;
+    use primordium_lib::model::road::RoadGrid;
+    use primordium_lib::model::sound::SoundGrid;
+    use primordium_lib::model::terrain::{LegacyTerrainGrid, TerrainGrid, TerrainType};
+
+    // Mirrors of the archived layouts of schemas 1 and 5.
+    macro_rules! snapshot {
+        ($name:ident, $food:ty, $terrain:ty, $pheromones:ty, $hall_of_fame:ty) => {
+            #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+            #[archive(check_bytes)]
+            struct $name {
+                food: Vec<$food>,
+                terrain: $terrain,
+                pheromones: $pheromones,
+                sound: SoundGrid,
+                pressure: PressureGrid,
+                influence: InfluenceGrid,
+                roads: RoadGrid,
+                pop_stats: PopulationStats,
+                hall_of_fame: $hall_of_fame,
+                world: Vec<u8>,
+            }
+        };
+    }
+    snapshot!(
+        V1,
+        LegacyFood,
+        LegacyTerrainGrid,
+        LegacyPheromoneGrid,
+        LegacyHallOfFame
+    );
+    snapshot!(V5, Food, TerrainGrid, PheromoneGrid, HallOfFame);
+
+    let mut config = AppConfig::default();
+    config.world.width = 20;
+    config.world.height = 20;
+    let mut world = World::new(5, config).expect("Failed to create world");
+    let bytes = encode_world_snapshot(&mut world).unwrap();
+    let v5: V5 = decode_snapshot(&bytes[..], &SnapshotMigrations::new(5)).unwrap();
+
+    let mut cells = vec![LegacyPheromoneCell::default(); 20 * 20];
+    cells[4 * 20 + 3].death_strength = 0.75;
+    cells[6 * 20 + 5].sig_b_strength = 0.5;
+    let v1 = V1 {
+        food: v5
+            .food
+            .iter()
+            .map(|f| LegacyFood {
+                x: f.x,
+                y: f.y,
+                value: f.value,
+                symbol: f.symbol,
+                color_rgb: f.color_rgb,
+                nutrient_type: f.nutrient_type,
+            })
+            .collect(),
+        terrain: LegacyTerrainGrid {
+            chunks: v5.terrain.chunks().to_vec(),
+            width: v5.terrain.width,
+            height: v5.terrain.height,
+            dust_bowl_timer: v5.terrain.dust_bowl_timer,
+        },
+        pheromones: LegacyPheromoneGrid {
+            cells,
+            width: 20,
+            height: 20,
+            decay_rate: 0.98,
+        },
+        sound: v5.sound,
+        pressure: v5.pressure,
+        influence: v5.influence,
+        roads: v5.roads,
+        pop_stats: v5.pop_stats,
+        hall_of_fame: LegacyHallOfFame::default(),
+        world: v5.world,
+    };
+
+    let mut loaded = decode_world(&encode_snapshot(&v1, 1).unwrap()).unwrap();
+    loaded.post_load();
+    let pheromones = &loaded.pheromones;
+    assert_eq!(pheromones.channels.len(), 5);
+    assert!(pheromones.channels.iter().all(|c| c.decay == 0.98));
+    let death = pheromones.channel_of(PheromoneType::Death).unwrap();
+    let signal_b = pheromones.channel("signal_b").unwrap();
+    assert_eq!(pheromones.strength(3, 4, death), 0.75);
+    assert_eq!(pheromones.strength(5, 6, signal_b), 0.5);
+    assert_eq!(pheromones.strength(5, 6, death), 0.0);
+    assert_eq!(loaded.get_population_count(), 5);
+    // Food from before plant genomes loads as wild, non-toxic plants.
+    let wild = PlantGenome::wild(0.0);
+    let mut plants = loaded.ecs.query::<&Food>();
+    assert!(plants
+        .iter()
+        .all(|(_, f)| f.toxicity == wild.toxicity && f.regrowth == wild.regrowth));
+    // Terrain from before the water layer is seeded from its cells.
+    let terrain = &loaded.terrain;
+    let oasis = terrain
+        .cells()
+        .position(|c| c.terrain_type == TerrainType::Oasis)
+        .expect("generated terrain has oases");
+    let (x, y) = (oasis % 20, oasis / 20);
+    assert_eq!(terrain.water_at(x as f64, y as f64), 1.0);
+}
+
+/// The world each `tests/fixtures/snapshot_v<schema>.bin.gz` was saved from,
+/// by the build that introduced that schema.
+fn fixture_world() -> World {
+    let mut config = AppConfig::default();
+    config.world.width = 24;
+    config.world.height = 16;
+    config.world.deterministic = true;
+    config.world.seed = Some(4);
+    let mut world = World::new(6, config).expect("Failed to create world");
+    let mut env = Environment::default();
+    while world.tick < 40 {
+        world.update(&mut env).unwrap();
+    }
+    let entities = world.get_all_entities();
+    primordium_core::systems::stats::update_hall_of_fame(
+        std::sync::Arc::make_mut(&mut world.hall_of_fame),
+        &entities,
+        world.tick,
+    );
+    world
+}
+
+/// Snapshots checked in by older builds keep loading, and one of the
+/// current schema is checked in too: an archived layout that changes
+/// without a schema bump fails to read it. When bumping the schema, bless
+/// the new fixture and commit it together with the migration:
+///
+/// ```bash
+/// PRIMORDIUM_BLESS=1 cargo test --test persistence fixtures
+/// ```
+#[tokio::test]
+async fn test_snapshot_fixtures_of_every_schema_load() {
+    use flate2::read::GzDecoder;
+    use primordium_data::{MAX_BODY_SIZE, MIN_BODY_SIZE};
+    use primordium_io::persistence::snapshot_version;
+    use primordium_lib::model::persistence::{
+        decode_world, encode_world_snapshot, SNAPSHOT_SCHEMA_VERSION,
+    };
+    use std::io::{Read, Write};
+
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let path = |schema: u32| dir.join(format!("snapshot_v{schema}.bin.gz"));
+    let current = path(SNAPSHOT_SCHEMA_VERSION);
+    if std::env::var_os("PRIMORDIUM_BLESS").is_some() && !current.exists() {
+        let bytes = encode_world_snapshot(&mut fixture_world()).unwrap();
+        let mut gz = flate2::write::GzEncoder::new(
+            std::fs::File::create(&current).unwrap(),
+            flate2::Compression::best(),
+        );
+        gz.write_all(&bytes).unwrap();
+        gz.finish().unwrap();
+    }
+    assert!(
+        current.exists(),
+        "no snapshot fixture of schema {SNAPSHOT_SCHEMA_VERSION}; bless one"
+    );
+
+    for schema in 4..=SNAPSHOT_SCHEMA_VERSION {
+        let mut bytes = Vec::new();
+        GzDecoder::new(std::fs::File::open(path(schema)).unwrap())
+            .read_to_end(&mut bytes)
+            .unwrap();
+        assert_eq!(snapshot_version(&bytes), Some(schema));
+        let mut world =
+            decode_world(&bytes).unwrap_or_else(|e| panic!("schema {schema} fixture: {e:#}"));
+        world.post_load();
+        assert_eq!(world.tick, 40);
+        let hall_of_fame = &world.hall_of_fame.top_living;
+        assert!(!hall_of_fame.is_empty());
+        for (_, entity) in hall_of_fame {
+            let body_size = entity.intel.genotype.body_size;
+            assert!((MIN_BODY_SIZE..=MAX_BODY_SIZE).contains(&body_size));
+        }
+        if schema == 4 {
+            // Organisms from before the body size gene are single cells.
+            assert!(hall_of_fame
+                .iter()
+                .all(|(_, e)| e.intel.genotype.body_size == MIN_BODY_SIZE));
+            let (fitness, best) = &hall_of_fame[0];
+            assert_eq!(*fitness, 79.21052034718025);
+            assert_eq!(best.intel.genotype.max_speed, 1.062003221744353);
+        }
+        let mut env = Environment::default();
+        world.update(&mut env).unwrap();
+    }
 }
@@ -1,6 +1,7 @@
 mod common;
 use common::{EntityBuilder, WorldBuilder};
 use primordium_core::systems::social;
+use primordium_lib::model::brain::GenotypeLogic;
 use primordium_lib::model::config::AppConfig;
 
 #[tokio::test]
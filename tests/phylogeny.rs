@@ -0,0 +1,58 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_lib::model::lineage_tree::LineageTree;
+use primordium_lib::model::persistence;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_speciation_tree_exports_from_a_saved_world() {
+    let mut builder = WorldBuilder::new().with_seed(11).with_config(|c| {
+        c.world.deterministic = true;
+        c.evolution.speciation_threshold = 0.0;
+        c.metabolism.reproduction_threshold = 60.0;
+    });
+    for i in 0..12u128 {
+        builder = builder.with_entity(
+            EntityBuilder::new()
+                .id(Uuid::from_u128(i + 1))
+                .at(10.0 + (i % 4) as f64, 10.0 + (i / 4) as f64)
+                .energy(150.0)
+                .max_energy(200.0)
+                .lineage(Uuid::from_u128(100))
+                .build(),
+        );
+    }
+    let (mut world, mut env) = builder.build();
+    for _ in 0..300 {
+        world.update(&mut env).unwrap();
+    }
+    let speciated = world
+        .lineage_registry
+        .lineages
+        .values()
+        .filter(|r| r.parent_lineage_id.is_some())
+        .count();
+    assert!(speciated > 0, "no lineage split off");
+
+    let path = std::env::temp_dir().join(format!("phylogeny_{}.json", Uuid::new_v4()));
+    persistence::save_world(&mut world, &path).unwrap();
+    let loaded = persistence::load_world(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    let tree = LineageTree::build(&loaded.lineage_registry);
+    assert_eq!(tree.len(), world.lineage_registry.lineages.len());
+
+    let newick = tree.to_newick();
+    assert!(newick.ends_with(";\n"));
+    assert_eq!(newick.matches('(').count(), newick.matches(')').count());
+    // Every lineage is one labelled node.
+    assert_eq!(
+        newick.matches("Lineage-").count(),
+        world.lineage_registry.lineages.len()
+    );
+
+    let xml = tree.to_phyloxml();
+    let clades = xml.matches("<clade>").count();
+    assert_eq!(clades, xml.matches("</clade>").count());
+    assert!(clades >= tree.len());
+}
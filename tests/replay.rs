@@ -0,0 +1,82 @@
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::environment::Environment;
+use primordium_lib::model::replay::{self, ReplayRecorder, Verification};
+use primordium_lib::model::world::World;
+
+fn record(dir: &std::path::Path, ticks: u64, interval: u64) -> World {
+    let mut config = AppConfig::default();
+    config.world.width = 60;
+    config.world.height = 40;
+    config.world.seed = Some(4242);
+    config.world.deterministic = true;
+    let mut world = World::new_ephemeral(40, config).unwrap();
+    let mut env = Environment::default();
+    let mut recorder = ReplayRecorder::create(dir, &mut world, &env, interval).unwrap();
+    for _ in 0..ticks {
+        world.update(&mut env).unwrap();
+        recorder.record(&mut world, &env).unwrap();
+    }
+    world
+}
+
+#[tokio::test]
+async fn test_resimulated_range_matches_recording() {
+    let dir = std::env::temp_dir().join(format!("primordium_replay_{}", uuid::Uuid::new_v4()));
+    record(&dir, 60, 20);
+
+    assert_eq!(
+        replay::resimulate(&dir, 45, 60).unwrap(),
+        Verification::Verified {
+            checkpoint: 40,
+            from: 45,
+            to: 60
+        }
+    );
+    assert_eq!(
+        replay::resimulate(&dir, 1, 10).unwrap(),
+        Verification::Verified {
+            checkpoint: 0,
+            from: 1,
+            to: 10
+        }
+    );
+    assert!(replay::resimulate(&dir, 50, 61).is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_resimulation_reports_the_first_diverging_tick() {
+    let dir = std::env::temp_dir().join(format!("primordium_replay_{}", uuid::Uuid::new_v4()));
+    record(&dir, 30, 20);
+
+    // Tamper with the hash recorded for tick 25.
+    let path = dir.join(replay::STATS_FILE);
+    let stats = std::fs::read_to_string(&path).unwrap();
+    let tampered: Vec<String> = stats
+        .lines()
+        .map(|line| {
+            if line.starts_with("{\"tick\":25,") {
+                "{\"tick\":25,\"hash\":1}".to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    std::fs::write(&path, tampered.join("\n")).unwrap();
+
+    match replay::resimulate(&dir, 22, 30).unwrap() {
+        Verification::Diverged { tick, expected, .. } => {
+            assert_eq!((tick, expected), (25, 1));
+        }
+        other => panic!("expected a divergence, got {other:?}"),
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_recording_needs_a_deterministic_world() {
+    let dir = std::env::temp_dir().join(format!("primordium_replay_{}", uuid::Uuid::new_v4()));
+    let mut world = World::new_ephemeral(5, AppConfig::default()).unwrap();
+    let env = Environment::default();
+    assert!(ReplayRecorder::create(&dir, &mut world, &env, 10).is_err());
+}
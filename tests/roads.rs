@@ -0,0 +1,127 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_core::systems::civilization;
+use primordium_data::EntityStatus;
+use primordium_lib::model::influence::InfluenceGrid;
+use primordium_lib::model::road::RoadGrid;
+use primordium_lib::model::snapshot::InternalEntitySnapshot;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const WALKERS: Uuid = Uuid::from_u128(1);
+const STRANGERS: Uuid = Uuid::from_u128(2);
+
+fn walker(lineage_id: Uuid, x: f64) -> InternalEntitySnapshot {
+    InternalEntitySnapshot {
+        id: Uuid::new_v4(),
+        lineage_id,
+        parent_id: None,
+        x,
+        y: 5.5,
+        vx: 1.0,
+        vy: 0.0,
+        energy: 100.0,
+        birth_tick: 0,
+        offspring_count: 0,
+        generation: 1,
+        max_energy: 200.0,
+        r: 0,
+        g: 0,
+        b: 0,
+        rank: 0.0,
+        status: EntityStatus::Foraging,
+        trophic_potential: 0.5,
+        genotype: None,
+        memory_writes: 0,
+        memory_recalls: 0,
+        deceptions: 0,
+        infection_remaining: 0,
+        dominance: 0.0,
+    }
+}
+
+#[test]
+fn test_repeated_traffic_wears_a_road_that_strangers_erode() {
+    let config = primordium_lib::model::config::AppConfig::default().roads;
+    let influence = InfluenceGrid::new(20, 20);
+    let mut roads = RoadGrid::new(20, 20);
+
+    for _ in 0..40 {
+        roads.update(&[walker(WALKERS, 5.5)], &influence, &config);
+    }
+    let (owner, wear) = roads.get_road(5.5, 5.5);
+    assert_eq!(owner, Some(WALKERS));
+    assert!(wear > 0.5);
+    assert!(roads.speed_factor(5.5, 5.5, WALKERS, &config) > 1.1);
+    assert_eq!(roads.speed_factor(5.5, 5.5, STRANGERS, &config), 1.0);
+
+    for _ in 0..60 {
+        roads.update(&[walker(STRANGERS, 5.5)], &influence, &config);
+    }
+    assert_eq!(roads.get_road(5.5, 5.5).0, Some(STRANGERS));
+}
+
+#[test]
+fn test_entities_move_faster_on_their_own_roads() {
+    let travelled = |road: bool| {
+        let e = EntityBuilder::new()
+            .id(Uuid::from_u128(7))
+            .at(10.5, 10.5)
+            .energy(200.0)
+            .lineage(WALKERS)
+            .build();
+        let (mut world, mut env) = WorldBuilder::new()
+            .with_config(|c| {
+                c.world.deterministic = true;
+                c.roads.speed_bonus = 1.0;
+            })
+            .with_entity(e)
+            .build();
+        if road {
+            let roads = Arc::make_mut(&mut world.roads);
+            for cell in &mut roads.cells {
+                cell.lineage = Some(WALKERS);
+                cell.wear = 1.0;
+            }
+        }
+
+        world.update(&mut env).unwrap();
+        let p = world.get_all_entities()[0].position;
+        (p.x - 10.5).hypot(p.y - 10.5)
+    };
+
+    assert!(travelled(true) > travelled(false) * 1.5);
+}
+
+#[test]
+fn test_worn_roads_carry_outpost_supply_lines() {
+    let (mut world, _env) = WorldBuilder::new()
+        .with_outpost(10, 10, WALKERS)
+        .with_outpost(14, 10, WALKERS)
+        .build();
+    world.lineage_registry.record_birth(WALKERS, 0, 0);
+    if let Some(record) = world.lineage_registry.lineages.get_mut(&WALKERS) {
+        record.civilization_level = 2;
+    }
+    let links = |world: &primordium_lib::model::world::World| {
+        civilization::outpost_network(
+            &world.terrain,
+            world.width,
+            world.height,
+            &world.lineage_registry,
+            &world.roads,
+            &world.config.roads,
+        )
+        .links
+        .len()
+    };
+    assert_eq!(links(&world), 0);
+
+    let roads = Arc::make_mut(&mut world.roads);
+    for x in 11..14 {
+        let idx = 10 * roads.width as usize + x;
+        roads.cells[idx].lineage = Some(WALKERS);
+        roads.cells[idx].wear = 1.0;
+    }
+    assert_eq!(links(&world), 1);
+}
@@ -1,4 +1,5 @@
 use primordium_core::systems::stats;
+use primordium_lib::model::brain::GenotypeLogic;
 use primordium_lib::model::config::AppConfig;
 use primordium_lib::model::lifecycle;
 use primordium_lib::model::world::World;
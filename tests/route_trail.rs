@@ -0,0 +1,41 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+
+#[tokio::test]
+async fn test_route_trail_follows_selected_entity() {
+    let wanderer = EntityBuilder::new().at(20.0, 20.0).energy(500.0).build();
+    let id = wanderer.identity.id;
+
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_config(|c| c.analytics.trail_length = 5)
+        .with_entity(wanderer)
+        .build();
+
+    world.track_route(Some(id));
+    for _ in 0..8 {
+        world.update(&mut env).expect("Update failed");
+    }
+
+    let trail = world.route_trail.as_ref().expect("trail is active");
+    assert_eq!(trail.len(), 5);
+    let ticks: Vec<u64> = trail.points().iter().map(|p| p.tick).collect();
+    assert_eq!(ticks, vec![4, 5, 6, 7, 8]);
+
+    let (x, y) = world
+        .get_all_entities()
+        .iter()
+        .find(|e| e.identity.id == id)
+        .map(|e| (e.position.x, e.position.y))
+        .expect("tracked entity survives");
+    let last = trail.points().back().expect("trail has points");
+    assert_eq!((last.x, last.y), (x, y));
+
+    let csv = trail.to_csv();
+    assert!(csv.starts_with("tick,x,y\n"));
+    assert_eq!(csv.lines().count(), 6);
+
+    world.track_route(Some(id));
+    assert_eq!(world.route_trail.as_ref().map(|t| t.len()), Some(5));
+    world.track_route(None);
+    assert!(world.route_trail.is_none());
+}
@@ -0,0 +1,75 @@
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::state::environment::Environment;
+use primordium_lib::model::world::scenario::ScenarioEvent;
+use primordium_lib::model::world::World;
+
+#[test]
+fn test_scenario_events_parse_single_and_array() {
+    let single = ScenarioEvent::parse_many(r#"{"event": "food_boom", "amount": 5}"#).unwrap();
+    assert_eq!(single, vec![ScenarioEvent::FoodBoom { amount: 5 }]);
+
+    let many = ScenarioEvent::parse_many(
+        r#"[{"event": "carbon", "level": 900},
+            {"event": "climate", "state": null},
+            {"event": "outbreak", "x": 10, "y": 10}]"#,
+    )
+    .unwrap();
+    assert_eq!(many.len(), 3);
+    assert_eq!(
+        many[2],
+        ScenarioEvent::Outbreak {
+            x: Some(10.0),
+            y: Some(10.0),
+            radius: 10.0,
+        }
+    );
+    assert!(ScenarioEvent::parse_many(r#"{"event": "meteor"}"#).is_err());
+}
+
+#[test]
+fn test_scenario_events_apply_to_world() {
+    let mut world = World::new_ephemeral(10, AppConfig::default()).unwrap();
+    let mut env = Environment::default();
+    let dna =
+        primordium_lib::model::brain::create_genotype_random_with_rng(&mut rand::thread_rng())
+            .to_hex();
+
+    let before = world.get_population_count();
+    world
+        .apply_scenario_event(
+            &mut env,
+            &ScenarioEvent::Spawn {
+                dna,
+                count: 3,
+                x: Some(5.0),
+                y: Some(5.0),
+            },
+        )
+        .unwrap();
+    assert_eq!(world.get_population_count(), before + 3);
+
+    world
+        .apply_scenario_event(&mut env, &ScenarioEvent::Carbon { level: 5000.0 })
+        .unwrap();
+    assert_eq!(env.carbon_level, 2000.0);
+
+    let msg = world
+        .apply_scenario_event(
+            &mut env,
+            &ScenarioEvent::Outbreak {
+                x: Some(5.0),
+                y: Some(5.0),
+                radius: 0.0,
+            },
+        )
+        .unwrap();
+    assert!(msg.contains("infected 3"), "{msg}");
+
+    let bad = ScenarioEvent::Spawn {
+        dna: "zz".to_string(),
+        count: 1,
+        x: None,
+        y: None,
+    };
+    assert!(world.apply_scenario_event(&mut env, &bad).is_err());
+}
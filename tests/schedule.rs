@@ -0,0 +1,86 @@
+use primordium_data::{Disaster, DisasterRecord};
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::schedule::EventKind;
+use primordium_lib::model::state::environment::Environment;
+use primordium_lib::model::world::scenario::ScenarioEvent;
+use primordium_lib::model::world::World;
+
+fn quiet_world() -> World {
+    let mut config = AppConfig::default();
+    config.world.initial_population = 0;
+    config.world.disaster_chance = 0.0;
+    config.world.fossil_interval = 20;
+    config.world.power_grid_interval = 10;
+    config.seasons.duration = 15;
+    config.seasons.transition = 0;
+    World::new(0, config).unwrap()
+}
+
+#[tokio::test]
+async fn test_scenario_disasters_wait_on_the_calendar() {
+    let mut world = quiet_world();
+    let mut env = Environment::default();
+    let flare = Disaster::SolarFlare { duration: 300 };
+    let event = ScenarioEvent::parse_many(
+        r#"{"event": "disaster", "disaster": {"kind": "SolarFlare", "duration": 300}, "delay": 5}"#,
+    )
+    .unwrap();
+    world.apply_scenario_event(&mut env, &event[0]).unwrap();
+
+    assert_eq!(env.schedule.upcoming()[0].tick, 5);
+    for _ in 0..4 {
+        world.update(&mut env).unwrap();
+    }
+    assert!(world.disasters.is_empty());
+
+    world.update(&mut env).unwrap();
+    assert_eq!(
+        world.disasters,
+        vec![DisasterRecord {
+            tick: 5,
+            disaster: flare,
+        }]
+    );
+    assert!(env
+        .schedule
+        .upcoming()
+        .iter()
+        .all(|e| !matches!(e.kind, EventKind::Disaster { .. })));
+    let latest = env.schedule.recent().next().unwrap();
+    assert_eq!(latest.tick, 5);
+    assert_eq!(latest.kind, EventKind::Disaster { disaster: flare });
+}
+
+#[tokio::test]
+async fn test_recurring_events_are_listed_and_remembered() {
+    let mut world = quiet_world();
+    let mut env = Environment::default();
+    world.update(&mut env).unwrap();
+
+    let next = |env: &Environment, kind: EventKind| {
+        env.schedule
+            .upcoming()
+            .iter()
+            .find(|e| e.kind == kind)
+            .map(|e| e.tick)
+    };
+    assert_eq!(next(&env, EventKind::PowerGrid), Some(10));
+    assert_eq!(next(&env, EventKind::SeasonChange), Some(15));
+    assert_eq!(next(&env, EventKind::Fossil), Some(20));
+    let ticks: Vec<u64> = env.schedule.upcoming().iter().map(|e| e.tick).collect();
+    assert!(ticks.is_sorted());
+
+    for _ in 1..20 {
+        world.update(&mut env).unwrap();
+    }
+    assert_eq!(next(&env, EventKind::PowerGrid), Some(30));
+    assert_eq!(next(&env, EventKind::SeasonChange), Some(30));
+    assert_eq!(next(&env, EventKind::Fossil), Some(40));
+
+    // Only the latest of each recurring event is kept.
+    let recent: Vec<(EventKind, u64)> = env.schedule.recent().map(|e| (e.kind, e.tick)).collect();
+    assert_eq!(recent.len(), 3);
+    assert!(recent.contains(&(EventKind::PowerGrid, 20)));
+    assert!(recent.contains(&(EventKind::Fossil, 20)));
+    assert!(recent.contains(&(EventKind::SeasonChange, 15)));
+}
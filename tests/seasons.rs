@@ -0,0 +1,103 @@
+mod common;
+use common::WorldBuilder;
+use primordium_core::systems::environment as environment_system;
+use primordium_data::{LiveEvent, TerrainType};
+use primordium_lib::model::config::AppConfig;
+use primordium_lib::model::environment::{ClimateState, Environment, Season};
+use primordium_lib::model::terrain::TerrainGrid;
+
+#[tokio::test]
+async fn test_seasons_turn_and_are_announced() {
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_seed(3)
+        .with_config(|c| {
+            c.seasons.duration = 5;
+            c.seasons.transition = 0;
+        })
+        .build();
+
+    let mut turned = Vec::new();
+    for _ in 0..12 {
+        for event in world.update(&mut env).expect("Update failed") {
+            if let LiveEvent::SeasonChanged { from, to, tick, .. } = event {
+                turned.push((from, to, tick));
+            }
+        }
+    }
+
+    assert_eq!(
+        turned,
+        vec![
+            ("Spring".to_string(), "Summer".to_string(), 5),
+            ("Summer".to_string(), "Fall".to_string(), 10),
+        ]
+    );
+    assert_eq!(env.current_season, Season::Fall);
+    assert_eq!(env.season_modifiers, world.config.seasons.fall);
+}
+
+#[test]
+fn test_season_modifiers_blend_through_the_transition() {
+    let config = AppConfig::default().seasons;
+    let mut env = Environment::default();
+
+    environment_system::update_season(&mut env, config.duration - config.transition, &config);
+    assert_eq!(env.season_modifiers, config.spring);
+
+    environment_system::update_season(&mut env, config.duration - config.transition / 2, &config);
+    let food = env.current_food_multiplier();
+    assert!(food < config.spring.food && food > config.summer.food);
+
+    let entered = environment_system::update_season(&mut env, config.duration, &config);
+    assert_eq!(entered, Some(Season::Summer));
+    assert_eq!(env.season_modifiers, config.summer);
+}
+
+#[test]
+fn test_winter_cools_the_climate_and_starves_the_land() {
+    let config = AppConfig::default().seasons;
+    let mut env = Environment {
+        cpu_usage: 65.0,
+        ..Environment::default()
+    };
+    environment_system::update_season(&mut env, 0, &config);
+    assert_eq!(env.climate(), ClimateState::Hot);
+    let spring_food = env.food_spawn_multiplier();
+
+    environment_system::update_season(&mut env, config.duration * 3, &config);
+    assert_eq!(env.current_season, Season::Winter);
+    assert_eq!(env.climate(), ClimateState::Warm);
+    assert!(env.food_spawn_multiplier() < spring_food);
+}
+
+#[test]
+fn test_disabled_seasons_have_no_effect() {
+    let mut config = AppConfig::default().seasons;
+    config.enabled = false;
+    let mut env = Environment::default();
+
+    assert_eq!(
+        environment_system::update_season(&mut env, config.duration * 3, &config),
+        None
+    );
+    assert_eq!(env.current_season, Season::Spring);
+    assert_eq!(env.current_food_multiplier(), 1.0);
+}
+
+#[test]
+fn test_fertility_regrowth_follows_the_season() {
+    let mut spring = TerrainGrid::generate(20, 20, 5);
+    for y in 0..20 {
+        for x in 0..20 {
+            spring.set_cell_type(x, y, TerrainType::Plains);
+            spring.set_fertility(x, y, 0.3);
+        }
+    }
+    let mut winter = spring.clone();
+
+    spring.update(0.0, 1.25, 1, 5);
+    winter.update(0.0, 0.4, 1, 5);
+
+    let total = |t: &TerrainGrid| t.cells().map(|c| f64::from(c.fertility)).sum::<f64>();
+    assert!(total(&spring) > total(&winter));
+}
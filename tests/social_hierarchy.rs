@@ -147,7 +147,7 @@ async fn test_soldier_damage_bonus() {
         brain.connections.clear();
         brain.connections.push(Connection {
             from: 2,
-            to: 32, // Aggro
+            to: primordium_lib::model::brain::BRAIN_INPUTS + 3, // Aggro
             weight: 10.0,
             enabled: true,
             innovation: 9999,
@@ -74,22 +74,26 @@ async fn test_metabolic_cost_of_signaling() {
 
     // quiet: [x, y, speed, aggro, share, signal, emitA, emitB]
     let pressure = primordium_lib::model::pressure::PressureGrid::new(100, 100);
+    let pheromones = primordium_lib::model::pheromone::PheromoneGrid::new(100, 100);
     let influence = primordium_lib::model::influence::InfluenceGrid::new(100, 100);
+    let roads = primordium_lib::model::road::RoadGrid::new(100, 100);
     let mut ctx_q = ActionContext {
         env: &env,
         config: &config,
         terrain: &terrain,
         influence: &influence,
+        roads: &roads,
         snapshots: &[],
         entity_id_map: &std::collections::HashMap::new(),
         spatial_hash: &primordium_lib::model::spatial_hash::SpatialHash::new(5.0, 100, 100),
         pressure: &pressure,
+        pheromones: &pheromones,
         width: 100,
         height: 100,
     };
     {
         let mut out = ActionOutput::default();
-        action_system(&mut e_quiet, [0.0; 12], &mut ctx_q, &mut out);
+        action_system(&mut e_quiet, [0.0; 15], &mut ctx_q, &mut out);
         out
     };
 
@@ -99,17 +103,21 @@ async fn test_metabolic_cost_of_signaling() {
         config: &config,
         terrain: &terrain,
         influence: &influence,
+        roads: &roads,
         snapshots: &[],
         entity_id_map: &std::collections::HashMap::new(),
         spatial_hash: &primordium_lib::model::spatial_hash::SpatialHash::new(5.0, 100, 100),
         pressure: &pressure,
+        pheromones: &pheromones,
         width: 100,
         height: 100,
     };
     let mut out_l = ActionOutput::default();
     action_system(
         &mut e_loud,
-        [0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        [
+            0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+        ],
         &mut ctx_l,
         &mut out_l,
     );
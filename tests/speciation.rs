@@ -11,6 +11,7 @@ async fn test_longitudinal_genetic_divergence() {
         c.evolution.mutation_amount = 2.0; // Large jumps
         c.world.max_food = 100;
         c.metabolism.maturity_age = 50; // Fast generations
+        c.social.tribe_color_threshold = i32::MAX; // No predation between diverging lineages
     });
 
     let ancestor_id = uuid::Uuid::new_v4();
@@ -117,7 +118,7 @@ async fn test_reproductive_isolation_emergence() {
         let brain = &mut Arc::make_mut(&mut e1.intel.genotype).brain;
         brain.connections = vec![Connection {
             from: 0,
-            to: 29,
+            to: primordium_lib::model::brain::BRAIN_INPUTS,
             weight: 5.0,
             enabled: true,
             innovation: 1,
@@ -135,7 +136,7 @@ async fn test_reproductive_isolation_emergence() {
         let brain = &mut Arc::make_mut(&mut e2.intel.genotype).brain;
         brain.connections = vec![Connection {
             from: 0,
-            to: 29,
+            to: primordium_lib::model::brain::BRAIN_INPUTS,
             weight: -5.0,
             enabled: true,
             innovation: 1,
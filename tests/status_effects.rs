@@ -0,0 +1,171 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_core::systems::{biological, status};
+use primordium_data::{Health, Pathogen, Stacking, StatusEffect, StatusKind, TerrainType};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use uuid::Uuid;
+
+fn effect(kind: StatusKind, magnitude: f32, remaining: u32, stacking: Stacking) -> StatusEffect {
+    StatusEffect {
+        kind,
+        magnitude,
+        remaining,
+        stacking,
+    }
+}
+
+fn pathogen(lethality: f32, duration: u32) -> Pathogen {
+    Pathogen {
+        id: Uuid::from_u128(1),
+        lethality,
+        transmission: 1.0,
+        duration,
+        virulence: 1.0,
+        behavior_manipulation: None,
+    }
+}
+
+fn health_of(world: &primordium_lib::model::world::World) -> Health {
+    world
+        .ecs
+        .query::<&Health>()
+        .iter()
+        .next()
+        .map(|(_, h)| h.clone())
+        .unwrap()
+}
+
+#[test]
+fn test_stacking_policies() {
+    let mut effects = Vec::new();
+    let kind = StatusKind::Blessing;
+
+    status::apply(&mut effects, effect(kind, 0.5, 10, Stacking::Ignore));
+    status::apply(&mut effects, effect(kind, 0.9, 50, Stacking::Ignore));
+    assert_eq!(effects, vec![effect(kind, 0.5, 10, Stacking::Ignore)]);
+
+    status::apply(&mut effects, effect(kind, 0.2, 30, Stacking::Refresh));
+    assert_eq!(effects, vec![effect(kind, 0.2, 30, Stacking::Refresh)]);
+
+    status::apply(&mut effects, effect(kind, 0.1, 99, Stacking::Strongest));
+    assert_eq!(effects[0].magnitude, 0.2);
+    status::apply(&mut effects, effect(kind, 0.4, 5, Stacking::Strongest));
+    assert_eq!(effects[0].magnitude, 0.4);
+
+    let mut stacked = Vec::new();
+    for remaining in [5, 3, 8] {
+        status::apply(
+            &mut stacked,
+            effect(kind, 0.1, remaining, Stacking::Stack { max: 2 }),
+        );
+    }
+    let timers: Vec<u32> = stacked.iter().map(|e| e.remaining).collect();
+    assert_eq!(timers, vec![5, 8]);
+    assert!((status::magnitude(&stacked, kind) - 0.2).abs() < 1e-6);
+}
+
+#[test]
+fn test_infection_drains_then_wears_off_into_immunity() {
+    let mut entity = EntityBuilder::new().energy(100.0).build();
+    biological::infect_components(&mut entity.health, &pathogen(2.0, 3));
+
+    for tick in 0..3 {
+        assert!(entity.health.pathogen.is_some(), "still infected at {tick}");
+        let drained = status::process_status_effects(&mut entity.health, &mut entity.metabolism);
+        assert_eq!(drained, 2.0);
+    }
+    assert_eq!(entity.metabolism.energy, 94.0);
+    assert!(entity.health.pathogen.is_none());
+    assert!(entity.health.effects.is_empty());
+    assert!(entity.health.immunity > 0.0);
+}
+
+#[test]
+fn test_pathogen_without_effect_starts_an_infection() {
+    let mut entity = EntityBuilder::new().build();
+    entity.health.pathogen = Some(pathogen(0.0, 10));
+    status::process_status_effects(&mut entity.health, &mut entity.metabolism);
+    let infection = &entity.health.effects[0];
+    assert_eq!(infection.kind, StatusKind::Infection);
+    assert_eq!(infection.remaining, 9);
+}
+
+#[test]
+fn test_radiation_storms_leave_lingering_exposure() {
+    let (mut world, mut env) = WorldBuilder::new()
+        .with_entity(EntityBuilder::new().at(20.0, 20.0).energy(200.0).build())
+        .build();
+    env.radiation_timer = 100;
+    world.update(&mut env).unwrap();
+    let radiation = health_of(&world)
+        .effects
+        .into_iter()
+        .find(|e| e.kind == StatusKind::Radiation)
+        .expect("storm should irradiate");
+    assert_eq!(radiation.remaining, status::RADIATION_EXPOSURE_TICKS - 1);
+
+    env.radiation_timer = 0;
+    world.update(&mut env).unwrap();
+    assert!(status::has(
+        &health_of(&world).effects,
+        StatusKind::Radiation
+    ));
+}
+
+#[test]
+fn test_nest_shield_lowers_idle_cost() {
+    let run = |nest: bool| {
+        let mut builder = WorldBuilder::new()
+            .with_config(|c| c.world.deterministic = true)
+            .with_entity(EntityBuilder::new().at(20.5, 20.5).energy(200.0).build());
+        if nest {
+            builder = builder.with_terrain(20, 20, TerrainType::Nest);
+        }
+        let (mut world, mut env) = builder.build();
+        world.update(&mut env).unwrap();
+        let energy = world
+            .ecs
+            .query::<&primordium_data::Metabolism>()
+            .iter()
+            .next()
+            .map(|(_, m)| m.energy)
+            .unwrap();
+        energy
+    };
+    assert!(run(true) > run(false));
+
+    let mut effects = Vec::new();
+    status::apply(&mut effects, status::nest_shield(0.3));
+    status::apply(&mut effects, status::nest_shield(0.1));
+    assert_eq!(status::magnitude(&effects, StatusKind::NestShield), 0.3);
+}
+
+#[test]
+fn test_relief_blesses_the_lineage() {
+    let lineage = Uuid::from_u128(5);
+    let (mut world, _) = WorldBuilder::new()
+        .with_entity(EntityBuilder::new().lineage(lineage).energy(50.0).build())
+        .build();
+    world.apply_relief(lineage, 10.0);
+    let health = health_of(&world);
+    assert_eq!(
+        status::magnitude(&health.effects, StatusKind::Blessing),
+        status::BLESSING_IMMUNITY
+    );
+
+    // A blessing lifts resistance enough to shrug off a weak pathogen.
+    let mut rng = ChaCha8Rng::seed_from_u64(11);
+    let mut blessed = health.clone();
+    let weak = Pathogen {
+        virulence: 0.4,
+        ..pathogen(0.0, 10)
+    };
+    let infections = (0..200)
+        .filter(|_| {
+            blessed.pathogen = None;
+            biological::try_infect_components(&mut blessed, &weak, &mut rng)
+        })
+        .count();
+    assert!(infections < 20);
+}
@@ -0,0 +1,125 @@
+use primordium_core::systems::action::steering::{
+    self, SteeringForces, SteeringWeights, ARRIVAL_DISTANCE, WANDER_JITTER,
+};
+
+fn close(a: (f64, f64), b: (f64, f64)) -> bool {
+    (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9
+}
+
+#[test]
+fn test_seek_eases_off_on_arrival() {
+    assert!(close(steering::seek((0.0, 0.0), (10.0, 0.0)), (1.0, 0.0)));
+    assert!(close(steering::seek((0.0, 0.0), (0.0, 0.8)), (0.0, 0.8)));
+    assert!(close(
+        steering::seek((0.0, 0.0), (ARRIVAL_DISTANCE, 0.0)),
+        (0.0, 0.0)
+    ));
+}
+
+#[test]
+fn test_flee_points_away_from_threat() {
+    let force = steering::flee((5.0, 5.0), (2.0, 1.0));
+    assert!((steering::length(force) - 1.0).abs() < 1e-9);
+    assert!(close(force, (0.6, 0.8)));
+    assert!(close(steering::flee((1.0, 1.0), (1.0, 1.0)), (0.0, 0.0)));
+}
+
+#[test]
+fn test_wander_is_seeded_and_stays_near_heading() {
+    let heading = (1.0, 0.0);
+    for seed in [0, 1, 4_999, 9_999, 123_456_789] {
+        let force = steering::wander(heading, seed);
+        assert!((steering::length(force) - 1.0).abs() < 1e-9);
+        assert!(force.1.atan2(force.0).abs() <= WANDER_JITTER + 1e-9);
+        assert_eq!(force, steering::wander(heading, seed));
+    }
+    assert_ne!(
+        steering::wander(heading, 1),
+        steering::wander(heading, 7_000)
+    );
+    // Standing still, any direction is possible.
+    let still = steering::wander((0.0, 0.0), 5_000);
+    assert!(close(still, (-1.0, 0.0)));
+}
+
+#[test]
+fn test_follow_flow_samples_field_at_position() {
+    let field = |x: f64, _y: f64| (x >= 0.0).then_some((0.0, 3.0));
+    assert!(close(steering::follow_flow((2.0, 2.0), field), (0.0, 1.0)));
+    assert!(close(steering::follow_flow((-2.0, 2.0), field), (0.0, 0.0)));
+}
+
+#[test]
+fn test_separation_weights_by_proximity() {
+    let near = steering::separation((0.0, 0.0), [(0.5, 0.0)], 2.0, (0.0, 1.0));
+    let far = steering::separation((0.0, 0.0), [(1.5, 0.0)], 2.0, (0.0, 1.0));
+    assert!(close(near, (-0.75, 0.0)));
+    assert!(close(far, (-0.25, 0.0)));
+    assert!(close(
+        steering::separation((0.0, 0.0), [(3.0, 0.0)], 2.0, (0.0, 1.0)),
+        (0.0, 0.0)
+    ));
+    // Overlapping neighbours push along the given direction at half strength.
+    assert!(close(
+        steering::separation((0.0, 0.0), [(0.0, 0.0), (0.0, 0.0)], 2.0, (0.0, 1.0)),
+        (0.0, 1.0)
+    ));
+}
+
+#[test]
+fn test_cohesion_and_alignment_follow_the_group() {
+    assert!(close(
+        steering::cohesion((0.0, 0.0), (0.0, -4.0)),
+        (0.0, -1.0)
+    ));
+    assert!(close(
+        steering::alignment((0.2, 0.0), (0.2, 0.5)),
+        (0.0, 0.5)
+    ));
+    let turn = steering::alignment((1.0, 0.0), (-1.0, 0.0));
+    assert!(close(turn, (-1.0, 0.0)));
+}
+
+#[test]
+fn test_weights_follow_brain_outputs() {
+    let mut outputs = [0.0f32; 15];
+    outputs[0] = 1.0;
+    outputs[3] = 1.0;
+    outputs[4] = -1.0;
+    outputs[6] = 1.0;
+    outputs[7] = 1.0;
+    let hunter = SteeringWeights::from_outputs(&outputs);
+    assert_eq!(hunter.flee, 0.0);
+    assert_eq!(hunter.separation, 1.0);
+    assert_eq!(hunter.wander, 0.0);
+    assert_eq!(hunter.cohesion, 0.0);
+    assert_eq!(hunter.alignment, 1.0);
+
+    let idle = SteeringWeights::from_outputs(&[0.0; 15]);
+    assert_eq!(idle.wander, 1.0);
+    assert_eq!(idle.flee, 0.5);
+    assert_eq!(idle.cohesion, 0.5);
+}
+
+#[test]
+fn test_combine_blends_only_selected_behaviours() {
+    let forces = SteeringForces {
+        flee: (1.0, 0.0),
+        wander: (0.0, 1.0),
+        cohesion: (-1.0, 0.0),
+        ..SteeringForces::default()
+    };
+    let timid = SteeringWeights {
+        flee: 1.0,
+        ..SteeringWeights::default()
+    };
+    assert!(close(timid.combine(&forces, 0.5), (0.5, 0.0)));
+
+    let social = SteeringWeights {
+        flee: 0.5,
+        wander: 0.5,
+        cohesion: 1.0,
+        ..SteeringWeights::default()
+    };
+    assert!(close(social.combine(&forces, 1.0), (-0.5, 0.5)));
+}
@@ -71,21 +71,27 @@ async fn test_high_speed_metabolic_exhaustion() {
 
     let terrain = primordium_lib::model::terrain::TerrainGrid::generate(100, 100, 42);
     let pressure = primordium_lib::model::pressure::PressureGrid::new(100, 100);
+    let pheromones = primordium_lib::model::pheromone::PheromoneGrid::new(100, 100);
     let influence = primordium_lib::model::influence::InfluenceGrid::new(100, 100);
+    let roads = primordium_lib::model::road::RoadGrid::new(100, 100);
     let mut ctx = ActionContext {
         env: &env,
         config: &config,
         terrain: &terrain,
         influence: &influence,
+        roads: &roads,
         snapshots: &[],
         entity_id_map: &std::collections::HashMap::new(),
         spatial_hash: &primordium_lib::model::spatial_hash::SpatialHash::new(5.0, 100, 100),
         pressure: &pressure,
+        pheromones: &pheromones,
         width: 100,
         height: 100,
     };
 
-    let outputs = [0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+    let outputs = [
+        0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ];
 
     {
         let mut out = ActionOutput::default();
@@ -116,21 +122,27 @@ async fn test_inertia_responsiveness_penalty() {
     e_heavy.velocity.vx = 0.0;
 
     let pressure = primordium_lib::model::pressure::PressureGrid::new(100, 100);
+    let pheromones = primordium_lib::model::pheromone::PheromoneGrid::new(100, 100);
     let influence = primordium_lib::model::influence::InfluenceGrid::new(100, 100);
+    let roads = primordium_lib::model::road::RoadGrid::new(100, 100);
     let mut ctx = ActionContext {
         env: &env,
         config: &config,
         terrain: &terrain,
         influence: &influence,
+        roads: &roads,
         snapshots: &[],
         entity_id_map: &std::collections::HashMap::new(),
         spatial_hash: &primordium_lib::model::spatial_hash::SpatialHash::new(5.0, 100, 100),
         pressure: &pressure,
+        pheromones: &pheromones,
         width: 100,
         height: 100,
     };
 
-    let outputs = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+    let outputs = [
+        1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ];
     {
         let mut out = ActionOutput::default();
         action_system(&mut e_light, outputs, &mut ctx, &mut out);
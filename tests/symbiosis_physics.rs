@@ -14,8 +14,11 @@ async fn test_symbiosis_spring_force() {
     let snapshot = InternalEntitySnapshot {
         id: e2.identity.id,
         lineage_id: e2.metabolism.lineage_id,
+        parent_id: e2.identity.parent_id,
         x: e2.position.x,
         y: e2.position.y,
+        vx: e2.velocity.vx,
+        vy: e2.velocity.vy,
         energy: 100.0,
         birth_tick: 0,
         offspring_count: 0,
@@ -28,6 +31,11 @@ async fn test_symbiosis_spring_force() {
         status: primordium_lib::model::state::entity::EntityStatus::Bonded,
         trophic_potential: 0.5,
         genotype: Some(e2.intel.genotype.clone()),
+        memory_writes: 0,
+        memory_recalls: 0,
+        deceptions: 0,
+        infection_remaining: 0,
+        dominance: 0.0,
     };
 
     let env = Environment::default();
@@ -48,22 +56,26 @@ async fn test_symbiosis_spring_force() {
     id_map.insert(e2.identity.id, 0);
 
     let influence = primordium_lib::model::influence::InfluenceGrid::new(100, 100);
+
+    let roads = primordium_lib::model::road::RoadGrid::new(100, 100);
     let mut ctx = ActionContext {
         env: &env,
         config: &config,
         terrain: &terrain,
         influence: &influence,
+        roads: &roads,
         snapshots: &[snapshot],
         entity_id_map: &id_map,
         spatial_hash: &primordium_lib::model::spatial_hash::SpatialHash::new(5.0, 100, 100),
         pressure: &primordium_lib::model::pressure::PressureGrid::new(100, 100),
+        pheromones: &primordium_lib::model::pheromone::PheromoneGrid::new(100, 100),
         width: 100,
         height: 100,
     };
 
     // Outputs: Neutral movement (should stay still if no spring)
     // outputs[0] (dx) = 0.0 -> target vx 0.0
-    let outputs = [0.0; 12];
+    let outputs = [0.0; 15];
 
     e1.velocity.vx = 0.0;
     e1.velocity.vy = 0.0;
@@ -0,0 +1,94 @@
+mod common;
+use common::{EntityBuilder, WorldBuilder};
+use primordium_data::TagSource;
+use primordium_lib::model::persistence;
+use uuid::Uuid;
+
+fn ids(n: u128) -> Vec<Uuid> {
+    (1..=n).map(Uuid::from_u128).collect()
+}
+
+fn world_of(n: u128) -> primordium_lib::model::world::World {
+    let mut builder = WorldBuilder::new();
+    for (i, id) in ids(n).into_iter().enumerate() {
+        builder = builder.with_entity(
+            EntityBuilder::new()
+                .id(id)
+                .at(10.0 + 2.0 * i as f64, 10.0)
+                .energy(50.0 + 10.0 * i as f64)
+                .build(),
+        );
+    }
+    builder.build().0
+}
+
+#[test]
+fn test_tag_and_untag() {
+    let mut world = world_of(3);
+    let [a, b, c] = ids(3)[..] else {
+        unreachable!()
+    };
+
+    assert!(world.tag_entity(a, "protagonist", TagSource::User));
+    assert!(!world.tag_entity(a, "protagonist", TagSource::User));
+    assert!(world.tag_entity(a, "study-group-A", TagSource::System));
+    assert!(world.tag_entity(c, "study-group-A", TagSource::System));
+    assert!(!world.tag_entity(Uuid::from_u128(99), "ghost", TagSource::User));
+    assert!(!world.tag_entity(b, "  ", TagSource::User));
+
+    let tags = world.tags_of(a);
+    assert!(tags.user.contains("protagonist"));
+    assert!(tags.system.contains("study-group-A"));
+    assert_eq!(world.entities_tagged("study-group-A"), vec![a, c]);
+    assert!(world.entities_tagged("protagonist").contains(&a));
+
+    assert!(world.untag_entity(a, "protagonist"));
+    assert!(!world.untag_entity(a, "protagonist"));
+    assert!(world.untag_entity(c, "study-group-A"));
+    assert!(world.tags_of(c).is_empty());
+    assert_eq!(world.entities_tagged("study-group-A"), vec![a]);
+}
+
+#[test]
+fn test_snapshot_carries_tags_and_cohort_stats() {
+    let mut world = world_of(3);
+    let [a, _, c] = ids(3)[..] else {
+        unreachable!()
+    };
+    world.tag_entity(a, "cohort", TagSource::System);
+    world.tag_entity(c, "cohort", TagSource::System);
+    world.tag_entity(c, "protagonist", TagSource::User);
+
+    let snapshot = world.create_snapshot(None);
+    let tagged_c = snapshot.entities.iter().find(|e| e.id == c).unwrap();
+    assert_eq!(tagged_c.tags, vec!["protagonist", "cohort"]);
+
+    let stats = snapshot.tag_stats("cohort").unwrap();
+    assert_eq!(stats.count, 2);
+    assert_eq!(stats.mean_energy, 60.0);
+    assert_eq!(stats.centroid, (12.0, 10.0));
+    assert_eq!(stats.bounds, (10.0, 10.0, 14.0, 10.0));
+    assert!(snapshot.tag_stats("nobody").is_none());
+}
+
+#[test]
+fn test_tags_survive_save_and_load() {
+    let mut world = world_of(3);
+    let [a, b, _] = ids(3)[..] else {
+        unreachable!()
+    };
+    world.tag_entity(a, "protagonist", TagSource::User);
+    world.tag_entity(b, "study-group-A", TagSource::System);
+
+    let path = std::env::temp_dir().join(format!("tags_{}.json", Uuid::new_v4()));
+    persistence::save_world(&mut world, &path).unwrap();
+    assert!(world.entity_persist.is_empty());
+    assert_eq!(world.get_population_count(), 3);
+
+    let loaded = persistence::load_world(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded.get_population_count(), 3);
+    assert_eq!(loaded.entities_tagged("protagonist"), vec![a]);
+    assert!(loaded.tags_of(b).system.contains("study-group-A"));
+    assert!(loaded.tags_of(b).user.is_empty());
+}
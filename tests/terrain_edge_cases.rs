@@ -1,4 +1,4 @@
-use primordium_core::terrain::TerrainGrid;
+use primordium_core::terrain::{TerrainCell, TerrainGrid};
 use primordium_data::TerrainType;
 
 #[test]
@@ -80,3 +80,77 @@ fn test_terrain_food_spawn_modifier_robustness() {
     assert!(terrain.food_spawn_modifier(50.0, -10.0) >= 0.0);
     assert!(terrain.food_spawn_modifier(f64::INFINITY, 50.0) >= 0.0);
 }
+
+#[test]
+fn test_single_cell_edit_copies_one_chunk() {
+    let mut terrain = TerrainGrid::generate(50, 50, 42);
+    let snapshot = terrain.clone();
+    let chunk_count = terrain.chunks().len();
+    assert_eq!(terrain.shared_chunks(&snapshot), chunk_count);
+
+    terrain.set_fertility(10, 20, 0.0);
+
+    assert_eq!(terrain.shared_chunks(&snapshot), chunk_count - 1);
+    assert_eq!(terrain.get_cell(10, 20).fertility, 0.0);
+    assert_ne!(snapshot.get_cell(10, 20).fertility, 0.0);
+}
+
+#[test]
+fn test_succession_keeps_unchanged_chunks_shared() {
+    // Bare oases settle after one tick: full moisture, fertility capped at 1.
+    let oasis = TerrainCell {
+        terrain_type: TerrainType::Oasis,
+        original_type: TerrainType::Oasis,
+        plant_biomass: 0.0,
+        ..TerrainCell::default()
+    };
+    let mut terrain = TerrainGrid::from_cells(40, 40, vec![oasis; 40 * 40]);
+    terrain.update(0.0, 1.0, 1, 42);
+    let chunk_count = terrain.chunks().len();
+
+    let snapshot = terrain.clone();
+    terrain.update(0.0, 1.0, 2, 42);
+    assert_eq!(terrain.shared_chunks(&snapshot), chunk_count);
+
+    terrain.set_fertility(10, 20, 0.5);
+    let snapshot = terrain.clone();
+    terrain.update(0.0, 1.0, 3, 42);
+    assert_eq!(terrain.shared_chunks(&snapshot), chunk_count - 1);
+    assert!(terrain.get_cell(10, 20).fertility > 0.5);
+}
+
+#[test]
+fn test_chunked_terrain_serde_roundtrip() {
+    let mut terrain = TerrainGrid::generate(37, 23, 7);
+    terrain.set_cell_type(36, 22, TerrainType::Forest);
+
+    let json = serde_json::to_string(&terrain).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["cells"].as_array().unwrap().len(), 37 * 23);
+
+    let restored: TerrainGrid = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.len(), terrain.len());
+    assert_eq!(restored.chunks().len(), terrain.chunks().len());
+    assert_eq!(restored.get_cell(36, 22).terrain_type, TerrainType::Forest);
+    assert!(restored
+        .cells()
+        .zip(terrain.cells())
+        .all(|(a, b)| a.elevation == b.elevation));
+}
+
+#[test]
+fn test_update_keeps_cell_order_across_chunks() {
+    let mut terrain = TerrainGrid::generate(30, 30, 3);
+    let before: Vec<_> = terrain.cells().map(|c| c.elevation).collect();
+
+    terrain.update(0.0, 1.0, 1, 3);
+
+    let after: Vec<_> = terrain.cells().map(|c| c.elevation).collect();
+    assert_eq!(before, after);
+    for (i, chunk) in terrain.chunks().iter().enumerate() {
+        assert_eq!(
+            chunk.origin_y as usize,
+            i * primordium_core::terrain::CHUNK_ROWS as usize
+        );
+    }
+}
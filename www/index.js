@@ -9,6 +9,7 @@ const ticksEl = document.getElementById('ticks');
 const entitiesEl = document.getElementById('entities');
 const btnPause = document.getElementById('btn-pause');
 const btnReset = document.getElementById('btn-reset');
+const btnPalette = document.getElementById('btn-palette');
 
 // State
 let simulation = null;
@@ -16,6 +17,8 @@ let animationId = null;
 let lastTime = performance.now();
 let frames = 0;
 let isPaused = false;
+let themeToml = null;
+let paletteEnabled = true;
 let width = window.innerWidth;
 let height = window.innerHeight;
 
@@ -23,6 +26,29 @@ let height = window.innerHeight;
 const TARGET_FPS = 60;
 const FRAME_TIME = 1000 / TARGET_FPS;
 
+// Glyph theme shared with the terminal UI; the built-in one if absent
+async function loadTheme() {
+    try {
+        const response = await fetch('theme.toml');
+        return response.ok ? await response.text() : null;
+    } catch (e) {
+        return null;
+    }
+}
+
+function createSimulation() {
+    const sim = Simulation.new();
+    if (themeToml) {
+        try {
+            sim.set_glyph_theme(themeToml);
+        } catch (e) {
+            console.warn("Ignoring theme.toml: " + e);
+        }
+    }
+    sim.set_palette_enabled(paletteEnabled);
+    return sim;
+}
+
 async function start() {
     try {
         console.log("Initializing WASM...");
@@ -33,7 +59,8 @@ async function start() {
         window.addEventListener('resize', resize);
 
         console.log("Creating Simulation...");
-        simulation = Simulation.new();
+        themeToml = await loadTheme();
+        simulation = createSimulation();
 
         loading.style.display = 'none';
         uiPanel.style.display = 'block';
@@ -106,7 +133,16 @@ btnPause.addEventListener('click', () => {
 });
 
 btnReset.addEventListener('click', () => {
-    simulation = Simulation.new();
+    simulation = createSimulation();
+});
+
+// Tint the world by climate, era and daylight
+btnPalette.addEventListener('click', () => {
+    paletteEnabled = !paletteEnabled;
+    btnPalette.classList.toggle('active', paletteEnabled);
+    if (simulation) {
+        simulation.set_palette_enabled(paletteEnabled);
+    }
 });
 
 const btnConnect = document.getElementById('btn-connect');